@@ -0,0 +1,99 @@
+use std::time::Duration;
+
+use bytes::BytesMut;
+use codecs::{
+    self, decoding::Deserializer, decoding::Framer, BytesDeserializer, NewlineDelimitedDecoder,
+};
+use criterion::{
+    criterion_group, measurement::WallTime, BatchSize, BenchmarkGroup, Criterion, SamplingMode,
+    Throughput,
+};
+use futures::{executor::block_on, StreamExt};
+use tokio_util::codec::{Decoder, FramedRead};
+
+const DATAGRAMS: usize = 1_000;
+
+fn decoder() -> vector::codecs::Decoder {
+    vector::codecs::Decoder::new(
+        Framer::NewlineDelimited(NewlineDelimitedDecoder::new()),
+        Deserializer::Bytes(BytesDeserializer::new()),
+    )
+}
+
+/// Small datagrams holding a few lines each, like syslog or statsd traffic.
+fn datagrams() -> Vec<BytesMut> {
+    (0..DATAGRAMS)
+        .map(|index| {
+            BytesMut::from(
+                format!(
+                    "<13>Oct 16 12:00:00 host app[{}]: request handled in 12ms\n<13>Oct 16 12:00:00 host app[{}]: connection closed\n",
+                    index, index
+                )
+                .as_str(),
+            )
+        })
+        .collect()
+}
+
+fn decoding(c: &mut Criterion) {
+    let mut group: BenchmarkGroup<WallTime> =
+        c.benchmark_group("vector::codecs::decoding::datagram");
+    group.sampling_mode(SamplingMode::Auto);
+    group.throughput(Throughput::Bytes(
+        datagrams()
+            .iter()
+            .map(|datagram| datagram.len() as u64)
+            .sum(),
+    ));
+
+    // How datagram sources used to decode their payloads, copying them into the read buffer of a
+    // `FramedRead`.
+    group.bench_function("framed_read", |b| {
+        b.iter_batched(
+            datagrams,
+            |datagrams| {
+                for datagram in datagrams {
+                    let mut stream = FramedRead::new(datagram.as_ref(), decoder());
+                    block_on(async {
+                        while let Some(result) = stream.next().await {
+                            result.unwrap();
+                        }
+                    });
+                }
+            },
+            BatchSize::SmallInput,
+        )
+    });
+
+    // Decoding the payloads in place, slicing the frames out of them.
+    group.bench_function("in_place", |b| {
+        b.iter_batched(
+            datagrams,
+            |datagrams| {
+                for mut datagram in datagrams {
+                    let mut decoder = decoder();
+                    while decoder.decode_eof(&mut datagram).unwrap().is_some() {}
+                }
+            },
+            BatchSize::SmallInput,
+        )
+    });
+}
+
+criterion_group!(
+    name = benches;
+    config = Criterion::default()
+        .warm_up_time(Duration::from_secs(5))
+        .measurement_time(Duration::from_secs(30))
+        // degree of noise to ignore in measurements, here 1%
+        .noise_threshold(0.01)
+        // likelihood of noise registering as difference, here 5%
+        .significance_level(0.05)
+        // likelihood of capturing the true runtime, here 95%
+        .confidence_level(0.95)
+        // total number of bootstrap resamples, higher is less noisy but slower
+        .nresamples(100_000)
+        // total samples to collect within the set measurement time
+        .sample_size(150);
+    targets = decoding
+);
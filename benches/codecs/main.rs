@@ -1,11 +1,13 @@
 use criterion::criterion_main;
 
 mod character_delimited_bytes;
+mod datagram;
 mod encoder;
 mod newline_bytes;
 
 criterion_main!(
     character_delimited_bytes::benches,
+    datagram::benches,
     newline_bytes::benches,
     encoder::benches,
 );
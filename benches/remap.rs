@@ -1,11 +1,11 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, num::NonZeroUsize};
 
 use chrono::{DateTime, Utc};
 use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
 use indexmap::IndexMap;
 use vector::{
     config::{DataType, Output},
-    event::{Event, LogEvent, Value},
+    event::{Event, EventArray, LogEvent, Value},
     transforms::{
         remap::{Remap, RemapConfig},
         SyncTransform, TransformOutputsBuf,
@@ -19,7 +19,7 @@ criterion_group!(
     // encapsulates CI noise we saw in
     // https://github.com/vectordotdev/vector/issues/5394
     config = Criterion::default().noise_threshold(0.02);
-    targets = benchmark_remap
+    targets = benchmark_remap, benchmark_remap_batches
 );
 criterion_main!(benches);
 
@@ -185,3 +185,54 @@ fn benchmark_remap(c: &mut Criterion) {
         );
     });
 }
+
+fn benchmark_remap_batches(c: &mut Criterion) {
+    let mut group = c.benchmark_group("remap_batches");
+
+    let events = (0..1024)
+        .map(|n| {
+            let mut log = LogEvent::from("augment me");
+            log.insert("copy_from", format!("buz {}", n));
+            log
+        })
+        .collect::<Vec<_>>();
+    let count = events.len();
+
+    for batch_size in [1, 16, 128, 1024] {
+        group.bench_function(format!("add_fields/batch_size_{}", batch_size), |b| {
+            let mut tform: Box<dyn SyncTransform> = Box::new(
+                Remap::new_ast(
+                    RemapConfig {
+                        source: Some(
+                            indoc! {r#".foo = "bar"
+                                .bar = "baz"
+                                .copy = string!(.copy_from)
+                            "#}
+                            .to_string(),
+                        ),
+                        drop_on_error: true,
+                        drop_on_abort: true,
+                        batch_size: NonZeroUsize::new(batch_size).unwrap(),
+                        ..Default::default()
+                    },
+                    &Default::default(),
+                )
+                .unwrap()
+                .0,
+            );
+
+            b.iter_batched(
+                || EventArray::Logs(events.clone()),
+                |events| {
+                    let mut outputs = TransformOutputsBuf::new_with_capacity(
+                        vec![Output::default(DataType::all())],
+                        count,
+                    );
+                    tform.transform_all(events, &mut outputs);
+                    outputs.take_primary()
+                },
+                BatchSize::SmallInput,
+            );
+        });
+    }
+}
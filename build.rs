@@ -101,6 +101,7 @@ fn main() {
     {
         println!("cargo:rerun-if-changed=proto/dd_trace.proto");
         println!("cargo:rerun-if-changed=proto/dnstap.proto");
+        println!("cargo:rerun-if-changed=proto/envoy/service/accesslog/v3/als.proto");
         println!("cargo:rerun-if-changed=proto/ddsketch_full.proto");
         println!("cargo:rerun-if-changed=proto/dd_metric.proto");
         println!("cargo:rerun-if-changed=proto/google/pubsub/v1/pubsub.proto");
@@ -122,6 +123,7 @@ fn main() {
                 &[
                     "lib/vector-core/proto/event.proto",
                     "proto/dnstap.proto",
+                    "proto/envoy/service/accesslog/v3/als.proto",
                     "proto/ddsketch_full.proto",
                     "proto/dd_metric.proto",
                     "proto/dd_trace.proto",
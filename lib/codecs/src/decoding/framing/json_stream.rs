@@ -0,0 +1,318 @@
+use bytes::{Buf, Bytes, BytesMut};
+use derivative::Derivative;
+use serde::{Deserialize, Serialize};
+use tokio_util::codec::Decoder;
+use tracing::{trace, warn};
+use vector_config::configurable_component;
+
+use super::BoxedFramingError;
+
+/// Config used to build a `JsonStreamDecoder`.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq, Eq)]
+pub struct JsonStreamDecoderConfig {
+    #[serde(
+        default,
+        skip_serializing_if = "vector_core::serde::skip_serializing_if_default"
+    )]
+    /// Options for the JSON stream decoder.
+    pub json_stream: JsonStreamDecoderOptions,
+}
+
+/// Options for building a `JsonStreamDecoder`.
+#[configurable_component]
+#[derive(Clone, Debug, Derivative, PartialEq, Eq)]
+#[derivative(Default)]
+pub struct JsonStreamDecoderOptions {
+    /// The maximum length of a single JSON value.
+    ///
+    /// Values longer than this are discarded entirely.
+    #[serde(skip_serializing_if = "vector_core::serde::skip_serializing_if_default")]
+    pub max_length: Option<usize>,
+}
+
+impl JsonStreamDecoderConfig {
+    /// Creates a new `JsonStreamDecoderConfig`.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Build the `JsonStreamDecoder` from this configuration.
+    pub const fn build(&self) -> JsonStreamDecoder {
+        if let Some(max_length) = self.json_stream.max_length {
+            JsonStreamDecoder::new_with_max_length(max_length)
+        } else {
+            JsonStreamDecoder::new()
+        }
+    }
+}
+
+/// How far the current value has been scanned.
+///
+/// Keeping this around between calls avoids scanning a large value from its start again each time
+/// more of it arrives.
+#[derive(Clone, Copy, Debug, Default)]
+struct ScanState {
+    /// The number of bytes of the buffer already scanned.
+    offset: usize,
+    /// The nesting depth of objects and arrays.
+    depth: usize,
+    in_string: bool,
+    escaped: bool,
+    /// Whether the value is a bare scalar, such as a number or `true`.
+    in_scalar: bool,
+    /// Whether the value exceeds the maximum length and is being skipped.
+    discarding: bool,
+}
+
+/// A decoder for a stream of concatenated JSON values, such as pretty-printed JSON documents
+/// written one after the other.
+///
+/// Values may span any number of lines and are separated by optional whitespace. Each frame holds
+/// exactly one value, which is not validated beyond balancing its brackets and quotes.
+#[derive(Clone, Debug)]
+pub struct JsonStreamDecoder {
+    max_length: usize,
+    state: ScanState,
+}
+
+impl JsonStreamDecoder {
+    /// Creates a new `JsonStreamDecoder`.
+    pub const fn new() -> Self {
+        Self::new_with_max_length(usize::MAX)
+    }
+
+    /// Creates a `JsonStreamDecoder` with a maximum frame length limit.
+    ///
+    /// Any values longer than `max_length` bytes will be discarded entirely.
+    pub const fn new_with_max_length(max_length: usize) -> Self {
+        Self {
+            max_length,
+            state: ScanState {
+                offset: 0,
+                depth: 0,
+                in_string: false,
+                escaped: false,
+                in_scalar: false,
+                discarding: false,
+            },
+        }
+    }
+
+    /// Scans the buffer for the end of the current value, returning its length.
+    fn scan(&mut self, buf: &[u8]) -> Option<usize> {
+        let state = &mut self.state;
+        while state.offset < buf.len() {
+            let byte = buf[state.offset];
+            state.offset += 1;
+
+            if state.in_string {
+                if state.escaped {
+                    state.escaped = false;
+                } else if byte == b'\\' {
+                    state.escaped = true;
+                } else if byte == b'"' {
+                    state.in_string = false;
+                    if state.depth == 0 {
+                        return Some(state.offset);
+                    }
+                }
+                continue;
+            }
+
+            match byte {
+                // A scalar ends right before whitespace or the start of the next value.
+                b' ' | b'\t' | b'\r' | b'\n' | b'{' | b'[' | b'"' if state.in_scalar => {
+                    return Some(state.offset - 1);
+                }
+                b'{' | b'[' => state.depth += 1,
+                b'}' | b']' => {
+                    state.depth = state.depth.saturating_sub(1);
+                    if state.depth == 0 {
+                        return Some(state.offset);
+                    }
+                }
+                b'"' => state.in_string = true,
+                _ if state.depth == 0 => state.in_scalar = true,
+                _ => {}
+            }
+        }
+        None
+    }
+
+    fn discard_warning(&self, buf_len: usize) {
+        warn!(
+            message = "Discarding frame larger than max_length.",
+            buf_len = buf_len,
+            max_length = self.max_length,
+            internal_log_rate_limit = true
+        );
+    }
+}
+
+impl Default for JsonStreamDecoder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Decoder for JsonStreamDecoder {
+    type Item = Bytes;
+    type Error = BoxedFramingError;
+
+    fn decode(&mut self, buf: &mut BytesMut) -> Result<Option<Bytes>, Self::Error> {
+        loop {
+            if self.state.offset == 0 && !self.state.discarding {
+                let whitespace = buf
+                    .iter()
+                    .take_while(|byte| byte.is_ascii_whitespace())
+                    .count();
+                buf.advance(whitespace);
+                if buf.is_empty() {
+                    return Ok(None);
+                }
+            }
+
+            match self.scan(buf) {
+                None => {
+                    if self.state.offset > self.max_length {
+                        // Drop what has been scanned so far, but keep track of where in the value
+                        // we are to find its end.
+                        if !self.state.discarding {
+                            self.discard_warning(buf.len());
+                        }
+                        buf.advance(self.state.offset);
+                        self.state.offset = 0;
+                        self.state.discarding = true;
+                    }
+                    return Ok(None);
+                }
+                Some(length) => {
+                    let discarding = self.state.discarding;
+                    self.state = ScanState::default();
+                    if discarding || length > self.max_length {
+                        if !discarding {
+                            self.discard_warning(buf.len());
+                        }
+                        buf.advance(length);
+                        continue;
+                    }
+
+                    let frame = buf.split_to(length).freeze();
+                    trace!(
+                        message = "Decoding the frame.",
+                        bytes_processed = frame.len()
+                    );
+                    return Ok(Some(frame));
+                }
+            }
+        }
+    }
+
+    fn decode_eof(&mut self, buf: &mut BytesMut) -> Result<Option<Bytes>, Self::Error> {
+        if let Some(frame) = self.decode(buf)? {
+            return Ok(Some(frame));
+        }
+
+        let discarding = self.state.discarding;
+        self.state = ScanState::default();
+        if buf.iter().all(u8::is_ascii_whitespace) {
+            buf.clear();
+            Ok(None)
+        } else if discarding || buf.len() > self.max_length {
+            buf.clear();
+            Ok(None)
+        } else {
+            // An incomplete value is passed on as is, for the deserializer to report it.
+            Ok(Some(buf.split().freeze()))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+
+    use super::*;
+
+    fn decode_all(decoder: &mut JsonStreamDecoder, input: &str) -> Vec<Bytes> {
+        let mut buf = BytesMut::from(input);
+        let mut frames = Vec::new();
+        while let Some(frame) = decoder.decode_eof(&mut buf).unwrap() {
+            frames.push(frame);
+        }
+        frames
+    }
+
+    #[test]
+    fn decode_pretty_printed_documents() {
+        let input = indoc! {r#"
+            {
+              "message": "first",
+              "nested": { "list": [1, 2, {"a": "}"}] }
+            }
+            {
+              "message": "second \"quoted\" {"
+            }
+        "#};
+        let frames = decode_all(&mut JsonStreamDecoder::new(), input);
+
+        assert_eq!(frames.len(), 2);
+        assert!(frames[0].starts_with(b"{\n  \"message\": \"first\""));
+        assert!(frames[0].ends_with(b"}\n}"));
+        assert_eq!(
+            frames[1],
+            "{\n  \"message\": \"second \\\"quoted\\\" {\"\n}"
+        );
+    }
+
+    #[test]
+    fn decode_concatenated_values() {
+        let frames = decode_all(
+            &mut JsonStreamDecoder::new(),
+            r#"{"a":1}{"b":2}[3] "four" 5 true"#,
+        );
+
+        assert_eq!(
+            frames,
+            vec![
+                Bytes::from(r#"{"a":1}"#),
+                Bytes::from(r#"{"b":2}"#),
+                Bytes::from("[3]"),
+                Bytes::from(r#""four""#),
+                Bytes::from("5"),
+                Bytes::from("true"),
+            ]
+        );
+    }
+
+    #[test]
+    fn decode_value_split_across_reads() {
+        let mut decoder = JsonStreamDecoder::new();
+        let mut buf = BytesMut::from("{\"message\": \"hel");
+
+        assert_eq!(decoder.decode(&mut buf).unwrap(), None);
+        buf.extend_from_slice(b"lo\"}\n{");
+        assert_eq!(
+            decoder.decode(&mut buf).unwrap(),
+            Some(Bytes::from(r#"{"message": "hello"}"#))
+        );
+        assert_eq!(decoder.decode(&mut buf).unwrap(), None);
+        assert_eq!(
+            decoder.decode_eof(&mut buf).unwrap(),
+            Some(Bytes::from("{"))
+        );
+    }
+
+    #[test]
+    fn decode_max_length() {
+        let mut decoder = JsonStreamDecoder::new_with_max_length(10);
+        let mut buf = BytesMut::from(r#"{"a":"0123456789"#);
+
+        assert_eq!(decoder.decode(&mut buf).unwrap(), None);
+        buf.extend_from_slice(br#"0123456789"} {"b":2}"#);
+        assert_eq!(
+            decoder.decode(&mut buf).unwrap(),
+            Some(Bytes::from(r#"{"b":2}"#))
+        );
+    }
+}
@@ -5,6 +5,7 @@
 
 mod bytes;
 mod character_delimited;
+mod json_stream;
 mod length_delimited;
 mod newline_delimited;
 mod octet_counting;
@@ -16,12 +17,14 @@ pub use character_delimited::{
     CharacterDelimitedDecoder, CharacterDelimitedDecoderConfig, CharacterDelimitedDecoderOptions,
 };
 use dyn_clone::DynClone;
+pub use json_stream::{JsonStreamDecoder, JsonStreamDecoderConfig, JsonStreamDecoderOptions};
 pub use length_delimited::{LengthDelimitedDecoder, LengthDelimitedDecoderConfig};
 pub use newline_delimited::{
     NewlineDelimitedDecoder, NewlineDelimitedDecoderConfig, NewlineDelimitedDecoderOptions,
 };
 pub use octet_counting::{
     OctetCountingDecoder, OctetCountingDecoderConfig, OctetCountingDecoderOptions,
+    OctetCountingFraming,
 };
 use tokio_util::codec::LinesCodecError;
 
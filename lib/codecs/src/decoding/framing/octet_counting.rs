@@ -41,12 +41,44 @@ pub struct OctetCountingDecoderOptions {
     max_length: Option<usize>,
 }
 
+/// Framing of the streams decoded by an `OctetCountingDecoder`.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum OctetCountingFraming {
+    /// The framing is detected from the first frame of each stream.
+    ///
+    /// Streams whose first frame starts with a non-zero digit are octet-counted, and other streams
+    /// are newline-delimited.
+    Auto,
+
+    /// Frames are octet-counted, as specified in
+    /// [RFC 6587](https://tools.ietf.org/html/rfc6587#section-3.4.1).
+    ///
+    /// Newlines trailing a frame are skipped.
+    OctetCounting,
+
+    /// Frames are delimited by newlines, also known as non-transparent framing in
+    /// [RFC 6587](https://tools.ietf.org/html/rfc6587#section-3.4.2).
+    NewlineDelimited,
+}
+
+impl Default for OctetCountingFraming {
+    fn default() -> Self {
+        Self::Auto
+    }
+}
+
 /// Codec using the `Octet Counting` format as specified in
 /// https://tools.ietf.org/html/rfc6587#section-3.4.1.
+///
+/// Streams which aren't octet-counted are decoded as newline-delimited, depending on the
+/// [`OctetCountingFraming`] of the decoder.
 #[derive(Clone, Debug)]
 pub struct OctetCountingDecoder {
     other: LinesCodec,
     octet_decoding: Option<State>,
+    framing: OctetCountingFraming,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -62,6 +94,7 @@ impl OctetCountingDecoder {
         Self {
             other: LinesCodec::new(),
             octet_decoding: None,
+            framing: OctetCountingFraming::Auto,
         }
     }
 
@@ -70,9 +103,19 @@ impl OctetCountingDecoder {
         Self {
             other: LinesCodec::new_with_max_length(max_length),
             octet_decoding: None,
+            framing: OctetCountingFraming::Auto,
         }
     }
 
+    /// Sets the framing of the decoded streams, detected from their first frame by default.
+    ///
+    /// As the detected framing is kept for the rest of the stream, a decoder should only be used
+    /// for a single stream.
+    pub const fn with_framing(mut self, framing: OctetCountingFraming) -> Self {
+        self.framing = framing;
+        self
+    }
+
     /// Decode a frame.
     fn octet_decode(
         &mut self,
@@ -173,26 +216,24 @@ impl OctetCountingDecoder {
 
                     Ok(None)
                 } else if let Some(msg) = src.get(from..to) {
-                    let bytes = match std::str::from_utf8(msg) {
-                        Ok(_) => Bytes::copy_from_slice(msg),
-                        Err(_) => {
-                            // The data was not valid UTF8 :-(.
-                            //
-                            // Advance the buffer past the erroneous bytes to
-                            // prevent us getting stuck in an infinite loop.
-                            src.advance(to);
-                            self.octet_decoding = None;
-                            return Err(LinesCodecError::Io(io::Error::new(
-                                io::ErrorKind::InvalidData,
-                                "Unable to decode message as UTF8",
-                            )));
-                        }
-                    };
+                    if std::str::from_utf8(msg).is_err() {
+                        // The data was not valid UTF8 :-(.
+                        //
+                        // Advance the buffer past the erroneous bytes to
+                        // prevent us getting stuck in an infinite loop.
+                        src.advance(to);
+                        self.octet_decoding = None;
+                        return Err(LinesCodecError::Io(io::Error::new(
+                            io::ErrorKind::InvalidData,
+                            "Unable to decode message as UTF8",
+                        )));
+                    }
 
                     // We have managed to read the entire message as valid UTF8!
-                    src.advance(to);
+                    // Slice it out of the buffer rather than copying it.
+                    src.advance(from);
                     self.octet_decoding = None;
-                    Ok(Some(bytes))
+                    Ok(Some(src.split_to(len).freeze()))
                 } else {
                     // We have an acceptable number of bytes in this message,
                     // but not all the data was in the frame.
@@ -238,17 +279,40 @@ impl OctetCountingDecoder {
         &mut self,
         src: &mut BytesMut,
     ) -> Option<Result<Option<Bytes>, LinesCodecError>> {
-        if let Some(&first_byte) = src.first() {
-            if (49..=57).contains(&first_byte) {
-                // First character is non zero number so we can assume that
-                // octet count framing is used.
-                trace!("Octet counting encoded event detected.");
-                self.octet_decoding = Some(State::NotDiscarding);
+        match self.framing {
+            OctetCountingFraming::Auto => {
+                let &first_byte = src.first()?;
+                self.framing = if (49..=57).contains(&first_byte) {
+                    // First character is non zero number so we can assume that
+                    // octet count framing is used for the whole stream.
+                    trace!("Octet counting encoded stream detected.");
+                    OctetCountingFraming::OctetCounting
+                } else {
+                    trace!("Newline delimited stream detected.");
+                    OctetCountingFraming::NewlineDelimited
+                };
+                self.checked_decode(src)
             }
-        }
+            OctetCountingFraming::OctetCounting => {
+                if self.octet_decoding.is_none() {
+                    // Some senders trail frames with a newline, which isn't part of the next
+                    // frame.
+                    let skipped = src
+                        .iter()
+                        .take_while(|&&b| b == b'\n' || b == b'\r')
+                        .count();
+                    src.advance(skipped);
+                    if src.is_empty() {
+                        return Some(Ok(None));
+                    }
+                    self.octet_decoding = Some(State::NotDiscarding);
+                }
 
-        self.octet_decoding
-            .map(|state| self.octet_decode(state, src))
+                self.octet_decoding
+                    .map(|state| self.octet_decode(state, src))
+            }
+            OctetCountingFraming::NewlineDelimited => None,
+        }
     }
 }
 
@@ -415,4 +479,51 @@ mod tests {
         assert!(result.is_err());
         assert_eq!(b"32 something valid"[..], buffer);
     }
+
+    #[test]
+    fn auto_framing_is_detected_once_per_stream() {
+        let mut decoder = OctetCountingDecoder::new_with_max_length(128);
+        let mut buffer = BytesMut::with_capacity(32);
+
+        // A newline-delimited message starting with a digit isn't mistaken for an octet count
+        // once the stream is known to be newline-delimited.
+        buffer.put(&b"<13>first\n2 messages\n"[..]);
+        assert_eq!(
+            Some("<13>first".into()),
+            decoder.decode(&mut buffer).unwrap()
+        );
+        assert_eq!(
+            Some("2 messages".into()),
+            decoder.decode(&mut buffer).unwrap()
+        );
+
+        let mut decoder = OctetCountingDecoder::new_with_max_length(128);
+        let mut buffer = BytesMut::with_capacity(32);
+
+        // Newlines trailing octet-counted frames are skipped.
+        buffer.put(&b"5 first\n6 second\n"[..]);
+        assert_eq!(Some("first".into()), decoder.decode(&mut buffer).unwrap());
+        assert_eq!(Some("second".into()), decoder.decode(&mut buffer).unwrap());
+        assert_eq!(None, decoder.decode_eof(&mut buffer).unwrap());
+    }
+
+    #[test]
+    fn forced_framing() {
+        let mut decoder = OctetCountingDecoder::new_with_max_length(128)
+            .with_framing(OctetCountingFraming::NewlineDelimited);
+        let mut buffer = BytesMut::with_capacity(32);
+
+        buffer.put(&b"5 first\n"[..]);
+        assert_eq!(Some("5 first".into()), decoder.decode(&mut buffer).unwrap());
+
+        let mut decoder = OctetCountingDecoder::new_with_max_length(128)
+            .with_framing(OctetCountingFraming::OctetCounting);
+        let mut buffer = BytesMut::with_capacity(32);
+
+        buffer.put(&b"\n11 <13>message"[..]);
+        assert_eq!(
+            Some("<13>message".into()),
+            decoder.decode(&mut buffer).unwrap()
+        );
+    }
 }
@@ -17,15 +17,16 @@ pub use format::{SyslogDeserializer, SyslogDeserializerConfig};
 pub use framing::{
     BoxedFramer, BoxedFramingError, BytesDecoder, BytesDecoderConfig, CharacterDelimitedDecoder,
     CharacterDelimitedDecoderConfig, CharacterDelimitedDecoderOptions, FramingError,
-    LengthDelimitedDecoder, LengthDelimitedDecoderConfig, NewlineDelimitedDecoder,
-    NewlineDelimitedDecoderConfig, NewlineDelimitedDecoderOptions, OctetCountingDecoder,
-    OctetCountingDecoderConfig, OctetCountingDecoderOptions,
+    JsonStreamDecoder, JsonStreamDecoderConfig, JsonStreamDecoderOptions, LengthDelimitedDecoder,
+    LengthDelimitedDecoderConfig, NewlineDelimitedDecoder, NewlineDelimitedDecoderConfig,
+    NewlineDelimitedDecoderOptions, OctetCountingDecoder, OctetCountingDecoderConfig,
+    OctetCountingDecoderOptions, OctetCountingFraming,
 };
 use smallvec::SmallVec;
 use std::fmt::Debug;
 use vector_config::configurable_component;
 use vector_core::{
-    config::{DataType, LogNamespace},
+    config::{DataType, LogNamespace, Output},
     event::Event,
     schema,
 };
@@ -72,7 +73,7 @@ impl StreamDecodingError for Error {
 // since `serde` doesn't allow `flatten`ing these:
 // https://github.com/serde-rs/serde/issues/1402.
 #[configurable_component]
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 #[serde(tag = "method", rename_all = "snake_case")]
 pub enum FramingConfig {
     /// Configures the `BytesDecoder`.
@@ -82,6 +83,15 @@ pub enum FramingConfig {
         /// Options for the character delimited decoder.
         character_delimited: CharacterDelimitedDecoderOptions,
     },
+    /// Configures the `JsonStreamDecoder`.
+    JsonStream {
+        #[serde(
+            default,
+            skip_serializing_if = "vector_core::serde::skip_serializing_if_default"
+        )]
+        /// Options for the JSON stream decoder.
+        json_stream: JsonStreamDecoderOptions,
+    },
     /// Configures the `LengthDelimitedDecoder`.
     LengthDelimited,
     /// Configures the `NewlineDelimitedDecoder`.
@@ -118,6 +128,14 @@ impl From<CharacterDelimitedDecoderConfig> for FramingConfig {
     }
 }
 
+impl From<JsonStreamDecoderConfig> for FramingConfig {
+    fn from(config: JsonStreamDecoderConfig) -> Self {
+        Self::JsonStream {
+            json_stream: config.json_stream,
+        }
+    }
+}
+
 impl From<LengthDelimitedDecoderConfig> for FramingConfig {
     fn from(_: LengthDelimitedDecoderConfig) -> Self {
         Self::LengthDelimited
@@ -153,6 +171,12 @@ impl FramingConfig {
                 }
                 .build(),
             ),
+            FramingConfig::JsonStream { json_stream } => Framer::JsonStream(
+                JsonStreamDecoderConfig {
+                    json_stream: json_stream.clone(),
+                }
+                .build(),
+            ),
             FramingConfig::LengthDelimited => {
                 Framer::LengthDelimited(LengthDelimitedDecoderConfig.build())
             }
@@ -179,6 +203,8 @@ pub enum Framer {
     Bytes(BytesDecoder),
     /// Uses a `CharacterDelimitedDecoder` for framing.
     CharacterDelimited(CharacterDelimitedDecoder),
+    /// Uses a `JsonStreamDecoder` for framing.
+    JsonStream(JsonStreamDecoder),
     /// Uses a `LengthDelimitedDecoder` for framing.
     LengthDelimited(LengthDelimitedDecoder),
     /// Uses a `NewlineDelimitedDecoder` for framing.
@@ -197,6 +223,7 @@ impl tokio_util::codec::Decoder for Framer {
         match self {
             Framer::Bytes(framer) => framer.decode(src),
             Framer::CharacterDelimited(framer) => framer.decode(src),
+            Framer::JsonStream(framer) => framer.decode(src),
             Framer::LengthDelimited(framer) => framer.decode(src),
             Framer::NewlineDelimited(framer) => framer.decode(src),
             Framer::OctetCounting(framer) => framer.decode(src),
@@ -208,6 +235,7 @@ impl tokio_util::codec::Decoder for Framer {
         match self {
             Framer::Bytes(framer) => framer.decode_eof(src),
             Framer::CharacterDelimited(framer) => framer.decode_eof(src),
+            Framer::JsonStream(framer) => framer.decode_eof(src),
             Framer::LengthDelimited(framer) => framer.decode_eof(src),
             Framer::NewlineDelimited(framer) => framer.decode_eof(src),
             Framer::OctetCounting(framer) => framer.decode_eof(src),
@@ -216,14 +244,126 @@ impl tokio_util::codec::Decoder for Framer {
     }
 }
 
+/// The name of the output of the sources receiving the payloads that can't be decoded, when
+/// `on_error` is set to `route`.
+pub const DECODING_ERRORS_OUTPUT: &str = "errors";
+
 /// Configuration for building a `Deserializer`.
+#[configurable_component]
+#[derive(Clone, Debug)]
+pub struct DeserializerConfig {
+    #[serde(flatten)]
+    codec: DeserializerCodec,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        skip_serializing_if = "vector_core::serde::skip_serializing_if_default"
+    )]
+    on_error: DecodingErrorPolicy,
+}
+
+impl<T> From<T> for DeserializerConfig
+where
+    T: Into<DeserializerCodec>,
+{
+    fn from(codec: T) -> Self {
+        Self {
+            codec: codec.into(),
+            on_error: Default::default(),
+        }
+    }
+}
+
+impl DeserializerConfig {
+    /// Sets how payloads that can't be decoded are handled.
+    pub const fn with_on_error(mut self, on_error: DecodingErrorPolicy) -> Self {
+        self.on_error = on_error;
+        self
+    }
+
+    /// Gets the codec used to parse structured events.
+    pub const fn codec(&self) -> &DeserializerCodec {
+        &self.codec
+    }
+
+    /// Gets how payloads that can't be decoded are handled.
+    pub const fn on_error(&self) -> DecodingErrorPolicy {
+        self.on_error
+    }
+
+    /// Build the `Deserializer` from this configuration.
+    pub fn build(&self) -> Deserializer {
+        self.codec.build()
+    }
+
+    /// Return an appropriate default framer for the given deserializer
+    pub fn default_stream_framing(&self) -> FramingConfig {
+        self.codec.default_stream_framing()
+    }
+
+    /// Return the type of event build by this deserializer.
+    pub fn output_type(&self) -> DataType {
+        self.codec.output_type()
+    }
+
+    /// The schema produced by the deserializer.
+    pub fn schema_definition(&self, log_namespace: LogNamespace) -> schema::Definition {
+        self.codec.schema_definition(log_namespace)
+    }
+
+    /// Get the HTTP content type.
+    pub const fn content_type(&self, framer: &FramingConfig) -> &'static str {
+        self.codec.content_type(framer)
+    }
+
+    /// Adds the output receiving the payloads that can't be decoded to the outputs of a source,
+    /// if they are routed to it.
+    pub fn with_errors_output(&self, mut outputs: Vec<Output>) -> Vec<Output> {
+        if self.on_error == DecodingErrorPolicy::Route {
+            outputs.push(Output::default(DataType::Log).with_port(DECODING_ERRORS_OUTPUT));
+        }
+        outputs
+    }
+}
+
+/// How payloads that can't be decoded are handled.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum DecodingErrorPolicy {
+    /// Drop the payloads that can't be decoded.
+    ///
+    /// The errors are logged and counted in the `component_errors_total` internal metric.
+    Drop,
+
+    /// Pass the payloads that can't be decoded on as they are.
+    ///
+    /// The raw payload is kept as the message of the event, as with the `bytes` codec, and the
+    /// decoding error is set to the `decoding_error` metadata field.
+    Passthrough,
+
+    /// Route the payloads that can't be decoded to the `errors` output of the source.
+    ///
+    /// The events are shaped as with `passthrough`, so that they can be repaired by another
+    /// pipeline.
+    Route,
+}
+
+impl Default for DecodingErrorPolicy {
+    fn default() -> Self {
+        Self::Drop
+    }
+}
+
+/// The codec used to parse structured events from byte frames.
 // Unfortunately, copying options of the nested enum variants is necessary
 // since `serde` doesn't allow `flatten`ing these:
 // https://github.com/serde-rs/serde/issues/1402.
 #[configurable_component]
 #[derive(Clone, Debug)]
 #[serde(tag = "codec", rename_all = "snake_case")]
-pub enum DeserializerConfig {
+pub enum DeserializerCodec {
     /// Configures the `BytesDeserializer`.
     Bytes,
     /// Configures the `JsonDeserializer`.
@@ -239,59 +379,71 @@ pub enum DeserializerConfig {
     Gelf,
 }
 
-impl From<BytesDeserializerConfig> for DeserializerConfig {
+impl From<BytesDeserializerConfig> for DeserializerCodec {
     fn from(_: BytesDeserializerConfig) -> Self {
         Self::Bytes
     }
 }
 
-impl From<JsonDeserializerConfig> for DeserializerConfig {
+impl From<JsonDeserializerConfig> for DeserializerCodec {
     fn from(_: JsonDeserializerConfig) -> Self {
         Self::Json
     }
 }
 
 #[cfg(feature = "syslog")]
-impl From<SyslogDeserializerConfig> for DeserializerConfig {
+impl From<SyslogDeserializerConfig> for DeserializerCodec {
     fn from(_: SyslogDeserializerConfig) -> Self {
         Self::Syslog
     }
 }
 
-impl From<GelfDeserializerConfig> for DeserializerConfig {
+impl From<GelfDeserializerConfig> for DeserializerCodec {
     fn from(_: GelfDeserializerConfig) -> Self {
         Self::Gelf
     }
 }
 
-impl DeserializerConfig {
+impl From<NativeDeserializerConfig> for DeserializerCodec {
+    fn from(_: NativeDeserializerConfig) -> Self {
+        Self::Native
+    }
+}
+
+impl From<NativeJsonDeserializerConfig> for DeserializerCodec {
+    fn from(_: NativeJsonDeserializerConfig) -> Self {
+        Self::NativeJson
+    }
+}
+
+impl DeserializerCodec {
     /// Build the `Deserializer` from this configuration.
     pub fn build(&self) -> Deserializer {
         match self {
-            DeserializerConfig::Bytes => Deserializer::Bytes(BytesDeserializerConfig.build()),
-            DeserializerConfig::Json => Deserializer::Json(JsonDeserializerConfig.build()),
+            DeserializerCodec::Bytes => Deserializer::Bytes(BytesDeserializerConfig.build()),
+            DeserializerCodec::Json => Deserializer::Json(JsonDeserializerConfig.build()),
             #[cfg(feature = "syslog")]
-            DeserializerConfig::Syslog => Deserializer::Syslog(SyslogDeserializerConfig.build()),
-            DeserializerConfig::Native => Deserializer::Native(NativeDeserializerConfig.build()),
-            DeserializerConfig::NativeJson => {
+            DeserializerCodec::Syslog => Deserializer::Syslog(SyslogDeserializerConfig.build()),
+            DeserializerCodec::Native => Deserializer::Native(NativeDeserializerConfig.build()),
+            DeserializerCodec::NativeJson => {
                 Deserializer::NativeJson(NativeJsonDeserializerConfig.build())
             }
-            DeserializerConfig::Gelf => Deserializer::Gelf(GelfDeserializerConfig.build()),
+            DeserializerCodec::Gelf => Deserializer::Gelf(GelfDeserializerConfig.build()),
         }
     }
 
     /// Return an appropriate default framer for the given deserializer
     pub fn default_stream_framing(&self) -> FramingConfig {
         match self {
-            DeserializerConfig::Native => FramingConfig::LengthDelimited,
-            DeserializerConfig::Bytes
-            | DeserializerConfig::Json
-            | DeserializerConfig::Gelf
-            | DeserializerConfig::NativeJson => FramingConfig::NewlineDelimited {
+            DeserializerCodec::Native => FramingConfig::LengthDelimited,
+            DeserializerCodec::Bytes
+            | DeserializerCodec::Json
+            | DeserializerCodec::Gelf
+            | DeserializerCodec::NativeJson => FramingConfig::NewlineDelimited {
                 newline_delimited: Default::default(),
             },
             #[cfg(feature = "syslog")]
-            DeserializerConfig::Syslog => FramingConfig::NewlineDelimited {
+            DeserializerCodec::Syslog => FramingConfig::NewlineDelimited {
                 newline_delimited: Default::default(),
             },
         }
@@ -300,28 +452,28 @@ impl DeserializerConfig {
     /// Return the type of event build by this deserializer.
     pub fn output_type(&self) -> DataType {
         match self {
-            DeserializerConfig::Bytes => BytesDeserializerConfig.output_type(),
-            DeserializerConfig::Json => JsonDeserializerConfig.output_type(),
+            DeserializerCodec::Bytes => BytesDeserializerConfig.output_type(),
+            DeserializerCodec::Json => JsonDeserializerConfig.output_type(),
             #[cfg(feature = "syslog")]
-            DeserializerConfig::Syslog => SyslogDeserializerConfig.output_type(),
-            DeserializerConfig::Native => NativeDeserializerConfig.output_type(),
-            DeserializerConfig::NativeJson => NativeJsonDeserializerConfig.output_type(),
-            DeserializerConfig::Gelf => GelfDeserializerConfig.output_type(),
+            DeserializerCodec::Syslog => SyslogDeserializerConfig.output_type(),
+            DeserializerCodec::Native => NativeDeserializerConfig.output_type(),
+            DeserializerCodec::NativeJson => NativeJsonDeserializerConfig.output_type(),
+            DeserializerCodec::Gelf => GelfDeserializerConfig.output_type(),
         }
     }
 
     /// The schema produced by the deserializer.
     pub fn schema_definition(&self, log_namespace: LogNamespace) -> schema::Definition {
         match self {
-            DeserializerConfig::Bytes => BytesDeserializerConfig.schema_definition(log_namespace),
-            DeserializerConfig::Json => JsonDeserializerConfig.schema_definition(log_namespace),
+            DeserializerCodec::Bytes => BytesDeserializerConfig.schema_definition(log_namespace),
+            DeserializerCodec::Json => JsonDeserializerConfig.schema_definition(log_namespace),
             #[cfg(feature = "syslog")]
-            DeserializerConfig::Syslog => SyslogDeserializerConfig.schema_definition(log_namespace),
-            DeserializerConfig::Native => NativeDeserializerConfig.schema_definition(log_namespace),
-            DeserializerConfig::NativeJson => {
+            DeserializerCodec::Syslog => SyslogDeserializerConfig.schema_definition(log_namespace),
+            DeserializerCodec::Native => NativeDeserializerConfig.schema_definition(log_namespace),
+            DeserializerCodec::NativeJson => {
                 NativeJsonDeserializerConfig.schema_definition(log_namespace)
             }
-            DeserializerConfig::Gelf => GelfDeserializerConfig.schema_definition(log_namespace),
+            DeserializerCodec::Gelf => GelfDeserializerConfig.schema_definition(log_namespace),
         }
     }
 
@@ -329,13 +481,11 @@ impl DeserializerConfig {
     pub const fn content_type(&self, framer: &FramingConfig) -> &'static str {
         match (&self, framer) {
             (
-                DeserializerConfig::Json | DeserializerConfig::NativeJson,
+                DeserializerCodec::Json | DeserializerCodec::NativeJson,
                 FramingConfig::NewlineDelimited { .. },
             ) => "application/x-ndjson",
             (
-                DeserializerConfig::Gelf
-                | DeserializerConfig::Json
-                | DeserializerConfig::NativeJson,
+                DeserializerCodec::Gelf | DeserializerCodec::Json | DeserializerCodec::NativeJson,
                 FramingConfig::CharacterDelimited {
                     character_delimited:
                         CharacterDelimitedDecoderOptions {
@@ -344,16 +494,16 @@ impl DeserializerConfig {
                         },
                 },
             ) => "application/json",
-            (DeserializerConfig::Native, _) => "application/octet-stream",
+            (DeserializerCodec::Native, _) => "application/octet-stream",
             (
-                DeserializerConfig::Json
-                | DeserializerConfig::NativeJson
-                | DeserializerConfig::Bytes
-                | DeserializerConfig::Gelf,
+                DeserializerCodec::Json
+                | DeserializerCodec::NativeJson
+                | DeserializerCodec::Bytes
+                | DeserializerCodec::Gelf,
                 _,
             ) => "text/plain",
             #[cfg(feature = "syslog")]
-            (DeserializerConfig::Syslog, _) => "text/plain",
+            (DeserializerCodec::Syslog, _) => "text/plain",
         }
     }
 }
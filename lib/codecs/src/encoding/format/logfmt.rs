@@ -1,22 +1,34 @@
+use std::collections::BTreeMap;
+
 use bytes::BytesMut;
+use derivative::Derivative;
 use serde::{Deserialize, Serialize};
 use tokio_util::codec::Encoder;
-use vector_common::encode_logfmt;
-use vector_core::{config::DataType, event::Event, schema};
+use vector_common::encode_key_value;
+use vector_config::configurable_component;
+use vector_core::{
+    config::{log_schema, DataType},
+    event::{Event, Value},
+    schema,
+};
 
 /// Config used to build a `LogfmtSerializer`.
 #[derive(Debug, Clone, Default, Deserialize, Serialize)]
-pub struct LogfmtSerializerConfig;
+pub struct LogfmtSerializerConfig {
+    /// Options for the logfmt serializer.
+    #[serde(default)]
+    pub logfmt: LogfmtSerializerOptions,
+}
 
 impl LogfmtSerializerConfig {
     /// Creates a new `LogfmtSerializerConfig`.
-    pub const fn new() -> Self {
-        Self
+    pub const fn new(logfmt: LogfmtSerializerOptions) -> Self {
+        Self { logfmt }
     }
 
     /// Build the `LogfmtSerializer` from this configuration.
-    pub const fn build(&self) -> LogfmtSerializer {
-        LogfmtSerializer
+    pub fn build(&self) -> LogfmtSerializer {
+        LogfmtSerializer::new(self.logfmt.clone())
     }
 
     /// The data type of events that are accepted by `LogfmtSerializer`.
@@ -32,14 +44,45 @@ impl LogfmtSerializerConfig {
     }
 }
 
+/// Logfmt serializer options.
+#[configurable_component]
+#[derive(Clone, Debug, Default)]
+pub struct LogfmtSerializerOptions {
+    /// The keys that are encoded first, in the given order.
+    ///
+    /// All other keys follow in lexicographic order.
+    #[serde(default)]
+    pub fields_order: Vec<String>,
+
+    /// How nested fields are encoded.
+    #[serde(default)]
+    pub flatten: FlattenStrategy,
+}
+
+/// How nested fields are encoded in logfmt.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Derivative, Eq, PartialEq)]
+#[derivative(Default)]
+#[serde(rename_all = "snake_case")]
+pub enum FlattenStrategy {
+    /// Every nested value is encoded as its own key, named by its path, such as `parent.child=value`.
+    #[derivative(Default)]
+    Dotted,
+
+    /// Objects and arrays are encoded as the JSON value of their top-level key.
+    Json,
+}
+
 /// Serializer that converts an `Event` to bytes using the logfmt format.
-#[derive(Debug, Clone)]
-pub struct LogfmtSerializer;
+#[derive(Debug, Clone, Default)]
+pub struct LogfmtSerializer {
+    options: LogfmtSerializerOptions,
+}
 
 impl LogfmtSerializer {
     /// Creates a new `LogfmtSerializer`.
-    pub const fn new() -> Self {
-        Self
+    pub const fn new(options: LogfmtSerializerOptions) -> Self {
+        Self { options }
     }
 }
 
@@ -48,7 +91,19 @@ impl Encoder<Event> for LogfmtSerializer {
 
     fn encode(&mut self, event: Event, buffer: &mut BytesMut) -> Result<(), Self::Error> {
         let log = event.as_log();
-        let string = encode_logfmt::encode_value(log.value())?;
+        let mut map = match log.value() {
+            Value::Object(map) => map.clone(),
+            value => BTreeMap::from([(log_schema().message_key().to_owned(), value.clone())]),
+        };
+        if self.options.flatten == FlattenStrategy::Json {
+            for value in map.values_mut() {
+                if matches!(value, Value::Object(_) | Value::Array(_)) {
+                    *value = Value::from(serde_json::to_string(value)?);
+                }
+            }
+        }
+
+        let string = encode_key_value::to_string(&map, &self.options.fields_order, "=", " ", true)?;
         buffer.extend_from_slice(string.as_bytes());
 
         Ok(())
@@ -67,11 +122,64 @@ mod tests {
         let event = Event::Log(LogEvent::from(btreemap! {
             "foo" => Value::from("bar")
         }));
-        let mut serializer = LogfmtSerializer::new();
+        let mut serializer = LogfmtSerializer::default();
         let mut bytes = BytesMut::new();
 
         serializer.encode(event, &mut bytes).unwrap();
 
         assert_eq!(bytes.freeze(), "foo=bar");
     }
+
+    fn encode(options: LogfmtSerializerOptions, event: Event) -> String {
+        let mut bytes = BytesMut::new();
+        LogfmtSerializer::new(options)
+            .encode(event, &mut bytes)
+            .unwrap();
+        String::from_utf8(bytes.to_vec()).unwrap()
+    }
+
+    fn nested_event() -> Event {
+        Event::Log(LogEvent::from(btreemap! {
+            "msg" => Value::from("hello world"),
+            "level" => Value::from("info"),
+            "http" => Value::from(btreemap! {
+                "status" => Value::from(200),
+                "method" => Value::from("GET"),
+            }),
+        }))
+    }
+
+    #[test]
+    fn serialize_logfmt_dotted() {
+        assert_eq!(
+            encode(LogfmtSerializerOptions::default(), nested_event()),
+            r#"http.method=GET http.status=200 level=info msg="hello world""#
+        );
+    }
+
+    #[test]
+    fn serialize_logfmt_json() {
+        let options = LogfmtSerializerOptions {
+            flatten: FlattenStrategy::Json,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            encode(options, nested_event()),
+            r#"http="{\"method\":\"GET\",\"status\":200}" level=info msg="hello world""#
+        );
+    }
+
+    #[test]
+    fn serialize_logfmt_fields_order() {
+        let options = LogfmtSerializerOptions {
+            fields_order: vec!["level".into(), "msg".into()],
+            ..Default::default()
+        };
+
+        assert_eq!(
+            encode(options, nested_event()),
+            r#"level=info msg="hello world" http.method=GET http.status=200"#
+        );
+    }
 }
@@ -9,6 +9,7 @@ mod json;
 mod logfmt;
 mod native;
 mod native_json;
+mod pretty;
 mod raw_message;
 mod text;
 
@@ -18,9 +19,12 @@ pub use avro::{AvroSerializer, AvroSerializerConfig, AvroSerializerOptions};
 use dyn_clone::DynClone;
 pub use gelf::{GelfSerializer, GelfSerializerConfig};
 pub use json::{JsonSerializer, JsonSerializerConfig};
-pub use logfmt::{LogfmtSerializer, LogfmtSerializerConfig};
+pub use logfmt::{
+    FlattenStrategy, LogfmtSerializer, LogfmtSerializerConfig, LogfmtSerializerOptions,
+};
 pub use native::{NativeSerializer, NativeSerializerConfig};
 pub use native_json::{NativeJsonSerializer, NativeJsonSerializerConfig};
+pub use pretty::{ColorMode, PrettySerializer, PrettySerializerConfig, PrettySerializerOptions};
 pub use raw_message::{RawMessageSerializer, RawMessageSerializerConfig};
 pub use text::{TextSerializer, TextSerializerConfig};
 use vector_core::event::Event;
@@ -0,0 +1,231 @@
+use bytes::{BufMut, BytesMut};
+use derivative::Derivative;
+use serde::{Deserialize, Serialize};
+use tokio_util::codec::Encoder;
+use vector_config::configurable_component;
+use vector_core::{
+    config::{log_schema, DataType},
+    event::{Event, LogEvent},
+    schema,
+};
+
+/// The key of the field holding the level of a log event.
+const LEVEL_KEY: &str = "level";
+
+/// Config used to build a `PrettySerializer`.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct PrettySerializerConfig {
+    /// Options for the pretty serializer.
+    #[serde(default)]
+    pub pretty: PrettySerializerOptions,
+}
+
+impl PrettySerializerConfig {
+    /// Creates a new `PrettySerializerConfig`.
+    pub const fn new(colors: ColorMode) -> Self {
+        Self {
+            pretty: PrettySerializerOptions { colors },
+        }
+    }
+
+    /// Build the `PrettySerializer` from this configuration.
+    ///
+    /// Since the serializer doesn't know where its output ends up, `auto` disables colors here.
+    /// Callers writing to a terminal resolve `auto` themselves and use `PrettySerializer::new`.
+    pub const fn build(&self) -> PrettySerializer {
+        PrettySerializer::new(matches!(self.pretty.colors, ColorMode::Always))
+    }
+
+    /// The data type of events that are accepted by `PrettySerializer`.
+    pub fn input_type(&self) -> DataType {
+        DataType::Log | DataType::Metric
+    }
+
+    /// The schema required by the serializer.
+    pub fn schema_requirement(&self) -> schema::Requirement {
+        schema::Requirement::empty()
+    }
+}
+
+/// Pretty serializer options.
+#[configurable_component]
+#[derive(Clone, Debug, Default)]
+pub struct PrettySerializerOptions {
+    /// Whether or not to color the output.
+    #[serde(default)]
+    pub colors: ColorMode,
+}
+
+/// Whether or not to color output.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Derivative, Eq, PartialEq)]
+#[derivative(Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ColorMode {
+    /// Color the output if it is written to a terminal.
+    #[derivative(Default)]
+    Auto,
+
+    /// Always color the output.
+    Always,
+
+    /// Never color the output.
+    Never,
+}
+
+/// Serializer that converts an `Event` to human-readable text, meant for reading events on a
+/// console rather than for processing them further.
+///
+/// A log event is rendered as a line of its timestamp, level and message, followed by one indented
+/// line per remaining field, with keys aligned. Metrics are rendered by their `Display`
+/// implementation.
+#[derive(Debug, Clone)]
+pub struct PrettySerializer {
+    colors: bool,
+}
+
+impl PrettySerializer {
+    /// Creates a new `PrettySerializer`.
+    pub const fn new(colors: bool) -> Self {
+        Self { colors }
+    }
+
+    /// Wraps `text` in the ANSI escape sequence for `code` if colors are enabled.
+    fn paint(&self, code: &str, text: &str) -> String {
+        if self.colors {
+            format!("\x1b[{}m{}\x1b[0m", code, text)
+        } else {
+            text.to_string()
+        }
+    }
+
+    fn render_log(&self, log: &LogEvent) -> String {
+        let timestamp_key = log_schema().timestamp_key();
+        let message_key = log_schema().message_key();
+
+        let mut header = Vec::new();
+        if let Some(timestamp) = log.get(timestamp_key) {
+            header.push(self.paint("2", &timestamp.to_string_lossy()));
+        }
+        if let Some(level) = log.get(LEVEL_KEY) {
+            let level = level.to_string_lossy().to_uppercase();
+            let code = match level.as_str() {
+                "ERROR" | "ERR" | "CRITICAL" | "FATAL" => "31",
+                "WARN" | "WARNING" => "33",
+                "INFO" => "32",
+                "DEBUG" => "34",
+                "TRACE" => "35",
+                _ => "1",
+            };
+            header.push(self.paint(code, &format!("{:<5}", level)));
+        }
+        if let Some(message) = log.get(message_key) {
+            header.push(message.to_string_lossy());
+        }
+
+        let fields = log
+            .all_fields()
+            .map(|fields| {
+                fields
+                    .filter(|(key, _)| {
+                        key != timestamp_key && key != message_key && key != LEVEL_KEY
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+        let width = fields.iter().map(|(key, _)| key.len()).max().unwrap_or(0);
+
+        let mut lines = Vec::with_capacity(fields.len() + 1);
+        if !header.is_empty() {
+            lines.push(header.join(" "));
+        }
+        lines.extend(fields.into_iter().map(|(key, value)| {
+            format!(
+                "    {} = {}",
+                self.paint("36", &format!("{:<width$}", key, width = width)),
+                value.to_string_lossy()
+            )
+        }));
+        lines.join("\n")
+    }
+}
+
+impl Encoder<Event> for PrettySerializer {
+    type Error = vector_common::Error;
+
+    fn encode(&mut self, event: Event, buffer: &mut BytesMut) -> Result<(), Self::Error> {
+        match event {
+            Event::Log(log) => buffer.put(self.render_log(&log).as_bytes()),
+            Event::Metric(metric) => buffer.put(metric.to_string().as_bytes()),
+            Event::Trace(_) => {}
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::BytesMut;
+    use chrono::{TimeZone, Utc};
+    use vector_core::event::{Metric, MetricKind, MetricValue};
+
+    use super::*;
+
+    fn encode(colors: bool, event: Event) -> String {
+        let mut buffer = BytesMut::new();
+        PrettySerializer::new(colors)
+            .encode(event, &mut buffer)
+            .unwrap();
+        String::from_utf8(buffer.to_vec()).unwrap()
+    }
+
+    fn log() -> LogEvent {
+        let mut log = LogEvent::from("connection refused");
+        log.insert(
+            log_schema().timestamp_key(),
+            Utc.ymd(2022, 10, 1).and_hms(12, 0, 0),
+        );
+        log.insert("level", "warn");
+        log.insert("host", "localhost");
+        log.insert("http.status", 502);
+        log
+    }
+
+    #[test]
+    fn serialize_log_aligns_fields() {
+        assert_eq!(
+            encode(false, log().into()),
+            "2022-10-01T12:00:00Z WARN  connection refused\n    \
+             host        = localhost\n    \
+             http.status = 502"
+        );
+    }
+
+    #[test]
+    fn serialize_log_with_colors() {
+        let output = encode(true, log().into());
+
+        assert!(output.contains("\x1b[33mWARN \x1b[0m connection refused"));
+        assert!(output.contains("\x1b[36mhost       \x1b[0m = localhost"));
+    }
+
+    #[test]
+    fn serialize_log_without_header_fields() {
+        let mut log = LogEvent::default();
+        log.insert("foo", "bar");
+
+        assert_eq!(encode(false, log.into()), "    foo = bar");
+    }
+
+    #[test]
+    fn serialize_metric() {
+        let metric = Metric::new(
+            "requests",
+            MetricKind::Incremental,
+            MetricValue::Counter { value: 1.0 },
+        );
+
+        assert_eq!(encode(false, metric.into()), "requests{} + 1");
+    }
+}
@@ -8,11 +8,12 @@ use std::fmt::Debug;
 
 use bytes::BytesMut;
 pub use format::{
-    AvroSerializer, AvroSerializerConfig, AvroSerializerOptions, GelfSerializer,
-    GelfSerializerConfig, JsonSerializer, JsonSerializerConfig, LogfmtSerializer,
-    LogfmtSerializerConfig, NativeJsonSerializer, NativeJsonSerializerConfig, NativeSerializer,
-    NativeSerializerConfig, RawMessageSerializer, RawMessageSerializerConfig, TextSerializer,
-    TextSerializerConfig,
+    AvroSerializer, AvroSerializerConfig, AvroSerializerOptions, ColorMode, FlattenStrategy,
+    GelfSerializer, GelfSerializerConfig, JsonSerializer, JsonSerializerConfig, LogfmtSerializer,
+    LogfmtSerializerConfig, LogfmtSerializerOptions, NativeJsonSerializer,
+    NativeJsonSerializerConfig, NativeSerializer, NativeSerializerConfig, PrettySerializer,
+    PrettySerializerConfig, PrettySerializerOptions, RawMessageSerializer,
+    RawMessageSerializerConfig, TextSerializer, TextSerializerConfig,
 };
 pub use framing::{
     BoxedFramer, BoxedFramingError, BytesEncoder, BytesEncoderConfig, CharacterDelimitedEncoder,
@@ -200,7 +201,11 @@ pub enum SerializerConfig {
     Json,
 
     /// Logfmt serialization.
-    Logfmt,
+    Logfmt {
+        /// Logfmt serializer options.
+        #[serde(default)]
+        logfmt: LogfmtSerializerOptions,
+    },
 
     /// Native Vector serialization based on Protocol Buffers.
     Native,
@@ -208,6 +213,16 @@ pub enum SerializerConfig {
     /// Native Vector serialization based on JSON.
     NativeJson,
 
+    /// Human-readable serialization, for reading events on a console.
+    ///
+    /// Log events are rendered as a line of their timestamp, level, and message, followed by one aligned
+    /// line per remaining field. This format is not meant to be parsed again.
+    Pretty {
+        /// Pretty serializer options.
+        #[serde(default)]
+        pretty: PrettySerializerOptions,
+    },
+
     /// No serialization.
     ///
     /// This encoding, specifically, will only encode the `message` field of a log event. Users should take care if
@@ -244,8 +259,10 @@ impl From<JsonSerializerConfig> for SerializerConfig {
 }
 
 impl From<LogfmtSerializerConfig> for SerializerConfig {
-    fn from(_: LogfmtSerializerConfig) -> Self {
-        Self::Logfmt
+    fn from(config: LogfmtSerializerConfig) -> Self {
+        Self::Logfmt {
+            logfmt: config.logfmt,
+        }
     }
 }
 
@@ -261,6 +278,14 @@ impl From<NativeJsonSerializerConfig> for SerializerConfig {
     }
 }
 
+impl From<PrettySerializerConfig> for SerializerConfig {
+    fn from(config: PrettySerializerConfig) -> Self {
+        Self::Pretty {
+            pretty: config.pretty,
+        }
+    }
+}
+
 impl From<RawMessageSerializerConfig> for SerializerConfig {
     fn from(_: RawMessageSerializerConfig) -> Self {
         Self::RawMessage
@@ -282,11 +307,16 @@ impl SerializerConfig {
             )),
             SerializerConfig::Gelf => Ok(Serializer::Gelf(GelfSerializerConfig::new().build())),
             SerializerConfig::Json => Ok(Serializer::Json(JsonSerializerConfig.build())),
-            SerializerConfig::Logfmt => Ok(Serializer::Logfmt(LogfmtSerializerConfig.build())),
+            SerializerConfig::Logfmt { logfmt } => Ok(Serializer::Logfmt(
+                LogfmtSerializerConfig::new(logfmt.clone()).build(),
+            )),
             SerializerConfig::Native => Ok(Serializer::Native(NativeSerializerConfig.build())),
             SerializerConfig::NativeJson => {
                 Ok(Serializer::NativeJson(NativeJsonSerializerConfig.build()))
             }
+            SerializerConfig::Pretty { pretty } => Ok(Serializer::Pretty(
+                PrettySerializerConfig::new(pretty.colors).build(),
+            )),
             SerializerConfig::RawMessage => {
                 Ok(Serializer::RawMessage(RawMessageSerializerConfig.build()))
             }
@@ -302,9 +332,12 @@ impl SerializerConfig {
             }
             SerializerConfig::Gelf { .. } => GelfSerializerConfig::input_type(),
             SerializerConfig::Json => JsonSerializerConfig.input_type(),
-            SerializerConfig::Logfmt => LogfmtSerializerConfig.input_type(),
+            SerializerConfig::Logfmt { .. } => LogfmtSerializerConfig::default().input_type(),
             SerializerConfig::Native => NativeSerializerConfig.input_type(),
             SerializerConfig::NativeJson => NativeJsonSerializerConfig.input_type(),
+            SerializerConfig::Pretty { pretty } => {
+                PrettySerializerConfig::new(pretty.colors).input_type()
+            }
             SerializerConfig::RawMessage => RawMessageSerializerConfig.input_type(),
             SerializerConfig::Text => TextSerializerConfig.input_type(),
         }
@@ -318,9 +351,14 @@ impl SerializerConfig {
             }
             SerializerConfig::Gelf { .. } => GelfSerializerConfig::schema_requirement(),
             SerializerConfig::Json => JsonSerializerConfig.schema_requirement(),
-            SerializerConfig::Logfmt => LogfmtSerializerConfig.schema_requirement(),
+            SerializerConfig::Logfmt { .. } => {
+                LogfmtSerializerConfig::default().schema_requirement()
+            }
             SerializerConfig::Native => NativeSerializerConfig.schema_requirement(),
             SerializerConfig::NativeJson => NativeJsonSerializerConfig.schema_requirement(),
+            SerializerConfig::Pretty { pretty } => {
+                PrettySerializerConfig::new(pretty.colors).schema_requirement()
+            }
             SerializerConfig::RawMessage => RawMessageSerializerConfig.schema_requirement(),
             SerializerConfig::Text => TextSerializerConfig.schema_requirement(),
         }
@@ -342,6 +380,8 @@ pub enum Serializer {
     Native(NativeSerializer),
     /// Uses a `NativeJsonSerializer` for serialization.
     NativeJson(NativeJsonSerializer),
+    /// Uses a `PrettySerializer` for serialization.
+    Pretty(PrettySerializer),
     /// Uses a `RawMessageSerializer` for serialization.
     RawMessage(RawMessageSerializer),
     /// Uses a `TextSerializer` for serialization.
@@ -357,6 +397,7 @@ impl Serializer {
             | Serializer::Logfmt(_)
             | Serializer::Text(_)
             | Serializer::Native(_)
+            | Serializer::Pretty(_)
             | Serializer::RawMessage(_) => false,
         }
     }
@@ -376,6 +417,7 @@ impl Serializer {
             | Serializer::Logfmt(_)
             | Serializer::Text(_)
             | Serializer::Native(_)
+            | Serializer::Pretty(_)
             | Serializer::RawMessage(_) => {
                 panic!("Serializer does not support JSON")
             }
@@ -419,6 +461,12 @@ impl From<NativeJsonSerializer> for Serializer {
     }
 }
 
+impl From<PrettySerializer> for Serializer {
+    fn from(serializer: PrettySerializer) -> Self {
+        Self::Pretty(serializer)
+    }
+}
+
 impl From<RawMessageSerializer> for Serializer {
     fn from(serializer: RawMessageSerializer) -> Self {
         Self::RawMessage(serializer)
@@ -442,6 +490,7 @@ impl tokio_util::codec::Encoder<Event> for Serializer {
             Serializer::Logfmt(serializer) => serializer.encode(event, buffer),
             Serializer::Native(serializer) => serializer.encode(event, buffer),
             Serializer::NativeJson(serializer) => serializer.encode(event, buffer),
+            Serializer::Pretty(serializer) => serializer.encode(event, buffer),
             Serializer::RawMessage(serializer) => serializer.encode(event, buffer),
             Serializer::Text(serializer) => serializer.encode(event, buffer),
         }
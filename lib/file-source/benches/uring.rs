@@ -0,0 +1,82 @@
+//! Compares reading thousands of tailed files one `pread` at a time with reading them in batches
+//! through `io_uring`, both when the files have new data and when they're idle at their end.
+
+#[cfg(target_os = "linux")]
+mod linux {
+    use std::{
+        fs::File,
+        io::Write,
+        os::unix::{fs::FileExt, io::AsRawFd},
+    };
+
+    use criterion::{criterion_group, BenchmarkId, Criterion, Throughput};
+    use file_source::uring::{Ring, BUFFER_SIZE};
+
+    const FILES: usize = 2_000;
+    const LINE: &[u8] = b"2022-10-16T12:00:00Z INFO request handled in 12ms path=/api/v1/items\n";
+
+    fn read_files(c: &mut Criterion) {
+        let mut ring = match Ring::new() {
+            Ok(ring) => ring,
+            Err(error) => {
+                eprintln!("Skipping io_uring benchmarks: {}", error);
+                return;
+            }
+        };
+
+        let dir = tempfile::tempdir().unwrap();
+        let files = (0..FILES)
+            .map(|i| {
+                let path = dir.path().join(format!("{}.log", i));
+                let mut file = File::create(&path).unwrap();
+                for _ in 0..16 {
+                    file.write_all(LINE).unwrap();
+                }
+                File::open(&path).unwrap()
+            })
+            .collect::<Vec<_>>();
+        let len = (LINE.len() * 16) as u64;
+
+        let mut group = c.benchmark_group("file-source/uring");
+        group.throughput(Throughput::Elements(FILES as u64));
+        // The files are read from their beginning when they have new data, and from their end
+        // when they're idle.
+        for (name, offset) in [("new_data", 0), ("idle", len)] {
+            let reads = files
+                .iter()
+                .map(|file| (file.as_raw_fd(), offset))
+                .collect::<Vec<_>>();
+
+            group.bench_with_input(BenchmarkId::new("pread", name), &offset, |b, &offset| {
+                let mut buffer = vec![0; BUFFER_SIZE];
+                b.iter(|| {
+                    files
+                        .iter()
+                        .map(|file| file.read_at(&mut buffer, offset).unwrap())
+                        .sum::<usize>()
+                })
+            });
+            group.bench_with_input(BenchmarkId::new("io_uring", name), &reads, |b, reads| {
+                b.iter(|| {
+                    let mut total = 0;
+                    ring.read_batch(reads, |_, result| total += result.unwrap().len())
+                        .unwrap();
+                    total
+                })
+            });
+        }
+        group.finish();
+    }
+
+    criterion_group!(
+        name = benches;
+        config = Criterion::default();
+        targets = read_files
+    );
+}
+
+#[cfg(target_os = "linux")]
+criterion::criterion_main!(linux::benches);
+
+#[cfg(not(target_os = "linux"))]
+fn main() {}
@@ -225,6 +225,7 @@ impl Checkpointer {
             BytesChecksum(c) => format!("g{:x}.{}", c, pos),
             FirstLinesChecksum(c) => format!("h{:x}.{}", c, pos),
             DevInode(dev, ino) => format!("i{:x}.{:x}.{}", dev, ino, pos),
+            InodeChecksum(ino, c) => format!("j{:x}.{:x}.{}", ino, c, pos),
             Unknown(x) => format!("{:x}.{}", x, pos),
         };
         self.directory.join(path)
@@ -256,6 +257,12 @@ impl Checkpointer {
                         .unwrap();
                 (DevInode(dev, ino), pos)
             }
+            'j' => {
+                let (ino, c, pos) =
+                    scan_fmt!(file_name, "j{x}.{x}.{}", [hex u64], [hex u64], FilePosition)
+                        .unwrap();
+                (InodeChecksum(ino, c), pos)
+            }
             _ => {
                 let (c, pos) = scan_fmt!(file_name, "{x}.{}", [hex u64], FilePosition).unwrap();
                 (Unknown(c), pos)
@@ -433,6 +440,7 @@ mod test {
     fn test_checkpointer_basics() {
         let fingerprints = vec![
             FileFingerprint::DevInode(1, 2),
+            FileFingerprint::InodeChecksum(2, 1112),
             FileFingerprint::BytesChecksum(3456),
             FileFingerprint::FirstLinesChecksum(78910),
             FileFingerprint::Unknown(1337),
@@ -504,6 +512,7 @@ mod test {
     fn test_checkpointer_restart() {
         let fingerprints = vec![
             FileFingerprint::DevInode(1, 2),
+            FileFingerprint::InodeChecksum(2, 1112),
             FileFingerprint::BytesChecksum(3456),
             FileFingerprint::FirstLinesChecksum(78910),
             FileFingerprint::Unknown(1337),
@@ -742,6 +751,10 @@ mod test {
                 FileFingerprint::DevInode(1, 2),
                 r#"{"version":"1","checkpoints":[{"fingerprint":{"dev_inode":[1,2]},"position":1234}]}"#,
             ),
+            (
+                FileFingerprint::InodeChecksum(2, 1112),
+                r#"{"version":"1","checkpoints":[{"fingerprint":{"inode_checksum":[2,1112]},"position":1234}]}"#,
+            ),
             (
                 FileFingerprint::BytesChecksum(3456),
                 r#"{"version":"1","checkpoints":[{"fingerprint":{"checksum":3456},"position":1234}]}"#,
@@ -792,6 +805,11 @@ mod test {
       "position": 1234,
       "modified": "2021-07-12T18:19:11.769003Z"
     },
+    {
+      "fingerprint": { "inode_checksum": [ 2, 1112 ] },
+      "position": 1234,
+      "modified": "2021-07-12T18:19:11.769003Z"
+    },
     {
       "fingerprint": { "checksum": 3456 },
       "position": 1234,
@@ -817,6 +835,7 @@ mod test {
         "#;
         let fingerprints = vec![
             FileFingerprint::DevInode(1, 2),
+            FileFingerprint::InodeChecksum(2, 1112),
             FileFingerprint::BytesChecksum(3456),
             FileFingerprint::FirstLinesChecksum(1234),
             FileFingerprint::FirstLinesChecksum(78910),
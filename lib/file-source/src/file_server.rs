@@ -1,7 +1,7 @@
 use std::{
     cmp,
-    collections::{BTreeMap, HashSet},
-    fs::{self, remove_file},
+    collections::{BTreeMap, HashMap, HashSet},
+    fs::{self, remove_file, File},
     path::PathBuf,
     sync::Arc,
     time::{self, Duration},
@@ -15,14 +15,15 @@ use futures::{
 };
 use indexmap::IndexMap;
 use tokio::time::sleep;
-use tracing::{debug, error, info, trace};
+use tracing::{debug, error, info, trace, warn};
 
 use crate::{
     checkpointer::{Checkpointer, CheckpointsView},
     file_watcher::FileWatcher,
     fingerprinter::{FileFingerprint, Fingerprinter},
+    metadata_ext::PortableFileExt,
     paths_provider::PathsProvider,
-    FileSourceInternalEvents, ReadFrom,
+    FilePosition, FileSourceInternalEvents, ReadFrom,
 };
 
 /// `FileServer` is a Source which cooperatively schedules reads over files,
@@ -50,6 +51,8 @@ where
     pub fingerprinter: Fingerprinter,
     pub oldest_first: bool,
     pub remove_after: Option<Duration>,
+    pub copytruncate: bool,
+    pub io_uring: bool,
     pub emitter: E,
     pub handle: tokio::runtime::Handle,
 }
@@ -101,6 +104,10 @@ where
 
         let mut known_small_files = HashSet::new();
 
+        // The positions to resume the copies of files rotated by `copytruncate` from, by the
+        // checksum of the files before their truncation.
+        let mut copied_positions = HashMap::new();
+
         let mut existing_files = Vec::new();
         for path in self.paths_provider.paths().into_iter() {
             if let Some(file_id) = self.fingerprinter.get_fingerprint_or_log_error(
@@ -130,12 +137,32 @@ where
                 &mut fingerprint_buffer,
             );
 
-            self.watch_new_file(path, file_id, &mut fp_map, &checkpoints, true);
+            self.watch_new_file(path, file_id, &mut fp_map, &checkpoints, true, None);
         }
         self.emitter.emit_files_open(fp_map.len());
 
         let mut stats = TimingStats::default();
 
+        #[cfg(target_os = "linux")]
+        let mut ring = if self.io_uring {
+            match crate::uring::Ring::new() {
+                Ok(ring) => Some(ring),
+                Err(error) => {
+                    warn!(
+                        message = "The kernel doesn't support io_uring, falling back to regular reads.",
+                        %error,
+                    );
+                    None
+                }
+            }
+        } else {
+            None
+        };
+        #[cfg(not(target_os = "linux"))]
+        if self.io_uring {
+            warn!(message = "io_uring is only supported on Linux, falling back to regular reads.");
+        }
+
         // Spawn the checkpoint writer task
         let checkpoint_task_handle = self.handle.spawn(checkpoint_writer(
             checkpointer,
@@ -219,16 +246,56 @@ where
                                     }
                                 }
                             }
+                        } else if self.copytruncate {
+                            self.watch_rotated_file(
+                                path,
+                                file_id,
+                                &mut fp_map,
+                                &checkpoints,
+                                &mut copied_positions,
+                            );
+                            self.emitter.emit_files_open(fp_map.len());
                         } else {
                             // untracked file fingerprint
-                            self.watch_new_file(path, file_id, &mut fp_map, &checkpoints, false);
+                            self.watch_new_file(
+                                path,
+                                file_id,
+                                &mut fp_map,
+                                &checkpoints,
+                                false,
+                                None,
+                            );
                             self.emitter.emit_files_open(fp_map.len());
                         }
+                    } else if self.copytruncate && known_small_files.contains(&path) {
+                        // A truncated file is too small to be fingerprinted until it is written
+                        // to again, and is still read by its watcher meanwhile.
+                        if let Some(watcher) =
+                            fp_map.values_mut().find(|watcher| watcher.path == path)
+                        {
+                            watcher.set_file_findable(true);
+                        }
                     }
                 }
                 stats.record("discovery", start.elapsed());
             }
 
+            // Read the files ahead of their watchers in one batch, rather than one at a time.
+            #[cfg(target_os = "linux")]
+            if let Some(ring_ref) = ring.as_mut() {
+                let start = time::Instant::now();
+                if let Err(error) = prefetch(ring_ref, &mut fp_map) {
+                    warn!(
+                        message = "Failed to read files through io_uring, falling back to regular reads.",
+                        %error,
+                    );
+                    // The reads in flight have been waited for by `read_batch` before failing, or
+                    // else their buffers are kept alive by the ring, so it can be dropped.
+                    ring = None;
+                }
+                stats.record("prefetching", start.elapsed());
+            }
+
             // Collect lines by polling files.
             let mut global_bytes_read: usize = 0;
             let mut maxed_out_reading_single_file = false;
@@ -357,6 +424,76 @@ where
         }
     }
 
+    /// Watches a file whose fingerprint isn't watched, when detecting `copytruncate` rotations.
+    ///
+    /// Such a rotation copies a file under another name, and then truncates it, changing the
+    /// fingerprint of both the truncated file and, for the fingerprints including the inode, its
+    /// copy. The truncated file is still read by its watcher, which is only given its new
+    /// fingerprint, and the copy is read from where the file was read up to when it was
+    /// truncated, rather than from its beginning.
+    fn watch_rotated_file(
+        &self,
+        path: PathBuf,
+        file_id: FileFingerprint,
+        fp_map: &mut IndexMap<FileFingerprint, FileWatcher>,
+        checkpoints: &CheckpointsView,
+        copied_positions: &mut HashMap<u64, FilePosition>,
+    ) {
+        let inode = File::open(&path).and_then(|file| file.portable_ino()).ok();
+        let truncated = fp_map
+            .iter()
+            .find(|(_, watcher)| watcher.path == path && Some(watcher.inode()) == inode)
+            .map(|(old_id, _)| *old_id);
+        if let Some(old_id) = truncated {
+            let mut watcher = fp_map.shift_remove(&old_id).expect("watched file");
+            watcher.reset_if_truncated().ok();
+            let position = watcher
+                .take_truncated_from()
+                .unwrap_or_else(|| watcher.get_file_position());
+            info!(
+                message = "Watched file has been truncated.",
+                path = ?path,
+                position,
+            );
+            if let Some(checksum) = old_id.content_checksum() {
+                // Unless its copy is already watched.
+                if !fp_map
+                    .keys()
+                    .any(|id| id.content_checksum() == Some(checksum))
+                {
+                    copied_positions.insert(checksum, position);
+                }
+            }
+            checkpoints.set_dead(old_id);
+            watcher.set_file_findable(true);
+            fp_map.insert(file_id, watcher);
+            return;
+        }
+
+        let copied_from = file_id.content_checksum().and_then(|checksum| {
+            copied_positions.remove(&checksum).or_else(|| {
+                fp_map
+                    .iter()
+                    .find(|(id, _)| id.content_checksum() == Some(checksum))
+                    .map(|(_, watcher)| {
+                        watcher
+                            .truncated_from()
+                            .unwrap_or_else(|| watcher.get_file_position())
+                    })
+            })
+        });
+        if let Some(position) = copied_from {
+            info!(
+                message = "Found copy of watched file.",
+                path = ?path,
+                position,
+            );
+        }
+        self.watch_new_file(path, file_id, fp_map, checkpoints, false, copied_from);
+    }
+
+    /// Watches a new file, from `position` if set, or else from its checkpoint or the
+    /// configured position.
     fn watch_new_file(
         &self,
         path: PathBuf,
@@ -364,6 +501,7 @@ where
         fp_map: &mut IndexMap<FileFingerprint, FileWatcher>,
         checkpoints: &CheckpointsView,
         startup: bool,
+        position: Option<FilePosition>,
     ) {
         // Determine the initial _requested_ starting point in the file. This can be overridden
         // once the file is actually opened and we determine it is compressed, older than we're
@@ -382,7 +520,9 @@ where
         // `kubernetes_logs` source returns the files well after start-up, once it has populated
         // them from the k8s metadata, so we now just always use the checkpoints unless opted out.
         // https://github.com/vectordotdev/vector/issues/7139
-        let read_from = if !self.ignore_checkpoints {
+        let read_from = if let Some(position) = position {
+            ReadFrom::Checkpoint(position)
+        } else if !self.ignore_checkpoints {
             checkpoints
                 .get(file_id)
                 .map(ReadFrom::Checkpoint)
@@ -405,6 +545,7 @@ where
                     self.emitter.emit_file_added(&path);
                 }
                 watcher.set_file_findable(true);
+                watcher.set_detect_truncation(self.copytruncate);
                 fp_map.insert(file_id, watcher);
             }
             Err(error) => self.emitter.emit_file_watch_error(&path, error),
@@ -449,6 +590,31 @@ async fn checkpoint_writer(
 #[derive(Debug)]
 pub struct Shutdown;
 
+/// Reads the files to be read by their watchers ahead of them, in batches submitted to `ring`.
+#[cfg(target_os = "linux")]
+fn prefetch(
+    ring: &mut crate::uring::Ring,
+    fp_map: &mut IndexMap<FileFingerprint, FileWatcher>,
+) -> std::io::Result<()> {
+    use std::os::unix::io::AsRawFd;
+
+    let (watchers, reads): (Vec<_>, Vec<_>) = fp_map
+        .values()
+        .enumerate()
+        .filter(|(_, watcher)| watcher.should_read())
+        .filter_map(|(index, watcher)| {
+            let (file, offset) = watcher.prefetch_target()?;
+            Some((index, (file.as_raw_fd(), offset)))
+        })
+        .unzip();
+
+    ring.read_batch(&reads, |read, result| {
+        if let Some((_, watcher)) = fp_map.get_index_mut(watchers[read]) {
+            watcher.prefetched(result);
+        }
+    })
+}
+
 struct TimingStats {
     started_at: time::Instant,
     segments: BTreeMap<&'static str, Duration>,
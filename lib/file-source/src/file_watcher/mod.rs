@@ -10,9 +10,11 @@ use chrono::{DateTime, Utc};
 use flate2::bufread::MultiGzDecoder;
 use tracing::debug;
 
+use self::prefetch::{Prefetch, PrefetchReader};
 use crate::{
     buffer::read_until_with_max_size, metadata_ext::PortableFileExt, FilePosition, ReadFrom,
 };
+mod prefetch;
 #[cfg(test)]
 mod tests;
 
@@ -38,6 +40,10 @@ pub struct FileWatcher {
     pub path: PathBuf,
     findable: bool,
     reader: Box<dyn BufRead>,
+    /// The file being read, unless it is compressed, to detect its truncation.
+    file: Option<File>,
+    /// The data read ahead of the reader, unless the file is compressed.
+    prefetch: Option<Prefetch>,
     file_position: FilePosition,
     devno: u64,
     inode: u64,
@@ -47,6 +53,9 @@ pub struct FileWatcher {
     max_line_bytes: usize,
     line_delimiter: Bytes,
     buf: BytesMut,
+    detect_truncation: bool,
+    at_eof: bool,
+    truncated_from: Option<FilePosition>,
 }
 
 impl FileWatcher {
@@ -65,6 +74,7 @@ impl FileWatcher {
         let f = fs::File::open(&path)?;
         let (devno, ino) = (f.portable_dev()?, f.portable_ino()?);
         let metadata = f.metadata()?;
+        let file = f.try_clone()?;
         let mut reader = io::BufReader::new(f);
 
         let too_old = if let (Some(ignore_before), Ok(modified_time)) = (
@@ -77,16 +87,17 @@ impl FileWatcher {
         };
 
         let gzipped = is_gzipped(&mut reader)?;
+        let file = (!gzipped).then_some(file);
 
         // Determine the actual position at which we should start reading
-        let (reader, file_position): (Box<dyn BufRead>, FilePosition) =
+        let (reader, file_position, prefetch): (Box<dyn BufRead>, FilePosition, _) =
             match (gzipped, too_old, read_from) {
                 (true, true, _) => {
                     debug!(
                         message = "Not reading gzipped file older than `ignore_older`.",
                         ?path,
                     );
-                    (Box::new(null_reader()), 0)
+                    (Box::new(null_reader()), 0, None)
                 }
                 (true, _, ReadFrom::Checkpoint(file_position)) => {
                     debug!(
@@ -94,7 +105,7 @@ impl FileWatcher {
                         ?path,
                         %file_position
                     );
-                    (Box::new(null_reader()), file_position)
+                    (Box::new(null_reader()), file_position, None)
                 }
                 // TODO: This may become the default, leading us to stop reading gzipped files that
                 // we were reading before. Should we merge this and the next branch to read
@@ -105,26 +116,32 @@ impl FileWatcher {
                         message = "Can't read from the end of already-compressed file.",
                         ?path,
                     );
-                    (Box::new(null_reader()), 0)
-                }
-                (true, false, ReadFrom::Beginning) => {
-                    (Box::new(io::BufReader::new(MultiGzDecoder::new(reader))), 0)
+                    (Box::new(null_reader()), 0, None)
                 }
+                (true, false, ReadFrom::Beginning) => (
+                    Box::new(io::BufReader::new(MultiGzDecoder::new(reader))),
+                    0,
+                    None,
+                ),
                 (false, true, _) => {
                     let pos = reader.seek(io::SeekFrom::End(0)).unwrap();
-                    (Box::new(reader), pos)
+                    let (reader, prefetch) = file_reader(reader.into_inner(), pos);
+                    (reader, pos, Some(prefetch))
                 }
                 (false, false, ReadFrom::Checkpoint(file_position)) => {
                     let pos = reader.seek(io::SeekFrom::Start(file_position)).unwrap();
-                    (Box::new(reader), pos)
+                    let (reader, prefetch) = file_reader(reader.into_inner(), pos);
+                    (reader, pos, Some(prefetch))
                 }
                 (false, false, ReadFrom::Beginning) => {
                     let pos = reader.seek(io::SeekFrom::Start(0)).unwrap();
-                    (Box::new(reader), pos)
+                    let (reader, prefetch) = file_reader(reader.into_inner(), pos);
+                    (reader, pos, Some(prefetch))
                 }
                 (false, false, ReadFrom::End) => {
                     let pos = reader.seek(io::SeekFrom::End(0)).unwrap();
-                    (Box::new(reader), pos)
+                    let (reader, prefetch) = file_reader(reader.into_inner(), pos);
+                    (reader, pos, Some(prefetch))
                 }
             };
 
@@ -139,6 +156,8 @@ impl FileWatcher {
             path,
             findable: true,
             reader,
+            file,
+            prefetch,
             file_position,
             devno,
             inode: ino,
@@ -148,6 +167,9 @@ impl FileWatcher {
             max_line_bytes,
             line_delimiter,
             buf: BytesMut::new(),
+            detect_truncation: false,
+            at_eof: false,
+            truncated_from: None,
         })
     }
 
@@ -156,15 +178,22 @@ impl FileWatcher {
         if (file_handle.portable_dev()?, file_handle.portable_ino()?) != (self.devno, self.inode) {
             let mut reader = io::BufReader::new(fs::File::open(&path)?);
             let gzipped = is_gzipped(&mut reader)?;
+            self.file = if gzipped {
+                None
+            } else {
+                Some(reader.get_ref().try_clone()?)
+            };
             let new_reader: Box<dyn BufRead> = if gzipped {
+                self.prefetch = None;
                 if self.file_position != 0 {
                     Box::new(null_reader())
                 } else {
                     Box::new(io::BufReader::new(MultiGzDecoder::new(reader)))
                 }
             } else {
-                reader.seek(io::SeekFrom::Start(self.file_position))?;
-                Box::new(reader)
+                let (reader, prefetch) = file_reader(reader.into_inner(), self.file_position);
+                self.prefetch = Some(prefetch);
+                reader
             };
             self.reader = new_reader;
             self.devno = file_handle.portable_dev()?;
@@ -194,6 +223,67 @@ impl FileWatcher {
         self.file_position
     }
 
+    pub fn inode(&self) -> u64 {
+        self.inode
+    }
+
+    /// The file to read ahead of the reader along with the position to read it from, unless the
+    /// file is compressed or data read ahead of the reader is still to be consumed.
+    #[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+    pub(super) fn prefetch_target(&self) -> Option<(&File, FilePosition)> {
+        let offset = self.prefetch.as_ref()?.offset()?;
+        Some((self.file.as_ref()?, offset))
+    }
+
+    /// Hands the data read ahead of the reader from the position given by `prefetch_target` over
+    /// to the reader. Errors are left for the reader to run into.
+    #[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+    pub(super) fn prefetched(&mut self, result: io::Result<&[u8]>) {
+        if let (Some(prefetch), Ok(data)) = (&self.prefetch, result) {
+            prefetch.extend(data);
+        }
+    }
+
+    /// Sets whether to read the file from the beginning again when it is truncated below the read
+    /// position, as when it is rotated by `copytruncate`.
+    pub fn set_detect_truncation(&mut self, detect: bool) {
+        self.detect_truncation = detect;
+    }
+
+    /// The read position of the file when it was last truncated, if it was.
+    pub fn truncated_from(&self) -> Option<FilePosition> {
+        self.truncated_from
+    }
+
+    pub fn take_truncated_from(&mut self) -> Option<FilePosition> {
+        self.truncated_from.take()
+    }
+
+    /// Reads the file from the beginning again if it is smaller than the read position, that is if
+    /// it has been truncated since it was last read.
+    pub fn reset_if_truncated(&mut self) -> io::Result<()> {
+        let file = match &self.file {
+            Some(file) => file,
+            None => return Ok(()),
+        };
+        if file.metadata()?.len() >= self.file_position {
+            return Ok(());
+        }
+
+        debug!(
+            message = "Watched file has been truncated.",
+            path = ?self.path,
+            file_position = %self.file_position,
+        );
+        let (reader, prefetch) = file_reader(file.try_clone()?, 0);
+        self.reader = reader;
+        self.prefetch = Some(prefetch);
+        self.truncated_from = Some(self.file_position);
+        self.file_position = 0;
+        self.buf.clear();
+        Ok(())
+    }
+
     /// Read a single line from the underlying file
     ///
     /// This function will attempt to read a new line from its file, blocking,
@@ -202,6 +292,13 @@ impl FileWatcher {
     pub(super) fn read_line(&mut self) -> io::Result<Option<RawLine>> {
         self.track_read_attempt();
 
+        // The size of the file only needs checking once its end has been reached, rather than
+        // for each line.
+        if self.detect_truncation && self.at_eof {
+            self.at_eof = false;
+            self.reset_if_truncated()?;
+        }
+
         let reader = &mut self.reader;
         let file_position = &mut self.file_position;
         let initial_position = *file_position;
@@ -213,6 +310,7 @@ impl FileWatcher {
             self.max_line_bytes,
         ) {
             Ok(Some(_)) => {
+                self.at_eof = false;
                 self.track_read_success();
                 Ok(Some(RawLine {
                     offset: initial_position,
@@ -220,6 +318,7 @@ impl FileWatcher {
                 }))
             }
             Ok(None) => {
+                self.at_eof = true;
                 if !self.file_findable() {
                     self.set_dead();
                     // File has been deleted, so return what we have in the buffer, even though it
@@ -277,6 +376,13 @@ fn is_gzipped(r: &mut io::BufReader<fs::File>) -> io::Result<bool> {
     Ok(header_bytes.starts_with(&[0x1f, 0x8b]))
 }
 
+/// Boxes a reader of the uncompressed `file` from `position`, which data read ahead of it can be
+/// handed over to.
+fn file_reader(file: File, position: FilePosition) -> (Box<dyn BufRead>, Prefetch) {
+    let (reader, prefetch) = PrefetchReader::new(file, position);
+    (Box::new(io::BufReader::new(reader)), prefetch)
+}
+
 fn null_reader() -> impl BufRead {
     io::Cursor::new(Vec::new())
 }
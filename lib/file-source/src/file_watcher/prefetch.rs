@@ -0,0 +1,129 @@
+use std::{
+    cell::RefCell,
+    cmp,
+    fs::File,
+    io::{self, Read},
+    rc::Rc,
+};
+
+use bytes::{Buf, BytesMut};
+
+use crate::FilePosition;
+
+#[derive(Default)]
+struct State {
+    /// The position the next read of the file starts at.
+    offset: FilePosition,
+    /// The data read ahead of the reader.
+    data: BytesMut,
+    /// Whether the last read ahead of the reader found the end of the file.
+    eof: bool,
+}
+
+/// The data of a file read ahead of its `PrefetchReader`, as done by `FileServer` through
+/// `io_uring`.
+#[derive(Clone, Default)]
+pub(super) struct Prefetch(Rc<RefCell<State>>);
+
+impl Prefetch {
+    /// The position to read the file ahead of the reader from, unless data read ahead of it is
+    /// still to be consumed.
+    pub(super) fn offset(&self) -> Option<FilePosition> {
+        let state = self.0.borrow();
+        state.data.is_empty().then_some(state.offset)
+    }
+
+    /// Adds the data read ahead of the reader, at the position given by `offset`.
+    pub(super) fn extend(&self, data: &[u8]) {
+        let mut state = self.0.borrow_mut();
+        state.offset += data.len() as FilePosition;
+        state.data.extend_from_slice(data);
+        state.eof = data.is_empty();
+    }
+}
+
+/// Reads a file from a position, first returning the data read ahead of it.
+///
+/// The file is read with positional reads, so its cursor isn't used.
+pub(super) struct PrefetchReader {
+    file: File,
+    prefetch: Prefetch,
+}
+
+impl PrefetchReader {
+    pub(super) fn new(file: File, offset: FilePosition) -> (Self, Prefetch) {
+        let prefetch = Prefetch::default();
+        prefetch.0.borrow_mut().offset = offset;
+        (
+            Self {
+                file,
+                prefetch: prefetch.clone(),
+            },
+            prefetch,
+        )
+    }
+}
+
+impl Read for PrefetchReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let mut state = self.prefetch.0.borrow_mut();
+        if !state.data.is_empty() {
+            let len = cmp::min(buf.len(), state.data.len());
+            buf[..len].copy_from_slice(&state.data[..len]);
+            state.data.advance(len);
+            return Ok(len);
+        }
+        // The end of the file was just found when reading ahead, so there's no need to look for
+        // it again.
+        if state.eof {
+            state.eof = false;
+            return Ok(0);
+        }
+
+        let len = read_at(&self.file, buf, state.offset)?;
+        state.offset += len as FilePosition;
+        Ok(len)
+    }
+}
+
+#[cfg(unix)]
+fn read_at(file: &File, buf: &mut [u8], offset: FilePosition) -> io::Result<usize> {
+    std::os::unix::fs::FileExt::read_at(file, buf, offset)
+}
+
+#[cfg(windows)]
+fn read_at(file: &File, buf: &mut [u8], offset: FilePosition) -> io::Result<usize> {
+    std::os::windows::fs::FileExt::seek_read(file, buf, offset)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{BufRead, BufReader, Write};
+
+    use super::*;
+
+    #[test]
+    fn reads_prefetched_data_first() {
+        let mut file = tempfile::tempfile().unwrap();
+        file.write_all(b"first\nsecond\nthird\n").unwrap();
+
+        let (reader, prefetch) = PrefetchReader::new(file.try_clone().unwrap(), 6);
+        let mut reader = BufReader::new(reader);
+
+        assert_eq!(prefetch.offset(), Some(6));
+        prefetch.extend(b"seco");
+        assert_eq!(prefetch.offset(), None);
+
+        let mut lines = reader.by_ref().lines();
+        assert_eq!(lines.next().unwrap().unwrap(), "second");
+        assert_eq!(lines.next().unwrap().unwrap(), "third");
+        assert!(lines.next().is_none());
+
+        // Reading ahead at the end of the file spares the reader from looking for it again.
+        assert_eq!(prefetch.offset(), Some(19));
+        prefetch.extend(b"");
+        file.write_all(b"fourth\n").unwrap();
+        assert_eq!(reader.fill_buf().unwrap(), b"");
+        assert_eq!(reader.fill_buf().unwrap(), b"fourth\n");
+    }
+}
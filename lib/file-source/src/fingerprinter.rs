@@ -32,6 +32,10 @@ pub enum FingerprintStrategy {
         lines: usize,
     },
     DevInode,
+    InodeChecksum {
+        bytes: usize,
+        ignored_header_bytes: usize,
+    },
 }
 
 #[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, Serialize, Deserialize, Ord, PartialOrd)]
@@ -42,6 +46,7 @@ pub enum FileFingerprint {
     #[serde(alias = "first_line_checksum")]
     FirstLinesChecksum(u64),
     DevInode(u64, u64),
+    InodeChecksum(u64, u64),
     Unknown(u64),
 }
 
@@ -58,9 +63,28 @@ impl FileFingerprint {
                 buf.write_all(&ino.to_be_bytes()).expect("writing to array");
                 FINGERPRINT_CRC.checksum(&buf[..])
             }
+            InodeChecksum(ino, c) => {
+                let mut buf = Vec::with_capacity(std::mem::size_of_val(ino) * 2);
+                buf.write_all(&ino.to_be_bytes()).expect("writing to array");
+                buf.write_all(&c.to_be_bytes()).expect("writing to array");
+                FINGERPRINT_CRC.checksum(&buf[..])
+            }
             Unknown(c) => *c,
         }
     }
+
+    /// The checksum of the first bytes or lines of the file, for the strategies using one.
+    ///
+    /// Files with the same checksum have the same content at their beginning, such as a file
+    /// and its copy.
+    pub const fn content_checksum(&self) -> Option<u64> {
+        use FileFingerprint::*;
+
+        match self {
+            BytesChecksum(c) | FirstLinesChecksum(c) | InodeChecksum(_, c) => Some(*c),
+            DevInode(..) | Unknown(_) => None,
+        }
+    }
 }
 
 impl From<u64> for FileFingerprint {
@@ -84,6 +108,18 @@ impl Fingerprinter {
                 let ino = file_handle.portable_ino()?;
                 Ok(DevInode(dev, ino))
             }
+            FingerprintStrategy::InodeChecksum {
+                bytes,
+                ignored_header_bytes,
+            } => {
+                buffer.resize(bytes, 0u8);
+                let mut fp = fs::File::open(path)?;
+                let ino = fp.portable_ino()?;
+                fp.seek(SeekFrom::Start(ignored_header_bytes as u64))?;
+                fp.read_exact(&mut buffer[..bytes])?;
+                let fingerprint = FINGERPRINT_CRC.checksum(&buffer[..]);
+                Ok(InodeChecksum(ino, fingerprint))
+            }
             FingerprintStrategy::Checksum {
                 ignored_header_bytes,
                 bytes: _,
@@ -439,6 +475,54 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_inode_checksum_fingerprint() {
+        let fingerprinter = Fingerprinter {
+            strategy: FingerprintStrategy::InodeChecksum {
+                bytes: 16,
+                ignored_header_bytes: 0,
+            },
+            max_line_length: 1024,
+            ignore_not_found: false,
+        };
+
+        let target_dir = tempdir().unwrap();
+        let path = target_dir.path().join("app.log");
+        let copy_path = target_dir.path().join("app.log.1");
+        let small_path = target_dir.path().join("small.log");
+        fs::write(&path, b"first line of the log\n").unwrap();
+        fs::copy(&path, &copy_path).unwrap();
+        fs::write(&small_path, b"short\n").unwrap();
+
+        let mut buf = Vec::new();
+        assert!(fingerprinter
+            .get_fingerprint_of_file(&small_path, &mut buf)
+            .is_err());
+
+        let fingerprint = fingerprinter
+            .get_fingerprint_of_file(&path, &mut buf)
+            .unwrap();
+        let copy_fingerprint = fingerprinter
+            .get_fingerprint_of_file(&copy_path, &mut buf)
+            .unwrap();
+        assert_ne!(fingerprint, copy_fingerprint);
+        assert_eq!(
+            fingerprint.content_checksum(),
+            copy_fingerprint.content_checksum()
+        );
+
+        // Truncating and rewriting the file keeps its inode but changes its checksum.
+        fs::write(&path, b"a line after the rotation\n").unwrap();
+        let rewritten_fingerprint = fingerprinter
+            .get_fingerprint_of_file(&path, &mut buf)
+            .unwrap();
+        assert_ne!(fingerprint, rewritten_fingerprint);
+        assert_ne!(
+            fingerprint.content_checksum(),
+            rewritten_fingerprint.content_checksum()
+        );
+    }
+
     #[test]
     fn no_error_on_dir() {
         let target_dir = tempdir().unwrap();
@@ -12,6 +12,8 @@ mod fingerprinter;
 mod internal_events;
 mod metadata_ext;
 pub mod paths_provider;
+#[cfg(target_os = "linux")]
+pub mod uring;
 
 pub use self::{
     checkpointer::{Checkpointer, CheckpointsView, CHECKPOINT_FILE_NAME},
@@ -0,0 +1,202 @@
+//! Batched reads of files through `io_uring`.
+//!
+//! Tailing thousands of files means reading each of them on every pass over the files, even though
+//! most of them have no new data. Submitting the reads of all the files to a ring at once replaces
+//! one `read` system call per file with one `io_uring_enter` call per batch of files. The reads use
+//! buffers registered with the ring, so their pages aren't mapped again for each read.
+//!
+//! It requires Linux 5.1, and `Ring::new` fails on older kernels, or where `io_uring` is disabled,
+//! such as by the default seccomp profile of some container runtimes.
+
+use std::{io, mem, os::unix::io::RawFd};
+
+use io_uring::{opcode, types, IoUring};
+
+/// The number of reads submitted at once.
+pub const QUEUE_DEPTH: u32 = 128;
+
+/// The size of the buffers the reads are done into, that is the maximum number of bytes read from
+/// a file at once.
+pub const BUFFER_SIZE: usize = 16 * 1024;
+
+/// An `io_uring` instance reading files into registered buffers.
+pub struct Ring {
+    // Declared before the buffers, so that the ring is closed before they're freed.
+    ring: IoUring,
+    buffers: Vec<Vec<u8>>,
+    /// The number of reads submitted to the kernel that haven't completed yet.
+    in_flight: usize,
+}
+
+impl Ring {
+    /// Sets up a ring, failing if the kernel doesn't support `io_uring`.
+    pub fn new() -> io::Result<Self> {
+        let mut ring = Self {
+            ring: IoUring::new(QUEUE_DEPTH)?,
+            buffers: (0..QUEUE_DEPTH).map(|_| vec![0; BUFFER_SIZE]).collect(),
+            in_flight: 0,
+        };
+
+        let iovecs = ring
+            .buffers
+            .iter_mut()
+            .map(|buffer| libc::iovec {
+                iov_base: buffer.as_mut_ptr().cast(),
+                iov_len: buffer.len(),
+            })
+            .collect::<Vec<_>>();
+        // The buffers outlive the ring, which unregisters them when closed.
+        ring.ring.submitter().register_buffers(&iovecs)?;
+
+        Ok(ring)
+    }
+
+    /// Reads from each of `reads`, a file descriptor along with the offset to read it from,
+    /// calling `read` with the index of the read and the bytes read, or the error reading them.
+    ///
+    /// Zero bytes are read from files at their end, as with `pread`. All the reads are done when
+    /// this returns, so the file descriptors only need to be open for the duration of the call.
+    /// That holds on errors too: the reads still in flight are waited for before returning, so the
+    /// ring can be dropped right away.
+    pub fn read_batch(
+        &mut self,
+        reads: &[(RawFd, u64)],
+        mut read: impl FnMut(usize, io::Result<&[u8]>),
+    ) -> io::Result<()> {
+        let entries = (self.ring.params().sq_entries().min(QUEUE_DEPTH) as usize).max(1);
+        for (batch, chunk) in reads.chunks(entries).enumerate() {
+            let result = self.submit(chunk).and_then(|()| {
+                self.complete(|index, result| read(batch * entries + index, result))
+            });
+            if let Err(error) = result {
+                self.drain();
+                return Err(error);
+            }
+        }
+        Ok(())
+    }
+
+    /// Queues a read into the buffer of the same index for each of `reads`.
+    fn submit(&mut self, reads: &[(RawFd, u64)]) -> io::Result<()> {
+        let mut submission = self.ring.submission();
+        for (index, &(fd, offset)) in reads.iter().enumerate() {
+            let buffer = &mut self.buffers[index];
+            let entry = opcode::ReadFixed::new(
+                types::Fd(fd),
+                buffer.as_mut_ptr(),
+                buffer.len() as u32,
+                index as u16,
+            )
+            .offset(offset as libc::off_t)
+            .build()
+            .user_data(index as u64);
+            // SAFETY: The buffer is registered with the ring, and not read from until the read
+            // completes.
+            unsafe { submission.push(&entry) }
+                .map_err(|_| io::Error::new(io::ErrorKind::Other, "submission queue is full"))?;
+            self.in_flight += 1;
+        }
+        Ok(())
+    }
+
+    /// Submits the queued reads and waits for all of them to complete.
+    fn complete(&mut self, mut read: impl FnMut(usize, io::Result<&[u8]>)) -> io::Result<()> {
+        while self.in_flight > 0 {
+            match self.ring.submit_and_wait(self.in_flight) {
+                Ok(_) => {}
+                Err(error) if error.kind() == io::ErrorKind::Interrupted => continue,
+                Err(error) => return Err(error),
+            }
+
+            for entry in self.ring.completion() {
+                self.in_flight -= 1;
+                let index = entry.user_data() as usize;
+                let result = if entry.result() < 0 {
+                    Err(io::Error::from_raw_os_error(-entry.result()))
+                } else {
+                    Ok(&self.buffers[index][..entry.result() as usize])
+                };
+                read(index, result);
+            }
+        }
+        Ok(())
+    }
+
+    /// Waits for the reads in flight after a failure, discarding them.
+    fn drain(&mut self) {
+        while self.in_flight > 0 {
+            match self.ring.submit_and_wait(1) {
+                Ok(_) => {}
+                Err(error) if error.kind() == io::ErrorKind::Interrupted => {}
+                // The reads can't be waited for, `Drop` keeps their buffers alive.
+                Err(_) => return,
+            }
+            let completed = self.ring.completion().count();
+            self.in_flight -= completed;
+        }
+    }
+}
+
+impl Drop for Ring {
+    fn drop(&mut self) {
+        if self.in_flight > 0 {
+            // The kernel may still write into the buffers of the reads that haven't completed,
+            // after the ring is closed. They're leaked rather than freed, so that it doesn't write
+            // into memory that is reused meanwhile.
+            mem::forget(mem::take(&mut self.buffers));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs::File, io::Write, os::unix::io::AsRawFd};
+
+    use super::*;
+
+    #[test]
+    fn reads_batches_of_files() {
+        let mut ring = match Ring::new() {
+            Ok(ring) => ring,
+            // The kernel doesn't support `io_uring`, or it's disabled.
+            Err(_) => return,
+        };
+
+        let dir = tempfile::tempdir().unwrap();
+        let files = (0..QUEUE_DEPTH + 10)
+            .map(|i| {
+                let path = dir.path().join(format!("{}.log", i));
+                let mut file = File::create(&path).unwrap();
+                writeln!(file, "file {}", i).unwrap();
+                File::open(&path).unwrap()
+            })
+            .collect::<Vec<_>>();
+        let reads = files
+            .iter()
+            .enumerate()
+            .map(|(i, file)| (file.as_raw_fd(), if i % 2 == 0 { 0 } else { 5 }))
+            .collect::<Vec<_>>();
+
+        let mut results = vec![None; reads.len()];
+        ring.read_batch(&reads, |index, result| {
+            results[index] = Some(result.unwrap().to_vec());
+        })
+        .unwrap();
+
+        for (i, result) in results.into_iter().enumerate() {
+            let content = format!("file {}\n", i);
+            let expected = if i % 2 == 0 {
+                &content[..]
+            } else {
+                &content[5..]
+            };
+            assert_eq!(result.unwrap(), expected.as_bytes());
+        }
+
+        // Files at their end read zero bytes.
+        ring.read_batch(&[(files[0].as_raw_fd(), 7)], |_, result| {
+            assert_eq!(result.unwrap(), b"")
+        })
+        .unwrap();
+    }
+}
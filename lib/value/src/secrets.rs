@@ -40,6 +40,12 @@ impl Secrets {
         self.secrets.insert(key.into(), value.into());
     }
 
+    /// Returns `true` if the container holds no secrets.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.secrets.is_empty()
+    }
+
     /// Removes a secret
     pub fn remove(&mut self, key: &str) {
         self.secrets.remove(&key.to_owned());
@@ -11,25 +11,36 @@ pub type QueryResult<T> =
 #[derive(Debug)]
 pub struct Client {
     url: Url,
+    token: Option<String>,
 }
 
 impl Client {
     /// Returns a new GraphQL query client, bound to the provided URL.
     pub fn new(url: Url) -> Self {
-        Self { url }
+        Self { url, token: None }
+    }
+
+    /// Sets the API token sent as a bearer token with each query.
+    pub fn with_token(mut self, token: Option<String>) -> Self {
+        self.token = token;
+        self
     }
 
     pub async fn new_with_healthcheck(url: Url) -> Option<Self> {
+        // Create a new API client for connecting to the local/remote Vector instance.
+        Self::new(url).healthcheck().await
+    }
+
+    /// Returns the client if the GraphQL server is reachable, printing instructions for
+    /// enabling the API otherwise.
+    pub async fn healthcheck(self) -> Option<Self> {
         #![allow(clippy::print_stderr)]
 
         use crate::gql::HealthQueryExt;
 
-        // Create a new API client for connecting to the local/remote Vector instance.
-        let client = Self::new(url.clone());
-
         // Check that the GraphQL server is reachable
-        match client.health_query().await {
-            Ok(_) => Some(client),
+        match self.health_query().await {
+            Ok(_) => Some(self),
             _ => {
                 eprintln!(
                     indoc! {"
@@ -41,7 +52,7 @@ impl Client {
 
                     [api]
                       enabled = true"},
-                    url
+                    self.url
                 );
                 None
             }
@@ -56,9 +67,11 @@ impl Client {
     ) -> QueryResult<T> {
         let client = reqwest::Client::new();
 
-        client
-            .post(self.url.clone())
-            .json(request_body)
+        let mut request = client.post(self.url.clone()).json(request_body);
+        if let Some(token) = &self.token {
+            request = request.bearer_auth(token);
+        }
+        request
             .send()
             .await
             .with_context(|| {
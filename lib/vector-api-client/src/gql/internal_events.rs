@@ -0,0 +1,44 @@
+#![allow(clippy::upper_case_acronyms)]
+
+use graphql_client::GraphQLQuery;
+
+use crate::BoxedSubscription;
+
+/// Shorthand for a Chrono datetime, set to UTC.
+type DateTime = chrono::DateTime<chrono::Utc>;
+
+/// InternalEventsSubscription streams the errors and discarded events of components, as they are
+/// logged.
+#[derive(GraphQLQuery, Debug, Copy, Clone)]
+#[graphql(
+    schema_path = "graphql/schema.json",
+    query_path = "graphql/subscriptions/internal_events.graphql",
+    response_derives = "Debug, Clone, PartialEq"
+)]
+pub struct InternalEventsSubscription;
+
+pub trait InternalEventsSubscriptionExt {
+    /// Executes an internal events subscription, for the components matching any of the glob
+    /// `component_id_patterns`, or all components if there are none.
+    fn internal_events_subscription(
+        &self,
+        component_id_patterns: Vec<String>,
+    ) -> crate::BoxedSubscription<InternalEventsSubscription>;
+}
+
+impl InternalEventsSubscriptionExt for crate::SubscriptionClient {
+    /// Executes an internal events subscription.
+    fn internal_events_subscription(
+        &self,
+        component_id_patterns: Vec<String>,
+    ) -> BoxedSubscription<InternalEventsSubscription> {
+        let request_body =
+            InternalEventsSubscription::build_query(internal_events_subscription::Variables {
+                kinds: None,
+                component_id_patterns: (!component_id_patterns.is_empty())
+                    .then_some(component_id_patterns),
+            });
+
+        self.start::<InternalEventsSubscription>(&request_body)
+    }
+}
@@ -2,12 +2,14 @@
 
 mod components;
 mod health;
+mod internal_events;
 mod meta;
 mod metrics;
 mod tap;
 
 pub use components::*;
 pub use health::*;
+pub use internal_events::*;
 pub use metrics::*;
 pub use tap::*;
 
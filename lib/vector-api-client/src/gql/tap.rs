@@ -62,6 +62,7 @@ pub trait TapSubscriptionExt {
         encoding: TapEncodingFormat,
         limit: i64,
         interval: i64,
+        sample_rate: i64,
     ) -> crate::BoxedSubscription<OutputEventsByComponentIdPatternsSubscription>;
 }
 
@@ -74,6 +75,7 @@ impl TapSubscriptionExt for crate::SubscriptionClient {
         encoding: TapEncodingFormat,
         limit: i64,
         interval: i64,
+        sample_rate: i64,
     ) -> BoxedSubscription<OutputEventsByComponentIdPatternsSubscription> {
         let request_body = OutputEventsByComponentIdPatternsSubscription::build_query(
             output_events_by_component_id_patterns_subscription::Variables {
@@ -81,6 +83,7 @@ impl TapSubscriptionExt for crate::SubscriptionClient {
                 inputs_patterns: Some(inputs_patterns),
                 limit,
                 interval,
+                sample_rate,
                 encoding: encoding.into(),
             },
         );
@@ -13,7 +13,14 @@ use tokio::sync::{
     mpsc, oneshot,
 };
 use tokio_stream::{wrappers::BroadcastStream, Stream, StreamExt};
-use tokio_tungstenite::{connect_async, tungstenite::Message};
+use tokio_tungstenite::{
+    connect_async,
+    tungstenite::{
+        client::IntoClientRequest,
+        http::{header::AUTHORIZATION, HeaderValue},
+        Message,
+    },
+};
 use url::Url;
 use uuid::Uuid;
 
@@ -176,7 +183,22 @@ impl SubscriptionClient {
 pub async fn connect_subscription_client(
     url: Url,
 ) -> Result<SubscriptionClient, tokio_tungstenite::tungstenite::Error> {
-    let (ws, _) = connect_async(url).await?;
+    connect_subscription_client_with_token(url, None).await
+}
+
+/// Connect to a new WebSocket GraphQL server endpoint like `connect_subscription_client`,
+/// sending `token`, if any, as a bearer token in the handshake.
+pub async fn connect_subscription_client_with_token(
+    url: Url,
+    token: Option<&str>,
+) -> Result<SubscriptionClient, tokio_tungstenite::tungstenite::Error> {
+    let mut request = url.into_client_request()?;
+    if let Some(token) = token {
+        let value = HeaderValue::from_str(&format!("Bearer {}", token))
+            .map_err(|error| tokio_tungstenite::tungstenite::Error::HttpFormat(error.into()))?;
+        request.headers_mut().insert(AUTHORIZATION, value);
+    }
+    let (ws, _) = connect_async(request).await?;
     let (mut ws_tx, mut ws_rx) = futures::StreamExt::split(ws);
 
     let (send_tx, mut send_rx) = mpsc::unbounded_channel::<Payload>();
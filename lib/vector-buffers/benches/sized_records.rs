@@ -82,6 +82,8 @@ fn create_disk_v1_variant(_max_events: usize, max_size: u64) -> BufferType {
 fn create_disk_v2_variant(_max_events: usize, max_size: u64) -> BufferType {
     BufferType::DiskV2 {
         max_size: NonZeroU64::new(max_size).unwrap(),
+        max_age_secs: None,
+        shards: NonZeroUsize::new(1).unwrap(),
         when_full: WhenFull::DropNewest,
     }
 }
@@ -21,7 +21,7 @@ use vector_buffers::{
         builder::TopologyBuilder,
         channel::{BufferReceiver, BufferSender},
     },
-    BufferType, Bufferable, EventCount, WhenFull,
+    BufferType, Bufferable, EventCount, Prioritized, WhenFull,
 };
 use vector_common::byte_size_of::ByteSizeOf;
 use vector_common::finalization::{
@@ -67,6 +67,8 @@ impl EventCount for VariableMessage {
     }
 }
 
+impl Prioritized for VariableMessage {}
+
 impl Finalizable for VariableMessage {
     fn take_finalizers(&mut self) -> EventFinalizers {
         std::mem::take(&mut self.finalizers)
@@ -282,6 +284,8 @@ where
             );
             BufferType::DiskV2 {
                 max_size: max_size_bytes,
+                max_age_secs: None,
+                shards: std::num::NonZeroUsize::new(1).unwrap(),
                 when_full,
             }
         }
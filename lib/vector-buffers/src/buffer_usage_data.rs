@@ -11,7 +11,10 @@ use tracing::{Instrument, Span};
 use vector_common::internal_event::emit;
 
 use crate::{
-    internal_events::{BufferCreated, BufferEventsDropped, BufferEventsReceived, BufferEventsSent},
+    internal_events::{
+        BufferCreated, BufferEventsDropped, BufferEventsExpired, BufferEventsReceived,
+        BufferEventsSent,
+    },
     spawn_named,
 };
 
@@ -140,6 +143,14 @@ impl BufferUsageHandle {
             self.state.dropped.increment(count, byte_size);
         }
     }
+
+    /// Increments the number of events (and their total size) expired by this buffer component.
+    ///
+    /// This represents events that were discarded, rather than read out of the buffer, for being
+    /// older than the configured maximum age.
+    pub fn increment_expired_event_count_and_byte_size(&self, count: u64, byte_size: u64) {
+        self.state.expired.increment(count, byte_size);
+    }
 }
 
 #[derive(Debug, Default)]
@@ -149,6 +160,7 @@ struct BufferUsageData {
     sent: CategoryMetrics,
     dropped: CategoryMetrics,
     dropped_intentional: CategoryMetrics,
+    expired: CategoryMetrics,
     max_size: CategoryMetrics,
 }
 
@@ -165,6 +177,7 @@ impl BufferUsageData {
         let sent = self.sent.get();
         let dropped = self.dropped.get();
         let dropped_intentional = self.dropped_intentional.get();
+        let expired = self.expired.get();
         let max_size = self.max_size.get();
 
         BufferUsageSnapshot {
@@ -176,6 +189,8 @@ impl BufferUsageData {
             dropped_event_byte_size: dropped.event_byte_size,
             dropped_event_count_intentional: dropped_intentional.event_count,
             dropped_event_byte_size_intentional: dropped_intentional.event_byte_size,
+            expired_event_count: expired.event_count,
+            expired_event_byte_size: expired.event_byte_size,
             max_size_bytes: max_size.event_byte_size,
             max_size_events: max_size
                 .event_count
@@ -196,6 +211,8 @@ pub struct BufferUsageSnapshot {
     pub dropped_event_byte_size: u64,
     pub dropped_event_count_intentional: u64,
     pub dropped_event_byte_size_intentional: u64,
+    pub expired_event_count: u64,
+    pub expired_event_byte_size: u64,
     pub max_size_bytes: u64,
     pub max_size_events: usize,
 }
@@ -300,6 +317,15 @@ impl BufferUsage {
                             byte_size: dropped_intentional.event_byte_size,
                         });
                     }
+
+                    let expired = stage.expired.consume();
+                    if expired.has_updates() {
+                        emit(BufferEventsExpired {
+                            idx: stage.idx,
+                            count: expired.event_count,
+                            byte_size: expired.event_byte_size,
+                        });
+                    }
                 }
             }
         };
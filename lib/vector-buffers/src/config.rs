@@ -3,6 +3,7 @@ use std::{
     num::{NonZeroU64, NonZeroUsize},
     path::{Path, PathBuf},
     slice,
+    time::Duration,
 };
 
 use serde::{de, Deserialize, Deserializer, Serialize};
@@ -16,7 +17,7 @@ use crate::{
         builder::{TopologyBuilder, TopologyError},
         channel::{BufferReceiver, BufferSender},
     },
-    variants::{DiskV1Buffer, DiskV2Buffer, MemoryBuffer},
+    variants::{DiskV1Buffer, DiskV2Buffer, MemoryBuffer, ShardedDiskV2Buffer},
     Bufferable, WhenFull,
 };
 
@@ -40,7 +41,14 @@ enum BufferTypeKind {
     DiskV2,
 }
 
-const ALL_FIELDS: [&str; 4] = ["type", "max_events", "max_size", "when_full"];
+const ALL_FIELDS: [&str; 6] = [
+    "type",
+    "max_events",
+    "max_size",
+    "max_age_secs",
+    "shards",
+    "when_full",
+];
 
 struct BufferTypeVisitor;
 
@@ -52,6 +60,8 @@ impl BufferTypeVisitor {
         let mut kind: Option<BufferTypeKind> = None;
         let mut max_events: Option<NonZeroUsize> = None;
         let mut max_size: Option<NonZeroU64> = None;
+        let mut max_age_secs: Option<NonZeroU64> = None;
+        let mut shards: Option<NonZeroUsize> = None;
         let mut when_full: Option<WhenFull> = None;
         while let Some(key) = map.next_key::<String>()? {
             match key.as_str() {
@@ -73,6 +83,18 @@ impl BufferTypeVisitor {
                     }
                     max_size = Some(map.next_value()?);
                 }
+                "max_age_secs" => {
+                    if max_age_secs.is_some() {
+                        return Err(de::Error::duplicate_field("max_age_secs"));
+                    }
+                    max_age_secs = Some(map.next_value()?);
+                }
+                "shards" => {
+                    if shards.is_some() {
+                        return Err(de::Error::duplicate_field("shards"));
+                    }
+                    shards = Some(map.next_value()?);
+                }
                 "when_full" => {
                     if when_full.is_some() {
                         return Err(de::Error::duplicate_field("when_full"));
@@ -94,6 +116,18 @@ impl BufferTypeVisitor {
                         &["type", "max_events", "when_full"],
                     ));
                 }
+                if max_age_secs.is_some() {
+                    return Err(de::Error::unknown_field(
+                        "max_age_secs",
+                        &["type", "max_events", "when_full"],
+                    ));
+                }
+                if shards.is_some() {
+                    return Err(de::Error::unknown_field(
+                        "shards",
+                        &["type", "max_events", "when_full"],
+                    ));
+                }
                 Ok(BufferType::Memory {
                     max_events: max_events.unwrap_or_else(memory_buffer_default_max_events),
                     when_full,
@@ -106,6 +140,18 @@ impl BufferTypeVisitor {
                         &["type", "max_size", "when_full"],
                     ));
                 }
+                if max_age_secs.is_some() {
+                    return Err(de::Error::unknown_field(
+                        "max_age_secs",
+                        &["type", "max_size", "when_full"],
+                    ));
+                }
+                if shards.is_some() {
+                    return Err(de::Error::unknown_field(
+                        "shards",
+                        &["type", "max_size", "when_full"],
+                    ));
+                }
                 Ok(BufferType::DiskV1 {
                     max_size: max_size.ok_or_else(|| de::Error::missing_field("max_size"))?,
                     when_full,
@@ -115,11 +161,13 @@ impl BufferTypeVisitor {
                 if max_events.is_some() {
                     return Err(de::Error::unknown_field(
                         "max_events",
-                        &["type", "max_size", "when_full"],
+                        &["type", "max_size", "max_age_secs", "shards", "when_full"],
                     ));
                 }
                 Ok(BufferType::DiskV2 {
                     max_size: max_size.ok_or_else(|| de::Error::missing_field("max_size"))?,
+                    max_age_secs,
+                    shards: shards.unwrap_or_else(disk_buffer_default_shards),
                     when_full,
                 })
             }
@@ -155,6 +203,10 @@ pub const fn memory_buffer_default_max_events() -> NonZeroUsize {
     unsafe { NonZeroUsize::new_unchecked(500) }
 }
 
+pub const fn disk_buffer_default_shards() -> NonZeroUsize {
+    unsafe { NonZeroUsize::new_unchecked(1) }
+}
+
 /// Disk usage configurtion for disk-backed buffers.
 #[derive(Debug)]
 pub struct DiskUsage {
@@ -228,6 +280,31 @@ pub enum BufferType {
         /// Must be at least ~256 megabytes (268435488 bytes).
         max_size: NonZeroU64,
 
+        /// The maximum age of buffered events, in seconds.
+        ///
+        /// Events older than this are discarded instead of being sent, which is useful for data that
+        /// loses its value quickly, and would be harmful to replay after a long outage.
+        ///
+        /// Events are expired in batches, as they were written to disk, once the newest event of
+        /// their batch is older than this, which is checked every second. Events may therefore be
+        /// sent even though they are older than this.
+        #[serde(default)]
+        max_age_secs: Option<NonZeroU64>,
+
+        /// The number of shards to spread the buffer over.
+        ///
+        /// Each shard is an independent disk buffer with its own writer, which avoids contention on a
+        /// single writer at very high event rates. Events keep their order within a shard, but not
+        /// across shards. The maximum size of the buffer is split evenly between the shards, each
+        /// of which must be at least ~256 megabytes.
+        ///
+        /// The first shard holds the events buffered before sharding was enabled, and shards
+        /// left over from a configuration with more shards, even a single one, are still read from
+        /// until they're empty. The events left in them count towards the maximum size of the
+        /// buffer, and the rest of it is split between the configured shards.
+        #[serde(default = "disk_buffer_default_shards")]
+        shards: NonZeroUsize,
+
         #[configurable(derived)]
         #[serde(default)]
         when_full: WhenFull,
@@ -314,9 +391,26 @@ impl BufferType {
             BufferType::DiskV2 {
                 when_full,
                 max_size,
+                max_age_secs,
+                shards,
             } => {
                 let data_dir = data_dir.ok_or(BufferBuildError::RequiresDataDir)?;
-                builder.stage(DiskV2Buffer::new(id, data_dir, max_size), when_full);
+                let max_age = max_age_secs.map(|secs| Duration::from_secs(secs.get()));
+                // Shards left over from a configuration with more shards are only read by sharded
+                // buffers.
+                if shards.get() == 1
+                    && !ShardedDiskV2Buffer::has_leftover_shards(&data_dir, &id, shards)
+                {
+                    builder.stage(
+                        DiskV2Buffer::new(id, data_dir, max_size, max_age),
+                        when_full,
+                    );
+                } else {
+                    builder.stage(
+                        ShardedDiskV2Buffer::new(id, data_dir, max_size, max_age, shards),
+                        when_full,
+                    );
+                }
             }
         };
 
@@ -557,8 +651,60 @@ max_events: 42
           "#,
             BufferType::DiskV2 {
                 max_size: NonZeroU64::new(1024).unwrap(),
+                max_age_secs: None,
+                shards: NonZeroUsize::new(1).unwrap(),
+                when_full: WhenFull::Block,
+            },
+        );
+    }
+
+    #[test]
+    fn parse_disk_max_age() {
+        check_single_stage(
+            r#"
+          type: disk
+          max_size: 1024
+          max_age_secs: 3600
+          "#,
+            BufferType::DiskV2 {
+                max_size: NonZeroU64::new(1024).unwrap(),
+                max_age_secs: NonZeroU64::new(3600),
+                shards: NonZeroUsize::new(1).unwrap(),
+                when_full: WhenFull::Block,
+            },
+        );
+
+        let source = r#"
+          type: memory
+          max_age_secs: 3600
+          "#;
+        let error = serde_yaml::from_str::<BufferConfig>(source).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "data did not match any variant of untagged enum BufferConfig"
+        );
+    }
+
+    #[test]
+    fn parse_disk_shards() {
+        check_single_stage(
+            r#"
+          type: disk
+          max_size: 1024
+          shards: 4
+          "#,
+            BufferType::DiskV2 {
+                max_size: NonZeroU64::new(1024).unwrap(),
+                max_age_secs: None,
+                shards: NonZeroUsize::new(4).unwrap(),
                 when_full: WhenFull::Block,
             },
         );
+
+        let source = r#"
+          type: memory
+          shards: 4
+          "#;
+        assert!(serde_yaml::from_str::<BufferConfig>(source).is_err());
     }
 }
@@ -89,6 +89,26 @@ impl InternalEvent for BufferEventsDropped {
     }
 }
 
+pub struct BufferEventsExpired {
+    pub idx: usize,
+    pub count: u64,
+    pub byte_size: u64,
+}
+
+impl InternalEvent for BufferEventsExpired {
+    fn emit(self) {
+        debug!(
+            message = "Events expired.",
+            count = %self.count,
+            stage = %self.idx,
+        );
+        // Expired events are acknowledged like events that were sent, which is where the buffer
+        // size gauges are decremented.
+        counter!("buffer_expired_events_total", self.count, "stage" => self.idx.to_string());
+        counter!("buffer_expired_bytes_total", self.byte_size, "stage" => self.idx.to_string());
+    }
+}
+
 pub struct BufferReadError {
     pub error_code: &'static str,
     pub error: String,
@@ -97,6 +97,7 @@ pub trait Bufferable:
     + ByteSizeOf
     + Encodable
     + EventCount
+    + Prioritized
     + Debug
     + Send
     + Sync
@@ -112,6 +113,7 @@ impl<T> Bufferable for T where
         + ByteSizeOf
         + Encodable
         + EventCount
+        + Prioritized
         + Debug
         + Send
         + Sync
@@ -125,6 +127,35 @@ pub trait EventCount {
     fn event_count(&self) -> usize;
 }
 
+/// Priority with which an item is read out of a buffer.
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub enum Priority {
+    /// Read out in the order the item was written.
+    Normal,
+
+    /// Read out ahead of any normal-priority items.
+    High,
+}
+
+impl Default for Priority {
+    fn default() -> Self {
+        Priority::Normal
+    }
+}
+
+pub trait Prioritized {
+    /// Gets the priority of this item.
+    ///
+    /// Only in-memory buffers read out items by priority. Disk buffers always read out items in the
+    /// order they were written.
+    ///
+    /// This is called for every item sent to an in-memory buffer, so it shouldn't have to look at
+    /// everything the item holds.
+    fn priority(&self) -> Priority {
+        Priority::Normal
+    }
+}
+
 impl<T> EventCount for Vec<T>
 where
     T: EventCount,
@@ -7,7 +7,7 @@ use vector_common::finalization::{
     AddBatchNotifier, BatchNotifier, EventFinalizer, EventFinalizers, Finalizable,
 };
 
-use crate::{encoding::FixedEncodable, EventCount};
+use crate::{encoding::FixedEncodable, EventCount, Prioritized};
 
 macro_rules! message_wrapper {
     ($id:ident: $ty:ty, $event_count:expr) => {
@@ -38,6 +38,8 @@ macro_rules! message_wrapper {
             }
         }
 
+        impl Prioritized for $id {}
+
         impl Finalizable for $id {
             fn take_finalizers(&mut self) -> EventFinalizers {
                 std::mem::take(&mut self.1)
@@ -97,6 +99,8 @@ impl EventCount for Message {
     }
 }
 
+impl Prioritized for Message {}
+
 impl Arbitrary for Message {
     fn arbitrary(g: &mut Gen) -> Self {
         Message {
@@ -209,6 +213,8 @@ impl EventCount for UndecodableRecord {
     }
 }
 
+impl Prioritized for UndecodableRecord {}
+
 impl FixedEncodable for UndecodableRecord {
     type EncodeError = io::Error;
     type DecodeError = io::Error;
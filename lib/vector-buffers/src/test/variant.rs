@@ -80,7 +80,7 @@ impl Variant {
                 id,
             } => {
                 builder.stage(
-                    DiskV2Buffer::new(id.clone(), data_dir.clone(), *max_size),
+                    DiskV2Buffer::new(id.clone(), data_dir.clone(), *max_size, None),
                     *when_full,
                 );
             }
@@ -8,11 +8,11 @@ use std::{
 };
 
 use async_stream::stream;
-use crossbeam_queue::ArrayQueue;
+use crossbeam_queue::{ArrayQueue, SegQueue};
 use futures::Stream;
 use tokio::sync::{Notify, OwnedSemaphorePermit, Semaphore, TryAcquireError};
 
-use crate::Bufferable;
+use crate::{Bufferable, Priority};
 
 /// Error returned by `LimitedSender::send` when the receiver has disconnected.
 #[derive(Debug, PartialEq, Eq)]
@@ -57,6 +57,9 @@ impl<T: fmt::Debug> std::error::Error for TrySendError<T> {}
 #[derive(Debug)]
 struct Inner<T> {
     data: Arc<ArrayQueue<(OwnedSemaphorePermit, T)>>,
+    // High-priority items are read out before any item in `data`. They are bounded by the same
+    // limiter, and are expected to be rare, so we don't preallocate space for them.
+    priority_data: Arc<SegQueue<(OwnedSemaphorePermit, T)>>,
     limit: usize,
     limiter: Arc<Semaphore>,
     read_waker: Arc<Notify>,
@@ -66,6 +69,7 @@ impl<T> Clone for Inner<T> {
     fn clone(&self) -> Self {
         Self {
             data: self.data.clone(),
+            priority_data: self.priority_data.clone(),
             limit: self.limit,
             limiter: self.limiter.clone(),
             read_waker: self.read_waker.clone(),
@@ -93,6 +97,18 @@ impl<T: Bufferable> LimitedSender<T> {
         self.inner.limiter.available_permits()
     }
 
+    fn push(&self, permits: OwnedSemaphorePermit, item: T) {
+        match item.priority() {
+            Priority::High => self.inner.priority_data.push((permits, item)),
+            Priority::Normal => self
+                .inner
+                .data
+                .push((permits, item))
+                .expect("acquired permits but channel reported being full"),
+        }
+        self.inner.read_waker.notify_one();
+    }
+
     /// Sends an item into the channel.
     ///
     /// # Errors
@@ -113,11 +129,7 @@ impl<T: Bufferable> LimitedSender<T> {
             Err(_) => return Err(SendError(item)),
         };
 
-        self.inner
-            .data
-            .push((permits, item))
-            .expect("acquired permits but channel reported being full");
-        self.inner.read_waker.notify_one();
+        self.push(permits, item);
 
         trace!("Sent item.");
 
@@ -150,11 +162,7 @@ impl<T: Bufferable> LimitedSender<T> {
             }
         };
 
-        self.inner
-            .data
-            .push((permits, item))
-            .expect("acquired permits but channel reported being full");
-        self.inner.read_waker.notify_one();
+        self.push(permits, item);
 
         trace!("Attempt to send item succeeded.");
 
@@ -196,7 +204,12 @@ impl<T: Send + 'static> LimitedReceiver<T> {
 
     pub async fn next(&mut self) -> Option<T> {
         loop {
-            if let Some((_permit, item)) = self.inner.data.pop() {
+            if let Some((_permit, item)) = self
+                .inner
+                .priority_data
+                .pop()
+                .or_else(|| self.inner.data.pop())
+            {
                 return Some(item);
             }
 
@@ -235,6 +248,7 @@ impl<T> Drop for LimitedReceiver<T> {
 pub fn limited<T>(limit: usize) -> (LimitedSender<T>, LimitedReceiver<T>) {
     let inner = Inner {
         data: Arc::new(ArrayQueue::new(limit)),
+        priority_data: Arc::new(SegQueue::new()),
         limit,
         limiter: Arc::new(Semaphore::new(limit)),
         read_waker: Arc::new(Notify::new()),
@@ -287,6 +301,26 @@ mod tests {
         assert_eq!(Some(msg), assert_ready!(recv.poll()));
     }
 
+    #[tokio::test]
+    async fn high_priority_items_received_first() {
+        let (mut tx, mut rx) = limited(4);
+
+        tx.send(Sample(1)).await.unwrap();
+        tx.send(Sample(2)).await.unwrap();
+        tx.send(Sample::high_priority(3)).await.unwrap();
+        tx.send(Sample::high_priority(4)).await.unwrap();
+
+        // High-priority items share the capacity of the channel.
+        assert_eq!(0, tx.available_capacity());
+
+        assert_eq!(Some(Sample::high_priority(3)), rx.next().await);
+        assert_eq!(Some(Sample::high_priority(4)), rx.next().await);
+        assert_eq!(Some(Sample(1)), rx.next().await);
+        assert_eq!(Some(Sample(2)), rx.next().await);
+
+        assert_eq!(4, rx.available_capacity());
+    }
+
     #[test]
     fn sender_waits_for_more_capacity_when_none_available() {
         let (mut tx, mut rx) = limited(1);
@@ -31,6 +31,9 @@ pub enum ReceiverAdapter<T: Bufferable> {
 
     /// The disk v2 buffer.
     DiskV2(disk_v2::Reader<T, ProductionFilesystem>),
+
+    /// The disk v2 buffer, sharded over several readers.
+    ShardedDiskV2(disk_v2::ShardedReader<T>),
 }
 
 impl<T: Bufferable> From<LimitedReceiver<T>> for ReceiverAdapter<T> {
@@ -51,6 +54,12 @@ impl<T: Bufferable> From<disk_v2::Reader<T, ProductionFilesystem>> for ReceiverA
     }
 }
 
+impl<T: Bufferable> From<disk_v2::ShardedReader<T>> for ReceiverAdapter<T> {
+    fn from(v: disk_v2::ShardedReader<T>) -> Self {
+        Self::ShardedDiskV2(v)
+    }
+}
+
 impl<T> ReceiverAdapter<T>
 where
     T: Bufferable,
@@ -73,6 +82,18 @@ where
                     },
                 }
             },
+            ReceiverAdapter::ShardedDiskV2(reader) => loop {
+                match reader.next().await {
+                    Ok(result) => break result,
+                    Err(e) => match e.as_recoverable_error() {
+                        Some(re) => {
+                            emit(re);
+                            continue;
+                        }
+                        None => panic!("Reader encountered unrecoverable error: {:?}", e),
+                    },
+                }
+            },
         }
     }
 }
@@ -24,6 +24,9 @@ pub enum SenderAdapter<T: Bufferable> {
 
     /// The disk v2 buffer.
     DiskV2(Arc<Mutex<disk_v2::Writer<T, ProductionFilesystem>>>),
+
+    /// The disk v2 buffer, sharded over several writers.
+    ShardedDiskV2(disk_v2::ShardedWriter<T>),
 }
 
 impl<T: Bufferable> From<LimitedSender<T>> for SenderAdapter<T> {
@@ -44,6 +47,12 @@ impl<T: Bufferable> From<disk_v2::Writer<T, ProductionFilesystem>> for SenderAda
     }
 }
 
+impl<T: Bufferable> From<disk_v2::ShardedWriter<T>> for SenderAdapter<T> {
+    fn from(v: disk_v2::ShardedWriter<T>) -> Self {
+        Self::ShardedDiskV2(v)
+    }
+}
+
 impl<T> SenderAdapter<T>
 where
     T: Bufferable,
@@ -55,20 +64,8 @@ where
                 writer.send(item).await;
                 Ok(())
             }
-            Self::DiskV2(writer) => {
-                let mut writer = writer.lock().await;
-
-                writer.write_record(item).await.map(|_| ()).map_err(|e| {
-                    // TODO: Could some errors be handled and not be unrecoverable? Right now,
-                    // encoding should theoretically be recoverable -- encoded value was too big, or
-                    // error during encoding -- but the traits don't allow for recovering the
-                    // original event value because we have to consume it to do the encoding... but
-                    // that might not always be the case.
-                    error!("Disk buffer writer has encountered an unrecoverable error.");
-
-                    e.into()
-                })
-            }
+            Self::DiskV2(writer) => write_disk_v2(&mut *writer.lock().await, item).await,
+            Self::ShardedDiskV2(writer) => write_disk_v2(&mut *writer.lock().await, item).await,
         }
     }
 
@@ -79,20 +76,8 @@ where
                 .map(|()| None)
                 .or_else(|e| Ok(Some(e.into_inner()))),
             Self::DiskV1(writer) => Ok(writer.try_send(item)),
-            Self::DiskV2(writer) => {
-                let mut writer = writer.lock().await;
-
-                writer.try_write_record(item).await.map_err(|e| {
-                    // TODO: Could some errors be handled and not be unrecoverable? Right now,
-                    // encoding should theoretically be recoverable -- encoded value was too big, or
-                    // error during encoding -- but the traits don't allow for recovering the
-                    // original event value because we have to consume it to do the encoding... but
-                    // that might not always be the case.
-                    error!("Disk buffer writer has encountered an unrecoverable error.");
-
-                    e.into()
-                })
-            }
+            Self::DiskV2(writer) => try_write_disk_v2(&mut *writer.lock().await, item).await,
+            Self::ShardedDiskV2(writer) => try_write_disk_v2(&mut *writer.lock().await, item).await,
         }
     }
 
@@ -103,14 +88,12 @@ where
                 writer.flush();
                 Ok(())
             }
-            Self::DiskV2(writer) => {
-                let mut writer = writer.lock().await;
-                writer.flush().await.map_err(|e| {
-                    // Errors on the I/O path, which is all that flushing touches, are never recoverable.
-                    error!("Disk buffer writer has encountered an unrecoverable error.");
-
-                    e.into()
-                })
+            Self::DiskV2(writer) => flush_disk_v2(&mut *writer.lock().await).await,
+            Self::ShardedDiskV2(writer) => {
+                for shard in writer.writers() {
+                    flush_disk_v2(&mut *shard.lock().await).await?;
+                }
+                Ok(())
             }
         }
     }
@@ -118,11 +101,50 @@ where
     pub fn capacity(&self) -> Option<usize> {
         match self {
             Self::InMemory(tx) => Some(tx.available_capacity()),
-            Self::DiskV1(_) | Self::DiskV2(_) => None,
+            Self::DiskV1(_) | Self::DiskV2(_) | Self::ShardedDiskV2(_) => None,
         }
     }
 }
 
+async fn write_disk_v2<T: Bufferable>(
+    writer: &mut disk_v2::Writer<T, ProductionFilesystem>,
+    item: T,
+) -> crate::Result<()> {
+    writer.write_record(item).await.map(|_| ()).map_err(|e| {
+        // TODO: Could some errors be handled and not be unrecoverable? Right now,
+        // encoding should theoretically be recoverable -- encoded value was too big, or
+        // error during encoding -- but the traits don't allow for recovering the
+        // original event value because we have to consume it to do the encoding... but
+        // that might not always be the case.
+        error!("Disk buffer writer has encountered an unrecoverable error.");
+
+        e.into()
+    })
+}
+
+async fn try_write_disk_v2<T: Bufferable>(
+    writer: &mut disk_v2::Writer<T, ProductionFilesystem>,
+    item: T,
+) -> crate::Result<Option<T>> {
+    writer.try_write_record(item).await.map_err(|e| {
+        // See `write_disk_v2` about recovering from errors.
+        error!("Disk buffer writer has encountered an unrecoverable error.");
+
+        e.into()
+    })
+}
+
+async fn flush_disk_v2<T: Bufferable>(
+    writer: &mut disk_v2::Writer<T, ProductionFilesystem>,
+) -> crate::Result<()> {
+    writer.flush().await.map_err(|e| {
+        // Errors on the I/O path, which is all that flushing touches, are never recoverable.
+        error!("Disk buffer writer has encountered an unrecoverable error.");
+
+        e.into()
+    })
+}
+
 /// A buffer sender.
 ///
 /// The sender handles sending events into the buffer, as well as the behavior around handling
@@ -9,12 +9,21 @@ use crate::{
     buffer_usage_data::BufferUsageHandle,
     encoding::FixedEncodable,
     topology::channel::{BufferReceiver, BufferSender},
-    Bufferable, EventCount, WhenFull,
+    Bufferable, EventCount, Prioritized, Priority, WhenFull,
 };
 
 #[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
 pub(crate) struct Sample(pub u64);
 
+// The highest bit of a sample marks it as high-priority, which keeps the sample encodable as-is.
+const HIGH_PRIORITY_BIT: u64 = 1 << 63;
+
+impl Sample {
+    pub(crate) fn high_priority(v: u64) -> Self {
+        Self(v | HIGH_PRIORITY_BIT)
+    }
+}
+
 impl From<u64> for Sample {
     fn from(v: u64) -> Self {
         Self(v)
@@ -71,6 +80,16 @@ impl EventCount for Sample {
     }
 }
 
+impl Prioritized for Sample {
+    fn priority(&self) -> Priority {
+        if self.0 & HIGH_PRIORITY_BIT == 0 {
+            Priority::Normal
+        } else {
+            Priority::High
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct BasicError(pub(crate) String);
 
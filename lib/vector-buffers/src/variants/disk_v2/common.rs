@@ -30,6 +30,10 @@ pub const MINIMUM_MAX_RECORD_SIZE: usize = align16(RECORD_HEADER_LEN + 1);
 // have it configured.
 pub const DEFAULT_FLUSH_INTERVAL: Duration = Duration::from_millis(500);
 
+// The reader checks the age of the data file it's reading at most this often, when a maximum data
+// file age is configured, so that checking it doesn't cost a syscall per record.
+pub const DATA_FILE_AGE_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
 // Using 256KB as it aligns nicely with the I/O size exposed by major cloud providers.  This may not
 // be the underlying block size used by the OS, but it still aligns well with what will happen on
 // the "backend" for cloud providers, which is simply a useful default for when we want to look at
@@ -153,6 +157,13 @@ pub struct DiskBufferConfig<FS> {
     /// amount of data written since the last flush would be lost.
     pub(crate) flush_interval: Duration,
 
+    /// Maximum age of a data file before its records are expired.
+    ///
+    /// The reader periodically checks when the data file it's reading was last written to, and
+    /// once that's longer ago than this, discards the records written until then instead of
+    /// reading them.
+    pub(crate) max_data_file_age: Option<Duration>,
+
     /// Filesystem implementation for opening data files.
     ///
     /// We allow parameterizing the filesystem implementation for ease of testing.  The "filesystem"
@@ -173,6 +184,7 @@ where
     pub(crate) max_record_size: Option<usize>,
     pub(crate) write_buffer_size: Option<usize>,
     pub(crate) flush_interval: Option<Duration>,
+    pub(crate) max_data_file_age: Option<Duration>,
     pub(crate) filesystem: FS,
 }
 
@@ -188,6 +200,7 @@ impl DiskBufferConfigBuilder {
             max_record_size: None,
             write_buffer_size: None,
             flush_interval: None,
+            max_data_file_age: None,
             filesystem: ProductionFilesystem,
         }
     }
@@ -268,6 +281,20 @@ where
         self
     }
 
+    /// Sets the maximum age of a data file before its records are expired.
+    ///
+    /// The reader periodically checks when the data file it's reading was last written to, and
+    /// once that's longer ago than this, discards the records written until then instead of
+    /// reading them.  As data files are only appended to, every record discarded this way is at
+    /// least this old.
+    ///
+    /// Defaults to no maximum age.
+    #[allow(dead_code)]
+    pub fn max_data_file_age(mut self, max_age: Duration) -> Self {
+        self.max_data_file_age = Some(max_age);
+        self
+    }
+
     /// Filesystem implementation for opening data files.
     ///
     /// We allow parameterizing the filesystem implementation for ease of testing.  The "filesystem"
@@ -287,6 +314,7 @@ where
             max_record_size: self.max_record_size,
             write_buffer_size: self.write_buffer_size,
             flush_interval: self.flush_interval,
+            max_data_file_age: self.max_data_file_age,
             filesystem,
         }
     }
@@ -427,6 +455,7 @@ where
             max_record_size,
             write_buffer_size,
             flush_interval,
+            max_data_file_age: self.max_data_file_age,
             filesystem,
         })
     }
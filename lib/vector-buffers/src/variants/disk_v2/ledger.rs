@@ -541,6 +541,12 @@ where
         self.usage_handle
             .increment_dropped_event_count_and_byte_size(count, 0, false);
     }
+
+    /// Tracks the statistics of records that were expired rather than read.
+    pub fn track_expired_events(&self, event_count: u64, total_record_size: u64) {
+        self.usage_handle
+            .increment_expired_event_count_and_byte_size(event_count, total_record_size);
+    }
 }
 
 impl<FS> Ledger<FS>
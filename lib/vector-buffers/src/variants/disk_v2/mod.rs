@@ -142,6 +142,7 @@ use std::{
     num::NonZeroU64,
     path::{Path, PathBuf},
     sync::Arc,
+    time::Duration,
 };
 
 use async_trait::async_trait;
@@ -155,6 +156,7 @@ mod ledger;
 mod reader;
 mod record;
 mod ser;
+mod sharded;
 mod v1_migration;
 mod writer;
 
@@ -166,6 +168,7 @@ pub use self::{
     io::{Filesystem, ProductionFilesystem},
     ledger::LedgerLoadCreateError,
     reader::{Reader, ReaderError},
+    sharded::{ShardedDiskV2Buffer, ShardedReader, ShardedWriter},
     writer::{Writer, WriterError},
 };
 use self::{ledger::Ledger, v1_migration::try_disk_v1_migration};
@@ -268,14 +271,21 @@ pub struct DiskV2Buffer {
     id: String,
     data_dir: PathBuf,
     max_size: NonZeroU64,
+    max_age: Option<Duration>,
 }
 
 impl DiskV2Buffer {
-    pub fn new(id: String, data_dir: PathBuf, max_size: NonZeroU64) -> Self {
+    pub fn new(
+        id: String,
+        data_dir: PathBuf,
+        max_size: NonZeroU64,
+        max_age: Option<Duration>,
+    ) -> Self {
         Self {
             id,
             data_dir,
             max_size,
+            max_age,
         }
     }
 }
@@ -303,6 +313,7 @@ where
             &self.data_dir,
             self.id.as_str(),
             self.max_size,
+            self.max_age,
         )
         .await?;
 
@@ -315,6 +326,7 @@ async fn build_disk_v2_buffer<T>(
     data_dir: &Path,
     id: &str,
     max_size: NonZeroU64,
+    max_age: Option<Duration>,
 ) -> Result<
     (
         Writer<T, ProductionFilesystem>,
@@ -328,9 +340,12 @@ where
     usage_handle.set_buffer_limits(Some(max_size.get()), None);
 
     let buffer_path = get_disk_v2_data_dir_path(data_dir, id);
-    let config = DiskBufferConfigBuilder::from_path(buffer_path)
-        .max_buffer_size(max_size.get())
-        .build()?;
+    let mut config_builder =
+        DiskBufferConfigBuilder::from_path(buffer_path).max_buffer_size(max_size.get());
+    if let Some(max_age) = max_age {
+        config_builder = config_builder.max_data_file_age(max_age);
+    }
+    let config = config_builder.build()?;
     Buffer::from_config(config, usage_handle)
         .await
         .map_err(Into::into)
@@ -5,6 +5,7 @@ use std::{
     num::NonZeroU64,
     path::PathBuf,
     sync::Arc,
+    time::Instant,
 };
 
 use crc32fast::Hasher;
@@ -14,7 +15,7 @@ use tokio::io::{AsyncBufReadExt, AsyncRead, BufReader};
 use vector_common::{finalization::BatchNotifier, finalizer::OrderedFinalizer};
 
 use super::{
-    common::create_crc32c_hasher,
+    common::{create_crc32c_hasher, DATA_FILE_AGE_CHECK_INTERVAL},
     ledger::Ledger,
     record::{validate_record_archive, ArchivedRecord, Record, RecordStatus},
     Filesystem,
@@ -222,6 +223,11 @@ where
         }
     }
 
+    /// Gets a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        self.reader.get_ref()
+    }
+
     #[cfg_attr(test, instrument(skip(self), level = "trace"))]
     async fn read_length_delimiter(
         &mut self,
@@ -408,6 +414,8 @@ where
     data_file_record_count: u64,
     data_file_marked_record_count: u64,
     ready_to_read: bool,
+    data_file_age_checked_at: Option<Instant>,
+    data_file_expired_bytes: u64,
     record_acks: OrderedAcknowledgements<u64, u64>,
     data_file_acks: OrderedAcknowledgements<u64, (PathBuf, u64)>,
     finalizer: OrderedFinalizer<u64>,
@@ -434,6 +442,8 @@ where
             data_file_record_count: 0,
             data_file_marked_record_count: 0,
             ready_to_read: false,
+            data_file_age_checked_at: None,
+            data_file_expired_bytes: 0,
             record_acks: OrderedAcknowledgements::from_acked(next_expected_record_id),
             data_file_acks: OrderedAcknowledgements::from_acked(0),
             finalizer,
@@ -445,6 +455,8 @@ where
         self.reader = None;
         self.bytes_read = 0;
         self.data_file_start_record_id = None;
+        self.data_file_age_checked_at = None;
+        self.data_file_expired_bytes = 0;
     }
 
     fn track_read(&mut self, record_id: u64, record_bytes: u64, event_count: NonZeroU64) {
@@ -792,6 +804,53 @@ where
         }
     }
 
+    /// Checks how much of the current data file has exceeded the maximum data file age, if the
+    /// last check was at least `DATA_FILE_AGE_CHECK_INTERVAL` ago.
+    ///
+    /// Data files are only ever appended to, so all of the data within a data file when it was last
+    /// modified was written at that time or before.  Once that's longer ago than the maximum age,
+    /// the records within that data are expired, even if the writer appends newer records to the
+    /// data file afterwards.  Checking periodically, rather than once the writer has moved on from
+    /// the data file, expires records even if no newer records are written.
+    async fn check_data_file_age(&mut self) -> io::Result<()> {
+        let max_age = match self.ledger.config().max_data_file_age {
+            Some(max_age) => max_age,
+            None => return Ok(()),
+        };
+        let check_due = self.data_file_age_checked_at.map_or(true, |checked_at| {
+            checked_at.elapsed() >= DATA_FILE_AGE_CHECK_INTERVAL
+        });
+        if !check_due {
+            return Ok(());
+        }
+        self.data_file_age_checked_at = Some(Instant::now());
+
+        let reader = self
+            .reader
+            .as_ref()
+            .expect("reader should exist after `ensure_ready_for_read`");
+        let metadata = reader.get_ref().metadata().await?;
+        let age = metadata
+            .modified()
+            .and_then(|modified| modified.elapsed().ok());
+        if matches!(age, Some(age) if age > max_age)
+            && metadata.len() > self.data_file_expired_bytes
+        {
+            debug!(
+                data_file_path = self
+                    .ledger
+                    .get_current_reader_data_file_path()
+                    .to_string_lossy()
+                    .as_ref(),
+                expired_bytes = metadata.len(),
+                "Data file exceeds maximum age. Expiring its records."
+            );
+            self.data_file_expired_bytes = metadata.len();
+        }
+
+        Ok(())
+    }
+
     /// Seeks to where this reader previously left off.
     ///
     /// In cases where Vector has restarted, but the reader hasn't yet finished a file, we would
@@ -932,6 +991,18 @@ where
     /// the error.
     #[cfg_attr(test, instrument(skip(self), level = "trace"))]
     pub async fn next(&mut self) -> Result<Option<T>, ReaderError<T>> {
+        loop {
+            // Expired records are dropped rather than returned, which acknowledges them, so that
+            // their data file is deleted as usual once we're through it.
+            match self.next_record().await? {
+                Some((_, true)) => continue,
+                record => return Ok(record.map(|(record, _)| record)),
+            }
+        }
+    }
+
+    /// Reads the next record, along with whether or not it has expired.
+    async fn next_record(&mut self) -> Result<Option<(T, bool)>, ReaderError<T>> {
         let mut force_check_pending_data_files = false;
 
         let token = loop {
@@ -960,11 +1031,6 @@ where
 
             self.ensure_ready_for_read().await.context(IoSnafu)?;
 
-            let reader = self
-                .reader
-                .as_mut()
-                .expect("reader should exist after `ensure_ready_for_read`");
-
             let (reader_file_id, writer_file_id) = self.ledger.get_current_reader_writer_file_id();
 
             // Essentially: is the writer still writing to this data file or not?
@@ -972,6 +1038,12 @@ where
             // A necessary invariant to have to understand if the record reader should actually keep
             // waiting for data, or if a data file had a partial write/missing data and should be skipped.
             let is_finalized = reader_file_id != writer_file_id;
+            self.check_data_file_age().await.context(IoSnafu)?;
+
+            let reader = self
+                .reader
+                .as_mut()
+                .expect("reader should exist after `ensure_ready_for_read`");
 
             // Try reading a record, which if successful, gives us a token to actually read/get a
             // reference to the record.  This is a slightly-tricky song-and-dance due to rustc not
@@ -1088,17 +1160,24 @@ where
         record.add_batch_notifier(batch);
         self.finalizer.add(record_events.get(), receiver);
 
+        // Records are expired once all of their data was written longer ago than the maximum age.
+        let expired = self.ready_to_read && self.bytes_read <= self.data_file_expired_bytes;
         if self.ready_to_read {
-            trace!(
-                record_id,
-                record_events,
-                record_bytes,
-                data_file_id = self.ledger.get_current_reader_file_id(),
-                "Read record."
-            );
+            if expired {
+                self.ledger
+                    .track_expired_events(record_events.get(), record_bytes);
+            } else {
+                trace!(
+                    record_id,
+                    record_events,
+                    record_bytes,
+                    data_file_id = self.ledger.get_current_reader_file_id(),
+                    "Read record."
+                );
+            }
         }
 
-        Ok(Some(record))
+        Ok(Some((record, expired)))
     }
 }
 
@@ -0,0 +1,305 @@
+//! Sharded disk buffers.
+//!
+//! A single disk buffer only has one writer, which every sender has to take turns on. At high event
+//! rates, this lock becomes the bottleneck of the buffer, so a sharded buffer spreads the writes
+//! over several independent disk buffers instead, each with their own writer, reader, and ledger.
+//! Records keep the order they were written in within a shard, but not across shards.
+//!
+//! The first shard lives in the data directory of the unsharded buffer with the same ID, so that
+//! records written before a buffer was sharded are still read. Shards beyond the configured number
+//! which already exist on disk, from a configuration with more shards, are still read from, but no
+//! longer written to. The records left in them count towards the maximum size of the buffer, and
+//! their directories are removed once they're found empty when the buffer is built.
+
+use std::{
+    error::Error,
+    io,
+    num::{NonZeroU64, NonZeroUsize},
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use futures::{
+    stream::{self, BoxStream, SelectAll},
+    StreamExt,
+};
+use snafu::Snafu;
+use tokio::sync::{Mutex, MutexGuard};
+use vector_common::finalization::Finalizable;
+
+use super::{
+    get_disk_v2_data_dir_path, try_disk_v1_migration, Buffer, DiskBufferConfig,
+    DiskBufferConfigBuilder, ProductionFilesystem, Reader, ReaderError, Writer,
+};
+use crate::{
+    buffer_usage_data::BufferUsageHandle,
+    topology::{
+        builder::IntoBuffer,
+        channel::{ReceiverAdapter, SenderAdapter},
+    },
+    Bufferable,
+};
+
+#[derive(Debug, Snafu)]
+pub enum ShardedBufferError {
+    #[snafu(display(
+        "the {} bytes left in shards beyond the configured number leave less than the minimum size of a shard of the {} bytes of `max_size`; raise `max_size` until they're read",
+        leftover_size,
+        max_size
+    ))]
+    LeftoverShardsTooLarge { leftover_size: u64, max_size: u64 },
+}
+
+/// A disk buffer made of several independent disk buffers.
+pub struct ShardedDiskV2Buffer {
+    id: String,
+    data_dir: PathBuf,
+    max_size: NonZeroU64,
+    max_age: Option<Duration>,
+    shards: NonZeroUsize,
+}
+
+impl ShardedDiskV2Buffer {
+    pub fn new(
+        id: String,
+        data_dir: PathBuf,
+        max_size: NonZeroU64,
+        max_age: Option<Duration>,
+        shards: NonZeroUsize,
+    ) -> Self {
+        Self {
+            id,
+            data_dir,
+            max_size,
+            max_age,
+            shards,
+        }
+    }
+
+    /// Returns `true` if shards beyond the first `shards` of the buffer exist on disk, so that the
+    /// buffer has to be read as a sharded buffer even if it's configured with a single shard.
+    pub fn has_leftover_shards(data_dir: &Path, buffer_id: &str, shards: NonZeroUsize) -> bool {
+        leftover_shards(data_dir, buffer_id, shards.get())
+            .map_or(false, |leftover| !leftover.is_empty())
+    }
+}
+
+#[async_trait]
+impl<T> IntoBuffer<T> for ShardedDiskV2Buffer
+where
+    T: Bufferable + Clone + Finalizable,
+{
+    fn provides_instrumentation(&self) -> bool {
+        true
+    }
+
+    async fn into_buffer_parts(
+        self: Box<Self>,
+        usage_handle: BufferUsageHandle,
+    ) -> Result<(SenderAdapter<T>, ReceiverAdapter<T>), Box<dyn Error + Send + Sync>> {
+        try_disk_v1_migration::<T>(self.data_dir.as_path(), self.id.as_str()).await?;
+
+        usage_handle.set_buffer_limits(Some(self.max_size.get()), None);
+
+        let mut writers = Vec::new();
+        let mut readers = SelectAll::new();
+
+        // Shards left over from a configuration with more shards are opened first, so that the
+        // records left in them are counted in the maximum size. They're never written to again, so
+        // they're removed once empty.
+        let mut leftover_size = 0;
+        for shard in leftover_shards(&self.data_dir, &self.id, self.shards.get())? {
+            let buffer_path = get_shard_data_dir_path(&self.data_dir, &self.id, shard);
+            let config = self.shard_config(buffer_path.clone(), self.max_size.get())?;
+            let (writer, reader, ledger) =
+                Buffer::<T>::from_config_inner(config, usage_handle.clone()).await?;
+
+            if ledger.get_total_records() == 0 {
+                drop((writer, reader, ledger));
+                if let Err(error) = tokio::fs::remove_dir_all(&buffer_path).await {
+                    warn!(
+                        message = "Failed to remove empty leftover shard of disk buffer.",
+                        path = ?buffer_path,
+                        %error,
+                    );
+                }
+                continue;
+            }
+
+            leftover_size += ledger.get_total_buffer_size();
+            writers.push(Mutex::new(writer));
+            readers.push(read_shard(reader));
+        }
+
+        // The rest of the maximum size is split evenly between the shards being written to.
+        let shards = self.shards.get();
+        let max_shard_size = self.max_size.get().saturating_sub(leftover_size) / shards as u64;
+        let mut shard_writers = Vec::with_capacity(shards);
+        for shard in 0..shards {
+            let buffer_path = get_shard_data_dir_path(&self.data_dir, &self.id, shard);
+            let config = self
+                .shard_config(buffer_path, max_shard_size)
+                .map_err(|error| {
+                    if leftover_size > 0 {
+                        Box::new(ShardedBufferError::LeftoverShardsTooLarge {
+                            leftover_size,
+                            max_size: self.max_size.get(),
+                        }) as Box<dyn Error + Send + Sync>
+                    } else {
+                        error
+                    }
+                })?;
+            let (writer, reader) = Buffer::from_config(config, usage_handle.clone()).await?;
+
+            shard_writers.push(Mutex::new(writer));
+            readers.push(read_shard(reader));
+        }
+        // The shards being written to go first, as only those are picked by senders.
+        shard_writers.append(&mut writers);
+        let writers = shard_writers;
+
+        let writer = ShardedWriter {
+            writers: writers.into(),
+            shards,
+            next_shard: Arc::new(AtomicUsize::new(0)),
+        };
+        let reader = ShardedReader { readers };
+
+        Ok((writer.into(), reader.into()))
+    }
+}
+
+impl ShardedDiskV2Buffer {
+    fn shard_config(
+        &self,
+        buffer_path: PathBuf,
+        max_shard_size: u64,
+    ) -> Result<DiskBufferConfig<ProductionFilesystem>, Box<dyn Error + Send + Sync>> {
+        let mut config_builder =
+            DiskBufferConfigBuilder::from_path(buffer_path).max_buffer_size(max_shard_size);
+        if let Some(max_age) = self.max_age {
+            config_builder = config_builder.max_data_file_age(max_age);
+        }
+        Ok(config_builder.build()?)
+    }
+}
+
+/// Gets the shards beyond the first `shards` of the buffer which exist on disk, in order.
+fn leftover_shards(data_dir: &Path, buffer_id: &str, shards: usize) -> io::Result<Vec<usize>> {
+    let buffer_path = get_disk_v2_data_dir_path(data_dir, buffer_id);
+    let buffers_dir = buffer_path
+        .parent()
+        .expect("buffer data directory should have a parent");
+    let prefix = format!("{}.shard-", buffer_id);
+    let mut leftover = match std::fs::read_dir(buffers_dir) {
+        Ok(entries) => entries
+            .filter_map(|entry| {
+                let name = entry.ok()?.file_name();
+                let shard = name
+                    .to_str()?
+                    .strip_prefix(&prefix)?
+                    .parse::<usize>()
+                    .ok()?;
+                (shard >= shards).then(|| shard)
+            })
+            .collect::<Vec<_>>(),
+        Err(error) if error.kind() == io::ErrorKind::NotFound => Vec::new(),
+        Err(error) => return Err(error),
+    };
+    leftover.sort_unstable();
+    Ok(leftover)
+}
+
+fn get_shard_data_dir_path(base_dir: &Path, buffer_id: &str, shard: usize) -> PathBuf {
+    if shard == 0 {
+        get_disk_v2_data_dir_path(base_dir, buffer_id)
+    } else {
+        get_disk_v2_data_dir_path(base_dir, &format!("{}.shard-{}", buffer_id, shard))
+    }
+}
+
+fn read_shard<T>(
+    reader: Reader<T, ProductionFilesystem>,
+) -> BoxStream<'static, Result<T, ReaderError<T>>>
+where
+    T: Bufferable,
+{
+    stream::unfold(reader, |mut reader| async move {
+        match reader.next().await {
+            Ok(Some(record)) => Some((Ok(record), reader)),
+            Ok(None) => None,
+            Err(error) => Some((Err(error), reader)),
+        }
+    })
+    .boxed()
+}
+
+/// The writing side of a sharded disk buffer.
+///
+/// Each write goes to the next shard in turn, or to any other shard whose writer is free if that
+/// one is in use by another sender.
+#[derive(Debug)]
+pub struct ShardedWriter<T: Bufferable> {
+    writers: Arc<[Mutex<Writer<T, ProductionFilesystem>>]>,
+    shards: usize,
+    next_shard: Arc<AtomicUsize>,
+}
+
+impl<T: Bufferable> Clone for ShardedWriter<T> {
+    fn clone(&self) -> Self {
+        Self {
+            writers: Arc::clone(&self.writers),
+            shards: self.shards,
+            next_shard: Arc::clone(&self.next_shard),
+        }
+    }
+}
+
+impl<T: Bufferable> ShardedWriter<T> {
+    /// Locks the writer of the shard to write the next record to.
+    pub(crate) async fn lock(&self) -> MutexGuard<'_, Writer<T, ProductionFilesystem>> {
+        let next_shard = self.next_shard.fetch_add(1, Ordering::Relaxed);
+        for offset in 0..self.shards {
+            if let Ok(writer) = self.writers[(next_shard + offset) % self.shards].try_lock() {
+                return writer;
+            }
+        }
+        self.writers[next_shard % self.shards].lock().await
+    }
+
+    /// Gets the writers of all the shards, including those that are only read from.
+    pub(crate) fn writers(&self) -> &[Mutex<Writer<T, ProductionFilesystem>>] {
+        &self.writers
+    }
+}
+
+/// The reading side of a sharded disk buffer, reading from whichever shard has records first.
+pub struct ShardedReader<T: Bufferable> {
+    readers: SelectAll<BoxStream<'static, Result<T, ReaderError<T>>>>,
+}
+
+impl<T: Bufferable> std::fmt::Debug for ShardedReader<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ShardedReader")
+            .field("shards", &self.readers.len())
+            .finish()
+    }
+}
+
+impl<T: Bufferable> ShardedReader<T> {
+    /// Reads the next record from any of the shards.
+    ///
+    /// Returns `None` once the writers have been dropped and all the shards have been read.
+    ///
+    /// # Errors
+    ///
+    /// If an error occurred while reading from a shard, an error variant will be returned.
+    pub async fn next(&mut self) -> Result<Option<T>, ReaderError<T>> {
+        self.readers.next().await.transpose()
+    }
+}
@@ -0,0 +1,112 @@
+use std::time::Duration;
+
+use super::{get_minimum_data_file_size_for_record_payload, read_next, read_next_some};
+use crate::{
+    assert_buffer_is_empty, assert_reader_writer_v2_file_positions,
+    buffer_usage_data::BufferUsageHandle,
+    test::{acknowledge, with_temp_dir, SizedRecord},
+    variants::disk_v2::{Buffer, DiskBufferConfigBuilder},
+};
+
+#[tokio::test]
+async fn reader_expires_records_in_old_data_files() {
+    with_temp_dir(|dir| {
+        let data_dir = dir.to_path_buf();
+
+        async move {
+            // Create our buffer such that every record ends up in its own data file, and data files
+            // expire quickly.
+            let first_record = SizedRecord::new(92);
+            let second_record = SizedRecord::new(96);
+
+            let max_data_file_size = get_minimum_data_file_size_for_record_payload(&second_record);
+            let config = DiskBufferConfigBuilder::from_path(data_dir)
+                .max_data_file_size(max_data_file_size)
+                .max_record_size(usize::try_from(max_data_file_size).unwrap())
+                .max_data_file_age(Duration::from_millis(100))
+                .build()
+                .expect("creating buffer should not fail");
+            let usage_handle = BufferUsageHandle::noop();
+            let (mut writer, mut reader, ledger) =
+                Buffer::from_config_inner(config, usage_handle.clone())
+                    .await
+                    .expect("should not fail to create buffer");
+
+            // Write the first record, and let its data file age past the limit before writing the
+            // second record, which rolls the writer over to the next data file.
+            writer
+                .write_record(first_record)
+                .await
+                .expect("write should not fail");
+            writer.flush().await.expect("flush should not fail");
+
+            tokio::time::sleep(Duration::from_millis(200)).await;
+
+            writer
+                .write_record(second_record.clone())
+                .await
+                .expect("write should not fail");
+            writer.flush().await.expect("flush should not fail");
+            writer.close();
+
+            assert_reader_writer_v2_file_positions!(ledger, 0, 1);
+
+            // The first record is expired, so the first read should give us the second record, and
+            // then there should be nothing left.
+            let second_record_read = read_next_some(&mut reader).await;
+            assert_eq!(second_record_read, second_record);
+            acknowledge(second_record_read).await;
+
+            let final_empty_read = read_next(&mut reader).await;
+            assert_eq!(final_empty_read, None);
+
+            assert_buffer_is_empty!(ledger);
+            assert_reader_writer_v2_file_positions!(ledger, 1, 1);
+            assert_eq!(usage_handle.snapshot().expired_event_count, 1);
+        }
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn reader_expires_records_in_current_data_file() {
+    with_temp_dir(|dir| {
+        let data_dir = dir.to_path_buf();
+
+        async move {
+            // Create our buffer such that data files expire quickly, but both records fit in the
+            // same data file, so the writer never moves on from it.
+            let config = DiskBufferConfigBuilder::from_path(data_dir)
+                .max_data_file_age(Duration::from_millis(100))
+                .build()
+                .expect("creating buffer should not fail");
+            let usage_handle = BufferUsageHandle::noop();
+            let (mut writer, mut reader, ledger) =
+                Buffer::from_config_inner(config, usage_handle.clone())
+                    .await
+                    .expect("should not fail to create buffer");
+
+            for record in [SizedRecord::new(92), SizedRecord::new(96)] {
+                writer
+                    .write_record(record)
+                    .await
+                    .expect("write should not fail");
+            }
+            writer.flush().await.expect("flush should not fail");
+
+            // Let the data file age past the limit without writing anything newer to the buffer.
+            tokio::time::sleep(Duration::from_millis(200)).await;
+            writer.close();
+
+            assert_reader_writer_v2_file_positions!(ledger, 0, 0);
+
+            // Both records are expired, so there should be nothing to read.
+            let final_empty_read = read_next(&mut reader).await;
+            assert_eq!(final_empty_read, None);
+
+            assert_buffer_is_empty!(ledger);
+            assert_eq!(usage_handle.snapshot().expired_event_count, 2);
+        }
+    })
+    .await;
+}
@@ -22,16 +22,21 @@ type FilesystemUnderTest = ProductionFilesystem;
 
 mod acknowledgements;
 mod basic;
+mod expiry;
 mod invariants;
 mod known_errors;
 mod model;
 mod record;
+mod sharded;
 mod size_limits;
 
 #[async_trait]
 impl AsyncFile for DuplexStream {
     async fn metadata(&self) -> io::Result<Metadata> {
-        Ok(Metadata { len: 0 })
+        Ok(Metadata {
+            len: 0,
+            modified: None,
+        })
     }
 
     async fn sync_all(&self) -> io::Result<()> {
@@ -42,7 +47,10 @@ impl AsyncFile for DuplexStream {
 #[async_trait]
 impl AsyncFile for Cursor<Vec<u8>> {
     async fn metadata(&self) -> io::Result<Metadata> {
-        Ok(Metadata { len: 0 })
+        Ok(Metadata {
+            len: 0,
+            modified: None,
+        })
     }
 
     async fn sync_all(&self) -> io::Result<()> {
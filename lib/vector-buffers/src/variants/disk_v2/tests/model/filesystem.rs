@@ -216,7 +216,10 @@ impl AsyncFile for TestFile {
             inner.buf.as_ref().expect("file buf consumed").len()
         };
 
-        Ok(Metadata { len: len as u64 })
+        Ok(Metadata {
+            len: len as u64,
+            modified: None,
+        })
     }
 
     async fn sync_all(&self) -> io::Result<()> {
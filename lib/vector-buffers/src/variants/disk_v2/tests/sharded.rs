@@ -0,0 +1,109 @@
+use std::num::{NonZeroU64, NonZeroUsize};
+
+use vector_common::finalization::Finalizable;
+
+use crate::{
+    buffer_usage_data::BufferUsageHandle,
+    test::{acknowledge, with_temp_dir, SizedRecord},
+    topology::builder::IntoBuffer,
+    variants::disk_v2::{get_disk_v2_data_dir_path, ShardedDiskV2Buffer},
+};
+
+#[tokio::test]
+async fn sharded_read_write_loop() {
+    with_temp_dir(|dir| {
+        let data_dir = dir.to_path_buf();
+
+        async move {
+            // Each shard needs to be allowed at least the minimum size of a buffer.
+            let buffer = ShardedDiskV2Buffer::new(
+                "sharded".to_string(),
+                data_dir.clone(),
+                NonZeroU64::new(4 * 1024 * 1024 * 1024).unwrap(),
+                None,
+                NonZeroUsize::new(4).unwrap(),
+            );
+            let (mut sender, mut receiver) = IntoBuffer::<SizedRecord>::into_buffer_parts(
+                Box::new(buffer),
+                BufferUsageHandle::noop(),
+            )
+            .await
+            .expect("should not fail to create buffer");
+
+            for shard in ["sharded", "sharded.shard-1", "sharded.shard-3"] {
+                assert!(get_disk_v2_data_dir_path(&data_dir, shard).exists());
+            }
+
+            let expected_items = (512..612).collect::<Vec<_>>();
+            for item in &expected_items {
+                sender
+                    .send(SizedRecord::new(*item))
+                    .await
+                    .expect("write should not fail");
+            }
+            sender.flush().await.expect("flush should not fail");
+            drop(sender);
+
+            // Records are only ordered within each shard, so compare them regardless of order.
+            let mut actual_items = Vec::new();
+            while let Some(mut record) = receiver.next().await {
+                acknowledge(record.take_finalizers()).await;
+                actual_items.push(record.0);
+            }
+            actual_items.sort_unstable();
+            assert_eq!(expected_items, actual_items);
+        }
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn leftover_shards_are_read_after_reducing_shards() {
+    with_temp_dir(|dir| {
+        let data_dir = dir.to_path_buf();
+
+        async move {
+            let build = |shards| {
+                IntoBuffer::<SizedRecord>::into_buffer_parts(
+                    Box::new(ShardedDiskV2Buffer::new(
+                        "sharded".to_string(),
+                        data_dir.clone(),
+                        NonZeroU64::new(2 * 1024 * 1024 * 1024).unwrap(),
+                        None,
+                        NonZeroUsize::new(shards).unwrap(),
+                    )),
+                    BufferUsageHandle::noop(),
+                )
+            };
+
+            let (mut sender, receiver) = build(2).await.expect("should not fail to create buffer");
+            let expected_items = (512..532).collect::<Vec<_>>();
+            for item in &expected_items {
+                sender
+                    .send(SizedRecord::new(*item))
+                    .await
+                    .expect("write should not fail");
+            }
+            sender.flush().await.expect("flush should not fail");
+            drop((sender, receiver));
+
+            // A single shard is configured, but the second shard still has records to be read.
+            let one_shard = NonZeroUsize::new(1).unwrap();
+            assert!(ShardedDiskV2Buffer::has_leftover_shards(
+                &data_dir, "sharded", one_shard
+            ));
+
+            let (sender, mut receiver) = build(1).await.expect("should not fail to create buffer");
+            drop(sender);
+
+            let mut actual_items = Vec::new();
+            while let Some(mut record) = receiver.next().await {
+                acknowledge(record.take_finalizers()).await;
+                actual_items.push(record.0);
+            }
+            actual_items.sort_unstable();
+            assert_eq!(expected_items, actual_items);
+        }
+    })
+    .await;
+}
@@ -25,3 +25,25 @@ impl InternalEvent for EventsReceived {
         counter!("events_in_total", self.count as u64);
     }
 }
+
+/// The estimated size of the received events once encoded as JSON.
+///
+/// Unlike the received bytes, which depend on the protocol and encoding of each source, this is
+/// comparable across sources.
+#[derive(Debug)]
+pub struct EventsReceivedJsonSize {
+    pub byte_size: usize,
+}
+
+impl InternalEvent for EventsReceivedJsonSize {
+    fn emit(self) {
+        counter!(
+            "component_received_event_estimated_json_bytes_total",
+            self.byte_size as u64
+        );
+    }
+
+    fn name(&self) -> Option<&'static str> {
+        Some("EventsReceivedJsonSize")
+    }
+}
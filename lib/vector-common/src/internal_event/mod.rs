@@ -9,7 +9,7 @@ pub use metrics::SharedString;
 
 pub use bytes_received::BytesReceived;
 pub use bytes_sent::BytesSent;
-pub use events_received::EventsReceived;
+pub use events_received::{EventsReceived, EventsReceivedJsonSize};
 pub use events_sent::{EventsSent, DEFAULT_OUTPUT};
 pub use prelude::{error_stage, error_type};
 
@@ -1,11 +1,26 @@
+use std::cell::Cell;
+
 use vector_config::{configurable_component, ConfigurableString};
 
+thread_local! {
+    static REDACT: Cell<bool> = Cell::new(false);
+}
+
+/// Runs `f` with sensitive strings serialized as `**REDACTED**` instead of their content, such as
+/// to show a configuration without leaking credentials.
+pub fn with_redaction<T>(f: impl FnOnce() -> T) -> T {
+    let redacting = REDACT.with(|redact| redact.replace(true));
+    let result = f();
+    REDACT.with(|redact| redact.set(redacting));
+    result
+}
+
 /// Wrapper for sensitive strings containing credentials
 #[configurable_component(no_deser, no_ser)]
 #[cfg_attr(
     feature = "serde",
-    derive(::serde::Deserialize, ::serde::Serialize),
-    serde(from = "String", into = "String")
+    derive(::serde::Deserialize),
+    serde(from = "String")
 )]
 #[configurable(metadata(sensitive))]
 #[derive(Clone, Default, PartialEq, Eq)]
@@ -25,6 +40,17 @@ impl From<SensitiveString> for String {
 
 impl ConfigurableString for SensitiveString {}
 
+#[cfg(feature = "serde")]
+impl ::serde::Serialize for SensitiveString {
+    fn serialize<S: ::serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if REDACT.with(Cell::get) {
+            serializer.serialize_str("**REDACTED**")
+        } else {
+            serializer.serialize_str(&self.0)
+        }
+    }
+}
+
 impl std::fmt::Display for SensitiveString {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "**REDACTED**")
@@ -57,6 +83,14 @@ mod tests {
         assert_eq!(result, json_value);
     }
 
+    #[test]
+    fn redacted_serialization() {
+        let value = SensitiveString("hello world".to_string());
+        let result = with_redaction(|| serde_json::to_string(&value).unwrap());
+        assert_eq!(result, "\"**REDACTED**\"");
+        assert_eq!(serde_json::to_string(&value).unwrap(), "\"hello world\"");
+    }
+
     #[test]
     fn hide_content() {
         let value = SensitiveString("hello world".to_string());
@@ -4,6 +4,7 @@ use snafu::Snafu;
 use toml::Value;
 
 use super::{ComponentMarker, GenerateConfig};
+use crate::{Configurable, CustomAttribute};
 
 #[derive(Debug, Snafu, Clone, PartialEq, Eq)]
 pub enum ExampleError {
@@ -18,6 +19,7 @@ pub enum ExampleError {
 pub struct ComponentDescription<T: ComponentMarker + Sized> {
     component_name: &'static str,
     example_value: fn() -> Option<Value>,
+    status: fn() -> Option<String>,
     _component_type: PhantomData<T>,
 }
 
@@ -34,10 +36,21 @@ where
     /// `stdin`, you would say that the component is a "source called `stdin`".
     ///
     /// The type parameter `C` must be the component's configuration type that implements `GenerateConfig`.
-    pub const fn new<C: GenerateConfig>(component_name: &'static str) -> Self {
+    pub const fn new<C: GenerateConfig + Configurable>(component_name: &'static str) -> Self {
         ComponentDescription {
             component_name,
             example_value: || Some(C::generate_config()),
+            status: || {
+                C::metadata()
+                    .custom_attributes()
+                    .iter()
+                    .find_map(|attribute| match attribute {
+                        CustomAttribute::KeyValue { key, value } if key == "status" => {
+                            Some(value.clone())
+                        }
+                        _ => None,
+                    })
+            },
             _component_type: PhantomData,
         }
     }
@@ -58,6 +71,18 @@ where
             .and_then(|t| (t.example_value)().ok_or(ExampleError::MissingExample))
     }
 
+    /// Gets the status of the component with the given component name, such as `alpha`.
+    ///
+    /// The status is set with the `status` metadata of the component's configuration type, i.e.
+    /// `#[configurable(metadata(status = "alpha"))]`. If the component has no status, or if no
+    /// component, identified by `T` and the given name, is registered, `None` is returned.
+    pub fn status(component_name: &str) -> Option<String> {
+        inventory::iter::<ComponentDescription<T>>
+            .into_iter()
+            .find(|t| t.component_name == component_name)
+            .and_then(|t| (t.status)())
+    }
+
     /// Gets a sorted list of all registered components of the given component type.
     pub fn types() -> Vec<&'static str> {
         let mut types = Vec::new();
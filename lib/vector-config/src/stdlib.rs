@@ -6,6 +6,7 @@ use std::{
         NonZeroU8, NonZeroUsize,
     },
     path::PathBuf,
+    sync::Arc,
     time::Duration,
 };
 
@@ -60,6 +61,36 @@ where
     }
 }
 
+// Shared pointers, which are configured the same way as the value they point to.
+impl<T> Configurable for Arc<T>
+where
+    T: Configurable + Serialize,
+{
+    fn referenceable_name() -> Option<&'static str> {
+        T::referenceable_name()
+    }
+
+    fn description() -> Option<&'static str> {
+        T::description()
+    }
+
+    fn is_optional() -> bool {
+        T::is_optional()
+    }
+
+    fn metadata() -> Metadata<Self> {
+        T::metadata().convert()
+    }
+
+    fn validate_metadata(metadata: &Metadata<Self>) -> Result<(), GenerateError> {
+        T::validate_metadata(&metadata.convert())
+    }
+
+    fn generate_schema(gen: &mut SchemaGenerator) -> Result<SchemaObject, GenerateError> {
+        T::generate_schema(gen)
+    }
+}
+
 impl Configurable for bool {
     fn generate_schema(_: &mut SchemaGenerator) -> Result<SchemaObject, GenerateError> {
         Ok(generate_bool_schema())
@@ -1,4 +1,10 @@
-use std::{fs::DirBuilder, path::PathBuf, time::Duration};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs::DirBuilder,
+    num::NonZeroUsize,
+    path::PathBuf,
+    time::Duration,
+};
 
 use snafu::{ResultExt, Snafu};
 use vector_common::TimeZone;
@@ -96,6 +102,217 @@ pub struct GlobalOptions {
     /// a small amount of memory for each metric.
     #[serde(skip_serializing_if = "crate::serde::skip_serializing_if_default")]
     pub expire_metrics_secs: Option<f64>,
+
+    /// The path to a compiled protobuf descriptor set, as produced by `protoc --descriptor_set_out`.
+    ///
+    /// The message types defined in this file can be used by the `parse_proto` and `encode_proto`
+    /// VRL functions, in remap programs as well as in conditions and templates.
+    #[serde(skip_serializing_if = "crate::serde::skip_serializing_if_default")]
+    pub protobuf_descriptor_set: Option<PathBuf>,
+
+    /// The maximum size of an event, in bytes, once encoded as JSON.
+    ///
+    /// Events larger than this are handled by sources as set by their `oversized_events` option
+    /// as soon as they're decoded, so that a single giant event can't exhaust the memory of Vector
+    /// or the limits of sinks downstream. Sources can override this limit with their own
+    /// `max_event_bytes` option.
+    #[serde(skip_serializing_if = "crate::serde::skip_serializing_if_default")]
+    pub max_event_bytes: Option<NonZeroUsize>,
+
+    /// Named rate limits shared by the sinks referencing them.
+    ///
+    /// Sinks reference a pool with their top-level `rate_limit_pool` option, and collectively
+    /// respect its limit, such as the quota of an API key used by several sinks. The requests of the
+    /// sinks waiting on a pool are sent in turns, so that every sink gets a fair share of the limit.
+    /// Only sinks with `request` settings support pools.
+    #[serde(skip_serializing_if = "crate::serde::skip_serializing_if_default")]
+    pub rate_limit_pools: BTreeMap<String, RateLimitPoolConfig>,
+
+    /// Functions defined in VRL, available to every VRL program by their name.
+    ///
+    /// Functions let parsing logic be written once and called from any `remap` transform or VRL
+    /// condition, such as `parse_app_log!(.message)`. Functions can use their parameters and the
+    /// functions built into VRL, but not other functions defined here, nor the event they're
+    /// called on.
+    #[serde(skip_serializing_if = "crate::serde::skip_serializing_if_default")]
+    pub vrl_functions: BTreeMap<String, VrlFunctionConfig>,
+
+    /// Features that are disabled unless explicitly enabled.
+    ///
+    /// Feature flags can also be enabled with the `--feature-flags` command line option. Vector
+    /// refuses to start with a configuration relying on a disabled feature.
+    #[serde(skip_serializing_if = "crate::serde::skip_serializing_if_default")]
+    pub feature_flags: BTreeSet<FeatureFlag>,
+
+    #[configurable(derived)]
+    #[serde(skip_serializing_if = "crate::serde::skip_serializing_if_default")]
+    pub host_metadata: Option<HostMetadataConfig>,
+}
+
+/// Metadata of the host Vector runs on, fetched from the metadata services of cloud providers.
+///
+/// The metadata is fetched once at startup, then refreshed periodically. It can be used in
+/// templates as `{{ host_metadata.<key> }}`, with the `get_host_metadata` VRL function, and added
+/// to the events of sources with their `host_metadata_key` option.
+#[configurable_component]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct HostMetadataConfig {
+    /// The cloud providers whose metadata services are queried.
+    ///
+    /// The metadata of every provider answering is collected, so only the provider Vector runs on
+    /// needs to be listed to avoid waiting on the others at startup.
+    #[serde(default = "default_host_metadata_providers")]
+    pub providers: Vec<HostMetadataProvider>,
+
+    /// Whether to fetch the tags of the instance, as `tags.<name>` keys.
+    ///
+    /// On EC2, access to tags in the instance metadata must be allowed on the instance, otherwise
+    /// only the other metadata is kept. Labels of GCE instances aren't exposed by its metadata
+    /// service.
+    #[serde(default)]
+    pub tags: bool,
+
+    /// How often, in seconds, to refresh the metadata.
+    ///
+    /// Set to `0` to only fetch the metadata at startup and when the configuration is reloaded.
+    #[serde(default = "default_host_metadata_refresh_interval_secs")]
+    pub refresh_interval_secs: u64,
+
+    /// The timeout, in seconds, of the requests to the metadata services.
+    #[serde(default = "default_host_metadata_request_timeout_secs")]
+    pub request_timeout_secs: u64,
+}
+
+/// A cloud provider whose metadata service describes the instance Vector runs on.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum HostMetadataProvider {
+    /// Amazon EC2, through the instance metadata service.
+    Ec2,
+
+    /// Google Compute Engine, through the metadata server.
+    Gce,
+
+    /// Azure virtual machines, through the instance metadata service.
+    Azure,
+}
+
+impl HostMetadataProvider {
+    /// Gets the name of the provider, as used in the configuration.
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Ec2 => "ec2",
+            Self::Gce => "gce",
+            Self::Azure => "azure",
+        }
+    }
+}
+
+fn default_host_metadata_providers() -> Vec<HostMetadataProvider> {
+    vec![
+        HostMetadataProvider::Ec2,
+        HostMetadataProvider::Gce,
+        HostMetadataProvider::Azure,
+    ]
+}
+
+const fn default_host_metadata_refresh_interval_secs() -> u64 {
+    600
+}
+
+const fn default_host_metadata_request_timeout_secs() -> u64 {
+    1
+}
+
+/// A feature that is disabled unless explicitly enabled.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[serde(rename_all = "snake_case")]
+pub enum FeatureFlag {
+    /// Allows components whose status is `alpha` to be used.
+    ///
+    /// Alpha components are experimental: their configuration and behavior may change in
+    /// backwards incompatible ways, and their internal metrics are labeled with
+    /// `component_status = "alpha"`.
+    AlphaComponents,
+
+    /// Audits the internal events emitted by each component.
+    ///
+    /// The internal metrics of each component are periodically checked for the events that the
+    /// component specification requires for the events flowing through it, such as a source
+    /// sending events without emitting `ComponentEventsReceived`. Violations are logged and
+    /// counted by the `internal_events_audit_violations_total` metric.
+    InternalEventsAudit,
+}
+
+impl FeatureFlag {
+    /// Gets the name of the feature flag, as used in the configuration.
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::AlphaComponents => "alpha_components",
+            Self::InternalEventsAudit => "internal_events_audit",
+        }
+    }
+}
+
+impl std::fmt::Display for FeatureFlag {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl std::str::FromStr for FeatureFlag {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "alpha_components" => Ok(Self::AlphaComponents),
+            "internal_events_audit" => Ok(Self::InternalEventsAudit),
+            _ => Err(format!("unknown feature flag {:?}", s)),
+        }
+    }
+}
+
+/// A rate limit shared by several sinks.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct RateLimitPoolConfig {
+    /// The maximum number of requests the sinks of the pool are allowed to make within the
+    /// `rate_limit_duration_secs` time window.
+    pub rate_limit_num: u64,
+
+    /// The time window, in seconds, used for the `rate_limit_num` option.
+    #[serde(default = "default_rate_limit_duration_secs")]
+    pub rate_limit_duration_secs: u64,
+}
+
+const fn default_rate_limit_duration_secs() -> u64 {
+    1
+}
+
+/// A function defined in VRL.
+#[configurable_component]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct VrlFunctionConfig {
+    /// The names of the parameters of the function, which are variables of its source.
+    ///
+    /// Every parameter is required, and can be passed by position or by name.
+    #[serde(default)]
+    pub parameters: Vec<String>,
+
+    /// The VRL source of the function, whose result is the result of the function.
+    ///
+    /// Either this or `file` must be set.
+    pub source: Option<String>,
+
+    /// The path to a file containing the VRL source of the function.
+    ///
+    /// Either this or `source` must be set.
+    pub file: Option<PathBuf>,
 }
 
 impl GlobalOptions {
@@ -192,6 +409,48 @@ impl GlobalOptions {
             errors.push("conflicting values for 'timezone' found".to_owned());
         }
 
+        if conflicts(&self.protobuf_descriptor_set, &with.protobuf_descriptor_set) {
+            errors.push("conflicting values for 'protobuf_descriptor_set' found".to_owned());
+        }
+
+        if conflicts(&self.max_event_bytes, &with.max_event_bytes) {
+            errors.push("conflicting values for 'max_event_bytes' found".to_owned());
+        }
+
+        if conflicts(&self.host_metadata, &with.host_metadata) {
+            errors.push("conflicting values for 'host_metadata' found".to_owned());
+        }
+
+        let mut rate_limit_pools = self.rate_limit_pools.clone();
+        for (name, pool) in with.rate_limit_pools {
+            match rate_limit_pools.get(&name) {
+                Some(existing) if *existing != pool => {
+                    errors.push(format!(
+                        "conflicting values for 'rate_limit_pools.{}' found",
+                        name
+                    ));
+                }
+                _ => {
+                    rate_limit_pools.insert(name, pool);
+                }
+            }
+        }
+
+        let mut vrl_functions = self.vrl_functions.clone();
+        for (name, function) in with.vrl_functions {
+            match vrl_functions.get(&name) {
+                Some(existing) if *existing != function => {
+                    errors.push(format!(
+                        "conflicting values for 'vrl_functions.{}' found",
+                        name
+                    ));
+                }
+                _ => {
+                    vrl_functions.insert(name, function);
+                }
+            }
+        }
+
         if errors.is_empty() {
             Ok(Self {
                 data_dir,
@@ -201,6 +460,19 @@ impl GlobalOptions {
                 proxy: self.proxy.merge(&with.proxy),
                 expire_metrics: self.expire_metrics.or(with.expire_metrics),
                 expire_metrics_secs: self.expire_metrics_secs.or(with.expire_metrics_secs),
+                protobuf_descriptor_set: self
+                    .protobuf_descriptor_set
+                    .clone()
+                    .or(with.protobuf_descriptor_set),
+                max_event_bytes: self.max_event_bytes.or(with.max_event_bytes),
+                rate_limit_pools,
+                vrl_functions,
+                feature_flags: self
+                    .feature_flags
+                    .union(&with.feature_flags)
+                    .copied()
+                    .collect(),
+                host_metadata: self.host_metadata.clone().or(with.host_metadata),
             })
         } else {
             Err(errors)
@@ -7,7 +7,10 @@ mod log_schema;
 pub mod proxy;
 
 use crate::event::LogEvent;
-pub use global_options::GlobalOptions;
+pub use global_options::{
+    FeatureFlag, GlobalOptions, HostMetadataConfig, HostMetadataProvider, RateLimitPoolConfig,
+    VrlFunctionConfig,
+};
 pub use log_schema::{init_log_schema, log_schema, LogSchema};
 use lookup::lookup_v2::ValuePath;
 use lookup::{path, PathPrefix};
@@ -7,7 +7,7 @@ use std::{iter, slice, vec};
 use futures::{stream, Stream};
 #[cfg(test)]
 use quickcheck::{Arbitrary, Gen};
-use vector_buffers::EventCount;
+use vector_buffers::{EventCount, Prioritized, Priority};
 use vector_common::finalization::{AddBatchNotifier, BatchNotifier, EventFinalizers, Finalizable};
 
 use super::{
@@ -240,6 +240,16 @@ impl EventCount for EventArray {
     }
 }
 
+impl Prioritized for EventArray {
+    /// Arrays are prioritized by their first event, as events are only coalesced into arrays with
+    /// events of the same priority.
+    fn priority(&self) -> Priority {
+        self.iter_events()
+            .next()
+            .map_or(Priority::Normal, |event| event.metadata().priority())
+    }
+}
+
 impl EventContainer for EventArray {
     type IntoIter = EventArrayIntoIter;
 
@@ -409,20 +419,19 @@ impl EventArrayBuffer {
 
     #[must_use]
     fn push(&mut self, event: Event) -> Option<EventArray> {
+        let coalesces = self.buffer.as_ref().map_or(false, |array| {
+            array.len() < self.max_size && array.priority() == event.metadata().priority()
+        });
         match (event, &mut self.buffer) {
-            (Event::Log(event), Some(EventArray::Logs(array))) if array.len() < self.max_size => {
+            (Event::Log(event), Some(EventArray::Logs(array))) if coalesces => {
                 array.push(event);
                 None
             }
-            (Event::Metric(event), Some(EventArray::Metrics(array)))
-                if array.len() < self.max_size =>
-            {
+            (Event::Metric(event), Some(EventArray::Metrics(array))) if coalesces => {
                 array.push(event);
                 None
             }
-            (Event::Trace(event), Some(EventArray::Traces(array)))
-                if array.len() < self.max_size =>
-            {
+            (Event::Trace(event), Some(EventArray::Traces(array))) if coalesces => {
                 array.push(event);
                 None
             }
@@ -0,0 +1,216 @@
+use std::io;
+
+use chrono::{DateTime, Timelike, Utc};
+use serde::Serialize;
+
+use super::{EventArray, EventRef, LogEvent, Metric, TraceEvent, Value};
+
+const QUOTES_SIZE: usize = 2;
+const BRACES_SIZE: usize = 2;
+const BRACKETS_SIZE: usize = 2;
+const COLON_SIZE: usize = 1;
+const COMMA_SIZE: usize = 1;
+const NULL_SIZE: usize = 4;
+const TRUE_SIZE: usize = 4;
+const FALSE_SIZE: usize = 5;
+/// The size of an RFC 3339 timestamp without fractional seconds, such as `2022-10-01T12:00:00Z`.
+const TIMESTAMP_SIZE: usize = 20;
+
+/// The estimated size of an event once encoded as JSON.
+///
+/// The estimate doesn't account for escaped characters in strings, so that it can be computed
+/// without serializing the event.
+pub trait EstimatedJsonEncodedSizeOf {
+    fn estimated_json_encoded_size_of(&self) -> usize;
+}
+
+impl EstimatedJsonEncodedSizeOf for Value {
+    fn estimated_json_encoded_size_of(&self) -> usize {
+        match self {
+            Value::Bytes(bytes) => bytes.len() + QUOTES_SIZE,
+            Value::Regex(regex) => regex.as_str().len() + QUOTES_SIZE,
+            Value::Integer(integer) => integer_size(*integer),
+            Value::Float(float) => serialized_size(&float.into_inner()),
+            Value::Boolean(true) => TRUE_SIZE,
+            Value::Boolean(false) => FALSE_SIZE,
+            Value::Timestamp(timestamp) => timestamp_size(timestamp),
+            Value::Object(map) => {
+                BRACES_SIZE
+                    + map
+                        .iter()
+                        .map(|(key, value)| {
+                            key.len()
+                                + QUOTES_SIZE
+                                + COLON_SIZE
+                                + value.estimated_json_encoded_size_of()
+                        })
+                        .sum::<usize>()
+                    + separators_size(map.len())
+            }
+            Value::Array(values) => {
+                BRACKETS_SIZE
+                    + values
+                        .iter()
+                        .map(EstimatedJsonEncodedSizeOf::estimated_json_encoded_size_of)
+                        .sum::<usize>()
+                    + separators_size(values.len())
+            }
+            Value::Null => NULL_SIZE,
+        }
+    }
+}
+
+impl EstimatedJsonEncodedSizeOf for LogEvent {
+    fn estimated_json_encoded_size_of(&self) -> usize {
+        self.value().estimated_json_encoded_size_of()
+    }
+}
+
+impl EstimatedJsonEncodedSizeOf for TraceEvent {
+    fn estimated_json_encoded_size_of(&self) -> usize {
+        self.value().estimated_json_encoded_size_of()
+    }
+}
+
+impl EstimatedJsonEncodedSizeOf for Metric {
+    fn estimated_json_encoded_size_of(&self) -> usize {
+        // Metrics are small and structured, so they are counted while serializing them.
+        serialized_size(self)
+    }
+}
+
+impl<'a> EstimatedJsonEncodedSizeOf for EventRef<'a> {
+    fn estimated_json_encoded_size_of(&self) -> usize {
+        match self {
+            EventRef::Log(log) => log.estimated_json_encoded_size_of(),
+            EventRef::Metric(metric) => metric.estimated_json_encoded_size_of(),
+            EventRef::Trace(trace) => trace.estimated_json_encoded_size_of(),
+        }
+    }
+}
+
+impl EstimatedJsonEncodedSizeOf for EventArray {
+    fn estimated_json_encoded_size_of(&self) -> usize {
+        self.iter_events()
+            .map(|event| event.estimated_json_encoded_size_of())
+            .sum()
+    }
+}
+
+const fn separators_size(len: usize) -> usize {
+    len.saturating_sub(1) * COMMA_SIZE
+}
+
+fn integer_size(integer: i64) -> usize {
+    let sign = usize::from(integer < 0);
+    let mut digits = 1;
+    let mut rest = integer.unsigned_abs() / 10;
+    while rest > 0 {
+        digits += 1;
+        rest /= 10;
+    }
+    sign + digits
+}
+
+/// Timestamps are encoded in RFC 3339 format, with as many fractional digits as needed out of
+/// milliseconds, microseconds and nanoseconds.
+fn timestamp_size(timestamp: &DateTime<Utc>) -> usize {
+    let nanos = timestamp.nanosecond() % 1_000_000_000;
+    let fraction = if nanos == 0 {
+        0
+    } else if nanos % 1_000_000 == 0 {
+        4
+    } else if nanos % 1_000 == 0 {
+        7
+    } else {
+        10
+    };
+    TIMESTAMP_SIZE + fraction + QUOTES_SIZE
+}
+
+/// Counts the bytes written to it.
+#[derive(Default)]
+struct ByteCounter(usize);
+
+impl io::Write for ByteCounter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0 += buf.len();
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+fn serialized_size<T: Serialize>(value: &T) -> usize {
+    let mut counter = ByteCounter::default();
+    // Writing to the counter can't fail, and the values serialized here always serialize.
+    let _ = serde_json::to_writer(&mut counter, value);
+    counter.0
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use chrono::TimeZone;
+
+    use super::*;
+    use crate::event::{MetricKind, MetricValue};
+
+    fn assert_exact(value: Value) {
+        assert_eq!(
+            value.estimated_json_encoded_size_of(),
+            serde_json::to_vec(&value).unwrap().len(),
+            "{:?}",
+            value
+        );
+    }
+
+    #[test]
+    fn matches_encoded_size_of_values() {
+        assert_exact(Value::from("foo"));
+        assert_exact(Value::from(0));
+        assert_exact(Value::from(-1234));
+        assert_exact(Value::from(i64::MIN));
+        assert_exact(Value::from(i64::MAX));
+        assert_exact(Value::from(1.5));
+        assert_exact(Value::from(true));
+        assert_exact(Value::from(false));
+        assert_exact(Value::Null);
+        assert_exact(Value::Array(vec![]));
+        assert_exact(Value::Array(vec![Value::from(1), Value::from("a")]));
+        assert_exact(Value::Object(BTreeMap::new()));
+        assert_exact(Value::Object(BTreeMap::from([
+            ("a".to_owned(), Value::from(1)),
+            (
+                "nested".to_owned(),
+                Value::Object(BTreeMap::from([("b".to_owned(), Value::Null)])),
+            ),
+        ])));
+    }
+
+    #[test]
+    fn matches_encoded_size_of_timestamps() {
+        let timestamp = Utc.ymd(2022, 10, 1).and_hms(12, 0, 0);
+        assert_exact(Value::from(timestamp));
+        assert_exact(Value::from(timestamp.with_nanosecond(123_000_000).unwrap()));
+        assert_exact(Value::from(timestamp.with_nanosecond(123_456_000).unwrap()));
+        assert_exact(Value::from(timestamp.with_nanosecond(123_456_789).unwrap()));
+    }
+
+    #[test]
+    fn matches_encoded_size_of_metrics() {
+        let metric = Metric::new(
+            "requests",
+            MetricKind::Incremental,
+            MetricValue::Counter { value: 1.0 },
+        );
+
+        assert_eq!(
+            metric.estimated_json_encoded_size_of(),
+            serde_json::to_vec(&metric).unwrap().len()
+        );
+    }
+}
@@ -301,6 +301,24 @@ impl LogEvent {
         }
     }
 
+    /// Move the fields carrying the given semantic meanings to their target paths.
+    ///
+    /// All values are removed before any are re-inserted, so meanings can swap fields without
+    /// clobbering each other. Meanings not defined in the event's schema definition are ignored.
+    pub fn map_meanings<'a>(&mut self, targets: impl IntoIterator<Item = (&'a str, &'a str)>) {
+        let moved = targets
+            .into_iter()
+            .filter_map(|(meaning, target)| {
+                let key = self.find_key_by_meaning(meaning)?;
+                self.remove(key.as_str()).map(|value| (target, value))
+            })
+            .collect::<Vec<_>>();
+
+        for (target, value) in moved {
+            self.insert(target, value);
+        }
+    }
+
     pub fn remove<'a>(&mut self, path: impl TargetPath<'a>) -> Option<Value> {
         self.remove_prune(path, false)
     }
@@ -658,6 +676,36 @@ mod test {
         assert_eq!(expected_value, actual_value);
     }
 
+    #[test]
+    fn map_meanings() {
+        let definition = crate::schema::Definition::empty_legacy_namespace()
+            .with_field("msg", value::Kind::bytes(), Some("message"))
+            .with_field("hostname", value::Kind::bytes(), Some("host"));
+        let metadata = EventMetadata::default().with_schema_definition(&Arc::new(definition));
+
+        let mut log = LogEvent::from_parts(
+            value!({
+                msg: "hello",
+                hostname: "message"
+            }),
+            metadata,
+        );
+        log.map_meanings([
+            ("message", "hostname"),
+            ("host", "msg"),
+            ("severity", "level"),
+        ]);
+        let (actual_value, _) = log.into_parts();
+
+        assert_eq!(
+            actual_value,
+            value!({
+                hostname: "hello",
+                msg: "message"
+            })
+        );
+    }
+
     #[test]
     fn insert() {
         let mut log = LogEvent::default();
@@ -68,23 +68,24 @@ impl<'a> ToLua<'a> for Metric {
     #![allow(clippy::wrong_self_convention)] // this trait is defined by mlua
     fn to_lua(self, lua: &'a Lua) -> LuaResult<LuaValue> {
         let tbl = lua.create_table()?;
+        let (series, data, _) = self.into_parts();
 
-        tbl.raw_set("name", self.name())?;
-        if let Some(namespace) = self.namespace() {
+        tbl.raw_set("name", series.name.name)?;
+        if let Some(namespace) = series.name.namespace {
             tbl.raw_set("namespace", namespace)?;
         }
-        if let Some(ts) = self.data.time.timestamp {
+        if let Some(ts) = data.time.timestamp {
             tbl.raw_set("timestamp", timestamp_to_table(lua, ts)?)?;
         }
-        if let Some(i) = self.data.time.interval_ms {
+        if let Some(i) = data.time.interval_ms {
             tbl.raw_set("interval_ms", i.get())?;
         }
-        if let Some(tags) = self.series.tags {
+        if let Some(tags) = series.tags {
             tbl.raw_set("tags", tags)?;
         }
-        tbl.raw_set("kind", self.data.kind)?;
+        tbl.raw_set("kind", data.kind)?;
 
-        match self.data.value {
+        match data.value {
             MetricValue::Counter { value } => {
                 let counter = lua.create_table()?;
                 counter.raw_set("value", value)?;
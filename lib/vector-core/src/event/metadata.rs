@@ -1,30 +1,40 @@
 #![deny(missing_docs)]
 
 use std::collections::BTreeMap;
-use std::sync::Arc;
+use std::sync::{
+    atomic::{AtomicU64, Ordering},
+    Arc,
+};
+use std::time::{Duration, Instant};
 
 use serde::{Deserialize, Serialize};
 use value::{Kind, Secrets, Value};
+use vector_buffers::Priority;
 use vector_common::EventDataEq;
 
-use super::{BatchNotifier, EventFinalizer, EventFinalizers, EventStatus};
+use super::{BatchNotifier, EventFinalizer, EventFinalizers, EventStatus, ResponseHandle};
 use crate::config::LogNamespace;
 use crate::{schema, ByteSizeOf};
 
 const DATADOG_API_KEY: &str = "datadog_api_key";
 const SPLUNK_HEC_TOKEN: &str = "splunk_hec_token";
+/// The metadata field holding the buffer priority of an event.
+pub const PRIORITY_FIELD: &str = "priority";
 
 /// The top-level metadata structure contained by both `struct Metric`
 /// and `struct LogEvent` types.
+///
+/// The metadata value and secrets are shared between clones of an event until one of them is
+/// modified, so that fanning an event out to multiple components doesn't copy them.
 #[derive(Clone, Debug, Deserialize, PartialEq, PartialOrd, Serialize)]
 pub struct EventMetadata {
     /// Arbitrary data stored with an event
     #[serde(default = "default_metadata_value", skip)]
-    value: Value,
+    value: Arc<Value>,
 
     /// Storage for secrets
     #[serde(default, skip)]
-    secrets: Secrets,
+    secrets: Arc<Secrets>,
 
     #[serde(default, skip)]
     finalizers: EventFinalizers,
@@ -35,10 +45,50 @@ pub struct EventMetadata {
     /// TODO(Jean): must not skip serialization to track schemas across restarts.
     #[serde(default = "default_schema_definition", skip)]
     schema_definition: Arc<schema::Definition>,
+
+    /// The trace of the event, if it is followed through the topology.
+    #[serde(default, skip)]
+    trace: Option<EventTrace>,
+
+    /// The handle to the response to the request the event was received in, if its source
+    /// replies with the response set downstream.
+    #[serde(default, skip)]
+    response_handle: Option<ResponseHandle>,
 }
 
-fn default_metadata_value() -> Value {
-    Value::Object(BTreeMap::new())
+/// Marks an event followed through the topology, with each component it passes through logging it.
+///
+/// Traces are kept in memory only, and so are lost when events are written to disk buffers.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, PartialOrd)]
+pub struct EventTrace {
+    id: u64,
+    started: Instant,
+}
+
+impl EventTrace {
+    /// Starts a new trace, with an identifier unique to this process.
+    pub fn start() -> Self {
+        static NEXT_ID: AtomicU64 = AtomicU64::new(1);
+
+        Self {
+            id: NEXT_ID.fetch_add(1, Ordering::Relaxed),
+            started: Instant::now(),
+        }
+    }
+
+    /// Gets the identifier of the trace, shared by all the events derived from the traced one.
+    pub const fn id(&self) -> u64 {
+        self.id
+    }
+
+    /// Gets the time elapsed since the trace was started.
+    pub fn elapsed(&self) -> Duration {
+        self.started.elapsed()
+    }
+}
+
+fn default_metadata_value() -> Arc<Value> {
+    Arc::new(Value::Object(BTreeMap::new()))
 }
 
 /// Secret access functions
@@ -50,7 +100,7 @@ impl EventMetadata {
 
     /// Returns a mutable reference to the metadata value
     pub fn value_mut(&mut self) -> &mut Value {
-        &mut self.value
+        Arc::make_mut(&mut self.value)
     }
 
     /// Returns a reference to the secrets
@@ -60,7 +110,7 @@ impl EventMetadata {
 
     /// Returns a mutable reference to the secrets
     pub fn secrets_mut(&mut self) -> &mut Secrets {
-        &mut self.secrets
+        Arc::make_mut(&mut self.secrets)
     }
 
     /// Return the datadog API key, if it exists
@@ -70,7 +120,7 @@ impl EventMetadata {
 
     /// Set the datadog API key to passed value
     pub fn set_datadog_api_key(&mut self, secret: Arc<str>) {
-        self.secrets.insert(DATADOG_API_KEY, secret);
+        self.secrets_mut().insert(DATADOG_API_KEY, secret);
     }
 
     /// Return the splunk hec token, if it exists
@@ -80,17 +130,19 @@ impl EventMetadata {
 
     /// Set the splunk hec token to passed value
     pub fn set_splunk_hec_token(&mut self, secret: Arc<str>) {
-        self.secrets.insert(SPLUNK_HEC_TOKEN, secret);
+        self.secrets_mut().insert(SPLUNK_HEC_TOKEN, secret);
     }
 }
 
 impl Default for EventMetadata {
     fn default() -> Self {
         Self {
-            value: Value::Object(BTreeMap::new()),
-            secrets: Secrets::new(),
+            value: default_metadata_value(),
+            secrets: Arc::new(Secrets::new()),
             finalizers: Default::default(),
             schema_definition: default_schema_definition(),
+            trace: None,
+            response_handle: None,
         }
     }
 }
@@ -151,9 +203,16 @@ impl EventMetadata {
     /// Merge the other `EventMetadata` into this.
     /// If a Datadog API key is not set in `self`, the one from `other` will be used.
     /// If a Splunk HEC token is not set in `self`, the one from `other` will be used.
+    /// If `self` isn't traced, the trace of `other` will be used.
     pub fn merge(&mut self, other: Self) {
         self.finalizers.merge(other.finalizers);
-        self.secrets.merge(other.secrets);
+        self.trace = self.trace.or(other.trace);
+        self.response_handle = self.response_handle.take().or(other.response_handle);
+        if !other.secrets.is_empty() {
+            let secrets =
+                Arc::try_unwrap(other.secrets).unwrap_or_else(|secrets| (*secrets).clone());
+            self.secrets_mut().merge(secrets);
+        }
     }
 
     /// Update the finalizer(s) status.
@@ -186,6 +245,23 @@ impl EventMetadata {
         self.finalizers.merge(finalizers);
     }
 
+    /// Get the buffer priority of the event.
+    ///
+    /// Events are high-priority when the `priority` metadata field is set to `"high"`, such as
+    /// with `%priority = "high"` in a `remap` transform.
+    pub fn priority(&self) -> Priority {
+        // Looked up directly in the fields, as this is checked for every event coalesced into an
+        // array.
+        match self
+            .value
+            .as_object()
+            .and_then(|fields| fields.get(PRIORITY_FIELD))
+        {
+            Some(Value::Bytes(priority)) if priority.as_ref() == b"high" => Priority::High,
+            _ => Priority::Normal,
+        }
+    }
+
     /// Get the schema definition.
     pub fn schema_definition(&self) -> &schema::Definition {
         self.schema_definition.as_ref()
@@ -195,6 +271,33 @@ impl EventMetadata {
     pub fn set_schema_definition(&mut self, definition: &Arc<schema::Definition>) {
         self.schema_definition = Arc::clone(definition);
     }
+
+    /// Get the trace of the event, if it is followed through the topology.
+    pub fn trace(&self) -> Option<&EventTrace> {
+        self.trace.as_ref()
+    }
+
+    /// Set the trace of the event, to follow it through the topology.
+    pub fn set_trace(&mut self, trace: EventTrace) {
+        self.trace = Some(trace);
+    }
+
+    /// Get the handle to the response to the request the event was received in, if its source
+    /// replies with the response set downstream.
+    pub fn response_handle(&self) -> Option<&ResponseHandle> {
+        self.response_handle.as_ref()
+    }
+
+    /// Set the handle to the response to the request the event was received in.
+    pub fn set_response_handle(&mut self, handle: ResponseHandle) {
+        self.response_handle = Some(handle);
+    }
+
+    /// Take the handle to the response to the request the event was received in, so the source
+    /// no longer waits for the event before replying.
+    pub fn take_response_handle(&mut self) -> Option<ResponseHandle> {
+        self.response_handle.take()
+    }
 }
 
 impl EventDataEq for EventMetadata {
@@ -242,4 +345,16 @@ mod test {
         assert_eq!(metadata.datadog_api_key().unwrap().as_ref(), SECRET);
         assert_eq!(metadata.splunk_hec_token().unwrap().as_ref(), SECRET2);
     }
+
+    #[test]
+    fn priority() {
+        let mut metadata = EventMetadata::default();
+        assert_eq!(metadata.priority(), Priority::Normal);
+
+        metadata.value_mut().insert(PRIORITY_FIELD, "low");
+        assert_eq!(metadata.priority(), Priority::Normal);
+
+        metadata.value_mut().insert(PRIORITY_FIELD, "high");
+        assert_eq!(metadata.priority(), Priority::High);
+    }
 }
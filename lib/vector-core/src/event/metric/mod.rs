@@ -5,6 +5,7 @@ use std::{
     convert::AsRef,
     fmt::{self, Display, Formatter},
     num::NonZeroU32,
+    sync::Arc,
 };
 
 use chrono::{DateTime, Utc};
@@ -33,14 +34,17 @@ pub use self::value::*;
 pub type MetricTags = BTreeMap<String, String>;
 
 /// A metric.
+///
+/// The series and data of a metric are shared between its clones until one of them is modified,
+/// so that fanning a metric out to multiple components doesn't copy them.
 #[configurable_component]
 #[derive(Clone, Debug, PartialEq, PartialOrd)]
 pub struct Metric {
     #[serde(flatten)]
-    pub(super) series: MetricSeries,
+    pub(super) series: Arc<MetricSeries>,
 
     #[serde(flatten)]
-    pub(super) data: MetricData,
+    pub(super) data: Arc<MetricData>,
 
     /// Internal event metadata.
     #[serde(skip, default = "EventMetadata::default")]
@@ -61,21 +65,21 @@ impl Metric {
         metadata: EventMetadata,
     ) -> Self {
         Self {
-            series: MetricSeries {
+            series: Arc::new(MetricSeries {
                 name: MetricName {
                     name: name.into(),
                     namespace: None,
                 },
                 tags: None,
-            },
-            data: MetricData {
+            }),
+            data: Arc::new(MetricData {
                 time: MetricTime {
                     timestamp: None,
                     interval_ms: None,
                 },
                 kind,
                 value,
-            },
+            }),
             metadata,
         }
     }
@@ -84,7 +88,7 @@ impl Metric {
     #[inline]
     #[must_use]
     pub fn with_name(mut self, name: impl Into<String>) -> Self {
-        self.series.name.name = name.into();
+        self.series_mut().name.name = name.into();
         self
     }
 
@@ -92,15 +96,25 @@ impl Metric {
     #[inline]
     #[must_use]
     pub fn with_namespace<T: Into<String>>(mut self, namespace: Option<T>) -> Self {
-        self.series.name.namespace = namespace.map(Into::into);
+        self.series_mut().name.namespace = namespace.map(Into::into);
         self
     }
 
+    /// Sets the name of this metric.
+    pub fn set_name(&mut self, name: impl Into<String>) {
+        self.series_mut().name.name = name.into();
+    }
+
+    /// Sets the namespace of this metric.
+    pub fn set_namespace<T: Into<String>>(&mut self, namespace: Option<T>) {
+        self.series_mut().name.namespace = namespace.map(Into::into);
+    }
+
     /// Consumes this metric, returning it with an updated timestamp.
     #[inline]
     #[must_use]
     pub fn with_timestamp(mut self, timestamp: Option<DateTime<Utc>>) -> Self {
-        self.data.time.timestamp = timestamp;
+        self.data_mut().time.timestamp = timestamp;
         self
     }
 
@@ -108,7 +122,7 @@ impl Metric {
     #[inline]
     #[must_use]
     pub fn with_interval_ms(mut self, interval_ms: Option<NonZeroU32>) -> Self {
-        self.data.time.interval_ms = interval_ms;
+        self.data_mut().time.interval_ms = interval_ms;
         self
     }
 
@@ -134,7 +148,7 @@ impl Metric {
     #[inline]
     #[must_use]
     pub fn with_tags(mut self, tags: Option<MetricTags>) -> Self {
-        self.series.tags = tags;
+        self.series_mut().tags = tags;
         self
     }
 
@@ -142,7 +156,7 @@ impl Metric {
     #[inline]
     #[must_use]
     pub fn with_value(mut self, value: MetricValue) -> Self {
-        self.data.value = value;
+        self.data_mut().value = value;
         self
     }
 
@@ -155,14 +169,21 @@ impl Metric {
         &self.series
     }
 
+    /// Gets a mutable reference to the series of this metric, copying it first if it's shared
+    /// with clones of this metric.
+    pub(super) fn series_mut(&mut self) -> &mut MetricSeries {
+        Arc::make_mut(&mut self.series)
+    }
+
     /// Gets a reference to the data of this metric.
     pub fn data(&self) -> &MetricData {
         &self.data
     }
 
-    /// Gets a mutable reference to the data of this metric.
+    /// Gets a mutable reference to the data of this metric, copying it first if it's shared with
+    /// clones of this metric.
     pub fn data_mut(&mut self) -> &mut MetricData {
-        &mut self.data
+        Arc::make_mut(&mut self.data)
     }
 
     /// Gets a reference to the metadata of this metric.
@@ -192,7 +213,7 @@ impl Metric {
     /// Takes the namespace out of this metric, if it exists, leaving it empty.
     #[inline]
     pub fn take_namespace(&mut self) -> Option<String> {
-        self.series.name.namespace.take()
+        self.series_mut().name.namespace.take()
     }
 
     /// Gets a reference to the tags of this metric, if they exist.
@@ -222,7 +243,7 @@ impl Metric {
     /// Gets a mutable reference to the value of this metric.
     #[inline]
     pub fn value_mut(&mut self) -> &mut MetricValue {
-        &mut self.data.value
+        &mut self.data_mut().value
     }
 
     /// Gets the kind of this metric.
@@ -240,15 +261,19 @@ impl Metric {
     /// Decomposes a `Metric` into its individual parts.
     #[inline]
     pub fn into_parts(self) -> (MetricSeries, MetricData, EventMetadata) {
-        (self.series, self.data, self.metadata)
+        (
+            unwrap_or_clone(self.series),
+            unwrap_or_clone(self.data),
+            self.metadata,
+        )
     }
 
     /// Creates a `Metric` directly from the raw components of another metric.
     #[inline]
     pub fn from_parts(series: MetricSeries, data: MetricData, metadata: EventMetadata) -> Self {
         Self {
-            series,
-            data,
+            series: Arc::new(series),
+            data: Arc::new(data),
             metadata,
         }
     }
@@ -260,7 +285,7 @@ impl Metric {
     pub fn into_absolute(self) -> Self {
         Self {
             series: self.series,
-            data: self.data.into_absolute(),
+            data: Arc::new(unwrap_or_clone(self.data).into_absolute()),
             metadata: self.metadata,
         }
     }
@@ -272,7 +297,7 @@ impl Metric {
     pub fn into_incremental(self) -> Self {
         Self {
             series: self.series,
-            data: self.data.into_incremental(),
+            data: Arc::new(unwrap_or_clone(self.data).into_incremental()),
             metadata: self.metadata,
         }
     }
@@ -297,7 +322,7 @@ impl Metric {
 
     /// Removes a tag from this metric, returning the value of the tag if the tag was previously in the metric.
     pub fn remove_tag(&mut self, key: &str) -> Option<String> {
-        self.series.remove_tag(key)
+        self.series_mut().remove_tag(key)
     }
 
     /// Returns `true` if `name` tag is present, and matches the provided `value`
@@ -319,19 +344,19 @@ impl Metric {
     ///
     /// *Note:* This will create the tags map if it is not present.
     pub fn insert_tag(&mut self, name: String, value: String) -> Option<String> {
-        self.series.insert_tag(name, value)
+        self.series_mut().insert_tag(name, value)
     }
 
     /// Gets the given tag's corresponding entry in this metric.
     ///
     /// *Note:* This will create the tags map if it is not present, even if nothing is later inserted.
     pub fn tag_entry(&mut self, key: String) -> btree_map::Entry<String, String> {
-        self.series.tag_entry(key)
+        self.series_mut().tag_entry(key)
     }
 
     /// Zeroes out the data in this metric.
     pub fn zero(&mut self) {
-        self.data.zero();
+        self.data_mut().zero();
     }
 
     /// Adds the data from the `other` metric to this one.
@@ -339,13 +364,13 @@ impl Metric {
     /// The other metric must be incremental and contain the same value type as this one.
     #[must_use]
     pub fn add(&mut self, other: impl AsRef<MetricData>) -> bool {
-        self.data.add(other.as_ref())
+        self.data_mut().add(other.as_ref())
     }
 
     /// Updates this metric by adding the data from `other`.
     #[must_use]
     pub fn update(&mut self, other: impl AsRef<MetricData>) -> bool {
-        self.data.update(other.as_ref())
+        self.data_mut().update(other.as_ref())
     }
 
     /// Subtracts the data from the `other` metric from this one.
@@ -353,10 +378,15 @@ impl Metric {
     /// The other metric must contain the same value type as this one.
     #[must_use]
     pub fn subtract(&mut self, other: impl AsRef<MetricData>) -> bool {
-        self.data.subtract(other.as_ref())
+        self.data_mut().subtract(other.as_ref())
     }
 }
 
+/// Takes the value out of `arc`, or clones it if it's shared.
+fn unwrap_or_clone<T: Clone>(arc: Arc<T>) -> T {
+    Arc::try_unwrap(arc).unwrap_or_else(|arc| (*arc).clone())
+}
+
 impl AsRef<MetricData> for Metric {
     fn as_ref(&self) -> &MetricData {
         &self.data
@@ -634,10 +664,47 @@ mod test {
             .with_value(MetricValue::Counter { value: 3.0 })
             .with_timestamp(Some(ts()));
 
-        assert!(counter.data.add(&delta.data));
+        assert!(counter.data_mut().add(&delta.data));
         assert_eq!(counter, expected);
     }
 
+    #[test]
+    fn clones_share_data_until_modified() {
+        let metric = Metric::new(
+            "counter",
+            MetricKind::Incremental,
+            MetricValue::Counter { value: 1.0 },
+        )
+        .with_tags(Some(tags()));
+        let mut clone = metric.clone();
+        assert!(Arc::ptr_eq(&metric.series, &clone.series));
+        assert!(Arc::ptr_eq(&metric.data, &clone.data));
+
+        clone.insert_tag("other_tag".to_owned(), "value".to_owned());
+        assert!(!Arc::ptr_eq(&metric.series, &clone.series));
+        assert!(Arc::ptr_eq(&metric.data, &clone.data));
+        assert_eq!(metric.tag_value("other_tag"), None);
+        assert_eq!(metric.value(), clone.value());
+    }
+
+    #[test]
+    fn series_keys() {
+        let metric = Metric::new(
+            "counter",
+            MetricKind::Incremental,
+            MetricValue::Counter { value: 1.0 },
+        )
+        .with_namespace(Some("vector"))
+        .with_tags(Some(tags()));
+        let key = MetricSeriesKey::from(metric.series());
+        assert_eq!(key, MetricSeriesKey::new(metric.series().clone()));
+        assert_eq!(&key.to_series(), metric.series());
+
+        let other = metric.clone().with_tags(None);
+        assert_ne!(key, MetricSeriesKey::from(other.series()));
+        assert_eq!(key.into_series(), *metric.series());
+    }
+
     #[test]
     fn merge_gauges() {
         let mut gauge = Metric::new(
@@ -660,7 +727,7 @@ mod test {
             .with_value(MetricValue::Gauge { value: -1.0 })
             .with_timestamp(Some(ts()));
 
-        assert!(gauge.data.add(&delta.data));
+        assert!(gauge.data_mut().add(&delta.data));
         assert_eq!(gauge, expected);
     }
 
@@ -692,7 +759,7 @@ mod test {
             })
             .with_timestamp(Some(ts()));
 
-        assert!(set.data.add(&delta.data));
+        assert!(set.data_mut().add(&delta.data));
         assert_eq!(set, expected);
     }
 
@@ -727,7 +794,7 @@ mod test {
             })
             .with_timestamp(Some(ts()));
 
-        assert!(dist.data.add(&delta.data));
+        assert!(dist.data_mut().add(&delta.data));
         assert_eq!(dist, expected);
     }
 
@@ -1056,7 +1123,7 @@ mod test {
             .with_timestamp(Some(ts()))
             .with_interval_ms(std::num::NonZeroU32::new(35));
 
-        assert!(gauge.data.add(&delta.data));
+        assert!(gauge.data_mut().add(&delta.data));
         assert_eq!(gauge, expected);
     }
 
@@ -1084,7 +1151,7 @@ mod test {
             .with_timestamp(Some(ts()))
             .with_interval_ms(std::num::NonZeroU32::new(20));
 
-        assert!(gauge.data.add(&delta.data));
+        assert!(gauge.data_mut().add(&delta.data));
         assert_eq!(gauge, expected);
     }
 }
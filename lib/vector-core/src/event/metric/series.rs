@@ -1,6 +1,11 @@
 use core::fmt;
-use std::collections::btree_map;
+use std::{
+    collections::btree_map,
+    hash::{Hash, Hasher},
+};
 
+use smallvec::SmallVec;
+use twox_hash::XxHash64;
 use vector_common::byte_size_of::ByteSizeOf;
 use vector_config::configurable_component;
 
@@ -76,6 +81,87 @@ impl ByteSizeOf for MetricSeries {
     }
 }
 
+/// The tags of a metric series key, sorted by name.
+type SeriesKeyTags = SmallVec<[(String, String); 4]>;
+
+/// A metric series prepared for use as the key of a map of series.
+///
+/// The tags are held in a small vector rather than a map, which avoids allocating tree nodes for
+/// the handful of tags most series have, and the hash of the series is computed once when the key
+/// is created rather than on each lookup.
+#[derive(Clone, Debug)]
+pub struct MetricSeriesKey {
+    hash: u64,
+    name: MetricName,
+    tags: Option<SeriesKeyTags>,
+}
+
+impl MetricSeriesKey {
+    /// Creates the key of the given series, taking ownership of its name and tags.
+    pub fn new(series: MetricSeries) -> Self {
+        Self::from_parts(
+            series.name,
+            series.tags.map(|tags| tags.into_iter().collect()),
+        )
+    }
+
+    fn from_parts(name: MetricName, tags: Option<SeriesKeyTags>) -> Self {
+        let mut hasher = XxHash64::default();
+        name.hash(&mut hasher);
+        tags.hash(&mut hasher);
+        Self {
+            hash: hasher.finish(),
+            name,
+            tags,
+        }
+    }
+
+    /// Gets a reference to the name of the series.
+    pub fn name(&self) -> &MetricName {
+        &self.name
+    }
+
+    /// Gets a copy of the series this key was created from.
+    pub fn to_series(&self) -> MetricSeries {
+        self.clone().into_series()
+    }
+
+    /// Converts this key back into the series it was created from.
+    pub fn into_series(self) -> MetricSeries {
+        MetricSeries {
+            name: self.name,
+            tags: self.tags.map(|tags| tags.into_iter().collect()),
+        }
+    }
+}
+
+impl From<&MetricSeries> for MetricSeriesKey {
+    fn from(series: &MetricSeries) -> Self {
+        Self::from_parts(
+            series.name.clone(),
+            series.tags.as_ref().map(|tags| {
+                tags.iter()
+                    .map(|(key, value)| (key.clone(), value.clone()))
+                    .collect()
+            }),
+        )
+    }
+}
+
+impl PartialEq for MetricSeriesKey {
+    fn eq(&self, other: &Self) -> bool {
+        self.hash == other.hash && self.name == other.name && self.tags == other.tags
+    }
+}
+
+impl Eq for MetricSeriesKey {}
+
+impl Hash for MetricSeriesKey {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_u64(self.hash);
+    }
+}
+
 /// Metric name.
 #[configurable_component]
 #[derive(Clone, Debug, Eq, Hash, PartialEq, PartialOrd, Ord)]
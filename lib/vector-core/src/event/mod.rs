@@ -7,24 +7,27 @@ use std::{
 use crate::ByteSizeOf;
 pub use ::value::Value;
 pub use array::{into_event_stream, EventArray, EventContainer, LogArray, MetricArray, TraceArray};
+pub use estimated_json_encoded_size_of::EstimatedJsonEncodedSizeOf;
 pub use finalization::{
     BatchNotifier, BatchStatus, BatchStatusReceiver, EventFinalizer, EventFinalizers, EventStatus,
     Finalizable,
 };
 pub use log_event::LogEvent;
-pub use metadata::{EventMetadata, WithMetadata};
+pub use metadata::{EventMetadata, EventTrace, WithMetadata, PRIORITY_FIELD};
 pub use metric::{Metric, MetricKind, MetricValue, StatisticKind};
 pub use r#ref::{EventMutRef, EventRef};
+pub use response::{Response, ResponseHandle, ResponseReceiver};
 use serde::{Deserialize, Serialize};
 pub use trace::TraceEvent;
 use vector_buffers::EventCount;
 use vector_common::{finalization, EventDataEq};
 #[cfg(feature = "vrl")]
-pub use vrl_target::{TargetEvents, VrlTarget};
+pub use vrl_target::{TargetEvents, VrlTarget, VrlTargetRef};
 
 pub mod array;
 pub mod discriminant;
 pub mod error;
+mod estimated_json_encoded_size_of;
 mod log_event;
 #[cfg(feature = "lua")]
 pub mod lua;
@@ -33,6 +36,7 @@ mod metadata;
 pub mod metric;
 pub mod proto;
 mod r#ref;
+mod response;
 mod ser;
 #[cfg(test)]
 mod test;
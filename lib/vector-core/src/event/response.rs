@@ -0,0 +1,148 @@
+#![deny(missing_docs)]
+
+use std::{
+    cmp::Ordering,
+    fmt,
+    future::Future,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+use bytes::Bytes;
+use parking_lot::Mutex;
+use tokio::sync::oneshot;
+
+/// The response a source replies to a request with, as set by the components downstream of it.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Response {
+    /// The status of the response, as an HTTP status code.
+    pub status: u16,
+    /// The body of the response.
+    pub body: Bytes,
+}
+
+/// A handle to the response to the request an event was received in, shared by all the events of
+/// the request.
+///
+/// Components downstream of the source set the response through the handle, and the source
+/// receives it once all the events of the request are dropped. When several responses are set
+/// for the same request, the one with the highest status is kept, so that the rejection of any of
+/// the events of a request rejects the whole request.
+///
+/// Handles are kept in memory only, and so are lost when events are written to disk buffers.
+#[derive(Clone)]
+pub struct ResponseHandle(Arc<ResponseHandleInner>);
+
+struct ResponseHandleInner {
+    response: Mutex<Option<Response>>,
+    sender: Option<oneshot::Sender<Option<Response>>>,
+}
+
+impl ResponseHandle {
+    /// Creates a new handle, and the receiver of the response set through it.
+    #[must_use]
+    pub fn new_with_receiver() -> (Self, ResponseReceiver) {
+        let (sender, receiver) = oneshot::channel();
+        let handle = Self(Arc::new(ResponseHandleInner {
+            response: Mutex::new(None),
+            sender: Some(sender),
+        }));
+        (handle, ResponseReceiver(receiver))
+    }
+
+    /// Sets the response to the request, unless a response with a higher status is already set.
+    pub fn set(&self, response: Response) {
+        let mut current = self.0.response.lock();
+        if current
+            .as_ref()
+            .map_or(true, |current| response.status > current.status)
+        {
+            *current = Some(response);
+        }
+    }
+}
+
+impl Drop for ResponseHandleInner {
+    fn drop(&mut self) {
+        if let Some(sender) = self.sender.take() {
+            // The source may have stopped waiting for the response.
+            _ = sender.send(self.response.get_mut().take());
+        }
+    }
+}
+
+impl fmt::Debug for ResponseHandle {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_tuple("ResponseHandle")
+            .field(&*self.0.response.lock())
+            .finish()
+    }
+}
+
+impl PartialEq for ResponseHandle {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl PartialOrd for ResponseHandle {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.eq(other).then_some(Ordering::Equal)
+    }
+}
+
+/// The receiving end of a [`ResponseHandle`], resolving to the response set, if any, once all
+/// the events of the request are dropped.
+#[pin_project::pin_project]
+pub struct ResponseReceiver(#[pin] oneshot::Receiver<Option<Response>>);
+
+impl Future for ResponseReceiver {
+    type Output = Option<Response>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.project()
+            .0
+            .poll(cx)
+            .map(|response| response.unwrap_or(None))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::FutureExt;
+
+    use super::*;
+
+    fn response(status: u16, body: &'static str) -> Response {
+        Response {
+            status,
+            body: Bytes::from_static(body.as_bytes()),
+        }
+    }
+
+    #[test]
+    fn receives_nothing_when_unset() {
+        let (handle, receiver) = ResponseHandle::new_with_receiver();
+        let clone = handle.clone();
+        drop(handle);
+        drop(clone);
+        assert_eq!(receiver.now_or_never().unwrap(), None);
+    }
+
+    #[test]
+    fn keeps_the_highest_status() {
+        let (handle, receiver) = ResponseHandle::new_with_receiver();
+        let clone = handle.clone();
+        handle.set(response(200, "ok"));
+        clone.set(response(422, "invalid"));
+        handle.set(response(400, "bad"));
+        clone.set(response(422, "other"));
+        drop(handle);
+        drop(clone);
+        assert_eq!(
+            receiver.now_or_never().unwrap(),
+            Some(response(422, "invalid"))
+        );
+    }
+}
@@ -1,6 +1,7 @@
 use std::{
     collections::{BTreeMap, BTreeSet},
     iter,
+    sync::Arc,
 };
 
 use chrono::{DateTime, NaiveDateTime, Utc};
@@ -121,8 +122,8 @@ impl Arbitrary for Metric {
         let value = MetricValue::arbitrary(g);
         let metadata = EventMetadata::arbitrary(g);
         let mut metric = Metric::new_with_metadata(name, kind, value, metadata);
-        metric.data = MetricData::arbitrary(g);
-        metric.series = MetricSeries::arbitrary(g);
+        metric.data = Arc::new(MetricData::arbitrary(g));
+        metric.series = Arc::new(MetricSeries::arbitrary(g));
 
         metric
     }
@@ -135,18 +136,18 @@ impl Arbitrary for Metric {
             name.shrink()
                 .map(move |name| metric.clone().with_name(name))
                 .flat_map(|metric| {
-                    let data = metric.data.clone();
+                    let data = (*metric.data).clone();
                     data.shrink().map(move |data| {
                         let mut new_metric = metric.clone();
-                        new_metric.data = data;
+                        new_metric.data = Arc::new(data);
                         new_metric
                     })
                 })
                 .flat_map(|metric| {
-                    let series = metric.series.clone();
+                    let series = (*metric.series).clone();
                     series.shrink().map(move |series| {
                         let mut new_metric = metric.clone();
-                        new_metric.series = series;
+                        new_metric.series = Arc::new(series);
                         new_metric
                     })
                 }),
@@ -52,3 +52,31 @@ fn event_iteration_order() {
         ]
     );
 }
+
+#[test]
+fn arrays_only_coalesce_events_of_the_same_priority() {
+    use vector_buffers::{Prioritized, Priority};
+
+    let event = |priority: &str| {
+        let mut log = LogEvent::default();
+        log.metadata_mut()
+            .value_mut()
+            .insert(PRIORITY_FIELD, priority.to_owned());
+        Event::from(log)
+    };
+
+    let arrays = array::events_into_arrays(
+        vec![event("low"), event("low"), event("high"), event("low")],
+        None,
+    )
+    .map(|array| (array.len(), array.priority()))
+    .collect::<Vec<_>>();
+    assert_eq!(
+        arrays,
+        vec![
+            (2, Priority::Normal),
+            (1, Priority::High),
+            (1, Priority::Normal),
+        ]
+    );
+}
@@ -5,7 +5,7 @@ use lookup::{OwnedTargetPath, OwnedValuePath, PathPrefix};
 use snafu::Snafu;
 use vrl_lib::{prelude::VrlValueConvert, ProgramInfo, SecretTarget};
 
-use super::{Event, EventMetadata, LogEvent, Metric, MetricKind, TraceEvent, Value};
+use super::{Event, EventMetadata, EventRef, LogEvent, Metric, MetricKind, TraceEvent, Value};
 use crate::config::log_schema;
 
 const VALID_METRIC_PATHS_SET: &str = ".name, .namespace, .timestamp, .kind, .tags";
@@ -13,6 +13,8 @@ const VALID_METRIC_PATHS_SET: &str = ".name, .namespace, .timestamp, .kind, .tag
 /// We can get the `type` of the metric in Remap, but can't set it.
 const VALID_METRIC_PATHS_GET: &str = ".name, .namespace, .timestamp, .kind, .tags, .type";
 
+const READ_ONLY_TARGET_ERROR: &str = "cannot change a read-only event";
+
 /// Metrics aren't interested in paths that have a length longer than 3.
 ///
 /// The longest path is 2, and we need to check that a third segment doesn't exist as we don't want
@@ -209,21 +211,21 @@ impl vrl_lib::Target for VrlTarget {
                             }
                             ["name"] => {
                                 let value = value.clone().try_bytes().map_err(|e| e.to_string())?;
-                                metric.series.name.name =
+                                metric.series_mut().name.name =
                                     String::from_utf8_lossy(&value).into_owned();
                             }
                             ["namespace"] => {
                                 let value = value.clone().try_bytes().map_err(|e| e.to_string())?;
-                                metric.series.name.namespace =
+                                metric.series_mut().name.namespace =
                                     Some(String::from_utf8_lossy(&value).into_owned());
                             }
                             ["timestamp"] => {
                                 let value =
                                     value.clone().try_timestamp().map_err(|e| e.to_string())?;
-                                metric.data.time.timestamp = Some(value);
+                                metric.data_mut().time.timestamp = Some(value);
                             }
                             ["kind"] => {
-                                metric.data.kind = MetricKind::try_from(value.clone())?;
+                                metric.data_mut().kind = MetricKind::try_from(value.clone())?;
                             }
                             _ => {
                                 return Err(MetricPathError::InvalidPath {
@@ -307,9 +309,11 @@ impl vrl_lib::Target for VrlTarget {
                         .get(0)
                     {
                         let removed_value = match paths.as_slice() {
-                            ["namespace"] => metric.series.name.namespace.take().map(Into::into),
-                            ["timestamp"] => metric.data.time.timestamp.take().map(Into::into),
-                            ["tags"] => metric.series.tags.take().map(|map| {
+                            ["namespace"] => metric.take_namespace().map(Into::into),
+                            ["timestamp"] => {
+                                metric.data_mut().time.timestamp.take().map(Into::into)
+                            }
+                            ["tags"] => metric.series_mut().tags.take().map(|map| {
                                 map.into_iter()
                                     .map(|(k, v)| (k, v.into()))
                                     .collect::<::value::Value>()
@@ -354,6 +358,77 @@ impl SecretTarget for VrlTarget {
     }
 }
 
+/// An adapter to turn borrowed `Event`s into read-only `vrl_lib::Target`s, for programs compiled
+/// read-only, which can't change the event and so don't need a copy of it.
+#[derive(Debug)]
+pub enum VrlTargetRef<'a> {
+    /// A log or trace event.
+    Event(&'a Value, &'a EventMetadata),
+    /// A metric, along with the value of its fields read by the program.
+    Metric {
+        metadata: &'a EventMetadata,
+        value: Value,
+    },
+}
+
+impl<'a> VrlTargetRef<'a> {
+    pub fn new(event: EventRef<'a>, info: &ProgramInfo) -> Self {
+        match event {
+            EventRef::Log(log) => VrlTargetRef::Event(log.value(), log.metadata()),
+            EventRef::Metric(metric) => VrlTargetRef::Metric {
+                metadata: metric.metadata(),
+                value: precompute_metric_value(metric, info),
+            },
+            EventRef::Trace(trace) => VrlTargetRef::Event(trace.value(), trace.metadata()),
+        }
+    }
+
+    fn metadata(&self) -> &EventMetadata {
+        match *self {
+            VrlTargetRef::Event(_, metadata) | VrlTargetRef::Metric { metadata, .. } => metadata,
+        }
+    }
+}
+
+impl vrl_lib::Target for VrlTargetRef<'_> {
+    fn target_insert(&mut self, _: &OwnedTargetPath, _: ::value::Value) -> Result<(), String> {
+        Err(READ_ONLY_TARGET_ERROR.to_owned())
+    }
+
+    fn target_get(&self, target_path: &OwnedTargetPath) -> Result<Option<&Value>, String> {
+        match target_path.prefix {
+            PathPrefix::Event => match self {
+                VrlTargetRef::Event(value, _) => Ok(value.get(&target_path.path)),
+                VrlTargetRef::Metric { value, .. } => target_get_metric(&target_path.path, value),
+            },
+            PathPrefix::Metadata => Ok(self.metadata().value().get(&target_path.path)),
+        }
+    }
+
+    fn target_get_mut(&mut self, _: &OwnedTargetPath) -> Result<Option<&mut Value>, String> {
+        Err(READ_ONLY_TARGET_ERROR.to_owned())
+    }
+
+    fn target_remove(
+        &mut self,
+        _: &OwnedTargetPath,
+        _: bool,
+    ) -> Result<Option<::value::Value>, String> {
+        Err(READ_ONLY_TARGET_ERROR.to_owned())
+    }
+}
+
+impl SecretTarget for VrlTargetRef<'_> {
+    fn get_secret(&self, key: &str) -> Option<&str> {
+        self.metadata().secrets().get_secret(key)
+    }
+
+    // Secrets can't be changed through a read-only target either, so changing them is ignored.
+    fn insert_secret(&mut self, _: &str, _: &str) {}
+
+    fn remove_secret(&mut self, _: &str) {}
+}
+
 /// Retrieves a value from a the provided metric using the path.
 /// Currently the root path and the following paths are supported:
 /// - name
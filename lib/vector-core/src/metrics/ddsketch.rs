@@ -650,6 +650,17 @@ impl AgentDDSketch {
         Ok(())
     }
 
+    /// Overrides the sum, and thus the average, of all observations within the sketch.
+    ///
+    /// This is used when the exact sum is known, while the observations were only inserted
+    /// approximately, such as when interpolating histogram buckets.
+    fn set_sum(&mut self, sum: f64) {
+        if self.count > 0 {
+            self.sum = sum;
+            self.avg = sum / f64::from(self.count);
+        }
+    }
+
     /// Adds a bin directly into the sketch.
     ///
     /// Used only for unit testing so that we can create a sketch with an exact layout, which allows
@@ -787,10 +798,13 @@ impl AgentDDSketch {
                 }
                 Some(sketch)
             }
-            MetricValue::AggregatedHistogram { buckets, .. } => {
+            MetricValue::AggregatedHistogram { buckets, sum, .. } => {
                 let delta_buckets = mem::take(buckets);
                 let mut sketch = AgentDDSketch::with_agent_defaults();
                 sketch.insert_interpolate_buckets(delta_buckets)?;
+                // Interpolating the buckets only approximates the sum of the observations, which
+                // the histogram tracks exactly.
+                sketch.set_sum(*sum);
                 Some(sketch)
             }
             // We can't convert from any other metric value.
@@ -1096,7 +1110,10 @@ fn round_to_even(v: f64) -> f64 {
 #[cfg(test)]
 mod tests {
     use super::{round_to_even, AgentDDSketch, Config, AGENT_DEFAULT_EPS, MAX_KEY};
-    use crate::event::metric::Bucket;
+    use crate::event::{
+        metric::{Bucket, MetricSketch},
+        Metric, MetricKind, MetricValue,
+    };
 
     const FLOATING_POINT_ACCEPTABLE_ERROR: f64 = 1.0e-10;
 
@@ -1436,6 +1453,40 @@ mod tests {
         assert_eq!(actual.bins(), expected.bins());
     }
 
+    #[test]
+    fn test_transform_histogram_keeps_exact_sum() {
+        let histogram = Metric::new(
+            "latency",
+            MetricKind::Incremental,
+            MetricValue::AggregatedHistogram {
+                buckets: vec![
+                    Bucket {
+                        upper_limit: 1.0,
+                        count: 3,
+                    },
+                    Bucket {
+                        upper_limit: 10.0,
+                        count: 7,
+                    },
+                ],
+                count: 10,
+                sum: 42.5,
+            },
+        );
+
+        let metric = AgentDDSketch::transform_to_sketch(histogram).unwrap();
+        let sketch = match metric.value() {
+            MetricValue::Sketch {
+                sketch: MetricSketch::AgentDDSketch(sketch),
+            } => sketch,
+            value => panic!("expected a sketch, got {:?}", value),
+        };
+
+        assert_eq!(sketch.count(), 10);
+        assert_eq!(sketch.sum(), Some(42.5));
+        assert_eq!(sketch.avg(), Some(4.25));
+    }
+
     #[test]
     fn test_histogram_interpolation_agent_similarity() {
         #[derive(Clone)]
@@ -11,6 +11,7 @@ impl LabelFilter for VectorLabelFilter {
             || key == "component_type"
             || key == "component_kind"
             || key == "component_name"
+            || key == "component_status"
             || key == "buffer_type"
     }
 }
@@ -55,8 +55,8 @@ fn test_cardinality_metric() {
             .map(Event::into_metric)
             .find(|metric| metric.name() == super::CARDINALITY_KEY_NAME)
             .unwrap();
-        match metric.data.value {
-            crate::event::MetricValue::Counter { value } => value,
+        match metric.value() {
+            crate::event::MetricValue::Counter { value } => *value,
             _ => panic!("invalid metric value type, expected counter, got something else"),
         }
     };
@@ -27,6 +27,12 @@ struct SemanticMeaning {
     /// provided `Definition`, but it *must* error if it is defined, but its type does not meet the
     /// requirement.
     optional: bool,
+
+    /// The field the sink expects this meaning to be stored in.
+    ///
+    /// When semantic mapping is enabled, the field carrying this meaning is renamed to this path
+    /// before the event reaches the sink.
+    target: Option<&'static str>,
 }
 
 impl Requirement {
@@ -66,8 +72,31 @@ impl Requirement {
         self
     }
 
+    /// Set the field in which the sink expects to find the given meaning.
+    ///
+    /// The meaning must have been added through `required_meaning` or `optional_meaning` first,
+    /// otherwise this is a no-op.
+    #[must_use]
+    pub fn with_target(mut self, meaning: &'static str, target: &'static str) -> Self {
+        if let Some(meaning) = self.meaning.get_mut(meaning) {
+            meaning.target = Some(target);
+        }
+        self
+    }
+
+    /// Get the list of `(meaning, target field)` pairs configured for this requirement.
+    pub fn targets(&self) -> impl Iterator<Item = (&'static str, &'static str)> + '_ {
+        self.meaning
+            .iter()
+            .filter_map(|(identifier, meaning)| meaning.target.map(|target| (*identifier, target)))
+    }
+
     fn insert_meaning(&mut self, identifier: &'static str, kind: Kind, optional: bool) {
-        let meaning = SemanticMeaning { kind, optional };
+        let meaning = SemanticMeaning {
+            kind,
+            optional,
+            target: None,
+        };
         self.meaning.insert(identifier, meaning);
     }
 
@@ -353,4 +382,20 @@ mod tests {
             assert_eq!(got, want, "{}", title);
         }
     }
+
+    #[test]
+    fn test_targets() {
+        let requirement = Requirement::empty()
+            .required_meaning("message", Kind::bytes())
+            .optional_meaning("host", Kind::bytes())
+            .optional_meaning("severity", Kind::bytes())
+            .with_target("message", "msg")
+            .with_target("host", "hostname")
+            .with_target("unknown", "foo");
+
+        assert_eq!(
+            requirement.targets().collect::<Vec<_>>(),
+            vec![("host", "hostname"), ("message", "msg")]
+        );
+    }
 }
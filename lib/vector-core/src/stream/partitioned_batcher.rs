@@ -286,6 +286,9 @@ impl BatcherSettings {
     }
 }
 
+/// Gets the allocation and item limits of the new batches of a partition.
+type PartitionLimits<K> = Box<dyn Fn(&K) -> (usize, usize) + Send + Sync>;
+
 #[pin_project]
 pub struct PartitionedBatcher<St, Prt, KT>
 where
@@ -296,6 +299,8 @@ where
     batch_allocation_limit: usize,
     /// The maximum number of items that are allowed per-batch
     batch_item_limit: usize,
+    /// The limits of each partition, overriding the limits above when set.
+    partition_limits: Option<PartitionLimits<Prt::Key>>,
     /// The store of live batches. Note that the key here is an option type,
     /// on account of the interface of `Prt`.
     batches: HashMap<Prt::Key, Batch<Prt::Item>, BuildHasherDefault<XxHash64>>,
@@ -323,6 +328,7 @@ where
         Self {
             batch_allocation_limit: settings.size_limit,
             batch_item_limit: settings.item_limit,
+            partition_limits: None,
             batches: HashMap::default(),
             closed_batches: Vec::default(),
             timer: ExpirationQueue::new(settings.timeout),
@@ -350,6 +356,7 @@ where
             batch_allocation_limit: batch_allocation_limit
                 .map_or(usize::max_value(), NonZeroUsize::get),
             batch_item_limit: batch_item_limit.get(),
+            partition_limits: None,
             batches: HashMap::default(),
             closed_batches: Vec::default(),
             timer,
@@ -357,6 +364,20 @@ where
             stream: stream.fuse(),
         }
     }
+
+    /// Gets the allocation and item limits of each new batch of a partition from `limits`,
+    /// instead of using the same limits for every batch.
+    ///
+    /// This allows the size of the batches of each partition to be adjusted while running, such
+    /// as based on how long the batches take to be sent.
+    #[must_use]
+    pub fn with_partition_limits(
+        mut self,
+        limits: impl Fn(&Prt::Key) -> (usize, usize) + Send + Sync + 'static,
+    ) -> Self {
+        self.partition_limits = Some(Box::new(limits));
+        self
+    }
 }
 
 impl<St, Prt, KT> Stream for PartitionedBatcher<St, Prt, KT>
@@ -413,8 +434,16 @@ where
                 }
                 Poll::Ready(Some(item)) => {
                     let item_key = this.partitioner.partition(&item);
-                    let item_limit: usize = *this.batch_item_limit;
-                    let alloc_limit: usize = *this.batch_allocation_limit;
+                    let partition_limits = &*this.partition_limits;
+                    let (batch_allocation_limit, batch_item_limit) =
+                        (*this.batch_allocation_limit, *this.batch_item_limit);
+                    let new_batch = |item_key: &Prt::Key| {
+                        let (alloc_limit, item_limit) = match partition_limits {
+                            Some(limits) => limits(item_key),
+                            None => (batch_allocation_limit, batch_item_limit),
+                        };
+                        Batch::new(item_limit, alloc_limit)
+                    };
 
                     if let Some(batch) = this.batches.get_mut(&item_key) {
                         if batch.has_space(&item) {
@@ -422,7 +451,7 @@ where
                             // push the item in and loop back around.
                             batch.push(item);
                         } else {
-                            let new_batch = Batch::new(item_limit, alloc_limit).with(item);
+                            let new_batch = new_batch(&item_key).with(item);
                             let batch = mem::replace(batch, new_batch);
 
                             // The batch for this partition key was set to
@@ -437,7 +466,7 @@ where
                         // create one and create the expiration entries as well.
                         // This allows the batch to expire before filling up,
                         // and vise versa.
-                        let batch = Batch::new(item_limit, alloc_limit).with(item);
+                        let batch = new_batch(&item_key).with(item);
                         this.batches.insert(item_key.clone(), batch);
                         this.timer.insert(item_key);
                     }
@@ -457,7 +486,7 @@ mod test {
         time::Duration,
     };
 
-    use futures::{stream, Stream};
+    use futures::{stream, Stream, StreamExt};
     use pin_project::pin_project;
     use proptest::prelude::*;
     use tokio::{pin, time::advance};
@@ -735,6 +764,34 @@ mod test {
         }
     }
 
+    #[tokio::test]
+    async fn batches_use_partition_limits() {
+        // Partition `0` is limited to batches of two items, while partition `1` keeps the item
+        // limit of the batcher.
+        let partitioner = TestPartitioner {
+            key_space: NonZeroU8::new(2).unwrap(),
+        };
+        let batcher = PartitionedBatcher::with_timer(
+            stream::iter(0..12_u64),
+            partitioner,
+            TestTimer::new(vec![]),
+            NonZeroUsize::new(4).unwrap(),
+            None,
+        )
+        .with_partition_limits(|key| match key {
+            0 => (usize::MAX, 2),
+            _ => (usize::MAX, 4),
+        });
+
+        let batches: Vec<(u8, Vec<u64>)> = batcher.collect().await;
+        let mut sizes = batches
+            .into_iter()
+            .map(|(key, batch)| (key, batch.len()))
+            .collect::<Vec<_>>();
+        sizes.sort_unstable();
+        assert_eq!(sizes, vec![(0, 2), (0, 2), (0, 2), (1, 2), (1, 4)]);
+    }
+
     #[tokio::test(start_paused = true)]
     #[allow(clippy::semicolon_if_nothing_returned)] // https://github.com/rust-lang/rust-clippy/issues/7438
     async fn expiration_queue_impl_keyed_timer() {
@@ -403,15 +403,24 @@ impl Connected for MaybeTlsIncomingStream<TcpStream> {
     fn connect_info(&self) -> Self::ConnectInfo {
         MaybeTlsConnectInfo {
             remote_addr: self.peer_addr(),
-            peer_certs: self
-                .ssl_stream()
-                .and_then(|s| s.ssl().peer_cert_chain())
-                .map(|s| {
-                    s.into_iter()
+            peer_certs: self.ssl_stream().and_then(|s| {
+                let ssl = s.ssl();
+                // On the server side, the chain doesn't include the certificate of the peer
+                // itself, so it's added first.
+                let certificate = ssl.peer_certificate()?;
+                let chain = ssl
+                    .peer_cert_chain()
+                    .into_iter()
+                    .flatten()
+                    .map(ToOwned::to_owned);
+                Some(
+                    std::iter::once(certificate)
+                        .chain(chain)
                         .filter_map(|c| c.to_pem().ok())
                         .map(Certificate::from_pem)
-                        .collect()
-                }),
+                        .collect(),
+                )
+            }),
         }
     }
 }
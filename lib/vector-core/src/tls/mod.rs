@@ -18,7 +18,9 @@ mod maybe_tls;
 mod outgoing;
 mod settings;
 
-pub use incoming::{CertificateMetadata, MaybeTlsIncomingStream, MaybeTlsListener};
+pub use incoming::{
+    CertificateMetadata, MaybeTlsConnectInfo, MaybeTlsIncomingStream, MaybeTlsListener,
+};
 pub use maybe_tls::MaybeTls;
 pub use settings::{
     MaybeTlsSettings, TlsConfig, TlsEnableableConfig, TlsSettings, TlsSourceConfig,
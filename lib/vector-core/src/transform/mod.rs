@@ -1,6 +1,7 @@
 use std::{collections::HashMap, error, pin::Pin};
 
 use futures::{Stream, StreamExt};
+use vector_buffers::Prioritized;
 use vector_common::internal_event::{emit, EventsSent, DEFAULT_OUTPUT};
 use vector_common::EventDataEq;
 
@@ -390,6 +391,19 @@ impl TransformOutputsBuf {
     pub fn is_empty(&self) -> bool {
         self.len() == 0
     }
+
+    /// Iterates over the events in the buffer, along with the name of the output they are sent to.
+    pub fn iter_events(&self) -> impl Iterator<Item = (Option<&str>, EventRef)> {
+        let primary = self
+            .primary_buffer
+            .iter()
+            .flat_map(|buf| buf.iter_events().map(|event| (None, event)));
+        let named = self.named_buffers.iter().flat_map(|(name, buf)| {
+            buf.iter_events()
+                .map(move |event| (Some(name.as_str()), event))
+        });
+        primary.chain(named)
+    }
 }
 
 impl ByteSizeOf for TransformOutputsBuf {
@@ -412,15 +426,20 @@ impl OutputBuffer {
     }
 
     pub fn push(&mut self, event: Event) {
-        // Coalesce multiple pushes of the same type into one array.
+        // Coalesce multiple pushes of the same type and priority into one array, as arrays are
+        // buffered by the priority of their first event.
+        let coalesces = self
+            .0
+            .last()
+            .map_or(false, |last| last.priority() == event.metadata().priority());
         match (event, self.0.last_mut()) {
-            (Event::Log(log), Some(EventArray::Logs(logs))) => {
+            (Event::Log(log), Some(EventArray::Logs(logs))) if coalesces => {
                 logs.push(log);
             }
-            (Event::Metric(metric), Some(EventArray::Metrics(metrics))) => {
+            (Event::Metric(metric), Some(EventArray::Metrics(metrics))) if coalesces => {
                 metrics.push(metric);
             }
-            (Event::Trace(trace), Some(EventArray::Traces(traces))) => {
+            (Event::Trace(trace), Some(EventArray::Traces(traces))) if coalesces => {
                 traces.push(trace);
             }
             (event, _) => {
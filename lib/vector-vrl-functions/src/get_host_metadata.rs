@@ -0,0 +1,95 @@
+use std::{
+    collections::BTreeMap,
+    sync::{Arc, RwLock},
+};
+
+use ::value::Value;
+use vrl::prelude::*;
+use vrl::state::TypeState;
+
+/// The metadata of the host Vector runs on, such as the region of the cloud instance.
+///
+/// This is a handle to values shared with the task refreshing them, so cloning it is cheap and
+/// the clones see the refreshed values. It is passed to the `get_host_metadata` function as
+/// external context.
+#[derive(Clone, Debug, Default)]
+pub struct HostMetadata(Arc<RwLock<BTreeMap<String, String>>>);
+
+impl HostMetadata {
+    /// Gets the value of the given key, such as `cloud.region`.
+    pub fn get(&self, key: &str) -> Option<String> {
+        self.0.read().expect("poisoned lock").get(key).cloned()
+    }
+
+    /// Gets all the values, keyed by name.
+    pub fn all(&self) -> BTreeMap<String, String> {
+        self.0.read().expect("poisoned lock").clone()
+    }
+
+    /// Replaces all the values.
+    pub fn replace(&self, values: BTreeMap<String, String>) {
+        *self.0.write().expect("poisoned lock") = values;
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct GetHostMetadata;
+
+impl Function for GetHostMetadata {
+    fn identifier(&self) -> &'static str {
+        "get_host_metadata"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "key",
+            kind: kind::BYTES,
+            required: true,
+        }]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            title: "Get the region of the cloud instance",
+            source: r#"get_host_metadata("cloud.region")"#,
+            result: Ok("null"),
+        }]
+    }
+
+    fn compile(
+        &self,
+        _state: &TypeState,
+        ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let key = arguments.required("key");
+        // Without host metadata, such as in the VRL REPL, every key is missing.
+        let metadata = ctx
+            .get_external_context::<HostMetadata>()
+            .cloned()
+            .unwrap_or_default();
+
+        Ok(GetHostMetadataFn { key, metadata }.as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct GetHostMetadataFn {
+    key: Box<dyn Expression>,
+    metadata: HostMetadata,
+}
+
+impl FunctionExpression for GetHostMetadataFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let key = self.key.resolve(ctx)?;
+        let key = key.try_bytes_utf8_lossy()?;
+        Ok(self
+            .metadata
+            .get(key.as_ref())
+            .map_or(Value::Null, Into::into))
+    }
+
+    fn type_def(&self, _: &TypeState) -> TypeDef {
+        TypeDef::bytes().add_null().infallible()
+    }
+}
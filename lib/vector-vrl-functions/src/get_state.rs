@@ -0,0 +1,64 @@
+use ::value::Value;
+use vrl::prelude::*;
+use vrl::state::TypeState;
+
+use crate::state::StateStore;
+
+#[derive(Clone, Copy, Debug)]
+pub struct GetState;
+
+impl Function for GetState {
+    fn identifier(&self) -> &'static str {
+        "get_state"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "key",
+            kind: kind::BYTES,
+            required: true,
+        }]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            title: "Get a missing key of the state",
+            source: r#"get_state("host-1")"#,
+            result: Ok("null"),
+        }]
+    }
+
+    fn compile(
+        &self,
+        _state: &TypeState,
+        ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let key = arguments.required("key");
+        // Without a state store, such as in the VRL REPL, the state only lives in this call.
+        let store = ctx
+            .get_external_context::<StateStore>()
+            .cloned()
+            .unwrap_or_default();
+
+        Ok(GetStateFn { key, store }.as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct GetStateFn {
+    key: Box<dyn Expression>,
+    store: StateStore,
+}
+
+impl FunctionExpression for GetStateFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let key = self.key.resolve(ctx)?;
+        let key = key.try_bytes_utf8_lossy()?;
+        Ok(self.store.get(key.as_ref()).unwrap_or(Value::Null))
+    }
+
+    fn type_def(&self, _: &TypeState) -> TypeDef {
+        TypeDef::any().infallible()
+    }
+}
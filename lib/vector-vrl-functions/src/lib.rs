@@ -1,12 +1,17 @@
 #![deny(warnings)]
 
+pub mod get_host_metadata;
 pub mod get_metadata_field;
 pub mod get_secret;
+pub mod get_state;
 pub mod remove_metadata_field;
 pub mod remove_secret;
 pub mod set_metadata_field;
 pub mod set_secret;
 pub mod set_semantic_meaning;
+pub mod set_state;
+pub mod state;
+pub mod user_function;
 
 use ::value::Value;
 use lookup::OwnedTargetPath;
@@ -28,15 +33,30 @@ pub enum MetadataKey {
 
 pub const LEGACY_METADATA_KEYS: [&str; 2] = ["datadog_api_key", "splunk_hec_token"];
 
+/// Gets the functions specific to Vector, along with the functions defined in the configuration.
 pub fn vrl_functions() -> Vec<Box<dyn vrl::Function>> {
+    let mut functions = builtin_vrl_functions();
+    functions.extend(
+        user_function::registered()
+            .into_iter()
+            .map(|function| Box::new(function) as _),
+    );
+    functions
+}
+
+/// Gets the functions specific to Vector, which functions defined in the configuration can use.
+pub fn builtin_vrl_functions() -> Vec<Box<dyn vrl::Function>> {
     vec![
         Box::new(get_metadata_field::GetMetadataField) as _,
         Box::new(remove_metadata_field::RemoveMetadataField) as _,
         Box::new(set_metadata_field::SetMetadataField) as _,
         Box::new(set_semantic_meaning::SetSemanticMeaning) as _,
         Box::new(get_secret::GetSecret) as _,
+        Box::new(get_host_metadata::GetHostMetadata) as _,
         Box::new(remove_secret::RemoveSecret) as _,
         Box::new(set_secret::SetSecret) as _,
+        Box::new(get_state::GetState) as _,
+        Box::new(set_state::SetState) as _,
     ]
 }
 
@@ -0,0 +1,108 @@
+use std::time::Duration;
+
+use ::value::Value;
+use vrl::prelude::*;
+use vrl::state::TypeState;
+
+use crate::state::StateStore;
+
+#[derive(Clone, Copy, Debug)]
+pub struct SetState;
+
+impl Function for SetState {
+    fn identifier(&self) -> &'static str {
+        "set_state"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "key",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "value",
+                kind: kind::ANY,
+                required: true,
+            },
+            Parameter {
+                keyword: "ttl_secs",
+                kind: kind::INTEGER,
+                required: false,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "Set a key of the state",
+                source: r#"set_state("host-1", now())"#,
+                result: Ok("null"),
+            },
+            Example {
+                title: "Set a key of the state expiring after an hour",
+                source: r#"set_state!("host-1", true, ttl_secs: 3600)"#,
+                result: Ok("null"),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &TypeState,
+        ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let key = arguments.required("key");
+        let value = arguments.required("value");
+        let ttl_secs = arguments.optional("ttl_secs");
+        // Without a state store, such as in the VRL REPL, the state only lives in this call.
+        let store = ctx
+            .get_external_context::<StateStore>()
+            .cloned()
+            .unwrap_or_default();
+
+        Ok(SetStateFn {
+            key,
+            value,
+            ttl_secs,
+            store,
+        }
+        .as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct SetStateFn {
+    key: Box<dyn Expression>,
+    value: Box<dyn Expression>,
+    ttl_secs: Option<Box<dyn Expression>>,
+    store: StateStore,
+}
+
+impl FunctionExpression for SetStateFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let key = self.key.resolve(ctx)?;
+        let key = key.try_bytes_utf8_lossy()?.into_owned();
+        let value = self.value.resolve(ctx)?;
+        let ttl = match &self.ttl_secs {
+            Some(expr) => {
+                let ttl_secs = expr.resolve(ctx)?.try_integer()?;
+                let ttl_secs = u64::try_from(ttl_secs)
+                    .ok()
+                    .filter(|ttl_secs| *ttl_secs > 0)
+                    .ok_or(format!("ttl_secs must be positive, got {}", ttl_secs))?;
+                Some(Duration::from_secs(ttl_secs))
+            }
+            None => None,
+        };
+
+        Ok(self.store.set(key, value, ttl).unwrap_or(Value::Null))
+    }
+
+    fn type_def(&self, _: &TypeState) -> TypeDef {
+        TypeDef::any().with_fallibility(self.ttl_secs.is_some())
+    }
+}
@@ -0,0 +1,183 @@
+use std::{
+    collections::{BTreeMap, HashMap, HashSet, VecDeque},
+    sync::{Arc, Mutex},
+    time::{Duration, SystemTime},
+};
+
+use ::value::Value;
+
+/// The state of a component, set and read by the `set_state` and `get_state` functions.
+///
+/// This is a handle to entries shared by all the clones of a program, so cloning it is cheap and
+/// the clones see the same entries. It is passed to the functions as external context.
+#[derive(Clone, Debug, Default)]
+pub struct StateStore(Arc<Mutex<State>>);
+
+#[derive(Debug, Default)]
+struct State {
+    entries: HashMap<String, (StateEntry, u64)>,
+    /// The keys in the order they were set, along with the number of the set, to evict the
+    /// oldest ones first. Keys set again are found again later in the queue, and their earlier
+    /// sets are skipped.
+    order: VecDeque<(String, u64)>,
+    sets: u64,
+    /// The keys set or removed since the changes were last taken.
+    changed: HashSet<String>,
+    limits: StateLimits,
+}
+
+/// The limits of a [`StateStore`].
+#[derive(Clone, Copy, Debug, Default)]
+pub struct StateLimits {
+    /// The time-to-live of the values set without one.
+    pub default_ttl: Option<Duration>,
+
+    /// The maximum number of entries. Setting a new key evicts the oldest entries beyond it.
+    pub max_entries: Option<usize>,
+}
+
+/// A value of the state, with the time it expires at if it has a time-to-live.
+#[derive(Clone, Debug, PartialEq)]
+pub struct StateEntry {
+    pub value: Value,
+    pub expires_at: Option<SystemTime>,
+}
+
+impl StateEntry {
+    fn is_expired(&self, now: SystemTime) -> bool {
+        self.expires_at
+            .map_or(false, |expires_at| expires_at <= now)
+    }
+}
+
+impl State {
+    fn insert(&mut self, key: String, entry: StateEntry) -> Option<StateEntry> {
+        self.sets += 1;
+        self.order.push_back((key.clone(), self.sets));
+        self.changed.insert(key.clone());
+        let previous = self
+            .entries
+            .insert(key, (entry, self.sets))
+            .map(|(entry, _)| entry);
+
+        if let Some(max_entries) = self.limits.max_entries {
+            while self.entries.len() > max_entries {
+                let (key, set) = match self.order.pop_front() {
+                    Some(oldest) => oldest,
+                    None => break,
+                };
+                if self.entries.get(&key).map(|(_, latest)| *latest) == Some(set) {
+                    self.entries.remove(&key);
+                    self.changed.insert(key);
+                }
+            }
+        }
+        // Drops the sets overridden since, so the queue doesn't grow with the keys set again.
+        if self.order.len() > 2 * self.entries.len() + 16 {
+            let entries = &self.entries;
+            self.order
+                .retain(|(key, set)| entries.get(key).map(|(_, latest)| latest) == Some(set));
+        }
+
+        previous
+    }
+
+    fn remove(&mut self, key: &str) -> Option<StateEntry> {
+        let (entry, _) = self.entries.remove(key)?;
+        self.changed.insert(key.to_owned());
+        Some(entry)
+    }
+}
+
+impl StateStore {
+    /// Creates a store holding the given entries, dropping the expired ones.
+    pub fn new(
+        limits: StateLimits,
+        entries: impl IntoIterator<Item = (String, StateEntry)>,
+    ) -> Self {
+        let now = SystemTime::now();
+        let mut state = State {
+            limits,
+            ..Default::default()
+        };
+        for (key, entry) in entries {
+            if !entry.is_expired(now) {
+                state.insert(key, entry);
+            }
+        }
+        state.changed.clear();
+        Self(Arc::new(Mutex::new(state)))
+    }
+
+    /// Gets the value of the given key, unless it's missing or expired.
+    pub fn get(&self, key: &str) -> Option<Value> {
+        let mut state = self.0.lock().expect("poisoned lock");
+        match state.entries.get(key) {
+            Some((entry, _)) if entry.is_expired(SystemTime::now()) => {
+                state.remove(key);
+                None
+            }
+            entry => entry.map(|(entry, _)| entry.value.clone()),
+        }
+    }
+
+    /// Sets the value of the given key, expiring after `ttl` or the default time-to-live of the
+    /// store. Setting a key to `null` removes it.
+    ///
+    /// Returns the previous value of the key, unless it was missing or expired.
+    pub fn set(&self, key: String, value: Value, ttl: Option<Duration>) -> Option<Value> {
+        let now = SystemTime::now();
+        let mut state = self.0.lock().expect("poisoned lock");
+        let previous = if value.is_null() {
+            state.remove(&key)
+        } else {
+            let expires_at = ttl.or(state.limits.default_ttl).map(|ttl| now + ttl);
+            state.insert(key, StateEntry { value, expires_at })
+        };
+        previous
+            .filter(|entry| !entry.is_expired(now))
+            .map(|entry| entry.value)
+    }
+
+    /// Gets all the entries that haven't expired yet, removing the expired ones from the store.
+    pub fn entries(&self) -> BTreeMap<String, StateEntry> {
+        let now = SystemTime::now();
+        let mut state = self.0.lock().expect("poisoned lock");
+        state.entries.retain(|_, (entry, _)| !entry.is_expired(now));
+        state
+            .entries
+            .iter()
+            .map(|(key, (entry, _))| (key.clone(), entry.clone()))
+            .collect()
+    }
+
+    /// Takes the keys set or removed since the last call, along with their entry, or `None` for
+    /// the keys removed.
+    pub fn take_changes(&self) -> BTreeMap<String, Option<StateEntry>> {
+        let mut state = self.0.lock().expect("poisoned lock");
+        let changed = std::mem::take(&mut state.changed);
+        changed
+            .into_iter()
+            .map(|key| {
+                let entry = state.entries.get(&key).map(|(entry, _)| entry.clone());
+                (key, entry)
+            })
+            .collect()
+    }
+
+    /// Returns the number of entries, including the expired ones not removed yet.
+    pub fn len(&self) -> usize {
+        self.0.lock().expect("poisoned lock").entries.len()
+    }
+
+    /// Returns whether the store has no entries.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns whether this handle is the only one left, such as once the programs using the
+    /// store are dropped.
+    pub fn is_unique(&self) -> bool {
+        Arc::strong_count(&self.0) == 1
+    }
+}
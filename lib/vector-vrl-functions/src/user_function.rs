@@ -0,0 +1,182 @@
+use std::sync::{Arc, Mutex, RwLock};
+
+use vrl::{
+    diagnostic::DiagnosticList,
+    prelude::*,
+    state::{Runtime, TypeState},
+    CompilationResult, CompileConfig, Program,
+};
+
+/// The functions defined in the configuration, available to every VRL program compiled after
+/// they're registered.
+static USER_FUNCTIONS: RwLock<Vec<UserFunction>> = RwLock::new(Vec::new());
+
+/// The names and parameter lists leaked for the functions compiled so far.
+///
+/// Functions need them to be `'static`, so they're leaked, but only once: compiling a function
+/// again, such as when the configuration is reloaded, reuses them.
+static INTERNED_NAMES: Mutex<Vec<&'static str>> = Mutex::new(Vec::new());
+static INTERNED_PARAMETERS: Mutex<Vec<&'static [Parameter]>> = Mutex::new(Vec::new());
+
+/// Replaces the functions defined in the configuration.
+///
+/// Programs compiled before keep using the functions they were compiled with.
+pub fn register(functions: Vec<UserFunction>) {
+    *USER_FUNCTIONS.write().expect("poisoned lock") = functions;
+}
+
+pub(crate) fn registered() -> Vec<UserFunction> {
+    USER_FUNCTIONS.read().expect("poisoned lock").clone()
+}
+
+/// A function defined in VRL, whose parameters are variables of its source and whose result is
+/// the result of its source.
+///
+/// Functions using fallible functions, such as `parse_json!`, are fallible themselves.
+#[derive(Clone, Debug)]
+pub struct UserFunction {
+    identifier: &'static str,
+    parameters: &'static [Parameter],
+    program: Arc<Program>,
+}
+
+impl UserFunction {
+    /// Compiles a function, returning it along with the warnings of its source.
+    ///
+    /// The function can use the given functions, but not itself.
+    pub fn compile(
+        name: &str,
+        parameters: &[String],
+        source: &str,
+        functions: &[Box<dyn Function>],
+        mut config: CompileConfig,
+    ) -> std::result::Result<(Self, DiagnosticList), DiagnosticList> {
+        let mut state = TypeState::default();
+        for parameter in parameters {
+            state.local.declare_variable(parameter, TypeDef::any());
+        }
+        config.set_read_only();
+
+        let CompilationResult {
+            program, warnings, ..
+        } = vrl::compile_with_state(source, functions, &state, config)?;
+
+        Ok((
+            Self {
+                identifier: intern_name(name),
+                parameters: intern_parameters(parameters),
+                program: Arc::new(program),
+            },
+            warnings,
+        ))
+    }
+
+    /// Whether the source of the function reads or writes the event it's called on, rather than
+    /// only using its parameters.
+    ///
+    /// The queries of functions aren't known to the programs calling them, so the parts of
+    /// metrics they read may not be available to them.
+    pub fn queries_event(&self) -> bool {
+        let info = self.program.info();
+        !info.target_queries.is_empty() || !info.target_assignments.is_empty()
+    }
+}
+
+fn intern_name(name: &str) -> &'static str {
+    let mut names = INTERNED_NAMES.lock().expect("poisoned lock");
+    match names.iter().find(|interned| **interned == name) {
+        Some(interned) => interned,
+        None => {
+            let interned = Box::leak(name.to_owned().into_boxed_str());
+            names.push(interned);
+            interned
+        }
+    }
+}
+
+fn intern_parameters(keywords: &[String]) -> &'static [Parameter] {
+    let mut parameters = INTERNED_PARAMETERS.lock().expect("poisoned lock");
+    let found = parameters.iter().find(|interned| {
+        interned.len() == keywords.len()
+            && interned
+                .iter()
+                .zip(keywords)
+                .all(|(parameter, keyword)| parameter.keyword == keyword)
+    });
+    match found {
+        Some(interned) => interned,
+        None => {
+            let interned = keywords
+                .iter()
+                .map(|keyword| Parameter {
+                    keyword: intern_name(keyword),
+                    kind: kind::ANY,
+                    required: true,
+                })
+                .collect::<Vec<_>>();
+            let interned = Box::leak(interned.into_boxed_slice());
+            parameters.push(interned);
+            interned
+        }
+    }
+}
+
+impl Function for UserFunction {
+    fn identifier(&self) -> &'static str {
+        self.identifier
+    }
+
+    fn summary(&self) -> &'static str {
+        "A function defined in the configuration."
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        self.parameters
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[]
+    }
+
+    fn compile(
+        &self,
+        _state: &TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let arguments = self
+            .parameters
+            .iter()
+            .map(|parameter| (parameter.keyword, arguments.required(parameter.keyword)))
+            .collect();
+
+        Ok(UserFunctionFn {
+            program: Arc::clone(&self.program),
+            arguments,
+        }
+        .as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct UserFunctionFn {
+    program: Arc<Program>,
+    arguments: Vec<(&'static str, Box<dyn Expression>)>,
+}
+
+impl FunctionExpression for UserFunctionFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let mut state = Runtime::default();
+        for (keyword, argument) in &self.arguments {
+            state.set_variable(keyword, argument.resolve(ctx)?);
+        }
+
+        let timezone = *ctx.timezone();
+        let mut ctx = Context::new(ctx.target_mut(), &mut state, &timezone);
+        self.program.resolve(&mut ctx)
+    }
+
+    fn type_def(&self, _: &TypeState) -> TypeDef {
+        TypeDef::any().with_fallibility(self.program.info().fallible)
+    }
+}
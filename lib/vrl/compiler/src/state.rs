@@ -55,6 +55,18 @@ impl LocalEnv {
         self.bindings.get(ident)
     }
 
+    /// Declares a variable before the program is compiled, such as a parameter of a function
+    /// defined in VRL, whose value is set with [`Runtime::set_variable`] before the program runs.
+    pub fn declare_variable(&mut self, name: &str, type_def: TypeDef) {
+        self.bindings.insert(
+            Ident::new(name),
+            Details {
+                type_def,
+                value: None,
+            },
+        );
+    }
+
     #[cfg(any(feature = "expr-assignment", feature = "expr-function_call"))]
     pub(crate) fn insert_variable(&mut self, ident: Ident, details: Details) {
         self.bindings.insert(ident, details);
@@ -189,6 +201,11 @@ impl Runtime {
         self.variables.insert(ident, value);
     }
 
+    /// Sets a variable declared with [`LocalEnv::declare_variable`].
+    pub fn set_variable(&mut self, name: &str, value: Value) {
+        self.insert_variable(Ident::new(name), value);
+    }
+
     pub(crate) fn remove_variable(&mut self, ident: &Ident) {
         self.variables.remove(ident);
     }
@@ -0,0 +1,230 @@
+use ::value::Value;
+use rust_decimal::{
+    prelude::{FromPrimitive, ToPrimitive},
+    Decimal,
+};
+use vrl::prelude::*;
+
+use crate::units::{data_unit, duration_unit, SiBase};
+
+fn convert_unit(value: Value, from: Value, to: Value, base: Option<Value>) -> Resolved {
+    let value: Decimal = match value {
+        Value::Integer(v) => v.into(),
+        Value::Float(v) => {
+            Decimal::from_f64(*v).ok_or(format!("unable to convert number: {}", v))?
+        }
+        value => {
+            return Err(value::Error::Expected {
+                got: value.kind(),
+                expected: Kind::integer() | Kind::float(),
+            }
+            .into())
+        }
+    };
+    let from = from.try_bytes_utf8_lossy()?;
+    let to = to.try_bytes_utf8_lossy()?;
+    let si_base = match base {
+        Some(base) => SiBase::from_integer(base.try_integer()?)?,
+        None => SiBase::Ten,
+    };
+
+    // Durations are tried first, so that `m` is a minute when converted to or from seconds, and
+    // a megabyte when converted to or from bytes.
+    let (from_factor, to_factor) = match (duration_unit(&from), duration_unit(&to)) {
+        (Some(from), Some(to)) => (from, to),
+        _ => match (data_unit(&from, si_base), data_unit(&to, si_base)) {
+            (Some(from), Some(to)) => (from, to),
+            (None, _) if duration_unit(&from).is_none() => {
+                return Err(format!("unknown unit: '{}'", from).into())
+            }
+            (_, None) if duration_unit(&to).is_none() => {
+                return Err(format!("unknown unit: '{}'", to).into())
+            }
+            _ => return Err(format!("incompatible units: '{}' and '{}'", from, to).into()),
+        },
+    };
+
+    let number = value
+        .checked_mul(from_factor)
+        .and_then(|number| number.checked_div(to_factor))
+        .ok_or(format!("number out of range: {}", value))?;
+    let number = number
+        .to_f64()
+        .ok_or(format!("unable to format number: '{}'", number))?;
+    Ok(Value::from_f64_or_zero(number))
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct ConvertUnit;
+
+impl Function for ConvertUnit {
+    fn identifier(&self) -> &'static str {
+        "convert_unit"
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "durations",
+                source: r#"convert_unit!(1500, from: "ms", to: "s")"#,
+                result: Ok("1.5"),
+            },
+            Example {
+                title: "data sizes",
+                source: r#"convert_unit!(2.5, from: "GiB", to: "MiB")"#,
+                result: Ok("2560.0"),
+            },
+            Example {
+                title: "data sizes in base 2",
+                source: r#"convert_unit!(1, from: "MB", to: "kB", base: 2)"#,
+                result: Ok("1024.0"),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let from = arguments.required("from");
+        let to = arguments.required("to");
+        let base = arguments.optional("base");
+
+        Ok(ConvertUnitFn {
+            value,
+            from,
+            to,
+            base,
+        }
+        .as_expr())
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::INTEGER | kind::FLOAT,
+                required: true,
+            },
+            Parameter {
+                keyword: "from",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "to",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "base",
+                kind: kind::INTEGER,
+                required: false,
+            },
+        ]
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ConvertUnitFn {
+    value: Box<dyn Expression>,
+    from: Box<dyn Expression>,
+    to: Box<dyn Expression>,
+    base: Option<Box<dyn Expression>>,
+}
+
+impl FunctionExpression for ConvertUnitFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        let from = self.from.resolve(ctx)?;
+        let to = self.to.resolve(ctx)?;
+        let base = self
+            .base
+            .as_ref()
+            .map(|expr| expr.resolve(ctx))
+            .transpose()?;
+
+        convert_unit(value, from, to, base)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::float().fallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        convert_unit => ConvertUnit;
+
+        durations {
+            args: func_args![value: 1500,
+                             from: "ms",
+                             to: "s"],
+            want: Ok(1.5),
+            tdef: TypeDef::float().fallible(),
+        }
+
+        minutes {
+            args: func_args![value: 2,
+                             from: "h",
+                             to: "m"],
+            want: Ok(120.0),
+            tdef: TypeDef::float().fallible(),
+        }
+
+        data_sizes {
+            args: func_args![value: 2.5,
+                             from: "GiB",
+                             to: "MiB"],
+            want: Ok(2560.0),
+            tdef: TypeDef::float().fallible(),
+        }
+
+        megabytes {
+            args: func_args![value: 3,
+                             from: "m",
+                             to: "kB"],
+            want: Ok(3000.0),
+            tdef: TypeDef::float().fallible(),
+        }
+
+        bits {
+            args: func_args![value: 1,
+                             from: "kB",
+                             to: "bits"],
+            want: Ok(8000.0),
+            tdef: TypeDef::float().fallible(),
+        }
+
+        base_2 {
+            args: func_args![value: 1,
+                             from: "MB",
+                             to: "kB",
+                             base: 2],
+            want: Ok(1024.0),
+            tdef: TypeDef::float().fallible(),
+        }
+
+        error_unknown_unit {
+            args: func_args![value: 1,
+                             from: "s",
+                             to: "fortnight"],
+            want: Err("unknown unit: 'fortnight'"),
+            tdef: TypeDef::float().fallible(),
+        }
+
+        error_incompatible_units {
+            args: func_args![value: 1,
+                             from: "s",
+                             to: "MB"],
+            want: Err("incompatible units: 's' and 'MB'"),
+            tdef: TypeDef::float().fallible(),
+        }
+    ];
+}
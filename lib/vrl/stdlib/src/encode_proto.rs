@@ -0,0 +1,159 @@
+use ::value::Value;
+use bytes::Bytes;
+use prost::Message;
+use prost_reflect::MessageDescriptor;
+use vrl::prelude::*;
+
+use crate::protobuf_util::{message_descriptor, value_to_message};
+
+fn encode_proto(value: Value, descriptor: &MessageDescriptor) -> Resolved {
+    let message = value_to_message(value, descriptor).map_err(|error| {
+        format!(
+            "unable to encode {} message: {}",
+            descriptor.full_name(),
+            error
+        )
+    })?;
+
+    Ok(Value::from(Bytes::from(message.encode_to_vec())))
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct EncodeProto;
+
+impl Function for EncodeProto {
+    fn identifier(&self) -> &'static str {
+        "encode_proto"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::OBJECT,
+                required: true,
+            },
+            Parameter {
+                keyword: "message_type",
+                kind: kind::BYTES,
+                required: true,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            title: "Encode a protobuf message",
+            source: r#"encode_base64(encode_proto!({ "message": "hello" }, "example.Event"))"#,
+            result: Ok("CgVoZWxsbw=="),
+        }]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let descriptor = message_descriptor(ctx, &arguments)?;
+
+        Ok(EncodeProtoFn { value, descriptor }.as_expr())
+    }
+}
+
+#[derive(Clone, Debug)]
+struct EncodeProtoFn {
+    value: Box<dyn Expression>,
+    descriptor: MessageDescriptor,
+}
+
+impl FunctionExpression for EncodeProtoFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+
+        encode_proto(value, &self.descriptor)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        // Values that don't match the message type are only detected at runtime.
+        TypeDef::bytes().fallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use prost_reflect::DynamicMessage;
+
+    use super::*;
+    use crate::protobuf_util::{message_to_value, test_util::descriptors};
+
+    fn event_descriptor() -> MessageDescriptor {
+        descriptors()
+            .message("test.Event")
+            .expect("test.Event is defined")
+    }
+
+    fn roundtrip(value: Value) -> Value {
+        let descriptor = event_descriptor();
+        let encoded = encode_proto(value, &descriptor).unwrap();
+        let message = DynamicMessage::decode(descriptor, encoded.try_bytes().unwrap()).unwrap();
+        message_to_value(&message)
+    }
+
+    #[test]
+    fn encodes_message() {
+        let value = value!({
+            message: "hello",
+            count: 3,
+            tags: ["a", "b"],
+            level: "INFO",
+            source: { host: "localhost" },
+        });
+
+        assert_eq!(roundtrip(value.clone()), value);
+    }
+
+    #[test]
+    fn encodes_enum_numbers() {
+        assert_eq!(roundtrip(value!({ level: 2 })), value!({ level: "ERROR" }));
+    }
+
+    #[test]
+    fn skips_null_fields() {
+        assert_eq!(
+            roundtrip(value!({ message: "hello", source: null })),
+            value!({ message: "hello" })
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_fields() {
+        let error = encode_proto(value!({ foo: "bar" }), &event_descriptor()).unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            r#"unable to encode test.Event message: unknown field "foo" in message test.Event"#
+        );
+    }
+
+    #[test]
+    fn rejects_mismatched_types() {
+        let error = encode_proto(value!({ count: "three" }), &event_descriptor()).unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "unable to encode test.Event message: count: expected integer, got string"
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_enum_values() {
+        let error = encode_proto(value!({ level: "DEBUG" }), &event_descriptor()).unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            r#"unable to encode test.Event message: level: unknown value "DEBUG" of enum test.Level"#
+        );
+    }
+}
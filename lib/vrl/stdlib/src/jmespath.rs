@@ -0,0 +1,126 @@
+use ::value::Value;
+use vrl::prelude::*;
+
+fn jmespath(value: Value, expression: Value) -> Resolved {
+    let expression = expression.try_bytes_utf8_lossy()?;
+    let expression = ::jmespath::compile(&expression)
+        .map_err(|e| format!("unable to compile JMESPath expression: {}", e))?;
+    let data =
+        serde_json::to_value(&value).map_err(|e| format!("unable to convert value: {}", e))?;
+    let result = expression
+        .search(data)
+        .map_err(|e| format!("unable to evaluate JMESPath expression: {}", e))?;
+    let result = serde_json::to_value(&*result)
+        .map_err(|e| format!("unable to convert JMESPath result: {}", e))?;
+
+    Ok(Value::from(result))
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Jmespath;
+
+impl Function for Jmespath {
+    fn identifier(&self) -> &'static str {
+        "jmespath"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::OBJECT | kind::ARRAY,
+                required: true,
+            },
+            Parameter {
+                keyword: "expression",
+                kind: kind::BYTES,
+                required: true,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "nested field",
+                source: r#"jmespath!({"foo": {"bar": "baz"}}, "foo.bar")"#,
+                result: Ok(r#""baz""#),
+            },
+            Example {
+                title: "projection",
+                source: r#"jmespath!({"users": [{"name": "a", "age": 20}, {"name": "b", "age": 40}]}, "users[?age > `30`].name")"#,
+                result: Ok(r#"["b"]"#),
+            },
+            Example {
+                title: "missing field",
+                source: r#"jmespath!({"foo": "bar"}, "baz")"#,
+                result: Ok("null"),
+            },
+            Example {
+                title: "expression from a variable",
+                source: indoc! {r#"
+                    expression = "length(foo)"
+                    jmespath!({"foo": [1, 2, 3]}, expression)
+                "#},
+                result: Ok("3"),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let expression = arguments.required("expression");
+
+        Ok(JmespathFn { value, expression }.as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct JmespathFn {
+    value: Box<dyn Expression>,
+    expression: Box<dyn Expression>,
+}
+
+impl FunctionExpression for JmespathFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        let expression = self.expression.resolve(ctx)?;
+
+        jmespath(value, expression)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::any().fallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        jmespath => Jmespath;
+
+        field {
+            args: func_args![value: value!({"foo": {"bar": [1, 2, 3]}}), expression: "foo.bar[-1]"],
+            want: Ok(3),
+            tdef: TypeDef::any().fallible(),
+        }
+
+        multiselect {
+            args: func_args![value: value!({"a": 1, "b": "x", "c": true}), expression: "{first: a, second: b}"],
+            want: Ok(value!({"first": 1, "second": "x"})),
+            tdef: TypeDef::any().fallible(),
+        }
+    ];
+
+    #[test]
+    fn invalid_expression() {
+        assert!(jmespath(value!({"foo": 1}), value!("foo[?")).is_err());
+    }
+}
@@ -0,0 +1,126 @@
+use ::value::Value;
+use vrl::prelude::*;
+
+fn json_path(value: Value, path: Value) -> Resolved {
+    let path = path.try_bytes_utf8_lossy()?;
+    let data =
+        serde_json::to_value(&value).map_err(|e| format!("unable to convert value: {}", e))?;
+    let matches = jsonpath_lib::select(&data, &path)
+        .map_err(|e| format!("unable to evaluate JSONPath expression: {}", e))?;
+
+    Ok(Value::Array(
+        matches.into_iter().cloned().map(Value::from).collect(),
+    ))
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct JsonPath;
+
+impl Function for JsonPath {
+    fn identifier(&self) -> &'static str {
+        "json_path"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::OBJECT | kind::ARRAY,
+                required: true,
+            },
+            Parameter {
+                keyword: "path",
+                kind: kind::BYTES,
+                required: true,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "nested field",
+                source: r#"json_path!({"foo": {"bar": "baz"}}, "$.foo.bar")"#,
+                result: Ok(r#"["baz"]"#),
+            },
+            Example {
+                title: "filter",
+                source: r#"json_path!({"users": [{"name": "a", "age": 20}, {"name": "b", "age": 40}]}, "$.users[?(@.age > 30)].name")"#,
+                result: Ok(r#"["b"]"#),
+            },
+            Example {
+                title: "recursive descent",
+                source: r#"json_path!({"a": {"id": 1, "b": {"id": 2}}}, "$..id")"#,
+                result: Ok("[1, 2]"),
+            },
+            Example {
+                title: "no match",
+                source: r#"json_path!({"foo": "bar"}, "$.baz")"#,
+                result: Ok("[]"),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let path = arguments.required("path");
+
+        Ok(JsonPathFn { value, path }.as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct JsonPathFn {
+    value: Box<dyn Expression>,
+    path: Box<dyn Expression>,
+}
+
+impl FunctionExpression for JsonPathFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        let path = self.path.resolve(ctx)?;
+
+        json_path(value, path)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::array(Collection::any()).fallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        json_path => JsonPath;
+
+        wildcard {
+            args: func_args![value: value!({"items": [{"id": 1}, {"id": 2}]}), path: "$.items[*].id"],
+            want: Ok(value!([1, 2])),
+            tdef: TypeDef::array(Collection::any()).fallible(),
+        }
+
+        index {
+            args: func_args![value: value!([{"id": 1}, {"id": 2}]), path: "$[1]"],
+            want: Ok(value!([{"id": 2}])),
+            tdef: TypeDef::array(Collection::any()).fallible(),
+        }
+
+        no_match {
+            args: func_args![value: value!({"foo": 1}), path: "$.bar"],
+            want: Ok(value!([])),
+            tdef: TypeDef::array(Collection::any()).fallible(),
+        }
+    ];
+
+    #[test]
+    fn invalid_path() {
+        assert!(json_path(value!({"foo": 1}), value!("$.foo[")).is_err());
+    }
+}
@@ -28,6 +28,15 @@
 
 mod util;
 
+#[cfg(any(feature = "encode_proto", feature = "parse_proto"))]
+mod protobuf_util;
+#[cfg(any(
+    feature = "convert_unit",
+    feature = "parse_bytes",
+    feature = "parse_duration_any"
+))]
+mod units;
+
 #[cfg(feature = "append")]
 mod append;
 #[cfg(feature = "array")]
@@ -46,6 +55,8 @@ mod chunks;
 mod compact;
 #[cfg(feature = "contains")]
 mod contains;
+#[cfg(feature = "convert_unit")]
+mod convert_unit;
 #[cfg(feature = "decode_base64")]
 mod decode_base64;
 #[cfg(feature = "decode_percent")]
@@ -66,6 +77,8 @@ mod encode_key_value;
 mod encode_logfmt;
 #[cfg(feature = "encode_percent")]
 mod encode_percent;
+#[cfg(feature = "encode_proto")]
+mod encode_proto;
 #[cfg(feature = "encrypt")]
 mod encrypt;
 #[cfg(feature = "ends_with")]
@@ -144,8 +157,12 @@ mod is_regex;
 mod is_string;
 #[cfg(feature = "is_timestamp")]
 mod is_timestamp;
+#[cfg(feature = "jmespath")]
+mod jmespath;
 #[cfg(feature = "join")]
 mod join;
+#[cfg(feature = "json_path")]
+mod json_path;
 #[cfg(feature = "keys")]
 mod keys;
 #[cfg(feature = "length")]
@@ -190,6 +207,8 @@ mod parse_aws_alb_log;
 mod parse_aws_cloudwatch_log_subscription_message;
 #[cfg(feature = "parse_aws_vpc_flow_log")]
 mod parse_aws_vpc_flow_log;
+#[cfg(feature = "parse_bytes")]
+mod parse_bytes;
 #[cfg(feature = "parse_cef")]
 mod parse_cef;
 #[cfg(feature = "parse_common_log")]
@@ -198,6 +217,8 @@ mod parse_common_log;
 mod parse_csv;
 #[cfg(feature = "parse_duration")]
 mod parse_duration;
+#[cfg(feature = "parse_duration_any")]
+mod parse_duration_any;
 #[cfg(feature = "parse_glog")]
 mod parse_glog;
 #[cfg(feature = "parse_grok")]
@@ -218,6 +239,8 @@ mod parse_linux_authorization;
 mod parse_logfmt;
 #[cfg(feature = "parse_nginx_log")]
 mod parse_nginx_log;
+#[cfg(feature = "parse_proto")]
+mod parse_proto;
 #[cfg(feature = "parse_query_string")]
 mod parse_query_string;
 #[cfg(feature = "parse_regex")]
@@ -335,6 +358,8 @@ pub use chunks::Chunks;
 pub use compact::Compact;
 #[cfg(feature = "contains")]
 pub use contains::Contains;
+#[cfg(feature = "convert_unit")]
+pub use convert_unit::ConvertUnit;
 #[cfg(feature = "decode_base64")]
 pub use decode_base64::DecodeBase64;
 #[cfg(feature = "decode_percent")]
@@ -355,6 +380,8 @@ pub use encode_key_value::EncodeKeyValue;
 pub use encode_logfmt::EncodeLogfmt;
 #[cfg(feature = "encode_percent")]
 pub use encode_percent::EncodePercent;
+#[cfg(feature = "encode_proto")]
+pub use encode_proto::EncodeProto;
 #[cfg(feature = "encrypt")]
 pub use encrypt::Encrypt;
 #[cfg(feature = "ends_with")]
@@ -433,8 +460,12 @@ pub use is_regex::IsRegex;
 pub use is_string::IsString;
 #[cfg(feature = "is_timestamp")]
 pub use is_timestamp::IsTimestamp;
+#[cfg(feature = "jmespath")]
+pub use jmespath::Jmespath;
 #[cfg(feature = "join")]
 pub use join::Join;
+#[cfg(feature = "json_path")]
+pub use json_path::JsonPath;
 #[cfg(feature = "keys")]
 pub use keys::Keys;
 #[cfg(feature = "length")]
@@ -469,6 +500,8 @@ pub use parse_aws_alb_log::ParseAwsAlbLog;
 pub use parse_aws_cloudwatch_log_subscription_message::ParseAwsCloudWatchLogSubscriptionMessage;
 #[cfg(feature = "parse_aws_vpc_flow_log")]
 pub use parse_aws_vpc_flow_log::ParseAwsVpcFlowLog;
+#[cfg(feature = "parse_bytes")]
+pub use parse_bytes::ParseBytes;
 #[cfg(feature = "parse_cef")]
 pub use parse_cef::ParseCef;
 #[cfg(feature = "parse_common_log")]
@@ -477,6 +510,8 @@ pub use parse_common_log::ParseCommonLog;
 pub use parse_csv::ParseCsv;
 #[cfg(feature = "parse_duration")]
 pub use parse_duration::ParseDuration;
+#[cfg(feature = "parse_duration_any")]
+pub use parse_duration_any::ParseDurationAny;
 #[cfg(feature = "parse_glog")]
 pub use parse_glog::ParseGlog;
 #[cfg(feature = "parse_grok")]
@@ -497,6 +532,8 @@ pub use parse_linux_authorization::ParseLinuxAuthorization;
 pub use parse_logfmt::ParseLogFmt;
 #[cfg(feature = "parse_nginx_log")]
 pub use parse_nginx_log::ParseNginxLog;
+#[cfg(feature = "parse_proto")]
+pub use parse_proto::ParseProto;
 #[cfg(feature = "parse_query_string")]
 pub use parse_query_string::ParseQueryString;
 #[cfg(feature = "parse_regex")]
@@ -600,6 +637,8 @@ pub use values::Values;
 pub use crate::array::Array;
 #[cfg(feature = "md5")]
 pub use crate::md5::Md5;
+#[cfg(any(feature = "encode_proto", feature = "parse_proto"))]
+pub use crate::protobuf_util::ProtobufDescriptors;
 #[cfg(feature = "sha1")]
 pub use crate::sha1::Sha1;
 
@@ -624,6 +663,8 @@ pub fn all() -> Vec<Box<dyn vrl::Function>> {
         Box::new(Compact),
         #[cfg(feature = "contains")]
         Box::new(Contains),
+        #[cfg(feature = "convert_unit")]
+        Box::new(ConvertUnit),
         #[cfg(feature = "decode_base64")]
         Box::new(DecodeBase64),
         #[cfg(feature = "decode_percent")]
@@ -644,6 +685,8 @@ pub fn all() -> Vec<Box<dyn vrl::Function>> {
         Box::new(EncodeLogfmt),
         #[cfg(feature = "encode_percent")]
         Box::new(EncodePercent),
+        #[cfg(feature = "encode_proto")]
+        Box::new(EncodeProto),
         #[cfg(feature = "encrypt")]
         Box::new(Encrypt),
         #[cfg(feature = "ends_with")]
@@ -722,8 +765,12 @@ pub fn all() -> Vec<Box<dyn vrl::Function>> {
         Box::new(IsString),
         #[cfg(feature = "is_timestamp")]
         Box::new(IsTimestamp),
+        #[cfg(feature = "jmespath")]
+        Box::new(Jmespath),
         #[cfg(feature = "join")]
         Box::new(Join),
+        #[cfg(feature = "json_path")]
+        Box::new(JsonPath),
         #[cfg(feature = "keys")]
         Box::new(Keys),
         #[cfg(feature = "length")]
@@ -764,6 +811,8 @@ pub fn all() -> Vec<Box<dyn vrl::Function>> {
         Box::new(ParseAwsCloudWatchLogSubscriptionMessage),
         #[cfg(feature = "parse_aws_vpc_flow_log")]
         Box::new(ParseAwsVpcFlowLog),
+        #[cfg(feature = "parse_bytes")]
+        Box::new(ParseBytes),
         #[cfg(feature = "parse_cef")]
         Box::new(ParseCef),
         #[cfg(feature = "parse_common_log")]
@@ -772,6 +821,8 @@ pub fn all() -> Vec<Box<dyn vrl::Function>> {
         Box::new(ParseCsv),
         #[cfg(feature = "parse_duration")]
         Box::new(ParseDuration),
+        #[cfg(feature = "parse_duration_any")]
+        Box::new(ParseDurationAny),
         #[cfg(feature = "parse_glog")]
         Box::new(ParseGlog),
         #[cfg(feature = "parse_grok")]
@@ -792,6 +843,8 @@ pub fn all() -> Vec<Box<dyn vrl::Function>> {
         Box::new(ParseLogFmt),
         #[cfg(feature = "parse_nginx_log")]
         Box::new(ParseNginxLog),
+        #[cfg(feature = "parse_proto")]
+        Box::new(ParseProto),
         #[cfg(feature = "parse_query_string")]
         Box::new(ParseQueryString),
         #[cfg(feature = "parse_regex")]
@@ -0,0 +1,234 @@
+use std::str::FromStr;
+
+use ::value::Value;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use rust_decimal::{prelude::ToPrimitive, Decimal};
+use vrl::prelude::*;
+
+use crate::units::{data_unit, SiBase};
+
+fn parse_bytes(bytes: Value, unit: Option<Value>, base: Option<Value>) -> Resolved {
+    let bytes = bytes.try_bytes()?;
+    let value = String::from_utf8_lossy(&bytes);
+    let si_base = match base {
+        Some(base) => SiBase::from_integer(base.try_integer()?)?,
+        None => SiBase::Ten,
+    };
+    let conversion_factor = match unit {
+        Some(unit) => {
+            let bytes = unit.try_bytes()?;
+            let string = String::from_utf8_lossy(&bytes);
+
+            data_unit(&string, si_base).ok_or(format!("unknown unit format: '{}'", string))?
+        }
+        None => Decimal::ONE,
+    };
+    let captures = RE
+        .captures(&value)
+        .ok_or(format!("unable to parse bytes: '{}'", value))?;
+    let value = Decimal::from_str(&captures["value"])
+        .map_err(|error| format!("unable to parse number: {}", error))?;
+    let unit = data_unit(&captures["unit"], si_base)
+        .ok_or(format!("unknown bytes unit: '{}'", &captures["unit"]))?;
+    let number = value
+        .checked_mul(unit)
+        .and_then(|number| number.checked_div(conversion_factor))
+        .ok_or(format!("bytes out of range: '{}'", captures[0].trim()))?;
+    let number = number
+        .to_f64()
+        .ok_or(format!("unable to format bytes: '{}'", number))?;
+    Ok(Value::from_f64_or_zero(number))
+}
+
+static RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"(?ix)                        # i: case-insensitive, x: ignore whitespace + comments
+            \A
+            \s*
+            (?P<value>[0-9]*\.?[0-9]+) # value: integer or float
+            \s*                        # optional space between value and unit
+            (?P<unit>[a-z]*)           # unit: optional prefix and suffix, bytes if empty
+            \s*
+            \z",
+    )
+    .unwrap()
+});
+
+#[derive(Clone, Copy, Debug)]
+pub struct ParseBytes;
+
+impl Function for ParseBytes {
+    fn identifier(&self) -> &'static str {
+        "parse_bytes"
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "IEC prefix",
+                source: r#"parse_bytes!("1.5GiB", unit: "MiB")"#,
+                result: Ok("1536.0"),
+            },
+            Example {
+                title: "SI prefix",
+                source: r#"parse_bytes!("2 kB")"#,
+                result: Ok("2000.0"),
+            },
+            Example {
+                title: "SI prefix in base 2",
+                source: r#"parse_bytes!("2 kB", base: 2)"#,
+                result: Ok("2048.0"),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let unit = arguments.optional("unit");
+        let base = arguments.optional("base");
+
+        Ok(ParseBytesFn { value, unit, base }.as_expr())
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "unit",
+                kind: kind::BYTES,
+                required: false,
+            },
+            Parameter {
+                keyword: "base",
+                kind: kind::INTEGER,
+                required: false,
+            },
+        ]
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ParseBytesFn {
+    value: Box<dyn Expression>,
+    unit: Option<Box<dyn Expression>>,
+    base: Option<Box<dyn Expression>>,
+}
+
+impl FunctionExpression for ParseBytesFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let bytes = self.value.resolve(ctx)?;
+        let unit = self
+            .unit
+            .as_ref()
+            .map(|expr| expr.resolve(ctx))
+            .transpose()?;
+        let base = self
+            .base
+            .as_ref()
+            .map(|expr| expr.resolve(ctx))
+            .transpose()?;
+
+        parse_bytes(bytes, unit, base)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::float().fallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        parse_bytes => ParseBytes;
+
+        bytes {
+            args: func_args![value: "512"],
+            want: Ok(512.0),
+            tdef: TypeDef::float().fallible(),
+        }
+
+        iec {
+            args: func_args![value: "1.5GiB"],
+            want: Ok(1_610_612_736.0),
+            tdef: TypeDef::float().fallible(),
+        }
+
+        iec_unit {
+            args: func_args![value: "1.5GiB",
+                             unit: "MiB"],
+            want: Ok(1536.0),
+            tdef: TypeDef::float().fallible(),
+        }
+
+        si {
+            args: func_args![value: " 2 kB "],
+            want: Ok(2000.0),
+            tdef: TypeDef::float().fallible(),
+        }
+
+        si_base_2 {
+            args: func_args![value: "2 kB",
+                             base: 2],
+            want: Ok(2048.0),
+            tdef: TypeDef::float().fallible(),
+        }
+
+        bits {
+            args: func_args![value: "100Mb",
+                             unit: "MB"],
+            want: Ok(12.5),
+            tdef: TypeDef::float().fallible(),
+        }
+
+        long_units {
+            args: func_args![value: "3 megabytes",
+                             unit: "kilobits"],
+            want: Ok(24000.0),
+            tdef: TypeDef::float().fallible(),
+        }
+
+        error_invalid {
+            args: func_args![value: "foo"],
+            want: Err("unable to parse bytes: 'foo'"),
+            tdef: TypeDef::float().fallible(),
+        }
+
+        error_number {
+            args: func_args![value: "kB"],
+            want: Err("unable to parse bytes: 'kB'"),
+            tdef: TypeDef::float().fallible(),
+        }
+
+        error_unit {
+            args: func_args![value: "1 XB"],
+            want: Err("unknown bytes unit: 'XB'"),
+            tdef: TypeDef::float().fallible(),
+        }
+
+        error_format {
+            args: func_args![value: "1 kB",
+                             unit: "w"],
+            want: Err("unknown unit format: 'w'"),
+            tdef: TypeDef::float().fallible(),
+        }
+
+        error_base {
+            args: func_args![value: "1 kB",
+                             base: 8],
+            want: Err("invalid base 8: must be 2 or 10"),
+            tdef: TypeDef::float().fallible(),
+        }
+    ];
+}
@@ -0,0 +1,359 @@
+use std::str::FromStr;
+
+use ::value::Value;
+use once_cell::sync::Lazy;
+use regex::{Captures, Regex};
+use rust_decimal::{prelude::ToPrimitive, Decimal};
+use vrl::prelude::*;
+
+use crate::units::duration_unit;
+
+fn parse_duration_any(bytes: Value, unit: Option<Value>) -> Resolved {
+    let bytes = bytes.try_bytes()?;
+    let value = String::from_utf8_lossy(&bytes);
+    let conversion_factor = match unit {
+        Some(unit) => {
+            let bytes = unit.try_bytes()?;
+            let string = String::from_utf8_lossy(&bytes);
+
+            duration_unit(&string).ok_or(format!("unknown unit format: '{}'", string))?
+        }
+        None => Decimal::ONE,
+    };
+    let seconds =
+        parse_seconds(value.trim())?.ok_or(format!("unable to parse duration: '{}'", value))?;
+    let number = seconds
+        .checked_div(conversion_factor)
+        .ok_or(format!("duration out of range: '{}'", value))?;
+    let number = number
+        .to_f64()
+        .ok_or(format!("unable to format duration: '{}'", number))?;
+    Ok(Value::from_f64_or_zero(number))
+}
+
+/// Parses a duration into a number of seconds, trying each of the supported formats in turn.
+///
+/// Returns `None` if the duration isn't in any of these formats.
+fn parse_seconds(value: &str) -> Result<Option<Decimal>, String> {
+    let (negative, value) = match value.strip_prefix('-') {
+        Some(value) => (true, value),
+        None => (false, value),
+    };
+
+    let seconds = if let Some(captures) = ISO_8601_RE.captures(value) {
+        iso_8601(&captures)?
+    } else if let Some(captures) = CLOCK_RE.captures(value) {
+        Some(sum(&captures, &["days", "hours", "minutes", "seconds"])?)
+    } else {
+        compound(value)?
+    };
+
+    Ok(seconds.map(|seconds| if negative { -seconds } else { seconds }))
+}
+
+/// Sums the weeks, days, hours, minutes and seconds of an ISO 8601 duration, such as `P1DT2H`.
+fn iso_8601(captures: &Captures<'_>) -> Result<Option<Decimal>, String> {
+    let has_date = captures.name("weeks").is_some() || captures.name("days").is_some();
+    let has_time = ["hours", "minutes", "seconds"]
+        .iter()
+        .any(|name| captures.name(name).is_some());
+    if (!has_date && !has_time) || (captures.name("time").is_some() && !has_time) {
+        return Ok(None);
+    }
+
+    sum(captures, &["weeks", "days", "hours", "minutes", "seconds"]).map(Some)
+}
+
+/// Sums the parts of a compound duration, such as `1h30m` or `2 hours, 30 minutes and 5s`.
+///
+/// Parts may only be separated by whitespace, commas or `and`.
+fn compound(value: &str) -> Result<Option<Decimal>, String> {
+    let mut seconds = None;
+    let mut end = 0;
+
+    for captures in PART_RE.captures_iter(value) {
+        let part = captures.get(0).expect("whole match");
+        if !is_separator(&value[end..part.start()]) {
+            return Ok(None);
+        }
+        end = part.end();
+
+        let number = decimal(&captures["value"])?;
+        let unit = duration_unit(&captures["unit"])
+            .ok_or(format!("unknown duration unit: '{}'", &captures["unit"]))?;
+        let part_seconds = number
+            .checked_mul(unit)
+            .and_then(|part| part.checked_add(seconds.unwrap_or_default()))
+            .ok_or(format!("duration out of range: '{}'", value))?;
+        seconds = Some(part_seconds);
+    }
+
+    if !is_separator(&value[end..]) {
+        return Ok(None);
+    }
+    Ok(seconds)
+}
+
+fn is_separator(gap: &str) -> bool {
+    matches!(
+        gap.trim_matches(|c: char| c.is_whitespace() || c == ','),
+        "" | "and"
+    )
+}
+
+/// Sums the named parts of a duration, each captured as a number in the unit it's named after.
+fn sum(captures: &Captures<'_>, names: &[&str]) -> Result<Decimal, String> {
+    names.iter().try_fold(Decimal::ZERO, |seconds, name| {
+        let number = match captures.name(name) {
+            Some(number) => decimal(number.as_str())?,
+            None => return Ok(seconds),
+        };
+        let unit = duration_unit(name).expect("named after a duration unit");
+        number
+            .checked_mul(unit)
+            .and_then(|part| part.checked_add(seconds))
+            .ok_or(format!("duration out of range: '{}'", &captures[0]))
+    })
+}
+
+fn decimal(number: &str) -> Result<Decimal, String> {
+    Decimal::from_str(number).map_err(|error| format!("unable to parse number: {}", error))
+}
+
+static ISO_8601_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"(?ix)                                   # i: case-insensitive, x: ignore whitespace + comments
+            \A
+            P
+            (?:(?P<weeks>[0-9]*\.?[0-9]+)W)?
+            (?:(?P<days>[0-9]*\.?[0-9]+)D)?
+            (?P<time>T
+                (?:(?P<hours>[0-9]*\.?[0-9]+)H)?
+                (?:(?P<minutes>[0-9]*\.?[0-9]+)M)?
+                (?:(?P<seconds>[0-9]*\.?[0-9]+)S)?
+            )?
+            \z",
+    )
+    .unwrap()
+});
+
+static CLOCK_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"(?x)                                    # x: ignore whitespace + comments
+            \A
+            (?:
+                (?:(?P<days>[0-9]+)[-.])?         # optional days, such as `1-` or `1.`
+                (?P<hours>[0-9]+):                # optional hours
+            )?
+            (?P<minutes>[0-9]+):
+            (?P<seconds>[0-9]+(?:\.[0-9]+)?)
+            \z",
+    )
+    .unwrap()
+});
+
+static PART_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"(?ix)                                   # i: case-insensitive, x: ignore whitespace + comments
+            (?P<value>[0-9]*\.?[0-9]+)            # value: integer or float
+            \s*                                   # optional space between value and unit
+            (?P<unit>[µμa-z]+)                    # unit: abbreviated or long name
+        ",
+    )
+    .unwrap()
+});
+
+#[derive(Clone, Copy, Debug)]
+pub struct ParseDurationAny;
+
+impl Function for ParseDurationAny {
+    fn identifier(&self) -> &'static str {
+        "parse_duration_any"
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "compound duration",
+                source: r#"parse_duration_any!("1h30m", unit: "m")"#,
+                result: Ok("90.0"),
+            },
+            Example {
+                title: "long unit names",
+                source: r#"parse_duration_any!("2 minutes and 30 seconds")"#,
+                result: Ok("150.0"),
+            },
+            Example {
+                title: "ISO 8601 duration",
+                source: r#"parse_duration_any!("PT1M30.5S", unit: "ms")"#,
+                result: Ok("90500.0"),
+            },
+            Example {
+                title: "clock duration",
+                source: r#"parse_duration_any!("01:02:03")"#,
+                result: Ok("3723.0"),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let unit = arguments.optional("unit");
+
+        Ok(ParseDurationAnyFn { value, unit }.as_expr())
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "unit",
+                kind: kind::BYTES,
+                required: false,
+            },
+        ]
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ParseDurationAnyFn {
+    value: Box<dyn Expression>,
+    unit: Option<Box<dyn Expression>>,
+}
+
+impl FunctionExpression for ParseDurationAnyFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let bytes = self.value.resolve(ctx)?;
+        let unit = self
+            .unit
+            .as_ref()
+            .map(|expr| expr.resolve(ctx))
+            .transpose()?;
+
+        parse_duration_any(bytes, unit)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::float().fallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        parse_duration_any => ParseDurationAny;
+
+        single {
+            args: func_args![value: "1005ms"],
+            want: Ok(1.005),
+            tdef: TypeDef::float().fallible(),
+        }
+
+        compound {
+            args: func_args![value: "1h30m",
+                             unit: "m"],
+            want: Ok(90.0),
+            tdef: TypeDef::float().fallible(),
+        }
+
+        long_names {
+            args: func_args![value: "2 hours, 30 minutes and 5s",
+                             unit: "seconds"],
+            want: Ok(9005.0),
+            tdef: TypeDef::float().fallible(),
+        }
+
+        weeks {
+            args: func_args![value: "1.5 weeks",
+                             unit: "d"],
+            want: Ok(10.5),
+            tdef: TypeDef::float().fallible(),
+        }
+
+        negative {
+            args: func_args![value: "-1m30s"],
+            want: Ok(-90.0),
+            tdef: TypeDef::float().fallible(),
+        }
+
+        iso_8601 {
+            args: func_args![value: "P1DT2H3M4.5S"],
+            want: Ok(93784.5),
+            tdef: TypeDef::float().fallible(),
+        }
+
+        iso_8601_weeks {
+            args: func_args![value: "P2W",
+                             unit: "d"],
+            want: Ok(14.0),
+            tdef: TypeDef::float().fallible(),
+        }
+
+        clock {
+            args: func_args![value: "01:02:03.5"],
+            want: Ok(3723.5),
+            tdef: TypeDef::float().fallible(),
+        }
+
+        clock_minutes {
+            args: func_args![value: "2:30",
+                             unit: "m"],
+            want: Ok(2.5),
+            tdef: TypeDef::float().fallible(),
+        }
+
+        clock_days {
+            args: func_args![value: "1-00:00:30"],
+            want: Ok(86430.0),
+            tdef: TypeDef::float().fallible(),
+        }
+
+        error_invalid {
+            args: func_args![value: "foo"],
+            want: Err("unable to parse duration: 'foo'"),
+            tdef: TypeDef::float().fallible(),
+        }
+
+        error_number {
+            args: func_args![value: "1h 30"],
+            want: Err("unable to parse duration: '1h 30'"),
+            tdef: TypeDef::float().fallible(),
+        }
+
+        error_iso_8601_empty {
+            args: func_args![value: "PT"],
+            want: Err("unable to parse duration: 'PT'"),
+            tdef: TypeDef::float().fallible(),
+        }
+
+        error_iso_8601_years {
+            args: func_args![value: "P1Y"],
+            want: Err("unable to parse duration: 'P1Y'"),
+            tdef: TypeDef::float().fallible(),
+        }
+
+        error_unit {
+            args: func_args![value: "1 fortnight"],
+            want: Err("unknown duration unit: 'fortnight'"),
+            tdef: TypeDef::float().fallible(),
+        }
+
+        error_format {
+            args: func_args![value: "1s",
+                             unit: "fortnight"],
+            want: Err("unknown unit format: 'fortnight'"),
+            tdef: TypeDef::float().fallible(),
+        }
+    ];
+}
@@ -0,0 +1,143 @@
+use ::value::Value;
+use prost_reflect::{DynamicMessage, MessageDescriptor};
+use vrl::prelude::*;
+
+use crate::protobuf_util::{message_descriptor, message_to_value};
+
+fn parse_proto(value: Value, descriptor: &MessageDescriptor) -> Resolved {
+    let bytes = value.try_bytes()?;
+    let message = DynamicMessage::decode(descriptor.clone(), bytes).map_err(|error| {
+        format!(
+            "unable to parse {} message: {}",
+            descriptor.full_name(),
+            error
+        )
+    })?;
+
+    Ok(message_to_value(&message))
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct ParseProto;
+
+impl Function for ParseProto {
+    fn identifier(&self) -> &'static str {
+        "parse_proto"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "message_type",
+                kind: kind::BYTES,
+                required: true,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            title: "Parse a protobuf message",
+            source: r#"parse_proto!(decode_base64!("CgVoZWxsbw=="), "example.Event")"#,
+            result: Ok(r#"{ "message": "hello" }"#),
+        }]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let descriptor = message_descriptor(ctx, &arguments)?;
+
+        Ok(ParseProtoFn { value, descriptor }.as_expr())
+    }
+}
+
+#[derive(Clone, Debug)]
+struct ParseProtoFn {
+    value: Box<dyn Expression>,
+    descriptor: MessageDescriptor,
+}
+
+impl FunctionExpression for ParseProtoFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+
+        parse_proto(value, &self.descriptor)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::object(Collection::any()).fallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protobuf_util::{
+        test_util::{descriptors, encode},
+        value_to_message,
+    };
+
+    fn event_descriptor() -> MessageDescriptor {
+        descriptors()
+            .message("test.Event")
+            .expect("test.Event is defined")
+    }
+
+    #[test]
+    fn parses_message() {
+        let descriptor = event_descriptor();
+        let message = value_to_message(
+            value!({
+                message: "hello",
+                count: 3,
+                tags: ["a", "b"],
+                level: "ERROR",
+                source: { host: "localhost" },
+            }),
+            &descriptor,
+        )
+        .unwrap();
+
+        let parsed = parse_proto(Value::from(encode(&message)), &descriptor).unwrap();
+
+        assert_eq!(
+            parsed,
+            value!({
+                message: "hello",
+                count: 3,
+                tags: ["a", "b"],
+                level: "ERROR",
+                source: { host: "localhost" },
+            })
+        );
+    }
+
+    #[test]
+    fn leaves_out_absent_fields() {
+        let descriptor = event_descriptor();
+        let message = value_to_message(value!({ count: 1 }), &descriptor).unwrap();
+
+        let parsed = parse_proto(Value::from(encode(&message)), &descriptor).unwrap();
+
+        assert_eq!(parsed, value!({ count: 1 }));
+    }
+
+    #[test]
+    fn rejects_invalid_message() {
+        let error = parse_proto(Value::from("\u{ff}garbage"), &event_descriptor()).unwrap_err();
+
+        assert!(error
+            .to_string()
+            .starts_with("unable to parse test.Event message"));
+    }
+}
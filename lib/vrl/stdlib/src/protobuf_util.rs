@@ -0,0 +1,386 @@
+use std::{collections::BTreeMap, fmt};
+
+use ::value::Value;
+use prost_reflect::{
+    DescriptorError, DescriptorPool, DynamicMessage, FieldDescriptor, Kind, MapKey,
+    MessageDescriptor, Value as ProtobufValue,
+};
+use vrl::{
+    diagnostic::{Label, Note, Span},
+    prelude::*,
+};
+
+/// The protobuf message types available to the `parse_proto` and `encode_proto` functions.
+///
+/// This is expected to be provided to the compiler as an external context. Message types are
+/// resolved when the program is compiled, so that unknown types are reported up front.
+#[derive(Clone, Debug)]
+pub struct ProtobufDescriptors(DescriptorPool);
+
+impl ProtobufDescriptors {
+    /// Decode an encoded `FileDescriptorSet`, such as the output of `protoc --descriptor_set_out`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the descriptor set can't be decoded, or if its files reference types
+    /// that are not part of the set.
+    pub fn decode(bytes: &[u8]) -> Result<Self, DescriptorError> {
+        DescriptorPool::decode(bytes).map(Self)
+    }
+
+    pub(crate) fn message(&self, name: &str) -> Option<MessageDescriptor> {
+        self.0.get_message_by_name(name)
+    }
+}
+
+#[derive(Debug)]
+pub(crate) enum Error {
+    DescriptorsNotLoaded,
+    UnknownMessageType(String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::DescriptorsNotLoaded => f.write_str("protobuf descriptor set not loaded"),
+            Error::UnknownMessageType(name) => write!(f, "unknown message type {:?}", name),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl DiagnosticMessage for Error {
+    fn code(&self) -> usize {
+        112
+    }
+
+    fn labels(&self) -> Vec<Label> {
+        vec![Label::primary(
+            format!("protobuf error: {}", self),
+            Span::default(),
+        )]
+    }
+
+    fn notes(&self) -> Vec<Note> {
+        match self {
+            Error::DescriptorsNotLoaded => vec![Note::Hint(
+                "set the global `protobuf_descriptor_set` option".to_owned(),
+            )],
+            Error::UnknownMessageType(_) => vec![],
+        }
+    }
+}
+
+/// Resolve the literal `message_type` argument against the descriptors in the external context.
+pub(crate) fn message_descriptor(
+    ctx: &FunctionCompileContext,
+    arguments: &ArgumentList,
+) -> Result<MessageDescriptor, Box<dyn DiagnosticMessage>> {
+    let name = arguments
+        .required_literal("message_type")?
+        .to_value()
+        .try_bytes_utf8_lossy()
+        .expect("message type not bytes")
+        .into_owned();
+
+    let descriptors = ctx
+        .get_external_context::<ProtobufDescriptors>()
+        .ok_or_else(|| Box::new(Error::DescriptorsNotLoaded) as Box<dyn DiagnosticMessage>)?;
+
+    descriptors
+        .message(&name)
+        .ok_or_else(|| Box::new(Error::UnknownMessageType(name)) as Box<dyn DiagnosticMessage>)
+}
+
+/// Convert a decoded message into an object, keyed by field name.
+///
+/// Fields that are not present in the message are left out.
+pub(crate) fn message_to_value(message: &DynamicMessage) -> Value {
+    let object = message
+        .descriptor()
+        .fields()
+        .filter(|field| message.has_field(field))
+        .map(|field| {
+            let value = message.get_field(&field);
+            (field.name().to_owned(), field_to_value(&field, &value))
+        })
+        .collect::<BTreeMap<_, _>>();
+
+    Value::Object(object)
+}
+
+fn field_to_value(field: &FieldDescriptor, value: &ProtobufValue) -> Value {
+    match value {
+        ProtobufValue::Bool(value) => Value::from(*value),
+        ProtobufValue::I32(value) => Value::from(*value),
+        ProtobufValue::I64(value) => Value::from(*value),
+        ProtobufValue::U32(value) => Value::from(*value),
+        ProtobufValue::U64(value) => Value::from(*value),
+        ProtobufValue::F32(value) => Value::from_f64_or_zero(f64::from(*value)),
+        ProtobufValue::F64(value) => Value::from_f64_or_zero(*value),
+        ProtobufValue::String(value) => Value::from(value.as_str()),
+        ProtobufValue::Bytes(value) => Value::from(value.clone()),
+        ProtobufValue::EnumNumber(number) => match field.kind() {
+            Kind::Enum(descriptor) => descriptor
+                .get_value(*number)
+                .map_or_else(|| Value::from(*number), |value| Value::from(value.name())),
+            _ => Value::from(*number),
+        },
+        ProtobufValue::Message(message) => message_to_value(message),
+        ProtobufValue::List(values) => values
+            .iter()
+            .map(|value| field_to_value(field, value))
+            .collect::<Vec<_>>()
+            .into(),
+        ProtobufValue::Map(entries) => {
+            let value_field = match field.kind() {
+                Kind::Message(entry) => entry.map_entry_value_field(),
+                _ => unreachable!("map fields have a message kind"),
+            };
+            entries
+                .iter()
+                .map(|(key, value)| (map_key_to_string(key), field_to_value(&value_field, value)))
+                .collect::<BTreeMap<_, _>>()
+                .into()
+        }
+    }
+}
+
+fn map_key_to_string(key: &MapKey) -> String {
+    match key {
+        MapKey::Bool(key) => key.to_string(),
+        MapKey::I32(key) => key.to_string(),
+        MapKey::I64(key) => key.to_string(),
+        MapKey::U32(key) => key.to_string(),
+        MapKey::U64(key) => key.to_string(),
+        MapKey::String(key) => key.clone(),
+    }
+}
+
+/// Convert an object into a message of the given type.
+///
+/// Null values are treated as absent fields.
+pub(crate) fn value_to_message(
+    value: Value,
+    descriptor: &MessageDescriptor,
+) -> Result<DynamicMessage, String> {
+    let object = match value {
+        Value::Object(object) => object,
+        value => {
+            return Err(format!(
+                "expected object for message {}, got {}",
+                descriptor.full_name(),
+                value.kind()
+            ))
+        }
+    };
+
+    let mut message = DynamicMessage::new(descriptor.clone());
+    for (name, value) in object {
+        if value.is_null() {
+            continue;
+        }
+        let field = descriptor.get_field_by_name(&name).ok_or_else(|| {
+            format!(
+                "unknown field {:?} in message {}",
+                name,
+                descriptor.full_name()
+            )
+        })?;
+        let value =
+            value_to_field(value, &field).map_err(|error| format!("{}: {}", name, error))?;
+        message.set_field(&field, value);
+    }
+
+    Ok(message)
+}
+
+fn value_to_field(value: Value, field: &FieldDescriptor) -> Result<ProtobufValue, String> {
+    if field.is_map() {
+        let entry = match field.kind() {
+            Kind::Message(entry) => entry,
+            _ => unreachable!("map fields have a message kind"),
+        };
+        let (key_field, value_field) = (entry.map_entry_key_field(), entry.map_entry_value_field());
+        let object = match value {
+            Value::Object(object) => object,
+            value => return Err(format!("expected object for map, got {}", value.kind())),
+        };
+        return object
+            .into_iter()
+            .map(|(key, value)| {
+                Ok((
+                    string_to_map_key(&key, &key_field.kind())?,
+                    value_to_kind(value, &value_field.kind())?,
+                ))
+            })
+            .collect::<Result<_, String>>()
+            .map(ProtobufValue::Map);
+    }
+
+    if field.is_list() {
+        return match value {
+            Value::Array(values) => values
+                .into_iter()
+                .map(|value| value_to_kind(value, &field.kind()))
+                .collect::<Result<_, _>>()
+                .map(ProtobufValue::List),
+            value => Err(format!("expected array, got {}", value.kind())),
+        };
+    }
+
+    value_to_kind(value, &field.kind())
+}
+
+fn value_to_kind(value: Value, kind: &Kind) -> Result<ProtobufValue, String> {
+    fn integer<T: TryFrom<i64>>(value: &Value) -> Result<T, String> {
+        match value {
+            Value::Integer(integer) => {
+                T::try_from(*integer).map_err(|_| format!("integer {} out of range", integer))
+            }
+            value => Err(format!("expected integer, got {}", value.kind())),
+        }
+    }
+
+    fn float(value: &Value) -> Result<f64, String> {
+        match value {
+            Value::Float(float) => Ok(float.into_inner()),
+            Value::Integer(integer) => Ok(*integer as f64),
+            value => Err(format!("expected float, got {}", value.kind())),
+        }
+    }
+
+    Ok(match kind {
+        Kind::Double => ProtobufValue::F64(float(&value)?),
+        Kind::Float => ProtobufValue::F32(float(&value)? as f32),
+        Kind::Int32 | Kind::Sint32 | Kind::Sfixed32 => ProtobufValue::I32(integer(&value)?),
+        Kind::Int64 | Kind::Sint64 | Kind::Sfixed64 => ProtobufValue::I64(integer(&value)?),
+        Kind::Uint32 | Kind::Fixed32 => ProtobufValue::U32(integer(&value)?),
+        Kind::Uint64 | Kind::Fixed64 => ProtobufValue::U64(integer(&value)?),
+        Kind::Bool => match value {
+            Value::Boolean(boolean) => ProtobufValue::Bool(boolean),
+            value => return Err(format!("expected boolean, got {}", value.kind())),
+        },
+        Kind::String => match value {
+            Value::Bytes(bytes) => ProtobufValue::String(String::from_utf8_lossy(&bytes).into()),
+            value => return Err(format!("expected string, got {}", value.kind())),
+        },
+        Kind::Bytes => match value {
+            Value::Bytes(bytes) => ProtobufValue::Bytes(bytes),
+            value => return Err(format!("expected string, got {}", value.kind())),
+        },
+        Kind::Enum(descriptor) => match value {
+            Value::Bytes(bytes) => {
+                let name = String::from_utf8_lossy(&bytes);
+                let value = descriptor.get_value_by_name(&name).ok_or_else(|| {
+                    format!(
+                        "unknown value {:?} of enum {}",
+                        name,
+                        descriptor.full_name()
+                    )
+                })?;
+                ProtobufValue::EnumNumber(value.number())
+            }
+            value => ProtobufValue::EnumNumber(integer(&value)?),
+        },
+        Kind::Message(descriptor) => ProtobufValue::Message(value_to_message(value, descriptor)?),
+    })
+}
+
+fn string_to_map_key(key: &str, kind: &Kind) -> Result<MapKey, String> {
+    let invalid = || format!("invalid map key {:?}", key);
+    Ok(match kind {
+        Kind::Bool => MapKey::Bool(key.parse().map_err(|_| invalid())?),
+        Kind::Int32 | Kind::Sint32 | Kind::Sfixed32 => {
+            MapKey::I32(key.parse().map_err(|_| invalid())?)
+        }
+        Kind::Int64 | Kind::Sint64 | Kind::Sfixed64 => {
+            MapKey::I64(key.parse().map_err(|_| invalid())?)
+        }
+        Kind::Uint32 | Kind::Fixed32 => MapKey::U32(key.parse().map_err(|_| invalid())?),
+        Kind::Uint64 | Kind::Fixed64 => MapKey::U64(key.parse().map_err(|_| invalid())?),
+        _ => MapKey::String(key.to_owned()),
+    })
+}
+
+#[cfg(test)]
+pub(crate) mod test_util {
+    use bytes::Bytes;
+    use prost::Message;
+    use prost_reflect::DynamicMessage;
+    use prost_types::{
+        field_descriptor_proto::{Label, Type},
+        DescriptorProto, EnumDescriptorProto, EnumValueDescriptorProto, FieldDescriptorProto,
+        FileDescriptorProto, FileDescriptorSet,
+    };
+
+    use super::ProtobufDescriptors;
+
+    pub(crate) fn encode(message: &DynamicMessage) -> Bytes {
+        Bytes::from(message.encode_to_vec())
+    }
+
+    fn field(name: &str, number: i32, r#type: Type, label: Label) -> FieldDescriptorProto {
+        FieldDescriptorProto {
+            name: Some(name.to_owned()),
+            number: Some(number),
+            r#type: Some(r#type as i32),
+            label: Some(label as i32),
+            ..Default::default()
+        }
+    }
+
+    /// Descriptors for a `test.Event` message, with scalar, repeated, enum, and nested fields.
+    pub(crate) fn descriptors() -> ProtobufDescriptors {
+        let level = FieldDescriptorProto {
+            type_name: Some(".test.Level".to_owned()),
+            ..field("level", 4, Type::Enum, Label::Optional)
+        };
+        let source = FieldDescriptorProto {
+            type_name: Some(".test.Source".to_owned()),
+            ..field("source", 5, Type::Message, Label::Optional)
+        };
+
+        let file = FileDescriptorProto {
+            name: Some("test.proto".to_owned()),
+            package: Some("test".to_owned()),
+            syntax: Some("proto3".to_owned()),
+            message_type: vec![
+                DescriptorProto {
+                    name: Some("Event".to_owned()),
+                    field: vec![
+                        field("message", 1, Type::String, Label::Optional),
+                        field("count", 2, Type::Int64, Label::Optional),
+                        field("tags", 3, Type::String, Label::Repeated),
+                        level,
+                        source,
+                    ],
+                    ..Default::default()
+                },
+                DescriptorProto {
+                    name: Some("Source".to_owned()),
+                    field: vec![field("host", 1, Type::String, Label::Optional)],
+                    ..Default::default()
+                },
+            ],
+            enum_type: vec![EnumDescriptorProto {
+                name: Some("Level".to_owned()),
+                value: ["UNKNOWN", "INFO", "ERROR"]
+                    .iter()
+                    .zip(0..)
+                    .map(|(name, number)| EnumValueDescriptorProto {
+                        name: Some((*name).to_owned()),
+                        number: Some(number),
+                        ..Default::default()
+                    })
+                    .collect(),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        let set = FileDescriptorSet { file: vec![file] };
+        ProtobufDescriptors::decode(&set.encode_to_vec()).expect("valid descriptor set")
+    }
+}
@@ -0,0 +1,145 @@
+//! Units of data sizes and durations, shared by the functions parsing and converting quantities.
+//!
+//! Units are represented by the number of bytes or seconds in them, as decimals so that
+//! conversions between them are exact.
+
+use rust_decimal::Decimal;
+
+/// The power of the SI prefixes of data sizes, such as `kB`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum SiBase {
+    /// `1kB` is 1000 bytes.
+    Ten,
+    /// `1kB` is 1024 bytes, like `1KiB`.
+    Two,
+}
+
+impl SiBase {
+    pub(crate) fn from_integer(base: i64) -> Result<Self, String> {
+        match base {
+            10 => Ok(Self::Ten),
+            2 => Ok(Self::Two),
+            _ => Err(format!("invalid base {}: must be 2 or 10", base)),
+        }
+    }
+}
+
+/// Gets the number of bytes in a data size unit.
+///
+/// Units are made of an optional SI or IEC prefix, such as `k`, `kilo`, `Ki` or `kibi`, and of a
+/// `B` or `byte` suffix for bytes, or a `b` or `bit` suffix for bits. Units with only a prefix,
+/// such as `M`, are bytes. Prefixes are case-insensitive, so that `mb` is a megabit.
+pub(crate) fn data_unit(unit: &str, si_base: SiBase) -> Option<Decimal> {
+    let (prefix, bits) = split_data_suffix(unit);
+
+    let (exponent, iec) = match prefix.trim_end().to_ascii_lowercase().as_str() {
+        "" => (0, false),
+        "k" | "kilo" => (1, false),
+        "ki" | "kibi" => (1, true),
+        "m" | "mega" => (2, false),
+        "mi" | "mebi" => (2, true),
+        "g" | "giga" => (3, false),
+        "gi" | "gibi" => (3, true),
+        "t" | "tera" => (4, false),
+        "ti" | "tebi" => (4, true),
+        "p" | "peta" => (5, false),
+        "pi" | "pebi" => (5, true),
+        "e" | "exa" => (6, false),
+        "ei" | "exbi" => (6, true),
+        _ => return None,
+    };
+
+    let base = if iec || si_base == SiBase::Two {
+        1024
+    } else {
+        1000
+    };
+    let bytes = (0..exponent).fold(Decimal::ONE, |bytes, _| bytes * Decimal::from(base));
+    Some(if bits {
+        bytes / Decimal::from(8)
+    } else {
+        bytes
+    })
+}
+
+/// Splits the byte or bit suffix off a data size unit, returning whether it's a bit unit.
+fn split_data_suffix(unit: &str) -> (&str, bool) {
+    for (suffix, bits) in [
+        ("bytes", false),
+        ("byte", false),
+        ("bits", true),
+        ("bit", true),
+    ] {
+        if let Some(prefix) = strip_suffix_ignore_case(unit, suffix) {
+            return (prefix, bits);
+        }
+    }
+
+    if let Some(prefix) = unit.strip_suffix('B') {
+        (prefix, false)
+    } else if let Some(prefix) = unit.strip_suffix('b') {
+        (prefix, true)
+    } else {
+        (unit, false)
+    }
+}
+
+fn strip_suffix_ignore_case<'a>(value: &'a str, suffix: &str) -> Option<&'a str> {
+    let split = value.len().checked_sub(suffix.len())?;
+    value
+        .get(split..)
+        .filter(|end| end.eq_ignore_ascii_case(suffix))
+        .and_then(|_| value.get(..split))
+}
+
+/// Gets the number of seconds in a duration unit, such as `ms`, `min` or `hours`.
+pub(crate) fn duration_unit(unit: &str) -> Option<Decimal> {
+    let seconds = match unit.to_lowercase().as_str() {
+        "ns" | "nsec" | "nsecs" | "nanosecond" | "nanoseconds" => Decimal::new(1, 9),
+        "us" | "µs" | "μs" | "usec" | "usecs" | "microsecond" | "microseconds" => {
+            Decimal::new(1, 6)
+        }
+        "ms" | "msec" | "msecs" | "millisecond" | "milliseconds" => Decimal::new(1, 3),
+        "cs" => Decimal::new(1, 2),
+        "ds" => Decimal::new(1, 1),
+        "s" | "sec" | "secs" | "second" | "seconds" => Decimal::new(1, 0),
+        "m" | "min" | "mins" | "minute" | "minutes" => Decimal::new(60, 0),
+        "h" | "hr" | "hrs" | "hour" | "hours" => Decimal::new(3_600, 0),
+        "d" | "day" | "days" => Decimal::new(86_400, 0),
+        "w" | "wk" | "wks" | "week" | "weeks" => Decimal::new(604_800, 0),
+        _ => return None,
+    };
+    Some(seconds)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn data_units() {
+        let bytes = |unit| data_unit(unit, SiBase::Ten);
+
+        assert_eq!(bytes("B"), Some(Decimal::from(1)));
+        assert_eq!(bytes("bytes"), Some(Decimal::from(1)));
+        assert_eq!(bytes("b"), Some(Decimal::new(125, 3)));
+        assert_eq!(bytes("kB"), Some(Decimal::from(1_000)));
+        assert_eq!(bytes("KiB"), Some(Decimal::from(1_024)));
+        assert_eq!(bytes("Gi"), Some(Decimal::from(1_073_741_824)));
+        assert_eq!(bytes("Mb"), Some(Decimal::from(125_000)));
+        assert_eq!(bytes("megabytes"), Some(Decimal::from(1_000_000)));
+        assert_eq!(bytes("M bytes"), Some(Decimal::from(1_000_000)));
+        assert_eq!(bytes("iB"), None);
+        assert_eq!(bytes("xB"), None);
+
+        assert_eq!(data_unit("kB", SiBase::Two), Some(Decimal::from(1_024)));
+    }
+
+    #[test]
+    fn duration_units() {
+        assert_eq!(duration_unit("MS"), Some(Decimal::new(1, 3)));
+        assert_eq!(duration_unit("µs"), Some(Decimal::new(1, 6)));
+        assert_eq!(duration_unit("Hours"), Some(Decimal::from(3_600)));
+        assert_eq!(duration_unit("fortnight"), None);
+    }
+}
@@ -2,6 +2,7 @@
 #![allow(clippy::print_stderr)] // tests
 
 mod test_enrichment;
+mod test_protobuf;
 
 use std::{str::FromStr, time::Instant};
 
@@ -184,6 +185,7 @@ fn main() {
         let external_env = vrl::state::ExternalEnv::default();
         let mut config = CompileConfig::default();
         config.set_custom(test_enrichment.clone());
+        config.set_custom(test_protobuf::test_protobuf_descriptors());
 
         // Set some read-only paths that can be tested
         for (path, recursive) in &test.read_only_paths {
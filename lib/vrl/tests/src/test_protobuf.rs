@@ -0,0 +1,29 @@
+use prost::Message;
+use prost_types::{
+    field_descriptor_proto::{Label, Type},
+    DescriptorProto, FieldDescriptorProto, FileDescriptorProto, FileDescriptorSet,
+};
+
+/// Descriptors for the `example.Event` message of the `parse_proto` and `encode_proto` examples.
+pub(crate) fn test_protobuf_descriptors() -> stdlib::ProtobufDescriptors {
+    let file = FileDescriptorProto {
+        name: Some("example.proto".to_owned()),
+        package: Some("example".to_owned()),
+        syntax: Some("proto3".to_owned()),
+        message_type: vec![DescriptorProto {
+            name: Some("Event".to_owned()),
+            field: vec![FieldDescriptorProto {
+                name: Some("message".to_owned()),
+                number: Some(1),
+                r#type: Some(Type::String as i32),
+                label: Some(Label::Optional as i32),
+                ..Default::default()
+            }],
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+
+    let set = FileDescriptorSet { file: vec![file] };
+    stdlib::ProtobufDescriptors::decode(&set.encode_to_vec()).expect("valid descriptor set")
+}
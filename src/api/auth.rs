@@ -0,0 +1,255 @@
+use std::{collections::HashSet, sync::Arc};
+
+use async_graphql::{Context, Guard};
+use vector_common::sensitive_string::SensitiveString;
+
+use crate::{
+    config::api::{ApiScope, ApiToken},
+    event::Metric,
+};
+
+/// What the token of a request grants access to.
+#[derive(Clone, Debug)]
+pub struct ApiAccess {
+    scopes: HashSet<ApiScope>,
+    components: ComponentAccess,
+}
+
+impl ApiAccess {
+    fn new(token: &ApiToken) -> Self {
+        Self {
+            scopes: token.scopes.iter().copied().collect(),
+            components: token
+                .components
+                .as_deref()
+                .map_or_else(ComponentAccess::all, ComponentAccess::only),
+        }
+    }
+
+    pub fn allows(&self, scope: ApiScope) -> bool {
+        self.scopes.contains(&scope)
+    }
+}
+
+/// The components a request has access to.
+#[derive(Clone, Debug, Default)]
+pub struct ComponentAccess(Option<Arc<Vec<glob::Pattern>>>);
+
+impl ComponentAccess {
+    /// Access to all components.
+    pub const fn all() -> Self {
+        Self(None)
+    }
+
+    /// Returns the component access of the request in `ctx`. Without API tokens configured,
+    /// there is no access in the context, and all components are accessible.
+    pub fn from_context(ctx: &Context<'_>) -> Self {
+        ctx.data_opt::<ApiAccess>()
+            .map(|access| access.components.clone())
+            .unwrap_or_default()
+    }
+
+    /// Access to the components matching any of the glob `patterns`.
+    pub fn only(patterns: &[String]) -> Self {
+        // Patterns are validated when loading the config.
+        Self(Some(Arc::new(
+            patterns
+                .iter()
+                .filter_map(|pattern| glob::Pattern::new(pattern).ok())
+                .collect(),
+        )))
+    }
+
+    pub fn allows(&self, component_id: &str) -> bool {
+        self.0.as_ref().map_or(true, |patterns| {
+            patterns.iter().any(|pattern| pattern.matches(component_id))
+        })
+    }
+
+    /// Whether the component a component metric is tagged with is accessible.
+    pub fn allows_metric(&self, metric: &Metric) -> bool {
+        self.0.is_none()
+            || metric
+                .tag_value("component_id")
+                .map_or(false, |component_id| self.allows(&component_id))
+    }
+}
+
+/// Rejects GraphQL fields for requests whose token lacks the scope.
+pub struct ScopeGuard(ApiScope);
+
+impl ScopeGuard {
+    pub const fn new(scope: ApiScope) -> Self {
+        Self(scope)
+    }
+}
+
+#[async_trait::async_trait]
+impl Guard for ScopeGuard {
+    async fn check(&self, ctx: &Context<'_>) -> async_graphql::Result<()> {
+        match ctx.data_opt::<ApiAccess>() {
+            Some(access) if !access.allows(self.0) => {
+                Err(format!("The API token doesn't grant the {:?} scope.", self.0).into())
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Rejection of requests without a valid token.
+#[derive(Debug)]
+pub struct Unauthorized;
+
+impl warp::reject::Reject for Unauthorized {}
+
+/// Rejection of requests whose token lacks the scope of the endpoint.
+#[derive(Debug)]
+pub struct Forbidden;
+
+impl warp::reject::Reject for Forbidden {}
+
+/// Checks the tokens of requests against the configured API tokens.
+#[derive(Clone, Debug)]
+pub struct Authenticator {
+    tokens: Arc<Vec<(SensitiveString, ApiAccess)>>,
+}
+
+impl Authenticator {
+    pub fn new(tokens: &[ApiToken]) -> Self {
+        Self {
+            tokens: Arc::new(
+                tokens
+                    .iter()
+                    .map(|token| (token.token.clone(), ApiAccess::new(token)))
+                    .collect(),
+            ),
+        }
+    }
+
+    /// Returns the access granted by the `Authorization` header of a request, or `None` if no
+    /// tokens are configured.
+    pub fn authenticate(
+        &self,
+        authorization: Option<&str>,
+    ) -> Result<Option<ApiAccess>, Unauthorized> {
+        if self.tokens.is_empty() {
+            return Ok(None);
+        }
+
+        let token = authorization
+            .and_then(|header| header.strip_prefix("Bearer "))
+            .ok_or(Unauthorized)?
+            .trim();
+        // Every token is compared in constant time, so that the time taken doesn't reveal how much
+        // of a token matched. `memcmp::eq` requires slices of the same length.
+        let mut granted = None;
+        for (expected, access) in self.tokens.iter() {
+            let expected = expected.inner().as_bytes();
+            if expected.len() == token.len()
+                && openssl::memcmp::eq(expected, token.as_bytes())
+                && granted.is_none()
+            {
+                granted = Some(access.clone());
+            }
+        }
+        granted.map(Some).ok_or(Unauthorized)
+    }
+
+    /// Checks that the `Authorization` header of a request carries a token granting `scope`.
+    ///
+    /// Unlike `authenticate`, this requires a token even if no tokens are configured, so that
+    /// endpoints guarded by it are never left open.
+    pub fn authorize(
+        &self,
+        authorization: Option<&str>,
+        scope: ApiScope,
+    ) -> Result<(), warp::Rejection> {
+        match self.authenticate(authorization) {
+            Ok(Some(access)) if access.allows(scope) => Ok(()),
+            Ok(Some(_)) => Err(warp::reject::custom(Forbidden)),
+            Ok(None) | Err(Unauthorized) => Err(warp::reject::custom(Unauthorized)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn authenticator() -> Authenticator {
+        Authenticator::new(&[
+            ApiToken {
+                token: "admin".to_owned().into(),
+                scopes: vec![ApiScope::ReadMetrics, ApiScope::Tap, ApiScope::Control],
+                components: None,
+            },
+            ApiToken {
+                token: "team-a".to_owned().into(),
+                scopes: vec![ApiScope::ReadMetrics],
+                components: Some(vec!["team_a_*".to_owned()]),
+            },
+        ])
+    }
+
+    #[test]
+    fn no_tokens_allows_everything() {
+        assert!(matches!(
+            Authenticator::new(&[]).authenticate(None),
+            Ok(None)
+        ));
+    }
+
+    #[test]
+    fn rejects_missing_and_unknown_tokens() {
+        let authenticator = authenticator();
+
+        assert!(authenticator.authenticate(None).is_err());
+        assert!(authenticator.authenticate(Some("Bearer other")).is_err());
+        assert!(authenticator.authenticate(Some("admin")).is_err());
+    }
+
+    #[test]
+    fn grants_scopes_and_components() {
+        let authenticator = authenticator();
+
+        let admin = authenticator
+            .authenticate(Some("Bearer admin"))
+            .unwrap()
+            .unwrap();
+        assert!(admin.allows(ApiScope::Tap));
+        assert!(admin.components.allows("team_b_logs"));
+
+        let team_a = authenticator
+            .authenticate(Some("Bearer team-a"))
+            .unwrap()
+            .unwrap();
+        assert!(team_a.allows(ApiScope::ReadMetrics));
+        assert!(!team_a.allows(ApiScope::Tap));
+        assert!(team_a.components.allows("team_a_logs"));
+        assert!(!team_a.components.allows("team_b_logs"));
+    }
+
+    #[test]
+    fn authorizes_scoped_tokens_only() {
+        let authenticator = authenticator();
+
+        assert!(authenticator
+            .authorize(Some("Bearer admin"), ApiScope::Control)
+            .is_ok());
+        assert!(authenticator
+            .authorize(Some("Bearer team-a"), ApiScope::Control)
+            .unwrap_err()
+            .find::<Forbidden>()
+            .is_some());
+        assert!(authenticator
+            .authorize(None, ApiScope::Control)
+            .unwrap_err()
+            .find::<Unauthorized>()
+            .is_some());
+        assert!(Authenticator::new(&[])
+            .authorize(None, ApiScope::Control)
+            .unwrap_err()
+            .find::<Unauthorized>()
+            .is_some());
+    }
+}
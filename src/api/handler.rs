@@ -6,6 +6,9 @@ use std::sync::{
 use serde_json::json;
 use warp::{reply::json, Rejection, Reply};
 
+use super::auth::{Forbidden, Unauthorized};
+use crate::signal::{SignalTo, SignalTx};
+
 // Health handler, responds with '{ ok: true }' when running and '{ ok: false}'
 // when shutting down
 pub(super) async fn health(running: Arc<AtomicBool>) -> Result<impl Reply, Rejection> {
@@ -21,3 +24,45 @@ pub(super) async fn health(running: Arc<AtomicBool>) -> Result<impl Reply, Rejec
         ))
     }
 }
+
+// Admin handler, forwards the signal to the application, the same way as the equivalent OS
+// signal, and responds with '202 Accepted' once it's queued
+pub(super) async fn signal(signal: SignalTo, tx: SignalTx) -> Result<impl Reply, Rejection> {
+    info!(message = "Received signal through the API.", ?signal);
+    if tx.send(signal).is_ok() {
+        Ok(warp::reply::with_status(
+            json(&json!({"ok": true})),
+            warp::http::StatusCode::ACCEPTED,
+        ))
+    } else {
+        Ok(warp::reply::with_status(
+            json(&json!({"ok": false})),
+            warp::http::StatusCode::SERVICE_UNAVAILABLE,
+        ))
+    }
+}
+
+// Unauthorized handler, responds with '401 Unauthorized' to requests without a valid API token
+// and with '403 Forbidden' to requests whose token lacks the scope of the endpoint, and passes on
+// all other rejections
+pub(super) async fn unauthorized(rejection: Rejection) -> Result<impl Reply, Rejection> {
+    if rejection.find::<Unauthorized>().is_some() {
+        Ok(warp::reply::with_header(
+            warp::reply::with_status(
+                json(&json!({"error": "A valid API token is required."})),
+                warp::http::StatusCode::UNAUTHORIZED,
+            ),
+            "WWW-Authenticate",
+            "Bearer",
+        )
+        .into_response())
+    } else if rejection.find::<Forbidden>().is_some() {
+        Ok(warp::reply::with_status(
+            json(&json!({"error": "The API token doesn't grant access to this endpoint."})),
+            warp::http::StatusCode::FORBIDDEN,
+        )
+        .into_response())
+    } else {
+        Err(rejection)
+    }
+}
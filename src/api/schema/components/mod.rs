@@ -8,19 +8,22 @@ use std::{
     collections::{HashMap, HashSet},
 };
 
-use async_graphql::{Enum, InputObject, Interface, Object, Subscription};
+use async_graphql::{Context, Enum, InputObject, Interface, Object, Subscription};
 use once_cell::sync::Lazy;
 use tokio_stream::{wrappers::BroadcastStream, Stream, StreamExt};
 use vector_config::NamedComponent;
 use vector_core::internal_event::DEFAULT_OUTPUT;
 
 use crate::{
-    api::schema::{
-        components::state::component_by_component_key,
-        filter::{self, filter_items},
-        relay, sort,
+    api::{
+        auth::{ComponentAccess, ScopeGuard},
+        schema::{
+            components::state::component_by_component_key,
+            filter::{self, filter_items},
+            relay, sort,
+        },
     },
-    config::{ComponentKey, Config, TransformConfig},
+    config::{api::ApiScope, ComponentKey, Config, TransformConfig},
     filter_check,
 };
 use crate::{config::SourceConfig, topology::schema::merged_definition};
@@ -112,8 +115,10 @@ pub struct ComponentsQuery;
 #[Object]
 impl ComponentsQuery {
     /// Configured components (sources/transforms/sinks)
+    #[graphql(guard = "ScopeGuard::new(ApiScope::ReadMetrics)")]
     async fn components(
         &self,
+        ctx: &Context<'_>,
         after: Option<String>,
         before: Option<String>,
         first: Option<i32>,
@@ -122,7 +127,13 @@ impl ComponentsQuery {
         sort: Option<Vec<sort::SortField<ComponentsSortFieldName>>>,
     ) -> relay::ConnectionResult<Component> {
         let filter = filter.unwrap_or_default();
-        let mut components = filter_items(state::get_components().into_iter(), &filter);
+        let access = ComponentAccess::from_context(ctx);
+        let mut components = filter_items(
+            state::get_components()
+                .into_iter()
+                .filter(|c| access.allows(c.get_component_key().id())),
+            &filter,
+        );
 
         if let Some(sort_fields) = sort {
             sort::by_fields(&mut components, &sort_fields);
@@ -137,8 +148,10 @@ impl ComponentsQuery {
     }
 
     /// Configured sources
+    #[graphql(guard = "ScopeGuard::new(ApiScope::ReadMetrics)")]
     async fn sources(
         &self,
+        ctx: &Context<'_>,
         after: Option<String>,
         before: Option<String>,
         first: Option<i32>,
@@ -147,7 +160,13 @@ impl ComponentsQuery {
         sort: Option<Vec<sort::SortField<source::SourcesSortFieldName>>>,
     ) -> relay::ConnectionResult<source::Source> {
         let filter = filter.unwrap_or_default();
-        let mut sources = filter_items(state::get_sources().into_iter(), &filter);
+        let access = ComponentAccess::from_context(ctx);
+        let mut sources = filter_items(
+            state::get_sources()
+                .into_iter()
+                .filter(|c| access.allows(c.0.component_key.id())),
+            &filter,
+        );
 
         if let Some(sort_fields) = sort {
             sort::by_fields(&mut sources, &sort_fields);
@@ -162,8 +181,10 @@ impl ComponentsQuery {
     }
 
     /// Configured transforms
+    #[graphql(guard = "ScopeGuard::new(ApiScope::ReadMetrics)")]
     async fn transforms(
         &self,
+        ctx: &Context<'_>,
         after: Option<String>,
         before: Option<String>,
         first: Option<i32>,
@@ -172,7 +193,13 @@ impl ComponentsQuery {
         sort: Option<Vec<sort::SortField<transform::TransformsSortFieldName>>>,
     ) -> relay::ConnectionResult<transform::Transform> {
         let filter = filter.unwrap_or_default();
-        let mut transforms = filter_items(state::get_transforms().into_iter(), &filter);
+        let access = ComponentAccess::from_context(ctx);
+        let mut transforms = filter_items(
+            state::get_transforms()
+                .into_iter()
+                .filter(|c| access.allows(c.0.component_key.id())),
+            &filter,
+        );
 
         if let Some(sort_fields) = sort {
             sort::by_fields(&mut transforms, &sort_fields);
@@ -187,8 +214,10 @@ impl ComponentsQuery {
     }
 
     /// Configured sinks
+    #[graphql(guard = "ScopeGuard::new(ApiScope::ReadMetrics)")]
     async fn sinks(
         &self,
+        ctx: &Context<'_>,
         after: Option<String>,
         before: Option<String>,
         first: Option<i32>,
@@ -197,7 +226,13 @@ impl ComponentsQuery {
         sort: Option<Vec<sort::SortField<sink::SinksSortFieldName>>>,
     ) -> relay::ConnectionResult<sink::Sink> {
         let filter = filter.unwrap_or_default();
-        let mut sinks = filter_items(state::get_sinks().into_iter(), &filter);
+        let access = ComponentAccess::from_context(ctx);
+        let mut sinks = filter_items(
+            state::get_sinks()
+                .into_iter()
+                .filter(|c| access.allows(c.0.component_key.id())),
+            &filter,
+        );
 
         if let Some(sort_fields) = sort {
             sort::by_fields(&mut sinks, &sort_fields);
@@ -212,7 +247,15 @@ impl ComponentsQuery {
     }
 
     /// Gets a configured component by component_key
-    async fn component_by_component_key(&self, component_id: String) -> Option<Component> {
+    #[graphql(guard = "ScopeGuard::new(ApiScope::ReadMetrics)")]
+    async fn component_by_component_key(
+        &self,
+        ctx: &Context<'_>,
+        component_id: String,
+    ) -> Option<Component> {
+        if !ComponentAccess::from_context(ctx).allows(&component_id) {
+            return None;
+        }
         let key = ComponentKey::from(component_id);
         component_by_component_key(&key)
     }
@@ -236,17 +279,23 @@ pub struct ComponentsSubscription;
 #[Subscription]
 impl ComponentsSubscription {
     /// Subscribes to all newly added components
-    async fn component_added(&self) -> impl Stream<Item = Component> {
-        BroadcastStream::new(COMPONENT_CHANGED.subscribe()).filter_map(|c| match c {
-            Ok(ComponentChanged::Added(c)) => Some(c),
+    #[graphql(guard = "ScopeGuard::new(ApiScope::ReadMetrics)")]
+    async fn component_added(&self, ctx: &Context<'_>) -> impl Stream<Item = Component> {
+        let access = ComponentAccess::from_context(ctx);
+        BroadcastStream::new(COMPONENT_CHANGED.subscribe()).filter_map(move |c| match c {
+            Ok(ComponentChanged::Added(c)) if access.allows(c.get_component_key().id()) => Some(c),
             _ => None,
         })
     }
 
     /// Subscribes to all removed components
-    async fn component_removed(&self) -> impl Stream<Item = Component> {
-        BroadcastStream::new(COMPONENT_CHANGED.subscribe()).filter_map(|c| match c {
-            Ok(ComponentChanged::Removed(c)) => Some(c),
+    #[graphql(guard = "ScopeGuard::new(ApiScope::ReadMetrics)")]
+    async fn component_removed(&self, ctx: &Context<'_>) -> impl Stream<Item = Component> {
+        let access = ComponentAccess::from_context(ctx);
+        BroadcastStream::new(COMPONENT_CHANGED.subscribe()).filter_map(move |c| match c {
+            Ok(ComponentChanged::Removed(c)) if access.allows(c.get_component_key().id()) => {
+                Some(c)
+            }
             _ => None,
         })
     }
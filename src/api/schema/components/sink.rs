@@ -1,13 +1,16 @@
 use std::cmp;
 
-use async_graphql::{Enum, InputObject, Object};
+use async_graphql::{Context, Enum, InputObject, Object};
 
 use super::{source, state, transform, Component};
 use crate::{
-    api::schema::{
-        filter,
-        metrics::{self, IntoSinkMetrics},
-        sort,
+    api::{
+        auth::ComponentAccess,
+        schema::{
+            filter,
+            metrics::{self, IntoSinkMetrics},
+            sort,
+        },
     },
     config::{ComponentKey, OutputId},
     filter_check,
@@ -90,10 +93,12 @@ impl Sink {
     }
 
     /// Source inputs
-    pub async fn sources(&self) -> Vec<source::Source> {
+    pub async fn sources(&self, ctx: &Context<'_>) -> Vec<source::Source> {
+        let access = ComponentAccess::from_context(ctx);
         self.0
             .inputs
             .iter()
+            .filter(|output_id| access.allows(output_id.component.id()))
             .filter_map(|output_id| match state::component_by_output_id(output_id) {
                 Some(Component::Source(s)) => Some(s),
                 _ => None,
@@ -102,10 +107,12 @@ impl Sink {
     }
 
     /// Transform inputs
-    pub async fn transforms(&self) -> Vec<transform::Transform> {
+    pub async fn transforms(&self, ctx: &Context<'_>) -> Vec<transform::Transform> {
+        let access = ComponentAccess::from_context(ctx);
         self.0
             .inputs
             .iter()
+            .filter(|output_id| access.allows(output_id.component.id()))
             .filter_map(|output_id| match state::component_by_output_id(output_id) {
                 Some(Component::Transform(t)) => Some(t),
                 _ => None,
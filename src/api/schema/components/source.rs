@@ -1,13 +1,16 @@
 use std::cmp;
 
-use async_graphql::{Enum, InputObject, Object};
+use async_graphql::{Context, Enum, InputObject, Object};
 
 use super::{sink, state, transform, Component};
 use crate::{
-    api::schema::{
-        filter,
-        metrics::{self, outputs_by_component_key, IntoSourceMetrics, Output},
-        sort,
+    api::{
+        auth::ComponentAccess,
+        schema::{
+            filter,
+            metrics::{self, outputs_by_component_key, IntoSourceMetrics, Output},
+            sort,
+        },
     },
     config::{ComponentKey, DataType, OutputId},
     filter_check,
@@ -112,8 +115,10 @@ impl Source {
     }
 
     /// Transform outputs
-    pub async fn transforms(&self) -> Vec<transform::Transform> {
-        state::filter_components(|(_component_key, components)| match components {
+    pub async fn transforms(&self, ctx: &Context<'_>) -> Vec<transform::Transform> {
+        let access = ComponentAccess::from_context(ctx);
+        state::filter_components(|(component_key, components)| match components {
+            _ if !access.allows(component_key.id()) => None,
             Component::Transform(t)
                 if t.0.inputs.contains(&OutputId::from(&self.0.component_key)) =>
             {
@@ -124,8 +129,10 @@ impl Source {
     }
 
     /// Sink outputs
-    pub async fn sinks(&self) -> Vec<sink::Sink> {
-        state::filter_components(|(_component_key, components)| match components {
+    pub async fn sinks(&self, ctx: &Context<'_>) -> Vec<sink::Sink> {
+        let access = ComponentAccess::from_context(ctx);
+        state::filter_components(|(component_key, components)| match components {
+            _ if !access.allows(component_key.id()) => None,
             Component::Sink(s) if s.0.inputs.contains(&OutputId::from(&self.0.component_key)) => {
                 Some(s.clone())
             }
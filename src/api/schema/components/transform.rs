@@ -1,13 +1,16 @@
 use std::cmp;
 
-use async_graphql::{Enum, InputObject, Object};
+use async_graphql::{Context, Enum, InputObject, Object};
 
 use super::{sink, source, state, Component};
 use crate::{
-    api::schema::{
-        filter,
-        metrics::{self, outputs_by_component_key, IntoTransformMetrics, Output},
-        sort,
+    api::{
+        auth::ComponentAccess,
+        schema::{
+            filter,
+            metrics::{self, outputs_by_component_key, IntoTransformMetrics, Output},
+            sort,
+        },
     },
     config::{ComponentKey, OutputId},
     filter_check,
@@ -73,10 +76,12 @@ impl Transform {
     }
 
     /// Source inputs
-    pub async fn sources(&self) -> Vec<source::Source> {
+    pub async fn sources(&self, ctx: &Context<'_>) -> Vec<source::Source> {
+        let access = ComponentAccess::from_context(ctx);
         self.0
             .inputs
             .iter()
+            .filter(|output_id| access.allows(output_id.component.id()))
             .filter_map(|output_id| match state::component_by_output_id(output_id) {
                 Some(Component::Source(s)) => Some(s),
                 _ => None,
@@ -85,8 +90,10 @@ impl Transform {
     }
 
     /// Transform outputs
-    pub async fn transforms(&self) -> Vec<Transform> {
-        state::filter_components(|(_component_key, components)| match components {
+    pub async fn transforms(&self, ctx: &Context<'_>) -> Vec<Transform> {
+        let access = ComponentAccess::from_context(ctx);
+        state::filter_components(|(component_key, components)| match components {
+            _ if !access.allows(component_key.id()) => None,
             Component::Transform(t)
                 if t.0.inputs.contains(&OutputId::from(&self.0.component_key)) =>
             {
@@ -97,8 +104,10 @@ impl Transform {
     }
 
     /// Sink outputs
-    pub async fn sinks(&self) -> Vec<sink::Sink> {
-        state::filter_components(|(_component_key, components)| match components {
+    pub async fn sinks(&self, ctx: &Context<'_>) -> Vec<sink::Sink> {
+        let access = ComponentAccess::from_context(ctx);
+        state::filter_components(|(component_key, components)| match components {
+            _ if !access.allows(component_key.id()) => None,
             Component::Sink(s) if s.0.inputs.contains(&OutputId::from(&self.0.component_key)) => {
                 Some(s.clone())
             }
@@ -0,0 +1,81 @@
+use std::sync::RwLock;
+
+use async_graphql::Object;
+use once_cell::sync::Lazy;
+
+use crate::{
+    api::auth::ScopeGuard,
+    config::{api::ApiScope, util::json::serialize_resolved, Config, ConfigBuilder},
+};
+
+/// The resolved configuration of the running topology, serialized to JSON.
+static RESOLVED_CONFIG: Lazy<RwLock<String>> = Lazy::new(Default::default);
+
+#[derive(Default)]
+pub struct ConfigQuery;
+
+#[Object]
+impl ConfigQuery {
+    /// JSON configuration of the running topology, as Vector resolved it: with environment
+    /// variables interpolated and defaults filled in, but with sensitive values, such as
+    /// passwords, masked
+    #[graphql(guard = "ScopeGuard::new(ApiScope::Control)")]
+    async fn resolved_config(&self) -> String {
+        RESOLVED_CONFIG
+            .read()
+            .expect("Couldn't acquire lock on the resolved config. Please report this.")
+            .clone()
+    }
+}
+
+/// Updates the resolved configuration with the config of the running topology. If it can't be
+/// serialized, the configuration of the previous topology is cleared rather than kept.
+pub fn update_config(config: &Config) -> serde_json::Result<()> {
+    let json = serialize_resolved(&ConfigBuilder::from(config.clone()), false);
+
+    let mut resolved = RESOLVED_CONFIG
+        .write()
+        .expect("Couldn't acquire lock on the resolved config. Please report this.");
+    match json {
+        Ok(json) => {
+            *resolved = json;
+            Ok(())
+        }
+        Err(error) => {
+            resolved.clear();
+            Err(error)
+        }
+    }
+}
+
+#[cfg(all(test, feature = "sources-demo_logs", feature = "sinks-blackhole"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn masks_sensitive_values() {
+        let config = ConfigBuilder::from_toml(
+            r#"
+            [api]
+            tokens = [{ token = "secret-token", scopes = ["control"] }]
+
+            [sources.in]
+            type = "demo_logs"
+            format = "syslog"
+
+            [sinks.out]
+            type = "blackhole"
+            inputs = ["in"]
+        "#,
+        )
+        .build()
+        .unwrap();
+
+        update_config(&config).unwrap();
+
+        let json: serde_json::Value =
+            serde_json::from_str(&RESOLVED_CONFIG.read().unwrap()).unwrap();
+        assert_eq!(json["sources"]["in"]["format"], "syslog");
+        assert_eq!(json["api"]["tokens"][0]["token"], "**REDACTED**");
+    }
+}
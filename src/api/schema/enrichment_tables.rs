@@ -0,0 +1,80 @@
+use async_graphql::{Context, Object, SimpleObject};
+use chrono::{DateTime, Utc};
+
+use crate::{
+    api::auth::ScopeGuard,
+    config::api::ApiScope,
+    enrichment_tables::status,
+    signal::{SignalTo, SignalTx},
+};
+
+/// The reload status of an enrichment table
+#[derive(SimpleObject)]
+pub struct EnrichmentTable {
+    /// Enrichment table name
+    name: String,
+
+    /// Number of rows loaded, for tables made of rows, such as `file` tables
+    rows: Option<i64>,
+
+    /// When the table was last loaded
+    last_reload_at: Option<DateTime<Utc>>,
+
+    /// Error of the last failed reload, if the table hasn't loaded since
+    last_error: Option<String>,
+
+    /// Number of failed reloads
+    reload_errors: i64,
+}
+
+impl EnrichmentTable {
+    fn new(name: String, status: status::TableStatus) -> Self {
+        Self {
+            name,
+            rows: status.rows.map(|rows| rows as i64),
+            last_reload_at: status.last_reload,
+            last_error: status.last_error,
+            reload_errors: status.reload_errors as i64,
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct EnrichmentTablesQuery;
+
+#[Object]
+impl EnrichmentTablesQuery {
+    /// Enrichment tables of the running topology, with their reload status
+    async fn enrichment_tables(&self) -> Vec<EnrichmentTable> {
+        status::statuses()
+            .into_iter()
+            .map(|(name, status)| EnrichmentTable::new(name, status))
+            .collect()
+    }
+}
+
+#[derive(Default)]
+pub struct EnrichmentTablesMutation;
+
+#[Object]
+impl EnrichmentTablesMutation {
+    /// Reloads an enrichment table, even if its data hasn't changed. The configuration and the
+    /// other components aren't reloaded. Returns once the reload is requested, the
+    /// `enrichmentTables` query reports its outcome
+    #[graphql(guard = "ScopeGuard::new(ApiScope::Control)")]
+    async fn reload_enrichment_table(
+        &self,
+        ctx: &Context<'_>,
+        name: String,
+    ) -> async_graphql::Result<bool> {
+        if !status::contains(&name) {
+            return Err(format!("Enrichment table {:?} doesn't exist.", name).into());
+        }
+
+        info!(message = "Enrichment table reload requested through the API.", table = %name);
+        ctx.data::<SignalTx>()?
+            .send(SignalTo::ReloadEnrichmentTable(name))
+            .map_err(|_| "Vector is shutting down.")?;
+        Ok(true)
+    }
+}
@@ -15,7 +15,14 @@ use rand::{rngs::SmallRng, Rng, SeedableRng};
 use tokio::{select, sync::mpsc, time};
 use tokio_stream::wrappers::ReceiverStream;
 
-use crate::{api::tap::TapController, topology::WatchRx};
+use crate::{
+    api::{
+        auth::{ComponentAccess, ScopeGuard},
+        tap::TapController,
+    },
+    config::api::ApiScope,
+    topology::WatchRx,
+};
 
 /// Patterns (glob) used by tap to match against components and access events
 /// flowing into (for_inputs) or out of (for_outputs) specified components
@@ -23,6 +30,10 @@ use crate::{api::tap::TapController, topology::WatchRx};
 pub struct TapPatterns {
     pub for_outputs: HashSet<String>,
     pub for_inputs: HashSet<String>,
+    /// The components that can be tapped, regardless of the patterns.
+    pub components: ComponentAccess,
+    /// Only one of every `sample_rate` events of each matched output is tapped.
+    pub sample_rate: u32,
 }
 
 impl TapPatterns {
@@ -30,6 +41,8 @@ impl TapPatterns {
         Self {
             for_outputs,
             for_inputs,
+            components: ComponentAccess::all(),
+            sample_rate: 1,
         }
     }
 
@@ -49,6 +62,7 @@ pub struct EventsSubscription;
 #[Subscription]
 impl EventsSubscription {
     /// A stream of events emitted from matched component ID patterns
+    #[graphql(guard = "ScopeGuard::new(ApiScope::Tap)")]
     pub async fn output_events_by_component_id_patterns<'a>(
         &'a self,
         ctx: &'a Context<'a>,
@@ -56,12 +70,15 @@ impl EventsSubscription {
         inputs_patterns: Option<Vec<String>>,
         #[graphql(default = 500)] interval: u32,
         #[graphql(default = 100, validator(minimum = 1, maximum = 10_000))] limit: u32,
+        #[graphql(default = 1, validator(minimum = 1))] sample_rate: u32,
     ) -> impl Stream<Item = Vec<OutputEventsPayload>> + 'a {
         let watch_rx = ctx.data_unchecked::<WatchRx>().clone();
 
         let patterns = TapPatterns {
             for_outputs: outputs_patterns.into_iter().collect(),
             for_inputs: inputs_patterns.unwrap_or_default().into_iter().collect(),
+            components: ComponentAccess::from_context(ctx),
+            sample_rate,
         };
         // Client input is confined to `u32` to provide sensible bounds.
         create_events_stream(watch_rx, patterns, interval as u64, limit as usize)
@@ -0,0 +1,176 @@
+use async_graphql::{Context, Enum, SimpleObject, Subscription};
+use chrono::{DateTime, Utc};
+use futures::{future::ready, Stream, StreamExt};
+use lookup::event_path;
+
+use crate::{
+    api::auth::{ComponentAccess, ScopeGuard},
+    config::api::ApiScope,
+    event::LogEvent,
+    trace::TraceSubscription,
+};
+
+/// Kind of internal event
+#[derive(Enum, Copy, Clone, Debug, Eq, PartialEq)]
+pub enum InternalEventKind {
+    /// A component failed to process events, or to perform one of its other tasks
+    Error,
+
+    /// A component discarded events
+    Discard,
+}
+
+/// Error or discard logged by a component
+#[derive(SimpleObject, Debug, Clone)]
+pub struct InternalEvent {
+    /// Kind of internal event
+    kind: InternalEventKind,
+
+    /// Component ID
+    component_id: String,
+
+    /// Component kind, such as `source`
+    component_kind: String,
+
+    /// Component type, such as `http`
+    component_type: String,
+
+    /// Time at which the internal event was logged
+    timestamp: Option<DateTime<Utc>>,
+
+    /// Logged message
+    message: String,
+
+    /// Error description, for errors
+    error: Option<String>,
+
+    /// Error type, such as `parser_failed`, for errors
+    error_type: Option<String>,
+
+    /// Stage of the component the error occurred in, such as `processing`, for errors
+    stage: Option<String>,
+
+    /// Reason the events were discarded for, for discards
+    reason: Option<String>,
+
+    /// Whether the events were discarded on purpose, such as by a filter, for discards
+    intentional: Option<bool>,
+
+    /// Number of discarded events, for discards
+    count: Option<i64>,
+}
+
+impl InternalEvent {
+    /// Reads an internal log of a component, returning `None` if it isn't an error or a discard.
+    fn from_log(log: &LogEvent) -> Option<Self> {
+        let string = |field: &str| log.get(field).map(|value| value.to_string_lossy());
+        let span_field = |field: &str| {
+            log.get(event_path!("vector", field))
+                .map(|value| value.to_string_lossy())
+        };
+
+        let kind = if log.contains("error_type") {
+            InternalEventKind::Error
+        } else if log.contains("intentional") && log.contains("reason") {
+            InternalEventKind::Discard
+        } else {
+            return None;
+        };
+
+        Some(Self {
+            kind,
+            component_id: span_field("component_id")?,
+            component_kind: span_field("component_kind").unwrap_or_default(),
+            component_type: span_field("component_type").unwrap_or_default(),
+            timestamp: log
+                .get("timestamp")
+                .and_then(|value| value.as_timestamp())
+                .copied(),
+            message: string("message").unwrap_or_default(),
+            error: string("error"),
+            error_type: string("error_type"),
+            stage: string("stage"),
+            reason: string("reason"),
+            intentional: log.get("intentional").and_then(|value| value.as_boolean()),
+            count: log.get("count").and_then(|value| value.as_integer()),
+        })
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct InternalEventsSubscription;
+
+#[Subscription]
+impl InternalEventsSubscription {
+    /// Errors and discarded events of the components matching the optional glob
+    /// `component_id_patterns`, streamed as they are logged. Like internal logs, they are subject
+    /// to the log level and rate limiting, so intentional discards are only streamed at the
+    /// `debug` level.
+    #[graphql(guard = "ScopeGuard::new(ApiScope::ReadMetrics)")]
+    async fn internal_events(
+        &self,
+        ctx: &Context<'_>,
+        kinds: Option<Vec<InternalEventKind>>,
+        component_id_patterns: Option<Vec<String>>,
+    ) -> impl Stream<Item = InternalEvent> {
+        let access = ComponentAccess::from_context(ctx);
+        let patterns = component_id_patterns
+            .map(|patterns| ComponentAccess::only(&patterns))
+            .unwrap_or_default();
+
+        TraceSubscription::subscribe()
+            .into_stream()
+            .filter_map(move |log| {
+                ready(InternalEvent::from_log(&log).filter(|event| {
+                    kinds
+                        .as_ref()
+                        .map_or(true, |kinds| kinds.contains(&event.kind))
+                        && access.allows(&event.component_id)
+                        && patterns.allows(&event.component_id)
+                }))
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_component_errors_and_discards() {
+        let mut log = LogEvent::from("Failed to parse.");
+        log.insert("error", "invalid syntax");
+        log.insert("error_type", "parser_failed");
+        log.insert("stage", "processing");
+        log.insert(event_path!("vector", "component_id"), "parse");
+        log.insert(event_path!("vector", "component_kind"), "transform");
+        log.insert(event_path!("vector", "component_type"), "remap");
+
+        let event = InternalEvent::from_log(&log).unwrap();
+        assert_eq!(event.kind, InternalEventKind::Error);
+        assert_eq!(event.component_id, "parse");
+        assert_eq!(event.component_type, "remap");
+        assert_eq!(event.error_type.as_deref(), Some("parser_failed"));
+        assert_eq!(event.reason, None);
+
+        let mut log = LogEvent::from("Events dropped");
+        log.insert("intentional", false);
+        log.insert("reason", "Failed to render template.");
+        log.insert("count", 2_i64);
+        log.insert(event_path!("vector", "component_id"), "out");
+
+        let event = InternalEvent::from_log(&log).unwrap();
+        assert_eq!(event.kind, InternalEventKind::Discard);
+        assert_eq!(event.intentional, Some(false));
+        assert_eq!(event.count, Some(2));
+
+        // Logs outside of components, and other logs of components, aren't internal events.
+        let mut log = LogEvent::from("Failed to load config.");
+        log.insert("error_type", "configuration_failed");
+        assert!(InternalEvent::from_log(&log).is_none());
+
+        let mut log = LogEvent::from("Healthcheck passed.");
+        log.insert(event_path!("vector", "component_id"), "out");
+        assert!(InternalEvent::from_log(&log).is_none());
+    }
+}
@@ -0,0 +1,46 @@
+use async_graphql::{Object, SimpleObject};
+use chrono::{DateTime, Utc};
+use tokio::time::Duration;
+
+use crate::{api::auth::ScopeGuard, config::api::ApiScope, trace};
+
+/// The filter of Vector's internal logs
+#[derive(SimpleObject)]
+pub struct LogFilter {
+    /// Filter directives, such as `info,vector::sinks::http=debug`
+    filter: String,
+
+    /// When the filter reverts to the one set at startup, if it's temporary
+    expires_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Default)]
+pub struct LoggingMutation;
+
+#[Object]
+impl LoggingMutation {
+    /// Replaces the filter of internal logs, in the same format as `VECTOR_LOG`, until `ttlSecs`
+    /// elapse and the filter set at startup is restored
+    #[graphql(guard = "ScopeGuard::new(ApiScope::Control)")]
+    async fn set_log_filter(
+        &self,
+        filter: String,
+        #[graphql(default = 600, validator(minimum = 1, maximum = 86_400))] ttl_secs: i32,
+    ) -> async_graphql::Result<LogFilter> {
+        let ttl = Duration::from_secs(ttl_secs as u64);
+        trace::set_log_filter(&filter, ttl)?;
+        Ok(LogFilter {
+            filter,
+            expires_at: Some(Utc::now() + chrono::Duration::seconds(i64::from(ttl_secs))),
+        })
+    }
+
+    /// Restores the filter of internal logs set at startup
+    #[graphql(guard = "ScopeGuard::new(ApiScope::Control)")]
+    async fn reset_log_filter(&self) -> async_graphql::Result<LogFilter> {
+        Ok(LogFilter {
+            filter: trace::reset_log_filter()?,
+            expires_at: None,
+        })
+    }
+}
@@ -15,7 +15,7 @@ mod uptime;
 #[cfg(feature = "sources-host_metrics")]
 mod host;
 
-use async_graphql::{Interface, Object, Subscription};
+use async_graphql::{Context, Interface, Object, Subscription};
 use chrono::{DateTime, Utc};
 pub use errors::{ComponentErrorsTotal, ErrorsTotal};
 pub use events_in::EventsInTotal;
@@ -38,7 +38,10 @@ use tokio_stream::{Stream, StreamExt};
 pub use transform::{IntoTransformMetrics, TransformMetrics};
 pub use uptime::Uptime;
 
-use crate::config::ComponentKey;
+use crate::{
+    api::auth::{ComponentAccess, ScopeGuard},
+    config::{api::ApiScope, ComponentKey},
+};
 
 #[derive(Interface)]
 #[graphql(field(name = "timestamp", type = "Option<DateTime<Utc>>"))]
@@ -55,6 +58,7 @@ pub struct MetricsQuery;
 impl MetricsQuery {
     #[cfg(feature = "sources-host_metrics")]
     /// Vector host metrics
+    #[graphql(guard = "ScopeGuard::new(ApiScope::ReadMetrics)")]
     async fn host_metrics(&self) -> host::HostMetrics {
         host::HostMetrics::new()
     }
@@ -66,6 +70,7 @@ pub struct MetricsSubscription;
 #[Subscription]
 impl MetricsSubscription {
     /// Metrics for how long the Vector instance has been running
+    #[graphql(guard = "ScopeGuard::new(ApiScope::ReadMetrics)")]
     async fn uptime(
         &self,
         #[graphql(default = 1000, validator(minimum = 10, maximum = 60_000))] interval: i32,
@@ -77,6 +82,7 @@ impl MetricsSubscription {
     }
 
     /// Event processing metrics.
+    #[graphql(guard = "ScopeGuard::new(ApiScope::ReadMetrics)")]
     async fn processed_events_total(
         &self,
         #[graphql(default = 1000, validator(minimum = 10, maximum = 60_000))] interval: i32,
@@ -88,6 +94,7 @@ impl MetricsSubscription {
     }
 
     /// Event processing throughput sampled over the provided millisecond `interval`.
+    #[graphql(guard = "ScopeGuard::new(ApiScope::ReadMetrics)")]
     async fn processed_events_throughput(
         &self,
         #[graphql(default = 1000, validator(minimum = 10, maximum = 60_000))] interval: i32,
@@ -97,13 +104,17 @@ impl MetricsSubscription {
     }
 
     /// Component event processing throughput metrics over `interval`.
+    #[graphql(guard = "ScopeGuard::new(ApiScope::ReadMetrics)")]
     async fn component_processed_events_throughputs(
         &self,
+        ctx: &Context<'_>,
         #[graphql(default = 1000, validator(minimum = 10, maximum = 60_000))] interval: i32,
     ) -> impl Stream<Item = Vec<ComponentProcessedEventsThroughput>> {
+        let access = ComponentAccess::from_context(ctx);
         component_counter_throughputs(interval, &|m| m.name() == "processed_events_total").map(
-            |m| {
+            move |m| {
                 m.into_iter()
+                    .filter(|(m, _)| access.allows_metric(m))
                     .map(|(m, throughput)| {
                         ComponentProcessedEventsThroughput::new(
                             ComponentKey::from(m.tag_value("component_id").unwrap()),
@@ -116,19 +127,26 @@ impl MetricsSubscription {
     }
 
     /// Component event processing metrics over `interval`.
+    #[graphql(guard = "ScopeGuard::new(ApiScope::ReadMetrics)")]
     async fn component_processed_events_totals(
         &self,
+        ctx: &Context<'_>,
         #[graphql(default = 1000, validator(minimum = 10, maximum = 60_000))] interval: i32,
     ) -> impl Stream<Item = Vec<ComponentProcessedEventsTotal>> {
-        component_counter_metrics(interval, &|m| m.name() == "processed_events_total").map(|m| {
-            m.into_iter()
-                .map(ComponentProcessedEventsTotal::new)
-                .collect()
-        })
+        let access = ComponentAccess::from_context(ctx);
+        component_counter_metrics(interval, &|m| m.name() == "processed_events_total").map(
+            move |m| {
+                m.into_iter()
+                    .filter(|m| access.allows_metric(m))
+                    .map(ComponentProcessedEventsTotal::new)
+                    .collect()
+            },
+        )
     }
 
     /// Total received events metrics
     #[graphql(deprecation = "Use component_received_events_totals instead")]
+    #[graphql(guard = "ScopeGuard::new(ApiScope::ReadMetrics)")]
     async fn received_events_total(
         &self,
         #[graphql(default = 1000, validator(minimum = 10, maximum = 60_000))] interval: i32,
@@ -141,6 +159,7 @@ impl MetricsSubscription {
 
     /// Total received events throughput sampled over the provided millisecond `interval`
     #[graphql(deprecation = "Use component_received_events_throughputs instead")]
+    #[graphql(guard = "ScopeGuard::new(ApiScope::ReadMetrics)")]
     async fn received_events_throughput(
         &self,
         #[graphql(default = 1000, validator(minimum = 10, maximum = 60_000))] interval: i32,
@@ -150,13 +169,17 @@ impl MetricsSubscription {
     }
 
     /// Total incoming component events throughput metrics over `interval`
+    #[graphql(guard = "ScopeGuard::new(ApiScope::ReadMetrics)")]
     async fn component_received_events_throughputs(
         &self,
+        ctx: &Context<'_>,
         #[graphql(default = 1000, validator(minimum = 10, maximum = 60_000))] interval: i32,
     ) -> impl Stream<Item = Vec<ComponentReceivedEventsThroughput>> {
+        let access = ComponentAccess::from_context(ctx);
         component_counter_throughputs(interval, &|m| m.name() == "component_received_events_total")
-            .map(|m| {
+            .map(move |m| {
                 m.into_iter()
+                    .filter(|(m, _)| access.allows_metric(m))
                     .map(|(m, throughput)| {
                         ComponentReceivedEventsThroughput::new(
                             ComponentKey::from(m.tag_value("component_id").unwrap()),
@@ -168,13 +191,17 @@ impl MetricsSubscription {
     }
 
     /// Total received component event metrics over `interval`
+    #[graphql(guard = "ScopeGuard::new(ApiScope::ReadMetrics)")]
     async fn component_received_events_totals(
         &self,
+        ctx: &Context<'_>,
         #[graphql(default = 1000, validator(minimum = 10, maximum = 60_000))] interval: i32,
     ) -> impl Stream<Item = Vec<ComponentReceivedEventsTotal>> {
+        let access = ComponentAccess::from_context(ctx);
         component_counter_metrics(interval, &|m| m.name() == "component_received_events_total").map(
-            |m| {
+            move |m| {
                 m.into_iter()
+                    .filter(|m| access.allows_metric(m))
                     .map(ComponentReceivedEventsTotal::new)
                     .collect()
             },
@@ -183,6 +210,7 @@ impl MetricsSubscription {
 
     /// Total sent events metrics
     #[graphql(deprecation = "Use component_sent_events_totals instead")]
+    #[graphql(guard = "ScopeGuard::new(ApiScope::ReadMetrics)")]
     async fn sent_events_total(
         &self,
         #[graphql(default = 1000, validator(minimum = 10, maximum = 60_000))] interval: i32,
@@ -195,6 +223,7 @@ impl MetricsSubscription {
 
     /// Total outgoing events throughput sampled over the provided millisecond `interval`
     #[graphql(deprecation = "Use component_sent_events_throughputs instead")]
+    #[graphql(guard = "ScopeGuard::new(ApiScope::ReadMetrics)")]
     async fn sent_events_throughput(
         &self,
         #[graphql(default = 1000, validator(minimum = 10, maximum = 60_000))] interval: i32,
@@ -204,12 +233,16 @@ impl MetricsSubscription {
     }
 
     /// Total outgoing component event throughput metrics over `interval`
+    #[graphql(guard = "ScopeGuard::new(ApiScope::ReadMetrics)")]
     async fn component_sent_events_throughputs(
         &self,
+        ctx: &Context<'_>,
         #[graphql(default = 1000, validator(minimum = 10, maximum = 60_000))] interval: i32,
     ) -> impl Stream<Item = Vec<ComponentSentEventsThroughput>> {
-        component_sent_events_total_throughputs_with_outputs(interval).map(|m| {
+        let access = ComponentAccess::from_context(ctx);
+        component_sent_events_total_throughputs_with_outputs(interval).map(move |m| {
             m.into_iter()
+                .filter(|(key, _, _)| access.allows(key.id()))
                 .map(|(key, total_throughput, outputs)| {
                     ComponentSentEventsThroughput::new(key, total_throughput, outputs)
                 })
@@ -218,18 +251,23 @@ impl MetricsSubscription {
     }
 
     /// Total outgoing component event metrics over `interval`
+    #[graphql(guard = "ScopeGuard::new(ApiScope::ReadMetrics)")]
     async fn component_sent_events_totals(
         &self,
+        ctx: &Context<'_>,
         #[graphql(default = 1000, validator(minimum = 10, maximum = 60_000))] interval: i32,
     ) -> impl Stream<Item = Vec<ComponentSentEventsTotal>> {
-        component_sent_events_totals_metrics_with_outputs(interval).map(|ms| {
+        let access = ComponentAccess::from_context(ctx);
+        component_sent_events_totals_metrics_with_outputs(interval).map(move |ms| {
             ms.into_iter()
+                .filter(|(m, _)| access.allows_metric(m))
                 .map(|(m, m_by_outputs)| ComponentSentEventsTotal::new(m, m_by_outputs))
                 .collect()
         })
     }
 
     /// Byte processing metrics.
+    #[graphql(guard = "ScopeGuard::new(ApiScope::ReadMetrics)")]
     async fn processed_bytes_total(
         &self,
         #[graphql(default = 1000, validator(minimum = 10, maximum = 60_000))] interval: i32,
@@ -241,6 +279,7 @@ impl MetricsSubscription {
     }
 
     /// Byte processing throughput sampled over a provided millisecond `interval`.
+    #[graphql(guard = "ScopeGuard::new(ApiScope::ReadMetrics)")]
     async fn processed_bytes_throughput(
         &self,
         #[graphql(default = 1000, validator(minimum = 10, maximum = 60_000))] interval: i32,
@@ -250,35 +289,48 @@ impl MetricsSubscription {
     }
 
     /// Component byte processing metrics over `interval`.
+    #[graphql(guard = "ScopeGuard::new(ApiScope::ReadMetrics)")]
     async fn component_processed_bytes_totals(
         &self,
+        ctx: &Context<'_>,
         #[graphql(default = 1000, validator(minimum = 10, maximum = 60_000))] interval: i32,
     ) -> impl Stream<Item = Vec<ComponentProcessedBytesTotal>> {
-        component_counter_metrics(interval, &|m| m.name() == "processed_bytes_total").map(|m| {
-            m.into_iter()
-                .map(ComponentProcessedBytesTotal::new)
-                .collect()
-        })
+        let access = ComponentAccess::from_context(ctx);
+        component_counter_metrics(interval, &|m| m.name() == "processed_bytes_total").map(
+            move |m| {
+                m.into_iter()
+                    .filter(|m| access.allows_metric(m))
+                    .map(ComponentProcessedBytesTotal::new)
+                    .collect()
+            },
+        )
     }
 
     /// Component byte processing throughput over `interval`
+    #[graphql(guard = "ScopeGuard::new(ApiScope::ReadMetrics)")]
     async fn component_processed_bytes_throughputs(
         &self,
+        ctx: &Context<'_>,
         #[graphql(default = 1000, validator(minimum = 10, maximum = 60_000))] interval: i32,
     ) -> impl Stream<Item = Vec<ComponentProcessedBytesThroughput>> {
-        component_counter_throughputs(interval, &|m| m.name() == "processed_bytes_total").map(|m| {
-            m.into_iter()
-                .map(|(m, throughput)| {
-                    ComponentProcessedBytesThroughput::new(
-                        ComponentKey::from(m.tag_value("component_id").unwrap()),
-                        throughput as i64,
-                    )
-                })
-                .collect()
-        })
+        let access = ComponentAccess::from_context(ctx);
+        component_counter_throughputs(interval, &|m| m.name() == "processed_bytes_total").map(
+            move |m| {
+                m.into_iter()
+                    .filter(|(m, _)| access.allows_metric(m))
+                    .map(|(m, throughput)| {
+                        ComponentProcessedBytesThroughput::new(
+                            ComponentKey::from(m.tag_value("component_id").unwrap()),
+                            throughput as i64,
+                        )
+                    })
+                    .collect()
+            },
+        )
     }
 
     /// Total error metrics.
+    #[graphql(guard = "ScopeGuard::new(ApiScope::ReadMetrics)")]
     async fn errors_total(
         &self,
         #[graphql(default = 1000, validator(minimum = 10, maximum = 60_000))] interval: i32,
@@ -289,15 +341,25 @@ impl MetricsSubscription {
     }
 
     /// Component error metrics over `interval`.
+    #[graphql(guard = "ScopeGuard::new(ApiScope::ReadMetrics)")]
     async fn component_errors_totals(
         &self,
+        ctx: &Context<'_>,
         #[graphql(default = 1000, validator(minimum = 10, maximum = 60_000))] interval: i32,
     ) -> impl Stream<Item = Vec<ComponentErrorsTotal>> {
-        component_counter_metrics(interval, &|m| m.name().ends_with("_errors_total"))
-            .map(|m| m.into_iter().map(ComponentErrorsTotal::new).collect())
+        let access = ComponentAccess::from_context(ctx);
+        component_counter_metrics(interval, &|m| m.name().ends_with("_errors_total")).map(
+            move |m| {
+                m.into_iter()
+                    .filter(|m| access.allows_metric(m))
+                    .map(ComponentErrorsTotal::new)
+                    .collect()
+            },
+        )
     }
 
     /// All metrics.
+    #[graphql(guard = "ScopeGuard::new(ApiScope::ReadMetrics)")]
     async fn metrics(
         &self,
         #[graphql(default = 1000, validator(minimum = 10, maximum = 60_000))] interval: i32,
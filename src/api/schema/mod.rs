@@ -1,13 +1,17 @@
 pub mod components;
+pub mod config;
+mod enrichment_tables;
 pub mod events;
 pub mod filter;
 mod health;
+mod internal_events;
+mod logging;
 mod meta;
 mod metrics;
 mod relay;
 pub mod sort;
 
-use async_graphql::{EmptyMutation, MergedObject, MergedSubscription, Schema, SchemaBuilder};
+use async_graphql::{MergedObject, MergedSubscription, Schema, SchemaBuilder};
 
 #[derive(MergedObject, Default)]
 pub struct Query(
@@ -15,6 +19,14 @@ pub struct Query(
     components::ComponentsQuery,
     metrics::MetricsQuery,
     meta::MetaQuery,
+    config::ConfigQuery,
+    enrichment_tables::EnrichmentTablesQuery,
+);
+
+#[derive(MergedObject, Default)]
+pub struct Mutation(
+    logging::LoggingMutation,
+    enrichment_tables::EnrichmentTablesMutation,
 );
 
 #[derive(MergedSubscription, Default)]
@@ -23,9 +35,14 @@ pub struct Subscription(
     metrics::MetricsSubscription,
     components::ComponentsSubscription,
     events::EventsSubscription,
+    internal_events::InternalEventsSubscription,
 );
 
 /// Build a new GraphQL schema, comprised of Query, Mutation and Subscription types
-pub fn build_schema() -> SchemaBuilder<Query, EmptyMutation, Subscription> {
-    Schema::build(Query::default(), EmptyMutation, Subscription::default())
+pub fn build_schema() -> SchemaBuilder<Query, Mutation, Subscription> {
+    Schema::build(
+        Query::default(),
+        Mutation::default(),
+        Subscription::default(),
+    )
 }
@@ -12,8 +12,15 @@ use async_graphql_warp::{graphql_protocol, GraphQLResponse, GraphQLWebSocket};
 use tokio::sync::oneshot;
 use warp::{filters::BoxedFilter, http::Response, ws::Ws, Filter, Reply};
 
-use super::{handler, schema, ShutdownTx};
-use crate::{config, topology};
+use super::{
+    auth::{ApiAccess, Authenticator},
+    handler, schema, ShutdownTx,
+};
+use crate::{
+    config::{self, api::ApiScope},
+    signal::{SignalTo, SignalTx},
+    topology,
+};
 
 pub struct Server {
     _shutdown: ShutdownTx,
@@ -27,8 +34,15 @@ impl Server {
         config: &config::Config,
         watch_rx: topology::WatchRx,
         running: Arc<AtomicBool>,
+        signal_tx: SignalTx,
     ) -> Self {
-        let routes = make_routes(config.api.playground, watch_rx, running);
+        let routes = make_routes(
+            config.api.playground,
+            Authenticator::new(&config.api.tokens),
+            watch_rx,
+            running,
+            signal_tx,
+        );
 
         let (_shutdown, rx) = oneshot::channel();
         let (addr, server) = warp::serve(routes).bind_with_graceful_shutdown(
@@ -39,7 +53,7 @@ impl Server {
         );
 
         // Update component schema with the config before starting the server.
-        schema::components::update_config(config);
+        Self::update_schema(config);
 
         // Spawn the server in the background.
         tokio::spawn(server);
@@ -56,14 +70,23 @@ impl Server {
     /// directly involve `self`, it provides a neater API to expose an internal implementation
     /// detail than exposing the function of the sub-mod directly.
     pub fn update_config(&self, config: &config::Config) {
-        schema::components::update_config(config)
+        Self::update_schema(config);
+    }
+
+    fn update_schema(config: &config::Config) {
+        schema::components::update_config(config);
+        if let Err(error) = schema::config::update_config(config) {
+            error!(message = "Failed serializing the resolved config for the API.", %error);
+        }
     }
 }
 
 fn make_routes(
     playground: bool,
+    authenticator: Authenticator,
     watch_tx: topology::WatchRx,
     running: Arc<AtomicBool>,
+    signal_tx: SignalTx,
 ) -> BoxedFilter<(impl Reply,)> {
     // Routes...
 
@@ -72,23 +95,64 @@ fn make_routes(
         .and(with_shared(running))
         .and_then(handler::health);
 
+    // The GraphQL schema. Its mutations can reload the config from disk, as the admin endpoints do.
+    let graphql_schema = schema::build_schema().data(signal_tx.clone()).finish();
+
+    // Admin endpoints, reloading the config from disk and gracefully shutting down, as SIGHUP
+    // and SIGTERM do. They require a token with the `control` scope, even if no tokens are
+    // configured for the other endpoints.
+    let admin_authenticator = authenticator.clone();
+    let admin = warp::post()
+        .and(
+            warp::path("reload")
+                .map(|| SignalTo::ReloadFromDisk)
+                .or(warp::path("quit").map(|| SignalTo::Shutdown))
+                .unify(),
+        )
+        .and(warp::path::end())
+        .and(warp::header::optional::<String>("authorization").and_then(
+            move |authorization: Option<String>| {
+                let result =
+                    admin_authenticator.authorize(authorization.as_deref(), ApiScope::Control);
+                async move { result }
+            },
+        ))
+        .untuple_one()
+        .and(warp::any().map(move || signal_tx.clone()))
+        .and_then(handler::signal);
+
     // 404.
     let not_found = warp::any().and_then(|| async { Err(warp::reject::not_found()) });
 
+    // Checks the API token of GraphQL requests, extracting what it grants access to. Without
+    // tokens configured, all requests are let through without access restrictions.
+    let authenticate = warp::header::optional::<String>("authorization").and_then(
+        move |authorization: Option<String>| {
+            let result = authenticator
+                .authenticate(authorization.as_deref())
+                .map_err(warp::reject::custom);
+            async move { result }
+        },
+    );
+
     // GraphQL subscription handler. Creates a Warp WebSocket handler and for each connection,
     // parses the required headers for GraphQL and builds per-connection context based on the
     // provided `WatchTx` channel sender. This allows GraphQL resolvers to subscribe to
     // topology changes.
-    let graphql_subscription_handler =
-        warp::ws()
-            .and(graphql_protocol())
-            .map(move |ws: Ws, protocol: WebSocketProtocols| {
-                let schema = schema::build_schema().finish();
+    let graphql_subscription_handler = warp::ws()
+        .and(graphql_protocol())
+        .and(authenticate.clone())
+        .map(
+            move |ws: Ws, protocol: WebSocketProtocols, access: Option<ApiAccess>| {
+                let schema = graphql_schema.clone();
                 let watch_tx = watch_tx.clone();
 
                 let reply = ws.on_upgrade(move |socket| {
                     let mut data = Data::default();
                     data.insert(watch_tx);
+                    if let Some(access) = access {
+                        data.insert(access);
+                    }
 
                     GraphQLWebSocket::new(socket, schema, protocol)
                         .with_data(data)
@@ -100,18 +164,31 @@ fn make_routes(
                     "Sec-WebSocket-Protocol",
                     protocol.sec_websocket_protocol(),
                 )
-            });
+            },
+        );
 
     // Handle GraphQL queries. Headers will first be parsed to determine whether the query is
     // a subscription and if so, an attempt will be made to upgrade the connection to WebSockets.
     // All other queries will fall back to the default HTTP handler.
-    let graphql_handler = warp::path("graphql").and(graphql_subscription_handler.or(
-        async_graphql_warp::graphql(schema::build_schema().finish()).and_then(
-            |(schema, request): (Schema<_, _, _>, Request)| async move {
-                Ok::<_, Infallible>(GraphQLResponse::from(schema.execute(request).await))
-            },
-        ),
-    ));
+    let graphql_handler =
+        warp::path("graphql").and(
+            graphql_subscription_handler.or(
+                async_graphql_warp::graphql(graphql_schema)
+                    .and(authenticate)
+                    .and_then(
+                        |(schema, request): (Schema<_, _, _>, Request),
+                         access: Option<ApiAccess>| async move {
+                            let request = match access {
+                                Some(access) => request.data(access),
+                                None => request,
+                            };
+                            Ok::<_, Infallible>(GraphQLResponse::from(
+                                schema.execute(request).await,
+                            ))
+                        },
+                    ),
+            ),
+        );
 
     // Provide a playground for executing GraphQL queries/mutations/subscriptions.
     let graphql_playground = if playground {
@@ -131,9 +208,11 @@ fn make_routes(
     // Wire up the health + GraphQL endpoints. Provides a permissive CORS policy to allow for
     // cross-origin interaction with the Vector API.
     health
+        .or(admin)
         .or(graphql_handler)
         .or(graphql_playground)
         .or(not_found)
+        .recover(handler::unauthorized)
         .with(
             warp::cors()
                 .allow_any_origin()
@@ -145,6 +224,7 @@ fn make_routes(
                     "Access-Control-Request-Method",
                     "Access-Control-Allow-Origin",
                     "Access-Control-Request-Headers",
+                    "Authorization",
                     "Content-Type",
                     "X-Apollo-Tracing", // for Apollo GraphQL clients
                     "Pragma",
@@ -21,7 +21,7 @@ use super::{
 };
 use crate::{
     config::ComponentKey,
-    event::{EventArray, LogArray, MetricArray, TraceArray},
+    event::{EventArray, EventContainer, LogArray, MetricArray, TraceArray},
     topology::{fanout, fanout::ControlChannel, TapOutput, TapResource, WatchRx},
 };
 
@@ -130,14 +130,47 @@ impl TapPayload {
 pub struct TapTransformer {
     tap_tx: TapSender,
     output: TapOutput,
+    sample_rate: u32,
+    /// The number of events seen, used to pick one of every `sample_rate` events.
+    seen: u64,
 }
 
 impl TapTransformer {
-    pub const fn new(tap_tx: TapSender, output: TapOutput) -> Self {
-        Self { tap_tx, output }
+    pub const fn new(tap_tx: TapSender, output: TapOutput, sample_rate: u32) -> Self {
+        Self {
+            tap_tx,
+            output,
+            sample_rate,
+            seen: 0,
+        }
+    }
+
+    /// Keeps one of every `sample_rate` events, counting across calls.
+    fn sample<T>(&mut self, events: Vec<T>) -> Vec<T> {
+        if self.sample_rate <= 1 {
+            return events;
+        }
+        let sample_rate = u64::from(self.sample_rate);
+        events
+            .into_iter()
+            .filter(|_| {
+                let keep = self.seen % sample_rate == 0;
+                self.seen += 1;
+                keep
+            })
+            .collect()
     }
 
     pub fn try_send(&mut self, events: EventArray) {
+        let events = match events {
+            EventArray::Logs(logs) => EventArray::Logs(self.sample(logs)),
+            EventArray::Metrics(metrics) => EventArray::Metrics(self.sample(metrics)),
+            EventArray::Traces(traces) => EventArray::Traces(self.sample(traces)),
+        };
+        if events.is_empty() {
+            return;
+        }
+
         let payload = match events {
             EventArray::Logs(logs) => TapPayload::Log(self.output.clone(), logs),
             EventArray::Metrics(metrics) => TapPayload::Metric(self.output.clone(), metrics),
@@ -286,7 +319,7 @@ async fn tap_handler(
                 // Matching an input pattern is equivalent to matching the outputs of the component's inputs
                 for pattern in patterns.for_inputs.iter() {
                     match inputs.iter().filter(|(key, _)|
-                        pattern.matches_glob(&key.to_string())
+                        patterns.components.allows(key.id()) && pattern.matches_glob(&key.to_string())
                     ).flat_map(|(_, related_inputs)| related_inputs.iter().map(|id| id.to_string()).collect::<Vec<_>>()).collect::<HashSet<_>>() {
                         found if !found.is_empty() => {
                             component_id_patterns.insert(Pattern::InputPattern(pattern.clone(), found.into_iter().collect::<Vec<_>>()));
@@ -299,7 +332,12 @@ async fn tap_handler(
 
                 // Loop over all outputs, and connect sinks for the components that match one
                 // or more patterns.
-                for (output, control_tx) in outputs.iter() {
+                // Outputs of components the client has no access to are skipped, as if they
+                // didn't exist.
+                for (output, control_tx) in outputs
+                    .iter()
+                    .filter(|(output, _)| patterns.components.allows(output.output_id.component.id()))
+                {
                     match component_id_patterns
                         .iter()
                         .filter(|pattern| pattern.matches_glob(&output.output_id.to_string()))
@@ -316,7 +354,7 @@ async fn tap_handler(
                             // wrap each event payload with the necessary metadata before forwarding
                             // it to our global tap receiver.
                             let (tap_buffer_tx, mut tap_buffer_rx) = TopologyBuilder::standalone_memory(TAP_BUFFER_SIZE, WhenFull::DropNewest).await;
-                            let mut tap_transformer = TapTransformer::new(tx.clone(), output.clone());
+                            let mut tap_transformer = TapTransformer::new(tx.clone(), output.clone(), patterns.sample_rate);
 
                             tokio::spawn(async move {
                                 while let Some(events) = tap_buffer_rx.next().await {
@@ -384,13 +422,13 @@ async fn tap_handler(
 
                 // Warnings on invalid matches.
                 for pattern in patterns.for_inputs.iter() {
-                    let invalid_matches = source_keys.iter().filter(|key| pattern.matches_glob(key)).cloned().collect::<Vec<_>>();
+                    let invalid_matches = source_keys.iter().filter(|key| patterns.components.allows(key) && pattern.matches_glob(key)).cloned().collect::<Vec<_>>();
                     if !invalid_matches.is_empty() {
                         notifications.push(send_invalid_input_pattern_match(tx.clone(), pattern.clone(), invalid_matches).boxed())
                     }
                 }
                 for pattern in patterns.for_outputs.iter() {
-                    let invalid_matches = sink_keys.iter().filter(|key| pattern.matches_glob(key)).cloned().collect::<Vec<_>>();
+                    let invalid_matches = sink_keys.iter().filter(|key| patterns.components.allows(key) && pattern.matches_glob(key)).cloned().collect::<Vec<_>>();
                     if !invalid_matches.is_empty() {
                         notifications.push(send_invalid_output_pattern_match(tx.clone(), pattern.clone(), invalid_matches).boxed())
                     }
@@ -422,6 +460,7 @@ mod tests {
     use tokio::sync::watch;
 
     use super::*;
+    use crate::api::auth::ComponentAccess;
     use crate::api::schema::events::output::OutputEventsPayload;
     use crate::api::schema::events::{create_events_stream, log, metric};
     use crate::config::{Config, OutputId};
@@ -448,6 +487,30 @@ mod tests {
         }
     }
 
+    #[test]
+    /// A tap transformer should only forward one of every `sample_rate` events.
+    fn transformer_samples_events() {
+        let (tap_tx, mut tap_rx) = tokio_mpsc::channel(10);
+        let output = TapOutput {
+            output_id: OutputId::from(&ComponentKey::from("test")),
+            component_kind: "source",
+            component_type: "demo".to_string(),
+        };
+        let mut transformer = TapTransformer::new(tap_tx, output, 3);
+
+        transformer.try_send(EventArray::Logs(vec![LogEvent::default(); 4]));
+        transformer.try_send(EventArray::Logs(vec![LogEvent::default(); 2]));
+        transformer.try_send(EventArray::Logs(vec![LogEvent::default(); 1]));
+
+        for expected in [2, 1] {
+            match tap_rx.try_recv() {
+                Ok(TapPayload::Log(_, logs)) => assert_eq!(logs.len(), expected),
+                _ => panic!("unexpected payload"),
+            }
+        }
+        assert!(tap_rx.try_recv().is_err());
+    }
+
     #[tokio::test]
     /// A tap sink should match a pattern, receive the correct notifications,
     /// and receive events
@@ -541,6 +604,45 @@ mod tests {
         ));
     }
 
+    #[tokio::test]
+    /// Components the client has no access to can't be tapped, and aren't reported as matches.
+    async fn tap_skips_inaccessible_components() {
+        let id = OutputId::from(&ComponentKey::from("test"));
+
+        let (_fanout, control_tx) = fanout::Fanout::new();
+        let mut outputs = HashMap::new();
+        outputs.insert(
+            TapOutput {
+                output_id: id,
+                component_kind: "source",
+                component_type: "demo".to_string(),
+            },
+            control_tx,
+        );
+        let tap_resource = TapResource {
+            outputs,
+            inputs: HashMap::new(),
+            source_keys: Vec::new(),
+            sink_keys: Vec::new(),
+            removals: HashSet::new(),
+        };
+
+        let (watch_tx, watch_rx) = watch::channel(TapResource::default());
+        let (sink_tx, mut sink_rx) = tokio_mpsc::channel(10);
+
+        let mut patterns = TapPatterns::new(HashSet::from(["tes*".to_string()]), HashSet::new());
+        patterns.components = ComponentAccess::only(&["other_*".to_string()]);
+        let _controller = TapController::new(watch_rx, sink_tx, patterns);
+
+        watch_tx.send(tap_resource).unwrap();
+
+        assert!(matches!(
+            sink_rx.recv().await,
+            Some(TapPayload::Notification(Notification::NotMatched(not_matched)))
+                if not_matched.pattern == "tes*"
+        ));
+    }
+
     fn assert_notification(payload: OutputEventsPayload) -> Notification {
         if let OutputEventsPayload::Notification(event_notification) = payload {
             event_notification.notification
@@ -585,6 +687,9 @@ mod tests {
             BlackholeConfig {
                 print_interval_secs: 1,
                 rate: None,
+                latency: None,
+                error_rate: 0.0,
+                backpressure: None,
                 acknowledgements: Default::default(),
             },
         );
@@ -634,6 +739,7 @@ mod tests {
                     namespace: None,
                     tags: None,
                 })],
+                flush_interval_secs: 60,
             },
         );
         config.add_sink(
@@ -642,6 +748,9 @@ mod tests {
             BlackholeConfig {
                 print_interval_secs: 1,
                 rate: None,
+                latency: None,
+                error_rate: 0.0,
+                backpressure: None,
                 acknowledgements: Default::default(),
             },
         );
@@ -692,6 +801,9 @@ mod tests {
             BlackholeConfig {
                 print_interval_secs: 1,
                 rate: None,
+                latency: None,
+                error_rate: 0.0,
+                backpressure: None,
                 acknowledgements: Default::default(),
             },
         );
@@ -745,6 +857,9 @@ mod tests {
             BlackholeConfig {
                 print_interval_secs: 1,
                 rate: None,
+                latency: None,
+                error_rate: 0.0,
+                backpressure: None,
                 acknowledgements: Default::default(),
             },
         );
@@ -819,6 +934,9 @@ mod tests {
             BlackholeConfig {
                 print_interval_secs: 1,
                 rate: None,
+                latency: None,
+                error_rate: 0.0,
+                backpressure: None,
                 acknowledgements: Default::default(),
             },
         );
@@ -879,6 +997,9 @@ mod tests {
             BlackholeConfig {
                 print_interval_secs: 1,
                 rate: None,
+                latency: None,
+                error_rate: 0.0,
+                backpressure: None,
                 acknowledgements: Default::default(),
             },
         );
@@ -955,6 +1076,9 @@ mod tests {
             BlackholeConfig {
                 print_interval_secs: 1,
                 rate: None,
+                latency: None,
+                error_rate: 0.0,
+                backpressure: None,
                 acknowledgements: Default::default(),
             },
         );
@@ -184,6 +184,7 @@ async fn integration_test_source_metric() {
                 namespace: None,
                 tags: None,
             })],
+            flush_interval_secs: 60,
         },
     );
     config.add_sink(
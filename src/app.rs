@@ -23,8 +23,8 @@ use crate::service;
 use crate::{api, internal_events::ApiStarted};
 use crate::{
     cli::{handle_config_errors, Color, LogFormat, Opts, RootOpts, SubCommand},
-    config::{self},
-    generate, generate_schema, graph, heartbeat, list,
+    config::{self, feature_flags::FeatureFlag},
+    generate, generate_schema, graph, heartbeat, internal_events_audit, list,
     signal::{self, SignalTo},
     topology::{self, RunningTopology},
     trace, unit_test, validate,
@@ -129,6 +129,8 @@ impl Application {
             }
         }
 
+        config::feature_flags::init_cli_feature_flags(&root_opts.feature_flags);
+
         let rt = rt_builder.build().expect("Unable to create async runtime");
 
         let config = {
@@ -190,6 +192,10 @@ impl Application {
                 #[cfg(not(feature = "enterprise-tests"))]
                 config::init_log_schema(&config_paths, true).map_err(handle_config_errors)?;
 
+                if let Some(policy) = &root_opts.policy {
+                    config::init_policy(policy).map_err(handle_config_errors)?;
+                }
+
                 let mut config = config::load_from_paths_with_provider_and_secrets(
                     &config_paths,
                     &mut signal_handler,
@@ -220,15 +226,23 @@ impl Application {
                     Err(_) => None,
                 };
 
+                crate::host_metadata::start(&config.global)
+                    .await
+                    .map_err(|error| {
+                        error!(message = "Unable to start host metadata provider.", %error);
+                        exitcode::CONFIG
+                    })?;
+
                 let diff = config::ConfigDiff::initial(&config);
                 let pieces = topology::build_or_log_errors(&config, &diff, HashMap::new())
                     .await
                     .ok_or(exitcode::CONFIG)?;
 
                 #[cfg(feature = "api")]
-                let api = config.api;
+                let api = config.api.clone();
 
                 let result = topology::start_validated(config, diff, pieces).await;
+                crate::providers::complete_reload(result.is_some());
                 let (topology, graceful_crash) = result.ok_or(exitcode::CONFIG)?;
 
                 Ok(ApplicationConfig {
@@ -278,6 +292,9 @@ impl Application {
         rt.block_on(async move {
             emit!(VectorStarted);
             tokio::spawn(heartbeat::heartbeat());
+            if config::feature_flags::is_enabled(&topology.config().global, FeatureFlag::InternalEventsAudit) {
+                tokio::spawn(internal_events_audit::audit());
+            }
 
             // Configure the API server, if applicable.
             #[cfg(feature = "api")]
@@ -289,7 +306,7 @@ impl Application {
                     playground: api_config.playground
                 });
 
-                Some(api::Server::start(topology.config(), topology.watch(), Arc::<AtomicBool>::clone(&topology.running)))
+                Some(api::Server::start(topology.config(), topology.watch(), Arc::<AtomicBool>::clone(&topology.running), signal_handler.clone_tx()))
             } else {
                 info!(message="API is disabled, enable by setting `api.enabled` to `true` and use commands like `vector top`.");
                 None
@@ -333,11 +350,17 @@ impl Application {
                                                     api_server.update_config(topology.config());
                                                 }
 
+                                                reload_host_metadata(topology.config()).await;
+                                                crate::providers::complete_reload(true);
                                                 emit!(VectorReloaded { config_paths: &config_paths })
                                             },
-                                            Ok(false) => emit!(VectorReloadError),
+                                            Ok(false) => {
+                                                crate::providers::complete_reload(false);
+                                                emit!(VectorReloadError)
+                                            },
                                             // Trigger graceful shutdown for what remains of the topology
                                             Err(()) => {
+                                                crate::providers::complete_reload(false);
                                                 emit!(VectorReloadError);
                                                 emit!(VectorRecoveryError);
                                                 break SignalTo::Shutdown;
@@ -346,6 +369,7 @@ impl Application {
                                         sources_finished = topology.sources_finished();
                                     },
                                     Err(_) => {
+                                        crate::providers::complete_reload(false);
                                         emit!(VectorConfigLoadError);
                                     }
                                 }
@@ -388,11 +412,17 @@ impl Application {
                                                 api_server.update_config(topology.config());
                                             }
 
+                                            reload_host_metadata(topology.config()).await;
+                                            crate::providers::complete_reload(true);
                                             emit!(VectorReloaded { config_paths: &config_paths })
                                         },
-                                        Ok(false) => emit!(VectorReloadError),
+                                        Ok(false) => {
+                                            crate::providers::complete_reload(false);
+                                            emit!(VectorReloadError)
+                                        },
                                         // Trigger graceful shutdown for what remains of the topology
                                         Err(()) => {
+                                            crate::providers::complete_reload(false);
                                             emit!(VectorReloadError);
                                             emit!(VectorRecoveryError);
                                             break SignalTo::Shutdown;
@@ -400,9 +430,15 @@ impl Application {
                                     }
                                     sources_finished = topology.sources_finished();
                                 } else {
+                                    crate::providers::complete_reload(false);
                                     emit!(VectorConfigLoadError);
                                 }
                             },
+                            Ok(SignalTo::ReloadEnrichmentTable(name)) => {
+                                if !topology.reload_enrichment_table(&name).await {
+                                    warn!(message = "Enrichment table to reload doesn't exist.", table = %name);
+                                }
+                            },
                             Err(RecvError::Lagged(amt)) => warn!("Overflow, dropped {} signals.", amt),
                             Err(RecvError::Closed) => break SignalTo::Shutdown,
                             Ok(signal) => break signal,
@@ -437,3 +473,10 @@ impl Application {
         });
     }
 }
+
+/// Fetches the host metadata again with the reloaded configuration.
+async fn reload_host_metadata(config: &config::Config) {
+    if let Err(error) = crate::host_metadata::start(&config.global).await {
+        error!(message = "Unable to restart host metadata provider.", %error);
+    }
+}
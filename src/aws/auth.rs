@@ -1,9 +1,23 @@
-use std::time::Duration;
+use std::{
+    collections::BTreeMap,
+    path::PathBuf,
+    time::{Duration, SystemTime},
+};
 
 use aws_config::{
-    default_provider::credentials::DefaultCredentialsChain, sts::AssumeRoleProviderBuilder,
+    default_provider::credentials::DefaultCredentialsChain,
+    meta::credentials::LazyCachingCredentialsProvider,
+    provider_config::ProviderConfig,
+    sts::AssumeRoleProviderBuilder,
+    web_identity_token::{StaticConfiguration, WebIdentityTokenCredentialsProvider},
+};
+use aws_sdk_sts::model::Tag;
+use aws_types::{
+    credentials::{self, future, CredentialsError, ProvideCredentials, SharedCredentialsProvider},
+    region::Region,
+    Credentials,
 };
-use aws_types::{credentials::SharedCredentialsProvider, region::Region, Credentials};
+use chrono::Utc;
 use vector_common::sensitive_string::SensitiveString;
 use vector_config::configurable_component;
 
@@ -37,11 +51,36 @@ pub enum AwsAuthentication {
         profile: Option<String>,
     },
 
+    /// Assume the given role ARN with a web identity token read from a file.
+    WebIdentity(#[configurable(derived)] WebIdentity),
+
     /// Assume the given role ARN.
     Role {
         /// The ARN of the role to assume.
         assume_role: String,
 
+        /// The external ID to pass when assuming the role.
+        ///
+        /// Required by roles whose trust policy grants access to a third party.
+        external_id: Option<String>,
+
+        /// The name of the role session.
+        ///
+        /// If not set, a name is generated.
+        session_name: Option<String>,
+
+        /// Session tags to pass when assuming the role.
+        #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+        session_tags: BTreeMap<String, String>,
+
+        /// Roles to assume, in order, before assuming `assume_role`.
+        ///
+        /// The first role is assumed with the credentials found by the default credentials chain,
+        /// and each following role with the credentials of the one before it. This allows reaching
+        /// roles that can only be assumed from another account.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        role_chain: Vec<ChainedRole>,
+
         /// Timeout for assuming the role, in seconds.
         load_timeout_secs: Option<u64>,
 
@@ -60,6 +99,148 @@ pub enum AwsAuthentication {
     },
 }
 
+/// A role assumed with a web identity token read from a file.
+///
+/// This is how IAM roles for service accounts are assumed on EKS.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct WebIdentity {
+    /// Path to the file holding the web identity token.
+    web_identity_token_file: PathBuf,
+
+    /// The ARN of the role to assume.
+    assume_role: String,
+
+    /// The name of the role session.
+    ///
+    /// If not set, a name is generated.
+    session_name: Option<String>,
+
+    /// The AWS region to send STS requests to.
+    ///
+    /// If not set, this will default to the configured region
+    /// for the service itself.
+    region: Option<String>,
+}
+
+/// A role assumed as part of a role chain.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct ChainedRole {
+    /// The ARN of the role to assume.
+    assume_role: String,
+
+    /// The external ID to pass when assuming the role.
+    external_id: Option<String>,
+
+    /// The name of the role session.
+    ///
+    /// If not set, a name is generated.
+    session_name: Option<String>,
+
+    /// Session tags to pass when assuming the role.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    session_tags: BTreeMap<String, String>,
+}
+
+impl ChainedRole {
+    /// Builds a provider of the credentials of this role, assuming it with the credentials of
+    /// `source`.
+    fn credentials_provider(
+        &self,
+        region: Region,
+        source: SharedCredentialsProvider,
+    ) -> SharedCredentialsProvider {
+        if !self.session_tags.is_empty() {
+            // The SDK's assume role provider can't pass session tags, so the role is assumed
+            // through STS directly instead.
+            let config = aws_sdk_sts::Config::builder()
+                .region(region)
+                .credentials_provider(source)
+                .build();
+            let provider = TaggedAssumeRoleProvider {
+                client: aws_sdk_sts::Client::from_conf(config),
+                role: self.clone(),
+            };
+            return SharedCredentialsProvider::new(
+                LazyCachingCredentialsProvider::builder()
+                    .load(provider)
+                    .build(),
+            );
+        }
+
+        let mut builder = AssumeRoleProviderBuilder::new(&self.assume_role).region(region);
+        if let Some(external_id) = &self.external_id {
+            builder = builder.external_id(external_id);
+        }
+        if let Some(session_name) = &self.session_name {
+            builder = builder.session_name(session_name);
+        }
+        SharedCredentialsProvider::new(builder.build(source))
+    }
+}
+
+/// Provides the credentials of a role assumed with session tags.
+#[derive(Debug)]
+struct TaggedAssumeRoleProvider {
+    client: aws_sdk_sts::Client,
+    role: ChainedRole,
+}
+
+impl TaggedAssumeRoleProvider {
+    async fn credentials(&self) -> credentials::Result {
+        let tags = self
+            .role
+            .session_tags
+            .iter()
+            .map(|(key, value)| Tag::builder().key(key).value(value).build())
+            .collect();
+        let output = self
+            .client
+            .assume_role()
+            .role_arn(&self.role.assume_role)
+            .role_session_name(
+                self.role
+                    .session_name
+                    .clone()
+                    .unwrap_or_else(default_session_name),
+            )
+            .set_external_id(self.role.external_id.clone())
+            .set_tags(Some(tags))
+            .send()
+            .await
+            .map_err(CredentialsError::provider_error)?;
+        let credentials = output
+            .credentials()
+            .ok_or_else(|| CredentialsError::unhandled("STS returned no credentials."))?;
+
+        Ok(Credentials::new(
+            credentials.access_key_id().unwrap_or_default(),
+            credentials.secret_access_key().unwrap_or_default(),
+            credentials.session_token().map(ToOwned::to_owned),
+            credentials
+                .expiration()
+                .and_then(|expiration| SystemTime::try_from(*expiration).ok()),
+            "AssumeRoleWithSessionTags",
+        ))
+    }
+}
+
+impl ProvideCredentials for TaggedAssumeRoleProvider {
+    fn provide_credentials<'a>(&'a self) -> future::ProvideCredentials<'a>
+    where
+        Self: 'a,
+    {
+        future::ProvideCredentials::new(self.credentials())
+    }
+}
+
+fn default_session_name() -> String {
+    format!("vector-{}", Utc::now().timestamp_millis())
+}
+
 impl AwsAuthentication {
     pub async fn credentials_provider(
         &self,
@@ -77,17 +258,51 @@ impl AwsAuthentication {
             AwsAuthentication::File { .. } => {
                 Err("Overriding the credentials file is not supported.".into())
             }
+            AwsAuthentication::WebIdentity(WebIdentity {
+                web_identity_token_file,
+                assume_role,
+                session_name,
+                region,
+            }) => {
+                let auth_region = region.clone().map(Region::new).unwrap_or(service_region);
+                let provider = WebIdentityTokenCredentialsProvider::builder()
+                    .static_configuration(StaticConfiguration {
+                        web_identity_token_file: web_identity_token_file.clone(),
+                        role_arn: assume_role.clone(),
+                        session_name: session_name.clone().unwrap_or_else(default_session_name),
+                    })
+                    .configure(&ProviderConfig::default().with_region(Some(auth_region)))
+                    .build();
+
+                Ok(SharedCredentialsProvider::new(
+                    LazyCachingCredentialsProvider::builder()
+                        .load(provider)
+                        .build(),
+                ))
+            }
             AwsAuthentication::Role {
                 assume_role,
+                external_id,
+                session_name,
+                session_tags,
+                role_chain,
                 load_timeout_secs,
                 region,
             } => {
                 let auth_region = region.clone().map(Region::new).unwrap_or(service_region);
-                let provider = AssumeRoleProviderBuilder::new(assume_role)
-                    .region(auth_region.clone())
-                    .build(default_credentials_provider(auth_region, *load_timeout_secs).await);
-
-                Ok(SharedCredentialsProvider::new(provider))
+                let role = ChainedRole {
+                    assume_role: assume_role.clone(),
+                    external_id: external_id.clone(),
+                    session_name: session_name.clone(),
+                    session_tags: session_tags.clone(),
+                };
+
+                let mut provider =
+                    default_credentials_provider(auth_region.clone(), *load_timeout_secs).await;
+                for role in role_chain.iter().chain(std::iter::once(&role)) {
+                    provider = role.credentials_provider(auth_region.clone(), provider);
+                }
+                Ok(provider)
             }
             AwsAuthentication::Default { load_timeout_secs } => Ok(SharedCredentialsProvider::new(
                 default_credentials_provider(service_region, *load_timeout_secs).await,
@@ -201,6 +416,7 @@ mod tests {
                 assume_role,
                 load_timeout_secs,
                 region,
+                ..
             } => {
                 assert_eq!(&assume_role, "auth.root");
                 assert_eq!(load_timeout_secs, Some(10));
@@ -210,6 +426,88 @@ mod tests {
         }
     }
 
+    #[test]
+    fn parsing_assume_role_chain() {
+        let config = toml::from_str::<ComponentConfig>(
+            r#"
+            auth.assume_role = "arn:aws:iam::222222222222:role/target"
+            auth.external_id = "target-id"
+            auth.session_tags.team = "observability"
+
+            [[auth.role_chain]]
+            assume_role = "arn:aws:iam::111111111111:role/hop"
+            external_id = "hop-id"
+            session_name = "hop"
+        "#,
+        )
+        .unwrap();
+
+        match config.auth {
+            AwsAuthentication::Role {
+                assume_role,
+                external_id,
+                session_tags,
+                role_chain,
+                ..
+            } => {
+                assert_eq!(&assume_role, "arn:aws:iam::222222222222:role/target");
+                assert_eq!(external_id.unwrap(), "target-id");
+                assert_eq!(session_tags["team"], "observability");
+                assert_eq!(role_chain.len(), 1);
+                assert_eq!(
+                    &role_chain[0].assume_role,
+                    "arn:aws:iam::111111111111:role/hop"
+                );
+                assert_eq!(role_chain[0].external_id.as_deref(), Some("hop-id"));
+                assert_eq!(role_chain[0].session_name.as_deref(), Some("hop"));
+            }
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn parsing_web_identity() {
+        let config = toml::from_str::<ComponentConfig>(
+            r#"
+            auth.web_identity_token_file = "/var/run/secrets/token"
+            auth.assume_role = "arn:aws:iam::123456789012:role/vector"
+            auth.region = "us-west-2"
+        "#,
+        )
+        .unwrap();
+
+        match config.auth {
+            AwsAuthentication::WebIdentity(WebIdentity {
+                web_identity_token_file,
+                assume_role,
+                session_name,
+                region,
+            }) => {
+                assert_eq!(
+                    web_identity_token_file,
+                    PathBuf::from("/var/run/secrets/token")
+                );
+                assert_eq!(&assume_role, "arn:aws:iam::123456789012:role/vector");
+                assert_eq!(session_name, None);
+                assert_eq!(region.unwrap(), "us-west-2");
+            }
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn parsing_web_identity_with_unknown_fields() {
+        let config = toml::from_str::<ComponentConfig>(
+            r#"
+            auth.web_identity_token_file = "/var/run/secrets/token"
+            auth.assume_role = "arn:aws:iam::123456789012:role/vector"
+            auth.external_id = "unsupported"
+        "#,
+        );
+
+        assert!(config.is_err());
+    }
+
     #[test]
     fn parsing_static() {
         let config = toml::from_str::<ComponentConfig>(
@@ -0,0 +1,286 @@
+use std::{
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use aws_smithy_client::SdkError;
+use vector_config::configurable_component;
+
+use super::{
+    create_client, is_retriable_error, AwsAuthentication, ClientBuilder, RegionOrEndpoint,
+};
+use crate::{
+    config::ProxyConfig,
+    internal_events::{AwsEndpointRequestServed, AwsEndpointSwitched},
+    tls::TlsConfig,
+};
+
+/// Configuration of the regions, or endpoints, to fail over to when the primary one keeps failing.
+#[configurable_component]
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct FailoverConfig {
+    /// The regions, or endpoints, to fail over to, in order of preference.
+    ///
+    /// Each of them takes the same `region`, `endpoint`, and `endpoint_variant` options as the
+    /// primary one.
+    pub regions: Vec<RegionOrEndpoint>,
+
+    /// The number of consecutive requests failing with a retriable error, such as a timeout or a
+    /// server error, after which the next region is used.
+    #[serde(default = "default_threshold")]
+    pub threshold: u32,
+
+    /// How long, in seconds, to wait after failing over before trying the primary region again.
+    #[serde(default = "default_failback_secs")]
+    pub failback_secs: u64,
+}
+
+const fn default_threshold() -> u32 {
+    5
+}
+
+const fn default_failback_secs() -> u64 {
+    300
+}
+
+/// Creates a client for the primary region and each of the failover regions.
+pub async fn create_failover_clients<T: ClientBuilder>(
+    auth: &AwsAuthentication,
+    region: &RegionOrEndpoint,
+    failover: Option<&FailoverConfig>,
+    proxy: &ProxyConfig,
+    tls_options: &Option<TlsConfig>,
+    is_sink: bool,
+) -> crate::Result<RegionFailover<T::Client>>
+where
+    T::Client: Clone,
+{
+    let mut endpoints = Vec::new();
+    for target in targets(region, failover) {
+        let client = create_client::<T>(
+            auth,
+            target.region(),
+            target.endpoint()?,
+            target.endpoint_variant,
+            proxy,
+            tls_options,
+            is_sink,
+        )
+        .await?;
+        endpoints.push((endpoint_name(target), client));
+    }
+
+    RegionFailover::new(endpoints, failover)
+}
+
+/// The primary region, followed by the failover regions.
+pub fn targets<'a>(
+    region: &'a RegionOrEndpoint,
+    failover: Option<&'a FailoverConfig>,
+) -> impl Iterator<Item = &'a RegionOrEndpoint> {
+    std::iter::once(region).chain(failover.into_iter().flat_map(|failover| &failover.regions))
+}
+
+/// The name of a region, or endpoint, as reported in internal metrics.
+pub fn endpoint_name(target: &RegionOrEndpoint) -> String {
+    target
+        .endpoint
+        .clone()
+        .or_else(|| target.region.clone())
+        .unwrap_or_else(|| "default".to_owned())
+}
+
+/// A client for each of the regions a sink can send to, along with which of them is in use.
+///
+/// The first client, of the primary region, is used until `threshold` consecutive requests fail
+/// with a retriable error. The next one is then used, until it fails as many times or until
+/// `failback` has elapsed, at which point the primary region is tried again.
+#[derive(Clone)]
+pub struct RegionFailover<C> {
+    endpoints: Arc<[(String, C)]>,
+    threshold: u32,
+    failback: Duration,
+    state: Arc<Mutex<FailoverState>>,
+}
+
+#[derive(Debug)]
+struct FailoverState {
+    active: usize,
+    failures: u32,
+    failed_over_at: Option<Instant>,
+}
+
+impl<C: Clone> RegionFailover<C> {
+    /// Creates a failover without other regions than the primary one.
+    pub fn single(client: C) -> Self {
+        Self::with_settings(
+            vec![("primary".to_owned(), client)],
+            u32::MAX,
+            Duration::MAX,
+        )
+    }
+
+    /// Creates a failover between the clients of the `targets` of the configuration, named as
+    /// with `endpoint_name`.
+    pub fn new(
+        mut endpoints: Vec<(String, C)>,
+        failover: Option<&FailoverConfig>,
+    ) -> crate::Result<Self> {
+        match failover {
+            Some(failover) if failover.regions.is_empty() => {
+                Err("`failover.regions` can't be empty.".into())
+            }
+            Some(failover) if failover.threshold == 0 => {
+                Err("`failover.threshold` must be greater than zero.".into())
+            }
+            Some(failover) => Ok(Self::with_settings(
+                endpoints,
+                failover.threshold,
+                Duration::from_secs(failover.failback_secs),
+            )),
+            None => {
+                let (_, client) = endpoints.pop().expect("primary client must exist");
+                Ok(Self::single(client))
+            }
+        }
+    }
+
+    fn with_settings(endpoints: Vec<(String, C)>, threshold: u32, failback: Duration) -> Self {
+        Self {
+            endpoints: endpoints.into(),
+            threshold,
+            failback,
+            state: Arc::new(Mutex::new(FailoverState {
+                active: 0,
+                failures: 0,
+                failed_over_at: None,
+            })),
+        }
+    }
+
+    /// The client of the primary region.
+    pub fn primary(&self) -> C {
+        self.endpoints[0].1.clone()
+    }
+
+    /// Selects the client to send the next request with, along with its index, to report the
+    /// outcome of the request with.
+    pub fn select(&self) -> (usize, C) {
+        let mut state = self.state.lock().expect("poisoned lock");
+        if let Some(failed_over_at) = state.failed_over_at {
+            if failed_over_at.elapsed() >= self.failback {
+                self.switch(&mut state, 0, "failback");
+            }
+        }
+        (state.active, self.endpoints[state.active].1.clone())
+    }
+
+    /// Reports the outcome of a request sent with the client at `index`.
+    ///
+    /// Only retriable errors are counted as failures of the region, as other errors are caused by
+    /// the request itself.
+    pub fn report<T, E>(&self, index: usize, result: &Result<T, SdkError<E>>) {
+        match result {
+            Ok(_) => self.report_success(index),
+            Err(error) if is_retriable_error(error) => self.report_failure(index),
+            Err(_) => {}
+        }
+    }
+
+    pub fn report_success(&self, index: usize) {
+        if self.endpoints.len() == 1 {
+            return;
+        }
+        emit!(AwsEndpointRequestServed {
+            endpoint: &self.endpoints[index].0,
+        });
+        let mut state = self.state.lock().expect("poisoned lock");
+        if state.active == index {
+            state.failures = 0;
+        }
+    }
+
+    pub fn report_failure(&self, index: usize) {
+        let mut state = self.state.lock().expect("poisoned lock");
+        // Failures of requests sent before the last switch don't count against the new region.
+        if state.active != index || self.endpoints.len() == 1 {
+            return;
+        }
+        state.failures += 1;
+        if state.failures >= self.threshold {
+            let next = (index + 1) % self.endpoints.len();
+            self.switch(&mut state, next, "failover");
+        }
+    }
+
+    fn switch(&self, state: &mut FailoverState, to: usize, reason: &'static str) {
+        if state.active != to {
+            emit!(AwsEndpointSwitched {
+                from: &self.endpoints[state.active].0,
+                to: &self.endpoints[to].0,
+                reason,
+            });
+        }
+        state.active = to;
+        state.failures = 0;
+        state.failed_over_at = (to != 0).then(Instant::now);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn failover(failback: Duration) -> RegionFailover<&'static str> {
+        RegionFailover::with_settings(
+            vec![
+                ("us-east-1".to_owned(), "primary"),
+                ("us-west-2".to_owned(), "secondary"),
+            ],
+            2,
+            failback,
+        )
+    }
+
+    #[test]
+    fn fails_over_after_consecutive_failures() {
+        let failover = failover(Duration::from_secs(300));
+
+        failover.report_failure(0);
+        failover.report_success(0);
+        failover.report_failure(0);
+        assert_eq!(failover.select(), (0, "primary"));
+
+        failover.report_failure(0);
+        assert_eq!(failover.select(), (1, "secondary"));
+
+        // Late failures of the primary region don't count against the secondary one.
+        failover.report_failure(0);
+        failover.report_failure(0);
+        assert_eq!(failover.select(), (1, "secondary"));
+
+        failover.report_failure(1);
+        failover.report_failure(1);
+        assert_eq!(failover.select(), (0, "primary"));
+    }
+
+    #[test]
+    fn fails_back_to_primary() {
+        let failover = failover(Duration::ZERO);
+
+        failover.report_failure(0);
+        failover.report_failure(0);
+        assert_eq!(failover.select(), (0, "primary"));
+    }
+
+    #[test]
+    fn single_region_never_fails_over() {
+        let failover = RegionFailover::single("primary");
+
+        for _ in 0..10 {
+            failover.report_failure(0);
+        }
+        assert_eq!(failover.select(), (0, "primary"));
+    }
+}
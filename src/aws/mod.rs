@@ -1,4 +1,5 @@
 pub mod auth;
+pub mod failover;
 pub mod region;
 
 use std::future::Future;
@@ -25,9 +26,10 @@ use aws_types::credentials::{ProvideCredentials, SharedCredentialsProvider};
 use aws_types::region::Region;
 use aws_types::SdkConfig;
 use bytes::Bytes;
+pub use failover::{create_failover_clients, FailoverConfig, RegionFailover};
 use once_cell::sync::OnceCell;
 use regex::RegexSet;
-pub use region::RegionOrEndpoint;
+pub use region::{EndpointVariant, RegionOrEndpoint};
 use tower::{Layer, Service, ServiceBuilder};
 
 use crate::config::ProxyConfig;
@@ -81,6 +83,9 @@ pub trait ClientBuilder {
     type Client;
     type DefaultMiddleware: SmithyMiddleware<DynConnector> + Clone + Send + Sync + 'static;
 
+    /// The name of the service in the hostnames of its endpoints.
+    const SERVICE_NAME: &'static str;
+
     fn default_middleware() -> Self::DefaultMiddleware;
 
     fn build(client: aws_smithy_client::Client, config: &aws_types::SdkConfig) -> Self::Client;
@@ -135,6 +140,7 @@ pub async fn create_client<T: ClientBuilder>(
     auth: &AwsAuthentication,
     region: Option<Region>,
     endpoint: Option<Endpoint>,
+    endpoint_variant: Option<EndpointVariant>,
     proxy: &ProxyConfig,
     tls_options: &Option<TlsConfig>,
     is_sink: bool,
@@ -145,6 +151,14 @@ pub async fn create_client<T: ClientBuilder>(
     // error up front if later SDK calls will fail due to lack of region configuration
     let region = resolve_region(region).await?;
 
+    let endpoint = match (endpoint, endpoint_variant) {
+        (Some(_), Some(_)) => {
+            return Err("`endpoint` and `endpoint_variant` can't both be set.".into())
+        }
+        (None, Some(variant)) => Some(variant.endpoint(T::SERVICE_NAME, &region)?),
+        (endpoint, None) => endpoint,
+    };
+
     // Build the configuration first.
     let mut config_builder = SdkConfig::builder()
         .credentials_provider(auth.credentials_provider(region.clone()).await?)
@@ -15,6 +15,54 @@ pub struct RegionOrEndpoint {
 
     /// The API endpoint of the service.
     pub endpoint: Option<String>,
+
+    /// The variant of the standard endpoint of the service to use.
+    ///
+    /// Can't be combined with a custom `endpoint`.
+    pub endpoint_variant: Option<EndpointVariant>,
+}
+
+/// A variant of the standard endpoints of AWS services.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum EndpointVariant {
+    /// Use the FIPS 140-2 validated endpoint.
+    Fips,
+
+    /// Use the dual-stack endpoint, reachable over both IPv4 and IPv6.
+    Dualstack,
+}
+
+impl EndpointVariant {
+    /// Builds the endpoint of this variant for `service` in `region`.
+    pub fn endpoint(self, service: &str, region: &Region) -> crate::Result<Endpoint> {
+        let region = region.as_ref();
+        let china = region.starts_with("cn-");
+        let uri = match self {
+            Self::Fips if china => {
+                return Err(format!("There are no FIPS endpoints in region {:?}.", region).into())
+            }
+            Self::Fips => format!("https://{}-fips.{}.amazonaws.com", service, region),
+            // S3 predates the shared dual-stack domains.
+            Self::Dualstack if service == "s3" => format!(
+                "https://s3.dualstack.{}.amazonaws.com{}",
+                region,
+                if china { ".cn" } else { "" }
+            ),
+            Self::Dualstack => format!(
+                "https://{}.{}.{}",
+                service,
+                region,
+                if china {
+                    "api.amazonwebservices.com.cn"
+                } else {
+                    "api.aws"
+                }
+            ),
+        };
+        Ok(Endpoint::immutable(Uri::from_str(&uri)?))
+    }
 }
 
 impl RegionOrEndpoint {
@@ -22,6 +70,7 @@ impl RegionOrEndpoint {
         Self {
             region: Some(region),
             endpoint: None,
+            endpoint_variant: None,
         }
     }
 
@@ -29,6 +78,7 @@ impl RegionOrEndpoint {
         Self {
             region: Some(region.into()),
             endpoint: Some(endpoint.into()),
+            endpoint_variant: None,
         }
     }
 
@@ -73,4 +123,32 @@ mod tests {
         "#})
         .is_ok());
     }
+
+    #[test]
+    fn endpoint_variants() {
+        let region = Region::new("us-east-1");
+        let uri = |variant: EndpointVariant, service| {
+            variant
+                .endpoint(service, &region)
+                .unwrap()
+                .uri()
+                .to_string()
+        };
+
+        assert_eq!(
+            uri(EndpointVariant::Fips, "sqs"),
+            "https://sqs-fips.us-east-1.amazonaws.com/"
+        );
+        assert_eq!(
+            uri(EndpointVariant::Dualstack, "sqs"),
+            "https://sqs.us-east-1.api.aws/"
+        );
+        assert_eq!(
+            uri(EndpointVariant::Dualstack, "s3"),
+            "https://s3.dualstack.us-east-1.amazonaws.com/"
+        );
+        assert!(EndpointVariant::Fips
+            .endpoint("sqs", &Region::new("cn-north-1"))
+            .is_err());
+    }
 }
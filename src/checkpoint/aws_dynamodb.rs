@@ -0,0 +1,129 @@
+use async_trait::async_trait;
+use aws_sdk_dynamodb::{model::AttributeValue, types::Blob, Client};
+use bytes::Bytes;
+use vector_config::configurable_component;
+
+use super::{checkpoint_name, default_key_prefix, CheckpointStore, CheckpointStoreError};
+use crate::{
+    aws::{create_client, AwsAuthentication, RegionOrEndpoint},
+    common::dynamodb::DynamoDbClientBuilder,
+    config::{ComponentKey, ProxyConfig},
+};
+
+/// The attribute holding the name of a checkpoint, which is the partition key of the table.
+const KEY_ATTRIBUTE: &str = "key";
+
+/// The attribute holding the contents of a checkpoint.
+const CHECKPOINT_ATTRIBUTE: &str = "checkpoint";
+
+/// Configuration for storing checkpoints in an AWS DynamoDB table.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct DynamoDbCheckpointStoreConfig {
+    /// The name of the table to store checkpoints in.
+    ///
+    /// The partition key of the table must be a string attribute named `key`. The checkpoints
+    /// are stored in the binary `checkpoint` attribute of the items.
+    table: String,
+
+    /// The prefix of the keys of the items checkpoints are stored in.
+    ///
+    /// The checkpoint of a source is stored in the item whose key is made of this prefix followed
+    /// by the identity of the node, the ID of the source and the name of the checkpoint, such as
+    /// `vector/checkpoints/node-1/my_source/checkpoints.json`.
+    #[serde(default = "default_key_prefix")]
+    key_prefix: String,
+
+    /// The identity of the node the checkpoints are stored for.
+    ///
+    /// A node resumes from the checkpoints stored under its identity, which defaults to the
+    /// hostname. Set it to an identity that outlives the node, such as the name of the
+    /// Kubernetes node with `${VECTOR_SELF_NODE_NAME}`, to resume on a node replacing it.
+    node_id: Option<String>,
+
+    #[serde(flatten)]
+    #[configurable(derived)]
+    region: RegionOrEndpoint,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    auth: AwsAuthentication,
+}
+
+impl DynamoDbCheckpointStoreConfig {
+    pub(super) async fn build(
+        &self,
+        key: &ComponentKey,
+        file_name: &str,
+        proxy: &ProxyConfig,
+    ) -> crate::Result<Box<dyn CheckpointStore>> {
+        let client = create_client::<DynamoDbClientBuilder>(
+            &self.auth,
+            self.region.region(),
+            self.region.endpoint()?,
+            self.region.endpoint_variant,
+            proxy,
+            &None,
+            false,
+        )
+        .await?;
+        Ok(Box::new(DynamoDbCheckpointStore {
+            client,
+            table: self.table.clone(),
+            key: checkpoint_name(&self.key_prefix, self.node_id.as_deref(), key, file_name)?,
+        }))
+    }
+}
+
+/// A checkpoint store holding the checkpoint of a source in a DynamoDB item.
+struct DynamoDbCheckpointStore {
+    client: Client,
+    table: String,
+    key: String,
+}
+
+#[async_trait]
+impl CheckpointStore for DynamoDbCheckpointStore {
+    async fn load(&self) -> Result<Option<Bytes>, CheckpointStoreError> {
+        let output = self
+            .client
+            .get_item()
+            .table_name(&self.table)
+            .key(KEY_ATTRIBUTE, AttributeValue::S(self.key.clone()))
+            .consistent_read(true)
+            .send()
+            .await
+            .map_err(|error| self.error(error))?;
+        Ok(output
+            .item()
+            .and_then(|item| item.get(CHECKPOINT_ATTRIBUTE))
+            .and_then(|checkpoint| checkpoint.as_b().ok())
+            .map(|checkpoint| Bytes::copy_from_slice(checkpoint.as_ref())))
+    }
+
+    async fn store(&self, checkpoint: Bytes) -> Result<(), CheckpointStoreError> {
+        self.client
+            .put_item()
+            .table_name(&self.table)
+            .item(KEY_ATTRIBUTE, AttributeValue::S(self.key.clone()))
+            .item(
+                CHECKPOINT_ATTRIBUTE,
+                AttributeValue::B(Blob::new(checkpoint.to_vec())),
+            )
+            .send()
+            .await
+            .map(|_| ())
+            .map_err(|error| self.error(error))
+    }
+}
+
+impl DynamoDbCheckpointStore {
+    fn error(&self, source: impl Into<crate::Error>) -> CheckpointStoreError {
+        CheckpointStoreError::DynamoDb {
+            table: self.table.clone(),
+            key: self.key.clone(),
+            source: source.into(),
+        }
+    }
+}
@@ -0,0 +1,121 @@
+use async_trait::async_trait;
+use aws_sdk_s3::{types::SdkError, Client};
+use bytes::Bytes;
+use vector_config::configurable_component;
+
+use super::{checkpoint_name, default_key_prefix, CheckpointStore, CheckpointStoreError};
+use crate::{
+    aws::{create_client, AwsAuthentication, RegionOrEndpoint},
+    common::s3::S3ClientBuilder,
+    config::{ComponentKey, ProxyConfig},
+};
+
+/// Configuration for storing checkpoints in an AWS S3 bucket.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct S3CheckpointStoreConfig {
+    /// The name of the bucket to store checkpoints in.
+    bucket: String,
+
+    /// The prefix of the keys of the objects checkpoints are stored in.
+    ///
+    /// The checkpoint of a source is stored in the object whose key is made of this prefix
+    /// followed by the identity of the node, the ID of the source and the name of the checkpoint,
+    /// such as `vector/checkpoints/node-1/my_source/checkpoints.json`.
+    #[serde(default = "default_key_prefix")]
+    key_prefix: String,
+
+    /// The identity of the node the checkpoints are stored for.
+    ///
+    /// A node resumes from the checkpoints stored under its identity, which defaults to the
+    /// hostname. Set it to an identity that outlives the node, such as the name of the
+    /// Kubernetes node with `${VECTOR_SELF_NODE_NAME}`, to resume on a node replacing it.
+    node_id: Option<String>,
+
+    #[serde(flatten)]
+    #[configurable(derived)]
+    region: RegionOrEndpoint,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    auth: AwsAuthentication,
+}
+
+impl S3CheckpointStoreConfig {
+    pub(super) async fn build(
+        &self,
+        key: &ComponentKey,
+        file_name: &str,
+        proxy: &ProxyConfig,
+    ) -> crate::Result<Box<dyn CheckpointStore>> {
+        let client = create_client::<S3ClientBuilder>(
+            &self.auth,
+            self.region.region(),
+            self.region.endpoint()?,
+            self.region.endpoint_variant,
+            proxy,
+            &None,
+            false,
+        )
+        .await?;
+        Ok(Box::new(S3CheckpointStore {
+            client,
+            bucket: self.bucket.clone(),
+            key: checkpoint_name(&self.key_prefix, self.node_id.as_deref(), key, file_name)?,
+        }))
+    }
+}
+
+/// A checkpoint store holding the checkpoint of a source in an S3 object.
+struct S3CheckpointStore {
+    client: Client,
+    bucket: String,
+    key: String,
+}
+
+#[async_trait]
+impl CheckpointStore for S3CheckpointStore {
+    async fn load(&self) -> Result<Option<Bytes>, CheckpointStoreError> {
+        let object = match self
+            .client
+            .get_object()
+            .bucket(&self.bucket)
+            .key(&self.key)
+            .send()
+            .await
+        {
+            Ok(object) => object,
+            Err(SdkError::ServiceError { err, raw: _ }) if err.is_no_such_key() => return Ok(None),
+            Err(error) => return Err(self.error(error)),
+        };
+        let checkpoint = object
+            .body
+            .collect()
+            .await
+            .map_err(|error| self.error(error))?;
+        Ok(Some(checkpoint.into_bytes()))
+    }
+
+    async fn store(&self, checkpoint: Bytes) -> Result<(), CheckpointStoreError> {
+        self.client
+            .put_object()
+            .bucket(&self.bucket)
+            .key(&self.key)
+            .body(checkpoint.into())
+            .send()
+            .await
+            .map(|_| ())
+            .map_err(|error| self.error(error))
+    }
+}
+
+impl S3CheckpointStore {
+    fn error(&self, source: impl Into<crate::Error>) -> CheckpointStoreError {
+        CheckpointStoreError::S3 {
+            bucket: self.bucket.clone(),
+            key: self.key.clone(),
+            source: source.into(),
+        }
+    }
+}
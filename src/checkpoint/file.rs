@@ -0,0 +1,57 @@
+use std::{io, path::PathBuf};
+
+use async_trait::async_trait;
+use bytes::Bytes;
+
+use super::{write_atomically, CheckpointStore, CheckpointStoreError};
+
+/// A checkpoint store backed by a local file.
+#[derive(Clone, Debug)]
+pub struct FileCheckpointStore {
+    path: PathBuf,
+}
+
+impl FileCheckpointStore {
+    pub const fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+}
+
+#[async_trait]
+impl CheckpointStore for FileCheckpointStore {
+    async fn load(&self) -> Result<Option<Bytes>, CheckpointStoreError> {
+        match tokio::fs::read(&self.path).await {
+            Ok(checkpoint) if checkpoint.is_empty() => Ok(None),
+            Ok(checkpoint) => Ok(Some(checkpoint.into())),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(source) => Err(CheckpointStoreError::File {
+                path: self.path.clone(),
+                source,
+            }),
+        }
+    }
+
+    async fn store(&self, checkpoint: Bytes) -> Result<(), CheckpointStoreError> {
+        write_atomically(&self.path, &checkpoint).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::tempdir;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn stores_checkpoints() {
+        let directory = tempdir().unwrap();
+        let store = FileCheckpointStore::new(directory.path().join("checkpoint.txt"));
+        assert_eq!(store.load().await.unwrap(), None);
+
+        store.store(Bytes::from("first")).await.unwrap();
+        assert_eq!(store.load().await.unwrap(), Some(Bytes::from("first")));
+
+        store.store(Bytes::from("2nd")).await.unwrap();
+        assert_eq!(store.load().await.unwrap(), Some(Bytes::from("2nd")));
+    }
+}
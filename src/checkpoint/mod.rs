@@ -0,0 +1,369 @@
+//! Storage for the checkpoints of sources, such as the positions they read files up to or the
+//! cursors of the journals they follow.
+//!
+//! Checkpoints are stored as opaque bytes, encoded by each source as it sees fit, in a file under
+//! the data directory of the source by default. Remote stores allow sources to resume where they
+//! left off when Vector runs on ephemeral nodes, whose data directory doesn't outlive them.
+
+use std::{
+    io,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use snafu::Snafu;
+use tokio::sync::Notify;
+use vector_config::configurable_component;
+use vector_core::config::GlobalOptions;
+
+use crate::{
+    config::{ComponentKey, ProxyConfig},
+    internal_events::CheckpointMirrorError,
+    sources::Source,
+};
+
+#[cfg(feature = "checkpoint-stores-aws_dynamodb")]
+mod aws_dynamodb;
+#[cfg(feature = "checkpoint-stores-aws_s3")]
+mod aws_s3;
+mod file;
+#[cfg(feature = "checkpoint-stores-redis")]
+mod redis;
+
+#[cfg(feature = "checkpoint-stores-aws_dynamodb")]
+pub use self::aws_dynamodb::DynamoDbCheckpointStoreConfig;
+#[cfg(feature = "checkpoint-stores-aws_s3")]
+pub use self::aws_s3::S3CheckpointStoreConfig;
+pub use self::file::FileCheckpointStore;
+#[cfg(feature = "checkpoint-stores-redis")]
+pub use self::redis::RedisCheckpointStoreConfig;
+
+/// A store holding the checkpoint of a single source.
+#[async_trait]
+pub trait CheckpointStore: Send + Sync {
+    /// Loads the checkpoint, if one was stored.
+    ///
+    /// # Errors
+    ///
+    /// If the store can't be read, an error variant explaining the issue is returned.
+    async fn load(&self) -> Result<Option<Bytes>, CheckpointStoreError>;
+
+    /// Stores the checkpoint, replacing the previous one.
+    ///
+    /// # Errors
+    ///
+    /// If the store can't be written, an error variant explaining the issue is returned.
+    async fn store(&self, checkpoint: Bytes) -> Result<(), CheckpointStoreError>;
+}
+
+#[derive(Debug, Snafu)]
+pub enum CheckpointStoreError {
+    #[snafu(display("Failed to access checkpoint file {:?}: {}", path, source))]
+    File { path: PathBuf, source: io::Error },
+
+    #[cfg(feature = "checkpoint-stores-redis")]
+    #[snafu(display("Failed to access checkpoint key {:?} in Redis: {}", key, source))]
+    Redis {
+        key: String,
+        source: ::redis::RedisError,
+    },
+
+    #[cfg(feature = "checkpoint-stores-aws_s3")]
+    #[snafu(display(
+        "Failed to access checkpoint object {:?} in S3 bucket {:?}: {}",
+        key,
+        bucket,
+        source
+    ))]
+    S3 {
+        bucket: String,
+        key: String,
+        source: crate::Error,
+    },
+
+    #[cfg(feature = "checkpoint-stores-aws_dynamodb")]
+    #[snafu(display(
+        "Failed to access checkpoint item {:?} in DynamoDB table {:?}: {}",
+        key,
+        table,
+        source
+    ))]
+    DynamoDb {
+        table: String,
+        key: String,
+        source: crate::Error,
+    },
+}
+
+/// Where the checkpoints of a source are stored.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum CheckpointStoreConfig {
+    /// Stores checkpoints in the data directory of the source.
+    File,
+
+    /// Stores checkpoints in Redis.
+    #[cfg(feature = "checkpoint-stores-redis")]
+    Redis(#[configurable(derived)] RedisCheckpointStoreConfig),
+
+    /// Stores checkpoints in an AWS S3 bucket.
+    #[cfg(feature = "checkpoint-stores-aws_s3")]
+    AwsS3(#[configurable(derived)] S3CheckpointStoreConfig),
+
+    /// Stores checkpoints in an AWS DynamoDB table.
+    #[cfg(feature = "checkpoint-stores-aws_dynamodb")]
+    AwsDynamodb(#[configurable(derived)] DynamoDbCheckpointStoreConfig),
+}
+
+impl Default for CheckpointStoreConfig {
+    fn default() -> Self {
+        Self::File
+    }
+}
+
+impl CheckpointStoreConfig {
+    /// Whether the checkpoints are stored in the data directory of the source.
+    pub const fn is_local(&self) -> bool {
+        matches!(self, Self::File)
+    }
+
+    /// Builds the store for the checkpoints of the source with the given key.
+    ///
+    /// Local checkpoints are stored in the file named `file_name` in the data directory of the
+    /// source, resolved from `data_dir` and the global options, while remote stores derive the
+    /// name of the checkpoint from the identity of the node, the key of the source and
+    /// `file_name`.
+    ///
+    /// # Errors
+    ///
+    /// If the data directory can't be resolved, or the client of a remote store can't be built,
+    /// an error variant explaining the issue is returned.
+    #[cfg_attr(
+        not(any(
+            feature = "checkpoint-stores-aws_s3",
+            feature = "checkpoint-stores-aws_dynamodb"
+        )),
+        allow(unused_variables)
+    )]
+    pub async fn build(
+        &self,
+        key: &ComponentKey,
+        globals: &GlobalOptions,
+        data_dir: Option<&PathBuf>,
+        file_name: &str,
+        proxy: &ProxyConfig,
+    ) -> crate::Result<Box<dyn CheckpointStore>> {
+        match self {
+            Self::File => {
+                let data_dir = globals.resolve_and_make_data_subdir(data_dir, key.id())?;
+                Ok(Box::new(FileCheckpointStore::new(data_dir.join(file_name))))
+            }
+            #[cfg(feature = "checkpoint-stores-redis")]
+            Self::Redis(config) => config.build(key, file_name).await,
+            #[cfg(feature = "checkpoint-stores-aws_s3")]
+            Self::AwsS3(config) => config.build(key, file_name, proxy).await,
+            #[cfg(feature = "checkpoint-stores-aws_dynamodb")]
+            Self::AwsDynamodb(config) => config.build(key, file_name, proxy).await,
+        }
+    }
+}
+
+/// Keeps a copy of a checkpoint file, written by a source on its own, in a remote store.
+pub struct MirroredCheckpointFile {
+    store: Box<dyn CheckpointStore>,
+    path: PathBuf,
+    written: Arc<Notify>,
+}
+
+impl MirroredCheckpointFile {
+    pub fn new(store: Box<dyn CheckpointStore>, path: PathBuf) -> Self {
+        Self {
+            store,
+            path,
+            written: Arc::new(Notify::new()),
+        }
+    }
+
+    /// Gets the hook the source calls whenever it writes the checkpoint file, to copy it to the
+    /// store.
+    pub fn on_written(&self) -> Arc<dyn Fn() + Send + Sync> {
+        let written = Arc::clone(&self.written);
+        Arc::new(move || written.notify_one())
+    }
+
+    /// Restores the checkpoint file from the store, unless it already exists.
+    ///
+    /// An existing file is left as it is, as it was written by a previous run on this node, and
+    /// is at least as recent as the copy in the store.
+    ///
+    /// # Errors
+    ///
+    /// If the store can't be read, or the file can't be written, an error variant explaining
+    /// the issue is returned.
+    pub async fn restore(&self) -> Result<(), CheckpointStoreError> {
+        if self.path.exists() {
+            return Ok(());
+        }
+        if let Some(checkpoint) = self.store.load().await? {
+            write_atomically(&self.path, &checkpoint).await?;
+        }
+        Ok(())
+    }
+
+    /// Wraps `source`, copying the checkpoint file to the store whenever the source writes it
+    /// and it changed, and once more after the source stopped.
+    pub fn mirror(self, source: Source) -> Source {
+        Box::pin(async move {
+            let mut source = source;
+            let mut mirrored = None;
+            let result = loop {
+                tokio::select! {
+                    result = &mut source => break result,
+                    _ = self.written.notified() => self.copy(&mut mirrored).await,
+                }
+            };
+            self.copy(&mut mirrored).await;
+            result
+        })
+    }
+
+    async fn copy(&self, mirrored: &mut Option<Bytes>) {
+        let checkpoint = match tokio::fs::read(&self.path).await {
+            Ok(checkpoint) => Bytes::from(checkpoint),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => return,
+            Err(source) => {
+                emit!(CheckpointMirrorError {
+                    error: &CheckpointStoreError::File {
+                        path: self.path.clone(),
+                        source,
+                    },
+                });
+                return;
+            }
+        };
+        if mirrored.as_ref() == Some(&checkpoint) {
+            return;
+        }
+        match self.store.store(checkpoint.clone()).await {
+            Ok(()) => *mirrored = Some(checkpoint),
+            Err(error) => emit!(CheckpointMirrorError { error: &error }),
+        }
+    }
+}
+
+/// Writes `contents` to a temporary file next to `path` and renames it over `path`, so that the
+/// file is never left partially written.
+async fn write_atomically(path: &Path, contents: &[u8]) -> Result<(), CheckpointStoreError> {
+    let tmp_path = path.with_extension("new");
+    let result = async {
+        tokio::fs::write(&tmp_path, contents).await?;
+        tokio::fs::rename(&tmp_path, path).await
+    }
+    .await;
+    result.map_err(|source| CheckpointStoreError::File {
+        path: path.to_owned(),
+        source,
+    })
+}
+
+/// The name of the checkpoint file `file_name` of the component with the given key in remote
+/// stores, on the node with the given identity, or else on the host Vector runs on.
+///
+/// The same component runs on every node of a deployment, each reading its own inputs, so their
+/// checkpoints are kept apart.
+#[cfg(any(
+    feature = "checkpoint-stores-redis",
+    feature = "checkpoint-stores-aws_s3",
+    feature = "checkpoint-stores-aws_dynamodb"
+))]
+fn checkpoint_name(
+    key_prefix: &str,
+    node_id: Option<&str>,
+    key: &ComponentKey,
+    file_name: &str,
+) -> crate::Result<String> {
+    let node_id = match node_id {
+        Some(node_id) => node_id.to_owned(),
+        None => crate::get_hostname()?,
+    };
+    Ok(format!(
+        "{}{}/{}/{}",
+        key_prefix,
+        node_id,
+        key.id(),
+        file_name
+    ))
+}
+
+#[cfg(any(
+    feature = "checkpoint-stores-redis",
+    feature = "checkpoint-stores-aws_s3",
+    feature = "checkpoint-stores-aws_dynamodb"
+))]
+fn default_key_prefix() -> String {
+    "vector/checkpoints/".to_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::tempdir;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn mirrors_checkpoint_files() {
+        let directory = tempdir().unwrap();
+        let store = FileCheckpointStore::new(directory.path().join("store"));
+        store.store(Bytes::from("checkpoint")).await.unwrap();
+
+        let path = directory.path().join("checkpoints.json");
+        let mirrored = MirroredCheckpointFile::new(Box::new(store), path.clone());
+        mirrored.restore().await.unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "checkpoint");
+
+        // An existing file isn't overwritten by the copy in the store.
+        std::fs::write(&path, "newer").unwrap();
+        mirrored.restore().await.unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "newer");
+
+        let source: Source = Box::pin(async { Ok(()) });
+        mirrored.mirror(source).await.unwrap();
+        let store = FileCheckpointStore::new(directory.path().join("store"));
+        assert_eq!(store.load().await.unwrap(), Some(Bytes::from("newer")));
+    }
+
+    #[tokio::test]
+    async fn mirrors_checkpoint_files_when_written() {
+        let directory = tempdir().unwrap();
+        let store = FileCheckpointStore::new(directory.path().join("store"));
+        let path = directory.path().join("checkpoints.json");
+        let mirrored = MirroredCheckpointFile::new(Box::new(store), path.clone());
+        let on_written = mirrored.on_written();
+
+        let (stop, stopped) = tokio::sync::oneshot::channel::<()>();
+        let source: Source = Box::pin(async move {
+            stopped.await.ok();
+            Ok(())
+        });
+        let mirror = tokio::spawn(mirrored.mirror(source));
+
+        std::fs::write(&path, "written").unwrap();
+        on_written();
+        let store = FileCheckpointStore::new(directory.path().join("store"));
+        let mut checkpoint = None;
+        for _ in 0..50 {
+            checkpoint = store.load().await.unwrap();
+            if checkpoint.is_some() {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+        }
+        assert_eq!(checkpoint, Some(Bytes::from("written")));
+
+        stop.send(()).unwrap();
+        mirror.await.unwrap().unwrap();
+    }
+}
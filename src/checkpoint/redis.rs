@@ -0,0 +1,79 @@
+use async_trait::async_trait;
+use bytes::Bytes;
+use redis::aio::ConnectionManager;
+use snafu::ResultExt;
+use vector_config::configurable_component;
+
+use super::{
+    checkpoint_name, default_key_prefix, CheckpointStore, CheckpointStoreError, RedisSnafu,
+};
+use crate::config::ComponentKey;
+
+/// Configuration for storing checkpoints in Redis.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct RedisCheckpointStoreConfig {
+    /// The Redis URL to connect to.
+    ///
+    /// The URL _must_ take the form of `protocol://server:port/db` where the protocol can either be
+    /// `redis` or `rediss` for connections secured via TLS.
+    url: String,
+
+    /// The prefix of the keys checkpoints are stored under.
+    ///
+    /// The checkpoint of a source is stored under the key made of this prefix followed by the
+    /// identity of the node, the ID of the source and the name of the checkpoint, such as
+    /// `vector/checkpoints/node-1/my_source/checkpoints.json`.
+    #[serde(default = "default_key_prefix")]
+    key_prefix: String,
+
+    /// The identity of the node the checkpoints are stored for.
+    ///
+    /// A node resumes from the checkpoints stored under its identity, which defaults to the
+    /// hostname. Set it to an identity that outlives the node, such as the name of the
+    /// Kubernetes node with `${VECTOR_SELF_NODE_NAME}`, to resume on a node replacing it.
+    node_id: Option<String>,
+}
+
+impl RedisCheckpointStoreConfig {
+    pub(super) async fn build(
+        &self,
+        key: &ComponentKey,
+        file_name: &str,
+    ) -> crate::Result<Box<dyn CheckpointStore>> {
+        let client = redis::Client::open(self.url.as_str())?;
+        let connection = client.get_tokio_connection_manager().await?;
+        Ok(Box::new(RedisCheckpointStore {
+            connection,
+            key: checkpoint_name(&self.key_prefix, self.node_id.as_deref(), key, file_name)?,
+        }))
+    }
+}
+
+/// A checkpoint store holding the checkpoint of a source in a Redis key.
+struct RedisCheckpointStore {
+    connection: ConnectionManager,
+    key: String,
+}
+
+#[async_trait]
+impl CheckpointStore for RedisCheckpointStore {
+    async fn load(&self) -> Result<Option<Bytes>, CheckpointStoreError> {
+        redis::cmd("GET")
+            .arg(&self.key)
+            .query_async::<_, Option<Vec<u8>>>(&mut self.connection.clone())
+            .await
+            .map(|checkpoint| checkpoint.map(Bytes::from))
+            .context(RedisSnafu { key: &self.key })
+    }
+
+    async fn store(&self, checkpoint: Bytes) -> Result<(), CheckpointStoreError> {
+        redis::cmd("SET")
+            .arg(&self.key)
+            .arg(checkpoint.as_ref())
+            .query_async::<_, ()>(&mut self.connection.clone())
+            .await
+            .context(RedisSnafu { key: &self.key })
+    }
+}
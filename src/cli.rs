@@ -8,7 +8,10 @@ use crate::service;
 use crate::tap;
 #[cfg(feature = "api-client")]
 use crate::top;
-use crate::{config, generate, get_version, graph, list, unit_test, validate};
+use crate::{
+    config::{self, feature_flags::FeatureFlag},
+    generate, get_version, graph, list, unit_test, validate,
+};
 
 #[derive(Parser, Debug)]
 #[command(rename_all = "kebab-case")]
@@ -156,6 +159,16 @@ pub struct RootOpts {
         default_value = "10"
     )]
     pub internal_log_rate_limit: u64,
+
+    /// Enable feature flags, such as `alpha_components`, in addition to those enabled by the
+    /// `feature_flags` global option.
+    #[arg(long, env = "VECTOR_FEATURE_FLAGS", value_delimiter(','))]
+    pub feature_flags: Vec<FeatureFlag>,
+
+    /// Read the policy the configuration must comply with from a file, in addition to the `policy`
+    /// section of the configuration. The format is detected from the file name.
+    #[arg(long, env = "VECTOR_POLICY")]
+    pub policy: Option<PathBuf>,
 }
 
 impl RootOpts {
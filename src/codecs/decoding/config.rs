@@ -38,6 +38,8 @@ impl DecodingConfig {
         // Build the deserializer.
         let deserializer = self.decoding.build();
 
-        Decoder::new(framer, deserializer).with_log_namespace(self.log_namespace)
+        Decoder::new(framer, deserializer)
+            .with_log_namespace(self.log_namespace)
+            .with_on_error(self.decoding.on_error())
     }
 }
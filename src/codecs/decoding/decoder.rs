@@ -1,13 +1,14 @@
 use bytes::{Bytes, BytesMut};
 use codecs::decoding::{
-    format::Deserializer as _, BoxedFramingError, BytesDeserializer, Deserializer, Error, Framer,
-    NewlineDelimitedDecoder,
+    format::Deserializer as _, BoxedFramingError, BytesDeserializer, DecodingErrorPolicy,
+    Deserializer, Error, Framer, NewlineDelimitedDecoder,
 };
+use lookup::{lookup_v2::ValuePath, metadata_path, path, PathPrefix};
 use smallvec::SmallVec;
-use vector_core::config::LogNamespace;
+use vector_core::config::{log_schema, LogNamespace};
 
 use crate::{
-    event::Event,
+    event::{Event, LogEvent},
     internal_events::{DecoderDeserializeError, DecoderFramingError},
 };
 
@@ -18,6 +19,7 @@ pub struct Decoder {
     framer: Framer,
     deserializer: Deserializer,
     log_namespace: LogNamespace,
+    on_error: DecodingErrorPolicy,
 }
 
 impl Default for Decoder {
@@ -26,6 +28,7 @@ impl Default for Decoder {
             framer: Framer::NewlineDelimited(NewlineDelimitedDecoder::new()),
             deserializer: Deserializer::Bytes(BytesDeserializer::new()),
             log_namespace: LogNamespace::Legacy,
+            on_error: DecodingErrorPolicy::Drop,
         }
     }
 }
@@ -39,6 +42,7 @@ impl Decoder {
             framer,
             deserializer,
             log_namespace: LogNamespace::Legacy,
+            on_error: DecodingErrorPolicy::Drop,
         }
     }
 
@@ -48,6 +52,12 @@ impl Decoder {
         self
     }
 
+    /// Sets how byte frames that can't be parsed are handled.
+    pub const fn with_on_error(mut self, on_error: DecodingErrorPolicy) -> Self {
+        self.on_error = on_error;
+        self
+    }
+
     /// Handles the framing result and parses it into a structured event, if
     /// possible.
     ///
@@ -68,13 +78,62 @@ impl Decoder {
 
         let byte_size = frame.len();
         // Parse structured events from the byte frame.
-        self.deserializer
-            .parse(frame, self.log_namespace)
-            .map(|events| Some((events, byte_size)))
-            .map_err(|error| {
+        match self.deserializer.parse(frame.clone(), self.log_namespace) {
+            Ok(events) => Ok(Some((events, byte_size))),
+            Err(error) => {
                 emit!(DecoderDeserializeError { error: &error });
-                Error::ParsingError(error)
-            })
+                if self.on_error == DecodingErrorPolicy::Drop {
+                    return Err(Error::ParsingError(error));
+                }
+
+                // Keep the raw frame, as the `bytes` codec would have decoded it.
+                let mut events = BytesDeserializer::new()
+                    .parse(frame, self.log_namespace)
+                    .map_err(Error::ParsingError)?;
+                for event in &mut events {
+                    if let Event::Log(log) = event {
+                        annotate_decoding_error(log, &error.to_string());
+                    }
+                }
+                Ok(Some((events, byte_size)))
+            }
+        }
+    }
+}
+
+/// Sets the error of a byte frame that couldn't be parsed on the event holding the raw frame.
+fn annotate_decoding_error(log: &mut LogEvent, error: &str) {
+    match log.namespace() {
+        LogNamespace::Legacy => {
+            log.insert(
+                (
+                    PathPrefix::Event,
+                    log_schema().metadata_key().concat(path!("decoding_error")),
+                ),
+                error,
+            );
+        }
+        LogNamespace::Vector => {
+            log.insert(metadata_path!("vector", "decoding_error"), error);
+        }
+    }
+}
+
+/// Whether the event holds a raw byte frame that couldn't be parsed.
+pub fn has_decoding_error(event: &Event) -> bool {
+    match event {
+        Event::Log(log) => match log.namespace() {
+            LogNamespace::Legacy => log
+                .get((
+                    PathPrefix::Event,
+                    log_schema().metadata_key().concat(path!("decoding_error")),
+                ))
+                .is_some(),
+            LogNamespace::Vector => log
+                .get(metadata_path!("vector", "decoding_error"))
+                .is_some(),
+        },
+        _ => false,
     }
 }
 
@@ -95,10 +154,10 @@ impl tokio_util::codec::Decoder for Decoder {
 
 #[cfg(test)]
 mod tests {
-    use super::Decoder;
+    use super::{has_decoding_error, Decoder};
     use bytes::Bytes;
     use codecs::{
-        decoding::{Deserializer, Framer},
+        decoding::{DecodingErrorPolicy, Deserializer, Framer},
         JsonDeserializer, NewlineDelimitedDecoder, StreamDecodingError,
     };
     use futures::{stream, StreamExt};
@@ -132,4 +191,30 @@ mod tests {
         let event = next.unwrap().0.pop().unwrap().into_log();
         assert_eq!(event.get("bar").unwrap(), &Value::from(2));
     }
+    #[tokio::test]
+    async fn framed_read_passes_through_undecodable_frames() {
+        let iter = stream::iter(
+            ["invalid\n", "{ \"foo\": 1 }\n"]
+                .into_iter()
+                .map(Bytes::from),
+        );
+        let stream = iter.map(Ok::<_, std::io::Error>);
+        let reader = StreamReader::new(stream);
+        let decoder = Decoder::new(
+            Framer::NewlineDelimited(NewlineDelimitedDecoder::new()),
+            Deserializer::Json(JsonDeserializer::new()),
+        )
+        .with_on_error(DecodingErrorPolicy::Passthrough);
+        let mut stream = FramedRead::new(reader, decoder);
+
+        let next = stream.next().await.unwrap();
+        let event = next.unwrap().0.pop().unwrap();
+        assert!(has_decoding_error(&event));
+        assert_eq!(event.as_log()["message"], Value::from("invalid"));
+
+        let next = stream.next().await.unwrap();
+        let event = next.unwrap().0.pop().unwrap();
+        assert!(!has_decoding_error(&event));
+        assert_eq!(event.as_log()["foo"], Value::from(1));
+    }
 }
@@ -2,4 +2,4 @@ mod config;
 mod decoder;
 
 pub use config::DecodingConfig;
-pub use decoder::Decoder;
+pub use decoder::{has_decoding_error, Decoder};
@@ -111,6 +111,7 @@ impl EncodingConfigWithFraming {
                 Serializer::Gelf(_)
                 | Serializer::Logfmt(_)
                 | Serializer::NativeJson(_)
+                | Serializer::Pretty(_)
                 | Serializer::RawMessage(_)
                 | Serializer::Text(_),
             ) => NewlineDelimitedEncoder::new().into(),
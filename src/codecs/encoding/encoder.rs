@@ -120,6 +120,7 @@ impl Encoder<Framer> {
                 | Serializer::Json(_)
                 | Serializer::Logfmt(_)
                 | Serializer::NativeJson(_)
+                | Serializer::Pretty(_)
                 | Serializer::RawMessage(_)
                 | Serializer::Text(_),
                 _,
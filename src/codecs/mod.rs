@@ -7,7 +7,7 @@ mod decoding;
 mod encoding;
 mod ready_frames;
 
-pub use decoding::{Decoder, DecodingConfig};
+pub use decoding::{has_decoding_error, Decoder, DecodingConfig};
 pub use encoding::{
     Encoder, EncodingConfig, EncodingConfigWithFraming, SinkType, TimestampFormat, Transformer,
 };
@@ -0,0 +1,19 @@
+use crate::aws::ClientBuilder;
+
+pub(crate) struct DynamoDbClientBuilder;
+
+impl ClientBuilder for DynamoDbClientBuilder {
+    type Config = aws_sdk_dynamodb::config::Config;
+    type Client = aws_sdk_dynamodb::client::Client;
+    type DefaultMiddleware = aws_sdk_dynamodb::middleware::DefaultMiddleware;
+
+    const SERVICE_NAME: &'static str = "dynamodb";
+
+    fn default_middleware() -> Self::DefaultMiddleware {
+        aws_sdk_dynamodb::middleware::DefaultMiddleware::new()
+    }
+
+    fn build(client: aws_smithy_client::Client, config: &aws_types::SdkConfig) -> Self::Client {
+        aws_sdk_dynamodb::client::Client::with_config(client, config.into())
+    }
+}
@@ -16,5 +16,13 @@ pub(crate) mod datadog;
 ))]
 pub(crate) mod sqs;
 
-#[cfg(any(feature = "sources-aws_s3", feature = "sinks-aws_s3"))]
+#[cfg(feature = "checkpoint-stores-aws_dynamodb")]
+pub(crate) mod dynamodb;
+
+#[cfg(any(
+    feature = "sources-aws_s3",
+    feature = "sinks-aws_s3",
+    feature = "checkpoint-stores-aws_s3",
+    feature = "providers-aws_s3"
+))]
 pub(crate) mod s3;
@@ -7,6 +7,8 @@ impl ClientBuilder for S3ClientBuilder {
     type Client = aws_sdk_s3::client::Client;
     type DefaultMiddleware = aws_sdk_s3::middleware::DefaultMiddleware;
 
+    const SERVICE_NAME: &'static str = "s3";
+
     fn default_middleware() -> Self::DefaultMiddleware {
         aws_sdk_s3::middleware::DefaultMiddleware::new()
     }
@@ -48,6 +48,8 @@ impl ConditionalConfig for VrlConfig {
 
         let mut config = CompileConfig::default();
         config.set_custom(enrichment_tables.clone());
+        config.set_custom(crate::host_metadata::store());
+        crate::protobuf_descriptors::set_custom(&mut config);
         config.set_read_only();
 
         let CompilationResult {
@@ -1,10 +1,11 @@
 use std::net::{Ipv4Addr, SocketAddr};
 
+use vector_common::sensitive_string::SensitiveString;
 use vector_config::configurable_component;
 
 /// API options.
 #[configurable_component]
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, PartialEq)]
 #[serde(default, deny_unknown_fields)]
 pub struct Options {
     /// Whether or not the API endpoint is available.
@@ -18,6 +19,49 @@ pub struct Options {
     /// Whether or not to expose the GraphQL playground on the API endpoint.
     #[serde(default = "default_playground")]
     pub playground: bool,
+
+    /// Tokens granting access to the API.
+    ///
+    /// If any tokens are configured, requests to the GraphQL endpoint must carry one of them as a
+    /// bearer token in the `Authorization` header. The health endpoint and the playground stay
+    /// accessible without a token.
+    #[serde(default)]
+    pub tokens: Vec<ApiToken>,
+}
+
+/// An API token, along with what it grants access to.
+#[configurable_component]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ApiToken {
+    /// The token, as sent by clients.
+    pub token: SensitiveString,
+
+    /// The scopes granted to the token.
+    pub scopes: Vec<ApiScope>,
+
+    /// Glob patterns of the IDs of the components the token has access to.
+    ///
+    /// Components not matching any of the patterns are left out of component queries, metrics,
+    /// and tap results. By default, the token has access to all components.
+    #[serde(default)]
+    pub components: Option<Vec<String>>,
+}
+
+/// A set of operations on the API.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub enum ApiScope {
+    /// Query health, components and metrics, and subscribe to metrics.
+    ReadMetrics,
+
+    /// Tap into the events flowing through components.
+    Tap,
+
+    /// Control the running instance, change its logging filter, and read its resolved
+    /// configuration.
+    Control,
 }
 
 impl Default for Options {
@@ -26,6 +70,7 @@ impl Default for Options {
             enabled: default_enabled(),
             playground: default_playground(),
             address: default_address(),
+            tokens: Vec::new(),
         }
     }
 }
@@ -66,10 +111,14 @@ impl Options {
             }
         };
 
+        let mut tokens = std::mem::take(&mut self.tokens);
+        tokens.extend(other.tokens);
+
         let options = Options {
             address,
             enabled: self.enabled | other.enabled,
             playground: self.playground & other.playground,
+            tokens,
         };
 
         *self = options;
@@ -83,6 +132,7 @@ fn bool_merge() {
         enabled: true,
         address: None,
         playground: false,
+        tokens: Vec::new(),
     };
 
     a.merge(Options::default()).unwrap();
@@ -93,6 +143,7 @@ fn bool_merge() {
             enabled: true,
             address: default_address(),
             playground: false,
+            tokens: Vec::new(),
         }
     );
 }
@@ -104,6 +155,7 @@ fn bind_merge() {
         enabled: true,
         address: Some(address),
         playground: true,
+        tokens: Vec::new(),
     };
 
     a.merge(Options::default()).unwrap();
@@ -114,6 +166,7 @@ fn bind_merge() {
             enabled: true,
             address: Some(address),
             playground: true,
+            tokens: Vec::new(),
         }
     );
 }
@@ -18,8 +18,8 @@ use super::api;
 #[cfg(feature = "enterprise")]
 use super::enterprise;
 use super::{
-    compiler, schema, ComponentKey, Config, EnrichmentTableOuter, HealthcheckOptions, SinkOuter,
-    SourceOuter, TestDefinition, TransformOuter,
+    compiler, schema, ComponentKey, Config, EnrichmentTableOuter, HealthcheckOptions,
+    PolicyOptions, SinkOuter, SourceOuter, TestDefinition, TransformOuter,
 };
 
 /// A complete Vector configuration.
@@ -48,6 +48,10 @@ pub struct ConfigBuilder {
     #[serde(default)]
     pub healthchecks: HealthcheckOptions,
 
+    #[configurable(derived)]
+    #[serde(default, skip_serializing_if = "PolicyOptions::is_empty")]
+    pub policy: PolicyOptions,
+
     /// All configured enrichment tables.
     #[serde(default)]
     pub enrichment_tables: IndexMap<ComponentKey, EnrichmentTableOuter>,
@@ -88,6 +92,8 @@ struct ConfigBuilderHash<'a> {
     schema: &'a schema::Options,
     global: &'a GlobalOptions,
     healthchecks: &'a HealthcheckOptions,
+    #[serde(skip_serializing_if = "PolicyOptions::is_empty")]
+    policy: &'a PolicyOptions,
     enrichment_tables: BTreeMap<&'a ComponentKey, &'a EnrichmentTableOuter>,
     sources: BTreeMap<&'a ComponentKey, &'a SourceOuter>,
     sinks: BTreeMap<&'a ComponentKey, &'a SinkOuter<String>>,
@@ -167,6 +173,7 @@ impl<'a> From<&'a ConfigBuilder> for ConfigBuilderHash<'a> {
             schema: &value.schema,
             global: &value.global,
             healthchecks: &value.healthchecks,
+            policy: &value.policy,
             enrichment_tables: value.enrichment_tables.iter().collect(),
             sources: value.sources.iter().collect(),
             sinks: value.sinks.iter().collect(),
@@ -188,6 +195,7 @@ impl From<Config> for ConfigBuilder {
             #[cfg(feature = "enterprise")]
             enterprise,
             healthchecks,
+            policy,
             enrichment_tables,
             sources,
             sinks,
@@ -218,6 +226,7 @@ impl From<Config> for ConfigBuilder {
             #[cfg(feature = "enterprise")]
             enterprise,
             healthchecks,
+            policy,
             enrichment_tables,
             sources,
             sinks,
@@ -331,6 +340,8 @@ impl ConfigBuilder {
 
         self.healthchecks.merge(with.healthchecks);
 
+        self.policy.append(with.policy);
+
         with.enrichment_tables.keys().for_each(|k| {
             if self.enrichment_tables.contains_key(k) {
                 errors.push(format!("duplicate enrichment_table name found: {}", k));
@@ -17,6 +17,11 @@ pub struct Opts {
     #[arg(short, long)]
     include_defaults: bool,
 
+    /// Print the configuration as Vector resolves it: with environment variables interpolated
+    /// and defaults filled in, but with sensitive values, such as passwords, masked
+    #[arg(long)]
+    resolved: bool,
+
     /// Read configuration from one or more files. Wildcard paths are supported.
     /// File format is detected from the file name.
     /// If zero files are specified the default config path
@@ -90,6 +95,17 @@ pub fn cmd(opts: &Opts) -> exitcode::ExitCode {
         None => return exitcode::CONFIG,
     };
 
+    if opts.resolved {
+        let json = super::util::json::serialize_resolved(&builder, opts.pretty);
+
+        #[allow(clippy::print_stdout)]
+        {
+            println!("{}", json.expect("config should be serializable"));
+        }
+
+        return exitcode::OK;
+    }
+
     // Load source TOML.
     let source = match load_source_from_paths(&paths) {
         Ok((map, _)) => map,
@@ -125,7 +141,9 @@ mod tests {
     use vector_config::component::{SinkDescription, SourceDescription, TransformDescription};
 
     use crate::{
-        config::util::json::{merge as merge_json, serialize as serialize_to_json},
+        config::util::json::{
+            merge as merge_json, serialize as serialize_to_json, serialize_resolved,
+        },
         config::{vars, ConfigBuilder},
         generate::{generate_example, TransformInputsStrategy},
     };
@@ -197,6 +215,45 @@ mod tests {
         );
     }
 
+    #[test]
+    fn resolved_interpolates_env_vars_and_masks_secrets() {
+        let (config_source, _) = vars::interpolate(
+            r#"
+            [sources.in]
+            type = "demo_logs"
+            format = "${VECTOR_CONFIG_RESOLVED_TEST}"
+
+            [sinks.out]
+            type = "http"
+            inputs = ["in"]
+            uri = "https://example.com"
+            encoding.codec = "json"
+            auth.strategy = "basic"
+            auth.user = "user"
+            auth.password = "hunter2"
+        "#,
+            &HashMap::from([(
+                "VECTOR_CONFIG_RESOLVED_TEST".to_string(),
+                "syslog".to_string(),
+            )]),
+        )
+        .unwrap();
+
+        let json: serde_json::Value = serde_json::from_str(
+            &serialize_resolved(&ConfigBuilder::from_toml(&config_source), false).unwrap(),
+        )
+        .unwrap();
+
+        assert_eq!(json["sources"]["in"]["format"], json!("syslog"));
+        assert_eq!(json["sinks"]["out"]["auth"]["user"], json!("user"));
+        assert_eq!(
+            json["sinks"]["out"]["auth"]["password"],
+            json!("**REDACTED**")
+        );
+        // Defaults are filled in.
+        assert_eq!(json["sinks"]["out"]["compression"], json!("none"));
+    }
+
     /// Select any 2-4 sources
     fn arb_sources() -> impl Strategy<Value = Vec<&'static str>> {
         sample::subsequence(SourceDescription::types(), 2..=4)
@@ -3,8 +3,8 @@ use std::collections::HashSet;
 use indexmap::{IndexMap, IndexSet};
 
 use super::{
-    builder::ConfigBuilder, graph::Graph, schema, validation, ComponentKey, Config, OutputId,
-    SourceConfig, TransformConfig,
+    builder::ConfigBuilder, feature_flags, graph::Graph, policy, schema, validation, ComponentKey,
+    Config, OutputId, SourceConfig, TransformConfig,
 };
 
 /// to handle the expansions when building the graph we need to be able to get the list of inputs
@@ -38,6 +38,11 @@ pub fn compile(mut builder: ConfigBuilder) -> Result<(Config, Vec<String>), Vec<
         errors.extend(name_errors);
     }
 
+    // policies apply to the components as they are configured, before they are expanded
+    if let Err(policy_errors) = policy::check(&builder) {
+        errors.extend(policy_errors);
+    }
+
     let expansions = expand_macros(&mut builder)?;
 
     expand_globs(&mut builder);
@@ -54,6 +59,31 @@ pub fn compile(mut builder: ConfigBuilder) -> Result<(Config, Vec<String>), Vec<
         errors.extend(output_errors);
     }
 
+    #[cfg(feature = "api")]
+    if let Err(api_errors) = validation::check_api(&builder) {
+        errors.extend(api_errors);
+    }
+
+    if let Err(pool_errors) = validation::check_rate_limit_pools(&builder) {
+        errors.extend(pool_errors);
+    }
+
+    if let Err(schedule_errors) = validation::check_delivery_schedules(&builder) {
+        errors.extend(schedule_errors);
+    }
+
+    if let Err(rewrite_errors) = validation::check_metric_rewrites(&builder) {
+        errors.extend(rewrite_errors);
+    }
+
+    if let Err(render_error_output_errors) = validation::check_render_error_outputs(&builder) {
+        errors.extend(render_error_output_errors);
+    }
+
+    if let Err(feature_flag_errors) = feature_flags::check(&builder) {
+        errors.extend(feature_flag_errors);
+    }
+
     #[cfg(feature = "enterprise")]
     let hash = Some(builder.sha256_hash());
 
@@ -68,6 +98,7 @@ pub fn compile(mut builder: ConfigBuilder) -> Result<(Config, Vec<String>), Vec<
         #[cfg(feature = "enterprise")]
         enterprise,
         healthchecks,
+        policy,
         enrichment_tables,
         sources,
         sinks,
@@ -125,6 +156,7 @@ pub fn compile(mut builder: ConfigBuilder) -> Result<(Config, Vec<String>), Vec<
             enterprise,
             hash,
             healthchecks,
+            policy,
             enrichment_tables,
             sources,
             sinks,
@@ -134,6 +166,8 @@ pub fn compile(mut builder: ConfigBuilder) -> Result<(Config, Vec<String>), Vec<
             secret,
         };
 
+        validation::check_transform_schemas(&config)?;
+        validation::check_buffer_priorities(&config)?;
         config.propagate_acknowledgements()?;
 
         let warnings = validation::warnings(&config);
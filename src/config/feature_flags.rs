@@ -0,0 +1,157 @@
+//! Feature flags gating experimental components.
+//!
+//! Components are marked as experimental with the `status` metadata of their configuration type,
+//! i.e. `#[configurable(metadata(status = "alpha"))]`. Vector refuses to load a configuration using
+//! them unless the matching feature flag is enabled, either with the `feature_flags` global option
+//! or with the `--feature-flags` command line option.
+
+use std::{collections::BTreeSet, sync::RwLock};
+
+use once_cell::sync::Lazy;
+use vector_config::{
+    component::{SinkDescription, SourceDescription, TransformDescription},
+    NamedComponent,
+};
+pub use vector_core::config::FeatureFlag;
+
+use super::{ConfigBuilder, GlobalOptions};
+
+/// The status of components that are only available with the `alpha_components` feature flag.
+pub const ALPHA_STATUS: &str = "alpha";
+
+static CLI_FEATURE_FLAGS: Lazy<RwLock<BTreeSet<FeatureFlag>>> = Lazy::new(Default::default);
+
+/// Enables the feature flags given on the command line, for every configuration loaded afterwards.
+pub fn init_cli_feature_flags(flags: &[FeatureFlag]) {
+    *CLI_FEATURE_FLAGS.write().unwrap() = flags.iter().copied().collect();
+}
+
+/// Whether `flag` is enabled, either by `global` or on the command line.
+pub fn is_enabled(global: &GlobalOptions, flag: FeatureFlag) -> bool {
+    global.feature_flags.contains(&flag) || CLI_FEATURE_FLAGS.read().unwrap().contains(&flag)
+}
+
+/// Gets the status of the source of type `component_type`, such as `alpha`.
+pub fn source_status(component_type: &str) -> Option<String> {
+    SourceDescription::status(component_type)
+}
+
+/// Gets the status of the transform of type `component_type`, such as `alpha`.
+pub fn transform_status(component_type: &str) -> Option<String> {
+    TransformDescription::status(component_type)
+}
+
+/// Gets the status of the sink of type `component_type`, such as `alpha`.
+pub fn sink_status(component_type: &str) -> Option<String> {
+    SinkDescription::status(component_type)
+}
+
+/// Checks that the components of `config` don't require a feature flag that isn't enabled.
+pub fn check(config: &ConfigBuilder) -> Result<(), Vec<String>> {
+    check_with(config, |kind, component_type| match kind {
+        "Source" => source_status(component_type),
+        "Transform" => transform_status(component_type),
+        _ => sink_status(component_type),
+    })
+}
+
+fn check_with(
+    config: &ConfigBuilder,
+    status: impl Fn(&str, &str) -> Option<String>,
+) -> Result<(), Vec<String>> {
+    if is_enabled(&config.global, FeatureFlag::AlphaComponents) {
+        return Ok(());
+    }
+
+    let components = config
+        .sources
+        .iter()
+        .map(|(key, source)| ("Source", key, source.inner.get_component_name()))
+        .chain(
+            config
+                .transforms
+                .iter()
+                .map(|(key, transform)| ("Transform", key, transform.inner.get_component_name())),
+        )
+        .chain(
+            config
+                .sinks
+                .iter()
+                .map(|(key, sink)| ("Sink", key, sink.inner.get_component_name())),
+        );
+
+    let errors = components
+        .filter(|(kind, _, component_type)| {
+            status(kind, component_type).as_deref() == Some(ALPHA_STATUS)
+        })
+        .map(|(kind, key, component_type)| {
+            format!(
+                "{} \"{}\" has type \"{}\", which is an alpha component. Enable the `{}` feature flag to use it.",
+                kind,
+                key,
+                component_type,
+                FeatureFlag::AlphaComponents,
+            )
+        })
+        .collect::<Vec<_>>();
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+
+    use super::*;
+    use crate::config::format::{deserialize, Format};
+
+    fn builder(config: &str) -> ConfigBuilder {
+        deserialize(config, Format::Toml).unwrap()
+    }
+
+    const CONFIG: &str = indoc! {r#"
+        [sources.in]
+        type = "test_basic"
+
+        [sinks.out]
+        type = "test_basic"
+        inputs = ["in"]
+    "#};
+
+    fn alpha_sources(kind: &str, component_type: &str) -> Option<String> {
+        (kind == "Source" && component_type == "test_basic").then(|| ALPHA_STATUS.to_owned())
+    }
+
+    #[test]
+    fn rejects_alpha_components() {
+        let errors = check_with(&builder(CONFIG), alpha_sources).unwrap_err();
+
+        assert_eq!(
+            errors,
+            vec!["Source \"in\" has type \"test_basic\", which is an alpha component. Enable the `alpha_components` feature flag to use it.".to_owned()]
+        );
+    }
+
+    #[test]
+    fn allows_alpha_components_when_enabled() {
+        let config = format!("feature_flags = [\"alpha_components\"]\n{}", CONFIG);
+
+        assert!(check_with(&builder(&config), alpha_sources).is_ok());
+    }
+
+    #[test]
+    fn allows_components_without_status() {
+        assert!(check(&builder(CONFIG)).is_ok());
+    }
+
+    #[cfg(feature = "transforms-delay")]
+    #[test]
+    fn reads_status_of_components() {
+        assert_eq!(transform_status("delay").as_deref(), Some(ALPHA_STATUS));
+        assert_eq!(transform_status("filter"), None);
+    }
+}
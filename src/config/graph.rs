@@ -70,7 +70,7 @@ impl Graph {
             graph.nodes.insert(
                 id.clone(),
                 Node::Source {
-                    outputs: config.inner.outputs(schema.log_namespace()),
+                    outputs: config.outputs(schema.log_namespace()),
                 },
             );
         }
@@ -1,4 +1,4 @@
-use std::{collections::HashMap, io::Read};
+use std::{collections::HashMap, io::Read, path::Path};
 
 use indexmap::IndexMap;
 use toml::value::Table;
@@ -76,6 +76,16 @@ impl Process for ConfigBuilderLoader {
                 );
             }
             None => {
+                // The templates of the components are compiled as they're deserialized, so the
+                // descriptor set their VRL expressions resolve protobuf messages with has to be
+                // loaded first.
+                if let Some(path) = table
+                    .get("protobuf_descriptor_set")
+                    .and_then(toml::Value::as_str)
+                {
+                    crate::protobuf_descriptors::configure(Some(Path::new(path)))
+                        .map_err(|error| vec![error])?;
+                }
                 self.builder.append(deserialize_table(table)?)?;
             }
         };
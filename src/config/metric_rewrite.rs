@@ -0,0 +1,195 @@
+//! Rewriting of the names and tags of the metrics received by a sink.
+//!
+//! Backends often have their own naming conventions, such as a common prefix for the names of
+//! metrics or different names for well-known tags. Rewriting the metrics as the sink receives
+//! them avoids a `remap` transform for these simple renames.
+
+use std::collections::HashMap;
+
+use snafu::Snafu;
+use vector_config::configurable_component;
+
+use crate::event::{EventArray, Metric};
+
+#[derive(Debug, Snafu, PartialEq, Eq)]
+pub enum MetricRewriteError {
+    #[snafu(display("The `metrics` option only applies to sinks accepting metrics."))]
+    NotAMetricSink,
+    #[snafu(display("Tag {:?} can't be both renamed and removed.", tag))]
+    TagRenamedAndRemoved { tag: String },
+}
+
+/// Rewrites the names and tags of the metrics received by the sink.
+///
+/// Metrics are renamed, then prefixed, and their tags are renamed, removed, and added, in this
+/// order. Logs and traces are left unchanged.
+#[configurable_component]
+#[derive(Clone, Debug, Default)]
+#[serde(deny_unknown_fields)]
+pub struct MetricRewriteConfig {
+    /// The namespace set on every metric, replacing their own.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub namespace: Option<String>,
+
+    /// The new names of metrics, by their current name.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub rename: HashMap<String, String>,
+
+    /// A prefix added to the name of every metric, once renamed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prefix: Option<String>,
+
+    /// The new names of tags, by their current name.
+    ///
+    /// A renamed tag replaces any tag that already has its new name.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub tag_map: HashMap<String, String>,
+
+    /// The names of the tags removed from every metric, once renamed.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub remove_tags: Vec<String>,
+
+    /// The tags added to every metric, replacing any tag of the same name.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub add_tags: HashMap<String, String>,
+}
+
+impl MetricRewriteConfig {
+    /// Checks that the rewrite applies to a sink, which accepts metrics when `accepts_metrics`.
+    pub fn validate(&self, accepts_metrics: bool) -> Result<(), MetricRewriteError> {
+        if !accepts_metrics {
+            return Err(MetricRewriteError::NotAMetricSink);
+        }
+        match self
+            .tag_map
+            .keys()
+            .find(|tag| self.remove_tags.contains(tag))
+        {
+            Some(tag) => Err(MetricRewriteError::TagRenamedAndRemoved { tag: tag.clone() }),
+            None => Ok(()),
+        }
+    }
+
+    /// Rewrites the metrics of `events`.
+    pub fn rewrite(&self, mut events: EventArray) -> EventArray {
+        if let EventArray::Metrics(metrics) = &mut events {
+            metrics
+                .iter_mut()
+                .for_each(|metric| self.rewrite_metric(metric));
+        }
+        events
+    }
+
+    fn rewrite_metric(&self, metric: &mut Metric) {
+        if let Some(namespace) = &self.namespace {
+            metric.set_namespace(Some(namespace.as_str()));
+        }
+
+        let name = self.rename.get(metric.name()).map(String::as_str);
+        if name.is_some() || self.prefix.is_some() {
+            let name = name.unwrap_or_else(|| metric.name());
+            let name = match &self.prefix {
+                Some(prefix) => format!("{}{}", prefix, name),
+                None => name.to_owned(),
+            };
+            metric.set_name(name);
+        }
+
+        for (tag, new_tag) in &self.tag_map {
+            if let Some(value) = metric.remove_tag(tag) {
+                metric.insert_tag(new_tag.clone(), value);
+            }
+        }
+        for tag in &self.remove_tags {
+            metric.remove_tag(tag);
+        }
+        for (tag, value) in &self.add_tags {
+            metric.insert_tag(tag.clone(), value.clone());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::{LogEvent, MetricKind, MetricValue};
+
+    fn rewrite() -> MetricRewriteConfig {
+        toml::from_str(
+            r#"
+            namespace = "app"
+            rename = { requests = "http_requests" }
+            prefix = "vector_"
+            tag_map = { host = "hostname" }
+            remove_tags = ["pid"]
+            add_tags = { env = "production" }
+            "#,
+        )
+        .unwrap()
+    }
+
+    fn metric(name: &str, tags: &[(&str, &str)]) -> Metric {
+        Metric::new(
+            name,
+            MetricKind::Incremental,
+            MetricValue::Counter { value: 1.0 },
+        )
+        .with_tags(Some(
+            tags.iter()
+                .map(|(tag, value)| (tag.to_string(), value.to_string()))
+                .collect(),
+        ))
+    }
+
+    #[test]
+    fn rewrites_metrics() {
+        let events = EventArray::Metrics(vec![
+            metric("requests", &[("host", "a"), ("pid", "1")]),
+            metric("errors", &[("hostname", "b"), ("env", "staging")]),
+        ]);
+
+        let metrics = match rewrite().rewrite(events) {
+            EventArray::Metrics(metrics) => metrics,
+            events => panic!("unexpected events {:?}", events),
+        };
+
+        assert_eq!(
+            metrics,
+            vec![
+                metric(
+                    "vector_http_requests",
+                    &[("hostname", "a"), ("env", "production")]
+                )
+                .with_namespace(Some("app")),
+                metric("vector_errors", &[("hostname", "b"), ("env", "production")])
+                    .with_namespace(Some("app")),
+            ]
+        );
+    }
+
+    #[test]
+    fn leaves_logs_unchanged() {
+        let log = LogEvent::from("message");
+        let events = EventArray::from(log.clone());
+
+        assert_eq!(rewrite().rewrite(events), EventArray::from(log));
+    }
+
+    #[test]
+    fn validates() {
+        assert_eq!(
+            rewrite().validate(false),
+            Err(MetricRewriteError::NotAMetricSink)
+        );
+        assert_eq!(rewrite().validate(true), Ok(()));
+
+        let mut config = rewrite();
+        config.remove_tags.push("host".to_owned());
+        assert_eq!(
+            config.validate(true),
+            Err(MetricRewriteError::TagRenamedAndRemoved {
+                tag: "host".to_owned()
+            })
+        );
+    }
+}
@@ -21,11 +21,15 @@ mod diff;
 mod enrichment_table;
 #[cfg(feature = "enterprise")]
 pub mod enterprise;
+pub mod feature_flags;
 pub mod format;
 mod graph;
 mod id;
 pub(crate) mod loading;
+mod metric_rewrite;
+mod policy;
 pub mod provider;
+mod schedule;
 mod schema;
 mod secret;
 mod sink;
@@ -47,10 +51,13 @@ pub use loading::{
     load, load_builder_from_paths, load_from_paths, load_from_paths_with_provider_and_secrets,
     load_from_str, load_source_from_paths, merge_path_lists, process_paths, CONFIG_PATHS,
 };
+pub use metric_rewrite::{MetricRewriteConfig, MetricRewriteError};
+pub use policy::{init_policy, PolicyComponentKind, PolicyOptions, PolicyRule};
 pub use provider::ProviderConfig;
+pub use schedule::{DeliverySchedule, DeliveryScheduleConfig};
 pub use secret::SecretBackend;
 pub use sink::{SinkConfig, SinkContext, SinkHealthcheckOptions, SinkOuter};
-pub use source::{SourceConfig, SourceContext, SourceOuter};
+pub use source::{IngestionLimits, OversizedEventPolicy, SourceConfig, SourceContext, SourceOuter};
 pub use transform::{
     InnerTopology, InnerTopologyTransform, TransformConfig, TransformContext, TransformOuter,
 };
@@ -96,7 +103,7 @@ impl ConfigPath {
     }
 }
 
-#[derive(Debug, Default)]
+#[derive(Clone, Debug, Default)]
 pub struct Config {
     #[cfg(feature = "api")]
     pub api: api::Options,
@@ -106,6 +113,7 @@ pub struct Config {
     pub enterprise: Option<enterprise::Options>,
     pub global: GlobalOptions,
     pub healthchecks: HealthcheckOptions,
+    pub policy: PolicyOptions,
     sources: IndexMap<ComponentKey, SourceOuter>,
     sinks: IndexMap<ComponentKey, SinkOuter<OutputId>>,
     transforms: IndexMap<ComponentKey, TransformOuter<OutputId>>,
@@ -630,6 +638,148 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    #[cfg(feature = "transforms-filter")]
+    async fn render_error_output_without_output() {
+        let err = load(
+            r#"
+            [sources.in]
+            type = "test_basic"
+
+            [transforms.quota]
+            type = "filter"
+            inputs = ["in"]
+            condition = "true"
+            quota.max_events = 10
+            quota.window_secs = 60
+            quota.key_field = { template = "{{ service }}", on_render_error = "error_output" }
+
+            [sinks.out]
+            type = "test_basic"
+            inputs = ["quota"]
+            "#,
+            Format::Toml,
+        )
+        .await
+        .unwrap_err();
+
+        assert_eq!(
+            err,
+            vec!["Transform \"quota\" has no `render_errors` output, so its templates can't use the `error_output` rendering error policy."]
+        );
+    }
+
+    #[tokio::test]
+    #[cfg(feature = "transforms-remap")]
+    async fn schema_warnings() {
+        let warnings = load(
+            r#"
+            schema.enabled = true
+
+            [sources.in]
+            type = "test_basic"
+
+            [transforms.shape]
+            type = "remap"
+            inputs = ["in"]
+            source = '. = { "foo": "bar" }'
+
+            [transforms.read]
+            type = "remap"
+            inputs = ["shape"]
+            source = ".bar = .foo; .baz = .fooo"
+
+            [sinks.out]
+            type = "test_basic"
+            inputs = ["read"]
+            "#,
+            Format::Toml,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            warnings,
+            vec!["Transform \"read\" program reads field `.fooo`, which the schema of its inputs doesn't contain"]
+        );
+    }
+
+    #[tokio::test]
+    #[cfg(feature = "transforms-remap")]
+    async fn schema_errors() {
+        let config = r#"
+            [sources.in]
+            type = "test_basic"
+
+            [transforms.shape]
+            type = "remap"
+            inputs = ["in"]
+            source = '. = { "foo": 1 }'
+
+            [transforms.read]
+            type = "remap"
+            inputs = ["shape"]
+            source = ".bar = upcase!(.foo)"
+
+            [sinks.out]
+            type = "test_basic"
+            inputs = ["read"]
+            "#;
+
+        let errors = load(&format!("schema.enabled = true\n{}", config), Format::Toml)
+            .await
+            .unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0]
+            .starts_with("Transform \"read\" program does not match the schema of its inputs:"));
+
+        // Without schemas, any event may reach the transform.
+        assert!(load(config, Format::Toml).await.is_ok());
+    }
+
+    #[tokio::test]
+    #[cfg(feature = "transforms-remap")]
+    async fn priority_with_disk_buffer() {
+        let config = |buffer_type: &str| {
+            format!(
+                r#"
+                [sources.in]
+                type = "test_basic"
+
+                [transforms.prioritize]
+                type = "remap"
+                inputs = ["in"]
+                source = '%priority = "high"'
+
+                [transforms.pass]
+                type = "test_basic"
+                inputs = ["prioritize"]
+                suffix = "foo"
+                increase = 1.25
+
+                [sinks.out]
+                type = "test_basic"
+                inputs = ["pass"]
+                buffer.type = "{}"
+                "#,
+                buffer_type
+            )
+        };
+
+        assert!(load(&config("memory"), Format::Toml).await.is_ok());
+
+        let errors = load(
+            &format!("{}buffer.max_size = 268435488", config("disk")),
+            Format::Toml,
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(
+            errors,
+            vec!["Sink \"out\" has a disk buffer, which doesn't read out events by priority, but transform \"prioritize\" sets the priority of its events."]
+        );
+    }
+
     #[tokio::test]
     async fn duplicate_name() {
         let err = load(
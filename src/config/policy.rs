@@ -0,0 +1,486 @@
+//! Policies the configuration must comply with, checked when it's loaded.
+//!
+//! Fleets managed centrally often share a file of policies, e.g. that sinks use TLS, or that
+//! sources are annotated with the tenant owning them. Vector refuses to load a configuration
+//! violating any of the rules of its `policy`, reporting the IDs of the rules in the errors.
+//!
+//! The policy is either part of the configuration, or loaded from the separate file given with
+//! `--policy`, so that it can be managed apart from the configuration it applies to.
+
+use std::{collections::HashSet, fs::File, path::Path, sync::Mutex};
+
+use indexmap::IndexMap;
+use once_cell::sync::Lazy;
+use serde_json::Value;
+use vector_config::{configurable_component, NamedComponent};
+
+use super::{loading, ComponentKey, ConfigBuilder, Format};
+
+/// The policy loaded from the file given with `--policy`, checked along with the policy of the
+/// configuration.
+static POLICY: Lazy<Mutex<PolicyOptions>> = Lazy::new(Default::default);
+
+/// Loads the policy from the file at `path`, whose format is detected from its name, to check all
+/// the configurations loaded from then on against it.
+///
+/// The file holds the same options as the `policy` section of the configuration.
+pub fn init_policy(path: &Path) -> Result<(), Vec<String>> {
+    let policy = load_policy(path)?;
+    *POLICY.lock().expect("poisoned lock") = policy;
+    Ok(())
+}
+
+fn load_policy(path: &Path) -> Result<PolicyOptions, Vec<String>> {
+    let format = Format::from_path(path).map_err(|path| {
+        vec![format!(
+            "Could not detect the format of policy file {:?}.",
+            path
+        )]
+    })?;
+    let file = File::open(path)
+        .map_err(|error| vec![format!("Could not open policy file {:?}: {}", path, error)])?;
+    let (policy, _) = loading::load::<_, PolicyOptions>(file, format).map_err(|errors| {
+        errors
+            .into_iter()
+            .map(|error| format!("Invalid policy file {:?}: {}", path, error))
+            .collect::<Vec<_>>()
+    })?;
+    Ok(policy)
+}
+
+/// Policies the configuration must comply with.
+#[configurable_component]
+#[derive(Clone, Debug, Default)]
+#[serde(deny_unknown_fields)]
+pub struct PolicyOptions {
+    /// The rules the components must comply with.
+    ///
+    /// The rules of all the configuration files are checked.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub rules: Vec<PolicyRule>,
+}
+
+impl PolicyOptions {
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    pub(super) fn append(&mut self, with: Self) {
+        self.rules.extend(with.rules);
+    }
+}
+
+/// A rule the components must comply with.
+///
+/// The rule applies to the components matching its `kind` and `types`, which must comply with all
+/// of its conditions.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct PolicyRule {
+    /// The ID of the rule, reported along with the components violating it.
+    pub id: String,
+
+    /// A description of the policy enforced by the rule, reported along with its ID.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+
+    /// The kind of components the rule applies to.
+    ///
+    /// The rule applies to all kinds of components when unset.
+    #[configurable(derived)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kind: Option<PolicyComponentKind>,
+
+    /// The types of components the rule applies to, such as `http`.
+    ///
+    /// The rule applies to all types of components when empty.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub types: Vec<String>,
+
+    /// Whether the components the rule applies to are denied altogether.
+    #[serde(
+        default,
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    pub deny: bool,
+
+    /// The options the components must set, such as `tls.enabled`.
+    ///
+    /// Nested options are separated by a dot. An option is set when it's present and neither null
+    /// nor `false`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub require_options: Vec<String>,
+
+    /// The options the components must not set, such as `tls.key_pass`.
+    ///
+    /// An option is set as for `require_options`, so this can't forbid options explicitly set to
+    /// `false`. Use `require_values` to require an option to have a given value instead.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub forbid_options: Vec<String>,
+
+    /// The values the options of the components must have, such as `tls.verify_certificate =
+    /// true`.
+    ///
+    /// Nested options are separated by a dot. Options not set fail the rule, unless their value is
+    /// required to be `false`, which is what unset boolean options mean.
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    pub require_values: IndexMap<String, toml::Value>,
+
+    /// The annotations the components must set, such as `tenant`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub require_annotations: Vec<String>,
+}
+
+/// A kind of component.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum PolicyComponentKind {
+    /// Sources.
+    Source,
+
+    /// Transforms.
+    Transform,
+
+    /// Sinks.
+    Sink,
+}
+
+impl PolicyComponentKind {
+    const fn as_str(self) -> &'static str {
+        match self {
+            Self::Source => "source",
+            Self::Transform => "transform",
+            Self::Sink => "sink",
+        }
+    }
+}
+
+/// A component, as seen by the rules.
+struct Component<'a> {
+    kind: PolicyComponentKind,
+    key: &'a ComponentKey,
+    component_type: &'static str,
+    annotations: &'a IndexMap<String, String>,
+    options: Value,
+}
+
+impl PolicyRule {
+    fn applies_to(&self, component: &Component<'_>) -> bool {
+        self.kind.map_or(true, |kind| kind == component.kind)
+            && (self.types.is_empty()
+                || self
+                    .types
+                    .iter()
+                    .any(|component_type| component_type == component.component_type))
+    }
+
+    fn is_empty(&self) -> bool {
+        !self.deny
+            && self.require_options.is_empty()
+            && self.forbid_options.is_empty()
+            && self.require_values.is_empty()
+            && self.require_annotations.is_empty()
+    }
+
+    /// Gets the reasons `component` violates the rule.
+    fn violations(&self, component: &Component<'_>) -> Vec<String> {
+        let mut violations = Vec::new();
+        if self.deny {
+            violations.push(format!(
+                "{} components of type {:?} are denied",
+                component.kind.as_str(),
+                component.component_type
+            ));
+        }
+        for option in &self.require_options {
+            if !is_set(&component.options, option) {
+                violations.push(format!("option {:?} must be set", option));
+            }
+        }
+        for option in &self.forbid_options {
+            if is_set(&component.options, option) {
+                violations.push(format!("option {:?} must not be set", option));
+            }
+        }
+        for (option, required) in &self.require_values {
+            let required = serde_json::to_value(required).unwrap_or_default();
+            let value = get(&component.options, option);
+            let complies = match (value, &required) {
+                (None | Some(Value::Null), Value::Bool(false)) => true,
+                (Some(value), required) => value == required,
+                (None, _) => false,
+            };
+            if !complies {
+                violations.push(format!("option {:?} must be {}", option, required));
+            }
+        }
+        for annotation in &self.require_annotations {
+            if !component.annotations.contains_key(annotation) {
+                violations.push(format!("annotation {:?} must be set", annotation));
+            }
+        }
+        violations
+    }
+}
+
+/// Gets the option at the dotted `path` of `options`.
+fn get<'a>(options: &'a Value, path: &str) -> Option<&'a Value> {
+    path.split('.')
+        .try_fold(options, |value, segment| value.get(segment))
+}
+
+/// Whether the option at the dotted `path` of `options` is present and neither null nor `false`.
+fn is_set(options: &Value, path: &str) -> bool {
+    !matches!(
+        get(options, path),
+        None | Some(Value::Null) | Some(Value::Bool(false))
+    )
+}
+
+/// Checks that the components of `config` comply with the rules of its policy, and of the policy
+/// loaded with [`init_policy`].
+pub fn check(config: &ConfigBuilder) -> Result<(), Vec<String>> {
+    let loaded = POLICY.lock().expect("poisoned lock");
+    let rules = config
+        .policy
+        .rules
+        .iter()
+        .chain(loaded.rules.iter())
+        .collect::<Vec<_>>();
+    if rules.is_empty() {
+        return Ok(());
+    }
+
+    let mut errors = Vec::new();
+    let mut ids = HashSet::new();
+    for rule in &rules {
+        if !ids.insert(rule.id.as_str()) {
+            errors.push(format!("Duplicate policy rule ID {:?}.", rule.id));
+        }
+        if rule.is_empty() {
+            errors.push(format!(
+                "Policy rule {:?} has no condition, it should deny the components or require or forbid options or annotations.",
+                rule.id
+            ));
+        }
+    }
+
+    let components = config
+        .sources
+        .iter()
+        .map(|(key, source)| Component {
+            kind: PolicyComponentKind::Source,
+            key,
+            component_type: source.inner.get_component_name(),
+            annotations: &source.annotations,
+            options: serde_json::to_value(&source.inner).unwrap_or_default(),
+        })
+        .chain(config.transforms.iter().map(|(key, transform)| Component {
+            kind: PolicyComponentKind::Transform,
+            key,
+            component_type: transform.inner.get_component_name(),
+            annotations: &transform.annotations,
+            options: serde_json::to_value(&transform.inner).unwrap_or_default(),
+        }))
+        .chain(config.sinks.iter().map(|(key, sink)| Component {
+            kind: PolicyComponentKind::Sink,
+            key,
+            component_type: sink.inner.get_component_name(),
+            annotations: &sink.annotations,
+            options: serde_json::to_value(&sink.inner).unwrap_or_default(),
+        }));
+
+    for component in components {
+        for rule in rules.iter().filter(|rule| rule.applies_to(&component)) {
+            for violation in rule.violations(&component) {
+                let description = rule
+                    .description
+                    .as_ref()
+                    .map(|description| format!(" ({})", description))
+                    .unwrap_or_default();
+                errors.push(format!(
+                    "{} {:?} violates policy rule {:?}: {}.{}",
+                    component.kind.as_str(),
+                    component.key.id(),
+                    rule.id,
+                    violation,
+                    description
+                ));
+            }
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+#[cfg(all(
+    test,
+    feature = "sources-demo_logs",
+    feature = "transforms-remap",
+    feature = "sinks-console"
+))]
+mod tests {
+    use super::*;
+
+    fn check_toml(policy: &str) -> Result<(), Vec<String>> {
+        let config = ConfigBuilder::from_toml(&format!(
+            r#"
+            [sources.in]
+            type = "demo_logs"
+            format = "shuffle"
+            lines = ["line"]
+            annotations.tenant = "team-a"
+
+            [transforms.remap]
+            type = "remap"
+            inputs = ["in"]
+            source = "."
+
+            [sinks.out]
+            type = "console"
+            inputs = ["remap"]
+            encoding.codec = "json"
+
+            {}
+            "#,
+            policy
+        ));
+        check(&config)
+    }
+
+    #[test]
+    fn accepts_compliant_configs() {
+        assert_eq!(
+            check_toml(
+                r#"
+                [[policy.rules]]
+                id = "sources-have-tenant"
+                kind = "source"
+                require_annotations = ["tenant"]
+
+                [[policy.rules]]
+                id = "json-output"
+                types = ["console"]
+                require_options = ["encoding.codec"]
+                forbid_options = ["encoding.except_fields"]
+                "#
+            ),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn reports_violations_with_rule_ids() {
+        assert_eq!(
+            check_toml(
+                r#"
+                [[policy.rules]]
+                id = "components-have-tenant"
+                description = "All components are owned by a tenant."
+                require_annotations = ["tenant"]
+
+                [[policy.rules]]
+                id = "no-console"
+                kind = "sink"
+                types = ["console"]
+                deny = true
+
+                [[policy.rules]]
+                id = "sinks-require-tls"
+                kind = "sink"
+                require_options = ["tls.enabled"]
+                "#
+            ),
+            Err(vec![
+                r#"transform "remap" violates policy rule "components-have-tenant": annotation "tenant" must be set. (All components are owned by a tenant.)"#.to_owned(),
+                r#"sink "out" violates policy rule "components-have-tenant": annotation "tenant" must be set. (All components are owned by a tenant.)"#.to_owned(),
+                r#"sink "out" violates policy rule "no-console": sink components of type "console" are denied."#.to_owned(),
+                r#"sink "out" violates policy rule "sinks-require-tls": option "tls.enabled" must be set."#.to_owned(),
+            ])
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_rules() {
+        assert_eq!(
+            check_toml(
+                r#"
+                [[policy.rules]]
+                id = "empty"
+
+                [[policy.rules]]
+                id = "empty"
+                deny = true
+                kind = "transform"
+                types = ["filter"]
+                "#
+            ),
+            Err(vec![
+                r#"Policy rule "empty" has no condition, it should deny the components or require or forbid options or annotations."#.to_owned(),
+                r#"Duplicate policy rule ID "empty"."#.to_owned(),
+            ])
+        );
+    }
+
+    #[test]
+    fn checks_required_values() {
+        assert_eq!(
+            check_toml(
+                r#"
+                [[policy.rules]]
+                id = "remap-drops-on-error"
+                kind = "transform"
+                require_values = { drop_on_error = true, reroute_dropped = false }
+
+                [[policy.rules]]
+                id = "json-output"
+                kind = "sink"
+                require_values."encoding.codec" = "text"
+                "#
+            ),
+            Err(vec![
+                r#"transform "remap" violates policy rule "remap-drops-on-error": option "drop_on_error" must be true."#.to_owned(),
+                r#"sink "out" violates policy rule "json-output": option "encoding.codec" must be "text"."#.to_owned(),
+            ])
+        );
+    }
+
+    #[test]
+    fn loads_policy_files() {
+        let directory = tempfile::tempdir().unwrap();
+        let path = directory.path().join("policy.toml");
+        std::fs::write(
+            &path,
+            r#"
+            [[rules]]
+            id = "sources-have-tenant"
+            kind = "source"
+            require_annotations = ["tenant"]
+            "#,
+        )
+        .unwrap();
+        let policy = load_policy(&path).unwrap();
+        assert_eq!(policy.rules.len(), 1);
+        assert_eq!(policy.rules[0].id, "sources-have-tenant");
+
+        std::fs::write(&path, "[[rules]]\nid = 1\n").unwrap();
+        assert!(load_policy(&path).is_err());
+        assert!(load_policy(&directory.path().join("policy")).is_err());
+    }
+
+    #[test]
+    fn checks_nested_options() {
+        let options = serde_json::json!({"tls": {"enabled": true, "verify": false}, "codec": null});
+        assert!(is_set(&options, "tls.enabled"));
+        assert!(!is_set(&options, "tls.verify"));
+        assert!(!is_set(&options, "tls.ca_file"));
+        assert!(!is_set(&options, "codec"));
+        assert!(!is_set(&options, "codec.kind"));
+    }
+}
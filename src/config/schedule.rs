@@ -0,0 +1,363 @@
+//! Delivery schedules, restricting the times at which a sink receives events.
+//!
+//! Outside of the windows of its schedule, a sink stops pulling events from its buffer, so the
+//! events wait there until the next window opens.
+
+use std::time::Duration;
+
+use chrono::{DateTime, Datelike, NaiveDateTime, TimeZone as _, Timelike, Utc};
+use futures::{stream::BoxStream, Stream, StreamExt};
+use snafu::Snafu;
+use vector_common::TimeZone;
+use vector_config::configurable_component;
+
+use crate::internal_events::DeliveryWindowChanged;
+
+/// How far ahead the next opening or closing of a window is looked for, in minutes.
+///
+/// Schedules whose state doesn't change within this horizon are checked again at its end.
+const HORIZON_MINUTES: i64 = 366 * 24 * 60;
+
+const MONTHS: [&str; 12] = [
+    "jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec",
+];
+const WEEKDAYS: [&str; 7] = ["sun", "mon", "tue", "wed", "thu", "fri", "sat"];
+
+#[derive(Debug, Snafu, PartialEq, Eq)]
+pub enum ScheduleError {
+    #[snafu(display("A delivery schedule must have at least one window."))]
+    NoWindows,
+    #[snafu(display("Invalid delivery window {:?}: {}", window, reason))]
+    InvalidWindow { window: String, reason: String },
+}
+
+/// Delivery schedule of a sink.
+///
+/// Outside of its windows, the sink stops taking events from its buffer, so the events are held in
+/// the buffer until the next window opens. The buffer should be large enough to hold the events
+/// received between windows, and should be a disk buffer for them to be kept across restarts.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct DeliveryScheduleConfig {
+    /// The windows during which events are delivered, as cron expressions.
+    ///
+    /// Each expression has the five fields of a crontab entry: minute, hour, day of month, month,
+    /// and day of week. Events are delivered during every minute matched by any of the
+    /// expressions. For example, `* 0-5 * * *` delivers events from midnight to 6 AM, and
+    /// `* * * * sat,sun` delivers them during the weekend.
+    pub windows: Vec<String>,
+
+    /// The name of the time zone the windows are in.
+    ///
+    /// By default, the [global `timezone`][global_timezone] option is used. The time zone name may
+    /// be any name in the [TZ database][tz_database], or `local` to indicate system local time.
+    ///
+    /// [global_timezone]: https://vector.dev/docs/reference/configuration//global-options#timezone
+    /// [tz_database]: https://en.wikipedia.org/wiki/List_of_tz_database_time_zones
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timezone: Option<TimeZone>,
+}
+
+impl DeliveryScheduleConfig {
+    pub fn build(&self, default_timezone: TimeZone) -> Result<DeliverySchedule, ScheduleError> {
+        if self.windows.is_empty() {
+            return Err(ScheduleError::NoWindows);
+        }
+        let windows = self
+            .windows
+            .iter()
+            .map(|window| {
+                Window::parse(window).map_err(|reason| ScheduleError::InvalidWindow {
+                    window: window.clone(),
+                    reason,
+                })
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(DeliverySchedule {
+            windows,
+            timezone: self.timezone.unwrap_or(default_timezone),
+        })
+    }
+}
+
+/// The values allowed for a field of a window.
+#[derive(Clone, Copy, Debug)]
+struct Field {
+    /// A bit for each allowed value.
+    allowed: u64,
+    /// Whether the field was anything other than `*`, which matters for the day fields.
+    restricted: bool,
+}
+
+impl Field {
+    /// Parses a field as a comma-separated list of values, ranges, or `*`, each optionally with a
+    /// step, such as `*/15` or `1-5/2`.
+    fn parse(field: &str, min: u32, max: u32, names: &[&str]) -> Result<Self, String> {
+        let parse_value = |value: &str| -> Result<u32, String> {
+            let value = match names
+                .iter()
+                .position(|name| name.eq_ignore_ascii_case(value))
+            {
+                Some(index) => index as u32 + min,
+                None => value
+                    .parse()
+                    .map_err(|_| format!("{:?} is not a valid value", value))?,
+            };
+            if value < min || value > max {
+                return Err(format!("{} is not between {} and {}", value, min, max));
+            }
+            Ok(value)
+        };
+
+        let mut allowed = 0;
+        for part in field.split(',') {
+            let (range, step) = match part.split_once('/') {
+                Some((range, step)) => match step.parse::<u32>() {
+                    Ok(step) if step > 0 => (range, step),
+                    _ => return Err(format!("{:?} is not a valid step", step)),
+                },
+                None => (part, 1),
+            };
+            let (start, end) = match range {
+                "*" => (min, max),
+                range => match range.split_once('-') {
+                    Some((start, end)) => (parse_value(start)?, parse_value(end)?),
+                    None if step > 1 => (parse_value(range)?, max),
+                    None => {
+                        let value = parse_value(range)?;
+                        (value, value)
+                    }
+                },
+            };
+            if start > end {
+                return Err(format!("{:?} is not a valid range", range));
+            }
+            for value in (start..=end).step_by(step as usize) {
+                allowed |= 1 << value;
+            }
+        }
+
+        Ok(Self {
+            allowed,
+            restricted: field != "*",
+        })
+    }
+
+    const fn contains(&self, value: u32) -> bool {
+        self.allowed & (1 << value) != 0
+    }
+}
+
+/// A window of a delivery schedule, in the syntax of a crontab entry.
+#[derive(Clone, Copy, Debug)]
+struct Window {
+    minutes: Field,
+    hours: Field,
+    days_of_month: Field,
+    months: Field,
+    days_of_week: Field,
+}
+
+impl Window {
+    fn parse(window: &str) -> Result<Self, String> {
+        let fields = window.split_whitespace().collect::<Vec<_>>();
+        let [minutes, hours, days_of_month, months, days_of_week]: [&str; 5] = fields
+            .try_into()
+            .map_err(|fields: Vec<&str>| format!("expected 5 fields, got {}", fields.len()))?;
+
+        let mut days_of_week = Field::parse(days_of_week, 0, 7, &WEEKDAYS)?;
+        // Both 0 and 7 are Sunday.
+        if days_of_week.contains(7) {
+            days_of_week.allowed |= 1;
+        }
+
+        Ok(Self {
+            minutes: Field::parse(minutes, 0, 59, &[])?,
+            hours: Field::parse(hours, 0, 23, &[])?,
+            days_of_month: Field::parse(days_of_month, 1, 31, &[])?,
+            months: Field::parse(months, 1, 12, &MONTHS)?,
+            days_of_week,
+        })
+    }
+
+    fn matches(&self, time: &NaiveDateTime) -> bool {
+        let day_of_month = self.days_of_month.contains(time.day());
+        let day_of_week = self
+            .days_of_week
+            .contains(time.weekday().num_days_from_sunday());
+        // As in cron, a day matches either of the day fields when both are restricted.
+        let day = if self.days_of_month.restricted && self.days_of_week.restricted {
+            day_of_month || day_of_week
+        } else {
+            day_of_month && day_of_week
+        };
+
+        day && self.minutes.contains(time.minute())
+            && self.hours.contains(time.hour())
+            && self.months.contains(time.month())
+    }
+}
+
+/// The delivery schedule of a sink.
+#[derive(Clone, Debug)]
+pub struct DeliverySchedule {
+    windows: Vec<Window>,
+    timezone: TimeZone,
+}
+
+impl DeliverySchedule {
+    /// Whether events are delivered at the given time.
+    pub fn is_open(&self, time: DateTime<Utc>) -> bool {
+        let local = match self.timezone {
+            TimeZone::Local => time.with_timezone(&chrono::Local).naive_local(),
+            TimeZone::Named(tz) => time.with_timezone(&tz).naive_local(),
+        };
+        self.windows.iter().any(|window| window.matches(&local))
+    }
+
+    /// Finds the start of the first minute after `now` at which the schedule is no longer in the
+    /// given state, or the end of the horizon if it stays in that state until then.
+    fn next_change(&self, now: DateTime<Utc>, open: bool) -> DateTime<Utc> {
+        let seconds = now.timestamp();
+        let start = Utc.timestamp(seconds - seconds.rem_euclid(60), 0);
+        (1..=HORIZON_MINUTES)
+            .map(|minutes| start + chrono::Duration::minutes(minutes))
+            .find(|time| self.is_open(*time) != open)
+            .unwrap_or_else(|| start + chrono::Duration::minutes(HORIZON_MINUTES))
+    }
+
+    /// Passes the items of a stream on only during the windows of the schedule.
+    ///
+    /// Outside of the windows, the stream isn't polled, leaving its items where they are.
+    pub fn gate<'a, S>(self, input: S) -> BoxStream<'a, S::Item>
+    where
+        S: Stream + Send + 'a,
+        S::Item: Send,
+    {
+        Box::pin(async_stream::stream! {
+            futures::pin_mut!(input);
+
+            let mut open = false;
+            let mut next_change: Option<DateTime<Utc>> = None;
+            loop {
+                let now = Utc::now();
+                if next_change.map_or(true, |next_change| now >= next_change) {
+                    open = self.is_open(now);
+                    next_change = Some(self.next_change(now, open));
+                    emit!(DeliveryWindowChanged { open });
+                }
+                let sleep = tokio::time::sleep(
+                    next_change
+                        .and_then(|next_change| (next_change - now).to_std().ok())
+                        .unwrap_or(Duration::ZERO),
+                );
+
+                if open {
+                    tokio::select! {
+                        item = input.next() => match item {
+                            Some(item) => yield item,
+                            None => break,
+                        },
+                        _ = sleep => {}
+                    }
+                } else {
+                    sleep.await;
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn schedule(windows: &[&str]) -> DeliverySchedule {
+        DeliveryScheduleConfig {
+            windows: windows.iter().map(|window| window.to_string()).collect(),
+            timezone: None,
+        }
+        .build(TimeZone::parse("UTC").unwrap())
+        .unwrap()
+    }
+
+    fn at(day: u32, hour: u32, minute: u32) -> DateTime<Utc> {
+        // 2022-10-01 is a Saturday.
+        Utc.ymd(2022, 10, day).and_hms(hour, minute, 0)
+    }
+
+    #[test]
+    fn matches_windows() {
+        let off_peak = schedule(&["* 0-5 * * *", "* 22-23 * * *"]);
+        assert!(off_peak.is_open(at(3, 1, 30)));
+        assert!(off_peak.is_open(at(3, 23, 59)));
+        assert!(!off_peak.is_open(at(3, 6, 0)));
+        assert!(!off_peak.is_open(at(3, 12, 0)));
+
+        let weekend = schedule(&["* * * * sat,sun"]);
+        assert!(weekend.is_open(at(1, 12, 0)));
+        assert!(weekend.is_open(at(2, 12, 0)));
+        assert!(!weekend.is_open(at(3, 12, 0)));
+
+        let quarter_hours = schedule(&["*/15 * * * *"]);
+        assert!(quarter_hours.is_open(at(3, 12, 45)));
+        assert!(!quarter_hours.is_open(at(3, 12, 46)));
+    }
+
+    #[test]
+    fn matches_either_day_field() {
+        // The first of the month, and every Monday.
+        let schedule = schedule(&["* * 1 * 1"]);
+        assert!(schedule.is_open(at(1, 12, 0)));
+        assert!(schedule.is_open(at(3, 12, 0)));
+        assert!(!schedule.is_open(at(4, 12, 0)));
+    }
+
+    #[test]
+    fn finds_next_change() {
+        let schedule = schedule(&["* 0-5 * * *"]);
+        assert_eq!(schedule.next_change(at(3, 12, 30), false), at(4, 0, 0));
+        assert_eq!(schedule.next_change(at(4, 0, 0), true), at(4, 6, 0));
+    }
+
+    #[test]
+    fn uses_timezone() {
+        let schedule = DeliveryScheduleConfig {
+            windows: vec!["* 0-5 * * *".to_owned()],
+            timezone: TimeZone::parse("Europe/Paris"),
+        }
+        .build(TimeZone::Local)
+        .unwrap();
+        // 23:30 UTC is 01:30 in Paris in October.
+        assert!(schedule.is_open(at(3, 23, 30)));
+        assert!(!schedule.is_open(at(3, 4, 30)));
+    }
+
+    #[test]
+    fn rejects_invalid_windows() {
+        let build = |window: &str| {
+            DeliveryScheduleConfig {
+                windows: vec![window.to_owned()],
+                timezone: None,
+            }
+            .build(TimeZone::Local)
+            .map(|_| ())
+        };
+        assert!(build("* 0-5 * *").is_err());
+        assert!(build("* 24 * * *").is_err());
+        assert!(build("*/0 * * * *").is_err());
+        assert!(build("* 5-1 * * *").is_err());
+        assert!(build("0 0 * * mon-fri").is_ok());
+        assert_eq!(
+            DeliveryScheduleConfig {
+                windows: Vec::new(),
+                timezone: None,
+            }
+            .build(TimeZone::Local)
+            .map(|_| ()),
+            Err(ScheduleError::NoWindows)
+        );
+    }
+}
@@ -16,6 +16,14 @@ pub struct Options {
     #[serde(default = "default_validation")]
     pub validation: bool,
 
+    /// Whether or not fields are automatically mapped to the locations sinks expect.
+    ///
+    /// When enabled, fields carrying a semantic meaning (such as `message`, `timestamp`, or `host`) are renamed to the
+    /// field a sink expects for that meaning right before events are handed to the sink, removing the need for
+    /// per-sink `remap` glue.
+    #[serde(default = "default_semantic_mapping")]
+    pub semantic_mapping: bool,
+
     /// Whether or not to enable log namespacing.
     pub log_namespace: Option<bool>,
 }
@@ -47,6 +55,7 @@ impl Options {
         // If either config enables these flags, it is enabled.
         self.enabled |= with.enabled;
         self.validation |= with.validation;
+        self.semantic_mapping |= with.semantic_mapping;
     }
 }
 
@@ -55,6 +64,7 @@ impl Default for Options {
         Self {
             enabled: default_enabled(),
             validation: default_validation(),
+            semantic_mapping: default_semantic_mapping(),
             log_namespace: None,
         }
     }
@@ -68,6 +78,10 @@ const fn default_validation() -> bool {
     false
 }
 
+const fn default_semantic_mapping() -> bool {
+    false
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -80,16 +94,19 @@ mod test {
                 Options {
                     enabled: false,
                     validation: false,
+                    semantic_mapping: false,
                     log_namespace: None,
                 },
                 Options {
                     enabled: false,
                     validation: false,
+                    semantic_mapping: false,
                     log_namespace: Some(true),
                 },
                 Some(Options {
                     enabled: false,
                     validation: false,
+                    semantic_mapping: false,
                     log_namespace: Some(true),
                 }),
             ),
@@ -98,11 +115,13 @@ mod test {
                 Options {
                     enabled: false,
                     validation: false,
+                    semantic_mapping: false,
                     log_namespace: Some(false),
                 },
                 Options {
                     enabled: false,
                     validation: false,
+                    semantic_mapping: false,
                     log_namespace: Some(true),
                 },
                 None,
@@ -112,16 +131,40 @@ mod test {
                 Options {
                     enabled: false,
                     validation: false,
+                    semantic_mapping: false,
                     log_namespace: None,
                 },
                 Options {
                     enabled: true,
                     validation: false,
+                    semantic_mapping: false,
                     log_namespace: None,
                 },
                 Some(Options {
                     enabled: true,
                     validation: false,
+                    semantic_mapping: false,
+                    log_namespace: None,
+                }),
+            ),
+            (
+                "enable semantic mapping",
+                Options {
+                    enabled: false,
+                    validation: false,
+                    semantic_mapping: false,
+                    log_namespace: None,
+                },
+                Options {
+                    enabled: false,
+                    validation: false,
+                    semantic_mapping: true,
+                    log_namespace: None,
+                },
+                Some(Options {
+                    enabled: false,
+                    validation: false,
+                    semantic_mapping: true,
                     log_namespace: None,
                 }),
             ),
@@ -130,16 +173,19 @@ mod test {
                 Options {
                     enabled: false,
                     validation: false,
+                    semantic_mapping: false,
                     log_namespace: None,
                 },
                 Options {
                     enabled: false,
                     validation: true,
+                    semantic_mapping: false,
                     log_namespace: None,
                 },
                 Some(Options {
                     enabled: false,
                     validation: true,
+                    semantic_mapping: false,
                     log_namespace: None,
                 }),
             ),
@@ -1,5 +1,6 @@
 use async_trait::async_trait;
 use enum_dispatch::enum_dispatch;
+use indexmap::IndexMap;
 use serde::Serialize;
 use vector_buffers::{BufferConfig, BufferType};
 use vector_config::{configurable_component, Configurable, NamedComponent};
@@ -8,7 +9,9 @@ use vector_core::{
     sink::VectorSink,
 };
 
-use super::{schema, ComponentKey, ProxyConfig, Resource};
+use super::{
+    schema, ComponentKey, DeliveryScheduleConfig, MetricRewriteConfig, ProxyConfig, Resource,
+};
 use crate::sinks::{util::UriSerde, Healthcheck, Sinks};
 
 /// Fully resolved sink component.
@@ -48,6 +51,27 @@ where
     )]
     proxy: ProxyConfig,
 
+    /// The name of the rate limit pool to share with other sinks.
+    ///
+    /// The pool must be defined in the `rate_limit_pools` global option. Its limit applies on top
+    /// of the rate limit of the sink.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rate_limit_pool: Option<String>,
+
+    #[configurable(derived)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schedule: Option<DeliveryScheduleConfig>,
+
+    #[configurable(derived)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metrics: Option<MetricRewriteConfig>,
+
+    /// Free-form annotations of the component, such as the team or tenant owning it.
+    ///
+    /// Annotations are only used to check the components against the rules of the `policy`.
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    pub annotations: IndexMap<String, String>,
+
     #[serde(flatten)]
     pub inner: Sinks,
 }
@@ -64,6 +88,10 @@ where
             healthcheck_uri: None,
             inner: inner.into(),
             proxy: Default::default(),
+            rate_limit_pool: None,
+            schedule: None,
+            metrics: None,
+            annotations: IndexMap::new(),
         }
     }
 
@@ -121,6 +149,10 @@ where
             healthcheck: self.healthcheck,
             healthcheck_uri: self.healthcheck_uri,
             proxy: self.proxy,
+            rate_limit_pool: self.rate_limit_pool,
+            schedule: self.schedule,
+            metrics: self.metrics,
+            annotations: self.annotations,
         }
     }
 }
@@ -1,15 +1,20 @@
-use std::collections::HashMap;
+use std::{
+    collections::HashMap,
+    num::{NonZeroU32, NonZeroUsize},
+};
 
 use async_trait::async_trait;
+use codecs::decoding::DECODING_ERRORS_OUTPUT;
 use enum_dispatch::enum_dispatch;
+use indexmap::IndexMap;
 use vector_config::{configurable_component, NamedComponent};
 use vector_core::{
-    config::{AcknowledgementsConfig, GlobalOptions, LogNamespace, Output},
+    config::{AcknowledgementsConfig, DataType, GlobalOptions, LogNamespace, Output},
     source::Source,
 };
 
 use super::{schema, ComponentKey, ProxyConfig, Resource};
-use crate::{shutdown::ShutdownSignal, sources::Sources, SourceSender};
+use crate::{conditions::AnyCondition, shutdown::ShutdownSignal, sources::Sources, SourceSender};
 
 /// Fully resolved source component.
 #[configurable_component]
@@ -22,9 +27,60 @@ pub struct SourceOuter {
     )]
     pub proxy: ProxyConfig,
 
+    /// The field to add the host metadata to the events of this source under.
+    ///
+    /// The metadata is only fetched if the `host_metadata` global option is set. Its keys, such as
+    /// `cloud.region`, are kept as they are in the object set to this field.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub host_metadata_key: Option<String>,
+
+    /// A condition the events of this source must match to be sent on.
+    ///
+    /// The condition is evaluated as soon as events are decoded, before they are sent to the
+    /// components consuming the source, so that unwanted events are dropped as early as possible.
+    #[configurable(derived)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub filter: Option<AnyCondition>,
+
+    /// A condition matching the events of this source to follow through the topology.
+    ///
+    /// Each component a matching event, or an event derived from it, passes through logs it along
+    /// with the time elapsed since it was sent by this source. Events dropped by the `filter` of the
+    /// source aren't traced.
+    #[configurable(derived)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trace: Option<AnyCondition>,
+
+    /// The maximum size, in bytes, of the events of this source.
+    ///
+    /// The size of an event is estimated as the size of its JSON encoding. Overrides the
+    /// `max_event_bytes` global option for this source.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_event_bytes: Option<NonZeroUsize>,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        skip_serializing_if = "vector_core::serde::skip_serializing_if_default"
+    )]
+    pub oversized_events: OversizedEventPolicy,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        skip_serializing_if = "vector_core::serde::skip_serializing_if_default"
+    )]
+    pub limits: IngestionLimits,
+
     #[serde(default, skip)]
     pub sink_acknowledgements: bool,
 
+    /// Free-form annotations of the component, such as the team or tenant owning it.
+    ///
+    /// Annotations are only used to check the components against the rules of the `policy`.
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    pub annotations: IndexMap<String, String>,
+
     #[serde(flatten)]
     pub(crate) inner: Sources,
 }
@@ -33,10 +89,93 @@ impl SourceOuter {
     pub(crate) fn new<I: Into<Sources>>(inner: I) -> Self {
         Self {
             proxy: Default::default(),
+            host_metadata_key: None,
+            filter: None,
+            trace: None,
+            max_event_bytes: None,
+            oversized_events: Default::default(),
+            limits: Default::default(),
             sink_acknowledgements: false,
+            annotations: IndexMap::new(),
             inner: inner.into(),
         }
     }
+
+    /// Gets the list of outputs exposed by this source, including the errors output oversized
+    /// events are routed to.
+    pub(crate) fn outputs(&self, global_log_namespace: LogNamespace) -> Vec<Output> {
+        let mut outputs = self.inner.outputs(global_log_namespace);
+        if self.oversized_events == OversizedEventPolicy::Route {
+            // Oversized events can be any of the events the source decodes.
+            let ty = outputs
+                .iter()
+                .fold(DataType::empty(), |ty, output| ty | output.ty);
+            match outputs
+                .iter_mut()
+                .find(|output| output.port.as_deref() == Some(DECODING_ERRORS_OUTPUT))
+            {
+                Some(errors) => errors.ty |= ty,
+                None => outputs.push(Output::default(ty).with_port(DECODING_ERRORS_OUTPUT)),
+            }
+        }
+        outputs
+    }
+
+    /// Whether the source routes the payloads it can't decode to its errors output, as set by its
+    /// `decoding.on_error` option.
+    pub(crate) fn routes_decoding_errors(&self, global_log_namespace: LogNamespace) -> bool {
+        self.inner
+            .outputs(global_log_namespace)
+            .iter()
+            .any(|output| output.port.as_deref() == Some(DECODING_ERRORS_OUTPUT))
+    }
+}
+
+/// What to do with the events of a source exceeding the maximum event size.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum OversizedEventPolicy {
+    /// Drop the events.
+    Drop,
+
+    /// Truncate the message of log events to fit the maximum event size.
+    ///
+    /// Events which still exceed the maximum event size after truncation, such as metrics, are
+    /// dropped.
+    Truncate,
+
+    /// Send the events to the `errors` output of the source.
+    Route,
+}
+
+impl Default for OversizedEventPolicy {
+    fn default() -> Self {
+        Self::Drop
+    }
+}
+
+/// Limits of the rate at which a source ingests events.
+///
+/// Once a limit is reached, the source is held back until enough time has passed for its events to
+/// fit in the limit, which slows down reading from the upstream system where the source supports
+/// it, rather than dropping events.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct IngestionLimits {
+    /// The maximum number of events sent by the source per second.
+    ///
+    /// Bursts of up to this number of events are sent without delay.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub events_per_second: Option<NonZeroU32>,
+
+    /// The maximum number of bytes of events sent by the source per second.
+    ///
+    /// The size of an event is estimated as the size of its JSON encoding. Bursts of up to this
+    /// number of bytes are sent without delay.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bytes_per_second: Option<NonZeroU32>,
 }
 
 /// Generalized interface for describing and building source components.
@@ -26,6 +26,12 @@ where
     #[serde(default = "Default::default")] // https://github.com/serde-rs/serde/issues/1541
     pub inputs: Vec<T>,
 
+    /// Free-form annotations of the component, such as the team or tenant owning it.
+    ///
+    /// Annotations are only used to check the components against the rules of the `policy`.
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    pub annotations: IndexMap<String, String>,
+
     #[serde(flatten)]
     pub inner: Transforms,
 }
@@ -37,6 +43,7 @@ where
     pub(crate) fn new<I: Into<Transforms>>(inputs: Vec<T>, inner: I) -> Self {
         TransformOuter {
             inputs,
+            annotations: IndexMap::new(),
             inner: inner.into(),
         }
     }
@@ -55,6 +62,7 @@ where
     {
         TransformOuter {
             inputs,
+            annotations: self.annotations,
             inner: self.inner,
         }
     }
@@ -99,6 +107,7 @@ impl TransformOuter<String> {
             for (inner_name, inner_transform) in inner_topology.inner {
                 let child = TransformOuter {
                     inputs: inner_transform.inputs,
+                    annotations: self.annotations.clone(),
                     inner: inner_transform.inner,
                 };
                 children.push(inner_name.clone());
@@ -204,6 +213,30 @@ pub trait TransformConfig: NamedComponent + core::fmt::Debug + Send + Sync {
         Ok(())
     }
 
+    /// Checks the configuration of the transform against the schema of its inputs.
+    ///
+    /// Unlike `validate`, this is only called once the topology is known and schemas are enabled, so
+    /// the provided `merged_definition` is the actual shape of the events reaching the transform.
+    ///
+    /// # Errors
+    ///
+    /// If the transform can't process events of that shape, an error variant containing a list of
+    /// all validation errors is returned. Otherwise, the parts of the configuration that don't match
+    /// the schema without breaking the transform are returned as warnings.
+    fn check_schema(
+        &self,
+        _merged_definition: &schema::Definition,
+    ) -> Result<Vec<String>, Vec<String>> {
+        Ok(Vec::new())
+    }
+
+    /// Whether or not this transform may set the buffer priority of the events it outputs.
+    ///
+    /// Sinks downstream of such a transform can't use buffers that ignore the priority of events.
+    fn sets_priority(&self) -> bool {
+        false
+    }
+
     /// Whether or not concurrency should be enabled for this transform.
     ///
     /// When enabled, this transform may be run in parallel in order to attempt to maximize
@@ -3,12 +3,18 @@ use crate::topology::schema::merged_definition;
 use futures_util::{stream, FutureExt, StreamExt, TryFutureExt, TryStreamExt};
 use heim::{disk::Partition, units::information::byte};
 use indexmap::IndexMap;
-use std::{collections::HashMap, path::PathBuf};
+use std::{
+    collections::{HashMap, HashSet},
+    path::PathBuf,
+};
+use vector_buffers::BufferType;
 use vector_core::internal_event::DEFAULT_OUTPUT;
 
 use super::{
-    builder::ConfigBuilder, ComponentKey, Config, OutputId, Resource, SourceConfig, TransformConfig,
+    builder::ConfigBuilder, ComponentKey, Config, DataType, Output, OutputId, Resource, SinkConfig,
+    SourceConfig, TransformConfig,
 };
+use crate::template::RENDER_ERRORS_OUTPUT;
 
 /// Check that provide + topology config aren't present in the same builder, which is an error.
 pub fn check_provider(config: &ConfigBuilder) -> Result<(), Vec<String>> {
@@ -41,6 +47,174 @@ pub fn check_names<'a, I: Iterator<Item = &'a ComponentKey>>(names: I) -> Result
     }
 }
 
+/// Check that the API tokens are usable.
+#[cfg(feature = "api")]
+pub fn check_api(config: &ConfigBuilder) -> Result<(), Vec<String>> {
+    let mut errors = vec![];
+
+    for (index, token) in config.api.tokens.iter().enumerate() {
+        if token.token.inner().is_empty() {
+            errors.push(format!("API token #{} is empty.", index + 1));
+        }
+        for pattern in token.components.iter().flatten() {
+            if let Err(error) = glob::Pattern::new(pattern) {
+                errors.push(format!(
+                    "API token #{} has an invalid component pattern {:?}: {}",
+                    index + 1,
+                    pattern,
+                    error
+                ));
+            }
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+/// Check that the rate limit pools allow requests.
+pub fn check_rate_limit_pools(config: &ConfigBuilder) -> Result<(), Vec<String>> {
+    let mut errors = vec![];
+
+    for (name, pool) in &config.global.rate_limit_pools {
+        if pool.rate_limit_num == 0 {
+            errors.push(format!(
+                "Rate limit pool {:?} must allow at least one request.",
+                name
+            ));
+        }
+        if pool.rate_limit_duration_secs == 0 {
+            errors.push(format!(
+                "Rate limit pool {:?} must have a time window of at least one second.",
+                name
+            ));
+        }
+    }
+
+    for (key, sink) in &config.sinks {
+        if let Some(pool) = &sink.rate_limit_pool {
+            if !config.global.rate_limit_pools.contains_key(pool) {
+                errors.push(format!(
+                    "Sink \"{}\" references undefined rate limit pool {:?}.",
+                    key, pool
+                ));
+            }
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+/// Check that the delivery schedules of sinks are valid.
+pub fn check_delivery_schedules(config: &ConfigBuilder) -> Result<(), Vec<String>> {
+    let errors = config
+        .sinks
+        .iter()
+        .filter_map(|(key, sink)| {
+            let schedule = sink.schedule.as_ref()?;
+            let error = schedule.build(config.global.timezone).err()?;
+            Some(format!("Sink \"{}\": {}", key, error))
+        })
+        .collect::<Vec<_>>();
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+/// Check that the metric rewrites of sinks are valid.
+pub fn check_metric_rewrites(config: &ConfigBuilder) -> Result<(), Vec<String>> {
+    let errors = config
+        .sinks
+        .iter()
+        .filter_map(|(key, sink)| {
+            let rewrite = sink.metrics.as_ref()?;
+            let accepts_metrics = sink.inner.input().data_type().contains(DataType::Metric);
+            let error = rewrite.validate(accepts_metrics).err()?;
+            Some(format!("Sink \"{}\": {}", key, error))
+        })
+        .collect::<Vec<_>>();
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+/// Check that the templates with the `error_output` rendering error policy belong to components
+/// with the output the events are sent to.
+pub fn check_render_error_outputs(config: &ConfigBuilder) -> Result<(), Vec<String>> {
+    let has_output = |outputs: Vec<Output>| {
+        outputs
+            .iter()
+            .any(|output| output.port.as_deref() == Some(RENDER_ERRORS_OUTPUT))
+    };
+    let sources = config.sources.iter().map(|(key, source)| {
+        (
+            "Source",
+            key,
+            serde_json::to_value(&source.inner),
+            has_output(source.outputs(config.schema.log_namespace())),
+        )
+    });
+    let transforms = config.transforms.iter().map(|(key, transform)| {
+        (
+            "Transform",
+            key,
+            serde_json::to_value(&transform.inner),
+            has_output(transform.inner.outputs(&schema::Definition::any())),
+        )
+    });
+    let sinks = config
+        .sinks
+        .iter()
+        .map(|(key, sink)| ("Sink", key, serde_json::to_value(&sink.inner), false));
+
+    let errors = sources
+        .chain(transforms)
+        .chain(sinks)
+        .filter_map(|(kind, key, options, has_output)| {
+            let routes = options.map_or(false, |options| routes_render_errors(&options));
+            (routes && !has_output).then(|| {
+                format!(
+                    "{} \"{}\" has no `{}` output, so its templates can't use the `error_output` rendering error policy.",
+                    kind, key, RENDER_ERRORS_OUTPUT
+                )
+            })
+        })
+        .collect::<Vec<_>>();
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+/// Whether the serialized options of a component hold a template with the `error_output` policy.
+fn routes_render_errors(options: &serde_json::Value) -> bool {
+    match options {
+        serde_json::Value::Object(map) => {
+            map.get("on_render_error")
+                .and_then(serde_json::Value::as_str)
+                == Some("error_output")
+                || map.values().any(routes_render_errors)
+        }
+        serde_json::Value::Array(values) => values.iter().any(routes_render_errors),
+        _ => false,
+    }
+}
+
 pub fn check_shape(config: &ConfigBuilder) -> Result<(), Vec<String>> {
     let mut errors = vec![];
 
@@ -152,7 +326,7 @@ pub fn check_resources(config: &ConfigBuilder) -> Result<(), Vec<String>> {
 pub fn check_outputs(config: &ConfigBuilder) -> Result<(), Vec<String>> {
     let mut errors = Vec::new();
     for (key, source) in config.sources.iter() {
-        let outputs = source.inner.outputs(config.schema.log_namespace());
+        let outputs = source.outputs(config.schema.log_namespace());
         if outputs
             .iter()
             .map(|output| output.port.as_deref().unwrap_or(""))
@@ -192,6 +366,97 @@ pub fn check_outputs(config: &ConfigBuilder) -> Result<(), Vec<String>> {
     }
 }
 
+/// Check each transform against the schema of its inputs.
+///
+/// Unlike `check_outputs`, which has to assume that any event may reach a transform, this runs once
+/// the topology is known, and only if schemas are enabled. The warnings are reported by `warnings`.
+pub fn check_transform_schemas(config: &Config) -> Result<(), Vec<String>> {
+    let errors = transform_schema_checks(config)
+        .filter_map(|(key, result)| result.err().map(|errors| (key, errors)))
+        .flat_map(|(key, errors)| {
+            errors
+                .into_iter()
+                .map(move |error| format!("Transform \"{}\" {}", key, error))
+        })
+        .collect::<Vec<_>>();
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+fn transform_schema_checks(
+    config: &Config,
+) -> impl Iterator<Item = (&ComponentKey, Result<Vec<String>, Vec<String>>)> + '_ {
+    let mut cache = HashMap::new();
+    let enabled = config.schema.enabled;
+    config
+        .transforms
+        .iter()
+        .filter(move |_| enabled)
+        .map(move |(key, transform)| {
+            let definition = merged_definition(&transform.inputs, config, &mut cache);
+            (key, transform.inner.check_schema(&definition))
+        })
+}
+
+/// Check that the sinks with disk buffers don't receive events whose priority is set by a
+/// transform.
+///
+/// Only memory buffers read out high-priority events first, while disk buffers always read out
+/// events in the order they were written, so the priority would silently be ignored.
+pub fn check_buffer_priorities(config: &Config) -> Result<(), Vec<String>> {
+    let errors = config
+        .sinks
+        .iter()
+        .filter(|(_, sink)| {
+            sink.buffer
+                .stages()
+                .iter()
+                .any(|stage| !matches!(stage, BufferType::Memory { .. }))
+        })
+        .filter_map(|(key, sink)| {
+            let transform = prioritizing_transform(config, &sink.inputs)?;
+            Some(format!(
+                "Sink \"{}\" has a disk buffer, which doesn't read out events by priority, but transform \"{}\" sets the priority of its events.",
+                key, transform
+            ))
+        })
+        .collect::<Vec<_>>();
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+/// Finds a transform setting the priority of events, among the given inputs and their own inputs.
+fn prioritizing_transform<'a>(
+    config: &'a Config,
+    inputs: &'a [OutputId],
+) -> Option<&'a ComponentKey> {
+    let mut visited = HashSet::new();
+    let mut pending = inputs
+        .iter()
+        .map(|input| &input.component)
+        .collect::<Vec<_>>();
+    while let Some(key) = pending.pop() {
+        if !visited.insert(key) {
+            continue;
+        }
+        if let Some(transform) = config.transforms.get(key) {
+            if transform.inner.sets_priority() {
+                return Some(key);
+            }
+            pending.extend(transform.inputs.iter().map(|input| &input.component));
+        }
+    }
+    None
+}
+
 pub async fn check_buffer_preconditions(config: &Config) -> Result<(), Vec<String>> {
     // We need to assert that Vector's data directory is located on a mountpoint that has enough
     // capacity to allow all sinks with disk buffers configured to be able to use up to their
@@ -374,6 +639,16 @@ pub fn warnings(config: &Config) -> Vec<String> {
         }
     }
 
+    for (key, result) in transform_schema_checks(config) {
+        if let Ok(schema_warnings) = result {
+            warnings.extend(
+                schema_warnings
+                    .into_iter()
+                    .map(|warning| format!("Transform \"{}\" {}", key, warning)),
+            );
+        }
+    }
+
     warnings
 }
 
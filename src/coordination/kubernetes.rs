@@ -0,0 +1,172 @@
+use std::{collections::BTreeMap, path::PathBuf, time::Duration};
+
+use async_trait::async_trait;
+use k8s_openapi::{
+    api::coordination::v1::{Lease, LeaseSpec},
+    apimachinery::pkg::apis::meta::v1::{MicroTime, ObjectMeta},
+    chrono::{self, Utc},
+};
+use kube::{
+    api::{Api, DeleteParams, ListParams, Patch, PatchParams, Preconditions},
+    config::{self, KubeConfigOptions},
+    Client, Config as ClientConfig,
+};
+use snafu::ResultExt;
+use vector_config::configurable_component;
+
+use super::{CoordinationBackend, CoordinationError, KubernetesSnafu};
+use crate::config::ComponentKey;
+
+/// The label holding the group of a lease.
+const GROUP_LABEL: &str = "vector.dev/coordination-group";
+
+/// The field manager of the leases applied by Vector.
+const FIELD_MANAGER: &str = "vector";
+
+/// Configuration for holding coordination groups as Kubernetes leases.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct KubernetesCoordinationConfig {
+    /// The namespace the leases are created in.
+    ///
+    /// If not set, the namespace of the Kubernetes client configuration is used, which is the
+    /// namespace of the pod Vector runs in when using the in-cluster configuration.
+    namespace: Option<String>,
+
+    /// Optional path to a kubeconfig file readable by Vector. If not set,
+    /// Vector will try to connect to Kubernetes using in-cluster configuration.
+    kube_config_file: Option<PathBuf>,
+}
+
+impl KubernetesCoordinationConfig {
+    pub(super) async fn build(
+        &self,
+        key: &ComponentKey,
+    ) -> crate::Result<Box<dyn CoordinationBackend>> {
+        let client_config = match &self.kube_config_file {
+            Some(kc) => {
+                ClientConfig::from_custom_kubeconfig(
+                    config::Kubeconfig::read_from(kc)?,
+                    &KubeConfigOptions::default(),
+                )
+                .await?
+            }
+            None => ClientConfig::infer().await?,
+        };
+        let client = Client::try_from(client_config)?;
+        let api = match &self.namespace {
+            Some(namespace) => Api::namespaced(client, namespace),
+            None => Api::default_namespaced(client),
+        };
+        Ok(Box::new(KubernetesCoordinationBackend {
+            api,
+            // Label values are restricted in length and characters, unlike the IDs of sources.
+            group: format!("{:016x}", seahash::hash(key.id().as_bytes())),
+        }))
+    }
+}
+
+/// A coordination backend holding each member of a group as a lease, labelled with the group.
+struct KubernetesCoordinationBackend {
+    api: Api<Lease>,
+    group: String,
+}
+
+impl KubernetesCoordinationBackend {
+    fn lease_name(member: &str) -> String {
+        format!("vector-{}", member)
+    }
+
+    /// Deletes a lease left behind by a member which stopped without leaving its group.
+    ///
+    /// The lease is only deleted if it wasn't renewed in the meantime.
+    async fn delete_expired(&self, lease: &Lease) {
+        let name = match &lease.metadata.name {
+            Some(name) => name,
+            None => return,
+        };
+        let params = DeleteParams {
+            preconditions: Some(Preconditions {
+                resource_version: lease.metadata.resource_version.clone(),
+                uid: None,
+            }),
+            ..Default::default()
+        };
+        if let Err(error) = self.api.delete(name, &params).await {
+            debug!(message = "Failed to delete expired lease.", lease = %name, %error);
+        }
+    }
+}
+
+#[async_trait]
+impl CoordinationBackend for KubernetesCoordinationBackend {
+    async fn renew(
+        &mut self,
+        member: &str,
+        lease: Duration,
+    ) -> Result<Vec<String>, CoordinationError> {
+        let name = Self::lease_name(member);
+        let lease = Lease {
+            metadata: ObjectMeta {
+                name: Some(name.clone()),
+                labels: Some(BTreeMap::from([(
+                    GROUP_LABEL.to_owned(),
+                    self.group.clone(),
+                )])),
+                ..Default::default()
+            },
+            spec: Some(LeaseSpec {
+                holder_identity: Some(member.to_owned()),
+                lease_duration_seconds: Some(lease.as_secs() as i32),
+                renew_time: Some(MicroTime(Utc::now())),
+                ..Default::default()
+            }),
+        };
+        self.api
+            .patch(
+                &name,
+                &PatchParams::apply(FIELD_MANAGER).force(),
+                &Patch::Apply(&lease),
+            )
+            .await
+            .context(KubernetesSnafu { lease: &name })?;
+
+        let leases = self
+            .api
+            .list(&ListParams::default().labels(&format!("{}={}", GROUP_LABEL, self.group)))
+            .await
+            .context(KubernetesSnafu { lease: &name })?;
+        let now = Utc::now();
+        let mut members = Vec::new();
+        for lease in leases {
+            let spec = match &lease.spec {
+                Some(spec) => spec,
+                None => continue,
+            };
+            let expires_at = spec
+                .renew_time
+                .as_ref()
+                .zip(spec.lease_duration_seconds)
+                .map(|(renewed_at, duration)| {
+                    renewed_at.0 + chrono::Duration::seconds(duration.into())
+                });
+            match (&spec.holder_identity, expires_at) {
+                (Some(holder), Some(expires_at)) if expires_at > now => {
+                    members.push(holder.clone());
+                }
+                _ => self.delete_expired(&lease).await,
+            }
+        }
+        Ok(members)
+    }
+
+    async fn leave(&mut self, member: &str) -> Result<(), CoordinationError> {
+        let name = Self::lease_name(member);
+        self.api
+            .delete(&name, &DeleteParams::default())
+            .await
+            .map(|_| ())
+            .context(KubernetesSnafu { lease: &name })
+    }
+}
@@ -0,0 +1,280 @@
+//! Coordination between the instances of Vector running the same source.
+//!
+//! Polling sources fetch their data from remote services on their own, so several instances of
+//! Vector running the same source all fetch the same data. When coordination is enabled, the
+//! instances of a source join a group held in a shared backend, and split the work of the source,
+//! such as the endpoints it scrapes, into shards that are each owned by a single member of the
+//! group. Members hold a lease on their membership which they keep renewing, and the shards of a
+//! member whose lease expired, because it stopped or can no longer reach the backend, move to the
+//! remaining members.
+
+use std::{
+    hash::{Hash, Hasher},
+    num::NonZeroU64,
+    time::{Duration, Instant},
+};
+
+use async_trait::async_trait;
+use seahash::SeaHasher;
+use snafu::Snafu;
+use tokio::sync::watch;
+use uuid::Uuid;
+use vector_common::shutdown::ShutdownSignal;
+use vector_config::configurable_component;
+
+use crate::{
+    config::ComponentKey,
+    internal_events::{CoordinationGroupChanged, CoordinationMembershipError},
+};
+
+#[cfg(feature = "coordination-kubernetes")]
+mod kubernetes;
+mod redis;
+
+#[cfg(feature = "coordination-kubernetes")]
+pub use self::kubernetes::KubernetesCoordinationConfig;
+pub use self::redis::RedisCoordinationConfig;
+
+/// A backend holding the members of a coordination group.
+#[async_trait]
+trait CoordinationBackend: Send + Sync {
+    /// Renews the lease of `member` on its membership, and returns all the members whose lease
+    /// hasn't expired.
+    async fn renew(
+        &mut self,
+        member: &str,
+        lease: Duration,
+    ) -> Result<Vec<String>, CoordinationError>;
+
+    /// Removes `member` from the group.
+    async fn leave(&mut self, member: &str) -> Result<(), CoordinationError>;
+}
+
+#[derive(Debug, Snafu)]
+pub enum CoordinationError {
+    #[snafu(display("Timed out while renewing the lease on the membership."))]
+    Timeout,
+
+    #[cfg(feature = "coordination-kubernetes")]
+    #[snafu(display("Failed to access lease {:?} in Kubernetes: {}", lease, source))]
+    Kubernetes { lease: String, source: kube::Error },
+
+    #[snafu(display("Failed to access coordination group {:?} in Redis: {}", key, source))]
+    Redis {
+        key: String,
+        source: ::redis::RedisError,
+    },
+}
+
+/// Coordination between the instances of Vector running this source.
+///
+/// The instances of Vector running a source with the same ID, and the same coordination backend,
+/// split the work of the source between them, instead of all doing the same work.
+#[configurable_component]
+#[derive(Clone, Debug)]
+pub struct CoordinationConfig {
+    #[configurable(derived)]
+    #[serde(flatten)]
+    backend: CoordinationBackendConfig,
+
+    /// How long, in seconds, an instance stays a member of the group without renewing its lease.
+    ///
+    /// Leases are renewed three times per period. When an instance stops, or can't reach the
+    /// backend anymore, its work moves to the other instances once its lease expired.
+    #[serde(default = "default_lease_secs")]
+    lease_secs: NonZeroU64,
+}
+
+/// The backend holding the members of the coordination group.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum CoordinationBackendConfig {
+    /// Holds the members of the group as leases in Kubernetes.
+    #[cfg(feature = "coordination-kubernetes")]
+    Kubernetes(#[configurable(derived)] KubernetesCoordinationConfig),
+
+    /// Holds the members of the group in a sorted set in Redis.
+    Redis(#[configurable(derived)] RedisCoordinationConfig),
+}
+
+fn default_lease_secs() -> NonZeroU64 {
+    NonZeroU64::new(15).unwrap()
+}
+
+impl CoordinationConfig {
+    /// Joins the coordination group of the source with the given key.
+    ///
+    /// # Errors
+    ///
+    /// If the client of the backend can't be built, an error variant explaining the issue is
+    /// returned.
+    pub async fn build(&self, key: &ComponentKey) -> crate::Result<Coordinator> {
+        let backend = match &self.backend {
+            #[cfg(feature = "coordination-kubernetes")]
+            CoordinationBackendConfig::Kubernetes(config) => config.build(key).await?,
+            CoordinationBackendConfig::Redis(config) => config.build(key).await?,
+        };
+        Ok(Coordinator::new(
+            backend,
+            Duration::from_secs(self.lease_secs.get()),
+        ))
+    }
+}
+
+/// Keeps the membership of an instance in its coordination group.
+pub struct Coordinator {
+    backend: Box<dyn CoordinationBackend>,
+    member: String,
+    lease: Duration,
+    members: watch::Sender<Vec<String>>,
+}
+
+impl Coordinator {
+    fn new(backend: Box<dyn CoordinationBackend>, lease: Duration) -> Self {
+        let member = Uuid::new_v4().to_string();
+        let (members, _) = watch::channel(vec![member.clone()]);
+        Self {
+            backend,
+            member,
+            lease,
+            members,
+        }
+    }
+
+    /// Gets the shards of the work owned by this instance.
+    pub fn shards(&self) -> Shards {
+        Shards {
+            member: self.member.clone(),
+            members: self.members.subscribe(),
+        }
+    }
+
+    /// Keeps renewing the lease on the membership of this instance, until `shutdown` is
+    /// triggered, at which point this instance leaves the group.
+    ///
+    /// If the lease can't be renewed for longer than it lasts, the other members consider this
+    /// instance gone, and this instance considers itself alone in turn. Work is then duplicated
+    /// rather than lost until the backend can be reached again.
+    pub async fn run(mut self, mut shutdown: ShutdownSignal) {
+        let mut interval = tokio::time::interval(self.lease / 3);
+        let mut renewed = Instant::now();
+        loop {
+            tokio::select! {
+                _ = &mut shutdown => break,
+                _ = interval.tick() => {}
+            }
+
+            let result =
+                tokio::time::timeout(self.lease, self.backend.renew(&self.member, self.lease))
+                    .await
+                    .unwrap_or(Err(CoordinationError::Timeout));
+            let members = match result {
+                Ok(members) => {
+                    renewed = Instant::now();
+                    members
+                }
+                Err(error) => {
+                    emit!(CoordinationMembershipError { error: &error });
+                    if renewed.elapsed() < self.lease {
+                        continue;
+                    }
+                    Vec::new()
+                }
+            };
+            self.update_members(members);
+        }
+
+        if let Err(error) = self.backend.leave(&self.member).await {
+            emit!(CoordinationMembershipError { error: &error });
+        }
+    }
+
+    fn update_members(&self, mut members: Vec<String>) {
+        if !members.contains(&self.member) {
+            members.push(self.member.clone());
+        }
+        members.sort_unstable();
+        members.dedup();
+        self.members.send_if_modified(|current| {
+            if *current == members {
+                return false;
+            }
+            emit!(CoordinationGroupChanged {
+                members: members.len()
+            });
+            *current = members;
+            true
+        });
+    }
+}
+
+/// The shards of the work of a source owned by an instance.
+///
+/// Shards are assigned to the members of the group by rendezvous hashing, so that only the shards
+/// of the members joining or leaving the group move when it changes.
+#[derive(Clone, Debug)]
+pub struct Shards {
+    member: String,
+    members: watch::Receiver<Vec<String>>,
+}
+
+impl Shards {
+    /// Whether the shard identified by `shard` is owned by this instance.
+    pub fn owns(&self, shard: &str) -> bool {
+        owner(&self.members.borrow(), shard).map_or(true, |owner| owner == self.member)
+    }
+}
+
+/// Picks the member owning `shard`, as the member with the highest score for it.
+fn owner<'a>(members: &'a [String], shard: &str) -> Option<&'a str> {
+    members
+        .iter()
+        .max_by_key(|member| {
+            let mut hasher = SeaHasher::new();
+            member.hash(&mut hasher);
+            shard.hash(&mut hasher);
+            hasher.finish()
+        })
+        .map(String::as_str)
+}
+
+fn default_key_prefix() -> String {
+    "vector/coordination/".to_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn members(count: usize) -> Vec<String> {
+        (0..count)
+            .map(|member| format!("member-{}", member))
+            .collect()
+    }
+
+    #[test]
+    fn shards_have_a_single_owner() {
+        let members = members(3);
+        let shards = (0..100).map(|shard| format!("http://endpoint-{}", shard));
+        let mut owned = vec![0; members.len()];
+        for shard in shards {
+            let owner = owner(&members, &shard).unwrap();
+            owned[members.iter().position(|member| member == owner).unwrap()] += 1;
+        }
+        assert!(owned.iter().all(|&count| count > 0), "{:?}", owned);
+    }
+
+    #[test]
+    fn only_shards_of_departed_members_move() {
+        let before = members(4);
+        let after = members(3);
+        for shard in (0..100).map(|shard| format!("http://endpoint-{}", shard)) {
+            let previous = owner(&before, &shard).unwrap();
+            let current = owner(&after, &shard).unwrap();
+            if previous != "member-3" {
+                assert_eq!(previous, current);
+            }
+        }
+    }
+}
@@ -0,0 +1,101 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use async_trait::async_trait;
+use redis::aio::ConnectionManager;
+use snafu::ResultExt;
+use vector_config::configurable_component;
+
+use super::{default_key_prefix, CoordinationBackend, CoordinationError, RedisSnafu};
+use crate::config::ComponentKey;
+
+/// Configuration for holding coordination groups in Redis.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct RedisCoordinationConfig {
+    /// The Redis URL to connect to.
+    ///
+    /// The URL _must_ take the form of `protocol://server:port/db` where the protocol can either be
+    /// `redis` or `rediss` for connections secured via TLS.
+    url: String,
+
+    /// The prefix of the keys groups are held under.
+    ///
+    /// The group of a source is held in a sorted set under the key made of this prefix followed by
+    /// the ID of the source.
+    #[serde(default = "default_key_prefix")]
+    key_prefix: String,
+}
+
+impl RedisCoordinationConfig {
+    pub(super) async fn build(
+        &self,
+        key: &ComponentKey,
+    ) -> crate::Result<Box<dyn CoordinationBackend>> {
+        let client = redis::Client::open(self.url.as_str())?;
+        let connection = client.get_tokio_connection_manager().await?;
+        Ok(Box::new(RedisCoordinationBackend {
+            connection,
+            key: format!("{}{}", self.key_prefix, key.id()),
+        }))
+    }
+}
+
+/// A coordination backend holding the members of a group in a Redis sorted set, scored by the
+/// time their lease expires at.
+///
+/// Leases expire according to the clock of the member which renewed them, so the clocks of the
+/// members are expected to be in sync to well within the duration of a lease.
+struct RedisCoordinationBackend {
+    connection: ConnectionManager,
+    key: String,
+}
+
+#[async_trait]
+impl CoordinationBackend for RedisCoordinationBackend {
+    async fn renew(
+        &mut self,
+        member: &str,
+        lease: Duration,
+    ) -> Result<Vec<String>, CoordinationError> {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis() as u64;
+        let lease = lease.as_millis() as u64;
+        let (members,) = redis::pipe()
+            .atomic()
+            .cmd("ZADD")
+            .arg(&self.key)
+            .arg(now + lease)
+            .arg(member)
+            .ignore()
+            .cmd("ZREMRANGEBYSCORE")
+            .arg(&self.key)
+            .arg("-inf")
+            .arg(now)
+            .ignore()
+            // The whole group goes away once all of its members are gone.
+            .cmd("PEXPIRE")
+            .arg(&self.key)
+            .arg(lease)
+            .ignore()
+            .cmd("ZRANGE")
+            .arg(&self.key)
+            .arg(0)
+            .arg(-1)
+            .query_async::<_, (Vec<String>,)>(&mut self.connection)
+            .await
+            .context(RedisSnafu { key: &self.key })?;
+        Ok(members)
+    }
+
+    async fn leave(&mut self, member: &str) -> Result<(), CoordinationError> {
+        redis::cmd("ZREM")
+            .arg(&self.key)
+            .arg(member)
+            .query_async::<_, ()>(&mut self.connection)
+            .await
+            .context(RedisSnafu { key: &self.key })
+    }
+}
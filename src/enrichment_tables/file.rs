@@ -567,6 +567,10 @@ impl Table for File {
             .and_then(|metadata| metadata.modified()),
             Ok(modified) if modified > self.last_modified)
     }
+
+    fn row_count(&self) -> Option<usize> {
+        Some(self.data.len())
+    }
 }
 
 impl std::fmt::Debug for File {
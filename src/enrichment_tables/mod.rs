@@ -5,6 +5,7 @@ use vector_config::{configurable_component, NamedComponent};
 use crate::config::{EnrichmentTableConfig, GlobalOptions};
 
 pub mod file;
+pub mod status;
 
 #[cfg(feature = "enrichment-tables-geoip")]
 pub mod geoip;
@@ -0,0 +1,112 @@
+//! The reload status of the enrichment tables of the running topology, reported through the API.
+
+use std::{
+    collections::{BTreeMap, HashSet},
+    sync::RwLock,
+};
+
+use chrono::{DateTime, Utc};
+use once_cell::sync::Lazy;
+
+use crate::internal_events::{EnrichmentTableLoadError, EnrichmentTableLoaded};
+
+static STATUSES: Lazy<RwLock<BTreeMap<String, TableStatus>>> = Lazy::new(Default::default);
+
+/// The reload status of an enrichment table.
+#[derive(Clone, Debug, Default)]
+pub struct TableStatus {
+    /// The number of rows loaded, for tables made of rows.
+    pub rows: Option<usize>,
+    /// When the table was last loaded successfully.
+    pub last_reload: Option<DateTime<Utc>>,
+    /// The error of the last failed load, cleared once the table loads again.
+    pub last_error: Option<String>,
+    /// The number of failed loads.
+    pub reload_errors: u64,
+}
+
+/// Records that the table `name` was loaded with `rows` rows.
+pub fn loaded(name: &str, rows: Option<usize>) {
+    let now = Utc::now();
+    emit!(EnrichmentTableLoaded {
+        table: name,
+        rows,
+        timestamp: now.timestamp_millis() as f64 / 1000.0,
+    });
+
+    let mut statuses = STATUSES.write().expect("poisoned lock");
+    let status = statuses.entry(name.to_owned()).or_default();
+    status.rows = rows;
+    status.last_reload = Some(now);
+    status.last_error = None;
+}
+
+/// Records that loading the table `name` failed with `error`. The previously loaded data, if any,
+/// is still in use.
+pub fn failed(name: &str, error: String) {
+    emit!(EnrichmentTableLoadError {
+        table: name,
+        error: &error,
+    });
+
+    let mut statuses = STATUSES.write().expect("poisoned lock");
+    let status = statuses.entry(name.to_owned()).or_default();
+    status.last_error = Some(error);
+    status.reload_errors += 1;
+}
+
+/// Forgets the status of the tables no longer configured.
+pub fn retain(names: &HashSet<String>) {
+    STATUSES
+        .write()
+        .expect("poisoned lock")
+        .retain(|name, _| names.contains(name));
+}
+
+/// Gets the status of the tables, ordered by name.
+pub fn statuses() -> Vec<(String, TableStatus)> {
+    STATUSES
+        .read()
+        .expect("poisoned lock")
+        .iter()
+        .map(|(name, status)| (name.clone(), status.clone()))
+        .collect()
+}
+
+/// Whether the table `name` is part of the running topology.
+pub fn contains(name: &str) -> bool {
+    STATUSES.read().expect("poisoned lock").contains_key(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn find(name: &str) -> Option<TableStatus> {
+        statuses()
+            .into_iter()
+            .find(|(table, _)| table == name)
+            .map(|(_, status)| status)
+    }
+
+    #[test]
+    fn tracks_reloads_and_errors() {
+        assert!(!contains("status_test"));
+
+        loaded("status_test", Some(3));
+        failed("status_test", "Unreadable file.".to_owned());
+        let status = find("status_test").unwrap();
+        assert_eq!(status.rows, Some(3));
+        assert!(status.last_reload.is_some());
+        assert_eq!(status.last_error.as_deref(), Some("Unreadable file."));
+        assert_eq!(status.reload_errors, 1);
+
+        assert!(contains("status_test"));
+
+        loaded("status_test", Some(4));
+        let status = find("status_test").unwrap();
+        assert_eq!(status.rows, Some(4));
+        assert_eq!(status.last_error, None);
+        assert_eq!(status.reload_errors, 1);
+    }
+}
@@ -0,0 +1,567 @@
+use std::{collections::BTreeMap, sync::Mutex, time::Duration};
+
+use bytes::Bytes;
+use http::{Request, StatusCode};
+use hyper::{body::to_bytes as body_to_bytes, Body};
+use once_cell::sync::Lazy;
+use serde::Deserialize;
+use snafu::Snafu;
+use tokio::task::JoinHandle;
+use tracing::Instrument;
+use vector_core::config::{GlobalOptions, HostMetadataConfig, HostMetadataProvider};
+pub use vector_vrl_functions::get_host_metadata::HostMetadata;
+
+use crate::{
+    config::ProxyConfig,
+    event::{EventArray, Value},
+    http::HttpClient,
+    internal_events::{
+        HostMetadataProviderError, HostMetadataRefreshError, HostMetadataRefreshSuccessful,
+        HostMetadataTagsError,
+    },
+};
+
+/// The prefix of the template fields rendered from the host metadata.
+pub const TEMPLATE_PREFIX: &str = "host_metadata.";
+
+const EC2_ENDPOINT: &str = "http://169.254.169.254";
+const GCE_ENDPOINT: &str = "http://metadata.google.internal";
+const AZURE_ENDPOINT: &str = "http://169.254.169.254";
+
+const AZURE_API_VERSION: &str = "2021-02-01";
+
+#[cfg(not(test))]
+static HOST_METADATA: Lazy<HostMetadata> = Lazy::new(HostMetadata::default);
+
+#[cfg(test)]
+thread_local! {
+    // Tests get a store of their own, so that the values they set don't leak into other tests.
+    static HOST_METADATA: HostMetadata = HostMetadata::default();
+}
+
+/// The task refreshing the host metadata, replaced when the configuration is reloaded.
+static REFRESH_TASK: Lazy<Mutex<Option<JoinHandle<()>>>> = Lazy::new(Default::default);
+
+/// Gets a handle to the host metadata, which is empty unless the `host_metadata` global option is
+/// set.
+#[cfg(not(test))]
+pub fn store() -> HostMetadata {
+    HOST_METADATA.clone()
+}
+
+#[cfg(test)]
+pub fn store() -> HostMetadata {
+    HOST_METADATA.with(Clone::clone)
+}
+
+/// Adds the host metadata to the log events of the array, as an object set to the `key` field.
+pub fn tag_events(array: &mut EventArray, key: &str) {
+    let values = store().all();
+    if values.is_empty() {
+        return;
+    }
+    let object = Value::Object(
+        values
+            .into_iter()
+            .map(|(name, value)| (name, Value::from(value)))
+            .collect(),
+    );
+    for log in array.iter_logs_mut() {
+        log.insert(key, object.clone());
+    }
+}
+
+/// Fetches the host metadata as set by the `host_metadata` global option, then spawns a task
+/// refreshing it if a refresh interval is set.
+///
+/// This is called again when the configuration is reloaded, replacing the task refreshing the
+/// metadata of the previous configuration, and clearing the metadata if the option is unset.
+/// Failing to fetch the metadata isn't fatal, as Vector may run outside of the configured clouds.
+pub async fn start(global: &GlobalOptions) -> crate::Result<()> {
+    if let Some(task) = REFRESH_TASK.lock().expect("poisoned lock").take() {
+        task.abort();
+    }
+    let store = store();
+    let config = match &global.host_metadata {
+        Some(config) => config,
+        None => {
+            store.replace(BTreeMap::new());
+            return Ok(());
+        }
+    };
+
+    let proxy = ProxyConfig::merge_with_env(&global.proxy, &Default::default());
+    let fetcher = Fetcher::new(config, &proxy, Endpoints::default())?;
+    fetcher.refresh(&store).await;
+
+    if config.refresh_interval_secs > 0 {
+        let refresh_interval = Duration::from_secs(config.refresh_interval_secs);
+        let task = tokio::spawn(
+            async move {
+                let mut interval = tokio::time::interval(refresh_interval);
+                // The first tick completes immediately, and the metadata was just fetched.
+                interval.tick().await;
+                loop {
+                    interval.tick().await;
+                    fetcher.refresh(&store).await;
+                }
+            }
+            .instrument(info_span!("host_metadata").or_current()),
+        );
+        *REFRESH_TASK.lock().expect("poisoned lock") = Some(task);
+    }
+    Ok(())
+}
+
+#[derive(Debug, Snafu)]
+enum MetadataError {
+    #[snafu(display("Unexpected HTTP status: {}", status))]
+    UnexpectedHttpStatus { status: StatusCode },
+    #[snafu(display("Request timed out"))]
+    Timeout,
+}
+
+/// The base URLs of the metadata services, which tests point at mock servers.
+#[derive(Clone, Debug)]
+struct Endpoints {
+    ec2: String,
+    gce: String,
+    azure: String,
+}
+
+impl Default for Endpoints {
+    fn default() -> Self {
+        Self {
+            ec2: EC2_ENDPOINT.to_owned(),
+            gce: GCE_ENDPOINT.to_owned(),
+            azure: AZURE_ENDPOINT.to_owned(),
+        }
+    }
+}
+
+/// The instance identity document of EC2.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Ec2IdentityDocument {
+    account_id: String,
+    availability_zone: String,
+    region: String,
+    instance_id: String,
+    instance_type: String,
+    image_id: String,
+}
+
+/// The description of a GCE instance, as returned by the recursive `instance` query.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GceInstance {
+    id: u64,
+    hostname: String,
+    /// The full name of the machine type, like `projects/123/machineTypes/e2-medium`.
+    machine_type: String,
+    /// The full name of the zone, like `projects/123/zones/us-central1-a`.
+    zone: String,
+    image: Option<String>,
+}
+
+/// The description of an Azure virtual machine, as returned by the `instance/compute` query.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AzureCompute {
+    location: String,
+    name: String,
+    subscription_id: String,
+    vm_id: String,
+    vm_size: String,
+    #[serde(default)]
+    zone: String,
+    resource_group_name: String,
+    #[serde(default)]
+    tags_list: Vec<AzureTag>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AzureTag {
+    name: String,
+    value: String,
+}
+
+struct Fetcher {
+    client: HttpClient<Body>,
+    endpoints: Endpoints,
+    providers: Vec<HostMetadataProvider>,
+    tags: bool,
+    timeout: Duration,
+}
+
+impl Fetcher {
+    fn new(
+        config: &HostMetadataConfig,
+        proxy: &ProxyConfig,
+        endpoints: Endpoints,
+    ) -> crate::Result<Self> {
+        Ok(Self {
+            client: HttpClient::new(None, proxy)?,
+            endpoints,
+            providers: config.providers.clone(),
+            tags: config.tags,
+            timeout: Duration::from_secs(config.request_timeout_secs),
+        })
+    }
+
+    /// Replaces the values of `store` with freshly fetched ones, keeping the previous values if
+    /// none of the providers answer.
+    async fn refresh(&self, store: &HostMetadata) {
+        let results = futures::future::join_all(self.providers.iter().map(|provider| async move {
+            let result = match provider {
+                HostMetadataProvider::Ec2 => self.fetch_ec2().await,
+                HostMetadataProvider::Gce => self.fetch_gce().await,
+                HostMetadataProvider::Azure => self.fetch_azure().await,
+            };
+            (provider.name(), result)
+        }))
+        .await;
+
+        let mut values = BTreeMap::new();
+        let mut providers = Vec::new();
+        for (provider, result) in results {
+            match result {
+                Ok(provider_values) => {
+                    values.extend(provider_values);
+                    providers.push(provider);
+                }
+                Err(error) => emit!(HostMetadataProviderError { provider, error }),
+            }
+        }
+
+        if providers.is_empty() {
+            emit!(HostMetadataRefreshError);
+        } else {
+            emit!(HostMetadataRefreshSuccessful {
+                providers: &providers,
+                values: values.len(),
+            });
+            store.replace(values);
+        }
+    }
+
+    async fn get(&self, request: Request<Body>) -> crate::Result<Bytes> {
+        let response = tokio::time::timeout(self.timeout, self.client.send(request))
+            .await
+            .map_err(|_| MetadataError::Timeout)??;
+        match response.status() {
+            StatusCode::OK => Ok(body_to_bytes(response.into_body()).await?),
+            status => Err(MetadataError::UnexpectedHttpStatus { status }.into()),
+        }
+    }
+
+    async fn fetch_ec2(&self) -> crate::Result<BTreeMap<String, String>> {
+        let endpoint = &self.endpoints.ec2;
+        let token = self
+            .get(
+                Request::put(format!("{}/latest/api/token", endpoint))
+                    .header("X-aws-ec2-metadata-token-ttl-seconds", "300")
+                    .body(Body::empty())?,
+            )
+            .await?;
+        let request = |path: &str| -> crate::Result<Request<Body>> {
+            Ok(Request::get(format!("{}{}", endpoint, path))
+                .header("X-aws-ec2-metadata-token", token.as_ref())
+                .body(Body::empty())?)
+        };
+
+        let document: Ec2IdentityDocument = serde_json::from_slice(
+            &self
+                .get(request("/latest/dynamic/instance-identity/document")?)
+                .await?,
+        )?;
+        let hostname = self
+            .get(request("/latest/meta-data/local-hostname")?)
+            .await?;
+
+        let mut values = BTreeMap::from([
+            ("cloud.provider".to_owned(), "aws".to_owned()),
+            ("cloud.platform".to_owned(), "aws_ec2".to_owned()),
+            ("cloud.account.id".to_owned(), document.account_id),
+            ("cloud.region".to_owned(), document.region),
+            (
+                "cloud.availability_zone".to_owned(),
+                document.availability_zone,
+            ),
+            ("host.id".to_owned(), document.instance_id),
+            ("host.type".to_owned(), document.instance_type),
+            ("host.image.id".to_owned(), document.image_id),
+            (
+                "host.name".to_owned(),
+                String::from_utf8_lossy(&hostname).into_owned(),
+            ),
+        ]);
+
+        if self.tags {
+            // The tags are only listed if access to them is allowed on the instance, which
+            // shouldn't discard the rest of the metadata.
+            match self.fetch_ec2_tags(&request).await {
+                Ok(tags) => values.extend(tags),
+                Err(error) => emit!(HostMetadataTagsError {
+                    provider: HostMetadataProvider::Ec2.name(),
+                    error,
+                }),
+            }
+        }
+        Ok(values)
+    }
+
+    async fn fetch_ec2_tags(
+        &self,
+        request: impl Fn(&str) -> crate::Result<Request<Body>>,
+    ) -> crate::Result<BTreeMap<String, String>> {
+        let names = self
+            .get(request("/latest/meta-data/tags/instance")?)
+            .await?;
+        let mut tags = BTreeMap::new();
+        for name in String::from_utf8_lossy(&names).lines() {
+            let value = self
+                .get(request(&format!(
+                    "/latest/meta-data/tags/instance/{}",
+                    name
+                ))?)
+                .await?;
+            tags.insert(
+                format!("tags.{}", name),
+                String::from_utf8_lossy(&value).into_owned(),
+            );
+        }
+        Ok(tags)
+    }
+
+    async fn fetch_gce(&self) -> crate::Result<BTreeMap<String, String>> {
+        let request = |path: &str| -> crate::Result<Request<Body>> {
+            Ok(
+                Request::get(format!("{}/computeMetadata/v1{}", self.endpoints.gce, path))
+                    .header("Metadata-Flavor", "Google")
+                    .body(Body::empty())?,
+            )
+        };
+
+        let instance: GceInstance =
+            serde_json::from_slice(&self.get(request("/instance/?recursive=true")?).await?)?;
+        let project_id = self.get(request("/project/project-id")?).await?;
+
+        let zone = last_segment(&instance.zone).to_owned();
+        // Zones are named after their region, like `us-central1-a` in `us-central1`.
+        let region = zone
+            .rsplit_once('-')
+            .map_or_else(|| zone.clone(), |(region, _)| region.to_owned());
+        let mut values = BTreeMap::from([
+            ("cloud.provider".to_owned(), "gcp".to_owned()),
+            ("cloud.platform".to_owned(), "gcp_compute_engine".to_owned()),
+            (
+                "cloud.account.id".to_owned(),
+                String::from_utf8_lossy(&project_id).into_owned(),
+            ),
+            ("cloud.region".to_owned(), region),
+            ("cloud.availability_zone".to_owned(), zone),
+            ("host.id".to_owned(), instance.id.to_string()),
+            (
+                "host.type".to_owned(),
+                last_segment(&instance.machine_type).to_owned(),
+            ),
+            ("host.name".to_owned(), instance.hostname),
+        ]);
+        if let Some(image) = instance.image {
+            values.insert("host.image.id".to_owned(), image);
+        }
+        Ok(values)
+    }
+
+    async fn fetch_azure(&self) -> crate::Result<BTreeMap<String, String>> {
+        let compute: AzureCompute = serde_json::from_slice(
+            &self
+                .get(
+                    Request::get(format!(
+                        "{}/metadata/instance/compute?api-version={}",
+                        self.endpoints.azure, AZURE_API_VERSION
+                    ))
+                    .header("Metadata", "true")
+                    .body(Body::empty())?,
+                )
+                .await?,
+        )?;
+
+        let mut values = BTreeMap::from([
+            ("cloud.provider".to_owned(), "azure".to_owned()),
+            ("cloud.platform".to_owned(), "azure_vm".to_owned()),
+            ("cloud.account.id".to_owned(), compute.subscription_id),
+            ("cloud.region".to_owned(), compute.location),
+            ("host.id".to_owned(), compute.vm_id),
+            ("host.type".to_owned(), compute.vm_size),
+            ("host.name".to_owned(), compute.name),
+            (
+                "azure.resource_group".to_owned(),
+                compute.resource_group_name,
+            ),
+        ]);
+        if !compute.zone.is_empty() {
+            values.insert("cloud.availability_zone".to_owned(), compute.zone);
+        }
+        if self.tags {
+            values.extend(
+                compute
+                    .tags_list
+                    .into_iter()
+                    .map(|tag| (format!("tags.{}", tag.name), tag.value)),
+            );
+        }
+        Ok(values)
+    }
+}
+
+/// Gets the last segment of the full name of a GCE resource.
+fn last_segment(name: &str) -> &str {
+    name.rsplit('/').next().unwrap_or(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use warp::Filter;
+
+    use super::*;
+    use crate::test_util::next_addr;
+
+    fn config(providers: Vec<HostMetadataProvider>, tags: bool) -> HostMetadataConfig {
+        HostMetadataConfig {
+            providers,
+            tags,
+            refresh_interval_secs: 0,
+            request_timeout_secs: 1,
+        }
+    }
+
+    async fn fetch(config: HostMetadataConfig, endpoints: Endpoints) -> HostMetadata {
+        let fetcher = Fetcher::new(&config, &ProxyConfig::default(), endpoints).unwrap();
+        let store = HostMetadata::default();
+        fetcher.refresh(&store).await;
+        store
+    }
+
+    fn endpoints(address: &str) -> Endpoints {
+        Endpoints {
+            ec2: address.to_owned(),
+            gce: address.to_owned(),
+            azure: address.to_owned(),
+        }
+    }
+
+    #[tokio::test]
+    async fn fetches_ec2_metadata() {
+        let addr = next_addr();
+        let token = warp::put()
+            .and(warp::path!("latest" / "api" / "token"))
+            .map(|| "token");
+        let document = warp::path!("latest" / "dynamic" / "instance-identity" / "document")
+            .and(warp::header::exact("X-aws-ec2-metadata-token", "token"))
+            .map(|| {
+                r#"{"accountId":"123456789012","availabilityZone":"eu-west-1a","region":"eu-west-1","instanceId":"i-0abc","instanceType":"t3.micro","imageId":"ami-0def"}"#
+            });
+        let hostname = warp::path!("latest" / "meta-data" / "local-hostname").map(|| "ip-10-0-0-1");
+        let tags = warp::path!("latest" / "meta-data" / "tags" / "instance").map(|| "Name\nteam");
+        let tag = warp::path!("latest" / "meta-data" / "tags" / "instance" / String)
+            .map(|name: String| if name == "Name" { "web" } else { "platform" });
+        tokio::spawn(warp::serve(token.or(document).or(hostname).or(tags).or(tag)).run(addr));
+
+        let store = fetch(
+            config(vec![HostMetadataProvider::Ec2], true),
+            endpoints(&format!("http://{}", addr)),
+        )
+        .await;
+
+        assert_eq!(store.get("cloud.provider").as_deref(), Some("aws"));
+        assert_eq!(store.get("cloud.region").as_deref(), Some("eu-west-1"));
+        assert_eq!(store.get("host.id").as_deref(), Some("i-0abc"));
+        assert_eq!(store.get("host.name").as_deref(), Some("ip-10-0-0-1"));
+        assert_eq!(store.get("tags.Name").as_deref(), Some("web"));
+        assert_eq!(store.get("tags.team").as_deref(), Some("platform"));
+    }
+
+    #[tokio::test]
+    async fn fetches_ec2_metadata_without_tags() {
+        let addr = next_addr();
+        let token = warp::put()
+            .and(warp::path!("latest" / "api" / "token"))
+            .map(|| "token");
+        let document = warp::path!("latest" / "dynamic" / "instance-identity" / "document")
+            .map(|| {
+                r#"{"accountId":"123456789012","availabilityZone":"eu-west-1a","region":"eu-west-1","instanceId":"i-0abc","instanceType":"t3.micro","imageId":"ami-0def"}"#
+            });
+        let hostname = warp::path!("latest" / "meta-data" / "local-hostname").map(|| "ip-10-0-0-1");
+        // Access to the tags isn't allowed, so listing them is not found.
+        tokio::spawn(warp::serve(token.or(document).or(hostname)).run(addr));
+
+        let store = fetch(
+            config(vec![HostMetadataProvider::Ec2], true),
+            endpoints(&format!("http://{}", addr)),
+        )
+        .await;
+
+        assert_eq!(store.get("host.id").as_deref(), Some("i-0abc"));
+        assert_eq!(store.get("tags.Name"), None);
+    }
+
+    #[tokio::test]
+    async fn clears_values_when_unset() {
+        store().replace(BTreeMap::from([(
+            "cloud.region".to_owned(),
+            "eu-west-1".to_owned(),
+        )]));
+
+        start(&GlobalOptions::default()).await.unwrap();
+
+        assert!(store().all().is_empty());
+    }
+
+    #[tokio::test]
+    async fn fetches_gce_metadata() {
+        let addr = next_addr();
+        let instance = warp::path!("computeMetadata" / "v1" / "instance" / ..)
+            .and(warp::header::exact("Metadata-Flavor", "Google"))
+            .map(|| {
+                r#"{"id":4520031799277581759,"name":"web","hostname":"web.c.vector.internal","machineType":"projects/123/machineTypes/e2-medium","zone":"projects/123/zones/us-central1-a"}"#
+            });
+        let project =
+            warp::path!("computeMetadata" / "v1" / "project" / "project-id").map(|| "vector");
+        tokio::spawn(warp::serve(instance.or(project)).run(addr));
+
+        let store = fetch(
+            config(vec![HostMetadataProvider::Gce], false),
+            endpoints(&format!("http://{}", addr)),
+        )
+        .await;
+
+        assert_eq!(store.get("cloud.provider").as_deref(), Some("gcp"));
+        assert_eq!(store.get("cloud.account.id").as_deref(), Some("vector"));
+        assert_eq!(store.get("cloud.region").as_deref(), Some("us-central1"));
+        assert_eq!(
+            store.get("cloud.availability_zone").as_deref(),
+            Some("us-central1-a")
+        );
+        assert_eq!(store.get("host.type").as_deref(), Some("e2-medium"));
+    }
+
+    #[tokio::test]
+    async fn keeps_values_when_no_provider_answers() {
+        let store = HostMetadata::default();
+        store.replace(BTreeMap::from([(
+            "cloud.region".to_owned(),
+            "eu-west-1".to_owned(),
+        )]));
+        let fetcher = Fetcher::new(
+            &config(vec![HostMetadataProvider::Azure], false),
+            &ProxyConfig::default(),
+            endpoints(&format!("http://{}", next_addr())),
+        )
+        .unwrap();
+        fetcher.refresh(&store).await;
+
+        assert_eq!(store.get("cloud.region").as_deref(), Some("eu-west-1"));
+    }
+}
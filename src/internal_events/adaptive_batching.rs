@@ -0,0 +1,26 @@
+use std::time::Duration;
+
+use metrics::histogram;
+use vector_core::internal_event::InternalEvent;
+
+#[derive(Debug)]
+pub struct AdaptiveBatchLimit {
+    pub ratio: f64,
+    pub averaged_latency: Duration,
+    pub had_back_pressure: bool,
+}
+
+impl InternalEvent for AdaptiveBatchLimit {
+    fn emit(self) {
+        trace!(
+            message = "Changed batch limits.",
+            ratio = %self.ratio,
+            averaged_latency = ?self.averaged_latency,
+            had_back_pressure = %self.had_back_pressure,
+        );
+        // Like the adaptive concurrency ones, these are histograms, as each partition of a sink
+        // has its own values.
+        histogram!("adaptive_batch_ratio", self.ratio);
+        histogram!("adaptive_batch_averaged_latency", self.averaged_latency);
+    }
+}
@@ -0,0 +1,67 @@
+use metrics::counter;
+use vector_common::internal_event::{error_stage, error_type};
+use vector_core::internal_event::InternalEvent;
+
+#[cfg(target_os = "linux")]
+#[derive(Debug)]
+pub struct AuditdReadError {
+    pub error: std::io::Error,
+}
+
+#[cfg(target_os = "linux")]
+impl InternalEvent for AuditdReadError {
+    fn emit(self) {
+        error!(
+            message = "Failed to read from the audit netlink socket.",
+            error = %self.error,
+            error_code = "netlink_read_failed",
+            error_type = error_type::READER_FAILED,
+            stage = error_stage::RECEIVING,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "netlink_read_failed",
+            "error_type" => error_type::READER_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct AuditdInvalidRecord<'a> {
+    pub record: &'a str,
+}
+
+impl InternalEvent for AuditdInvalidRecord<'_> {
+    fn emit(self) {
+        error!(
+            message = "Failed to parse audit record.",
+            record = %self.record,
+            error_code = "invalid_record",
+            error_type = error_type::PARSER_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "invalid_record",
+            "error_type" => error_type::PARSER_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+    }
+}
+
+#[cfg(target_os = "linux")]
+#[derive(Debug)]
+pub struct AuditdReceiveBufferOverrun;
+
+#[cfg(target_os = "linux")]
+impl InternalEvent for AuditdReceiveBufferOverrun {
+    fn emit(self) {
+        warn!(
+            message = "Audit records were dropped by the kernel before being read.",
+            internal_log_rate_limit = true,
+        );
+        counter!("auditd_receive_buffer_overruns_total", 1);
+    }
+}
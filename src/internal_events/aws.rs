@@ -25,3 +25,41 @@ impl InternalEvent for AwsBytesSent {
         );
     }
 }
+
+#[derive(Debug)]
+pub struct AwsEndpointRequestServed<'a> {
+    pub endpoint: &'a str,
+}
+
+impl<'a> InternalEvent for AwsEndpointRequestServed<'a> {
+    fn emit(self) {
+        counter!(
+            "aws_endpoint_requests_served_total", 1,
+            "endpoint" => self.endpoint.to_owned(),
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct AwsEndpointSwitched<'a> {
+    pub from: &'a str,
+    pub to: &'a str,
+    pub reason: &'static str,
+}
+
+impl<'a> InternalEvent for AwsEndpointSwitched<'a> {
+    fn emit(self) {
+        warn!(
+            message = "Switching AWS endpoint.",
+            from = %self.from,
+            to = %self.to,
+            reason = %self.reason,
+        );
+        counter!(
+            "aws_endpoint_switches_total", 1,
+            "from" => self.from.to_owned(),
+            "to" => self.to.to_owned(),
+            "reason" => self.reason,
+        );
+    }
+}
@@ -0,0 +1,37 @@
+use metrics::counter;
+use vector_common::internal_event::{error_stage, error_type};
+use vector_core::internal_event::InternalEvent;
+
+use crate::{
+    emit,
+    internal_events::{ComponentEventsDropped, UNINTENTIONAL},
+};
+
+#[derive(Debug)]
+pub struct BlackholeEventsRejected {
+    pub count: usize,
+}
+
+impl InternalEvent for BlackholeEventsRejected {
+    fn emit(self) {
+        let reason = "Events rejected by the configured error rate.";
+        error!(
+            message = reason,
+            count = self.count as u64,
+            error_code = "injected_error",
+            error_type = error_type::REQUEST_FAILED,
+            stage = error_stage::SENDING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "injected_error",
+            "error_type" => error_type::REQUEST_FAILED,
+            "stage" => error_stage::SENDING,
+        );
+        emit!(ComponentEventsDropped::<UNINTENTIONAL> {
+            count: self.count as u64,
+            reason
+        });
+    }
+}
@@ -0,0 +1,28 @@
+use metrics::counter;
+use vector_core::internal_event::InternalEvent;
+
+use vector_common::internal_event::{error_stage, error_type};
+
+use crate::checkpoint::CheckpointStoreError;
+
+#[derive(Debug)]
+pub struct CheckpointMirrorError<'a> {
+    pub error: &'a CheckpointStoreError,
+}
+
+impl<'a> InternalEvent for CheckpointMirrorError<'a> {
+    fn emit(self) {
+        error!(
+            message = "Failed to copy checkpoints to the checkpoint store.",
+            error = %self.error,
+            error_type = error_type::WRITER_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::WRITER_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
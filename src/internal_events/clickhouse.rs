@@ -0,0 +1,36 @@
+use metrics::counter;
+use vector_common::internal_event::{error_stage, error_type};
+use vector_core::internal_event::InternalEvent;
+
+use crate::{
+    emit,
+    internal_events::{ComponentEventsDropped, UNINTENTIONAL},
+};
+
+#[derive(Debug)]
+pub struct ClickhouseTableLimitReached<'a> {
+    pub table: &'a str,
+    pub max_tables: usize,
+}
+
+impl<'a> InternalEvent for ClickhouseTableLimitReached<'a> {
+    fn emit(self) {
+        let reason = "Table limit reached.";
+        error!(
+            message = reason,
+            table = %self.table,
+            max_tables = self.max_tables as u64,
+            error_code = "table_limit_reached",
+            error_type = error_type::CONDITION_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "table_limit_reached",
+            "error_type" => error_type::CONDITION_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+        emit!(ComponentEventsDropped::<UNINTENTIONAL> { count: 1, reason });
+    }
+}
@@ -0,0 +1,29 @@
+use metrics::counter;
+use vector_core::internal_event::InternalEvent;
+
+use vector_common::internal_event::{error_stage, error_type};
+
+#[derive(Debug)]
+pub struct ClockReadError<E> {
+    pub method: &'static str,
+    pub error: E,
+}
+
+impl<E: std::fmt::Display> InternalEvent for ClockReadError<E> {
+    fn emit(self) {
+        error!(
+            message = "Failed to read the status of the system clock.",
+            method = self.method,
+            error = %self.error,
+            error_type = error_type::READER_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::READER_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
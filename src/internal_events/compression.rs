@@ -0,0 +1,39 @@
+use metrics::counter;
+use vector_core::internal_event::InternalEvent;
+
+use crate::{
+    emit,
+    internal_events::{ComponentEventsDropped, UNINTENTIONAL},
+};
+use vector_common::internal_event::{error_stage, error_type};
+
+#[derive(Debug)]
+pub struct CompressionFieldError<'a, E> {
+    pub field: &'a str,
+    pub error: E,
+    pub drop_event: bool,
+}
+
+impl<E: std::fmt::Display> InternalEvent for CompressionFieldError<'_, E> {
+    fn emit(self) {
+        error!(
+            message = "Failed to process the payload of the field.",
+            field = %self.field,
+            error = %self.error,
+            error_type = error_type::CONVERSION_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::CONVERSION_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+        if self.drop_event {
+            emit!(ComponentEventsDropped::<UNINTENTIONAL> {
+                count: 1,
+                reason: "Failed to process the payload of the field.",
+            });
+        }
+    }
+}
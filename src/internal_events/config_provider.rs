@@ -0,0 +1,45 @@
+use metrics::{counter, gauge};
+use vector_core::internal_event::InternalEvent;
+
+#[derive(Debug)]
+pub struct ConfigProviderVersionApplied<'a> {
+    pub provider: &'static str,
+    pub version: i64,
+    pub digest: &'a str,
+}
+
+impl<'a> InternalEvent for ConfigProviderVersionApplied<'a> {
+    fn emit(self) {
+        info!(
+            message = "Applied configuration version from provider.",
+            provider = %self.provider,
+            version = %self.version,
+            digest = %self.digest,
+        );
+        gauge!(
+            "config_provider_version", self.version as f64,
+            "provider" => self.provider,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct ConfigProviderError<'a> {
+    pub provider: &'static str,
+    pub error: &'a str,
+}
+
+impl<'a> InternalEvent for ConfigProviderError<'a> {
+    fn emit(self) {
+        error!(
+            message = "Failed to fetch configuration from provider.",
+            provider = %self.provider,
+            error = %self.error,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "config_provider_errors_total", 1,
+            "provider" => self.provider,
+        );
+    }
+}
@@ -0,0 +1,19 @@
+use crate::{
+    emit,
+    internal_events::{ComponentEventsDropped, INTENTIONAL},
+};
+use vector_core::internal_event::InternalEvent;
+
+#[derive(Debug)]
+pub struct ContentDedupeEventsDropped {
+    pub count: u64,
+}
+
+impl InternalEvent for ContentDedupeEventsDropped {
+    fn emit(self) {
+        emit!(ComponentEventsDropped::<INTENTIONAL> {
+            count: self.count,
+            reason: "Events have been seen within the deduplication window.",
+        });
+    }
+}
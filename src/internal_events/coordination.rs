@@ -0,0 +1,43 @@
+use metrics::{counter, gauge};
+use vector_core::internal_event::InternalEvent;
+
+use vector_common::internal_event::{error_stage, error_type};
+
+use crate::coordination::CoordinationError;
+
+#[derive(Debug)]
+pub struct CoordinationMembershipError<'a> {
+    pub error: &'a CoordinationError,
+}
+
+impl<'a> InternalEvent for CoordinationMembershipError<'a> {
+    fn emit(self) {
+        error!(
+            message = "Failed to update the membership in the coordination group.",
+            error = %self.error,
+            error_type = error_type::REQUEST_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::REQUEST_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct CoordinationGroupChanged {
+    pub members: usize,
+}
+
+impl InternalEvent for CoordinationGroupChanged {
+    fn emit(self) {
+        debug!(
+            message = "Coordination group changed.",
+            members = %self.members,
+        );
+        gauge!("coordination_group_members", self.members as f64);
+    }
+}
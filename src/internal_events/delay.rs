@@ -0,0 +1,98 @@
+use std::path::PathBuf;
+
+use metrics::{counter, gauge};
+use vector_common::internal_event::{error_stage, error_type};
+use vector_core::internal_event::InternalEvent;
+
+use crate::{
+    emit,
+    internal_events::{ComponentEventsDropped, UNINTENTIONAL},
+};
+
+#[derive(Debug)]
+pub struct DelayedEvents {
+    pub in_memory: usize,
+    pub on_disk: usize,
+}
+
+impl InternalEvent for DelayedEvents {
+    fn emit(self) {
+        gauge!("delayed_events", self.in_memory as f64, "location" => "memory");
+        gauge!("delayed_events", self.on_disk as f64, "location" => "disk");
+    }
+}
+
+#[derive(Debug)]
+pub struct DelayReleaseTimeError {
+    pub error: String,
+}
+
+impl InternalEvent for DelayReleaseTimeError {
+    fn emit(self) {
+        warn!(
+            message = "Failed to evaluate the release time of event, falling back to `delay_secs`.",
+            error = %self.error,
+            error_type = error_type::CONVERSION_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::CONVERSION_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct DelayQueueWriteError {
+    pub error: std::io::Error,
+    pub path: PathBuf,
+}
+
+impl InternalEvent for DelayQueueWriteError {
+    fn emit(self) {
+        error!(
+            message = "Failed to write held events to disk, keeping them in memory.",
+            error = %self.error,
+            path = ?self.path,
+            error_type = error_type::WRITER_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::WRITER_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct DelayQueueReadError {
+    pub error: String,
+    pub path: PathBuf,
+    pub count: u64,
+}
+
+impl InternalEvent for DelayQueueReadError {
+    fn emit(self) {
+        error!(
+            message = "Failed to read held events from disk.",
+            error = %self.error,
+            path = ?self.path,
+            error_type = error_type::READER_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::READER_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+        emit!(ComponentEventsDropped::<UNINTENTIONAL> {
+            count: self.count,
+            reason: "Failed to read held events from disk.",
+        });
+    }
+}
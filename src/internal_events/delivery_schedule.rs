@@ -0,0 +1,18 @@
+use metrics::gauge;
+use vector_core::internal_event::InternalEvent;
+
+#[derive(Debug)]
+pub struct DeliveryWindowChanged {
+    pub open: bool,
+}
+
+impl InternalEvent for DeliveryWindowChanged {
+    fn emit(self) {
+        if self.open {
+            debug!(message = "Delivery window is open, delivering events.");
+        } else {
+            debug!(message = "Delivery window is closed, holding events in the buffer.");
+        }
+        gauge!("delivery_window_open", if self.open { 1.0 } else { 0.0 });
+    }
+}
@@ -0,0 +1,45 @@
+use metrics::counter;
+use vector_core::internal_event::InternalEvent;
+
+use vector_common::internal_event::{error_stage, error_type};
+
+#[derive(Debug)]
+pub struct ElasticsearchDeadLetterSent<'a> {
+    pub count: usize,
+    pub index: &'a str,
+}
+
+impl InternalEvent for ElasticsearchDeadLetterSent<'_> {
+    fn emit(self) {
+        debug!(
+            message = "Sent rejected documents to the dead letter index.",
+            count = self.count,
+            index = self.index,
+        );
+        counter!("dead_letter_events_total", self.count as u64);
+    }
+}
+
+#[derive(Debug)]
+pub struct ElasticsearchDeadLetterError<E> {
+    pub count: usize,
+    pub error: E,
+}
+
+impl<E: std::fmt::Display> InternalEvent for ElasticsearchDeadLetterError<E> {
+    fn emit(self) {
+        error!(
+            message = "Failed to send rejected documents to the dead letter index.",
+            count = self.count,
+            error = %self.error,
+            error_type = error_type::REQUEST_FAILED,
+            stage = error_stage::SENDING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::REQUEST_FAILED,
+            "stage" => error_stage::SENDING,
+        );
+    }
+}
@@ -0,0 +1,54 @@
+use metrics::{counter, gauge};
+use vector_core::internal_event::InternalEvent;
+
+#[derive(Debug)]
+pub struct EnrichmentTableLoaded<'a> {
+    pub table: &'a str,
+    pub rows: Option<usize>,
+    pub timestamp: f64,
+}
+
+impl<'a> InternalEvent for EnrichmentTableLoaded<'a> {
+    fn emit(self) {
+        info!(
+            message = "Enrichment table loaded.",
+            table = %self.table,
+            rows = ?self.rows,
+        );
+        counter!(
+            "enrichment_table_reloads_total", 1,
+            "table" => self.table.to_owned(),
+        );
+        gauge!(
+            "enrichment_table_last_reload_timestamp_seconds", self.timestamp,
+            "table" => self.table.to_owned(),
+        );
+        if let Some(rows) = self.rows {
+            gauge!(
+                "enrichment_table_rows", rows as f64,
+                "table" => self.table.to_owned(),
+            );
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct EnrichmentTableLoadError<'a> {
+    pub table: &'a str,
+    pub error: &'a str,
+}
+
+impl<'a> InternalEvent for EnrichmentTableLoadError<'a> {
+    fn emit(self) {
+        error!(
+            message = "Failed to load enrichment table.",
+            table = %self.table,
+            error = %self.error,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "enrichment_table_reload_errors_total", 1,
+            "table" => self.table.to_owned(),
+        );
+    }
+}
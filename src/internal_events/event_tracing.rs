@@ -0,0 +1,47 @@
+use vector_core::{event::EventTrace, internal_event::InternalEvent};
+
+#[derive(Debug)]
+pub struct EventTraceStarted<'a> {
+    pub trace: &'a EventTrace,
+}
+
+impl InternalEvent for EventTraceStarted<'_> {
+    fn emit(self) {
+        info!(
+            message = "Started tracing event.",
+            trace_id = self.trace.id(),
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct TracedEventReceived<'a> {
+    pub trace: &'a EventTrace,
+}
+
+impl InternalEvent for TracedEventReceived<'_> {
+    fn emit(self) {
+        info!(
+            message = "Traced event received.",
+            trace_id = self.trace.id(),
+            elapsed_ms = self.trace.elapsed().as_secs_f64() * 1000.0,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct TracedEventSent<'a> {
+    pub trace: &'a EventTrace,
+    pub output: &'a str,
+}
+
+impl InternalEvent for TracedEventSent<'_> {
+    fn emit(self) {
+        info!(
+            message = "Traced event sent.",
+            trace_id = self.trace.id(),
+            output = %self.output,
+            elapsed_ms = self.trace.elapsed().as_secs_f64() * 1000.0,
+        );
+    }
+}
@@ -82,7 +82,7 @@ impl<'a, P: std::fmt::Debug> InternalEvent for FileIoError<'a, P> {
 
 #[cfg(any(feature = "sources-file", feature = "sources-kubernetes_logs"))]
 mod source {
-    use std::{io::Error, path::Path, time::Duration};
+    use std::{io::Error, path::Path, sync::Arc, time::Duration};
 
     use file_source::FileSourceInternalEvents;
     use metrics::counter;
@@ -407,8 +407,14 @@ mod source {
         }
     }
 
-    #[derive(Clone)]
-    pub struct FileSourceInternalEventsEmitter;
+    #[derive(Clone, Default)]
+    pub struct FileSourceInternalEventsEmitter {
+        /// Called with the path of each file that stops being watched, to release its state.
+        pub on_unwatched: Option<Arc<dyn Fn(&Path) + Send + Sync>>,
+
+        /// Called whenever the checkpoints are written, to copy them elsewhere.
+        pub on_checkpointed: Option<Arc<dyn Fn() + Send + Sync>>,
+    }
 
     impl FileSourceInternalEvents for FileSourceInternalEventsEmitter {
         fn emit_file_added(&self, file: &Path) {
@@ -428,6 +434,9 @@ mod source {
 
         fn emit_file_unwatched(&self, file: &Path) {
             emit!(FileUnwatched { file });
+            if let Some(on_unwatched) = &self.on_unwatched {
+                on_unwatched(file);
+            }
         }
 
         fn emit_file_deleted(&self, file: &Path) {
@@ -448,6 +457,9 @@ mod source {
 
         fn emit_file_checkpointed(&self, count: usize, duration: Duration) {
             emit!(FileCheckpointed { count, duration });
+            if let Some(on_checkpointed) = &self.on_checkpointed {
+                on_checkpointed();
+            }
         }
 
         fn emit_file_checkpoint_write_error(&self, error: Error) {
@@ -36,3 +36,29 @@ impl InternalEventHandle for FilterEventsDroppedHandle {
         self.events_discarded.increment(data.0 as u64);
     }
 }
+
+#[derive(Debug)]
+pub struct FilterQuotaExceeded;
+
+impl RegisterInternalEvent for FilterQuotaExceeded {
+    type Handle = FilterQuotaExceededHandle;
+    fn register(self) -> Self::Handle {
+        Self::Handle {
+            events_dropped: register!(ComponentEventsDropped::<INTENTIONAL>::from(
+                "Events exceeded the quota of their key."
+            )),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct FilterQuotaExceededHandle {
+    events_dropped: Registered<ComponentEventsDropped<'static, INTENTIONAL>>,
+}
+
+impl InternalEventHandle for FilterQuotaExceededHandle {
+    type Data = Count;
+    fn emit(&self, data: Count) {
+        self.events_dropped.emit(data);
+    }
+}
@@ -0,0 +1,34 @@
+use metrics::counter;
+use vector_common::internal_event::{error_stage, error_type};
+use vector_core::internal_event::InternalEvent;
+
+use crate::{
+    emit,
+    internal_events::{ComponentEventsDropped, UNINTENTIONAL},
+};
+
+#[derive(Debug)]
+pub struct GcpCloudLoggingEntryParseError {
+    pub error: serde_json::Error,
+}
+
+impl InternalEvent for GcpCloudLoggingEntryParseError {
+    fn emit(self) {
+        let reason = "Failed to parse log entry.";
+        error!(
+            message = reason,
+            error = %self.error,
+            error_code = "failed_parsing_log_entry",
+            error_type = error_type::PARSER_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "failed_parsing_log_entry",
+            "error_type" => error_type::PARSER_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+        emit!(ComponentEventsDropped::<UNINTENTIONAL> { count: 1, reason });
+    }
+}
@@ -0,0 +1,30 @@
+use metrics::counter;
+use vector_core::internal_event::InternalEvent;
+
+use vector_common::internal_event::{error_stage, error_type};
+
+#[derive(Debug)]
+pub struct GrpcServerInvalidMessage<'a> {
+    pub method: &'a str,
+    pub error: &'a str,
+}
+
+impl<'a> InternalEvent for GrpcServerInvalidMessage<'a> {
+    fn emit(self) {
+        error!(
+            message = "Failed to decode request message.",
+            method = %self.method,
+            error = %self.error,
+            error_code = "invalid_message",
+            error_type = error_type::PARSER_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "invalid_message",
+            "error_type" => error_type::PARSER_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
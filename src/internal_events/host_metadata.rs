@@ -0,0 +1,67 @@
+use metrics::counter;
+use vector_common::internal_event::{error_stage, error_type};
+use vector_core::internal_event::InternalEvent;
+
+#[derive(Debug)]
+pub struct HostMetadataRefreshSuccessful<'a> {
+    pub providers: &'a [&'static str],
+    pub values: usize,
+}
+
+impl<'a> InternalEvent for HostMetadataRefreshSuccessful<'a> {
+    fn emit(self) {
+        debug!(
+            message = "Host metadata refreshed.",
+            providers = ?self.providers,
+            values = %self.values,
+        );
+        counter!("host_metadata_refresh_successful_total", 1);
+    }
+}
+
+#[derive(Debug)]
+pub struct HostMetadataProviderError {
+    pub provider: &'static str,
+    pub error: crate::Error,
+}
+
+impl InternalEvent for HostMetadataProviderError {
+    fn emit(self) {
+        // Only one provider answers on a given host, so this is expected for the others.
+        debug!(
+            message = "Host metadata provider unavailable.",
+            provider = %self.provider,
+            error = %self.error,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct HostMetadataTagsError {
+    pub provider: &'static str,
+    pub error: crate::Error,
+}
+
+impl InternalEvent for HostMetadataTagsError {
+    fn emit(self) {
+        warn!(
+            message = "Host metadata tags unavailable, keeping the other values.",
+            provider = %self.provider,
+            error = %self.error,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct HostMetadataRefreshError;
+
+impl InternalEvent for HostMetadataRefreshError {
+    fn emit(self) {
+        error!(
+            message = "Host metadata refresh failed, none of the providers answered.",
+            error_type = error_type::REQUEST_FAILED,
+            stage = error_stage::PROCESSING,
+        );
+        counter!("host_metadata_refresh_failed_total", 1);
+    }
+}
@@ -102,6 +102,24 @@ impl<'a> InternalEvent for HttpBadRequest<'a> {
     }
 }
 
+#[derive(Debug)]
+pub struct HttpRequestThrottled<'a> {
+    pub max_concurrent_requests: usize,
+    pub http_path: &'a str,
+}
+
+impl InternalEvent for HttpRequestThrottled<'_> {
+    fn emit(self) {
+        warn!(
+            message = "Rejecting request, too many requests are pending.",
+            max_concurrent_requests = %self.max_concurrent_requests,
+            http_path = %self.http_path,
+            internal_log_rate_limit = true,
+        );
+        counter!("http_requests_throttled_total", 1);
+    }
+}
+
 #[derive(Debug)]
 pub struct HttpDecompressError<'a> {
     pub error: &'a dyn Error,
@@ -13,6 +13,7 @@ use vector_common::internal_event::{error_stage, error_type};
 #[derive(Debug)]
 pub struct AboutToSendHttpRequest<'a, T> {
     pub request: &'a Request<T>,
+    pub endpoint: &'a str,
 }
 
 fn remove_sensitive(headers: &HeaderMap<HeaderValue>) -> HeaderMap<HeaderValue> {
@@ -40,13 +41,18 @@ impl<'a, T: HttpBody> InternalEvent for AboutToSendHttpRequest<'a, T> {
             headers = ?remove_sensitive(self.request.headers()),
             body = %FormatBody(self.request.body()),
         );
-        counter!("http_client_requests_sent_total", 1, "method" => self.request.method().to_string());
+        counter!(
+            "http_client_requests_sent_total", 1,
+            "method" => self.request.method().to_string(),
+            "endpoint" => self.endpoint.to_owned(),
+        );
     }
 }
 
 #[derive(Debug)]
 pub struct GotHttpResponse<'a, T> {
     pub response: &'a Response<T>,
+    pub endpoint: &'a str,
     pub roundtrip: Duration,
 }
 
@@ -54,20 +60,31 @@ impl<'a, T: HttpBody> InternalEvent for GotHttpResponse<'a, T> {
     fn emit(self) {
         debug!(
             message = "HTTP response.",
+            endpoint = %self.endpoint,
             status = %self.response.status(),
             version = ?self.response.version(),
             headers = ?remove_sensitive(self.response.headers()),
             body = %FormatBody(self.response.body()),
         );
-        counter!("http_client_responses_total", 1, "status" => self.response.status().as_u16().to_string());
-        histogram!("http_client_rtt_seconds", self.roundtrip);
-        histogram!("http_client_response_rtt_seconds", self.roundtrip, "status" => self.response.status().as_u16().to_string());
+        let status = self.response.status().as_u16().to_string();
+        counter!(
+            "http_client_responses_total", 1,
+            "status" => status.clone(),
+            "endpoint" => self.endpoint.to_owned(),
+        );
+        histogram!("http_client_rtt_seconds", self.roundtrip, "endpoint" => self.endpoint.to_owned());
+        histogram!(
+            "http_client_response_rtt_seconds", self.roundtrip,
+            "status" => status,
+            "endpoint" => self.endpoint.to_owned(),
+        );
     }
 }
 
 #[derive(Debug)]
 pub struct GotHttpWarning<'a> {
     pub error: &'a Error,
+    pub endpoint: &'a str,
     pub roundtrip: Duration,
 }
 
@@ -76,6 +93,7 @@ impl<'a> InternalEvent for GotHttpWarning<'a> {
         warn!(
             message = "HTTP error.",
             error = %self.error,
+            endpoint = %self.endpoint,
             error_type = error_type::REQUEST_FAILED,
             stage = error_stage::PROCESSING,
         );
@@ -84,9 +102,17 @@ impl<'a> InternalEvent for GotHttpWarning<'a> {
             "error_type" => error_type::REQUEST_FAILED,
             "stage" => error_stage::PROCESSING,
         );
-        counter!("http_client_errors_total", 1, "error_kind" => self.error.to_string());
-        histogram!("http_client_rtt_seconds", self.roundtrip);
-        histogram!("http_client_error_rtt_seconds", self.roundtrip, "error_kind" => self.error.to_string());
+        counter!(
+            "http_client_errors_total", 1,
+            "error_kind" => self.error.to_string(),
+            "endpoint" => self.endpoint.to_owned(),
+        );
+        histogram!("http_client_rtt_seconds", self.roundtrip, "endpoint" => self.endpoint.to_owned());
+        histogram!(
+            "http_client_error_rtt_seconds", self.roundtrip,
+            "error_kind" => self.error.to_string(),
+            "endpoint" => self.endpoint.to_owned(),
+        );
     }
 }
 
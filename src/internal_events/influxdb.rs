@@ -34,3 +34,48 @@ impl InternalEvent for InfluxdbEncodingError {
         });
     }
 }
+
+#[derive(Debug)]
+pub struct InfluxdbFieldTypeConflict<'a> {
+    pub measurement: &'a str,
+    pub field: &'a str,
+    pub expected: &'static str,
+    pub actual: &'static str,
+    pub policy: &'static str,
+}
+
+impl<'a> InternalEvent for InfluxdbFieldTypeConflict<'a> {
+    fn emit(self) {
+        warn!(
+            message = "Field type conflicts with the existing series.",
+            measurement = %self.measurement,
+            field = %self.field,
+            expected = %self.expected,
+            actual = %self.actual,
+            policy = %self.policy,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "field_type_conflicts_total", 1,
+            "policy" => self.policy,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct InfluxdbFieldTypeLearned<'a> {
+    pub measurement: &'a str,
+    pub field: &'a str,
+    pub field_type: &'static str,
+}
+
+impl<'a> InternalEvent for InfluxdbFieldTypeLearned<'a> {
+    fn emit(self) {
+        debug!(
+            message = "InfluxDB reported the type of a field.",
+            measurement = %self.measurement,
+            field = %self.field,
+            field_type = %self.field_type,
+        );
+    }
+}
@@ -0,0 +1,21 @@
+use std::time::Duration;
+
+use metrics::{counter, histogram};
+use vector_core::internal_event::InternalEvent;
+
+#[derive(Debug)]
+pub struct IngestionThrottled {
+    pub delay: Duration,
+}
+
+impl InternalEvent for IngestionThrottled {
+    fn emit(self) {
+        debug!(
+            message = "Ingestion limit reached, delaying events.",
+            delay_secs = %self.delay.as_secs_f64(),
+            internal_log_rate_limit = true,
+        );
+        counter!("ingestion_throttled_total", 1);
+        histogram!("ingestion_throttle_delay_seconds", self.delay);
+    }
+}
@@ -0,0 +1,31 @@
+use metrics::counter;
+use vector_core::internal_event::InternalEvent;
+
+#[derive(Debug)]
+pub struct InternalEventsAuditViolation<'a> {
+    pub component_kind: &'a str,
+    pub component_id: &'a str,
+    pub component_type: &'a str,
+    pub missing_event: &'static str,
+    pub reason: &'static str,
+}
+
+impl<'a> InternalEvent for InternalEventsAuditViolation<'a> {
+    fn emit(self) {
+        warn!(
+            message = "Component doesn't emit a required internal event.",
+            component_kind = %self.component_kind,
+            component_id = %self.component_id,
+            component_type = %self.component_type,
+            missing_event = %self.missing_event,
+            reason = %self.reason,
+        );
+        counter!(
+            "internal_events_audit_violations_total", 1,
+            "component_kind" => self.component_kind.to_owned(),
+            "component_id" => self.component_id.to_owned(),
+            "component_type" => self.component_type.to_owned(),
+            "missing_event" => self.missing_event,
+        );
+    }
+}
@@ -4,6 +4,8 @@ use vector_core::internal_event::InternalEvent;
 
 use vector_common::internal_event::{error_stage, error_type};
 
+use crate::checkpoint::CheckpointStoreError;
+
 #[derive(Debug)]
 pub struct JournaldInvalidRecordError {
     pub error: serde_json::Error,
@@ -74,15 +76,13 @@ impl InternalEvent for JournaldReadError {
 
 #[derive(Debug)]
 pub struct JournaldCheckpointSetError {
-    pub error: std::io::Error,
-    pub filename: String,
+    pub error: CheckpointStoreError,
 }
 
 impl InternalEvent for JournaldCheckpointSetError {
     fn emit(self) {
         error!(
             message = "Could not set journald checkpoint.",
-            filename = ?self.filename,
             error = %self.error,
             stage = error_stage::PROCESSING,
             error_type = error_type::IO_FAILED,
@@ -96,16 +96,14 @@ impl InternalEvent for JournaldCheckpointSetError {
 }
 
 #[derive(Debug)]
-pub struct JournaldCheckpointFileOpenError {
-    pub error: std::io::Error,
-    pub path: String,
+pub struct JournaldCheckpointLoadError {
+    pub error: CheckpointStoreError,
 }
 
-impl InternalEvent for JournaldCheckpointFileOpenError {
+impl InternalEvent for JournaldCheckpointLoadError {
     fn emit(self) {
         error!(
-            message = "Unable to open checkpoint file.",
-            path = ?self.path,
+            message = "Unable to load journald checkpoint.",
             error = %self.error,
             stage = error_stage::RECEIVING,
             error_type = error_type::IO_FAILED,
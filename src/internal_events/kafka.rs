@@ -109,6 +109,66 @@ impl InternalEvent for KafkaReadError {
     }
 }
 
+#[derive(Debug)]
+pub struct KafkaPartitionsPaused {
+    pub count: usize,
+}
+
+impl InternalEvent for KafkaPartitionsPaused {
+    fn emit(self) {
+        debug!(
+            message = "Paused fetching from partitions due to downstream backpressure.",
+            count = %self.count,
+        );
+        counter!("kafka_consumer_pauses_total", 1);
+    }
+
+    fn name(&self) -> Option<&'static str> {
+        Some("KafkaPartitionsPaused")
+    }
+}
+
+#[derive(Debug)]
+pub struct KafkaPartitionsResumed {
+    pub count: usize,
+}
+
+impl InternalEvent for KafkaPartitionsResumed {
+    fn emit(self) {
+        debug!(
+            message = "Resumed fetching from partitions.",
+            count = %self.count,
+        );
+    }
+
+    fn name(&self) -> Option<&'static str> {
+        Some("KafkaPartitionsResumed")
+    }
+}
+
+#[derive(Debug)]
+pub struct KafkaPartitionPauseError {
+    pub error: rdkafka::error::KafkaError,
+}
+
+impl InternalEvent for KafkaPartitionPauseError {
+    fn emit(self) {
+        error!(
+            message = "Failed to pause or resume partitions.",
+            error = %self.error,
+            error_code = "kafka_partition_pause",
+            error_type = error_type::READER_FAILED,
+            stage = error_stage::RECEIVING,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "kafka_partition_pause",
+            "error_type" => error_type::READER_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
+
 #[derive(Debug)]
 pub struct KafkaStatisticsReceived<'a> {
     pub statistics: &'a rdkafka::Statistics,
@@ -173,3 +233,27 @@ impl InternalEvent for KafkaHeaderExtractionError<'_> {
         counter!("kafka_header_extraction_failures_total", 1);
     }
 }
+
+#[derive(Debug)]
+pub struct KafkaPartitionExtractionError<'a> {
+    pub partition_field: &'a str,
+}
+
+impl InternalEvent for KafkaPartitionExtractionError<'_> {
+    fn emit(self) {
+        error!(
+            message = "Failed to extract partition. Value should be a non-negative integer.",
+            error_code = "extracting_partition",
+            error_type = error_type::PARSER_FAILED,
+            stage = error_stage::PROCESSING,
+            partition_field = self.partition_field,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "extracting_partition",
+            "error_type" => error_type::PARSER_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+    }
+}
@@ -0,0 +1,27 @@
+use metrics::counter;
+use vector_common::internal_event::{error_stage, error_type};
+use vector_core::internal_event::InternalEvent;
+
+use crate::sinks::logstash::LogstashError;
+
+#[derive(Debug)]
+pub struct LogstashSendError {
+    pub error: LogstashError,
+}
+
+impl InternalEvent for LogstashSendError {
+    fn emit(self) {
+        error!(
+            message = "Unable to send events to Logstash.",
+            error = %self.error,
+            error_type = error_type::WRITER_FAILED,
+            stage = error_stage::SENDING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::WRITER_FAILED,
+            "stage" => error_stage::SENDING,
+        );
+    }
+}
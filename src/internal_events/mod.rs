@@ -1,5 +1,6 @@
 pub mod prelude;
 
+mod adaptive_batching;
 mod adaptive_concurrency;
 mod aggregate;
 #[cfg(any(feature = "sources-amqp", feature = "sinks-amqp"))]
@@ -8,6 +9,8 @@ mod amqp;
 mod apache_metrics;
 #[cfg(feature = "api")]
 mod api;
+#[cfg(all(unix, feature = "sources-auditd"))]
+mod auditd;
 #[cfg(feature = "aws-core")]
 mod aws;
 #[cfg(feature = "sinks-aws_cloudwatch_logs")]
@@ -21,22 +24,44 @@ mod aws_kinesis_firehose;
 #[cfg(any(feature = "sources-aws_s3", feature = "sources-aws_sqs",))]
 mod aws_sqs;
 mod batch;
+#[cfg(feature = "sinks-blackhole")]
+mod blackhole;
+#[cfg(feature = "checkpoint-stores")]
+mod checkpoint;
+#[cfg(feature = "sinks-clickhouse")]
+mod clickhouse;
+#[cfg(feature = "sources-clock")]
+mod clock;
 mod codecs;
 mod common;
+#[cfg(feature = "transforms-compression")]
+mod compression;
 mod conditions;
+mod config_provider;
+#[cfg(feature = "transforms-content_dedupe")]
+mod content_dedupe;
+#[cfg(feature = "coordination")]
+mod coordination;
 #[cfg(feature = "sinks-datadog_metrics")]
 mod datadog_metrics;
 #[cfg(feature = "sinks-datadog_traces")]
 mod datadog_traces;
 #[cfg(feature = "transforms-dedupe")]
 mod dedupe;
+#[cfg(feature = "transforms-delay")]
+mod delay;
+mod delivery_schedule;
 #[cfg(feature = "sources-demo_logs")]
 mod demo_logs;
 #[cfg(feature = "sources-dnstap")]
 mod dnstap;
 #[cfg(feature = "sources-docker_logs")]
 mod docker_logs;
+#[cfg(feature = "sinks-elasticsearch")]
+mod elasticsearch;
 mod encoding_transcode;
+mod enrichment_tables;
+mod event_tracing;
 #[cfg(feature = "sources-eventstoredb_metrics")]
 mod eventstoredb_metrics;
 #[cfg(feature = "sources-exec")]
@@ -45,11 +70,16 @@ mod exec;
 mod filter;
 #[cfg(feature = "sources-fluent")]
 mod fluent;
+#[cfg(feature = "sources-gcp_cloud_logging")]
+mod gcp_cloud_logging;
 #[cfg(feature = "sources-gcp_pubsub")]
 mod gcp_pubsub;
 #[cfg(feature = "transforms-geoip")]
 mod geoip;
+#[cfg(feature = "sources-grpc_server")]
+mod grpc_server;
 mod heartbeat;
+mod host_metadata;
 #[cfg(feature = "sources-host_metrics")]
 mod host_metrics;
 mod http;
@@ -58,18 +88,24 @@ pub mod http_client;
 mod http_scrape;
 #[cfg(feature = "sinks-influxdb")]
 mod influxdb;
+mod ingestion_limit;
+mod internal_events_audit;
 #[cfg(feature = "sources-internal_logs")]
 mod internal_logs;
 #[cfg(feature = "sources-internal_metrics")]
 mod internal_metrics;
 #[cfg(all(unix, feature = "sources-journald"))]
 mod journald;
+#[cfg(all(unix, feature = "sinks-journald"))]
+mod journald_sink;
 #[cfg(any(feature = "sources-kafka", feature = "sinks-kafka"))]
 mod kafka;
 #[cfg(feature = "sources-kubernetes_logs")]
 mod kubernetes_logs;
 mod log_to_metric;
 mod logplex;
+#[cfg(feature = "sinks-logstash")]
+mod logstash_sink;
 #[cfg(feature = "sinks-loki")]
 mod loki;
 #[cfg(feature = "transforms-lua")]
@@ -80,10 +116,15 @@ mod metric_to_log;
 mod mongodb_metrics;
 #[cfg(feature = "sinks-nats")]
 mod nats;
+#[cfg(feature = "sources-netflow")]
+mod netflow;
 #[cfg(feature = "sources-nginx_metrics")]
 mod nginx_metrics;
 mod open;
+mod oversized_event;
 mod parser;
+#[cfg(feature = "sources-pcap")]
+mod pcap;
 #[cfg(feature = "sources-postgresql_metrics")]
 mod postgresql_metrics;
 mod process;
@@ -97,8 +138,16 @@ mod redis;
 mod reduce;
 mod remap;
 mod sample;
+#[cfg(feature = "transforms-schema_map")]
+mod schema_map;
 #[cfg(feature = "sinks-sematext")]
 mod sematext_metrics;
+#[cfg(feature = "sources-sflow")]
+mod sflow;
+#[cfg(feature = "transforms-sigma")]
+mod sigma;
+#[cfg(feature = "sources-snmp")]
+mod snmp;
 mod socket;
 #[cfg(any(feature = "sources-splunk_hec", feature = "sinks-splunk_hec"))]
 mod splunk_hec;
@@ -114,10 +163,16 @@ mod tcp;
 mod template;
 #[cfg(feature = "transforms-throttle")]
 mod throttle;
+#[cfg(feature = "transforms-time_skew")]
+mod time_skew;
 mod udp;
 mod unix;
+#[cfg(feature = "sources-vector")]
+mod vector_source;
 #[cfg(feature = "sinks-websocket")]
 mod websocket;
+#[cfg(feature = "sources-windows_perfcounters")]
+mod windows_perfcounters;
 
 #[cfg(any(
     feature = "sources-file",
@@ -138,6 +193,8 @@ pub(crate) use self::amqp::*;
 pub(crate) use self::apache_metrics::*;
 #[cfg(feature = "api")]
 pub(crate) use self::api::*;
+#[cfg(all(unix, feature = "sources-auditd"))]
+pub(crate) use self::auditd::*;
 #[cfg(feature = "aws-core")]
 pub(crate) use self::aws::*;
 #[cfg(feature = "sinks-aws_cloudwatch_logs")]
@@ -150,19 +207,38 @@ pub(crate) use self::aws_ecs_metrics::*;
 pub(crate) use self::aws_kinesis_firehose::*;
 #[cfg(any(feature = "sources-aws_s3", feature = "sources-aws_sqs",))]
 pub(crate) use self::aws_sqs::*;
+#[cfg(feature = "sinks-blackhole")]
+pub(crate) use self::blackhole::*;
+#[cfg(feature = "checkpoint-stores")]
+pub(crate) use self::checkpoint::*;
+#[cfg(feature = "sinks-clickhouse")]
+pub(crate) use self::clickhouse::*;
+#[cfg(feature = "sources-clock")]
+pub(crate) use self::clock::*;
 pub(crate) use self::codecs::*;
+#[cfg(feature = "transforms-compression")]
+pub(crate) use self::compression::*;
+#[cfg(feature = "transforms-content_dedupe")]
+pub(crate) use self::content_dedupe::*;
+#[cfg(feature = "coordination")]
+pub(crate) use self::coordination::*;
 #[cfg(feature = "sinks-datadog_metrics")]
 pub(crate) use self::datadog_metrics::*;
 #[cfg(feature = "sinks-datadog_traces")]
 pub(crate) use self::datadog_traces::*;
 #[cfg(feature = "transforms-dedupe")]
 pub(crate) use self::dedupe::*;
+#[cfg(feature = "transforms-delay")]
+pub(crate) use self::delay::*;
+pub(crate) use self::delivery_schedule::*;
 #[cfg(feature = "sources-demo_logs")]
 pub(crate) use self::demo_logs::*;
 #[cfg(feature = "sources-dnstap")]
 pub(crate) use self::dnstap::*;
 #[cfg(feature = "sources-docker_logs")]
 pub(crate) use self::docker_logs::*;
+#[cfg(feature = "sinks-elasticsearch")]
+pub(crate) use self::elasticsearch::*;
 #[cfg(feature = "sources-eventstoredb_metrics")]
 pub(crate) use self::eventstoredb_metrics::*;
 #[cfg(feature = "sources-exec")]
@@ -177,10 +253,14 @@ pub(crate) use self::file::*;
 pub(crate) use self::filter::*;
 #[cfg(feature = "sources-fluent")]
 pub(crate) use self::fluent::*;
+#[cfg(feature = "sources-gcp_cloud_logging")]
+pub(crate) use self::gcp_cloud_logging::*;
 #[cfg(feature = "sources-gcp_pubsub")]
 pub(crate) use self::gcp_pubsub::*;
 #[cfg(feature = "transforms-geoip")]
 pub(crate) use self::geoip::*;
+#[cfg(feature = "sources-grpc_server")]
+pub(crate) use self::grpc_server::*;
 #[cfg(feature = "sources-host_metrics")]
 pub(crate) use self::host_metrics::*;
 #[cfg(any(
@@ -194,12 +274,15 @@ pub(crate) use self::http::*;
 pub(crate) use self::http_scrape::*;
 #[cfg(feature = "sinks-influxdb")]
 pub(crate) use self::influxdb::*;
+pub(crate) use self::ingestion_limit::*;
 #[cfg(feature = "sources-internal_logs")]
 pub(crate) use self::internal_logs::*;
 #[cfg(feature = "sources-internal_metrics")]
 pub(crate) use self::internal_metrics::*;
 #[cfg(all(unix, feature = "sources-journald"))]
 pub(crate) use self::journald::*;
+#[cfg(all(unix, feature = "sinks-journald"))]
+pub(crate) use self::journald_sink::*;
 #[cfg(any(feature = "sources-kafka", feature = "sinks-kafka"))]
 pub(crate) use self::kafka::*;
 #[cfg(feature = "sources-kubernetes_logs")]
@@ -207,6 +290,8 @@ pub(crate) use self::kubernetes_logs::*;
 pub(crate) use self::log_to_metric::*;
 #[cfg(feature = "sources-heroku_logs")]
 pub(crate) use self::logplex::*;
+#[cfg(feature = "sinks-logstash")]
+pub(crate) use self::logstash_sink::*;
 #[cfg(feature = "sinks-loki")]
 pub(crate) use self::loki::*;
 #[cfg(feature = "transforms-lua")]
@@ -215,9 +300,14 @@ pub(crate) use self::lua::*;
 pub(crate) use self::metric_to_log::*;
 #[cfg(feature = "sinks-nats")]
 pub(crate) use self::nats::*;
+#[cfg(feature = "sources-netflow")]
+pub(crate) use self::netflow::*;
 #[cfg(feature = "sources-nginx_metrics")]
 pub(crate) use self::nginx_metrics::*;
+pub(crate) use self::oversized_event::*;
 pub(crate) use self::parser::*;
+#[cfg(feature = "sources-pcap")]
+pub(crate) use self::pcap::*;
 #[cfg(feature = "sources-postgresql_metrics")]
 pub(crate) use self::postgresql_metrics::*;
 #[cfg(any(feature = "sources-prometheus", feature = "sinks-prometheus"))]
@@ -232,8 +322,16 @@ pub(crate) use self::reduce::*;
 pub(crate) use self::remap::*;
 #[cfg(feature = "transforms-sample")]
 pub(crate) use self::sample::*;
+#[cfg(feature = "transforms-schema_map")]
+pub(crate) use self::schema_map::*;
 #[cfg(feature = "sinks-sematext")]
 pub(crate) use self::sematext_metrics::*;
+#[cfg(feature = "sources-sflow")]
+pub(crate) use self::sflow::*;
+#[cfg(feature = "transforms-sigma")]
+pub(crate) use self::sigma::*;
+#[cfg(feature = "sources-snmp")]
+pub(crate) use self::snmp::*;
 #[cfg(any(feature = "sources-splunk_hec", feature = "sinks-splunk_hec"))]
 pub(crate) use self::splunk_hec::*;
 #[cfg(feature = "sinks-statsd")]
@@ -246,10 +344,13 @@ pub(crate) use self::syslog::*;
 pub(crate) use self::tag_cardinality_limit::*;
 #[cfg(feature = "transforms-throttle")]
 pub(crate) use self::throttle::*;
+#[cfg(feature = "transforms-time_skew")]
+pub(crate) use self::time_skew::*;
 #[cfg(all(
     any(
         feature = "sinks-socket",
         feature = "sinks-statsd",
+        feature = "sources-auditd",
         feature = "sources-dnstap",
         feature = "sources-metrics",
         feature = "sources-statsd",
@@ -259,13 +360,19 @@ pub(crate) use self::throttle::*;
     unix
 ))]
 pub(crate) use self::unix::*;
+#[cfg(feature = "sources-vector")]
+pub(crate) use self::vector_source::*;
 #[cfg(feature = "sinks-websocket")]
 pub(crate) use self::websocket::*;
 #[cfg(windows)]
 pub(crate) use self::windows::*;
+#[cfg(feature = "sources-windows_perfcounters")]
+pub(crate) use self::windows_perfcounters::*;
 pub(crate) use self::{
-    adaptive_concurrency::*, batch::*, common::*, conditions::*, encoding_transcode::*,
-    heartbeat::*, open::*, process::*, socket::*, tcp::*, template::*, udp::*,
+    adaptive_batching::*, adaptive_concurrency::*, batch::*, common::*, conditions::*,
+    config_provider::*, encoding_transcode::*, enrichment_tables::*, event_tracing::*,
+    heartbeat::*, host_metadata::*, internal_events_audit::*, open::*, process::*, socket::*,
+    tcp::*, template::*, udp::*,
 };
 
 // this version won't be needed once all `InternalEvent`s implement `name()`
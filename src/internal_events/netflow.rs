@@ -0,0 +1,100 @@
+use std::net::SocketAddr;
+
+use metrics::counter;
+use vector_common::internal_event::{error_stage, error_type};
+use vector_core::internal_event::InternalEvent;
+
+use crate::sources::netflow::parser::ParseError;
+
+#[derive(Debug)]
+pub struct NetflowBindError {
+    pub address: SocketAddr,
+    pub error: std::io::Error,
+}
+
+impl InternalEvent for NetflowBindError {
+    fn emit(self) {
+        error!(
+            message = "Failed to bind to UDP listener socket.",
+            address = %self.address,
+            error = %self.error,
+            error_code = "failed_udp_binding",
+            error_type = error_type::CONNECTION_FAILED,
+            stage = error_stage::RECEIVING,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "failed_udp_binding",
+            "error_type" => error_type::CONNECTION_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct NetflowReceiveError {
+    pub error: std::io::Error,
+}
+
+impl InternalEvent for NetflowReceiveError {
+    fn emit(self) {
+        error!(
+            message = "Failed to read UDP datagram.",
+            error = %self.error,
+            error_code = "failed_udp_datagram",
+            error_type = error_type::CONNECTION_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "failed_udp_datagram",
+            "error_type" => error_type::CONNECTION_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct NetflowParseError<'a> {
+    pub exporter: SocketAddr,
+    pub error: &'a ParseError,
+}
+
+impl<'a> InternalEvent for NetflowParseError<'a> {
+    fn emit(self) {
+        error!(
+            message = "Invalid flow datagram, discarding.",
+            exporter = %self.exporter,
+            error = %self.error,
+            error_code = "invalid_datagram",
+            error_type = error_type::PARSER_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "invalid_datagram",
+            "error_type" => error_type::PARSER_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct NetflowTemplatesMissing {
+    pub exporter: SocketAddr,
+    pub count: usize,
+}
+
+impl InternalEvent for NetflowTemplatesMissing {
+    fn emit(self) {
+        debug!(
+            message = "Skipped data sets with unknown templates.",
+            exporter = %self.exporter,
+            count = %self.count,
+            internal_log_rate_limit = true,
+        );
+        counter!("netflow_missing_template_sets_total", self.count as u64);
+    }
+}
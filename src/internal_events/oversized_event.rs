@@ -0,0 +1,76 @@
+use metrics::counter;
+use vector_common::internal_event::{error_stage, error_type};
+use vector_core::internal_event::InternalEvent;
+
+use crate::{
+    emit,
+    internal_events::{ComponentEventsDropped, UNINTENTIONAL},
+};
+
+#[derive(Debug)]
+pub struct OversizedEventDropped {
+    pub byte_size: usize,
+    pub max_bytes: usize,
+}
+
+impl InternalEvent for OversizedEventDropped {
+    fn emit(self) {
+        let reason = "Event exceeds the maximum event size.";
+        error!(
+            message = reason,
+            byte_size = %self.byte_size,
+            max_bytes = %self.max_bytes,
+            error_type = error_type::CONDITION_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::CONDITION_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+        emit!(ComponentEventsDropped::<UNINTENTIONAL> { count: 1, reason });
+    }
+}
+
+#[derive(Debug)]
+pub struct OversizedEventTruncated {
+    pub byte_size: usize,
+    pub max_bytes: usize,
+}
+
+impl InternalEvent for OversizedEventTruncated {
+    fn emit(self) {
+        warn!(
+            message = "Truncated event exceeding the maximum event size.",
+            byte_size = %self.byte_size,
+            max_bytes = %self.max_bytes,
+            internal_log_rate_limit = true,
+        );
+        counter!("oversized_events_truncated_total", 1);
+    }
+}
+
+#[derive(Debug)]
+pub struct OversizedEventRouted {
+    pub byte_size: usize,
+    pub max_bytes: usize,
+}
+
+impl InternalEvent for OversizedEventRouted {
+    fn emit(self) {
+        error!(
+            message = "Event exceeds the maximum event size, sending it to the errors output.",
+            byte_size = %self.byte_size,
+            max_bytes = %self.max_bytes,
+            error_type = error_type::CONDITION_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::CONDITION_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+    }
+}
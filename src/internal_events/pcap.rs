@@ -0,0 +1,42 @@
+use metrics::counter;
+use vector_common::internal_event::{error_stage, error_type};
+use vector_core::internal_event::InternalEvent;
+
+#[derive(Debug)]
+pub struct PcapCaptureError {
+    pub error: pcap::Error,
+}
+
+impl InternalEvent for PcapCaptureError {
+    fn emit(self) {
+        error!(
+            message = "Failed to read from packet capture.",
+            error = %self.error,
+            error_code = "capture_failed",
+            error_type = error_type::READER_FAILED,
+            stage = error_stage::RECEIVING,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "capture_failed",
+            "error_type" => error_type::READER_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct PcapPacketsDropped {
+    pub count: u64,
+}
+
+impl InternalEvent for PcapPacketsDropped {
+    fn emit(self) {
+        warn!(
+            message = "Packets were dropped by the kernel or the interface before being read.",
+            count = %self.count,
+            internal_log_rate_limit = true,
+        );
+        counter!("pcap_dropped_packets_total", self.count);
+    }
+}
@@ -9,10 +9,15 @@ use vector_common::internal_event::{error_stage, error_type};
 
 #[derive(Debug)]
 pub struct RemapMappingError {
+    /// The number of events the mapping failed with.
+    pub count: u64,
     /// If set to true, the remap transform has dropped the event after a failed
     /// mapping. This internal event will reflect that in its messaging.
     pub event_dropped: bool,
+    /// The error of the first failed mapping.
     pub error: String,
+    /// The errors of the failed mappings, one per event, up to a few of them.
+    pub error_samples: Vec<String>,
 }
 
 impl InternalEvent for RemapMappingError {
@@ -20,28 +25,32 @@ impl InternalEvent for RemapMappingError {
         error!(
             message = "Mapping failed with event.",
             error = ?self.error,
+            error_samples = ?self.error_samples,
+            count = self.count,
             error_type = error_type::CONVERSION_FAILED,
             stage = error_stage::PROCESSING,
             internal_log_rate_limit = true,
         );
         counter!(
-            "component_errors_total", 1,
+            "component_errors_total", self.count,
             "error_type" => error_type::CONVERSION_FAILED,
             "stage" => error_stage::PROCESSING,
         );
         if self.event_dropped {
             emit!(ComponentEventsDropped::<UNINTENTIONAL> {
-                count: 1,
+                count: self.count,
                 reason: "Mapping failed with event.",
             });
         }
         // deprecated
-        counter!("processing_errors_total", 1);
+        counter!("processing_errors_total", self.count);
     }
 }
 
 #[derive(Debug)]
 pub struct RemapMappingAbort {
+    /// The number of events whose mapping was aborted.
+    pub count: u64,
     /// If set to true, the remap transform has dropped the event after an abort
     /// during mapping. This internal event will reflect that in its messaging.
     pub event_dropped: bool,
@@ -51,14 +60,37 @@ impl InternalEvent for RemapMappingAbort {
     fn emit(self) {
         debug!(
             message = "Event mapping aborted.",
+            count = self.count,
             internal_log_rate_limit = true
         );
 
         if self.event_dropped {
             emit!(ComponentEventsDropped::<UNINTENTIONAL> {
-                count: 1,
+                count: self.count,
                 reason: "Event mapping aborted.",
             });
         }
     }
 }
+
+#[derive(Debug)]
+pub struct RemapStateCheckpointError {
+    pub error: String,
+}
+
+impl InternalEvent for RemapStateCheckpointError {
+    fn emit(self) {
+        error!(
+            message = "Failed to checkpoint the state.",
+            error = %self.error,
+            error_type = error_type::WRITER_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::WRITER_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+    }
+}
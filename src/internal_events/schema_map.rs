@@ -0,0 +1,76 @@
+use std::path::Path;
+
+use metrics::counter;
+use vector_common::{
+    conversion,
+    internal_event::{error_stage, error_type},
+};
+use vector_core::internal_event::InternalEvent;
+
+#[derive(Debug)]
+pub struct SchemaMapConversionError<'a> {
+    pub field: &'a str,
+    pub error: conversion::Error,
+}
+
+impl<'a> InternalEvent for SchemaMapConversionError<'a> {
+    fn emit(self) {
+        error!(
+            message = "Failed to coerce field, keeping its original value.",
+            field = self.field,
+            error = %self.error,
+            error_code = "failed_coercing_field",
+            error_type = error_type::CONVERSION_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "failed_coercing_field",
+            "error_type" => error_type::CONVERSION_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct SchemaMapReloaded<'a> {
+    pub path: &'a Path,
+    pub rules: usize,
+}
+
+impl<'a> InternalEvent for SchemaMapReloaded<'a> {
+    fn emit(self) {
+        info!(
+            message = "Reloaded mapping file.",
+            path = %self.path.display(),
+            rules = self.rules,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct SchemaMapReloadError<'a> {
+    pub path: &'a Path,
+    pub error: crate::Error,
+}
+
+impl<'a> InternalEvent for SchemaMapReloadError<'a> {
+    fn emit(self) {
+        error!(
+            message = "Failed to reload mapping file, keeping the previous rules.",
+            path = %self.path.display(),
+            error = %self.error,
+            error_code = "failed_reloading_mapping",
+            error_type = error_type::CONFIGURATION_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "failed_reloading_mapping",
+            "error_type" => error_type::CONFIGURATION_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+    }
+}
@@ -0,0 +1,82 @@
+use std::net::SocketAddr;
+
+use metrics::counter;
+use vector_common::internal_event::{error_stage, error_type};
+use vector_core::internal_event::InternalEvent;
+
+use crate::sources::sflow::parser::ParseError;
+
+#[derive(Debug)]
+pub struct SflowBindError {
+    pub address: SocketAddr,
+    pub error: std::io::Error,
+}
+
+impl InternalEvent for SflowBindError {
+    fn emit(self) {
+        error!(
+            message = "Failed to bind to UDP listener socket.",
+            address = %self.address,
+            error = %self.error,
+            error_code = "failed_udp_binding",
+            error_type = error_type::CONNECTION_FAILED,
+            stage = error_stage::RECEIVING,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "failed_udp_binding",
+            "error_type" => error_type::CONNECTION_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct SflowReceiveError {
+    pub error: std::io::Error,
+}
+
+impl InternalEvent for SflowReceiveError {
+    fn emit(self) {
+        error!(
+            message = "Failed to read UDP datagram.",
+            error = %self.error,
+            error_code = "failed_udp_datagram",
+            error_type = error_type::CONNECTION_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "failed_udp_datagram",
+            "error_type" => error_type::CONNECTION_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct SflowParseError<'a> {
+    pub agent: SocketAddr,
+    pub error: &'a ParseError,
+}
+
+impl<'a> InternalEvent for SflowParseError<'a> {
+    fn emit(self) {
+        error!(
+            message = "Invalid sFlow datagram, discarding.",
+            agent = %self.agent,
+            error = %self.error,
+            error_code = "invalid_datagram",
+            error_type = error_type::PARSER_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "invalid_datagram",
+            "error_type" => error_type::PARSER_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+    }
+}
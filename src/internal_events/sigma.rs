@@ -0,0 +1,19 @@
+use std::path::Path;
+
+use vector_core::internal_event::InternalEvent;
+
+#[derive(Debug)]
+pub struct SigmaRuleSkipped<'a> {
+    pub path: &'a Path,
+    pub error: &'a str,
+}
+
+impl<'a> InternalEvent for SigmaRuleSkipped<'a> {
+    fn emit(self) {
+        warn!(
+            message = "Skipping Sigma rule that can't be loaded.",
+            path = %self.path.display(),
+            error = %self.error,
+        );
+    }
+}
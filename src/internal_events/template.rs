@@ -50,3 +50,21 @@ impl<'a> InternalEvent for TemplateRenderingError<'a> {
         }
     }
 }
+
+#[derive(Debug)]
+pub struct TemplateRenderingFallback<'a> {
+    pub template: &'a str,
+    pub error: &'a crate::template::TemplateRenderingError,
+}
+
+impl<'a> InternalEvent for TemplateRenderingFallback<'a> {
+    fn emit(self) {
+        warn!(
+            message = "Failed to render template, using its fallback value.",
+            template = %self.template,
+            error = %self.error,
+            internal_log_rate_limit = true,
+        );
+        counter!("template_render_fallbacks_total", 1);
+    }
+}
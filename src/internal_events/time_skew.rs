@@ -0,0 +1,24 @@
+use crate::{
+    emit,
+    internal_events::{ComponentEventsDropped, INTENTIONAL},
+};
+use vector_core::internal_event::InternalEvent;
+
+#[derive(Debug)]
+pub struct TimeSkewEventDropped {
+    pub skew: &'static str,
+}
+
+impl InternalEvent for TimeSkewEventDropped {
+    fn emit(self) {
+        debug!(
+            message = "Event timestamp is outside of the tolerances.",
+            skew = self.skew,
+            internal_log_rate_limit = true,
+        );
+        emit!(ComponentEventsDropped::<INTENTIONAL> {
+            count: 1,
+            reason: "Event timestamp is outside of the tolerances.",
+        });
+    }
+}
@@ -1,4 +1,4 @@
-use metrics::counter;
+use metrics::{counter, gauge};
 use vector_core::internal_event::InternalEvent;
 
 use vector_common::internal_event::{error_stage, error_type};
@@ -87,3 +87,40 @@ impl InternalEvent for UdpSendIncompleteError {
         counter!("connection_send_errors_total", 1, "mode" => "udp");
     }
 }
+
+#[derive(Debug)]
+pub struct UdpReceiveBufferSize {
+    pub requested: Option<usize>,
+    pub size: usize,
+}
+
+impl InternalEvent for UdpReceiveBufferSize {
+    fn emit(self) {
+        let size = self.size;
+        if let Some(requested) = self.requested.filter(|&requested| size < requested) {
+            warn!(
+                message = "UDP socket receive buffer is smaller than configured.",
+                requested,
+                size,
+                help = "Raise `net.core.rmem_max`, or grant the `CAP_NET_ADMIN` capability.",
+            );
+        }
+        gauge!("udp_receive_buffer_bytes", size as f64);
+    }
+}
+
+#[derive(Debug)]
+pub struct UdpReceiveDrops {
+    pub count: u64,
+}
+
+impl InternalEvent for UdpReceiveDrops {
+    fn emit(self) {
+        warn!(
+            message = "Datagrams dropped by the operating system because the UDP socket receive buffer was full.",
+            count = self.count,
+            internal_log_rate_limit = true,
+        );
+        counter!("udp_receive_buffer_drops_total", self.count);
+    }
+}
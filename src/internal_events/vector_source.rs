@@ -0,0 +1,79 @@
+use std::net::SocketAddr;
+
+use metrics::counter;
+use vector_core::internal_event::InternalEvent;
+
+use vector_common::internal_event::{error_stage, error_type};
+
+#[derive(Debug)]
+pub struct VectorClientEventsReceived<'a> {
+    pub client: &'a str,
+    pub count: usize,
+    pub byte_size: usize,
+}
+
+impl<'a> InternalEvent for VectorClientEventsReceived<'a> {
+    fn emit(self) {
+        trace!(
+            message = "Events received from client.",
+            client = %self.client,
+            count = %self.count,
+            byte_size = %self.byte_size,
+        );
+        counter!(
+            "client_received_events_total", self.count as u64,
+            "client" => self.client.to_owned(),
+        );
+        counter!(
+            "client_received_bytes_total", self.byte_size as u64,
+            "client" => self.client.to_owned(),
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct VectorClientUnauthenticatedError {
+    pub peer_addr: Option<SocketAddr>,
+}
+
+const UNAUTHENTICATED: &str = "unauthenticated";
+
+impl InternalEvent for VectorClientUnauthenticatedError {
+    fn emit(self) {
+        error!(
+            message = "Rejected request from unauthenticated client.",
+            peer_addr = ?self.peer_addr,
+            error_code = UNAUTHENTICATED,
+            error_type = error_type::REQUEST_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => UNAUTHENTICATED,
+            "error_type" => error_type::REQUEST_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct VectorClientRateLimited<'a> {
+    pub client: &'a str,
+    pub count: usize,
+}
+
+impl<'a> InternalEvent for VectorClientRateLimited<'a> {
+    fn emit(self) {
+        warn!(
+            message = "Client exceeded its rate limit, rejecting request.",
+            client = %self.client,
+            count = %self.count,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "client_rate_limited_requests_total", 1,
+            "client" => self.client.to_owned(),
+        );
+    }
+}
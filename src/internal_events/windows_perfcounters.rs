@@ -0,0 +1,29 @@
+use metrics::counter;
+use vector_core::internal_event::InternalEvent;
+
+use vector_common::internal_event::{error_stage, error_type};
+
+#[derive(Debug)]
+pub struct WindowsPerfcountersCollectError<'a, E> {
+    pub counter: Option<&'a str>,
+    pub error: E,
+}
+
+impl<'a, E: std::fmt::Display> InternalEvent for WindowsPerfcountersCollectError<'a, E> {
+    fn emit(self) {
+        error!(
+            message = "Failed to collect performance counters.",
+            counter = self.counter,
+            error = %self.error,
+            error_type = error_type::READER_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::READER_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
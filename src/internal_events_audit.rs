@@ -0,0 +1,293 @@
+//! Audit of the internal events emitted by components, enabled by the `internal_events_audit`
+//! feature flag.
+//!
+//! The component specification (`docs/specs/component.md`) requires components to emit internal
+//! events for the events flowing through them. The audit periodically captures the internal
+//! metrics, and checks that the counters of the required events of each component moved along
+//! with its events.
+
+use std::{
+    collections::{BTreeMap, HashSet},
+    time::Duration,
+};
+
+use tokio::time::interval;
+
+use crate::{
+    event::{Metric, MetricValue},
+    internal_events::InternalEventsAuditViolation,
+    metrics::Controller,
+};
+
+const AUDIT_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Audits the internal events of the components every 30 seconds.
+pub async fn audit() {
+    let controller = match Controller::get() {
+        Ok(controller) => controller,
+        Err(error) => {
+            warn!(message = "Internal events audit is disabled.", %error);
+            return;
+        }
+    };
+
+    info!(message = "Auditing the internal events of components.");
+    let mut audit = Audit::default();
+    let mut interval = interval(AUDIT_INTERVAL);
+    loop {
+        interval.tick().await;
+        for violation in audit.check(&controller.capture_metrics()) {
+            emit!(InternalEventsAuditViolation {
+                component_kind: &violation.component.kind,
+                component_id: &violation.component.id,
+                component_type: &violation.component.component_type,
+                missing_event: violation.missing_event,
+                reason: violation.reason,
+            });
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+struct Component {
+    kind: String,
+    id: String,
+    component_type: String,
+}
+
+impl Component {
+    fn from_metric(metric: &Metric) -> Option<Self> {
+        Some(Self {
+            kind: metric.tag_value("component_kind")?,
+            id: metric.tag_value("component_id")?,
+            component_type: metric.tag_value("component_type")?,
+        })
+    }
+}
+
+/// The totals of the counters of the internal events required from a component.
+#[derive(Debug, Default)]
+struct Counters {
+    received_events: f64,
+    received_bytes: f64,
+    sent_events: f64,
+    sent_bytes: f64,
+    errors: f64,
+    discarded_events: f64,
+    unintentionally_discarded_events: f64,
+}
+
+impl Counters {
+    fn add(&mut self, metric: &Metric) {
+        let value = match metric.value() {
+            MetricValue::Counter { value } => *value,
+            _ => return,
+        };
+        match metric.name() {
+            "component_received_events_total" => self.received_events += value,
+            "component_received_bytes_total" => self.received_bytes += value,
+            "component_sent_events_total" => self.sent_events += value,
+            "component_sent_bytes_total" => self.sent_bytes += value,
+            "component_errors_total" => self.errors += value,
+            "component_discarded_events_total" => {
+                self.discarded_events += value;
+                if metric.tag_matches("intentional", "false") {
+                    self.unintentionally_discarded_events += value;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Gets the internal events missing from a component of the given kind, along with the
+    /// reason they are required.
+    fn missing_events(&self, kind: &str) -> Vec<(&'static str, &'static str)> {
+        let mut missing = Vec::new();
+        if kind == "source" && self.sent_events > 0.0 && self.received_events == 0.0 {
+            missing.push((
+                "ComponentEventsReceived",
+                "events were sent without being received",
+            ));
+        }
+        if kind == "source" && self.received_events > 0.0 && self.received_bytes == 0.0 {
+            missing.push((
+                "ComponentBytesReceived",
+                "events were received without the bytes they were decoded from",
+            ));
+        }
+        if self.received_events > 0.0
+            && self.sent_events == 0.0
+            && self.discarded_events == 0.0
+            && self.errors == 0.0
+        {
+            missing.push((
+                "ComponentEventsSent",
+                "events were received, but none were sent, dropped, or failed",
+            ));
+        }
+        if kind == "sink" && self.sent_events > 0.0 && self.sent_bytes == 0.0 {
+            missing.push((
+                "ComponentBytesSent",
+                "events were sent without the bytes they were encoded to",
+            ));
+        }
+        if self.unintentionally_discarded_events > 0.0 && self.errors == 0.0 {
+            missing.push((
+                "ComponentError",
+                "events were unintentionally dropped without an error",
+            ));
+        }
+        missing
+    }
+}
+
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+struct Violation {
+    component: Component,
+    missing_event: &'static str,
+    reason: &'static str,
+}
+
+/// The state of the audit between two checks.
+#[derive(Default)]
+struct Audit {
+    /// The violations found by the last check, which are only reported when they are still found
+    /// by the next one, as the required events may lag behind the events of the component, e.g.
+    /// the events sent by a sink once its batch is full.
+    pending: HashSet<Violation>,
+    /// The violations already reported, which aren't reported again.
+    reported: HashSet<Violation>,
+}
+
+impl Audit {
+    /// Checks the captured internal metrics, returning the violations to report.
+    fn check(&mut self, metrics: &[Metric]) -> Vec<Violation> {
+        let mut components = BTreeMap::<Component, Counters>::new();
+        for metric in metrics {
+            if let Some(component) = Component::from_metric(metric) {
+                components.entry(component).or_default().add(metric);
+            }
+        }
+
+        let violations = components
+            .into_iter()
+            .flat_map(|(component, counters)| {
+                counters.missing_events(&component.kind).into_iter().map(
+                    move |(missing_event, reason)| Violation {
+                        component: component.clone(),
+                        missing_event,
+                        reason,
+                    },
+                )
+            })
+            .collect::<Vec<_>>();
+
+        let confirmed = violations
+            .iter()
+            .filter(|violation| {
+                self.pending.contains(*violation) && !self.reported.contains(*violation)
+            })
+            .cloned()
+            .collect::<Vec<_>>();
+        self.reported.extend(confirmed.iter().cloned());
+        self.pending = violations.into_iter().collect();
+        confirmed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::MetricKind;
+
+    fn counter(name: &str, kind: &str, id: &str, value: f64) -> Metric {
+        Metric::new(name, MetricKind::Absolute, MetricValue::Counter { value }).with_tags(Some(
+            [
+                ("component_kind", kind),
+                ("component_id", id),
+                ("component_type", "test"),
+            ]
+            .into_iter()
+            .map(|(key, value)| (key.to_owned(), value.to_owned()))
+            .collect(),
+        ))
+    }
+
+    fn missing_events(violations: &[Violation]) -> Vec<(&str, &'static str)> {
+        violations
+            .iter()
+            .map(|violation| (violation.component.id.as_str(), violation.missing_event))
+            .collect()
+    }
+
+    #[test]
+    fn reports_persistent_violations_once() {
+        let metrics = vec![
+            counter("component_received_events_total", "source", "in", 10.0),
+            counter("component_received_bytes_total", "source", "in", 100.0),
+            counter("component_sent_events_total", "source", "in", 10.0),
+            counter("component_received_events_total", "sink", "out", 10.0),
+        ];
+        let mut audit = Audit::default();
+
+        assert!(audit.check(&metrics).is_empty());
+        assert_eq!(
+            missing_events(&audit.check(&metrics)),
+            vec![("out", "ComponentEventsSent")]
+        );
+        assert!(audit.check(&metrics).is_empty());
+    }
+
+    #[test]
+    fn ignores_violations_fixed_by_the_next_check() {
+        let mut audit = Audit::default();
+        audit.check(&[counter(
+            "component_received_events_total",
+            "sink",
+            "out",
+            10.0,
+        )]);
+
+        let violations = audit.check(&[
+            counter("component_received_events_total", "sink", "out", 10.0),
+            counter("component_sent_events_total", "sink", "out", 10.0),
+            counter("component_sent_bytes_total", "sink", "out", 100.0),
+        ]);
+        assert!(violations.is_empty());
+    }
+
+    #[test]
+    fn checks_the_events_required_by_kind() {
+        let mut discarded = counter(
+            "component_discarded_events_total",
+            "transform",
+            "remap",
+            1.0,
+        );
+        discarded.insert_tag("intentional".to_owned(), "false".to_owned());
+        let metrics = vec![
+            counter("component_sent_events_total", "source", "in", 10.0),
+            counter(
+                "component_received_events_total",
+                "transform",
+                "remap",
+                10.0,
+            ),
+            counter("component_sent_events_total", "transform", "remap", 9.0),
+            discarded,
+            counter("component_received_events_total", "sink", "out", 9.0),
+            counter("component_sent_events_total", "sink", "out", 9.0),
+        ];
+        let mut audit = Audit::default();
+        audit.check(&metrics);
+
+        assert_eq!(
+            missing_events(&audit.check(&metrics)),
+            vec![
+                ("out", "ComponentBytesSent"),
+                ("in", "ComponentEventsReceived"),
+                ("remap", "ComponentError"),
+            ]
+        );
+    }
+}
@@ -49,16 +49,22 @@ pub mod app;
 pub mod async_read;
 #[cfg(feature = "aws-config")]
 pub mod aws;
+#[cfg(feature = "checkpoint-stores")]
+pub mod checkpoint;
 #[allow(unreachable_pub)]
 pub mod codecs;
 pub(crate) mod common;
+#[cfg(feature = "coordination")]
+pub mod coordination;
 pub mod encoding_transcode;
 pub mod enrichment_tables;
 #[cfg(feature = "gcp")]
 pub mod gcp;
 pub(crate) mod graph;
 pub mod heartbeat;
+pub mod host_metadata;
 pub mod http;
+pub mod internal_events_audit;
 #[allow(unreachable_pub)]
 #[cfg(any(feature = "sources-kafka", feature = "sinks-kafka"))]
 pub mod kafka;
@@ -70,6 +76,7 @@ pub mod list;
 pub(crate) mod nats;
 #[allow(unreachable_pub)]
 pub(crate) mod proto;
+pub mod protobuf_descriptors;
 pub mod providers;
 pub mod secrets;
 pub mod serde;
@@ -99,10 +106,13 @@ pub mod transforms;
 pub mod types;
 pub mod udp;
 pub mod unit_test;
+#[cfg(unix)]
+pub mod unix;
 pub(crate) mod utilization;
 pub mod validate;
 #[cfg(windows)]
 pub mod vector_windows;
+pub mod vrl_functions;
 
 pub use source_sender::SourceSender;
 pub use vector_common::{shutdown, Error, Result};
@@ -0,0 +1,96 @@
+//! The protobuf descriptor set of the `protobuf_descriptor_set` global option, which the
+//! `parse_proto` and `encode_proto` VRL functions resolve message types with.
+
+use std::path::Path;
+#[cfg(not(test))]
+use std::sync::RwLock;
+
+#[cfg(not(test))]
+use once_cell::sync::Lazy;
+use vrl::CompileConfig;
+use vrl_stdlib::ProtobufDescriptors;
+
+#[cfg(not(test))]
+static DESCRIPTORS: Lazy<RwLock<Option<ProtobufDescriptors>>> = Lazy::new(Default::default);
+
+#[cfg(test)]
+thread_local! {
+    // Tests get descriptors of their own, so that the ones they load don't leak into other tests.
+    static DESCRIPTORS: std::cell::RefCell<Option<ProtobufDescriptors>> = Default::default();
+}
+
+/// Loads the descriptor set at `path`, or unloads the loaded one if there's no path, for the VRL
+/// programs compiled afterwards.
+///
+/// Programs compiled before keep using the descriptors they were compiled with.
+pub fn configure(path: Option<&Path>) -> Result<(), String> {
+    let descriptors = path.map(load).transpose()?;
+
+    #[cfg(not(test))]
+    {
+        *DESCRIPTORS.write().expect("poisoned lock") = descriptors;
+    }
+    #[cfg(test)]
+    DESCRIPTORS.with(|loaded| *loaded.borrow_mut() = descriptors);
+
+    Ok(())
+}
+
+/// Adds the loaded descriptor set, if any, to the external context of a VRL program to compile.
+pub fn set_custom(config: &mut CompileConfig) {
+    #[cfg(not(test))]
+    let descriptors = DESCRIPTORS.read().expect("poisoned lock").clone();
+    #[cfg(test)]
+    let descriptors = DESCRIPTORS.with(|loaded| loaded.borrow().clone());
+
+    if let Some(descriptors) = descriptors {
+        config.set_custom(descriptors);
+    }
+}
+
+fn load(path: &Path) -> Result<ProtobufDescriptors, String> {
+    let bytes = std::fs::read(path).map_err(|error| {
+        format!(
+            "Could not read protobuf descriptor set {:?}: {}",
+            path, error
+        )
+    })?;
+
+    ProtobufDescriptors::decode(&bytes)
+        .map_err(|error| format!("Invalid protobuf descriptor set {:?}: {}", path, error))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rejects_invalid_descriptor_sets() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("invalid.desc");
+        std::fs::write(&path, b"\xffnot a descriptor set").unwrap();
+
+        let error = configure(Some(&path)).unwrap_err();
+        assert!(error.starts_with("Invalid protobuf descriptor set"));
+
+        let error = configure(Some(&dir.path().join("missing.desc"))).unwrap_err();
+        assert!(error.starts_with("Could not read protobuf descriptor set"));
+    }
+
+    #[test]
+    fn loads_empty_descriptor_sets() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("empty.desc");
+        std::fs::write(&path, b"").unwrap();
+
+        configure(Some(&path)).unwrap();
+        let mut config = CompileConfig::default();
+        set_custom(&mut config);
+        assert!(config.get_custom::<ProtobufDescriptors>().is_some());
+
+        configure(None).unwrap();
+        let mut config = CompileConfig::default();
+        set_custom(&mut config);
+        assert!(config.get_custom::<ProtobufDescriptors>().is_none());
+    }
+}
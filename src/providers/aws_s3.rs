@@ -0,0 +1,151 @@
+use aws_sdk_s3::Client;
+use bytes::Bytes;
+use vector_config::configurable_component;
+
+use super::{
+    remote::{BundleSource, Remote, RolloutConfig, SignatureConfig},
+    BuildResult,
+};
+use crate::{
+    aws::{create_client, AwsAuthentication, RegionOrEndpoint},
+    common::s3::S3ClientBuilder,
+    config::{provider::ProviderConfig, GenerateConfig, ProxyConfig},
+    signal,
+};
+
+const fn default_poll_interval_secs() -> u64 {
+    30
+}
+
+/// Configuration for the `aws_s3` provider.
+#[configurable_component(provider("aws_s3"))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct S3Config {
+    /// The name of the bucket holding the configuration bundle.
+    bucket: String,
+
+    /// The key of the object holding the configuration bundle.
+    key: String,
+
+    #[serde(flatten)]
+    #[configurable(derived)]
+    region: RegionOrEndpoint,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    auth: AwsAuthentication,
+
+    /// How often to poll the provider, in seconds.
+    #[serde(default = "default_poll_interval_secs")]
+    poll_interval_secs: u64,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    proxy: ProxyConfig,
+
+    #[configurable(derived)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    signature: Option<SignatureConfig>,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    rollout: RolloutConfig,
+}
+
+impl GenerateConfig for S3Config {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"bucket = "vector-config"
+            key = "vector.toml"
+            region = "us-east-1""#,
+        )
+        .unwrap()
+    }
+}
+
+/// The bundle fetched by the `aws_s3` provider.
+struct S3Bundle {
+    client: Client,
+    bucket: String,
+    key: String,
+}
+
+impl S3Bundle {
+    async fn get_object(&self, key: &str) -> Result<Bytes, String> {
+        let error = |error: &dyn std::fmt::Display| {
+            format!(
+                "Failed to get object {:?} from S3 bucket {:?}: {}",
+                key, self.bucket, error
+            )
+        };
+        let object = self
+            .client
+            .get_object()
+            .bucket(&self.bucket)
+            .key(key)
+            .send()
+            .await
+            .map_err(|e| error(&e))?;
+        let body = object.body.collect().await.map_err(|e| error(&e))?;
+        Ok(body.into_bytes())
+    }
+}
+
+#[async_trait::async_trait]
+impl BundleSource for S3Bundle {
+    const PROVIDER: &'static str = "aws_s3";
+
+    async fn fetch(&self) -> Result<Bytes, String> {
+        self.get_object(&self.key).await
+    }
+
+    async fn fetch_signature(&self, location: Option<&str>) -> Result<Bytes, String> {
+        match location {
+            Some(key) => self.get_object(key).await,
+            None => self.get_object(&format!("{}.sig", self.key)).await,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl ProviderConfig for S3Config {
+    async fn build(&mut self, signal_handler: &mut signal::SignalHandler) -> BuildResult {
+        let proxy = ProxyConfig::from_env().merge(&self.proxy);
+        let client = create_client::<S3ClientBuilder>(
+            &self.auth,
+            self.region.region(),
+            self.region
+                .endpoint()
+                .map_err(|error| vec![error.to_string()])?,
+            self.region.endpoint_variant,
+            &proxy,
+            &None,
+            false,
+        )
+        .await
+        .map_err(|error| vec![error.to_string()])?;
+
+        let bundle = S3Bundle {
+            client,
+            bucket: self.bucket.clone(),
+            key: self.key.clone(),
+        };
+
+        // Poll for changes to remote configuration.
+        Remote::new(
+            bundle,
+            self.signature.as_ref(),
+            self.rollout.clone(),
+            self.poll_interval_secs,
+        )?
+        .build(signal_handler)
+        .await
+    }
+}
@@ -1,20 +1,20 @@
-use async_stream::stream;
-use bytes::Buf;
-use futures::Stream;
+use bytes::Bytes;
 use hyper::Body;
 use indexmap::IndexMap;
-use tokio::time;
 use url::Url;
 use vector_config::configurable_component;
 
 use crate::{
-    config::{self, provider::ProviderConfig, ProxyConfig},
+    config::{provider::ProviderConfig, ProxyConfig},
     http::HttpClient,
     signal,
     tls::{TlsConfig, TlsSettings},
 };
 
-use super::BuildResult;
+use super::{
+    remote::{BundleSource, Remote, RolloutConfig, SignatureConfig},
+    BuildResult,
+};
 
 /// Request settings.
 #[configurable_component]
@@ -56,6 +56,14 @@ pub struct HttpConfig {
         skip_serializing_if = "crate::serde::skip_serializing_if_default"
     )]
     proxy: ProxyConfig,
+
+    #[configurable(derived)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    signature: Option<SignatureConfig>,
+
+    #[configurable(derived)]
+    #[serde(skip_serializing_if = "crate::serde::skip_serializing_if_default")]
+    rollout: RolloutConfig,
 }
 
 impl Default for HttpConfig {
@@ -66,6 +74,8 @@ impl Default for HttpConfig {
             poll_interval_secs: 30,
             tls_options: None,
             proxy: Default::default(),
+            signature: None,
+            rollout: RolloutConfig::default(),
         }
     }
 }
@@ -76,7 +86,7 @@ async fn http_request(
     tls_options: &Option<TlsConfig>,
     headers: &IndexMap<String, String>,
     proxy: &ProxyConfig,
-) -> Result<bytes::Bytes, &'static str> {
+) -> Result<Bytes, &'static str> {
     let tls_settings = TlsSettings::from_options(tls_options).map_err(|_| "Invalid TLS options")?;
     let http_client =
         HttpClient::<Body>::new(tls_settings, proxy).map_err(|_| "Invalid TLS settings")?;
@@ -123,52 +133,37 @@ async fn http_request(
         })
 }
 
-/// Calls `http_request`, serializing the result to a `ConfigBuilder`.
-async fn http_request_to_config_builder(
-    url: &Url,
-    tls_options: &Option<TlsConfig>,
-    headers: &IndexMap<String, String>,
-    proxy: &ProxyConfig,
-) -> BuildResult {
-    let config_str = http_request(url, tls_options, headers, proxy)
-        .await
-        .map_err(|e| vec![e.to_owned()])?;
-
-    let (config_builder, warnings) =
-        config::load(config_str.chunk(), crate::config::format::Format::Toml)?;
-
-    for warning in warnings.into_iter() {
-        warn!("{}", warning);
-    }
-
-    Ok(config_builder)
-}
-
-/// Polls the HTTP endpoint after/every `poll_interval_secs`, returning a stream of `ConfigBuilder`.
-fn poll_http(
-    poll_interval_secs: u64,
+/// The bundle fetched by the `http` provider.
+struct HttpBundle {
     url: Url,
     tls_options: Option<TlsConfig>,
     headers: IndexMap<String, String>,
     proxy: ProxyConfig,
-) -> impl Stream<Item = signal::SignalTo> {
-    let duration = time::Duration::from_secs(poll_interval_secs);
-    let mut interval = time::interval_at(time::Instant::now() + duration, duration);
-
-    stream! {
-        loop {
-            interval.tick().await;
-
-            match http_request_to_config_builder(&url, &tls_options, &headers, &proxy).await {
-                Ok(config_builder) => yield signal::SignalTo::ReloadFromConfigBuilder(config_builder),
-                Err(_) => {},
-            };
-
-            info!(
-                message = "HTTP provider is waiting.",
-                poll_interval_secs = ?poll_interval_secs,
-                url = ?url.as_str());
-        }
+}
+
+#[async_trait::async_trait]
+impl BundleSource for HttpBundle {
+    const PROVIDER: &'static str = "http";
+
+    async fn fetch(&self) -> Result<Bytes, String> {
+        http_request(&self.url, &self.tls_options, &self.headers, &self.proxy)
+            .await
+            .map_err(ToOwned::to_owned)
+    }
+
+    async fn fetch_signature(&self, location: Option<&str>) -> Result<Bytes, String> {
+        let url = match location {
+            Some(location) => Url::parse(location)
+                .map_err(|error| format!("Invalid signature URL {:?}: {}", location, error))?,
+            None => {
+                let mut url = self.url.clone();
+                url.set_path(&format!("{}.sig", self.url.path()));
+                url
+            }
+        };
+        http_request(&url, &self.tls_options, &self.headers, &self.proxy)
+            .await
+            .map_err(ToOwned::to_owned)
     }
 }
 
@@ -180,24 +175,22 @@ impl ProviderConfig for HttpConfig {
             .take()
             .ok_or_else(|| vec!["URL is required for the `http` provider.".to_owned()])?;
 
-        let tls_options = self.tls_options.take();
-        let poll_interval_secs = self.poll_interval_secs;
-        let request = self.request.clone();
-
-        let proxy = ProxyConfig::from_env().merge(&self.proxy);
-        let config_builder =
-            http_request_to_config_builder(&url, &tls_options, &request.headers, &proxy).await?;
-
-        // Poll for changes to remote configuration.
-        signal_handler.add(poll_http(
-            poll_interval_secs,
+        let bundle = HttpBundle {
             url,
-            tls_options,
-            request.headers.clone(),
-            proxy.clone(),
-        ));
+            tls_options: self.tls_options.take(),
+            headers: self.request.headers.clone(),
+            proxy: ProxyConfig::from_env().merge(&self.proxy),
+        };
 
-        Ok(config_builder)
+        // Poll for changes to remote configuration.
+        Remote::new(
+            bundle,
+            self.signature.as_ref(),
+            self.rollout.clone(),
+            self.poll_interval_secs,
+        )?
+        .build(signal_handler)
+        .await
     }
 }
 
@@ -6,7 +6,12 @@ use crate::{
     signal,
 };
 
+#[cfg(feature = "providers-aws_s3")]
+pub mod aws_s3;
 pub mod http;
+mod remote;
+
+pub use remote::{complete_reload, RolloutConfig, SignatureConfig};
 
 pub type BuildResult = std::result::Result<ConfigBuilder, Vec<String>>;
 
@@ -16,6 +21,11 @@ pub type BuildResult = std::result::Result<ConfigBuilder, Vec<String>>;
 #[serde(tag = "type", rename_all = "snake_case")]
 #[enum_dispatch(ProviderConfig)]
 pub enum Providers {
+    /// AWS S3.
+    #[cfg(feature = "providers-aws_s3")]
+    #[serde(alias = "s3")]
+    AwsS3(#[configurable(derived)] aws_s3::S3Config),
+
     /// HTTP.
     Http(#[configurable(derived)] http::HttpConfig),
 }
@@ -26,6 +36,8 @@ impl NamedComponent for Providers {
 
     fn get_component_name(&self) -> &'static str {
         match self {
+            #[cfg(feature = "providers-aws_s3")]
+            Self::AwsS3(config) => config.get_component_name(),
             Self::Http(config) => config.get_component_name(),
         }
     }
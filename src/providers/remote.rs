@@ -0,0 +1,510 @@
+//! Verification and staged rollout of the configuration bundles fetched by remote providers.
+//!
+//! Remote providers poll a configuration bundle, and reload Vector once it changes. Bundles are
+//! identified by the SHA-256 digest of their content, so unchanged bundles aren't reloaded.
+//!
+//! Bundles are versioned by their top-level `version`, an integer defaulting to zero. As it's part
+//! of the signed content of the bundle, bundles with a lower version than the one applied are
+//! rejected, so that older signed bundles can't be replayed to roll the configuration back.
+
+use std::{
+    path::PathBuf,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use async_stream::stream;
+use async_trait::async_trait;
+use bytes::Bytes;
+use futures::Stream;
+#[cfg(not(test))]
+use once_cell::sync::Lazy;
+use openssl::{
+    hash::MessageDigest,
+    pkey::{Id, PKey, Public},
+    sign::Verifier,
+};
+use tokio::time::{self, Instant};
+use vector_config::configurable_component;
+
+use super::BuildResult;
+use crate::{
+    config::{self, ConfigBuilder},
+    internal_events::{ConfigProviderError, ConfigProviderVersionApplied},
+    signal,
+};
+
+/// Verification of the detached signatures of the configuration bundles.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct SignatureConfig {
+    /// The path of the PEM file holding the public key the bundles are signed with.
+    ///
+    /// RSA and ECDSA signatures are verified with a SHA-256 digest, and Ed25519 signatures
+    /// without one.
+    pub public_key_file: PathBuf,
+
+    /// The location of the base64 encoded signature of the bundles.
+    ///
+    /// This is a URL for the `http` provider, and an object key for the `aws_s3` provider.
+    /// Defaults to the location of the bundle followed by `.sig`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub location: Option<String>,
+}
+
+/// Staged rollout of new versions of the configuration bundle across a fleet of instances.
+#[configurable_component]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[serde(deny_unknown_fields, default)]
+pub struct RolloutConfig {
+    /// The duration over which new versions of the bundle are rolled out, in seconds.
+    ///
+    /// Each instance waits for a share of this duration, derived from its ID and the digest of the
+    /// bundle, before applying a new version, so the instances of a fleet apply it gradually rather than
+    /// all at once. New versions are applied as soon as they are fetched when zero.
+    pub duration_secs: u64,
+
+    /// The ID of the instance, from which its share of the rollout duration is derived.
+    ///
+    /// Defaults to the hostname.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub instance_id: Option<String>,
+}
+
+impl RolloutConfig {
+    /// Gets how long the instance waits before applying the bundle with the given digest.
+    fn delay(&self, digest: &str) -> Duration {
+        if self.duration_secs == 0 {
+            return Duration::ZERO;
+        }
+
+        let instance_id = self
+            .instance_id
+            .clone()
+            .or_else(|| crate::get_hostname().ok())
+            .unwrap_or_default();
+        let hash = openssl::sha::sha256(format!("{}/{}", instance_id, digest).as_bytes());
+        let mut bytes = [0; 8];
+        bytes.copy_from_slice(&hash[..8]);
+        let share = u64::from_be_bytes(bytes) % 1000;
+
+        Duration::from_millis(self.duration_secs.saturating_mul(share))
+    }
+}
+
+/// The location a remote provider fetches the configuration bundle from.
+#[async_trait]
+pub(super) trait BundleSource: Send + Sync + 'static {
+    /// The name of the provider, reported in its internal events.
+    const PROVIDER: &'static str;
+
+    /// Fetches the bundle.
+    async fn fetch(&self) -> Result<Bytes, String>;
+
+    /// Fetches the signature of the bundle at `location`, or next to the bundle when unset.
+    async fn fetch_signature(&self, location: Option<&str>) -> Result<Bytes, String>;
+}
+
+struct SignatureVerifier {
+    key: PKey<Public>,
+    location: Option<String>,
+}
+
+impl SignatureVerifier {
+    fn new(config: &SignatureConfig) -> Result<Self, String> {
+        let path = &config.public_key_file;
+        let pem = std::fs::read(path)
+            .map_err(|error| format!("Couldn't read public key file {:?}: {}", path, error))?;
+        let key = PKey::public_key_from_pem(&pem)
+            .map_err(|error| format!("Invalid public key in {:?}: {}", path, error))?;
+        Ok(Self {
+            key,
+            location: config.location.clone(),
+        })
+    }
+
+    fn verify(&self, bundle: &[u8], signature: &[u8]) -> Result<(), String> {
+        let signature = std::str::from_utf8(signature)
+            .ok()
+            .and_then(|signature| {
+                let signature = signature.split_whitespace().collect::<String>();
+                openssl::base64::decode_block(&signature).ok()
+            })
+            .ok_or_else(|| "Signature of the configuration bundle isn't base64.".to_owned())?;
+
+        let verifier = if self.key.id() == Id::ED25519 {
+            Verifier::new_without_digest(&self.key)
+        } else {
+            Verifier::new(MessageDigest::sha256(), &self.key)
+        };
+        match verifier.and_then(|mut verifier| verifier.verify_oneshot(&signature, bundle)) {
+            Ok(true) => Ok(()),
+            Ok(false) | Err(_) => {
+                Err("Signature of the configuration bundle doesn't match it.".to_owned())
+            }
+        }
+    }
+}
+
+/// Gets the digest of `bundle`, the hex encoded SHA-256 digest of its content.
+fn digest(bundle: &[u8]) -> String {
+    openssl::sha::sha256(bundle)
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+/// Loads the configuration of `bundle`, along with its version.
+fn load(bundle: &[u8]) -> Result<(ConfigBuilder, i64), Vec<String>> {
+    let (mut table, warnings) =
+        config::load::<_, toml::value::Table>(bundle, config::Format::Toml)?;
+
+    for warning in warnings.into_iter() {
+        warn!("{}", warning);
+    }
+
+    let version = match table.remove("version") {
+        None => 0,
+        Some(toml::Value::Integer(version)) => version,
+        Some(_) => {
+            return Err(vec![
+                "The `version` of the configuration bundle must be an integer.".to_owned(),
+            ])
+        }
+    };
+    let config_builder = toml::Value::Table(table)
+        .try_into()
+        .map_err(|error: toml::de::Error| vec![error.to_string()])?;
+
+    Ok((config_builder, version))
+}
+
+/// A version of the configuration bundle of a provider.
+#[derive(Clone, Debug)]
+struct BundleVersion {
+    provider: &'static str,
+    version: i64,
+    digest: String,
+}
+
+/// The version of the bundle Vector was last reloaded with, and the version it's being reloaded
+/// with, if any.
+#[derive(Default)]
+struct Versions {
+    applied: Option<BundleVersion>,
+    pending: Option<BundleVersion>,
+}
+
+#[cfg(not(test))]
+static VERSIONS: Lazy<Arc<Mutex<Versions>>> = Lazy::new(Default::default);
+
+#[cfg(test)]
+thread_local! {
+    // Tests get versions of their own, so that the versions they apply don't leak into other tests.
+    static VERSIONS: Arc<Mutex<Versions>> = Default::default();
+}
+
+#[cfg(not(test))]
+fn versions() -> Arc<Mutex<Versions>> {
+    Arc::clone(&VERSIONS)
+}
+
+#[cfg(test)]
+fn versions() -> Arc<Mutex<Versions>> {
+    VERSIONS.with(Arc::clone)
+}
+
+/// Records whether the reload of Vector with the bundle last loaded by a provider succeeded,
+/// reporting its version as applied if it did.
+pub fn complete_reload(succeeded: bool) {
+    let versions = versions();
+    let mut versions = versions.lock().expect("poisoned lock");
+    if let Some(pending) = versions.pending.take() {
+        if succeeded {
+            emit!(ConfigProviderVersionApplied {
+                provider: pending.provider,
+                version: pending.version,
+                digest: &pending.digest,
+            });
+            versions.applied = Some(pending);
+        }
+    }
+}
+
+/// Checks that `version` isn't lower than the version applied.
+fn check_version(version: i64) -> Result<(), String> {
+    let versions = versions();
+    let versions = versions.lock().expect("poisoned lock");
+    match &versions.applied {
+        Some(applied) if version < applied.version => Err(format!(
+            "Version {} of the configuration bundle is lower than the applied version {}.",
+            version, applied.version
+        )),
+        _ => Ok(()),
+    }
+}
+
+fn applied_digest() -> Option<String> {
+    let versions = versions();
+    let versions = versions.lock().expect("poisoned lock");
+    versions
+        .applied
+        .as_ref()
+        .map(|applied| applied.digest.clone())
+}
+
+fn stage_reload(version: BundleVersion) {
+    versions().lock().expect("poisoned lock").pending = Some(version);
+}
+
+/// A configuration bundle fetched from `S`, verified and rolled out as configured.
+pub(super) struct Remote<S> {
+    source: S,
+    verifier: Option<SignatureVerifier>,
+    rollout: RolloutConfig,
+    poll_interval: Duration,
+}
+
+impl<S: BundleSource> Remote<S> {
+    pub(super) fn new(
+        source: S,
+        signature: Option<&SignatureConfig>,
+        rollout: RolloutConfig,
+        poll_interval_secs: u64,
+    ) -> Result<Self, Vec<String>> {
+        let verifier = signature
+            .map(SignatureVerifier::new)
+            .transpose()
+            .map_err(|error| vec![error])?;
+        Ok(Self {
+            source,
+            verifier,
+            rollout,
+            poll_interval: Duration::from_secs(poll_interval_secs),
+        })
+    }
+
+    /// Fetches the bundle and verifies its signature, returning it along with its digest.
+    async fn fetch(&self) -> Result<(String, Bytes), String> {
+        let bundle = self.source.fetch().await?;
+        if let Some(verifier) = &self.verifier {
+            let signature = self
+                .source
+                .fetch_signature(verifier.location.as_deref())
+                .await?;
+            verifier.verify(&bundle, &signature)?;
+        }
+        Ok((digest(&bundle), bundle))
+    }
+
+    /// Loads the current version of the bundle, then polls for new versions, reloading Vector
+    /// with them through `signal_handler`.
+    ///
+    /// The version loaded is only reported as applied once Vector is reloaded with it, as
+    /// recorded with [`complete_reload`].
+    pub(super) async fn build(self, signal_handler: &mut signal::SignalHandler) -> BuildResult {
+        let (digest, bundle) = self.fetch().await.map_err(|error| vec![error])?;
+        let (config_builder, version) = load(&bundle)?;
+        check_version(version).map_err(|error| vec![error])?;
+        stage_reload(BundleVersion {
+            provider: S::PROVIDER,
+            version,
+            digest: digest.clone(),
+        });
+
+        signal_handler.add(self.poll(digest));
+
+        Ok(config_builder)
+    }
+
+    fn poll(self, loaded: String) -> impl Stream<Item = signal::SignalTo> {
+        let mut interval =
+            time::interval_at(Instant::now() + self.poll_interval, self.poll_interval);
+        // The digest of the bundle last loaded, which isn't loaded again unless it changes, even
+        // if it failed to load or the reload with it failed.
+        let mut loaded = Some(loaded);
+        // The digest of the bundle waiting for its share of the rollout duration, and when it's
+        // due.
+        let mut staged: Option<(String, Instant)> = None;
+
+        stream! {
+            loop {
+                interval.tick().await;
+
+                let (digest, bundle) = match self.fetch().await {
+                    Ok(fetched) => fetched,
+                    Err(error) => {
+                        emit!(ConfigProviderError { provider: S::PROVIDER, error: &error });
+                        continue;
+                    }
+                };
+                if loaded.as_ref() == Some(&digest) || applied_digest().as_ref() == Some(&digest) {
+                    staged = None;
+                    continue;
+                }
+
+                let due = match &staged {
+                    Some((staged_digest, due)) if *staged_digest == digest => *due,
+                    _ => {
+                        let delay = self.rollout.delay(&digest);
+                        if !delay.is_zero() {
+                            info!(
+                                message = "New configuration version staged for rollout.",
+                                provider = %S::PROVIDER,
+                                digest = %digest,
+                                delay_secs = %delay.as_secs(),
+                            );
+                        }
+                        let due = Instant::now() + delay;
+                        staged = Some((digest.clone(), due));
+                        due
+                    }
+                };
+                if Instant::now() < due {
+                    continue;
+                }
+
+                staged = None;
+                loaded = Some(digest.clone());
+                let result = load(&bundle).and_then(|(config_builder, version)| {
+                    check_version(version).map_err(|error| vec![error])?;
+                    Ok((config_builder, version))
+                });
+                match result {
+                    Ok((config_builder, version)) => {
+                        stage_reload(BundleVersion { provider: S::PROVIDER, version, digest });
+                        yield signal::SignalTo::ReloadFromConfigBuilder(config_builder);
+                    }
+                    Err(errors) => {
+                        emit!(ConfigProviderError { provider: S::PROVIDER, error: &errors.join(" ") });
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use openssl::{
+        ec::{EcGroup, EcKey},
+        nid::Nid,
+        pkey::Private,
+        rsa::Rsa,
+        sign::Signer,
+    };
+
+    use super::*;
+
+    const BUNDLE: &[u8] = br#"
+        [sources.in]
+        type = "demo_logs"
+        format = "shuffle"
+        lines = ["line"]
+    "#;
+
+    fn sign(key: &PKey<Private>, bundle: &[u8]) -> Bytes {
+        let mut signer = if key.id() == Id::ED25519 {
+            Signer::new_without_digest(key).unwrap()
+        } else {
+            Signer::new(MessageDigest::sha256(), key).unwrap()
+        };
+        let signature = signer.sign_oneshot_to_vec(bundle).unwrap();
+        Bytes::from(openssl::base64::encode_block(&signature))
+    }
+
+    fn verifier(key: &PKey<Private>) -> SignatureVerifier {
+        let pem = key.public_key_to_pem().unwrap();
+        SignatureVerifier {
+            key: PKey::public_key_from_pem(&pem).unwrap(),
+            location: None,
+        }
+    }
+
+    #[test]
+    fn verifies_signatures() {
+        let keys = [
+            PKey::generate_ed25519().unwrap(),
+            PKey::from_rsa(Rsa::generate(2048).unwrap()).unwrap(),
+            PKey::from_ec_key(
+                EcKey::generate(&EcGroup::from_curve_name(Nid::X9_62_PRIME256V1).unwrap()).unwrap(),
+            )
+            .unwrap(),
+        ];
+
+        for key in &keys {
+            let verifier = verifier(key);
+            let signature = sign(key, BUNDLE);
+            assert_eq!(verifier.verify(BUNDLE, &signature), Ok(()));
+            assert!(verifier.verify(b"tampered", &signature).is_err());
+            assert!(verifier.verify(BUNDLE, b"not base64!").is_err());
+        }
+    }
+
+    #[test]
+    fn rollout_delay_is_stable_and_bounded() {
+        let rollout = RolloutConfig {
+            duration_secs: 600,
+            instance_id: Some("vector-0".to_owned()),
+        };
+        let delay = rollout.delay("v1");
+        assert_eq!(delay, rollout.delay("v1"));
+        assert!(delay < Duration::from_secs(600));
+
+        let delays = (0..100)
+            .map(|i| {
+                RolloutConfig {
+                    instance_id: Some(format!("vector-{}", i)),
+                    ..rollout.clone()
+                }
+                .delay("v1")
+            })
+            .collect::<std::collections::HashSet<_>>();
+        assert!(delays.len() > 50);
+
+        assert_eq!(RolloutConfig::default().delay("v1"), Duration::ZERO);
+    }
+
+    #[test]
+    fn digests_are_of_the_content() {
+        assert_eq!(digest(BUNDLE), digest(BUNDLE));
+        assert_ne!(digest(BUNDLE), digest(b"other"));
+        assert_eq!(digest(b"").len(), 64);
+    }
+
+    #[test]
+    fn loads_bundle_versions() {
+        let (config_builder, version) = load(BUNDLE).unwrap();
+        assert_eq!(version, 0);
+        assert_eq!(config_builder.sources.len(), 1);
+
+        let versioned = [b"version = 42\n".as_slice(), BUNDLE].concat();
+        let (config_builder, version) = load(&versioned).unwrap();
+        assert_eq!(version, 42);
+        assert_eq!(config_builder.sources.len(), 1);
+
+        let invalid = [b"version = \"42\"\n".as_slice(), BUNDLE].concat();
+        assert!(load(&invalid).is_err());
+    }
+
+    #[test]
+    fn rejects_lower_versions_once_applied() {
+        let version = |version| BundleVersion {
+            provider: "test",
+            version,
+            digest: digest(&version.to_le_bytes()),
+        };
+
+        stage_reload(version(-1));
+        complete_reload(false);
+        assert!(check_version(-2).is_ok());
+
+        stage_reload(version(3));
+        complete_reload(true);
+        assert!(check_version(3).is_ok());
+        assert!(check_version(4).is_ok());
+        assert!(check_version(2).is_err());
+        assert_eq!(applied_digest(), Some(digest(&3_i64.to_le_bytes())));
+    }
+}
@@ -53,6 +53,19 @@ struct InstallOpts {
         value_delimiter(',')
     )]
     config_dirs: Vec<PathBuf>,
+
+    /// Restart the service when it fails, waiting the given delays in seconds before the first,
+    /// second, and following restarts. The last delay is used for all further consecutive
+    /// failures, so that a list such as `5,30,300` backs off between restarts.
+    ///
+    /// If no delays are specified, the service isn't restarted when it fails.
+    #[arg(long, value_delimiter(','))]
+    restart_delays: Vec<u64>,
+
+    /// How long the service must run without failing, in seconds, for the delays of
+    /// `--restart-delays` to start over from the first one.
+    #[arg(default_value = "86400", long)]
+    restart_reset_period: u64,
 }
 
 impl InstallOpts {
@@ -71,21 +84,31 @@ impl InstallOpts {
             description: OsString::from(description),
             executable_path: current_exe,
             launch_arguments: arguments,
+            restart_delays: self
+                .restart_delays
+                .iter()
+                .copied()
+                .map(Duration::from_secs)
+                .collect(),
+            restart_reset_period: Duration::from_secs(self.restart_reset_period),
         }
     }
 
+    /// The config paths of the service, made absolute since the service doesn't run from the
+    /// current directory.
     fn config_paths_with_formats(&self) -> Vec<config::ConfigPath> {
+        let current_dir = std::env::current_dir().unwrap_or_default();
         config::merge_path_lists(vec![
             (&self.config_paths, None),
             (&self.config_paths_toml, Some(config::Format::Toml)),
             (&self.config_paths_json, Some(config::Format::Json)),
             (&self.config_paths_yaml, Some(config::Format::Yaml)),
         ])
-        .map(|(path, hint)| config::ConfigPath::File(path, hint))
+        .map(|(path, hint)| config::ConfigPath::File(current_dir.join(path), hint))
         .chain(
             self.config_dirs
                 .iter()
-                .map(|dir| config::ConfigPath::Dir(dir.to_path_buf())),
+                .map(|dir| config::ConfigPath::Dir(current_dir.join(dir))),
         )
         .collect()
     }
@@ -153,6 +176,9 @@ struct ServiceInfo {
 
     executable_path: std::path::PathBuf,
     launch_arguments: Vec<OsString>,
+
+    restart_delays: Vec<Duration>,
+    restart_reset_period: Duration,
 }
 
 impl Default for ServiceInfo {
@@ -165,6 +191,8 @@ impl Default for ServiceInfo {
             description: OsString::from(crate::built_info::PKG_DESCRIPTION),
             executable_path: current_exe,
             launch_arguments: vec![],
+            restart_delays: vec![],
+            restart_reset_period: Duration::default(),
         }
     }
 }
@@ -214,6 +242,8 @@ fn control_service(service: &ServiceInfo, action: ControlAction) -> exitcode::Ex
         description: service.description.clone(),
         executable_path: service.executable_path.clone(),
         launch_arguments: service.launch_arguments.clone(),
+        restart_delays: service.restart_delays.clone(),
+        restart_reset_period: service.restart_reset_period,
     };
 
     let res = match action {
@@ -15,6 +15,8 @@ pub enum SignalTo {
     ReloadFromConfigBuilder(ConfigBuilder),
     /// Signal to reload config from the filesystem.
     ReloadFromDisk,
+    /// Signal to reload an enrichment table, leaving the rest of the topology as it is.
+    ReloadEnrichmentTable(String),
     /// Signal to shutdown process.
     Shutdown,
     /// Shutdown process immediately.
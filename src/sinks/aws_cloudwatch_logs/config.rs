@@ -7,7 +7,9 @@ use vector_config::configurable_component;
 
 use crate::{
     aws::{
-        create_client, create_smithy_client, resolve_region, AwsAuthentication, ClientBuilder,
+        create_client, create_smithy_client,
+        failover::{endpoint_name, targets},
+        resolve_region, AwsAuthentication, ClientBuilder, FailoverConfig, RegionFailover,
         RegionOrEndpoint,
     },
     codecs::{Encoder, EncodingConfig},
@@ -17,12 +19,16 @@ use crate::{
     },
     sinks::{
         aws_cloudwatch_logs::{
-            healthcheck::healthcheck, request_builder::CloudwatchRequestBuilder,
-            retry::CloudwatchRetryLogic, service::CloudwatchLogsPartitionSvc, sink::CloudwatchSink,
+            healthcheck::healthcheck,
+            request_builder::CloudwatchRequestBuilder,
+            retry::CloudwatchRetryLogic,
+            service::{CloudwatchLogsPartitionSvc, SmithyClient},
+            sink::CloudwatchSink,
         },
         util::{
-            http::RequestConfig, BatchConfig, Compression, ServiceBuilderExt, SinkBatchSettings,
-            TowerRequestConfig,
+            adaptive_batching::{AdaptiveBatchController, AdaptiveBatchSettings},
+            http::RequestConfig,
+            BatchConfig, Compression, ServiceBuilderExt, SinkBatchSettings, TowerRequestConfig,
         },
         Healthcheck, VectorSink,
     },
@@ -37,6 +43,8 @@ impl ClientBuilder for CloudwatchLogsClientBuilder {
     type Client = aws_sdk_cloudwatchlogs::client::Client;
     type DefaultMiddleware = aws_sdk_cloudwatchlogs::middleware::DefaultMiddleware;
 
+    const SERVICE_NAME: &'static str = "logs";
+
     fn default_middleware() -> Self::DefaultMiddleware {
         aws_sdk_cloudwatchlogs::middleware::DefaultMiddleware::new()
     }
@@ -73,6 +81,10 @@ pub struct CloudwatchLogsSinkConfig {
     #[serde(flatten)]
     pub region: RegionOrEndpoint,
 
+    #[configurable(derived)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub failover: Option<FailoverConfig>,
+
     /// Dynamically create a [log group][log_group] if it does not already exist.
     ///
     /// This will ignore `create_missing_stream` directly after creating the group and will create
@@ -97,6 +109,14 @@ pub struct CloudwatchLogsSinkConfig {
     #[serde(default)]
     pub batch: BatchConfig<CloudwatchLogsDefaultBatchSettings>,
 
+    /// Adjusts the size of the batches of each log stream to the latency of its requests.
+    ///
+    /// When set, the batch limits are the size of the largest batches sent to each log stream, and
+    /// the batches of log streams whose requests are slow or fail are made smaller, instead of
+    /// always using the batch limits.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub adaptive_batching: Option<AdaptiveBatchSettings>,
+
     #[configurable(derived)]
     #[serde(default)]
     pub request: RequestConfig,
@@ -129,6 +149,7 @@ impl CloudwatchLogsSinkConfig {
             &self.auth,
             self.region.region(),
             self.region.endpoint()?,
+            self.region.endpoint_variant,
             proxy,
             &self.tls,
             true,
@@ -136,19 +157,38 @@ impl CloudwatchLogsSinkConfig {
         .await
     }
 
-    pub async fn create_smithy_client(
+    /// Creates the clients of the primary region and of each of the failover regions.
+    pub async fn create_clients(
         &self,
         proxy: &ProxyConfig,
-    ) -> crate::Result<aws_smithy_client::Client> {
-        let region = resolve_region(self.region.region()).await?;
-        create_smithy_client::<CloudwatchLogsClientBuilder>(
-            region,
-            proxy,
-            &self.tls,
-            true,
-            RetryConfig::disabled(),
-        )
-        .await
+    ) -> crate::Result<RegionFailover<(CloudwatchLogsClient, SmithyClient)>> {
+        let mut endpoints = Vec::new();
+        for target in targets(&self.region, self.failover.as_ref()) {
+            let client = create_client::<CloudwatchLogsClientBuilder>(
+                &self.auth,
+                target.region(),
+                target.endpoint()?,
+                target.endpoint_variant,
+                proxy,
+                &self.tls,
+                true,
+            )
+            .await?;
+            let region = resolve_region(target.region()).await?;
+            let smithy_client = create_smithy_client::<CloudwatchLogsClientBuilder>(
+                region,
+                proxy,
+                &self.tls,
+                true,
+                RetryConfig::disabled(),
+            )
+            .await?;
+            endpoints.push((
+                endpoint_name(target),
+                (client, std::sync::Arc::new(smithy_client)),
+            ));
+        }
+        RegionFailover::new(endpoints, self.failover.as_ref())
     }
 }
 
@@ -156,25 +196,26 @@ impl CloudwatchLogsSinkConfig {
 impl SinkConfig for CloudwatchLogsSinkConfig {
     async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
         let batcher_settings = self.batch.into_batcher_settings()?;
+        let adaptive_batching = self
+            .adaptive_batching
+            .map(|settings| AdaptiveBatchController::new(settings, batcher_settings))
+            .transpose()?;
         let request_settings = self
             .request
             .tower
             .unwrap_with(&TowerRequestConfig::default());
-        let client = self.create_client(cx.proxy()).await?;
-        let smithy_client = self.create_smithy_client(cx.proxy()).await?;
+        let clients = self.create_clients(cx.proxy()).await?;
+        let (client, _) = clients.primary();
         let svc = ServiceBuilder::new()
             .settings(request_settings, CloudwatchRetryLogic::new())
-            .service(CloudwatchLogsPartitionSvc::new(
-                self.clone(),
-                client.clone(),
-                std::sync::Arc::new(smithy_client),
-            ));
+            .service(CloudwatchLogsPartitionSvc::new(self.clone(), clients));
         let transformer = self.encoding.transformer();
         let serializer = self.encoding.build()?;
         let encoder = Encoder::<()>::new(serializer);
         let healthcheck = healthcheck(self.clone(), client).boxed();
         let sink = CloudwatchSink {
             batcher_settings,
+            adaptive_batching,
             request_builder: CloudwatchRequestBuilder {
                 group_template: self.group_name.clone(),
                 stream_template: self.stream_name.clone(),
@@ -210,10 +251,12 @@ fn default_config(encoding: EncodingConfig) -> CloudwatchLogsSinkConfig {
         group_name: Default::default(),
         stream_name: Default::default(),
         region: Default::default(),
+        failover: None,
         create_missing_group: Default::default(),
         create_missing_stream: Default::default(),
         compression: Default::default(),
         batch: Default::default(),
+        adaptive_batching: None,
         request: Default::default(),
         tls: Default::default(),
         assume_role: Default::default(),
@@ -44,11 +44,13 @@ async fn cloudwatch_insert_log_event() {
         stream_name: Template::try_from(stream_name.as_str()).unwrap(),
         group_name: Template::try_from(GROUP_NAME).unwrap(),
         region: RegionOrEndpoint::with_both("localstack", watchlogs_address().as_str()),
+        failover: None,
         encoding: TextSerializerConfig::new().into(),
         create_missing_group: None,
         create_missing_stream: None,
         compression: Default::default(),
         batch: Default::default(),
+        adaptive_batching: None,
         request: Default::default(),
         tls: Default::default(),
         assume_role: None,
@@ -94,11 +96,13 @@ async fn cloudwatch_insert_log_events_sorted() {
         stream_name: Template::try_from(stream_name.as_str()).unwrap(),
         group_name: Template::try_from(GROUP_NAME).unwrap(),
         region: RegionOrEndpoint::with_both("localstack", watchlogs_address().as_str()),
+        failover: None,
         encoding: TextSerializerConfig::new().into(),
         create_missing_group: None,
         create_missing_stream: None,
         compression: Default::default(),
         batch: Default::default(),
+        adaptive_batching: None,
         request: Default::default(),
         tls: Default::default(),
         assume_role: None,
@@ -163,11 +167,13 @@ async fn cloudwatch_insert_out_of_range_timestamp() {
         stream_name: Template::try_from(stream_name.as_str()).unwrap(),
         group_name: Template::try_from(GROUP_NAME).unwrap(),
         region: RegionOrEndpoint::with_both("localstack", watchlogs_address().as_str()),
+        failover: None,
         encoding: TextSerializerConfig::new().into(),
         create_missing_group: None,
         create_missing_stream: None,
         compression: Default::default(),
         batch: Default::default(),
+        adaptive_batching: None,
         request: Default::default(),
         tls: Default::default(),
         assume_role: None,
@@ -236,11 +242,13 @@ async fn cloudwatch_dynamic_group_and_stream_creation() {
         stream_name: Template::try_from(stream_name.as_str()).unwrap(),
         group_name: Template::try_from(group_name.as_str()).unwrap(),
         region: RegionOrEndpoint::with_both("localstack", watchlogs_address().as_str()),
+        failover: None,
         encoding: TextSerializerConfig::new().into(),
         create_missing_group: None,
         create_missing_stream: None,
         compression: Default::default(),
         batch: Default::default(),
+        adaptive_batching: None,
         request: Default::default(),
         tls: Default::default(),
         assume_role: None,
@@ -291,11 +299,13 @@ async fn cloudwatch_insert_log_event_batched() {
         stream_name: Template::try_from(stream_name.as_str()).unwrap(),
         group_name: Template::try_from(group_name.as_str()).unwrap(),
         region: RegionOrEndpoint::with_both("localstack", watchlogs_address().as_str()),
+        failover: None,
         encoding: TextSerializerConfig::new().into(),
         create_missing_group: None,
         create_missing_stream: None,
         compression: Default::default(),
         batch,
+        adaptive_batching: None,
         request: Default::default(),
         tls: Default::default(),
         assume_role: None,
@@ -341,11 +351,13 @@ async fn cloudwatch_insert_log_event_partitioned() {
         group_name: Template::try_from(GROUP_NAME).unwrap(),
         stream_name: Template::try_from(format!("{}-{{{{key}}}}", stream_name)).unwrap(),
         region: RegionOrEndpoint::with_both("localstack", watchlogs_address().as_str()),
+        failover: None,
         encoding: TextSerializerConfig::new().into(),
         create_missing_group: None,
         create_missing_stream: None,
         compression: Default::default(),
         batch: Default::default(),
+        adaptive_batching: None,
         request: Default::default(),
         tls: Default::default(),
         assume_role: None,
@@ -433,11 +445,13 @@ async fn cloudwatch_healthcheck() {
         stream_name: Template::try_from("test-stream").unwrap(),
         group_name: Template::try_from(GROUP_NAME).unwrap(),
         region: RegionOrEndpoint::with_both("localstack", watchlogs_address().as_str()),
+        failover: None,
         encoding: TextSerializerConfig::new().into(),
         create_missing_group: None,
         create_missing_stream: None,
         compression: Default::default(),
         batch: Default::default(),
+        adaptive_batching: None,
         request: Default::default(),
         tls: Default::default(),
         assume_role: None,
@@ -458,7 +472,7 @@ async fn create_client_test() -> CloudwatchLogsClient {
     ));
     let proxy = ProxyConfig::default();
 
-    create_client::<CloudwatchLogsClientBuilder>(&auth, region, endpoint, &proxy, &None, true)
+    create_client::<CloudwatchLogsClientBuilder>(&auth, region, endpoint, None, &proxy, &None, true)
         .await
         .unwrap()
 }
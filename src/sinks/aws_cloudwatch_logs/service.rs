@@ -26,6 +26,7 @@ use vector_core::{internal_event::CountByteSize, stream::DriverResponse};
 use vrl::prelude::fmt::Debug;
 
 use crate::{
+    aws::RegionFailover,
     event::EventStatus,
     sinks::{
         aws_cloudwatch_logs::{
@@ -33,7 +34,8 @@ use crate::{
             sink::BatchCloudwatchRequest, CloudwatchKey,
         },
         util::{
-            retries::FixedRetryPolicy, EncodedLength, TowerRequestConfig, TowerRequestSettings,
+            retries::{FixedRetryPolicy, RetryLogic},
+            EncodedLength, TowerRequestConfig, TowerRequestSettings,
         },
     },
 };
@@ -125,12 +127,11 @@ impl DriverResponse for CloudwatchResponse {
 impl CloudwatchLogsPartitionSvc {
     pub fn new(
         config: CloudwatchLogsSinkConfig,
-        client: CloudwatchLogsClient,
         // we store a separate smithy_client to set request headers for PutLogEvents since the regular
         // client cannot set headers
         //
         // https://github.com/awslabs/aws-sdk-rust/issues/537
-        smithy_client: SmithyClient,
+        clients: RegionFailover<(CloudwatchLogsClient, SmithyClient)>,
     ) -> Self {
         let request_settings = config
             .request
@@ -139,10 +140,9 @@ impl CloudwatchLogsPartitionSvc {
 
         Self {
             config,
-            clients: HashMap::new(),
+            svcs: HashMap::new(),
             request_settings,
-            client,
-            smithy_client,
+            clients,
         }
     }
 }
@@ -170,7 +170,7 @@ impl Service<BatchCloudwatchRequest> for CloudwatchLogsPartitionSvc {
                     .build()
             })
             .collect();
-        let svc = if let Some(svc) = &mut self.clients.get_mut(&key) {
+        let svc = if let Some(svc) = &mut self.svcs.get_mut(&key) {
             svc.clone()
         } else {
             // Concurrency limit is 1 because we need token from previous request.
@@ -190,11 +190,10 @@ impl Service<BatchCloudwatchRequest> for CloudwatchLogsPartitionSvc {
                 .service(CloudwatchLogsSvc::new(
                     self.config.clone(),
                     &key,
-                    self.client.clone(),
-                    std::sync::Arc::clone(&self.smithy_client),
+                    self.clients.clone(),
                 ));
 
-            self.clients.insert(key, svc.clone());
+            self.svcs.insert(key, svc.clone());
             svc
         };
 
@@ -212,8 +211,7 @@ impl CloudwatchLogsSvc {
     pub fn new(
         config: CloudwatchLogsSinkConfig,
         key: &CloudwatchKey,
-        client: CloudwatchLogsClient,
-        smithy_client: SmithyClient,
+        clients: RegionFailover<(CloudwatchLogsClient, SmithyClient)>,
     ) -> Self {
         let group_name = key.group.clone();
         let stream_name = key.stream.clone();
@@ -223,8 +221,8 @@ impl CloudwatchLogsSvc {
 
         CloudwatchLogsSvc {
             headers: config.request.headers,
-            client,
-            smithy_client,
+            clients,
+            client_index: None,
             stream_name,
             group_name,
             create_missing_group,
@@ -285,7 +283,7 @@ impl CloudwatchLogsSvc {
 impl Service<Vec<InputLogEvent>> for CloudwatchLogsSvc {
     type Response = ();
     type Error = CloudwatchError;
-    type Future = request::CloudwatchFuture;
+    type Future = BoxFuture<'static, Result<(), CloudwatchError>>;
 
     fn poll_ready(&mut self, cx: &mut Context) -> Poll<Result<(), Self::Error>> {
         if let Some(rx) = &mut self.token_rx {
@@ -302,9 +300,16 @@ impl Service<Vec<InputLogEvent>> for CloudwatchLogsSvc {
             let (tx, rx) = oneshot::channel();
             self.token_rx = Some(rx);
 
+            // Sequence tokens are only valid in the region they were issued in.
+            let (index, (client, smithy_client)) = self.clients.select();
+            if self.client_index.replace(index) != Some(index) {
+                self.token = None;
+            }
+
+            let clients = self.clients.clone();
             request::CloudwatchFuture::new(
-                self.client.clone(),
-                std::sync::Arc::clone(&self.smithy_client),
+                client,
+                smithy_client,
                 self.headers.clone(),
                 self.stream_name.clone(),
                 self.group_name.clone(),
@@ -314,6 +319,14 @@ impl Service<Vec<InputLogEvent>> for CloudwatchLogsSvc {
                 self.token.take(),
                 tx,
             )
+            .inspect(move |result| match result {
+                Ok(()) => clients.report_success(index),
+                Err(error) if CloudwatchRetryLogic::<()>::new().is_retriable_error(error) => {
+                    clients.report_failure(index)
+                }
+                Err(_) => {}
+            })
+            .boxed()
         } else {
             panic!("poll_ready was not called; this is a bug!");
         }
@@ -321,8 +334,9 @@ impl Service<Vec<InputLogEvent>> for CloudwatchLogsSvc {
 }
 
 pub struct CloudwatchLogsSvc {
-    client: CloudwatchLogsClient,
-    smithy_client: SmithyClient,
+    clients: RegionFailover<(CloudwatchLogsClient, SmithyClient)>,
+    /// The index of the client the previous request was sent with.
+    client_index: Option<usize>,
     headers: IndexMap<String, String>,
     stream_name: String,
     group_name: String,
@@ -341,8 +355,7 @@ impl EncodedLength for InputLogEvent {
 #[derive(Clone)]
 pub struct CloudwatchLogsPartitionSvc {
     config: CloudwatchLogsSinkConfig,
-    clients: HashMap<CloudwatchKey, Svc>,
+    svcs: HashMap<CloudwatchKey, Svc>,
     request_settings: TowerRequestSettings,
-    client: CloudwatchLogsClient,
-    smithy_client: SmithyClient,
+    clients: RegionFailover<(CloudwatchLogsClient, SmithyClient)>,
 }
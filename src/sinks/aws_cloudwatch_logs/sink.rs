@@ -17,12 +17,18 @@ use crate::{
             request_builder::{CloudwatchRequest, CloudwatchRequestBuilder},
             CloudwatchKey,
         },
-        util::SinkBuilderExt,
+        util::{
+            adaptive_batching::{
+                AdaptiveBatchController, AdaptiveBatchRequest, AdaptiveBatchService,
+            },
+            SinkBuilderExt,
+        },
     },
 };
 
 pub struct CloudwatchSink<S> {
     pub batcher_settings: BatcherSettings,
+    pub adaptive_batching: Option<AdaptiveBatchController<CloudwatchKey>>,
     pub(super) request_builder: CloudwatchRequestBuilder,
     pub service: S,
 }
@@ -32,14 +38,15 @@ where
     S: Service<BatchCloudwatchRequest> + Send + 'static,
     S::Future: Send + 'static,
     S::Response: DriverResponse + Send + 'static,
-    S::Error: fmt::Debug + Into<crate::Error> + Send,
+    S::Error: fmt::Debug + Into<crate::Error> + Send + 'static,
 {
     async fn run_inner(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
         let mut request_builder = self.request_builder;
         let batcher_settings = self.batcher_settings;
-        let service = self.service;
+        let adaptive_batching = self.adaptive_batching;
+        let service = AdaptiveBatchService::new(self.service, adaptive_batching.clone());
 
-        input
+        let batches = input
             .filter_map(|event| future::ready(request_builder.build(event)))
             .filter(|req| {
                 let now = Utc::now();
@@ -48,8 +55,16 @@ where
                 let age_range = start..end;
                 future::ready(age_range.contains(&req.timestamp))
             })
-            .batched_partitioned(CloudwatchParititoner, batcher_settings)
-            .map(|(key, events)| BatchCloudwatchRequest { key, events })
+            .batched_partitioned(CloudwatchParititoner, batcher_settings);
+        let batches = match adaptive_batching {
+            Some(controller) => batches.with_partition_limits(move |key| controller.limits(key)),
+            None => batches,
+        };
+
+        batches
+            .map(|(key, events)| {
+                AdaptiveBatchRequest::new(key.clone(), BatchCloudwatchRequest { key, events })
+            })
             .into_driver(service)
             .run()
             .await
@@ -85,7 +100,7 @@ where
     S: Service<BatchCloudwatchRequest> + Send + 'static,
     S::Future: Send + 'static,
     S::Response: DriverResponse + Send + 'static,
-    S::Error: fmt::Debug + Into<crate::Error> + Send,
+    S::Error: fmt::Debug + Into<crate::Error> + Send + 'static,
 {
     async fn run(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
         self.run_inner(input).await
@@ -106,6 +106,8 @@ impl ClientBuilder for CloudwatchMetricsClientBuilder {
     type Client = aws_sdk_cloudwatch::client::Client;
     type DefaultMiddleware = aws_sdk_cloudwatch::middleware::DefaultMiddleware;
 
+    const SERVICE_NAME: &'static str = "monitoring";
+
     fn default_middleware() -> Self::DefaultMiddleware {
         aws_sdk_cloudwatch::middleware::DefaultMiddleware::new()
     }
@@ -165,6 +167,7 @@ impl CloudWatchMetricsSinkConfig {
             &self.auth,
             region,
             self.region.endpoint()?,
+            self.region.endpoint_variant,
             proxy,
             &self.tls,
             true,
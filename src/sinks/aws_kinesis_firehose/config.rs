@@ -14,12 +14,12 @@ use super::{
     sink::KinesisSink,
 };
 use crate::{
-    aws::{create_client, is_retriable_error, AwsAuthentication, ClientBuilder, RegionOrEndpoint},
-    codecs::{Encoder, EncodingConfig},
-    config::{
-        AcknowledgementsConfig, DataType, GenerateConfig, Input, ProxyConfig, SinkConfig,
-        SinkContext,
+    aws::{
+        create_failover_clients, is_retriable_error, AwsAuthentication, ClientBuilder,
+        FailoverConfig, RegionOrEndpoint,
     },
+    codecs::{Encoder, EncodingConfig},
+    config::{AcknowledgementsConfig, DataType, GenerateConfig, Input, SinkConfig, SinkContext},
     sinks::{
         util::{
             retries::RetryLogic, BatchConfig, Compression, ServiceBuilderExt, SinkBatchSettings,
@@ -57,6 +57,10 @@ pub struct KinesisFirehoseSinkConfig {
     #[serde(flatten)]
     pub region: RegionOrEndpoint,
 
+    #[configurable(derived)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub failover: Option<FailoverConfig>,
+
     #[configurable(derived)]
     pub encoding: EncodingConfig,
 
@@ -131,6 +135,8 @@ impl ClientBuilder for KinesisFirehoseClientBuilder {
     type Client = aws_sdk_firehose::client::Client;
     type DefaultMiddleware = aws_sdk_firehose::middleware::DefaultMiddleware;
 
+    const SERVICE_NAME: &'static str = "firehose";
+
     fn default_middleware() -> Self::DefaultMiddleware {
         aws_sdk_firehose::middleware::DefaultMiddleware::new()
     }
@@ -143,8 +149,16 @@ impl ClientBuilder for KinesisFirehoseClientBuilder {
 #[async_trait::async_trait]
 impl SinkConfig for KinesisFirehoseSinkConfig {
     async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
-        let client = self.create_client(&cx.proxy).await?;
-        let healthcheck = self.clone().healthcheck(client.clone()).boxed();
+        let clients = create_failover_clients::<KinesisFirehoseClientBuilder>(
+            &self.auth,
+            &self.region,
+            self.failover.as_ref(),
+            &cx.proxy,
+            &self.tls,
+            true,
+        )
+        .await?;
+        let healthcheck = self.clone().healthcheck(clients.primary()).boxed();
 
         let batch_settings = self
             .batch
@@ -159,7 +173,7 @@ impl SinkConfig for KinesisFirehoseSinkConfig {
         let service = ServiceBuilder::new()
             .settings(request_limits, KinesisRetryLogic)
             .service(KinesisService {
-                client,
+                clients,
                 region,
                 stream_name: self.stream_name.clone(),
             });
@@ -217,18 +231,6 @@ impl KinesisFirehoseSinkConfig {
             Err(source) => Err(HealthcheckError::DescribeDeliveryStreamFailed { source }.into()),
         }
     }
-
-    pub async fn create_client(&self, proxy: &ProxyConfig) -> crate::Result<KinesisFirehoseClient> {
-        create_client::<KinesisFirehoseClientBuilder>(
-            &self.auth,
-            self.region.region(),
-            self.region.endpoint()?,
-            proxy,
-            &self.tls,
-            true,
-        )
-        .await
-    }
 }
 
 #[derive(Clone)]
@@ -46,6 +46,7 @@ async fn firehose_put_records() {
     let config = KinesisFirehoseSinkConfig {
         stream_name: stream.clone(),
         region: region.clone(),
+        failover: None,
         encoding: JsonSerializerConfig::new().into(), // required for ES destination w/ localstack
         compression: Compression::None,
         batch,
@@ -135,6 +136,7 @@ async fn firehose_client() -> aws_sdk_firehose::Client {
         &auth,
         region_endpoint.region(),
         region_endpoint.endpoint().unwrap(),
+        None,
         &proxy,
         &None,
         true,
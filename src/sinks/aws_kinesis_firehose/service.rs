@@ -9,11 +9,11 @@ use tracing::Instrument;
 use vector_core::{internal_event::CountByteSize, stream::DriverResponse};
 
 use super::request_builder::KinesisRequest;
-use crate::event::EventStatus;
+use crate::{aws::RegionFailover, event::EventStatus};
 
 #[derive(Clone)]
 pub struct KinesisService {
-    pub client: KinesisFirehoseClient,
+    pub clients: RegionFailover<KinesisFirehoseClient>,
     pub region: Option<Region>,
     pub stream_name: String,
 }
@@ -52,17 +52,20 @@ impl Service<Vec<KinesisRequest>> for KinesisService {
 
         let records = requests.into_iter().map(|req| req.record).collect();
 
-        let client = self.client.clone();
+        let clients = self.clients.clone();
+        let (index, client) = clients.select();
 
         let stream_name = self.stream_name.clone();
         Box::pin(async move {
-            client
+            let result = client
                 .put_record_batch()
                 .set_records(Some(records))
                 .delivery_stream_name(stream_name)
                 .send()
                 .instrument(info_span!("request").or_current())
-                .await?;
+                .await;
+            clients.report(index, &result);
+            result?;
 
             Ok(KinesisResponse {
                 events_byte_size,
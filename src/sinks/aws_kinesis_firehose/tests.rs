@@ -28,6 +28,7 @@ async fn check_batch_size() {
     let config = KinesisFirehoseSinkConfig {
         stream_name: String::from("test"),
         region: RegionOrEndpoint::with_both("local", "http://localhost:4566"),
+        failover: None,
         encoding: JsonSerializerConfig::new().into(),
         compression: Compression::None,
         batch,
@@ -56,6 +57,7 @@ async fn check_batch_events() {
     let config = KinesisFirehoseSinkConfig {
         stream_name: String::from("test"),
         region: RegionOrEndpoint::with_both("local", "http://localhost:4566"),
+        failover: None,
         encoding: JsonSerializerConfig::new().into(),
         compression: Compression::None,
         batch,
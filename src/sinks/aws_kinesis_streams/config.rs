@@ -13,12 +13,12 @@ use super::{
     sink::KinesisSink,
 };
 use crate::{
-    aws::{create_client, is_retriable_error, AwsAuthentication, ClientBuilder, RegionOrEndpoint},
-    codecs::{Encoder, EncodingConfig},
-    config::{
-        AcknowledgementsConfig, DataType, GenerateConfig, Input, ProxyConfig, SinkConfig,
-        SinkContext,
+    aws::{
+        create_failover_clients, is_retriable_error, AwsAuthentication, ClientBuilder,
+        FailoverConfig, RegionOrEndpoint,
     },
+    codecs::{Encoder, EncodingConfig},
+    config::{AcknowledgementsConfig, DataType, GenerateConfig, Input, SinkConfig, SinkContext},
     sinks::{
         util::{
             retries::RetryLogic, BatchConfig, Compression, ServiceBuilderExt, SinkBatchSettings,
@@ -52,6 +52,8 @@ impl ClientBuilder for KinesisClientBuilder {
     type Client = aws_sdk_kinesis::client::Client;
     type DefaultMiddleware = aws_sdk_kinesis::middleware::DefaultMiddleware;
 
+    const SERVICE_NAME: &'static str = "kinesis";
+
     fn default_middleware() -> Self::DefaultMiddleware {
         aws_sdk_kinesis::middleware::DefaultMiddleware::new()
     }
@@ -88,6 +90,10 @@ pub struct KinesisSinkConfig {
     #[serde(flatten)]
     pub region: RegionOrEndpoint,
 
+    #[configurable(derived)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub failover: Option<FailoverConfig>,
+
     #[configurable(derived)]
     pub encoding: EncodingConfig,
 
@@ -146,25 +152,21 @@ impl KinesisSinkConfig {
             Err(source) => Err(HealthcheckError::DescribeStreamFailed { source }.into()),
         }
     }
-
-    pub async fn create_client(&self, proxy: &ProxyConfig) -> crate::Result<KinesisClient> {
-        create_client::<KinesisClientBuilder>(
-            &self.auth,
-            self.region.region(),
-            self.region.endpoint()?,
-            proxy,
-            &self.tls,
-            true,
-        )
-        .await
-    }
 }
 
 #[async_trait::async_trait]
 impl SinkConfig for KinesisSinkConfig {
     async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
-        let client = self.create_client(&cx.proxy).await?;
-        let healthcheck = self.clone().healthcheck(client.clone()).boxed();
+        let clients = create_failover_clients::<KinesisClientBuilder>(
+            &self.auth,
+            &self.region,
+            self.failover.as_ref(),
+            &cx.proxy,
+            &self.tls,
+            true,
+        )
+        .await?;
+        let healthcheck = self.clone().healthcheck(clients.primary()).boxed();
 
         let batch_settings = self.batch.into_batcher_settings()?;
 
@@ -174,7 +176,7 @@ impl SinkConfig for KinesisSinkConfig {
         let service = ServiceBuilder::new()
             .settings(request_settings, KinesisRetryLogic)
             .service(KinesisService {
-                client,
+                clients,
                 stream_name: self.stream_name.clone(),
                 region,
             });
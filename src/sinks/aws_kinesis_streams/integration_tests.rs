@@ -36,6 +36,7 @@ async fn kinesis_put_records() {
         stream_name: stream.clone(),
         partition_key_field: None,
         region: RegionOrEndpoint::with_both("localstack", kinesis_address().as_str()),
+        failover: None,
         encoding: TextSerializerConfig::new().into(),
         compression: Compression::None,
         batch,
@@ -114,6 +115,7 @@ async fn client() -> aws_sdk_kinesis::Client {
         &auth,
         region.region(),
         region.endpoint().unwrap(),
+        None,
         &proxy,
         &None,
         true,
@@ -10,11 +10,14 @@ use tower::Service;
 use tracing::Instrument;
 use vector_core::{internal_event::CountByteSize, stream::DriverResponse};
 
-use crate::{event::EventStatus, sinks::aws_kinesis_streams::request_builder::KinesisRequest};
+use crate::{
+    aws::RegionFailover, event::EventStatus,
+    sinks::aws_kinesis_streams::request_builder::KinesisRequest,
+};
 
 #[derive(Clone)]
 pub struct KinesisService {
-    pub client: KinesisClient,
+    pub clients: RegionFailover<KinesisClient>,
     pub stream_name: String,
     pub region: Option<Region>,
 }
@@ -56,17 +59,20 @@ impl Service<Vec<KinesisRequest>> for KinesisService {
             .map(|req| req.put_records_request)
             .collect();
 
-        let client = self.client.clone();
+        let clients = self.clients.clone();
+        let (index, client) = clients.select();
 
         let stream_name = self.stream_name.clone();
         Box::pin(async move {
-            let _response: PutRecordsOutput = client
+            let result = client
                 .put_records()
                 .set_records(Some(records))
                 .stream_name(stream_name)
                 .send()
                 .instrument(info_span!("request").or_current())
-                .await?;
+                .await;
+            clients.report(index, &result);
+            let _response: PutRecordsOutput = result?;
 
             Ok(KinesisResponse {
                 count,
@@ -11,7 +11,7 @@ use vector_core::sink::VectorSink;
 
 use super::sink::S3RequestOptions;
 use crate::{
-    aws::{AwsAuthentication, RegionOrEndpoint},
+    aws::{AwsAuthentication, EndpointVariant, FailoverConfig, RegionOrEndpoint},
     codecs::{Encoder, EncodingConfigWithFraming, SinkType},
     config::{
         AcknowledgementsConfig, DataType, GenerateConfig, Input, ProxyConfig, SinkConfig,
@@ -25,8 +25,10 @@ use crate::{
             sink::S3Sink,
         },
         util::{
-            partitioner::KeyPartitioner, BatchConfig, BulkSizeBasedDefaultBatchSettings,
-            Compression, ServiceBuilderExt, TowerRequestConfig,
+            adaptive_batching::{AdaptiveBatchController, AdaptiveBatchSettings},
+            partitioner::KeyPartitioner,
+            BatchConfig, BulkSizeBasedDefaultBatchSettings, Compression, ServiceBuilderExt,
+            TowerRequestConfig,
         },
         Healthcheck,
     },
@@ -92,6 +94,17 @@ pub struct S3SinkConfig {
     #[serde(flatten)]
     pub region: RegionOrEndpoint,
 
+    #[configurable(derived)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub failover: Option<FailoverConfig>,
+
+    /// Whether or not to use S3 Transfer Acceleration.
+    ///
+    /// Transfer acceleration must be enabled on the bucket, and the bucket name must not contain
+    /// dots. Can't be combined with a custom `endpoint` or the `fips` endpoint variant.
+    #[serde(default)]
+    pub accelerate: bool,
+
     #[serde(flatten)]
     pub encoding: EncodingConfigWithFraming,
 
@@ -103,6 +116,14 @@ pub struct S3SinkConfig {
     #[serde(default)]
     pub batch: BatchConfig<BulkSizeBasedDefaultBatchSettings>,
 
+    /// Adjusts the size of the batches of each key prefix to the latency of its requests.
+    ///
+    /// When set, the batch limits are the size of the largest batches sent to each key prefix,
+    /// and the batches of key prefixes whose requests are slow or fail are made smaller, instead of
+    /// always using the batch limits.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub adaptive_batching: Option<AdaptiveBatchSettings>,
+
     #[configurable(derived)]
     #[serde(default)]
     pub request: TowerRequestConfig,
@@ -133,9 +154,12 @@ impl GenerateConfig for S3SinkConfig {
             filename_extension: None,
             options: S3Options::default(),
             region: RegionOrEndpoint::default(),
+            failover: None,
+            accelerate: false,
             encoding: (None::<FramingConfig>, TextSerializerConfig::new()).into(),
             compression: Compression::gzip_default(),
             batch: BatchConfig::default(),
+            adaptive_batching: None,
             request: TowerRequestConfig::default(),
             tls: Some(TlsConfig::default()),
             auth: AwsAuthentication::default(),
@@ -149,7 +173,16 @@ impl GenerateConfig for S3SinkConfig {
 impl SinkConfig for S3SinkConfig {
     async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
         let service = self.create_service(&cx.proxy).await?;
-        let healthcheck = self.build_healthcheck(service.client())?;
+        let healthcheck = if self.accelerate {
+            // Buckets are checked through the regular endpoint, which accepts requests
+            // addressing the bucket in the path.
+            let service =
+                s3_common::config::create_service(&self.region, &self.auth, &cx.proxy, &self.tls)
+                    .await?;
+            self.build_healthcheck(service.client())?
+        } else {
+            self.build_healthcheck(service.client())?
+        };
         let sink = self.build_processor(service)?;
         Ok((sink, healthcheck))
     }
@@ -176,6 +209,10 @@ impl S3SinkConfig {
 
         // Configure our partitioning/batching.
         let batch_settings = self.batch.into_batcher_settings()?;
+        let adaptive_batching = self
+            .adaptive_batching
+            .map(|settings| AdaptiveBatchController::new(settings, batch_settings))
+            .transpose()?;
         let key_prefix = self
             .key_prefix
             .as_ref()
@@ -208,7 +245,8 @@ impl S3SinkConfig {
             compression: self.compression,
         };
 
-        let sink = S3Sink::new(service, request_options, partitioner, batch_settings);
+        let sink = S3Sink::new(service, request_options, partitioner, batch_settings)
+            .with_adaptive_batching(adaptive_batching);
 
         Ok(VectorSink::from_event_streamsink(sink))
     }
@@ -218,16 +256,92 @@ impl S3SinkConfig {
     }
 
     pub async fn create_service(&self, proxy: &ProxyConfig) -> crate::Result<S3Service> {
-        s3_common::config::create_service(&self.region, &self.auth, proxy, &self.tls).await
+        if !self.accelerate {
+            return s3_common::config::create_failover_service(
+                &self.region,
+                self.failover.as_ref(),
+                &self.auth,
+                proxy,
+                &self.tls,
+            )
+            .await;
+        }
+        if self.failover.is_some() {
+            return Err("`accelerate` can't be combined with `failover`.".into());
+        }
+
+        let region = RegionOrEndpoint {
+            region: self.region.region.clone(),
+            endpoint: Some(self.accelerate_endpoint()?),
+            endpoint_variant: None,
+        };
+        let service =
+            s3_common::config::create_service(&region, &self.auth, proxy, &self.tls).await?;
+        Ok(S3Service::with_bucket_in_host(service.client()))
+    }
+
+    fn accelerate_endpoint(&self) -> crate::Result<String> {
+        if self.region.endpoint.is_some() {
+            return Err("`accelerate` can't be combined with a custom `endpoint`.".into());
+        }
+        let dualstack = match self.region.endpoint_variant {
+            None => "",
+            Some(EndpointVariant::Dualstack) => ".dualstack",
+            Some(EndpointVariant::Fips) => {
+                return Err(
+                    "`accelerate` can't be combined with the `fips` endpoint variant.".into(),
+                )
+            }
+        };
+        if self.bucket.contains('.') {
+            return Err("`accelerate` requires a bucket name without dots.".into());
+        }
+
+        Ok(format!(
+            "https://{}.s3-accelerate{}.amazonaws.com",
+            self.bucket, dualstack
+        ))
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::S3SinkConfig;
+    use super::*;
 
     #[test]
     fn generate_config() {
         crate::test_util::test_generate_config::<S3SinkConfig>();
     }
+
+    fn accelerated(bucket: &str, extra: &str) -> S3SinkConfig {
+        toml::from_str(&format!(
+            r#"
+            bucket = "{}"
+            region = "us-east-1"
+            accelerate = true
+            encoding.codec = "text"
+            {}
+        "#,
+            bucket, extra
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn accelerate_endpoints() {
+        assert_eq!(
+            accelerated("logs", "").accelerate_endpoint().unwrap(),
+            "https://logs.s3-accelerate.amazonaws.com"
+        );
+        assert_eq!(
+            accelerated("logs", r#"endpoint_variant = "dualstack""#)
+                .accelerate_endpoint()
+                .unwrap(),
+            "https://logs.s3-accelerate.dualstack.amazonaws.com"
+        );
+        assert!(accelerated("logs", r#"endpoint_variant = "fips""#)
+            .accelerate_endpoint()
+            .is_err());
+        assert!(accelerated("my.logs", "").accelerate_endpoint().is_err());
+    }
 }
@@ -330,6 +330,7 @@ async fn client() -> S3Client {
         &auth,
         region.region(),
         region.endpoint().unwrap(),
+        None,
         &proxy,
         &tls_options,
         true,
@@ -351,9 +352,12 @@ fn config(bucket: &str, batch_size: usize) -> S3SinkConfig {
         filename_extension: None,
         options: S3Options::default(),
         region: RegionOrEndpoint::with_both("minio", s3_address()),
+        failover: None,
+        accelerate: false,
         encoding: (None::<FramingConfig>, TextSerializerConfig::new()).into(),
         compression: Compression::None,
         batch,
+        adaptive_batching: None,
         request: TowerRequestConfig::default(),
         tls: Default::default(),
         auth: Default::default(),
@@ -143,6 +143,7 @@ impl SqsSinkConfig {
             &self.auth,
             self.region.region(),
             self.region.endpoint()?,
+            self.region.endpoint_variant,
             proxy,
             &self.tls,
             true,
@@ -33,6 +33,7 @@ async fn create_test_client() -> SqsClient {
         &auth,
         Some(Region::new("localstack")),
         Some(Endpoint::immutable(Uri::from_str(&endpoint).unwrap())),
+        None,
         &proxy,
         &None,
         true,
@@ -1,26 +1,46 @@
-use std::collections::HashMap;
-
+use codecs::{
+    encoding::{FramingConfig, SerializerConfig},
+    JsonSerializerConfig, NewlineDelimitedEncoderConfig,
+};
+use futures::FutureExt;
+use http::{Request, StatusCode};
+use hyper::Body;
+use percent_encoding::{utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
 use vector_common::sensitive_string::SensitiveString;
 use vector_config::configurable_component;
 
 use crate::{
+    codecs::{EncodingConfigWithFraming, Transformer},
     config::{
         log_schema, AcknowledgementsConfig, DataType, GenerateConfig, Input, SinkConfig,
         SinkContext,
     },
+    http::{Auth, HttpClient},
     sinks::{
-        elasticsearch::{ElasticsearchAuth, ElasticsearchConfig},
-        util::{http::RequestConfig, Compression},
-        Healthcheck, VectorSink,
+        http::{HttpMethod, HttpSinkConfig},
+        util::{
+            http::RequestConfig, BatchConfig, Compression, RealtimeSizeBasedDefaultBatchSettings,
+            UriSerde,
+        },
+        Healthcheck, HealthcheckError, VectorSink,
     },
-    tls::TlsConfig,
+    tls::{TlsConfig, TlsSettings},
 };
 
 static CLOUD_URL: &str = "https://cloud.axiom.co";
 
+/// The characters encoded in a path segment, which are all but the unreserved characters of
+/// RFC 3986.
+const PATH_SEGMENT: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'-')
+    .remove(b'.')
+    .remove(b'_')
+    .remove(b'~');
+
 /// Configuration for the `axiom` sink.
 #[configurable_component(sink("axiom"))]
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, Debug, Derivative)]
+#[derivative(Default)]
 pub struct AxiomConfig {
     /// URI of the Axiom endpoint to send data to.
     ///
@@ -43,9 +63,23 @@ pub struct AxiomConfig {
     #[serde(default)]
     request: RequestConfig,
 
+    /// The compression of the requests.
+    ///
+    /// Requests are compressed with gzip by default.
+    #[serde(default = "Compression::gzip_default")]
+    #[derivative(Default(value = "Compression::gzip_default()"))]
+    compression: Compression,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    encoding: Transformer,
+
     #[configurable(derived)]
     #[serde(default)]
-    compression: Compression,
+    batch: BatchConfig<RealtimeSizeBasedDefaultBatchSettings>,
 
     #[configurable(derived)]
     tls: Option<TlsConfig>,
@@ -75,35 +109,41 @@ impl GenerateConfig for AxiomConfig {
 impl SinkConfig for AxiomConfig {
     async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
         let mut request = self.request.clone();
-        request.headers.insert(
-            "X-Axiom-Org-Id".to_string(),
-            self.org_id.clone().unwrap_or_default(),
-        );
-        let mut query = HashMap::with_capacity(1);
-        query.insert(
-            "timestamp-field".to_string(),
-            log_schema().timestamp_key().to_string(),
-        );
+        if let Some(org_id) = &self.org_id {
+            request
+                .headers
+                .insert("X-Axiom-Org-Id".to_string(), org_id.clone());
+        }
 
-        // Axiom has a custom high-performance database that can be ingested
-        // into using our HTTP endpoints, including one compatible with the
-        // Elasticsearch Bulk API.
-        // This configuration wraps the Elasticsearch config to minimize the
-        // amount of code.
-        let elasticsearch_config = ElasticsearchConfig {
-            endpoints: vec![self.build_endpoint()],
+        // Axiom ingests newline delimited JSON, so this configuration wraps the HTTP sink
+        // config to minimize the amount of code.
+        let http_config = HttpSinkConfig {
+            uri: self.ingest_uri().parse()?,
+            method: Some(HttpMethod::Post),
+            auth: Some(self.auth()),
+            headers: None,
             compression: self.compression,
-            auth: Some(ElasticsearchAuth::Basic {
-                user: "axiom".to_string(),
-                password: self.token.clone(),
-            }),
-            query: Some(query),
+            encoding: EncodingConfigWithFraming::new(
+                Some(FramingConfig::from(NewlineDelimitedEncoderConfig::new())),
+                SerializerConfig::from(JsonSerializerConfig::new()),
+                self.encoding.clone(),
+            ),
+            batch: self.batch,
+            request: request.clone(),
             tls: self.tls.clone(),
-            request,
-            ..Default::default()
+            acknowledgements: self.acknowledgements,
         };
 
-        elasticsearch_config.build(cx).await
+        let healthcheck = healthcheck(
+            self.dataset_uri().parse()?,
+            self.auth(),
+            request,
+            HttpClient::new(TlsSettings::from_options(&self.tls)?, cx.proxy())?,
+        )
+        .boxed();
+        let (sink, _) = http_config.build(cx).await?;
+
+        Ok((sink, healthcheck))
     }
 
     fn input(&self) -> Input {
@@ -116,22 +156,115 @@ impl SinkConfig for AxiomConfig {
 }
 
 impl AxiomConfig {
-    fn build_endpoint(&self) -> String {
-        let url = if let Some(url) = self.url.as_ref() {
-            url.clone()
-        } else {
-            CLOUD_URL.to_string()
-        };
+    fn url(&self) -> &str {
+        self.url
+            .as_deref()
+            .unwrap_or(CLOUD_URL)
+            .trim_end_matches('/')
+    }
 
-        format!("{}/api/v1/datasets/{}/elastic", url, self.dataset)
+    fn dataset_uri(&self) -> String {
+        format!(
+            "{}/api/v1/datasets/{}",
+            self.url(),
+            utf8_percent_encode(&self.dataset, PATH_SEGMENT)
+        )
+    }
+
+    /// The URI of the ingest endpoint, which names the timestamp field of the events so that
+    /// Axiom uses it as the time of the events.
+    fn ingest_uri(&self) -> String {
+        let timestamp_field = utf8_percent_encode(log_schema().timestamp_key(), NON_ALPHANUMERIC);
+        format!(
+            "{}/ingest?timestamp-field={}",
+            self.dataset_uri(),
+            timestamp_field
+        )
+    }
+
+    fn auth(&self) -> Auth {
+        Auth::Bearer {
+            token: self.token.clone(),
+        }
+    }
+}
+
+async fn healthcheck(
+    uri: UriSerde,
+    auth: Auth,
+    request: RequestConfig,
+    client: HttpClient,
+) -> crate::Result<()> {
+    let mut builder = Request::get(&uri.uri);
+    for (name, value) in &request.headers {
+        builder = builder.header(name.as_str(), value.as_str());
+    }
+    let mut request = builder.body(Body::empty())?;
+    auth.apply(&mut request);
+
+    let response = client.send(request).await?;
+
+    match response.status() {
+        StatusCode::OK => Ok(()),
+        status => Err(HealthcheckError::UnexpectedStatus { status }.into()),
     }
 }
 
 #[cfg(test)]
 mod test {
+    use super::*;
+
     #[test]
     fn generate_config() {
-        crate::test_util::test_generate_config::<super::AxiomConfig>();
+        crate::test_util::test_generate_config::<AxiomConfig>();
+    }
+
+    #[test]
+    fn defaults_to_gzip() {
+        let config: AxiomConfig = toml::from_str(
+            r#"token = "xxxx"
+            dataset = "logs""#,
+        )
+        .unwrap();
+
+        assert_eq!(config.compression, Compression::gzip_default());
+        assert_eq!(AxiomConfig::default().compression, config.compression);
+    }
+
+    #[test]
+    fn builds_ingest_uri() {
+        let config = AxiomConfig {
+            url: Some("https://axiom.example.com/".to_owned()),
+            dataset: "logs".to_owned(),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            config.ingest_uri(),
+            "https://axiom.example.com/api/v1/datasets/logs/ingest?timestamp-field=timestamp"
+        );
+
+        let config = AxiomConfig {
+            dataset: "logs".to_owned(),
+            ..Default::default()
+        };
+        assert_eq!(
+            config.dataset_uri(),
+            "https://cloud.axiom.co/api/v1/datasets/logs"
+        );
+    }
+
+    #[test]
+    fn encodes_dataset_in_uri() {
+        let config = AxiomConfig {
+            dataset: "my-logs/2022 #1".to_owned(),
+            ..Default::default()
+        };
+        assert_eq!(
+            config.dataset_uri(),
+            "https://cloud.axiom.co/api/v1/datasets/my-logs%2F2022%20%231"
+        );
+        config.ingest_uri().parse::<http::Uri>().unwrap();
     }
 }
 
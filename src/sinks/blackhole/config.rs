@@ -1,4 +1,5 @@
 use futures::{future, FutureExt};
+use snafu::Snafu;
 use vector_config::configurable_component;
 
 use crate::{
@@ -28,6 +29,26 @@ pub struct BlackholeConfig {
     /// By default, there is no limit.
     pub rate: Option<usize>,
 
+    /// Artificial latency applied to each batch of events before it is acknowledged.
+    ///
+    /// Batches are handled one at a time, so the latency also limits the rate at which events
+    /// are consumed. By default, events are acknowledged as soon as they are consumed.
+    #[configurable(derived)]
+    pub latency: Option<LatencyConfig>,
+
+    /// The fraction of batches of events, between `0.0` and `1.0`, rejected instead of being
+    /// acknowledged.
+    ///
+    /// Sources waiting on the acknowledgement of rejected events are told that their delivery
+    /// failed.
+    pub error_rate: f64,
+
+    /// Periods during which the sink stops consuming events altogether.
+    ///
+    /// By default, the sink consumes events continuously.
+    #[configurable(derived)]
+    pub backpressure: Option<BackpressureConfig>,
+
     #[configurable(derived)]
     #[serde(
         default,
@@ -37,10 +58,76 @@ pub struct BlackholeConfig {
     pub acknowledgements: AcknowledgementsConfig,
 }
 
+/// Distribution of the artificial latency applied to batches of events.
+#[configurable_component]
+#[derive(Clone, Copy, Debug)]
+#[serde(tag = "distribution", rename_all = "snake_case")]
+pub enum LatencyConfig {
+    /// The same latency for every batch.
+    Constant {
+        /// The latency, in milliseconds.
+        ms: u64,
+    },
+
+    /// A latency picked uniformly between two bounds.
+    Uniform {
+        /// The lowest latency, in milliseconds.
+        min_ms: u64,
+
+        /// The highest latency, in milliseconds.
+        max_ms: u64,
+    },
+
+    /// A latency following a normal distribution, with negative latencies rounded up to zero.
+    Normal {
+        /// The mean latency, in milliseconds.
+        mean_ms: f64,
+
+        /// The standard deviation of the latency, in milliseconds.
+        std_dev_ms: f64,
+    },
+
+    /// A latency following an exponential distribution, where most batches are handled quickly
+    /// but a few take much longer.
+    Exponential {
+        /// The mean latency, in milliseconds.
+        mean_ms: f64,
+    },
+}
+
+/// Configuration of the periods during which the sink stops consuming events.
+#[configurable_component]
+#[derive(Clone, Copy, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct BackpressureConfig {
+    /// The number of seconds between the start of two periods of backpressure.
+    pub interval_secs: u64,
+
+    /// The number of seconds each period of backpressure lasts.
+    pub duration_secs: u64,
+}
+
+#[derive(Debug, PartialEq, Snafu)]
+pub enum BuildError {
+    #[snafu(display("Error rate must be between 0.0 and 1.0, got {}", error_rate))]
+    InvalidErrorRate { error_rate: f64 },
+    #[snafu(display("Invalid latency distribution: {}", reason))]
+    InvalidLatency { reason: &'static str },
+    #[snafu(display(
+        "Backpressure interval must be longer than its duration, got {} and {} seconds",
+        interval_secs,
+        duration_secs
+    ))]
+    InvalidBackpressure {
+        interval_secs: u64,
+        duration_secs: u64,
+    },
+}
+
 #[async_trait::async_trait]
 impl SinkConfig for BlackholeConfig {
     async fn build(&self, _cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
-        let sink = BlackholeSink::new(self.clone());
+        let sink = BlackholeSink::new(self.clone())?;
         let healthcheck = future::ok(()).boxed();
 
         Ok((VectorSink::Stream(Box::new(sink)), healthcheck))
@@ -7,8 +7,12 @@ pub use config::BlackholeConfig;
 mod tests {
 
     use crate::{
+        event::{BatchNotifier, BatchStatus},
         sinks::{
-            blackhole::{config::BlackholeConfig, sink::BlackholeSink},
+            blackhole::{
+                config::{BlackholeConfig, BuildError, LatencyConfig},
+                sink::BlackholeSink,
+            },
             VectorSink,
         },
         test_util::{
@@ -21,12 +25,57 @@ mod tests {
         let config = BlackholeConfig {
             print_interval_secs: 10,
             rate: None,
+            latency: None,
+            error_rate: 0.0,
+            backpressure: None,
             acknowledgements: Default::default(),
         };
-        let sink = BlackholeSink::new(config);
+        let sink = BlackholeSink::new(config).unwrap();
         let sink = VectorSink::Stream(Box::new(sink));
 
         let (_input_lines, events) = random_events_with_stream(100, 10, None);
         run_and_assert_nonsending_sink_compliance(sink, events, &[]).await;
     }
+
+    #[tokio::test]
+    async fn blackhole_rejects_events() {
+        let config = BlackholeConfig {
+            print_interval_secs: 0,
+            latency: Some(LatencyConfig::Constant { ms: 10 }),
+            error_rate: 1.0,
+            ..Default::default()
+        };
+        let sink = BlackholeSink::new(config).unwrap();
+        let sink = VectorSink::Stream(Box::new(sink));
+
+        let (batch, mut receiver) = BatchNotifier::new_with_receiver();
+        let (_input_lines, events) = random_events_with_stream(100, 10, Some(batch));
+        sink.run(events).await.unwrap();
+
+        assert_eq!(receiver.try_recv(), Ok(BatchStatus::Rejected));
+    }
+
+    #[test]
+    fn blackhole_rejects_invalid_faults() {
+        let config = BlackholeConfig {
+            error_rate: 1.5,
+            ..Default::default()
+        };
+        assert_eq!(
+            BlackholeSink::new(config).err(),
+            Some(BuildError::InvalidErrorRate { error_rate: 1.5 })
+        );
+
+        let config = BlackholeConfig {
+            latency: Some(LatencyConfig::Uniform {
+                min_ms: 20,
+                max_ms: 10,
+            }),
+            ..Default::default()
+        };
+        assert!(matches!(
+            BlackholeSink::new(config),
+            Err(BuildError::InvalidLatency { .. })
+        ));
+    }
 }
@@ -8,16 +8,25 @@ use std::{
 
 use async_trait::async_trait;
 use futures::{stream::BoxStream, StreamExt};
+use rand::{
+    distributions::{Distribution, Uniform},
+    Rng,
+};
+use rand_distr::{Exp, Normal};
 use tokio::{
     select,
     sync::watch,
-    time::{interval, sleep_until},
+    time::{interval, sleep, sleep_until},
 };
 use vector_core::{internal_event::EventsSent, ByteSizeOf};
 
 use crate::{
-    event::{EventArray, EventContainer},
-    sinks::{blackhole::config::BlackholeConfig, util::StreamSink},
+    event::{EventArray, EventContainer, EventStatus, Finalizable},
+    internal_events::BlackholeEventsRejected,
+    sinks::{
+        blackhole::config::{BlackholeConfig, BuildError, LatencyConfig},
+        util::StreamSink,
+    },
 };
 
 pub struct BlackholeSink {
@@ -25,17 +34,113 @@ pub struct BlackholeSink {
     total_raw_bytes: Arc<AtomicUsize>,
     config: BlackholeConfig,
     last: Option<Instant>,
+    latency: Option<Latency>,
+    next_backpressure: Option<Instant>,
 }
 
 impl BlackholeSink {
-    pub fn new(config: BlackholeConfig) -> Self {
-        BlackholeSink {
+    pub fn new(config: BlackholeConfig) -> Result<Self, BuildError> {
+        if !(0.0..=1.0).contains(&config.error_rate) {
+            return Err(BuildError::InvalidErrorRate {
+                error_rate: config.error_rate,
+            });
+        }
+        let latency = config.latency.map(Latency::new).transpose()?;
+        let next_backpressure = match config.backpressure {
+            Some(backpressure) if backpressure.interval_secs <= backpressure.duration_secs => {
+                return Err(BuildError::InvalidBackpressure {
+                    interval_secs: backpressure.interval_secs,
+                    duration_secs: backpressure.duration_secs,
+                });
+            }
+            Some(backpressure) => {
+                Some(Instant::now() + Duration::from_secs(backpressure.interval_secs))
+            }
+            None => None,
+        };
+
+        Ok(BlackholeSink {
             config,
             total_events: Arc::new(AtomicUsize::new(0)),
             total_raw_bytes: Arc::new(AtomicUsize::new(0)),
             last: None,
+            latency,
+            next_backpressure,
+        })
+    }
+
+    /// Stops consuming events for the duration of a period of backpressure, if one is due.
+    async fn apply_backpressure(&mut self) {
+        if let (Some(backpressure), Some(next)) = (self.config.backpressure, self.next_backpressure)
+        {
+            let now = Instant::now();
+            if now >= next {
+                sleep(Duration::from_secs(backpressure.duration_secs)).await;
+                self.next_backpressure =
+                    Some(now + Duration::from_secs(backpressure.interval_secs));
+            }
         }
     }
+
+    fn should_reject(&self) -> bool {
+        self.config.error_rate > 0.0 && rand::thread_rng().gen_bool(self.config.error_rate)
+    }
+}
+
+/// The distribution artificial latencies are sampled from.
+enum Latency {
+    Constant(Duration),
+    Uniform(Uniform<u64>),
+    Normal(Normal<f64>),
+    Exponential(Exp<f64>),
+}
+
+impl Latency {
+    fn new(config: LatencyConfig) -> Result<Self, BuildError> {
+        match config {
+            LatencyConfig::Constant { ms } => Ok(Self::Constant(Duration::from_millis(ms))),
+            LatencyConfig::Uniform { min_ms, max_ms } if min_ms <= max_ms => {
+                Ok(Self::Uniform(Uniform::new_inclusive(min_ms, max_ms)))
+            }
+            LatencyConfig::Uniform { .. } => Err(BuildError::InvalidLatency {
+                reason: "`min_ms` must not be greater than `max_ms`",
+            }),
+            LatencyConfig::Normal {
+                mean_ms,
+                std_dev_ms,
+            } if mean_ms.is_finite() && std_dev_ms.is_finite() => Normal::new(mean_ms, std_dev_ms)
+                .map(Self::Normal)
+                .map_err(|_| BuildError::InvalidLatency {
+                    reason: "`std_dev_ms` must not be negative",
+                }),
+            LatencyConfig::Normal { .. } => Err(BuildError::InvalidLatency {
+                reason: "`mean_ms` and `std_dev_ms` must be finite",
+            }),
+            LatencyConfig::Exponential { mean_ms } if mean_ms > 0.0 && mean_ms.is_finite() => {
+                Exp::new(1.0 / mean_ms).map(Self::Exponential).map_err(|_| {
+                    BuildError::InvalidLatency {
+                        reason: "`mean_ms` must be positive",
+                    }
+                })
+            }
+            LatencyConfig::Exponential { .. } => Err(BuildError::InvalidLatency {
+                reason: "`mean_ms` must be positive",
+            }),
+        }
+    }
+
+    fn sample(&self) -> Duration {
+        let mut rng = rand::thread_rng();
+        let ms = match self {
+            Self::Constant(latency) => return *latency,
+            Self::Uniform(distribution) => {
+                return Duration::from_millis(distribution.sample(&mut rng))
+            }
+            Self::Normal(distribution) => distribution.sample(&mut rng),
+            Self::Exponential(distribution) => distribution.sample(&mut rng),
+        };
+        Duration::from_secs_f64(ms.max(0.0) / 1000.0)
+    }
 }
 
 #[async_trait]
@@ -73,7 +178,7 @@ impl StreamSink<EventArray> for BlackholeSink {
             });
         }
 
-        while let Some(events) = input.next().await {
+        while let Some(mut events) = input.next().await {
             if let Some(rate) = self.config.rate {
                 let factor: f32 = 1.0 / rate as f32;
                 let secs: f32 = factor * (events.len() as f32);
@@ -82,6 +187,22 @@ impl StreamSink<EventArray> for BlackholeSink {
                 self.last = Some(until);
             }
 
+            self.apply_backpressure().await;
+
+            if let Some(latency) = &self.latency {
+                sleep(latency.sample()).await;
+            }
+
+            if self.should_reject() {
+                events
+                    .take_finalizers()
+                    .update_status(EventStatus::Rejected);
+                emit!(BlackholeEventsRejected {
+                    count: events.len()
+                });
+                continue;
+            }
+
             let message_len = events.size_of();
 
             let _ = self.total_events.fetch_add(events.len(), Ordering::AcqRel);
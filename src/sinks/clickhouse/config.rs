@@ -11,6 +11,7 @@ use crate::{
         },
         Healthcheck, VectorSink,
     },
+    template::Template,
     tls::TlsConfig,
 };
 
@@ -18,7 +19,8 @@ use super::http_sink::build_http_sink;
 
 /// Configuration for the `clickhouse` sink.
 #[configurable_component(sink("clickhouse"))]
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, Debug, Derivative)]
+#[derivative(Default)]
 #[serde(deny_unknown_fields)]
 pub struct ClickhouseConfig {
     /// The endpoint of the Clickhouse server.
@@ -26,7 +28,18 @@ pub struct ClickhouseConfig {
     pub endpoint: UriSerde,
 
     /// The table that data will be inserted into.
-    pub table: String,
+    #[configurable(metadata(templateable))]
+    pub table: Template,
+
+    /// The maximum number of distinct tables data is inserted into.
+    ///
+    /// Each table is batched separately, so this bounds the number of batches a `table` template
+    /// can open. Tables without events for longer than the batch timeout, whose batches are
+    /// flushed, make room for new tables. Events rendering a table name beyond the limit are
+    /// dropped.
+    #[serde(default = "default_max_tables")]
+    #[derivative(Default(value = "default_max_tables()"))]
+    pub max_tables: usize,
 
     /// The database that contains the table that data will be inserted into.
     pub database: Option<String>,
@@ -35,6 +48,10 @@ pub struct ClickhouseConfig {
     #[serde(default)]
     pub skip_unknown_fields: bool,
 
+    #[configurable(derived)]
+    #[serde(default)]
+    pub async_insert: AsyncInsertSettings,
+
     #[configurable(derived)]
     #[serde(default = "Compression::gzip_default")]
     pub compression: Compression,
@@ -69,6 +86,31 @@ pub struct ClickhouseConfig {
     pub acknowledgements: AcknowledgementsConfig,
 }
 
+/// Settings for the [asynchronous inserts][async_inserts] of Clickhouse.
+///
+/// [async_inserts]: https://clickhouse.com/docs/en/optimize/asynchronous-inserts
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Derivative)]
+#[derivative(Default)]
+#[serde(deny_unknown_fields, default)]
+pub struct AsyncInsertSettings {
+    /// Sets `async_insert`, letting Clickhouse buffer the inserted data and write it to the table
+    /// in larger batches.
+    pub enabled: bool,
+
+    /// Sets `wait_for_async_insert`, making Clickhouse respond once the buffered data is written to
+    /// the table.
+    ///
+    /// If disabled, events are acknowledged as soon as Clickhouse buffered them, and errors
+    /// writing them to the table are not reported back.
+    #[derivative(Default(value = "true"))]
+    pub wait: bool,
+}
+
+const fn default_max_tables() -> usize {
+    100
+}
+
 impl_generate_config_from_default!(ClickhouseConfig);
 
 #[async_trait::async_trait]
@@ -1,23 +1,31 @@
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
 use bytes::{BufMut, Bytes, BytesMut};
 use futures::{FutureExt, SinkExt};
 use http::{Request, StatusCode, Uri};
 use hyper::Body;
 use snafu::ResultExt;
 
-use super::ClickhouseConfig;
+use super::{config::AsyncInsertSettings, ClickhouseConfig};
 use crate::{
     codecs::Transformer,
     config::SinkContext,
     event::Event,
     http::{HttpClient, HttpError, MaybeAuth},
+    internal_events::{ClickhouseTableLimitReached, TemplateRenderingError},
     sinks::{
         util::{
-            http::{BatchedHttpSink, HttpEventEncoder, HttpRetryLogic, HttpSink},
+            http::{HttpEventEncoder, HttpRetryLogic, HttpSink, PartitionHttpSink},
             retries::{RetryAction, RetryLogic},
-            Buffer, TowerRequestConfig,
+            Buffer, PartitionBuffer, PartitionInnerBuffer, RealtimeSizeBasedDefaultBatchSettings,
+            SinkBatchSettings, TowerRequestConfig,
         },
         Healthcheck, HealthcheckError, UriParseSnafu, VectorSink,
     },
+    template::Template,
     tls::TlsSettings,
 };
 
@@ -35,9 +43,9 @@ pub(crate) async fn build_http_sink(
         ..cfg.clone()
     };
 
-    let sink = BatchedHttpSink::with_logic(
+    let sink = PartitionHttpSink::with_retry_logic(
         config.clone(),
-        Buffer::new(batch.size, cfg.compression),
+        PartitionBuffer::new(Buffer::new(batch.size, cfg.compression)),
         ClickhouseRetryLogic::default(),
         request,
         batch.timeout,
@@ -52,33 +60,91 @@ pub(crate) async fn build_http_sink(
 
 pub struct ClickhouseEventEncoder {
     transformer: Transformer,
+    table: Template,
+    max_tables: usize,
+    /// The tables events were inserted into, along with the time of their last event.
+    tables: HashMap<String, Instant>,
+    /// How long a table stays without events before its batch is flushed.
+    idle_timeout: Duration,
 }
 
-impl HttpEventEncoder<BytesMut> for ClickhouseEventEncoder {
-    fn encode_event(&mut self, mut event: Event) -> Option<BytesMut> {
+impl ClickhouseEventEncoder {
+    /// Renders the table of `event`, unless that would exceed the maximum number of tables.
+    ///
+    /// The tables without events for longer than the batch timeout no longer count towards the
+    /// limit, as their batches are flushed, and are evicted once the limit is reached.
+    fn render_table(&mut self, event: &Event, now: Instant) -> Option<String> {
+        let table = self
+            .table
+            .render_string(event)
+            .map_err(|error| {
+                emit!(TemplateRenderingError {
+                    error,
+                    field: Some("table"),
+                    drop_event: true,
+                });
+            })
+            .ok()?;
+
+        if let Some(last_event) = self.tables.get_mut(&table) {
+            *last_event = now;
+            return Some(table);
+        }
+
+        if self.tables.len() >= self.max_tables {
+            let idle_timeout = self.idle_timeout;
+            self.tables
+                .retain(|_, last_event| now.saturating_duration_since(*last_event) < idle_timeout);
+        }
+        if self.tables.len() >= self.max_tables {
+            emit!(ClickhouseTableLimitReached {
+                table: &table,
+                max_tables: self.max_tables,
+            });
+            return None;
+        }
+        self.tables.insert(table.clone(), now);
+
+        Some(table)
+    }
+}
+
+impl HttpEventEncoder<PartitionInnerBuffer<BytesMut, String>> for ClickhouseEventEncoder {
+    fn encode_event(&mut self, mut event: Event) -> Option<PartitionInnerBuffer<BytesMut, String>> {
+        let table = self.render_table(&event, Instant::now())?;
+
         self.transformer.transform(&mut event);
         let log = event.into_log();
 
         let mut body = crate::serde::json::to_bytes(&log).expect("Events should be valid json!");
         body.put_u8(b'\n');
 
-        Some(body)
+        Some(PartitionInnerBuffer::new(body, table))
     }
 }
 
 #[async_trait::async_trait]
 impl HttpSink for ClickhouseConfig {
-    type Input = BytesMut;
-    type Output = BytesMut;
+    type Input = PartitionInnerBuffer<BytesMut, String>;
+    type Output = PartitionInnerBuffer<BytesMut, String>;
     type Encoder = ClickhouseEventEncoder;
 
     fn build_encoder(&self) -> Self::Encoder {
         ClickhouseEventEncoder {
             transformer: self.encoding.clone(),
+            table: self.table.clone(),
+            max_tables: self.max_tables,
+            tables: HashMap::new(),
+            idle_timeout: Duration::from_secs_f64(
+                self.batch
+                    .timeout_secs
+                    .unwrap_or(RealtimeSizeBasedDefaultBatchSettings::TIMEOUT_SECS),
+            ),
         }
     }
 
-    async fn build_request(&self, events: Self::Output) -> crate::Result<http::Request<Bytes>> {
+    async fn build_request(&self, output: Self::Output) -> crate::Result<http::Request<Bytes>> {
+        let (events, table) = output.into_parts();
         let database = if let Some(database) = &self.database {
             database.as_str()
         } else {
@@ -88,8 +154,9 @@ impl HttpSink for ClickhouseConfig {
         let uri = set_uri_query(
             &self.endpoint.with_default_parts().uri,
             database,
-            &self.table,
+            &table,
             self.skip_unknown_fields,
+            self.async_insert,
         )
         .expect("Unable to encode uri");
 
@@ -126,7 +193,13 @@ async fn healthcheck(client: HttpClient, config: ClickhouseConfig) -> crate::Res
     }
 }
 
-fn set_uri_query(uri: &Uri, database: &str, table: &str, skip_unknown: bool) -> crate::Result<Uri> {
+fn set_uri_query(
+    uri: &Uri,
+    database: &str,
+    table: &str,
+    skip_unknown: bool,
+    async_insert: AsyncInsertSettings,
+) -> crate::Result<Uri> {
     let query = url::form_urlencoded::Serializer::new(String::new())
         .append_pair(
             "query",
@@ -147,6 +220,14 @@ fn set_uri_query(uri: &Uri, database: &str, table: &str, skip_unknown: bool) ->
     if skip_unknown {
         uri.push_str("input_format_skip_unknown_fields=1&");
     }
+    if async_insert.enabled {
+        uri.push_str("async_insert=1&");
+        uri.push_str(if async_insert.wait {
+            "wait_for_async_insert=1&"
+        } else {
+            "wait_for_async_insert=0&"
+        });
+    }
     uri.push_str(query.as_str());
 
     uri.parse::<Uri>()
@@ -154,6 +235,32 @@ fn set_uri_query(uri: &Uri, database: &str, table: &str, skip_unknown: bool) ->
         .map_err(Into::into)
 }
 
+/// Clickhouse error codes for data that doesn't match the schema of the table, or for a table that
+/// doesn't exist. Retrying requests failing with them can't succeed.
+///
+/// Error code definitions: https://github.com/ClickHouse/ClickHouse/blob/master/src/Common/ErrorCodes.cpp
+const SCHEMA_MISMATCH_ERROR_CODES: &[u32] = &[
+    6,   // CANNOT_PARSE_TEXT
+    16,  // NO_SUCH_COLUMN_IN_TABLE
+    26,  // CANNOT_PARSE_QUOTED_STRING
+    27,  // CANNOT_PARSE_INPUT_ASSERTION_FAILED
+    38,  // CANNOT_PARSE_DATE
+    41,  // CANNOT_PARSE_DATETIME
+    53,  // TYPE_MISMATCH
+    60,  // UNKNOWN_TABLE
+    72,  // CANNOT_PARSE_NUMBER
+    81,  // UNKNOWN_DATABASE
+    117, // INCORRECT_DATA
+];
+
+/// Parses the error code from the body of a Clickhouse error response, which starts with
+/// `Code: {code_num}`.
+fn error_code(body: &[u8]) -> Option<u32> {
+    let body = body.strip_prefix(b"Code: ")?;
+    let digits = body.iter().take_while(|byte| byte.is_ascii_digit()).count();
+    std::str::from_utf8(&body[..digits]).ok()?.parse().ok()
+}
+
 #[derive(Debug, Default, Clone)]
 struct ClickhouseRetryLogic {
     inner: HttpRetryLogic,
@@ -168,25 +275,26 @@ impl RetryLogic for ClickhouseRetryLogic {
     }
 
     fn should_retry_response(&self, response: &Self::Response) -> RetryAction {
+        let body = response.body();
+
+        // Older versions of clickhouse return 500's for schema mismatches, so the error code in
+        // the body is checked regardless of the status, so that those errors are not retried.
+        //
+        // Reference: https://github.com/vectordotdev/vector/pull/693#issuecomment-517332654
+        // Fix already merged: https://github.com/ClickHouse/ClickHouse/pull/6271
+        if !response.status().is_success() {
+            if let Some(code) = error_code(body) {
+                if SCHEMA_MISMATCH_ERROR_CODES.contains(&code) {
+                    return RetryAction::DontRetry(
+                        format!("schema mismatch: {}", String::from_utf8_lossy(body).trim()).into(),
+                    );
+                }
+            }
+        }
+
         match response.status() {
             StatusCode::INTERNAL_SERVER_ERROR => {
-                let body = response.body();
-
-                // Currently, clickhouse returns 500's incorrect data and type mismatch errors.
-                // This attempts to check if the body starts with `Code: {code_num}` and to not
-                // retry those errors.
-                //
-                // Reference: https://github.com/vectordotdev/vector/pull/693#issuecomment-517332654
-                // Error code definitions: https://github.com/ClickHouse/ClickHouse/blob/master/dbms/src/Common/ErrorCodes.cpp
-                //
-                // Fix already merged: https://github.com/ClickHouse/ClickHouse/pull/6271
-                if body.starts_with(b"Code: 117") {
-                    RetryAction::DontRetry("incorrect data".into())
-                } else if body.starts_with(b"Code: 53") {
-                    RetryAction::DontRetry("type mismatch".into())
-                } else {
-                    RetryAction::Retry(String::from_utf8_lossy(body).to_string().into())
-                }
+                RetryAction::Retry(String::from_utf8_lossy(body).to_string().into())
             }
             _ => self.inner.should_retry_response(response),
         }
@@ -196,6 +304,7 @@ impl RetryLogic for ClickhouseRetryLogic {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::event::LogEvent;
 
     #[test]
     fn generate_config() {
@@ -209,6 +318,7 @@ mod tests {
             "my_database",
             "my_table",
             false,
+            AsyncInsertSettings::default(),
         )
         .unwrap();
         assert_eq!(uri.to_string(), "http://localhost:80/?input_format_import_nested_json=1&query=INSERT+INTO+%22my_database%22.%22my_table%22+FORMAT+JSONEachRow");
@@ -218,11 +328,28 @@ mod tests {
             "my_database",
             "my_\"table\"",
             false,
+            AsyncInsertSettings::default(),
         )
         .unwrap();
         assert_eq!(uri.to_string(), "http://localhost:80/?input_format_import_nested_json=1&query=INSERT+INTO+%22my_database%22.%22my_%5C%22table%5C%22%22+FORMAT+JSONEachRow");
     }
 
+    #[test]
+    fn encode_async_insert() {
+        let uri = set_uri_query(
+            &"http://localhost:80".parse().unwrap(),
+            "my_database",
+            "my_table",
+            true,
+            AsyncInsertSettings {
+                enabled: true,
+                wait: false,
+            },
+        )
+        .unwrap();
+        assert_eq!(uri.to_string(), "http://localhost:80/?input_format_import_nested_json=1&input_format_skip_unknown_fields=1&async_insert=1&wait_for_async_insert=0&query=INSERT+INTO+%22my_database%22.%22my_table%22+FORMAT+JSONEachRow");
+    }
+
     #[test]
     fn encode_invalid() {
         set_uri_query(
@@ -230,7 +357,116 @@ mod tests {
             "my_database",
             "my_table",
             false,
+            AsyncInsertSettings::default(),
         )
         .unwrap_err();
     }
+
+    #[test]
+    fn partitions_by_table() {
+        let config: ClickhouseConfig = toml::from_str(
+            r#"
+            endpoint = "http://localhost:8123"
+            table = "logs_{{ team }}"
+            max_tables = 2
+        "#,
+        )
+        .unwrap();
+        let mut encoder = config.build_encoder();
+
+        let mut table_of = |team: &str| {
+            let mut log = LogEvent::from("message");
+            log.insert("team", team);
+            encoder
+                .encode_event(log.into())
+                .map(|output| output.into_parts().1)
+        };
+
+        assert_eq!(table_of("a").as_deref(), Some("logs_a"));
+        assert_eq!(table_of("b").as_deref(), Some("logs_b"));
+        assert_eq!(table_of("c"), None);
+        assert_eq!(table_of("a").as_deref(), Some("logs_a"));
+
+        // Events failing to render the table are dropped.
+        assert!(encoder
+            .encode_event(LogEvent::from("message").into())
+            .is_none());
+    }
+
+    #[test]
+    fn evicts_idle_tables() {
+        let config: ClickhouseConfig = toml::from_str(
+            r#"
+            endpoint = "http://localhost:8123"
+            table = "logs_{{ team }}"
+            max_tables = 2
+            batch.timeout_secs = 10
+        "#,
+        )
+        .unwrap();
+        let mut encoder = config.build_encoder();
+        let start = Instant::now();
+
+        let mut table_of = |team: &str, secs: u64| {
+            let mut log = LogEvent::from("message");
+            log.insert("team", team);
+            encoder.render_table(&log.into(), start + Duration::from_secs(secs))
+        };
+
+        assert_eq!(table_of("a", 0).as_deref(), Some("logs_a"));
+        assert_eq!(table_of("b", 5).as_deref(), Some("logs_b"));
+        assert_eq!(table_of("c", 9), None);
+        // The batch of `a` is flushed once it's idle for the batch timeout, making room for `c`,
+        // while `b` is kept, as it got more events since.
+        assert_eq!(table_of("b", 12).as_deref(), Some("logs_b"));
+        assert_eq!(table_of("c", 12).as_deref(), Some("logs_c"));
+        assert_eq!(table_of("a", 13), None);
+    }
+
+    fn retry_action(status: StatusCode, body: &'static str) -> RetryAction {
+        let response = http::Response::builder()
+            .status(status)
+            .body(Bytes::from(body))
+            .unwrap();
+        ClickhouseRetryLogic::default().should_retry_response(&response)
+    }
+
+    #[test]
+    fn does_not_retry_schema_mismatches() {
+        assert!(retry_action(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "Code: 117, e.displayText() = DB::Exception: Unknown field found while parsing JSONEachRow format: unknown"
+        )
+        .is_not_retryable());
+        assert!(retry_action(
+            StatusCode::BAD_REQUEST,
+            "Code: 27. DB::Exception: Cannot parse input: expected '\"' before: '1}': (while reading the value of key timestamp)"
+        )
+        .is_not_retryable());
+        assert!(retry_action(
+            StatusCode::NOT_FOUND,
+            "Code: 60. DB::Exception: Table default.logs_c doesn't exist"
+        )
+        .is_not_retryable());
+    }
+
+    #[test]
+    fn retries_other_errors() {
+        assert!(retry_action(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "Code: 252. DB::Exception: Too many parts (300)"
+        )
+        .is_retryable());
+        assert!(retry_action(StatusCode::SERVICE_UNAVAILABLE, "").is_retryable());
+    }
+
+    #[test]
+    fn parses_error_codes() {
+        assert_eq!(
+            error_code(b"Code: 53. DB::Exception: Type mismatch"),
+            Some(53)
+        );
+        assert_eq!(error_code(b"Code: 117, e.displayText()"), Some(117));
+        assert_eq!(error_code(b"Internal Server Error"), None);
+    }
 }
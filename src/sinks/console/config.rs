@@ -1,6 +1,6 @@
 use codecs::{
-    encoding::{Framer, FramingConfig},
-    JsonSerializerConfig,
+    encoding::{ColorMode, Framer, FramingConfig, Serializer, SerializerConfig},
+    JsonSerializerConfig, PrettySerializer,
 };
 use futures::{future, FutureExt};
 use tokio::io;
@@ -26,6 +26,20 @@ pub enum Target {
     Stderr,
 }
 
+impl Target {
+    /// Whether the target is attached to a terminal.
+    fn is_terminal(&self) -> bool {
+        match self {
+            #[cfg(unix)]
+            Target::Stdout => atty::is(atty::Stream::Stdout),
+            #[cfg(unix)]
+            Target::Stderr => atty::is(atty::Stream::Stderr),
+            #[cfg(windows)]
+            _ => false, // ANSI colors are not supported by cmd.exe
+        }
+    }
+}
+
 /// Configuration for the `console` sink.
 #[configurable_component(sink("console"))]
 #[derive(Clone, Debug)]
@@ -62,7 +76,12 @@ impl GenerateConfig for ConsoleSinkConfig {
 impl SinkConfig for ConsoleSinkConfig {
     async fn build(&self, _cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
         let transformer = self.encoding.transformer();
-        let (framer, serializer) = self.encoding.build(SinkType::StreamBased)?;
+        let (framer, mut serializer) = self.encoding.build(SinkType::StreamBased)?;
+        if let SerializerConfig::Pretty { pretty } = self.encoding.config().1 {
+            if pretty.colors == ColorMode::Auto {
+                serializer = Serializer::Pretty(PrettySerializer::new(self.target.is_terminal()));
+            }
+        }
         let encoder = Encoder::<Framer>::new(framer, serializer);
 
         let sink: VectorSink = match self.target {
@@ -205,7 +205,12 @@ impl SinkConfig for DatadogLogsConfig {
             .optional_meaning("source", Kind::bytes())
             .optional_meaning("severity", Kind::bytes())
             .optional_meaning("service", Kind::bytes())
-            .optional_meaning("trace_id", Kind::bytes());
+            .optional_meaning("trace_id", Kind::bytes())
+            // The reserved attributes of Datadog, which the fields carrying these meanings are
+            // renamed to when semantic mapping is enabled.
+            .with_target("source", "ddsource")
+            .with_target("severity", "status")
+            .with_target("service", "service");
 
         Input::log().with_schema_requirement(requirement)
     }
@@ -217,10 +222,24 @@ impl SinkConfig for DatadogLogsConfig {
 
 #[cfg(test)]
 mod test {
-    use crate::sinks::datadog::logs::DatadogLogsConfig;
+    use crate::{config::SinkConfig, sinks::datadog::logs::DatadogLogsConfig};
 
     #[test]
     fn generate_config() {
         crate::test_util::test_generate_config::<DatadogLogsConfig>();
     }
+
+    #[test]
+    fn maps_meanings_to_reserved_attributes() {
+        let input = DatadogLogsConfig::default().input();
+
+        assert_eq!(
+            input.schema_requirement().targets().collect::<Vec<_>>(),
+            vec![
+                ("service", "service"),
+                ("severity", "status"),
+                ("source", "ddsource")
+            ]
+        );
+    }
 }
@@ -62,6 +62,14 @@ impl ElasticsearchCommon {
         let http_auth = authorization.choose_one(&uri.auth)?;
         let base_url = uri.uri.to_string().trim_end_matches('/').to_owned();
 
+        if config
+            .aws
+            .as_ref()
+            .map_or(false, |aws| aws.endpoint_variant.is_some())
+        {
+            return Err(ParseError::EndpointVariantUnsupported.into());
+        }
+
         let aws_auth = match &config.auth {
             Some(ElasticsearchAuth::Basic { .. }) | None => None,
             Some(ElasticsearchAuth::Aws(aws)) => {
@@ -16,6 +16,7 @@ use crate::{
     internal_events::TemplateRenderingError,
     sinks::{
         elasticsearch::{
+            dead_letter::DeadLetter,
             health::ElasticsearchHealthLogic,
             retry::ElasticsearchRetryLogic,
             service::{ElasticsearchService, HttpRequestBuilder},
@@ -82,6 +83,14 @@ pub struct ElasticsearchConfig {
     /// The name of the pipeline to apply.
     pub pipeline: Option<String>,
 
+    /// The name of the index to send documents rejected by Elasticsearch to.
+    ///
+    /// Documents that Elasticsearch rejects for reasons that retrying won't fix, such as mapping
+    /// conflicts, are sent to this index along with the reason they were rejected, instead of being
+    /// dropped. The original document is stored as a string in the `document` field, so that it
+    /// can't cause further mapping conflicts.
+    pub dead_letter_index: Option<String>,
+
     #[configurable(derived)]
     #[serde(default)]
     pub mode: ElasticsearchMode,
@@ -397,7 +406,13 @@ impl SinkConfig for ElasticsearchConfig {
                 let endpoint = common.base_url.clone();
 
                 let http_request_builder = HttpRequestBuilder::new(&common, self);
-                let service = ElasticsearchService::new(client.clone(), http_request_builder);
+                let dead_letter = self.dead_letter_index.clone().map(|index| DeadLetter {
+                    index,
+                    encoder: common.request_builder.encoder.clone(),
+                    compression: self.compression,
+                });
+                let service =
+                    ElasticsearchService::new(client.clone(), http_request_builder, dead_letter);
 
                 (endpoint, service)
             })
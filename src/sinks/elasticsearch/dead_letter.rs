@@ -0,0 +1,234 @@
+use std::io::{self, Read, Write};
+
+use bytes::{Buf, Bytes};
+use chrono::Utc;
+use flate2::read::{MultiGzDecoder, ZlibDecoder};
+use serde_json::json;
+
+use super::{
+    encoder::{write_bulk_action, ElasticsearchEncoder},
+    retry::EsResultResponse,
+    service::ElasticsearchRequest,
+    DATA_STREAM_TIMESTAMP_KEY,
+};
+use crate::{
+    event::EventFinalizers,
+    sinks::util::{Compression, Compressor},
+};
+
+/// Sends the documents Elasticsearch rejected permanently to a dead letter index, along with the
+/// reason they were rejected.
+#[derive(Clone, Debug)]
+pub struct DeadLetter {
+    pub index: String,
+    pub encoder: ElasticsearchEncoder,
+    pub compression: Compression,
+}
+
+/// A bulk request for the dead letter index.
+pub struct DeadLetterRequest {
+    pub request: ElasticsearchRequest,
+    /// Whether all of the documents rejected by Elasticsearch are in the request, as opposed to
+    /// only some of them being rejected permanently.
+    pub complete: bool,
+}
+
+impl DeadLetter {
+    /// Builds the dead letter request for the bulk request `payload`, given the body of the
+    /// response to it.
+    ///
+    /// Returns `None` if no document was rejected permanently, or if the response can't be matched
+    /// with the documents of the request.
+    pub fn build_request(&self, payload: &Bytes, response: &[u8]) -> Option<DeadLetterRequest> {
+        let items = serde_json::from_slice::<EsResultResponse>(response)
+            .ok()?
+            .items;
+        let payload = self.decompress(payload).ok()?;
+        let lines = payload
+            .split(|&byte| byte == b'\n')
+            .filter(|line| !line.is_empty())
+            .collect::<Vec<_>>();
+        // Each document is preceded by its bulk action.
+        if lines.len() != items.len() * 2 {
+            return None;
+        }
+
+        let timestamp = Utc::now();
+        let mut complete = true;
+        let mut batch_size = 0;
+        let mut compressor = Compressor::from(self.compression);
+        for (item, document) in items.into_iter().zip(lines.chunks(2).map(|lines| lines[1])) {
+            let result = item.result();
+            let permanent = result.is_permanent_error();
+            let error = match result.error {
+                Some(error) => error,
+                None => continue,
+            };
+            if !permanent {
+                complete = false;
+                continue;
+            }
+
+            let document = json!({
+                DATA_STREAM_TIMESTAMP_KEY: timestamp,
+                "index": result.index,
+                "status": result.status,
+                "error": {
+                    "type": error.err_type,
+                    "reason": error.reason,
+                },
+                "document": String::from_utf8_lossy(document),
+            });
+            write_bulk_action(
+                &mut compressor,
+                "create",
+                &self.index,
+                &self.encoder.doc_type,
+                self.encoder.suppress_type_name,
+                &None,
+            )
+            .ok()?;
+            compressor.write_all(b"\n").ok()?;
+            serde_json::to_writer(&mut compressor, &document).ok()?;
+            compressor.write_all(b"\n").ok()?;
+            batch_size += 1;
+        }
+
+        if batch_size == 0 {
+            return None;
+        }
+
+        Some(DeadLetterRequest {
+            request: ElasticsearchRequest {
+                payload: compressor.finish().ok()?.freeze(),
+                finalizers: EventFinalizers::default(),
+                batch_size,
+                events_byte_size: 0,
+            },
+            complete,
+        })
+    }
+
+    fn decompress(&self, payload: &Bytes) -> io::Result<Vec<u8>> {
+        let mut decompressed = Vec::new();
+        match self.compression {
+            Compression::None => decompressed.extend_from_slice(payload),
+            Compression::Gzip(_) => {
+                MultiGzDecoder::new(payload.clone().reader()).read_to_end(&mut decompressed)?;
+            }
+            Compression::Zlib(_) => {
+                ZlibDecoder::new(payload.clone().reader()).read_to_end(&mut decompressed)?;
+            }
+        }
+        Ok(decompressed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PAYLOAD: &str = concat!(
+        r#"{"index":{"_index":"logs"}}"#,
+        "\n",
+        r#"{"message":"first"}"#,
+        "\n",
+        r#"{"index":{"_index":"logs"}}"#,
+        "\n",
+        r#"{"message":1}"#,
+        "\n",
+        r#"{"index":{"_index":"logs"}}"#,
+        "\n",
+        r#"{"message":"third"}"#,
+        "\n",
+    );
+
+    fn dead_letter(compression: Compression) -> DeadLetter {
+        DeadLetter {
+            index: "dead-letters".to_owned(),
+            encoder: ElasticsearchEncoder {
+                suppress_type_name: true,
+                ..Default::default()
+            },
+            compression,
+        }
+    }
+
+    fn response(third_status: u16) -> String {
+        format!(
+            r#"{{"took":3,"errors":true,"items":[
+                {{"index":{{"_index":"logs","status":201}}}},
+                {{"index":{{"_index":"logs","status":400,"error":{{"type":"mapper_parsing_exception","reason":"failed to parse field [message]"}}}}}},
+                {{"index":{{"_index":"logs","status":{},"error":{{"type":"other","reason":"other"}}}}}}
+            ]}}"#,
+            third_status
+        )
+    }
+
+    fn documents(request: &ElasticsearchRequest) -> Vec<serde_json::Value> {
+        let payload = String::from_utf8(request.payload.to_vec()).unwrap();
+        let lines = payload.lines().collect::<Vec<_>>();
+        lines
+            .chunks(2)
+            .map(|lines| {
+                assert_eq!(lines[0], r#"{"create":{"_index":"dead-letters"}}"#);
+                serde_json::from_str(lines[1]).unwrap()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn reroutes_rejected_documents() {
+        let DeadLetterRequest { request, complete } = dead_letter(Compression::None)
+            .build_request(&PAYLOAD.into(), response(409).as_bytes())
+            .unwrap();
+
+        assert!(complete);
+        assert_eq!(request.batch_size, 2);
+        let documents = documents(&request);
+        assert_eq!(documents[0]["document"], r#"{"message":1}"#);
+        assert_eq!(documents[0]["index"], "logs");
+        assert_eq!(documents[0]["status"], 400);
+        assert_eq!(documents[0]["error"]["type"], "mapper_parsing_exception");
+        assert_eq!(
+            documents[0]["error"]["reason"],
+            "failed to parse field [message]"
+        );
+        assert_eq!(documents[1]["document"], r#"{"message":"third"}"#);
+    }
+
+    #[test]
+    fn skips_transient_errors() {
+        let DeadLetterRequest { request, complete } = dead_letter(Compression::None)
+            .build_request(&PAYLOAD.into(), response(429).as_bytes())
+            .unwrap();
+
+        assert!(!complete);
+        assert_eq!(request.batch_size, 1);
+    }
+
+    #[test]
+    fn reads_compressed_payloads() {
+        let mut compressor = Compressor::from(Compression::gzip_default());
+        compressor.write_all(PAYLOAD.as_bytes()).unwrap();
+        let payload = compressor.finish().unwrap().freeze();
+
+        let dead_letter = dead_letter(Compression::gzip_default());
+        let DeadLetterRequest { request, .. } = dead_letter
+            .build_request(&payload, response(409).as_bytes())
+            .unwrap();
+
+        let payload = dead_letter.decompress(&request.payload).unwrap();
+        assert_eq!(
+            String::from_utf8(payload).unwrap().lines().count(),
+            2 * request.batch_size
+        );
+    }
+
+    #[test]
+    fn ignores_mismatched_responses() {
+        assert!(dead_letter(Compression::None)
+            .build_request(&Bytes::from(r#"{"index":{}}"#), response(409).as_bytes())
+            .is_none());
+    }
+}
@@ -70,7 +70,7 @@ impl Encoder<Vec<ProcessedEvent>> for ElasticsearchEncoder {
     }
 }
 
-fn write_bulk_action(
+pub(super) fn write_bulk_action(
     writer: &mut dyn Write,
     bulk_action: &str,
     index: &str,
@@ -1,5 +1,6 @@
 mod common;
 mod config;
+mod dead_letter;
 mod encoder;
 mod health;
 mod request_builder;
@@ -184,6 +185,10 @@ pub enum ParseError {
     BatchActionTemplate { source: TemplateParseError },
     #[snafu(display("aws.region required when AWS authentication is in use"))]
     RegionRequired,
+    #[snafu(display(
+        "aws.endpoint_variant is not supported, as requests are sent to the configured endpoints"
+    ))]
+    EndpointVariantUnsupported,
     #[snafu(display("Endpoints option must be specified"))]
     EndpointRequired,
     #[snafu(display(
@@ -2,6 +2,7 @@ use http::StatusCode;
 use serde::Deserialize;
 
 use crate::{
+    event::EventStatus,
     http::HttpError,
     sinks::{
         elasticsearch::service::ElasticsearchResponse,
@@ -10,12 +11,12 @@ use crate::{
 };
 
 #[derive(Deserialize, Debug)]
-struct EsResultResponse {
-    items: Vec<EsResultItem>,
+pub(super) struct EsResultResponse {
+    pub(super) items: Vec<EsResultItem>,
 }
 
 #[derive(Deserialize, Debug)]
-enum EsResultItem {
+pub(super) enum EsResultItem {
     #[serde(rename = "index")]
     Index(EsIndexResult),
     #[serde(rename = "create")]
@@ -24,7 +25,7 @@ enum EsResultItem {
 
 impl EsResultItem {
     #[allow(clippy::missing_const_for_fn)] // const cannot run destructor
-    fn result(self) -> EsIndexResult {
+    pub(super) fn result(self) -> EsIndexResult {
         match self {
             EsResultItem::Index(r) => r,
             EsResultItem::Create(r) => r,
@@ -33,15 +34,30 @@ impl EsResultItem {
 }
 
 #[derive(Deserialize, Debug)]
-struct EsIndexResult {
-    error: Option<EsErrorDetails>,
+pub(super) struct EsIndexResult {
+    #[serde(rename = "_index")]
+    pub(super) index: Option<String>,
+    #[serde(default)]
+    pub(super) status: u16,
+    pub(super) error: Option<EsErrorDetails>,
+}
+
+impl EsIndexResult {
+    /// Whether the document was rejected for a reason that retrying won't fix, such as a mapping
+    /// conflict.
+    pub(super) fn is_permanent_error(&self) -> bool {
+        self.error.is_some()
+            && StatusCode::from_u16(self.status).map_or(false, |status| {
+                status.is_client_error() && status != StatusCode::TOO_MANY_REQUESTS
+            })
+    }
 }
 
 #[derive(Deserialize, Debug)]
-struct EsErrorDetails {
-    reason: String,
+pub(super) struct EsErrorDetails {
+    pub(super) reason: String,
     #[serde(rename = "type")]
-    err_type: String,
+    pub(super) err_type: String,
 }
 
 #[derive(Clone)]
@@ -78,7 +94,11 @@ impl RetryLogic for ElasticsearchRetryLogic {
             _ if status.is_success() => {
                 let body = String::from_utf8_lossy(response.http_response.body());
 
-                if body.contains("\"errors\":true") {
+                // Rejected documents that were all sent to the dead letter index count as
+                // delivered.
+                if body.contains("\"errors\":true")
+                    && response.event_status != EventStatus::Delivered
+                {
                     RetryAction::DontRetry(get_error_reason(&body).into())
                 } else {
                     RetryAction::Successful
@@ -109,7 +129,6 @@ mod tests {
     use pretty_assertions::assert_eq;
 
     use super::*;
-    use crate::event::EventStatus;
 
     #[test]
     fn handles_error_response() {
@@ -130,6 +149,25 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn handles_dead_lettered_error_response() {
+        let json = "{\"took\":185,\"errors\":true,\"items\":[{\"index\":{\"_index\":\"test-hgw28jv10u\",\"_type\":\"log_lines\",\"_id\":\"3GhQLXEBE62DvOOUKdFH\",\"status\":400,\"error\":{\"type\":\"illegal_argument_exception\",\"reason\":\"mapper [message] of different type, current_type [long], merged_type [text]\"}}}]}";
+        let response = Response::builder()
+            .status(StatusCode::OK)
+            .body(Bytes::from(json))
+            .unwrap();
+        let logic = ElasticsearchRetryLogic;
+        assert!(matches!(
+            logic.should_retry_response(&ElasticsearchResponse {
+                http_response: response,
+                event_status: EventStatus::Delivered,
+                batch_size: 1,
+                events_byte_size: 1,
+            }),
+            RetryAction::Successful
+        ));
+    }
+
     #[test]
     fn get_index_error_reason() {
         let json = "{\"took\":185,\"errors\":true,\"items\":[{\"index\":{\"_index\":\"test-hgw28jv10u\",\"_type\":\"log_lines\",\"_id\":\"3GhQLXEBE62DvOOUKdFH\",\"status\":400,\"error\":{\"type\":\"illegal_argument_exception\",\"reason\":\"mapper [message] of different type, current_type [long], merged_type [text]\"}}}]}";
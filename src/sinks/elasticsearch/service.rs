@@ -17,13 +17,17 @@ use crate::sinks::elasticsearch::sign_request;
 use crate::{
     event::{EventFinalizers, EventStatus, Finalizable},
     http::{Auth, HttpClient},
+    internal_events::{ElasticsearchDeadLetterError, ElasticsearchDeadLetterSent},
     sinks::util::{
         http::{HttpBatchService, RequestConfig},
         Compression, ElementCount,
     },
 };
 
-use super::{ElasticsearchCommon, ElasticsearchConfig};
+use super::{
+    dead_letter::{DeadLetter, DeadLetterRequest},
+    ElasticsearchCommon, ElasticsearchConfig,
+};
 
 #[derive(Clone)]
 pub struct ElasticsearchRequest {
@@ -51,18 +55,22 @@ impl Finalizable for ElasticsearchRequest {
     }
 }
 
+type ElasticsearchBatchService = HttpBatchService<
+    BoxFuture<'static, Result<http::Request<Bytes>, crate::Error>>,
+    ElasticsearchRequest,
+>;
+
 #[derive(Clone)]
 pub struct ElasticsearchService {
-    batch_service: HttpBatchService<
-        BoxFuture<'static, Result<http::Request<Bytes>, crate::Error>>,
-        ElasticsearchRequest,
-    >,
+    batch_service: ElasticsearchBatchService,
+    dead_letter: Option<Arc<DeadLetter>>,
 }
 
 impl ElasticsearchService {
     pub fn new(
         http_client: HttpClient<Body>,
         http_request_builder: HttpRequestBuilder,
+        dead_letter: Option<DeadLetter>,
     ) -> ElasticsearchService {
         let http_request_builder = Arc::new(http_request_builder);
         let batch_service = HttpBatchService::new(http_client, move |req| {
@@ -71,7 +79,10 @@ impl ElasticsearchService {
                 Box::pin(async move { request_builder.build_request(req).await });
             future
         });
-        ElasticsearchService { batch_service }
+        ElasticsearchService {
+            batch_service,
+            dead_letter: dead_letter.map(Arc::new),
+        }
     }
 }
 
@@ -160,12 +171,25 @@ impl Service<ElasticsearchRequest> for ElasticsearchService {
     fn call(&mut self, req: ElasticsearchRequest) -> Self::Future {
         // Emission of Error internal event is handled upstream by the caller.
         let mut http_service = self.batch_service.clone();
+        let dead_letter = self.dead_letter.clone();
         Box::pin(async move {
             http_service.ready().await?;
             let batch_size = req.batch_size;
             let events_byte_size = req.events_byte_size;
+            let payload = req.payload.clone();
             let http_response = http_service.call(req).await?;
-            let event_status = get_event_status(&http_response);
+            let mut event_status = get_event_status(&http_response);
+            if let Some(dead_letter) = dead_letter {
+                if event_status == EventStatus::Rejected && http_response.status().is_success() {
+                    event_status = send_dead_letters(
+                        &dead_letter,
+                        &mut http_service,
+                        &payload,
+                        http_response.body(),
+                    )
+                    .await;
+                }
+            }
             Ok(ElasticsearchResponse {
                 event_status,
                 http_response,
@@ -176,6 +200,55 @@ impl Service<ElasticsearchRequest> for ElasticsearchService {
     }
 }
 
+/// Sends the documents of the bulk request `payload` rejected permanently to the dead letter
+/// index, returning the status of the events of the request.
+async fn send_dead_letters(
+    dead_letter: &DeadLetter,
+    http_service: &mut ElasticsearchBatchService,
+    payload: &Bytes,
+    response: &[u8],
+) -> EventStatus {
+    let DeadLetterRequest { request, complete } = match dead_letter.build_request(payload, response)
+    {
+        Some(request) => request,
+        None => return EventStatus::Rejected,
+    };
+    let count = request.batch_size;
+
+    let result = match http_service.ready().await {
+        Ok(http_service) => http_service.call(request).await,
+        Err(error) => Err(error),
+    };
+    match result {
+        Ok(response) if get_event_status(&response) == EventStatus::Delivered => {
+            emit!(ElasticsearchDeadLetterSent {
+                count,
+                index: &dead_letter.index,
+            });
+            if complete {
+                EventStatus::Delivered
+            } else {
+                EventStatus::Rejected
+            }
+        }
+        Ok(response) => {
+            emit!(ElasticsearchDeadLetterError {
+                count,
+                error: format!(
+                    "{}: {}",
+                    response.status(),
+                    String::from_utf8_lossy(response.body())
+                ),
+            });
+            EventStatus::Rejected
+        }
+        Err(error) => {
+            emit!(ElasticsearchDeadLetterError { count, error });
+            EventStatus::Rejected
+        }
+    }
+}
+
 fn get_event_status(response: &Response<Bytes>) -> EventStatus {
     let status = response.status();
     if status.is_success() {
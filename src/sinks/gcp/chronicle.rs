@@ -0,0 +1,715 @@
+//! This sink sends data to Google Chronicle, either as unstructured log entries that Chronicle
+//! parses according to their log type, or as events already in the Unified Data Model (UDM).
+//! See https://cloud.google.com/chronicle/docs/reference/ingestion-api for more information.
+use std::{fmt, io, num::NonZeroUsize};
+
+use async_trait::async_trait;
+use bytes::{Bytes, BytesMut};
+use futures::stream::BoxStream;
+use futures_util::StreamExt;
+use goauth::scopes::Scope;
+use indexmap::IndexMap;
+use indoc::indoc;
+use serde_json::{json, Map, Value};
+use snafu::Snafu;
+use tokio_util::codec::Encoder as _;
+use tower::{Service, ServiceBuilder};
+use vector_config::configurable_component;
+use vector_core::{
+    config::{AcknowledgementsConfig, Input},
+    event::{Event, EventFinalizers, Finalizable},
+    partition::Partitioner,
+    sink::{StreamSink, VectorSink},
+    stream::{BatcherSettings, DriverResponse},
+};
+
+use super::chronicle_unstructured::{
+    build_healthcheck, create_endpoint, ChronicleRequest, ChronicleService,
+    ChronicleUnstructuredDefaultBatchSettings, Region,
+};
+use crate::{
+    codecs::{self, EncodingConfig},
+    config::{log_schema, GenerateConfig, SinkConfig, SinkContext},
+    gcp::GcpAuthConfig,
+    http::HttpClient,
+    internal_events::{SinkRequestBuildError, TemplateRenderingError},
+    sinks::{
+        gcs_common::config::GcsRetryLogic,
+        util::{
+            encoding::{as_tracked_write, Encoder},
+            metadata::{RequestMetadata, RequestMetadataBuilder},
+            request_builder::EncodeResult,
+            service::ServiceBuilderExt,
+            BatchConfig, Compression, RequestBuilder, SinkBuilderExt, TowerRequestConfig,
+        },
+        Healthcheck,
+    },
+    template::Template,
+    tls::{TlsConfig, TlsSettings},
+};
+
+/// Google Chronicle ingestion APIs.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ChronicleApi {
+    /// Unstructured log entries, parsed by Chronicle according to their log type.
+    Unstructured,
+
+    /// Events already in the Unified Data Model (UDM).
+    ///
+    /// Each log event is sent as a UDM event, as is.
+    Udm,
+}
+
+impl Default for ChronicleApi {
+    fn default() -> Self {
+        Self::Unstructured
+    }
+}
+
+impl ChronicleApi {
+    const fn path(self) -> &'static str {
+        match self {
+            Self::Unstructured => "v2/unstructuredlogentries:batchCreate",
+            Self::Udm => "v2/udmevents:batchCreate",
+        }
+    }
+}
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("`log_type` must be set to send unstructured log entries"))]
+    MissingLogType,
+
+    #[snafu(display("`encoding` must be set to send unstructured log entries"))]
+    MissingEncoding,
+}
+
+/// Configuration for the `gcp_chronicle` sink.
+#[configurable_component(sink("gcp_chronicle"))]
+#[configurable(metadata(status = "alpha"))]
+#[derive(Clone, Debug)]
+pub struct ChronicleConfig {
+    /// The endpoint to send data to.
+    pub endpoint: Option<String>,
+
+    #[configurable(derived)]
+    pub region: Option<Region>,
+
+    /// The Unique identifier (UUID) corresponding to the Chronicle instance.
+    ///
+    /// Events are routed to the Chronicle instance of the rendered identifier, so that a single
+    /// sink can send events to several instances.
+    #[configurable(metadata(templateable))]
+    pub customer_id: Template,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub api: ChronicleApi,
+
+    /// The type of log entries in a request.
+    ///
+    /// This must be one of the [supported log types][unstructured_log_types_doc], otherwise
+    /// Chronicle will reject the entry with an error. Required with the `unstructured` API.
+    ///
+    /// [unstructured_log_types_doc]: https://cloud.google.com/chronicle/docs/ingestion/parser-list/supported-default-parsers
+    #[configurable(metadata(templateable))]
+    pub log_type: Option<Template>,
+
+    /// The namespace of the log entries in a request, for the `unstructured` API.
+    #[configurable(metadata(templateable))]
+    pub namespace: Option<Template>,
+
+    /// Ingestion labels attached to the events, as label keys mapped to their values.
+    ///
+    /// Unstructured log entries are labelled per request, while UDM events get the labels
+    /// appended to their `metadata.ingestion_labels`.
+    #[configurable(metadata(templateable))]
+    #[serde(default)]
+    pub labels: IndexMap<String, Template>,
+
+    #[serde(flatten)]
+    pub auth: GcpAuthConfig,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub batch: BatchConfig<ChronicleUnstructuredDefaultBatchSettings>,
+
+    /// The encoding of unstructured log entries.
+    ///
+    /// Required with the `unstructured` API, and ignored with the `udm` API.
+    pub encoding: Option<EncodingConfig>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub request: TowerRequestConfig,
+
+    #[configurable(derived)]
+    pub tls: Option<TlsConfig>,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        deserialize_with = "crate::serde::bool_or_struct",
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    acknowledgements: AcknowledgementsConfig,
+}
+
+impl GenerateConfig for ChronicleConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(indoc! {r#"
+            credentials_path = "/path/to/credentials.json"
+            customer_id = "customer_id"
+            log_type = "log_type"
+            encoding.codec = "text"
+        "#})
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+impl SinkConfig for ChronicleConfig {
+    async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        let request_builder = ChronicleRequestBuilder::new(self)?;
+
+        let creds = self.auth.build(Scope::MalachiteIngestion).await?;
+
+        let tls = TlsSettings::from_options(&self.tls)?;
+        let client = HttpClient::new(tls, cx.proxy())?;
+
+        let endpoint = create_endpoint(self.endpoint.as_deref(), self.region, self.api.path())?;
+
+        // For the healthcheck we see if we can fetch the list of available log types.
+        let healthcheck_endpoint =
+            create_endpoint(self.endpoint.as_deref(), self.region, "v2/logtypes")?;
+        let healthcheck = build_healthcheck(client.clone(), &healthcheck_endpoint, creds.clone())?;
+
+        let request = self.request.unwrap_with(&TowerRequestConfig {
+            rate_limit_num: Some(1000),
+            ..Default::default()
+        });
+
+        let service = ServiceBuilder::new()
+            .settings(request, GcsRetryLogic)
+            .service(ChronicleService::new(client, endpoint, creds));
+
+        let sink = ChronicleSink {
+            service,
+            request_builder,
+            partitioner: ChroniclePartitioner::new(self),
+            batcher_settings: self.batch.into_batcher_settings()?,
+        };
+
+        Ok((VectorSink::from_event_streamsink(sink), healthcheck))
+    }
+
+    fn input(&self) -> Input {
+        Input::log()
+    }
+
+    fn acknowledgements(&self) -> &AcknowledgementsConfig {
+        &self.acknowledgements
+    }
+}
+
+/// The Chronicle instance and the labels a batch of events is sent with.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+struct ChroniclePartitionKey {
+    customer_id: String,
+    log_type: Option<String>,
+    namespace: Option<String>,
+    labels: Vec<(String, String)>,
+}
+
+/// Partitions events by their rendered customer ID, log type, namespace and labels.
+struct ChroniclePartitioner {
+    customer_id: Template,
+    log_type: Option<Template>,
+    namespace: Option<Template>,
+    labels: IndexMap<String, Template>,
+}
+
+impl ChroniclePartitioner {
+    fn new(config: &ChronicleConfig) -> Self {
+        Self {
+            customer_id: config.customer_id.clone(),
+            // The log type and namespace are only sent to the unstructured API.
+            log_type: config
+                .log_type
+                .clone()
+                .filter(|_| config.api == ChronicleApi::Unstructured),
+            namespace: config
+                .namespace
+                .clone()
+                .filter(|_| config.api == ChronicleApi::Unstructured),
+            labels: config.labels.clone(),
+        }
+    }
+}
+
+fn render(template: &Template, event: &Event, field: &'static str) -> Option<String> {
+    template
+        .render_string(event)
+        .map_err(|error| {
+            emit!(TemplateRenderingError {
+                error,
+                field: Some(field),
+                drop_event: true,
+            });
+        })
+        .ok()
+}
+
+impl Partitioner for ChroniclePartitioner {
+    type Item = Event;
+    type Key = Option<ChroniclePartitionKey>;
+
+    fn partition(&self, item: &Self::Item) -> Self::Key {
+        let customer_id = render(&self.customer_id, item, "customer_id")?;
+        let log_type = match &self.log_type {
+            Some(log_type) => Some(render(log_type, item, "log_type")?),
+            None => None,
+        };
+        let namespace = match &self.namespace {
+            Some(namespace) => Some(render(namespace, item, "namespace")?),
+            None => None,
+        };
+        let labels = self
+            .labels
+            .iter()
+            .map(|(key, value)| Some((key.clone(), render(value, item, "labels")?)))
+            .collect::<Option<_>>()?;
+
+        Some(ChroniclePartitionKey {
+            customer_id,
+            log_type,
+            namespace,
+            labels,
+        })
+    }
+}
+
+struct ChronicleSink<Svc> {
+    service: Svc,
+    request_builder: ChronicleRequestBuilder,
+    partitioner: ChroniclePartitioner,
+    batcher_settings: BatcherSettings,
+}
+
+impl<Svc> ChronicleSink<Svc>
+where
+    Svc: Service<ChronicleRequest> + Send + 'static,
+    Svc::Future: Send + 'static,
+    Svc::Response: DriverResponse + Send + 'static,
+    Svc::Error: fmt::Debug + Into<crate::Error> + Send,
+{
+    async fn run_inner(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        let builder_limit = NonZeroUsize::new(64);
+
+        input
+            .batched_partitioned(self.partitioner, self.batcher_settings)
+            .filter_map(|(key, batch)| async move {
+                // A `TemplateRenderingError` will have been emitted by `ChroniclePartitioner` if
+                // the key here is `None`, thus no further `EventsDropped` event needs emitting at
+                // this stage.
+                key.map(move |key| (key, batch))
+            })
+            .request_builder(builder_limit, self.request_builder)
+            .filter_map(|request| async move {
+                match request {
+                    Err(error) => {
+                        emit!(SinkRequestBuildError { error });
+                        None
+                    }
+                    Ok(request) => Some(request),
+                }
+            })
+            .into_driver(self.service)
+            .run()
+            .await
+    }
+}
+
+#[async_trait]
+impl<Svc> StreamSink<Event> for ChronicleSink<Svc>
+where
+    Svc: Service<ChronicleRequest> + Send + 'static,
+    Svc::Future: Send + 'static,
+    Svc::Response: DriverResponse + Send + 'static,
+    Svc::Error: fmt::Debug + Into<crate::Error> + Send,
+{
+    async fn run(mut self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        self.run_inner(input).await
+    }
+}
+
+/// Encodes batches of events into the request bodies of the configured API.
+#[derive(Clone, Debug)]
+struct ChronicleEncoder {
+    encoder: Option<codecs::Encoder<()>>,
+    transformer: codecs::Transformer,
+}
+
+impl ChronicleEncoder {
+    fn unstructured_entries(
+        &self,
+        events: Vec<Event>,
+        encoder: &codecs::Encoder<()>,
+    ) -> Vec<Value> {
+        let mut encoder = encoder.clone();
+        events
+            .into_iter()
+            .filter_map(|mut event| {
+                let timestamp = event
+                    .as_log()
+                    .get(log_schema().timestamp_key())
+                    .and_then(|ts| ts.as_timestamp())
+                    .cloned();
+                let mut bytes = BytesMut::new();
+                self.transformer.transform(&mut event);
+                encoder.encode(event, &mut bytes).ok()?;
+
+                let mut entry = json!({
+                    "log_text": String::from_utf8_lossy(&bytes),
+                });
+
+                if let Some(ts) = timestamp {
+                    entry["ts_rfc3339"] = ts
+                        .to_rfc3339_opts(chrono::SecondsFormat::AutoSi, true)
+                        .into();
+                }
+
+                Some(entry)
+            })
+            .collect()
+    }
+
+    fn udm_events(&self, events: Vec<Event>, labels: &[Value]) -> Vec<Value> {
+        events
+            .into_iter()
+            .filter_map(|mut event| {
+                self.transformer.transform(&mut event);
+                let mut udm_event = serde_json::to_value(event.as_log()).ok()?;
+
+                if !labels.is_empty() {
+                    if let Some(metadata) = udm_event
+                        .as_object_mut()
+                        .map(|udm_event| udm_event.entry("metadata").or_insert_with(|| json!({})))
+                        .and_then(Value::as_object_mut)
+                    {
+                        if let Some(ingestion_labels) = metadata
+                            .entry("ingestion_labels")
+                            .or_insert_with(|| json!([]))
+                            .as_array_mut()
+                        {
+                            ingestion_labels.extend_from_slice(labels);
+                        }
+                    }
+                }
+
+                Some(udm_event)
+            })
+            .collect()
+    }
+}
+
+impl Encoder<(ChroniclePartitionKey, Vec<Event>)> for ChronicleEncoder {
+    fn encode_input(
+        &self,
+        input: (ChroniclePartitionKey, Vec<Event>),
+        writer: &mut dyn io::Write,
+    ) -> io::Result<usize> {
+        let (key, events) = input;
+        let labels = key
+            .labels
+            .into_iter()
+            .map(|(key, value)| json!({ "key": key, "value": value }))
+            .collect::<Vec<_>>();
+
+        let mut body = Map::new();
+        body.insert("customer_id".to_string(), key.customer_id.into());
+
+        match &self.encoder {
+            Some(encoder) => {
+                if let Some(log_type) = key.log_type {
+                    body.insert("log_type".to_string(), log_type.into());
+                }
+                if let Some(namespace) = key.namespace {
+                    body.insert("namespace".to_string(), namespace.into());
+                }
+                if !labels.is_empty() {
+                    body.insert("labels".to_string(), labels.into());
+                }
+                body.insert(
+                    "entries".to_string(),
+                    self.unstructured_entries(events, encoder).into(),
+                );
+            }
+            None => {
+                body.insert(
+                    "events".to_string(),
+                    self.udm_events(events, &labels).into(),
+                );
+            }
+        }
+
+        as_tracked_write::<_, _, io::Error>(writer, &body, |writer, body| {
+            serde_json::to_writer(writer, body)?;
+            Ok(())
+        })
+    }
+}
+
+struct ChronicleRequestPayload {
+    bytes: Bytes,
+}
+
+impl From<Bytes> for ChronicleRequestPayload {
+    fn from(bytes: Bytes) -> Self {
+        Self { bytes }
+    }
+}
+
+impl AsRef<[u8]> for ChronicleRequestPayload {
+    fn as_ref(&self) -> &[u8] {
+        self.bytes.as_ref()
+    }
+}
+
+#[derive(Clone, Debug)]
+struct ChronicleRequestBuilder {
+    encoder: ChronicleEncoder,
+}
+
+impl ChronicleRequestBuilder {
+    fn new(config: &ChronicleConfig) -> crate::Result<Self> {
+        let (encoder, transformer) = match config.api {
+            ChronicleApi::Unstructured => {
+                if config.log_type.is_none() {
+                    return Err(BuildError::MissingLogType.into());
+                }
+                let encoding = config
+                    .encoding
+                    .as_ref()
+                    .ok_or(BuildError::MissingEncoding)?;
+                let serializer = encoding.config().build()?;
+                (
+                    Some(codecs::Encoder::<()>::new(serializer)),
+                    encoding.transformer(),
+                )
+            }
+            ChronicleApi::Udm => (
+                None,
+                config
+                    .encoding
+                    .as_ref()
+                    .map(EncodingConfig::transformer)
+                    .unwrap_or_default(),
+            ),
+        };
+
+        Ok(Self {
+            encoder: ChronicleEncoder {
+                encoder,
+                transformer,
+            },
+        })
+    }
+}
+
+impl RequestBuilder<(ChroniclePartitionKey, Vec<Event>)> for ChronicleRequestBuilder {
+    type Metadata = (EventFinalizers, RequestMetadataBuilder);
+    type Events = (ChroniclePartitionKey, Vec<Event>);
+    type Encoder = ChronicleEncoder;
+    type Payload = ChronicleRequestPayload;
+    type Request = ChronicleRequest;
+    type Error = io::Error;
+
+    fn compression(&self) -> Compression {
+        Compression::None
+    }
+
+    fn encoder(&self) -> &Self::Encoder {
+        &self.encoder
+    }
+
+    fn split_input(
+        &self,
+        input: (ChroniclePartitionKey, Vec<Event>),
+    ) -> (Self::Metadata, Self::Events) {
+        let (key, mut events) = input;
+        let finalizers = events.take_finalizers();
+
+        let metadata = RequestMetadata::builder(&events);
+        ((finalizers, metadata), (key, events))
+    }
+
+    fn build_request(
+        &self,
+        metadata: Self::Metadata,
+        payload: EncodeResult<Self::Payload>,
+    ) -> Self::Request {
+        let (finalizers, metadata_builder) = metadata;
+
+        let metadata = metadata_builder.build(&payload);
+        let body = payload.into_payload().bytes;
+
+        ChronicleRequest {
+            body,
+            finalizers,
+            metadata,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+
+    use super::*;
+    use crate::event::LogEvent;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<ChronicleConfig>();
+    }
+
+    fn parse_config(source: &str) -> ChronicleConfig {
+        toml::from_str(source).unwrap()
+    }
+
+    fn encode(config: &ChronicleConfig, events: Vec<Event>) -> Vec<(Value, usize)> {
+        let partitioner = ChroniclePartitioner::new(config);
+        let encoder = ChronicleRequestBuilder::new(config).unwrap().encoder;
+
+        let mut batches: IndexMap<ChroniclePartitionKey, Vec<Event>> = IndexMap::new();
+        for event in events {
+            let key = partitioner.partition(&event).unwrap();
+            batches.entry(key).or_default().push(event);
+        }
+
+        batches
+            .into_iter()
+            .map(|batch| {
+                let count = batch.1.len();
+                let mut body = Vec::new();
+                encoder.encode_input(batch, &mut body).unwrap();
+                (serde_json::from_slice(&body).unwrap(), count)
+            })
+            .collect()
+    }
+
+    fn log(message: &str, customer_id: &str) -> Event {
+        let mut log = LogEvent::from(message);
+        log.insert("customer_id", customer_id);
+        log.insert(
+            log_schema().timestamp_key(),
+            Utc.ymd(2022, 10, 1).and_hms(12, 30, 0),
+        );
+        log.into()
+    }
+
+    #[test]
+    fn routes_unstructured_entries_by_customer_id() {
+        let config = parse_config(indoc! {r#"
+            customer_id = "{{ customer_id }}"
+            log_type = "WINDOWS_DNS"
+            namespace = "prod"
+            labels.env = "production"
+            encoding.codec = "text"
+        "#});
+
+        let batches = encode(
+            &config,
+            vec![log("one", "a"), log("two", "b"), log("three", "a")],
+        );
+
+        assert_eq!(batches.len(), 2);
+        assert_eq!(batches[0].1, 2);
+        assert_eq!(
+            batches[0].0,
+            json!({
+                "customer_id": "a",
+                "log_type": "WINDOWS_DNS",
+                "namespace": "prod",
+                "labels": [{ "key": "env", "value": "production" }],
+                "entries": [
+                    { "log_text": "one", "ts_rfc3339": "2022-10-01T12:30:00Z" },
+                    { "log_text": "three", "ts_rfc3339": "2022-10-01T12:30:00Z" },
+                ],
+            })
+        );
+        assert_eq!(batches[1].0["customer_id"], "b");
+    }
+
+    #[test]
+    fn labels_udm_events() {
+        let config = parse_config(indoc! {r#"
+            customer_id = "c8c65bfa-5f2c-42d4-9189-64bb7b939f2c"
+            api = "udm"
+            labels.env = "production"
+        "#});
+
+        let mut event = LogEvent::default();
+        event.insert("metadata.event_type", "NETWORK_DNS");
+        event.insert("metadata.ingestion_labels[0].key", "team");
+        event.insert("metadata.ingestion_labels[0].value", "security");
+        event.insert("principal.hostname", "host");
+
+        let batches = encode(&config, vec![event.into()]);
+
+        assert_eq!(
+            batches[0].0,
+            json!({
+                "customer_id": "c8c65bfa-5f2c-42d4-9189-64bb7b939f2c",
+                "events": [{
+                    "metadata": {
+                        "event_type": "NETWORK_DNS",
+                        "ingestion_labels": [
+                            { "key": "team", "value": "security" },
+                            { "key": "env", "value": "production" },
+                        ],
+                    },
+                    "principal": { "hostname": "host" },
+                }],
+            })
+        );
+    }
+
+    #[test]
+    fn unstructured_requires_log_type_and_encoding() {
+        let config = parse_config(indoc! {r#"
+            customer_id = "customer_id"
+            encoding.codec = "text"
+        "#});
+        assert!(ChronicleRequestBuilder::new(&config).is_err());
+
+        let config = parse_config(indoc! {r#"
+            customer_id = "customer_id"
+            log_type = "WINDOWS_DNS"
+        "#});
+        assert!(ChronicleRequestBuilder::new(&config).is_err());
+    }
+
+    #[test]
+    fn regional_endpoints() {
+        assert_eq!(
+            create_endpoint(None, Some(Region::EuropeWest2), ChronicleApi::Udm.path()).unwrap(),
+            "https://europe-west2-malachiteingestion-pa.googleapis.com/v2/udmevents:batchCreate"
+        );
+        assert_eq!(
+            create_endpoint(
+                Some("http://localhost:8080/"),
+                None,
+                ChronicleApi::Unstructured.path()
+            )
+            .unwrap(),
+            "http://localhost:8080/v2/unstructuredlogentries:batchCreate"
+        );
+        assert!(
+            create_endpoint(Some("http://localhost"), Some(Region::Us), "v2/logtypes").is_err()
+        );
+    }
+}
@@ -66,6 +66,46 @@ pub enum Region {
 
     /// APAC region.
     Asia,
+
+    /// London region.
+    #[serde(rename = "europe-west2")]
+    EuropeWest2,
+
+    /// Frankfurt region.
+    #[serde(rename = "europe-west3")]
+    EuropeWest3,
+
+    /// Zurich region.
+    #[serde(rename = "europe-west6")]
+    EuropeWest6,
+
+    /// Paris region.
+    #[serde(rename = "europe-west9")]
+    EuropeWest9,
+
+    /// Sydney region.
+    #[serde(rename = "australia-southeast1")]
+    AustraliaSoutheast1,
+
+    /// Tokyo region.
+    #[serde(rename = "asia-northeast1")]
+    AsiaNortheast1,
+
+    /// Mumbai region.
+    #[serde(rename = "asia-south1")]
+    AsiaSouth1,
+
+    /// Tel Aviv region.
+    #[serde(rename = "me-west1")]
+    MeWest1,
+
+    /// Dammam region.
+    #[serde(rename = "me-central2")]
+    MeCentral2,
+
+    /// Toronto region.
+    #[serde(rename = "northamerica-northeast2")]
+    NorthamericaNortheast2,
 }
 
 impl Region {
@@ -75,6 +115,22 @@ impl Region {
             Region::Eu => "https://europe-malachiteingestion-pa.googleapis.com",
             Region::Us => "https://malachiteingestion-pa.googleapis.com",
             Region::Asia => "https://asia-southeast1-malachiteingestion-pa.googleapis.com",
+            Region::EuropeWest2 => "https://europe-west2-malachiteingestion-pa.googleapis.com",
+            Region::EuropeWest3 => "https://europe-west3-malachiteingestion-pa.googleapis.com",
+            Region::EuropeWest6 => "https://europe-west6-malachiteingestion-pa.googleapis.com",
+            Region::EuropeWest9 => "https://europe-west9-malachiteingestion-pa.googleapis.com",
+            Region::AustraliaSoutheast1 => {
+                "https://australia-southeast1-malachiteingestion-pa.googleapis.com"
+            }
+            Region::AsiaNortheast1 => {
+                "https://asia-northeast1-malachiteingestion-pa.googleapis.com"
+            }
+            Region::AsiaSouth1 => "https://asia-south1-malachiteingestion-pa.googleapis.com",
+            Region::MeWest1 => "https://me-west1-malachiteingestion-pa.googleapis.com",
+            Region::MeCentral2 => "https://me-central2-malachiteingestion-pa.googleapis.com",
+            Region::NorthamericaNortheast2 => {
+                "https://northamerica-northeast2-malachiteingestion-pa.googleapis.com"
+            }
         }
     }
 }
@@ -242,19 +298,28 @@ impl ChronicleUnstructuredConfig {
     }
 
     fn create_endpoint(&self, path: &str) -> Result<String, ChronicleError> {
-        Ok(format!(
-            "{}/{}",
-            match (&self.endpoint, self.region) {
-                (Some(endpoint), None) => endpoint.trim_end_matches('/'),
-                (None, Some(region)) => region.endpoint(),
-                (Some(_), Some(_)) => return Err(ChronicleError::BothRegionAndEndpoint),
-                (None, None) => return Err(ChronicleError::RegionOrEndpoint),
-            },
-            path
-        ))
+        create_endpoint(self.endpoint.as_deref(), self.region, path)
     }
 }
 
+/// Builds the URL of an API path, at either the custom endpoint or the endpoint of the region.
+pub(super) fn create_endpoint(
+    endpoint: Option<&str>,
+    region: Option<Region>,
+    path: &str,
+) -> Result<String, ChronicleError> {
+    Ok(format!(
+        "{}/{}",
+        match (endpoint, region) {
+            (Some(endpoint), None) => endpoint.trim_end_matches('/'),
+            (None, Some(region)) => region.endpoint(),
+            (Some(_), Some(_)) => return Err(ChronicleError::BothRegionAndEndpoint),
+            (None, None) => return Err(ChronicleError::RegionOrEndpoint),
+        },
+        path
+    ))
+}
+
 #[derive(Clone, Debug)]
 pub struct ChronicleRequest {
     pub body: Bytes,
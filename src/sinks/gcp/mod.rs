@@ -1,6 +1,7 @@
 use serde::{Deserialize, Serialize};
 use vector_config::configurable_component;
 
+pub mod chronicle;
 pub mod chronicle_unstructured;
 pub mod cloud_storage;
 pub mod pubsub;
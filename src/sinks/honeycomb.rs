@@ -1,3 +1,5 @@
+use std::borrow::Cow;
+
 use bytes::Bytes;
 use futures::{FutureExt, SinkExt};
 use http::{Request, StatusCode, Uri};
@@ -8,12 +10,15 @@ use vector_config::configurable_component;
 use crate::{
     codecs::Transformer,
     config::{log_schema, AcknowledgementsConfig, GenerateConfig, Input, SinkConfig, SinkContext},
-    event::{Event, Value},
+    event::{Event, LogEvent, Value},
     http::HttpClient,
+    internal_events::TemplateRenderingError,
     sinks::util::{
-        http::{BatchedHttpSink, HttpEventEncoder, HttpSink},
-        BatchConfig, BoxedRawValue, JsonArrayBuffer, SinkBatchSettings, TowerRequestConfig,
+        http::{HttpEventEncoder, HttpSink, PartitionHttpSink},
+        BatchConfig, BoxedRawValue, JsonArrayBuffer, PartitionBuffer, PartitionInnerBuffer,
+        SinkBatchSettings, TowerRequestConfig,
     },
+    template::Template,
 };
 
 /// Configuration for the `honeycomb` sink.
@@ -27,9 +32,11 @@ pub struct HoneycombConfig {
     api_key: SensitiveString,
 
     /// The dataset that Vector will send logs to.
-    // TODO: we probably want to make this a template
-    // but this limits us in how we can do our healthcheck.
-    dataset: String,
+    #[configurable(metadata(templateable))]
+    dataset: Template,
+
+    #[configurable(derived)]
+    markers: Option<MarkersConfig>,
 
     #[configurable(derived)]
     #[serde(default)]
@@ -55,10 +62,49 @@ pub struct HoneycombConfig {
     acknowledgements: AcknowledgementsConfig,
 }
 
+/// Configuration for creating [markers][markers] from events.
+///
+/// Events with the `type_field` are sent to the Markers API of their dataset instead of being sent
+/// as events, for example to mark deploys.
+///
+/// [markers]: https://docs.honeycomb.io/api/markers/
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct MarkersConfig {
+    /// The log field identifying marker events, holding the type of the marker, such as `deploy`.
+    #[serde(default = "default_marker_type_field")]
+    type_field: String,
+
+    /// The log field holding the message of the marker.
+    #[serde(default = "default_marker_message_field")]
+    message_field: String,
+
+    /// The log field holding the URL of the marker.
+    #[serde(default = "default_marker_url_field")]
+    url_field: String,
+}
+
 fn default_endpoint() -> String {
-    "https://api.honeycomb.io/1/batch".to_string()
+    "https://api.honeycomb.io".to_string()
+}
+
+fn default_marker_type_field() -> String {
+    "marker_type".to_string()
+}
+
+fn default_marker_message_field() -> String {
+    log_schema().message_key().to_string()
 }
 
+fn default_marker_url_field() -> String {
+    "marker_url".to_string()
+}
+
+/// The metadata field holding the sample rate of an event, for events sampled before reaching
+/// Vector.
+const SAMPLE_RATE_KEY: &str = "samplerate";
+
 #[derive(Clone, Copy, Debug, Default)]
 struct HoneycombDefaultBatchSettings;
 
@@ -87,11 +133,11 @@ impl SinkConfig for HoneycombConfig {
         let request_settings = self.request.unwrap_with(&TowerRequestConfig::default());
         let batch_settings = self.batch.into_batch_settings()?;
 
-        let buffer = JsonArrayBuffer::new(batch_settings.size);
+        let buffer = PartitionBuffer::new(JsonArrayBuffer::new(batch_settings.size));
 
         let client = HttpClient::new(None, cx.proxy())?;
 
-        let sink = BatchedHttpSink::new(
+        let sink = PartitionHttpSink::new(
             self.clone(),
             buffer,
             request_settings,
@@ -114,12 +160,83 @@ impl SinkConfig for HoneycombConfig {
     }
 }
 
+/// The requests events are batched into.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum HoneycombPartition {
+    /// Events sent to the Batch API of the dataset.
+    Events { dataset: String },
+    /// A marker sent to the Markers API of the dataset. The Markers API takes a single marker per
+    /// request, so each marker gets its own partition.
+    Marker { dataset: String, id: u64 },
+}
+
 pub struct HoneycombEventEncoder {
     transformer: Transformer,
+    dataset: Template,
+    markers: Option<MarkersConfig>,
+    next_marker_id: u64,
+}
+
+/// Encodes the marker of a marker event.
+fn encode_marker(markers: &MarkersConfig, log: &LogEvent) -> Option<serde_json::Value> {
+    let marker_type = log.get(markers.type_field.as_str())?;
+
+    let mut marker = serde_json::Map::new();
+    marker.insert("type".into(), marker_type.to_string_lossy().into());
+    if let Some(message) = log.get(markers.message_field.as_str()) {
+        marker.insert("message".into(), message.to_string_lossy().into());
+    }
+    if let Some(url) = log.get(markers.url_field.as_str()) {
+        marker.insert("url".into(), url.to_string_lossy().into());
+    }
+    if let Some(Value::Timestamp(ts)) = log.get(log_schema().timestamp_key()) {
+        marker.insert("start_time".into(), ts.timestamp().into());
+    }
+
+    Some(marker.into())
+}
+
+/// Returns the sample rate an event was sampled with, if any.
+fn sample_rate(event: &Event) -> Option<u64> {
+    match event.metadata().value().get(SAMPLE_RATE_KEY)? {
+        Value::Integer(rate) => u64::try_from(*rate).ok(),
+        Value::Float(rate) => Some(rate.into_inner().round() as u64),
+        _ => None,
+    }
+    .filter(|rate| *rate > 0)
 }
 
-impl HttpEventEncoder<serde_json::Value> for HoneycombEventEncoder {
-    fn encode_event(&mut self, mut event: Event) -> Option<serde_json::Value> {
+impl HttpEventEncoder<PartitionInnerBuffer<serde_json::Value, HoneycombPartition>>
+    for HoneycombEventEncoder
+{
+    fn encode_event(
+        &mut self,
+        mut event: Event,
+    ) -> Option<PartitionInnerBuffer<serde_json::Value, HoneycombPartition>> {
+        let dataset = self
+            .dataset
+            .render_string(&event)
+            .map_err(|error| {
+                emit!(TemplateRenderingError {
+                    error,
+                    field: Some("dataset"),
+                    drop_event: true,
+                });
+            })
+            .ok()?;
+
+        if let Some(markers) = &self.markers {
+            if let Some(marker) = encode_marker(markers, event.as_log()) {
+                let id = self.next_marker_id;
+                self.next_marker_id = self.next_marker_id.wrapping_add(1);
+                return Some(PartitionInnerBuffer::new(
+                    marker,
+                    HoneycombPartition::Marker { dataset, id },
+                ));
+            }
+        }
+
+        let sample_rate = sample_rate(&event);
         self.transformer.transform(&mut event);
         let mut log = event.into_log();
 
@@ -130,56 +247,80 @@ impl HttpEventEncoder<serde_json::Value> for HoneycombEventEncoder {
             chrono::Utc::now()
         };
 
-        let data = json!({
+        let mut data = json!({
             "time": timestamp.to_rfc3339_opts(chrono::SecondsFormat::Nanos, true),
             "data": log.convert_to_fields(),
         });
+        if let Some(sample_rate) = sample_rate {
+            data["samplerate"] = sample_rate.into();
+        }
 
-        Some(data)
+        Some(PartitionInnerBuffer::new(
+            data,
+            HoneycombPartition::Events { dataset },
+        ))
     }
 }
 
 #[async_trait::async_trait]
 impl HttpSink for HoneycombConfig {
-    type Input = serde_json::Value;
-    type Output = Vec<BoxedRawValue>;
+    type Input = PartitionInnerBuffer<serde_json::Value, HoneycombPartition>;
+    type Output = PartitionInnerBuffer<Vec<BoxedRawValue>, HoneycombPartition>;
     type Encoder = HoneycombEventEncoder;
 
     fn build_encoder(&self) -> Self::Encoder {
         HoneycombEventEncoder {
             transformer: self.encoding.clone(),
+            dataset: self.dataset.clone(),
+            markers: self.markers.clone(),
+            next_marker_id: 0,
         }
     }
 
-    async fn build_request(&self, events: Self::Output) -> crate::Result<Request<Bytes>> {
-        let uri = self.build_uri();
+    async fn build_request(&self, output: Self::Output) -> crate::Result<Request<Bytes>> {
+        let (events, partition) = output.into_parts();
+        let (uri, body) = match partition {
+            HoneycombPartition::Events { dataset } => (
+                self.build_uri("batch", &dataset),
+                crate::serde::json::to_bytes(&events).unwrap().freeze(),
+            ),
+            HoneycombPartition::Marker { dataset, .. } => (
+                self.build_uri("markers", &dataset),
+                crate::serde::json::to_bytes(&events[0]).unwrap().freeze(),
+            ),
+        };
         let request = Request::post(uri).header("X-Honeycomb-Team", self.api_key.inner());
-        let body = crate::serde::json::to_bytes(&events).unwrap().freeze();
 
         request.body(body).map_err(Into::into)
     }
 }
 
 impl HoneycombConfig {
-    fn build_uri(&self) -> Uri {
-        let uri = format!("{}/{}", self.endpoint, self.dataset);
+    fn build_uri(&self, api: &str, dataset: &str) -> Uri {
+        let dataset = Cow::<str>::from(percent_encoding::utf8_percent_encode(
+            dataset,
+            percent_encoding::NON_ALPHANUMERIC,
+        ));
+        let uri = format!("{}/1/{}/{}", self.endpoint, api, dataset);
 
         uri.parse::<Uri>().expect("This should be a valid uri")
     }
 }
 
 async fn healthcheck(config: HoneycombConfig, client: HttpClient) -> crate::Result<()> {
-    let req = config
-        .build_request(Vec::new())
-        .await?
-        .map(hyper::Body::from);
+    // The datasets of the events are unknown before they are rendered, so only the API key is
+    // checked.
+    let uri = format!("{}/1/auth", config.endpoint);
+    let req = Request::get(uri)
+        .header("X-Honeycomb-Team", config.api_key.inner())
+        .body(hyper::Body::empty())?;
 
     let res = client.send(req).await?;
 
     let status = res.status();
     let body = hyper::body::to_bytes(res.into_body()).await?;
 
-    if status == StatusCode::BAD_REQUEST {
+    if status == StatusCode::OK {
         Ok(())
     } else if status == StatusCode::UNAUTHORIZED {
         let json: serde_json::Value = serde_json::from_slice(&body[..])?;
@@ -208,17 +349,16 @@ async fn healthcheck(config: HoneycombConfig, client: HttpClient) -> crate::Resu
 #[cfg(test)]
 mod test {
     use futures::{future::ready, stream};
-    use vector_core::event::{Event, LogEvent};
 
     use crate::{
-        config::{GenerateConfig, SinkConfig, SinkContext},
+        config::SinkContext,
         test_util::{
             components::{run_and_assert_sink_compliance, HTTP_SINK_TAGS},
             http::{always_200_response, spawn_blackhole_http_server},
         },
     };
 
-    use super::HoneycombConfig;
+    use super::*;
 
     #[test]
     fn generate_config() {
@@ -240,4 +380,75 @@ mod test {
         let event = Event::Log(LogEvent::from("simple message"));
         run_and_assert_sink_compliance(sink, stream::once(ready(event)), &HTTP_SINK_TAGS).await;
     }
+
+    fn encode(config: &str, event: Event) -> (serde_json::Value, HoneycombPartition) {
+        let config = toml::from_str::<HoneycombConfig>(config).unwrap();
+        config
+            .build_encoder()
+            .encode_event(event)
+            .unwrap()
+            .into_parts()
+    }
+
+    #[test]
+    fn encodes_sample_rate_and_dataset() {
+        let mut log = LogEvent::from("simple message");
+        log.insert("service", "api");
+        log.metadata_mut()
+            .value_mut()
+            .insert(SAMPLE_RATE_KEY, Value::Integer(20));
+
+        let (value, partition) = encode(
+            r#"
+            api_key = "key"
+            dataset = "logs-{{ service }}"
+            "#,
+            log.into(),
+        );
+        assert_eq!(value["samplerate"], 20);
+        assert_eq!(value["data"]["message"], "simple message");
+        assert_eq!(
+            partition,
+            HoneycombPartition::Events {
+                dataset: "logs-api".to_owned()
+            }
+        );
+    }
+
+    #[test]
+    fn encodes_markers() {
+        let config = r#"
+            api_key = "key"
+            dataset = "logs"
+            markers = {}
+        "#;
+
+        let mut log = LogEvent::from("Deployed v1.2.3");
+        log.insert("marker_type", "deploy");
+        log.insert("marker_url", "https://example.com/releases/v1.2.3");
+        let (value, partition) = encode(config, log.into());
+        assert_eq!(value["type"], "deploy");
+        assert_eq!(value["message"], "Deployed v1.2.3");
+        assert_eq!(value["url"], "https://example.com/releases/v1.2.3");
+        assert!(value["start_time"].is_i64());
+        assert!(
+            matches!(partition, HoneycombPartition::Marker { dataset, .. } if dataset == "logs")
+        );
+
+        let (value, partition) = encode(config, LogEvent::from("simple message").into());
+        assert!(value.get("samplerate").is_none());
+        assert!(matches!(partition, HoneycombPartition::Events { .. }));
+    }
+
+    #[test]
+    fn encodes_dataset_in_uri() {
+        let config =
+            toml::from_str::<HoneycombConfig>(&HoneycombConfig::generate_config().to_string())
+                .unwrap();
+
+        assert_eq!(
+            config.build_uri("batch", "my dataset").to_string(),
+            "https://api.honeycomb.io/1/batch/my%20dataset"
+        );
+    }
 }
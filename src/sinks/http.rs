@@ -19,12 +19,14 @@ use crate::{
     config::{AcknowledgementsConfig, GenerateConfig, Input, SinkConfig, SinkContext},
     event::Event,
     http::{Auth, HttpClient, MaybeAuth},
+    internal_events::TemplateRenderingError,
     sinks::util::{
         self,
-        http::{BatchedHttpSink, HttpEventEncoder, RequestConfig},
-        BatchConfig, Buffer, Compression, RealtimeSizeBasedDefaultBatchSettings,
-        TowerRequestConfig, UriSerde,
+        http::{HttpEventEncoder, PartitionHttpSink, RequestConfig},
+        BatchConfig, Buffer, Compression, PartitionBuffer, PartitionInnerBuffer,
+        RealtimeSizeBasedDefaultBatchSettings, TowerRequestConfig, UriSerde,
     },
+    template::Template,
     tls::{TlsConfig, TlsSettings},
 };
 
@@ -40,6 +42,27 @@ enum BuildError {
         value: String,
         source: header::InvalidHeaderValue,
     },
+    #[snafu(display("Invalid template for header {}: {}", name, source))]
+    InvalidHeaderTemplate {
+        name: String,
+        source: crate::template::TemplateParseError,
+    },
+}
+
+/// The rendered values of the templated headers of a batch, or `None` for those that couldn't be
+/// rendered and are left out.
+///
+/// Events are partitioned by these values, so every request only carries events that rendered the
+/// same headers.
+type HeaderValues = Vec<Option<HeaderValue>>;
+
+/// The value of a request header.
+#[derive(Clone, Debug)]
+enum HeaderTemplate {
+    Static(HeaderValue),
+    /// A value rendered from each event, such as `{{ %tenant_id }}` to read the `tenant_id` field of
+    /// its metadata.
+    Dynamic(Template),
 }
 
 /// Configuration for the `http` sink.
@@ -59,6 +82,11 @@ pub struct HttpSinkConfig {
     pub auth: Option<Auth>,
 
     /// A list of custom headers to add to each request.
+    ///
+    /// Header values containing field templates (`{{ ... }}`) are templated, in which case events are batched
+    /// separately for each distinct set of rendered header values. Templates can read the fields of the event or of its
+    /// metadata, such as `{{ %tenant_id }}`. A header that can't be rendered for an event is left out of the request
+    /// carrying it.
     #[configurable(deprecated)]
     pub headers: Option<IndexMap<String, String>>,
 
@@ -153,6 +181,7 @@ struct HttpSink {
     pub encoder: Encoder<Framer>,
     pub batch: BatchConfig<RealtimeSizeBasedDefaultBatchSettings>,
     pub request: RequestConfig,
+    pub headers: IndexMap<HeaderName, HeaderTemplate>,
 }
 
 #[cfg(test)]
@@ -169,6 +198,7 @@ fn default_sink(encoding: EncodingConfigWithFraming) -> HttpSink {
         encoder,
         batch: Default::default(),
         request: Default::default(),
+        headers: Default::default(),
     }
 }
 
@@ -190,6 +220,7 @@ impl SinkConfig for HttpSinkConfig {
         let mut request = self.request.clone();
         request.add_old_option(self.headers.clone());
         validate_headers(&request.headers, &self.auth)?;
+        let headers = build_header_templates(&request.headers)?;
 
         let (framer, serializer) = self.encoding.build(SinkType::MessageBased)?;
         let encoder = Encoder::<Framer>::new(framer, serializer);
@@ -203,6 +234,7 @@ impl SinkConfig for HttpSinkConfig {
             encoder,
             batch: self.batch,
             request,
+            headers,
         };
 
         let request = sink
@@ -211,9 +243,9 @@ impl SinkConfig for HttpSinkConfig {
             .unwrap_with(&TowerRequestConfig::default());
 
         let batch = sink.batch.into_batch_settings()?;
-        let sink = BatchedHttpSink::new(
+        let sink = PartitionHttpSink::new(
             sink,
-            Buffer::new(batch.size, Compression::None),
+            PartitionBuffer::new(Buffer::new(batch.size, Compression::None)),
             request,
             batch.timeout,
             client,
@@ -237,33 +269,78 @@ impl SinkConfig for HttpSinkConfig {
 pub struct HttpSinkEventEncoder {
     encoder: Encoder<Framer>,
     transformer: Transformer,
+    headers: Vec<(HeaderName, Template)>,
 }
 
-impl HttpEventEncoder<BytesMut> for HttpSinkEventEncoder {
-    fn encode_event(&mut self, mut event: Event) -> Option<BytesMut> {
+impl HttpSinkEventEncoder {
+    fn render_headers(&self, event: &Event) -> HeaderValues {
+        self.headers
+            .iter()
+            .map(|(name, template)| {
+                let value = template
+                    .render_string(event)
+                    .map_err(|error| {
+                        emit!(TemplateRenderingError {
+                            error,
+                            field: Some(name.as_str()),
+                            drop_event: false,
+                        });
+                    })
+                    .ok()?;
+                HeaderValue::from_str(&value)
+                    .map_err(|error| {
+                        warn!(
+                            message = "Rendered header value is invalid, leaving the header out.",
+                            header = %name,
+                            %error,
+                            internal_log_rate_limit = true,
+                        );
+                    })
+                    .ok()
+            })
+            .collect()
+    }
+}
+
+impl HttpEventEncoder<PartitionInnerBuffer<BytesMut, HeaderValues>> for HttpSinkEventEncoder {
+    fn encode_event(
+        &mut self,
+        mut event: Event,
+    ) -> Option<PartitionInnerBuffer<BytesMut, HeaderValues>> {
+        let headers = self.render_headers(&event);
+
         self.transformer.transform(&mut event);
 
         let mut body = BytesMut::new();
         self.encoder.encode(event, &mut body).ok()?;
 
-        Some(body)
+        Some(PartitionInnerBuffer::new(body, headers))
     }
 }
 
 #[async_trait::async_trait]
 impl util::http::HttpSink for HttpSink {
-    type Input = BytesMut;
-    type Output = BytesMut;
+    type Input = PartitionInnerBuffer<BytesMut, HeaderValues>;
+    type Output = PartitionInnerBuffer<BytesMut, HeaderValues>;
     type Encoder = HttpSinkEventEncoder;
 
     fn build_encoder(&self) -> Self::Encoder {
         HttpSinkEventEncoder {
             encoder: self.encoder.clone(),
             transformer: self.transformer.clone(),
+            headers: self
+                .headers
+                .iter()
+                .filter_map(|(name, template)| match template {
+                    HeaderTemplate::Static(_) => None,
+                    HeaderTemplate::Dynamic(template) => Some((name.clone(), template.clone())),
+                })
+                .collect(),
         }
     }
 
-    async fn build_request(&self, mut body: Self::Output) -> crate::Result<http::Request<Bytes>> {
+    async fn build_request(&self, output: Self::Output) -> crate::Result<http::Request<Bytes>> {
+        let (mut body, rendered_headers) = output.into_parts();
         let method = match &self.method.unwrap_or(HttpMethod::Post) {
             HttpMethod::Get => Method::GET,
             HttpMethod::Head => Method::HEAD,
@@ -334,8 +411,17 @@ impl util::http::HttpSink for HttpSink {
             Compression::None => {}
         }
 
-        for (header, value) in self.request.headers.iter() {
-            builder = builder.header(header.as_str(), value.as_str());
+        let mut rendered_headers = rendered_headers.into_iter();
+        for (header, template) in self.headers.iter() {
+            let value = match template {
+                HeaderTemplate::Static(value) => Some(value.clone()),
+                HeaderTemplate::Dynamic(_) => rendered_headers
+                    .next()
+                    .expect("every dynamic header is rendered"),
+            };
+            if let Some(value) = value {
+                builder = builder.header(header, value);
+            }
         }
 
         let mut request = builder.body(body.freeze()).unwrap();
@@ -380,6 +466,38 @@ fn validate_headers(map: &IndexMap<String, String>, auth: &Option<Auth>) -> crat
     Ok(())
 }
 
+/// Builds the values of the headers, only templating those containing template syntax.
+fn build_header_templates(
+    map: &IndexMap<String, String>,
+) -> crate::Result<IndexMap<HeaderName, HeaderTemplate>> {
+    map.iter()
+        .map(|(name, value)| {
+            let header = HeaderName::from_bytes(name.as_bytes())
+                .with_context(|_| InvalidHeaderNameSnafu { name })?;
+            // Only values with field templates are templated, so static values such as `50%` or
+            // percent-encoded ones are left alone rather than read as strftime specifiers.
+            let template = if value.contains("{{") {
+                let template = Template::try_from(value.as_str())
+                    .with_context(|_| InvalidHeaderTemplateSnafu { name })?;
+                if template.is_dynamic() {
+                    HeaderTemplate::Dynamic(template)
+                } else {
+                    HeaderTemplate::Static(
+                        HeaderValue::from_str(template.get_ref())
+                            .with_context(|_| InvalidHeaderValueSnafu { value })?,
+                    )
+                }
+            } else {
+                HeaderTemplate::Static(
+                    HeaderValue::from_str(value)
+                        .with_context(|_| InvalidHeaderValueSnafu { value })?,
+                )
+            };
+            Ok((header, template))
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use std::{
@@ -397,6 +515,7 @@ mod tests {
     use headers::{Authorization, HeaderMapExt};
     use http::request::Parts;
     use hyper::{Method, Response, StatusCode};
+    use lookup::metadata_path;
     use serde::Deserialize;
     use vector_core::event::{BatchNotifier, BatchStatus, LogEvent};
 
@@ -426,9 +545,10 @@ mod tests {
 
         let sink = default_sink((None::<FramingConfig>, TextSerializerConfig::new()).into());
         let mut encoder = sink.build_encoder();
-        let bytes = encoder.encode_event(event).unwrap();
+        let (bytes, headers) = encoder.encode_event(event).unwrap().into_parts();
 
         assert_eq!(bytes, Vec::from("hello world\n"));
+        assert!(headers.is_empty());
     }
 
     #[test]
@@ -443,7 +563,7 @@ mod tests {
                 .into(),
         );
         let mut encoder = sink.build_encoder();
-        let bytes = encoder.encode_event(event).unwrap();
+        let (bytes, _) = encoder.encode_event(event).unwrap().into_parts();
 
         #[derive(Deserialize, Debug)]
         #[serde(deny_unknown_fields)]
@@ -458,6 +578,85 @@ mod tests {
         assert_eq!(output.message, "hello world".to_string());
     }
 
+    #[test]
+    fn http_encode_event_renders_templated_headers() {
+        let mut sink = default_sink((None::<FramingConfig>, TextSerializerConfig::new()).into());
+        sink.headers = build_header_templates(
+            &vec![
+                ("X-Scope-OrgID".to_string(), "{{ tenant }}".to_string()),
+                ("X-Static".to_string(), "static".to_string()),
+            ]
+            .into_iter()
+            .collect(),
+        )
+        .unwrap();
+        let mut encoder = sink.build_encoder();
+
+        let mut log = LogEvent::from("hello world");
+        log.insert("tenant", "acme");
+        let (_, headers) = encoder.encode_event(log.into()).unwrap().into_parts();
+        assert_eq!(headers, vec![Some(HeaderValue::from_static("acme"))]);
+
+        // Events are kept, without the headers that can't be rendered.
+        let event = Event::Log(LogEvent::from("no tenant"));
+        let (bytes, headers) = encoder.encode_event(event).unwrap().into_parts();
+        assert_eq!(bytes, Vec::from("no tenant\n"));
+        assert_eq!(headers, vec![None]);
+    }
+
+    #[test]
+    fn http_encode_event_renders_headers_from_metadata() {
+        let mut sink = default_sink((None::<FramingConfig>, TextSerializerConfig::new()).into());
+        sink.headers = build_header_templates(
+            &vec![("X-Scope-OrgID".to_string(), "{{ %tenant_id }}".to_string())]
+                .into_iter()
+                .collect(),
+        )
+        .unwrap();
+        let mut encoder = sink.build_encoder();
+
+        let mut log = LogEvent::from("hello world");
+        log.insert(metadata_path!("tenant_id"), "acme");
+        let (_, headers) = encoder.encode_event(log.into()).unwrap().into_parts();
+        assert_eq!(headers, vec![Some(HeaderValue::from_static("acme"))]);
+    }
+
+    #[test]
+    fn http_headers_without_template_syntax_are_static() {
+        let headers = build_header_templates(
+            &vec![("X-Static".to_string(), "{ not a template }".to_string())]
+                .into_iter()
+                .collect(),
+        )
+        .unwrap();
+        assert!(matches!(
+            headers.values().next(),
+            Some(HeaderTemplate::Static(value)) if value == "{ not a template }"
+        ));
+    }
+
+    #[test]
+    fn http_headers_with_percent_signs_are_static() {
+        let headers = build_header_templates(
+            &vec![
+                ("X-Ratio".to_string(), "50%".to_string()),
+                ("X-Encoded".to_string(), "a%20b".to_string()),
+                ("X-Date".to_string(), "%Y-%m-%d".to_string()),
+            ]
+            .into_iter()
+            .collect(),
+        )
+        .unwrap();
+        let values = headers
+            .values()
+            .map(|header| match header {
+                HeaderTemplate::Static(value) => value.to_str().unwrap(),
+                HeaderTemplate::Dynamic(_) => panic!("header should be static"),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(values, vec!["50%", "a%20b", "%Y-%m-%d"]);
+    }
+
     #[test]
     fn http_validates_normal_headers() {
         let config = r#"
@@ -0,0 +1,663 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::{Arc, Mutex},
+};
+
+use bytes::Bytes;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::Deserialize;
+use vector_config::configurable_component;
+
+use super::{Field, ProtocolVersion};
+use crate::{
+    http::HttpError,
+    internal_events::{
+        ComponentEventsDropped, InfluxdbFieldTypeConflict, InfluxdbFieldTypeLearned, INTENTIONAL,
+    },
+    sinks::util::{
+        http::HttpRetryLogic,
+        retries::{RetryAction, RetryLogic},
+    },
+};
+
+/// Configuration for handling fields whose type conflicts with the type of the field in the
+/// existing series.
+///
+/// InfluxDB rejects points with such fields. The type of a field is the type it was first written
+/// with, or the type InfluxDB reported when rejecting a point.
+#[configurable_component]
+#[derive(Clone, Debug, Derivative)]
+#[derivative(Default)]
+#[serde(deny_unknown_fields)]
+pub struct FieldTypeConflictConfig {
+    #[configurable(derived)]
+    #[serde(default)]
+    pub policy: FieldTypeConflictPolicy,
+
+    /// The measurement to write points with conflicting fields to, when using the `reroute` policy.
+    ///
+    /// Defaults to the measurement of the sink, suffixed with `_conflicts`.
+    pub measurement: Option<String>,
+
+    /// The maximum number of fields whose type is tracked, across all measurements.
+    ///
+    /// Once reached, the fields tracked the longest are forgotten first, and their type is tracked
+    /// again from the next point written with them.
+    #[serde(default = "default_max_fields")]
+    #[derivative(Default(value = "default_max_fields()"))]
+    pub max_fields: usize,
+}
+
+const fn default_max_fields() -> usize {
+    10_000
+}
+
+/// What to do with a field whose type conflicts with the type of the field in the existing series.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Derivative, Eq, PartialEq)]
+#[derivative(Default)]
+#[serde(rename_all = "snake_case")]
+pub enum FieldTypeConflictPolicy {
+    /// Cast the value to the type of the existing field.
+    ///
+    /// Values that can't be cast are dropped from the point.
+    Cast,
+
+    /// Drop the field from the point.
+    DropField,
+
+    /// Drop the point, as InfluxDB would when rejecting it.
+    #[derivative(Default)]
+    DropPoint,
+
+    /// Write the point to the conflict measurement instead, with the conflicting fields as
+    /// strings.
+    Reroute,
+}
+
+impl FieldTypeConflictPolicy {
+    const fn as_str(self) -> &'static str {
+        match self {
+            Self::Cast => "cast",
+            Self::DropField => "drop_field",
+            Self::DropPoint => "drop_point",
+            Self::Reroute => "reroute",
+        }
+    }
+}
+
+/// The type of a field, as InfluxDB sees it.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(super) enum FieldType {
+    Float,
+    Integer,
+    Unsigned,
+    String,
+    Boolean,
+}
+
+impl FieldType {
+    const fn of(field: &Field, protocol_version: ProtocolVersion) -> Self {
+        match field {
+            Field::String(_) => Self::String,
+            Field::Float(_) => Self::Float,
+            // InfluxDB v1 has no unsigned integers, so they are written as integers.
+            Field::UnsignedInt(_) => match protocol_version {
+                ProtocolVersion::V1 => Self::Integer,
+                ProtocolVersion::V2 | ProtocolVersion::V3 => Self::Unsigned,
+            },
+            Field::Int(_) => Self::Integer,
+            Field::Bool(_) => Self::Boolean,
+        }
+    }
+
+    fn parse(name: &str) -> Option<Self> {
+        match name {
+            "float" => Some(Self::Float),
+            "integer" => Some(Self::Integer),
+            "unsigned" | "uinteger" => Some(Self::Unsigned),
+            "string" => Some(Self::String),
+            "boolean" => Some(Self::Boolean),
+            _ => None,
+        }
+    }
+
+    const fn as_str(self) -> &'static str {
+        match self {
+            Self::Float => "float",
+            Self::Integer => "integer",
+            Self::Unsigned => "unsigned",
+            Self::String => "string",
+            Self::Boolean => "boolean",
+        }
+    }
+
+    /// Casts `field` to this type, if its value can be represented by it.
+    fn cast(self, field: Field) -> Option<Field> {
+        match (self, field) {
+            (Self::String, Field::String(s)) => Some(Field::String(s)),
+            (Self::String, Field::Float(f)) => Some(Field::String(f.to_string())),
+            (Self::String, Field::UnsignedInt(u)) => Some(Field::String(u.to_string())),
+            (Self::String, Field::Int(i)) => Some(Field::String(i.to_string())),
+            (Self::String, Field::Bool(b)) => Some(Field::String(b.to_string())),
+
+            (Self::Float, Field::Float(f)) => Some(Field::Float(f)),
+            (Self::Float, Field::UnsignedInt(u)) => Some(Field::Float(u as f64)),
+            (Self::Float, Field::Int(i)) => Some(Field::Float(i as f64)),
+            (Self::Float, Field::String(s)) => s.trim().parse().ok().map(Field::Float),
+
+            (Self::Integer, Field::Int(i)) => Some(Field::Int(i)),
+            (Self::Integer, Field::UnsignedInt(u)) => i64::try_from(u).ok().map(Field::Int),
+            (Self::Integer, Field::Float(f)) => float_to_integer(f).map(Field::Int),
+            (Self::Integer, Field::String(s)) => s.trim().parse().ok().map(Field::Int),
+
+            (Self::Unsigned, Field::UnsignedInt(u)) => Some(Field::UnsignedInt(u)),
+            (Self::Unsigned, Field::Int(i)) => u64::try_from(i).ok().map(Field::UnsignedInt),
+            (Self::Unsigned, Field::Float(f)) => float_to_integer(f)
+                .and_then(|i| u64::try_from(i).ok())
+                .map(Field::UnsignedInt),
+            (Self::Unsigned, Field::String(s)) => s.trim().parse().ok().map(Field::UnsignedInt),
+
+            (Self::Boolean, Field::Bool(b)) => Some(Field::Bool(b)),
+            (Self::Boolean, Field::String(s)) => s.trim().parse().ok().map(Field::Bool),
+
+            _ => None,
+        }
+    }
+}
+
+fn float_to_integer(value: f64) -> Option<i64> {
+    if value.fract() == 0.0 && value >= i64::MIN as f64 && value <= i64::MAX as f64 {
+        Some(value as i64)
+    } else {
+        None
+    }
+}
+
+/// The types of the fields written so far, by measurement.
+#[derive(Clone, Debug)]
+pub(super) struct FieldTypes(Arc<Mutex<KnownTypes>>);
+
+#[derive(Debug)]
+struct KnownTypes {
+    measurements: HashMap<String, HashMap<String, FieldType>>,
+    /// The fields in the order they were first tracked, to forget the oldest ones first.
+    order: VecDeque<(String, String)>,
+    max_fields: usize,
+}
+
+impl KnownTypes {
+    fn get(&self, measurement: &str) -> Option<&HashMap<String, FieldType>> {
+        self.measurements.get(measurement)
+    }
+
+    /// Sets the type of a field, forgetting the fields tracked the longest beyond the maximum
+    /// number of fields.
+    fn insert(&mut self, measurement: &str, field: &str, field_type: FieldType) {
+        let fields = self.measurements.entry(measurement.to_owned()).or_default();
+        if fields.insert(field.to_owned(), field_type).is_some() {
+            return;
+        }
+        self.order
+            .push_back((measurement.to_owned(), field.to_owned()));
+
+        while self.order.len() > self.max_fields {
+            let (measurement, field) = match self.order.pop_front() {
+                Some(oldest) => oldest,
+                None => break,
+            };
+            if let Some(fields) = self.measurements.get_mut(&measurement) {
+                fields.remove(&field);
+                if fields.is_empty() {
+                    self.measurements.remove(&measurement);
+                }
+            }
+        }
+    }
+}
+
+impl FieldTypes {
+    fn new(max_fields: usize) -> Self {
+        Self(Arc::new(Mutex::new(KnownTypes {
+            measurements: HashMap::new(),
+            order: VecDeque::new(),
+            max_fields,
+        })))
+    }
+
+    /// Records the type InfluxDB reported for a field, replacing the type it was first written
+    /// with.
+    fn learn(&self, measurement: String, field: String, field_type: FieldType) {
+        emit!(InfluxdbFieldTypeLearned {
+            measurement: &measurement,
+            field: &field,
+            field_type: field_type.as_str(),
+        });
+        self.0
+            .lock()
+            .expect("mutex poisoned")
+            .insert(&measurement, &field, field_type);
+    }
+}
+
+/// What to do with a point after resolving its field type conflicts.
+#[derive(Debug, Eq, PartialEq)]
+pub(super) enum Resolution {
+    /// Write the point to its measurement.
+    Write,
+    /// Write the point to the conflict measurement.
+    Reroute,
+    /// Drop the point.
+    Drop,
+}
+
+/// Applies the field type conflict policy to the points of a sink.
+#[derive(Clone, Debug)]
+pub(super) struct ConflictResolver {
+    policy: FieldTypeConflictPolicy,
+    conflict_measurement: String,
+    protocol_version: ProtocolVersion,
+    types: FieldTypes,
+}
+
+impl ConflictResolver {
+    pub(super) fn new(
+        config: &FieldTypeConflictConfig,
+        measurement: &str,
+        protocol_version: ProtocolVersion,
+    ) -> Self {
+        Self {
+            policy: config.policy,
+            conflict_measurement: config
+                .measurement
+                .clone()
+                .unwrap_or_else(|| format!("{}_conflicts", measurement)),
+            protocol_version,
+            types: FieldTypes::new(config.max_fields),
+        }
+    }
+
+    pub(super) fn conflict_measurement(&self) -> &str {
+        &self.conflict_measurement
+    }
+
+    /// The retry logic learning the field types InfluxDB reports when rejecting points.
+    pub(super) fn retry_logic(&self) -> InfluxDbRetryLogic {
+        InfluxDbRetryLogic {
+            inner: HttpRetryLogic,
+            types: self.types.clone(),
+        }
+    }
+
+    /// Resolves the conflicts between the `fields` of a point in `measurement` and the fields
+    /// written so far, recording the types of new fields.
+    pub(super) fn resolve(
+        &self,
+        measurement: &str,
+        fields: &mut HashMap<String, Field>,
+    ) -> Resolution {
+        let mut types = self.types.0.lock().expect("mutex poisoned");
+
+        let conflicts = find_conflicts(
+            types.get(measurement),
+            fields,
+            self.protocol_version,
+            measurement,
+            self.policy,
+        );
+        let resolution = if conflicts.is_empty() {
+            Resolution::Write
+        } else {
+            match self.policy {
+                FieldTypeConflictPolicy::Cast => {
+                    for (name, field_type) in conflicts {
+                        if let Some(field) = fields.remove(&name).and_then(|f| field_type.cast(f)) {
+                            fields.insert(name, field);
+                        }
+                    }
+                    Resolution::Write
+                }
+                FieldTypeConflictPolicy::DropField => {
+                    for (name, _) in conflicts {
+                        fields.remove(&name);
+                    }
+                    Resolution::Write
+                }
+                FieldTypeConflictPolicy::DropPoint => Resolution::Drop,
+                FieldTypeConflictPolicy::Reroute => {
+                    for (name, _) in conflicts {
+                        if let Some(field) = fields.remove(&name) {
+                            let field = FieldType::String
+                                .cast(field)
+                                .expect("fields can always be cast to strings");
+                            fields.insert(name, field);
+                        }
+                    }
+                    // Fields conflicting in the conflict measurement too are dropped.
+                    let conflicts = find_conflicts(
+                        types.get(&self.conflict_measurement),
+                        fields,
+                        self.protocol_version,
+                        &self.conflict_measurement,
+                        FieldTypeConflictPolicy::DropField,
+                    );
+                    for (name, _) in conflicts {
+                        fields.remove(&name);
+                    }
+                    Resolution::Reroute
+                }
+            }
+        };
+
+        if resolution == Resolution::Drop || fields.is_empty() {
+            emit!(ComponentEventsDropped::<INTENTIONAL> {
+                count: 1,
+                reason: "Field type conflict.",
+            });
+            return Resolution::Drop;
+        }
+
+        let measurement = match resolution {
+            Resolution::Reroute => self.conflict_measurement.as_str(),
+            _ => measurement,
+        };
+        for (name, field) in fields.iter() {
+            let known = types
+                .get(measurement)
+                .map_or(false, |known| known.contains_key(name));
+            if !known {
+                types.insert(
+                    measurement,
+                    name,
+                    FieldType::of(field, self.protocol_version),
+                );
+            }
+        }
+
+        resolution
+    }
+}
+
+/// Returns the fields conflicting with the `known` field types, along with their known type.
+fn find_conflicts(
+    known: Option<&HashMap<String, FieldType>>,
+    fields: &HashMap<String, Field>,
+    protocol_version: ProtocolVersion,
+    measurement: &str,
+    policy: FieldTypeConflictPolicy,
+) -> Vec<(String, FieldType)> {
+    let known = match known {
+        Some(known) => known,
+        None => return Vec::new(),
+    };
+    fields
+        .iter()
+        .filter_map(|(name, field)| {
+            let field_type = FieldType::of(field, protocol_version);
+            match known.get(name) {
+                Some(&expected) if expected != field_type => {
+                    emit!(InfluxdbFieldTypeConflict {
+                        measurement,
+                        field: name,
+                        expected: expected.as_str(),
+                        actual: field_type.as_str(),
+                        policy: policy.as_str(),
+                    });
+                    Some((name.clone(), expected))
+                }
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+/// Retry logic recording the field types InfluxDB reports when rejecting points with conflicting
+/// field types.
+#[derive(Clone, Debug)]
+pub(super) struct InfluxDbRetryLogic {
+    inner: HttpRetryLogic,
+    types: FieldTypes,
+}
+
+impl RetryLogic for InfluxDbRetryLogic {
+    type Error = HttpError;
+    type Response = http::Response<Bytes>;
+
+    fn is_retriable_error(&self, error: &Self::Error) -> bool {
+        self.inner.is_retriable_error(error)
+    }
+
+    fn should_retry_response(&self, response: &Self::Response) -> RetryAction {
+        if response.status().is_client_error() {
+            for (measurement, field, field_type) in reported_conflicts(response.body()) {
+                self.types.learn(measurement, field, field_type);
+            }
+        }
+        self.inner.should_retry_response(response)
+    }
+}
+
+// InfluxDB v1 and v2 report the first conflict of a write, in plain text or in the `message` of a
+// JSON body.
+static V2_CONFLICT: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r#"input field "(?P<field>[^"]+)" on measurement "(?P<measurement>[^"]+)" is type \w+, already exists as type (?P<type>\w+)"#,
+    )
+    .unwrap()
+});
+
+// InfluxDB v3 reports each rejected line.
+static V3_CONFLICT: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"column '(?P<field>[^']+)', expected iox::column_type::field::(?P<type>\w+)"#)
+        .unwrap()
+});
+
+#[derive(Deserialize)]
+struct ErrorBody {
+    message: Option<String>,
+    error: Option<String>,
+    #[serde(default)]
+    data: Vec<RejectedLine>,
+}
+
+#[derive(Deserialize)]
+struct RejectedLine {
+    original_line: String,
+    error_message: String,
+}
+
+/// Parses the field type conflicts reported in the body of a response to a write.
+fn reported_conflicts(body: &[u8]) -> Vec<(String, String, FieldType)> {
+    let body = match serde_json::from_slice::<ErrorBody>(body) {
+        Ok(body) if !body.data.is_empty() => {
+            return body
+                .data
+                .iter()
+                .filter_map(|line| {
+                    let captures = V3_CONFLICT.captures(&line.error_message)?;
+                    Some((
+                        line_measurement(&line.original_line),
+                        captures["field"].to_owned(),
+                        FieldType::parse(&captures["type"])?,
+                    ))
+                })
+                .collect();
+        }
+        Ok(ErrorBody {
+            message: Some(message),
+            ..
+        })
+        | Ok(ErrorBody {
+            error: Some(message),
+            ..
+        }) => message,
+        _ => String::from_utf8_lossy(body).into_owned(),
+    };
+
+    V2_CONFLICT
+        .captures_iter(&body)
+        .filter_map(|captures| {
+            Some((
+                captures["measurement"].to_owned(),
+                captures["field"].to_owned(),
+                FieldType::parse(&captures["type"])?,
+            ))
+        })
+        .collect()
+}
+
+/// Returns the unescaped measurement of a line of line protocol.
+fn line_measurement(line: &str) -> String {
+    let mut measurement = String::new();
+    let mut chars = line.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => measurement.extend(chars.next()),
+            ',' | ' ' => break,
+            c => measurement.push(c),
+        }
+    }
+    measurement
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn resolver(policy: FieldTypeConflictPolicy) -> ConflictResolver {
+        ConflictResolver::new(
+            &FieldTypeConflictConfig {
+                policy,
+                ..Default::default()
+            },
+            "logs",
+            ProtocolVersion::V2,
+        )
+    }
+
+    fn fields(value: Field) -> HashMap<String, Field> {
+        vec![
+            ("message".to_owned(), Field::String("hello".to_owned())),
+            ("value".to_owned(), value),
+        ]
+        .into_iter()
+        .collect()
+    }
+
+    fn resolve(resolver: &ConflictResolver, value: Field) -> (Resolution, HashMap<String, Field>) {
+        let mut fields = fields(value);
+        let resolution = resolver.resolve("logs", &mut fields);
+        (resolution, fields)
+    }
+
+    #[test]
+    fn drops_conflicting_points() {
+        let resolver = resolver(FieldTypeConflictPolicy::DropPoint);
+
+        assert_eq!(resolve(&resolver, Field::Float(1.5)).0, Resolution::Write);
+        assert_eq!(resolve(&resolver, Field::Float(2.5)).0, Resolution::Write);
+        assert_eq!(resolve(&resolver, Field::Int(3)).0, Resolution::Drop);
+    }
+
+    #[test]
+    fn drops_conflicting_fields() {
+        let resolver = resolver(FieldTypeConflictPolicy::DropField);
+        resolve(&resolver, Field::Float(1.5));
+
+        let (resolution, fields) = resolve(&resolver, Field::Bool(true));
+        assert_eq!(resolution, Resolution::Write);
+        assert!(fields.contains_key("message"));
+        assert!(!fields.contains_key("value"));
+    }
+
+    #[test]
+    fn casts_conflicting_fields() {
+        let resolver = resolver(FieldTypeConflictPolicy::Cast);
+        resolve(&resolver, Field::Float(1.5));
+
+        let (_, fields) = resolve(&resolver, Field::Int(3));
+        assert!(matches!(fields["value"], Field::Float(value) if value == 3.0));
+
+        let (_, fields) = resolve(&resolver, Field::String("4.5".to_owned()));
+        assert!(matches!(fields["value"], Field::Float(value) if value == 4.5));
+
+        let (resolution, fields) = resolve(&resolver, Field::String("n/a".to_owned()));
+        assert_eq!(resolution, Resolution::Write);
+        assert!(!fields.contains_key("value"));
+    }
+
+    #[test]
+    fn reroutes_conflicting_points() {
+        let resolver = resolver(FieldTypeConflictPolicy::Reroute);
+        assert_eq!(resolver.conflict_measurement(), "logs_conflicts");
+        resolve(&resolver, Field::Float(1.5));
+
+        let (resolution, fields) = resolve(&resolver, Field::Int(3));
+        assert_eq!(resolution, Resolution::Reroute);
+        assert!(matches!(&fields["value"], Field::String(value) if value == "3"));
+    }
+
+    #[test]
+    fn forgets_oldest_fields() {
+        let resolver = ConflictResolver::new(
+            &FieldTypeConflictConfig {
+                max_fields: 2,
+                ..Default::default()
+            },
+            "logs",
+            ProtocolVersion::V2,
+        );
+        let resolve = |measurement: &str, name: &str, field: Field| {
+            let mut fields = HashMap::from([(name.to_owned(), field)]);
+            resolver.resolve(measurement, &mut fields)
+        };
+
+        resolve("logs", "first", Field::Float(1.5));
+        resolve("logs", "second", Field::Float(1.5));
+        resolve("other", "third", Field::Float(1.5));
+
+        // The type of `first` was forgotten, so that any type is written again.
+        assert_eq!(resolve("logs", "first", Field::Int(1)), Resolution::Write);
+        assert_eq!(resolve("other", "third", Field::Int(1)), Resolution::Drop);
+    }
+
+    #[test]
+    fn learns_reported_types() {
+        let resolver = resolver(FieldTypeConflictPolicy::DropPoint);
+        resolve(&resolver, Field::Int(3));
+
+        let response = http::Response::builder()
+            .status(422)
+            .body(Bytes::from(
+                r#"{"code":"unprocessable entity","message":"failure writing points to database: partial write: field type conflict: input field \"value\" on measurement \"logs\" is type integer, already exists as type float dropped=1"}"#,
+            ))
+            .unwrap();
+        assert!(resolver
+            .retry_logic()
+            .should_retry_response(&response)
+            .is_not_retryable());
+
+        assert_eq!(resolve(&resolver, Field::Int(3)).0, Resolution::Drop);
+        assert_eq!(resolve(&resolver, Field::Float(3.0)).0, Resolution::Write);
+    }
+
+    #[test]
+    fn parses_reported_conflicts() {
+        assert_eq!(
+            reported_conflicts(
+                br#"partial write: field type conflict: input field "value" on measurement "cpu" is type integer, already exists as type float dropped=1"#
+            ),
+            vec![("cpu".to_owned(), "value".to_owned(), FieldType::Float)]
+        );
+
+        assert_eq!(
+            reported_conflicts(
+                br#"{"error":"partial write of line protocol occurred","data":[{"original_line":"my\\ logs,host=a value=1i 1","line_number":1,"error_message":"invalid column type for column 'value', expected iox::column_type::field::float, got iox::column_type::field::integer"}]}"#
+            ),
+            vec![("my logs".to_owned(), "value".to_owned(), FieldType::Float)]
+        );
+
+        assert!(reported_conflicts(b"unable to parse points").is_empty());
+    }
+}
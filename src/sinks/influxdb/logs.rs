@@ -14,8 +14,9 @@ use crate::{
     internal_events::InfluxdbEncodingError,
     sinks::{
         influxdb::{
+            conflicts::{ConflictResolver, FieldTypeConflictConfig, Resolution},
             encode_timestamp, healthcheck, influx_line_protocol, influxdb_settings, Field,
-            InfluxDb1Settings, InfluxDb2Settings, ProtocolVersion,
+            InfluxDb1Settings, InfluxDb2Settings, InfluxDb3Settings, ProtocolVersion,
         },
         util::{
             http::{BatchedHttpSink, HttpEventEncoder, HttpSink},
@@ -64,6 +65,15 @@ pub struct InfluxDbLogsConfig {
     #[serde(flatten)]
     pub influxdb2_settings: Option<InfluxDb2Settings>,
 
+    /// Configuration settings for InfluxDB v3.x.
+    ///
+    /// Set instead of the settings for InfluxDB v0.x/v1.x or v2.x.
+    pub influxdb3: Option<InfluxDb3Settings>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub field_type_conflicts: FieldTypeConflictConfig,
+
     #[configurable(derived)]
     #[serde(
         skip_serializing_if = "crate::serde::skip_serializing_if_default",
@@ -99,6 +109,7 @@ struct InfluxDbLogsSink {
     measurement: String,
     tags: HashSet<String>,
     transformer: Transformer,
+    conflicts: ConflictResolver,
 }
 
 impl GenerateConfig for InfluxDbLogsConfig {
@@ -137,6 +148,7 @@ impl SinkConfig for InfluxDbLogsConfig {
         let settings = influxdb_settings(
             self.influxdb1_settings.clone(),
             self.influxdb2_settings.clone(),
+            self.influxdb3.clone(),
         )
         .unwrap();
 
@@ -146,6 +158,9 @@ impl SinkConfig for InfluxDbLogsConfig {
         let token = settings.token();
         let protocol_version = settings.protocol_version();
 
+        let conflicts =
+            ConflictResolver::new(&self.field_type_conflicts, &measurement, protocol_version);
+        let retry_logic = conflicts.retry_logic();
         let sink = InfluxDbLogsSink {
             uri,
             token: token.inner().to_owned(),
@@ -153,11 +168,13 @@ impl SinkConfig for InfluxDbLogsConfig {
             measurement,
             tags,
             transformer: self.encoding.clone(),
+            conflicts,
         };
 
-        let sink = BatchedHttpSink::new(
+        let sink = BatchedHttpSink::with_logic(
             sink,
             Buffer::new(batch.size, Compression::None),
+            retry_logic,
             request,
             batch.timeout,
             client,
@@ -181,6 +198,7 @@ struct InfluxDbLogsEncoder {
     measurement: String,
     tags: HashSet<String>,
     transformer: Transformer,
+    conflicts: ConflictResolver,
 }
 
 impl HttpEventEncoder<BytesMut> for InfluxDbLogsEncoder {
@@ -210,10 +228,16 @@ impl HttpEventEncoder<BytesMut> for InfluxDbLogsEncoder {
             }
         });
 
+        let measurement = match self.conflicts.resolve(&self.measurement, &mut fields) {
+            Resolution::Write => self.measurement.as_str(),
+            Resolution::Reroute => self.conflicts.conflict_measurement(),
+            Resolution::Drop => return None,
+        };
+
         let mut output = BytesMut::new();
         if let Err(error_message) = influx_line_protocol(
             self.protocol_version,
-            &self.measurement,
+            measurement,
             Some(tags),
             Some(fields),
             timestamp,
@@ -242,6 +266,7 @@ impl HttpSink for InfluxDbLogsSink {
             measurement: self.measurement.clone(),
             tags: self.tags.clone(),
             transformer: self.transformer.clone(),
+            conflicts: self.conflicts.clone(),
         }
     }
 
@@ -281,6 +306,7 @@ impl InfluxDbLogsConfig {
             config.endpoint,
             config.influxdb1_settings,
             config.influxdb2_settings,
+            config.influxdb3,
             client,
         )?;
 
@@ -512,6 +538,39 @@ mod tests {
         assert_eq!("1542182950000000011\n", line_protocol.3);
     }
 
+    #[test]
+    fn test_encode_event_with_field_type_conflict() {
+        let mut sink = create_sink(
+            "http://localhost:9999",
+            "my-token",
+            ProtocolVersion::V2,
+            "vector",
+            ["metric_type"].to_vec(),
+        );
+        sink.conflicts = ConflictResolver::new(
+            &toml::from_str(r#"policy = "reroute""#).unwrap(),
+            "vector",
+            ProtocolVersion::V2,
+        );
+        let mut encoder = sink.build_encoder();
+
+        let mut event = Event::Log(LogEvent::from("hello"));
+        event.as_mut_log().insert("value", 100);
+        encoder.encode_event(event).unwrap();
+
+        let mut event = Event::Log(LogEvent::from("hello"));
+        event.as_mut_log().insert("value", "n/a");
+        let bytes = encoder.encode_event(event).unwrap();
+        let string = std::str::from_utf8(&bytes).unwrap();
+
+        let line_protocol = split_line_protocol(string);
+        assert_eq!("vector_conflicts", line_protocol.0);
+        assert_fields(
+            line_protocol.2.to_string(),
+            ["value=\"n/a\"", "message=\"hello\""].to_vec(),
+        );
+    }
+
     #[test]
     fn test_encode_nested_fields() {
         let mut event = LogEvent::default();
@@ -745,6 +804,7 @@ mod tests {
         let token = token.to_string();
         let measurement = measurement.to_string();
         let tags: HashSet<String> = tags.into_iter().map(|tag| tag.to_string()).collect();
+        let conflicts = ConflictResolver::new(&Default::default(), &measurement, protocol_version);
         InfluxDbLogsSink {
             uri,
             token,
@@ -752,6 +812,7 @@ mod tests {
             measurement,
             tags,
             transformer: Default::default(),
+            conflicts,
         }
     }
 }
@@ -794,6 +855,8 @@ mod integration_tests {
                 bucket: BUCKET.to_string(),
                 token: TOKEN.to_string().into(),
             }),
+            influxdb3: None,
+            field_type_conflicts: Default::default(),
             encoding: Default::default(),
             batch: Default::default(),
             request: Default::default(),
@@ -24,13 +24,14 @@ use crate::{
     internal_events::InfluxdbEncodingError,
     sinks::{
         influxdb::{
+            conflicts::{ConflictResolver, FieldTypeConflictConfig, Resolution},
             encode_timestamp, healthcheck, influx_line_protocol, influxdb_settings, Field,
-            InfluxDb1Settings, InfluxDb2Settings, ProtocolVersion,
+            InfluxDb1Settings, InfluxDb2Settings, InfluxDb3Settings, ProtocolVersion,
         },
         util::{
             buffer::metrics::{MetricNormalize, MetricNormalizer, MetricSet, MetricsBuffer},
             encode_namespace,
-            http::{HttpBatchService, HttpRetryLogic},
+            http::HttpBatchService,
             statistic::{validate_quantiles, DistributionStatistic},
             BatchConfig, EncodedEvent, SinkBatchSettings, TowerRequestConfig,
         },
@@ -43,6 +44,7 @@ use crate::{
 struct InfluxDbSvc {
     config: InfluxDbConfig,
     protocol_version: ProtocolVersion,
+    conflicts: ConflictResolver,
     inner: HttpBatchService<BoxFuture<'static, crate::Result<hyper::Request<Bytes>>>>,
 }
 
@@ -76,6 +78,15 @@ pub struct InfluxDbConfig {
     #[serde(flatten)]
     pub influxdb2_settings: Option<InfluxDb2Settings>,
 
+    /// Configuration settings for InfluxDB v3.x.
+    ///
+    /// Set instead of the settings for InfluxDB v0.x/v1.x or v2.x.
+    pub influxdb3: Option<InfluxDb3Settings>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub field_type_conflicts: FieldTypeConflictConfig,
+
     #[configurable(derived)]
     #[serde(default)]
     pub batch: BatchConfig<InfluxDbDefaultBatchSettings>,
@@ -124,6 +135,7 @@ impl SinkConfig for InfluxDbConfig {
             self.clone().endpoint,
             self.clone().influxdb1_settings,
             self.clone().influxdb2_settings,
+            self.clone().influxdb3,
             client.clone(),
         )?;
         validate_quantiles(&self.quantiles)?;
@@ -145,6 +157,7 @@ impl InfluxDbSvc {
         let settings = influxdb_settings(
             config.influxdb1_settings.clone(),
             config.influxdb2_settings.clone(),
+            config.influxdb3.clone(),
         )?;
 
         let endpoint = config.endpoint.clone();
@@ -161,16 +174,25 @@ impl InfluxDbSvc {
 
         let http_service = HttpBatchService::new(client, create_build_request(uri, token.inner()));
 
+        // Metrics have no single measurement, so the conflict measurement defaults to one named
+        // after the default namespace.
+        let conflicts = ConflictResolver::new(
+            &config.field_type_conflicts,
+            config.default_namespace.as_deref().unwrap_or("vector"),
+            protocol_version,
+        );
+        let retry_logic = conflicts.retry_logic();
         let influxdb_http_service = InfluxDbSvc {
             config,
             protocol_version,
+            conflicts,
             inner: http_service,
         };
         let mut normalizer = MetricNormalizer::<InfluxMetricNormalize>::default();
 
         let sink = request
             .batch_sink(
-                HttpRetryLogic,
+                retry_logic,
                 influxdb_http_service,
                 MetricsBuffer::new(batch.size),
                 batch.timeout,
@@ -205,6 +227,7 @@ impl Service<Vec<Metric>> for InfluxDbSvc {
             self.config.default_namespace.as_deref(),
             self.config.tags.as_ref(),
             &self.config.quantiles,
+            Some(&self.conflicts),
         );
         let body = input.freeze();
 
@@ -272,6 +295,7 @@ fn encode_events(
     default_namespace: Option<&str>,
     tags: Option<&HashMap<String, String>>,
     quantiles: &[f64],
+    conflicts: Option<&ConflictResolver>,
 ) -> BytesMut {
     let mut output = BytesMut::new();
     let count = events.len() as u64;
@@ -280,14 +304,28 @@ fn encode_events(
         let fullname = encode_namespace(event.namespace().or(default_namespace), '.', event.name());
         let ts = encode_timestamp(event.timestamp());
         let tags = merge_tags(&event, tags);
-        let (metric_type, fields) = get_type_and_fields(event.value(), quantiles);
+        let (metric_type, mut fields) = get_type_and_fields(event.value(), quantiles);
 
         let mut unwrapped_tags = tags.unwrap_or_default();
         unwrapped_tags.insert("metric_type".to_owned(), metric_type.to_owned());
 
+        let mut measurement = fullname.as_str();
+        if let (Some(conflicts), Some(fields)) = (conflicts, fields.as_mut()) {
+            match conflicts.resolve(&fullname, fields) {
+                Resolution::Write => {}
+                Resolution::Reroute => {
+                    // The conflict measurement is shared by all metrics, so their name is kept as
+                    // a tag.
+                    unwrapped_tags.insert("measurement".to_owned(), fullname.clone());
+                    measurement = conflicts.conflict_measurement();
+                }
+                Resolution::Drop => continue,
+            }
+        }
+
         if let Err(error_message) = influx_line_protocol(
             protocol_version,
-            &fullname,
+            measurement,
             Some(unwrapped_tags),
             fields,
             ts,
@@ -482,7 +520,8 @@ mod tests {
             .with_timestamp(Some(ts())),
         ];
 
-        let line_protocols = encode_events(ProtocolVersion::V2, events, Some("vector"), None, &[]);
+        let line_protocols =
+            encode_events(ProtocolVersion::V2, events, Some("vector"), None, &[], None);
         assert_eq!(
             line_protocols,
             "ns.total,metric_type=counter value=1.5 1542182950000000011\n\
@@ -490,6 +529,60 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_encode_with_field_type_conflict() {
+        let conflicts = ConflictResolver::new(
+            &toml::from_str(r#"policy = "reroute""#).unwrap(),
+            "vector",
+            ProtocolVersion::V2,
+        );
+        let events = vec![
+            Metric::new(
+                "latency",
+                MetricKind::Absolute,
+                MetricValue::AggregatedHistogram {
+                    buckets: vector_core::buckets![1.0 => 1, 2.0 => 2],
+                    count: 3,
+                    sum: 5.0,
+                },
+            )
+            .with_namespace(Some("ns"))
+            .with_timestamp(Some(ts())),
+            Metric::new(
+                "latency",
+                MetricKind::Absolute,
+                MetricValue::Distribution {
+                    samples: vector_core::samples![1.0 => 1, 2.0 => 2],
+                    statistic: StatisticKind::Histogram,
+                },
+            )
+            .with_namespace(Some("ns"))
+            .with_timestamp(Some(ts())),
+        ];
+
+        let line_protocols = encode_events(
+            ProtocolVersion::V2,
+            events,
+            None,
+            None,
+            &[],
+            Some(&conflicts),
+        );
+        let line_protocols =
+            String::from_utf8(line_protocols.freeze().as_ref().to_owned()).unwrap();
+        let line_protocols: Vec<&str> = line_protocols.split('\n').collect();
+        assert_eq!(line_protocols.len(), 2);
+
+        let line_protocol = split_line_protocol(line_protocols[1]);
+        assert_eq!("vector_conflicts", line_protocol.0);
+        assert_eq!(
+            "measurement=ns.latency,metric_type=distribution",
+            line_protocol.1
+        );
+        let fields = line_protocol.2;
+        assert!(fields.split(',').any(|field| field == "count=\"3\""));
+    }
+
     #[test]
     fn test_encode_gauge() {
         let events = vec![Metric::new(
@@ -501,7 +594,7 @@ mod tests {
         .with_tags(Some(tags()))
         .with_timestamp(Some(ts()))];
 
-        let line_protocols = encode_events(ProtocolVersion::V2, events, None, None, &[]);
+        let line_protocols = encode_events(ProtocolVersion::V2, events, None, None, &[], None);
         assert_eq!(
             line_protocols,
             "ns.meter,metric_type=gauge,normal_tag=value,true_tag=true value=-1.5 1542182950000000011"
@@ -521,7 +614,7 @@ mod tests {
         .with_tags(Some(tags()))
         .with_timestamp(Some(ts()))];
 
-        let line_protocols = encode_events(ProtocolVersion::V2, events, None, None, &[]);
+        let line_protocols = encode_events(ProtocolVersion::V2, events, None, None, &[], None);
         assert_eq!(
             line_protocols,
             "ns.users,metric_type=set,normal_tag=value,true_tag=true value=2 1542182950000000011"
@@ -543,7 +636,7 @@ mod tests {
         .with_tags(Some(tags()))
         .with_timestamp(Some(ts()))];
 
-        let line_protocols = encode_events(ProtocolVersion::V1, events, None, None, &[]);
+        let line_protocols = encode_events(ProtocolVersion::V1, events, None, None, &[], None);
         let line_protocols =
             String::from_utf8(line_protocols.freeze().as_ref().to_owned()).unwrap();
         let line_protocols: Vec<&str> = line_protocols.split('\n').collect();
@@ -584,7 +677,7 @@ mod tests {
         .with_tags(Some(tags()))
         .with_timestamp(Some(ts()))];
 
-        let line_protocols = encode_events(ProtocolVersion::V2, events, None, None, &[]);
+        let line_protocols = encode_events(ProtocolVersion::V2, events, None, None, &[], None);
         let line_protocols =
             String::from_utf8(line_protocols.freeze().as_ref().to_owned()).unwrap();
         let line_protocols: Vec<&str> = line_protocols.split('\n').collect();
@@ -625,7 +718,7 @@ mod tests {
         .with_tags(Some(tags()))
         .with_timestamp(Some(ts()))];
 
-        let line_protocols = encode_events(ProtocolVersion::V1, events, None, None, &[]);
+        let line_protocols = encode_events(ProtocolVersion::V1, events, None, None, &[], None);
         let line_protocols =
             String::from_utf8(line_protocols.freeze().as_ref().to_owned()).unwrap();
         let line_protocols: Vec<&str> = line_protocols.split('\n').collect();
@@ -666,7 +759,7 @@ mod tests {
         .with_tags(Some(tags()))
         .with_timestamp(Some(ts()))];
 
-        let line_protocols = encode_events(ProtocolVersion::V2, events, None, None, &[]);
+        let line_protocols = encode_events(ProtocolVersion::V2, events, None, None, &[], None);
         let line_protocols =
             String::from_utf8(line_protocols.freeze().as_ref().to_owned()).unwrap();
         let line_protocols: Vec<&str> = line_protocols.split('\n').collect();
@@ -738,7 +831,7 @@ mod tests {
             .with_timestamp(Some(ts())),
         ];
 
-        let line_protocols = encode_events(ProtocolVersion::V2, events, None, None, &[]);
+        let line_protocols = encode_events(ProtocolVersion::V2, events, None, None, &[], None);
         let line_protocols =
             String::from_utf8(line_protocols.freeze().as_ref().to_owned()).unwrap();
         let line_protocols: Vec<&str> = line_protocols.split('\n').collect();
@@ -816,7 +909,7 @@ mod tests {
         .with_tags(Some(tags()))
         .with_timestamp(Some(ts()))];
 
-        let line_protocols = encode_events(ProtocolVersion::V2, events, None, None, &[]);
+        let line_protocols = encode_events(ProtocolVersion::V2, events, None, None, &[], None);
         assert_eq!(line_protocols.len(), 0);
     }
 
@@ -834,7 +927,7 @@ mod tests {
         .with_tags(Some(tags()))
         .with_timestamp(Some(ts()))];
 
-        let line_protocols = encode_events(ProtocolVersion::V2, events, None, None, &[]);
+        let line_protocols = encode_events(ProtocolVersion::V2, events, None, None, &[], None);
         assert_eq!(line_protocols.len(), 0);
     }
 
@@ -858,6 +951,7 @@ mod tests {
             None,
             None,
             &default_summary_quantiles(),
+            None,
         );
         let line_protocols =
             String::from_utf8(line_protocols.freeze().as_ref().to_owned()).unwrap();
@@ -922,6 +1016,7 @@ mod tests {
             Some("ns"),
             Some(tags).as_ref(),
             &[],
+            None,
         );
         let line_protocols =
             String::from_utf8(line_protocols.freeze().as_ref().to_owned()).unwrap();
@@ -997,6 +1092,8 @@ mod integration_tests {
                 password: None,
             }),
             influxdb2_settings: None,
+            influxdb3: None,
+            field_type_conflicts: Default::default(),
             batch: Default::default(),
             request: Default::default(),
             tls,
@@ -1090,6 +1187,8 @@ mod integration_tests {
                 bucket: BUCKET.to_string(),
                 token: TOKEN.to_string().into(),
             }),
+            influxdb3: None,
+            field_type_conflicts: Default::default(),
             quantiles: default_summary_quantiles(),
             batch: Default::default(),
             request: Default::default(),
@@ -1,3 +1,4 @@
+mod conflicts;
 pub mod logs;
 pub mod metrics;
 
@@ -33,11 +34,12 @@ pub(in crate::sinks) enum Field {
 pub(in crate::sinks) enum ProtocolVersion {
     V1,
     V2,
+    V3,
 }
 
 #[derive(Debug, Snafu)]
 enum ConfigError {
-    #[snafu(display("InfluxDB v1 or v2 should be configured as endpoint."))]
+    #[snafu(display("InfluxDB v1, v2 or v3 should be configured as endpoint."))]
     MissingConfiguration,
     #[snafu(display(
         "Unclear settings. Both version configured v1: {:?}, v2: {:?}.",
@@ -48,6 +50,8 @@ enum ConfigError {
         v1_settings: InfluxDb1Settings,
         v2_settings: InfluxDb2Settings,
     },
+    #[snafu(display("Unclear settings. InfluxDB v3 configured along with v1 or v2."))]
+    V3AndOtherConfiguration,
 }
 
 /// Configuration settings for InfluxDB v0.x/v1.x.
@@ -102,6 +106,19 @@ pub struct InfluxDb2Settings {
     token: SensitiveString,
 }
 
+/// Configuration settings for InfluxDB v3.x.
+#[configurable_component]
+#[derive(Clone, Debug)]
+pub struct InfluxDb3Settings {
+    /// The name of the database to write into.
+    database: String,
+
+    /// The [token][token_docs] to authenticate with.
+    ///
+    /// [token_docs]: https://docs.influxdata.com/influxdb3/core/admin/tokens/
+    token: SensitiveString,
+}
+
 trait InfluxDbSettings: std::fmt::Debug {
     fn write_uri(&self, endpoint: String) -> crate::Result<Uri>;
     fn healthcheck_uri(&self, endpoint: String) -> crate::Result<Uri>;
@@ -164,35 +181,75 @@ impl InfluxDbSettings for InfluxDb2Settings {
     }
 }
 
+impl InfluxDbSettings for InfluxDb3Settings {
+    fn write_uri(&self, endpoint: String) -> crate::Result<Uri> {
+        encode_uri(
+            &endpoint,
+            "api/v3/write_lp",
+            &[
+                ("db", Some(self.database.clone())),
+                ("precision", Some("nanosecond".to_owned())),
+            ],
+        )
+    }
+
+    fn healthcheck_uri(&self, endpoint: String) -> crate::Result<Uri> {
+        encode_uri(&endpoint, "ping", &[])
+    }
+
+    fn token(&self) -> SensitiveString {
+        self.token.clone()
+    }
+
+    fn protocol_version(&self) -> ProtocolVersion {
+        ProtocolVersion::V3
+    }
+}
+
 fn influxdb_settings(
     influxdb1_settings: Option<InfluxDb1Settings>,
     influxdb2_settings: Option<InfluxDb2Settings>,
+    influxdb3_settings: Option<InfluxDb3Settings>,
 ) -> Result<Box<dyn InfluxDbSettings>, crate::Error> {
-    match (influxdb1_settings, influxdb2_settings) {
-        (Some(v1_settings), Some(v2_settings)) => Err(ConfigError::BothConfiguration {
+    match (influxdb1_settings, influxdb2_settings, influxdb3_settings) {
+        (Some(v1_settings), Some(v2_settings), _) => Err(ConfigError::BothConfiguration {
             v1_settings,
             v2_settings,
         }
         .into()),
-        (None, None) => Err(ConfigError::MissingConfiguration.into()),
-        (Some(settings), _) => Ok(Box::new(settings)),
-        (_, Some(settings)) => Ok(Box::new(settings)),
+        (Some(_), _, Some(_)) | (_, Some(_), Some(_)) => {
+            Err(ConfigError::V3AndOtherConfiguration.into())
+        }
+        (None, None, None) => Err(ConfigError::MissingConfiguration.into()),
+        (Some(settings), _, _) => Ok(Box::new(settings)),
+        (_, Some(settings), _) => Ok(Box::new(settings)),
+        (_, _, Some(settings)) => Ok(Box::new(settings)),
     }
 }
 
 // V1: https://docs.influxdata.com/influxdb/v1.7/tools/api/#ping-http-endpoint
 // V2: https://v2.docs.influxdata.com/v2.0/api/#operation/GetHealth
+// V3: https://docs.influxdata.com/influxdb3/core/api/v3/#operation/GetPing
 fn healthcheck(
     endpoint: String,
     influxdb1_settings: Option<InfluxDb1Settings>,
     influxdb2_settings: Option<InfluxDb2Settings>,
+    influxdb3_settings: Option<InfluxDb3Settings>,
     mut client: HttpClient,
 ) -> crate::Result<super::Healthcheck> {
-    let settings = influxdb_settings(influxdb1_settings, influxdb2_settings)?;
+    let settings = influxdb_settings(influxdb1_settings, influxdb2_settings, influxdb3_settings)?;
 
     let uri = settings.healthcheck_uri(endpoint)?;
 
-    let request = hyper::Request::get(uri).body(hyper::Body::empty()).unwrap();
+    let mut request = hyper::Request::get(uri);
+    // Unlike the earlier versions, InfluxDB v3 requires authentication for pings.
+    if let ProtocolVersion::V3 = settings.protocol_version() {
+        request = request.header(
+            "Authorization",
+            format!("Token {}", settings.token().inner()),
+        );
+    }
+    let request = request.body(hyper::Body::empty()).unwrap();
 
     Ok(async move {
         client
@@ -287,7 +344,7 @@ fn encode_fields(
                 output.put_slice(&i.to_string().into_bytes());
                 let c = match protocol_version {
                     ProtocolVersion::V1 => 'i',
-                    ProtocolVersion::V2 => 'u',
+                    ProtocolVersion::V2 | ProtocolVersion::V3 => 'u',
                 };
                 let mut c_buffer: [u8; 4] = [0; 4];
                 output.put_slice(c.encode_utf8(&mut c_buffer).as_bytes());
@@ -554,6 +611,7 @@ mod tests {
         pub influxdb1_settings: Option<InfluxDb1Settings>,
         #[serde(flatten)]
         pub influxdb2_settings: Option<InfluxDb2Settings>,
+        pub influxdb3: Option<InfluxDb3Settings>,
     }
 
     #[test]
@@ -565,7 +623,11 @@ mod tests {
         database = "my-database"
     "#;
         let config: InfluxDbTestConfig = toml::from_str(config).unwrap();
-        let settings = influxdb_settings(config.influxdb1_settings, config.influxdb2_settings);
+        let settings = influxdb_settings(
+            config.influxdb1_settings,
+            config.influxdb2_settings,
+            config.influxdb3,
+        );
         assert_eq!(
             settings.expect_err("expected error").to_string(),
             "Unclear settings. Both version configured v1: InfluxDb1Settings { database: \"my-database\", consistency: None, retention_policy_name: None, username: None, password: None }, v2: InfluxDb2Settings { org: \"my-org\", bucket: \"my-bucket\", token: \"**REDACTED**\" }.".to_owned()
@@ -577,10 +639,14 @@ mod tests {
         let config = r#"
     "#;
         let config: InfluxDbTestConfig = toml::from_str(config).unwrap();
-        let settings = influxdb_settings(config.influxdb1_settings, config.influxdb2_settings);
+        let settings = influxdb_settings(
+            config.influxdb1_settings,
+            config.influxdb2_settings,
+            config.influxdb3,
+        );
         assert_eq!(
             settings.expect_err("expected error").to_string(),
-            "InfluxDB v1 or v2 should be configured as endpoint.".to_owned()
+            "InfluxDB v1, v2 or v3 should be configured as endpoint.".to_owned()
         );
     }
 
@@ -590,7 +656,12 @@ mod tests {
         database = "my-database"
     "#;
         let config: InfluxDbTestConfig = toml::from_str(config).unwrap();
-        let _ = influxdb_settings(config.influxdb1_settings, config.influxdb2_settings).unwrap();
+        let _ = influxdb_settings(
+            config.influxdb1_settings,
+            config.influxdb2_settings,
+            config.influxdb3,
+        )
+        .unwrap();
     }
 
     #[test]
@@ -601,7 +672,47 @@ mod tests {
         token = "my-token"
     "#;
         let config: InfluxDbTestConfig = toml::from_str(config).unwrap();
-        let _ = influxdb_settings(config.influxdb1_settings, config.influxdb2_settings).unwrap();
+        let _ = influxdb_settings(
+            config.influxdb1_settings,
+            config.influxdb2_settings,
+            config.influxdb3,
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_influxdb3_settings() {
+        let config = r#"
+        influxdb3.database = "my-database"
+        influxdb3.token = "my-token"
+    "#;
+        let config: InfluxDbTestConfig = toml::from_str(config).unwrap();
+        let settings = influxdb_settings(
+            config.influxdb1_settings,
+            config.influxdb2_settings,
+            config.influxdb3,
+        )
+        .unwrap();
+        assert!(matches!(settings.protocol_version(), ProtocolVersion::V3));
+    }
+
+    #[test]
+    fn test_influxdb_settings_v3_and_other() {
+        let config = r#"
+        database = "my-database"
+        influxdb3.database = "my-database"
+        influxdb3.token = "my-token"
+    "#;
+        let config: InfluxDbTestConfig = toml::from_str(config).unwrap();
+        let settings = influxdb_settings(
+            config.influxdb1_settings,
+            config.influxdb2_settings,
+            config.influxdb3,
+        );
+        assert_eq!(
+            settings.expect_err("expected error").to_string(),
+            "Unclear settings. InfluxDB v3 configured along with v1 or v2.".to_owned()
+        );
     }
 
     #[test]
@@ -637,6 +748,22 @@ mod tests {
         )
     }
 
+    #[test]
+    fn test_influxdb3_test_write_uri() {
+        let settings = InfluxDb3Settings {
+            database: "my-database".to_owned(),
+            token: "my-token".to_owned().into(),
+        };
+
+        let uri = settings
+            .write_uri("http://localhost:8181".to_owned())
+            .unwrap();
+        assert_eq!(
+            "http://localhost:8181/api/v3/write_lp?db=my-database&precision=nanosecond",
+            uri.to_string()
+        )
+    }
+
     #[test]
     fn test_influxdb1_test_healthcheck_uri() {
         let settings = InfluxDb1Settings {
@@ -905,10 +1032,16 @@ mod integration_tests {
         let proxy = ProxyConfig::default();
         let client = HttpClient::new(None, &proxy).unwrap();
 
-        healthcheck(endpoint, influxdb1_settings, influxdb2_settings, client)
-            .unwrap()
-            .await
-            .unwrap()
+        healthcheck(
+            endpoint,
+            influxdb1_settings,
+            influxdb2_settings,
+            None,
+            client,
+        )
+        .unwrap()
+        .await
+        .unwrap()
     }
 
     #[tokio::test]
@@ -926,10 +1059,16 @@ mod integration_tests {
         let proxy = ProxyConfig::default();
         let client = HttpClient::new(None, &proxy).unwrap();
 
-        healthcheck(endpoint, influxdb1_settings, influxdb2_settings, client)
-            .unwrap()
-            .await
-            .unwrap();
+        healthcheck(
+            endpoint,
+            influxdb1_settings,
+            influxdb2_settings,
+            None,
+            client,
+        )
+        .unwrap()
+        .await
+        .unwrap();
     }
 
     #[tokio::test]
@@ -947,10 +1086,16 @@ mod integration_tests {
         let proxy = ProxyConfig::default();
         let client = HttpClient::new(None, &proxy).unwrap();
 
-        healthcheck(endpoint, influxdb1_settings, influxdb2_settings, client)
-            .unwrap()
-            .await
-            .unwrap();
+        healthcheck(
+            endpoint,
+            influxdb1_settings,
+            influxdb2_settings,
+            None,
+            client,
+        )
+        .unwrap()
+        .await
+        .unwrap();
     }
 
     #[tokio::test]
@@ -968,9 +1113,15 @@ mod integration_tests {
         let proxy = ProxyConfig::default();
         let client = HttpClient::new(None, &proxy).unwrap();
 
-        healthcheck(endpoint, influxdb1_settings, influxdb2_settings, client)
-            .unwrap()
-            .await
-            .unwrap();
+        healthcheck(
+            endpoint,
+            influxdb1_settings,
+            influxdb2_settings,
+            None,
+            client,
+        )
+        .unwrap()
+        .await
+        .unwrap();
     }
 }
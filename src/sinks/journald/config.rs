@@ -0,0 +1,144 @@
+use std::path::PathBuf;
+
+use futures::FutureExt;
+use snafu::{ResultExt, Snafu};
+use vector_config::configurable_component;
+
+use super::{encoder::JournalEncoder, sink::JournaldSink};
+use crate::{
+    codecs::Transformer,
+    config::{AcknowledgementsConfig, GenerateConfig, Input, SinkConfig, SinkContext},
+    sinks::{Healthcheck, VectorSink},
+};
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("Invalid default priority {}: must be between 0 and 7", priority))]
+    InvalidDefaultPriority { priority: u8 },
+}
+
+#[derive(Debug, Snafu)]
+enum HealthcheckError {
+    #[snafu(display("Journal socket {:?} is unavailable: {}", path, source))]
+    SocketUnavailable {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+}
+
+/// Configuration for the `journald` sink.
+#[configurable_component(sink("journald"))]
+#[configurable(metadata(status = "alpha"))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct JournaldSinkConfig {
+    /// The path of the socket the journal receives entries on.
+    #[serde(default = "default_socket_path")]
+    pub socket_path: PathBuf,
+
+    /// The field holding the priority of events.
+    ///
+    /// Priorities are either syslog severity numbers, from `0` (`emerg`) to `7` (`debug`), or
+    /// severity names, such as `err`, `warning`, or `info`.
+    #[serde(default = "default_priority_key")]
+    pub priority_key: String,
+
+    /// The priority of the events whose priority is missing or unknown, from `0` to `7`.
+    #[serde(default = "default_priority")]
+    pub default_priority: u8,
+
+    /// The syslog identifier of the events without a `SYSLOG_IDENTIFIER` field.
+    #[serde(default = "default_syslog_identifier")]
+    pub syslog_identifier: String,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    pub encoding: Transformer,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        deserialize_with = "crate::serde::bool_or_struct",
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    pub acknowledgements: AcknowledgementsConfig,
+}
+
+fn default_socket_path() -> PathBuf {
+    PathBuf::from("/run/systemd/journal/socket")
+}
+
+fn default_priority_key() -> String {
+    "severity".to_owned()
+}
+
+const fn default_priority() -> u8 {
+    6
+}
+
+fn default_syslog_identifier() -> String {
+    "vector".to_owned()
+}
+
+impl GenerateConfig for JournaldSinkConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self {
+            socket_path: default_socket_path(),
+            priority_key: default_priority_key(),
+            default_priority: default_priority(),
+            syslog_identifier: default_syslog_identifier(),
+            encoding: Transformer::default(),
+            acknowledgements: AcknowledgementsConfig::default(),
+        })
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+impl SinkConfig for JournaldSinkConfig {
+    async fn build(&self, _cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        if self.default_priority > 7 {
+            return Err(Box::new(BuildError::InvalidDefaultPriority {
+                priority: self.default_priority,
+            }));
+        }
+
+        let encoder = JournalEncoder::new(
+            self.priority_key.clone(),
+            self.default_priority,
+            self.syslog_identifier.clone(),
+        );
+        let sink = JournaldSink::new(self.socket_path.clone(), self.encoding.clone(), encoder);
+        let healthcheck = healthcheck(self.socket_path.clone()).boxed();
+
+        Ok((VectorSink::from_event_streamsink(sink), healthcheck))
+    }
+
+    fn input(&self) -> Input {
+        Input::log()
+    }
+
+    fn acknowledgements(&self) -> &AcknowledgementsConfig {
+        &self.acknowledgements
+    }
+}
+
+async fn healthcheck(path: PathBuf) -> crate::Result<()> {
+    tokio::fs::metadata(&path)
+        .await
+        .context(SocketUnavailableSnafu { path })?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<JournaldSinkConfig>();
+    }
+}
@@ -0,0 +1,205 @@
+use bytes::{BufMut, Bytes, BytesMut};
+
+use crate::{
+    config::log_schema,
+    event::{LogEvent, Value},
+};
+
+/// The maximum length of the names of journal fields.
+const MAX_FIELD_NAME_LENGTH: usize = 64;
+
+/// Encodes log events as journal entries, in the native protocol of the journal.
+#[derive(Clone, Debug)]
+pub(super) struct JournalEncoder {
+    priority_key: String,
+    default_priority: u8,
+    syslog_identifier: String,
+}
+
+impl JournalEncoder {
+    pub(super) const fn new(
+        priority_key: String,
+        default_priority: u8,
+        syslog_identifier: String,
+    ) -> Self {
+        Self {
+            priority_key,
+            default_priority,
+            syslog_identifier,
+        }
+    }
+
+    /// Encodes the fields of a log event into `buf`.
+    ///
+    /// The message of the event is written as the `MESSAGE` field, and its priority as the
+    /// `PRIORITY` field, while other fields are written under their sanitized name.
+    pub(super) fn encode(&self, log: &LogEvent, buf: &mut BytesMut) {
+        let priority = log
+            .get(self.priority_key.as_str())
+            .and_then(priority)
+            .unwrap_or(self.default_priority);
+        write_field(buf, "PRIORITY", priority.to_string().as_bytes());
+
+        let message_key = log_schema().message_key();
+        let mut has_syslog_identifier = false;
+        for (name, value) in log.all_fields().into_iter().flatten() {
+            if name == message_key {
+                write_field(buf, "MESSAGE", &value_bytes(value));
+                continue;
+            }
+
+            let name = match sanitize_field_name(&name) {
+                Some(name) if name != "MESSAGE" && name != "PRIORITY" => name,
+                _ => continue,
+            };
+            has_syslog_identifier |= name == "SYSLOG_IDENTIFIER";
+            write_field(buf, &name, &value_bytes(value));
+        }
+
+        if !has_syslog_identifier {
+            write_field(buf, "SYSLOG_IDENTIFIER", self.syslog_identifier.as_bytes());
+        }
+    }
+}
+
+/// Writes a field, as `NAME=value` if the value is on a single line, or as its name followed by
+/// the length of its value otherwise.
+fn write_field(buf: &mut BytesMut, name: &str, value: &[u8]) {
+    buf.put_slice(name.as_bytes());
+    if value.contains(&b'\n') {
+        buf.put_u8(b'\n');
+        buf.put_u64_le(value.len() as u64);
+    } else {
+        buf.put_u8(b'=');
+    }
+    buf.put_slice(value);
+    buf.put_u8(b'\n');
+}
+
+fn value_bytes(value: &Value) -> Bytes {
+    match value {
+        Value::Bytes(bytes) => bytes.clone(),
+        value => value.to_string_lossy().into(),
+    }
+}
+
+/// Sanitizes a field name into a valid journal field name.
+///
+/// Journal field names may only contain uppercase letters, digits, and underscores, and must
+/// start with a letter. Names are uppercased, their invalid characters are replaced with
+/// underscores, and their leading characters which aren't letters are removed, so that fields
+/// can't be mistaken for the trusted fields the journal adds, whose names start with an
+/// underscore. Returns `None` if no valid name is left.
+fn sanitize_field_name(name: &str) -> Option<String> {
+    let name: String = name
+        .chars()
+        .skip_while(|c| !c.is_ascii_alphabetic())
+        .take(MAX_FIELD_NAME_LENGTH)
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_uppercase()
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    (!name.is_empty()).then_some(name)
+}
+
+/// Maps a syslog severity number or name to a priority.
+fn priority(value: &Value) -> Option<u8> {
+    match value {
+        Value::Integer(priority) => u8::try_from(*priority).ok().filter(|p| *p <= 7),
+        Value::Bytes(bytes) => {
+            let severity = String::from_utf8_lossy(bytes);
+            let severity = severity.trim();
+            if let Ok(priority) = severity.parse::<u8>() {
+                return (priority <= 7).then_some(priority);
+            }
+            match severity.to_ascii_lowercase().as_str() {
+                "emerg" | "emergency" | "panic" => Some(0),
+                "alert" => Some(1),
+                "crit" | "critical" | "fatal" => Some(2),
+                "err" | "error" => Some(3),
+                "warn" | "warning" => Some(4),
+                "notice" => Some(5),
+                "info" | "informational" => Some(6),
+                "debug" | "trace" => Some(7),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn encode(log: &LogEvent) -> BytesMut {
+        let encoder = JournalEncoder::new("severity".to_owned(), 6, "vector".to_owned());
+        let mut buf = BytesMut::new();
+        encoder.encode(log, &mut buf);
+        buf
+    }
+
+    #[test]
+    fn encodes_fields() {
+        let mut log = LogEvent::default();
+        log.insert("message", "hello");
+        log.insert("severity", "warning");
+        log.insert("user.name", "Jane");
+        log.insert("_private", 1);
+        log.insert("SYSLOG_IDENTIFIER", "app");
+
+        let buf = encode(&log);
+        let lines = std::str::from_utf8(&buf)
+            .unwrap()
+            .lines()
+            .collect::<Vec<_>>();
+        assert_eq!(lines[0], "PRIORITY=4");
+        assert!(lines.contains(&"MESSAGE=hello"));
+        assert!(lines.contains(&"SEVERITY=warning"));
+        assert!(lines.contains(&"USER_NAME=Jane"));
+        assert!(lines.contains(&"PRIVATE=1"));
+        assert!(lines.contains(&"SYSLOG_IDENTIFIER=app"));
+        assert_eq!(lines.len(), 6);
+    }
+
+    #[test]
+    fn encodes_multiline_values_with_their_length() {
+        let mut log = LogEvent::default();
+        log.insert("message", "first\nsecond");
+
+        let buf = encode(&log);
+        let mut expected = BytesMut::new();
+        expected.put_slice(b"PRIORITY=6\nMESSAGE\n");
+        expected.put_u64_le(12);
+        expected.put_slice(b"first\nsecond\nSYSLOG_IDENTIFIER=vector\n");
+        assert_eq!(buf, expected);
+    }
+
+    #[test]
+    fn sanitizes_field_names() {
+        assert_eq!(
+            sanitize_field_name("foo.bar-baz"),
+            Some("FOO_BAR_BAZ".to_owned())
+        );
+        assert_eq!(sanitize_field_name("__1abc"), Some("ABC".to_owned()));
+        assert_eq!(sanitize_field_name("_123"), None);
+        assert_eq!(
+            sanitize_field_name(&"a".repeat(100)).map(|name| name.len()),
+            Some(MAX_FIELD_NAME_LENGTH)
+        );
+    }
+
+    #[test]
+    fn maps_priorities() {
+        assert_eq!(priority(&Value::from(3)), Some(3));
+        assert_eq!(priority(&Value::from(8)), None);
+        assert_eq!(priority(&Value::from("2")), Some(2));
+        assert_eq!(priority(&Value::from("ERROR")), Some(3));
+        assert_eq!(priority(&Value::from("informational")), Some(6));
+        assert_eq!(priority(&Value::from("verbose")), None);
+    }
+}
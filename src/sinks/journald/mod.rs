@@ -0,0 +1,12 @@
+//! The `journald` sink, writing log events to the local systemd journal.
+//!
+//! Entries are sent to the journal using its [native protocol][native_protocol], with the fields
+//! of events sanitized into valid journal field names.
+//!
+//! [native_protocol]: https://systemd.io/JOURNAL_NATIVE_PROTOCOL/
+
+mod config;
+mod encoder;
+mod sink;
+
+pub use config::JournaldSinkConfig;
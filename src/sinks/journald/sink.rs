@@ -0,0 +1,120 @@
+use std::path::PathBuf;
+
+use async_trait::async_trait;
+use bytes::BytesMut;
+use futures::{stream::BoxStream, StreamExt};
+use tokio::net::UnixDatagram;
+use vector_core::{
+    internal_event::{ByteSize, BytesSent, EventsSent, InternalEventHandle as _, Protocol},
+    ByteSizeOf,
+};
+
+use super::encoder::JournalEncoder;
+use crate::{
+    codecs::Transformer,
+    event::{Event, EventStatus, Finalizable},
+    internal_events::JournaldSendError,
+    sinks::util::StreamSink,
+};
+
+pub(super) struct JournaldSink {
+    socket_path: PathBuf,
+    transformer: Transformer,
+    encoder: JournalEncoder,
+}
+
+impl JournaldSink {
+    pub(super) const fn new(
+        socket_path: PathBuf,
+        transformer: Transformer,
+        encoder: JournalEncoder,
+    ) -> Self {
+        Self {
+            socket_path,
+            transformer,
+            encoder,
+        }
+    }
+}
+
+#[async_trait]
+impl StreamSink<Event> for JournaldSink {
+    async fn run(self: Box<Self>, mut input: BoxStream<'_, Event>) -> Result<(), ()> {
+        let socket = UnixDatagram::unbound().map_err(|error| {
+            error!(message = "Unable to create journal socket.", %error);
+        })?;
+        let bytes_sent = register!(BytesSent::from(Protocol::UNIX));
+
+        while let Some(mut event) = input.next().await {
+            let byte_size = event.size_of();
+            self.transformer.transform(&mut event);
+
+            let finalizers = event.take_finalizers();
+            let mut bytes = BytesMut::new();
+            self.encoder.encode(event.as_log(), &mut bytes);
+
+            // Each entry is sent as a single datagram, so entries larger than the maximum
+            // datagram size of the socket are rejected, rather than passed in a memory file.
+            match socket.send_to(&bytes, &self.socket_path).await {
+                Ok(_) => {
+                    finalizers.update_status(EventStatus::Delivered);
+                    emit!(EventsSent {
+                        byte_size,
+                        count: 1,
+                        output: None,
+                    });
+                    bytes_sent.emit(ByteSize(bytes.len()));
+                }
+                Err(error) => {
+                    finalizers.update_status(EventStatus::Errored);
+                    emit!(JournaldSendError { error });
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::stream;
+    use tempfile::tempdir;
+    use vector_core::sink::VectorSink;
+
+    use super::*;
+    use crate::{
+        event::LogEvent,
+        test_util::components::{run_and_assert_sink_compliance, SINK_TAGS},
+    };
+
+    #[tokio::test]
+    async fn sends_entries_to_the_journal() {
+        let directory = tempdir().unwrap();
+        let socket_path = directory.path().join("socket");
+        let journal = UnixDatagram::bind(&socket_path).unwrap();
+
+        let sink = JournaldSink::new(
+            socket_path,
+            Transformer::default(),
+            JournalEncoder::new("severity".to_owned(), 6, "vector".to_owned()),
+        );
+        let mut log = LogEvent::default();
+        log.insert("message", "hello");
+        log.insert("severity", "err");
+
+        run_and_assert_sink_compliance(
+            VectorSink::from_event_streamsink(sink),
+            stream::once(async { Event::from(log) }),
+            &SINK_TAGS,
+        )
+        .await;
+
+        let mut buf = [0; 1024];
+        let len = journal.recv(&mut buf).await.unwrap();
+        assert_eq!(
+            std::str::from_utf8(&buf[..len]).unwrap(),
+            "PRIORITY=3\nMESSAGE=hello\nSEVERITY=err\nSYSLOG_IDENTIFIER=vector\n"
+        );
+    }
+}
@@ -40,6 +40,24 @@ pub struct KafkaSinkConfig {
     /// Kafka uses a hash of the key to choose the partition or uses round-robin if the record has no key.
     pub key_field: Option<String>,
 
+    /// The log field name or tags key to use for the partition of each message.
+    ///
+    /// The field must hold the number of the partition, as an integer or a string. The partition
+    /// of messages whose event doesn't have the field, or whose field isn't a partition number, is
+    /// selected by the `partitioner`.
+    pub partition_field: Option<String>,
+
+    #[configurable(derived)]
+    pub partitioner: Option<KafkaPartitioner>,
+
+    /// How long the messages without a key are sent to the same partition, in milliseconds,
+    /// before another partition is selected for them.
+    ///
+    /// Sending consecutive messages without a key to the same partition batches them together,
+    /// rather than scattering them across all partitions. `0` disables sticky partitioning. By
+    /// default, twice `batch.timeout_secs`.
+    pub sticky_partitioning_linger_ms: Option<u64>,
+
     #[configurable(derived)]
     pub encoding: EncodingConfig,
 
@@ -87,6 +105,53 @@ pub struct KafkaSinkConfig {
     pub acknowledgements: AcknowledgementsConfig,
 }
 
+/// The partitioner selecting the partition of each message from its key.
+///
+/// By default, the `consistent_random` partitioner of `librdkafka` is used.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum KafkaPartitioner {
+    /// A CRC32 hash of the key, with the messages with an empty or no key all sent to the same
+    /// partition.
+    Consistent,
+
+    /// A CRC32 hash of the key, with the messages with an empty or no key sent to random
+    /// partitions.
+    ConsistentRandom,
+
+    /// A random partition for each message.
+    Random,
+
+    /// A murmur2 hash of the key, consistent with Java clients, with the messages without a key
+    /// all sent to the same partition.
+    Murmur2,
+
+    /// A murmur2 hash of the key, consistent with Java clients, with the messages without a key
+    /// sent to random partitions, as the default partitioner of Java clients does.
+    Murmur2Random,
+
+    /// A FNV-1a hash of the key, with the messages without a key all sent to the same partition.
+    Fnv1a,
+
+    /// A FNV-1a hash of the key, with the messages without a key sent to random partitions.
+    Fnv1aRandom,
+}
+
+impl KafkaPartitioner {
+    const fn as_str(self) -> &'static str {
+        match self {
+            Self::Consistent => "consistent",
+            Self::ConsistentRandom => "consistent_random",
+            Self::Random => "random",
+            Self::Murmur2 => "murmur2",
+            Self::Murmur2Random => "murmur2_random",
+            Self::Fnv1a => "fnv1a",
+            Self::Fnv1aRandom => "fnv1a_random",
+        }
+    }
+}
+
 const fn default_socket_timeout_ms() -> u64 {
     60000 // default in librdkafka
 }
@@ -179,6 +244,24 @@ impl KafkaSinkConfig {
                     );
                     client_config.set(key, &value.to_string());
                 }
+                if let Some(partitioner) = self.partitioner {
+                    let key = "partitioner";
+                    if let Some(val) = self.librdkafka_options.get(key) {
+                        return Err(format!("Setting `partitioner` sets `librdkafka_options.{}={}`.\
+                                        The config already sets this as `librdkafka_options.partitioner={}`.\
+                                        Please delete one.", key, partitioner.as_str(), val).into());
+                    }
+                    client_config.set(key, partitioner.as_str());
+                }
+                if let Some(value) = self.sticky_partitioning_linger_ms {
+                    let key = "sticky.partitioning.linger.ms";
+                    if let Some(val) = self.librdkafka_options.get(key) {
+                        return Err(format!("Setting `sticky_partitioning_linger_ms` sets `librdkafka_options.{}={}`.\
+                                        The config already sets this as `librdkafka_options.sticky.partitioning.linger.ms={}`.\
+                                        Please delete one.", key, value, val).into());
+                    }
+                    client_config.set(key, &value.to_string());
+                }
             }
 
             KafkaRole::Consumer => {
@@ -201,6 +284,9 @@ impl GenerateConfig for KafkaSinkConfig {
             bootstrap_servers: "10.14.22.123:9092,10.14.23.332:9092".to_owned(),
             topic: "topic-1234".to_owned(),
             key_field: Some("user_id".to_owned()),
+            partition_field: None,
+            partitioner: None,
+            sticky_partitioning_linger_ms: None,
             encoding: JsonSerializerConfig::new().into(),
             batch: Default::default(),
             compression: KafkaCompression::None,
@@ -240,4 +326,49 @@ mod tests {
     fn generate_config() {
         KafkaSinkConfig::generate_config();
     }
+
+    fn producer_config(config: &str) -> crate::Result<ClientConfig> {
+        toml::from_str::<KafkaSinkConfig>(&format!(
+            r#"
+            bootstrap_servers = "localhost:9092"
+            topic = "topic"
+            encoding.codec = "json"
+            {}
+            "#,
+            config
+        ))
+        .unwrap()
+        .to_rdkafka(KafkaRole::Producer)
+    }
+
+    #[test]
+    fn sets_partitioning_options() {
+        let client_config = producer_config(
+            r#"
+            partitioner = "murmur2_random"
+            sticky_partitioning_linger_ms = 50
+            "#,
+        )
+        .unwrap();
+        assert_eq!(client_config.get("partitioner"), Some("murmur2_random"));
+        assert_eq!(
+            client_config.get("sticky.partitioning.linger.ms"),
+            Some("50")
+        );
+
+        let client_config = producer_config("").unwrap();
+        assert_eq!(client_config.get("partitioner"), None);
+        assert_eq!(client_config.get("sticky.partitioning.linger.ms"), None);
+    }
+
+    #[test]
+    fn rejects_partitioner_set_twice() {
+        assert!(producer_config(
+            r#"
+            partitioner = "murmur2"
+            librdkafka_options.partitioner = "fnv1a"
+            "#,
+        )
+        .is_err());
+    }
 }
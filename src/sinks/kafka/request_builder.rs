@@ -6,13 +6,16 @@ use vector_core::{config::LogSchema, ByteSizeOf};
 use crate::{
     codecs::{Encoder, Transformer},
     event::{Event, Finalizable, Value},
-    internal_events::{KafkaHeaderExtractionError, TemplateRenderingError},
+    internal_events::{
+        KafkaHeaderExtractionError, KafkaPartitionExtractionError, TemplateRenderingError,
+    },
     sinks::kafka::service::{KafkaRequest, KafkaRequestMetadata},
     template::Template,
 };
 
 pub struct KafkaRequestBuilder {
     pub key_field: Option<String>,
+    pub partition_field: Option<String>,
     pub headers_key: Option<String>,
     pub topic_template: Template,
     pub transformer: Transformer,
@@ -37,6 +40,7 @@ impl KafkaRequestBuilder {
         let metadata = KafkaRequestMetadata {
             finalizers: event.take_finalizers(),
             key: get_key(&event, &self.key_field),
+            partition: get_partition(&event, &self.partition_field),
             timestamp_millis: get_timestamp_millis(&event, self.log_schema),
             headers: get_headers(&event, &self.headers_key),
             topic,
@@ -67,6 +71,25 @@ fn get_key(event: &Event, key_field: &Option<String>) -> Option<Bytes> {
     })
 }
 
+fn get_partition(event: &Event, partition_field: &Option<String>) -> Option<i32> {
+    let partition_field = partition_field.as_ref()?;
+    let value = match event {
+        Event::Log(log) => match log.get(partition_field.as_str())? {
+            Value::Integer(partition) => i32::try_from(*partition).ok(),
+            value => String::from_utf8_lossy(&value.coerce_to_bytes())
+                .parse()
+                .ok(),
+        },
+        Event::Metric(metric) => metric.tags()?.get(partition_field)?.parse().ok(),
+        _ => return None,
+    };
+    let partition = value.filter(|partition| *partition >= 0);
+    if partition.is_none() {
+        emit!(KafkaPartitionExtractionError { partition_field });
+    }
+    partition
+}
+
 fn get_timestamp_millis(event: &Event, log_schema: &'static LogSchema) -> Option<i64> {
     match &event {
         Event::Log(log) => log
@@ -135,4 +158,21 @@ mod tests {
         assert_eq!(headers.get(1).unwrap().0, "b-key");
         assert_eq!(headers.get(1).unwrap().1, "b-value".as_bytes());
     }
+
+    #[test]
+    fn kafka_get_partition() {
+        let partition_field = Some("partition".to_owned());
+        let mut event = Event::Log(LogEvent::from("hello"));
+        assert_eq!(get_partition(&event, &partition_field), None);
+
+        event.as_mut_log().insert("partition", 3);
+        assert_eq!(get_partition(&event, &partition_field), Some(3));
+        assert_eq!(get_partition(&event, &None), None);
+
+        event.as_mut_log().insert("partition", "7");
+        assert_eq!(get_partition(&event, &partition_field), Some(7));
+
+        event.as_mut_log().insert("partition", -1);
+        assert_eq!(get_partition(&event, &partition_field), None);
+    }
 }
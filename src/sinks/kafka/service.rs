@@ -30,6 +30,7 @@ pub struct KafkaRequest {
 pub struct KafkaRequestMetadata {
     pub finalizers: EventFinalizers,
     pub key: Option<Bytes>,
+    pub partition: Option<i32>,
     pub timestamp_millis: Option<i64>,
     pub headers: Option<OwnedHeaders>,
     pub topic: String,
@@ -88,6 +89,9 @@ impl Service<KafkaRequest> for KafkaService {
             if let Some(key) = &request.metadata.key {
                 record = record.key(&key[..]);
             }
+            if let Some(partition) = request.metadata.partition {
+                record = record.partition(partition);
+            }
             if let Some(timestamp) = request.metadata.timestamp_millis {
                 record = record.timestamp(timestamp);
             }
@@ -42,6 +42,7 @@ pub struct KafkaSink {
     service: KafkaService,
     topic: Template,
     key_field: Option<String>,
+    partition_field: Option<String>,
     headers_key: Option<String>,
 }
 
@@ -69,6 +70,7 @@ impl KafkaSink {
             service: KafkaService::new(producer),
             topic: Template::try_from(config.topic).context(TopicTemplateSnafu)?,
             key_field: config.key_field,
+            partition_field: config.partition_field,
         })
     }
 
@@ -77,6 +79,7 @@ impl KafkaSink {
         let service = ConcurrencyLimit::new(self.service, QUEUED_MIN_MESSAGES as usize);
         let mut request_builder = KafkaRequestBuilder {
             key_field: self.key_field,
+            partition_field: self.partition_field,
             headers_key: self.headers_key,
             topic_template: self.topic,
             transformer: self.transformer,
@@ -57,6 +57,9 @@ mod integration_test {
             bootstrap_servers: kafka_address(9091),
             topic: topic.clone(),
             key_field: None,
+            partition_field: None,
+            partitioner: None,
+            sticky_partitioning_linger_ms: None,
             encoding: TextSerializerConfig::new().into(),
             batch: BatchConfig::default(),
             compression: KafkaCompression::None,
@@ -111,6 +114,9 @@ mod integration_test {
             compression: KafkaCompression::None,
             encoding: TextSerializerConfig::new().into(),
             key_field: None,
+            partition_field: None,
+            partitioner: None,
+            sticky_partitioning_linger_ms: None,
             auth: KafkaAuthConfig {
                 sasl: None,
                 tls: None,
@@ -243,6 +249,9 @@ mod integration_test {
             bootstrap_servers: server.clone(),
             topic: format!("{}-%Y%m%d", topic),
             key_field: None,
+            partition_field: None,
+            partitioner: None,
+            sticky_partitioning_linger_ms: None,
             encoding: TextSerializerConfig::new().into(),
             batch: BatchConfig::default(),
             compression,
@@ -0,0 +1,135 @@
+use std::time::Duration;
+
+use futures::FutureExt;
+use snafu::Snafu;
+use vector_config::configurable_component;
+
+use super::{encoder::LogstashEncoder, sink::LogstashSink};
+use crate::{
+    codecs::Transformer,
+    config::{AcknowledgementsConfig, GenerateConfig, Input, SinkConfig, SinkContext},
+    sinks::{
+        util::{tcp::TcpSinkConfig, BatchConfig, SinkBatchSettings},
+        Healthcheck, VectorSink,
+    },
+};
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("Invalid compression level {}: must be between 0 and 9", level))]
+    InvalidCompressionLevel { level: u32 },
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct LogstashDefaultBatchSettings;
+
+// Like Beats, events are sent in windows of up to 2048 events, each acknowledged by Logstash
+// before the next one is sent.
+impl SinkBatchSettings for LogstashDefaultBatchSettings {
+    const MAX_EVENTS: Option<usize> = Some(2048);
+    const MAX_BYTES: Option<usize> = None;
+    const TIMEOUT_SECS: f64 = 1.0;
+}
+
+/// Configuration for the `logstash` sink.
+#[configurable_component(sink("logstash"))]
+#[configurable(metadata(status = "alpha"))]
+#[derive(Clone, Debug)]
+pub struct LogstashSinkConfig {
+    #[serde(flatten)]
+    pub tcp: TcpSinkConfig,
+
+    /// The zlib compression level of the windows of events, from `0` (no compression) to `9`.
+    #[serde(default = "default_compression_level")]
+    pub compression_level: u32,
+
+    /// The number of seconds to wait for Logstash to acknowledge a window of events.
+    ///
+    /// If Logstash doesn't acknowledge the window in time, the connection is closed and the
+    /// window is sent again. Partial acknowledgements, such as the keepalives Logstash sends while
+    /// it's busy, restart the wait.
+    #[serde(default = "default_timeout_secs")]
+    pub timeout_secs: u64,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub batch: BatchConfig<LogstashDefaultBatchSettings>,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    pub encoding: Transformer,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        deserialize_with = "crate::serde::bool_or_struct",
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    pub acknowledgements: AcknowledgementsConfig,
+}
+
+const fn default_compression_level() -> u32 {
+    3
+}
+
+const fn default_timeout_secs() -> u64 {
+    30
+}
+
+impl GenerateConfig for LogstashSinkConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self {
+            tcp: TcpSinkConfig::from_address("127.0.0.1:5044".to_owned()),
+            compression_level: default_compression_level(),
+            timeout_secs: default_timeout_secs(),
+            batch: BatchConfig::default(),
+            encoding: Transformer::default(),
+            acknowledgements: AcknowledgementsConfig::default(),
+        })
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+impl SinkConfig for LogstashSinkConfig {
+    async fn build(&self, _cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        if self.compression_level > 9 {
+            return Err(Box::new(BuildError::InvalidCompressionLevel {
+                level: self.compression_level,
+            }));
+        }
+
+        let connector = self.tcp.build_connector()?;
+        let encoder = LogstashEncoder::new(self.encoding.clone(), self.compression_level);
+        let sink = LogstashSink::new(
+            connector.clone(),
+            encoder,
+            self.batch.into_batcher_settings()?,
+            Duration::from_secs(self.timeout_secs),
+        );
+        let healthcheck = async move { connector.healthcheck().await }.boxed();
+
+        Ok((VectorSink::from_event_streamsink(sink), healthcheck))
+    }
+
+    fn input(&self) -> Input {
+        Input::log()
+    }
+
+    fn acknowledgements(&self) -> &AcknowledgementsConfig {
+        &self.acknowledgements
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<LogstashSinkConfig>();
+    }
+}
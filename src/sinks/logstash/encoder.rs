@@ -0,0 +1,186 @@
+use std::io::{self, Write};
+
+use bytes::{BufMut, Bytes, BytesMut};
+use flate2::write::ZlibEncoder;
+use lookup::event_path;
+
+use crate::{codecs::Transformer, config::log_schema, event::Event};
+
+const PROTOCOL_VERSION: u8 = b'2';
+const WINDOW_SIZE_FRAME: u8 = b'W';
+const JSON_FRAME: u8 = b'J';
+const COMPRESSED_FRAME: u8 = b'C';
+pub(super) const ACK_FRAME: u8 = b'A';
+
+/// Encodes windows of events into Lumberjack v2 frames.
+///
+/// https://github.com/logstash-plugins/logstash-input-beats/blob/master/PROTOCOL.md
+#[derive(Clone, Debug)]
+pub(super) struct LogstashEncoder {
+    transformer: Transformer,
+    compression_level: u32,
+}
+
+impl LogstashEncoder {
+    pub(super) const fn new(transformer: Transformer, compression_level: u32) -> Self {
+        Self {
+            transformer,
+            compression_level,
+        }
+    }
+
+    /// Encodes a window of events, from the fields encoded by `encode_fields`, returning its
+    /// frames and the number of events, which is also the sequence number Logstash acknowledges the
+    /// window with.
+    ///
+    /// Each window starts sequence numbers over from `1`, as Beats do.
+    pub(super) fn encode_window(&self, events: Vec<Vec<u8>>) -> io::Result<(Bytes, u32)> {
+        let mut frames = BytesMut::new();
+        let mut sequence_number = 0;
+        for fields in events {
+            sequence_number += 1;
+            frames.put_u8(PROTOCOL_VERSION);
+            frames.put_u8(JSON_FRAME);
+            frames.put_u32(sequence_number);
+            frames.put_u32(fields.len() as u32);
+            frames.put_slice(&fields);
+        }
+
+        let mut window = BytesMut::with_capacity(frames.len() + 12);
+        window.put_u8(PROTOCOL_VERSION);
+        window.put_u8(WINDOW_SIZE_FRAME);
+        window.put_u32(sequence_number);
+
+        if self.compression_level > 0 {
+            let mut encoder = ZlibEncoder::new(
+                Vec::with_capacity(frames.len() / 4),
+                flate2::Compression::new(self.compression_level),
+            );
+            encoder.write_all(&frames)?;
+            let compressed = encoder.finish()?;
+
+            window.put_u8(PROTOCOL_VERSION);
+            window.put_u8(COMPRESSED_FRAME);
+            window.put_u32(compressed.len() as u32);
+            window.put_slice(&compressed);
+        } else {
+            window.put_slice(&frames);
+        }
+
+        Ok((window.freeze(), sequence_number))
+    }
+
+    /// Serializes the fields of an event to JSON, with its timestamp as `@timestamp`, as Logstash
+    /// expects from Beats.
+    pub(super) fn encode_fields(&self, mut event: Event) -> serde_json::Result<Vec<u8>> {
+        self.transformer.transform(&mut event);
+
+        let log = event.as_mut_log();
+        if !log.contains(event_path!("@timestamp")) {
+            if let Some(timestamp) = log.remove(log_schema().timestamp_key()) {
+                log.insert(event_path!("@timestamp"), timestamp);
+            }
+        }
+
+        serde_json::to_vec(log)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Read;
+
+    use bytes::Buf;
+    use chrono::{TimeZone, Utc};
+    use flate2::read::ZlibDecoder;
+
+    use super::*;
+    use crate::event::LogEvent;
+
+    fn events() -> Vec<Vec<u8>> {
+        let encoder = LogstashEncoder::new(Transformer::default(), 0);
+        ["first", "second"]
+            .into_iter()
+            .map(|message| {
+                let mut log = LogEvent::from(message);
+                log.insert(
+                    log_schema().timestamp_key(),
+                    Utc.ymd(2022, 10, 1).and_hms(12, 30, 0),
+                );
+                encoder.encode_fields(log.into()).unwrap()
+            })
+            .collect()
+    }
+
+    fn read_json_frames(mut frames: &[u8]) -> Vec<(u32, serde_json::Value)> {
+        let mut decoded = Vec::new();
+        while frames.has_remaining() {
+            assert_eq!(frames.get_u8(), PROTOCOL_VERSION);
+            assert_eq!(frames.get_u8(), JSON_FRAME);
+            let sequence_number = frames.get_u32();
+            let length = frames.get_u32() as usize;
+            decoded.push((
+                sequence_number,
+                serde_json::from_slice(&frames[..length]).unwrap(),
+            ));
+            frames.advance(length);
+        }
+        decoded
+    }
+
+    #[test]
+    fn encodes_json_frames() {
+        let encoder = LogstashEncoder::new(Transformer::default(), 0);
+        let (window, sequence_number) = encoder.encode_window(events()).unwrap();
+        assert_eq!(sequence_number, 2);
+
+        let mut window = window.as_ref();
+        assert_eq!(&window[..2], b"2W");
+        window.advance(2);
+        assert_eq!(window.get_u32(), 2);
+
+        let frames = read_json_frames(window);
+        assert_eq!(
+            frames,
+            vec![
+                (
+                    1,
+                    serde_json::json!({
+                        "message": "first",
+                        "@timestamp": "2022-10-01T12:30:00Z",
+                    })
+                ),
+                (
+                    2,
+                    serde_json::json!({
+                        "message": "second",
+                        "@timestamp": "2022-10-01T12:30:00Z",
+                    })
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn compresses_frames() {
+        let (uncompressed, _) = LogstashEncoder::new(Transformer::default(), 0)
+            .encode_window(events())
+            .unwrap();
+        let (window, sequence_number) = LogstashEncoder::new(Transformer::default(), 3)
+            .encode_window(events())
+            .unwrap();
+        assert_eq!(sequence_number, 2);
+
+        let mut window = window.as_ref();
+        assert_eq!(&window[..6], &uncompressed[..6]);
+        window.advance(6);
+        assert_eq!(&window[..2], b"2C");
+        window.advance(2);
+        let length = window.get_u32() as usize;
+        assert_eq!(window.len(), length);
+
+        let mut frames = Vec::new();
+        ZlibDecoder::new(window).read_to_end(&mut frames).unwrap();
+        assert_eq!(frames, &uncompressed[6..]);
+    }
+}
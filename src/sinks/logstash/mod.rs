@@ -0,0 +1,14 @@
+//! The `logstash` sink, sending log events to Logstash with the [Beats (Lumberjack v2)
+//! protocol][protocol].
+//!
+//! Events are sent in windows of JSON frames, optionally compressed, and are only acknowledged
+//! once Logstash has acknowledged the last frame of their window.
+//!
+//! [protocol]: https://github.com/logstash-plugins/logstash-input-beats/blob/master/PROTOCOL.md
+
+mod config;
+mod encoder;
+mod sink;
+
+pub use config::LogstashSinkConfig;
+pub(crate) use sink::LogstashError;
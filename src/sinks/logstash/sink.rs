@@ -0,0 +1,353 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+use futures::{stream::BoxStream, StreamExt};
+use snafu::{ResultExt, Snafu};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::TcpStream,
+    time::{sleep, timeout},
+};
+use vector_core::{
+    internal_event::{ByteSize, BytesSent, EventsSent, InternalEventHandle as _, Protocol},
+    stream::BatcherSettings,
+    ByteSizeOf,
+};
+
+use super::encoder::{LogstashEncoder, ACK_FRAME};
+use crate::{
+    event::{Event, EventFinalizers, EventStatus, Finalizable},
+    internal_events::{ConnectionOpen, EncoderSerializeError, LogstashSendError, OpenGauge},
+    sinks::util::{tcp::TcpConnector, SinkBuilderExt, StreamSink},
+    tls::MaybeTlsStream,
+};
+
+#[derive(Debug, Snafu)]
+pub(crate) enum LogstashError {
+    #[snafu(display("Failed to encode window: {}", source))]
+    Encode { source: std::io::Error },
+
+    #[snafu(display("Failed to send window: {}", source))]
+    Send { source: std::io::Error },
+
+    #[snafu(display("Failed to read acknowledgement: {}", source))]
+    ReadAck { source: std::io::Error },
+
+    #[snafu(display("Window wasn't acknowledged within {:?}", timeout))]
+    AckTimeout { timeout: Duration },
+
+    #[snafu(display(
+        "Unexpected frame {:?} of protocol version {:?}, expected an acknowledgement",
+        frame_type as char,
+        version as char
+    ))]
+    UnexpectedFrame { version: u8, frame_type: u8 },
+}
+
+/// A window of encoded events, waiting for Logstash to acknowledge it.
+struct Window {
+    frames: bytes::Bytes,
+    sequence_number: u32,
+    finalizers: EventFinalizers,
+    count: usize,
+    byte_size: usize,
+}
+
+pub(super) struct LogstashSink {
+    connector: TcpConnector,
+    encoder: LogstashEncoder,
+    batch_settings: BatcherSettings,
+    timeout: Duration,
+}
+
+impl LogstashSink {
+    pub(super) const fn new(
+        connector: TcpConnector,
+        encoder: LogstashEncoder,
+        batch_settings: BatcherSettings,
+        timeout: Duration,
+    ) -> Self {
+        Self {
+            connector,
+            encoder,
+            batch_settings,
+            timeout,
+        }
+    }
+
+    /// Encodes a window of events, or returns `None` if none of them could be encoded.
+    ///
+    /// The events that can't be encoded are rejected, and left out of the window.
+    fn encode_window(&self, events: Vec<Event>) -> Result<Option<Window>, LogstashError> {
+        let mut finalizers = EventFinalizers::default();
+        let mut byte_size = 0;
+        let mut encoded = Vec::with_capacity(events.len());
+        for mut event in events {
+            let event_finalizers = event.take_finalizers();
+            let event_byte_size = event.size_of();
+            match self.encoder.encode_fields(event) {
+                Ok(fields) => {
+                    finalizers.merge(event_finalizers);
+                    byte_size += event_byte_size;
+                    encoded.push(fields);
+                }
+                Err(error) => {
+                    event_finalizers.update_status(EventStatus::Rejected);
+                    emit!(EncoderSerializeError {
+                        error: &error.into()
+                    });
+                }
+            }
+        }
+        if encoded.is_empty() {
+            return Ok(None);
+        }
+
+        let count = encoded.len();
+        let (frames, sequence_number) = match self.encoder.encode_window(encoded) {
+            Ok(window) => window,
+            Err(source) => {
+                finalizers.update_status(EventStatus::Errored);
+                return Err(LogstashError::Encode { source });
+            }
+        };
+
+        Ok(Some(Window {
+            frames,
+            sequence_number,
+            finalizers,
+            count,
+            byte_size,
+        }))
+    }
+
+    /// Sends a window of events, and waits for Logstash to acknowledge all of them.
+    async fn send_window(
+        &self,
+        stream: &mut MaybeTlsStream<TcpStream>,
+        window: &Window,
+    ) -> Result<(), LogstashError> {
+        stream.write_all(&window.frames).await.context(SendSnafu)?;
+        stream.flush().await.context(SendSnafu)?;
+
+        // Logstash may acknowledge the window in several parts, and keeps sending the last
+        // acknowledged sequence number while it's busy processing events, so every
+        // acknowledgement restarts the wait.
+        let mut ack = [0; 6];
+        loop {
+            timeout(self.timeout, stream.read_exact(&mut ack))
+                .await
+                .map_err(|_| LogstashError::AckTimeout {
+                    timeout: self.timeout,
+                })?
+                .context(ReadAckSnafu)?;
+
+            let [version, frame_type, sequence_number @ ..] = ack;
+            if frame_type != ACK_FRAME {
+                return Err(LogstashError::UnexpectedFrame {
+                    version,
+                    frame_type,
+                });
+            }
+            if u32::from_be_bytes(sequence_number) >= window.sequence_number {
+                return Ok(());
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl StreamSink<Event> for LogstashSink {
+    async fn run(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        let bytes_sent = register!(BytesSent::from(Protocol::TCP));
+        let mut input = input.batched(self.batch_settings.into_byte_size_config());
+
+        // The window that failed to be sent, to send again once reconnected.
+        let mut pending: Option<Window> = None;
+        let mut backoff = TcpConnector::fresh_backoff();
+
+        loop {
+            let mut window = match pending.take() {
+                Some(window) => window,
+                None => match input.next().await {
+                    Some(events) => match self.encode_window(events) {
+                        Ok(Some(window)) => window,
+                        Ok(None) => continue,
+                        Err(error) => {
+                            emit!(LogstashSendError { error });
+                            continue;
+                        }
+                    },
+                    None => break,
+                },
+            };
+
+            let mut stream = self.connector.connect_backoff().await;
+            let _open_token = OpenGauge::new().open(|count| emit!(ConnectionOpen { count }));
+
+            loop {
+                if let Err(error) = self.send_window(&mut stream, &window).await {
+                    emit!(LogstashSendError { error });
+                    pending = Some(window);
+                    sleep(backoff.next().unwrap()).await;
+                    break;
+                }
+                backoff = TcpConnector::fresh_backoff();
+
+                window.finalizers.update_status(EventStatus::Delivered);
+                emit!(EventsSent {
+                    count: window.count,
+                    byte_size: window.byte_size,
+                    output: None,
+                });
+                bytes_sent.emit(ByteSize(window.frames.len()));
+
+                // Windows of events that all failed to be encoded aren't sent.
+                window = loop {
+                    match input.next().await {
+                        Some(events) => match self.encode_window(events) {
+                            Ok(Some(window)) => break window,
+                            Ok(None) => {}
+                            Err(error) => emit!(LogstashSendError { error }),
+                        },
+                        None => return Ok(()),
+                    }
+                };
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(all(test, feature = "sources-logstash"))]
+mod tests {
+    use futures::stream;
+    use tokio::net::TcpListener;
+    use vector_core::event::{BatchNotifier, BatchStatus};
+
+    use super::*;
+    use crate::{
+        config::{SinkConfig, SinkContext, SourceConfig, SourceContext},
+        event::LogEvent,
+        sinks::logstash::LogstashSinkConfig,
+        sources::logstash::LogstashConfig,
+        test_util::{
+            collect_n,
+            components::{run_and_assert_sink_compliance, SINK_TAGS},
+            next_addr, trace_init, wait_for_tcp,
+        },
+        SourceSender,
+    };
+
+    fn sink_config(address: std::net::SocketAddr, compression_level: u32) -> LogstashSinkConfig {
+        toml::from_str(&format!(
+            r#"
+            address = "{}"
+            compression_level = {}
+            batch.max_events = 3
+            "#,
+            address, compression_level
+        ))
+        .unwrap()
+    }
+
+    async fn sends_to_logstash_source(compression_level: u32) {
+        trace_init();
+
+        let address = next_addr();
+        let source: LogstashConfig =
+            toml::from_str(&format!(r#"address = "{}""#, address)).unwrap();
+        let (tx, rx) = SourceSender::new_test();
+        tokio::spawn(
+            source
+                .build(SourceContext::new_test(tx, None))
+                .await
+                .unwrap(),
+        );
+        wait_for_tcp(address).await;
+
+        let (sink, _) = sink_config(address, compression_level)
+            .build(SinkContext::new_test())
+            .await
+            .unwrap();
+
+        let (batch, mut receiver) = BatchNotifier::new_with_receiver();
+        let events = (0..10)
+            .map(|i| {
+                Event::from(LogEvent::from(format!("event {}", i)).with_batch_notifier(&batch))
+            })
+            .collect::<Vec<_>>();
+        drop(batch);
+
+        run_and_assert_sink_compliance(sink, stream::iter(events), &SINK_TAGS).await;
+        assert_eq!(receiver.try_recv(), Ok(BatchStatus::Delivered));
+
+        let output = collect_n(rx, 10).await;
+        for (i, event) in output.iter().enumerate() {
+            assert_eq!(
+                event.as_log()["message"],
+                format!("event {}", i).into(),
+                "event {}",
+                i
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn sends_to_logstash_source_uncompressed() {
+        sends_to_logstash_source(0).await;
+    }
+
+    #[tokio::test]
+    async fn sends_to_logstash_source_compressed() {
+        sends_to_logstash_source(3).await;
+    }
+
+    #[tokio::test]
+    async fn resends_unacknowledged_windows() {
+        trace_init();
+
+        let address = next_addr();
+        let listener = TcpListener::bind(address).await.unwrap();
+
+        let mut config = sink_config(address, 0);
+        config.timeout_secs = 1;
+        let (sink, _) = config.build(SinkContext::new_test()).await.unwrap();
+
+        let server = tokio::spawn(async move {
+            let mut windows = Vec::new();
+            for ack in [false, true] {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                let mut header = [0; 6];
+                socket.read_exact(&mut header).await.unwrap();
+                windows.push(header);
+
+                // Skip the JSON frame of the single event.
+                let mut frame = [0; 10];
+                socket.read_exact(&mut frame).await.unwrap();
+                let mut fields =
+                    vec![0; u32::from_be_bytes(frame[6..].try_into().unwrap()) as usize];
+                socket.read_exact(&mut fields).await.unwrap();
+
+                if ack {
+                    // A partial acknowledgement first, as Logstash sends while it's busy.
+                    socket.write_all(b"2A\0\0\0\0").await.unwrap();
+                    socket.write_all(b"2A\0\0\0\x01").await.unwrap();
+                } else {
+                    // Let the sink time out waiting for the acknowledgement.
+                    sleep(Duration::from_millis(1200)).await;
+                }
+            }
+            windows
+        });
+
+        let (batch, mut receiver) = BatchNotifier::new_with_receiver();
+        let event = Event::from(LogEvent::from("event").with_batch_notifier(&batch));
+        drop(batch);
+        sink.run(stream::iter(vec![event])).await.unwrap();
+
+        assert_eq!(receiver.try_recv(), Ok(BatchStatus::Delivered));
+        assert_eq!(server.await.unwrap(), [*b"2W\0\0\0\x01"; 2]);
+    }
+}
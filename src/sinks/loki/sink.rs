@@ -46,6 +46,13 @@ impl KeyPartitioner {
     pub const fn new(template: Option<Template>) -> Self {
         Self(template)
     }
+
+    /// Returns `true` if events for which the key fails to render must be dropped.
+    fn drops_on_render_error(&self) -> bool {
+        self.0
+            .as_ref()
+            .map_or(false, Template::drops_on_render_error)
+    }
 }
 
 impl Partitioner for KeyPartitioner {
@@ -59,7 +66,7 @@ impl Partitioner for KeyPartitioner {
                     emit!(TemplateRenderingError {
                         error,
                         field: Some("tenant_id"),
-                        drop_event: false,
+                        drop_event: t.drops_on_render_error(),
                     })
                 })
                 .ok()
@@ -202,6 +209,9 @@ impl EventEncoder {
 
     pub(super) fn encode_event(&mut self, mut event: Event) -> Option<LokiRecord> {
         let tenant_id = self.key_partitioner.partition(&event);
+        if tenant_id.is_none() && self.key_partitioner.drops_on_render_error() {
+            return None;
+        }
         let finalizers = event.take_finalizers();
         let mut labels = self.build_labels(&event);
         self.remove_label_fields(&mut event);
@@ -59,10 +59,14 @@ pub mod http;
 pub mod humio;
 #[cfg(any(feature = "sinks-influxdb", feature = "prometheus-integration-tests"))]
 pub mod influxdb;
+#[cfg(all(unix, feature = "sinks-journald"))]
+pub mod journald;
 #[cfg(feature = "sinks-kafka")]
 pub mod kafka;
 #[cfg(feature = "sinks-logdna")]
 pub mod logdna;
+#[cfg(feature = "sinks-logstash")]
+pub mod logstash;
 #[cfg(feature = "sinks-loki")]
 pub mod loki;
 #[cfg(feature = "sinks-nats")]
@@ -214,6 +218,10 @@ pub enum Sinks {
     #[cfg(feature = "sinks-file")]
     File(#[configurable(derived)] file::FileSinkConfig),
 
+    /// Google Chronicle.
+    #[cfg(feature = "sinks-gcp")]
+    GcpChronicle(#[configurable(derived)] gcp::chronicle::ChronicleConfig),
+
     /// Google Chronicle (unstructured).
     #[cfg(feature = "sinks-gcp")]
     GcpChronicleUnstructured(
@@ -260,6 +268,10 @@ pub enum Sinks {
     #[cfg(any(feature = "sinks-influxdb", feature = "prometheus-integration-tests"))]
     InfluxdbMetrics(#[configurable(derived)] influxdb::metrics::InfluxDbConfig),
 
+    /// Journald.
+    #[cfg(all(unix, feature = "sinks-journald"))]
+    Journald(#[configurable(derived)] journald::JournaldSinkConfig),
+
     /// Kafka.
     #[cfg(feature = "sinks-kafka")]
     Kafka(#[configurable(derived)] kafka::KafkaSinkConfig),
@@ -268,6 +280,10 @@ pub enum Sinks {
     #[cfg(feature = "sinks-logdna")]
     Logdna(#[configurable(derived)] logdna::LogdnaConfig),
 
+    /// Logstash.
+    #[cfg(feature = "sinks-logstash")]
+    Logstash(#[configurable(derived)] logstash::LogstashSinkConfig),
+
     /// Loki.
     #[cfg(feature = "sinks-loki")]
     Loki(#[configurable(derived)] loki::LokiConfig),
@@ -411,6 +427,8 @@ impl NamedComponent for Sinks {
             #[cfg(feature = "sinks-file")]
             Self::File(config) => config.get_component_name(),
             #[cfg(feature = "sinks-gcp")]
+            Self::GcpChronicle(config) => config.get_component_name(),
+            #[cfg(feature = "sinks-gcp")]
             Self::GcpChronicleUnstructured(config) => config.get_component_name(),
             #[cfg(feature = "sinks-gcp")]
             Self::GcpStackdriverLogs(config) => config.get_component_name(),
@@ -432,10 +450,14 @@ impl NamedComponent for Sinks {
             Self::InfluxdbLogs(config) => config.get_component_name(),
             #[cfg(any(feature = "sinks-influxdb", feature = "prometheus-integration-tests"))]
             Self::InfluxdbMetrics(config) => config.get_component_name(),
+            #[cfg(all(unix, feature = "sinks-journald"))]
+            Self::Journald(config) => config.get_component_name(),
             #[cfg(feature = "sinks-kafka")]
             Self::Kafka(config) => config.get_component_name(),
             #[cfg(feature = "sinks-logdna")]
             Self::Logdna(config) => config.get_component_name(),
+            #[cfg(feature = "sinks-logstash")]
+            Self::Logstash(config) => config.get_component_name(),
             #[cfg(feature = "sinks-loki")]
             Self::Loki(config) => config.get_component_name(),
             #[cfg(feature = "sinks-nats")]
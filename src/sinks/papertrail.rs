@@ -134,21 +134,22 @@ impl tokio_util::codec::Encoder<Event> for PapertrailEncoder {
             .remove(log_schema().host_key())
             .map(|host| host.to_string_lossy());
 
-        let process = self
-            .process
-            .as_ref()
-            .and_then(|t| {
-                t.render_string(&event)
-                    .map_err(|error| {
-                        emit!(TemplateRenderingError {
-                            error,
-                            field: Some("process"),
-                            drop_event: false,
-                        })
-                    })
-                    .ok()
-            })
-            .unwrap_or_else(|| String::from("vector"));
+        let process = match self.process.as_ref().map(|t| (t, t.render_string(&event))) {
+            Some((_, Ok(process))) => process,
+            Some((template, Err(error))) => {
+                let drop_event = template.drops_on_render_error();
+                emit!(TemplateRenderingError {
+                    error: error.clone(),
+                    field: Some("process"),
+                    drop_event,
+                });
+                if drop_event {
+                    return Err(codecs::encoding::Error::SerializingError(Box::new(error)));
+                }
+                String::from("vector")
+            }
+            None => String::from("vector"),
+        };
 
         let formatter = Formatter3164 {
             facility: Facility::LOG_USER,
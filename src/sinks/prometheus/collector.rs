@@ -1,6 +1,6 @@
 use std::{collections::BTreeMap, fmt::Write as _};
 
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 use indexmap::map::IndexMap;
 use prometheus_parser::{proto, METRIC_NAME_LABEL};
 use vector_core::event::metric::{samples_to_buckets, MetricSketch, Quantile};
@@ -218,9 +218,43 @@ pub(super) trait MetricCollector {
     }
 }
 
+/// A sample of a series linked to the trace it was observed in, rendered in the OpenMetrics
+/// format.
+#[derive(Clone, Debug, PartialEq)]
+pub(super) struct Exemplar {
+    pub labels: BTreeMap<String, String>,
+    pub value: f64,
+    pub timestamp: DateTime<Utc>,
+}
+
+/// The series being encoded in the OpenMetrics format.
+struct OpenMetricsSeries {
+    r#type: proto::MetricType,
+    /// The exemplar of the series, until it's attached to one of its samples.
+    exemplar: Option<Exemplar>,
+}
+
+impl OpenMetricsSeries {
+    /// Whether samples with the given suffix are part of metric points of this type. The extra
+    /// statistics of distributions rendered as summaries, such as `_min`, aren't.
+    fn allows(&self, suffix: &str) -> bool {
+        use proto::MetricType;
+        match self.r#type {
+            MetricType::Counter => matches!(suffix, "" | "_created"),
+            MetricType::Histogram => matches!(suffix, "_bucket" | "_sum" | "_count" | "_created"),
+            MetricType::Summary => matches!(suffix, "" | "_sum" | "_count" | "_created"),
+            _ => suffix.is_empty(),
+        }
+    }
+}
+
 pub(super) struct StringCollector {
     // BTreeMap ensures we get sorted output, which whilst not required is preferable
     processed: BTreeMap<String, String>,
+    /// Whether the metrics are encoded in the OpenMetrics format, instead of the Prometheus text
+    /// format.
+    openmetrics: bool,
+    series: Option<OpenMetricsSeries>,
 }
 
 impl MetricCollector for StringCollector {
@@ -228,12 +262,16 @@ impl MetricCollector for StringCollector {
 
     fn new() -> Self {
         let processed = BTreeMap::new();
-        Self { processed }
+        Self {
+            processed,
+            openmetrics: false,
+            series: None,
+        }
     }
 
     fn emit_metadata(&mut self, name: &str, fullname: &str, value: &MetricValue) {
         if !self.processed.contains_key(fullname) {
-            let header = Self::encode_header(name, fullname, value);
+            let header = Self::encode_header(name, fullname, value, self.openmetrics);
             self.processed.insert(fullname.into(), header);
         }
     }
@@ -252,21 +290,116 @@ impl MetricCollector for StringCollector {
             .get_mut(name)
             .expect("metric metadata not encoded");
 
-        result.push_str(name);
-        result.push_str(suffix);
-        Self::encode_tags(result, tags, extra);
-        let _ = match timestamp_millis {
-            None => writeln!(result, " {}", value),
-            Some(timestamp) => writeln!(result, " {} {}", value, timestamp),
+        let series = match &mut self.series {
+            None => {
+                result.push_str(name);
+                result.push_str(suffix);
+                Self::encode_tags(result, tags, extra);
+                let _ = match timestamp_millis {
+                    None => writeln!(result, " {}", value),
+                    Some(timestamp) => writeln!(result, " {} {}", value, timestamp),
+                };
+                return;
+            }
+            Some(series) if !series.allows(suffix) => return,
+            Some(series) => series,
         };
+
+        // Counters are named after their family, without the `_total` suffix of their samples.
+        if series.r#type == proto::MetricType::Counter {
+            result.push_str(name.strip_suffix("_total").unwrap_or(name));
+            result.push_str(if suffix.is_empty() { "_total" } else { suffix });
+        } else {
+            result.push_str(name);
+            result.push_str(suffix);
+        }
+        let exemplar = match (series.r#type, suffix, &extra) {
+            (proto::MetricType::Counter, "", _) => series.exemplar.take(),
+            (proto::MetricType::Histogram, "_bucket", Some((_, upper_limit))) => {
+                // The exemplar of a histogram is attached to the first bucket it falls in.
+                let upper_limit = upper_limit.parse::<f64>().unwrap_or(f64::INFINITY);
+                match &series.exemplar {
+                    Some(exemplar) if exemplar.value <= upper_limit => series.exemplar.take(),
+                    _ => None,
+                }
+            }
+            _ => None,
+        };
+        Self::encode_tags(result, tags, extra);
+        let _ = write!(result, " {}", value);
+        if let Some(timestamp) = timestamp_millis {
+            let _ = write!(result, " {}", seconds(timestamp));
+        }
+        if let Some(exemplar) = exemplar {
+            result.push_str(" # ");
+            Self::encode_tags(result, Some(&exemplar.labels), None);
+            let _ = write!(
+                result,
+                " {} {}",
+                exemplar.value,
+                seconds(exemplar.timestamp.timestamp_millis())
+            );
+        }
+        result.push('\n');
     }
 
     fn finish(self) -> String {
-        self.processed.into_iter().map(|(_, value)| value).collect()
+        let mut output = self
+            .processed
+            .into_iter()
+            .map(|(_, value)| value)
+            .collect::<String>();
+        if self.openmetrics {
+            output.push_str("# EOF\n");
+        }
+        output
     }
 }
 
 impl StringCollector {
+    /// Creates a collector encoding metrics in the [OpenMetrics][openmetrics] format.
+    ///
+    /// [openmetrics]: https://github.com/OpenObservability/OpenMetrics/blob/main/specification/OpenMetrics.md
+    pub(super) fn new_openmetrics() -> Self {
+        Self {
+            openmetrics: true,
+            ..Self::new()
+        }
+    }
+
+    /// Encodes a metric in the OpenMetrics format, along with the time its series was created
+    /// and its exemplar, if any.
+    pub(super) fn encode_openmetrics(
+        &mut self,
+        default_namespace: Option<&str>,
+        buckets: &[f64],
+        quantiles: &[f64],
+        metric: &Metric,
+        created: DateTime<Utc>,
+        exemplar: Option<&Exemplar>,
+    ) {
+        let r#type = prometheus_metric_type(metric.value());
+        self.series = Some(OpenMetricsSeries {
+            r#type,
+            exemplar: exemplar.cloned(),
+        });
+        self.encode_metric(default_namespace, buckets, quantiles, metric);
+
+        if metric.kind() == MetricKind::Absolute
+            && matches!(
+                r#type,
+                proto::MetricType::Counter
+                    | proto::MetricType::Histogram
+                    | proto::MetricType::Summary
+            )
+        {
+            let name =
+                encode_namespace(metric.namespace().or(default_namespace), '_', metric.name());
+            let created = seconds(created.timestamp_millis());
+            self.emit_value(None, &name, "_created", created, metric.tags(), None);
+        }
+        self.series = None;
+    }
     fn encode_tags(
         result: &mut String,
         tags: Option<&BTreeMap<String, String>>,
@@ -292,11 +425,21 @@ impl StringCollector {
         .ok();
     }
 
-    fn encode_header(name: &str, fullname: &str, value: &MetricValue) -> String {
-        let r#type = prometheus_metric_type(value).as_str();
+    fn encode_header(name: &str, fullname: &str, value: &MetricValue, openmetrics: bool) -> String {
+        let r#type = prometheus_metric_type(value);
+        // In the OpenMetrics format, the `_total` suffix of counters is only part of their samples.
+        let fullname = match r#type {
+            proto::MetricType::Counter if openmetrics => {
+                fullname.strip_suffix("_total").unwrap_or(fullname)
+            }
+            _ => fullname,
+        };
         format!(
             "# HELP {} {}\n# TYPE {} {}\n",
-            fullname, name, fullname, r#type
+            fullname,
+            name,
+            fullname,
+            r#type.as_str()
         )
     }
 
@@ -418,6 +561,11 @@ impl MetricCollector for TimeSeries {
     }
 }
 
+/// Converts a timestamp in milliseconds to the seconds of the OpenMetrics format.
+fn seconds(timestamp_millis: i64) -> f64 {
+    timestamp_millis as f64 / 1000.0
+}
+
 const fn prometheus_metric_type(metric_value: &MetricValue) -> proto::MetricType {
     use proto::MetricType;
     match metric_value {
@@ -946,4 +1094,86 @@ mod tests {
             "#}
         );
     }
+
+    #[test]
+    fn encodes_openmetrics_text() {
+        let created = Utc.ymd(2021, 2, 3).and_hms(4, 0, 0);
+        let exemplar = |value| Exemplar {
+            labels: [("trace_id".to_owned(), "4bf92f35".to_owned())].into(),
+            value,
+            timestamp: timestamp(),
+        };
+
+        let counter = Metric::new(
+            "hits_total".to_owned(),
+            MetricKind::Absolute,
+            MetricValue::Counter { value: 10.0 },
+        )
+        .with_tags(Some(tags()))
+        .with_timestamp(Some(timestamp()));
+        let mut histogram = VariableHistogram::new(&[1.0, 2.1, 3.0]);
+        histogram.record_many(&[0.4, 2.0, 1.75, 2.6, 2.25, 2.5][..]);
+        let histogram = Metric::new(
+            "requests".to_owned(),
+            MetricKind::Absolute,
+            MetricValue::AggregatedHistogram {
+                buckets: histogram.buckets(),
+                count: histogram.count(),
+                sum: histogram.sum(),
+            },
+        )
+        .with_timestamp(Some(timestamp()));
+        let summary = Metric::new(
+            "latency".to_owned(),
+            MetricKind::Absolute,
+            MetricValue::Distribution {
+                samples: vector_core::samples![1.0 => 3, 2.0 => 1],
+                statistic: StatisticKind::Summary,
+            },
+        );
+
+        let mut collector = StringCollector::new_openmetrics();
+        collector.encode_openmetrics(
+            Some("vector"),
+            &[],
+            &[0.5],
+            &counter,
+            created,
+            Some(&exemplar(2.0)),
+        );
+        collector.encode_openmetrics(
+            Some("vector"),
+            &[],
+            &[0.5],
+            &histogram,
+            created,
+            Some(&exemplar(1.75)),
+        );
+        collector.encode_openmetrics(Some("vector"), &[], &[0.5], &summary, created, None);
+        assert_eq!(
+            collector.finish(),
+            indoc! {r#"
+                # HELP vector_hits hits_total
+                # TYPE vector_hits counter
+                vector_hits_total{code="200"} 10 1612325106.789 # {trace_id="4bf92f35"} 2 1612325106.789
+                vector_hits_created{code="200"} 1612324800
+                # HELP vector_latency latency
+                # TYPE vector_latency summary
+                vector_latency{quantile="0.5"} 1
+                vector_latency_sum 5
+                vector_latency_count 4
+                vector_latency_created 1612324800
+                # HELP vector_requests requests
+                # TYPE vector_requests histogram
+                vector_requests_bucket{le="1"} 1 1612325106.789
+                vector_requests_bucket{le="2.1"} 3 1612325106.789 # {trace_id="4bf92f35"} 1.75 1612325106.789
+                vector_requests_bucket{le="3"} 6 1612325106.789
+                vector_requests_bucket{le="+Inf"} 6 1612325106.789
+                vector_requests_sum 11.5 1612325106.789
+                vector_requests_count 6 1612325106.789
+                vector_requests_created 1612324800
+                # EOF
+            "#}
+        );
+    }
 }
@@ -1,4 +1,5 @@
 use std::{
+    collections::BTreeMap,
     convert::Infallible,
     hash::Hash,
     mem::{discriminant, Discriminant},
@@ -8,6 +9,7 @@ use std::{
 };
 
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use futures::{future, stream::BoxStream, FutureExt, StreamExt};
 use hyper::{
     header::HeaderValue,
@@ -27,11 +29,11 @@ use vector_core::{
     ByteSizeOf,
 };
 
-use super::collector::{MetricCollector, StringCollector};
+use super::collector::{Exemplar, MetricCollector, StringCollector};
 use crate::{
     config::{AcknowledgementsConfig, GenerateConfig, Input, Resource, SinkConfig, SinkContext},
     event::{
-        metric::{Metric, MetricData, MetricKind, MetricSeries, MetricValue},
+        metric::{Metric, MetricData, MetricKind, MetricSeriesKey, MetricValue},
         Event, EventStatus, Finalizable,
     },
     http::Auth,
@@ -49,6 +51,15 @@ use crate::{
 
 const MIN_FLUSH_PERIOD_SECS: u64 = 1;
 
+const OPENMETRICS_CONTENT_TYPE: &str = "application/openmetrics-text";
+
+/// The fields of the metadata of metrics linking them to a trace, rendered as the labels of their
+/// exemplars.
+const EXEMPLAR_LABELS: [&str; 2] = ["trace_id", "span_id"];
+
+/// The maximum combined length of the names and values of the labels of an exemplar.
+const MAX_EXEMPLAR_LABELS_LENGTH: usize = 128;
+
 #[derive(Debug, Snafu)]
 enum BuildError {
     #[snafu(display("Flush period for sets must be greater or equal to {} secs", min))]
@@ -126,6 +137,20 @@ pub struct PrometheusExporterConfig {
     #[serde(default)]
     pub suppress_timestamp: bool,
 
+    /// Serves the metrics in the [OpenMetrics][openmetrics] format to the scrapers requesting it.
+    ///
+    /// Scrapers accepting the `application/openmetrics-text` content type, such as Prometheus, are
+    /// served the metrics in the OpenMetrics format, along with the time their series were
+    /// created, and the exemplars linking them to the trace they were last observed in. Other
+    /// scrapers are still served the Prometheus text format.
+    ///
+    /// The exemplars are made of the `trace_id` and `span_id` fields of the metadata of the
+    /// metrics.
+    ///
+    /// [openmetrics]: https://openmetrics.io/
+    #[serde(default)]
+    pub openmetrics: bool,
+
     #[configurable(derived)]
     #[serde(
         default,
@@ -147,6 +172,7 @@ impl Default for PrometheusExporterConfig {
             distributions_as_summaries: default_distributions_as_summaries(),
             flush_period_secs: default_flush_period_secs(),
             suppress_timestamp: default_suppress_timestamp(),
+            openmetrics: false,
             acknowledgements: Default::default(),
         }
     }
@@ -212,11 +238,14 @@ struct PrometheusExporter {
     metrics: Arc<RwLock<IndexMap<MetricRef, (Metric, MetricMetadata)>>>,
 }
 
-/// Expiration metadata for a metric.
-#[derive(Clone, Copy, Debug)]
+/// Expiration metadata for a metric, along with the time its series was created and its latest
+/// exemplar.
+#[derive(Clone, Debug)]
 struct MetricMetadata {
     expiration_window: Duration,
     expires_at: Instant,
+    created: DateTime<Utc>,
+    exemplar: Option<Exemplar>,
 }
 
 impl MetricMetadata {
@@ -224,6 +253,8 @@ impl MetricMetadata {
         Self {
             expiration_window,
             expires_at: Instant::now() + expiration_window,
+            created: Utc::now(),
+            exemplar: None,
         }
     }
 
@@ -248,7 +279,7 @@ impl MetricMetadata {
 // name is X and type is Y and has these tags".
 #[derive(Clone, Debug)]
 struct MetricRef {
-    series: MetricSeries,
+    series: MetricSeriesKey,
     kind: MetricKind,
     value: Discriminant<MetricValue>,
     bounds: Option<Vec<f64>>,
@@ -269,7 +300,7 @@ impl MetricRef {
         };
 
         Self {
-            series: metric.series().clone(),
+            series: MetricSeriesKey::from(metric.series()),
             kind: metric.kind(),
             value: discriminant(metric.value()),
             bounds,
@@ -368,9 +399,63 @@ fn authorized(req: &Request<Body>, auth: &Option<Auth>) -> bool {
     false
 }
 
+/// Whether the request accepts the OpenMetrics format.
+fn accepts_openmetrics(req: &Request<Body>) -> bool {
+    req.headers()
+        .get_all(hyper::header::ACCEPT)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .any(|media_range| {
+            media_range.split(';').next().map_or(false, |media_type| {
+                media_type
+                    .trim()
+                    .eq_ignore_ascii_case(OPENMETRICS_CONTENT_TYPE)
+            })
+        })
+}
+
+/// Gets the labels of the exemplar of a metric from the trace it's linked to in its metadata.
+fn exemplar_labels(metric: &Metric) -> Option<BTreeMap<String, String>> {
+    let labels = EXEMPLAR_LABELS
+        .iter()
+        .filter_map(|key| {
+            let value = metric.metadata().value().get(*key)?;
+            Some(((*key).to_owned(), value.to_string_lossy()))
+        })
+        .collect::<BTreeMap<_, _>>();
+    let length = labels
+        .iter()
+        .map(|(key, value)| key.chars().count() + value.chars().count())
+        .sum::<usize>();
+    (!labels.is_empty() && length <= MAX_EXEMPLAR_LABELS_LENGTH).then_some(labels)
+}
+
+/// Gets the value observed by a metric before it's normalized, for its exemplar: the last sample
+/// of distributions, or the increment of incremental counters.
+fn observed_value(metric: &Metric) -> Option<f64> {
+    match (metric.kind(), metric.value()) {
+        (_, MetricValue::Distribution { samples, .. }) => samples.last().map(|sample| sample.value),
+        (MetricKind::Incremental, MetricValue::Counter { value }) => Some(*value),
+        _ => None,
+    }
+}
+
+/// Gets the increment of an absolute counter since the previous value of its series.
+fn counter_increment(metric: &Metric, previous: Option<&Metric>) -> Option<f64> {
+    match (metric.value(), previous.map(Metric::value)) {
+        (MetricValue::Counter { value }, Some(MetricValue::Counter { value: previous })) => {
+            Some(value - previous)
+        }
+        (MetricValue::Counter { value }, _) => Some(*value),
+        _ => None,
+    }
+}
+
 fn handle(
     req: Request<Body>,
     auth: &Option<Auth>,
+    openmetrics: bool,
     default_namespace: Option<&str>,
     buckets: &[f64],
     quantiles: &[f64],
@@ -389,6 +474,34 @@ fn handle(
     }
 
     match (req.method(), req.uri().path()) {
+        (&Method::GET, "/metrics") if openmetrics && accepts_openmetrics(&req) => {
+            let mut collector = StringCollector::new_openmetrics();
+
+            for (_, (metric, metadata)) in metrics {
+                collector.encode_openmetrics(
+                    default_namespace,
+                    buckets,
+                    quantiles,
+                    metric,
+                    metadata.created,
+                    metadata.exemplar.as_ref(),
+                );
+            }
+
+            let body = collector.finish();
+            let body_size = body.size_of();
+
+            *response.body_mut() = body.into();
+
+            response.headers_mut().insert(
+                "Content-Type",
+                HeaderValue::from_static(
+                    "application/openmetrics-text; version=1.0.0; charset=utf-8",
+                ),
+            );
+
+            bytes_sent.emit(ByteSize(body_size));
+        }
         (&Method::GET, "/metrics") => {
             let mut collector = StringCollector::new();
 
@@ -438,6 +551,7 @@ impl PrometheusExporter {
         let buckets = self.config.buckets.clone();
         let quantiles = self.config.quantiles.clone();
         let auth = self.config.auth.clone();
+        let openmetrics = self.config.openmetrics;
 
         let new_service = make_service_fn(move |_| {
             let span = Span::current();
@@ -462,6 +576,7 @@ impl PrometheusExporter {
                         let response = handle(
                             req,
                             &auth,
+                            openmetrics,
                             default_namespace.as_deref(),
                             &buckets,
                             &quantiles,
@@ -548,7 +663,9 @@ impl StreamSink<Event> for PrometheusExporter {
 
                 for metric_ref in metrics_to_expire {
                     metrics.remove(&metric_ref);
-                    normalizer.get_state_mut().remove(&metric_ref.series);
+                    normalizer
+                        .get_state_mut()
+                        .remove(&metric_ref.series.to_series());
                 }
             }
 
@@ -556,6 +673,17 @@ impl StreamSink<Event> for PrometheusExporter {
             let mut metric = event.into_metric();
             let finalizers = metric.take_finalizers();
 
+            // Exemplars are only rendered in the OpenMetrics format.
+            let exemplar = self
+                .config
+                .openmetrics
+                .then(|| exemplar_labels(&metric))
+                .flatten()
+                .map(|labels| {
+                    let timestamp = metric.timestamp().unwrap_or_else(Utc::now);
+                    (labels, timestamp, observed_value(&metric))
+                });
+
             if let Some(normalized) = normalizer.normalize(metric) {
                 let normalized = if self.config.suppress_timestamp {
                     normalized.with_timestamp(None)
@@ -568,13 +696,27 @@ impl StreamSink<Event> for PrometheusExporter {
                 let mut metrics = self.metrics.write().unwrap();
 
                 let metric_ref = MetricRef::from_metric(&normalized);
+                let previous = metrics.get(&metric_ref).map(|(data, _)| data);
+                let exemplar = exemplar.and_then(|(labels, timestamp, observed)| {
+                    let value = observed.or_else(|| counter_increment(&normalized, previous))?;
+                    Some(Exemplar {
+                        labels,
+                        value,
+                        timestamp,
+                    })
+                });
                 match metrics.get_mut(&metric_ref) {
                     Some((data, metadata)) => {
                         *data = normalized;
                         metadata.refresh();
+                        if exemplar.is_some() {
+                            metadata.exemplar = exemplar;
+                        }
                     }
                     None => {
-                        metrics.insert(metric_ref, (normalized, MetricMetadata::new(flush_period)));
+                        let mut metadata = MetricMetadata::new(flush_period);
+                        metadata.exemplar = exemplar;
+                        metrics.insert(metric_ref, (normalized, metadata));
                     }
                 }
                 finalizers.update_status(EventStatus::Delivered);
@@ -590,7 +732,7 @@ impl StreamSink<Event> for PrometheusExporter {
 
 #[cfg(test)]
 mod tests {
-    use chrono::{Duration, Utc};
+    use chrono::{Duration, TimeZone, Utc};
     use futures::stream;
     use indoc::indoc;
     use pretty_assertions::assert_eq;
@@ -778,6 +920,98 @@ mod tests {
         assert_eq!(response_result.unwrap_err(), StatusCode::UNAUTHORIZED);
     }
 
+    #[test]
+    fn serves_openmetrics_to_scrapers_accepting_it() {
+        let mut metric = Metric::new(
+            "hits".to_owned(),
+            MetricKind::Absolute,
+            MetricValue::Counter { value: 3.0 },
+        );
+        metric
+            .metadata_mut()
+            .value_mut()
+            .insert("trace_id", "4bf92f35");
+        let labels = exemplar_labels(&metric).unwrap();
+        assert_eq!(labels.len(), 1);
+
+        let mut metadata = MetricMetadata::new(std::time::Duration::from_secs(60));
+        metadata.exemplar = Some(Exemplar {
+            labels,
+            value: 1.0,
+            timestamp: Utc.timestamp(1612325106, 0),
+        });
+        metadata.created = Utc.timestamp(1612325000, 0);
+        let metrics = IndexMap::from([(MetricRef::from_metric(&metric), (metric, metadata))]);
+
+        let bytes_sent = register!(BytesSent::from(Protocol::HTTP));
+        let fetch = |openmetrics, accept| {
+            let request = Request::get("/metrics")
+                .header(hyper::header::ACCEPT, accept)
+                .body(Body::empty())
+                .unwrap();
+            let response = handle(
+                request,
+                &None,
+                openmetrics,
+                None,
+                &[],
+                &[],
+                &metrics,
+                &bytes_sent,
+            );
+            response.headers()["Content-Type"]
+                .to_str()
+                .unwrap()
+                .to_owned()
+        };
+
+        let accept = "application/openmetrics-text;version=1.0.0,text/plain;version=0.0.4;q=0.5";
+        assert_eq!(
+            fetch(true, accept),
+            "application/openmetrics-text; version=1.0.0; charset=utf-8"
+        );
+        assert_eq!(fetch(false, accept), "text/plain; version=0.0.4");
+        assert_eq!(fetch(true, "text/plain"), "text/plain; version=0.0.4");
+
+        let mut collector = StringCollector::new_openmetrics();
+        for (_, (metric, metadata)) in &metrics {
+            collector.encode_openmetrics(
+                None,
+                &[],
+                &[],
+                metric,
+                metadata.created,
+                metadata.exemplar.as_ref(),
+            );
+        }
+        assert_eq!(
+            collector.finish(),
+            indoc! {r#"
+                # HELP hits hits
+                # TYPE hits counter
+                hits_total 3 # {trace_id="4bf92f35"} 1 1612325106
+                hits_created 1612325000
+                # EOF
+            "#}
+        );
+    }
+
+    #[test]
+    fn exemplars_observe_increments() {
+        let counter =
+            |kind, value| Metric::new("hits".to_owned(), kind, MetricValue::Counter { value });
+        assert_eq!(
+            observed_value(&counter(MetricKind::Incremental, 2.0)),
+            Some(2.0)
+        );
+        assert_eq!(observed_value(&counter(MetricKind::Absolute, 5.0)), None);
+
+        let previous = counter(MetricKind::Absolute, 3.0);
+        let current = counter(MetricKind::Absolute, 5.0);
+        assert_eq!(counter_increment(&current, Some(&previous)), Some(2.0));
+        assert_eq!(counter_increment(&current, None), Some(5.0));
+    }
+
     #[tokio::test]
     async fn updates_timestamps() {
         let timestamp1 = Utc::now();
@@ -48,6 +48,10 @@ enum Errors {
     SetMetricInvalid,
     #[snafu(display("aws.region required when AWS authentication is in use"))]
     AwsRegionRequired,
+    #[snafu(display(
+        "aws.endpoint_variant is not supported, as requests are sent to the configured endpoint"
+    ))]
+    AwsEndpointVariantUnsupported,
 }
 
 /// Configuration for the `prometheus_remote_write` sink.
@@ -133,6 +137,14 @@ impl SinkConfig for RemoteWriteConfig {
         let client = HttpClient::new(tls_settings, cx.proxy())?;
         let tenant_id = self.tenant_id.clone();
 
+        if self
+            .aws
+            .as_ref()
+            .map_or(false, |aws| aws.endpoint_variant.is_some())
+        {
+            return Err(Errors::AwsEndpointVariantUnsupported.into());
+        }
+
         let (http_auth, credentials_provider, aws_region) = match &self.auth {
             Some(PrometheusRemoteWriteAuth::Basic { user, password }) => (
                 Some(Auth::Basic {
@@ -478,6 +490,21 @@ mod tests {
         assert!(auth.starts_with("AWS4-HMAC-SHA256"));
     }
 
+    #[tokio::test]
+    async fn rejects_aws_endpoint_variant() {
+        let config: RemoteWriteConfig = toml::from_str(indoc! {r#"
+            endpoint = "http://localhost:9090/write"
+            [aws]
+            region = "us-east-1"
+            endpoint_variant = "fips"
+        "#})
+        .unwrap();
+        let error = config.build(SinkContext::new_test()).await.err().unwrap();
+        assert!(error
+            .to_string()
+            .starts_with("aws.endpoint_variant is not supported"));
+    }
+
     #[tokio::test]
     async fn sends_x_scope_orgid_header() {
         let outputs = send_request(
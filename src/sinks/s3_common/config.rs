@@ -13,7 +13,10 @@ use vector_config::configurable_component;
 
 use super::service::{S3Response, S3Service};
 use crate::{
-    aws::{create_client, is_retriable_error, AwsAuthentication, RegionOrEndpoint},
+    aws::{
+        create_failover_clients, is_retriable_error, AwsAuthentication, FailoverConfig,
+        RegionOrEndpoint,
+    },
     common::s3::S3ClientBuilder,
     config::ProxyConfig,
     sinks::{util::retries::RetryLogic, Healthcheck},
@@ -318,12 +321,28 @@ pub async fn create_service(
     proxy: &ProxyConfig,
     tls_options: &Option<TlsConfig>,
 ) -> crate::Result<S3Service> {
-    let endpoint = region.endpoint()?;
-    let region = region.region();
-    let client =
-        create_client::<S3ClientBuilder>(auth, region.clone(), endpoint, proxy, tls_options, true)
-            .await?;
-    Ok(S3Service::new(client))
+    create_failover_service(region, None, auth, proxy, tls_options).await
+}
+
+/// Creates a service sending objects to the primary region, and to the failover regions once it
+/// keeps failing.
+pub async fn create_failover_service(
+    region: &RegionOrEndpoint,
+    failover: Option<&FailoverConfig>,
+    auth: &AwsAuthentication,
+    proxy: &ProxyConfig,
+    tls_options: &Option<TlsConfig>,
+) -> crate::Result<S3Service> {
+    let clients = create_failover_clients::<S3ClientBuilder>(
+        auth,
+        region,
+        failover,
+        proxy,
+        tls_options,
+        true,
+    )
+    .await?;
+    Ok(S3Service::with_failover(clients))
 }
 
 #[cfg(test)]
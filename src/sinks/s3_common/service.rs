@@ -1,12 +1,16 @@
 use std::task::{Context, Poll};
 
 use aws_sdk_s3::{
+    client::fluent_builders::PutObject,
     error::PutObjectError,
+    output::PutObjectOutput,
     types::{ByteStream, SdkError},
     Client as S3Client,
 };
+use aws_smithy_http::body::SdkBody;
 use bytes::Bytes;
 use futures::future::BoxFuture;
+use http::uri::InvalidUri;
 use md5::Digest;
 use tower::Service;
 use tracing::Instrument;
@@ -17,6 +21,7 @@ use vector_core::{
 };
 
 use super::config::S3Options;
+use crate::aws::RegionFailover;
 
 #[derive(Debug, Clone)]
 pub struct S3Request {
@@ -65,16 +70,38 @@ impl DriverResponse for S3Response {
 /// limits, rate limits, and more.
 #[derive(Clone)]
 pub struct S3Service {
-    client: S3Client,
+    clients: RegionFailover<S3Client>,
+    bucket_in_host: bool,
 }
 
 impl S3Service {
-    pub const fn new(client: S3Client) -> S3Service {
-        S3Service { client }
+    pub fn new(client: S3Client) -> S3Service {
+        S3Service::with_failover(RegionFailover::single(client))
     }
 
+    /// Creates a service failing over to the clients of other regions when the primary one keeps
+    /// failing.
+    pub const fn with_failover(clients: RegionFailover<S3Client>) -> S3Service {
+        S3Service {
+            clients,
+            bucket_in_host: false,
+        }
+    }
+
+    /// Creates a service for a client whose endpoint addresses the bucket through its hostname,
+    /// such as the transfer acceleration endpoint of a bucket.
+    ///
+    /// The SDK always puts the bucket in the path of requests, so it is removed from there again.
+    pub fn with_bucket_in_host(client: S3Client) -> S3Service {
+        S3Service {
+            clients: RegionFailover::single(client),
+            bucket_in_host: true,
+        }
+    }
+
+    /// The client of the primary region.
     pub fn client(&self) -> S3Client {
-        self.client.clone()
+        self.clients.primary()
     }
 }
 
@@ -114,13 +141,16 @@ impl Service<S3Request> for S3Service {
         let count = request.metadata.count;
         let events_byte_size = request.metadata.byte_size;
 
-        let client = self.client.clone();
+        let clients = self.clients.clone();
+        let (index, client) = clients.select();
+        let bucket_in_host = self.bucket_in_host;
 
         Box::pin(async move {
+            let bucket = request.bucket;
             let request = client
                 .put_object()
                 .body(bytes_to_bytestream(request.body))
-                .bucket(request.bucket)
+                .bucket(bucket.clone())
                 .key(request.metadata.partition_key)
                 .set_content_encoding(content_encoding)
                 .set_content_type(content_type)
@@ -135,7 +165,14 @@ impl Service<S3Request> for S3Service {
                 .set_tagging(tagging)
                 .content_md5(content_md5);
 
-            let result = request.send().in_current_span().await;
+            let result = if bucket_in_host {
+                send_with_bucket_in_host(request, &bucket)
+                    .in_current_span()
+                    .await
+            } else {
+                request.send().in_current_span().await
+            };
+            clients.report(index, &result);
 
             result.map(|_| S3Response {
                 count,
@@ -148,3 +185,72 @@ impl Service<S3Request> for S3Service {
 fn bytes_to_bytestream(buf: Bytes) -> ByteStream {
     ByteStream::from(buf)
 }
+
+async fn send_with_bucket_in_host(
+    request: PutObject,
+    bucket: &str,
+) -> Result<PutObjectOutput, SdkError<PutObjectError>> {
+    request
+        .customize()
+        .await?
+        .map_request(|request| remove_bucket_from_path(bucket, request))
+        .map_err(|error| SdkError::ConstructionFailure(Box::new(error)))?
+        .send()
+        .await
+}
+
+/// Removes the leading bucket segment from the path of a path-style request.
+fn remove_bucket_from_path(
+    bucket: &str,
+    mut request: http::Request<SdkBody>,
+) -> Result<http::Request<SdkBody>, InvalidUri> {
+    let rest = request
+        .uri()
+        .path_and_query()
+        .and_then(|path| path.as_str().strip_prefix('/'))
+        .and_then(|path| path.strip_prefix(bucket))
+        .filter(|rest| rest.is_empty() || rest.starts_with('/') || rest.starts_with('?'))
+        .map(|rest| {
+            if rest.starts_with('/') {
+                rest.to_owned()
+            } else {
+                format!("/{}", rest)
+            }
+        });
+    if let Some(path) = rest {
+        *request.uri_mut() = path.parse()?;
+    }
+    Ok(request)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn path_without_bucket(bucket: &str, uri: &str) -> String {
+        let request = http::Request::builder()
+            .uri(uri)
+            .body(SdkBody::empty())
+            .unwrap();
+        remove_bucket_from_path(bucket, request)
+            .unwrap()
+            .uri()
+            .to_string()
+    }
+
+    #[test]
+    fn removes_bucket_from_path() {
+        assert_eq!(
+            path_without_bucket(
+                "logs",
+                "/logs/date=2022-10-01/1664582400.log.gz?x-id=PutObject"
+            ),
+            "/date=2022-10-01/1664582400.log.gz?x-id=PutObject"
+        );
+        assert_eq!(path_without_bucket("logs", "/logs"), "/");
+        assert_eq!(
+            path_without_bucket("logs", "/logs-archive/key"),
+            "/logs-archive/key"
+        );
+    }
+}
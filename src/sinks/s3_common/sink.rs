@@ -13,7 +13,13 @@ use vector_core::{
 use crate::internal_events::SinkRequestBuildError;
 use crate::{
     event::Event,
-    sinks::util::{partitioner::KeyPartitioner, RequestBuilder, SinkBuilderExt},
+    sinks::util::{
+        adaptive_batching::{
+            AdaptiveBatchController, AdaptiveBatchRequestBuilder, AdaptiveBatchService,
+        },
+        partitioner::KeyPartitioner,
+        RequestBuilder, SinkBuilderExt,
+    },
 };
 
 pub struct S3Sink<Svc, RB> {
@@ -21,6 +27,7 @@ pub struct S3Sink<Svc, RB> {
     request_builder: RB,
     partitioner: KeyPartitioner,
     batcher_settings: BatcherSettings,
+    adaptive_batching: Option<AdaptiveBatchController<String>>,
 }
 
 impl<Svc, RB> S3Sink<Svc, RB> {
@@ -35,8 +42,20 @@ impl<Svc, RB> S3Sink<Svc, RB> {
             service,
             request_builder,
             batcher_settings,
+            adaptive_batching: None,
         }
     }
+
+    /// Adjusts the size of the batches of each key prefix to the latency of its requests with
+    /// `controller`.
+    #[must_use]
+    pub fn with_adaptive_batching(
+        mut self,
+        controller: Option<AdaptiveBatchController<String>>,
+    ) -> Self {
+        self.adaptive_batching = controller;
+        self
+    }
 }
 
 impl<Svc, RB> S3Sink<Svc, RB>
@@ -44,7 +63,7 @@ where
     Svc: Service<RB::Request> + Send + 'static,
     Svc::Future: Send + 'static,
     Svc::Response: DriverResponse + Send + 'static,
-    Svc::Error: fmt::Debug + Into<crate::Error> + Send,
+    Svc::Error: fmt::Debug + Into<crate::Error> + Send + 'static,
     RB: RequestBuilder<(String, Vec<Event>)> + Send + Sync + 'static,
     RB::Error: fmt::Display + Send,
     RB::Request: Finalizable + Send,
@@ -54,10 +73,22 @@ where
         let settings = self.batcher_settings;
 
         let builder_limit = NonZeroUsize::new(64);
-        let request_builder = self.request_builder;
+        let request_builder = AdaptiveBatchRequestBuilder::new(self.request_builder);
+        let adaptive_batching = self.adaptive_batching;
+        let service = AdaptiveBatchService::new(self.service, adaptive_batching.clone());
+
+        let batches = input.batched_partitioned(partitioner, settings);
+        let batches = match adaptive_batching {
+            Some(controller) => batches.with_partition_limits(move |key: &Option<String>| {
+                key.as_ref()
+                    .map_or((settings.size_limit, settings.item_limit), |key| {
+                        controller.limits(key)
+                    })
+            }),
+            None => batches,
+        };
 
-        let sink = input
-            .batched_partitioned(partitioner, settings)
+        let sink = batches
             .filter_map(|(key, batch)| async move { key.map(move |k| (k, batch)) })
             .request_builder(builder_limit, request_builder)
             .filter_map(|request| async move {
@@ -69,7 +100,7 @@ where
                     Ok(req) => Some(req),
                 }
             })
-            .into_driver(self.service);
+            .into_driver(service);
 
         sink.run().await
     }
@@ -81,7 +112,7 @@ where
     Svc: Service<RB::Request> + Send + 'static,
     Svc::Future: Send + 'static,
     Svc::Response: DriverResponse + Send + 'static,
-    Svc::Error: fmt::Debug + Into<crate::Error> + Send,
+    Svc::Error: fmt::Debug + Into<crate::Error> + Send + 'static,
     RB: RequestBuilder<(String, Vec<Event>)> + Send + Sync + 'static,
     RB::Error: fmt::Display + Send,
     RB::Request: Finalizable + Send,
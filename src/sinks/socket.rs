@@ -42,6 +42,12 @@ pub enum Mode {
     /// Unix Domain Socket.
     #[cfg(unix)]
     Unix(#[configurable(transparent)] UnixMode),
+
+    /// Unix Domain Socket, in sequenced packet mode.
+    ///
+    /// Each event is sent as a single packet, without framing.
+    #[cfg(unix)]
+    UnixSeqpacket(#[configurable(transparent)] UnixSeqpacketMode),
 }
 
 /// TCP configuration.
@@ -78,6 +84,18 @@ pub struct UnixMode {
     encoding: EncodingConfigWithFraming,
 }
 
+/// Unix Domain Socket configuration, in sequenced packet mode.
+#[cfg(unix)]
+#[configurable_component]
+#[derive(Clone, Debug)]
+pub struct UnixSeqpacketMode {
+    #[serde(flatten)]
+    config: UnixSinkConfig,
+
+    #[configurable(derived)]
+    encoding: EncodingConfig,
+}
+
 impl GenerateConfig for SocketSinkConfig {
     fn generate_config() -> toml::Value {
         toml::from_str(
@@ -137,6 +155,13 @@ impl SinkConfig for SocketSinkConfig {
                 let encoder = Encoder::<Framer>::new(framer, serializer);
                 config.build(transformer, encoder)
             }
+            #[cfg(unix)]
+            Mode::UnixSeqpacket(UnixSeqpacketMode { config, encoding }) => {
+                let transformer = encoding.transformer();
+                let serializer = encoding.build()?;
+                let encoder = Encoder::<()>::new(serializer);
+                config.build_seqpacket(transformer, encoder)
+            }
         }
     }
 
@@ -146,6 +171,10 @@ impl SinkConfig for SocketSinkConfig {
             Mode::Udp(UdpMode { encoding, .. }) => encoding.config().input_type(),
             #[cfg(unix)]
             Mode::Unix(UnixMode { encoding, .. }) => encoding.config().1.input_type(),
+            #[cfg(unix)]
+            Mode::UnixSeqpacket(UnixSeqpacketMode { encoding, .. }) => {
+                encoding.config().input_type()
+            }
         };
         Input::new(encoder_input_type & DataType::Log)
     }
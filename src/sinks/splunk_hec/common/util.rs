@@ -148,12 +148,18 @@ pub fn render_template_string<'a>(
             emit!(TemplateRenderingError {
                 error,
                 field: Some(field_name),
-                drop_event: false
+                drop_event: template.drops_on_render_error(),
             });
         })
         .ok()
 }
 
+/// Returns `true` if the event must be dropped because `template` failed to render, as set by its
+/// `on_render_error` policy.
+pub fn drops_unrendered(template: Option<&Template>, rendered: &Option<String>) -> bool {
+    rendered.is_none() && template.map_or(false, Template::drops_on_render_error)
+}
+
 #[cfg(test)]
 mod tests {
     use bytes::Bytes;
@@ -1,7 +1,7 @@
 use std::{fmt, num::NonZeroUsize, sync::Arc};
 
 use async_trait::async_trait;
-use futures_util::{stream::BoxStream, StreamExt};
+use futures_util::{future, stream::BoxStream, StreamExt};
 use tower::Service;
 use vector_core::{
     event::{Event, LogEvent, Value},
@@ -19,8 +19,8 @@ use crate::{
     internal_events::TemplateRenderingError,
     sinks::{
         splunk_hec::common::{
-            render_template_string, request::HecRequest, EndpointTarget, INDEX_FIELD,
-            SOURCETYPE_FIELD, SOURCE_FIELD,
+            drops_unrendered, render_template_string, request::HecRequest, EndpointTarget,
+            INDEX_FIELD, SOURCETYPE_FIELD, SOURCE_FIELD,
         },
         util::{processed_event::ProcessedEvent, SinkBuilderExt},
     },
@@ -76,7 +76,7 @@ where
         };
 
         let sink = input
-            .map(move |event| process_log(event, &data))
+            .filter_map(move |event| future::ready(process_log(event, &data)))
             .batched_partitioned(
                 if self.endpoint_target == EndpointTarget::Raw {
                     // We only need to partition by the metadata fields for the raw endpoint since those fields
@@ -228,7 +228,9 @@ impl ByteSizeOf for HecLogsProcessedEventMetadata {
 
 pub type HecProcessedEvent = ProcessedEvent<LogEvent, HecLogsProcessedEventMetadata>;
 
-pub fn process_log(event: Event, data: &HecLogData) -> HecProcessedEvent {
+/// Processes a log event, returning `None` if it must be dropped because one of its templates
+/// failed to render.
+pub fn process_log(event: Event, data: &HecLogData) -> Option<HecProcessedEvent> {
     let event_byte_size = event.size_of();
     let mut log = event.into_log();
 
@@ -244,6 +246,14 @@ pub fn process_log(event: Event, data: &HecLogData) -> HecProcessedEvent {
         .index
         .and_then(|index| render_template_string(index, &log, INDEX_FIELD));
 
+    if [data.sourcetype, data.source, data.index]
+        .into_iter()
+        .zip([&sourcetype, &source, &index])
+        .any(|(template, rendered)| drops_unrendered(template, rendered))
+    {
+        return None;
+    }
+
     let host = log.get(data.host_key).cloned();
 
     let timestamp = if data.timestamp_key.is_empty() {
@@ -287,8 +297,8 @@ pub fn process_log(event: Event, data: &HecLogData) -> HecProcessedEvent {
         endpoint_target: data.endpoint_target,
     };
 
-    ProcessedEvent {
+    Some(ProcessedEvent {
         event: log,
         metadata,
-    }
+    })
 }
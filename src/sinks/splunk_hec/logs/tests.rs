@@ -87,6 +87,7 @@ fn get_processed_event_timestamp(
             endpoint_target: EndpointTarget::Event,
         },
     )
+    .unwrap()
 }
 
 fn get_processed_event() -> HecProcessedEvent {
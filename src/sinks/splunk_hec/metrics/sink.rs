@@ -16,7 +16,7 @@ use crate::{
     config::SinkContext,
     internal_events::SplunkInvalidMetricReceivedError,
     sinks::{
-        splunk_hec::common::{render_template_string, request::HecRequest},
+        splunk_hec::common::{drops_unrendered, render_template_string, request::HecRequest},
         util::{encode_namespace, processed_event::ProcessedEvent, SinkBuilderExt},
     },
     template::Template,
@@ -169,10 +169,18 @@ pub fn process_metric(
         HecMetricsProcessedEventMetadata::extract_metric_name(&metric, default_namespace);
     let metric_value = HecMetricsProcessedEventMetadata::extract_metric_value(&metric)?;
 
+    let templates = [sourcetype, source, index];
     let sourcetype =
         sourcetype.and_then(|sourcetype| render_template_string(sourcetype, &metric, "sourcetype"));
     let source = source.and_then(|source| render_template_string(source, &metric, "source"));
     let index = index.and_then(|index| render_template_string(index, &metric, "index"));
+    if templates
+        .into_iter()
+        .zip([&sourcetype, &source, &index])
+        .any(|(template, rendered)| drops_unrendered(template, rendered))
+    {
+        return None;
+    }
     let host = metric.tag_value(host_key);
 
     let metadata = HecMetricsProcessedEventMetadata {
@@ -46,6 +46,10 @@ pub struct StatsdSinkConfig {
     #[serde(alias = "namespace")]
     pub default_namespace: Option<String>,
 
+    #[configurable(derived)]
+    #[serde(default)]
+    pub flavor: StatsdFlavor,
+
     #[serde(flatten)]
     pub mode: Mode,
 
@@ -58,6 +62,30 @@ pub struct StatsdSinkConfig {
     pub acknowledgements: AcknowledgementsConfig,
 }
 
+/// The flavor of the StatsD protocol spoken by the receiver.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum StatsdFlavor {
+    /// The DogStatsD protocol.
+    ///
+    /// Tags are sent, and distributions are sent as histograms (`h`) or distributions (`d`),
+    /// depending on their statistic.
+    Dogstatsd,
+
+    /// The original StatsD protocol.
+    ///
+    /// Tags aren't sent, as the protocol doesn't support them, and distributions are sent as
+    /// timings (`ms`).
+    Statsd,
+}
+
+impl Default for StatsdFlavor {
+    fn default() -> Self {
+        Self::Dogstatsd
+    }
+}
+
 /// Socket mode.
 #[configurable_component]
 #[derive(Clone, Debug)]
@@ -103,6 +131,7 @@ impl GenerateConfig for StatsdSinkConfig {
     fn generate_config() -> toml::Value {
         toml::Value::try_from(&Self {
             default_namespace: None,
+            flavor: StatsdFlavor::default(),
             mode: Mode::Udp(StatsdUdpConfig {
                 batch: Default::default(),
                 udp: UdpSinkConfig::from_address(default_address().to_string()),
@@ -120,7 +149,10 @@ impl SinkConfig for StatsdSinkConfig {
         _cx: SinkContext,
     ) -> crate::Result<(super::VectorSink, super::Healthcheck)> {
         let default_namespace = self.default_namespace.clone();
-        let mut encoder = StatsdEncoder { default_namespace };
+        let mut encoder = StatsdEncoder {
+            default_namespace,
+            flavor: self.flavor,
+        };
         match &self.mode {
             Mode::Tcp(config) => config.build(Default::default(), encoder),
             Mode::Udp(config) => {
@@ -187,6 +219,7 @@ fn push_event<V: Display>(
     val: V,
     metric_type: &str,
     sample_rate: Option<u32>,
+    tags: Option<&MetricTags>,
 ) {
     buf.push(format!("{}:{}|{}", metric.name(), val, metric_type));
 
@@ -196,7 +229,7 @@ fn push_event<V: Display>(
         }
     };
 
-    if let Some(t) = metric.tags() {
+    if let Some(t) = tags {
         buf.push(format!("#{}", encode_tags(t)));
     };
 }
@@ -204,6 +237,7 @@ fn push_event<V: Display>(
 #[derive(Debug, Clone)]
 struct StatsdEncoder {
     default_namespace: Option<String>,
+    flavor: StatsdFlavor,
 }
 
 impl Encoder<Event> for StatsdEncoder {
@@ -213,22 +247,27 @@ impl Encoder<Event> for StatsdEncoder {
         let mut buf = Vec::new();
 
         let metric = event.as_metric();
+        let tags = match self.flavor {
+            StatsdFlavor::Dogstatsd => metric.tags(),
+            StatsdFlavor::Statsd => None,
+        };
         match metric.value() {
             MetricValue::Counter { value } => {
-                push_event(&mut buf, metric, value, "c", None);
+                push_event(&mut buf, metric, value, "c", None, tags);
             }
             MetricValue::Gauge { value } => {
                 match metric.kind() {
                     MetricKind::Incremental => {
-                        push_event(&mut buf, metric, format!("{:+}", value), "g", None)
+                        push_event(&mut buf, metric, format!("{:+}", value), "g", None, tags)
                     }
-                    MetricKind::Absolute => push_event(&mut buf, metric, value, "g", None),
+                    MetricKind::Absolute => push_event(&mut buf, metric, value, "g", None, tags),
                 };
             }
             MetricValue::Distribution { samples, statistic } => {
-                let metric_type = match statistic {
-                    StatisticKind::Histogram => "h",
-                    StatisticKind::Summary => "d",
+                let metric_type = match (self.flavor, statistic) {
+                    (StatsdFlavor::Statsd, _) => "ms",
+                    (StatsdFlavor::Dogstatsd, StatisticKind::Histogram) => "h",
+                    (StatsdFlavor::Dogstatsd, StatisticKind::Summary) => "d",
                 };
 
                 // TODO: This would actually be good to potentially add a helper combinator for, in the same vein as
@@ -248,12 +287,13 @@ impl Encoder<Event> for StatsdEncoder {
                         sample.value,
                         metric_type,
                         Some(sample.rate),
+                        tags,
                     );
                 }
             }
             MetricValue::Set { values } => {
                 for val in values {
-                    push_event(&mut buf, metric, val, "s", None);
+                    push_event(&mut buf, metric, val, "s", None, tags);
                 }
             }
             _ => {
@@ -365,6 +405,7 @@ mod test {
         let event = Event::Metric(metric1.clone());
         let mut encoder = StatsdEncoder {
             default_namespace: None,
+            flavor: StatsdFlavor::Dogstatsd,
         };
         let mut frame = BytesMut::new();
         encoder.encode(event, &mut frame).unwrap();
@@ -383,6 +424,7 @@ mod test {
         let event = Event::Metric(metric1);
         let mut encoder = StatsdEncoder {
             default_namespace: None,
+            flavor: StatsdFlavor::Dogstatsd,
         };
         let mut frame = BytesMut::new();
         encoder.encode(event, &mut frame).unwrap();
@@ -403,6 +445,7 @@ mod test {
         let event = Event::Metric(metric1.clone());
         let mut encoder = StatsdEncoder {
             default_namespace: None,
+            flavor: StatsdFlavor::Dogstatsd,
         };
         let mut frame = BytesMut::new();
         encoder.encode(event, &mut frame).unwrap();
@@ -422,6 +465,7 @@ mod test {
         let event = Event::Metric(metric1.clone());
         let mut encoder = StatsdEncoder {
             default_namespace: None,
+            flavor: StatsdFlavor::Dogstatsd,
         };
         let mut frame = BytesMut::new();
         encoder.encode(event, &mut frame).unwrap();
@@ -455,6 +499,7 @@ mod test {
         let event = Event::Metric(metric1);
         let mut encoder = StatsdEncoder {
             default_namespace: None,
+            flavor: StatsdFlavor::Dogstatsd,
         };
         let mut frame = BytesMut::new();
         encoder.encode(event, &mut frame).unwrap();
@@ -476,6 +521,7 @@ mod test {
         let event = Event::Metric(metric1.clone());
         let mut encoder = StatsdEncoder {
             default_namespace: None,
+            flavor: StatsdFlavor::Dogstatsd,
         };
         let mut frame = BytesMut::new();
         encoder.encode(event, &mut frame).unwrap();
@@ -483,6 +529,36 @@ mod test {
         vector_common::assert_event_data_eq!(metric1, metric2);
     }
 
+    #[test]
+    fn test_encode_statsd_flavor() {
+        let mut encoder = StatsdEncoder {
+            default_namespace: None,
+            flavor: StatsdFlavor::Statsd,
+        };
+        let metrics = vec![
+            Metric::new(
+                "counter",
+                MetricKind::Incremental,
+                MetricValue::Counter { value: 1.5 },
+            ),
+            Metric::new(
+                "timing",
+                MetricKind::Incremental,
+                MetricValue::Distribution {
+                    samples: vector_core::samples![2.0 => 10],
+                    statistic: StatisticKind::Summary,
+                },
+            ),
+        ];
+
+        let mut frame = BytesMut::new();
+        for metric in metrics {
+            let event = Event::Metric(metric.with_tags(Some(tags())));
+            encoder.encode(event, &mut frame).unwrap();
+        }
+        assert_eq!(&frame[..], b"counter:1.5|c\ntiming:2|ms|@0.1\n".as_slice());
+    }
+
     #[tokio::test]
     async fn test_send_to_statsd() {
         trace_init();
@@ -493,6 +569,7 @@ mod test {
 
         let config = StatsdSinkConfig {
             default_namespace: Some("ns".into()),
+            flavor: StatsdFlavor::Dogstatsd,
             mode: Mode::Udp(StatsdUdpConfig {
                 batch,
                 udp: UdpSinkConfig::from_address(addr.to_string()),
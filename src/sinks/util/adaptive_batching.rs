@@ -0,0 +1,440 @@
+//! Adjust the size of the batches of each partition of a sink to the latency of its requests.
+
+use std::{
+    collections::HashMap,
+    hash::Hash,
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use futures::future::BoxFuture;
+use snafu::Snafu;
+use tower::Service;
+use vector_config::configurable_component;
+use vector_core::stream::{BatcherSettings, DriverResponse};
+
+use super::{request_builder::EncodeResult, Compression, RequestBuilder};
+use crate::{
+    event::{EventFinalizers, EventStatus, Finalizable},
+    internal_events::AdaptiveBatchLimit,
+};
+
+/// The fraction of the batch limits by which the batches of a partition grow after each request
+/// that was fast enough.
+const INCREASE_STEP: f64 = 0.05;
+
+#[derive(Debug, Snafu, PartialEq, Eq)]
+pub enum AdaptiveBatchError {
+    #[snafu(display("`adaptive_batching.{}` must be between 0 and 1", name))]
+    InvalidRatio { name: &'static str },
+    #[snafu(display(
+        "`adaptive_batching.target_latency_secs` must be a finite number greater than zero"
+    ))]
+    InvalidTargetLatency,
+}
+
+/// Configuration of adaptive batching.
+///
+/// With adaptive batching, the batch limits of a sink are the size of the largest batches sent to
+/// each partition, such as a log stream. The batches of a partition are made smaller while its
+/// requests are slower than the target latency or fail, and grow back to the batch limits once its
+/// requests are fast again.
+#[configurable_component]
+#[derive(Clone, Copy, Debug)]
+#[serde(default, deny_unknown_fields)]
+pub struct AdaptiveBatchSettings {
+    /// The latency, in seconds, that requests should stay under.
+    ///
+    /// The batches of a partition are made smaller while the average latency of its requests is
+    /// above this. Must be a finite number greater than `0`.
+    #[configurable(validation(range(min = 0.0)))]
+    pub target_latency_secs: f64,
+
+    /// The smallest fraction of the batch limits that batches can be made.
+    ///
+    /// Valid values are greater than `0` and less than or equal to `1`.
+    #[configurable(validation(range(min = 0.0, max = 1.0)))]
+    pub min_ratio: f64,
+
+    /// The fraction of their current size to make batches when decreasing their size.
+    ///
+    /// Valid values are greater than `0` and less than `1`. Smaller values cause batches to be made
+    /// smaller more rapidly when latency increases.
+    #[configurable(validation(range(min = 0.0, max = 1.0)))]
+    pub decrease_ratio: f64,
+
+    /// The weighting of new latency measurements compared to older measurements.
+    ///
+    /// Valid values are greater than `0` and less than or equal to `1`. The average latency of
+    /// requests is an exponentially weighted moving average (EWMA), and smaller values cause it to
+    /// adjust more slowly, which may be useful if a service has unusually high response variability.
+    #[configurable(validation(range(min = 0.0, max = 1.0)))]
+    pub ewma_alpha: f64,
+}
+
+impl AdaptiveBatchSettings {
+    pub const fn const_default() -> Self {
+        Self {
+            target_latency_secs: 1.0,
+            min_ratio: 0.1,
+            decrease_ratio: 0.9,
+            ewma_alpha: 0.4,
+        }
+    }
+
+    fn validate(&self) -> Result<(), AdaptiveBatchError> {
+        let ratios = [
+            ("min_ratio", self.min_ratio, true),
+            ("decrease_ratio", self.decrease_ratio, false),
+            ("ewma_alpha", self.ewma_alpha, true),
+        ];
+        for (name, ratio, one_allowed) in ratios {
+            // Written so that `NaN` is rejected too.
+            if !(ratio > 0.0 && ratio <= 1.0) || (ratio == 1.0 && !one_allowed) {
+                return Err(AdaptiveBatchError::InvalidRatio { name });
+            }
+        }
+        if !self.target_latency_secs.is_finite() || self.target_latency_secs <= 0.0 {
+            return Err(AdaptiveBatchError::InvalidTargetLatency);
+        }
+        Ok(())
+    }
+}
+
+impl Default for AdaptiveBatchSettings {
+    fn default() -> Self {
+        AdaptiveBatchSettings::const_default()
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+struct PartitionState {
+    /// The fraction of the batch limits to limit the batches of the partition to.
+    ratio: f64,
+    /// The moving average of the latency of the requests of the partition, in seconds.
+    latency: f64,
+}
+
+struct Inner<K> {
+    settings: AdaptiveBatchSettings,
+    batcher_settings: BatcherSettings,
+    /// The partitions whose batches were made smaller than the batch limits.
+    partitions: Mutex<HashMap<K, PartitionState>>,
+}
+
+/// Tracks the latency of the requests of each partition of a sink, to make their batches smaller
+/// or larger.
+pub struct AdaptiveBatchController<K> {
+    inner: Arc<Inner<K>>,
+}
+
+impl<K> Clone for AdaptiveBatchController<K> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: Arc::clone(&self.inner),
+        }
+    }
+}
+
+impl<K> AdaptiveBatchController<K>
+where
+    K: Clone + Eq + Hash,
+{
+    pub fn new(
+        settings: AdaptiveBatchSettings,
+        batcher_settings: BatcherSettings,
+    ) -> Result<Self, AdaptiveBatchError> {
+        settings.validate()?;
+        Ok(Self {
+            inner: Arc::new(Inner {
+                settings,
+                batcher_settings,
+                partitions: Mutex::new(HashMap::new()),
+            }),
+        })
+    }
+
+    /// Gets the allocation and item limits of the next batch of a partition.
+    pub fn limits(&self, key: &K) -> (usize, usize) {
+        let ratio = self
+            .inner
+            .partitions
+            .lock()
+            .expect("poisoned lock")
+            .get(key)
+            .map_or(1.0, |state| state.ratio);
+        let settings = &self.inner.batcher_settings;
+        (
+            scale(settings.size_limit, ratio),
+            scale(settings.item_limit, ratio),
+        )
+    }
+
+    /// Records the latency of a request of a partition, and whether it was successful, to adjust
+    /// the size of the next batches of the partition.
+    pub fn record(&self, key: &K, latency: Duration, success: bool) {
+        let settings = &self.inner.settings;
+        let latency = latency.as_secs_f64();
+
+        let mut partitions = self.inner.partitions.lock().expect("poisoned lock");
+        let mut state = match partitions.get(key) {
+            Some(state) => PartitionState {
+                ratio: state.ratio,
+                latency: state.latency + settings.ewma_alpha * (latency - state.latency),
+            },
+            None => PartitionState {
+                ratio: 1.0,
+                latency,
+            },
+        };
+
+        state.ratio = if !success || state.latency > settings.target_latency_secs {
+            (state.ratio * settings.decrease_ratio).max(settings.min_ratio)
+        } else {
+            (state.ratio + INCREASE_STEP).min(1.0)
+        };
+
+        emit!(AdaptiveBatchLimit {
+            ratio: state.ratio,
+            averaged_latency: Duration::from_secs_f64(state.latency),
+            had_back_pressure: !success,
+        });
+
+        // Partitions whose batches are back to the batch limits are forgotten, so that only the
+        // partitions currently being slowed down are tracked.
+        if state.ratio >= 1.0 {
+            partitions.remove(key);
+        } else {
+            partitions.insert(key.clone(), state);
+        }
+    }
+}
+
+/// Scales a batch limit, leaving it unlimited if it is.
+fn scale(limit: usize, ratio: f64) -> usize {
+    if limit == usize::MAX {
+        limit
+    } else {
+        ((limit as f64 * ratio).round() as usize).max(1)
+    }
+}
+
+/// A request built from a batch of a partition, whose latency is recorded for the partition.
+pub struct AdaptiveBatchRequest<K, R> {
+    key: K,
+    request: R,
+}
+
+impl<K, R> AdaptiveBatchRequest<K, R> {
+    pub const fn new(key: K, request: R) -> Self {
+        Self { key, request }
+    }
+}
+
+impl<K, R> Finalizable for AdaptiveBatchRequest<K, R>
+where
+    R: Finalizable,
+{
+    fn take_finalizers(&mut self) -> EventFinalizers {
+        self.request.take_finalizers()
+    }
+}
+
+/// A request builder whose requests carry the partition of their batch, to be sent through an
+/// [`AdaptiveBatchService`].
+pub struct AdaptiveBatchRequestBuilder<RB> {
+    inner: RB,
+}
+
+impl<RB> AdaptiveBatchRequestBuilder<RB> {
+    pub const fn new(inner: RB) -> Self {
+        Self { inner }
+    }
+}
+
+impl<K, E, RB> RequestBuilder<(K, E)> for AdaptiveBatchRequestBuilder<RB>
+where
+    K: Clone,
+    RB: RequestBuilder<(K, E)>,
+{
+    type Metadata = (K, RB::Metadata);
+    type Events = RB::Events;
+    type Encoder = RB::Encoder;
+    type Payload = RB::Payload;
+    type Request = AdaptiveBatchRequest<K, RB::Request>;
+    type Error = RB::Error;
+
+    fn compression(&self) -> Compression {
+        self.inner.compression()
+    }
+
+    fn encoder(&self) -> &Self::Encoder {
+        self.inner.encoder()
+    }
+
+    fn split_input(&self, input: (K, E)) -> (Self::Metadata, Self::Events) {
+        let key = input.0.clone();
+        let (metadata, events) = self.inner.split_input(input);
+        ((key, metadata), events)
+    }
+
+    fn encode_events(
+        &self,
+        events: Self::Events,
+    ) -> Result<EncodeResult<Self::Payload>, Self::Error> {
+        self.inner.encode_events(events)
+    }
+
+    fn build_request(
+        &self,
+        (key, metadata): Self::Metadata,
+        payload: EncodeResult<Self::Payload>,
+    ) -> Self::Request {
+        AdaptiveBatchRequest::new(key, self.inner.build_request(metadata, payload))
+    }
+}
+
+/// A service recording the latency of the requests of each partition with an
+/// [`AdaptiveBatchController`], if adaptive batching is enabled.
+#[derive(Clone)]
+pub struct AdaptiveBatchService<K, S> {
+    inner: S,
+    controller: Option<AdaptiveBatchController<K>>,
+}
+
+impl<K, S> AdaptiveBatchService<K, S> {
+    pub const fn new(inner: S, controller: Option<AdaptiveBatchController<K>>) -> Self {
+        Self { inner, controller }
+    }
+}
+
+impl<K, S, R> Service<AdaptiveBatchRequest<K, R>> for AdaptiveBatchService<K, S>
+where
+    K: Clone + Eq + Hash + Send + Sync + 'static,
+    S: Service<R>,
+    S::Future: Send + 'static,
+    S::Response: DriverResponse + Send + 'static,
+    S::Error: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: AdaptiveBatchRequest<K, R>) -> Self::Future {
+        let AdaptiveBatchRequest { key, request } = request;
+        let controller = self.controller.clone();
+        let start = tokio::time::Instant::now();
+        let future = self.inner.call(request);
+
+        Box::pin(async move {
+            let result = future.await;
+            if let Some(controller) = controller {
+                let success = matches!(
+                    &result,
+                    Ok(response) if response.event_status() == EventStatus::Delivered
+                );
+                controller.record(&key, start.elapsed(), success);
+            }
+            result
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroUsize;
+
+    use super::*;
+
+    fn controller() -> AdaptiveBatchController<&'static str> {
+        AdaptiveBatchController::new(
+            AdaptiveBatchSettings::default(),
+            BatcherSettings::new(
+                Duration::from_secs(1),
+                NonZeroUsize::new(1_000_000).unwrap(),
+                NonZeroUsize::new(usize::MAX).unwrap(),
+            ),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn shrinks_slow_partitions() {
+        let controller = controller();
+        assert_eq!(controller.limits(&"slow"), (1_000_000, usize::MAX));
+
+        controller.record(&"slow", Duration::from_secs(5), true);
+        controller.record(&"fast", Duration::from_millis(100), true);
+
+        assert_eq!(controller.limits(&"slow"), (900_000, usize::MAX));
+        assert_eq!(controller.limits(&"fast"), (1_000_000, usize::MAX));
+    }
+
+    #[test]
+    fn shrinks_failing_partitions_down_to_min_ratio() {
+        let controller = controller();
+        for _ in 0..100 {
+            controller.record(&"failing", Duration::from_millis(100), false);
+        }
+        assert_eq!(controller.limits(&"failing"), (100_000, usize::MAX));
+    }
+
+    #[test]
+    fn grows_back_partitions() {
+        let controller = controller();
+        controller.record(&"partition", Duration::from_secs(2), true);
+        controller.record(&"partition", Duration::from_secs(2), true);
+        assert_eq!(controller.limits(&"partition"), (810_000, usize::MAX));
+
+        // The average latency has to go back under the target before batches grow.
+        controller.record(&"partition", Duration::from_millis(100), true);
+        assert_eq!(controller.limits(&"partition"), (729_000, usize::MAX));
+        controller.record(&"partition", Duration::from_millis(100), true);
+        assert_eq!(controller.limits(&"partition"), (779_000, usize::MAX));
+
+        for _ in 0..10 {
+            controller.record(&"partition", Duration::from_millis(100), true);
+        }
+        assert_eq!(controller.limits(&"partition"), (1_000_000, usize::MAX));
+        assert!(controller.inner.partitions.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn rejects_invalid_settings() {
+        let settings = AdaptiveBatchSettings {
+            decrease_ratio: 1.0,
+            ..Default::default()
+        };
+        assert_eq!(
+            settings.validate(),
+            Err(AdaptiveBatchError::InvalidRatio {
+                name: "decrease_ratio"
+            })
+        );
+
+        let settings = AdaptiveBatchSettings {
+            ewma_alpha: f64::NAN,
+            ..Default::default()
+        };
+        assert_eq!(
+            settings.validate(),
+            Err(AdaptiveBatchError::InvalidRatio { name: "ewma_alpha" })
+        );
+
+        for target_latency_secs in [0.0, f64::INFINITY, f64::NAN] {
+            let settings = AdaptiveBatchSettings {
+                target_latency_secs,
+                ..Default::default()
+            };
+            assert_eq!(
+                settings.validate(),
+                Err(AdaptiveBatchError::InvalidTargetLatency)
+            );
+        }
+    }
+}
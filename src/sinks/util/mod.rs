@@ -1,3 +1,4 @@
+pub mod adaptive_batching;
 pub mod adaptive_concurrency;
 pub mod batch;
 pub mod buffer;
@@ -17,6 +17,7 @@ pub use crate::sinks::util::service::{
     concurrency::{concurrency_is_none, Concurrency},
     health::{HealthConfig, HealthLogic, HealthService},
     map::Map,
+    rate_limit_pool::{PoolRateLimit, RateLimitPool, RateLimitPoolLayer},
 };
 use crate::{
     internal_events::OpenGauge,
@@ -34,14 +35,18 @@ use crate::{
 mod concurrency;
 mod health;
 mod map;
+pub mod rate_limit_pool;
 
-pub type Svc<S, L> = RateLimit<AdaptiveConcurrencyLimit<Retry<FixedRetryPolicy<L>, Timeout<S>>, L>>;
+pub type Svc<S, L> =
+    PoolRateLimit<RateLimit<AdaptiveConcurrencyLimit<Retry<FixedRetryPolicy<L>, Timeout<S>>, L>>>;
 pub type TowerBatchedSink<S, B, RL> = BatchSink<Svc<S, RL>, B>;
 pub type TowerPartitionSink<S, B, RL, K> = PartitionBatchSink<Svc<S, RL>, B, K>;
 
 // Distributed service types
-pub type DistributedService<S, RL, HL, K, Req> = RateLimit<
-    Retry<FixedRetryPolicy<RL>, Buffer<Balance<DiscoveryService<S, RL, HL, K>, Req>, Req>>,
+pub type DistributedService<S, RL, HL, K, Req> = PoolRateLimit<
+    RateLimit<
+        Retry<FixedRetryPolicy<RL>, Buffer<Balance<DiscoveryService<S, RL, HL, K>, Req>, Req>>,
+    >,
 >;
 pub type DiscoveryService<S, RL, HL, K> =
     BoxStream<'static, Result<Change<K, SingleDistributedService<S, RL, HL>>, crate::Error>>;
@@ -216,6 +221,7 @@ impl TowerRequestConfig {
                     .unwrap_or(RETRY_INITIAL_BACKOFF_SECONDS_DEFAULT),
             ),
             adaptive_concurrency: self.adaptive_concurrency,
+            rate_limit_pool: rate_limit_pool::current(),
         }
     }
 }
@@ -230,6 +236,8 @@ pub struct TowerRequestSettings {
     pub retry_max_duration_secs: Duration,
     pub retry_initial_backoff_secs: Duration,
     pub adaptive_concurrency: AdaptiveConcurrencySettings,
+    /// The pool of the sink, if it shares a rate limit with other sinks.
+    pub rate_limit_pool: Option<Arc<RateLimitPool>>,
 }
 
 impl TowerRequestSettings {
@@ -342,6 +350,7 @@ impl TowerRequestSettings {
 
         // Build sink service
         ServiceBuilder::new()
+            .layer(RateLimitPoolLayer::new(self.rate_limit_pool.clone()))
             .rate_limit(self.rate_limit_num, self.rate_limit_duration)
             .retry(policy)
             .layer(BufferLayer::new(max_concurrency))
@@ -370,6 +379,9 @@ where
     fn layer(&self, inner: S) -> Self::Service {
         let policy = self.settings.retry_policy(self.retry_logic.clone());
         ServiceBuilder::new()
+            .layer(RateLimitPoolLayer::new(
+                self.settings.rate_limit_pool.clone(),
+            ))
             .rate_limit(
                 self.settings.rate_limit_num,
                 self.settings.rate_limit_duration,
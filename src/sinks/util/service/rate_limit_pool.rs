@@ -0,0 +1,430 @@
+//! Rate limits shared by several sinks.
+//!
+//! Pools are defined in the `rate_limit_pools` global option, and sinks join them with their
+//! `rate_limit_pool` option. The request middleware of a sink built while its pool is in scope
+//! acquires a permit from the pool for every request, on top of the rate limit of the sink.
+//! Sinks without tower request settings build no such middleware, so their pool is rejected.
+
+use std::{
+    collections::{BTreeMap, HashMap, VecDeque},
+    future::Future,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc, Mutex, Weak,
+    },
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use futures::ready;
+use indexmap::IndexMap;
+use once_cell::sync::Lazy;
+use tokio::{
+    sync::oneshot,
+    time::{sleep_until, Instant},
+};
+use tower::{Layer, Service};
+use vector_core::config::RateLimitPoolConfig;
+
+static POOLS: Lazy<Mutex<HashMap<String, Arc<RateLimitPool>>>> = Lazy::new(Default::default);
+
+tokio::task_local! {
+    static CURRENT_POOL: Option<ScopedPool>;
+}
+
+/// The pool of the sink being built, along with whether its request middleware took it.
+struct ScopedPool {
+    pool: Arc<RateLimitPool>,
+    taken: AtomicBool,
+}
+
+/// Registers the pools of the `rate_limit_pools` global option.
+///
+/// Pools whose configuration didn't change are kept, so that rebuilt sinks keep sharing them with
+/// the sinks that weren't rebuilt.
+pub fn configure(pools: &BTreeMap<String, RateLimitPoolConfig>) {
+    let mut registered = POOLS.lock().unwrap();
+    registered.retain(|name, pool| pools.get(name) == Some(&pool.config));
+    for (name, config) in pools {
+        registered
+            .entry(name.clone())
+            .or_insert_with(|| Arc::new(RateLimitPool::new(*config)));
+    }
+}
+
+/// Returns the registered pool named `name`.
+pub fn get(name: &str) -> Option<Arc<RateLimitPool>> {
+    POOLS.lock().unwrap().get(name).cloned()
+}
+
+/// Runs `future`, the build of a sink, with `pool` as the pool of the request middleware built
+/// by it.
+///
+/// Returns whether the pool was taken by the request middleware, which is never the case for sinks
+/// without tower request settings.
+pub async fn scope<F: Future>(pool: Option<Arc<RateLimitPool>>, future: F) -> (F::Output, bool) {
+    match pool {
+        Some(pool) => {
+            let scoped = ScopedPool {
+                pool,
+                taken: AtomicBool::new(false),
+            };
+            CURRENT_POOL
+                .scope(Some(scoped), async {
+                    let output = future.await;
+                    let taken = CURRENT_POOL.with(|scoped| {
+                        scoped
+                            .as_ref()
+                            .map_or(false, |scoped| scoped.taken.load(Ordering::Relaxed))
+                    });
+                    (output, taken)
+                })
+                .await
+        }
+        None => (CURRENT_POOL.scope(None, future).await, false),
+    }
+}
+
+/// Returns the pool of the sink being built, if any.
+pub fn current() -> Option<Arc<RateLimitPool>> {
+    CURRENT_POOL
+        .try_with(|scoped| {
+            scoped.as_ref().map(|scoped| {
+                scoped.taken.store(true, Ordering::Relaxed);
+                Arc::clone(&scoped.pool)
+            })
+        })
+        .ok()
+        .flatten()
+}
+
+/// A rate limit shared by several sinks.
+///
+/// Within every time window, up to `rate_limit_num` permits are granted. Once they are used up,
+/// the requests of the members are queued, and the permits of the next windows are granted to the
+/// members in turns, so that every member gets a fair share of the limit.
+#[derive(Debug)]
+pub struct RateLimitPool {
+    config: RateLimitPoolConfig,
+    duration: Duration,
+    next_member: AtomicU64,
+    state: Mutex<PoolState>,
+}
+
+#[derive(Debug)]
+struct PoolState {
+    window_start: Instant,
+    used: u64,
+    /// The permits waited on, by member, in the order the members started waiting.
+    waiting: IndexMap<u64, VecDeque<oneshot::Sender<()>>>,
+    /// The index of the member in `waiting` to grant the next permit to.
+    next: usize,
+    dispatching: bool,
+}
+
+impl RateLimitPool {
+    pub fn new(config: RateLimitPoolConfig) -> Self {
+        Self {
+            config,
+            duration: Duration::from_secs(config.rate_limit_duration_secs),
+            next_member: AtomicU64::new(0),
+            state: Mutex::new(PoolState {
+                window_start: Instant::now(),
+                used: 0,
+                waiting: IndexMap::new(),
+                next: 0,
+                dispatching: false,
+            }),
+        }
+    }
+
+    fn member(self: &Arc<Self>) -> PoolMember {
+        PoolMember {
+            pool: Arc::clone(self),
+            id: self.next_member.fetch_add(1, Ordering::Relaxed),
+        }
+    }
+
+    /// Takes a permit for `member` if one is available, or returns the receiver the permit is
+    /// granted to once it is its turn.
+    fn try_acquire(self: &Arc<Self>, member: u64) -> Option<oneshot::Receiver<()>> {
+        let mut state = self.state.lock().unwrap();
+        self.refresh(&mut state);
+        if state.waiting.is_empty() && state.used < self.config.rate_limit_num {
+            state.used += 1;
+            return None;
+        }
+
+        let (tx, rx) = oneshot::channel();
+        state.waiting.entry(member).or_default().push_back(tx);
+        if !state.dispatching {
+            state.dispatching = true;
+            tokio::spawn(dispatch(Arc::downgrade(self)));
+        }
+        Some(rx)
+    }
+
+    /// Starts a new time window if the current one is over, and grants its permits to the waiting
+    /// members.
+    fn refresh(&self, state: &mut PoolState) {
+        let now = Instant::now();
+        if now < state.window_start + self.duration {
+            return;
+        }
+        state.window_start = now;
+        state.used = 0;
+
+        while state.used < self.config.rate_limit_num && !state.waiting.is_empty() {
+            let index = state.next % state.waiting.len();
+            let (_, queue) = state
+                .waiting
+                .get_index_mut(index)
+                .expect("index is in bounds");
+            // The receiver is gone if the service waiting on it was dropped.
+            if queue.pop_front().map_or(false, |tx| tx.send(()).is_ok()) {
+                state.used += 1;
+            }
+            if queue.is_empty() {
+                // The next member moves to this index.
+                state.waiting.shift_remove_index(index);
+                state.next = index;
+            } else {
+                state.next = index + 1;
+            }
+        }
+    }
+
+    fn leave(&self, member: u64) {
+        self.state.lock().unwrap().waiting.shift_remove(&member);
+    }
+}
+
+/// Grants the permits of the following time windows until no member is waiting anymore.
+async fn dispatch(pool: Weak<RateLimitPool>) {
+    loop {
+        let window_end = match pool.upgrade() {
+            Some(pool) => pool.state.lock().unwrap().window_start + pool.duration,
+            None => return,
+        };
+        sleep_until(window_end).await;
+
+        let pool = match pool.upgrade() {
+            Some(pool) => pool,
+            None => return,
+        };
+        let mut state = pool.state.lock().unwrap();
+        pool.refresh(&mut state);
+        if state.waiting.is_empty() {
+            state.dispatching = false;
+            return;
+        }
+    }
+}
+
+/// A sink sharing the limit of a pool.
+#[derive(Debug)]
+struct PoolMember {
+    pool: Arc<RateLimitPool>,
+    id: u64,
+}
+
+impl Drop for PoolMember {
+    fn drop(&mut self) {
+        self.pool.leave(self.id);
+    }
+}
+
+/// Applies the limit of a pool to the requests of a service.
+#[derive(Clone, Debug)]
+pub struct RateLimitPoolLayer {
+    pool: Option<Arc<RateLimitPool>>,
+}
+
+impl RateLimitPoolLayer {
+    pub const fn new(pool: Option<Arc<RateLimitPool>>) -> Self {
+        Self { pool }
+    }
+}
+
+impl<S> Layer<S> for RateLimitPoolLayer {
+    type Service = PoolRateLimit<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        PoolRateLimit {
+            inner,
+            member: self.pool.as_ref().map(|pool| Arc::new(pool.member())),
+            permit: Permit::None,
+        }
+    }
+}
+
+#[derive(Debug)]
+enum Permit {
+    None,
+    Waiting(oneshot::Receiver<()>),
+    Acquired,
+}
+
+/// A service whose requests each take a permit of a pool, if it is a member of one.
+#[derive(Debug)]
+pub struct PoolRateLimit<S> {
+    inner: S,
+    member: Option<Arc<PoolMember>>,
+    permit: Permit,
+}
+
+impl<S: Clone> Clone for PoolRateLimit<S> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            member: self.member.clone(),
+            permit: Permit::None,
+        }
+    }
+}
+
+impl<S, Request> Service<Request> for PoolRateLimit<S>
+where
+    S: Service<Request>,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = S::Future;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        if let Some(member) = &self.member {
+            loop {
+                match &mut self.permit {
+                    Permit::None => {
+                        self.permit = match member.pool.try_acquire(member.id) {
+                            Some(rx) => Permit::Waiting(rx),
+                            None => Permit::Acquired,
+                        };
+                    }
+                    Permit::Waiting(rx) => {
+                        // The sender is only dropped without granting the permit when the pool
+                        // is gone, in which case there is no limit to respect anymore.
+                        let _ = ready!(Pin::new(rx).poll(cx));
+                        self.permit = Permit::Acquired;
+                    }
+                    Permit::Acquired => break,
+                }
+            }
+        }
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request) -> Self::Future {
+        if self.member.is_some() {
+            assert!(
+                matches!(self.permit, Permit::Acquired),
+                "poll_ready must be called before call"
+            );
+            self.permit = Permit::None;
+        }
+        self.inner.call(request)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::future;
+    use tower::ServiceExt;
+
+    use super::*;
+
+    fn pool(rate_limit_num: u64) -> Arc<RateLimitPool> {
+        Arc::new(RateLimitPool::new(RateLimitPoolConfig {
+            rate_limit_num,
+            rate_limit_duration_secs: 1,
+        }))
+    }
+
+    fn service(pool: &Arc<RateLimitPool>) -> PoolRateLimit<impl Service<(), Error = ()> + Clone> {
+        RateLimitPoolLayer::new(Some(Arc::clone(pool)))
+            .layer(tower::service_fn(|()| future::ready(Ok(()))))
+    }
+
+    #[tokio::test]
+    async fn limits_members_collectively() {
+        tokio::time::pause();
+        let pool = pool(2);
+        let mut first = service(&pool);
+        let mut second = service(&pool);
+
+        first.ready().await.unwrap().call(()).await.unwrap();
+        second.ready().await.unwrap().call(()).await.unwrap();
+
+        let start = Instant::now();
+        first.ready().await.unwrap().call(()).await.unwrap();
+        assert!(start.elapsed() >= Duration::from_secs(1));
+    }
+
+    #[tokio::test]
+    async fn shares_permits_fairly() {
+        tokio::time::pause();
+        let pool = pool(1);
+        // Use up the permit of the first window.
+        let mut busy = service(&pool);
+        busy.ready().await.unwrap().call(()).await.unwrap();
+
+        // The busy member queues several requests before the quiet one queues its request.
+        let mut waiting = (0..3).map(|_| busy.clone()).collect::<Vec<_>>();
+        for service in &mut waiting {
+            assert!(futures::poll!(service.ready()).is_pending());
+        }
+        let mut quiet = service(&pool);
+        assert!(futures::poll!(quiet.ready()).is_pending());
+
+        // The quiet member gets the permit of the third window, even though the busy member was
+        // waiting on more permits before it.
+        tokio::time::advance(Duration::from_secs(1)).await;
+        tokio::task::yield_now().await;
+        assert!(futures::poll!(waiting[0].ready()).is_ready());
+        assert!(futures::poll!(quiet.ready()).is_pending());
+        tokio::time::advance(Duration::from_secs(1)).await;
+        tokio::task::yield_now().await;
+        assert!(futures::poll!(quiet.ready()).is_ready());
+        assert!(futures::poll!(waiting[1].ready()).is_pending());
+    }
+
+    #[tokio::test]
+    async fn ignores_services_without_pool() {
+        let mut service = RateLimitPoolLayer::new(None)
+            .layer(tower::service_fn(|()| future::ready(Ok::<_, ()>(()))));
+        for _ in 0..10 {
+            service.ready().await.unwrap().call(()).await.unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn reports_taken_pools() {
+        let (_, taken) = scope(Some(pool(1)), async {}).await;
+        assert!(!taken);
+
+        let (current, taken) = scope(Some(pool(1)), async { current() }).await;
+        assert!(current.is_some());
+        assert!(taken);
+    }
+
+    #[test]
+    fn keeps_unchanged_pools() {
+        let config = RateLimitPoolConfig {
+            rate_limit_num: 10,
+            rate_limit_duration_secs: 1,
+        };
+        configure(&BTreeMap::from([("kept".to_owned(), config)]));
+        let kept = get("kept").unwrap();
+
+        configure(&BTreeMap::from([
+            ("kept".to_owned(), config),
+            ("added".to_owned(), config),
+        ]));
+        assert!(Arc::ptr_eq(&kept, &get("kept").unwrap()));
+        assert!(get("added").is_some());
+
+        configure(&BTreeMap::new());
+        assert!(get("kept").is_none());
+    }
+}
@@ -102,11 +102,7 @@ impl TcpSinkConfig {
         transformer: Transformer,
         encoder: impl Encoder<Event, Error = codecs::encoding::Error> + Clone + Send + Sync + 'static,
     ) -> crate::Result<(VectorSink, Healthcheck)> {
-        let uri = self.address.parse::<http::Uri>()?;
-        let host = uri.host().ok_or(SinkBuildError::MissingHost)?.to_string();
-        let port = uri.port_u16().ok_or(SinkBuildError::MissingPort)?;
-        let tls = MaybeTlsSettings::from_config(&self.tls, false)?;
-        let connector = TcpConnector::new(host, port, self.keepalive, tls, self.send_buffer_bytes);
+        let connector = self.build_connector()?;
         let sink = TcpSink::new(connector.clone(), transformer, encoder);
 
         Ok((
@@ -114,10 +110,25 @@ impl TcpSinkConfig {
             Box::pin(async move { connector.healthcheck().await }),
         ))
     }
+
+    /// Builds a connector to the address, for sinks implementing their own protocol over TCP.
+    pub(crate) fn build_connector(&self) -> crate::Result<TcpConnector> {
+        let uri = self.address.parse::<http::Uri>()?;
+        let host = uri.host().ok_or(SinkBuildError::MissingHost)?.to_string();
+        let port = uri.port_u16().ok_or(SinkBuildError::MissingPort)?;
+        let tls = MaybeTlsSettings::from_config(&self.tls, false)?;
+        Ok(TcpConnector::new(
+            host,
+            port,
+            self.keepalive,
+            tls,
+            self.send_buffer_bytes,
+        ))
+    }
 }
 
 #[derive(Clone)]
-struct TcpConnector {
+pub(crate) struct TcpConnector {
     host: String,
     port: u16,
     keepalive: Option<TcpKeepaliveConfig>,
@@ -147,7 +158,7 @@ impl TcpConnector {
         Self::new(host, port, None, None.into(), None)
     }
 
-    const fn fresh_backoff() -> ExponentialBackoff {
+    pub(crate) const fn fresh_backoff() -> ExponentialBackoff {
         // TODO: make configurable
         ExponentialBackoff::from_millis(2)
             .factor(250)
@@ -184,7 +195,7 @@ impl TcpConnector {
             })
     }
 
-    async fn connect_backoff(&self) -> MaybeTlsStream<TcpStream> {
+    pub(crate) async fn connect_backoff(&self) -> MaybeTlsStream<TcpStream> {
         let mut backoff = Self::fresh_backoff();
         loop {
             match self.connect().await {
@@ -202,7 +213,7 @@ impl TcpConnector {
         }
     }
 
-    async fn healthcheck(&self) -> crate::Result<()> {
+    pub(crate) async fn healthcheck(&self) -> crate::Result<()> {
         self.connect().await.map(|_| ()).map_err(Into::into)
     }
 }
@@ -1,19 +1,22 @@
-use std::{path::PathBuf, pin::Pin, time::Duration};
+use std::{future::Future, path::PathBuf, pin::Pin, time::Duration};
 
 use async_trait::async_trait;
 use bytes::{Bytes, BytesMut};
-use futures::{stream::BoxStream, SinkExt, StreamExt};
+use futures::{future, stream::BoxStream, SinkExt, StreamExt};
 use snafu::{ResultExt, Snafu};
 use tokio::{net::UnixStream, time::sleep};
 use tokio_util::codec::Encoder;
+use vector_common::internal_event::{
+    ByteSize, BytesSent, InternalEventHandle, Protocol, Registered,
+};
 use vector_config::configurable_component;
 use vector_core::ByteSizeOf;
 
 use crate::{
     codecs::Transformer,
-    event::{Event, Finalizable},
+    event::{Event, EventStatus, Finalizable},
     internal_events::{
-        ConnectionOpen, OpenGauge, SocketMode, UnixSocketConnectionError,
+        ConnectionOpen, OpenGauge, SocketEventsSent, SocketMode, UnixSocketConnectionError,
         UnixSocketConnectionEstablished, UnixSocketError,
     },
     sink::VecSinkExt,
@@ -25,6 +28,7 @@ use crate::{
         },
         Healthcheck, VectorSink,
     },
+    unix::{socket_path, UnixSeqpacket},
 };
 
 #[derive(Debug, Snafu)]
@@ -39,7 +43,8 @@ pub enum UnixError {
 pub struct UnixSinkConfig {
     /// The Unix socket path.
     ///
-    /// This should be an absolute path.
+    /// This should be an absolute path. On Linux, a path starting with `@` designates an address
+    /// in the abstract namespace, such as `@vector`.
     pub path: PathBuf,
 }
 
@@ -60,6 +65,26 @@ impl UnixSinkConfig {
             Box::pin(async move { connector.healthcheck().await }),
         ))
     }
+
+    /// Builds a sink sending each encoded event as a single packet over a `SOCK_SEQPACKET`
+    /// socket, so `encoder` shouldn't frame the events.
+    pub fn build_seqpacket(
+        &self,
+        transformer: Transformer,
+        encoder: impl Encoder<Event, Error = codecs::encoding::Error> + Clone + Send + Sync + 'static,
+    ) -> crate::Result<(VectorSink, Healthcheck)> {
+        let connector = UnixConnector::new(self.path.clone());
+        let sink = UnixSeqpacketSink::new(connector.clone(), transformer, encoder);
+        Ok((
+            VectorSink::from_event_streamsink(sink),
+            Box::pin(async move {
+                connector
+                    .connect_seqpacket()
+                    .map(|_| ())
+                    .map_err(Into::into)
+            }),
+        ))
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -80,16 +105,35 @@ impl UnixConnector {
     }
 
     async fn connect(&self) -> Result<UnixStream, UnixError> {
-        UnixStream::connect(&self.path).await.context(ConnectSnafu)
+        UnixStream::connect(socket_path(&self.path))
+            .await
+            .context(ConnectSnafu)
+    }
+
+    fn connect_seqpacket(&self) -> Result<UnixSeqpacket, UnixError> {
+        UnixSeqpacket::connect(&socket_path(&self.path)).context(ConnectSnafu)
     }
 
     async fn connect_backoff(&self) -> UnixStream {
+        self.retry_connect(|| self.connect()).await
+    }
+
+    async fn connect_seqpacket_backoff(&self) -> UnixSeqpacket {
+        self.retry_connect(|| future::ready(self.connect_seqpacket()))
+            .await
+    }
+
+    async fn retry_connect<T, F, Fut>(&self, connect: F) -> T
+    where
+        F: Fn() -> Fut,
+        Fut: Future<Output = Result<T, UnixError>>,
+    {
         let mut backoff = Self::fresh_backoff();
         loop {
-            match self.connect().await {
-                Ok(stream) => {
+            match connect().await {
+                Ok(socket) => {
                     emit!(UnixSocketConnectionEstablished { path: &self.path });
-                    return stream;
+                    return socket;
                 }
                 Err(error) => {
                     emit!(UnixSocketConnectionError {
@@ -185,6 +229,80 @@ where
     }
 }
 
+struct UnixSeqpacketSink<E>
+where
+    E: Encoder<Event, Error = codecs::encoding::Error> + Clone + Send + Sync,
+{
+    connector: UnixConnector,
+    transformer: Transformer,
+    encoder: E,
+    bytes_sent: Registered<BytesSent>,
+}
+
+impl<E> UnixSeqpacketSink<E>
+where
+    E: Encoder<Event, Error = codecs::encoding::Error> + Clone + Send + Sync,
+{
+    fn new(connector: UnixConnector, transformer: Transformer, encoder: E) -> Self {
+        Self {
+            connector,
+            transformer,
+            encoder,
+            bytes_sent: register!(BytesSent::from(Protocol::UNIX)),
+        }
+    }
+}
+
+#[async_trait]
+impl<E> StreamSink<Event> for UnixSeqpacketSink<E>
+where
+    E: Encoder<Event, Error = codecs::encoding::Error> + Clone + Send + Sync,
+{
+    async fn run(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        let mut input = input.peekable();
+
+        let mut encoder = self.encoder.clone();
+        while Pin::new(&mut input).peek().await.is_some() {
+            let socket = self.connector.connect_seqpacket_backoff().await;
+            let _open_token = OpenGauge::new().open(|count| emit!(ConnectionOpen { count }));
+            while let Some(mut event) = input.next().await {
+                let byte_size = event.size_of();
+
+                self.transformer.transform(&mut event);
+
+                let finalizers = event.take_finalizers();
+                let mut bytes = BytesMut::new();
+                if encoder.encode(event, &mut bytes).is_err() {
+                    continue;
+                }
+
+                match socket.send(&bytes).await {
+                    Ok(_) => {
+                        emit!(SocketEventsSent {
+                            mode: SocketMode::Unix,
+                            count: 1,
+                            byte_size,
+                        });
+
+                        self.bytes_sent.emit(ByteSize(bytes.len()));
+                        finalizers.update_status(EventStatus::Delivered);
+                    }
+                    Err(error) => {
+                        emit!(UnixSocketError {
+                            error: &error,
+                            path: &self.connector.path
+                        });
+                        finalizers.update_status(EventStatus::Errored);
+                        break;
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use codecs::{encoding::Framer, NewlineDelimitedEncoder, TextSerializer};
@@ -256,4 +374,33 @@ mod tests {
         // Receive the data sent by the Sink to the receiver
         assert_eq!(input_lines, receiver.await);
     }
+
+    #[tokio::test]
+    async fn unix_seqpacket_sink_sends_a_packet_per_event() {
+        let out_path = temp_uds_path("unix_seqpacket_test");
+        let listener = crate::unix::UnixSeqpacketListener::bind(&out_path).unwrap();
+
+        let config = UnixSinkConfig::new(out_path);
+        let (sink, _healthcheck) = config
+            .build_seqpacket(
+                Default::default(),
+                Encoder::<()>::new(TextSerializer::new().into()),
+            )
+            .unwrap();
+
+        let (input_lines, events) = random_lines_with_stream(100, 10, None);
+        sink.run(events).await.unwrap();
+
+        let socket = listener.accept().await.unwrap();
+        let mut buf = [0; 256];
+        let mut output_lines = Vec::new();
+        loop {
+            let len = socket.recv(&mut buf).await.unwrap();
+            if len == 0 {
+                break;
+            }
+            output_lines.push(String::from_utf8(buf[..len].to_vec()).unwrap());
+        }
+        assert_eq!(input_lines, output_lines);
+    }
 }
@@ -1,9 +1,10 @@
-use http::Uri;
+use http::{HeaderValue, Uri};
 use hyper::client::HttpConnector;
 use hyper_openssl::HttpsConnector;
 use hyper_proxy::ProxyConnector;
 use tonic::body::BoxBody;
 use tower::ServiceBuilder;
+use vector_common::sensitive_string::SensitiveString;
 use vector_config::configurable_component;
 
 use super::{
@@ -48,6 +49,13 @@ pub struct VectorConfig {
     #[serde(default)]
     compression: bool,
 
+    /// The token to authenticate with to the downstream Vector instance.
+    ///
+    /// It's sent in the `authorization` metadata of requests, as `Bearer <token>`, and must be one
+    /// of the `auth.tokens` of the downstream `vector` source.
+    #[serde(default)]
+    auth_token: Option<SensitiveString>,
+
     #[configurable(derived)]
     #[serde(default)]
     pub batch: BatchConfig<RealtimeEventBasedDefaultBatchSettings>,
@@ -80,6 +88,7 @@ fn default_config(address: &str) -> VectorConfig {
         version: None,
         address: address.to_owned(),
         compression: false,
+        auth_token: None,
         batch: BatchConfig::default(),
         request: TowerRequestConfig::default(),
         tls: None,
@@ -94,6 +103,16 @@ impl SinkConfig for VectorConfig {
         let uri = with_default_scheme(&self.address, tls.is_tls())?;
 
         let client = new_client(&tls, cx.proxy())?;
+        let authorization = self
+            .auth_token
+            .as_ref()
+            .map(|token| {
+                HeaderValue::from_str(&format!("Bearer {}", token.inner())).map(|mut value| {
+                    value.set_sensitive(true);
+                    value
+                })
+            })
+            .transpose()?;
 
         let healthcheck_uri = cx
             .healthcheck
@@ -101,9 +120,14 @@ impl SinkConfig for VectorConfig {
             .clone()
             .map(|uri| uri.uri)
             .unwrap_or_else(|| uri.clone());
-        let healthcheck_client = VectorService::new(client.clone(), healthcheck_uri, false);
+        let healthcheck_client = VectorService::new(
+            client.clone(),
+            healthcheck_uri,
+            false,
+            authorization.clone(),
+        );
         let healthcheck = healthcheck(healthcheck_client, cx.healthcheck);
-        let service = VectorService::new(client, uri, self.compression);
+        let service = VectorService::new(client, uri, self.compression, authorization);
         let request_settings = self.request.unwrap_with(&TowerRequestConfig::default());
         let batch_settings = self.batch.into_batcher_settings()?;
 
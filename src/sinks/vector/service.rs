@@ -1,7 +1,7 @@
 use std::task::{Context, Poll};
 
 use futures::{future::BoxFuture, TryFutureExt};
-use http::Uri;
+use http::{HeaderValue, Uri};
 use hyper::client::HttpConnector;
 use hyper_openssl::HttpsConnector;
 use hyper_proxy::ProxyConnector;
@@ -61,11 +61,13 @@ impl VectorService {
         hyper_client: hyper::Client<ProxyConnector<HttpsConnector<HttpConnector>>, BoxBody>,
         uri: Uri,
         compression: bool,
+        authorization: Option<HeaderValue>,
     ) -> Self {
         let (protocol, endpoint) = uri::protocol_endpoint(uri.clone());
         let mut proto_client = proto_vector::Client::new(HyperSvc {
             uri,
             client: hyper_client,
+            authorization,
         });
 
         if compression {
@@ -129,6 +131,7 @@ impl tower::Service<VectorRequest> for VectorService {
 pub struct HyperSvc {
     uri: Uri,
     client: hyper::Client<ProxyConnector<HttpsConnector<HttpConnector>>, BoxBody>,
+    authorization: Option<HeaderValue>,
 }
 
 impl tower::Service<hyper::Request<BoxBody>> for HyperSvc {
@@ -149,6 +152,10 @@ impl tower::Service<hyper::Request<BoxBody>> for HyperSvc {
             .unwrap();
 
         *req.uri_mut() = uri;
+        if let Some(authorization) = &self.authorization {
+            req.headers_mut()
+                .insert(http::header::AUTHORIZATION, authorization.clone());
+        }
 
         Box::pin(self.client.request(req))
     }
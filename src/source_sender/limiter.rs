@@ -0,0 +1,78 @@
+use std::{num::NonZeroU32, sync::Arc, time::Instant};
+
+use governor::{DefaultDirectRateLimiter, Quota, RateLimiter};
+
+use vector_core::event::{EstimatedJsonEncodedSizeOf, EventArray, EventContainer};
+
+use crate::{config::IngestionLimits, internal_events::IngestionThrottled};
+
+/// Token buckets limiting the rate of events sent by a source, shared by all of its outputs.
+#[derive(Clone)]
+pub(super) struct IngestionLimiter {
+    events: Option<Arc<TokenBucket>>,
+    bytes: Option<Arc<TokenBucket>>,
+}
+
+impl IngestionLimiter {
+    /// Creates the limiter of the given limits, or `None` if there aren't any.
+    pub(super) fn new(limits: IngestionLimits) -> Option<Self> {
+        let events = limits.events_per_second.map(TokenBucket::new).map(Arc::new);
+        let bytes = limits.bytes_per_second.map(TokenBucket::new).map(Arc::new);
+        (events.is_some() || bytes.is_some()).then_some(Self { events, bytes })
+    }
+
+    /// Waits until `events` fit in the limits.
+    ///
+    /// Returns the estimated JSON size of the events if it was measured for the bytes limit.
+    pub(super) async fn acquire(&self, events: &EventArray) -> Option<usize> {
+        let start = Instant::now();
+        let mut throttled = false;
+        if let Some(bucket) = &self.events {
+            throttled |= bucket.acquire(events.len()).await;
+        }
+        let byte_size = self
+            .bytes
+            .as_ref()
+            .map(|_| events.estimated_json_encoded_size_of());
+        if let (Some(bucket), Some(byte_size)) = (&self.bytes, byte_size) {
+            throttled |= bucket.acquire(byte_size).await;
+        }
+        if throttled {
+            emit!(IngestionThrottled {
+                delay: start.elapsed(),
+            });
+        }
+        byte_size
+    }
+}
+
+struct TokenBucket {
+    limiter: DefaultDirectRateLimiter,
+    burst: NonZeroU32,
+}
+
+impl TokenBucket {
+    fn new(per_second: NonZeroU32) -> Self {
+        Self {
+            limiter: RateLimiter::direct(Quota::per_second(per_second)),
+            burst: per_second,
+        }
+    }
+
+    /// Takes `amount` tokens, waiting for them to be available if needed. Amounts larger than the
+    /// bucket are taken in several bursts.
+    ///
+    /// Returns whether it had to wait.
+    async fn acquire(&self, mut amount: usize) -> bool {
+        let mut throttled = false;
+        while let Some(n) = NonZeroU32::new(amount.min(self.burst.get() as usize) as u32) {
+            if self.limiter.check_n(n).is_err() {
+                throttled = true;
+                // `n` is at most the burst size, so there is always enough capacity.
+                let _ = self.limiter.until_n_ready(n).await;
+            }
+            amount -= n.get() as usize;
+        }
+        throttled
+    }
+}
@@ -1,6 +1,7 @@
-use std::{collections::HashMap, fmt};
+use std::{collections::HashMap, fmt, num::NonZeroUsize};
 
 use chrono::Utc;
+use codecs::decoding::DECODING_ERRORS_OUTPUT;
 use futures::{Stream, StreamExt};
 use metrics::{register_histogram, Histogram};
 use value::Value;
@@ -9,15 +10,23 @@ use vector_buffers::topology::channel::{self, LimitedReceiver, LimitedSender};
 use vector_core::event::{into_event_stream, EventStatus};
 use vector_core::{
     config::{log_schema, Output},
-    event::{array, Event, EventArray, EventContainer, EventRef},
-    internal_event::{EventsSent, DEFAULT_OUTPUT},
+    event::{array, EstimatedJsonEncodedSizeOf, Event, EventArray, EventContainer, EventRef},
+    internal_event::{EventsReceivedJsonSize, EventsSent, DEFAULT_OUTPUT},
     ByteSizeOf,
 };
 
 mod errors;
+mod limiter;
 
 pub use errors::{ClosedError, StreamSendError};
 
+use self::limiter::IngestionLimiter;
+use crate::{
+    codecs::has_decoding_error,
+    config::{IngestionLimits, OversizedEventPolicy},
+    internal_events::{OversizedEventDropped, OversizedEventRouted, OversizedEventTruncated},
+};
+
 pub(crate) const CHUNK_SIZE: usize = 1000;
 
 #[cfg(test)]
@@ -30,6 +39,9 @@ pub struct Builder {
     inner: Option<Inner>,
     named_inners: HashMap<String, Inner>,
     lag_time: Option<Histogram>,
+    routes_decoding_errors: bool,
+    event_size_limit: Option<EventSizeLimit>,
+    ingestion_limiter: Option<IngestionLimiter>,
 }
 
 impl Builder {
@@ -41,6 +53,49 @@ impl Builder {
             inner: self.inner,
             named_inners: self.named_inners,
             lag_time: self.lag_time,
+            routes_decoding_errors: self.routes_decoding_errors,
+            event_size_limit: self.event_size_limit,
+            ingestion_limiter: self.ingestion_limiter,
+        }
+    }
+
+    /// Routes the events holding payloads that couldn't be decoded to the decoding errors output,
+    /// which must be added to the builder, as set by the `decoding.on_error` option of the source.
+    #[allow(clippy::missing_const_for_fn)]
+    pub fn with_decoding_errors_routed(self, routed: bool) -> Self {
+        Self {
+            routes_decoding_errors: routed,
+            ..self
+        }
+    }
+
+    /// Limits the size of the events sent, handling the events exceeding `max_bytes` as set by
+    /// `policy`.
+    ///
+    /// Events routed by the policy are sent to the decoding errors output, which must be added to
+    /// the builder.
+    #[allow(clippy::missing_const_for_fn)]
+    pub fn with_event_size_limit(
+        self,
+        max_bytes: Option<NonZeroUsize>,
+        policy: OversizedEventPolicy,
+    ) -> Self {
+        Self {
+            event_size_limit: max_bytes.map(|max_bytes| EventSizeLimit {
+                max_bytes: max_bytes.get(),
+                policy,
+            }),
+            ..self
+        }
+    }
+
+    /// Limits the rate of the events sent, holding back the source until they fit in `limits`.
+    ///
+    /// The limits are shared by all of the outputs added afterwards.
+    pub fn with_ingestion_limits(self, limits: IngestionLimits) -> Self {
+        Self {
+            ingestion_limiter: IngestionLimiter::new(limits),
+            ..self
         }
     }
 
@@ -51,13 +106,18 @@ impl Builder {
                     self.buf_size,
                     DEFAULT_OUTPUT.to_owned(),
                     self.lag_time.clone(),
+                    self.ingestion_limiter.clone(),
                 );
                 self.inner = Some(inner);
                 rx
             }
             Some(name) => {
-                let (inner, rx) =
-                    Inner::new_with_buffer(self.buf_size, name.clone(), self.lag_time.clone());
+                let (inner, rx) = Inner::new_with_buffer(
+                    self.buf_size,
+                    name.clone(),
+                    self.lag_time.clone(),
+                    self.ingestion_limiter.clone(),
+                );
                 self.named_inners.insert(name, inner);
                 rx
             }
@@ -70,6 +130,8 @@ impl Builder {
         SourceSender {
             inner: self.inner,
             named_inners: self.named_inners,
+            routes_decoding_errors: self.routes_decoding_errors,
+            event_size_limit: self.event_size_limit,
         }
     }
 }
@@ -78,6 +140,15 @@ impl Builder {
 pub struct SourceSender {
     inner: Option<Inner>,
     named_inners: HashMap<String, Inner>,
+    routes_decoding_errors: bool,
+    event_size_limit: Option<EventSizeLimit>,
+}
+
+/// The maximum size of the events sent by a source, and how the events exceeding it are handled.
+#[derive(Clone, Copy, Debug)]
+struct EventSizeLimit {
+    max_bytes: usize,
+    policy: OversizedEventPolicy,
 }
 
 impl SourceSender {
@@ -87,16 +158,21 @@ impl SourceSender {
             inner: None,
             named_inners: Default::default(),
             lag_time: Some(register_histogram!(LAG_TIME_NAME)),
+            routes_decoding_errors: false,
+            event_size_limit: None,
+            ingestion_limiter: None,
         }
     }
 
     pub fn new_with_buffer(n: usize) -> (Self, LimitedReceiver<EventArray>) {
         let lag_time = Some(register_histogram!(LAG_TIME_NAME));
-        let (inner, rx) = Inner::new_with_buffer(n, DEFAULT_OUTPUT.to_owned(), lag_time);
+        let (inner, rx) = Inner::new_with_buffer(n, DEFAULT_OUTPUT.to_owned(), lag_time, None);
         (
             Self {
                 inner: Some(inner),
                 named_inners: Default::default(),
+                routes_decoding_errors: false,
+                event_size_limit: None,
             },
             rx,
         )
@@ -160,7 +236,7 @@ impl SourceSender {
     ) -> impl Stream<Item = EventArray> + Unpin {
         // The lag_time parameter here will need to be filled in if this function is ever used for
         // non-test situations.
-        let (inner, recv) = Inner::new_with_buffer(100, name.clone(), None);
+        let (inner, recv) = Inner::new_with_buffer(100, name.clone(), None, None);
         let recv = recv.into_stream().map(move |mut events| {
             events.iter_events_mut().for_each(|mut event| {
                 let metadata = event.metadata_mut();
@@ -174,6 +250,10 @@ impl SourceSender {
     }
 
     pub async fn send_event(&mut self, event: impl Into<EventArray>) -> Result<(), ClosedError> {
+        if self.checks_events() {
+            return self.send_batch(event.into().into_events()).await;
+        }
+
         self.inner
             .as_mut()
             .expect("no default output")
@@ -186,6 +266,14 @@ impl SourceSender {
         S: Stream<Item = E> + Unpin,
         E: Into<Event> + ByteSizeOf,
     {
+        if self.checks_events() {
+            let mut stream = events.ready_chunks(CHUNK_SIZE);
+            while let Some(events) = stream.next().await {
+                self.send_batch(events).await?;
+            }
+            return Ok(());
+        }
+
         self.inner
             .as_mut()
             .expect("no default output")
@@ -198,6 +286,16 @@ impl SourceSender {
         E: Into<Event> + ByteSizeOf,
         I: IntoIterator<Item = E>,
     {
+        if self.checks_events() {
+            let events = self.send_errors(events).await?;
+            return self
+                .inner
+                .as_mut()
+                .expect("no default output")
+                .send_batch(events)
+                .await;
+        }
+
         self.inner
             .as_mut()
             .expect("no default output")
@@ -210,12 +308,120 @@ impl SourceSender {
         E: Into<Event> + ByteSizeOf,
         I: IntoIterator<Item = E>,
     {
+        if self.checks_events() && name != DECODING_ERRORS_OUTPUT {
+            let events = self.send_errors(events).await?;
+            return self
+                .named_inners
+                .get_mut(name)
+                .expect("unknown output")
+                .send_batch(events)
+                .await;
+        }
+
         self.named_inners
             .get_mut(name)
             .expect("unknown output")
             .send_batch(events)
             .await
     }
+
+    /// Whether the events need to be checked before being sent, for decoding errors or their size.
+    fn checks_events(&self) -> bool {
+        self.routes_decoding_errors || self.event_size_limit.is_some()
+    }
+
+    /// Routes or drops the events that can't be sent on as they are, and returns the others.
+    async fn send_errors<I, E>(&mut self, events: I) -> Result<Vec<Event>, ClosedError>
+    where
+        E: Into<Event> + ByteSizeOf,
+        I: IntoIterator<Item = E>,
+    {
+        let events = if self.routes_decoding_errors {
+            self.send_decoding_errors(events).await?
+        } else {
+            events.into_iter().map(Into::into).collect()
+        };
+        match self.event_size_limit {
+            Some(limit) => self.send_oversized_events(limit, events).await,
+            None => Ok(events),
+        }
+    }
+
+    /// Handles the events exceeding the maximum event size as set by the policy of `limit`, and
+    /// returns the others.
+    async fn send_oversized_events(
+        &mut self,
+        limit: EventSizeLimit,
+        events: Vec<Event>,
+    ) -> Result<Vec<Event>, ClosedError> {
+        let mut oversized = Vec::new();
+        let events = events
+            .into_iter()
+            .filter_map(|mut event| {
+                let byte_size = EventRef::from(&event).estimated_json_encoded_size_of();
+                if byte_size <= limit.max_bytes {
+                    return Some(event);
+                }
+                match limit.policy {
+                    OversizedEventPolicy::Truncate
+                        if truncate_message(&mut event, byte_size - limit.max_bytes)
+                            && EventRef::from(&event).estimated_json_encoded_size_of()
+                                <= limit.max_bytes =>
+                    {
+                        emit!(OversizedEventTruncated {
+                            byte_size,
+                            max_bytes: limit.max_bytes,
+                        });
+                        Some(event)
+                    }
+                    OversizedEventPolicy::Route => {
+                        emit!(OversizedEventRouted {
+                            byte_size,
+                            max_bytes: limit.max_bytes,
+                        });
+                        oversized.push(event);
+                        None
+                    }
+                    OversizedEventPolicy::Drop | OversizedEventPolicy::Truncate => {
+                        emit!(OversizedEventDropped {
+                            byte_size,
+                            max_bytes: limit.max_bytes,
+                        });
+                        None
+                    }
+                }
+            })
+            .collect();
+        if !oversized.is_empty() {
+            self.named_inners
+                .get_mut(DECODING_ERRORS_OUTPUT)
+                .expect("no errors output")
+                .send_batch(oversized)
+                .await?;
+        }
+        Ok(events)
+    }
+
+    /// Sends the events holding payloads that couldn't be decoded to the errors output, and
+    /// returns the others.
+    async fn send_decoding_errors<I, E>(&mut self, events: I) -> Result<Vec<Event>, ClosedError>
+    where
+        E: Into<Event> + ByteSizeOf,
+        I: IntoIterator<Item = E>,
+    {
+        let (failed, events): (Vec<Event>, Vec<Event>) = events
+            .into_iter()
+            .map(Into::into)
+            .partition(has_decoding_error);
+        if !failed.is_empty() {
+            self.named_inners
+                .get_mut(DECODING_ERRORS_OUTPUT)
+                .expect("no errors output")
+                .send_batch(failed)
+                .await?;
+        }
+        Ok(events)
+    }
 }
 
 #[derive(Clone)]
@@ -223,6 +429,7 @@ struct Inner {
     inner: LimitedSender<EventArray>,
     output: String,
     lag_time: Option<Histogram>,
+    ingestion_limiter: Option<IngestionLimiter>,
 }
 
 impl fmt::Debug for Inner {
@@ -240,6 +447,7 @@ impl Inner {
         n: usize,
         output: String,
         lag_time: Option<Histogram>,
+        ingestion_limiter: Option<IngestionLimiter>,
     ) -> (Self, LimitedReceiver<EventArray>) {
         let (tx, rx) = channel::limited(n);
         (
@@ -247,6 +455,7 @@ impl Inner {
                 inner: tx,
                 output,
                 lag_time,
+                ingestion_limiter,
             },
             rx,
         )
@@ -259,7 +468,13 @@ impl Inner {
             .for_each(|event| self.emit_lag_time(event, reference));
         let byte_size = events.size_of();
         let count = events.len();
+        let json_size = self.acquire(&events).await;
         self.inner.send(events).await.map_err(|_| ClosedError)?;
+        if let Some(json_size) = json_size {
+            emit!(EventsReceivedJsonSize {
+                byte_size: json_size
+            });
+        }
         emit!(EventsSent {
             count,
             byte_size,
@@ -300,10 +515,16 @@ impl Inner {
                 .for_each(|event| self.emit_lag_time(event, reference));
             let this_count = events.len();
             let this_size = events.size_of();
+            let this_json_size = self.acquire(&events).await;
             match self.inner.send(events).await {
                 Ok(()) => {
                     count += this_count;
                     byte_size += this_size;
+                    if let Some(json_size) = this_json_size {
+                        emit!(EventsReceivedJsonSize {
+                            byte_size: json_size
+                        });
+                    }
                 }
                 Err(error) => {
                     emit!(EventsSent {
@@ -325,6 +546,17 @@ impl Inner {
         Ok(())
     }
 
+    /// Waits until the events fit in the ingestion limits, if any.
+    ///
+    /// Returns the estimated JSON size of the events, which is only measured when they are limited
+    /// in bytes.
+    async fn acquire(&self, events: &EventArray) -> Option<usize> {
+        match &self.ingestion_limiter {
+            Some(limiter) => limiter.acquire(events).await,
+            None => None,
+        }
+    }
+
     /// Calculate the difference between the reference time and the
     /// timestamp stored in the given event reference, and emit the
     /// different, as expressed in milliseconds, as a histogram.
@@ -351,6 +583,28 @@ impl Inner {
     }
 }
 
+/// Shortens the message of a log event by at least `excess` bytes, keeping it valid UTF-8.
+///
+/// Returns whether the event has a message to shorten.
+fn truncate_message(event: &mut Event, excess: usize) -> bool {
+    let message = match event {
+        Event::Log(log) => log.get_mut(log_schema().message_key()),
+        Event::Metric(_) | Event::Trace(_) => None,
+    };
+    match message {
+        Some(Value::Bytes(bytes)) => {
+            let mut len = bytes.len().saturating_sub(excess);
+            // Back up to the start of a character, skipping UTF-8 continuation bytes.
+            while len > 0 && bytes[len] & 0xC0 == 0x80 {
+                len -= 1;
+            }
+            bytes.truncate(len);
+            true
+        }
+        _ => false,
+    }
+}
+
 fn get_timestamp_millis(value: &Value) -> Option<i64> {
     match value {
         Value::Timestamp(timestamp) => Some(timestamp.timestamp_millis()),
@@ -360,13 +614,151 @@ fn get_timestamp_millis(value: &Value) -> Option<i64> {
 
 #[cfg(test)]
 mod tests {
+    use std::num::NonZeroU32;
+
     use chrono::{DateTime, Duration};
     use rand::{thread_rng, Rng};
     use vector_core::event::{LogEvent, Metric, MetricKind, MetricValue, TraceEvent};
 
+    use vector_core::config::DataType;
+
     use super::*;
     use crate::metrics::{self, Controller};
 
+    fn oversized_sender(
+        policy: OversizedEventPolicy,
+    ) -> (
+        SourceSender,
+        impl Stream<Item = Event> + Unpin,
+        impl Stream<Item = Event> + Unpin,
+    ) {
+        let mut builder = SourceSender::builder()
+            .with_buffer(TEST_BUFFER_SIZE)
+            .with_event_size_limit(NonZeroUsize::new(128), policy);
+        let events = builder.add_output(Output::default(DataType::Log));
+        let errors =
+            builder.add_output(Output::default(DataType::Log).with_port(DECODING_ERRORS_OUTPUT));
+        (
+            builder.build(),
+            events.into_stream().flat_map(into_event_stream),
+            errors.into_stream().flat_map(into_event_stream),
+        )
+    }
+
+    fn message(event: &Event) -> String {
+        event.as_log()[log_schema().message_key()].to_string_lossy()
+    }
+
+    #[tokio::test]
+    async fn drops_oversized_events() {
+        let (mut sender, events, errors) = oversized_sender(OversizedEventPolicy::Drop);
+        sender
+            .send_batch(vec![
+                LogEvent::from("small"),
+                LogEvent::from("x".repeat(200)),
+            ])
+            .await
+            .unwrap();
+        drop(sender);
+
+        let events = events.collect::<Vec<_>>().await;
+        assert_eq!(events.len(), 1);
+        assert_eq!(message(&events[0]), "small");
+        assert_eq!(errors.collect::<Vec<_>>().await.len(), 0);
+    }
+
+    #[tokio::test]
+    async fn truncates_oversized_events() {
+        let (mut sender, events, _errors) = oversized_sender(OversizedEventPolicy::Truncate);
+        sender
+            .send_event(LogEvent::from("é".repeat(100)))
+            .await
+            .unwrap();
+        drop(sender);
+
+        let events = events.collect::<Vec<_>>().await;
+        assert_eq!(events.len(), 1);
+        let message = message(&events[0]);
+        assert!(!message.is_empty());
+        assert!(message.chars().all(|c| c == 'é'));
+        assert!(EventRef::from(&events[0]).estimated_json_encoded_size_of() <= 128);
+    }
+
+    #[tokio::test]
+    async fn routes_oversized_events() {
+        let (mut sender, events, errors) = oversized_sender(OversizedEventPolicy::Route);
+        sender
+            .send_event_stream(futures::stream::iter(vec![
+                LogEvent::from("x".repeat(200)),
+                LogEvent::from("small"),
+            ]))
+            .await
+            .unwrap();
+        drop(sender);
+
+        let events = events.collect::<Vec<_>>().await;
+        assert_eq!(events.len(), 1);
+        assert_eq!(message(&events[0]), "small");
+        let errors = errors.collect::<Vec<_>>().await;
+        assert_eq!(errors.len(), 1);
+        assert_eq!(message(&errors[0]), "x".repeat(200));
+    }
+
+    #[tokio::test]
+    async fn routes_decoding_errors_only_when_set() {
+        let failed = || {
+            let mut log = LogEvent::from("payload");
+            let path = format!("{}.decoding_error", log_schema().metadata_key());
+            log.insert(path.as_str(), "invalid");
+            log
+        };
+
+        // The errors output of oversized events doesn't receive the passed through payloads.
+        let (mut sender, events, errors) = oversized_sender(OversizedEventPolicy::Route);
+        sender.send_event(failed()).await.unwrap();
+        drop(sender);
+        assert_eq!(events.collect::<Vec<_>>().await.len(), 1);
+        assert_eq!(errors.collect::<Vec<_>>().await.len(), 0);
+
+        let mut builder = SourceSender::builder()
+            .with_buffer(TEST_BUFFER_SIZE)
+            .with_decoding_errors_routed(true);
+        let events = builder.add_output(Output::default(DataType::Log));
+        let errors =
+            builder.add_output(Output::default(DataType::Log).with_port(DECODING_ERRORS_OUTPUT));
+        let mut sender = builder.build();
+        sender.send_event(failed()).await.unwrap();
+        drop(sender);
+        let events = events.into_stream().flat_map(into_event_stream);
+        let errors = errors.into_stream().flat_map(into_event_stream);
+        assert_eq!(events.collect::<Vec<_>>().await.len(), 0);
+        assert_eq!(errors.collect::<Vec<_>>().await.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn delays_events_exceeding_ingestion_limits() {
+        let mut builder = SourceSender::builder()
+            .with_buffer(TEST_BUFFER_SIZE)
+            .with_ingestion_limits(IngestionLimits {
+                events_per_second: NonZeroU32::new(50),
+                bytes_per_second: None,
+            });
+        let events = builder.add_output(Output::default(DataType::Log));
+        let mut sender = builder.build();
+
+        // The first 50 events are a burst sent without delay, the next 25 wait for half a second.
+        let start = std::time::Instant::now();
+        sender
+            .send_batch((0..75).map(|_| LogEvent::from("event")))
+            .await
+            .unwrap();
+        assert!(start.elapsed() >= std::time::Duration::from_millis(400));
+        drop(sender);
+
+        let events = events.into_stream().flat_map(into_event_stream);
+        assert_eq!(events.collect::<Vec<_>>().await.len(), 75);
+    }
+
     #[tokio::test]
     async fn emits_lag_time_for_log() {
         emit_and_test(|timestamp| {
@@ -28,6 +28,7 @@ use vector_config::configurable_component;
 use vector_core::{
     config::{AcknowledgementsConfig, LogNamespace},
     event::Event,
+    ByteSizeOf,
 };
 
 #[derive(Debug, Snafu)]
@@ -134,7 +135,9 @@ impl SourceConfig for AmqpSourceConfig {
             .schema_definition(log_namespace)
             .with_standard_vector_source_metadata();
 
-        vec![Output::default(self.decoding.output_type()).with_schema_definition(schema_definition)]
+        self.decoding
+            .with_errors_output(vec![Output::default(self.decoding.output_type())
+                .with_schema_definition(schema_definition)])
     }
 
     fn can_acknowledge(&self) -> bool {
@@ -259,17 +262,17 @@ async fn receive_event(
         delivery_tag: msg.delivery_tag as i64,
     };
 
+    emit!(AmqpBytesReceived {
+        byte_size: msg.data.len(),
+        protocol: "amqp_0_9_1",
+    });
+
     let stream = stream! {
         while let Some(result) = stream.next().await {
             match result {
-                Ok((events, byte_size)) => {
-                    emit!(AmqpBytesReceived {
-                        byte_size,
-                        protocol: "amqp_0_9_1",
-                    });
-
+                Ok((events, _byte_size)) => {
                     emit!(EventsReceived {
-                        byte_size: byte_size,
+                        byte_size: events.size_of(),
                         count: events.len(),
                     });
 
@@ -0,0 +1,445 @@
+use std::{collections::BTreeMap, path::PathBuf, time::Duration};
+
+use bytes::Bytes;
+use futures::StreamExt;
+use tokio::{net::UnixStream, sync::mpsc, task::JoinHandle, time::sleep};
+use tokio_stream::wrappers::ReceiverStream;
+use tokio_util::codec::{FramedRead, LinesCodec, LinesCodecError};
+use vector_common::internal_event::{
+    ByteSize, BytesReceived, EventsReceived, InternalEventHandle as _, Protocol,
+};
+use vector_config::configurable_component;
+use vector_core::{config::LogNamespace, ByteSizeOf};
+
+use crate::{
+    config::{log_schema, DataType, GenerateConfig, Output, SourceConfig, SourceContext},
+    event::{Event, LogEvent, Value},
+    internal_events::{
+        AuditdInvalidRecord, StreamClosedError, UnixSocketConnectionError,
+        UnixSocketConnectionEstablished, UnixSocketError,
+    },
+    shutdown::ShutdownSignal,
+    SourceSender,
+};
+
+mod interpret;
+#[cfg(target_os = "linux")]
+mod netlink;
+mod reassembler;
+mod record;
+
+use self::{interpret::Interpreter, reassembler::Reassembler, record::Record};
+
+const CHANNEL_CAPACITY: usize = 1024;
+
+/// The maximum length of the records read from the dispatcher, longer ones are skipped.
+const MAX_RECORD_LENGTH: usize = 64 * 1024;
+
+/// How long to wait before connecting to the dispatcher again.
+const RECONNECT_DELAY: Duration = Duration::from_secs(1);
+
+/// Configuration for the `auditd` source.
+#[configurable_component(source("auditd"))]
+#[configurable(metadata(status = "alpha"))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct AuditdConfig {
+    #[configurable(derived)]
+    #[serde(default)]
+    mode: AuditdMode,
+
+    /// The path of the socket the `af_unix` plugin of the audit dispatcher writes records to, in
+    /// `audisp` mode.
+    ///
+    /// The plugin must write records in the `string` format.
+    #[serde(default = "default_socket_path")]
+    socket_path: PathBuf,
+
+    /// How long, in milliseconds, to wait for further records of an event whose end isn't marked
+    /// by an `EOE` record, before emitting it with the records received so far.
+    #[serde(default = "default_event_timeout_ms")]
+    event_timeout_ms: u64,
+
+    /// Whether to resolve the architecture, system call, user, and group fields of records into
+    /// readable values.
+    ///
+    /// The resolved values are added next to the raw ones, under the upper case name of the field,
+    /// as `auditd` does when writing enriched logs.
+    #[serde(default = "crate::serde::default_true")]
+    interpret: bool,
+}
+
+/// Where to read audit records from.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditdMode {
+    /// Subscribe to the audit netlink socket of the kernel.
+    ///
+    /// This requires the `CAP_AUDIT_READ` capability, and is only supported on Linux. The records
+    /// are received whether or not `auditd` is running.
+    Netlink,
+
+    /// Read the records forwarded by the `af_unix` plugin of the audit dispatcher.
+    Audisp,
+}
+
+impl Default for AuditdMode {
+    fn default() -> Self {
+        Self::Netlink
+    }
+}
+
+fn default_socket_path() -> PathBuf {
+    PathBuf::from("/var/run/audispd_events")
+}
+
+const fn default_event_timeout_ms() -> u64 {
+    2000
+}
+
+impl GenerateConfig for AuditdConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self {
+            mode: AuditdMode::default(),
+            socket_path: default_socket_path(),
+            event_timeout_ms: default_event_timeout_ms(),
+            interpret: true,
+        })
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+impl SourceConfig for AuditdConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
+        if self.event_timeout_ms == 0 {
+            return Err("`event_timeout_ms` must be greater than zero.".into());
+        }
+
+        let reader = match self.mode {
+            AuditdMode::Netlink => RecordReader::netlink()?,
+            AuditdMode::Audisp => RecordReader::Audisp(self.socket_path.clone()),
+        };
+
+        Ok(Box::pin(auditd_source(
+            self.clone(),
+            reader,
+            cx.shutdown,
+            cx.out,
+        )))
+    }
+
+    fn outputs(&self, _global_log_namespace: LogNamespace) -> Vec<Output> {
+        vec![Output::default(DataType::Log)]
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        false
+    }
+}
+
+/// Where the records are read from.
+enum RecordReader {
+    #[cfg(target_os = "linux")]
+    Netlink(netlink::AuditSocket),
+    Audisp(PathBuf),
+}
+
+impl RecordReader {
+    #[cfg(target_os = "linux")]
+    fn netlink() -> crate::Result<Self> {
+        // Open the socket up front, so that missing capabilities are reported when the
+        // configuration is loaded.
+        netlink::AuditSocket::open()
+            .map(Self::Netlink)
+            .map_err(|error| {
+                format!("Could not subscribe to the audit netlink socket: {}", error).into()
+            })
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    fn netlink() -> crate::Result<Self> {
+        Err("The `netlink` mode is only supported on Linux.".into())
+    }
+
+    /// Starts reading records into `sender`, until its receiving side is dropped.
+    fn spawn(self, sender: mpsc::Sender<Record>) -> JoinHandle<()> {
+        match self {
+            #[cfg(target_os = "linux")]
+            Self::Netlink(socket) => {
+                info!(message = "Reading audit records from netlink socket.");
+                let span = info_span!("auditd_netlink");
+                tokio::task::spawn_blocking(move || {
+                    let _enter = span.enter();
+                    socket.read_records(sender);
+                })
+            }
+            Self::Audisp(path) => {
+                info!(message = "Reading audit records from dispatcher.", path = ?path);
+                tokio::spawn(read_audisp_records(path, sender))
+            }
+        }
+    }
+}
+
+/// Reads records from the socket of the `af_unix` plugin of the audit dispatcher, connecting to it
+/// again whenever the connection is lost, until the receiving side of `sender` is dropped.
+async fn read_audisp_records(path: PathBuf, sender: mpsc::Sender<Record>) {
+    let bytes_received = register!(BytesReceived::from(Protocol::from("unix")));
+    loop {
+        let stream = match UnixStream::connect(&path).await {
+            Ok(stream) => {
+                emit!(UnixSocketConnectionEstablished { path: &path });
+                stream
+            }
+            Err(error) => {
+                emit!(UnixSocketConnectionError { error, path: &path });
+                sleep(RECONNECT_DELAY).await;
+                continue;
+            }
+        };
+
+        let mut lines = FramedRead::new(stream, LinesCodec::new_with_max_length(MAX_RECORD_LENGTH));
+        while let Some(line) = lines.next().await {
+            let line = match line {
+                Ok(line) => line,
+                // The rest of the record is skipped, and the next one read as usual.
+                Err(LinesCodecError::MaxLineLengthExceeded) => continue,
+                Err(LinesCodecError::Io(error)) => {
+                    emit!(UnixSocketError {
+                        error: &error,
+                        path: &path
+                    });
+                    break;
+                }
+            };
+            bytes_received.emit(ByteSize(line.len() + 1));
+            if line.trim().is_empty() {
+                continue;
+            }
+            match record::parse_line(&line) {
+                Some(record) => {
+                    if sender.send(record).await.is_err() {
+                        return;
+                    }
+                }
+                None => emit!(AuditdInvalidRecord { record: &line }),
+            }
+        }
+
+        debug!(message = "Connection to the audit dispatcher closed.", path = ?path);
+        sleep(RECONNECT_DELAY).await;
+    }
+}
+
+async fn auditd_source(
+    config: AuditdConfig,
+    reader: RecordReader,
+    shutdown: ShutdownSignal,
+    mut out: SourceSender,
+) -> Result<(), ()> {
+    let (sender, receiver) = mpsc::channel(CHANNEL_CAPACITY);
+    let reader = reader.spawn(sender);
+    let timeout = Duration::from_millis(config.event_timeout_ms);
+    let mut reassembler = Reassembler::new(timeout);
+    let mut interpreter = config.interpret.then(Interpreter::default);
+    let mut records = ReceiverStream::new(receiver)
+        .ready_chunks(CHANNEL_CAPACITY)
+        .take_until(shutdown);
+    let mut flush_interval = tokio::time::interval(timeout);
+
+    let mut result = Ok(());
+    loop {
+        let completed = tokio::select! {
+            chunk = records.next() => match chunk {
+                Some(chunk) => chunk
+                    .into_iter()
+                    .flat_map(|record| reassembler.push(record))
+                    .collect::<Vec<_>>(),
+                None => break,
+            },
+            _ = flush_interval.tick() => reassembler.flush_expired(),
+        };
+
+        if let Err(()) = send_events(&mut out, completed, interpreter.as_mut()).await {
+            result = Err(());
+            break;
+        }
+    }
+
+    // Dropping the receiver stops the netlink reader at its next read, while the dispatcher reader
+    // could be waiting for records or to connect again.
+    drop(records);
+    reader.abort();
+
+    if result.is_ok() {
+        result = send_events(&mut out, reassembler.flush_all(), interpreter.as_mut()).await;
+    }
+    result
+}
+
+async fn send_events(
+    out: &mut SourceSender,
+    completed: Vec<Vec<Record>>,
+    mut interpreter: Option<&mut Interpreter>,
+) -> Result<(), ()> {
+    if completed.is_empty() {
+        return Ok(());
+    }
+
+    let events = completed
+        .into_iter()
+        .map(|records| Event::from(records_to_log(records, interpreter.as_deref_mut())))
+        .collect::<Vec<_>>();
+    let count = events.len();
+    emit!(EventsReceived {
+        count,
+        byte_size: events.size_of(),
+    });
+
+    out.send_batch(events).await.map_err(|error| {
+        emit!(StreamClosedError { error, count });
+    })
+}
+
+/// Builds an event out of the records of an audit event.
+fn records_to_log(records: Vec<Record>, mut interpreter: Option<&mut Interpreter>) -> LogEvent {
+    let mut log = LogEvent::default();
+
+    let message = records
+        .iter()
+        .map(|record| record.text.as_str())
+        .collect::<Vec<_>>()
+        .join("\n");
+    log.insert(log_schema().message_key(), message);
+    log.insert(log_schema().timestamp_key(), records[0].timestamp);
+    log.insert("sequence", records[0].sequence);
+
+    let records = records
+        .into_iter()
+        .map(|record| {
+            let interpreted = interpreter
+                .as_deref_mut()
+                .map(|interpreter| interpreter.interpret(&record))
+                .unwrap_or_default();
+            let mut fields = BTreeMap::new();
+            // Repeated fields keep their first value, and enriched records already hold the
+            // values that would be interpreted.
+            for (key, value) in record.fields.into_iter().chain(interpreted) {
+                fields.entry(key).or_insert_with(|| Value::from(value));
+            }
+            fields.insert("type".to_owned(), Value::from(record.record_type));
+            Value::Object(fields)
+        })
+        .collect::<Vec<_>>();
+    log.insert("records", records);
+    log.insert(log_schema().source_type_key(), Bytes::from("auditd"));
+
+    log
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+    use tokio::{io::AsyncWriteExt, net::UnixListener};
+
+    use super::*;
+    use crate::test_util::{
+        collect_n,
+        components::{assert_source_compliance, SOURCE_TAGS},
+    };
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<AuditdConfig>();
+    }
+
+    const SYSCALL_EVENT: &str = concat!(
+        r#"type=SYSCALL msg=audit(1364481363.243:24287): arch=c000003e syscall=2 success=no exit=-13 ppid=2686 pid=3538 auid=4294967295 uid=0 comm="cat" exe="/usr/bin/cat" key="sshd_config""#,
+        "\n",
+        r#"type=CWD msg=audit(1364481363.243:24287): cwd="/home/alice""#,
+        "\n",
+        r#"type=PATH msg=audit(1364481363.243:24287): item=0 name="/etc/ssh/sshd_config" inode=409248 nametype=NORMAL"#,
+        "\n",
+        "type=EOE msg=audit(1364481363.243:24287): \n",
+    );
+
+    #[test]
+    fn builds_events_from_records() {
+        let mut reassembler = Reassembler::new(Duration::from_secs(60));
+        let completed = SYSCALL_EVENT
+            .lines()
+            .flat_map(|line| reassembler.push(record::parse_line(line).unwrap()))
+            .collect::<Vec<_>>();
+        assert_eq!(completed.len(), 1);
+
+        let mut interpreter = Interpreter::default();
+        let log = records_to_log(
+            completed.into_iter().next().unwrap(),
+            Some(&mut interpreter),
+        );
+
+        assert_eq!(log["sequence"], 24287.into());
+        assert_eq!(
+            log["timestamp"],
+            Utc.timestamp_opt(1364481363, 243_000_000).unwrap().into()
+        );
+        assert_eq!(log["records[0].type"], "SYSCALL".into());
+        assert_eq!(log["records[0].syscall"], "2".into());
+        assert_eq!(log["records[0].SYSCALL"], "open".into());
+        assert_eq!(log["records[0].AUID"], "unset".into());
+        assert_eq!(log["records[1].cwd"], "/home/alice".into());
+        assert_eq!(log["records[2].name"], "/etc/ssh/sshd_config".into());
+        assert!(log.get("records[3]").is_none());
+        assert_eq!(log["source_type"], "auditd".into());
+        assert_eq!(
+            log["message"],
+            SYSCALL_EVENT
+                .lines()
+                .take(3)
+                .collect::<Vec<_>>()
+                .join("\n")
+                .into()
+        );
+    }
+
+    #[tokio::test]
+    async fn reads_audisp_socket() {
+        assert_source_compliance(&SOURCE_TAGS, async {
+            let directory = tempfile::tempdir().unwrap();
+            let path = directory.path().join("audispd_events");
+            let listener = UnixListener::bind(&path).unwrap();
+
+            let config = AuditdConfig {
+                mode: AuditdMode::Audisp,
+                socket_path: path,
+                event_timeout_ms: 100,
+                interpret: false,
+            };
+            let (tx, rx) = SourceSender::new_test();
+            let source = config
+                .build(SourceContext::new_test(tx, None))
+                .await
+                .unwrap();
+            tokio::spawn(source);
+
+            let (mut stream, _) = listener.accept().await.unwrap();
+            stream.write_all(SYSCALL_EVENT.as_bytes()).await.unwrap();
+            stream
+                .write_all(b"type=AVC msg=audit(1364481364.100:24288): avc:  denied  { read } for pid=3538 comm=\"cat\"\n")
+                .await
+                .unwrap();
+
+            let events = collect_n(rx, 2).await;
+            let syscall = events[0].as_log();
+            assert_eq!(syscall["records[0].type"], "SYSCALL".into());
+            assert!(syscall.get("records[0].SYSCALL").is_none());
+            // Events without `EOE` record are emitted once the timeout elapses.
+            let avc = events[1].as_log();
+            assert_eq!(avc["records[0].type"], "AVC".into());
+            assert_eq!(avc["records[0].comm"], "cat".into());
+        })
+        .await;
+    }
+}
@@ -0,0 +1,174 @@
+use std::{
+    io, mem,
+    os::unix::io::{AsRawFd, FromRawFd, OwnedFd},
+};
+
+use tokio::sync::mpsc;
+use vector_common::internal_event::{ByteSize, BytesReceived, InternalEventHandle as _, Protocol};
+
+use super::record::{parse_message, Record};
+use crate::internal_events::{AuditdInvalidRecord, AuditdReadError, AuditdReceiveBufferOverrun};
+
+/// The multicast group of the audit netlink socket the kernel sends a copy of all records to.
+const AUDIT_NLGRP_READLOG: u32 = 1;
+
+/// The size of the header of netlink messages.
+const NLMSG_HDRLEN: usize = 16;
+
+/// The types of netlink messages below this one are commands and replies, not records.
+const FIRST_RECORD_TYPE: u16 = 1000;
+
+/// The maximum size of an audit netlink message, including its header.
+const MAX_AUDIT_MESSAGE_LENGTH: usize = 8970;
+
+/// How long, in milliseconds, a read from the socket waits for records before checking whether
+/// the source is shutting down.
+const READ_TIMEOUT_MS: libc::suseconds_t = 500;
+
+/// A socket subscribed to the records the kernel multicasts to readers of the audit log.
+///
+/// Subscribing requires the `CAP_AUDIT_READ` capability, and doesn't prevent `auditd` from
+/// receiving the records, unlike taking its place as the audit daemon would.
+pub struct AuditSocket {
+    fd: OwnedFd,
+}
+
+impl AuditSocket {
+    pub fn open() -> io::Result<Self> {
+        let fd = unsafe {
+            libc::socket(
+                libc::AF_NETLINK,
+                libc::SOCK_RAW | libc::SOCK_CLOEXEC,
+                libc::NETLINK_AUDIT,
+            )
+        };
+        if fd < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        let socket = Self {
+            fd: unsafe { OwnedFd::from_raw_fd(fd) },
+        };
+
+        let mut address: libc::sockaddr_nl = unsafe { mem::zeroed() };
+        address.nl_family = libc::AF_NETLINK as libc::sa_family_t;
+        address.nl_groups = AUDIT_NLGRP_READLOG;
+        let status = unsafe {
+            libc::bind(
+                socket.fd.as_raw_fd(),
+                &address as *const libc::sockaddr_nl as *const libc::sockaddr,
+                mem::size_of::<libc::sockaddr_nl>() as libc::socklen_t,
+            )
+        };
+        if status < 0 {
+            return Err(io::Error::last_os_error());
+        }
+
+        let timeout = libc::timeval {
+            tv_sec: 0,
+            tv_usec: READ_TIMEOUT_MS * 1000,
+        };
+        let status = unsafe {
+            libc::setsockopt(
+                socket.fd.as_raw_fd(),
+                libc::SOL_SOCKET,
+                libc::SO_RCVTIMEO,
+                &timeout as *const libc::timeval as *const libc::c_void,
+                mem::size_of::<libc::timeval>() as libc::socklen_t,
+            )
+        };
+        if status < 0 {
+            return Err(io::Error::last_os_error());
+        }
+
+        Ok(socket)
+    }
+
+    /// Reads records from the socket until the receiving side of `sender` is dropped.
+    pub fn read_records(self, sender: mpsc::Sender<Record>) {
+        let bytes_received = register!(BytesReceived::from(Protocol::from("netlink")));
+        let mut buffer = vec![0u8; MAX_AUDIT_MESSAGE_LENGTH];
+        loop {
+            let length = unsafe {
+                libc::recv(
+                    self.fd.as_raw_fd(),
+                    buffer.as_mut_ptr() as *mut libc::c_void,
+                    buffer.len(),
+                    0,
+                )
+            };
+            if length < 0 {
+                let error = io::Error::last_os_error();
+                match error.raw_os_error() {
+                    Some(libc::EAGAIN | libc::EINTR) => {
+                        if sender.is_closed() {
+                            break;
+                        }
+                    }
+                    // The kernel dropped records because they weren't read fast enough.
+                    Some(libc::ENOBUFS) => emit!(AuditdReceiveBufferOverrun),
+                    _ => {
+                        emit!(AuditdReadError { error });
+                        break;
+                    }
+                }
+                continue;
+            }
+
+            let datagram = &buffer[..length as usize];
+            bytes_received.emit(ByteSize(datagram.len()));
+            let (message_type, payload) = match parse_datagram(datagram) {
+                Some(message) => message,
+                None => continue,
+            };
+            let payload = String::from_utf8_lossy(payload);
+            match parse_message(message_type, &payload) {
+                Some(record) => {
+                    if sender.blocking_send(record).is_err() {
+                        break;
+                    }
+                }
+                None => emit!(AuditdInvalidRecord { record: &payload }),
+            }
+        }
+    }
+}
+
+/// The type and payload of the audit record held by a datagram.
+///
+/// The kernel sends a single record per datagram, and doesn't count the header in the length of
+/// the messages of some versions, so the payload is the whole rest of the datagram.
+fn parse_datagram(datagram: &[u8]) -> Option<(u16, &[u8])> {
+    if datagram.len() < NLMSG_HDRLEN {
+        return None;
+    }
+    let message_type = u16::from_ne_bytes([datagram[4], datagram[5]]);
+    (message_type >= FIRST_RECORD_TYPE).then(|| (message_type, &datagram[NLMSG_HDRLEN..]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn datagram(message_type: u16, length: usize, payload: &[u8]) -> Vec<u8> {
+        let mut datagram = Vec::new();
+        datagram.extend_from_slice(&(length as u32).to_ne_bytes());
+        datagram.extend_from_slice(&message_type.to_ne_bytes());
+        datagram.extend_from_slice(&[0; 10]);
+        datagram.extend_from_slice(payload);
+        datagram
+    }
+
+    #[test]
+    fn parses_datagrams() {
+        let payload = b"audit(1364481363.243:24287): cwd=\"/\"";
+        let (message_type, parsed) =
+            parse_datagram(&datagram(1307, payload.len(), payload)).unwrap();
+
+        assert_eq!(message_type, 1307);
+        assert_eq!(parsed, payload);
+
+        // Replies to commands aren't records.
+        assert!(parse_datagram(&datagram(2, NLMSG_HDRLEN + 20, &[0; 20])).is_none());
+        assert!(parse_datagram(&[0; 4]).is_none());
+    }
+}
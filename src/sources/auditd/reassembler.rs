@@ -0,0 +1,157 @@
+use std::{
+    collections::BTreeMap,
+    time::{Duration, Instant},
+};
+
+use super::record::{Record, END_OF_EVENT};
+
+/// The maximum number of events whose records are held while waiting for the rest of them.
+///
+/// Past it, the oldest events are emitted as they are.
+const MAX_PENDING_EVENTS: usize = 4096;
+
+/// Groups the records of the audit subsystem into events, by their serial number.
+///
+/// The kernel ends the records of an event with an `EOE` record, but only for the events of system
+/// calls. The records of other events are grouped until no record of the event was received for
+/// `timeout`.
+pub struct Reassembler {
+    timeout: Duration,
+    pending: BTreeMap<u64, PendingEvent>,
+}
+
+struct PendingEvent {
+    records: Vec<Record>,
+    last_record_at: Instant,
+}
+
+impl Reassembler {
+    pub fn new(timeout: Duration) -> Self {
+        Self {
+            timeout,
+            pending: BTreeMap::new(),
+        }
+    }
+
+    /// Adds a record, returning the records of the events it completes.
+    pub fn push(&mut self, record: Record) -> Vec<Vec<Record>> {
+        let mut completed = Vec::new();
+
+        if record.record_type == END_OF_EVENT {
+            completed.extend(
+                self.pending
+                    .remove(&record.sequence)
+                    .map(|event| event.records),
+            );
+            return completed;
+        }
+        if record.is_standalone() && !self.pending.contains_key(&record.sequence) {
+            completed.push(vec![record]);
+            return completed;
+        }
+
+        let now = Instant::now();
+        self.pending
+            .entry(record.sequence)
+            .or_insert_with(|| PendingEvent {
+                records: Vec::new(),
+                last_record_at: now,
+            })
+            .push(record, now);
+
+        while self.pending.len() > MAX_PENDING_EVENTS {
+            let oldest = *self.pending.keys().next().expect("pending events exist");
+            completed.extend(self.pending.remove(&oldest).map(|event| event.records));
+        }
+        completed
+    }
+
+    /// Removes the records of the events that didn't receive any record for `timeout`.
+    pub fn flush_expired(&mut self) -> Vec<Vec<Record>> {
+        let timeout = self.timeout;
+        let expired = self
+            .pending
+            .iter()
+            .filter(|(_, event)| event.last_record_at.elapsed() >= timeout)
+            .map(|(sequence, _)| *sequence)
+            .collect::<Vec<_>>();
+        expired
+            .into_iter()
+            .filter_map(|sequence| self.pending.remove(&sequence))
+            .map(|event| event.records)
+            .collect()
+    }
+
+    /// Removes the records of all the events, whether complete or not.
+    pub fn flush_all(&mut self) -> Vec<Vec<Record>> {
+        std::mem::take(&mut self.pending)
+            .into_values()
+            .map(|event| event.records)
+            .collect()
+    }
+}
+
+impl PendingEvent {
+    fn push(&mut self, record: Record, now: Instant) {
+        self.records.push(record);
+        self.last_record_at = now;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sources::auditd::record::parse_line;
+
+    fn record(line: &str) -> Record {
+        parse_line(line).unwrap()
+    }
+
+    #[test]
+    fn groups_records_until_end_of_event() {
+        let mut reassembler = Reassembler::new(Duration::from_secs(60));
+
+        assert!(reassembler
+            .push(record(
+                "type=SYSCALL msg=audit(1364481363.243:24287): arch=c000003e syscall=2"
+            ))
+            .is_empty());
+        assert!(reassembler
+            .push(record(
+                r#"type=CWD msg=audit(1364481363.243:24287): cwd="/home/alice""#
+            ))
+            .is_empty());
+        // Records of other events can be interleaved.
+        assert_eq!(
+            reassembler
+                .push(record(
+                    "type=USER_LOGIN msg=audit(1364481363.244:24288): pid=1 uid=0 msg='res=success'"
+                ))
+                .len(),
+            1
+        );
+
+        let completed = reassembler.push(record("type=EOE msg=audit(1364481363.243:24287): "));
+        assert_eq!(completed.len(), 1);
+        let types = completed[0]
+            .iter()
+            .map(|record| record.record_type.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(types, vec!["SYSCALL", "CWD"]);
+        assert!(reassembler.flush_all().is_empty());
+    }
+
+    #[test]
+    fn flushes_events_without_end_of_event() {
+        let mut reassembler = Reassembler::new(Duration::ZERO);
+
+        reassembler.push(record(
+            "type=AVC msg=audit(1364481363.243:24289): avc:  denied  { read } for pid=3538",
+        ));
+
+        let expired = reassembler.flush_expired();
+        assert_eq!(expired.len(), 1);
+        assert_eq!(expired[0][0].record_type, "AVC");
+        assert!(reassembler.flush_expired().is_empty());
+    }
+}
@@ -0,0 +1,364 @@
+use std::borrow::Cow;
+
+use chrono::{DateTime, TimeZone, Utc};
+
+/// The names of the audit record types, as `auditd` writes them, keyed by the type of the netlink
+/// messages they are sent with.
+const RECORD_TYPES: &[(u16, &str)] = &[
+    (1006, "LOGIN"),
+    (1100, "USER_AUTH"),
+    (1101, "USER_ACCT"),
+    (1102, "USER_MGMT"),
+    (1103, "CRED_ACQ"),
+    (1104, "CRED_DISP"),
+    (1105, "USER_START"),
+    (1106, "USER_END"),
+    (1107, "USER_AVC"),
+    (1108, "USER_CHAUTHTOK"),
+    (1109, "USER_ERR"),
+    (1110, "CRED_REFR"),
+    (1111, "USYS_CONFIG"),
+    (1112, "USER_LOGIN"),
+    (1113, "USER_LOGOUT"),
+    (1114, "ADD_USER"),
+    (1115, "DEL_USER"),
+    (1116, "ADD_GROUP"),
+    (1117, "DEL_GROUP"),
+    (1118, "DAC_CHECK"),
+    (1119, "CHGRP_ID"),
+    (1120, "TEST"),
+    (1121, "TRUSTED_APP"),
+    (1122, "USER_SELINUX_ERR"),
+    (1123, "USER_CMD"),
+    (1124, "USER_TTY"),
+    (1125, "CHUSER_ID"),
+    (1126, "GRP_AUTH"),
+    (1127, "SYSTEM_BOOT"),
+    (1128, "SYSTEM_SHUTDOWN"),
+    (1129, "SYSTEM_RUNLEVEL"),
+    (1130, "SERVICE_START"),
+    (1131, "SERVICE_STOP"),
+    (1132, "GRP_MGMT"),
+    (1133, "GRP_CHAUTHTOK"),
+    (1134, "MAC_CHECK"),
+    (1135, "ACCT_LOCK"),
+    (1136, "ACCT_UNLOCK"),
+    (1137, "USER_DEVICE"),
+    (1138, "SOFTWARE_UPDATE"),
+    (1300, "SYSCALL"),
+    (1302, "PATH"),
+    (1303, "IPC"),
+    (1304, "SOCKETCALL"),
+    (1305, "CONFIG_CHANGE"),
+    (1306, "SOCKADDR"),
+    (1307, "CWD"),
+    (1309, "EXECVE"),
+    (1311, "IPC_SET_PERM"),
+    (1312, "MQ_OPEN"),
+    (1313, "MQ_SENDRECV"),
+    (1314, "MQ_NOTIFY"),
+    (1315, "MQ_GETSETATTR"),
+    (1316, "KERNEL_OTHER"),
+    (1317, "FD_PAIR"),
+    (1318, "OBJ_PID"),
+    (1319, "TTY"),
+    (1320, "EOE"),
+    (1321, "BPRM_FCAPS"),
+    (1322, "CAPSET"),
+    (1323, "MMAP"),
+    (1324, "NETFILTER_PKT"),
+    (1325, "NETFILTER_CFG"),
+    (1326, "SECCOMP"),
+    (1327, "PROCTITLE"),
+    (1328, "FEATURE_CHANGE"),
+    (1329, "REPLACE"),
+    (1330, "KERN_MODULE"),
+    (1331, "FANOTIFY"),
+    (1332, "TIME_INJOFFSET"),
+    (1333, "TIME_ADJNTPVAL"),
+    (1334, "BPF"),
+    (1335, "EVENT_LISTENER"),
+    (1400, "AVC"),
+    (1401, "SELINUX_ERR"),
+    (1402, "AVC_PATH"),
+    (1403, "MAC_POLICY_LOAD"),
+    (1404, "MAC_STATUS"),
+    (1405, "MAC_CONFIG_CHANGE"),
+    (1406, "MAC_UNLBL_ALLOW"),
+    (1407, "MAC_CIPSOV4_ADD"),
+    (1408, "MAC_CIPSOV4_DEL"),
+    (1409, "MAC_MAP_ADD"),
+    (1410, "MAC_MAP_DEL"),
+    (1411, "MAC_IPSEC_ADDSA"),
+    (1412, "MAC_IPSEC_DELSA"),
+    (1413, "MAC_IPSEC_ADDSPD"),
+    (1414, "MAC_IPSEC_DELSPD"),
+    (1415, "MAC_IPSEC_EVENT"),
+    (1416, "MAC_UNLBL_STCADD"),
+    (1417, "MAC_UNLBL_STCDEL"),
+    (1418, "MAC_CALIPSO_ADD"),
+    (1419, "MAC_CALIPSO_DEL"),
+    (1700, "ANOM_PROMISCUOUS"),
+    (1701, "ANOM_ABEND"),
+    (1702, "ANOM_LINK"),
+    (1703, "ANOM_CREAT"),
+    (1800, "INTEGRITY_DATA"),
+    (1801, "INTEGRITY_METADATA"),
+    (1802, "INTEGRITY_STATUS"),
+    (1803, "INTEGRITY_HASH"),
+    (1804, "INTEGRITY_PCR"),
+    (1805, "INTEGRITY_RULE"),
+    (1806, "INTEGRITY_EVM_XATTR"),
+    (1807, "INTEGRITY_POLICY_RULE"),
+    (2000, "KERNEL"),
+];
+
+/// The type of the record ending the records of a multi-record event.
+pub const END_OF_EVENT: &str = "EOE";
+
+/// A record of the audit subsystem.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Record {
+    pub record_type: String,
+    pub timestamp: DateTime<Utc>,
+    /// The serial number of the event the record belongs to.
+    pub sequence: u64,
+    pub fields: Vec<(String, String)>,
+    /// The record as `auditd` writes it.
+    pub text: String,
+}
+
+impl Record {
+    /// Whether the record is an event of its own, rather than one of the records of an event.
+    ///
+    /// Records sent by user space programs, such as PAM, are never followed by others.
+    pub fn is_standalone(&self) -> bool {
+        record_type_number(&self.record_type)
+            .map_or(false, |number| matches!(number, 1100..=1199 | 2100..=2999))
+    }
+}
+
+/// The name of the record type sent with netlink messages of type `number`.
+pub fn record_type_name(number: u16) -> Cow<'static, str> {
+    RECORD_TYPES
+        .iter()
+        .find(|(type_number, _)| *type_number == number)
+        .map(|(_, name)| Cow::Borrowed(*name))
+        .unwrap_or_else(|| Cow::Owned(format!("UNKNOWN[{}]", number)))
+}
+
+fn record_type_number(name: &str) -> Option<u16> {
+    RECORD_TYPES
+        .iter()
+        .find(|(_, type_name)| *type_name == name)
+        .map(|(number, _)| *number)
+        .or_else(|| {
+            name.strip_prefix("UNKNOWN[")?
+                .strip_suffix(']')?
+                .parse()
+                .ok()
+        })
+}
+
+/// Parses a record as written by `auditd` and its dispatcher, such as
+/// `type=CWD msg=audit(1364481363.243:24287): cwd="/home/alice"`.
+pub fn parse_line(line: &str) -> Option<Record> {
+    let line = line.trim_end();
+    // Records are prefixed with the name of the node they come from when `name_format` is set.
+    let (node, rest) = match line.strip_prefix("node=") {
+        Some(rest) => rest.split_once(' ')?,
+        None => ("", line),
+    };
+    let (record_type, body) = rest.strip_prefix("type=")?.split_once(' ')?;
+    let body = body.strip_prefix("msg=")?;
+
+    let mut record = parse_body(record_type.to_owned(), body, line.to_owned())?;
+    if !node.is_empty() {
+        record
+            .fields
+            .insert(0, ("node".to_owned(), node.to_owned()));
+    }
+    Some(record)
+}
+
+/// Parses the payload of a netlink message of type `number`, such as
+/// `audit(1364481363.243:24287): cwd="/home/alice"`.
+pub fn parse_message(number: u16, payload: &str) -> Option<Record> {
+    // The payload of some messages is terminated by the NUL of a C string.
+    let payload = payload.trim_end_matches(|c: char| c == '\0' || c.is_ascii_whitespace());
+    let record_type = record_type_name(number);
+    let text = format!("type={} msg={}", record_type, payload);
+    parse_body(record_type.into_owned(), payload, text)
+}
+
+fn parse_body(record_type: String, body: &str, text: String) -> Option<Record> {
+    let (header, fields_text) = body.strip_prefix("audit(")?.split_once("):")?;
+    let (time, sequence) = header.split_once(':')?;
+    let (seconds, millis) = time.split_once('.')?;
+    let timestamp = Utc
+        .timestamp_opt(
+            seconds.parse().ok()?,
+            millis.parse::<u32>().ok()? * 1_000_000,
+        )
+        .single()?;
+
+    let mut fields = Vec::new();
+    parse_fields(&record_type, fields_text, &mut fields);
+    Some(Record {
+        record_type,
+        timestamp,
+        sequence: sequence.parse().ok()?,
+        fields,
+        text,
+    })
+}
+
+/// Parses the `key=value` pairs of a record.
+///
+/// Values can be double quoted strings, or, for those of user space records holding the message of
+/// the program sending it, single quoted lists of further pairs, whose pairs are added along the
+/// others.
+fn parse_fields(record_type: &str, text: &str, fields: &mut Vec<(String, String)>) {
+    // Enriched records separate the interpreted fields with a group separator.
+    let is_separator = |c: char| c.is_ascii_whitespace() || c == '\x1d';
+    let mut rest = text.trim_start_matches(is_separator);
+    while !rest.is_empty() {
+        let token_end = rest.find(is_separator).unwrap_or(rest.len());
+        let (key, value_start) = match rest[..token_end].split_once('=') {
+            Some((key, _)) => (key, key.len() + 1),
+            None => {
+                rest = rest[token_end..].trim_start_matches(is_separator);
+                continue;
+            }
+        };
+        let value_text = &rest[value_start..];
+
+        let (value, consumed) = match value_text.chars().next() {
+            Some(quote @ ('"' | '\'')) => {
+                let end = value_text[1..]
+                    .find(quote)
+                    .map_or(value_text.len(), |end| end + 1);
+                let value = &value_text[1..end];
+                let consumed = (end + 1).min(value_text.len());
+                if quote == '\'' {
+                    parse_fields(record_type, value, fields);
+                    rest = value_text[consumed..].trim_start_matches(is_separator);
+                    continue;
+                }
+                (value.to_owned(), consumed)
+            }
+            _ => {
+                let end = value_text.find(is_separator).unwrap_or(value_text.len());
+                let value = &value_text[..end];
+                let value = if is_encoded(record_type, key) {
+                    decode_hex(value).unwrap_or_else(|| value.to_owned())
+                } else {
+                    value.to_owned()
+                };
+                (value, end)
+            }
+        };
+
+        fields.push((key.to_owned(), value));
+        rest = value_text[consumed..].trim_start_matches(is_separator);
+    }
+}
+
+/// Whether the value of the field `key` is written hex encoded when not quoted, as the kernel does
+/// with the strings that could contain spaces, quotes, or control characters.
+fn is_encoded(record_type: &str, key: &str) -> bool {
+    match key {
+        "proctitle" | "comm" | "exe" | "name" | "cwd" | "cmd" | "acct" | "key" | "data"
+        | "path" | "old-disk" | "new-disk" | "old-chardev" | "new-chardev" => true,
+        _ => {
+            record_type == "EXECVE"
+                && key.starts_with('a')
+                && key[1..]
+                    .chars()
+                    .all(|c| c.is_ascii_digit() || c == '[' || c == ']')
+        }
+    }
+}
+
+/// Decodes a hex encoded string, separating the arguments of a `proctitle` with spaces.
+fn decode_hex(value: &str) -> Option<String> {
+    if value.is_empty() || value.len() % 2 != 0 {
+        return None;
+    }
+    let bytes = (0..value.len())
+        .step_by(2)
+        .map(|index| u8::from_str_radix(value.get(index..index + 2)?, 16).ok())
+        .collect::<Option<Vec<_>>>()?;
+    let decoded = String::from_utf8_lossy(&bytes);
+    Some(decoded.trim_end_matches('\0').replace('\0', " "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn field<'a>(record: &'a Record, key: &str) -> Option<&'a str> {
+        record
+            .fields
+            .iter()
+            .find(|(field, _)| field == key)
+            .map(|(_, value)| value.as_str())
+    }
+
+    #[test]
+    fn parses_syscall_records() {
+        let record = parse_line(
+            r#"type=SYSCALL msg=audit(1364481363.243:24287): arch=c000003e syscall=2 success=no exit=-13 a0=7fffd19c5592 ppid=2686 pid=3538 auid=1000 uid=1000 gid=1000 comm="cat" exe="/usr/bin/cat" key="sshd_config""#,
+        )
+        .unwrap();
+
+        assert_eq!(record.record_type, "SYSCALL");
+        assert_eq!(record.sequence, 24287);
+        assert_eq!(
+            record.timestamp,
+            Utc.timestamp_opt(1364481363, 243_000_000).unwrap()
+        );
+        assert_eq!(field(&record, "arch"), Some("c000003e"));
+        assert_eq!(field(&record, "exit"), Some("-13"));
+        assert_eq!(field(&record, "comm"), Some("cat"));
+        assert_eq!(field(&record, "key"), Some("sshd_config"));
+        assert!(!record.is_standalone());
+    }
+
+    #[test]
+    fn decodes_hex_encoded_values() {
+        let record = parse_line(
+            "node=web-1 type=PROCTITLE msg=audit(1364481363.243:24287): proctitle=636174002F6574632F7373682F737368645F636F6E666967",
+        )
+        .unwrap();
+
+        assert_eq!(field(&record, "node"), Some("web-1"));
+        assert_eq!(
+            field(&record, "proctitle"),
+            Some("cat /etc/ssh/sshd_config")
+        );
+    }
+
+    #[test]
+    fn flattens_user_messages() {
+        let record = parse_message(
+            1100,
+            "audit(1364481363.250:24290): pid=4210 uid=0 auid=1000 ses=2 msg='op=PAM:authentication grantors=pam_unix acct=\"alice\" exe=\"/usr/bin/su\" hostname=? addr=? terminal=pts/0 res=success'\0",
+        )
+        .unwrap();
+
+        assert_eq!(record.record_type, "USER_AUTH");
+        assert!(record.is_standalone());
+        assert_eq!(field(&record, "op"), Some("PAM:authentication"));
+        assert_eq!(field(&record, "acct"), Some("alice"));
+        assert_eq!(field(&record, "res"), Some("success"));
+        assert!(record.text.starts_with("type=USER_AUTH msg=audit("));
+    }
+
+    #[test]
+    fn rejects_invalid_records() {
+        assert!(parse_line("not an audit record").is_none());
+        assert!(parse_line("type=SYSCALL msg=audit(invalid): arch=c000003e").is_none());
+        assert_eq!(record_type_name(1999), "UNKNOWN[1999]");
+    }
+}
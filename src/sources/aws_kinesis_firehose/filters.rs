@@ -4,7 +4,9 @@ use bytes::{Buf, Bytes};
 use chrono::Utc;
 use flate2::read::MultiGzDecoder;
 use snafu::ResultExt;
-use vector_common::internal_event::{BytesReceived, Protocol};
+use vector_common::internal_event::{
+    ByteSize, BytesReceived, InternalEventHandle as _, Protocol, Registered,
+};
 use warp::{http::StatusCode, Filter};
 
 use super::{
@@ -32,7 +34,6 @@ pub fn firehose(
         compression: record_compression,
         decoder,
         acknowledgements,
-        bytes_received,
         out,
     };
     warp::post()
@@ -52,7 +53,7 @@ pub fn firehose(
                 })
                 .untuple_one(),
         )
-        .and(parse_body())
+        .and(parse_body(bytes_received))
         .and(warp::any().map(move || context.clone()))
         .and_then(handlers::firehose)
         .recover(handle_firehose_rejection)
@@ -61,33 +62,38 @@ pub fn firehose(
 /// Decode (if needed) and parse request body
 ///
 /// Firehose can be configured to gzip compress messages so we handle this here
-fn parse_body() -> impl Filter<Extract = (FirehoseRequest,), Error = warp::reject::Rejection> + Clone
-{
+fn parse_body(
+    bytes_received: Registered<BytesReceived>,
+) -> impl Filter<Extract = (FirehoseRequest,), Error = warp::reject::Rejection> + Clone {
     warp::any()
         .and(warp::header::optional::<String>("Content-Encoding"))
         .and(warp::header("X-Amz-Firehose-Request-Id"))
         .and(warp::body::bytes())
         .and_then(
-            |encoding: Option<String>, request_id: String, body: Bytes| async move {
-                match encoding {
-                    Some(s) if s == "gzip" => {
-                        Ok(Box::new(MultiGzDecoder::new(body.reader())) as Box<dyn io::Read>)
+            move |encoding: Option<String>, request_id: String, body: Bytes| {
+                // The bytes received are counted as sent over the wire, before decoding.
+                bytes_received.emit(ByteSize(body.len()));
+                async move {
+                    match encoding {
+                        Some(s) if s == "gzip" => {
+                            Ok(Box::new(MultiGzDecoder::new(body.reader())) as Box<dyn io::Read>)
+                        }
+                        Some(s) => Err(warp::reject::Rejection::from(
+                            RequestError::UnsupportedEncoding {
+                                encoding: s,
+                                request_id: request_id.clone(),
+                            },
+                        )),
+                        None => Ok(Box::new(body.reader()) as Box<dyn io::Read>),
                     }
-                    Some(s) => Err(warp::reject::Rejection::from(
-                        RequestError::UnsupportedEncoding {
-                            encoding: s,
-                            request_id: request_id.clone(),
-                        },
-                    )),
-                    None => Ok(Box::new(body.reader()) as Box<dyn io::Read>),
+                    .and_then(|r| {
+                        serde_json::from_reader(r)
+                            .context(ParseSnafu {
+                                request_id: request_id.clone(),
+                            })
+                            .map_err(warp::reject::custom)
+                    })
                 }
-                .and_then(|r| {
-                    serde_json::from_reader(r)
-                        .context(ParseSnafu {
-                            request_id: request_id.clone(),
-                        })
-                        .map_err(warp::reject::custom)
-                })
             },
         )
 }
@@ -9,9 +9,6 @@ use lookup::event_path;
 use snafu::{ResultExt, Snafu};
 use tokio_util::codec::FramedRead;
 use vector_common::finalization::AddBatchNotifier;
-use vector_common::internal_event::{
-    ByteSize, BytesReceived, InternalEventHandle as _, Registered,
-};
 use vector_core::{event::BatchNotifier, ByteSizeOf};
 use warp::reject;
 
@@ -35,7 +32,6 @@ pub(super) struct Context {
     pub(super) compression: Compression,
     pub(super) decoder: Decoder,
     pub(super) acknowledgements: bool,
-    pub(super) bytes_received: Registered<BytesReceived>,
     pub(super) out: SourceSender,
 }
 
@@ -52,7 +48,6 @@ pub(super) async fn firehose(
                 request_id: request_id.clone(),
             })
             .map_err(reject::custom)?;
-        context.bytes_received.emit(ByteSize(bytes.len()));
 
         let mut stream = FramedRead::new(bytes.as_ref(), context.decoder.clone());
         loop {
@@ -156,6 +156,7 @@ impl AwsS3Config {
             &self.auth,
             Some(region.clone()),
             endpoint.clone(),
+            self.region.endpoint_variant,
             proxy,
             &self.tls_options,
             false,
@@ -168,6 +169,7 @@ impl AwsS3Config {
                     &self.auth,
                     Some(region.clone()),
                     endpoint,
+                    self.region.endpoint_variant,
                     proxy,
                     &sqs.tls_options,
                     false,
@@ -766,12 +768,14 @@ mod integration_tests {
         let region_endpoint = RegionOrEndpoint {
             region: Some("us-east-1".to_owned()),
             endpoint: Some(s3_address()),
+            endpoint_variant: None,
         };
         let proxy_config = ProxyConfig::default();
         create_client::<S3ClientBuilder>(
             &auth,
             region_endpoint.region(),
             region_endpoint.endpoint().unwrap(),
+            None,
             &proxy_config,
             &None,
             false,
@@ -785,12 +789,14 @@ mod integration_tests {
         let region_endpoint = RegionOrEndpoint {
             region: Some("us-east-1".to_owned()),
             endpoint: Some(s3_address()),
+            endpoint_variant: None,
         };
         let proxy_config = ProxyConfig::default();
         create_client::<SqsClientBuilder>(
             &auth,
             region_endpoint.region(),
             region_endpoint.endpoint().unwrap(),
+            None,
             &proxy_config,
             &None,
             false,
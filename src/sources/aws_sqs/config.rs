@@ -115,7 +115,8 @@ impl SourceConfig for AwsSqsConfig {
     }
 
     fn outputs(&self, _global_log_namespace: LogNamespace) -> Vec<Output> {
-        vec![Output::default(self.decoding.output_type())]
+        self.decoding
+            .with_errors_output(vec![Output::default(self.decoding.output_type())])
     }
 
     fn can_acknowledge(&self) -> bool {
@@ -129,6 +130,7 @@ impl AwsSqsConfig {
             &self.auth,
             self.region.region(),
             self.region.endpoint()?,
+            self.region.endpoint_variant,
             &cx.proxy,
             &self.tls,
             false,
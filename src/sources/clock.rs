@@ -0,0 +1,348 @@
+use std::{path::PathBuf, process::ExitStatus};
+
+use chrono::Utc;
+use futures::StreamExt;
+use snafu::{ResultExt, Snafu};
+use tokio::{process::Command, time};
+use tokio_stream::wrappers::IntervalStream;
+use vector_common::internal_event::{ByteSize, BytesReceived, InternalEventHandle as _, Protocol};
+use vector_config::configurable_component;
+use vector_core::{config::LogNamespace, ByteSizeOf};
+
+use crate::{
+    config::{log_schema, DataType, Output, SourceConfig, SourceContext},
+    event::metric::{Metric, MetricKind, MetricTags, MetricValue},
+    internal_events::{ClockReadError, EventsReceived, StreamClosedError},
+    shutdown::ShutdownSignal,
+    SourceSender,
+};
+
+/// Configuration for the `clock` source.
+#[configurable_component(source("clock"))]
+#[configurable(metadata(status = "alpha"))]
+#[derive(Clone, Debug, Derivative)]
+#[derivative(Default)]
+#[serde(deny_unknown_fields, default)]
+pub struct ClockConfig {
+    /// The interval between reads of the clock status, in seconds.
+    #[derivative(Default(value = "15.0"))]
+    pub scrape_interval_secs: f64,
+
+    #[configurable(derived)]
+    pub method: ClockMethod,
+
+    /// The path of the `chronyc` binary.
+    ///
+    /// Only used by the `chronyc` method. By default, `chronyc` is looked up in the `PATH`.
+    #[derivative(Default(value = "default_chronyc_path()"))]
+    pub chronyc_path: PathBuf,
+
+    /// Overrides the default namespace for the metrics emitted by the source.
+    #[derivative(Default(value = "default_namespace()"))]
+    pub namespace: String,
+}
+
+/// The method used to read the status of the system clock.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Derivative, Eq, PartialEq)]
+#[derivative(Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ClockMethod {
+    /// Read the kernel clock discipline through the `adjtimex` system call.
+    ///
+    /// This works with any NTP daemon that disciplines the kernel clock, but is only supported
+    /// on Linux.
+    #[derivative(Default)]
+    Adjtimex,
+
+    /// Read the tracking status of `chronyd` through `chronyc`.
+    Chronyc,
+}
+
+impl ClockMethod {
+    const fn as_str(self) -> &'static str {
+        match self {
+            Self::Adjtimex => "adjtimex",
+            Self::Chronyc => "chronyc",
+        }
+    }
+}
+
+fn default_chronyc_path() -> PathBuf {
+    "chronyc".into()
+}
+
+fn default_namespace() -> String {
+    "clock".to_owned()
+}
+
+impl_generate_config_from_default!(ClockConfig);
+
+#[async_trait::async_trait]
+impl SourceConfig for ClockConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
+        if self.method == ClockMethod::Adjtimex && !cfg!(target_os = "linux") {
+            return Err("The `adjtimex` method is only supported on Linux.".into());
+        }
+        if !(self.scrape_interval_secs > 0.0 && self.scrape_interval_secs < u64::MAX as f64) {
+            return Err("`scrape_interval_secs` must be a positive number of seconds.".into());
+        }
+
+        Ok(Box::pin(
+            Clock {
+                method: self.method,
+                chronyc_path: self.chronyc_path.clone(),
+                namespace: self.namespace.clone(),
+                interval: time::Duration::from_secs_f64(self.scrape_interval_secs),
+                out: cx.out,
+                shutdown: cx.shutdown,
+            }
+            .run(),
+        ))
+    }
+
+    fn outputs(&self, _global_log_namespace: LogNamespace) -> Vec<Output> {
+        vec![Output::default(DataType::Metric)]
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        false
+    }
+}
+
+#[derive(Debug, Snafu)]
+enum ReadError {
+    #[snafu(display("Failed to run chronyc: {}", source))]
+    ChronycSpawn { source: std::io::Error },
+    #[snafu(display("chronyc exited with {}: {}", status, stderr))]
+    ChronycFailed { status: ExitStatus, stderr: String },
+    #[snafu(display("Unexpected chronyc output: {:?}", output))]
+    ChronycOutput { output: String },
+    #[cfg(target_os = "linux")]
+    #[snafu(display("adjtimex failed: {}", source))]
+    Adjtimex { source: std::io::Error },
+}
+
+/// A reading of the clock status, as pairs of metric names and values.
+type Readings = Vec<(&'static str, f64)>;
+
+struct Clock {
+    method: ClockMethod,
+    chronyc_path: PathBuf,
+    namespace: String,
+    interval: time::Duration,
+    out: SourceSender,
+    shutdown: ShutdownSignal,
+}
+
+impl Clock {
+    async fn run(mut self) -> Result<(), ()> {
+        let mut interval =
+            IntervalStream::new(time::interval(self.interval)).take_until(self.shutdown.clone());
+        let bytes_received = register!(BytesReceived::from(Protocol::NONE));
+        while interval.next().await.is_some() {
+            bytes_received.emit(ByteSize(0));
+            let readings = match self.read().await {
+                Ok(readings) => readings,
+                Err(error) => {
+                    emit!(ClockReadError {
+                        method: self.method.as_str(),
+                        error,
+                    });
+                    continue;
+                }
+            };
+
+            let mut tags = MetricTags::new();
+            tags.insert("method".into(), self.method.as_str().into());
+            if let Ok(hostname) = crate::get_hostname() {
+                tags.insert(log_schema().host_key().into(), hostname);
+            }
+            let timestamp = Utc::now();
+            let metrics = readings
+                .into_iter()
+                .map(|(name, value)| {
+                    Metric::new(name, MetricKind::Absolute, MetricValue::Gauge { value })
+                        .with_namespace(Some(self.namespace.clone()))
+                        .with_tags(Some(tags.clone()))
+                        .with_timestamp(Some(timestamp))
+                })
+                .collect::<Vec<_>>();
+
+            let count = metrics.len();
+            emit!(EventsReceived {
+                count,
+                byte_size: metrics.size_of(),
+            });
+
+            if let Err(error) = self.out.send_batch(metrics).await {
+                emit!(StreamClosedError { error, count });
+                return Err(());
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn read(&self) -> Result<Readings, ReadError> {
+        match self.method {
+            ClockMethod::Adjtimex => read_adjtimex(),
+            ClockMethod::Chronyc => {
+                let output = Command::new(&self.chronyc_path)
+                    .args(["-c", "tracking"])
+                    .kill_on_drop(true)
+                    .output()
+                    .await
+                    .context(ChronycSpawnSnafu)?;
+                if !output.status.success() {
+                    return Err(ReadError::ChronycFailed {
+                        status: output.status,
+                        stderr: String::from_utf8_lossy(&output.stderr).trim().to_owned(),
+                    });
+                }
+                parse_chronyc_tracking(&String::from_utf8_lossy(&output.stdout))
+            }
+        }
+    }
+}
+
+const fn bool_to_f64(value: bool) -> f64 {
+    if value {
+        1.0
+    } else {
+        0.0
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn read_adjtimex() -> Result<Readings, ReadError> {
+    // With `modes` zeroed, `adjtimex` only reads the state of the kernel clock into `timex`.
+    let mut timex: libc::timex = unsafe { std::mem::zeroed() };
+    let state = unsafe { libc::adjtimex(&mut timex) };
+    if state == -1 {
+        return Err(std::io::Error::last_os_error()).context(AdjtimexSnafu);
+    }
+
+    let offset_scale = if timex.status & libc::STA_NANO != 0 {
+        1e-9
+    } else {
+        1e-6
+    };
+    Ok(vec![
+        ("offset_seconds", timex.offset as f64 * offset_scale),
+        // The frequency offset is in ppm with a 16-bit fractional part.
+        ("frequency_ppm", timex.freq as f64 / 65536.0),
+        ("max_error_seconds", timex.maxerror as f64 * 1e-6),
+        ("estimated_error_seconds", timex.esterror as f64 * 1e-6),
+        ("synchronized", bool_to_f64(state != libc::TIME_ERROR)),
+    ])
+}
+
+#[cfg(not(target_os = "linux"))]
+fn read_adjtimex() -> Result<Readings, ReadError> {
+    unreachable!("the adjtimex method is rejected when building the source")
+}
+
+/// Parses the output of `chronyc -c tracking`.
+///
+/// The fields are separated by commas, in the order `chronyc tracking` prints them.
+fn parse_chronyc_tracking(output: &str) -> Result<Readings, ReadError> {
+    let invalid = || ReadError::ChronycOutput {
+        output: output.to_owned(),
+    };
+    let fields = output.trim().split(',').collect::<Vec<_>>();
+    if fields.len() < 14 {
+        return Err(invalid());
+    }
+    let field = |index: usize| fields[index].parse::<f64>().map_err(|_| invalid());
+
+    Ok(vec![
+        ("stratum", field(2)?),
+        ("offset_seconds", field(4)?),
+        ("last_offset_seconds", field(5)?),
+        ("rms_offset_seconds", field(6)?),
+        ("frequency_ppm", field(7)?),
+        ("skew_ppm", field(9)?),
+        ("root_delay_seconds", field(10)?),
+        ("root_dispersion_seconds", field(11)?),
+        (
+            "synchronized",
+            bool_to_f64(fields[13] != "Not synchronised"),
+        ),
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<ClockConfig>();
+    }
+
+    #[tokio::test]
+    async fn rejects_invalid_scrape_interval() {
+        for scrape_interval_secs in [0.0, -1.0, f64::NAN, f64::INFINITY] {
+            let config = ClockConfig {
+                method: ClockMethod::Chronyc,
+                scrape_interval_secs,
+                ..Default::default()
+            };
+            let (tx, _rx) = SourceSender::new_test();
+
+            assert!(config
+                .build(SourceContext::new_test(tx, None))
+                .await
+                .is_err());
+        }
+    }
+
+    #[test]
+    fn parses_chronyc_tracking() {
+        let readings = parse_chronyc_tracking(
+            "A29FC87B,162.159.200.123,3,1665138003.465259498,-0.000037428,-0.000019766,\
+             0.000262498,-7.643,-0.001,0.020,0.013614289,0.000742853,1031.4,Normal\n",
+        )
+        .unwrap();
+
+        assert_eq!(
+            readings,
+            vec![
+                ("stratum", 3.0),
+                ("offset_seconds", -0.000037428),
+                ("last_offset_seconds", -0.000019766),
+                ("rms_offset_seconds", 0.000262498),
+                ("frequency_ppm", -7.643),
+                ("skew_ppm", 0.020),
+                ("root_delay_seconds", 0.013614289),
+                ("root_dispersion_seconds", 0.000742853),
+                ("synchronized", 1.0),
+            ]
+        );
+    }
+
+    #[test]
+    fn parses_unsynchronized_chronyc_tracking() {
+        let readings = parse_chronyc_tracking(
+            "00000000,,0,0.000000000,0.000000000,0.000000000,0.000000000,0.000,0.000,0.000,\
+             1.000000000,1.000000000,0.0,Not synchronised\n",
+        )
+        .unwrap();
+
+        assert_eq!(readings.last(), Some(&("synchronized", 0.0)));
+    }
+
+    #[test]
+    fn rejects_invalid_chronyc_output() {
+        assert!(parse_chronyc_tracking("506 Cannot talk to daemon").is_err());
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn reads_adjtimex() {
+        let readings = read_adjtimex().unwrap();
+
+        assert!(readings.iter().any(|(name, _)| *name == "offset_seconds"));
+    }
+}
@@ -0,0 +1,284 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    net::SocketAddr,
+};
+
+use bytes::{Bytes, BytesMut};
+use chrono::Utc;
+use codecs::{
+    decoding::{Deserializer, Framer},
+    JsonDeserializer, NewlineDelimitedDecoder, StreamDecodingError,
+};
+use tokio_util::codec::Decoder as _;
+use vector_config::configurable_component;
+use vector_core::config::LogNamespace;
+use warp::http::HeaderMap;
+
+use crate::{
+    codecs::Decoder,
+    config::{
+        log_schema, AcknowledgementsConfig, DataType, GenerateConfig, Output, Resource,
+        SourceConfig, SourceContext,
+    },
+    event::{Event, Value},
+    serde::bool_or_struct,
+    sources::{
+        http::HttpMethod,
+        util::{decode, ErrorMessage, HttpSource, HttpSourceAuthConfig},
+    },
+    tls::TlsEnableableConfig,
+};
+
+/// The body of the request Logpush sends to validate a destination when a job is created.
+const VALIDATION_BODY: &str = r#"{"content":"tests"}"#;
+
+/// The prefix of the headers mapped into the `logpush` field of the events.
+const METADATA_HEADER_PREFIX: &str = "x-logpush-";
+
+/// The magic bytes gzip streams start with.
+const GZIP_MAGIC: &[u8] = &[0x1f, 0x8b];
+
+/// Configuration for the `cloudflare_logpush` source.
+#[configurable_component(source("cloudflare_logpush"))]
+#[configurable(metadata(status = "alpha"))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct LogpushConfig {
+    /// The address to listen for connections on.
+    address: SocketAddr,
+
+    /// The URL path on which Logpush jobs deliver logs.
+    #[serde(default = "default_path")]
+    path: String,
+
+    #[configurable(derived)]
+    tls: Option<TlsEnableableConfig>,
+
+    #[configurable(derived)]
+    auth: Option<HttpSourceAuthConfig>,
+
+    #[configurable(derived)]
+    #[serde(default, deserialize_with = "bool_or_struct")]
+    acknowledgements: AcknowledgementsConfig,
+}
+
+fn default_path() -> String {
+    "/".to_string()
+}
+
+impl GenerateConfig for LogpushConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self {
+            address: "0.0.0.0:80".parse().unwrap(),
+            path: default_path(),
+            tls: None,
+            auth: None,
+            acknowledgements: AcknowledgementsConfig::default(),
+        })
+        .unwrap()
+    }
+}
+
+#[derive(Clone)]
+struct LogpushSource {
+    decoder: Decoder,
+}
+
+impl HttpSource for LogpushSource {
+    fn build_events(
+        &self,
+        body: Bytes,
+        header_map: HeaderMap,
+        _query_parameters: HashMap<String, String>,
+        _full_path: &str,
+    ) -> Result<Vec<Event>, ErrorMessage> {
+        // Logpush compresses the files it delivers, but doesn't always announce it with a
+        // `Content-Encoding` header.
+        let body = if body.starts_with(GZIP_MAGIC) {
+            decode(&Some("gzip".to_owned()), body)?
+        } else {
+            body
+        };
+
+        if std::str::from_utf8(&body).map(str::trim) == Ok(VALIDATION_BODY) {
+            debug!(message = "Received Logpush destination validation request.");
+            return Ok(Vec::new());
+        }
+
+        let metadata = metadata_from_headers(&header_map);
+        let now = Utc::now();
+        let mut decoder = self.decoder.clone();
+        let mut buffer = BytesMut::from(&body[..]);
+        let mut events = Vec::new();
+        loop {
+            match decoder.decode_eof(&mut buffer) {
+                Ok(Some((decoded, _byte_size))) => events.extend(decoded),
+                Ok(None) => break,
+                Err(error) => {
+                    // Error is logged by `crate::codecs::Decoder`, no further handling is needed
+                    // here.
+                    if !error.can_continue() {
+                        break;
+                    }
+                }
+            }
+        }
+
+        for event in &mut events {
+            if let Event::Log(log) = event {
+                log.try_insert(
+                    log_schema().source_type_key(),
+                    Bytes::from("cloudflare_logpush"),
+                );
+                log.try_insert(log_schema().timestamp_key(), now);
+                if let Some(metadata) = &metadata {
+                    log.insert("logpush", metadata.clone());
+                }
+            }
+        }
+        Ok(events)
+    }
+}
+
+/// Collects the `X-Logpush-*` headers, set with the `header_*` parameters of the destination of a
+/// Logpush job, into an object keyed by the snake case rest of their names.
+fn metadata_from_headers(header_map: &HeaderMap) -> Option<Value> {
+    let metadata = header_map
+        .iter()
+        .filter_map(|(name, value)| {
+            let key = name.as_str().strip_prefix(METADATA_HEADER_PREFIX)?;
+            Some((
+                key.replace('-', "_"),
+                Value::from(Bytes::copy_from_slice(value.as_bytes())),
+            ))
+        })
+        .collect::<BTreeMap<_, _>>();
+    (!metadata.is_empty()).then(|| Value::Object(metadata))
+}
+
+#[async_trait::async_trait]
+impl SourceConfig for LogpushConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
+        let source = LogpushSource {
+            decoder: Decoder::new(
+                Framer::NewlineDelimited(NewlineDelimitedDecoder::new()),
+                Deserializer::Json(JsonDeserializer::new()),
+            ),
+        };
+        source.run(
+            self.address,
+            &self.path,
+            HttpMethod::Post,
+            true,
+            &self.tls,
+            &self.auth,
+            cx,
+            self.acknowledgements,
+        )
+    }
+
+    fn outputs(&self, _global_log_namespace: LogNamespace) -> Vec<Output> {
+        vec![Output::default(DataType::Log)]
+    }
+
+    fn resources(&self) -> Vec<Resource> {
+        vec![Resource::tcp(self.address)]
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use flate2::{write::GzEncoder, Compression};
+    use warp::http::HeaderValue;
+
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<LogpushConfig>();
+    }
+
+    fn source() -> LogpushSource {
+        LogpushSource {
+            decoder: Decoder::new(
+                Framer::NewlineDelimited(NewlineDelimitedDecoder::new()),
+                Deserializer::Json(JsonDeserializer::new()),
+            ),
+        }
+    }
+
+    fn gzip(body: &[u8]) -> Bytes {
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(body).unwrap();
+        encoder.finish().unwrap().into()
+    }
+
+    #[test]
+    fn decodes_gzipped_ndjson() {
+        let body = gzip(
+            concat!(
+                r#"{"ClientIP":"192.0.2.1","EdgeResponseStatus":200}"#,
+                "\n",
+                r#"{"ClientIP":"192.0.2.2","EdgeResponseStatus":404}"#,
+                "\n",
+            )
+            .as_bytes(),
+        );
+
+        let events = source()
+            .build_events(body, HeaderMap::new(), HashMap::new(), "/")
+            .unwrap();
+
+        assert_eq!(events.len(), 2);
+        let log = events[1].as_log();
+        assert_eq!(log["ClientIP"], "192.0.2.2".into());
+        assert_eq!(log["EdgeResponseStatus"], 404.into());
+        assert_eq!(log["source_type"], "cloudflare_logpush".into());
+        assert!(log.get("logpush").is_none());
+    }
+
+    #[test]
+    fn answers_validation_requests() {
+        let events = source()
+            .build_events(
+                gzip(VALIDATION_BODY.as_bytes()),
+                HeaderMap::new(),
+                HashMap::new(),
+                "/",
+            )
+            .unwrap();
+
+        assert!(events.is_empty());
+    }
+
+    #[test]
+    fn maps_metadata_headers() {
+        let mut headers = HeaderMap::new();
+        headers.insert("X-Logpush-Job-Name", HeaderValue::from_static("requests"));
+        headers.insert(
+            "X-Logpush-Dataset",
+            HeaderValue::from_static("http_requests"),
+        );
+        headers.insert("User-Agent", HeaderValue::from_static("Go-http-client/2.0"));
+
+        let events = source()
+            .build_events(
+                Bytes::from_static(br#"{"RayID":"7f0a"}"#),
+                headers,
+                HashMap::new(),
+                "/",
+            )
+            .unwrap();
+
+        let log = events[0].as_log();
+        assert_eq!(log["logpush.job_name"], "requests".into());
+        assert_eq!(log["logpush.dataset"], "http_requests".into());
+        assert_eq!(log.get("logpush").unwrap().as_object().unwrap().len(), 2);
+    }
+}
@@ -244,7 +244,7 @@ impl SourceConfig for DatadogAgentConfig {
             )
             .with_standard_vector_source_metadata();
 
-        if self.multiple_outputs {
+        let outputs = if self.multiple_outputs {
             vec![
                 Output::default(DataType::Metric).with_port(METRICS),
                 Output::default(DataType::Log)
@@ -254,7 +254,8 @@ impl SourceConfig for DatadogAgentConfig {
             ]
         } else {
             vec![Output::default(DataType::all()).with_schema_definition(definition)]
-        }
+        };
+        self.decoding.with_errors_output(outputs)
     }
 
     fn resources(&self) -> Vec<Resource> {
@@ -395,6 +396,12 @@ impl DatadogAgentSource {
         mut body: Bytes,
         path: &str,
     ) -> Result<Bytes, ErrorMessage> {
+        emit!(HttpBytesReceived {
+            byte_size: body.len(),
+            http_path: path,
+            protocol: self.protocol,
+        });
+
         if let Some(encodings) = header {
             for encoding in encodings.rsplit(',').map(str::trim) {
                 body = match encoding {
@@ -422,11 +429,6 @@ impl DatadogAgentSource {
                 }
             }
         }
-        emit!(HttpBytesReceived {
-            byte_size: body.len(),
-            http_path: path,
-            protocol: self.protocol,
-        });
         Ok(body)
     }
 }
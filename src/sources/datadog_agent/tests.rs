@@ -8,7 +8,7 @@ use std::{
 use bytes::Bytes;
 use chrono::{TimeZone, Utc};
 use codecs::{
-    decoding::{Deserializer, DeserializerConfig, Framer},
+    decoding::{Deserializer, DeserializerCodec, DeserializerConfig, Framer},
     BytesDecoder, BytesDeserializer,
 };
 use futures::{Stream, StreamExt};
@@ -1342,7 +1342,7 @@ fn test_config_outputs() {
         (
             "bytes / single output",
             TestCase {
-                decoding: DeserializerConfig::Bytes,
+                decoding: DeserializerCodec::Bytes.into(),
                 multiple_outputs: false,
                 want: HashMap::from([(
                     None,
@@ -1363,7 +1363,7 @@ fn test_config_outputs() {
         (
             "bytes / multiple output",
             TestCase {
-                decoding: DeserializerConfig::Bytes,
+                decoding: DeserializerCodec::Bytes.into(),
                 multiple_outputs: true,
                 want: HashMap::from([
                     (
@@ -1388,7 +1388,7 @@ fn test_config_outputs() {
         (
             "json / single output",
             TestCase {
-                decoding: DeserializerConfig::Json,
+                decoding: DeserializerCodec::Json.into(),
                 multiple_outputs: false,
                 want: HashMap::from([(
                     None,
@@ -1410,7 +1410,7 @@ fn test_config_outputs() {
         (
             "json / multiple output",
             TestCase {
-                decoding: DeserializerConfig::Json,
+                decoding: DeserializerCodec::Json.into(),
                 multiple_outputs: true,
                 want: HashMap::from([
                     (
@@ -1437,7 +1437,7 @@ fn test_config_outputs() {
         (
             "syslog / single output",
             TestCase {
-                decoding: DeserializerConfig::Syslog,
+                decoding: DeserializerCodec::Syslog.into(),
                 multiple_outputs: false,
                 want: HashMap::from([(
                     None,
@@ -1488,7 +1488,7 @@ fn test_config_outputs() {
         (
             "syslog / multiple output",
             TestCase {
-                decoding: DeserializerConfig::Syslog,
+                decoding: DeserializerCodec::Syslog.into(),
                 multiple_outputs: true,
                 want: HashMap::from([
                     (
@@ -277,7 +277,9 @@ impl SourceConfig for DemoLogsConfig {
             .schema_definition(log_namespace)
             .with_standard_vector_source_metadata();
 
-        vec![Output::default(self.decoding.output_type()).with_schema_definition(schema_definition)]
+        self.decoding
+            .with_errors_output(vec![Output::default(self.decoding.output_type())
+                .with_schema_definition(schema_definition)])
     }
 
     fn can_acknowledge(&self) -> bool {
@@ -0,0 +1,513 @@
+use std::{collections::BTreeMap, net::SocketAddr};
+
+use bytes::Bytes;
+use chrono::{TimeZone, Utc};
+use futures::TryFutureExt;
+use tonic::{Request, Response, Status, Streaming};
+use vector_config::configurable_component;
+use vector_core::{
+    config::{log_schema, LogNamespace},
+    event::{BatchNotifier, BatchStatus, BatchStatusReceiver, Event, LogEvent, Value},
+    ByteSizeOf,
+};
+
+use crate::{
+    config::{
+        AcknowledgementsConfig, DataType, GenerateConfig, Output, Resource, SourceConfig,
+        SourceContext,
+    },
+    internal_events::{EventsReceived, StreamClosedError},
+    serde::bool_or_struct,
+    sources::{util::grpc::run_grpc_server, Source},
+    tls::{MaybeTlsSettings, TlsEnableableConfig},
+    SourceSender,
+};
+
+// prost emits some generated code that includes clones on `Arc`
+// objects, which causes a clippy ding on this block. We don't
+// directly control the generated code, so allow this lint here.
+#[allow(clippy::clone_on_ref_ptr)]
+#[allow(warnings)]
+mod proto {
+    include!(concat!(env!("OUT_DIR"), "/envoy.service.accesslog.v3.rs"));
+}
+
+use proto::{
+    access_log_service_server::{AccessLogService, AccessLogServiceServer},
+    address, socket_address,
+    stream_access_logs_message::{Identifier, LogEntries},
+    AccessLogCommon, Address, HttpAccessLogEntry, StreamAccessLogsMessage,
+    StreamAccessLogsResponse, TcpAccessLogEntry,
+};
+
+/// Configuration for the `envoy_als` source.
+#[configurable_component(source("envoy_als"))]
+#[configurable(metadata(status = "alpha"))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct EnvoyAlsConfig {
+    /// The address to listen for gRPC connections on.
+    ///
+    /// It _must_ include a port.
+    address: SocketAddr,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    tls: Option<TlsEnableableConfig>,
+
+    #[configurable(derived)]
+    #[serde(default, deserialize_with = "bool_or_struct")]
+    acknowledgements: AcknowledgementsConfig,
+}
+
+impl GenerateConfig for EnvoyAlsConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self {
+            address: "0.0.0.0:9001".parse().unwrap(),
+            tls: None,
+            acknowledgements: Default::default(),
+        })
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+impl SourceConfig for EnvoyAlsConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<Source> {
+        let tls_settings = MaybeTlsSettings::from_config(&self.tls, true)?;
+        let acknowledgements = cx.do_acknowledgements(&self.acknowledgements);
+        let service = AccessLogServiceServer::new(Service {
+            pipeline: cx.out,
+            acknowledgements,
+        })
+        .accept_compressed(tonic::codec::CompressionEncoding::Gzip);
+
+        let source =
+            run_grpc_server(self.address, tls_settings, service, cx.shutdown).map_err(|error| {
+                error!(message = "Source future failed.", %error);
+            });
+
+        Ok(Box::pin(source))
+    }
+
+    fn outputs(&self, _global_log_namespace: LogNamespace) -> Vec<Output> {
+        vec![Output::default(DataType::Log)]
+    }
+
+    fn resources(&self) -> Vec<Resource> {
+        vec![Resource::tcp(self.address)]
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        true
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Service {
+    pipeline: SourceSender,
+    acknowledgements: bool,
+}
+
+#[tonic::async_trait]
+impl AccessLogService for Service {
+    async fn stream_access_logs(
+        &self,
+        request: Request<Streaming<StreamAccessLogsMessage>>,
+    ) -> Result<Response<StreamAccessLogsResponse>, Status> {
+        let mut stream = request.into_inner();
+
+        // Envoy only sends the identifier with the first message of each stream.
+        let mut identifier = None;
+        while let Some(message) = stream.message().await? {
+            if message.identifier.is_some() {
+                identifier = message.identifier;
+            }
+
+            let mut events = decode_message(identifier.as_ref(), message.log_entries);
+            if events.is_empty() {
+                continue;
+            }
+
+            let count = events.len();
+            let byte_size = events.size_of();
+
+            emit!(EventsReceived { count, byte_size });
+
+            let receiver = BatchNotifier::maybe_apply_to(self.acknowledgements, &mut events);
+
+            self.pipeline
+                .clone()
+                .send_batch(events)
+                .map_err(|error| {
+                    let message = error.to_string();
+                    emit!(StreamClosedError { error, count });
+                    Status::unavailable(message)
+                })
+                .and_then(|_| handle_batch_status(receiver))
+                .await?;
+        }
+
+        Ok(Response::new(StreamAccessLogsResponse {}))
+    }
+}
+
+async fn handle_batch_status(receiver: Option<BatchStatusReceiver>) -> Result<(), Status> {
+    let status = match receiver {
+        Some(receiver) => receiver.await,
+        None => BatchStatus::Delivered,
+    };
+
+    match status {
+        BatchStatus::Errored => Err(Status::internal("Delivery error")),
+        BatchStatus::Rejected => Err(Status::data_loss("Delivery failed")),
+        BatchStatus::Delivered => Ok(()),
+    }
+}
+
+fn decode_message(identifier: Option<&Identifier>, entries: Option<LogEntries>) -> Vec<Event> {
+    match entries {
+        Some(LogEntries::HttpLogs(logs)) => logs
+            .log_entry
+            .into_iter()
+            .map(|entry| decode_http_entry(identifier, entry).into())
+            .collect(),
+        Some(LogEntries::TcpLogs(logs)) => logs
+            .log_entry
+            .into_iter()
+            .map(|entry| decode_tcp_entry(identifier, entry).into())
+            .collect(),
+        None => Vec::new(),
+    }
+}
+
+fn decode_http_entry(identifier: Option<&Identifier>, entry: HttpAccessLogEntry) -> LogEvent {
+    let mut log = new_log("http", identifier, entry.common_properties);
+
+    if let Some(version) = http_version(entry.protocol_version) {
+        log.insert("protocol_version", version);
+    }
+
+    if let Some(request) = entry.request {
+        let mut fields = BTreeMap::new();
+        if let Some(method) = request_method(request.request_method) {
+            fields.insert("method".to_string(), Value::from(method));
+        }
+        insert_string(&mut fields, "scheme", request.scheme);
+        insert_string(&mut fields, "authority", request.authority);
+        if let Some(port) = request.port {
+            fields.insert("port".to_string(), Value::from(port.value));
+        }
+        insert_string(&mut fields, "path", request.path);
+        insert_string(&mut fields, "original_path", request.original_path);
+        insert_string(&mut fields, "user_agent", request.user_agent);
+        insert_string(&mut fields, "referer", request.referer);
+        insert_string(&mut fields, "forwarded_for", request.forwarded_for);
+        insert_string(&mut fields, "request_id", request.request_id);
+        fields.insert(
+            "headers_bytes".to_string(),
+            Value::from(request.request_headers_bytes),
+        );
+        fields.insert(
+            "body_bytes".to_string(),
+            Value::from(request.request_body_bytes),
+        );
+        insert_map(&mut fields, "headers", request.request_headers);
+        log.insert("request", fields);
+    }
+
+    if let Some(response) = entry.response {
+        let mut fields = BTreeMap::new();
+        if let Some(code) = response.response_code {
+            fields.insert("code".to_string(), Value::from(code.value));
+        }
+        insert_string(&mut fields, "code_details", response.response_code_details);
+        fields.insert(
+            "headers_bytes".to_string(),
+            Value::from(response.response_headers_bytes),
+        );
+        fields.insert(
+            "body_bytes".to_string(),
+            Value::from(response.response_body_bytes),
+        );
+        insert_map(&mut fields, "headers", response.response_headers);
+        insert_map(&mut fields, "trailers", response.response_trailers);
+        log.insert("response", fields);
+    }
+
+    log
+}
+
+fn decode_tcp_entry(identifier: Option<&Identifier>, entry: TcpAccessLogEntry) -> LogEvent {
+    let mut log = new_log("tcp", identifier, entry.common_properties);
+
+    if let Some(connection) = entry.connection_properties {
+        log.insert("connection.received_bytes", connection.received_bytes);
+        log.insert("connection.sent_bytes", connection.sent_bytes);
+    }
+
+    log
+}
+
+fn new_log(
+    access_log_type: &'static str,
+    identifier: Option<&Identifier>,
+    common: Option<AccessLogCommon>,
+) -> LogEvent {
+    let mut log = LogEvent::default();
+    log.insert(log_schema().source_type_key(), Bytes::from("envoy_als"));
+    log.insert("access_log_type", access_log_type);
+
+    if let Some(identifier) = identifier {
+        if !identifier.log_name.is_empty() {
+            log.insert("log_name", identifier.log_name.clone());
+        }
+        if let Some(node) = &identifier.node {
+            log.insert("node.id", node.id.clone());
+            log.insert("node.cluster", node.cluster.clone());
+        }
+    }
+
+    let timestamp = common
+        .as_ref()
+        .and_then(|common| common.start_time.as_ref())
+        .and_then(|ts| Utc.timestamp_opt(ts.seconds, ts.nanos as u32).single())
+        .unwrap_or_else(Utc::now);
+    log.insert(log_schema().timestamp_key(), timestamp);
+
+    if let Some(common) = common {
+        let mut fields = BTreeMap::new();
+        fields.insert("sample_rate".to_string(), Value::from(common.sample_rate));
+        insert_address(
+            &mut fields,
+            "downstream_remote_address",
+            common.downstream_remote_address,
+        );
+        insert_address(
+            &mut fields,
+            "downstream_local_address",
+            common.downstream_local_address,
+        );
+        insert_address(
+            &mut fields,
+            "upstream_remote_address",
+            common.upstream_remote_address,
+        );
+        insert_address(
+            &mut fields,
+            "upstream_local_address",
+            common.upstream_local_address,
+        );
+        insert_string(&mut fields, "upstream_cluster", common.upstream_cluster);
+        insert_string(
+            &mut fields,
+            "upstream_transport_failure_reason",
+            common.upstream_transport_failure_reason,
+        );
+        insert_string(&mut fields, "route_name", common.route_name);
+        insert_string(
+            &mut fields,
+            "connection_termination_details",
+            common.connection_termination_details,
+        );
+        insert_string(&mut fields, "stream_id", common.stream_id);
+        fields.insert(
+            "upstream_request_attempt_count".to_string(),
+            Value::from(common.upstream_request_attempt_count),
+        );
+        if let Some(duration) = common.duration {
+            let millis = duration.seconds as f64 * 1e3 + f64::from(duration.nanos) / 1e6;
+            fields.insert("duration_ms".to_string(), Value::from(millis));
+        }
+        if let Some(tls) = common.tls_properties {
+            if let Some(version) = tls_version(tls.tls_version) {
+                fields.insert("tls_version".to_string(), Value::from(version));
+            }
+            insert_string(&mut fields, "tls_sni_hostname", tls.tls_sni_hostname);
+        }
+        insert_map(&mut fields, "custom_tags", common.custom_tags);
+        log.insert("common", fields);
+    }
+
+    log
+}
+
+fn insert_string(fields: &mut BTreeMap<String, Value>, key: &str, value: String) {
+    if !value.is_empty() {
+        fields.insert(key.to_string(), Value::from(value));
+    }
+}
+
+fn insert_map(fields: &mut BTreeMap<String, Value>, key: &str, map: BTreeMap<String, String>) {
+    if !map.is_empty() {
+        let map = map
+            .into_iter()
+            .map(|(key, value)| (key, Value::from(value)))
+            .collect::<BTreeMap<_, _>>();
+        fields.insert(key.to_string(), Value::from(map));
+    }
+}
+
+fn insert_address(fields: &mut BTreeMap<String, Value>, key: &str, value: Option<Address>) {
+    let address = match value.and_then(|address| address.address) {
+        Some(address::Address::SocketAddress(socket)) => match socket.port_specifier {
+            Some(socket_address::PortSpecifier::PortValue(port)) => {
+                format!("{}:{}", socket.address, port)
+            }
+            Some(socket_address::PortSpecifier::NamedPort(port)) => {
+                format!("{}:{}", socket.address, port)
+            }
+            None => socket.address,
+        },
+        Some(address::Address::Pipe(pipe)) => pipe.path,
+        None => return,
+    };
+    fields.insert(key.to_string(), Value::from(address));
+}
+
+const fn http_version(version: i32) -> Option<&'static str> {
+    match version {
+        1 => Some("HTTP/1.0"),
+        2 => Some("HTTP/1.1"),
+        3 => Some("HTTP/2"),
+        4 => Some("HTTP/3"),
+        _ => None,
+    }
+}
+
+const fn request_method(method: i32) -> Option<&'static str> {
+    match method {
+        1 => Some("GET"),
+        2 => Some("HEAD"),
+        3 => Some("POST"),
+        4 => Some("PUT"),
+        5 => Some("DELETE"),
+        6 => Some("CONNECT"),
+        7 => Some("OPTIONS"),
+        8 => Some("TRACE"),
+        9 => Some("PATCH"),
+        _ => None,
+    }
+}
+
+const fn tls_version(version: i32) -> Option<&'static str> {
+    match version {
+        1 => Some("TLSv1"),
+        2 => Some("TLSv1.1"),
+        3 => Some("TLSv1.2"),
+        4 => Some("TLSv1.3"),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proto::{
+        stream_access_logs_message::{HttpAccessLogEntries, TcpAccessLogEntries},
+        ConnectionProperties, HttpRequestProperties, HttpResponseProperties, Node, SocketAddress,
+        UInt32Value,
+    };
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<EnvoyAlsConfig>();
+    }
+
+    fn identifier() -> Identifier {
+        Identifier {
+            node: Some(Node {
+                id: "sidecar~10.0.0.1".into(),
+                cluster: "frontend".into(),
+            }),
+            log_name: "als".into(),
+        }
+    }
+
+    fn common() -> AccessLogCommon {
+        AccessLogCommon {
+            downstream_remote_address: Some(Address {
+                address: Some(address::Address::SocketAddress(SocketAddress {
+                    address: "10.0.0.2".into(),
+                    port_specifier: Some(socket_address::PortSpecifier::PortValue(51234)),
+                })),
+            }),
+            start_time: Some(prost_types::Timestamp {
+                seconds: 1_600_000_000,
+                nanos: 0,
+            }),
+            upstream_cluster: "backend".into(),
+            duration: Some(proto::Duration {
+                seconds: 1,
+                nanos: 500_000_000,
+            }),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn decodes_http_entries() {
+        let entries = LogEntries::HttpLogs(HttpAccessLogEntries {
+            log_entry: vec![HttpAccessLogEntry {
+                common_properties: Some(common()),
+                protocol_version: 2,
+                request: Some(HttpRequestProperties {
+                    request_method: 1,
+                    authority: "example.com".into(),
+                    path: "/index.html".into(),
+                    ..Default::default()
+                }),
+                response: Some(HttpResponseProperties {
+                    response_code: Some(UInt32Value { value: 200 }),
+                    response_body_bytes: 1024,
+                    ..Default::default()
+                }),
+            }],
+        });
+
+        let events = decode_message(Some(&identifier()), Some(entries));
+        assert_eq!(events.len(), 1);
+        let log = events[0].as_log();
+
+        assert_eq!(log["access_log_type"], "http".into());
+        assert_eq!(log["log_name"], "als".into());
+        assert_eq!(log["node.cluster"], "frontend".into());
+        assert_eq!(log["protocol_version"], "HTTP/1.1".into());
+        assert_eq!(log["request.method"], "GET".into());
+        assert_eq!(log["request.path"], "/index.html".into());
+        assert_eq!(log["response.code"], 200.into());
+        assert_eq!(log["response.body_bytes"], 1024.into());
+        assert_eq!(
+            log["common.downstream_remote_address"],
+            "10.0.0.2:51234".into()
+        );
+        assert_eq!(log["common.upstream_cluster"], "backend".into());
+        assert_eq!(log["common.duration_ms"], 1500.0.into());
+        assert_eq!(
+            log[log_schema().timestamp_key()],
+            Utc.timestamp(1_600_000_000, 0).into()
+        );
+    }
+
+    #[test]
+    fn decodes_tcp_entries() {
+        let entries = LogEntries::TcpLogs(TcpAccessLogEntries {
+            log_entry: vec![TcpAccessLogEntry {
+                common_properties: Some(common()),
+                connection_properties: Some(ConnectionProperties {
+                    received_bytes: 10,
+                    sent_bytes: 20,
+                }),
+            }],
+        });
+
+        let events = decode_message(None, Some(entries));
+        assert_eq!(events.len(), 1);
+        let log = events[0].as_log();
+
+        assert_eq!(log["access_log_type"], "tcp".into());
+        assert!(!log.contains("log_name"));
+        assert_eq!(log["connection.received_bytes"], 10.into());
+        assert_eq!(log["connection.sent_bytes"], 20.into());
+    }
+}
@@ -256,7 +256,8 @@ impl SourceConfig for ExecConfig {
     }
 
     fn outputs(&self, _global_log_namespace: LogNamespace) -> Vec<Output> {
-        vec![Output::default(self.decoding.output_type())]
+        self.decoding
+            .with_errors_output(vec![Output::default(self.decoding.output_type())])
     }
 
     fn can_acknowledge(&self) -> bool {
@@ -1,28 +1,38 @@
-use std::{convert::TryInto, path::PathBuf, time::Duration};
+use std::{
+    collections::HashMap,
+    convert::TryInto,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
+};
 
-use bytes::Bytes;
+use bytes::{Bytes, BytesMut};
 use chrono::Utc;
+use codecs::decoding::FramingConfig;
 use file_source::{
     paths_provider::glob::{Glob, MatchOptions},
     Checkpointer, FileFingerprint, FileServer, FingerprintStrategy, Fingerprinter, Line, ReadFrom,
+    CHECKPOINT_FILE_NAME,
 };
 use futures::{FutureExt, Stream, StreamExt, TryFutureExt};
 use regex::bytes::Regex;
 use snafu::{ResultExt, Snafu};
 use tokio::{sync::oneshot, task::spawn_blocking};
+use tokio_util::codec::Decoder as _;
 use tracing::{Instrument, Span};
 use vector_common::finalizer::OrderedFinalizer;
 use vector_config::configurable_component;
 use vector_core::config::LogNamespace;
 
-use super::util::{EncodingConfig, MultilineConfig};
+use super::util::{EncodingConfig, LogPreset, MultilineConfig, PresetParser};
 use crate::{
+    checkpoint::{CheckpointStoreConfig, MirroredCheckpointFile},
     config::{log_schema, AcknowledgementsConfig, DataType, Output, SourceConfig, SourceContext},
     encoding_transcode::{Decoder, Encoder},
     event::{BatchNotifier, BatchStatus, LogEvent},
     internal_events::{
-        FileBytesReceived, FileEventsReceived, FileOpen, FileSourceInternalEventsEmitter,
-        StreamClosedError,
+        DecoderFramingError, FileBytesReceived, FileEventsReceived, FileOpen,
+        FileSourceInternalEventsEmitter, StreamClosedError,
     },
     line_agg::{self, LineAgg},
     serde::bool_or_struct,
@@ -57,6 +67,8 @@ enum BuildError {
         indicator: String,
         source: regex::Error,
     },
+    #[snafu(display("framing can't be used along with multiline aggregation"))]
+    FramingWithMultiline,
 }
 
 /// Configuration for the `file` source.
@@ -117,6 +129,13 @@ pub struct FileConfig {
     /// By default, the global `data_dir` option is used. Please make sure the user Vector is running as has write permissions to this directory.
     pub data_dir: Option<PathBuf>,
 
+    /// Where copies of the file checkpoint positions are stored.
+    ///
+    /// Checkpoints are always persisted to the data directory of the source. With a remote store, they are also copied to it, and restored from it when the data directory has none, so that the source resumes where it left off when running on ephemeral nodes.
+    #[configurable(derived)]
+    #[serde(default)]
+    pub checkpoint_store: CheckpointStoreConfig,
+
     /// Enables adding the file offset to each event and sets the name of the log field used.
     ///
     /// The value will be the byte offset of the start of the line within the file.
@@ -156,6 +175,13 @@ pub struct FileConfig {
     /// If not specified, multiline aggregation is disabled.
     pub multiline: Option<MultilineConfig>,
 
+    /// Framing of the lines of the files into messages, such as `json_stream` to read
+    /// pretty-printed JSON documents spanning multiple lines.
+    ///
+    /// The lines of each file are passed to the framing followed by a newline. If not specified,
+    /// each line is a message. Can't be used along with multiline aggregation.
+    pub framing: Option<FramingConfig>,
+
     /// An approximate limit on the amount of data read from a single file at a given time.
     pub max_read_bytes: usize,
 
@@ -168,12 +194,33 @@ pub struct FileConfig {
     #[serde(alias = "remove_after")]
     pub remove_after_secs: Option<u64>,
 
+    /// Detect files rotated by copying them under another name and truncating them, as done by
+    /// the `copytruncate` option of `logrotate`.
+    ///
+    /// Truncated files are read from their beginning again, rather than from where they were read
+    /// up to. With the `checksum` and `inode_checksum` fingerprinting strategies, the copies of
+    /// truncated files are also read from where the files were read up to before their truncation,
+    /// rather than from their beginning.
+    pub copytruncate: bool,
+
+    /// Read the files in batches through `io_uring`, rather than one at a time.
+    ///
+    /// This reduces the number of system calls made when tailing many files, most of which have no
+    /// new data most of the time. Only supported on Linux 5.1 and later, the files are read one at
+    /// a time when `io_uring` isn't available.
+    pub io_uring: bool,
+
     /// String sequence used to separate one file line from another.
     pub line_delimiter: String,
 
     #[configurable(derived)]
     pub encoding: Option<EncodingConfig>,
 
+    /// The format the lines of the files are parsed from into fields.
+    ///
+    /// If not specified, each line is the `message` of its event.
+    pub preset: Option<LogPreset>,
+
     #[configurable(derived)]
     #[serde(default, deserialize_with = "bool_or_struct")]
     acknowledgements: AcknowledgementsConfig,
@@ -212,6 +259,26 @@ pub enum FingerprintConfig {
     /// Use the [device and inode](https://en.wikipedia.org/wiki/Inode) as the identifier.
     #[serde(rename = "device_and_inode")]
     DevInode,
+
+    /// Use the [inode](https://en.wikipedia.org/wiki/Inode) and a checksum of the first bytes of
+    /// the file as the identifier.
+    ///
+    /// Unlike `device_and_inode`, the identifier doesn't depend on the device number, which can
+    /// change when NFS shares are mounted again, and the checksum tells apart files reusing the
+    /// inode of deleted files.
+    InodeChecksum {
+        /// The number of bytes to compute the checksum over.
+        ///
+        /// Files smaller than this, after the ignored header bytes, aren't read until they grow.
+        #[serde(default = "default_checksum_bytes")]
+        checksum_bytes: usize,
+
+        /// The number of bytes to skip ahead (or ignore) when reading the data used for generating the checksum.
+        ///
+        /// This can be helpful if all files share a common header that should be skipped.
+        #[serde(default)]
+        ignored_header_bytes: usize,
+    },
 }
 
 /// File position to use when reading a new file.
@@ -257,6 +324,13 @@ impl From<FingerprintConfig> for FingerprintStrategy {
                 }
             }
             FingerprintConfig::DevInode => FingerprintStrategy::DevInode,
+            FingerprintConfig::InodeChecksum {
+                checksum_bytes,
+                ignored_header_bytes,
+            } => FingerprintStrategy::InodeChecksum {
+                bytes: checksum_bytes,
+                ignored_header_bytes,
+            },
         }
     }
 }
@@ -269,6 +343,10 @@ const fn default_lines() -> usize {
     1
 }
 
+const fn default_checksum_bytes() -> usize {
+    256
+}
+
 #[derive(Debug)]
 pub(crate) struct FinalizerEntry {
     pub(crate) file_id: FileFingerprint,
@@ -295,15 +373,20 @@ impl Default for FileConfig {
             host_key: None,
             offset_key: None,
             data_dir: None,
+            checkpoint_store: CheckpointStoreConfig::default(),
             glob_minimum_cooldown_ms: 1000, // millis
             message_start_indicator: None,
             multi_line_timeout: 1000, // millis
             multiline: None,
+            framing: None,
             max_read_bytes: 2048,
             oldest_first: false,
             remove_after_secs: None,
+            copytruncate: false,
+            io_uring: false,
             line_delimiter: "\n".to_string(),
             encoding: None,
+            preset: None,
             acknowledgements: Default::default(),
         }
     }
@@ -334,17 +417,49 @@ impl SourceConfig for FileConfig {
                 Regex::new(indicator)
                     .with_context(|_| InvalidMessageStartIndicatorSnafu { indicator })?;
             }
+
+            if self.framing.is_some()
+                && (self.multiline.is_some() || self.message_start_indicator.is_some())
+            {
+                return Err(Box::new(BuildError::FramingWithMultiline));
+            }
         }
 
+        let mirrored_checkpoints = if self.checkpoint_store.is_local() {
+            None
+        } else {
+            let store = self
+                .checkpoint_store
+                .build(
+                    &cx.key,
+                    &cx.globals,
+                    self.data_dir.as_ref(),
+                    CHECKPOINT_FILE_NAME,
+                    &cx.proxy,
+                )
+                .await?;
+            let mirrored = MirroredCheckpointFile::new(store, data_dir.join(CHECKPOINT_FILE_NAME));
+            mirrored.restore().await?;
+            Some(mirrored)
+        };
+
         let acknowledgements = cx.do_acknowledgements(&self.acknowledgements);
 
-        Ok(file_source(
+        let on_checkpointed = mirrored_checkpoints
+            .as_ref()
+            .map(MirroredCheckpointFile::on_written);
+        let source = file_source(
             self,
             data_dir,
             cx.shutdown,
             cx.out,
             acknowledgements,
-        ))
+            on_checkpointed,
+        );
+        Ok(match mirrored_checkpoints {
+            Some(mirrored) => mirrored.mirror(source),
+            None => source,
+        })
     }
 
     fn outputs(&self, _global_log_namespace: LogNamespace) -> Vec<Output> {
@@ -362,6 +477,7 @@ pub fn file_source(
     shutdown: ShutdownSignal,
     mut out: SourceSender,
     acknowledgements: bool,
+    on_checkpointed: Option<Arc<dyn Fn() + Send + Sync>>,
 ) -> super::Source {
     let ignore_before = config
         .ignore_older_secs
@@ -377,7 +493,7 @@ pub fn file_source(
         &config.include,
         &config.exclude,
         MatchOptions::default(),
-        FileSourceInternalEventsEmitter,
+        FileSourceInternalEventsEmitter::default(),
     )
     .expect("invalid glob patterns");
 
@@ -390,6 +506,16 @@ pub fn file_source(
         None => Bytes::from(config.line_delimiter.clone()),
     };
 
+    let preset_parser = config.preset.map(PresetParser::new);
+    let emitter = FileSourceInternalEventsEmitter {
+        // The fields of W3C files are only needed while they're read.
+        on_unwatched: preset_parser.clone().map(|parser| {
+            Arc::new(move |file: &Path| parser.forget(&file.to_string_lossy()))
+                as Arc<dyn Fn(&Path) + Send + Sync>
+        }),
+        on_checkpointed,
+    };
+
     let checkpointer = Checkpointer::new(&data_dir);
     let file_server = FileServer {
         paths_provider,
@@ -408,7 +534,9 @@ pub fn file_source(
         },
         oldest_first: config.oldest_first,
         remove_after: config.remove_after_secs.map(Duration::from_secs),
-        emitter: FileSourceInternalEventsEmitter,
+        copytruncate: config.copytruncate,
+        io_uring: config.io_uring,
+        emitter,
         handle: tokio::runtime::Handle::current(),
     };
 
@@ -425,6 +553,7 @@ pub fn file_source(
     let include = config.include.clone();
     let exclude = config.exclude.clone();
     let multiline_config = config.multiline.clone();
+    let framing = config.framing.clone();
     let message_start_indicator = config.message_start_indicator.clone();
     let multi_line_timeout = config.multi_line_timeout;
 
@@ -479,7 +608,9 @@ pub fn file_source(
             });
 
         let messages: Box<dyn Stream<Item = Line> + Send + std::marker::Unpin> =
-            if let Some(ref multiline_config) = multiline_config {
+            if let Some(framing) = framing {
+                wrap_with_framing(rx, framing)
+            } else if let Some(ref multiline_config) = multiline_config {
                 wrap_with_line_agg(
                     rx,
                     multiline_config.try_into().unwrap(), // validated in build
@@ -496,22 +627,41 @@ pub fn file_source(
                 Box::new(rx)
             };
 
+        // Reading the fields of W3C files blocks, so it's done ahead of parsing their lines.
+        let messages = match preset_parser.clone() {
+            Some(parser) => messages
+                .then(move |line| {
+                    let parser = parser.clone();
+                    async move {
+                        parser
+                            .prepare(&line.filename, line.start_offset, &line.text)
+                            .await;
+                        line
+                    }
+                })
+                .boxed(),
+            None => messages.boxed(),
+        };
+
         // Once file server ends this will run until it has finished processing remaining
         // logs in the queue.
         let span = Span::current();
         let span2 = span.clone();
-        let mut messages = messages.map(move |line| {
+        let mut messages = messages.filter_map(move |line| {
             let _enter = span2.enter();
             let mut event = create_event(
                 line.text,
                 line.start_offset,
                 &line.filename,
                 &event_metadata,
+                preset_parser.as_ref(),
             );
 
             if let Some(finalizer) = &finalizer {
+                // Lines without an event are still acknowledged in order, once the batch
+                // notifier is dropped.
                 let (batch, receiver) = BatchNotifier::new_with_receiver();
-                event = event.with_batch_notifier(&batch);
+                event = event.map(|event| event.with_batch_notifier(&batch));
                 let entry = FinalizerEntry {
                     file_id: line.file_id,
                     offset: line.end_offset,
@@ -520,7 +670,7 @@ pub fn file_source(
             } else {
                 checkpoints.update(line.file_id, line.end_offset);
             }
-            event
+            futures::future::ready(event)
         });
         tokio::spawn(async move {
             match out
@@ -604,6 +754,63 @@ fn wrap_with_line_agg(
     )
 }
 
+/// The lines of a file buffered until they make up a whole frame.
+struct FramedFile {
+    framer: codecs::decoding::Framer,
+    buffer: BytesMut,
+    start_offset: u64,
+}
+
+/// Splits the lines of the files into frames, from each file on its own.
+///
+/// The frames start at the offset of the line their first byte was buffered with, and end at the
+/// offset of the line that completed them.
+fn wrap_with_framing(
+    rx: impl Stream<Item = Line> + Send + std::marker::Unpin + 'static,
+    config: FramingConfig,
+) -> Box<dyn Stream<Item = Line> + Send + std::marker::Unpin + 'static> {
+    let mut files = HashMap::<String, FramedFile>::new();
+    Box::new(rx.flat_map(move |line| {
+        let file = files
+            .entry(line.filename.clone())
+            .or_insert_with(|| FramedFile {
+                framer: config.build(),
+                buffer: BytesMut::new(),
+                start_offset: line.start_offset,
+            });
+        file.buffer.extend_from_slice(&line.text);
+        file.buffer.extend_from_slice(b"\n");
+
+        let mut frames = Vec::new();
+        loop {
+            match file.framer.decode(&mut file.buffer) {
+                Ok(Some(text)) => {
+                    frames.push(Line {
+                        text,
+                        filename: line.filename.clone(),
+                        file_id: line.file_id,
+                        start_offset: file.start_offset,
+                        end_offset: line.end_offset,
+                    });
+                    file.start_offset = line.start_offset;
+                }
+                Ok(None) => break,
+                Err(error) => {
+                    emit!(DecoderFramingError { error });
+                    file.buffer.clear();
+                    break;
+                }
+            }
+        }
+        // Files are only kept around while part of a frame is buffered.
+        if file.buffer.is_empty() {
+            files.remove(&line.filename);
+        }
+
+        futures::stream::iter(frames)
+    }))
+}
+
 struct EventMetadata {
     host_key: String,
     hostname: Option<String>,
@@ -611,15 +818,24 @@ struct EventMetadata {
     offset_key: Option<String>,
 }
 
-fn create_event(line: Bytes, offset: u64, file: &str, meta: &EventMetadata) -> LogEvent {
+fn create_event(
+    line: Bytes,
+    offset: u64,
+    file: &str,
+    meta: &EventMetadata,
+    preset_parser: Option<&PresetParser>,
+) -> Option<LogEvent> {
+    let mut event = match preset_parser {
+        Some(parser) => parser.parse(file, &line)?,
+        None => LogEvent::from_bytes_legacy(&line),
+    };
+
     emit!(FileEventsReceived {
         count: 1,
         file,
         byte_size: line.len(),
     });
 
-    let mut event = LogEvent::from_bytes_legacy(&line);
-
     // Add source type
     event.insert(log_schema().source_type_key(), Bytes::from("file"));
 
@@ -635,7 +851,7 @@ fn create_event(line: Bytes, offset: u64, file: &str, meta: &EventMetadata) -> L
         event.insert(meta.host_key.as_str(), hostname.clone());
     }
 
-    event
+    Some(event)
 }
 
 #[cfg(test)]
@@ -727,6 +943,24 @@ mod tests {
             }
         );
 
+        let config: FileConfig = toml::from_str(
+            r#"
+        copytruncate = true
+        [fingerprint]
+        strategy = "inode_checksum"
+        checksum_bytes = 64
+        "#,
+        )
+        .unwrap();
+        assert!(config.copytruncate);
+        assert_eq!(
+            config.fingerprint,
+            FingerprintConfig::InodeChecksum {
+                checksum_bytes: 64,
+                ignored_header_bytes: 0,
+            }
+        );
+
         let config: FileConfig = toml::from_str(
             r#"
         [encoding]
@@ -736,6 +970,14 @@ mod tests {
         .unwrap();
         assert_eq!(config.encoding, Some(EncodingConfig { charset: UTF_16LE }));
 
+        let config: FileConfig = toml::from_str(
+            r#"
+        preset = "windows_dns"
+        "#,
+        )
+        .unwrap();
+        assert_eq!(config.preset, Some(LogPreset::WindowsDns));
+
         let config: FileConfig = toml::from_str(
             r#"
         read_from = "beginning"
@@ -790,7 +1032,7 @@ mod tests {
             file_key,
             offset_key,
         };
-        let log = create_event(line, offset, file, &meta);
+        let log = create_event(line, offset, file, &meta, None).unwrap();
 
         assert_eq!(log["file"], "some_file.rs".into());
         assert_eq!(log["host"], "Some.Machine".into());
@@ -882,6 +1124,56 @@ mod tests {
         assert_eq!(received.len(), n + 1);
     }
 
+    #[tokio::test]
+    async fn file_w3c_preset() {
+        let dir = tempdir().unwrap();
+        let config = file::FileConfig {
+            include: vec![dir.path().join("*")],
+            preset: Some(LogPreset::W3c),
+            ..test_default_file_config(&dir)
+        };
+
+        let path = dir.path().join("u_ex221016.log");
+        let received = run_file_source(&config, false, Acks, async {
+            let mut file = File::create(&path).unwrap();
+
+            sleep_500_millis().await;
+
+            writeln!(
+                &mut file,
+                "#Software: Microsoft Internet Information Services 10.0"
+            )
+            .unwrap();
+            writeln!(
+                &mut file,
+                "#Fields: date time cs-method cs-uri-stem sc-status"
+            )
+            .unwrap();
+            writeln!(&mut file, "2022-10-16 08:15:42 GET /index.html 200").unwrap();
+            writeln!(
+                &mut file,
+                "#Fields: date time cs-uri-stem sc-status time-taken"
+            )
+            .unwrap();
+            writeln!(&mut file, "2022-10-16 08:15:43 /missing 404 3").unwrap();
+
+            sleep_500_millis().await;
+        })
+        .await;
+
+        let logs = received
+            .into_iter()
+            .map(Event::into_log)
+            .collect::<Vec<_>>();
+        assert_eq!(logs.len(), 2);
+        assert_eq!(logs[0]["cs_method"], "GET".into());
+        assert_eq!(logs[0]["sc_status"], 200.into());
+        assert!(!logs[1].contains("cs_method"));
+        assert_eq!(logs[1]["cs_uri_stem"], "/missing".into());
+        assert_eq!(logs[1]["time_taken"], 3.into());
+        assert_eq!(logs[1]["file"], path.to_str().unwrap().into());
+    }
+
     #[tokio::test]
     async fn file_truncate() {
         let n = 5;
@@ -1002,6 +1294,59 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn file_copytruncate() {
+        let n = 5;
+
+        let dir = tempdir().unwrap();
+        let config = file::FileConfig {
+            include: vec![dir.path().join("*")],
+            fingerprint: FingerprintConfig::InodeChecksum {
+                checksum_bytes: 8,
+                ignored_header_bytes: 0,
+            },
+            copytruncate: true,
+            ..test_default_file_config(&dir)
+        };
+
+        let path = dir.path().join("file");
+        let copy_path = dir.path().join("file.1");
+        let received = run_file_source(&config, false, NoAcks, async {
+            let mut file = File::create(&path).unwrap();
+
+            sleep_500_millis().await; // The files must be observed at its original length before writing to it
+
+            for i in 0..n {
+                writeln!(&mut file, "prerot line {}", i).unwrap();
+            }
+
+            sleep_500_millis().await; // The writes must be observed before rotating
+
+            fs::copy(&path, &copy_path).expect("could not copy");
+            file.set_len(0).unwrap();
+            file.seek(std::io::SeekFrom::Start(0)).unwrap();
+
+            sleep_500_millis().await; // The rotation must be observed before writing again
+
+            for i in 0..n {
+                writeln!(&mut file, "postrot {}", i).unwrap();
+            }
+
+            sleep_500_millis().await;
+        })
+        .await;
+
+        // Neither the copy nor the truncated file are read again from their beginning.
+        let mut expected = (0..n)
+            .map(|i| format!("prerot line {}", i))
+            .chain((0..n).map(|i| format!("postrot {}", i)))
+            .collect::<Vec<_>>();
+        expected.sort();
+        let mut received = extract_messages_string(received);
+        received.sort();
+        assert_eq!(received, expected);
+    }
+
     #[tokio::test]
     async fn file_multiple_paths() {
         let n = 5;
@@ -1756,6 +2101,68 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn json_stream_framing() {
+        let dir = tempdir().unwrap();
+        let config = file::FileConfig {
+            include: vec![dir.path().join("*")],
+            framing: Some(FramingConfig::JsonStream {
+                json_stream: Default::default(),
+            }),
+            ..test_default_file_config(&dir)
+        };
+
+        let path = dir.path().join("file");
+        let received = run_file_source(&config, false, NoAcks, async {
+            let mut file = File::create(&path).unwrap();
+
+            sleep_500_millis().await; // The files must be observed at their original lengths before writing to them
+
+            writeln!(&mut file, "{{").unwrap();
+            writeln!(&mut file, "  \"message\": \"first\"").unwrap();
+            writeln!(&mut file, "}}").unwrap();
+            writeln!(&mut file, "{{\"message\": \"second\"}} {{\"message\":").unwrap();
+            writeln!(&mut file, "\"third\"}}").unwrap();
+
+            sleep_500_millis().await;
+        })
+        .await;
+
+        let received = extract_messages_value(received);
+
+        assert_eq!(
+            received,
+            vec![
+                "{\n  \"message\": \"first\"\n}".into(),
+                "{\"message\": \"second\"}".into(),
+                "{\"message\":\n\"third\"}".into(),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn framing_with_multiline_is_rejected() {
+        let dir = tempdir().unwrap();
+        let config = file::FileConfig {
+            framing: Some(FramingConfig::JsonStream {
+                json_stream: Default::default(),
+            }),
+            message_start_indicator: Some("^\\{".to_owned()),
+            ..test_default_file_config(&dir)
+        };
+
+        let (tx, _rx) = SourceSender::new_test();
+        let error = config
+            .build(SourceContext::new_test(tx, None))
+            .await
+            .err()
+            .unwrap();
+        assert_eq!(
+            error.to_string(),
+            "framing can't be used along with multiline aggregation"
+        );
+    }
+
     #[tokio::test]
     async fn remove_file() {
         let n = 5;
@@ -1825,7 +2232,9 @@ mod tests {
             let data_dir = config.data_dir.clone().unwrap();
             let acks = !matches!(acking_mode, NoAcks);
 
-            tokio::spawn(file::file_source(config, data_dir, shutdown, tx, acks));
+            tokio::spawn(file::file_source(
+                config, data_dir, shutdown, tx, acks, None,
+            ));
 
             inner.await;
 
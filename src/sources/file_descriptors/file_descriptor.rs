@@ -76,7 +76,8 @@ impl SourceConfig for FileDescriptorSourceConfig {
     }
 
     fn outputs(&self, _global_log_namespace: LogNamespace) -> Vec<Output> {
-        vec![Output::default(self.decoding.output_type())]
+        self.decoding
+            .with_errors_output(vec![Output::default(self.decoding.output_type())])
     }
 
     fn resources(&self) -> Vec<Resource> {
@@ -0,0 +1,313 @@
+use std::collections::BTreeMap;
+
+use bytes::Bytes;
+use chrono::{DateTime, Utc};
+use derivative::Derivative;
+use lookup::event_path;
+use serde::Deserialize;
+use vector_config::configurable_component;
+use vector_core::config::{log_schema, LogNamespace};
+
+use crate::{
+    config::{AcknowledgementsConfig, DataType, Output, SourceConfig, SourceContext},
+    event::{LogEvent, Value},
+    gcp::GcpAuthConfig,
+    internal_events::GcpCloudLoggingEntryParseError,
+    serde::{bool_or_struct, default_decoding, default_framing_message_based},
+    sources::gcp_pubsub::{
+        default_full_response, default_keepalive, default_max_concurrency, default_poll_time,
+        MessageParser, PubsubConfig,
+    },
+    tls::TlsConfig,
+};
+
+/// Configuration for the `gcp_cloud_logging` source.
+#[configurable_component(source("gcp_cloud_logging"))]
+#[configurable(metadata(status = "alpha"))]
+#[derive(Clone, Debug, Derivative)]
+#[derivative(Default)]
+#[serde(deny_unknown_fields)]
+pub struct GcpCloudLoggingConfig {
+    /// The project name from which to pull logs.
+    pub project: String,
+
+    /// The subscription within the project which receives the log entries of a Cloud Logging sink.
+    pub subscription: String,
+
+    /// The endpoint from which to pull data.
+    pub endpoint: Option<String>,
+
+    #[serde(flatten)]
+    pub auth: GcpAuthConfig,
+
+    #[configurable(derived)]
+    pub tls: Option<TlsConfig>,
+
+    /// The maximum number of concurrent stream connections to open at once.
+    #[serde(default = "default_max_concurrency")]
+    #[derivative(Default(value = "default_max_concurrency()"))]
+    pub max_concurrency: usize,
+
+    /// The number of messages in a response to mark a stream as
+    /// "busy". This is used to determine if more streams should be
+    /// started.
+    #[serde(default = "default_full_response")]
+    #[derivative(Default(value = "default_full_response()"))]
+    pub full_response_size: usize,
+
+    /// How often to poll the currently active streams to see if they
+    /// are all busy and so open a new stream.
+    #[serde(default = "default_poll_time")]
+    #[derivative(Default(value = "default_poll_time()"))]
+    pub poll_time_seconds: f64,
+
+    /// The acknowledgement deadline, in seconds, to use for this stream.
+    ///
+    /// Messages that are not acknowledged when this deadline expires may be retransmitted.
+    pub ack_deadline_secs: Option<i32>,
+
+    /// The amount of time, in seconds, to wait between retry attempts after an error.
+    pub retry_delay_secs: Option<f64>,
+
+    /// The amount of time, in seconds, with no received activity
+    /// before sending a keepalive request. If this is set larger than
+    /// `60`, you may see periodic errors sent from the server.
+    #[serde(default = "default_keepalive")]
+    #[derivative(Default(value = "default_keepalive()"))]
+    pub keepalive_secs: f64,
+
+    #[configurable(derived)]
+    #[serde(default, deserialize_with = "bool_or_struct")]
+    pub acknowledgements: AcknowledgementsConfig,
+}
+
+impl_generate_config_from_default!(GcpCloudLoggingConfig);
+
+#[async_trait::async_trait]
+impl SourceConfig for GcpCloudLoggingConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<crate::sources::Source> {
+        let pubsub = PubsubConfig {
+            project: self.project.clone(),
+            subscription: self.subscription.clone(),
+            endpoint: self.endpoint.clone(),
+            auth: self.auth.clone(),
+            tls: self.tls.clone(),
+            max_concurrency: self.max_concurrency,
+            full_response_size: self.full_response_size,
+            poll_time_seconds: self.poll_time_seconds,
+            ack_deadline_secs: self.ack_deadline_secs,
+            ack_deadline_seconds: None,
+            retry_delay_secs: self.retry_delay_secs,
+            retry_delay_seconds: None,
+            keepalive_secs: self.keepalive_secs,
+            framing: default_framing_message_based(),
+            decoding: default_decoding(),
+            acknowledgements: self.acknowledgements,
+        };
+        pubsub
+            .build_source(cx, MessageParser::Log(parse_log_entry))
+            .await
+    }
+
+    fn outputs(&self, _global_log_namespace: LogNamespace) -> Vec<Output> {
+        vec![Output::default(DataType::Log)]
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        true
+    }
+}
+
+/// A `LogEntry` of Cloud Logging, as exported to Pub/Sub.
+///
+/// See <https://cloud.google.com/logging/docs/reference/v2/rest/v2/LogEntry>.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LogEntry {
+    log_name: String,
+    resource: Option<serde_json::Value>,
+    timestamp: Option<DateTime<Utc>>,
+    receive_timestamp: Option<DateTime<Utc>>,
+    severity: Option<String>,
+    insert_id: Option<String>,
+    #[serde(default)]
+    labels: BTreeMap<String, String>,
+    http_request: Option<serde_json::Value>,
+    operation: Option<serde_json::Value>,
+    trace: Option<String>,
+    span_id: Option<String>,
+    trace_sampled: Option<bool>,
+    source_location: Option<serde_json::Value>,
+    text_payload: Option<String>,
+    json_payload: Option<serde_json::Map<String, serde_json::Value>>,
+    proto_payload: Option<serde_json::Value>,
+}
+
+/// Turns the JSON encoded `LogEntry` `data` into a log event.
+///
+/// The payload is unwrapped: a `textPayload` becomes the message of the event, the fields of a
+/// `jsonPayload` are placed at the root of the event, and a `protoPayload` is kept as
+/// `proto_payload`. The metadata of the entry is added to the event as snake case fields, taking
+/// precedence over payload fields of the same name.
+fn parse_log_entry(data: &[u8]) -> Option<LogEvent> {
+    let entry = match serde_json::from_slice::<LogEntry>(data) {
+        Ok(entry) => entry,
+        Err(error) => {
+            emit!(GcpCloudLoggingEntryParseError { error });
+            return None;
+        }
+    };
+
+    let schema = log_schema();
+    let mut log = LogEvent::default();
+    if let Some(payload) = entry.json_payload {
+        for (key, value) in payload {
+            log.insert(event_path!(key.as_str()), Value::from(value));
+        }
+    }
+    if let Some(text) = entry.text_payload {
+        log.insert(schema.message_key(), text);
+    }
+    if let Some(payload) = entry.proto_payload {
+        log.insert("proto_payload", Value::from(payload));
+    }
+
+    log.insert("log_name", entry.log_name);
+    log.insert(
+        "severity",
+        entry.severity.unwrap_or_else(|| "DEFAULT".to_owned()),
+    );
+    if let Some(resource) = entry.resource {
+        log.insert("resource", Value::from(resource));
+    }
+    if !entry.labels.is_empty() {
+        log.insert(
+            "labels",
+            entry
+                .labels
+                .into_iter()
+                .map(|(key, value)| (key, Value::from(value)))
+                .collect::<BTreeMap<_, _>>(),
+        );
+    }
+    let optional_fields = [
+        ("insert_id", entry.insert_id.map(Value::from)),
+        ("http_request", entry.http_request.map(Value::from)),
+        ("operation", entry.operation.map(Value::from)),
+        ("trace", entry.trace.map(Value::from)),
+        ("span_id", entry.span_id.map(Value::from)),
+        ("trace_sampled", entry.trace_sampled.map(Value::from)),
+        ("source_location", entry.source_location.map(Value::from)),
+        (
+            "receive_timestamp",
+            entry.receive_timestamp.map(Value::from),
+        ),
+    ];
+    for (key, value) in optional_fields {
+        if let Some(value) = value {
+            log.insert(key, value);
+        }
+    }
+
+    if let Some(timestamp) = entry.timestamp.or(entry.receive_timestamp) {
+        log.insert(schema.timestamp_key(), timestamp);
+    }
+    log.insert(
+        schema.source_type_key(),
+        Bytes::from_static(b"gcp_cloud_logging"),
+    );
+    Some(log)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<GcpCloudLoggingConfig>();
+    }
+
+    fn parse(entry: serde_json::Value) -> LogEvent {
+        parse_log_entry(&serde_json::to_vec(&entry).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn unwraps_text_payload() {
+        let log = parse(json!({
+            "logName": "projects/vector/logs/stdout",
+            "resource": {
+                "type": "k8s_container",
+                "labels": { "namespace_name": "default", "pod_name": "web-1" },
+            },
+            "timestamp": "2022-10-01T12:00:00.123Z",
+            "receiveTimestamp": "2022-10-01T12:00:01Z",
+            "severity": "ERROR",
+            "insertId": "abc",
+            "labels": { "env": "prod" },
+            "textPayload": "connection refused",
+        }));
+
+        assert_eq!(log["message"], "connection refused".into());
+        assert_eq!(log["severity"], "ERROR".into());
+        assert_eq!(log["log_name"], "projects/vector/logs/stdout".into());
+        assert_eq!(log["resource.type"], "k8s_container".into());
+        assert_eq!(log["resource.labels.pod_name"], "web-1".into());
+        assert_eq!(log["labels.env"], "prod".into());
+        assert_eq!(log["insert_id"], "abc".into());
+        assert_eq!(
+            log["timestamp"],
+            Utc.ymd(2022, 10, 1).and_hms_milli(12, 0, 0, 123).into()
+        );
+        assert_eq!(
+            log["receive_timestamp"],
+            Utc.ymd(2022, 10, 1).and_hms(12, 0, 1).into()
+        );
+        assert_eq!(log["source_type"], "gcp_cloud_logging".into());
+    }
+
+    #[test]
+    fn unwraps_json_payload() {
+        let log = parse(json!({
+            "logName": "projects/vector/logs/app",
+            "jsonPayload": {
+                "message": "request served",
+                "latency_ms": 12,
+                "log_name": "overridden",
+            },
+        }));
+
+        assert_eq!(log["message"], "request served".into());
+        assert_eq!(log["latency_ms"], 12.into());
+        assert_eq!(log["log_name"], "projects/vector/logs/app".into());
+        assert_eq!(log["severity"], "DEFAULT".into());
+        assert!(log.get("timestamp").is_none());
+    }
+
+    #[test]
+    fn keeps_proto_payload() {
+        let log = parse(json!({
+            "logName": "projects/vector/logs/cloudaudit.googleapis.com%2Factivity",
+            "severity": "NOTICE",
+            "protoPayload": {
+                "@type": "type.googleapis.com/google.cloud.audit.AuditLog",
+                "methodName": "storage.buckets.create",
+            },
+        }));
+
+        assert_eq!(
+            log["proto_payload.methodName"],
+            "storage.buckets.create".into()
+        );
+        assert_eq!(log["severity"], "NOTICE".into());
+    }
+
+    #[test]
+    fn rejects_invalid_entries() {
+        assert!(parse_log_entry(b"not json").is_none());
+        assert!(parse_log_entry(br#"{"textPayload":"no log name"}"#).is_none());
+    }
+}
@@ -23,12 +23,15 @@ use vector_common::internal_event::{
 };
 use vector_common::{byte_size_of::ByteSizeOf, finalizer::UnorderedFinalizer};
 use vector_config::configurable_component;
-use vector_core::config::LogNamespace;
+use vector_core::{
+    config::{log_schema, LogNamespace},
+    internal_event::EventsReceived,
+};
 
 use crate::{
     codecs::{Decoder, DecodingConfig},
     config::{AcknowledgementsConfig, DataType, Output, SourceConfig, SourceContext},
-    event::{BatchNotifier, BatchStatus, Event, MaybeAsLogMut, Value},
+    event::{BatchNotifier, BatchStatus, Event, LogEvent, MaybeAsLogMut, Value},
     gcp::{GcpAuthConfig, GcpAuthenticator, Scope, PUBSUB_URL},
     internal_events::{
         GcpPubsubConnectError, GcpPubsubReceiveError, GcpPubsubStreamingPullError,
@@ -193,25 +196,65 @@ const fn default_retry_delay() -> f64 {
     1.0
 }
 
-const fn default_keepalive() -> f64 {
+pub(crate) const fn default_keepalive() -> f64 {
     60.0
 }
 
-const fn default_max_concurrency() -> usize {
+pub(crate) const fn default_max_concurrency() -> usize {
     10
 }
 
-const fn default_full_response() -> usize {
+pub(crate) const fn default_full_response() -> usize {
     100
 }
 
-const fn default_poll_time() -> f64 {
+pub(crate) const fn default_poll_time() -> f64 {
     2.0
 }
 
 #[async_trait::async_trait]
 impl SourceConfig for PubsubConfig {
     async fn build(&self, cx: SourceContext) -> crate::Result<crate::sources::Source> {
+        let decoder = DecodingConfig::new(
+            self.framing.clone(),
+            self.decoding.clone(),
+            LogNamespace::Legacy,
+        )
+        .build();
+        self.build_source(cx, MessageParser::Decoder(decoder)).await
+    }
+
+    fn outputs(&self, _global_log_namespace: LogNamespace) -> Vec<Output> {
+        self.decoding
+            .with_errors_output(vec![Output::default(DataType::Log)])
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        true
+    }
+}
+
+impl_generate_config_from_default!(PubsubConfig);
+
+/// Turns the data of the pulled messages into events.
+#[derive(Clone)]
+pub(crate) enum MessageParser {
+    /// Decodes the data with the configured framing and codec.
+    Decoder(Decoder),
+    /// Parses the data into a single log event, returning `None` if the data is invalid.
+    ///
+    /// The parser is responsible for reporting invalid data.
+    Log(fn(&[u8]) -> Option<LogEvent>),
+}
+
+impl PubsubConfig {
+    /// Builds a source pulling messages from the configured subscription, turning their data into
+    /// events with `parser`.
+    pub(crate) async fn build_source(
+        &self,
+        cx: SourceContext,
+        parser: MessageParser,
+    ) -> crate::Result<crate::sources::Source> {
         let ack_deadline_secs = match (self.ack_deadline_secs, self.ack_deadline_seconds) {
             (Some(ads), None) => ads,
             (None, Some(ads)) => {
@@ -271,12 +314,7 @@ impl SourceConfig for PubsubConfig {
                 "projects/{}/subscriptions/{}",
                 self.project, self.subscription
             ),
-            decoder: DecodingConfig::new(
-                self.framing.clone(),
-                self.decoding.clone(),
-                LogNamespace::Legacy,
-            )
-            .build(),
+            parser,
             acknowledgements: cx.do_acknowledgements(&self.acknowledgements),
             shutdown: cx.shutdown,
             out: cx.out,
@@ -293,18 +331,8 @@ impl SourceConfig for PubsubConfig {
         .map_err(|error| error!(message = "Source failed.", %error));
         Ok(Box::pin(source))
     }
-
-    fn outputs(&self, _global_log_namespace: LogNamespace) -> Vec<Output> {
-        vec![Output::default(DataType::Log)]
-    }
-
-    fn can_acknowledge(&self) -> bool {
-        true
-    }
 }
 
-impl_generate_config_from_default!(PubsubConfig);
-
 #[derive(Clone)]
 struct PubsubSource {
     endpoint: Endpoint,
@@ -312,7 +340,7 @@ struct PubsubSource {
     auth: GcpAuthenticator,
     token_generator: watch::Receiver<()>,
     subscription: String,
-    decoder: Decoder,
+    parser: MessageParser,
     acknowledgements: bool,
     ack_deadline_secs: i32,
     shutdown: ShutdownSignal,
@@ -608,19 +636,35 @@ impl PubsubSource {
                 .map(|(key, value)| (key, Value::Bytes(value.into())))
                 .collect(),
         );
-        util::decode_message(
-            self.decoder.clone(),
-            "gcp_pubsub",
-            &message.data,
-            message.publish_time.map(|dt| {
-                DateTime::from_utc(
-                    NaiveDateTime::from_timestamp(dt.seconds, dt.nanos as u32),
-                    Utc,
-                )
-            }),
-            batch,
-        )
-        .map(move |mut event| {
+        let publish_time = message.publish_time.map(|dt| {
+            DateTime::from_utc(
+                NaiveDateTime::from_timestamp(dt.seconds, dt.nanos as u32),
+                Utc,
+            )
+        });
+        let events: Box<dyn Iterator<Item = Event> + 'a> = match &self.parser {
+            MessageParser::Decoder(decoder) => Box::new(util::decode_message(
+                decoder.clone(),
+                "gcp_pubsub",
+                &message.data,
+                publish_time,
+                batch,
+            )),
+            MessageParser::Log(parse) => {
+                let event = parse(&message.data).map(|mut log| {
+                    if let Some(timestamp) = publish_time {
+                        log.try_insert(log_schema().timestamp_key(), timestamp);
+                    }
+                    emit!(EventsReceived {
+                        byte_size: log.size_of(),
+                        count: 1,
+                    });
+                    Event::from(log).with_batch_notifier_option(batch)
+                });
+                Box::new(event.into_iter())
+            }
+        };
+        events.map(move |mut event| {
             if let Some(log) = event.maybe_as_log_mut() {
                 log.insert("message_id", message.message_id.clone());
                 log.insert("attributes", attributes.clone());
@@ -0,0 +1,363 @@
+//! Messages described by a protobuf descriptor set, decoded and encoded without generated code.
+
+use std::collections::BTreeMap;
+
+use bytes::Bytes;
+use ordered_float::NotNan;
+use prost::Message as _;
+use prost_reflect::{
+    DescriptorError, DescriptorPool, DynamicMessage, FieldDescriptor, Kind, MapKey,
+    MessageDescriptor, ReflectMessage, Value as ProtobufValue,
+};
+
+use crate::event::Value;
+
+/// A method of a service, with the descriptors of its messages.
+#[derive(Clone, Debug)]
+pub struct Method {
+    pub input: MessageDescriptor,
+    pub output: MessageDescriptor,
+}
+
+/// The messages and methods of a descriptor set, by their fully qualified names such as
+/// `acme.agent.v1.Event`. Methods are named after their service, such as
+/// `acme.agent.v1.AgentService/PushEvents`.
+#[derive(Clone, Debug)]
+pub struct Descriptors(DescriptorPool);
+
+impl Descriptors {
+    /// Decodes an encoded descriptor set, which must include the imports of its files.
+    pub fn decode(bytes: &[u8]) -> Result<Self, DescriptorError> {
+        DescriptorPool::decode(bytes).map(Self)
+    }
+
+    pub fn method(&self, name: &str) -> Option<Method> {
+        let (service, method) = name.rsplit_once('/')?;
+        self.0
+            .services()
+            .find(|descriptor| descriptor.full_name() == service)?
+            .methods()
+            .find(|descriptor| descriptor.name() == method)
+            .map(|method| Method {
+                input: method.input(),
+                output: method.output(),
+            })
+    }
+
+    #[cfg(test)]
+    pub fn message(&self, name: &str) -> Option<MessageDescriptor> {
+        self.0.get_message_by_name(name)
+    }
+}
+
+/// Decodes a message into its fields, named as in the descriptor set.
+///
+/// Fields missing from the message, including those with their default value, are left out,
+/// as are unknown fields. Enum values are decoded into their name, and maps into objects.
+pub fn decode(
+    message: &MessageDescriptor,
+    buf: Bytes,
+) -> Result<BTreeMap<String, Value>, prost::DecodeError> {
+    DynamicMessage::decode(message.clone(), buf).map(|message| message_to_object(&message))
+}
+
+/// Encodes a message from a JSON object of its fields.
+///
+/// Enum values are given by name or number, `bytes` fields in base64 and 64-bit integers
+/// either as numbers or strings, as in the JSON mapping of protobuf.
+pub fn encode(
+    message: &MessageDescriptor,
+    json: &serde_json::Value,
+) -> Result<Bytes, serde_json::Error> {
+    DynamicMessage::deserialize(message.clone(), json)
+        .map(|message| Bytes::from(message.encode_to_vec()))
+}
+
+fn message_to_object(message: &DynamicMessage) -> BTreeMap<String, Value> {
+    message
+        .descriptor()
+        .fields()
+        .filter(|field| message.has_field(field))
+        .map(|field| {
+            let value = message.get_field(&field);
+            (field.name().to_owned(), field_to_value(&field, &value))
+        })
+        .collect()
+}
+
+fn field_to_value(field: &FieldDescriptor, value: &ProtobufValue) -> Value {
+    match value {
+        ProtobufValue::Bool(value) => Value::Boolean(*value),
+        ProtobufValue::I32(value) => Value::from(i64::from(*value)),
+        ProtobufValue::I64(value) => Value::from(*value),
+        ProtobufValue::U32(value) => Value::from(i64::from(*value)),
+        ProtobufValue::U64(value) => Value::from(*value as i64),
+        ProtobufValue::F32(value) => float(f64::from(*value)),
+        ProtobufValue::F64(value) => float(*value),
+        ProtobufValue::String(value) => Value::from(value.as_str()),
+        ProtobufValue::Bytes(value) => Value::Bytes(value.clone()),
+        ProtobufValue::EnumNumber(number) => match field.kind() {
+            Kind::Enum(descriptor) => descriptor.get_value(*number).map_or_else(
+                || Value::from(i64::from(*number)),
+                |value| Value::from(value.name()),
+            ),
+            _ => Value::from(i64::from(*number)),
+        },
+        ProtobufValue::Message(message) => Value::Object(message_to_object(message)),
+        ProtobufValue::List(values) => Value::Array(
+            values
+                .iter()
+                .map(|value| field_to_value(field, value))
+                .collect(),
+        ),
+        ProtobufValue::Map(entries) => {
+            let value_field = match field.kind() {
+                Kind::Message(entry) => entry.map_entry_value_field(),
+                _ => unreachable!("map fields have a message kind"),
+            };
+            Value::Object(
+                entries
+                    .iter()
+                    .map(|(key, value)| (map_key(key), field_to_value(&value_field, value)))
+                    .collect(),
+            )
+        }
+    }
+}
+
+fn map_key(key: &MapKey) -> String {
+    match key {
+        MapKey::Bool(key) => key.to_string(),
+        MapKey::I32(key) => key.to_string(),
+        MapKey::I64(key) => key.to_string(),
+        MapKey::U32(key) => key.to_string(),
+        MapKey::U64(key) => key.to_string(),
+        MapKey::String(key) => key.clone(),
+    }
+}
+
+/// Floats that are not a number decode into null, since values can't hold them.
+fn float(value: f64) -> Value {
+    NotNan::new(value).map_or(Value::Null, Value::Float)
+}
+
+#[cfg(test)]
+pub(super) mod tests {
+    use prost_types::{
+        field_descriptor_proto::{Label, Type},
+        DescriptorProto, EnumDescriptorProto, FieldDescriptorProto, FileDescriptorProto,
+        FileDescriptorSet, MessageOptions, MethodDescriptorProto, ServiceDescriptorProto,
+    };
+    use serde_json::json;
+
+    use super::*;
+
+    fn field(name: &str, number: i32, kind: Type, type_name: Option<&str>) -> FieldDescriptorProto {
+        FieldDescriptorProto {
+            name: Some(name.to_owned()),
+            number: Some(number),
+            label: Some(Label::Optional as i32),
+            r#type: Some(kind as i32),
+            type_name: type_name.map(str::to_owned),
+            ..Default::default()
+        }
+    }
+
+    fn repeated(field: FieldDescriptorProto) -> FieldDescriptorProto {
+        FieldDescriptorProto {
+            label: Some(Label::Repeated as i32),
+            ..field
+        }
+    }
+
+    fn message(name: &str, field: Vec<FieldDescriptorProto>) -> DescriptorProto {
+        DescriptorProto {
+            name: Some(name.to_owned()),
+            field,
+            ..Default::default()
+        }
+    }
+
+    /// The descriptor set of an `acme.agent.v1.AgentService` service, with a `Push` method.
+    pub(in crate::sources::grpc_server) fn descriptor_set() -> FileDescriptorSet {
+        let mut event = message(
+            "Event",
+            vec![
+                field("message", 1, Type::String, None),
+                field("count", 2, Type::Int64, None),
+                repeated(field("deltas", 3, Type::Sint32, None)),
+                field("level", 4, Type::Enum, Some(".acme.agent.v1.Level")),
+                repeated(field(
+                    "labels",
+                    5,
+                    Type::Message,
+                    Some(".acme.agent.v1.Event.LabelsEntry"),
+                )),
+                field("host", 6, Type::Message, Some(".acme.agent.v1.Host")),
+                field("payload", 7, Type::Bytes, None),
+                field("ratio", 8, Type::Double, None),
+                field("ok", 9, Type::Bool, None),
+            ],
+        );
+        event.nested_type.push(DescriptorProto {
+            options: Some(MessageOptions {
+                map_entry: Some(true),
+                ..Default::default()
+            }),
+            ..message(
+                "LabelsEntry",
+                vec![
+                    field("key", 1, Type::String, None),
+                    field("value", 2, Type::String, None),
+                ],
+            )
+        });
+
+        FileDescriptorSet {
+            file: vec![
+                FileDescriptorProto {
+                    name: Some("acme/agent/v1/agent.proto".to_owned()),
+                    package: Some("acme.agent.v1".to_owned()),
+                    message_type: vec![
+                        event,
+                        message(
+                            "Host",
+                            vec![
+                                field("name", 1, Type::String, None),
+                                field("parent", 2, Type::Message, Some(".acme.agent.v1.Host")),
+                            ],
+                        ),
+                        message(
+                            "PushResponse",
+                            vec![field("accepted", 1, Type::Uint32, None)],
+                        ),
+                    ],
+                    enum_type: vec![EnumDescriptorProto {
+                        name: Some("Level".to_owned()),
+                        value: ["UNKNOWN", "INFO", "ERROR"]
+                            .iter()
+                            .zip(0..)
+                            .map(|(name, number)| prost_types::EnumValueDescriptorProto {
+                                name: Some((*name).to_owned()),
+                                number: Some(number),
+                                ..Default::default()
+                            })
+                            .collect(),
+                        ..Default::default()
+                    }],
+                    service: vec![ServiceDescriptorProto {
+                        name: Some("AgentService".to_owned()),
+                        method: vec![MethodDescriptorProto {
+                            name: Some("Push".to_owned()),
+                            input_type: Some(".acme.agent.v1.Event".to_owned()),
+                            output_type: Some(".acme.agent.v1.PushResponse".to_owned()),
+                            client_streaming: Some(true),
+                            ..Default::default()
+                        }],
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                },
+                FileDescriptorProto {
+                    name: Some("test.proto".to_owned()),
+                    message_type: vec![message(
+                        "Test",
+                        vec![
+                            field("a", 1, Type::Int32, None),
+                            field("b", 2, Type::String, None),
+                            repeated(field("d", 4, Type::Int32, None)),
+                        ],
+                    )],
+                    ..Default::default()
+                },
+            ],
+        }
+    }
+
+    fn descriptors() -> Descriptors {
+        Descriptors::decode(&descriptor_set().encode_to_vec()).unwrap()
+    }
+
+    fn message_descriptor(name: &str) -> MessageDescriptor {
+        descriptors().message(name).unwrap()
+    }
+
+    #[test]
+    fn resolves_methods() {
+        let descriptors = descriptors();
+        let method = descriptors
+            .method("acme.agent.v1.AgentService/Push")
+            .unwrap();
+        assert_eq!(method.input.full_name(), "acme.agent.v1.Event");
+        assert_eq!(method.output.full_name(), "acme.agent.v1.PushResponse");
+        assert!(descriptors
+            .method("acme.agent.v1.AgentService/Pull")
+            .is_none());
+    }
+
+    #[test]
+    fn rejects_unknown_types() {
+        let mut set = descriptor_set();
+        set.file[0].message_type.remove(1);
+        assert!(Descriptors::decode(&set.encode_to_vec()).is_err());
+    }
+
+    #[test]
+    fn decodes_wire_format() {
+        // The examples of the encoding guide of protobuf.
+        let test = message_descriptor("Test");
+        let decode =
+            |bytes: &'static [u8]| Value::Object(decode(&test, Bytes::from(bytes)).unwrap());
+        assert_eq!(
+            decode(&[0x08, 0x96, 0x01]),
+            Value::from(json!({ "a": 150 }))
+        );
+        assert_eq!(
+            decode(&[0x12, 0x07, 0x74, 0x65, 0x73, 0x74, 0x69, 0x6e, 0x67]),
+            Value::from(json!({ "b": "testing" }))
+        );
+        assert_eq!(
+            decode(&[0x22, 0x06, 0x03, 0x8e, 0x02, 0x9e, 0xa7, 0x05]),
+            Value::from(json!({ "d": [3, 270, 86942] }))
+        );
+        // Unknown fields are skipped.
+        assert_eq!(
+            decode(&[0x28, 0x01, 0x08, 0x96, 0x01]),
+            Value::from(json!({ "a": 150 }))
+        );
+        assert!(super::decode(&test, Bytes::from_static(&[0x12, 0x07, 0x74])).is_err());
+    }
+
+    #[test]
+    fn round_trips_messages() {
+        let event = message_descriptor("acme.agent.v1.Event");
+        let message = json!({
+            "message": "disk full",
+            "count": -3,
+            "deltas": [1, -1, 300],
+            "level": "ERROR",
+            "labels": { "region": "eu", "zone": "b" },
+            "host": { "name": "db-1", "parent": { "name": "rack-4" } },
+            "payload": "AAEC",
+            "ratio": 0.5,
+            "ok": true,
+        });
+        let encoded = encode(&event, &message).unwrap();
+        let mut decoded = decode(&event, encoded).unwrap();
+
+        assert_eq!(
+            decoded.remove("payload"),
+            Some(Value::Bytes(Bytes::from_static(&[0, 1, 2])))
+        );
+        let mut expected = message;
+        expected.as_object_mut().unwrap().remove("payload");
+        assert_eq!(Value::Object(decoded), Value::from(expected));
+    }
+
+    #[test]
+    fn rejects_invalid_json() {
+        let response = message_descriptor("acme.agent.v1.PushResponse");
+        assert!(encode(&response, &json!({ "rejected": 1 })).is_err());
+        assert!(encode(&response, &json!({ "accepted": "many" })).is_err());
+    }
+}
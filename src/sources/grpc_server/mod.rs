@@ -0,0 +1,702 @@
+use std::{
+    collections::HashMap,
+    convert::Infallible,
+    net::SocketAddr,
+    path::PathBuf,
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+use bytes::{Buf, BufMut, Bytes};
+use chrono::Utc;
+use futures::{
+    future::{self, BoxFuture},
+    FutureExt, TryFutureExt,
+};
+use hyper::Body;
+use indexmap::IndexMap;
+use prost_reflect::{DescriptorError, MessageDescriptor};
+use snafu::{OptionExt, ResultExt, Snafu};
+use tonic::{
+    body::BoxBody,
+    codec::{Codec, CompressionEncoding, DecodeBuf, Decoder, EncodeBuf, Encoder},
+    server::{ClientStreamingService, Grpc},
+    Code, Request, Response, Status, Streaming,
+};
+use vector_config::configurable_component;
+use vector_core::{
+    config::{log_schema, LogNamespace},
+    event::{BatchNotifier, BatchStatus, BatchStatusReceiver, Event, LogEvent, ResponseHandle},
+    ByteSizeOf,
+};
+
+use crate::{
+    config::{
+        AcknowledgementsConfig, DataType, GenerateConfig, Output, Resource, SourceConfig,
+        SourceContext,
+    },
+    internal_events::{EventsReceived, GrpcServerInvalidMessage, StreamClosedError},
+    serde::bool_or_struct,
+    sources::{util::grpc::run_grpc_service, Source},
+    tls::{MaybeTlsSettings, TlsEnableableConfig},
+    SourceSender,
+};
+
+mod descriptor;
+
+use self::descriptor::Descriptors;
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("Could not read descriptor set {:?}: {}", path, source))]
+    ReadDescriptorSet {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+
+    #[snafu(display("Invalid descriptor set {:?}: {}", path, source))]
+    InvalidDescriptorSet {
+        path: PathBuf,
+        source: DescriptorError,
+    },
+
+    #[snafu(display("Method {:?} is not in the descriptor set", method))]
+    UnknownMethod { method: String },
+
+    #[snafu(display("Response of method {:?} is not valid JSON: {}", method, source))]
+    InvalidResponseJson {
+        method: String,
+        source: serde_json::Error,
+    },
+
+    #[snafu(display("Invalid response of method {:?}: {}", method, source))]
+    InvalidResponse {
+        method: String,
+        source: serde_json::Error,
+    },
+}
+
+/// Configuration for the `grpc_server` source.
+#[configurable_component(source("grpc_server"))]
+#[configurable(metadata(status = "alpha"))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct GrpcServerConfig {
+    /// The address to listen for gRPC connections on.
+    ///
+    /// It _must_ include a port.
+    address: SocketAddr,
+
+    /// The path of a protobuf descriptor set describing the services of the methods served.
+    ///
+    /// Such a file is generated by `protoc` with the `--descriptor_set_out` and `--include_imports`
+    /// options.
+    descriptor_set_file: PathBuf,
+
+    /// A table of the methods served, by their fully qualified name such as
+    /// `acme.agent.v1.AgentService/PushEvents`.
+    ///
+    /// Each request message is decoded into an event. Requests to other methods are rejected with
+    /// the `UNIMPLEMENTED` status.
+    methods: IndexMap<String, GrpcMethodConfig>,
+
+    /// The event key in which the name of the method called is stored.
+    #[serde(default = "default_method_key")]
+    method_key: String,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    tls: Option<TlsEnableableConfig>,
+
+    #[configurable(derived)]
+    #[serde(default, deserialize_with = "bool_or_struct")]
+    acknowledgements: AcknowledgementsConfig,
+
+    /// Whether to answer requests with the response set by a downstream `respond` transform.
+    ///
+    /// The source then waits for all the events of a request to be processed by the pipeline,
+    /// rather than only sent into it, before answering. A response with an HTTP status of `400` or
+    /// more fails the request with the matching gRPC status, such as `INVALID_ARGUMENT` for `400`
+    /// and `422`, and the body as its message. Otherwise, a non-empty body is the response message,
+    /// as a JSON object of its fields, replacing the `response` of the method.
+    #[serde(default)]
+    pipeline_response: bool,
+}
+
+/// A method served by the `grpc_server` source.
+#[configurable_component]
+#[derive(Clone, Debug, Default)]
+#[serde(deny_unknown_fields)]
+pub struct GrpcMethodConfig {
+    /// The output the events of the method are sent to.
+    ///
+    /// The events are sent to the `<source_id>.<output>` output, or to the default output if not
+    /// set. Several methods can share an output.
+    #[serde(default)]
+    output: Option<String>,
+
+    /// The response message returned to each request, as a JSON object of its fields.
+    ///
+    /// Enum values are given by name, and `bytes` fields in base64. The response is an empty
+    /// message, whose fields all have their default value, if not set.
+    #[serde(default)]
+    response: Option<String>,
+}
+
+fn default_method_key() -> String {
+    "grpc_method".to_string()
+}
+
+impl GenerateConfig for GrpcServerConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self {
+            address: "0.0.0.0:50051".parse().unwrap(),
+            descriptor_set_file: PathBuf::from("/etc/vector/services.desc"),
+            methods: IndexMap::new(),
+            method_key: default_method_key(),
+            tls: None,
+            acknowledgements: Default::default(),
+            pipeline_response: false,
+        })
+        .unwrap()
+    }
+}
+
+impl GrpcServerConfig {
+    fn descriptors(&self) -> Result<Descriptors, BuildError> {
+        let path = &self.descriptor_set_file;
+        let bytes = std::fs::read(path).context(ReadDescriptorSetSnafu { path })?;
+        Descriptors::decode(&bytes).context(InvalidDescriptorSetSnafu { path })
+    }
+
+    fn build_methods(
+        &self,
+        descriptors: &Descriptors,
+    ) -> Result<HashMap<String, Arc<Method>>, BuildError> {
+        let mut methods = HashMap::new();
+        for (name, config) in &self.methods {
+            let name = name.trim_start_matches('/');
+            let method = descriptors
+                .method(name)
+                .context(UnknownMethodSnafu { method: name })?;
+            let response = match &config.response {
+                Some(response) => {
+                    let response = serde_json::from_str(response)
+                        .context(InvalidResponseJsonSnafu { method: name })?;
+                    descriptor::encode(&method.output, &response)
+                        .context(InvalidResponseSnafu { method: name })?
+                }
+                None => Bytes::new(),
+            };
+            methods.insert(
+                format!("/{}", name),
+                Arc::new(Method {
+                    name: name.to_owned(),
+                    input: method.input,
+                    response_message: method.output,
+                    output: config.output.clone(),
+                    response,
+                }),
+            );
+        }
+        Ok(methods)
+    }
+}
+
+#[async_trait::async_trait]
+impl SourceConfig for GrpcServerConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<Source> {
+        let descriptors = self.descriptors()?;
+        let methods = self.build_methods(&descriptors)?;
+        let tls_settings = MaybeTlsSettings::from_config(&self.tls, true)?;
+        let acknowledgements = cx.do_acknowledgements(&self.acknowledgements);
+        let service = Service {
+            methods: Arc::new(methods),
+            method_key: Arc::from(self.method_key.as_str()),
+            out: cx.out,
+            acknowledgements,
+            pipeline_response: self.pipeline_response,
+        };
+
+        let source =
+            run_grpc_service(self.address, tls_settings, service, cx.shutdown).map_err(|error| {
+                error!(message = "Source future failed.", %error);
+            });
+
+        Ok(Box::pin(source))
+    }
+
+    fn outputs(&self, _global_log_namespace: LogNamespace) -> Vec<Output> {
+        let mut outputs = vec![Output::default(DataType::Log)];
+        for output in self
+            .methods
+            .values()
+            .filter_map(|method| method.output.as_ref())
+        {
+            if !outputs
+                .iter()
+                .any(|existing| existing.port.as_ref() == Some(output))
+            {
+                outputs.push(Output::default(DataType::Log).with_port(output));
+            }
+        }
+        outputs
+    }
+
+    fn resources(&self) -> Vec<Resource> {
+        vec![Resource::tcp(self.address)]
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        true
+    }
+}
+
+/// A method served, by its name such as `acme.agent.v1.AgentService/PushEvents`.
+#[derive(Debug)]
+struct Method {
+    name: String,
+    /// The descriptor of the request message.
+    input: MessageDescriptor,
+    /// The descriptor of the response message.
+    response_message: MessageDescriptor,
+    output: Option<String>,
+    /// The encoded response message.
+    response: Bytes,
+}
+
+/// Routes requests to the methods served, by the path of their URL.
+#[derive(Clone)]
+struct Service {
+    methods: Arc<HashMap<String, Arc<Method>>>,
+    method_key: Arc<str>,
+    out: SourceSender,
+    acknowledgements: bool,
+    pipeline_response: bool,
+}
+
+impl tower::Service<http::Request<Body>> for Service {
+    type Response = http::Response<BoxBody>;
+    type Error = Infallible;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: http::Request<Body>) -> Self::Future {
+        let method = match self.methods.get(request.uri().path()) {
+            Some(method) => Arc::clone(method),
+            None => {
+                let status =
+                    Status::unimplemented(format!("Method {} is not served", request.uri().path()));
+                return future::ok(status.to_http()).boxed();
+            }
+        };
+
+        let codec = MethodCodec {
+            method: Arc::clone(&method),
+        };
+        let handler = MethodHandler {
+            service: self.clone(),
+            method,
+        };
+        async move {
+            let mut grpc = Grpc::new(codec).accept_compressed(CompressionEncoding::Gzip);
+            Ok(grpc.client_streaming(handler, request).await)
+        }
+        .boxed()
+    }
+}
+
+/// Handles the requests of a method, whether the client streams messages or sends a single one.
+struct MethodHandler {
+    service: Service,
+    method: Arc<Method>,
+}
+
+impl ClientStreamingService<LogEvent> for MethodHandler {
+    type Response = Bytes;
+    type Future = BoxFuture<'static, Result<Response<Bytes>, Status>>;
+
+    fn call(&mut self, request: Request<Streaming<LogEvent>>) -> Self::Future {
+        let Service {
+            method_key,
+            out,
+            acknowledgements,
+            pipeline_response,
+            ..
+        } = self.service.clone();
+        let method = Arc::clone(&self.method);
+        async move {
+            let (response_handle, response_receiver) = if pipeline_response {
+                let (handle, receiver) = ResponseHandle::new_with_receiver();
+                (Some(handle), Some(receiver))
+            } else {
+                (None, None)
+            };
+
+            let mut stream = request.into_inner();
+            while let Some(mut log) = stream.message().await? {
+                log.try_insert(method_key.as_ref(), method.name.clone());
+                log.try_insert(log_schema().source_type_key(), Bytes::from("grpc_server"));
+                log.try_insert(log_schema().timestamp_key(), Utc::now());
+
+                if let Some(handle) = &response_handle {
+                    log.metadata_mut().set_response_handle(handle.clone());
+                }
+
+                let mut events = vec![Event::from(log)];
+                let count = events.len();
+                emit!(EventsReceived {
+                    count,
+                    byte_size: events.size_of(),
+                });
+
+                let receiver = BatchNotifier::maybe_apply_to(acknowledgements, &mut events);
+                let mut out = out.clone();
+                let sent = match &method.output {
+                    Some(output) => out.send_batch_named(output, events).await,
+                    None => out.send_batch(events).await,
+                };
+                if let Err(error) = sent {
+                    let message = error.to_string();
+                    emit!(StreamClosedError { error, count });
+                    return Err(Status::unavailable(message));
+                }
+                handle_batch_status(receiver).await?;
+            }
+            drop(response_handle);
+
+            match response_receiver {
+                Some(receiver) => match receiver.await {
+                    Some(response) => pipeline_response_message(&method, response),
+                    None => Ok(Response::new(method.response.clone())),
+                },
+                None => Ok(Response::new(method.response.clone())),
+            }
+        }
+        .boxed()
+    }
+}
+
+/// Builds the response of a method from the response set downstream of the source.
+fn pipeline_response_message(
+    method: &Method,
+    response: vector_core::event::Response,
+) -> Result<Response<Bytes>, Status> {
+    let body = String::from_utf8_lossy(&response.body);
+    if response.status >= 400 {
+        return Err(Status::new(status_code(response.status), body));
+    }
+    if body.is_empty() {
+        return Ok(Response::new(method.response.clone()));
+    }
+    serde_json::from_str(&body)
+        .map_err(|error| error.to_string())
+        .and_then(|fields| {
+            descriptor::encode(&method.response_message, &fields).map_err(|error| error.to_string())
+        })
+        .map(Response::new)
+        .map_err(|error| Status::internal(format!("Invalid response message: {}", error)))
+}
+
+/// Maps the HTTP status of an error response to the gRPC status with the same meaning.
+const fn status_code(status: u16) -> Code {
+    match status {
+        400 | 422 => Code::InvalidArgument,
+        401 => Code::Unauthenticated,
+        403 => Code::PermissionDenied,
+        404 => Code::NotFound,
+        409 => Code::Aborted,
+        412 => Code::FailedPrecondition,
+        429 => Code::ResourceExhausted,
+        499 => Code::Cancelled,
+        501 => Code::Unimplemented,
+        503 => Code::Unavailable,
+        504 => Code::DeadlineExceeded,
+        400..=499 => Code::FailedPrecondition,
+        _ => Code::Internal,
+    }
+}
+
+async fn handle_batch_status(receiver: Option<BatchStatusReceiver>) -> Result<(), Status> {
+    let status = match receiver {
+        Some(receiver) => receiver.await,
+        None => BatchStatus::Delivered,
+    };
+
+    match status {
+        BatchStatus::Errored => Err(Status::internal("Delivery error")),
+        BatchStatus::Rejected => Err(Status::data_loss("Delivery failed")),
+        BatchStatus::Delivered => Ok(()),
+    }
+}
+
+/// Decodes the request messages of a method into events, and encodes its response.
+#[derive(Clone)]
+struct MethodCodec {
+    method: Arc<Method>,
+}
+
+impl Codec for MethodCodec {
+    type Encode = Bytes;
+    type Decode = LogEvent;
+    type Encoder = ResponseEncoder;
+    type Decoder = Self;
+
+    fn encoder(&mut self) -> Self::Encoder {
+        ResponseEncoder
+    }
+
+    fn decoder(&mut self) -> Self::Decoder {
+        self.clone()
+    }
+}
+
+impl Decoder for MethodCodec {
+    type Item = LogEvent;
+    type Error = Status;
+
+    fn decode(&mut self, src: &mut DecodeBuf<'_>) -> Result<Option<Self::Item>, Self::Error> {
+        let message = src.copy_to_bytes(src.remaining());
+        match descriptor::decode(&self.method.input, message) {
+            Ok(fields) => Ok(Some(LogEvent::from(fields))),
+            Err(error) => {
+                let error = error.to_string();
+                emit!(GrpcServerInvalidMessage {
+                    method: &self.method.name,
+                    error: &error,
+                });
+                Err(Status::invalid_argument(error))
+            }
+        }
+    }
+}
+
+/// Encodes responses, which are encoded already.
+struct ResponseEncoder;
+
+impl Encoder for ResponseEncoder {
+    type Item = Bytes;
+    type Error = Status;
+
+    fn encode(&mut self, item: Self::Item, dst: &mut EncodeBuf<'_>) -> Result<(), Self::Error> {
+        dst.put(item);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::StreamExt;
+    use prost::Message as _;
+    use tonic::transport::Endpoint;
+
+    use super::*;
+    use crate::{
+        event::{EventStatus, Value},
+        test_util::{
+            collect_n,
+            components::{assert_source_compliance, SOURCE_TAGS},
+            next_addr, temp_file, wait_for_tcp,
+        },
+    };
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<GrpcServerConfig>();
+    }
+
+    fn config(address: SocketAddr, methods: &str) -> GrpcServerConfig {
+        let path = temp_file();
+        std::fs::write(&path, descriptor::tests::descriptor_set().encode_to_vec()).unwrap();
+        toml::from_str(&format!(
+            r#"
+                address = "{}"
+                descriptor_set_file = {:?}
+                {}
+            "#,
+            address, path, methods
+        ))
+        .unwrap()
+    }
+
+    /// Passes encoded messages through.
+    #[derive(Clone)]
+    struct RawCodec;
+
+    impl Codec for RawCodec {
+        type Encode = Bytes;
+        type Decode = Bytes;
+        type Encoder = ResponseEncoder;
+        type Decoder = Self;
+
+        fn encoder(&mut self) -> Self::Encoder {
+            ResponseEncoder
+        }
+
+        fn decoder(&mut self) -> Self::Decoder {
+            Self
+        }
+    }
+
+    impl Decoder for RawCodec {
+        type Item = Bytes;
+        type Error = Status;
+
+        fn decode(&mut self, src: &mut DecodeBuf<'_>) -> Result<Option<Self::Item>, Self::Error> {
+            Ok(Some(src.copy_to_bytes(src.remaining())))
+        }
+    }
+
+    async fn call(
+        address: SocketAddr,
+        path: &'static str,
+        message: Bytes,
+    ) -> Result<Bytes, Status> {
+        let channel = Endpoint::from_shared(format!("http://{}", address))
+            .unwrap()
+            .connect()
+            .await
+            .unwrap();
+        let mut client = tonic::client::Grpc::new(channel);
+        client.ready().await.unwrap();
+        client
+            .unary(
+                Request::new(message),
+                http::uri::PathAndQuery::from_static(path),
+                RawCodec,
+            )
+            .await
+            .map(Response::into_inner)
+    }
+
+    #[test]
+    fn rejects_unknown_methods() {
+        let config = config(
+            next_addr(),
+            r#"methods."acme.agent.v1.AgentService/Pull" = {}"#,
+        );
+        let descriptors = config.descriptors().unwrap();
+        assert!(matches!(
+            config.build_methods(&descriptors),
+            Err(BuildError::UnknownMethod { .. })
+        ));
+    }
+
+    #[test]
+    fn lists_outputs() {
+        let config = config(
+            next_addr(),
+            r#"
+                [methods."acme.agent.v1.AgentService/Push"]
+                output = "events"
+            "#,
+        );
+        let outputs = config
+            .outputs(LogNamespace::Legacy)
+            .into_iter()
+            .map(|output| output.port)
+            .collect::<Vec<_>>();
+        assert_eq!(outputs, vec![None, Some("events".to_owned())]);
+    }
+
+    #[tokio::test]
+    async fn receives_messages() {
+        assert_source_compliance(&SOURCE_TAGS, async {
+            let address = next_addr();
+            let config = config(
+                address,
+                r#"
+                    [methods."acme.agent.v1.AgentService/Push"]
+                    output = "events"
+                    response = '{"accepted": 1}'
+                "#,
+            );
+            let descriptors = config.descriptors().unwrap();
+
+            let (mut sender, _default) = SourceSender::new_test_finalize(EventStatus::Delivered);
+            let events = sender.add_outputs(EventStatus::Delivered, "events".to_owned());
+            let context = SourceContext::new_test(sender, None);
+            tokio::spawn(async move { config.build(context).await.unwrap().await.unwrap() });
+            wait_for_tcp(address).await;
+
+            let message = descriptor::encode(
+                &descriptors.message("acme.agent.v1.Event").unwrap(),
+                &serde_json::json!({ "message": "disk full", "level": "ERROR" }),
+            )
+            .unwrap();
+            let response = call(address, "/acme.agent.v1.AgentService/Push", message)
+                .await
+                .unwrap();
+            assert_eq!(
+                Value::Object(
+                    descriptor::decode(
+                        &descriptors.message("acme.agent.v1.PushResponse").unwrap(),
+                        response
+                    )
+                    .unwrap()
+                ),
+                Value::from(serde_json::json!({ "accepted": 1 }))
+            );
+
+            let events = collect_n(events, 1)
+                .await
+                .into_iter()
+                .flat_map(|events| events.into_events())
+                .collect::<Vec<_>>();
+            let log = events[0].as_log();
+            assert_eq!(log["message"], "disk full".into());
+            assert_eq!(log["level"], "ERROR".into());
+            assert_eq!(log["grpc_method"], "acme.agent.v1.AgentService/Push".into());
+            assert_eq!(log[log_schema().source_type_key()], "grpc_server".into());
+
+            let status = call(address, "/acme.agent.v1.AgentService/Pull", Bytes::new())
+                .await
+                .unwrap_err();
+            assert_eq!(status.code(), Code::Unimplemented);
+        })
+        .await;
+    }
+
+    #[tokio::test]
+    async fn answers_with_the_pipeline_response() {
+        let address = next_addr();
+        let config = config(
+            address,
+            r#"
+                pipeline_response = true
+                methods."acme.agent.v1.AgentService/Push" = {}
+            "#,
+        );
+        let descriptors = config.descriptors().unwrap();
+
+        let (sender, mut events) = SourceSender::new_test_finalize(EventStatus::Delivered);
+        let context = SourceContext::new_test(sender, None);
+        tokio::spawn(async move { config.build(context).await.unwrap().await.unwrap() });
+        wait_for_tcp(address).await;
+
+        let message = descriptor::encode(
+            &descriptors.message("acme.agent.v1.Event").unwrap(),
+            &serde_json::json!({ "message": "disk full" }),
+        )
+        .unwrap();
+        let request = tokio::spawn(call(address, "/acme.agent.v1.AgentService/Push", message));
+
+        let event = events.next().await.unwrap();
+        event
+            .metadata()
+            .response_handle()
+            .unwrap()
+            .set(vector_core::event::Response {
+                status: 422,
+                body: Bytes::from("missing level"),
+            });
+        drop(event);
+
+        let status = request.await.unwrap().unwrap_err();
+        assert_eq!(status.code(), Code::InvalidArgument);
+        assert_eq!(status.message(), "missing level");
+    }
+}
@@ -2,6 +2,7 @@ use std::{
     collections::HashMap,
     io::{BufRead, BufReader},
     net::SocketAddr,
+    num::NonZeroUsize,
     str::FromStr,
 };
 
@@ -26,7 +27,9 @@ use crate::{
     internal_events::{HerokuLogplexRequestReadError, HerokuLogplexRequestReceived},
     serde::{bool_or_struct, default_decoding, default_framing_message_based},
     sources::http::HttpMethod,
-    sources::util::{add_query_parameters, ErrorMessage, HttpSource, HttpSourceAuthConfig},
+    sources::util::{
+        add_query_parameters, ErrorMessage, HttpSource, HttpSourceAuthConfig, RequestLimit,
+    },
     tls::TlsEnableableConfig,
 };
 use lookup::event_path;
@@ -62,6 +65,24 @@ pub struct LogplexConfig {
     #[configurable(derived)]
     #[serde(default, deserialize_with = "bool_or_struct")]
     acknowledgements: AcknowledgementsConfig,
+
+    /// The maximum number of requests handled at the same time.
+    ///
+    /// Further requests are answered with `503 Service Unavailable` and a `Retry-After` header,
+    /// which makes Logplex retry them later, instead of being held in memory until the pipeline
+    /// catches up. With acknowledgements enabled, requests are pending until their events are
+    /// delivered. By default, the number of requests is not limited.
+    #[serde(default)]
+    max_concurrent_requests: Option<NonZeroUsize>,
+
+    /// The number of seconds Logplex is told to wait before retrying a request rejected because
+    /// of `max_concurrent_requests`.
+    #[serde(default = "default_retry_after_secs")]
+    retry_after_secs: u64,
+}
+
+const fn default_retry_after_secs() -> u64 {
+    5
 }
 
 impl GenerateConfig for LogplexConfig {
@@ -74,6 +95,8 @@ impl GenerateConfig for LogplexConfig {
             framing: default_framing_message_based(),
             decoding: default_decoding(),
             acknowledgements: AcknowledgementsConfig::default(),
+            max_concurrent_requests: None,
+            retry_after_secs: default_retry_after_secs(),
         })
         .unwrap()
     }
@@ -83,6 +106,7 @@ impl GenerateConfig for LogplexConfig {
 struct LogplexSource {
     query_parameters: Vec<String>,
     decoder: Decoder,
+    request_limit: Option<RequestLimit>,
 }
 
 impl HttpSource for LogplexSource {
@@ -97,6 +121,10 @@ impl HttpSource for LogplexSource {
         add_query_parameters(&mut events, &self.query_parameters, query_parameters);
         Ok(events)
     }
+
+    fn request_limit(&self) -> Option<RequestLimit> {
+        self.request_limit
+    }
 }
 
 #[async_trait::async_trait]
@@ -111,6 +139,12 @@ impl SourceConfig for LogplexConfig {
         let source = LogplexSource {
             query_parameters: self.query_parameters.clone(),
             decoder,
+            request_limit: self.max_concurrent_requests.map(|max_concurrent_requests| {
+                RequestLimit {
+                    max_concurrent_requests: max_concurrent_requests.get(),
+                    retry_after_secs: self.retry_after_secs,
+                }
+            }),
         };
         source.run(
             self.address,
@@ -125,7 +159,8 @@ impl SourceConfig for LogplexConfig {
     }
 
     fn outputs(&self, _global_log_namespace: LogNamespace) -> Vec<Output> {
-        vec![Output::default(self.decoding.output_type())]
+        self.decoding
+            .with_errors_output(vec![Output::default(self.decoding.output_type())])
     }
 
     fn resources(&self) -> Vec<Resource> {
@@ -157,19 +192,28 @@ fn decode_message(
     });
 
     // Deal with body
-    let events = body_to_events(decoder, body);
+    let frames = body_to_frames(body);
 
-    if events.len() != msg_count {
+    // The header counts Logplex messages, while the decoder may turn a message into any number of
+    // events, so the check is done before decoding.
+    if frames.len() != msg_count {
         let error_msg = format!(
-            "Parsed event count does not match message count header: {} vs {}",
-            events.len(),
+            "Message count in body does not match message count header: {} vs {}",
+            frames.len(),
             msg_count
         );
+        return Err(header_error_message("Logplex-Msg-Count", &error_msg));
+    }
 
-        if cfg!(test) {
-            panic!("{}", error_msg);
+    let mut events = frames
+        .into_iter()
+        .flat_map(|line| line_to_events(decoder.clone(), line))
+        .collect::<Vec<_>>();
+    for event in &mut events {
+        if let Event::Log(log) = event {
+            log.try_insert(event_path!("drain_token"), drain_token.to_owned());
+            log.try_insert(event_path!("frame_id"), frame_id.to_owned());
         }
-        return Err(header_error_message("Logplex-Msg-Count", &error_msg));
     }
 
     Ok(events)
@@ -192,7 +236,7 @@ fn header_error_message(name: &str, msg: &str) -> ErrorMessage {
     )
 }
 
-fn body_to_events(decoder: Decoder, body: Bytes) -> Vec<Event> {
+fn body_to_frames(body: Bytes) -> Vec<String> {
     let rdr = BufReader::new(body.reader());
     rdr.lines()
         .filter_map(|res| {
@@ -200,7 +244,6 @@ fn body_to_events(decoder: Decoder, body: Bytes) -> Vec<Event> {
                 .ok()
         })
         .filter(|s| !s.is_empty())
-        .flat_map(|line| line_to_events(decoder.clone(), line))
         .collect()
 }
 
@@ -269,14 +312,14 @@ fn line_to_events(mut decoder: Decoder, line: String) -> SmallVec<[Event; 1]> {
 
 #[cfg(test)]
 mod tests {
-    use std::net::SocketAddr;
+    use std::{net::SocketAddr, num::NonZeroUsize, time::Duration};
 
     use chrono::{DateTime, Utc};
     use futures::Stream;
     use pretty_assertions::assert_eq;
     use vector_core::event::{Event, EventStatus, Value};
 
-    use super::{HttpSourceAuthConfig, LogplexConfig};
+    use super::{default_retry_after_secs, HttpSourceAuthConfig, LogplexConfig};
     use crate::{
         config::{log_schema, SourceConfig, SourceContext},
         serde::{default_decoding, default_framing_message_based},
@@ -310,6 +353,8 @@ mod tests {
                 framing: default_framing_message_based(),
                 decoding: default_decoding(),
                 acknowledgements: acknowledgements.into(),
+                max_concurrent_requests: None,
+                retry_after_secs: default_retry_after_secs(),
             }
             .build(context)
             .await
@@ -327,20 +372,30 @@ mod tests {
         auth: Option<HttpSourceAuthConfig>,
         query: &str,
     ) -> u16 {
-        let len = body.lines().count();
+        send_with_count(address, body, auth, query, body.lines().count())
+            .await
+            .status()
+            .as_u16()
+    }
+
+    async fn send_with_count(
+        address: SocketAddr,
+        body: &str,
+        auth: Option<HttpSourceAuthConfig>,
+        query: &str,
+        msg_count: usize,
+    ) -> reqwest::Response {
         let mut req = reqwest::Client::new().post(&format!("http://{}/events?{}", address, query));
         if let Some(auth) = auth {
             req = req.basic_auth(auth.username, Some(auth.password.inner()));
         }
-        req.header("Logplex-Msg-Count", len)
+        req.header("Logplex-Msg-Count", msg_count)
             .header("Logplex-Frame-Id", "frame-foo")
             .header("Logplex-Drain-Token", "drain-bar")
             .body(body.to_owned())
             .send()
             .await
             .unwrap()
-            .status()
-            .as_u16()
     }
 
     fn make_auth() -> HttpSourceAuthConfig {
@@ -395,9 +450,66 @@ mod tests {
             assert_eq!(log[log_schema().source_type_key()], "heroku_logs".into());
             assert_eq!(log["appname"], "lumberjack-store".into());
             assert_eq!(log["absent"], Value::Null);
+            assert_eq!(log["drain_token"], "drain-bar".into());
+            assert_eq!(log["frame_id"], "frame-foo".into());
         }).await;
     }
 
+    #[tokio::test]
+    async fn logplex_rejects_wrong_message_count() {
+        let (_rx, addr) = source(None, vec![], EventStatus::Delivered, true).await;
+
+        let response = send_with_count(addr, SAMPLE_BODY, None, "", 2).await;
+        assert_eq!(400, response.status().as_u16());
+    }
+
+    #[tokio::test]
+    async fn logplex_counts_messages_not_events() {
+        let body =
+            r#"85 <158>1 2020-01-08T22:33:57.353034+00:00 host heroku router - [{"a":1},{"b":2}]"#;
+        let (sender, rx) = SourceSender::new_test_finalize(EventStatus::Delivered);
+        let address = next_addr();
+        let context = SourceContext::new_test(sender, None);
+        let config = LogplexConfig {
+            decoding: codecs::decoding::DeserializerCodec::Json.into(),
+            ..toml::from_str(&format!(r#"address = "{}""#, address)).unwrap()
+        };
+        tokio::spawn(async move { config.build(context).await.unwrap().await.unwrap() });
+        wait_for_tcp(address).await;
+
+        let events = spawn_collect_n(
+            async move { assert_eq!(200, send(address, body, None, "").await) },
+            rx,
+            2,
+        )
+        .await;
+
+        assert_eq!(events.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn logplex_rejects_requests_over_limit() {
+        // Events are never delivered, so requests stay pending.
+        let (sender, _rx) = SourceSender::new_test();
+        let address = next_addr();
+        let context = SourceContext::new_test(sender, None);
+        let config = LogplexConfig {
+            acknowledgements: true.into(),
+            max_concurrent_requests: NonZeroUsize::new(1),
+            retry_after_secs: 30,
+            ..toml::from_str(&format!(r#"address = "{}""#, address)).unwrap()
+        };
+        tokio::spawn(async move { config.build(context).await.unwrap().await.unwrap() });
+        wait_for_tcp(address).await;
+
+        tokio::spawn(async move { send(address, SAMPLE_BODY, None, "").await });
+        tokio::time::sleep(Duration::from_millis(500)).await;
+
+        let response = send_with_count(address, SAMPLE_BODY, None, "", 1).await;
+        assert_eq!(503, response.status().as_u16());
+        assert_eq!(response.headers()["retry-after"], "30");
+    }
+
     #[tokio::test]
     async fn logplex_handles_failures() {
         assert_source_compliance(&HTTP_PUSH_SOURCE_TAGS, async {
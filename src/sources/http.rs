@@ -3,11 +3,12 @@ use std::{collections::HashMap, net::SocketAddr};
 use bytes::{Bytes, BytesMut};
 use chrono::Utc;
 use codecs::{
-    decoding::{DeserializerConfig, FramingConfig},
+    decoding::{DecodingErrorPolicy, DeserializerConfig, FramingConfig, DECODING_ERRORS_OUTPUT},
     BytesDecoderConfig, BytesDeserializerConfig, JsonDeserializerConfig,
     NewlineDelimitedDecoderConfig,
 };
 use http::StatusCode;
+use indexmap::IndexMap;
 use lookup::event_path;
 use tokio_util::codec::Decoder as _;
 use vector_config::configurable_component;
@@ -116,6 +117,39 @@ pub struct SimpleHttpConfig {
     #[configurable(derived)]
     #[serde(default, deserialize_with = "bool_or_struct")]
     acknowledgements: AcknowledgementsConfig,
+
+    /// A table of route identifiers to the URL paths served by the route, each with its own decoding.
+    ///
+    /// The events of requests to a route are sent to the `<source_id>.<route_id>` output, so that a
+    /// single listener can receive several kinds of payloads. Routes are matched in order, and
+    /// `strict_path` applies to their paths as it does to `path`. Requests matching no route are
+    /// handled by `path`, and the events are sent to the default output, or rejected with a `404 Not
+    /// Found` response if they don't match `path` either.
+    #[serde(default)]
+    routes: IndexMap<String, HttpRouteConfig>,
+
+    /// Whether to answer requests with the response set by a downstream `respond` transform.
+    ///
+    /// The source then waits for all the events of a request to be processed by the pipeline,
+    /// rather than only sent into it, before answering. Requests whose events are not given a
+    /// response are answered with `200 OK`.
+    #[serde(default)]
+    pipeline_response: bool,
+}
+
+/// A route of the `http` source.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct HttpRouteConfig {
+    /// The URL path served by the route.
+    path: String,
+
+    #[configurable(derived)]
+    framing: Option<FramingConfig>,
+
+    #[configurable(derived)]
+    decoding: Option<DeserializerConfig>,
 }
 
 impl GenerateConfig for SimpleHttpConfig {
@@ -134,6 +168,8 @@ impl GenerateConfig for SimpleHttpConfig {
             framing: None,
             decoding: Some(default_decoding()),
             acknowledgements: AcknowledgementsConfig::default(),
+            routes: IndexMap::new(),
+            pipeline_response: false,
         })
         .unwrap()
     }
@@ -147,12 +183,76 @@ fn default_path_key() -> String {
     "path".to_string()
 }
 
+fn build_decoder(
+    framing: &Option<FramingConfig>,
+    decoding: &Option<DeserializerConfig>,
+) -> Decoder {
+    let decoding = decoding.clone().unwrap_or_else(default_decoding);
+    let framing = framing
+        .clone()
+        .unwrap_or_else(|| decoding.default_stream_framing());
+    DecodingConfig::new(framing, decoding, LogNamespace::Legacy).build()
+}
+
+/// Whether a request path matches a configured path, segment by segment, exactly if `strict` is
+/// set or as a prefix otherwise.
+fn path_matches(path: &str, request_path: &str, strict: bool) -> bool {
+    let mut expected = path.split('/').filter(|segment| !segment.is_empty());
+    let mut actual = request_path
+        .split('/')
+        .filter(|segment| !segment.is_empty());
+    loop {
+        match (expected.next(), actual.next()) {
+            (None, None) => return true,
+            (None, Some(_)) => return !strict,
+            (Some(expected), Some(actual)) if expected == actual => continue,
+            _ => return false,
+        }
+    }
+}
+
+#[derive(Clone)]
+struct HttpRoute {
+    output: String,
+    path: String,
+    decoder: Decoder,
+}
+
 #[derive(Clone)]
 struct SimpleHttpSource {
     headers: Vec<String>,
     query_parameters: Vec<String>,
     path_key: String,
     decoder: Decoder,
+    /// The paths of requests are only matched here, rather than by the server, when there are
+    /// routes.
+    path: String,
+    strict_path: bool,
+    routes: Vec<HttpRoute>,
+    pipeline_response: bool,
+}
+
+impl SimpleHttpSource {
+    /// Finds the route of a request, or `None` if it is handled by `path`.
+    fn route(&self, request_path: &str) -> Result<Option<&HttpRoute>, ErrorMessage> {
+        if self.routes.is_empty() {
+            return Ok(None);
+        }
+        if let Some(route) = self
+            .routes
+            .iter()
+            .find(|route| path_matches(&route.path, request_path, self.strict_path))
+        {
+            Ok(Some(route))
+        } else if path_matches(&self.path, request_path, self.strict_path) {
+            Ok(None)
+        } else {
+            Err(ErrorMessage::new(
+                StatusCode::NOT_FOUND,
+                "Not found".to_string(),
+            ))
+        }
+    }
 }
 
 impl HttpSource for SimpleHttpSource {
@@ -163,7 +263,10 @@ impl HttpSource for SimpleHttpSource {
         query_parameters: HashMap<String, String>,
         request_path: &str,
     ) -> Result<Vec<Event>, ErrorMessage> {
-        let mut decoder = self.decoder.clone();
+        let mut decoder = match self.route(request_path)? {
+            Some(route) => route.decoder.clone(),
+            None => self.decoder.clone(),
+        };
         let mut events = Vec::new();
         let mut bytes = BytesMut::new();
         bytes.extend_from_slice(&body);
@@ -199,6 +302,17 @@ impl HttpSource for SimpleHttpSource {
 
         Ok(events)
     }
+
+    fn output_name(&self, path: &str) -> Option<String> {
+        self.route(path)
+            .ok()
+            .flatten()
+            .map(|route| route.output.clone())
+    }
+
+    fn pipeline_response(&self) -> bool {
+        self.pipeline_response
+    }
 }
 
 #[async_trait::async_trait]
@@ -208,8 +322,16 @@ impl SourceConfig for SimpleHttpConfig {
             return Err("Using `encoding` is deprecated and does not have any effect when `decoding` or `framing` is provided. Configure `framing` and `decoding` instead.".into());
         }
 
-        let (framing, decoding) = if let Some(encoding) = self.encoding {
-            match encoding {
+        if self.routes.contains_key(DECODING_ERRORS_OUTPUT) {
+            return Err(format!(
+                "Cannot have a route with the reserved name `{}`.",
+                DECODING_ERRORS_OUTPUT
+            )
+            .into());
+        }
+
+        let decoder = if let Some(encoding) = self.encoding {
+            let (framing, decoding): (FramingConfig, DeserializerConfig) = match encoding {
                 Encoding::Text => (
                     NewlineDelimitedDecoderConfig::new().into(),
                     BytesDeserializerConfig::new().into(),
@@ -226,28 +348,43 @@ impl SourceConfig for SimpleHttpConfig {
                     BytesDecoderConfig::new().into(),
                     BytesDeserializerConfig::new().into(),
                 ),
-            }
+            };
+            DecodingConfig::new(framing, decoding, LogNamespace::Legacy).build()
         } else {
-            let decoding = self.decoding.clone().unwrap_or_else(default_decoding);
-            let framing = self
-                .framing
-                .clone()
-                .unwrap_or_else(|| decoding.default_stream_framing());
-            (framing, decoding)
+            build_decoder(&self.framing, &self.decoding)
+        };
+
+        let routes = self
+            .routes
+            .iter()
+            .map(|(output, route)| HttpRoute {
+                output: output.clone(),
+                path: route.path.clone(),
+                decoder: build_decoder(&route.framing, &route.decoding),
+            })
+            .collect::<Vec<_>>();
+        // With routes, the server accepts any path, which the source then matches itself.
+        let (path, strict_path) = if routes.is_empty() {
+            (self.path.as_str(), self.strict_path)
+        } else {
+            ("", false)
         };
 
-        let decoder = DecodingConfig::new(framing, decoding, LogNamespace::Legacy).build();
         let source = SimpleHttpSource {
             headers: self.headers.clone(),
             query_parameters: self.query_parameters.clone(),
             path_key: self.path_key.clone(),
             decoder,
+            path: self.path.clone(),
+            strict_path: self.strict_path,
+            routes,
+            pipeline_response: self.pipeline_response,
         };
         source.run(
             self.address,
-            self.path.as_str(),
+            path,
             self.method,
-            self.strict_path,
+            strict_path,
             &self.tls,
             &self.auth,
             cx,
@@ -256,12 +393,30 @@ impl SourceConfig for SimpleHttpConfig {
     }
 
     fn outputs(&self, _global_log_namespace: LogNamespace) -> Vec<Output> {
-        vec![Output::default(
-            self.decoding
-                .as_ref()
-                .map(|d| d.output_type())
-                .unwrap_or(DataType::Log),
-        )]
+        let output_type = |decoding: &Option<DeserializerConfig>| {
+            decoding.as_ref().map_or(DataType::Log, |d| d.output_type())
+        };
+        let mut outputs = vec![Output::default(output_type(&self.decoding))];
+        outputs.extend(
+            self.routes
+                .iter()
+                .map(|(id, route)| Output::default(output_type(&route.decoding)).with_port(id)),
+        );
+
+        // Decoding errors of all routes are sent to the same output.
+        let routing_errors = self
+            .decoding
+            .iter()
+            .chain(
+                self.routes
+                    .values()
+                    .filter_map(|route| route.decoding.as_ref()),
+            )
+            .find(|decoding| decoding.on_error() == DecodingErrorPolicy::Route);
+        match routing_errors {
+            Some(decoding) => decoding.with_errors_output(outputs),
+            None => outputs,
+        }
     }
 
     fn resources(&self) -> Vec<Resource> {
@@ -308,16 +463,20 @@ mod tests {
         write::{GzEncoder, ZlibEncoder},
         Compression,
     };
-    use futures::Stream;
+    use futures::{Stream, StreamExt};
     use http::{HeaderMap, Method};
+    use indexmap::IndexMap;
     use pretty_assertions::assert_eq;
 
+    use vector_core::config::LogNamespace;
+
     use super::SimpleHttpConfig;
     use crate::sources::http::HttpMethod;
     use crate::{
         config::{log_schema, SourceConfig, SourceContext},
-        event::{Event, EventStatus, Value},
+        event::{Event, EventStatus, Response, Value},
         test_util::{
+            collect_n,
             components::{self, assert_source_compliance, HTTP_PUSH_SOURCE_TAGS},
             next_addr, spawn_collect_n, trace_init, wait_for_tcp,
         },
@@ -369,6 +528,8 @@ mod tests {
                 framing,
                 decoding,
                 acknowledgements: acknowledgements.into(),
+                routes: IndexMap::new(),
+                pipeline_response: false,
             }
             .build(context)
             .await
@@ -1105,4 +1266,110 @@ mod tests {
 
         assert_eq!(200, send_request(addr, "GET", "", "/").await);
     }
+
+    #[tokio::test]
+    async fn http_routes() {
+        components::init_test();
+        let (mut sender, rx) = SourceSender::new_test_finalize(EventStatus::Delivered);
+        let github = sender.add_outputs(EventStatus::Delivered, "github".to_owned());
+        let address = next_addr();
+        let config = toml::from_str::<SimpleHttpConfig>(&format!(
+            r#"
+                address = "{}"
+                path = "/logs"
+
+                [routes.github]
+                path = "/webhooks/github"
+                decoding.codec = "json"
+            "#,
+            address
+        ))
+        .unwrap();
+        let context = SourceContext::new_test(sender, None);
+        tokio::spawn(async move { config.build(context).await.unwrap().await.unwrap() });
+        wait_for_tcp(address).await;
+
+        assert_eq!(
+            200,
+            send_with_path(address, r#"{"action":"opened"}"#, "/webhooks/github").await
+        );
+        assert_eq!(200, send_with_path(address, "test body", "/logs").await);
+        assert_eq!(404, send_with_path(address, "test body", "/webhooks").await);
+
+        let events = collect_n(github, 1)
+            .await
+            .into_iter()
+            .flat_map(|events| events.into_events())
+            .collect::<Vec<_>>();
+        let log = events[0].as_log();
+        assert_eq!(log["action"], "opened".into());
+        assert_eq!(log["path"], "/webhooks/github".into());
+
+        let events = collect_n(rx, 1).await;
+        let log = events[0].as_log();
+        assert_eq!(log[log_schema().message_key()], "test body".into());
+        assert_eq!(log["path"], "/logs".into());
+    }
+
+    #[tokio::test]
+    async fn http_pipeline_response() {
+        components::init_test();
+        let (sender, mut rx) = SourceSender::new_test_finalize(EventStatus::Delivered);
+        let address = next_addr();
+        let config = toml::from_str::<SimpleHttpConfig>(&format!(
+            r#"
+                address = "{}"
+                pipeline_response = true
+            "#,
+            address
+        ))
+        .unwrap();
+        let context = SourceContext::new_test(sender, None);
+        tokio::spawn(async move { config.build(context).await.unwrap().await.unwrap() });
+        wait_for_tcp(address).await;
+
+        let request = tokio::spawn(async move {
+            let response = reqwest::Client::new()
+                .post(&format!("http://{}/", address))
+                .body("test body")
+                .send()
+                .await
+                .unwrap();
+            (response.status().as_u16(), response.text().await.unwrap())
+        });
+
+        let event = rx.next().await.unwrap();
+        event.metadata().response_handle().unwrap().set(Response {
+            status: 422,
+            body: "invalid".into(),
+        });
+        drop(event);
+        assert_eq!(request.await.unwrap(), (422, "invalid".to_owned()));
+    }
+
+    #[test]
+    fn http_routes_outputs() {
+        let config = toml::from_str::<SimpleHttpConfig>(
+            r#"
+                address = "0.0.0.0:8080"
+
+                [routes.github]
+                path = "/webhooks/github"
+                decoding.codec = "json"
+
+                [routes.stripe]
+                path = "/webhooks/stripe"
+            "#,
+        )
+        .unwrap();
+        let outputs = config
+            .outputs(LogNamespace::Legacy)
+            .into_iter()
+            .map(|output| output.port)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            outputs,
+            vec![None, Some("github".to_owned()), Some("stripe".to_owned())]
+        );
+    }
 }
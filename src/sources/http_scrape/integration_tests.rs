@@ -14,7 +14,7 @@ use crate::{
     tls::TlsConfig,
     SourceSender,
 };
-use codecs::decoding::DeserializerConfig;
+use codecs::decoding::DeserializerCodec;
 use vector_config::NamedComponent;
 use vector_core::config::log_schema;
 
@@ -60,6 +60,7 @@ async fn invalid_endpoint() {
         auth: None,
         tls: None,
         log_namespace: None,
+        coordination: None,
     })
     .await;
 }
@@ -71,12 +72,13 @@ async fn scraped_logs_bytes() {
         endpoint: format!("{}/logs/bytes", dufs_address()),
         scrape_interval_secs: INTERVAL_SECS,
         query: HashMap::new(),
-        decoding: DeserializerConfig::Bytes,
+        decoding: DeserializerCodec::Bytes.into(),
         framing: default_framing_message_based(),
         headers: HashMap::new(),
         auth: None,
         tls: None,
         log_namespace: None,
+        coordination: None,
     })
     .await;
     // panics if not log event
@@ -94,12 +96,13 @@ async fn scraped_logs_json() {
         endpoint: format!("{}/logs/json.json", dufs_address()),
         scrape_interval_secs: INTERVAL_SECS,
         query: HashMap::new(),
-        decoding: DeserializerConfig::Json,
+        decoding: DeserializerCodec::Json.into(),
         framing: default_framing_message_based(),
         headers: HashMap::new(),
         auth: None,
         tls: None,
         log_namespace: None,
+        coordination: None,
     })
     .await;
     // panics if not log event
@@ -117,12 +120,13 @@ async fn scraped_metrics_native_json() {
         endpoint: format!("{}/metrics/native.json", dufs_address()),
         scrape_interval_secs: INTERVAL_SECS,
         query: HashMap::new(),
-        decoding: DeserializerConfig::NativeJson,
+        decoding: DeserializerCodec::NativeJson.into(),
         framing: default_framing_message_based(),
         headers: HashMap::new(),
         auth: None,
         tls: None,
         log_namespace: None,
+        coordination: None,
     })
     .await;
 
@@ -141,12 +145,13 @@ async fn scraped_trace_native_json() {
         endpoint: format!("{}/traces/native.json", dufs_address()),
         scrape_interval_secs: INTERVAL_SECS,
         query: HashMap::new(),
-        decoding: DeserializerConfig::NativeJson,
+        decoding: DeserializerCodec::NativeJson.into(),
         framing: default_framing_message_based(),
         headers: HashMap::new(),
         auth: None,
         tls: None,
         log_namespace: None,
+        coordination: None,
     })
     .await;
 
@@ -164,12 +169,13 @@ async fn unauthorized_no_auth() {
         endpoint: format!("{}/logs/json.json", dufs_auth_address()),
         scrape_interval_secs: INTERVAL_SECS,
         query: HashMap::new(),
-        decoding: DeserializerConfig::Json,
+        decoding: DeserializerCodec::Json.into(),
         framing: default_framing_message_based(),
         headers: HashMap::new(),
         auth: None,
         tls: None,
         log_namespace: None,
+        coordination: None,
     })
     .await;
 }
@@ -181,7 +187,7 @@ async fn unauthorized_wrong_auth() {
         endpoint: format!("{}/logs/json.json", dufs_auth_address()),
         scrape_interval_secs: INTERVAL_SECS,
         query: HashMap::new(),
-        decoding: DeserializerConfig::Json,
+        decoding: DeserializerCodec::Json.into(),
         framing: default_framing_message_based(),
         headers: HashMap::new(),
         tls: None,
@@ -190,6 +196,7 @@ async fn unauthorized_wrong_auth() {
             password: "morpheus".to_string().into(),
         }),
         log_namespace: None,
+        coordination: None,
     })
     .await;
 }
@@ -201,7 +208,7 @@ async fn authorized() {
         endpoint: format!("{}/logs/json.json", dufs_auth_address()),
         scrape_interval_secs: INTERVAL_SECS,
         query: HashMap::new(),
-        decoding: DeserializerConfig::Json,
+        decoding: DeserializerCodec::Json.into(),
         framing: default_framing_message_based(),
         headers: HashMap::new(),
         tls: None,
@@ -210,6 +217,7 @@ async fn authorized() {
             password: "pass".to_string().into(),
         }),
         log_namespace: None,
+        coordination: None,
     })
     .await;
 }
@@ -221,7 +229,7 @@ async fn tls_invalid_ca() {
         endpoint: format!("{}/logs/json.json", dufs_https_address()),
         scrape_interval_secs: INTERVAL_SECS,
         query: HashMap::new(),
-        decoding: DeserializerConfig::Json,
+        decoding: DeserializerCodec::Json.into(),
         framing: default_framing_message_based(),
         headers: HashMap::new(),
         tls: Some(TlsConfig {
@@ -230,6 +238,7 @@ async fn tls_invalid_ca() {
         }),
         auth: None,
         log_namespace: None,
+        coordination: None,
     })
     .await;
 }
@@ -241,7 +250,7 @@ async fn tls_valid() {
         endpoint: format!("{}/logs/json.json", dufs_https_address()),
         scrape_interval_secs: INTERVAL_SECS,
         query: HashMap::new(),
-        decoding: DeserializerConfig::Json,
+        decoding: DeserializerCodec::Json.into(),
         framing: default_framing_message_based(),
         headers: HashMap::new(),
         tls: Some(TlsConfig {
@@ -250,6 +259,7 @@ async fn tls_valid() {
         }),
         auth: None,
         log_namespace: None,
+        coordination: None,
     })
     .await;
 }
@@ -262,12 +272,13 @@ async fn shutdown() {
         endpoint: format!("{}/logs/json.json", dufs_address()),
         scrape_interval_secs: INTERVAL_SECS,
         query: HashMap::new(),
-        decoding: DeserializerConfig::Json,
+        decoding: DeserializerCodec::Json.into(),
         framing: default_framing_message_based(),
         headers: HashMap::new(),
         tls: None,
         auth: None,
         log_namespace: None,
+        coordination: None,
     };
 
     // build the context for the source and get a SourceShutdownCoordinator to signal with
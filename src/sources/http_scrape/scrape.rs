@@ -12,6 +12,7 @@ use tokio_util::codec::Decoder as _;
 use crate::{
     codecs::{Decoder, DecodingConfig},
     config::{SourceConfig, SourceContext},
+    coordination::CoordinationConfig,
     http::Auth,
     serde::default_decoding,
     serde::default_framing_message_based,
@@ -78,6 +79,9 @@ pub struct HttpScrapeConfig {
     /// The namespace to use for logs. This overrides the global setting
     #[serde(default)]
     pub log_namespace: Option<bool>,
+
+    #[configurable(derived)]
+    pub coordination: Option<CoordinationConfig>,
 }
 
 impl Default for HttpScrapeConfig {
@@ -92,6 +96,7 @@ impl Default for HttpScrapeConfig {
             tls: None,
             auth: None,
             log_namespace: None,
+            coordination: None,
         }
     }
 }
@@ -125,6 +130,11 @@ impl SourceConfig for HttpScrapeConfig {
             log_namespace,
         };
 
+        let coordinator = match &self.coordination {
+            Some(coordination) => Some(coordination.build(&cx.key).await?),
+            None => None,
+        };
+
         let inputs = GenericHttpScrapeInputs {
             urls,
             interval_secs: self.scrape_interval_secs,
@@ -134,6 +144,7 @@ impl SourceConfig for HttpScrapeConfig {
             tls,
             proxy: cx.proxy.clone(),
             shutdown: cx.shutdown,
+            coordinator,
         };
 
         Ok(http_scrape(inputs, context, cx.out).boxed())
@@ -149,7 +160,9 @@ impl SourceConfig for HttpScrapeConfig {
             .schema_definition(log_namespace)
             .with_standard_vector_source_metadata();
 
-        vec![Output::default(self.decoding.output_type()).with_schema_definition(schema_definition)]
+        self.decoding
+            .with_errors_output(vec![Output::default(self.decoding.output_type())
+                .with_schema_definition(schema_definition)])
     }
 
     fn can_acknowledge(&self) -> bool {
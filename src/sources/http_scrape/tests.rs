@@ -4,7 +4,7 @@ use warp::{http::HeaderMap, Filter};
 
 use crate::{serde::default_decoding, serde::default_framing_message_based};
 use codecs::decoding::{
-    CharacterDelimitedDecoderOptions, DeserializerConfig, FramingConfig,
+    CharacterDelimitedDecoderOptions, DeserializerCodec, FramingConfig,
     NewlineDelimitedDecoderOptions,
 };
 use vector_core::event::Event;
@@ -55,6 +55,7 @@ async fn bytes_decoding() {
         tls: None,
         auth: None,
         log_namespace: None,
+        coordination: None,
     })
     .await;
 }
@@ -76,7 +77,7 @@ async fn json_decoding_newline_delimited() {
         endpoint: format!("http://{}/endpoint", in_addr),
         scrape_interval_secs: INTERVAL_SECS,
         query: HashMap::new(),
-        decoding: DeserializerConfig::Json,
+        decoding: DeserializerCodec::Json.into(),
         framing: FramingConfig::NewlineDelimited {
             newline_delimited: NewlineDelimitedDecoderOptions::default(),
         },
@@ -84,6 +85,7 @@ async fn json_decoding_newline_delimited() {
         tls: None,
         auth: None,
         log_namespace: None,
+        coordination: None,
     })
     .await;
 }
@@ -105,7 +107,7 @@ async fn json_decoding_character_delimited() {
         endpoint: format!("http://{}/endpoint", in_addr),
         scrape_interval_secs: INTERVAL_SECS,
         query: HashMap::new(),
-        decoding: DeserializerConfig::Json,
+        decoding: DeserializerCodec::Json.into(),
         framing: FramingConfig::CharacterDelimited {
             character_delimited: CharacterDelimitedDecoderOptions {
                 delimiter: b',',
@@ -116,6 +118,7 @@ async fn json_decoding_character_delimited() {
         tls: None,
         auth: None,
         log_namespace: None,
+        coordination: None,
     })
     .await;
 }
@@ -142,12 +145,13 @@ async fn request_query_applied() {
                 vec!["val1".to_string(), "val2".to_string()],
             ),
         ]),
-        decoding: DeserializerConfig::Json,
+        decoding: DeserializerCodec::Json.into(),
         framing: default_framing_message_based(),
         headers: HashMap::new(),
         tls: None,
         auth: None,
         log_namespace: None,
+        coordination: None,
     })
     .await;
 
@@ -212,6 +216,7 @@ async fn headers_applied() {
         auth: None,
         tls: None,
         log_namespace: None,
+        coordination: None,
     })
     .await;
 }
@@ -233,12 +238,13 @@ async fn accept_header_override() {
         endpoint: format!("http://{}/endpoint", in_addr),
         scrape_interval_secs: INTERVAL_SECS,
         query: HashMap::new(),
-        decoding: DeserializerConfig::Bytes,
+        decoding: DeserializerCodec::Bytes.into(),
         framing: default_framing_message_based(),
         headers: HashMap::from([("ACCEPT".to_string(), vec!["application/json".to_string()])]),
         auth: None,
         tls: None,
         log_namespace: None,
+        coordination: None,
     })
     .await;
 }
@@ -1,6 +1,5 @@
 use std::{
     collections::{HashMap, HashSet},
-    io::SeekFrom,
     iter::FromIterator,
     path::PathBuf,
     process::Stdio,
@@ -21,8 +20,7 @@ use once_cell::sync::Lazy;
 use serde_json::{Error as JsonError, Value as JsonValue};
 use snafu::{ResultExt, Snafu};
 use tokio::{
-    fs::{File, OpenOptions},
-    io::{self, AsyncReadExt, AsyncSeekExt, AsyncWriteExt},
+    io,
     process::{Child, Command},
     sync::{Mutex, MutexGuard},
     time::sleep,
@@ -36,10 +34,11 @@ use vector_config::configurable_component;
 use vector_core::config::LogNamespace;
 
 use crate::{
+    checkpoint::{CheckpointStore, CheckpointStoreConfig, CheckpointStoreError},
     config::{log_schema, AcknowledgementsConfig, DataType, Output, SourceConfig, SourceContext},
     event::{BatchNotifier, BatchStatus, BatchStatusReceiver, LogEvent, Value},
     internal_events::{
-        EventsReceived, JournaldCheckpointFileOpenError, JournaldCheckpointSetError,
+        EventsReceived, JournaldCheckpointLoadError, JournaldCheckpointSetError,
         JournaldInvalidRecordError, JournaldReadError, JournaldStartJournalctlError,
         StreamClosedError,
     },
@@ -127,6 +126,13 @@ pub struct JournaldConfig {
     /// By default, the global `data_dir` option is used. Please make sure the user Vector is running as has write permissions to this directory.
     pub data_dir: Option<PathBuf>,
 
+    /// Where the journal cursor is stored.
+    ///
+    /// By default, it's stored in the data directory of the source. Remote stores allow the
+    /// source to resume where it left off when running on ephemeral nodes.
+    #[configurable(derived)]
+    pub checkpoint_store: CheckpointStoreConfig,
+
     /// The `systemd` journal is read in batches, and a checkpoint is set at the end of each batch. This option limits the size of the batch.
     pub batch_size: Option<usize>,
 
@@ -191,10 +197,16 @@ impl SourceConfig for JournaldConfig {
             warn!("Option `remap_priority` has been deprecated. Please use the `remap` transform and function `to_syslog_level` instead.");
         }
 
-        let data_dir = cx
-            .globals
-            // source are only global, name can be used for subdir
-            .resolve_and_make_data_subdir(self.data_dir.as_ref(), cx.key.id())?;
+        let checkpoint_store = self
+            .checkpoint_store
+            .build(
+                &cx.key,
+                &cx.globals,
+                self.data_dir.as_ref(),
+                CHECKPOINT_FILENAME,
+                &cx.proxy,
+            )
+            .await?;
 
         if let Some(unit) = self
             .include_units
@@ -212,9 +224,6 @@ impl SourceConfig for JournaldConfig {
             return Err(BuildError::DuplicatedMatches { field, value }.into());
         }
 
-        let mut checkpoint_path = data_dir;
-        checkpoint_path.push(CHECKPOINT_FILENAME);
-
         let journalctl_path = self
             .journalctl_path
             .clone()
@@ -234,7 +243,7 @@ impl SourceConfig for JournaldConfig {
             JournaldSource {
                 include_matches,
                 exclude_matches,
-                checkpoint_path,
+                checkpoint_store: Arc::from(checkpoint_store),
                 batch_size,
                 remap_priority: self.remap_priority,
                 out: cx.out,
@@ -257,7 +266,7 @@ impl SourceConfig for JournaldConfig {
 struct JournaldSource {
     include_matches: Matches,
     exclude_matches: Matches,
-    checkpoint_path: PathBuf,
+    checkpoint_store: Arc<dyn CheckpointStore>,
     batch_size: usize,
     remap_priority: bool,
     out: SourceSender,
@@ -267,17 +276,10 @@ struct JournaldSource {
 
 impl JournaldSource {
     async fn run_shutdown(self, shutdown: ShutdownSignal) -> Result<(), ()> {
-        let checkpointer = StatefulCheckpointer::new(self.checkpoint_path.clone())
+        let checkpointer = StatefulCheckpointer::new(Arc::clone(&self.checkpoint_store))
             .await
             .map_err(|error| {
-                emit!(JournaldCheckpointFileOpenError {
-                    error,
-                    path: self
-                        .checkpoint_path
-                        .to_str()
-                        .unwrap_or("unknown")
-                        .to_string(),
-                });
+                emit!(JournaldCheckpointLoadError { error });
             })?;
 
         let checkpointer = SharedCheckpointer::new(checkpointer);
@@ -717,40 +719,24 @@ impl Finalizer {
 }
 
 struct Checkpointer {
-    file: File,
-    filename: PathBuf,
+    store: Arc<dyn CheckpointStore>,
 }
 
 impl Checkpointer {
-    async fn new(filename: PathBuf) -> Result<Self, io::Error> {
-        let file = OpenOptions::new()
-            .read(true)
-            .write(true)
-            .create(true)
-            .open(&filename)
-            .await?;
-        Ok(Checkpointer { file, filename })
+    const fn new(store: Arc<dyn CheckpointStore>) -> Self {
+        Checkpointer { store }
     }
 
-    async fn set(&mut self, token: &str) -> Result<(), io::Error> {
-        self.file.seek(SeekFrom::Start(0)).await?;
-        self.file.write_all(format!("{}\n", token).as_bytes()).await
+    async fn set(&self, token: &str) -> Result<(), CheckpointStoreError> {
+        self.store.store(Bytes::from(format!("{}\n", token))).await
     }
 
-    async fn get(&mut self) -> Result<Option<String>, io::Error> {
-        let mut buf = Vec::<u8>::new();
-        self.file.seek(SeekFrom::Start(0)).await?;
-        self.file.read_to_end(&mut buf).await?;
-        match buf.len() {
-            0 => Ok(None),
-            _ => {
-                let text = String::from_utf8_lossy(&buf);
-                match text.find('\n') {
-                    Some(nl) => Ok(Some(String::from(&text[..nl]))),
-                    None => Ok(None), // Maybe return an error?
-                }
-            }
-        }
+    async fn get(&self) -> Result<Option<String>, CheckpointStoreError> {
+        Ok(self.store.load().await?.and_then(|buf| {
+            let text = String::from_utf8_lossy(&buf);
+            // Maybe return an error if there is no newline?
+            text.find('\n').map(|nl| String::from(&text[..nl]))
+        }))
     }
 }
 
@@ -760,8 +746,8 @@ struct StatefulCheckpointer {
 }
 
 impl StatefulCheckpointer {
-    async fn new(filename: PathBuf) -> Result<Self, io::Error> {
-        let mut checkpointer = Checkpointer::new(filename).await?;
+    async fn new(store: Arc<dyn CheckpointStore>) -> Result<Self, CheckpointStoreError> {
+        let checkpointer = Checkpointer::new(store);
         let cursor = checkpointer.get().await?;
         Ok(Self {
             checkpointer,
@@ -772,15 +758,7 @@ impl StatefulCheckpointer {
     async fn set(&mut self, token: impl Into<String>) {
         let token = token.into();
         if let Err(error) = self.checkpointer.set(&token).await {
-            emit!(JournaldCheckpointSetError {
-                error,
-                filename: self
-                    .checkpointer
-                    .filename
-                    .to_str()
-                    .unwrap_or("unknown")
-                    .to_string(),
-            });
+            emit!(JournaldCheckpointSetError { error });
         }
         self.cursor = Some(token);
     }
@@ -805,6 +783,7 @@ mod checkpointer_tests {
     use tokio::fs::read_to_string;
 
     use super::*;
+    use crate::checkpoint::FileCheckpointStore;
 
     #[test]
     fn generate_config() {
@@ -816,9 +795,7 @@ mod checkpointer_tests {
         let tempdir = tempdir().unwrap();
         let mut filename = tempdir.path().to_path_buf();
         filename.push(CHECKPOINT_FILENAME);
-        let mut checkpointer = Checkpointer::new(filename.clone())
-            .await
-            .expect("Creating checkpointer failed!");
+        let checkpointer = Checkpointer::new(Arc::new(FileCheckpointStore::new(filename.clone())));
 
         assert!(checkpointer.get().await.unwrap().is_none());
 
@@ -853,7 +830,7 @@ mod tests {
 
     use super::*;
     use crate::{
-        config::ComponentKey, event::Event, event::EventStatus,
+        checkpoint::FileCheckpointStore, config::ComponentKey, event::Event, event::EventStatus,
         test_util::components::assert_source_compliance,
     };
 
@@ -883,9 +860,8 @@ mod tests {
                 fs::create_dir(&checkpoint_path).unwrap();
                 checkpoint_path.push(CHECKPOINT_FILENAME);
 
-                let mut checkpointer = Checkpointer::new(checkpoint_path.clone())
-                    .await
-                    .expect("Creating checkpointer failed!");
+                let checkpointer =
+                    Checkpointer::new(Arc::new(FileCheckpointStore::new(checkpoint_path)));
 
                 checkpointer
                     .set(cursor)
@@ -2,6 +2,7 @@ use std::{
     collections::{BTreeMap, HashMap},
     io::Cursor,
     sync::Arc,
+    time::Duration,
 };
 
 use async_stream::stream;
@@ -16,6 +17,7 @@ use rdkafka::{
     config::ClientConfig,
     consumer::{Consumer, StreamConsumer},
     message::{BorrowedMessage, Headers, Message},
+    TopicPartitionList,
 };
 use snafu::{ResultExt, Snafu};
 use tokio_util::codec::FramedRead;
@@ -30,8 +32,8 @@ use crate::{
     config::{log_schema, AcknowledgementsConfig, LogSchema, Output, SourceConfig, SourceContext},
     event::{BatchNotifier, BatchStatus, Event, Value},
     internal_events::{
-        KafkaBytesReceived, KafkaEventsReceived, KafkaOffsetUpdateError, KafkaReadError,
-        StreamClosedError,
+        KafkaBytesReceived, KafkaEventsReceived, KafkaOffsetUpdateError, KafkaPartitionPauseError,
+        KafkaPartitionsPaused, KafkaPartitionsResumed, KafkaReadError, StreamClosedError,
     },
     kafka::{KafkaAuthConfig, KafkaStatisticsContext},
     serde::{bool_or_struct, default_decoding, default_framing_message_based},
@@ -91,6 +93,16 @@ pub struct KafkaSourceConfig {
     #[serde(default = "default_commit_interval_ms")]
     commit_interval_ms: u64,
 
+    /// The amount of time, in milliseconds, that sending events downstream may be blocked before the consumer pauses
+    /// fetching from its assigned partitions.
+    ///
+    /// While paused, `librdkafka` stops prefetching messages into its local queues, which keeps memory usage bounded and
+    /// lets consumer group lag reflect the backpressure being applied by the rest of the pipeline. Fetching is resumed
+    /// as soon as the blocked send completes.
+    #[serde(default = "default_backpressure_pause_ms")]
+    #[derivative(Default(value = "default_backpressure_pause_ms()"))]
+    backpressure_pause_ms: u64,
+
     /// Overrides the name of the log field used to add the message key to each event.
     ///
     /// The value will be the message key of the Kafka message itself.
@@ -170,6 +182,10 @@ const fn default_commit_interval_ms() -> u64 {
     5000 // default in librdkafka
 }
 
+const fn default_backpressure_pause_ms() -> u64 {
+    1000
+}
+
 fn default_auto_offset_reset() -> String {
     "largest".into() // default in librdkafka
 }
@@ -219,7 +235,8 @@ impl SourceConfig for KafkaSourceConfig {
     }
 
     fn outputs(&self, _global_log_namespace: LogNamespace) -> Vec<Output> {
-        vec![Output::default(self.decoding.output_type())]
+        self.decoding
+            .with_errors_output(vec![Output::default(self.decoding.output_type())])
     }
 
     fn can_acknowledge(&self) -> bool {
@@ -240,6 +257,7 @@ async fn kafka_source(
         OrderedFinalizer::<FinalizerEntry>::maybe_new(acknowledgements, shutdown.clone());
     let mut stream = consumer.stream();
     let keys = Keys::from(log_schema(), &config);
+    let pause_after = Duration::from_millis(config.backpressure_pause_ms);
 
     loop {
         tokio::select! {
@@ -264,7 +282,21 @@ async fn kafka_source(
                         partition: msg.partition(),
                     });
 
-                    parse_message(msg, decoder.clone(), keys, &finalizer, &mut out, &consumer).await;
+                    let send = parse_message(msg, decoder.clone(), keys, &finalizer, &mut out, &consumer);
+                    tokio::pin!(send);
+                    // If the pipeline is applying backpressure, stop fetching from the brokers until the
+                    // send goes through, rather than letting librdkafka buffer messages without bound.
+                    // The send may be blocked for good, so shutting down is still watched for.
+                    if tokio::time::timeout(pause_after, &mut send).await.is_err() {
+                        let paused = pause_partitions(&consumer);
+                        tokio::select! {
+                            _ = &mut shutdown => break,
+                            _ = &mut send => (),
+                        }
+                        if let Some(partitions) = paused {
+                            resume_partitions(&consumer, &partitions);
+                        }
+                    }
                 }
             },
         }
@@ -314,6 +346,43 @@ async fn parse_message(
     }
 }
 
+fn pause_partitions(
+    consumer: &StreamConsumer<KafkaStatisticsContext>,
+) -> Option<TopicPartitionList> {
+    let partitions = match consumer.assignment() {
+        Ok(partitions) => partitions,
+        Err(error) => {
+            emit!(KafkaPartitionPauseError { error });
+            return None;
+        }
+    };
+    match consumer.pause(&partitions) {
+        Ok(()) => {
+            emit!(KafkaPartitionsPaused {
+                count: partitions.count()
+            });
+            Some(partitions)
+        }
+        Err(error) => {
+            emit!(KafkaPartitionPauseError { error });
+            None
+        }
+    }
+}
+
+fn resume_partitions(
+    consumer: &StreamConsumer<KafkaStatisticsContext>,
+    partitions: &TopicPartitionList,
+) {
+    // Partitions revoked by a rebalance while we were paused are simply ignored by librdkafka.
+    match consumer.resume(partitions) {
+        Ok(()) => emit!(KafkaPartitionsResumed {
+            count: partitions.count()
+        }),
+        Err(error) => emit!(KafkaPartitionPauseError { error }),
+    }
+}
+
 // Turn the received message into a stream of parsed events.
 fn parse_stream<'a>(
     msg: &BorrowedMessage<'a>,
@@ -522,6 +591,7 @@ mod test {
             auto_offset_reset: "beginning".into(),
             session_timeout_ms: 6000,
             commit_interval_ms: 5000,
+            backpressure_pause_ms: 1000,
             key_field: "message_key".to_string(),
             topic_key: "topic".to_string(),
             partition_key: "partition".to_string(),
@@ -563,9 +633,11 @@ mod integration_test {
         util::Timeout,
         Offset, TopicPartitionList,
     };
+    use vector_common::event_test_util;
 
     use super::{test::*, *};
     use crate::{
+        event::into_event_stream,
         shutdown::ShutdownSignal,
         test_util::{collect_n, components::assert_source_compliance, random_string},
         SourceSender,
@@ -608,6 +680,95 @@ mod integration_test {
         }
     }
 
+    #[tokio::test]
+    async fn pauses_and_resumes_partitions_under_backpressure() {
+        const SEND_COUNT: usize = 10;
+
+        let topic = format!("test-topic-{}", random_string(10));
+        let group_id = format!("test-group-{}", random_string(10));
+        let now = Utc::now().timestamp_millis();
+
+        let config = KafkaSourceConfig {
+            backpressure_pause_ms: 100,
+            ..make_config(&topic, &group_id)
+        };
+        send_events(&topic, SEND_COUNT, "key", "before", now, "header", "value").await;
+
+        // Nothing reads from the sender until the partitions are paused, so the source is blocked.
+        let (trigger_shutdown, shutdown, shutdown_done) = ShutdownSignal::new_wired();
+        let (tx, rx) = SourceSender::new_with_buffer(1);
+        let consumer = create_consumer(&config).unwrap();
+        tokio::spawn(kafka_source(
+            config,
+            consumer,
+            crate::codecs::Decoder::default(),
+            shutdown,
+            tx,
+            false,
+        ));
+        tokio::time::sleep(Duration::from_secs(3)).await;
+        assert_eq!(
+            event_test_util::contains_name_once("KafkaPartitionsPaused"),
+            Ok(())
+        );
+        assert!(event_test_util::contains_name_once("KafkaPartitionsResumed").is_err());
+
+        // The events sent while paused can only be fetched once the partitions are resumed.
+        send_events(&topic, SEND_COUNT, "key", "after", now, "header", "value").await;
+        let rx = rx.into_stream().flat_map(into_event_stream);
+        let events = tokio::time::timeout(Duration::from_secs(10), collect_n(rx, 2 * SEND_COUNT))
+            .await
+            .expect("partitions weren't resumed");
+        assert_eq!(
+            event_test_util::contains_name_once("KafkaPartitionsResumed"),
+            Ok(())
+        );
+        assert_eq!(
+            events.last().unwrap().as_log()[log_schema().message_key()],
+            format!("after {}", SEND_COUNT - 1).into()
+        );
+
+        drop(trigger_shutdown);
+        shutdown_done.await;
+    }
+
+    #[tokio::test]
+    async fn shuts_down_while_paused() {
+        let topic = format!("test-topic-{}", random_string(10));
+        let group_id = format!("test-group-{}", random_string(10));
+
+        let config = KafkaSourceConfig {
+            backpressure_pause_ms: 100,
+            ..make_config(&topic, &group_id)
+        };
+        send_events(&topic, 10, "key", "text", 0, "header", "value").await;
+
+        let (trigger_shutdown, shutdown, shutdown_done) = ShutdownSignal::new_wired();
+        let (tx, _rx) = SourceSender::new_with_buffer(1);
+        let consumer = create_consumer(&config).unwrap();
+        let source = tokio::spawn(kafka_source(
+            config,
+            consumer,
+            crate::codecs::Decoder::default(),
+            shutdown,
+            tx,
+            false,
+        ));
+        tokio::time::sleep(Duration::from_secs(3)).await;
+        assert_eq!(
+            event_test_util::contains_name_once("KafkaPartitionsPaused"),
+            Ok(())
+        );
+
+        drop(trigger_shutdown);
+        tokio::time::timeout(Duration::from_secs(5), source)
+            .await
+            .expect("source didn't shut down while paused")
+            .unwrap()
+            .unwrap();
+        shutdown_done.await;
+    }
+
     #[tokio::test]
     async fn consumes_event_with_acknowledgements() {
         send_receive(true, |_| false, 10).await;
@@ -0,0 +1,263 @@
+use std::{collections::HashMap, net::SocketAddr};
+
+use bytes::Bytes;
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use vector_config::configurable_component;
+use vector_core::config::LogNamespace;
+use warp::http::{HeaderMap, StatusCode};
+
+use crate::{
+    config::{
+        log_schema, AcknowledgementsConfig, DataType, GenerateConfig, Output, Resource,
+        SourceConfig, SourceContext,
+    },
+    event::{Event, LogEvent, Value},
+    serde::bool_or_struct,
+    sources::{
+        http::HttpMethod,
+        util::{ErrorMessage, HttpSource, HttpSourceAuthConfig},
+    },
+    tls::TlsEnableableConfig,
+};
+
+/// The versions of the `audit.k8s.io` API group the apiserver sends batches of.
+const API_VERSIONS: &[&str] = &["audit.k8s.io/v1", "audit.k8s.io/v1beta1"];
+
+/// The fields of audit events holding their timestamp, in order of precedence.
+const TIMESTAMP_FIELDS: &[&str] = &["stageTimestamp", "requestReceivedTimestamp"];
+
+/// Configuration for the `kubernetes_audit` source.
+#[configurable_component(source("kubernetes_audit"))]
+#[configurable(metadata(status = "alpha"))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct KubernetesAuditConfig {
+    /// The address to listen for connections on.
+    address: SocketAddr,
+
+    /// The URL path on which the apiserver sends audit events.
+    #[serde(default = "default_path")]
+    path: String,
+
+    #[configurable(derived)]
+    tls: Option<TlsEnableableConfig>,
+
+    #[configurable(derived)]
+    auth: Option<HttpSourceAuthConfig>,
+
+    #[configurable(derived)]
+    #[serde(default, deserialize_with = "bool_or_struct")]
+    acknowledgements: AcknowledgementsConfig,
+}
+
+fn default_path() -> String {
+    "/".to_string()
+}
+
+impl GenerateConfig for KubernetesAuditConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self {
+            address: "0.0.0.0:8443".parse().unwrap(),
+            path: default_path(),
+            tls: None,
+            auth: None,
+            acknowledgements: AcknowledgementsConfig::default(),
+        })
+        .unwrap()
+    }
+}
+
+/// A batch of audit events, as sent by the webhook backend of the apiserver.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct EventList {
+    api_version: String,
+    kind: String,
+    /// Empty batches have `null` items.
+    #[serde(default)]
+    items: Option<Vec<serde_json::Value>>,
+}
+
+#[derive(Clone)]
+struct KubernetesAuditSource;
+
+impl HttpSource for KubernetesAuditSource {
+    fn build_events(
+        &self,
+        body: Bytes,
+        _header_map: HeaderMap,
+        _query_parameters: HashMap<String, String>,
+        _full_path: &str,
+    ) -> Result<Vec<Event>, ErrorMessage> {
+        // The apiserver retries batches answered with anything but a success, so only malformed
+        // batches, which would be rejected again, are answered with a client error.
+        let list: EventList = serde_json::from_slice(&body).map_err(|error| {
+            ErrorMessage::new(
+                StatusCode::BAD_REQUEST,
+                format!("Invalid audit event list: {}", error),
+            )
+        })?;
+        if list.kind != "EventList" || !API_VERSIONS.contains(&list.api_version.as_str()) {
+            return Err(ErrorMessage::new(
+                StatusCode::BAD_REQUEST,
+                format!(
+                    "Unsupported kind {:?} of API version {:?}, expected an `EventList` of {}.",
+                    list.kind,
+                    list.api_version,
+                    API_VERSIONS.join(" or ")
+                ),
+            ));
+        }
+
+        let now = Utc::now();
+        Ok(list
+            .items
+            .unwrap_or_default()
+            .into_iter()
+            .map(|item| {
+                let mut log = match Value::from(item) {
+                    Value::Object(fields) => LogEvent::from(fields),
+                    value => {
+                        let mut log = LogEvent::default();
+                        log.insert(log_schema().message_key(), value);
+                        log
+                    }
+                };
+                log.try_insert(
+                    log_schema().source_type_key(),
+                    Bytes::from("kubernetes_audit"),
+                );
+                let timestamp = event_timestamp(&log).unwrap_or(now);
+                log.try_insert(log_schema().timestamp_key(), timestamp);
+                Event::Log(log)
+            })
+            .collect())
+    }
+}
+
+/// The time the audit event was generated at, as the apiserver sends it as an RFC 3339 string.
+fn event_timestamp(log: &LogEvent) -> Option<DateTime<Utc>> {
+    TIMESTAMP_FIELDS
+        .iter()
+        .find_map(|field| match log.get(*field) {
+            Some(Value::Bytes(bytes)) => std::str::from_utf8(bytes)
+                .ok()
+                .and_then(|text| DateTime::parse_from_rfc3339(text).ok())
+                .map(|timestamp| timestamp.with_timezone(&Utc)),
+            _ => None,
+        })
+}
+
+#[async_trait::async_trait]
+impl SourceConfig for KubernetesAuditConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
+        KubernetesAuditSource.run(
+            self.address,
+            &self.path,
+            HttpMethod::Post,
+            true,
+            &self.tls,
+            &self.auth,
+            cx,
+            self.acknowledgements,
+        )
+    }
+
+    fn outputs(&self, _global_log_namespace: LogNamespace) -> Vec<Output> {
+        vec![Output::default(DataType::Log)]
+    }
+
+    fn resources(&self) -> Vec<Resource> {
+        vec![Resource::tcp(self.address)]
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<KubernetesAuditConfig>();
+    }
+
+    fn build_events(body: &'static str) -> Result<Vec<Event>, ErrorMessage> {
+        KubernetesAuditSource.build_events(
+            Bytes::from_static(body.as_bytes()),
+            HeaderMap::new(),
+            HashMap::new(),
+            "/",
+        )
+    }
+
+    #[test]
+    fn decodes_event_lists() {
+        let events = build_events(
+            r#"{
+                "kind": "EventList",
+                "apiVersion": "audit.k8s.io/v1",
+                "metadata": {},
+                "items": [
+                    {
+                        "level": "Metadata",
+                        "auditID": "0b5b3a4e-8f5c-4b4e-9d7a-3c1f0e2d1a6b",
+                        "stage": "ResponseComplete",
+                        "verb": "get",
+                        "user": {"username": "system:admin"},
+                        "objectRef": {"resource": "pods", "namespace": "default"},
+                        "requestReceivedTimestamp": "2022-10-01T12:00:00.000000Z",
+                        "stageTimestamp": "2022-10-01T12:00:00.250000Z"
+                    },
+                    {
+                        "level": "Metadata",
+                        "stage": "RequestReceived",
+                        "verb": "list",
+                        "requestReceivedTimestamp": "2022-10-01T12:00:01.000000Z"
+                    }
+                ]
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(events.len(), 2);
+        let log = events[0].as_log();
+        assert_eq!(log["verb"], "get".into());
+        assert_eq!(log["user.username"], "system:admin".into());
+        assert_eq!(log["objectRef.resource"], "pods".into());
+        assert_eq!(log["source_type"], "kubernetes_audit".into());
+        assert_eq!(
+            log["timestamp"],
+            Utc.ymd(2022, 10, 1).and_hms_milli(12, 0, 0, 250).into()
+        );
+        assert_eq!(
+            events[1].as_log()["timestamp"],
+            Utc.ymd(2022, 10, 1).and_hms(12, 0, 1).into()
+        );
+    }
+
+    #[test]
+    fn accepts_empty_event_lists() {
+        let events = build_events(
+            r#"{"kind":"EventList","apiVersion":"audit.k8s.io/v1beta1","items":null}"#,
+        )
+        .unwrap();
+
+        assert!(events.is_empty());
+    }
+
+    #[test]
+    fn rejects_other_kinds() {
+        let error = build_events(r#"{"kind":"Event","apiVersion":"audit.k8s.io/v1"}"#).unwrap_err();
+        assert_eq!(error.status_code(), StatusCode::BAD_REQUEST);
+
+        let error = build_events("not json").unwrap_err();
+        assert_eq!(error.status_code(), StatusCode::BAD_REQUEST);
+    }
+}
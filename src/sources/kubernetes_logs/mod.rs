@@ -5,13 +5,13 @@
 
 #![deny(missing_docs)]
 
-use std::{convert::TryInto, path::PathBuf, time::Duration};
+use std::{convert::TryInto, path::PathBuf, sync::Arc, time::Duration};
 
 use bytes::Bytes;
 use chrono::Utc;
 use file_source::{
     Checkpointer, FileServer, FileServerShutdown, FingerprintStrategy, Fingerprinter, Line,
-    ReadFrom,
+    ReadFrom, CHECKPOINT_FILE_NAME,
 };
 use futures_util::Stream;
 use k8s_openapi::api::core::v1::{Namespace, Node, Pod};
@@ -30,6 +30,7 @@ use vector_config::{configurable_component, NamedComponent};
 use vector_core::{transform::TaskTransform, ByteSizeOf};
 
 use crate::{
+    checkpoint::{CheckpointStoreConfig, MirroredCheckpointFile},
     config::{
         log_schema, ComponentKey, DataType, GenerateConfig, GlobalOptions, Output, SourceConfig,
         SourceContext,
@@ -101,6 +102,12 @@ pub struct Config {
     /// By default, the global `data_dir` option is used. Please make sure the user Vector is running as has write permissions to this directory.
     data_dir: Option<PathBuf>,
 
+    /// Where copies of the file checkpoint positions are stored.
+    ///
+    /// Checkpoints are always persisted to the data directory of the source. With a remote store, they are also copied to it, and restored from it when the data directory has none, so that the source resumes where it left off when its data directory doesn't outlive the `Pod` running Vector.
+    #[configurable(derived)]
+    checkpoint_store: CheckpointStoreConfig,
+
     #[configurable(derived)]
     #[serde(alias = "annotation_fields")]
     pod_annotation_fields: pod_metadata_annotator::FieldsSpec,
@@ -173,6 +180,7 @@ impl Default for Config {
             extra_field_selector: "".to_string(),
             auto_partial_merge: true,
             data_dir: None,
+            checkpoint_store: CheckpointStoreConfig::default(),
             pod_annotation_fields: pod_metadata_annotator::FieldsSpec::default(),
             namespace_annotation_fields: namespace_metadata_annotator::FieldsSpec::default(),
             node_annotation_fields: node_metadata_annotator::FieldsSpec::default(),
@@ -193,11 +201,42 @@ impl Default for Config {
 impl SourceConfig for Config {
     async fn build(&self, cx: SourceContext) -> crate::Result<sources::Source> {
         let source = Source::new(self, &cx.globals, &cx.key).await?;
-        Ok(Box::pin(source.run(cx.out, cx.shutdown).map(|result| {
-            result.map_err(|error| {
-                error!(message = "Source future failed.", %error);
-            })
-        })))
+
+        let mirrored_checkpoints = if self.checkpoint_store.is_local() {
+            None
+        } else {
+            let store = self
+                .checkpoint_store
+                .build(
+                    &cx.key,
+                    &cx.globals,
+                    self.data_dir.as_ref(),
+                    CHECKPOINT_FILE_NAME,
+                    &cx.proxy,
+                )
+                .await?;
+            let mirrored =
+                MirroredCheckpointFile::new(store, source.data_dir.join(CHECKPOINT_FILE_NAME));
+            mirrored.restore().await?;
+            Some(mirrored)
+        };
+
+        let on_checkpointed = mirrored_checkpoints
+            .as_ref()
+            .map(MirroredCheckpointFile::on_written);
+        let source: sources::Source = Box::pin(
+            source
+                .run(cx.out, cx.shutdown, on_checkpointed)
+                .map(|result| {
+                    result.map_err(|error| {
+                        error!(message = "Source future failed.", %error);
+                    })
+                }),
+        );
+        Ok(match mirrored_checkpoints {
+            Some(mirrored) => mirrored.mirror(source),
+            None => source,
+        })
     }
 
     fn outputs(&self, _global_log_namespace: LogNamespace) -> Vec<Output> {
@@ -313,6 +352,7 @@ impl Source {
         self,
         mut out: SourceSender,
         global_shutdown: ShutdownSignal,
+        on_checkpointed: Option<Arc<dyn Fn() + Send + Sync>>,
     ) -> crate::Result<()> {
         let Self {
             client,
@@ -449,8 +489,13 @@ impl Source {
             oldest_first: true,
             // We do not remove the log files, `kubelet` is responsible for it.
             remove_after: None,
-            // The standard emitter.
-            emitter: FileSourceInternalEventsEmitter,
+            copytruncate: false,
+            io_uring: false,
+            // The standard emitter, copying the checkpoints to the checkpoint store if any.
+            emitter: FileSourceInternalEventsEmitter {
+                on_checkpointed,
+                ..Default::default()
+            },
             // A handle to the current tokio runtime
             handle: tokio::runtime::Handle::current(),
         };
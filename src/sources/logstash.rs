@@ -49,6 +49,14 @@ pub struct LogstashConfig {
     /// The maximum number of TCP connections that will be allowed at any given time.
     connection_limit: Option<u32>,
 
+    /// Whether to move the `@timestamp` field of events to their timestamp.
+    ///
+    /// By default, `@timestamp` is kept, and only parsed for events without a timestamp. Elastic
+    /// Beats, such as Filebeat or Winlogbeat, always set it, so enabling this avoids keeping the
+    /// time of their events twice.
+    #[serde(default)]
+    move_timestamp: bool,
+
     #[configurable(derived)]
     #[serde(default, deserialize_with = "bool_or_struct")]
     acknowledgements: AcknowledgementsConfig,
@@ -63,6 +71,7 @@ impl GenerateConfig for LogstashConfig {
             receive_buffer_bytes: None,
             acknowledgements: Default::default(),
             connection_limit: None,
+            move_timestamp: false,
         })
         .unwrap()
     }
@@ -73,6 +82,7 @@ impl SourceConfig for LogstashConfig {
     async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
         let source = LogstashSource {
             timestamp_converter: types::Conversion::Timestamp(cx.globals.timezone),
+            move_timestamp: self.move_timestamp,
         };
         let shutdown_secs = 30;
         let tls_config = self.tls.as_ref().map(|tls| tls.tls_config.clone());
@@ -110,6 +120,7 @@ impl SourceConfig for LogstashConfig {
 #[derive(Debug, Clone)]
 struct LogstashSource {
     timestamp_converter: types::Conversion,
+    move_timestamp: bool,
 }
 
 impl TcpSource for LogstashSource {
@@ -127,7 +138,17 @@ impl TcpSource for LogstashSource {
         for event in events {
             let log = event.as_mut_log();
             log.try_insert(log_schema().source_type_key(), "logstash");
-            if log.get(log_schema().timestamp_key()).is_none() {
+            if self.move_timestamp {
+                let timestamp = log
+                    .remove(event_path!("@timestamp"))
+                    .and_then(|timestamp| {
+                        self.timestamp_converter
+                            .convert::<Value>(timestamp.coerce_to_bytes())
+                            .ok()
+                    })
+                    .unwrap_or_else(|| now.clone());
+                log.try_insert(log_schema().timestamp_key(), timestamp);
+            } else if log.get(log_schema().timestamp_key()).is_none() {
                 // Attempt to parse @timestamp if it exists; otherwise set to receipt time.
                 let timestamp = log
                     .get(event_path!("@timestamp"))
@@ -569,6 +590,7 @@ impl From<LogstashEventFrame> for SmallVec<[Event; 1]> {
 #[cfg(test)]
 mod test {
     use bytes::BufMut;
+    use chrono::{TimeZone, Utc};
     use rand::{thread_rng, Rng};
     use tokio::io::{AsyncReadExt, AsyncWriteExt};
 
@@ -608,6 +630,7 @@ mod test {
                 receive_buffer_bytes: None,
                 acknowledgements: true.into(),
                 connection_limit: None,
+                move_timestamp: false,
             }
             .build(SourceContext::new_test(sender, None))
             .await
@@ -638,6 +661,67 @@ mod test {
         assert!(log.get("timestamp").is_some());
     }
 
+    #[tokio::test]
+    async fn moves_beats_timestamp() {
+        let events = assert_source_compliance(&SOCKET_PUSH_SOURCE_TAGS, async {
+            let (sender, recv) = SourceSender::new_test_finalize(EventStatus::Delivered);
+            let address = next_addr();
+            let source = LogstashConfig {
+                address: address.into(),
+                tls: None,
+                keepalive: None,
+                receive_buffer_bytes: None,
+                acknowledgements: true.into(),
+                connection_limit: None,
+                move_timestamp: true,
+            }
+            .build(SourceContext::new_test(sender, None))
+            .await
+            .unwrap();
+            tokio::spawn(source);
+            wait_for_tcp(address).await;
+
+            spawn_collect_n(send_beats_window(address), recv, 2).await
+        })
+        .await;
+
+        assert_eq!(events.len(), 2);
+        let log = events[0].as_log();
+        assert_eq!(log["message"], "first".into());
+        assert_eq!(log["@metadata.beat"], "filebeat".into());
+        assert_eq!(
+            log["timestamp"],
+            Utc.ymd(2022, 10, 1).and_hms_milli(12, 30, 0, 123).into()
+        );
+        assert!(log.get("@timestamp").is_none());
+    }
+
+    /// Sends a window of two JSON events as Filebeat does, and checks its acknowledgement.
+    async fn send_beats_window(address: SocketAddr) {
+        let mut window = BytesMut::new();
+        window.put_slice(b"2W");
+        window.put_u32(2);
+        for (seq, message) in [(1, "first"), (2, "second")] {
+            let fields = serde_json::json!({
+                "@timestamp": "2022-10-01T12:30:00.123Z",
+                "@metadata": { "beat": "filebeat", "type": "_doc", "version": "8.4.3" },
+                "message": message,
+            })
+            .to_string();
+            window.put_slice(b"2J");
+            window.put_u32(seq);
+            window.put_u32(fields.len() as u32);
+            window.put_slice(fields.as_bytes());
+        }
+
+        let mut socket = tokio::net::TcpStream::connect(address).await.unwrap();
+        socket.write_all(&window).await.unwrap();
+
+        let mut output = BytesMut::new();
+        socket.read_buf(&mut output).await.unwrap();
+        assert_eq!(&output[..], b"2A\0\0\0\x02");
+    }
+
     fn encode_req(seq: u32, pairs: &[(&str, &str)]) -> Bytes {
         let mut req = BytesMut::new();
         req.put_u8(b'2');
@@ -780,6 +864,7 @@ mod integration_tests {
                 receive_buffer_bytes: None,
                 acknowledgements: false.into(),
                 connection_limit: None,
+                move_timestamp: false,
             }
             .build(SourceContext::new_test(sender, None))
             .await
@@ -5,6 +5,8 @@ use snafu::Snafu;
 pub mod amqp;
 #[cfg(feature = "sources-apache_metrics")]
 pub mod apache_metrics;
+#[cfg(all(unix, feature = "sources-auditd"))]
+pub mod auditd;
 #[cfg(feature = "sources-aws_ecs_metrics")]
 pub mod aws_ecs_metrics;
 #[cfg(feature = "sources-aws_kinesis_firehose")]
@@ -13,6 +15,10 @@ pub mod aws_kinesis_firehose;
 pub mod aws_s3;
 #[cfg(feature = "sources-aws_sqs")]
 pub mod aws_sqs;
+#[cfg(feature = "sources-clock")]
+pub mod clock;
+#[cfg(feature = "sources-cloudflare_logpush")]
+pub mod cloudflare_logpush;
 #[cfg(any(feature = "sources-datadog_agent"))]
 pub mod datadog_agent;
 #[cfg(feature = "sources-demo_logs")]
@@ -21,6 +27,8 @@ pub mod demo_logs;
 pub mod dnstap;
 #[cfg(feature = "sources-docker_logs")]
 pub mod docker_logs;
+#[cfg(feature = "sources-envoy_als")]
+pub mod envoy_als;
 #[cfg(feature = "sources-eventstoredb_metrics")]
 pub mod eventstoredb_metrics;
 #[cfg(feature = "sources-exec")]
@@ -34,8 +42,12 @@ pub mod file;
 pub mod file_descriptors;
 #[cfg(feature = "sources-fluent")]
 pub mod fluent;
+#[cfg(feature = "sources-gcp_cloud_logging")]
+pub mod gcp_cloud_logging;
 #[cfg(feature = "sources-gcp_pubsub")]
 pub mod gcp_pubsub;
+#[cfg(feature = "sources-grpc_server")]
+pub mod grpc_server;
 #[cfg(feature = "sources-heroku_logs")]
 pub mod heroku_logs;
 #[cfg(feature = "sources-host_metrics")]
@@ -52,6 +64,8 @@ pub mod internal_metrics;
 pub mod journald;
 #[cfg(feature = "sources-kafka")]
 pub mod kafka;
+#[cfg(feature = "sources-kubernetes_audit")]
+pub mod kubernetes_audit;
 #[cfg(feature = "sources-kubernetes_logs")]
 pub mod kubernetes_logs;
 #[cfg(all(feature = "sources-logstash"))]
@@ -60,16 +74,24 @@ pub mod logstash;
 pub mod mongodb_metrics;
 #[cfg(all(feature = "sources-nats"))]
 pub mod nats;
+#[cfg(feature = "sources-netflow")]
+pub mod netflow;
 #[cfg(feature = "sources-nginx_metrics")]
 pub mod nginx_metrics;
 #[cfg(feature = "sources-opentelemetry")]
 pub mod opentelemetry;
+#[cfg(feature = "sources-pcap")]
+pub mod pcap;
 #[cfg(feature = "sources-postgresql_metrics")]
 pub mod postgresql_metrics;
 #[cfg(feature = "sources-prometheus")]
 pub mod prometheus;
 #[cfg(feature = "sources-redis")]
 pub mod redis;
+#[cfg(feature = "sources-sflow")]
+pub mod sflow;
+#[cfg(feature = "sources-snmp")]
+pub mod snmp;
 #[cfg(feature = "sources-socket")]
 pub mod socket;
 #[cfg(feature = "sources-splunk_hec")]
@@ -80,6 +102,8 @@ pub mod statsd;
 pub mod syslog;
 #[cfg(feature = "sources-vector")]
 pub mod vector;
+#[cfg(feature = "sources-windows_perfcounters")]
+pub mod windows_perfcounters;
 
 pub(crate) mod util;
 
@@ -114,6 +138,10 @@ pub enum Sources {
     #[cfg(feature = "sources-apache_metrics")]
     ApacheMetrics(#[configurable(derived)] apache_metrics::ApacheMetricsConfig),
 
+    /// Linux audit subsystem.
+    #[cfg(all(unix, feature = "sources-auditd"))]
+    Auditd(#[configurable(derived)] auditd::AuditdConfig),
+
     /// AWS ECS Metrics.
     #[cfg(feature = "sources-aws_ecs_metrics")]
     AwsEcsMetrics(#[configurable(derived)] aws_ecs_metrics::AwsEcsMetricsSourceConfig),
@@ -130,6 +158,14 @@ pub enum Sources {
     #[cfg(feature = "sources-aws_sqs")]
     AwsSqs(#[configurable(derived)] aws_sqs::AwsSqsConfig),
 
+    /// Clock.
+    #[cfg(feature = "sources-clock")]
+    Clock(#[configurable(derived)] clock::ClockConfig),
+
+    /// Cloudflare Logpush.
+    #[cfg(feature = "sources-cloudflare_logpush")]
+    CloudflareLogpush(#[configurable(derived)] cloudflare_logpush::LogpushConfig),
+
     /// Datadog Agent.
     #[cfg(feature = "sources-datadog_agent")]
     DatadogAgent(#[configurable(derived)] datadog_agent::DatadogAgentConfig),
@@ -146,6 +182,10 @@ pub enum Sources {
     #[cfg(feature = "sources-docker_logs")]
     DockerLogs(#[configurable(derived)] docker_logs::DockerLogsConfig),
 
+    /// Envoy Access Log Service.
+    #[cfg(feature = "sources-envoy_als")]
+    EnvoyAls(#[configurable(derived)] envoy_als::EnvoyAlsConfig),
+
     /// EventStoreDB Metrics.
     #[cfg(feature = "sources-eventstoredb_metrics")]
     EventstoredbMetrics(#[configurable(derived)] eventstoredb_metrics::EventStoreDbConfig),
@@ -168,10 +208,18 @@ pub enum Sources {
     #[cfg(feature = "sources-fluent")]
     Fluent(#[configurable(derived)] fluent::FluentConfig),
 
+    /// GCP Cloud Logging.
+    #[cfg(feature = "sources-gcp_cloud_logging")]
+    GcpCloudLogging(#[configurable(derived)] gcp_cloud_logging::GcpCloudLoggingConfig),
+
     /// GCP Pub/Sub.
     #[cfg(feature = "sources-gcp_pubsub")]
     GcpPubsub(#[configurable(derived)] gcp_pubsub::PubsubConfig),
 
+    /// gRPC server.
+    #[cfg(feature = "sources-grpc_server")]
+    GrpcServer(#[configurable(derived)] grpc_server::GrpcServerConfig),
+
     /// Heroku Logs.
     #[cfg(feature = "sources-heroku_logs")]
     HerokuLogs(#[configurable(derived)] heroku_logs::LogplexConfig),
@@ -204,6 +252,10 @@ pub enum Sources {
     #[cfg(feature = "sources-kafka")]
     Kafka(#[configurable(derived)] kafka::KafkaSourceConfig),
 
+    /// Kubernetes audit webhook.
+    #[cfg(feature = "sources-kubernetes_audit")]
+    KubernetesAudit(#[configurable(derived)] kubernetes_audit::KubernetesAuditConfig),
+
     /// Kubernetes Logs.
     #[cfg(feature = "sources-kubernetes_logs")]
     KubernetesLogs(#[configurable(derived)] kubernetes_logs::Config),
@@ -220,6 +272,10 @@ pub enum Sources {
     #[cfg(all(feature = "sources-nats"))]
     Nats(#[configurable(derived)] nats::NatsSourceConfig),
 
+    /// NetFlow.
+    #[cfg(feature = "sources-netflow")]
+    Netflow(#[configurable(derived)] netflow::NetflowConfig),
+
     /// NGINX Metrics.
     #[cfg(feature = "sources-nginx_metrics")]
     NginxMetrics(#[configurable(derived)] nginx_metrics::NginxMetricsConfig),
@@ -228,6 +284,10 @@ pub enum Sources {
     #[cfg(feature = "sources-opentelemetry")]
     Opentelemetry(#[configurable(derived)] opentelemetry::OpentelemetryConfig),
 
+    /// Packet capture.
+    #[cfg(feature = "sources-pcap")]
+    Pcap(#[configurable(derived)] pcap::PcapConfig),
+
     /// PostgreSQL Metrics.
     #[cfg(feature = "sources-postgresql_metrics")]
     PostgresqlMetrics(#[configurable(derived)] postgresql_metrics::PostgresqlMetricsConfig),
@@ -266,6 +326,14 @@ pub enum Sources {
     #[cfg(test)]
     TestTripwire(#[configurable(derived)] crate::test_util::mock::sources::TripwireSourceConfig),
 
+    /// sFlow.
+    #[cfg(feature = "sources-sflow")]
+    Sflow(#[configurable(derived)] sflow::SflowConfig),
+
+    /// SNMP.
+    #[cfg(feature = "sources-snmp")]
+    Snmp(#[configurable(derived)] snmp::SnmpConfig),
+
     /// Socket.
     #[cfg(feature = "sources-socket")]
     Socket(#[configurable(derived)] socket::SocketConfig),
@@ -295,6 +363,10 @@ pub enum Sources {
     /// Vector.
     #[cfg(feature = "sources-vector")]
     Vector(#[configurable(derived)] vector::VectorConfig),
+
+    /// Windows Performance Counters.
+    #[cfg(feature = "sources-windows_perfcounters")]
+    WindowsPerfcounters(#[configurable(derived)] windows_perfcounters::WindowsPerfcountersConfig),
 }
 
 // We can't use `enum_dispatch` here because it doesn't support associated constants.
@@ -307,6 +379,8 @@ impl NamedComponent for Sources {
             Self::Amqp(config) => config.get_component_name(),
             #[cfg(feature = "sources-apache_metrics")]
             Self::ApacheMetrics(config) => config.get_component_name(),
+            #[cfg(all(unix, feature = "sources-auditd"))]
+            Self::Auditd(config) => config.get_component_name(),
             #[cfg(feature = "sources-aws_ecs_metrics")]
             Self::AwsEcsMetrics(config) => config.get_component_name(),
             #[cfg(feature = "sources-aws_kinesis_firehose")]
@@ -315,6 +389,10 @@ impl NamedComponent for Sources {
             Self::AwsS3(config) => config.get_component_name(),
             #[cfg(feature = "sources-aws_sqs")]
             Self::AwsSqs(config) => config.get_component_name(),
+            #[cfg(feature = "sources-clock")]
+            Self::Clock(config) => config.get_component_name(),
+            #[cfg(feature = "sources-cloudflare_logpush")]
+            Self::CloudflareLogpush(config) => config.get_component_name(),
             #[cfg(feature = "sources-datadog_agent")]
             Self::DatadogAgent(config) => config.get_component_name(),
             #[cfg(feature = "sources-demo_logs")]
@@ -323,6 +401,8 @@ impl NamedComponent for Sources {
             Self::Dnstap(config) => config.get_component_name(),
             #[cfg(feature = "sources-docker_logs")]
             Self::DockerLogs(config) => config.get_component_name(),
+            #[cfg(feature = "sources-envoy_als")]
+            Self::EnvoyAls(config) => config.get_component_name(),
             #[cfg(feature = "sources-eventstoredb_metrics")]
             Self::EventstoredbMetrics(config) => config.get_component_name(),
             #[cfg(feature = "sources-exec")]
@@ -333,8 +413,12 @@ impl NamedComponent for Sources {
             Self::FileDescriptor(config) => config.get_component_name(),
             #[cfg(feature = "sources-fluent")]
             Self::Fluent(config) => config.get_component_name(),
+            #[cfg(feature = "sources-gcp_cloud_logging")]
+            Self::GcpCloudLogging(config) => config.get_component_name(),
             #[cfg(feature = "sources-gcp_pubsub")]
             Self::GcpPubsub(config) => config.get_component_name(),
+            #[cfg(feature = "sources-grpc_server")]
+            Self::GrpcServer(config) => config.get_component_name(),
             #[cfg(feature = "sources-heroku_logs")]
             Self::HerokuLogs(config) => config.get_component_name(),
             #[cfg(feature = "sources-host_metrics")]
@@ -351,6 +435,8 @@ impl NamedComponent for Sources {
             Self::Journald(config) => config.get_component_name(),
             #[cfg(feature = "sources-kafka")]
             Self::Kafka(config) => config.get_component_name(),
+            #[cfg(feature = "sources-kubernetes_audit")]
+            Self::KubernetesAudit(config) => config.get_component_name(),
             #[cfg(feature = "sources-kubernetes_logs")]
             Self::KubernetesLogs(config) => config.get_component_name(),
             #[cfg(all(feature = "sources-logstash"))]
@@ -359,10 +445,14 @@ impl NamedComponent for Sources {
             Self::MongodbMetrics(config) => config.get_component_name(),
             #[cfg(all(feature = "sources-nats"))]
             Self::Nats(config) => config.get_component_name(),
+            #[cfg(feature = "sources-netflow")]
+            Self::Netflow(config) => config.get_component_name(),
             #[cfg(feature = "sources-nginx_metrics")]
             Self::NginxMetrics(config) => config.get_component_name(),
             #[cfg(feature = "sources-opentelemetry")]
             Self::Opentelemetry(config) => config.get_component_name(),
+            #[cfg(feature = "sources-pcap")]
+            Self::Pcap(config) => config.get_component_name(),
             #[cfg(feature = "sources-postgresql_metrics")]
             Self::PostgresqlMetrics(config) => config.get_component_name(),
             #[cfg(feature = "sources-prometheus")]
@@ -381,6 +471,10 @@ impl NamedComponent for Sources {
             Self::TestPanic(config) => config.get_component_name(),
             #[cfg(test)]
             Self::TestTripwire(config) => config.get_component_name(),
+            #[cfg(feature = "sources-sflow")]
+            Self::Sflow(config) => config.get_component_name(),
+            #[cfg(feature = "sources-snmp")]
+            Self::Snmp(config) => config.get_component_name(),
             #[cfg(feature = "sources-socket")]
             Self::Socket(config) => config.get_component_name(),
             #[cfg(feature = "sources-splunk_hec")]
@@ -395,6 +489,8 @@ impl NamedComponent for Sources {
             Self::UnitTestStream(config) => config.get_component_name(),
             #[cfg(feature = "sources-vector")]
             Self::Vector(config) => config.get_component_name(),
+            #[cfg(feature = "sources-windows_perfcounters")]
+            Self::WindowsPerfcounters(config) => config.get_component_name(),
         }
     }
 }
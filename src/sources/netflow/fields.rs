@@ -0,0 +1,147 @@
+//! Names and types of the well-known NetFlow v9 and IPFIX information elements.
+//!
+//! NetFlow v9 field types 1 through 127 share their numbering with the IANA IPFIX information
+//! elements, so a single table covers both. Names follow the IANA registry, converted to
+//! snake case.
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum FieldKind {
+    Unsigned,
+    Ipv4,
+    Ipv6,
+    Mac,
+    String,
+}
+
+const FIELDS: &[(u16, &str, FieldKind)] = &[
+    (1, "octet_delta_count", FieldKind::Unsigned),
+    (2, "packet_delta_count", FieldKind::Unsigned),
+    (3, "delta_flow_count", FieldKind::Unsigned),
+    (4, "protocol_identifier", FieldKind::Unsigned),
+    (5, "ip_class_of_service", FieldKind::Unsigned),
+    (6, "tcp_control_bits", FieldKind::Unsigned),
+    (7, "source_transport_port", FieldKind::Unsigned),
+    (8, "source_ipv4_address", FieldKind::Ipv4),
+    (9, "source_ipv4_prefix_length", FieldKind::Unsigned),
+    (10, "ingress_interface", FieldKind::Unsigned),
+    (11, "destination_transport_port", FieldKind::Unsigned),
+    (12, "destination_ipv4_address", FieldKind::Ipv4),
+    (13, "destination_ipv4_prefix_length", FieldKind::Unsigned),
+    (14, "egress_interface", FieldKind::Unsigned),
+    (15, "ip_next_hop_ipv4_address", FieldKind::Ipv4),
+    (16, "bgp_source_as_number", FieldKind::Unsigned),
+    (17, "bgp_destination_as_number", FieldKind::Unsigned),
+    (18, "bgp_next_hop_ipv4_address", FieldKind::Ipv4),
+    (19, "post_mcast_packet_delta_count", FieldKind::Unsigned),
+    (20, "post_mcast_octet_delta_count", FieldKind::Unsigned),
+    (21, "flow_end_sys_up_time", FieldKind::Unsigned),
+    (22, "flow_start_sys_up_time", FieldKind::Unsigned),
+    (23, "post_octet_delta_count", FieldKind::Unsigned),
+    (24, "post_packet_delta_count", FieldKind::Unsigned),
+    (25, "minimum_ip_total_length", FieldKind::Unsigned),
+    (26, "maximum_ip_total_length", FieldKind::Unsigned),
+    (27, "source_ipv6_address", FieldKind::Ipv6),
+    (28, "destination_ipv6_address", FieldKind::Ipv6),
+    (29, "source_ipv6_prefix_length", FieldKind::Unsigned),
+    (30, "destination_ipv6_prefix_length", FieldKind::Unsigned),
+    (31, "flow_label_ipv6", FieldKind::Unsigned),
+    (32, "icmp_type_code_ipv4", FieldKind::Unsigned),
+    (33, "igmp_type", FieldKind::Unsigned),
+    (34, "sampling_interval", FieldKind::Unsigned),
+    (35, "sampling_algorithm", FieldKind::Unsigned),
+    (36, "flow_active_timeout", FieldKind::Unsigned),
+    (37, "flow_idle_timeout", FieldKind::Unsigned),
+    (38, "engine_type", FieldKind::Unsigned),
+    (39, "engine_id", FieldKind::Unsigned),
+    (40, "exported_octet_total_count", FieldKind::Unsigned),
+    (41, "exported_message_total_count", FieldKind::Unsigned),
+    (42, "exported_flow_record_total_count", FieldKind::Unsigned),
+    (44, "source_ipv4_prefix", FieldKind::Ipv4),
+    (45, "destination_ipv4_prefix", FieldKind::Ipv4),
+    (46, "mpls_top_label_type", FieldKind::Unsigned),
+    (47, "mpls_top_label_ipv4_address", FieldKind::Ipv4),
+    (52, "minimum_ttl", FieldKind::Unsigned),
+    (53, "maximum_ttl", FieldKind::Unsigned),
+    (54, "fragment_identification", FieldKind::Unsigned),
+    (55, "post_ip_class_of_service", FieldKind::Unsigned),
+    (56, "source_mac_address", FieldKind::Mac),
+    (57, "post_destination_mac_address", FieldKind::Mac),
+    (58, "vlan_id", FieldKind::Unsigned),
+    (59, "post_vlan_id", FieldKind::Unsigned),
+    (60, "ip_version", FieldKind::Unsigned),
+    (61, "flow_direction", FieldKind::Unsigned),
+    (62, "ip_next_hop_ipv6_address", FieldKind::Ipv6),
+    (63, "bgp_next_hop_ipv6_address", FieldKind::Ipv6),
+    (64, "ipv6_extension_headers", FieldKind::Unsigned),
+    (70, "mpls_top_label_stack_section", FieldKind::Unsigned),
+    (80, "destination_mac_address", FieldKind::Mac),
+    (81, "post_source_mac_address", FieldKind::Mac),
+    (82, "interface_name", FieldKind::String),
+    (83, "interface_description", FieldKind::String),
+    (85, "octet_total_count", FieldKind::Unsigned),
+    (86, "packet_total_count", FieldKind::Unsigned),
+    (88, "fragment_offset", FieldKind::Unsigned),
+    (89, "forwarding_status", FieldKind::Unsigned),
+    (95, "application_id", FieldKind::Unsigned),
+    (96, "application_name", FieldKind::String),
+    (98, "post_ip_diff_serv_code_point", FieldKind::Unsigned),
+    (130, "exporter_ipv4_address", FieldKind::Ipv4),
+    (131, "exporter_ipv6_address", FieldKind::Ipv6),
+    (136, "flow_end_reason", FieldKind::Unsigned),
+    (138, "observation_point_id", FieldKind::Unsigned),
+    (139, "icmp_type_code_ipv6", FieldKind::Unsigned),
+    (144, "exporting_process_id", FieldKind::Unsigned),
+    (148, "flow_id", FieldKind::Unsigned),
+    (149, "observation_domain_id", FieldKind::Unsigned),
+    (150, "flow_start_seconds", FieldKind::Unsigned),
+    (151, "flow_end_seconds", FieldKind::Unsigned),
+    (152, "flow_start_milliseconds", FieldKind::Unsigned),
+    (153, "flow_end_milliseconds", FieldKind::Unsigned),
+    (154, "flow_start_microseconds", FieldKind::Unsigned),
+    (155, "flow_end_microseconds", FieldKind::Unsigned),
+    (156, "flow_start_nanoseconds", FieldKind::Unsigned),
+    (157, "flow_end_nanoseconds", FieldKind::Unsigned),
+    (160, "system_init_time_milliseconds", FieldKind::Unsigned),
+    (161, "flow_duration_milliseconds", FieldKind::Unsigned),
+    (176, "icmp_type_ipv4", FieldKind::Unsigned),
+    (177, "icmp_code_ipv4", FieldKind::Unsigned),
+    (178, "icmp_type_ipv6", FieldKind::Unsigned),
+    (179, "icmp_code_ipv6", FieldKind::Unsigned),
+    (180, "udp_source_port", FieldKind::Unsigned),
+    (181, "udp_destination_port", FieldKind::Unsigned),
+    (182, "tcp_source_port", FieldKind::Unsigned),
+    (183, "tcp_destination_port", FieldKind::Unsigned),
+    (192, "ip_ttl", FieldKind::Unsigned),
+    (195, "ip_diff_serv_code_point", FieldKind::Unsigned),
+    (225, "post_nat_source_ipv4_address", FieldKind::Ipv4),
+    (226, "post_nat_destination_ipv4_address", FieldKind::Ipv4),
+    (227, "post_napt_source_transport_port", FieldKind::Unsigned),
+    (
+        228,
+        "post_napt_destination_transport_port",
+        FieldKind::Unsigned,
+    ),
+    (230, "nat_event", FieldKind::Unsigned),
+    (234, "ingress_vrf_id", FieldKind::Unsigned),
+    (235, "egress_vrf_id", FieldKind::Unsigned),
+    (239, "biflow_direction", FieldKind::Unsigned),
+    (243, "dot1q_vlan_id", FieldKind::Unsigned),
+    (281, "post_nat_source_ipv6_address", FieldKind::Ipv6),
+    (282, "post_nat_destination_ipv6_address", FieldKind::Ipv6),
+];
+
+/// Returns the name of a well-known field type, if known.
+pub fn field_name(id: u16) -> Option<&'static str> {
+    FIELDS
+        .binary_search_by_key(&id, |(field_id, _, _)| *field_id)
+        .ok()
+        .map(|index| FIELDS[index].1)
+}
+
+/// Returns how the value of a field type should be rendered, defaulting to an unsigned integer.
+pub fn field_kind(id: u16) -> FieldKind {
+    FIELDS
+        .binary_search_by_key(&id, |(field_id, _, _)| *field_id)
+        .map(|index| FIELDS[index].2)
+        .unwrap_or(FieldKind::Unsigned)
+}
@@ -0,0 +1,198 @@
+use std::net::SocketAddr;
+
+use bytes::Bytes;
+use chrono::Utc;
+use tokio::net::UdpSocket;
+use vector_common::internal_event::{
+    ByteSize, BytesReceived, EventsReceived, InternalEventHandle as _, Protocol,
+};
+use vector_config::configurable_component;
+use vector_core::{config::LogNamespace, ByteSizeOf};
+
+use crate::{
+    config::{log_schema, DataType, GenerateConfig, Output, Resource, SourceConfig, SourceContext},
+    event::Event,
+    internal_events::{
+        NetflowBindError, NetflowParseError, NetflowReceiveError, NetflowTemplatesMissing,
+        StreamClosedError,
+    },
+    serde::default_max_length,
+    shutdown::ShutdownSignal,
+    udp, SourceSender,
+};
+
+mod fields;
+pub mod parser;
+
+use parser::{TemplateCache, DEFAULT_MAX_TEMPLATES};
+
+/// Configuration for the `netflow` source.
+#[configurable_component(source("netflow"))]
+#[configurable(metadata(status = "alpha"))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct NetflowConfig {
+    /// The address to listen for flow datagrams on.
+    address: SocketAddr,
+
+    /// The maximum size, in bytes, of incoming datagrams.
+    ///
+    /// Datagrams larger than this are discarded.
+    #[serde(default = "default_max_length")]
+    max_length: usize,
+
+    /// The size, in bytes, of the receive buffer used for the listening socket.
+    ///
+    /// This should not typically needed to be changed.
+    receive_buffer_bytes: Option<usize>,
+
+    /// The maximum number of NetFlow v9 and IPFIX templates to keep, across all exporters.
+    ///
+    /// Once reached, the templates received the longest ago are forgotten first.
+    #[serde(default = "default_max_templates")]
+    max_templates: usize,
+}
+
+const fn default_max_templates() -> usize {
+    DEFAULT_MAX_TEMPLATES
+}
+
+impl GenerateConfig for NetflowConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self {
+            address: "0.0.0.0:2055".parse().unwrap(),
+            max_length: default_max_length(),
+            receive_buffer_bytes: None,
+            max_templates: default_max_templates(),
+        })
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+impl SourceConfig for NetflowConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
+        Ok(Box::pin(netflow_udp(self.clone(), cx.shutdown, cx.out)))
+    }
+
+    fn outputs(&self, _global_log_namespace: LogNamespace) -> Vec<Output> {
+        vec![Output::default(DataType::Log)]
+    }
+
+    fn resources(&self) -> Vec<Resource> {
+        vec![Resource::udp(self.address)]
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        false
+    }
+}
+
+async fn netflow_udp(
+    config: NetflowConfig,
+    mut shutdown: ShutdownSignal,
+    mut out: SourceSender,
+) -> Result<(), ()> {
+    let socket = UdpSocket::bind(&config.address).await.map_err(|error| {
+        emit!(NetflowBindError {
+            address: config.address,
+            error,
+        })
+    })?;
+
+    if let Some(receive_buffer_bytes) = config.receive_buffer_bytes {
+        if let Err(error) = udp::set_receive_buffer_size(&socket, receive_buffer_bytes) {
+            warn!(message = "Failed configuring receive buffer size on UDP socket.", %error);
+        }
+    }
+
+    info!(message = "Listening.", address = %config.address);
+
+    let bytes_received = register!(BytesReceived::from(Protocol::UDP));
+    let mut templates = TemplateCache::new(config.max_templates);
+
+    // We add 1 to the max_length in order to determine if the received datagram has been truncated.
+    let mut buf = vec![0; config.max_length + 1];
+    loop {
+        let (byte_size, exporter) = tokio::select! {
+            recv = socket.recv_from(&mut buf) => match recv {
+                Ok(recv) => recv,
+                Err(error) => {
+                    emit!(NetflowReceiveError { error });
+                    continue;
+                }
+            },
+            _ = &mut shutdown => return Ok(()),
+        };
+
+        bytes_received.emit(ByteSize(byte_size));
+
+        if byte_size > config.max_length {
+            warn!(
+                message = "Discarding datagram larger than max_length.",
+                max_length = config.max_length,
+                internal_log_rate_limit = true
+            );
+            continue;
+        }
+
+        let decoded = match parser::decode(&buf[..byte_size], exporter, &mut templates) {
+            Ok(decoded) => decoded,
+            Err(error) => {
+                emit!(NetflowParseError {
+                    exporter,
+                    error: &error,
+                });
+                continue;
+            }
+        };
+
+        if decoded.missing_templates > 0 {
+            emit!(NetflowTemplatesMissing {
+                exporter,
+                count: decoded.missing_templates,
+            });
+        }
+
+        if decoded.events.is_empty() {
+            continue;
+        }
+
+        let now = Utc::now();
+        let events = decoded
+            .events
+            .into_iter()
+            .map(|mut log| {
+                log.try_insert(log_schema().source_type_key(), Bytes::from("netflow"));
+                log.try_insert(log_schema().timestamp_key(), now);
+                Event::from(log)
+            })
+            .collect::<Vec<_>>();
+
+        let count = events.len();
+        emit!(EventsReceived {
+            count,
+            byte_size: events.size_of(),
+        });
+
+        tokio::select! {
+            result = out.send_batch(events) => {
+                if let Err(error) = result {
+                    emit!(StreamClosedError { error, count });
+                    return Ok(());
+                }
+            }
+            _ = &mut shutdown => return Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<NetflowConfig>();
+    }
+}
@@ -0,0 +1,855 @@
+//! Decoding of NetFlow v5, NetFlow v9, and IPFIX datagrams.
+//!
+//! NetFlow v9 and IPFIX are template based: exporters periodically send templates describing the
+//! layout of their data records, and data records can only be decoded once the matching template
+//! has been seen. Templates are scoped to the exporter address and its source ID (v9) or
+//! observation domain (IPFIX), and are kept in a [`TemplateCache`] shared across datagrams.
+
+use std::{
+    collections::{HashMap, VecDeque},
+    fmt::Write as _,
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr},
+};
+
+use chrono::{DateTime, TimeZone, Utc};
+use snafu::Snafu;
+use vector_core::event::{LogEvent, Value};
+
+use super::fields::{field_kind, field_name, FieldKind};
+
+const NETFLOW_V5: u16 = 5;
+const NETFLOW_V9: u16 = 9;
+const IPFIX: u16 = 10;
+
+const V5_HEADER_LENGTH: usize = 24;
+const V5_RECORD_LENGTH: usize = 48;
+
+/// IPFIX field length signalling that the length is encoded inline with the value.
+const VARIABLE_LENGTH: u16 = 65535;
+
+#[derive(Debug, Eq, PartialEq, Snafu)]
+pub enum ParseError {
+    #[snafu(display("Unexpected end of datagram while reading {}", what))]
+    UnexpectedEof { what: &'static str },
+
+    #[snafu(display("Unsupported NetFlow version {}", version))]
+    UnsupportedVersion { version: u16 },
+
+    #[snafu(display("Invalid {} length {}", what, length))]
+    InvalidLength { what: &'static str, length: usize },
+}
+
+/// A single field of a template.
+#[derive(Clone, Debug, Eq, PartialEq)]
+struct FieldSpec {
+    id: u16,
+    length: u16,
+    enterprise: Option<u32>,
+}
+
+/// The layout of the data records of a given template ID.
+#[derive(Clone, Debug, Eq, PartialEq)]
+struct Template {
+    fields: Vec<FieldSpec>,
+
+    /// Number of leading fields that are scope fields, for options templates.
+    scope_fields: usize,
+
+    options: bool,
+}
+
+impl Template {
+    /// The smallest length a record of this template can have.
+    fn min_record_length(&self) -> usize {
+        self.fields
+            .iter()
+            .map(|field| {
+                if field.length == VARIABLE_LENGTH {
+                    1
+                } else {
+                    field.length as usize
+                }
+            })
+            .sum()
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+struct TemplateKey {
+    exporter: IpAddr,
+    domain: u32,
+    template_id: u16,
+}
+
+/// The number of templates kept by default.
+pub const DEFAULT_MAX_TEMPLATES: usize = 10_000;
+
+/// Templates received from exporters, keyed by exporter, source ID or observation domain, and
+/// template ID.
+///
+/// Beyond the maximum number of templates, the templates received the longest ago are forgotten
+/// first. Exporters send their templates again periodically, so the templates in use are kept.
+#[derive(Debug)]
+pub struct TemplateCache {
+    templates: HashMap<TemplateKey, (Template, u64)>,
+    /// The keys in the order their templates were received, along with the number of the insert.
+    /// Keys received again are found again later in the queue, and their earlier inserts are
+    /// skipped.
+    order: VecDeque<(TemplateKey, u64)>,
+    inserts: u64,
+    max_templates: usize,
+}
+
+impl Default for TemplateCache {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_TEMPLATES)
+    }
+}
+
+impl TemplateCache {
+    pub fn new(max_templates: usize) -> Self {
+        Self {
+            templates: HashMap::new(),
+            order: VecDeque::new(),
+            inserts: 0,
+            max_templates,
+        }
+    }
+
+    #[cfg(test)]
+    fn len(&self) -> usize {
+        self.templates.len()
+    }
+
+    fn insert(&mut self, exporter: IpAddr, domain: u32, template_id: u16, template: Template) {
+        let key = TemplateKey {
+            exporter,
+            domain,
+            template_id,
+        };
+        self.inserts += 1;
+        self.order.push_back((key, self.inserts));
+        self.templates.insert(key, (template, self.inserts));
+
+        while self.templates.len() > self.max_templates {
+            let (key, insert) = match self.order.pop_front() {
+                Some(oldest) => oldest,
+                None => break,
+            };
+            if self.templates.get(&key).map(|(_, latest)| *latest) == Some(insert) {
+                self.templates.remove(&key);
+            }
+        }
+        // Drops the inserts overridden since, so the queue doesn't grow with the templates sent
+        // again.
+        if self.order.len() > 2 * self.templates.len() + 16 {
+            let templates = &self.templates;
+            self.order.retain(|(key, insert)| {
+                templates.get(key).map(|(_, latest)| latest) == Some(insert)
+            });
+        }
+    }
+
+    fn remove(&mut self, exporter: IpAddr, domain: u32, template_id: u16) {
+        let key = TemplateKey {
+            exporter,
+            domain,
+            template_id,
+        };
+        self.templates.remove(&key);
+    }
+
+    fn get(&self, exporter: IpAddr, domain: u32, template_id: u16) -> Option<&Template> {
+        let key = TemplateKey {
+            exporter,
+            domain,
+            template_id,
+        };
+        self.templates.get(&key).map(|(template, _)| template)
+    }
+}
+
+/// The result of decoding a single datagram.
+#[derive(Debug, Default)]
+pub struct Decoded {
+    /// One event per flow or options record.
+    pub events: Vec<LogEvent>,
+
+    /// The number of data sets that were skipped because their template is not known yet.
+    pub missing_templates: usize,
+}
+
+/// Decode a NetFlow v5, NetFlow v9, or IPFIX datagram received from `exporter`.
+pub fn decode(
+    datagram: &[u8],
+    exporter: SocketAddr,
+    cache: &mut TemplateCache,
+) -> Result<Decoded, ParseError> {
+    let version = Reader::new(datagram).u16("version")?;
+    match version {
+        NETFLOW_V5 => decode_v5(datagram, exporter),
+        NETFLOW_V9 => decode_v9(datagram, exporter, cache),
+        IPFIX => decode_ipfix(datagram, exporter, cache),
+        version => Err(ParseError::UnsupportedVersion { version }),
+    }
+}
+
+fn new_event(flow_type: &'static str, record_type: &'static str, exporter: SocketAddr) -> LogEvent {
+    let mut log = LogEvent::default();
+    log.insert("flow_type", flow_type);
+    log.insert("record_type", record_type);
+    log.insert("exporter", exporter.ip().to_string());
+    log
+}
+
+fn export_time(secs: u32, nsecs: u32) -> Option<DateTime<Utc>> {
+    Utc.timestamp_opt(i64::from(secs), nsecs).single()
+}
+
+fn decode_v5(datagram: &[u8], exporter: SocketAddr) -> Result<Decoded, ParseError> {
+    let mut reader = Reader::new(datagram);
+    let _version = reader.u16("version")?;
+    let count = reader.u16("count")? as usize;
+    let sys_uptime = reader.u32("system uptime")?;
+    let unix_secs = reader.u32("export time")?;
+    let unix_nsecs = reader.u32("export time")?;
+    let flow_sequence = reader.u32("flow sequence")?;
+    let engine_type = reader.u8("engine type")?;
+    let engine_id = reader.u8("engine ID")?;
+    let sampling = reader.u16("sampling interval")?;
+
+    if datagram.len() < V5_HEADER_LENGTH + count * V5_RECORD_LENGTH {
+        return Err(ParseError::InvalidLength {
+            what: "NetFlow v5 datagram",
+            length: datagram.len(),
+        });
+    }
+
+    let timestamp = export_time(unix_secs, unix_nsecs);
+    let mut events = Vec::with_capacity(count);
+    for _ in 0..count {
+        let mut log = new_event("netflow_v5", "flow", exporter);
+        if let Some(timestamp) = timestamp {
+            log.insert("export_time", timestamp);
+        }
+        log.insert("sequence", flow_sequence);
+        log.insert("system_uptime", sys_uptime);
+        log.insert("engine_type", engine_type);
+        log.insert("engine_id", engine_id);
+        log.insert("sampling_interval", sampling & 0x3fff);
+
+        log.insert("source_ipv4_address", reader.ipv4("source address")?);
+        log.insert(
+            "destination_ipv4_address",
+            reader.ipv4("destination address")?,
+        );
+        log.insert("ip_next_hop_ipv4_address", reader.ipv4("next hop")?);
+        log.insert("ingress_interface", reader.u16("input interface")?);
+        log.insert("egress_interface", reader.u16("output interface")?);
+        log.insert("packet_delta_count", reader.u32("packet count")?);
+        log.insert("octet_delta_count", reader.u32("octet count")?);
+        log.insert("flow_start_sys_up_time", reader.u32("flow start")?);
+        log.insert("flow_end_sys_up_time", reader.u32("flow end")?);
+        log.insert("source_transport_port", reader.u16("source port")?);
+        log.insert(
+            "destination_transport_port",
+            reader.u16("destination port")?,
+        );
+        reader.skip(1, "padding")?;
+        log.insert("tcp_control_bits", reader.u8("TCP flags")?);
+        log.insert("protocol_identifier", reader.u8("protocol")?);
+        log.insert("ip_class_of_service", reader.u8("type of service")?);
+        log.insert("bgp_source_as_number", reader.u16("source AS")?);
+        log.insert("bgp_destination_as_number", reader.u16("destination AS")?);
+        log.insert("source_ipv4_prefix_length", reader.u8("source mask")?);
+        log.insert(
+            "destination_ipv4_prefix_length",
+            reader.u8("destination mask")?,
+        );
+        reader.skip(2, "padding")?;
+
+        events.push(log);
+    }
+
+    Ok(Decoded {
+        events,
+        missing_templates: 0,
+    })
+}
+
+fn decode_v9(
+    datagram: &[u8],
+    exporter: SocketAddr,
+    cache: &mut TemplateCache,
+) -> Result<Decoded, ParseError> {
+    let mut reader = Reader::new(datagram);
+    let _version = reader.u16("version")?;
+    let _count = reader.u16("count")?;
+    let sys_uptime = reader.u32("system uptime")?;
+    let unix_secs = reader.u32("export time")?;
+    let sequence = reader.u32("sequence")?;
+    let source_id = reader.u32("source ID")?;
+
+    let header = RecordHeader {
+        flow_type: "netflow_v9",
+        exporter,
+        export_time: export_time(unix_secs, 0),
+        sequence,
+        domain_field: "source_id",
+        domain: source_id,
+    };
+
+    let mut decoded = Decoded::default();
+    while reader.remaining() >= 4 {
+        let flowset_id = reader.u16("flowset ID")?;
+        let length = reader.u16("flowset length")? as usize;
+        if length < 4 {
+            return Err(ParseError::InvalidLength {
+                what: "flowset",
+                length,
+            });
+        }
+        let mut body = Reader::new(reader.take(length - 4, "flowset")?);
+
+        match flowset_id {
+            0 => {
+                while body.remaining() >= 4 {
+                    let template_id = body.u16("template ID")?;
+                    let field_count = body.u16("field count")?;
+                    let fields = (0..field_count)
+                        .map(|_| {
+                            Ok(FieldSpec {
+                                id: body.u16("field type")?,
+                                length: body.u16("field length")?,
+                                enterprise: None,
+                            })
+                        })
+                        .collect::<Result<Vec<_>, _>>()?;
+                    let template = Template {
+                        fields,
+                        scope_fields: 0,
+                        options: false,
+                    };
+                    cache.insert(exporter.ip(), source_id, template_id, template);
+                }
+            }
+            1 => {
+                while body.remaining() >= 6 {
+                    let template_id = body.u16("template ID")?;
+                    let scope_length = body.u16("option scope length")? as usize;
+                    let option_length = body.u16("option length")? as usize;
+                    let read_fields = |body: &mut Reader<'_>, length: usize, scope: bool| {
+                        (0..length / 4)
+                            .map(|_| {
+                                let id = body.u16("field type")?;
+                                let length = body.u16("field length")?;
+                                Ok(FieldSpec {
+                                    // Scope field types live in their own namespace, so keep them
+                                    // apart from regular field types.
+                                    id: if scope { id | SCOPE_FLAG } else { id },
+                                    length,
+                                    enterprise: None,
+                                })
+                            })
+                            .collect::<Result<Vec<_>, ParseError>>()
+                    };
+                    let mut fields = read_fields(&mut body, scope_length, true)?;
+                    let scope_fields = fields.len();
+                    fields.extend(read_fields(&mut body, option_length, false)?);
+                    let template = Template {
+                        fields,
+                        scope_fields,
+                        options: true,
+                    };
+                    cache.insert(exporter.ip(), source_id, template_id, template);
+                }
+            }
+            2..=255 => {}
+            template_id => match cache.get(exporter.ip(), source_id, template_id) {
+                Some(template) => {
+                    decode_records(&header, template_id, template, &mut body, &mut decoded)?;
+                }
+                None => decoded.missing_templates += 1,
+            },
+        }
+    }
+
+    for log in &mut decoded.events {
+        log.insert("system_uptime", sys_uptime);
+    }
+
+    Ok(decoded)
+}
+
+fn decode_ipfix(
+    datagram: &[u8],
+    exporter: SocketAddr,
+    cache: &mut TemplateCache,
+) -> Result<Decoded, ParseError> {
+    let mut reader = Reader::new(datagram);
+    let _version = reader.u16("version")?;
+    let length = reader.u16("message length")? as usize;
+    let export_secs = reader.u32("export time")?;
+    let sequence = reader.u32("sequence")?;
+    let domain = reader.u32("observation domain ID")?;
+
+    if length < 16 || length > datagram.len() {
+        return Err(ParseError::InvalidLength {
+            what: "IPFIX message",
+            length,
+        });
+    }
+    let mut reader = Reader::new(&datagram[16..length]);
+
+    let header = RecordHeader {
+        flow_type: "ipfix",
+        exporter,
+        export_time: export_time(export_secs, 0),
+        sequence,
+        domain_field: "observation_domain_id",
+        domain,
+    };
+
+    let mut decoded = Decoded::default();
+    while reader.remaining() >= 4 {
+        let set_id = reader.u16("set ID")?;
+        let length = reader.u16("set length")? as usize;
+        if length < 4 {
+            return Err(ParseError::InvalidLength {
+                what: "set",
+                length,
+            });
+        }
+        let mut body = Reader::new(reader.take(length - 4, "set")?);
+
+        match set_id {
+            2 | 3 => {
+                let options = set_id == 3;
+                let header_length = if options { 6 } else { 4 };
+                while body.remaining() >= header_length {
+                    let template_id = body.u16("template ID")?;
+                    let field_count = body.u16("field count")?;
+                    if field_count == 0 {
+                        // A template withdrawal.
+                        cache.remove(exporter.ip(), domain, template_id);
+                        continue;
+                    }
+                    let scope_fields = if options {
+                        body.u16("scope field count")? as usize
+                    } else {
+                        0
+                    };
+                    let fields = (0..field_count)
+                        .map(|_| {
+                            let id = body.u16("information element ID")?;
+                            let length = body.u16("field length")?;
+                            let enterprise = if id & 0x8000 != 0 {
+                                Some(body.u32("enterprise number")?)
+                            } else {
+                                None
+                            };
+                            Ok(FieldSpec {
+                                id: id & 0x7fff,
+                                length,
+                                enterprise,
+                            })
+                        })
+                        .collect::<Result<Vec<_>, ParseError>>()?;
+                    let template = Template {
+                        fields,
+                        scope_fields,
+                        options,
+                    };
+                    cache.insert(exporter.ip(), domain, template_id, template);
+                }
+            }
+            4..=255 => {}
+            template_id => match cache.get(exporter.ip(), domain, template_id) {
+                Some(template) => {
+                    decode_records(&header, template_id, template, &mut body, &mut decoded)?;
+                }
+                None => decoded.missing_templates += 1,
+            },
+        }
+    }
+
+    Ok(decoded)
+}
+
+/// Flag used to distinguish NetFlow v9 option scope field types from regular field types.
+const SCOPE_FLAG: u16 = 0x8000;
+
+struct RecordHeader {
+    flow_type: &'static str,
+    exporter: SocketAddr,
+    export_time: Option<DateTime<Utc>>,
+    sequence: u32,
+    domain_field: &'static str,
+    domain: u32,
+}
+
+fn decode_records(
+    header: &RecordHeader,
+    template_id: u16,
+    template: &Template,
+    body: &mut Reader<'_>,
+    decoded: &mut Decoded,
+) -> Result<(), ParseError> {
+    let min_length = template.min_record_length();
+    if min_length == 0 {
+        return Ok(());
+    }
+
+    // Anything shorter than a record at the end of the set is padding.
+    while body.remaining() >= min_length {
+        let record_type = if template.options { "options" } else { "flow" };
+        let mut log = new_event(header.flow_type, record_type, header.exporter);
+        if let Some(export_time) = header.export_time {
+            log.insert("export_time", export_time);
+        }
+        log.insert("sequence", header.sequence);
+        log.insert(header.domain_field, header.domain);
+        log.insert("template_id", template_id);
+
+        for (index, field) in template.fields.iter().enumerate() {
+            let length = if field.length == VARIABLE_LENGTH {
+                match body.u8("variable field length")? {
+                    255 => body.u16("variable field length")? as usize,
+                    length => length as usize,
+                }
+            } else {
+                field.length as usize
+            };
+            let data = body.take(length, "field value")?;
+
+            let scope = index < template.scope_fields;
+            let name = field_key(field, scope);
+            log.insert(name.as_str(), field_value(field, data));
+        }
+
+        decoded.events.push(log);
+    }
+
+    Ok(())
+}
+
+fn field_key(field: &FieldSpec, scope: bool) -> String {
+    match field.enterprise {
+        Some(enterprise) => format!("enterprise_{}_{}", enterprise, field.id),
+        None if field.id & SCOPE_FLAG != 0 => match field.id & !SCOPE_FLAG {
+            1 => "scope_system".to_string(),
+            2 => "scope_interface".to_string(),
+            3 => "scope_line_card".to_string(),
+            4 => "scope_cache".to_string(),
+            5 => "scope_template".to_string(),
+            id => format!("scope_{}", id),
+        },
+        None => {
+            let name = field_name(field.id)
+                .map(str::to_string)
+                .unwrap_or_else(|| format!("field_{}", field.id));
+            if scope {
+                format!("scope_{}", name)
+            } else {
+                name
+            }
+        }
+    }
+}
+
+fn field_value(field: &FieldSpec, data: &[u8]) -> Value {
+    let kind = match field.enterprise {
+        Some(_) => FieldKind::Unsigned,
+        None => field_kind(field.id),
+    };
+
+    match (kind, data.len()) {
+        (FieldKind::Ipv4, 4) => {
+            Value::from(Ipv4Addr::new(data[0], data[1], data[2], data[3]).to_string())
+        }
+        (FieldKind::Ipv6, 16) => {
+            let mut octets = [0; 16];
+            octets.copy_from_slice(data);
+            Value::from(Ipv6Addr::from(octets).to_string())
+        }
+        (FieldKind::Mac, 6) => Value::from(
+            data.iter()
+                .map(|byte| format!("{:02x}", byte))
+                .collect::<Vec<_>>()
+                .join(":"),
+        ),
+        (FieldKind::String, _) => Value::from(
+            String::from_utf8_lossy(data)
+                .trim_end_matches('\0')
+                .to_string(),
+        ),
+        (_, 1..=8) => Value::from(
+            data.iter()
+                .fold(0u64, |acc, byte| (acc << 8) | u64::from(*byte)),
+        ),
+        _ => {
+            let mut hex = String::with_capacity(data.len() * 2);
+            for byte in data {
+                let _ = write!(hex, "{:02x}", byte);
+            }
+            Value::from(hex)
+        }
+    }
+}
+
+struct Reader<'a> {
+    buf: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    const fn new(buf: &'a [u8]) -> Self {
+        Self { buf }
+    }
+
+    const fn remaining(&self) -> usize {
+        self.buf.len()
+    }
+
+    fn take(&mut self, length: usize, what: &'static str) -> Result<&'a [u8], ParseError> {
+        if self.buf.len() < length {
+            return Err(ParseError::UnexpectedEof { what });
+        }
+        let (head, tail) = self.buf.split_at(length);
+        self.buf = tail;
+        Ok(head)
+    }
+
+    fn skip(&mut self, length: usize, what: &'static str) -> Result<(), ParseError> {
+        self.take(length, what).map(|_| ())
+    }
+
+    fn u8(&mut self, what: &'static str) -> Result<u8, ParseError> {
+        Ok(self.take(1, what)?[0])
+    }
+
+    fn u16(&mut self, what: &'static str) -> Result<u16, ParseError> {
+        let bytes = self.take(2, what)?;
+        Ok(u16::from_be_bytes([bytes[0], bytes[1]]))
+    }
+
+    fn u32(&mut self, what: &'static str) -> Result<u32, ParseError> {
+        let bytes = self.take(4, what)?;
+        Ok(u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    fn ipv4(&mut self, what: &'static str) -> Result<String, ParseError> {
+        let bytes = self.take(4, what)?;
+        Ok(Ipv4Addr::new(bytes[0], bytes[1], bytes[2], bytes[3]).to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn exporter() -> SocketAddr {
+        "192.0.2.1:2055".parse().unwrap()
+    }
+
+    fn v5_datagram() -> Vec<u8> {
+        let mut datagram = vec![
+            0, 5, // version
+            0, 1, // count
+            0, 0, 0x03, 0xe8, // sys_uptime
+            0x5f, 0x5e, 0x10, 0x00, // unix_secs
+            0, 0, 0, 0, // unix_nsecs
+            0, 0, 0, 42, // flow_sequence
+            1, 2, // engine type, engine ID
+            0x40, 0x0a, // sampling interval
+        ];
+        datagram.extend_from_slice(&[
+            10, 0, 0, 1, // srcaddr
+            10, 0, 0, 2, // dstaddr
+            0, 0, 0, 0, // nexthop
+            0, 1, 0, 2, // input, output
+            0, 0, 0, 10, // dPkts
+            0, 0, 0x05, 0xdc, // dOctets
+            0, 0, 0, 1, // first
+            0, 0, 0, 2, // last
+            0x1f, 0x90, 0, 80, // srcport, dstport
+            0, 0x12, 6, 0, // pad1, tcp_flags, prot, tos
+            0, 0, 0, 0, // src_as, dst_as
+            24, 24, 0, 0, // src_mask, dst_mask, pad2
+        ]);
+        datagram
+    }
+
+    #[test]
+    fn decodes_netflow_v5() {
+        let mut cache = TemplateCache::default();
+        let decoded = decode(&v5_datagram(), exporter(), &mut cache).unwrap();
+
+        assert_eq!(decoded.events.len(), 1);
+        let log = &decoded.events[0];
+        assert_eq!(log["flow_type"], "netflow_v5".into());
+        assert_eq!(log["exporter"], "192.0.2.1".into());
+        assert_eq!(log["source_ipv4_address"], "10.0.0.1".into());
+        assert_eq!(log["destination_ipv4_address"], "10.0.0.2".into());
+        assert_eq!(log["source_transport_port"], 8080.into());
+        assert_eq!(log["destination_transport_port"], 80.into());
+        assert_eq!(log["octet_delta_count"], 1500.into());
+        assert_eq!(log["protocol_identifier"], 6.into());
+        assert_eq!(log["sampling_interval"], 10.into());
+        assert_eq!(log["sequence"], 42.into());
+    }
+
+    #[test]
+    fn rejects_truncated_netflow_v5() {
+        let mut datagram = v5_datagram();
+        datagram.truncate(50);
+        let mut cache = TemplateCache::default();
+
+        assert_eq!(
+            decode(&datagram, exporter(), &mut cache).unwrap_err(),
+            ParseError::InvalidLength {
+                what: "NetFlow v5 datagram",
+                length: 50
+            }
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_version() {
+        let mut cache = TemplateCache::default();
+        assert_eq!(
+            decode(&[0, 7, 0, 0], exporter(), &mut cache).unwrap_err(),
+            ParseError::UnsupportedVersion { version: 7 }
+        );
+    }
+
+    fn v9_header() -> Vec<u8> {
+        vec![
+            0, 9, // version
+            0, 1, // count
+            0, 0, 0, 1, // sys_uptime
+            0x5f, 0x5e, 0x10, 0x00, // unix_secs
+            0, 0, 0, 7, // sequence
+            0, 0, 0, 1, // source ID
+        ]
+    }
+
+    const V9_TEMPLATE: &[u8] = &[
+        0, 0, 0, 20, // flowset ID 0, length
+        1, 0, 0, 3, // template 256, 3 fields
+        0, 8, 0, 4, // IPV4_SRC_ADDR
+        0, 12, 0, 4, // IPV4_DST_ADDR
+        0, 1, 0, 4, // IN_BYTES
+    ];
+
+    const V9_DATA: &[u8] = &[
+        1, 0, 0, 28, // flowset ID 256, length
+        10, 0, 0, 1, 10, 0, 0, 2, 0, 0, 0, 100, // record 1
+        10, 0, 0, 3, 10, 0, 0, 4, 0, 0, 0, 200, // record 2
+    ];
+
+    #[test]
+    fn decodes_netflow_v9_with_template() {
+        let mut cache = TemplateCache::default();
+        let mut datagram = v9_header();
+        datagram.extend_from_slice(V9_TEMPLATE);
+        datagram.extend_from_slice(V9_DATA);
+
+        let decoded = decode(&datagram, exporter(), &mut cache).unwrap();
+        assert_eq!(decoded.missing_templates, 0);
+        assert_eq!(decoded.events.len(), 2);
+        assert_eq!(cache.len(), 1);
+
+        let log = &decoded.events[1];
+        assert_eq!(log["flow_type"], "netflow_v9".into());
+        assert_eq!(log["source_id"], 1.into());
+        assert_eq!(log["template_id"], 256.into());
+        assert_eq!(log["source_ipv4_address"], "10.0.0.3".into());
+        assert_eq!(log["destination_ipv4_address"], "10.0.0.4".into());
+        assert_eq!(log["octet_delta_count"], 200.into());
+    }
+
+    #[test]
+    fn forgets_oldest_templates() {
+        let mut cache = TemplateCache::new(2);
+        let exporter = exporter().ip();
+        let template = || Template {
+            fields: Vec::new(),
+            scope_fields: 0,
+            options: false,
+        };
+
+        cache.insert(exporter, 0, 256, template());
+        cache.insert(exporter, 0, 257, template());
+        // Receiving a template again makes it the most recent one.
+        cache.insert(exporter, 0, 256, template());
+        cache.insert(exporter, 0, 258, template());
+
+        assert_eq!(cache.len(), 2);
+        assert!(cache.get(exporter, 0, 256).is_some());
+        assert!(cache.get(exporter, 0, 257).is_none());
+        assert!(cache.get(exporter, 0, 258).is_some());
+
+        for template_id in 0..100 {
+            cache.insert(exporter, 0, 256, template());
+            cache.insert(exporter, 0, template_id, template());
+        }
+        assert!(cache.order.len() <= 2 * cache.len() + 16);
+    }
+
+    #[test]
+    fn netflow_v9_templates_are_cached_per_exporter() {
+        let mut cache = TemplateCache::default();
+        let mut templates = v9_header();
+        templates.extend_from_slice(V9_TEMPLATE);
+        decode(&templates, exporter(), &mut cache).unwrap();
+
+        let mut data = v9_header();
+        data.extend_from_slice(V9_DATA);
+
+        let decoded = decode(&data, exporter(), &mut cache).unwrap();
+        assert_eq!(decoded.events.len(), 2);
+
+        let other_exporter = "192.0.2.2:2055".parse().unwrap();
+        let decoded = decode(&data, other_exporter, &mut cache).unwrap();
+        assert!(decoded.events.is_empty());
+        assert_eq!(decoded.missing_templates, 1);
+    }
+
+    #[test]
+    fn decodes_ipfix_with_variable_length_fields() {
+        let mut cache = TemplateCache::default();
+        let mut datagram = vec![
+            0, 10, // version
+            0, 0, // length, filled in below
+            0x5f, 0x5e, 0x10, 0x00, // export time
+            0, 0, 0, 3, // sequence
+            0, 0, 0, 9, // observation domain
+        ];
+        datagram.extend_from_slice(&[
+            0, 2, 0, 24, // template set, length
+            1, 4, 0, 3, // template 260, 3 fields
+            0, 27, 0, 16, // sourceIPv6Address
+            0, 7, 0, 2, // sourceTransportPort
+            0x80, 0x64, 0xff, 0xff, // enterprise field 100, variable length
+            0, 0, 0x7a, 0x69, // enterprise number 31337
+        ]);
+        datagram.extend_from_slice(&[
+            1, 4, 0, 27, // data set 260, length
+            0x20, 0x01, 0x0d, 0xb8, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, // 2001:db8::1
+            0x01, 0xbb, // 443
+            3, b'f', b'o', b'o', // variable length value
+        ]);
+        let length = datagram.len() as u16;
+        datagram[2..4].copy_from_slice(&length.to_be_bytes());
+
+        let decoded = decode(&datagram, exporter(), &mut cache).unwrap();
+        assert_eq!(decoded.events.len(), 1);
+
+        let log = &decoded.events[0];
+        assert_eq!(log["flow_type"], "ipfix".into());
+        assert_eq!(log["observation_domain_id"], 9.into());
+        assert_eq!(log["source_ipv6_address"], "2001:db8::1".into());
+        assert_eq!(log["source_transport_port"], 443.into());
+        assert_eq!(log["enterprise_31337_100"], "666f6f".into());
+    }
+}
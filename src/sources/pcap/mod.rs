@@ -0,0 +1,309 @@
+use bytes::Bytes;
+use chrono::{TimeZone, Utc};
+use futures::StreamExt;
+use pcap::{Active, Capture};
+use snafu::{ResultExt, Snafu};
+use tokio::{sync::mpsc, task::spawn_blocking};
+use tokio_stream::wrappers::ReceiverStream;
+use vector_common::internal_event::{
+    ByteSize, BytesReceived, EventsReceived, InternalEventHandle as _, Protocol,
+};
+use vector_config::configurable_component;
+use vector_core::{config::LogNamespace, ByteSizeOf};
+
+use crate::{
+    config::{log_schema, DataType, GenerateConfig, Output, SourceConfig, SourceContext},
+    event::{Event, LogEvent},
+    internal_events::{PcapCaptureError, PcapPacketsDropped, StreamClosedError},
+    shutdown::ShutdownSignal,
+    SourceSender,
+};
+
+mod packet;
+
+/// The number of bytes captured from each packet to decode its headers.
+///
+/// This covers an Ethernet header with a VLAN tag, an IPv6 header, and a TCP header with options.
+const HEADERS_LENGTH: usize = 128;
+
+/// How long, in milliseconds, a read from the capture waits for packets before checking whether
+/// the source is shutting down.
+const READ_TIMEOUT_MS: i32 = 500;
+
+const CHANNEL_CAPACITY: usize = 1024;
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("Could not open capture on interface {:?}: {}", interface, source))]
+    OpenCapture {
+        interface: String,
+        source: pcap::Error,
+    },
+
+    #[snafu(display("Invalid BPF filter {:?}: {}", filter, source))]
+    InvalidFilter { filter: String, source: pcap::Error },
+}
+
+/// Configuration for the `pcap` source.
+#[configurable_component(source("pcap"))]
+#[configurable(metadata(status = "alpha"))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct PcapConfig {
+    /// The name of the network interface to capture packets on.
+    ///
+    /// On Linux, `any` captures packets on all interfaces.
+    interface: String,
+
+    /// A BPF filter expression, in `tcpdump` syntax, selecting the packets to capture.
+    ///
+    /// All packets are captured if not set.
+    filter: Option<String>,
+
+    /// Whether or not to put the interface into promiscuous mode.
+    #[serde(default)]
+    promiscuous: bool,
+
+    /// The maximum number of bytes of the packet payload to include in each event.
+    ///
+    /// Only the packet metadata is included if not set.
+    max_payload_bytes: Option<usize>,
+
+    /// The size, in bytes, of the kernel buffer holding captured packets until they are read.
+    ///
+    /// This should not typically needed to be changed.
+    buffer_size_bytes: Option<usize>,
+}
+
+impl GenerateConfig for PcapConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self {
+            interface: "eth0".to_string(),
+            filter: Some("tcp port 22".to_string()),
+            promiscuous: false,
+            max_payload_bytes: None,
+            buffer_size_bytes: None,
+        })
+        .unwrap()
+    }
+}
+
+impl PcapConfig {
+    fn open_capture(&self) -> crate::Result<Capture<Active>> {
+        let snaplen = HEADERS_LENGTH + self.max_payload_bytes.unwrap_or(0);
+
+        let mut capture = Capture::from_device(self.interface.as_str())
+            .and_then(|capture| {
+                let capture = capture
+                    .promisc(self.promiscuous)
+                    .snaplen(snaplen.try_into().unwrap_or(i32::MAX))
+                    .timeout(READ_TIMEOUT_MS);
+                let capture = match self.buffer_size_bytes {
+                    Some(size) => capture.buffer_size(size.try_into().unwrap_or(i32::MAX)),
+                    None => capture,
+                };
+                capture.open()
+            })
+            .context(OpenCaptureSnafu {
+                interface: self.interface.clone(),
+            })?;
+
+        if let Some(filter) = &self.filter {
+            capture
+                .filter(filter, true)
+                .context(InvalidFilterSnafu { filter })?;
+        }
+
+        Ok(capture)
+    }
+}
+
+#[async_trait::async_trait]
+impl SourceConfig for PcapConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
+        // Open the capture up front, so that missing permissions and invalid filters are
+        // reported when the configuration is loaded.
+        let capture = self.open_capture()?;
+
+        Ok(Box::pin(pcap_source(
+            self.clone(),
+            capture,
+            cx.shutdown,
+            cx.out,
+        )))
+    }
+
+    fn outputs(&self, _global_log_namespace: LogNamespace) -> Vec<Output> {
+        vec![Output::default(DataType::Log)]
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        false
+    }
+}
+
+/// A packet read from the capture.
+struct CapturedPacket {
+    timestamp_secs: i64,
+    timestamp_micros: u32,
+    length: u32,
+    data: Vec<u8>,
+}
+
+async fn pcap_source(
+    config: PcapConfig,
+    capture: Capture<Active>,
+    shutdown: ShutdownSignal,
+    mut out: SourceSender,
+) -> Result<(), ()> {
+    let linktype = capture.get_datalink().0;
+    let (sender, receiver) = mpsc::channel(CHANNEL_CAPACITY);
+
+    info!(message = "Capturing packets.", interface = %config.interface);
+
+    let span = info_span!("pcap_capture");
+    let capture_task = spawn_blocking(move || {
+        let _enter = span.enter();
+        capture_packets(capture, sender);
+    });
+
+    let bytes_received = register!(BytesReceived::from(Protocol::from("pcap")));
+    let mut stream = ReceiverStream::new(receiver)
+        .ready_chunks(CHANNEL_CAPACITY)
+        .take_until(shutdown);
+
+    while let Some(packets) = stream.next().await {
+        let events = packets
+            .into_iter()
+            .map(|packet| {
+                bytes_received.emit(ByteSize(packet.data.len()));
+                Event::from(packet_to_log(&config, linktype, packet))
+            })
+            .collect::<Vec<_>>();
+
+        let count = events.len();
+        emit!(EventsReceived {
+            count,
+            byte_size: events.size_of(),
+        });
+
+        if let Err(error) = out.send_batch(events).await {
+            emit!(StreamClosedError { error, count });
+            break;
+        }
+    }
+
+    // Dropping the receiver stops the capture thread at its next read.
+    drop(stream);
+    capture_task
+        .await
+        .map_err(|error| error!(message = "Packet capture unexpectedly stopped.", %error))
+}
+
+/// Reads packets from the capture until the receiving side of `sender` is dropped.
+fn capture_packets(mut capture: Capture<Active>, sender: mpsc::Sender<CapturedPacket>) {
+    let mut dropped = 0;
+    loop {
+        match capture.next_packet() {
+            Ok(packet) => {
+                let packet = CapturedPacket {
+                    timestamp_secs: i64::from(packet.header.ts.tv_sec),
+                    timestamp_micros: u32::try_from(packet.header.ts.tv_usec).unwrap_or(0),
+                    length: packet.header.len,
+                    data: packet.data.to_vec(),
+                };
+                if sender.blocking_send(packet).is_err() {
+                    break;
+                }
+            }
+            Err(pcap::Error::TimeoutExpired) => {
+                if sender.is_closed() {
+                    break;
+                }
+
+                if let Ok(stats) = capture.stats() {
+                    let total = u64::from(stats.dropped) + u64::from(stats.if_dropped);
+                    if total > dropped {
+                        emit!(PcapPacketsDropped {
+                            count: total - dropped,
+                        });
+                    }
+                    dropped = total;
+                }
+            }
+            Err(error) => {
+                emit!(PcapCaptureError { error });
+                break;
+            }
+        }
+    }
+}
+
+fn packet_to_log(config: &PcapConfig, linktype: i32, captured: CapturedPacket) -> LogEvent {
+    let mut log = LogEvent::default();
+
+    let payload = packet::decode(linktype, &captured.data, &mut log);
+    if let (Some(max_payload_bytes), Some(payload)) = (config.max_payload_bytes, payload) {
+        let length = payload.len().min(max_payload_bytes);
+        log.insert("payload", Bytes::copy_from_slice(&payload[..length]));
+    }
+
+    log.insert("interface", config.interface.clone());
+    log.insert("length", captured.length);
+    log.insert("captured_length", captured.data.len());
+
+    let timestamp = Utc
+        .timestamp_opt(captured.timestamp_secs, captured.timestamp_micros * 1_000)
+        .single()
+        .unwrap_or_else(Utc::now);
+    log.insert(log_schema().timestamp_key(), timestamp);
+    log.insert(log_schema().source_type_key(), Bytes::from("pcap"));
+
+    log
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<PcapConfig>();
+    }
+
+    #[test]
+    fn caps_payload() {
+        let config = PcapConfig {
+            interface: "lo".to_string(),
+            filter: None,
+            promiscuous: false,
+            max_payload_bytes: Some(4),
+            buffer_size_bytes: None,
+        };
+
+        let mut data = vec![0x45, 0, 0, 34, 0, 0, 0, 0, 64, 17, 0, 0];
+        data.extend_from_slice(&[127, 0, 0, 1, 127, 0, 0, 1]);
+        data.extend_from_slice(&[0x30, 0x39, 0x30, 0x39, 0, 14, 0, 0]);
+        data.extend_from_slice(b"abcdef");
+
+        let captured = CapturedPacket {
+            timestamp_secs: 1_600_000_000,
+            timestamp_micros: 250_000,
+            length: 1500,
+            data,
+        };
+        let log = packet_to_log(&config, packet::LINKTYPE_RAW, captured);
+
+        assert_eq!(log["payload"], "abcd".into());
+        assert_eq!(log["length"], 1500.into());
+        assert_eq!(log["captured_length"], 34.into());
+        assert_eq!(log["destination_port"], 12345.into());
+        assert_eq!(log["source_type"], "pcap".into());
+        assert_eq!(
+            log["timestamp"],
+            Utc.timestamp_opt(1_600_000_000, 250_000_000)
+                .unwrap()
+                .into()
+        );
+    }
+}
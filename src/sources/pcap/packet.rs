@@ -0,0 +1,213 @@
+//! Decoding of the link, network, and transport layer headers of captured packets.
+
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+use vector_core::event::LogEvent;
+
+/// Link type of Ethernet captures.
+pub const LINKTYPE_ETHERNET: i32 = 1;
+/// Link type of captures without a link layer header, starting with the IP header.
+pub const LINKTYPE_RAW: i32 = 101;
+/// Link type of Linux "cooked" captures, such as captures on the `any` device.
+pub const LINKTYPE_LINUX_SLL: i32 = 113;
+
+const ETHER_TYPE_IPV4: u16 = 0x0800;
+const ETHER_TYPE_IPV6: u16 = 0x86dd;
+const ETHER_TYPE_VLAN: u16 = 0x8100;
+
+const PROTOCOL_ICMP: u8 = 1;
+const PROTOCOL_TCP: u8 = 6;
+const PROTOCOL_UDP: u8 = 17;
+const PROTOCOL_ICMPV6: u8 = 58;
+
+/// Decode as many headers of `data` as possible into `log`, returning the transport payload.
+///
+/// Decoding stops silently at the first header that is unknown or was truncated by the snapshot
+/// length, so the fields present depend on the packet.
+pub fn decode<'a>(linktype: i32, data: &'a [u8], log: &mut LogEvent) -> Option<&'a [u8]> {
+    let (ether_type, network) = match linktype {
+        LINKTYPE_ETHERNET => decode_ethernet(data, log)?,
+        LINKTYPE_LINUX_SLL => {
+            let header = data.get(..16)?;
+            (u16::from_be_bytes([header[14], header[15]]), &data[16..])
+        }
+        LINKTYPE_RAW => match data.first()? >> 4 {
+            4 => (ETHER_TYPE_IPV4, data),
+            6 => (ETHER_TYPE_IPV6, data),
+            _ => return None,
+        },
+        _ => return None,
+    };
+
+    let (protocol, transport) = match ether_type {
+        ETHER_TYPE_IPV4 => decode_ipv4(network, log)?,
+        ETHER_TYPE_IPV6 => decode_ipv6(network, log)?,
+        _ => return None,
+    };
+
+    match protocol {
+        PROTOCOL_TCP => {
+            let header = transport.get(..20)?;
+            log.insert("source_port", u16::from_be_bytes([header[0], header[1]]));
+            log.insert(
+                "destination_port",
+                u16::from_be_bytes([header[2], header[3]]),
+            );
+            log.insert("tcp_flags", header[13]);
+            let offset = ((header[12] >> 4) as usize) * 4;
+            transport.get(offset..)
+        }
+        PROTOCOL_UDP => {
+            let header = transport.get(..8)?;
+            log.insert("source_port", u16::from_be_bytes([header[0], header[1]]));
+            log.insert(
+                "destination_port",
+                u16::from_be_bytes([header[2], header[3]]),
+            );
+            transport.get(8..)
+        }
+        PROTOCOL_ICMP | PROTOCOL_ICMPV6 => {
+            let header = transport.get(..4)?;
+            log.insert("icmp_type", header[0]);
+            log.insert("icmp_code", header[1]);
+            transport.get(4..)
+        }
+        _ => Some(transport),
+    }
+}
+
+fn decode_ethernet<'a>(data: &'a [u8], log: &mut LogEvent) -> Option<(u16, &'a [u8])> {
+    let header = data.get(..14)?;
+    log.insert("destination_mac", format_mac(&header[0..6]));
+    log.insert("source_mac", format_mac(&header[6..12]));
+
+    let mut ether_type = u16::from_be_bytes([header[12], header[13]]);
+    let mut offset = 14;
+    if ether_type == ETHER_TYPE_VLAN {
+        let tag = data.get(14..18)?;
+        log.insert("vlan", u16::from_be_bytes([tag[0], tag[1]]) & 0x0fff);
+        ether_type = u16::from_be_bytes([tag[2], tag[3]]);
+        offset = 18;
+    }
+    log.insert("ether_type", ether_type);
+
+    Some((ether_type, &data[offset..]))
+}
+
+fn decode_ipv4<'a>(data: &'a [u8], log: &mut LogEvent) -> Option<(u8, &'a [u8])> {
+    let header = data.get(..20)?;
+    let header_length = ((header[0] & 0x0f) as usize) * 4;
+    log.insert("ip_version", 4);
+    log.insert(
+        "source_address",
+        Ipv4Addr::new(header[12], header[13], header[14], header[15]).to_string(),
+    );
+    log.insert(
+        "destination_address",
+        Ipv4Addr::new(header[16], header[17], header[18], header[19]).to_string(),
+    );
+    log.insert("ttl", header[8]);
+    log.insert("protocol", header[9]);
+
+    // A fragment other than the first one does not start with a transport header.
+    let fragment_offset = u16::from_be_bytes([header[6], header[7]]) & 0x1fff;
+    if fragment_offset != 0 {
+        return None;
+    }
+    Some((header[9], data.get(header_length..)?))
+}
+
+fn decode_ipv6<'a>(data: &'a [u8], log: &mut LogEvent) -> Option<(u8, &'a [u8])> {
+    let header = data.get(..40)?;
+    let address = |bytes: &[u8]| {
+        let mut octets = [0; 16];
+        octets.copy_from_slice(bytes);
+        Ipv6Addr::from(octets).to_string()
+    };
+    log.insert("ip_version", 6);
+    log.insert("source_address", address(&header[8..24]));
+    log.insert("destination_address", address(&header[24..40]));
+    log.insert("ttl", header[7]);
+    log.insert("protocol", header[6]);
+
+    Some((header[6], &data[40..]))
+}
+
+fn format_mac(bytes: &[u8]) -> String {
+    bytes
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect::<Vec<_>>()
+        .join(":")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn udp_over_ethernet() -> Vec<u8> {
+        let mut frame = vec![
+            0x00, 0x11, 0x22, 0x33, 0x44, 0x55, // destination MAC
+            0x00, 0x66, 0x77, 0x88, 0x99, 0xaa, // source MAC
+            0x81, 0x00, 0x00, 0x2a, // VLAN 42
+            0x08, 0x00, // IPv4
+        ];
+        frame.extend_from_slice(&[
+            0x45, 0, 0, 33, 0, 0, 0, 0, 64, 17, 0, 0, // IPv4 header
+            192, 0, 2, 1, // source
+            192, 0, 2, 2, // destination
+        ]);
+        frame.extend_from_slice(&[0xd4, 0x31, 0, 53, 0, 13, 0, 0]); // UDP header
+        frame.extend_from_slice(b"hello");
+        frame
+    }
+
+    #[test]
+    fn decodes_udp_over_ethernet() {
+        let mut log = LogEvent::default();
+        let payload = decode(LINKTYPE_ETHERNET, &udp_over_ethernet(), &mut log);
+
+        assert_eq!(payload, Some(&b"hello"[..]));
+        assert_eq!(log["source_mac"], "00:66:77:88:99:aa".into());
+        assert_eq!(log["vlan"], 42.into());
+        assert_eq!(log["ether_type"], 0x0800.into());
+        assert_eq!(log["ip_version"], 4.into());
+        assert_eq!(log["source_address"], "192.0.2.1".into());
+        assert_eq!(log["destination_address"], "192.0.2.2".into());
+        assert_eq!(log["protocol"], 17.into());
+        assert_eq!(log["source_port"], 54321.into());
+        assert_eq!(log["destination_port"], 53.into());
+    }
+
+    #[test]
+    fn decodes_tcp_over_raw_ipv6() {
+        let mut packet = vec![0x60, 0, 0, 0, 0, 20, 6, 255];
+        packet.extend_from_slice(&[0x20, 0x01, 0x0d, 0xb8, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1]);
+        packet.extend_from_slice(&[0x20, 0x01, 0x0d, 0xb8, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 2]);
+        packet.extend_from_slice(&[
+            0x01, 0xbb, 0xc3, 0x50, 0, 0, 0, 0, 0, 0, 0, 0, 0x50, 0x12, 0, 0, 0, 0, 0, 0,
+        ]);
+
+        let mut log = LogEvent::default();
+        let payload = decode(LINKTYPE_RAW, &packet, &mut log);
+
+        assert_eq!(payload, Some(&[][..]));
+        assert_eq!(log["ip_version"], 6.into());
+        assert_eq!(log["source_address"], "2001:db8::1".into());
+        assert_eq!(log["ttl"], 255.into());
+        assert_eq!(log["source_port"], 443.into());
+        assert_eq!(log["tcp_flags"], 0x12.into());
+        assert!(log.get("source_mac").is_none());
+    }
+
+    #[test]
+    fn stops_at_truncated_headers() {
+        let mut frame = udp_over_ethernet();
+        frame.truncate(30);
+
+        let mut log = LogEvent::default();
+        assert_eq!(decode(LINKTYPE_ETHERNET, &frame, &mut log), None);
+        assert_eq!(log["vlan"], 42.into());
+        assert!(log.get("source_address").is_none());
+    }
+}
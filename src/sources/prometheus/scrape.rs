@@ -11,6 +11,7 @@ use vector_core::{config::LogNamespace, event::Event};
 use super::parser;
 use crate::{
     config::{self, GenerateConfig, Output, SourceConfig, SourceContext},
+    coordination::CoordinationConfig,
     http::Auth,
     internal_events::PrometheusParseError,
     sources::{
@@ -86,6 +87,9 @@ pub struct PrometheusScrapeConfig {
 
     #[configurable(derived)]
     auth: Option<Auth>,
+
+    #[configurable(derived)]
+    coordination: Option<CoordinationConfig>,
 }
 
 impl GenerateConfig for PrometheusScrapeConfig {
@@ -99,6 +103,7 @@ impl GenerateConfig for PrometheusScrapeConfig {
             query: HashMap::new(),
             tls: None,
             auth: None,
+            coordination: None,
         })
         .unwrap()
     }
@@ -121,6 +126,11 @@ impl SourceConfig for PrometheusScrapeConfig {
             endpoint_tag: self.endpoint_tag.clone(),
         };
 
+        let coordinator = match &self.coordination {
+            Some(coordination) => Some(coordination.build(&cx.key).await?),
+            None => None,
+        };
+
         let inputs = GenericHttpScrapeInputs {
             urls,
             interval_secs: self.scrape_interval_secs,
@@ -130,6 +140,7 @@ impl SourceConfig for PrometheusScrapeConfig {
             tls,
             proxy: cx.proxy.clone(),
             shutdown: cx.shutdown,
+            coordinator,
         };
 
         Ok(http_scrape(inputs, builder, cx.out).boxed())
@@ -333,6 +344,7 @@ mod test {
             honor_labels: true,
             query: HashMap::new(),
             auth: None,
+            coordination: None,
             tls: None,
         };
 
@@ -366,6 +378,7 @@ mod test {
             honor_labels: true,
             query: HashMap::new(),
             auth: None,
+            coordination: None,
             tls: None,
         };
 
@@ -417,6 +430,7 @@ mod test {
             honor_labels: false,
             query: HashMap::new(),
             auth: None,
+            coordination: None,
             tls: None,
         };
 
@@ -487,6 +501,7 @@ mod test {
                 ),
             ]),
             auth: None,
+            coordination: None,
             tls: None,
         };
 
@@ -591,6 +606,7 @@ mod test {
                 scrape_interval_secs: 1,
                 tls: None,
                 auth: None,
+                coordination: None,
             },
         );
         config.add_sink(
@@ -679,6 +695,7 @@ mod integration_tests {
             honor_labels: false,
             query: HashMap::new(),
             auth: None,
+            coordination: None,
             tls: None,
         };
 
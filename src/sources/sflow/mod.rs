@@ -0,0 +1,172 @@
+use std::net::SocketAddr;
+
+use bytes::Bytes;
+use chrono::Utc;
+use tokio::net::UdpSocket;
+use vector_common::internal_event::{
+    ByteSize, BytesReceived, EventsReceived, InternalEventHandle as _, Protocol,
+};
+use vector_config::configurable_component;
+use vector_core::{config::LogNamespace, ByteSizeOf};
+
+use crate::{
+    config::{log_schema, DataType, GenerateConfig, Output, Resource, SourceConfig, SourceContext},
+    event::Event,
+    internal_events::{SflowBindError, SflowParseError, SflowReceiveError, StreamClosedError},
+    serde::default_max_length,
+    shutdown::ShutdownSignal,
+    udp, SourceSender,
+};
+
+pub mod parser;
+
+/// Configuration for the `sflow` source.
+#[configurable_component(source("sflow"))]
+#[configurable(metadata(status = "alpha"))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct SflowConfig {
+    /// The address to listen for sFlow datagrams on.
+    address: SocketAddr,
+
+    /// The maximum size, in bytes, of incoming datagrams.
+    ///
+    /// Datagrams larger than this are discarded.
+    #[serde(default = "default_max_length")]
+    max_length: usize,
+
+    /// The size, in bytes, of the receive buffer used for the listening socket.
+    ///
+    /// This should not typically needed to be changed.
+    receive_buffer_bytes: Option<usize>,
+}
+
+impl GenerateConfig for SflowConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self {
+            address: "0.0.0.0:6343".parse().unwrap(),
+            max_length: default_max_length(),
+            receive_buffer_bytes: None,
+        })
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+impl SourceConfig for SflowConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
+        Ok(Box::pin(sflow_udp(self.clone(), cx.shutdown, cx.out)))
+    }
+
+    fn outputs(&self, _global_log_namespace: LogNamespace) -> Vec<Output> {
+        vec![Output::default(DataType::Log)]
+    }
+
+    fn resources(&self) -> Vec<Resource> {
+        vec![Resource::udp(self.address)]
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        false
+    }
+}
+
+async fn sflow_udp(
+    config: SflowConfig,
+    mut shutdown: ShutdownSignal,
+    mut out: SourceSender,
+) -> Result<(), ()> {
+    let socket = UdpSocket::bind(&config.address).await.map_err(|error| {
+        emit!(SflowBindError {
+            address: config.address,
+            error,
+        })
+    })?;
+
+    if let Some(receive_buffer_bytes) = config.receive_buffer_bytes {
+        if let Err(error) = udp::set_receive_buffer_size(&socket, receive_buffer_bytes) {
+            warn!(message = "Failed configuring receive buffer size on UDP socket.", %error);
+        }
+    }
+
+    info!(message = "Listening.", address = %config.address);
+
+    let bytes_received = register!(BytesReceived::from(Protocol::UDP));
+
+    // We add 1 to the max_length in order to determine if the received datagram has been truncated.
+    let mut buf = vec![0; config.max_length + 1];
+    loop {
+        let (byte_size, agent) = tokio::select! {
+            recv = socket.recv_from(&mut buf) => match recv {
+                Ok(recv) => recv,
+                Err(error) => {
+                    emit!(SflowReceiveError { error });
+                    continue;
+                }
+            },
+            _ = &mut shutdown => return Ok(()),
+        };
+
+        bytes_received.emit(ByteSize(byte_size));
+
+        if byte_size > config.max_length {
+            warn!(
+                message = "Discarding datagram larger than max_length.",
+                max_length = config.max_length,
+                internal_log_rate_limit = true
+            );
+            continue;
+        }
+
+        let events = match parser::decode(&buf[..byte_size]) {
+            Ok(events) => events,
+            Err(error) => {
+                emit!(SflowParseError {
+                    agent,
+                    error: &error,
+                });
+                continue;
+            }
+        };
+
+        if events.is_empty() {
+            continue;
+        }
+
+        let now = Utc::now();
+        let events = events
+            .into_iter()
+            .map(|mut log| {
+                log.try_insert(log_schema().source_type_key(), Bytes::from("sflow"));
+                log.try_insert(log_schema().timestamp_key(), now);
+                Event::from(log)
+            })
+            .collect::<Vec<_>>();
+
+        let count = events.len();
+        emit!(EventsReceived {
+            count,
+            byte_size: events.size_of(),
+        });
+
+        tokio::select! {
+            result = out.send_batch(events) => {
+                if let Err(error) = result {
+                    emit!(StreamClosedError { error, count });
+                    return Ok(());
+                }
+            }
+            _ = &mut shutdown => return Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<SflowConfig>();
+    }
+}
@@ -0,0 +1,562 @@
+//! Decoding of sFlow version 5 datagrams.
+//!
+//! A datagram carries a number of samples taken by an agent, each of which is made of a number of
+//! records. Flow samples describe a single sampled packet, and counter samples carry interface
+//! counters polled at regular intervals. Samples and records with formats that are not known are
+//! skipped, using their encoded length.
+
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+use snafu::Snafu;
+use vector_core::event::LogEvent;
+
+const SFLOW_V5: u32 = 5;
+
+const FLOW_SAMPLE: u32 = 1;
+const COUNTER_SAMPLE: u32 = 2;
+const EXPANDED_FLOW_SAMPLE: u32 = 3;
+const EXPANDED_COUNTER_SAMPLE: u32 = 4;
+
+const RAW_PACKET_HEADER: u32 = 1;
+const ETHERNET_FRAME: u32 = 2;
+const IPV4_DATA: u32 = 3;
+const IPV6_DATA: u32 = 4;
+const EXTENDED_SWITCH: u32 = 1001;
+const EXTENDED_ROUTER: u32 = 1002;
+
+const GENERIC_INTERFACE_COUNTERS: u32 = 1;
+const ETHERNET_INTERFACE_COUNTERS: u32 = 2;
+
+/// The counters of the Ethernet interface counters record, in encoding order.
+const ETHERNET_COUNTER_NAMES: [&str; 13] = [
+    "alignment_errors",
+    "fcs_errors",
+    "single_collision_frames",
+    "multiple_collision_frames",
+    "sqe_test_errors",
+    "deferred_transmissions",
+    "late_collisions",
+    "excessive_collisions",
+    "internal_mac_transmit_errors",
+    "carrier_sense_errors",
+    "frame_too_longs",
+    "internal_mac_receive_errors",
+    "symbol_errors",
+];
+
+#[derive(Debug, Eq, PartialEq, Snafu)]
+pub enum ParseError {
+    #[snafu(display("Unexpected end of datagram while reading {}", what))]
+    UnexpectedEof { what: &'static str },
+
+    #[snafu(display("Unsupported sFlow version {}", version))]
+    UnsupportedVersion { version: u32 },
+
+    #[snafu(display("Unsupported agent address type {}", address_type))]
+    UnsupportedAddressType { address_type: u32 },
+}
+
+/// The fields shared by every sample of a datagram.
+struct DatagramHeader {
+    agent_address: String,
+    sub_agent_id: u32,
+    sequence: u32,
+    uptime: u32,
+}
+
+impl DatagramHeader {
+    fn new_event(&self, sample_type: &'static str) -> LogEvent {
+        let mut log = LogEvent::default();
+        log.insert("sample_type", sample_type);
+        log.insert("agent_address", self.agent_address.clone());
+        log.insert("sub_agent_id", self.sub_agent_id);
+        log.insert("datagram_sequence", self.sequence);
+        log.insert("uptime", self.uptime);
+        log
+    }
+}
+
+/// Decode an sFlow v5 datagram into one event per flow or counter sample.
+pub fn decode(datagram: &[u8]) -> Result<Vec<LogEvent>, ParseError> {
+    let mut reader = Reader::new(datagram);
+
+    let version = reader.u32("version")?;
+    if version != SFLOW_V5 {
+        return Err(ParseError::UnsupportedVersion { version });
+    }
+
+    let header = DatagramHeader {
+        agent_address: reader.address("agent address")?.unwrap_or_default(),
+        sub_agent_id: reader.u32("sub agent ID")?,
+        sequence: reader.u32("sequence")?,
+        uptime: reader.u32("uptime")?,
+    };
+
+    let sample_count = reader.u32("sample count")?;
+    let mut events = Vec::new();
+    for _ in 0..sample_count {
+        let format = reader.u32("sample format")?;
+        let length = reader.u32("sample length")? as usize;
+        let mut sample = Reader::new(reader.take(length, "sample")?);
+
+        // Only the standard sample formats, in enterprise 0, are decoded.
+        let event = match format {
+            FLOW_SAMPLE => decode_flow_sample(&header, &mut sample, false)?,
+            EXPANDED_FLOW_SAMPLE => decode_flow_sample(&header, &mut sample, true)?,
+            COUNTER_SAMPLE => decode_counter_sample(&header, &mut sample, false)?,
+            EXPANDED_COUNTER_SAMPLE => decode_counter_sample(&header, &mut sample, true)?,
+            _ => continue,
+        };
+        events.push(event);
+    }
+
+    Ok(events)
+}
+
+fn decode_source_id(
+    log: &mut LogEvent,
+    sample: &mut Reader<'_>,
+    expanded: bool,
+) -> Result<(), ParseError> {
+    let (source_id_type, source_id_index) = if expanded {
+        (
+            sample.u32("source ID type")?,
+            sample.u32("source ID index")?,
+        )
+    } else {
+        let source_id = sample.u32("source ID")?;
+        (source_id >> 24, source_id & 0x00ff_ffff)
+    };
+    log.insert("source_id_type", source_id_type);
+    log.insert("source_id_index", source_id_index);
+    Ok(())
+}
+
+fn decode_interface(sample: &mut Reader<'_>, expanded: bool) -> Result<u32, ParseError> {
+    if expanded {
+        let _format = sample.u32("interface format")?;
+        sample.u32("interface")
+    } else {
+        // The two high bits encode the format, the remaining bits the interface index.
+        Ok(sample.u32("interface")? & 0x3fff_ffff)
+    }
+}
+
+fn decode_flow_sample(
+    header: &DatagramHeader,
+    sample: &mut Reader<'_>,
+    expanded: bool,
+) -> Result<LogEvent, ParseError> {
+    let mut log = header.new_event("flow");
+    log.insert("sequence", sample.u32("sample sequence")?);
+    decode_source_id(&mut log, sample, expanded)?;
+    log.insert("sampling_rate", sample.u32("sampling rate")?);
+    log.insert("sample_pool", sample.u32("sample pool")?);
+    log.insert("drops", sample.u32("drops")?);
+    log.insert("input_interface", decode_interface(sample, expanded)?);
+    log.insert("output_interface", decode_interface(sample, expanded)?);
+
+    let record_count = sample.u32("record count")?;
+    for _ in 0..record_count {
+        let format = sample.u32("record format")?;
+        let length = sample.u32("record length")? as usize;
+        let mut record = Reader::new(sample.take(length, "flow record")?);
+
+        match format {
+            RAW_PACKET_HEADER => {
+                let protocol = record.u32("header protocol")?;
+                log.insert("packet.header_protocol", protocol);
+                log.insert("packet.frame_length", record.u32("frame length")?);
+                log.insert("packet.stripped", record.u32("stripped")?);
+                let header_length = record.u32("header length")? as usize;
+                let bytes = record.take(header_length, "packet header")?;
+                // Header protocol 1 is Ethernet (ISO 8802-3).
+                if protocol == 1 {
+                    decode_ethernet(&mut log, bytes);
+                }
+            }
+            ETHERNET_FRAME => {
+                log.insert("packet.frame_length", record.u32("frame length")?);
+                log.insert("packet.source_mac", record.mac("source MAC")?);
+                log.insert("packet.destination_mac", record.mac("destination MAC")?);
+                log.insert("packet.ether_type", record.u32("ether type")?);
+            }
+            IPV4_DATA | IPV6_DATA => {
+                log.insert("packet.length", record.u32("length")?);
+                log.insert("packet.protocol", record.u32("protocol")?);
+                let (source, destination) = if format == IPV4_DATA {
+                    (record.ipv4("source IP")?, record.ipv4("destination IP")?)
+                } else {
+                    (record.ipv6("source IP")?, record.ipv6("destination IP")?)
+                };
+                log.insert("packet.source_address", source);
+                log.insert("packet.destination_address", destination);
+                log.insert("packet.source_port", record.u32("source port")?);
+                log.insert("packet.destination_port", record.u32("destination port")?);
+                log.insert("packet.tcp_flags", record.u32("TCP flags")?);
+            }
+            EXTENDED_SWITCH => {
+                log.insert("switch.source_vlan", record.u32("source VLAN")?);
+                log.insert("switch.source_priority", record.u32("source priority")?);
+                log.insert("switch.destination_vlan", record.u32("destination VLAN")?);
+                log.insert(
+                    "switch.destination_priority",
+                    record.u32("destination priority")?,
+                );
+            }
+            EXTENDED_ROUTER => {
+                if let Some(next_hop) = record.address("next hop")? {
+                    log.insert("router.next_hop", next_hop);
+                }
+                log.insert("router.source_mask_length", record.u32("source mask")?);
+                log.insert(
+                    "router.destination_mask_length",
+                    record.u32("destination mask")?,
+                );
+            }
+            _ => {}
+        }
+    }
+
+    Ok(log)
+}
+
+fn decode_counter_sample(
+    header: &DatagramHeader,
+    sample: &mut Reader<'_>,
+    expanded: bool,
+) -> Result<LogEvent, ParseError> {
+    let mut log = header.new_event("counter");
+    log.insert("sequence", sample.u32("sample sequence")?);
+    decode_source_id(&mut log, sample, expanded)?;
+
+    let record_count = sample.u32("record count")?;
+    for _ in 0..record_count {
+        let format = sample.u32("record format")?;
+        let length = sample.u32("record length")? as usize;
+        let mut record = Reader::new(sample.take(length, "counter record")?);
+
+        match format {
+            GENERIC_INTERFACE_COUNTERS => {
+                let r = &mut record;
+                log.insert("interface.index", r.u32("ifIndex")?);
+                log.insert("interface.type", r.u32("ifType")?);
+                log.insert("interface.speed", r.u64("ifSpeed")?);
+                log.insert("interface.direction", r.u32("ifDirection")?);
+                log.insert("interface.status", r.u32("ifStatus")?);
+                log.insert("interface.in_octets", r.u64("ifInOctets")?);
+                log.insert("interface.in_unicast_packets", r.u32("ifInUcastPkts")?);
+                log.insert(
+                    "interface.in_multicast_packets",
+                    r.u32("ifInMulticastPkts")?,
+                );
+                log.insert(
+                    "interface.in_broadcast_packets",
+                    r.u32("ifInBroadcastPkts")?,
+                );
+                log.insert("interface.in_discards", r.u32("ifInDiscards")?);
+                log.insert("interface.in_errors", r.u32("ifInErrors")?);
+                log.insert(
+                    "interface.in_unknown_protocols",
+                    r.u32("ifInUnknownProtos")?,
+                );
+                log.insert("interface.out_octets", r.u64("ifOutOctets")?);
+                log.insert("interface.out_unicast_packets", r.u32("ifOutUcastPkts")?);
+                log.insert(
+                    "interface.out_multicast_packets",
+                    r.u32("ifOutMulticastPkts")?,
+                );
+                log.insert(
+                    "interface.out_broadcast_packets",
+                    r.u32("ifOutBroadcastPkts")?,
+                );
+                log.insert("interface.out_discards", r.u32("ifOutDiscards")?);
+                log.insert("interface.out_errors", r.u32("ifOutErrors")?);
+                log.insert(
+                    "interface.promiscuous_mode",
+                    r.u32("ifPromiscuousMode")? != 0,
+                );
+            }
+            ETHERNET_INTERFACE_COUNTERS => {
+                for name in ETHERNET_COUNTER_NAMES {
+                    let value = record.u32(name)?;
+                    log.insert(format!("ethernet.{}", name).as_str(), value);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Ok(log)
+}
+
+/// Decode the addresses and ports of a sampled Ethernet frame header, as far as it was captured.
+fn decode_ethernet(log: &mut LogEvent, frame: &[u8]) {
+    if frame.len() < 14 {
+        return;
+    }
+    log.insert("packet.destination_mac", format_mac(&frame[0..6]));
+    log.insert("packet.source_mac", format_mac(&frame[6..12]));
+
+    let mut ether_type = u16::from_be_bytes([frame[12], frame[13]]);
+    let mut offset = 14;
+    if ether_type == 0x8100 && frame.len() >= 18 {
+        log.insert(
+            "packet.vlan",
+            u16::from_be_bytes([frame[14], frame[15]]) & 0x0fff,
+        );
+        ether_type = u16::from_be_bytes([frame[16], frame[17]]);
+        offset = 18;
+    }
+    log.insert("packet.ether_type", ether_type);
+
+    let ip = &frame[offset..];
+    let (protocol, transport) = match ether_type {
+        0x0800 if ip.len() >= 20 => {
+            let header_length = ((ip[0] & 0x0f) as usize) * 4;
+            log.insert("packet.source_address", ipv4(&ip[12..16]).to_string());
+            log.insert("packet.destination_address", ipv4(&ip[16..20]).to_string());
+            log.insert("packet.ttl", ip[8]);
+            (ip[9], ip.get(header_length..))
+        }
+        0x86dd if ip.len() >= 40 => {
+            log.insert("packet.source_address", ipv6(&ip[8..24]).to_string());
+            log.insert("packet.destination_address", ipv6(&ip[24..40]).to_string());
+            log.insert("packet.ttl", ip[7]);
+            (ip[6], ip.get(40..))
+        }
+        _ => return,
+    };
+    log.insert("packet.protocol", protocol);
+
+    // Ports are only decoded for TCP and UDP.
+    if let (6 | 17, Some(transport)) = (protocol, transport) {
+        if transport.len() >= 4 {
+            let source_port = u16::from_be_bytes([transport[0], transport[1]]);
+            let destination_port = u16::from_be_bytes([transport[2], transport[3]]);
+            log.insert("packet.source_port", source_port);
+            log.insert("packet.destination_port", destination_port);
+        }
+        if protocol == 6 && transport.len() >= 14 {
+            log.insert("packet.tcp_flags", transport[13]);
+        }
+    }
+}
+
+fn format_mac(bytes: &[u8]) -> String {
+    bytes
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect::<Vec<_>>()
+        .join(":")
+}
+
+fn ipv4(bytes: &[u8]) -> Ipv4Addr {
+    Ipv4Addr::new(bytes[0], bytes[1], bytes[2], bytes[3])
+}
+
+fn ipv6(bytes: &[u8]) -> Ipv6Addr {
+    let mut octets = [0; 16];
+    octets.copy_from_slice(bytes);
+    Ipv6Addr::from(octets)
+}
+
+/// A reader over XDR encoded data, in which every item is padded to a multiple of four bytes.
+struct Reader<'a> {
+    buf: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    const fn new(buf: &'a [u8]) -> Self {
+        Self { buf }
+    }
+
+    fn take(&mut self, length: usize, what: &'static str) -> Result<&'a [u8], ParseError> {
+        let padded = (length + 3) & !3;
+        if self.buf.len() < length {
+            return Err(ParseError::UnexpectedEof { what });
+        }
+        let data = &self.buf[..length];
+        self.buf = &self.buf[padded.min(self.buf.len())..];
+        Ok(data)
+    }
+
+    fn u32(&mut self, what: &'static str) -> Result<u32, ParseError> {
+        let bytes = self.take(4, what)?;
+        Ok(u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    fn u64(&mut self, what: &'static str) -> Result<u64, ParseError> {
+        Ok((u64::from(self.u32(what)?) << 32) | u64::from(self.u32(what)?))
+    }
+
+    fn ipv4(&mut self, what: &'static str) -> Result<String, ParseError> {
+        Ok(ipv4(self.take(4, what)?).to_string())
+    }
+
+    fn ipv6(&mut self, what: &'static str) -> Result<String, ParseError> {
+        Ok(ipv6(self.take(16, what)?).to_string())
+    }
+
+    fn mac(&mut self, what: &'static str) -> Result<String, ParseError> {
+        // MAC addresses are six bytes, padded to eight.
+        Ok(format_mac(self.take(6, what)?))
+    }
+
+    /// Reads an address prefixed with its type, returning `None` for the unknown address type.
+    fn address(&mut self, what: &'static str) -> Result<Option<String>, ParseError> {
+        match self.u32(what)? {
+            0 => Ok(None),
+            1 => self.ipv4(what).map(Some),
+            2 => self.ipv6(what).map(Some),
+            address_type => Err(ParseError::UnsupportedAddressType { address_type }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn push(buf: &mut Vec<u8>, value: u32) {
+        buf.extend_from_slice(&value.to_be_bytes());
+    }
+
+    fn datagram(samples: &[(u32, Vec<u8>)]) -> Vec<u8> {
+        let mut buf = Vec::new();
+        push(&mut buf, 5); // version
+        push(&mut buf, 1); // agent address type
+        buf.extend_from_slice(&[192, 0, 2, 10]);
+        push(&mut buf, 0); // sub agent ID
+        push(&mut buf, 17); // sequence
+        push(&mut buf, 60_000); // uptime
+        push(&mut buf, samples.len() as u32);
+        for (format, data) in samples {
+            push(&mut buf, *format);
+            push(&mut buf, data.len() as u32);
+            buf.extend_from_slice(data);
+        }
+        buf
+    }
+
+    fn raw_packet_record() -> Vec<u8> {
+        let mut frame = vec![
+            0, 0x11, 0x22, 0x33, 0x44, 0x55, // destination MAC
+            0, 0x66, 0x77, 0x88, 0x99, 0xaa, // source MAC
+            0x08, 0x00, // IPv4
+        ];
+        frame.extend_from_slice(&[
+            0x45, 0, 0, 40, 0, 0, 0, 0, 64, 6, 0, 0, // version, IHL, ..., TTL, protocol
+            10, 0, 0, 1, // source
+            10, 0, 0, 2, // destination
+        ]);
+        frame.extend_from_slice(&[
+            0xc3, 0x50, 0, 80, // ports
+            0, 0, 0, 0, 0, 0, 0, 0, 0x50, 0x02, // sequence, ack, offset, flags
+        ]);
+        frame.extend_from_slice(&[0, 0]); // pad to a multiple of four
+
+        let mut record = Vec::new();
+        push(&mut record, 1); // header protocol: Ethernet
+        push(&mut record, 64); // frame length
+        push(&mut record, 4); // stripped
+        push(&mut record, 48); // header length
+        record.extend_from_slice(&frame);
+        record
+    }
+
+    #[test]
+    fn decodes_flow_sample() {
+        let record = raw_packet_record();
+        let mut sample = Vec::new();
+        push(&mut sample, 3); // sample sequence
+        push(&mut sample, 7); // source ID: type 0, index 7
+        push(&mut sample, 1024); // sampling rate
+        push(&mut sample, 4096); // sample pool
+        push(&mut sample, 0); // drops
+        push(&mut sample, 7); // input
+        push(&mut sample, 8); // output
+        push(&mut sample, 1); // records
+        push(&mut sample, RAW_PACKET_HEADER);
+        push(&mut sample, record.len() as u32);
+        sample.extend_from_slice(&record);
+
+        let events = decode(&datagram(&[(FLOW_SAMPLE, sample)])).unwrap();
+        assert_eq!(events.len(), 1);
+
+        let log = &events[0];
+        assert_eq!(log["sample_type"], "flow".into());
+        assert_eq!(log["agent_address"], "192.0.2.10".into());
+        assert_eq!(log["datagram_sequence"], 17.into());
+        assert_eq!(log["source_id_index"], 7.into());
+        assert_eq!(log["sampling_rate"], 1024.into());
+        assert_eq!(log["input_interface"], 7.into());
+        assert_eq!(log["packet.source_mac"], "00:66:77:88:99:aa".into());
+        assert_eq!(log["packet.source_address"], "10.0.0.1".into());
+        assert_eq!(log["packet.destination_address"], "10.0.0.2".into());
+        assert_eq!(log["packet.protocol"], 6.into());
+        assert_eq!(log["packet.source_port"], 50000.into());
+        assert_eq!(log["packet.destination_port"], 80.into());
+        assert_eq!(log["packet.tcp_flags"], 2.into());
+    }
+
+    #[test]
+    fn decodes_counter_sample() {
+        let mut record = Vec::new();
+        push(&mut record, 3); // ifIndex
+        push(&mut record, 6); // ifType
+        record.extend_from_slice(&10_000_000_000u64.to_be_bytes()); // ifSpeed
+        push(&mut record, 1); // ifDirection
+        push(&mut record, 3); // ifStatus
+        record.extend_from_slice(&123_456u64.to_be_bytes()); // ifInOctets
+        for value in 1..=6 {
+            push(&mut record, value);
+        }
+        record.extend_from_slice(&654_321u64.to_be_bytes()); // ifOutOctets
+        for value in 1..=5 {
+            push(&mut record, value);
+        }
+        push(&mut record, 0); // ifPromiscuousMode
+
+        let mut sample = Vec::new();
+        push(&mut sample, 9); // sample sequence
+        push(&mut sample, 3); // source ID
+        push(&mut sample, 1); // records
+        push(&mut sample, GENERIC_INTERFACE_COUNTERS);
+        push(&mut sample, record.len() as u32);
+        sample.extend_from_slice(&record);
+
+        let events = decode(&datagram(&[
+            (COUNTER_SAMPLE, sample),
+            (0xdead, vec![0, 0, 0, 0]),
+        ]))
+        .unwrap();
+        assert_eq!(events.len(), 1);
+
+        let log = &events[0];
+        assert_eq!(log["sample_type"], "counter".into());
+        assert_eq!(log["interface.index"], 3.into());
+        assert_eq!(log["interface.speed"], 10_000_000_000i64.into());
+        assert_eq!(log["interface.in_octets"], 123_456.into());
+        assert_eq!(log["interface.out_octets"], 654_321.into());
+        assert_eq!(log["interface.promiscuous_mode"], false.into());
+    }
+
+    #[test]
+    fn rejects_other_versions() {
+        assert_eq!(
+            decode(&[0, 0, 0, 4]).unwrap_err(),
+            ParseError::UnsupportedVersion { version: 4 }
+        );
+    }
+
+    #[test]
+    fn rejects_truncated_datagrams() {
+        let mut buf = datagram(&[(FLOW_SAMPLE, vec![0; 32])]);
+        buf.truncate(buf.len() - 4);
+        assert_eq!(
+            decode(&buf).unwrap_err(),
+            ParseError::UnexpectedEof { what: "sample" }
+        );
+    }
+}
@@ -0,0 +1,575 @@
+//! Encoding and decoding of SNMP messages, which use the basic encoding rules (BER) of ASN.1.
+//!
+//! Only the subset of BER used by SNMP is supported: definite lengths, and the universal and
+//! application types of SMIv2.
+
+use std::{fmt, str::FromStr};
+
+use snafu::Snafu;
+
+pub const INTEGER: u8 = 0x02;
+pub const OCTET_STRING: u8 = 0x04;
+pub const NULL: u8 = 0x05;
+pub const OBJECT_IDENTIFIER: u8 = 0x06;
+pub const SEQUENCE: u8 = 0x30;
+
+const IP_ADDRESS: u8 = 0x40;
+const COUNTER32: u8 = 0x41;
+const GAUGE32: u8 = 0x42;
+const TIME_TICKS: u8 = 0x43;
+const OPAQUE: u8 = 0x44;
+const COUNTER64: u8 = 0x46;
+const NO_SUCH_OBJECT: u8 = 0x80;
+const NO_SUCH_INSTANCE: u8 = 0x81;
+const END_OF_MIB_VIEW: u8 = 0x82;
+
+pub const GET_REQUEST: u8 = 0xa0;
+pub const GET_NEXT_REQUEST: u8 = 0xa1;
+pub const RESPONSE: u8 = 0xa2;
+pub const GET_BULK_REQUEST: u8 = 0xa5;
+pub const REPORT: u8 = 0xa8;
+
+/// The version number of SNMPv2c in community-based messages.
+const VERSION_2C: i64 = 1;
+
+#[derive(Clone, Debug, Eq, PartialEq, Snafu)]
+pub enum DecodeError {
+    #[snafu(display("Unexpected end of message while reading {}", what))]
+    UnexpectedEof { what: &'static str },
+
+    #[snafu(display("Unexpected tag {:#04x} while reading {}", tag, what))]
+    UnexpectedTag { tag: u8, what: &'static str },
+
+    #[snafu(display("Invalid {}", what))]
+    Invalid { what: &'static str },
+}
+
+/// An object identifier, as its sub-identifiers.
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Oid(pub Vec<u32>);
+
+impl Oid {
+    /// Whether this object is `prefix` or one of its descendants.
+    pub fn starts_with(&self, prefix: &Oid) -> bool {
+        self.0.starts_with(&prefix.0)
+    }
+
+    /// The sub-identifiers after `prefix`, such as the index of an entry of a table.
+    pub fn suffix(&self, prefix: &Oid) -> &[u32] {
+        &self.0[prefix.0.len().min(self.0.len())..]
+    }
+
+    pub fn child(&self, sub_identifier: u32) -> Oid {
+        let mut oid = self.clone();
+        oid.0.push(sub_identifier);
+        oid
+    }
+}
+
+impl FromStr for Oid {
+    type Err = ();
+
+    /// Parses an OID in dotted notation, such as `1.3.6.1.2.1.1.3.0`, with an optional leading dot.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let sub_identifiers = s
+            .strip_prefix('.')
+            .unwrap_or(s)
+            .split('.')
+            .map(|part| part.parse::<u32>().map_err(|_| ()))
+            .collect::<Result<Vec<_>, _>>()?;
+        match sub_identifiers.as_slice() {
+            [first, second, ..] if *first <= 2 && (*first == 2 || *second < 40) => {
+                Ok(Self(sub_identifiers))
+            }
+            _ => Err(()),
+        }
+    }
+}
+
+impl fmt::Display for Oid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_dotted(f, &self.0)
+    }
+}
+
+/// Writes sub-identifiers in dotted notation.
+pub fn write_dotted(f: &mut impl fmt::Write, sub_identifiers: &[u32]) -> fmt::Result {
+    for (i, sub_identifier) in sub_identifiers.iter().enumerate() {
+        if i > 0 {
+            f.write_char('.')?;
+        }
+        write!(f, "{}", sub_identifier)?;
+    }
+    Ok(())
+}
+
+/// The value of a variable binding.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Value {
+    Integer(i64),
+    OctetString(Vec<u8>),
+    Null,
+    ObjectIdentifier(Oid),
+    IpAddress([u8; 4]),
+    Counter32(u32),
+    Gauge32(u32),
+    TimeTicks(u32),
+    Opaque(Vec<u8>),
+    Counter64(u64),
+    NoSuchObject,
+    NoSuchInstance,
+    EndOfMibView,
+}
+
+impl Value {
+    /// Whether this value is an exception, returned in place of the value of an object that
+    /// doesn't exist.
+    pub const fn is_exception(&self) -> bool {
+        matches!(
+            self,
+            Self::NoSuchObject | Self::NoSuchInstance | Self::EndOfMibView
+        )
+    }
+
+    fn encode(&self) -> Vec<u8> {
+        match self {
+            Self::Integer(value) => tlv(INTEGER, &integer_content(*value)),
+            Self::OctetString(bytes) => tlv(OCTET_STRING, bytes),
+            Self::Null => tlv(NULL, &[]),
+            Self::ObjectIdentifier(oid) => oid_tlv(oid),
+            Self::IpAddress(address) => tlv(IP_ADDRESS, address),
+            Self::Counter32(value) => tlv(COUNTER32, &unsigned_content(u64::from(*value))),
+            Self::Gauge32(value) => tlv(GAUGE32, &unsigned_content(u64::from(*value))),
+            Self::TimeTicks(value) => tlv(TIME_TICKS, &unsigned_content(u64::from(*value))),
+            Self::Opaque(bytes) => tlv(OPAQUE, bytes),
+            Self::Counter64(value) => tlv(COUNTER64, &unsigned_content(*value)),
+            Self::NoSuchObject => tlv(NO_SUCH_OBJECT, &[]),
+            Self::NoSuchInstance => tlv(NO_SUCH_INSTANCE, &[]),
+            Self::EndOfMibView => tlv(END_OF_MIB_VIEW, &[]),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct VarBind {
+    pub oid: Oid,
+    pub value: Value,
+}
+
+/// A protocol data unit, the request or response carried by a message.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Pdu {
+    pub pdu_type: u8,
+    pub request_id: i32,
+    /// The error status of responses, or the number of non-repeaters of `GetBulk` requests.
+    pub error_status: i64,
+    /// The error index of responses, or the maximum repetitions of `GetBulk` requests.
+    pub error_index: i64,
+    pub varbinds: Vec<VarBind>,
+}
+
+impl Pdu {
+    /// Creates a request for the given objects, whose values are left null.
+    pub fn request(pdu_type: u8, request_id: i32, oids: &[Oid]) -> Self {
+        Self {
+            pdu_type,
+            request_id,
+            error_status: 0,
+            error_index: 0,
+            varbinds: oids
+                .iter()
+                .map(|oid| VarBind {
+                    oid: oid.clone(),
+                    value: Value::Null,
+                })
+                .collect(),
+        }
+    }
+
+    pub fn encode(&self) -> Vec<u8> {
+        let varbinds = self
+            .varbinds
+            .iter()
+            .map(|varbind| sequence(SEQUENCE, &[oid_tlv(&varbind.oid), varbind.value.encode()]))
+            .collect::<Vec<_>>();
+        sequence(
+            self.pdu_type,
+            &[
+                integer(i64::from(self.request_id)),
+                integer(self.error_status),
+                integer(self.error_index),
+                sequence(SEQUENCE, &varbinds),
+            ],
+        )
+    }
+
+    pub fn decode(reader: &mut Reader<'_>) -> Result<Self, DecodeError> {
+        let (pdu_type, content) = reader.read_tlv("PDU")?;
+        if !matches!(
+            pdu_type,
+            GET_REQUEST | GET_NEXT_REQUEST | RESPONSE | GET_BULK_REQUEST | REPORT
+        ) {
+            return Err(DecodeError::UnexpectedTag {
+                tag: pdu_type,
+                what: "PDU",
+            });
+        }
+        let mut pdu = Reader::new(content);
+        let request_id = i32::try_from(pdu.read_integer("request ID")?)
+            .map_err(|_| DecodeError::Invalid { what: "request ID" })?;
+        let error_status = pdu.read_integer("error status")?;
+        let error_index = pdu.read_integer("error index")?;
+
+        let mut list = Reader::new(pdu.expect(SEQUENCE, "variable bindings")?);
+        let mut varbinds = Vec::new();
+        while !list.is_empty() {
+            let mut varbind = Reader::new(list.expect(SEQUENCE, "variable binding")?);
+            varbinds.push(VarBind {
+                oid: varbind.read_oid("variable binding name")?,
+                value: varbind.read_value()?,
+            });
+        }
+
+        Ok(Self {
+            pdu_type,
+            request_id,
+            error_status,
+            error_index,
+            varbinds,
+        })
+    }
+}
+
+/// Encodes an SNMPv2c message, authenticated by a community.
+pub fn encode_community_message(community: &[u8], pdu: &Pdu) -> Vec<u8> {
+    sequence(
+        SEQUENCE,
+        &[integer(VERSION_2C), octet_string(community), pdu.encode()],
+    )
+}
+
+/// Decodes an SNMPv2c message, returning its PDU.
+pub fn decode_community_message(message: &[u8]) -> Result<Pdu, DecodeError> {
+    let mut reader = Reader::new(Reader::new(message).expect(SEQUENCE, "message")?);
+    if reader.read_integer("version")? != VERSION_2C {
+        return Err(DecodeError::Invalid { what: "version" });
+    }
+    reader.expect(OCTET_STRING, "community")?;
+    Pdu::decode(&mut reader)
+}
+
+/// Encodes a value of type, or tag, `tag`.
+pub fn tlv(tag: u8, content: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(content.len() + 6);
+    out.push(tag);
+    if content.len() < 0x80 {
+        out.push(content.len() as u8);
+    } else {
+        let length = (content.len() as u64).to_be_bytes();
+        let skip = length.iter().take_while(|byte| **byte == 0).count();
+        out.push(0x80 | (length.len() - skip) as u8);
+        out.extend_from_slice(&length[skip..]);
+    }
+    out.extend_from_slice(content);
+    out
+}
+
+/// Encodes a constructed value, such as a sequence, made of already encoded values.
+pub fn sequence(tag: u8, parts: &[Vec<u8>]) -> Vec<u8> {
+    tlv(tag, &parts.concat())
+}
+
+pub fn integer(value: i64) -> Vec<u8> {
+    tlv(INTEGER, &integer_content(value))
+}
+
+pub fn octet_string(bytes: &[u8]) -> Vec<u8> {
+    tlv(OCTET_STRING, bytes)
+}
+
+/// Encodes an integer as the fewest two's complement bytes.
+fn integer_content(value: i64) -> Vec<u8> {
+    let bytes = value.to_be_bytes();
+    let mut skip = 0;
+    while skip < bytes.len() - 1 {
+        let redundant = (bytes[skip] == 0x00 && bytes[skip + 1] & 0x80 == 0)
+            || (bytes[skip] == 0xff && bytes[skip + 1] & 0x80 != 0);
+        if !redundant {
+            break;
+        }
+        skip += 1;
+    }
+    bytes[skip..].to_vec()
+}
+
+/// Encodes an unsigned integer, with a leading zero byte if its high bit is set.
+fn unsigned_content(value: u64) -> Vec<u8> {
+    let bytes = value.to_be_bytes();
+    let skip = bytes[..bytes.len() - 1]
+        .iter()
+        .take_while(|byte| **byte == 0)
+        .count();
+    let mut content = Vec::with_capacity(bytes.len() - skip + 1);
+    if bytes[skip] & 0x80 != 0 {
+        content.push(0);
+    }
+    content.extend_from_slice(&bytes[skip..]);
+    content
+}
+
+fn oid_tlv(oid: &Oid) -> Vec<u8> {
+    let mut content = Vec::new();
+    let (first, rest) = match oid.0.as_slice() {
+        [first, second, rest @ ..] => (first.saturating_mul(40).saturating_add(*second), rest),
+        [first] => (first.saturating_mul(40), &[][..]),
+        [] => (0, &[][..]),
+    };
+    for sub_identifier in std::iter::once(first).chain(rest.iter().copied()) {
+        let mut groups = vec![(sub_identifier & 0x7f) as u8];
+        let mut remaining = sub_identifier >> 7;
+        while remaining > 0 {
+            groups.push((remaining & 0x7f) as u8 | 0x80);
+            remaining >>= 7;
+        }
+        content.extend(groups.iter().rev());
+    }
+    tlv(OBJECT_IDENTIFIER, &content)
+}
+
+/// Reads encoded values from a buffer, in order.
+pub struct Reader<'a> {
+    buf: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    pub const fn new(buf: &'a [u8]) -> Self {
+        Self { buf }
+    }
+
+    pub const fn is_empty(&self) -> bool {
+        self.buf.is_empty()
+    }
+
+    /// Reads the next value, returning its tag and its content.
+    pub fn read_tlv(&mut self, what: &'static str) -> Result<(u8, &'a [u8]), DecodeError> {
+        let eof = DecodeError::UnexpectedEof { what };
+        let (&tag, rest) = self.buf.split_first().ok_or_else(|| eof.clone())?;
+        let (&first, mut rest) = rest.split_first().ok_or_else(|| eof.clone())?;
+
+        let length = if first & 0x80 == 0 {
+            usize::from(first)
+        } else {
+            let count = usize::from(first & 0x7f);
+            if count == 0 || count > 4 || rest.len() < count {
+                return Err(DecodeError::Invalid { what });
+            }
+            let length = rest[..count]
+                .iter()
+                .fold(0, |length, byte| (length << 8) | usize::from(*byte));
+            rest = &rest[count..];
+            length
+        };
+        if rest.len() < length {
+            return Err(eof);
+        }
+
+        let (content, rest) = rest.split_at(length);
+        self.buf = rest;
+        Ok((tag, content))
+    }
+
+    /// Reads the next value, which must be of type `tag`, returning its content.
+    pub fn expect(&mut self, tag: u8, what: &'static str) -> Result<&'a [u8], DecodeError> {
+        match self.read_tlv(what)? {
+            (found, content) if found == tag => Ok(content),
+            (found, _) => Err(DecodeError::UnexpectedTag { tag: found, what }),
+        }
+    }
+
+    pub fn read_integer(&mut self, what: &'static str) -> Result<i64, DecodeError> {
+        decode_integer(self.expect(INTEGER, what)?, what)
+    }
+
+    pub fn read_octet_string(&mut self, what: &'static str) -> Result<&'a [u8], DecodeError> {
+        self.expect(OCTET_STRING, what)
+    }
+
+    pub fn read_oid(&mut self, what: &'static str) -> Result<Oid, DecodeError> {
+        decode_oid(self.expect(OBJECT_IDENTIFIER, what)?, what)
+    }
+
+    fn read_value(&mut self) -> Result<Value, DecodeError> {
+        let what = "value";
+        let (tag, content) = self.read_tlv(what)?;
+        Ok(match tag {
+            INTEGER => Value::Integer(decode_integer(content, what)?),
+            OCTET_STRING => Value::OctetString(content.to_vec()),
+            NULL => Value::Null,
+            OBJECT_IDENTIFIER => Value::ObjectIdentifier(decode_oid(content, what)?),
+            IP_ADDRESS => Value::IpAddress(
+                content
+                    .try_into()
+                    .map_err(|_| DecodeError::Invalid { what: "IP address" })?,
+            ),
+            COUNTER32 => Value::Counter32(decode_unsigned32(content)?),
+            GAUGE32 => Value::Gauge32(decode_unsigned32(content)?),
+            TIME_TICKS => Value::TimeTicks(decode_unsigned32(content)?),
+            OPAQUE => Value::Opaque(content.to_vec()),
+            COUNTER64 => Value::Counter64(decode_unsigned(content, 8)?),
+            NO_SUCH_OBJECT => Value::NoSuchObject,
+            NO_SUCH_INSTANCE => Value::NoSuchInstance,
+            END_OF_MIB_VIEW => Value::EndOfMibView,
+            tag => return Err(DecodeError::UnexpectedTag { tag, what }),
+        })
+    }
+}
+
+fn decode_integer(content: &[u8], what: &'static str) -> Result<i64, DecodeError> {
+    if content.is_empty() || content.len() > 8 {
+        return Err(DecodeError::Invalid { what });
+    }
+    let initial = if content[0] & 0x80 != 0 { -1 } else { 0 };
+    Ok(content
+        .iter()
+        .fold(initial, |value, byte| (value << 8) | i64::from(*byte)))
+}
+
+/// Decodes an unsigned integer of up to `size` bytes, not counting a leading zero byte.
+fn decode_unsigned(content: &[u8], size: usize) -> Result<u64, DecodeError> {
+    let content = match content {
+        [0, rest @ ..] if !rest.is_empty() => rest,
+        content => content,
+    };
+    if content.is_empty() || content.len() > size {
+        return Err(DecodeError::Invalid {
+            what: "unsigned integer",
+        });
+    }
+    Ok(content
+        .iter()
+        .fold(0, |value, byte| (value << 8) | u64::from(*byte)))
+}
+
+fn decode_unsigned32(content: &[u8]) -> Result<u32, DecodeError> {
+    decode_unsigned(content, 4).map(|value| value as u32)
+}
+
+fn decode_oid(content: &[u8], what: &'static str) -> Result<Oid, DecodeError> {
+    let mut sub_identifiers = Vec::new();
+    let mut current: u32 = 0;
+    for (i, byte) in content.iter().enumerate() {
+        if current > u32::MAX >> 7 {
+            return Err(DecodeError::Invalid { what });
+        }
+        current = (current << 7) | u32::from(byte & 0x7f);
+        if byte & 0x80 == 0 {
+            if sub_identifiers.is_empty() {
+                let first = (current / 40).min(2);
+                sub_identifiers.push(first);
+                sub_identifiers.push(current - first * 40);
+            } else {
+                sub_identifiers.push(current);
+            }
+            current = 0;
+        } else if i == content.len() - 1 {
+            return Err(DecodeError::Invalid { what });
+        }
+    }
+    if sub_identifiers.is_empty() {
+        return Err(DecodeError::Invalid { what });
+    }
+    Ok(Oid(sub_identifiers))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn oid(s: &str) -> Oid {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn parses_oids() {
+        assert_eq!(
+            oid(".1.3.6.1.2.1.1.3.0"),
+            Oid(vec![1, 3, 6, 1, 2, 1, 1, 3, 0])
+        );
+        assert_eq!(oid("1.3.6.1").to_string(), "1.3.6.1");
+        assert!("1".parse::<Oid>().is_err());
+        assert!("3.1".parse::<Oid>().is_err());
+        assert!("1.3.x".parse::<Oid>().is_err());
+    }
+
+    #[test]
+    fn encodes_integers() {
+        assert_eq!(integer(0), vec![0x02, 0x01, 0x00]);
+        assert_eq!(integer(127), vec![0x02, 0x01, 0x7f]);
+        assert_eq!(integer(128), vec![0x02, 0x02, 0x00, 0x80]);
+        assert_eq!(integer(-129), vec![0x02, 0x02, 0xff, 0x7f]);
+        assert_eq!(
+            Value::Counter32(u32::MAX).encode(),
+            vec![0x41, 0x05, 0x00, 0xff, 0xff, 0xff, 0xff]
+        );
+    }
+
+    #[test]
+    fn encodes_long_lengths() {
+        let encoded = octet_string(&[0; 300]);
+        assert_eq!(&encoded[..4], &[0x04, 0x82, 0x01, 0x2c]);
+        let mut reader = Reader::new(&encoded);
+        assert_eq!(reader.read_octet_string("test").unwrap().len(), 300);
+        assert!(reader.is_empty());
+    }
+
+    #[test]
+    fn round_trips_messages() {
+        let pdu = Pdu {
+            pdu_type: RESPONSE,
+            request_id: 1234,
+            error_status: 0,
+            error_index: 0,
+            varbinds: vec![
+                VarBind {
+                    oid: oid("1.3.6.1.2.1.1.3.0"),
+                    value: Value::TimeTicks(123_456),
+                },
+                VarBind {
+                    oid: oid("1.3.6.1.2.1.31.1.1.1.6.100000"),
+                    value: Value::Counter64(u64::MAX),
+                },
+                VarBind {
+                    oid: oid("1.3.6.1.2.1.1.5.0"),
+                    value: Value::OctetString(b"switch1".to_vec()),
+                },
+                VarBind {
+                    oid: oid("1.3.6.1.2.1.4.20.1.1.10.0.0.1"),
+                    value: Value::IpAddress([10, 0, 0, 1]),
+                },
+                VarBind {
+                    oid: oid("1.3.6.1.2.1.2.2.1.8.1"),
+                    value: Value::Integer(-1),
+                },
+                VarBind {
+                    oid: oid("1.3.6.1.2.1.2.2.1.8.2"),
+                    value: Value::NoSuchInstance,
+                },
+            ],
+        };
+
+        let message = encode_community_message(b"public", &pdu);
+        assert_eq!(decode_community_message(&message).unwrap(), pdu);
+    }
+
+    #[test]
+    fn rejects_truncated_messages() {
+        let message = encode_community_message(
+            b"public",
+            &Pdu::request(GET_REQUEST, 1, &[oid("1.3.6.1.2.1.1.3.0")]),
+        );
+        assert!(matches!(
+            decode_community_message(&message[..message.len() - 1]),
+            Err(DecodeError::UnexpectedEof { .. })
+        ));
+    }
+}
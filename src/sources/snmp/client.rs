@@ -0,0 +1,366 @@
+//! Requests to an SNMP agent, over UDP.
+
+use std::{
+    io,
+    net::{Ipv4Addr, Ipv6Addr, SocketAddr},
+    time::Duration,
+};
+
+use rand::Rng;
+use snafu::{OptionExt, ResultExt, Snafu};
+use tokio::net::UdpSocket;
+
+use super::{
+    ber::{self, DecodeError, Oid, Pdu, Value, VarBind, GET_BULK_REQUEST, GET_REQUEST, REPORT},
+    usm::{self, Engine, User, UsmError},
+};
+
+/// The largest datagram that can be received.
+const MAX_DATAGRAM_SIZE: usize = 65535;
+
+/// The most objects requested at once by a `Get` request, so that responses stay small.
+const MAX_GET_OIDS: usize = 32;
+
+/// The prefix of the counters of USM, whose reports describe why a request was refused.
+const USM_STATS: [u32; 9] = [1, 3, 6, 1, 6, 3, 15, 1, 1];
+const NOT_IN_TIME_WINDOWS: u32 = 2;
+const UNKNOWN_ENGINE_IDS: u32 = 4;
+
+#[derive(Debug, Snafu)]
+pub enum RequestError {
+    #[snafu(display("Failed to resolve {}: {}", endpoint, source))]
+    Resolve { endpoint: String, source: io::Error },
+
+    #[snafu(display("No address found for {}", endpoint))]
+    NoAddress { endpoint: String },
+
+    #[snafu(display("Failed to send or receive a datagram: {}", source))]
+    Io { source: io::Error },
+
+    #[snafu(display("No response after {} attempts", attempts))]
+    Timeout { attempts: usize },
+
+    #[snafu(display("Invalid response: {}", source))]
+    Decode { source: DecodeError },
+
+    #[snafu(display("{}", source))]
+    Usm { source: UsmError },
+
+    #[snafu(display("No engine ID in the response to discovery"))]
+    Discovery,
+
+    #[snafu(display("Request refused: {}", report))]
+    Report { report: String },
+
+    #[snafu(display("Error status {} for variable binding {}", status, index))]
+    ErrorStatus { status: i64, index: i64 },
+
+    #[snafu(display("Walk of {} returned {}, which doesn't follow {}", root, oid, previous))]
+    OidNotIncreasing { root: Oid, oid: Oid, previous: Oid },
+}
+
+/// How requests are authenticated.
+#[derive(Clone)]
+pub enum Security {
+    /// SNMPv2c, with a community.
+    Community(Vec<u8>),
+
+    /// SNMPv3, with the user-based security model.
+    Usm {
+        user: User,
+        context_name: Vec<u8>,
+        /// The engine of the agent, discovered by the first request.
+        engine: Option<Engine>,
+    },
+}
+
+/// A client of an agent, whose socket is connected by the first request.
+pub struct Client {
+    endpoint: String,
+    socket: Option<UdpSocket>,
+    security: Security,
+    timeout: Duration,
+    retries: usize,
+    next_id: i32,
+    next_salt: u64,
+    bytes_received: usize,
+}
+
+impl Client {
+    pub fn new(endpoint: String, security: Security, timeout: Duration, retries: usize) -> Self {
+        let mut rng = rand::thread_rng();
+        Self {
+            endpoint,
+            socket: None,
+            security,
+            timeout,
+            retries,
+            next_id: rng.gen_range(0..i32::MAX),
+            next_salt: rng.gen(),
+            bytes_received: 0,
+        }
+    }
+
+    /// Returns the number of bytes received since the last call.
+    pub fn take_bytes_received(&mut self) -> usize {
+        std::mem::take(&mut self.bytes_received)
+    }
+
+    /// Gets the values of the given objects, which are instances such as `1.3.6.1.2.1.1.3.0`.
+    pub async fn get(&mut self, oids: &[Oid]) -> Result<Vec<VarBind>, RequestError> {
+        let mut varbinds = Vec::with_capacity(oids.len());
+        for oids in oids.chunks(MAX_GET_OIDS) {
+            let response = self.request(Pdu::request(GET_REQUEST, 0, oids)).await?;
+            varbinds.extend(response.varbinds);
+        }
+        Ok(varbinds)
+    }
+
+    /// Gets the values of the descendants of `root`, such as the entries of a column of a table,
+    /// with `GetBulk` requests of up to `max_repetitions` objects each.
+    pub async fn walk(
+        &mut self,
+        root: &Oid,
+        max_repetitions: u32,
+    ) -> Result<Vec<VarBind>, RequestError> {
+        let mut varbinds = Vec::new();
+        let mut previous = root.clone();
+        loop {
+            let mut request = Pdu::request(GET_BULK_REQUEST, 0, &[previous.clone()]);
+            request.error_index = i64::from(max_repetitions);
+            let response = self.request(request).await?;
+            if response.varbinds.is_empty() {
+                return Ok(varbinds);
+            }
+
+            for varbind in response.varbinds {
+                if !varbind.oid.starts_with(root) || varbind.value == Value::EndOfMibView {
+                    return Ok(varbinds);
+                }
+                // Agents must return objects in order, or walks would never end.
+                if varbind.oid <= previous {
+                    return Err(RequestError::OidNotIncreasing {
+                        root: root.clone(),
+                        oid: varbind.oid,
+                        previous,
+                    });
+                }
+                previous = varbind.oid.clone();
+                varbinds.push(varbind);
+            }
+        }
+    }
+
+    async fn request(&mut self, pdu: Pdu) -> Result<Pdu, RequestError> {
+        if self.socket.is_none() {
+            self.socket = Some(connect(&self.endpoint).await?);
+        }
+        let Self {
+            socket,
+            security,
+            timeout,
+            retries,
+            next_id,
+            next_salt,
+            bytes_received,
+            ..
+        } = self;
+        let mut exchange = Exchange {
+            socket: socket.as_ref().expect("socket is connected"),
+            timeout: *timeout,
+            attempts: *retries + 1,
+            bytes_received: 0,
+        };
+
+        let response = Self::send_request(&mut exchange, security, next_id, next_salt, pdu).await;
+        *bytes_received += exchange.bytes_received;
+        let response = response?;
+
+        if response.error_status != 0 {
+            return Err(RequestError::ErrorStatus {
+                status: response.error_status,
+                index: response.error_index,
+            });
+        }
+        Ok(response)
+    }
+
+    async fn send_request(
+        exchange: &mut Exchange<'_>,
+        security: &mut Security,
+        next_id: &mut i32,
+        next_salt: &mut u64,
+        mut pdu: Pdu,
+    ) -> Result<Pdu, RequestError> {
+        Ok(match security {
+            Security::Community(community) => {
+                let request_id = increment(next_id);
+                pdu.request_id = request_id;
+                let message = ber::encode_community_message(community, &pdu);
+                exchange
+                    .run(&message, |datagram| {
+                        let response =
+                            ber::decode_community_message(datagram).context(DecodeSnafu)?;
+                        Ok((response.request_id == request_id).then_some(response))
+                    })
+                    .await?
+            }
+            Security::Usm {
+                user,
+                context_name,
+                engine,
+            } => {
+                let mut resynced = false;
+                loop {
+                    if engine.is_none() {
+                        *engine = Some(exchange.discover(user, increment(next_id)).await?);
+                    }
+
+                    let msg_id = increment(next_id);
+                    pdu.request_id = msg_id;
+                    *next_salt = next_salt.wrapping_add(1);
+                    let message = usm::encode(
+                        user,
+                        engine.as_ref(),
+                        msg_id,
+                        context_name,
+                        &pdu,
+                        *next_salt,
+                    )
+                    .context(UsmSnafu)?;
+                    let response = exchange
+                        .run(&message, |datagram| {
+                            let response =
+                                usm::decode(user, engine.as_ref(), datagram).context(UsmSnafu)?;
+                            Ok((response.msg_id == msg_id).then_some(response))
+                        })
+                        .await?;
+                    if response.pdu.pdu_type != REPORT {
+                        break response.pdu;
+                    }
+
+                    // Engines whose time drifted, or which restarted with a new ID, are synced
+                    // again once per request.
+                    let counter = response
+                        .pdu
+                        .varbinds
+                        .first()
+                        .map(|varbind| &varbind.oid.0[..])
+                        .and_then(|oid| oid.strip_prefix(&USM_STATS[..]))
+                        .and_then(|suffix| suffix.first().copied());
+                    match counter {
+                        Some(NOT_IN_TIME_WINDOWS) if !resynced => {
+                            if let Some(engine) = engine {
+                                engine.sync(response.boots, response.time);
+                            }
+                        }
+                        Some(UNKNOWN_ENGINE_IDS) if !resynced => *engine = None,
+                        _ => return Err(report_error(&response.pdu)),
+                    }
+                    resynced = true;
+                }
+            }
+        })
+    }
+}
+
+/// Increments a request ID, returning its previous value. IDs stay positive.
+fn increment(id: &mut i32) -> i32 {
+    let current = *id;
+    *id = current.checked_add(1).unwrap_or(0);
+    current
+}
+
+fn report_error(report: &Pdu) -> RequestError {
+    let report = match report.varbinds.first() {
+        Some(varbind) => match varbind.oid.0.strip_prefix(&USM_STATS[..]) {
+            Some([1, 0]) => "unsupported security level".to_owned(),
+            Some([2, 0]) => "not in time window".to_owned(),
+            Some([3, 0]) => "unknown user name".to_owned(),
+            Some([4, 0]) => "unknown engine ID".to_owned(),
+            Some([5, 0]) => "wrong digest, the authentication password may be wrong".to_owned(),
+            Some([6, 0]) => "decryption error, the privacy password may be wrong".to_owned(),
+            _ => format!("report {}", varbind.oid),
+        },
+        None => "empty report".to_owned(),
+    };
+    RequestError::Report { report }
+}
+
+async fn connect(endpoint: &str) -> Result<UdpSocket, RequestError> {
+    let address = tokio::net::lookup_host(endpoint)
+        .await
+        .context(ResolveSnafu { endpoint })?
+        .next()
+        .context(NoAddressSnafu { endpoint })?;
+    let local: SocketAddr = if address.is_ipv4() {
+        (Ipv4Addr::UNSPECIFIED, 0).into()
+    } else {
+        (Ipv6Addr::UNSPECIFIED, 0).into()
+    };
+    let socket = UdpSocket::bind(local).await.context(IoSnafu)?;
+    socket.connect(address).await.context(IoSnafu)?;
+    Ok(socket)
+}
+
+/// Sends a message, and waits for the response to it.
+struct Exchange<'a> {
+    socket: &'a UdpSocket,
+    timeout: Duration,
+    attempts: usize,
+    bytes_received: usize,
+}
+
+impl<'a> Exchange<'a> {
+    /// Sends `message` until a datagram accepted by `response` is received, or the attempts are
+    /// exhausted. Datagrams for which `response` returns `None`, such as late responses to
+    /// previous requests, are skipped.
+    async fn run<T>(
+        &mut self,
+        message: &[u8],
+        mut response: impl FnMut(&[u8]) -> Result<Option<T>, RequestError>,
+    ) -> Result<T, RequestError> {
+        let mut buf = vec![0; MAX_DATAGRAM_SIZE];
+        for _ in 0..self.attempts {
+            self.socket.send(message).await.context(IoSnafu)?;
+
+            let deadline = tokio::time::Instant::now() + self.timeout;
+            loop {
+                let received = tokio::time::timeout_at(deadline, self.socket.recv(&mut buf)).await;
+                match received {
+                    Ok(received) => {
+                        let size = received.context(IoSnafu)?;
+                        self.bytes_received += size;
+                        if let Some(response) = response(&buf[..size])? {
+                            return Ok(response);
+                        }
+                    }
+                    Err(_) => break,
+                }
+            }
+        }
+        Err(RequestError::Timeout {
+            attempts: self.attempts,
+        })
+    }
+
+    /// Discovers the engine of the agent, whose ID and time authenticated requests must carry.
+    async fn discover(&mut self, user: &User, msg_id: i32) -> Result<Engine, RequestError> {
+        let request = Pdu::request(GET_REQUEST, msg_id, &[]);
+        let message = usm::encode(user, None, msg_id, &[], &request, 0).context(UsmSnafu)?;
+        let response = self
+            .run(&message, |datagram| {
+                let response = usm::decode(user, None, datagram).context(UsmSnafu)?;
+                Ok((response.msg_id == msg_id).then_some(response))
+            })
+            .await?;
+        if response.engine_id.is_empty() {
+            return Err(RequestError::Discovery);
+        }
+        Engine::new(user, response.engine_id, response.boots, response.time).map_err(|source| {
+            RequestError::Usm {
+                source: UsmError::Crypto { source },
+            }
+        })
+    }
+}
@@ -0,0 +1,231 @@
+//! Objects of common MIBs, which metrics can refer to by name rather than by OID.
+
+use super::IndexType;
+
+pub struct MibObject {
+    pub module: &'static str,
+    pub name: &'static str,
+    pub oid: &'static str,
+    /// The indexes of the table the object is a column of, or nothing for scalar objects.
+    pub indexes: &'static [(&'static str, IndexType)],
+}
+
+impl MibObject {
+    /// Whether the object is a scalar, whose only instance is `<oid>.0`.
+    pub const fn is_scalar(&self) -> bool {
+        self.indexes.is_empty()
+    }
+}
+
+const IF_INDEX: &[(&str, IndexType)] = &[("ifIndex", IndexType::Integer)];
+const HR_STORAGE_INDEX: &[(&str, IndexType)] = &[("hrStorageIndex", IndexType::Integer)];
+const HR_DEVICE_INDEX: &[(&str, IndexType)] = &[("hrDeviceIndex", IndexType::Integer)];
+
+const fn scalar(module: &'static str, name: &'static str, oid: &'static str) -> MibObject {
+    MibObject {
+        module,
+        name,
+        oid,
+        indexes: &[],
+    }
+}
+
+const fn column(
+    module: &'static str,
+    name: &'static str,
+    oid: &'static str,
+    indexes: &'static [(&'static str, IndexType)],
+) -> MibObject {
+    MibObject {
+        module,
+        name,
+        oid,
+        indexes,
+    }
+}
+
+const SNMPV2_MIB: &str = "SNMPv2-MIB";
+const IF_MIB: &str = "IF-MIB";
+const HOST_RESOURCES_MIB: &str = "HOST-RESOURCES-MIB";
+
+static OBJECTS: &[MibObject] = &[
+    scalar(SNMPV2_MIB, "sysDescr", "1.3.6.1.2.1.1.1"),
+    scalar(SNMPV2_MIB, "sysObjectID", "1.3.6.1.2.1.1.2"),
+    scalar(SNMPV2_MIB, "sysUpTime", "1.3.6.1.2.1.1.3"),
+    scalar(SNMPV2_MIB, "sysContact", "1.3.6.1.2.1.1.4"),
+    scalar(SNMPV2_MIB, "sysName", "1.3.6.1.2.1.1.5"),
+    scalar(SNMPV2_MIB, "sysLocation", "1.3.6.1.2.1.1.6"),
+    scalar(IF_MIB, "ifNumber", "1.3.6.1.2.1.2.1"),
+    column(IF_MIB, "ifIndex", "1.3.6.1.2.1.2.2.1.1", IF_INDEX),
+    column(IF_MIB, "ifDescr", "1.3.6.1.2.1.2.2.1.2", IF_INDEX),
+    column(IF_MIB, "ifType", "1.3.6.1.2.1.2.2.1.3", IF_INDEX),
+    column(IF_MIB, "ifMtu", "1.3.6.1.2.1.2.2.1.4", IF_INDEX),
+    column(IF_MIB, "ifSpeed", "1.3.6.1.2.1.2.2.1.5", IF_INDEX),
+    column(IF_MIB, "ifPhysAddress", "1.3.6.1.2.1.2.2.1.6", IF_INDEX),
+    column(IF_MIB, "ifAdminStatus", "1.3.6.1.2.1.2.2.1.7", IF_INDEX),
+    column(IF_MIB, "ifOperStatus", "1.3.6.1.2.1.2.2.1.8", IF_INDEX),
+    column(IF_MIB, "ifLastChange", "1.3.6.1.2.1.2.2.1.9", IF_INDEX),
+    column(IF_MIB, "ifInOctets", "1.3.6.1.2.1.2.2.1.10", IF_INDEX),
+    column(IF_MIB, "ifInUcastPkts", "1.3.6.1.2.1.2.2.1.11", IF_INDEX),
+    column(IF_MIB, "ifInDiscards", "1.3.6.1.2.1.2.2.1.13", IF_INDEX),
+    column(IF_MIB, "ifInErrors", "1.3.6.1.2.1.2.2.1.14", IF_INDEX),
+    column(
+        IF_MIB,
+        "ifInUnknownProtos",
+        "1.3.6.1.2.1.2.2.1.15",
+        IF_INDEX,
+    ),
+    column(IF_MIB, "ifOutOctets", "1.3.6.1.2.1.2.2.1.16", IF_INDEX),
+    column(IF_MIB, "ifOutUcastPkts", "1.3.6.1.2.1.2.2.1.17", IF_INDEX),
+    column(IF_MIB, "ifOutDiscards", "1.3.6.1.2.1.2.2.1.19", IF_INDEX),
+    column(IF_MIB, "ifOutErrors", "1.3.6.1.2.1.2.2.1.20", IF_INDEX),
+    column(IF_MIB, "ifName", "1.3.6.1.2.1.31.1.1.1.1", IF_INDEX),
+    column(
+        IF_MIB,
+        "ifInMulticastPkts",
+        "1.3.6.1.2.1.31.1.1.1.2",
+        IF_INDEX,
+    ),
+    column(
+        IF_MIB,
+        "ifInBroadcastPkts",
+        "1.3.6.1.2.1.31.1.1.1.3",
+        IF_INDEX,
+    ),
+    column(
+        IF_MIB,
+        "ifOutMulticastPkts",
+        "1.3.6.1.2.1.31.1.1.1.4",
+        IF_INDEX,
+    ),
+    column(
+        IF_MIB,
+        "ifOutBroadcastPkts",
+        "1.3.6.1.2.1.31.1.1.1.5",
+        IF_INDEX,
+    ),
+    column(IF_MIB, "ifHCInOctets", "1.3.6.1.2.1.31.1.1.1.6", IF_INDEX),
+    column(
+        IF_MIB,
+        "ifHCInUcastPkts",
+        "1.3.6.1.2.1.31.1.1.1.7",
+        IF_INDEX,
+    ),
+    column(
+        IF_MIB,
+        "ifHCInMulticastPkts",
+        "1.3.6.1.2.1.31.1.1.1.8",
+        IF_INDEX,
+    ),
+    column(
+        IF_MIB,
+        "ifHCInBroadcastPkts",
+        "1.3.6.1.2.1.31.1.1.1.9",
+        IF_INDEX,
+    ),
+    column(IF_MIB, "ifHCOutOctets", "1.3.6.1.2.1.31.1.1.1.10", IF_INDEX),
+    column(
+        IF_MIB,
+        "ifHCOutUcastPkts",
+        "1.3.6.1.2.1.31.1.1.1.11",
+        IF_INDEX,
+    ),
+    column(
+        IF_MIB,
+        "ifHCOutMulticastPkts",
+        "1.3.6.1.2.1.31.1.1.1.12",
+        IF_INDEX,
+    ),
+    column(
+        IF_MIB,
+        "ifHCOutBroadcastPkts",
+        "1.3.6.1.2.1.31.1.1.1.13",
+        IF_INDEX,
+    ),
+    column(IF_MIB, "ifHighSpeed", "1.3.6.1.2.1.31.1.1.1.15", IF_INDEX),
+    column(IF_MIB, "ifAlias", "1.3.6.1.2.1.31.1.1.1.18", IF_INDEX),
+    scalar(HOST_RESOURCES_MIB, "hrSystemUptime", "1.3.6.1.2.1.25.1.1"),
+    scalar(
+        HOST_RESOURCES_MIB,
+        "hrSystemProcesses",
+        "1.3.6.1.2.1.25.1.6",
+    ),
+    scalar(HOST_RESOURCES_MIB, "hrMemorySize", "1.3.6.1.2.1.25.2.2"),
+    column(
+        HOST_RESOURCES_MIB,
+        "hrStorageType",
+        "1.3.6.1.2.1.25.2.3.1.2",
+        HR_STORAGE_INDEX,
+    ),
+    column(
+        HOST_RESOURCES_MIB,
+        "hrStorageDescr",
+        "1.3.6.1.2.1.25.2.3.1.3",
+        HR_STORAGE_INDEX,
+    ),
+    column(
+        HOST_RESOURCES_MIB,
+        "hrStorageAllocationUnits",
+        "1.3.6.1.2.1.25.2.3.1.4",
+        HR_STORAGE_INDEX,
+    ),
+    column(
+        HOST_RESOURCES_MIB,
+        "hrStorageSize",
+        "1.3.6.1.2.1.25.2.3.1.5",
+        HR_STORAGE_INDEX,
+    ),
+    column(
+        HOST_RESOURCES_MIB,
+        "hrStorageUsed",
+        "1.3.6.1.2.1.25.2.3.1.6",
+        HR_STORAGE_INDEX,
+    ),
+    column(
+        HOST_RESOURCES_MIB,
+        "hrStorageAllocationFailures",
+        "1.3.6.1.2.1.25.2.3.1.7",
+        HR_STORAGE_INDEX,
+    ),
+    column(
+        HOST_RESOURCES_MIB,
+        "hrProcessorLoad",
+        "1.3.6.1.2.1.25.3.3.1.2",
+        HR_DEVICE_INDEX,
+    ),
+];
+
+/// Finds an object by its name, optionally qualified by its module such as in
+/// `IF-MIB::ifHCInOctets`.
+pub fn find(name: &str) -> Option<&'static MibObject> {
+    let (module, name) = match name.split_once("::") {
+        Some((module, name)) => (Some(module), name),
+        None => (None, name),
+    };
+    OBJECTS
+        .iter()
+        .find(|object| object.name == name && module.map_or(true, |module| module == object.module))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sources::snmp::ber::Oid;
+
+    #[test]
+    fn objects_have_valid_oids() {
+        for object in OBJECTS {
+            assert!(object.oid.parse::<Oid>().is_ok(), "{}", object.name);
+        }
+    }
+
+    #[test]
+    fn finds_objects() {
+        assert_eq!(
+            find("IF-MIB::ifHCInOctets").unwrap().oid,
+            "1.3.6.1.2.1.31.1.1.1.6"
+        );
+        assert_eq!(find("sysUpTime").unwrap().oid, "1.3.6.1.2.1.1.3");
+        assert!(find("SNMPv2-MIB::ifName").is_none());
+    }
+}
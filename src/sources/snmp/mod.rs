@@ -0,0 +1,1024 @@
+use std::{
+    collections::{HashMap, HashSet},
+    net::{IpAddr, Ipv4Addr, SocketAddr},
+    time::{Duration, Instant},
+};
+
+use chrono::{DateTime, Utc};
+use futures::{future::join_all, StreamExt};
+use snafu::{OptionExt, Snafu};
+use tokio::time;
+use tokio_stream::wrappers::IntervalStream;
+use vector_common::sensitive_string::SensitiveString;
+use vector_config::configurable_component;
+use vector_core::{config::LogNamespace, ByteSizeOf};
+
+use crate::{
+    config::{DataType, GenerateConfig, Output, SourceConfig, SourceContext},
+    event::metric::{Metric, MetricKind, MetricTags, MetricValue},
+    internal_events::{
+        CollectionCompleted, EndpointBytesReceived, SnmpEventsReceived, SnmpRequestError,
+        StreamClosedError,
+    },
+};
+
+pub mod ber;
+pub mod client;
+pub mod mib;
+pub mod usm;
+
+use ber::{Oid, Value, VarBind};
+use client::{Client, RequestError, Security};
+use mib::MibObject;
+
+/// The port agents listen on, unless the endpoint sets one.
+const DEFAULT_PORT: u16 = 161;
+
+/// The shortest password RFC 3414 allows.
+const MIN_PASSWORD_LENGTH: usize = 8;
+
+#[derive(Debug, Snafu)]
+enum SnmpBuildError {
+    #[snafu(display(
+        "Invalid OID {:?}: expected a numeric OID such as `1.3.6.1.2.1.1.3.0`, or the name of a known object such as `IF-MIB::ifHCInOctets`",
+        oid
+    ))]
+    InvalidOid { oid: String },
+
+    #[snafu(display("The metric of OID {:?} must have a `name`", oid))]
+    MissingName { oid: String },
+
+    #[snafu(display("`timeout_secs` must be a positive number of seconds"))]
+    InvalidTimeout,
+
+    #[snafu(display("`{}` must be at least {} characters long", field, MIN_PASSWORD_LENGTH))]
+    PasswordTooShort { field: &'static str },
+
+    #[snafu(display("`privacy_password` requires `auth_password`"))]
+    PrivacyWithoutAuth,
+
+    #[snafu(display("Failed to derive the keys of the user: {}", source))]
+    DeriveKeys { source: openssl::error::ErrorStack },
+}
+
+/// Configuration for the `snmp` source.
+#[configurable_component(source("snmp"))]
+#[configurable(metadata(status = "alpha"))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct SnmpConfig {
+    /// A list of agents to poll.
+    ///
+    /// Each endpoint is a host name or an IP address, optionally followed by a port. The port
+    /// defaults to `161`.
+    endpoints: Vec<String>,
+
+    /// The interval between polls, in seconds.
+    #[serde(default = "default_scrape_interval_secs")]
+    scrape_interval_secs: u64,
+
+    /// The time to wait for the response to a request, in seconds, before sending it again.
+    #[serde(default = "default_timeout_secs")]
+    timeout_secs: f64,
+
+    /// The number of times a request is sent again when no response is received.
+    #[serde(default = "default_retries")]
+    retries: usize,
+
+    /// The most objects returned by each `GetBulk` request of walks.
+    #[serde(default = "default_max_repetitions")]
+    max_repetitions: u32,
+
+    /// Overrides the default namespace for the metrics emitted by the source.
+    ///
+    /// If set to an empty string, no namespace is added to the metrics.
+    ///
+    /// By default, `snmp` is used.
+    #[serde(default = "default_namespace")]
+    namespace: String,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    auth: SnmpAuth,
+
+    /// The metrics polled from each agent.
+    metrics: Vec<SnmpMetricConfig>,
+}
+
+const fn default_scrape_interval_secs() -> u64 {
+    15
+}
+
+const fn default_timeout_secs() -> f64 {
+    5.0
+}
+
+const fn default_retries() -> usize {
+    1
+}
+
+const fn default_max_repetitions() -> u32 {
+    25
+}
+
+fn default_namespace() -> String {
+    "snmp".to_string()
+}
+
+fn default_community() -> SensitiveString {
+    "public".to_string().into()
+}
+
+/// The version of SNMP, and how requests are authenticated.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(tag = "version", rename_all = "snake_case", deny_unknown_fields)]
+pub enum SnmpAuth {
+    /// SNMPv2c, whose requests carry a community.
+    V2c {
+        /// The community of the requests.
+        #[serde(default = "default_community")]
+        community: SensitiveString,
+    },
+
+    /// SNMPv3, whose requests are authenticated, and optionally encrypted, by the user-based
+    /// security model.
+    V3 {
+        /// The name of the user.
+        username: String,
+
+        #[configurable(derived)]
+        #[serde(default)]
+        auth_protocol: AuthProtocol,
+
+        /// The password authenticating the requests.
+        ///
+        /// Requests are neither authenticated nor encrypted if unset.
+        auth_password: Option<SensitiveString>,
+
+        #[configurable(derived)]
+        #[serde(default)]
+        privacy_protocol: PrivacyProtocol,
+
+        /// The password encrypting the requests, which requires `auth_password`.
+        ///
+        /// Requests are not encrypted if unset.
+        privacy_password: Option<SensitiveString>,
+
+        /// The context of the requests, for agents exposing several contexts.
+        #[serde(default)]
+        context_name: String,
+    },
+}
+
+impl Default for SnmpAuth {
+    fn default() -> Self {
+        Self::V2c {
+            community: default_community(),
+        }
+    }
+}
+
+impl SnmpAuth {
+    fn security(&self) -> Result<Security, SnmpBuildError> {
+        match self {
+            Self::V2c { community } => {
+                Ok(Security::Community(community.inner().as_bytes().to_vec()))
+            }
+            Self::V3 {
+                username,
+                auth_protocol,
+                auth_password,
+                privacy_protocol,
+                privacy_password,
+                context_name,
+            } => {
+                let auth_password = auth_password
+                    .as_ref()
+                    .map(|password| check_password("auth_password", password))
+                    .transpose()?;
+                let privacy_password = privacy_password
+                    .as_ref()
+                    .map(|password| check_password("privacy_password", password))
+                    .transpose()?;
+                if privacy_password.is_some() && auth_password.is_none() {
+                    return Err(SnmpBuildError::PrivacyWithoutAuth);
+                }
+
+                let user = usm::User::new(
+                    username,
+                    auth_password.map(|password| (*auth_protocol, password)),
+                    privacy_password.map(|password| (*privacy_protocol, password)),
+                )
+                .map_err(|source| SnmpBuildError::DeriveKeys { source })?;
+                Ok(Security::Usm {
+                    user,
+                    context_name: context_name.as_bytes().to_vec(),
+                    engine: None,
+                })
+            }
+        }
+    }
+}
+
+fn check_password<'a>(
+    field: &'static str,
+    password: &'a SensitiveString,
+) -> Result<&'a str, SnmpBuildError> {
+    let password = password.inner();
+    if password.len() < MIN_PASSWORD_LENGTH {
+        return Err(SnmpBuildError::PasswordTooShort { field });
+    }
+    Ok(password)
+}
+
+/// The protocol authenticating SNMPv3 requests.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum AuthProtocol {
+    /// HMAC-MD5-96.
+    Md5,
+
+    /// HMAC-SHA-96.
+    Sha,
+}
+
+impl Default for AuthProtocol {
+    fn default() -> Self {
+        Self::Sha
+    }
+}
+
+/// The protocol encrypting SNMPv3 requests.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum PrivacyProtocol {
+    /// DES in CBC mode.
+    Des,
+
+    /// AES-128 in CFB mode.
+    Aes,
+}
+
+impl Default for PrivacyProtocol {
+    fn default() -> Self {
+        Self::Aes
+    }
+}
+
+/// A metric polled from each agent.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct SnmpMetricConfig {
+    /// The object polled.
+    ///
+    /// Either a numeric OID, such as `1.3.6.1.2.1.1.3.0`, or the name of an object of a common
+    /// MIB, optionally qualified by its module, such as `sysUpTime` or `IF-MIB::ifHCInOctets`.
+    /// Named scalars are polled with a `Get` of their instance, and named columns of tables are
+    /// walked.
+    oid: String,
+
+    /// The name of the metric.
+    ///
+    /// Defaults to the name of the object, for named objects.
+    name: Option<String>,
+
+    /// Whether the descendants of a numeric OID, such as the entries of a column of a table, are
+    /// walked rather than the OID polled with a `Get`.
+    #[serde(default)]
+    walk: bool,
+
+    #[configurable(derived)]
+    kind: Option<SnmpMetricKind>,
+
+    /// The labels decoded from the index of each walked object, in order.
+    ///
+    /// Defaults to the indexes of the table of named columns, and otherwise to an `index` label
+    /// with the whole index, such as `1` or `10.0.0.1`.
+    #[serde(default)]
+    indexes: Vec<SnmpIndexConfig>,
+
+    /// Labels set to the value of other columns of the table at the index of each walked object,
+    /// such as `ifName` for the counters of interfaces.
+    #[serde(default)]
+    lookups: Vec<SnmpLookupConfig>,
+}
+
+/// The type of a metric.
+///
+/// By default, `Counter32` and `Counter64` objects are counters, and other objects are gauges.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum SnmpMetricKind {
+    /// A counter.
+    Counter,
+
+    /// A gauge.
+    Gauge,
+}
+
+/// A label decoded from the index of walked objects.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct SnmpIndexConfig {
+    /// The name of the label.
+    label: String,
+
+    #[configurable(derived)]
+    #[serde(rename = "type", default)]
+    index_type: IndexType,
+}
+
+/// How an index is encoded in the sub-identifiers of an OID.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum IndexType {
+    /// A single integer, such as the `ifIndex` of interfaces.
+    Integer,
+
+    /// An IPv4 address, as four sub-identifiers.
+    IpAddress,
+
+    /// A string, as its length followed by each of its bytes.
+    String,
+}
+
+impl Default for IndexType {
+    fn default() -> Self {
+        Self::Integer
+    }
+}
+
+/// A label set to the value of another column of the table.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct SnmpLookupConfig {
+    /// The name of the label.
+    label: String,
+
+    /// The column whose value the label is set to, as a numeric OID or the name of an object.
+    oid: String,
+}
+
+impl GenerateConfig for SnmpConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"endpoints = ["127.0.0.1:161"]
+
+[auth]
+version = "v2c"
+community = "public"
+
+[[metrics]]
+oid = "sysUpTime"
+
+[[metrics]]
+oid = "IF-MIB::ifHCInOctets"
+lookups = [{ label = "ifName", oid = "IF-MIB::ifName" }]"#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+impl SourceConfig for SnmpConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
+        let plan = Plan::new(&self.metrics)?;
+        if !(self.timeout_secs.is_finite() && self.timeout_secs > 0.0) {
+            return Err(SnmpBuildError::InvalidTimeout.into());
+        }
+        let timeout = Duration::from_secs_f64(self.timeout_secs);
+        let security = self.auth.security()?;
+
+        let namespace = Some(self.namespace.clone()).filter(|namespace| !namespace.is_empty());
+        let mut agents = self
+            .endpoints
+            .iter()
+            .map(|endpoint| Agent {
+                endpoint: endpoint.clone(),
+                client: Client::new(
+                    endpoint_address(endpoint),
+                    security.clone(),
+                    timeout,
+                    self.retries,
+                ),
+                namespace: namespace.clone(),
+            })
+            .collect::<Vec<_>>();
+        let max_repetitions = self.max_repetitions.max(1);
+
+        let duration = time::Duration::from_secs(self.scrape_interval_secs);
+        let shutdown = cx.shutdown;
+        let mut out = cx.out;
+        Ok(Box::pin(async move {
+            let mut interval = IntervalStream::new(time::interval(duration)).take_until(shutdown);
+            while interval.next().await.is_some() {
+                let start = Instant::now();
+                let metrics = join_all(
+                    agents
+                        .iter_mut()
+                        .map(|agent| agent.collect(&plan, max_repetitions)),
+                )
+                .await;
+                emit!(CollectionCompleted {
+                    start,
+                    end: Instant::now()
+                });
+
+                let metrics = metrics.into_iter().flatten().collect::<Vec<_>>();
+                let count = metrics.len();
+                if let Err(error) = out.send_batch(metrics).await {
+                    emit!(StreamClosedError { error, count });
+                    return Err(());
+                }
+            }
+
+            Ok(())
+        }))
+    }
+
+    fn outputs(&self, _global_log_namespace: LogNamespace) -> Vec<Output> {
+        vec![Output::default(DataType::Metric)]
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        false
+    }
+}
+
+/// The address of an agent, with the default port unless the endpoint sets one.
+fn endpoint_address(endpoint: &str) -> String {
+    if endpoint.parse::<SocketAddr>().is_ok() {
+        return endpoint.to_owned();
+    }
+    if let Ok(ip) = endpoint.parse::<IpAddr>() {
+        return SocketAddr::new(ip, DEFAULT_PORT).to_string();
+    }
+    match endpoint.rsplit_once(':') {
+        Some((_, port)) if port.parse::<u16>().is_ok() => endpoint.to_owned(),
+        _ => format!("{}:{}", endpoint, DEFAULT_PORT),
+    }
+}
+
+/// The requests polling the metrics, resolved from their configuration.
+struct Plan {
+    metrics: Vec<MetricPlan>,
+    /// The instances polled with `Get` requests.
+    gets: Vec<Oid>,
+    /// The subtrees walked, including the columns of lookups.
+    walks: Vec<Oid>,
+}
+
+struct MetricPlan {
+    name: String,
+    oid: Oid,
+    walk: bool,
+    kind: Option<SnmpMetricKind>,
+    indexes: Vec<(String, IndexType)>,
+    lookups: Vec<(String, Oid)>,
+}
+
+impl Plan {
+    fn new(configs: &[SnmpMetricConfig]) -> Result<Self, SnmpBuildError> {
+        let metrics = configs
+            .iter()
+            .map(MetricPlan::new)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut gets = Vec::new();
+        let mut walks = Vec::new();
+        let mut seen = HashSet::new();
+        for metric in &metrics {
+            let walked = metric.lookups.iter().map(|(_, oid)| oid);
+            if metric.walk {
+                for oid in std::iter::once(&metric.oid).chain(walked) {
+                    if seen.insert((oid, true)) {
+                        walks.push(oid.clone());
+                    }
+                }
+            } else if seen.insert((&metric.oid, false)) {
+                gets.push(metric.oid.clone());
+            }
+        }
+
+        Ok(Self {
+            metrics,
+            gets,
+            walks,
+        })
+    }
+
+    /// Converts the results of a poll into metrics, without their namespace.
+    fn metrics(&self, results: &PollResults, host: &str, timestamp: DateTime<Utc>) -> Vec<Metric> {
+        // The values of the columns of lookups, by index.
+        let lookups = self
+            .metrics
+            .iter()
+            .flat_map(|metric| metric.lookups.iter().map(|(_, oid)| oid))
+            .map(|root| {
+                let values = results
+                    .walks
+                    .get(root)
+                    .into_iter()
+                    .flatten()
+                    .map(|varbind| (varbind.oid.suffix(root), display_value(&varbind.value)))
+                    .collect::<HashMap<_, _>>();
+                (root, values)
+            })
+            .collect::<HashMap<_, _>>();
+
+        let mut tags = MetricTags::new();
+        tags.insert("host".to_owned(), host.to_owned());
+
+        let mut metrics = Vec::new();
+        for metric in &self.metrics {
+            if !metric.walk {
+                if let Some(value) = results.gets.get(&metric.oid) {
+                    metrics.extend(metric.to_metric(value, tags.clone(), timestamp));
+                }
+                continue;
+            }
+
+            for varbind in results.walks.get(&metric.oid).into_iter().flatten() {
+                let index = varbind.oid.suffix(&metric.oid);
+                let mut tags = tags.clone();
+                match decode_index(index, &metric.indexes) {
+                    Some(labels) => tags.extend(labels),
+                    None => {
+                        tags.insert("index".to_owned(), dotted(index));
+                    }
+                }
+                for (label, root) in &metric.lookups {
+                    if let Some(value) = lookups.get(root).and_then(|values| values.get(index)) {
+                        tags.insert(label.clone(), value.clone());
+                    }
+                }
+                metrics.extend(metric.to_metric(&varbind.value, tags, timestamp));
+            }
+        }
+        metrics
+    }
+}
+
+impl MetricPlan {
+    fn new(config: &SnmpMetricConfig) -> Result<Self, SnmpBuildError> {
+        let (oid, object) = resolve_oid(&config.oid)?;
+        let name = config
+            .name
+            .clone()
+            .or_else(|| object.map(|object| object.name.to_owned()))
+            .context(MissingNameSnafu { oid: &config.oid })?;
+        let (oid, walk) = match object {
+            Some(object) if object.is_scalar() => (oid.child(0), false),
+            Some(_) => (oid, true),
+            None => (oid, config.walk),
+        };
+
+        let indexes = if config.indexes.is_empty() {
+            object
+                .map(|object| {
+                    object
+                        .indexes
+                        .iter()
+                        .map(|(label, index_type)| ((*label).to_owned(), *index_type))
+                        .collect()
+                })
+                .unwrap_or_default()
+        } else {
+            config
+                .indexes
+                .iter()
+                .map(|index| (index.label.clone(), index.index_type))
+                .collect()
+        };
+        let lookups = config
+            .lookups
+            .iter()
+            .map(|lookup| Ok((lookup.label.clone(), resolve_oid(&lookup.oid)?.0)))
+            .collect::<Result<_, SnmpBuildError>>()?;
+
+        Ok(Self {
+            name,
+            oid,
+            walk,
+            kind: config.kind,
+            indexes,
+            lookups,
+        })
+    }
+
+    /// Converts a value into a metric. Values which aren't numbers are reported as a gauge of `1`
+    /// with a `value` tag, and exceptions are skipped.
+    fn to_metric(
+        &self,
+        value: &Value,
+        mut tags: MetricTags,
+        timestamp: DateTime<Utc>,
+    ) -> Option<Metric> {
+        if value.is_exception() || *value == Value::Null {
+            return None;
+        }
+        let number = numeric_value(value).unwrap_or_else(|| {
+            tags.insert("value".to_owned(), display_value(value));
+            1.0
+        });
+        let counter = match self.kind {
+            Some(kind) => kind == SnmpMetricKind::Counter,
+            None => matches!(value, Value::Counter32(_) | Value::Counter64(_)),
+        };
+        let value = if counter {
+            MetricValue::Counter { value: number }
+        } else {
+            MetricValue::Gauge { value: number }
+        };
+        Some(
+            Metric::new(self.name.clone(), MetricKind::Absolute, value)
+                .with_tags(Some(tags))
+                .with_timestamp(Some(timestamp)),
+        )
+    }
+}
+
+/// Resolves a numeric OID, or the name of a known object.
+fn resolve_oid(oid: &str) -> Result<(Oid, Option<&'static MibObject>), SnmpBuildError> {
+    if let Ok(parsed) = oid.parse() {
+        return Ok((parsed, None));
+    }
+    let object = mib::find(oid).context(InvalidOidSnafu { oid })?;
+    let parsed = object.oid.parse().expect("objects have valid OIDs");
+    Ok((parsed, Some(object)))
+}
+
+/// Decodes the labels of an index, or returns `None` if it doesn't match their types.
+fn decode_index(
+    mut index: &[u32],
+    indexes: &[(String, IndexType)],
+) -> Option<Vec<(String, String)>> {
+    if indexes.is_empty() {
+        return None;
+    }
+    let mut labels = Vec::with_capacity(indexes.len());
+    for (label, index_type) in indexes {
+        let (value, rest) = match index_type {
+            IndexType::Integer => {
+                let (first, rest) = index.split_first()?;
+                (first.to_string(), rest)
+            }
+            IndexType::IpAddress => {
+                let (address, rest) = (index.get(..4)?, &index[4..]);
+                let octets = bytes(address)?;
+                (
+                    Ipv4Addr::new(octets[0], octets[1], octets[2], octets[3]).to_string(),
+                    rest,
+                )
+            }
+            IndexType::String => {
+                let (length, rest) = index.split_first()?;
+                let length = *length as usize;
+                let (string, rest) = (rest.get(..length)?, &rest[length..]);
+                (String::from_utf8_lossy(&bytes(string)?).into_owned(), rest)
+            }
+        };
+        labels.push((label.clone(), value));
+        index = rest;
+    }
+    index.is_empty().then_some(labels)
+}
+
+fn bytes(sub_identifiers: &[u32]) -> Option<Vec<u8>> {
+    sub_identifiers
+        .iter()
+        .map(|&sub_identifier| u8::try_from(sub_identifier).ok())
+        .collect()
+}
+
+fn dotted(sub_identifiers: &[u32]) -> String {
+    let mut dotted = String::new();
+    ber::write_dotted(&mut dotted, sub_identifiers).expect("writing to a string");
+    dotted
+}
+
+fn numeric_value(value: &Value) -> Option<f64> {
+    match value {
+        Value::Integer(value) => Some(*value as f64),
+        Value::Counter32(value) | Value::Gauge32(value) | Value::TimeTicks(value) => {
+            Some(f64::from(*value))
+        }
+        Value::Counter64(value) => Some(*value as f64),
+        // Some agents report numbers such as load averages as strings.
+        Value::OctetString(bytes) => std::str::from_utf8(bytes).ok()?.trim().parse().ok(),
+        _ => None,
+    }
+}
+
+/// The value of an object as a label. Strings which aren't printable, such as MAC addresses, are
+/// written in hexadecimal.
+fn display_value(value: &Value) -> String {
+    match value {
+        Value::OctetString(bytes) | Value::Opaque(bytes) => {
+            match std::str::from_utf8(bytes).map(|string| string.trim_end_matches('\0')) {
+                Ok(string) if !string.chars().any(char::is_control) => string.to_owned(),
+                _ => bytes
+                    .iter()
+                    .map(|byte| format!("{:02x}", byte))
+                    .collect::<Vec<_>>()
+                    .join(":"),
+            }
+        }
+        Value::Integer(value) => value.to_string(),
+        Value::ObjectIdentifier(oid) => oid.to_string(),
+        Value::IpAddress(address) => Ipv4Addr::from(*address).to_string(),
+        Value::Counter32(value) | Value::Gauge32(value) | Value::TimeTicks(value) => {
+            value.to_string()
+        }
+        Value::Counter64(value) => value.to_string(),
+        Value::Null | Value::NoSuchObject | Value::NoSuchInstance | Value::EndOfMibView => {
+            String::new()
+        }
+    }
+}
+
+/// The values returned by a poll of an agent.
+#[derive(Default)]
+struct PollResults {
+    gets: HashMap<Oid, Value>,
+    walks: HashMap<Oid, Vec<VarBind>>,
+}
+
+struct Agent {
+    endpoint: String,
+    client: Client,
+    namespace: Option<String>,
+}
+
+impl Agent {
+    async fn collect(&mut self, plan: &Plan, max_repetitions: u32) -> Vec<Metric> {
+        let results = self.poll(plan, max_repetitions).await;
+        let byte_size = self.client.take_bytes_received();
+        if byte_size > 0 {
+            emit!(EndpointBytesReceived {
+                byte_size,
+                protocol: "udp",
+                endpoint: &self.endpoint,
+            });
+        }
+
+        let timestamp = Utc::now();
+        let (up_value, mut metrics) = match results {
+            Ok(results) => (1.0, plan.metrics(&results, &self.endpoint, timestamp)),
+            Err(error) => {
+                emit!(SnmpRequestError {
+                    error,
+                    endpoint: &self.endpoint,
+                });
+                (0.0, Vec::new())
+            }
+        };
+
+        let mut tags = MetricTags::new();
+        tags.insert("host".to_owned(), self.endpoint.clone());
+        metrics.push(
+            Metric::new(
+                "up",
+                MetricKind::Absolute,
+                MetricValue::Gauge { value: up_value },
+            )
+            .with_tags(Some(tags))
+            .with_timestamp(Some(timestamp)),
+        );
+        let metrics = metrics
+            .into_iter()
+            .map(|metric| metric.with_namespace(self.namespace.clone()))
+            .collect::<Vec<_>>();
+
+        emit!(SnmpEventsReceived {
+            count: metrics.len(),
+            byte_size: metrics.size_of(),
+            endpoint: &self.endpoint,
+        });
+
+        metrics
+    }
+
+    async fn poll(
+        &mut self,
+        plan: &Plan,
+        max_repetitions: u32,
+    ) -> Result<PollResults, RequestError> {
+        let mut results = PollResults::default();
+        for varbind in self.client.get(&plan.gets).await? {
+            results.gets.insert(varbind.oid, varbind.value);
+        }
+        for root in &plan.walks {
+            let varbinds = self.client.walk(root, max_repetitions).await?;
+            results.walks.insert(root.clone(), varbinds);
+        }
+        Ok(results)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::BTreeMap, ops::Bound};
+
+    use tokio::net::UdpSocket;
+
+    use super::*;
+    use crate::{
+        event::Event,
+        test_util::components::{run_and_assert_source_compliance, PULL_SOURCE_TAGS},
+    };
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<SnmpConfig>();
+    }
+
+    #[test]
+    fn adds_default_port() {
+        assert_eq!(endpoint_address("10.0.0.1"), "10.0.0.1:161");
+        assert_eq!(endpoint_address("10.0.0.1:1161"), "10.0.0.1:1161");
+        assert_eq!(endpoint_address("::1"), "[::1]:161");
+        assert_eq!(endpoint_address("switch.local"), "switch.local:161");
+        assert_eq!(endpoint_address("switch.local:1161"), "switch.local:1161");
+    }
+
+    #[test]
+    fn decodes_indexes() {
+        let indexes = vec![
+            ("address".to_owned(), IndexType::IpAddress),
+            ("name".to_owned(), IndexType::String),
+            ("port".to_owned(), IndexType::Integer),
+        ];
+        assert_eq!(
+            decode_index(&[10, 0, 0, 1, 3, 101, 116, 104, 8], &indexes),
+            Some(vec![
+                ("address".to_owned(), "10.0.0.1".to_owned()),
+                ("name".to_owned(), "eth".to_owned()),
+                ("port".to_owned(), "8".to_owned()),
+            ])
+        );
+        assert_eq!(decode_index(&[10, 0, 0, 1, 3, 101], &indexes), None);
+        assert_eq!(decode_index(&[10, 0, 0, 1, 0, 8, 9], &indexes), None);
+    }
+
+    #[test]
+    fn rejects_unknown_objects() {
+        let config = toml::from_str::<SnmpMetricConfig>(r#"oid = "ifBogus""#).unwrap();
+        assert!(matches!(
+            MetricPlan::new(&config),
+            Err(SnmpBuildError::InvalidOid { .. })
+        ));
+        let config =
+            toml::from_str::<SnmpMetricConfig>(r#"oid = "1.3.6.1.4.1.9.2.1.57.0""#).unwrap();
+        assert!(matches!(
+            MetricPlan::new(&config),
+            Err(SnmpBuildError::MissingName { .. })
+        ));
+    }
+
+    fn oid(oid: &str) -> Oid {
+        oid.parse().unwrap()
+    }
+
+    /// Answers the `Get` and `GetBulk` requests of SNMPv2c from `objects`.
+    async fn spawn_agent(objects: BTreeMap<Oid, Value>) -> SocketAddr {
+        let socket = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let address = socket.local_addr().unwrap();
+        tokio::spawn(async move {
+            let mut buf = vec![0; 65535];
+            loop {
+                let (size, peer) = socket.recv_from(&mut buf).await.unwrap();
+                let request = ber::decode_community_message(&buf[..size]).unwrap();
+                let varbinds = match request.pdu_type {
+                    ber::GET_REQUEST => request
+                        .varbinds
+                        .iter()
+                        .map(|varbind| VarBind {
+                            oid: varbind.oid.clone(),
+                            value: objects
+                                .get(&varbind.oid)
+                                .cloned()
+                                .unwrap_or(Value::NoSuchObject),
+                        })
+                        .collect(),
+                    ber::GET_BULK_REQUEST => {
+                        let start = &request.varbinds[0].oid;
+                        let mut varbinds = objects
+                            .range((Bound::Excluded(start), Bound::Unbounded))
+                            .take(request.error_index as usize)
+                            .map(|(oid, value)| VarBind {
+                                oid: oid.clone(),
+                                value: value.clone(),
+                            })
+                            .collect::<Vec<_>>();
+                        if varbinds.is_empty() {
+                            varbinds.push(VarBind {
+                                oid: start.clone(),
+                                value: Value::EndOfMibView,
+                            });
+                        }
+                        varbinds
+                    }
+                    pdu_type => panic!("unexpected PDU type {:#x}", pdu_type),
+                };
+                let response = ber::Pdu {
+                    pdu_type: ber::RESPONSE,
+                    request_id: request.request_id,
+                    error_status: 0,
+                    error_index: 0,
+                    varbinds,
+                };
+                let message = ber::encode_community_message(b"public", &response);
+                socket.send_to(&message, peer).await.unwrap();
+            }
+        });
+        address
+    }
+
+    fn find<'a>(events: &'a [Event], name: &str, tag: Option<(&str, &str)>) -> &'a Metric {
+        events
+            .iter()
+            .map(Event::as_metric)
+            .find(|metric| {
+                metric.name() == name
+                    && tag.map_or(true, |(key, value)| {
+                        metric
+                            .tags()
+                            .and_then(|tags| tags.get(key))
+                            .map(String::as_str)
+                            == Some(value)
+                    })
+            })
+            .unwrap_or_else(|| panic!("no metric {}", name))
+    }
+
+    #[tokio::test]
+    async fn polls_agents() {
+        let objects = BTreeMap::from([
+            (oid("1.3.6.1.2.1.1.3.0"), Value::TimeTicks(4200)),
+            (
+                oid("1.3.6.1.2.1.1.5.0"),
+                Value::OctetString(b"switch".to_vec()),
+            ),
+            (
+                oid("1.3.6.1.2.1.31.1.1.1.1.1"),
+                Value::OctetString(b"lo".to_vec()),
+            ),
+            (
+                oid("1.3.6.1.2.1.31.1.1.1.1.2"),
+                Value::OctetString(b"eth0".to_vec()),
+            ),
+            (oid("1.3.6.1.2.1.31.1.1.1.6.1"), Value::Counter64(100)),
+            (oid("1.3.6.1.2.1.31.1.1.1.6.2"), Value::Counter64(200)),
+            (oid("1.3.6.1.2.1.31.1.1.1.10.1"), Value::Counter64(300)),
+        ]);
+        let address = spawn_agent(objects).await;
+
+        let config = toml::from_str::<SnmpConfig>(&format!(
+            r#"endpoints = ["{}"]
+max_repetitions = 1
+
+[[metrics]]
+oid = "sysUpTime"
+
+[[metrics]]
+oid = "sysName"
+
+[[metrics]]
+oid = "IF-MIB::ifHCInOctets"
+lookups = [{{ label = "ifName", oid = "ifName" }}]"#,
+            address
+        ))
+        .unwrap();
+
+        let events =
+            run_and_assert_source_compliance(config, Duration::from_secs(1), &PULL_SOURCE_TAGS)
+                .await;
+
+        let up = find(&events, "up", None);
+        assert_eq!(up.namespace(), Some("snmp"));
+        assert_eq!(up.value(), &MetricValue::Gauge { value: 1.0 });
+
+        let uptime = find(&events, "sysUpTime", None);
+        assert_eq!(uptime.value(), &MetricValue::Gauge { value: 4200.0 });
+        assert_eq!(
+            uptime.tags().unwrap().get("host").unwrap(),
+            &address.to_string()
+        );
+
+        let name = find(&events, "sysName", None);
+        assert_eq!(name.tags().unwrap().get("value").unwrap(), "switch");
+
+        let octets = find(&events, "ifHCInOctets", Some(("ifName", "eth0")));
+        assert_eq!(octets.value(), &MetricValue::Counter { value: 200.0 });
+        assert_eq!(octets.tags().unwrap().get("ifIndex").unwrap(), "2");
+    }
+}
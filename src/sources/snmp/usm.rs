@@ -0,0 +1,555 @@
+//! The user-based security model (USM) of SNMPv3, as defined by RFC 3414 and RFC 3826.
+//!
+//! Messages are authenticated with HMAC-MD5-96 or HMAC-SHA-96, and optionally encrypted with
+//! DES-CBC or AES-128-CFB, using keys derived from the passwords of the user and localized to the
+//! engine of the agent, which is discovered before the first request.
+
+use std::time::Instant;
+
+use openssl::{
+    error::ErrorStack,
+    hash::{Hasher, MessageDigest},
+    pkey::PKey,
+    sign::Signer,
+    symm::{Cipher, Crypter, Mode},
+};
+use snafu::{ResultExt, Snafu};
+
+use super::{
+    ber::{self, DecodeError, Pdu, Reader, SEQUENCE},
+    AuthProtocol, PrivacyProtocol,
+};
+
+const VERSION_3: i64 = 3;
+const USM_SECURITY_MODEL: i64 = 3;
+
+/// The largest message Vector accepts, which is the largest UDP payload.
+const MAX_MESSAGE_SIZE: i64 = 65507;
+
+const FLAG_AUTH: u8 = 0x01;
+const FLAG_PRIV: u8 = 0x02;
+const FLAG_REPORTABLE: u8 = 0x04;
+
+/// The length of the truncated HMACs authenticating messages.
+const AUTH_PARAMS_LENGTH: usize = 12;
+
+/// The length of the password expanded and hashed to derive keys from it.
+const EXPANDED_PASSWORD_LENGTH: usize = 1_048_576;
+
+#[derive(Debug, Snafu)]
+pub enum UsmError {
+    #[snafu(display("Invalid message: {}", source))]
+    Decode { source: DecodeError },
+
+    #[snafu(display("Message authentication failed"))]
+    AuthenticationFailed,
+
+    #[snafu(display("Cryptographic operation failed: {}", source))]
+    Crypto { source: ErrorStack },
+}
+
+impl From<DecodeError> for UsmError {
+    fn from(source: DecodeError) -> Self {
+        Self::Decode { source }
+    }
+}
+
+/// A user of the agent, with the keys derived from its passwords.
+#[derive(Clone)]
+pub struct User {
+    name: Vec<u8>,
+    auth: Option<(AuthProtocol, Vec<u8>)>,
+    privacy: Option<(PrivacyProtocol, Vec<u8>)>,
+}
+
+impl User {
+    /// Derives the keys of a user from its passwords.
+    ///
+    /// Privacy requires authentication, whose protocol also derives the privacy key.
+    pub fn new(
+        name: &str,
+        auth: Option<(AuthProtocol, &str)>,
+        privacy: Option<(PrivacyProtocol, &str)>,
+    ) -> Result<Self, ErrorStack> {
+        let auth = auth
+            .map(|(protocol, password)| Ok((protocol, password_to_key(protocol, password)?)))
+            .transpose()?;
+        let privacy = match (&auth, privacy) {
+            (Some((auth_protocol, _)), Some((protocol, password))) => {
+                Some((protocol, password_to_key(*auth_protocol, password)?))
+            }
+            _ => None,
+        };
+        Ok(Self {
+            name: name.as_bytes().to_vec(),
+            auth,
+            privacy,
+        })
+    }
+}
+
+/// The authoritative engine of an agent, with the keys of the user localized to it.
+#[derive(Clone)]
+pub struct Engine {
+    id: Vec<u8>,
+    boots: u32,
+    time: u32,
+    synced_at: Instant,
+    auth_key: Option<Vec<u8>>,
+    privacy_key: Option<Vec<u8>>,
+}
+
+impl Engine {
+    pub fn new(user: &User, id: Vec<u8>, boots: u32, time: u32) -> Result<Self, ErrorStack> {
+        let auth_key = user
+            .auth
+            .as_ref()
+            .map(|(protocol, key)| localize_key(*protocol, key, &id))
+            .transpose()?;
+        let privacy_key = match (&user.auth, &user.privacy) {
+            (Some((protocol, _)), Some((_, key))) => Some(localize_key(*protocol, key, &id)?),
+            _ => None,
+        };
+        Ok(Self {
+            id,
+            boots,
+            time,
+            synced_at: Instant::now(),
+            auth_key,
+            privacy_key,
+        })
+    }
+
+    /// Updates the boots and time of the engine, as reported by the agent.
+    pub fn sync(&mut self, boots: u32, time: u32) {
+        self.boots = boots;
+        self.time = time;
+        self.synced_at = Instant::now();
+    }
+
+    /// The current time of the engine, estimated from its time when it was last synced.
+    fn time(&self) -> u32 {
+        let elapsed = u32::try_from(self.synced_at.elapsed().as_secs()).unwrap_or(u32::MAX);
+        self.time.saturating_add(elapsed)
+    }
+}
+
+/// A decoded message.
+#[derive(Debug)]
+pub struct Message {
+    pub msg_id: i32,
+    pub engine_id: Vec<u8>,
+    pub boots: u32,
+    pub time: u32,
+    pub pdu: Pdu,
+}
+
+/// Encodes a message carrying `pdu` to the engine, or discovering it if it's unknown.
+///
+/// `salt` must be different for every encrypted message.
+pub fn encode(
+    user: &User,
+    engine: Option<&Engine>,
+    msg_id: i32,
+    context_name: &[u8],
+    pdu: &Pdu,
+    salt: u64,
+) -> Result<Vec<u8>, UsmError> {
+    let (flags, engine_id, boots, time, user_name) = match engine {
+        Some(engine) => {
+            let mut flags = FLAG_REPORTABLE;
+            if engine.auth_key.is_some() {
+                flags |= FLAG_AUTH;
+                if engine.privacy_key.is_some() {
+                    flags |= FLAG_PRIV;
+                }
+            }
+            (
+                flags,
+                &engine.id[..],
+                engine.boots,
+                engine.time(),
+                &user.name[..],
+            )
+        }
+        // Discovery messages are unauthenticated, and leave the engine and user empty.
+        None => (FLAG_REPORTABLE, &[][..], 0, 0, &[][..]),
+    };
+
+    let scoped_pdu = ber::sequence(
+        SEQUENCE,
+        &[
+            ber::octet_string(engine_id),
+            ber::octet_string(if engine.is_some() { context_name } else { &[] }),
+            pdu.encode(),
+        ],
+    );
+    let privacy_key = engine.and_then(|engine| engine.privacy_key.as_deref());
+    let (data, privacy_params) = match (privacy_key, &user.privacy) {
+        (Some(key), Some((protocol, _))) => {
+            let (encrypted, privacy_params) =
+                encrypt(*protocol, key, boots, time, salt, &scoped_pdu).context(CryptoSnafu)?;
+            (ber::octet_string(&encrypted), privacy_params)
+        }
+        _ => (scoped_pdu, Vec::new()),
+    };
+    let auth_params = if flags & FLAG_AUTH != 0 {
+        vec![0; AUTH_PARAMS_LENGTH]
+    } else {
+        Vec::new()
+    };
+
+    let usm = [
+        ber::octet_string(engine_id),
+        ber::integer(i64::from(boots)),
+        ber::integer(i64::from(time)),
+        ber::octet_string(user_name),
+        ber::octet_string(&auth_params),
+        ber::octet_string(&privacy_params),
+    ];
+    // The HMAC of the message replaces the placeholder of the authentication parameters, whose
+    // offset grows by the header of every value they're wrapped in.
+    let mut auth_params_offset = usm[..5].iter().map(Vec::len).sum::<usize>() - auth_params.len();
+    let usm = usm.concat();
+    let usm_sequence = ber::tlv(SEQUENCE, &usm);
+    auth_params_offset += usm_sequence.len() - usm.len();
+    let security_parameters = ber::octet_string(&usm_sequence);
+    auth_params_offset += security_parameters.len() - usm_sequence.len();
+
+    let version = ber::integer(VERSION_3);
+    let header = ber::sequence(
+        SEQUENCE,
+        &[
+            ber::integer(i64::from(msg_id)),
+            ber::integer(MAX_MESSAGE_SIZE),
+            ber::octet_string(&[flags]),
+            ber::integer(USM_SECURITY_MODEL),
+        ],
+    );
+    auth_params_offset += version.len() + header.len();
+    let content = [version, header, security_parameters, data].concat();
+    let mut message = ber::tlv(SEQUENCE, &content);
+    auth_params_offset += message.len() - content.len();
+
+    if let (Some(key), Some((protocol, _))) = (
+        engine.and_then(|engine| engine.auth_key.as_deref()),
+        &user.auth,
+    ) {
+        let mac = hmac(*protocol, key, &message).context(CryptoSnafu)?;
+        message[auth_params_offset..auth_params_offset + AUTH_PARAMS_LENGTH].copy_from_slice(&mac);
+    }
+
+    Ok(message)
+}
+
+/// Decodes a message, verifying its authentication and decrypting it with the keys localized to
+/// the engine.
+pub fn decode(user: &User, engine: Option<&Engine>, message: &[u8]) -> Result<Message, UsmError> {
+    let mut reader = Reader::new(Reader::new(message).expect(SEQUENCE, "message")?);
+    if reader.read_integer("version")? != VERSION_3 {
+        return Err(DecodeError::Invalid { what: "version" }.into());
+    }
+
+    let mut header = Reader::new(reader.expect(SEQUENCE, "header")?);
+    let msg_id = i32::try_from(header.read_integer("message ID")?)
+        .map_err(|_| DecodeError::Invalid { what: "message ID" })?;
+    header.read_integer("maximum message size")?;
+    let flags = match header.read_octet_string("message flags")? {
+        [flags] => *flags,
+        _ => {
+            return Err(DecodeError::Invalid {
+                what: "message flags",
+            }
+            .into())
+        }
+    };
+    if header.read_integer("security model")? != USM_SECURITY_MODEL {
+        return Err(DecodeError::Invalid {
+            what: "security model",
+        }
+        .into());
+    }
+
+    let mut usm = Reader::new(
+        Reader::new(reader.read_octet_string("security parameters")?)
+            .expect(SEQUENCE, "security parameters")?,
+    );
+    let engine_id = usm.read_octet_string("engine ID")?.to_vec();
+    let boots = read_u32(&mut usm, "engine boots")?;
+    let time = read_u32(&mut usm, "engine time")?;
+    usm.read_octet_string("user name")?;
+    let auth_params = usm.read_octet_string("authentication parameters")?;
+    let privacy_params = usm.read_octet_string("privacy parameters")?;
+
+    if flags & FLAG_AUTH != 0 {
+        let (protocol, key) = match (&user.auth, engine.and_then(|e| e.auth_key.as_deref())) {
+            (Some((protocol, _)), Some(key)) => (*protocol, key),
+            _ => return Err(UsmError::AuthenticationFailed),
+        };
+        if auth_params.len() != AUTH_PARAMS_LENGTH {
+            return Err(UsmError::AuthenticationFailed);
+        }
+        let offset = auth_params.as_ptr() as usize - message.as_ptr() as usize;
+        let mut unauthenticated = message.to_vec();
+        unauthenticated[offset..offset + AUTH_PARAMS_LENGTH].fill(0);
+        let mac = hmac(protocol, key, &unauthenticated).context(CryptoSnafu)?;
+        if !openssl::memcmp::eq(&mac, auth_params) {
+            return Err(UsmError::AuthenticationFailed);
+        }
+    }
+
+    let decrypted;
+    let mut scoped_pdu = if flags & FLAG_PRIV != 0 {
+        let (protocol, key) = match (&user.privacy, engine.and_then(|e| e.privacy_key.as_deref())) {
+            (Some((protocol, _)), Some(key)) => (*protocol, key),
+            _ => return Err(UsmError::AuthenticationFailed),
+        };
+        let encrypted = reader.read_octet_string("encrypted PDU")?;
+        decrypted = decrypt(protocol, key, boots, time, privacy_params, encrypted)?;
+        Reader::new(Reader::new(&decrypted).expect(SEQUENCE, "scoped PDU")?)
+    } else {
+        Reader::new(reader.expect(SEQUENCE, "scoped PDU")?)
+    };
+    scoped_pdu.read_octet_string("context engine ID")?;
+    scoped_pdu.read_octet_string("context name")?;
+    let pdu = Pdu::decode(&mut scoped_pdu)?;
+
+    Ok(Message {
+        msg_id,
+        engine_id,
+        boots,
+        time,
+        pdu,
+    })
+}
+
+fn read_u32(reader: &mut Reader<'_>, what: &'static str) -> Result<u32, DecodeError> {
+    u32::try_from(reader.read_integer(what)?).map_err(|_| DecodeError::Invalid { what })
+}
+
+fn digest(protocol: AuthProtocol) -> MessageDigest {
+    match protocol {
+        AuthProtocol::Md5 => MessageDigest::md5(),
+        AuthProtocol::Sha => MessageDigest::sha1(),
+    }
+}
+
+/// Derives a key from a password, by hashing it repeated over a megabyte.
+fn password_to_key(protocol: AuthProtocol, password: &str) -> Result<Vec<u8>, ErrorStack> {
+    let password = password.as_bytes();
+    let mut hasher = Hasher::new(digest(protocol))?;
+    let mut chunk = [0; 64];
+    for offset in (0..EXPANDED_PASSWORD_LENGTH).step_by(chunk.len()) {
+        for (i, byte) in chunk.iter_mut().enumerate() {
+            *byte = password[(offset + i) % password.len()];
+        }
+        hasher.update(&chunk)?;
+    }
+    Ok(hasher.finish()?.to_vec())
+}
+
+/// Localizes a key to an engine, so that a compromised agent doesn't reveal the keys of others.
+fn localize_key(
+    protocol: AuthProtocol,
+    key: &[u8],
+    engine_id: &[u8],
+) -> Result<Vec<u8>, ErrorStack> {
+    let mut hasher = Hasher::new(digest(protocol))?;
+    hasher.update(key)?;
+    hasher.update(engine_id)?;
+    hasher.update(key)?;
+    Ok(hasher.finish()?.to_vec())
+}
+
+fn hmac(protocol: AuthProtocol, key: &[u8], message: &[u8]) -> Result<Vec<u8>, ErrorStack> {
+    let key = PKey::hmac(key)?;
+    let mut signer = Signer::new(digest(protocol), &key)?;
+    signer.update(message)?;
+    let mut mac = signer.sign_to_vec()?;
+    mac.truncate(AUTH_PARAMS_LENGTH);
+    Ok(mac)
+}
+
+/// Encrypts a scoped PDU, returning it along with the privacy parameters of the message.
+fn encrypt(
+    protocol: PrivacyProtocol,
+    key: &[u8],
+    boots: u32,
+    time: u32,
+    salt: u64,
+    scoped_pdu: &[u8],
+) -> Result<(Vec<u8>, Vec<u8>), ErrorStack> {
+    match protocol {
+        PrivacyProtocol::Des => {
+            let salt = [boots.to_be_bytes(), (salt as u32).to_be_bytes()].concat();
+            let iv = des_iv(key, &salt);
+            let mut padded = scoped_pdu.to_vec();
+            padded.resize((padded.len() + 7) / 8 * 8, 0);
+            let encrypted = crypt(Cipher::des_cbc(), Mode::Encrypt, &key[..8], &iv, &padded)?;
+            Ok((encrypted, salt))
+        }
+        PrivacyProtocol::Aes => {
+            let salt = salt.to_be_bytes().to_vec();
+            let iv = aes_iv(boots, time, &salt);
+            let encrypted = crypt(
+                Cipher::aes_128_cfb128(),
+                Mode::Encrypt,
+                &key[..16],
+                &iv,
+                scoped_pdu,
+            )?;
+            Ok((encrypted, salt))
+        }
+    }
+}
+
+fn decrypt(
+    protocol: PrivacyProtocol,
+    key: &[u8],
+    boots: u32,
+    time: u32,
+    privacy_params: &[u8],
+    encrypted: &[u8],
+) -> Result<Vec<u8>, UsmError> {
+    if privacy_params.len() != 8 {
+        return Err(DecodeError::Invalid {
+            what: "privacy parameters",
+        }
+        .into());
+    }
+    match protocol {
+        PrivacyProtocol::Des => {
+            let iv = des_iv(key, privacy_params);
+            crypt(Cipher::des_cbc(), Mode::Decrypt, &key[..8], &iv, encrypted)
+        }
+        PrivacyProtocol::Aes => {
+            let iv = aes_iv(boots, time, privacy_params);
+            crypt(
+                Cipher::aes_128_cfb128(),
+                Mode::Decrypt,
+                &key[..16],
+                &iv,
+                encrypted,
+            )
+        }
+    }
+    .context(CryptoSnafu)
+}
+
+/// The IV of DES, the pre-IV of the key XORed with the salt.
+fn des_iv(key: &[u8], salt: &[u8]) -> Vec<u8> {
+    key[8..16].iter().zip(salt).map(|(a, b)| a ^ b).collect()
+}
+
+/// The IV of AES, the boots and time of the engine followed by the salt.
+fn aes_iv(boots: u32, time: u32, salt: &[u8]) -> Vec<u8> {
+    [&boots.to_be_bytes()[..], &time.to_be_bytes()[..], salt].concat()
+}
+
+fn crypt(
+    cipher: Cipher,
+    mode: Mode,
+    key: &[u8],
+    iv: &[u8],
+    input: &[u8],
+) -> Result<Vec<u8>, ErrorStack> {
+    let mut crypter = Crypter::new(cipher, mode, key, Some(iv))?;
+    crypter.pad(false);
+    let mut output = vec![0; input.len() + cipher.block_size()];
+    let mut count = crypter.update(input, &mut output)?;
+    count += crypter.finalize(&mut output[count..])?;
+    output.truncate(count);
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sources::snmp::ber::{Oid, Value, VarBind, GET_REQUEST, RESPONSE};
+
+    /// The engine ID of the examples of RFC 3414, appendix A.3.
+    const ENGINE_ID: [u8; 12] = [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 2];
+
+    fn to_hex(bytes: &[u8]) -> String {
+        bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+    }
+
+    #[test]
+    fn localizes_keys() {
+        let user = User::new("user", Some((AuthProtocol::Md5, "maplesyrup")), None).unwrap();
+        let engine = Engine::new(&user, ENGINE_ID.to_vec(), 0, 0).unwrap();
+        assert_eq!(
+            to_hex(&engine.auth_key.unwrap()),
+            "526f5eed9fcce26f8964c2930787d82b"
+        );
+
+        let user = User::new("user", Some((AuthProtocol::Sha, "maplesyrup")), None).unwrap();
+        let engine = Engine::new(&user, ENGINE_ID.to_vec(), 0, 0).unwrap();
+        assert_eq!(
+            to_hex(&engine.auth_key.unwrap()),
+            "6695febc9288e36282235fc7151f128497b38f3f"
+        );
+    }
+
+    fn response() -> Pdu {
+        Pdu {
+            pdu_type: RESPONSE,
+            request_id: 7,
+            error_status: 0,
+            error_index: 0,
+            varbinds: vec![VarBind {
+                oid: "1.3.6.1.2.1.1.3.0".parse::<Oid>().unwrap(),
+                value: Value::TimeTicks(42),
+            }],
+        }
+    }
+
+    fn round_trip(privacy: Option<PrivacyProtocol>) {
+        let user = User::new(
+            "vector",
+            Some((AuthProtocol::Sha, "authpassword")),
+            privacy.map(|protocol| (protocol, "privpassword")),
+        )
+        .unwrap();
+        let engine = Engine::new(&user, ENGINE_ID.to_vec(), 3, 1000).unwrap();
+
+        let mut message = encode(&user, Some(&engine), 99, b"", &response(), 1).unwrap();
+        let decoded = decode(&user, Some(&engine), &message).unwrap();
+        assert_eq!(decoded.msg_id, 99);
+        assert_eq!(decoded.engine_id, ENGINE_ID);
+        assert_eq!(decoded.boots, 3);
+        assert_eq!(decoded.pdu, response());
+
+        let last = message.len() - 1;
+        message[last] ^= 0xff;
+        assert!(matches!(
+            decode(&user, Some(&engine), &message),
+            Err(UsmError::AuthenticationFailed)
+        ));
+    }
+
+    #[test]
+    fn round_trips_authenticated_messages() {
+        round_trip(None);
+    }
+
+    #[test]
+    fn round_trips_des_encrypted_messages() {
+        round_trip(Some(PrivacyProtocol::Des));
+    }
+
+    #[test]
+    fn round_trips_aes_encrypted_messages() {
+        round_trip(Some(PrivacyProtocol::Aes));
+    }
+
+    #[test]
+    fn encodes_discovery_messages() {
+        let user = User::new("vector", Some((AuthProtocol::Md5, "authpassword")), None).unwrap();
+        let request = Pdu::request(GET_REQUEST, 1, &[]);
+        let message = encode(&user, None, 1, b"", &request, 0).unwrap();
+        let decoded = decode(&user, None, &message).unwrap();
+        assert!(decoded.engine_id.is_empty());
+        assert_eq!(decoded.pdu, request);
+    }
+}
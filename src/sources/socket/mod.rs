@@ -3,7 +3,7 @@ mod udp;
 #[cfg(unix)]
 mod unix;
 
-use codecs::NewlineDelimitedDecoderConfig;
+use codecs::{decoding::DeserializerConfig, NewlineDelimitedDecoderConfig};
 use vector_config::configurable_component;
 use vector_core::config::LogNamespace;
 
@@ -11,7 +11,7 @@ use vector_core::config::LogNamespace;
 use crate::serde::default_framing_message_based;
 use crate::{
     codecs::DecodingConfig,
-    config::{log_schema, DataType, GenerateConfig, Output, Resource, SourceConfig, SourceContext},
+    config::{log_schema, GenerateConfig, Output, Resource, SourceConfig, SourceContext},
     sources::util::TcpSource,
     tls::MaybeTlsSettings,
 };
@@ -44,6 +44,13 @@ pub enum Mode {
     #[cfg(unix)]
     #[serde(alias = "unix")]
     UnixStream(#[configurable(derived)] unix::UnixConfig),
+
+    /// Listen on UDS, in sequenced packet mode. (Unix domain socket)
+    ///
+    /// Like the datagram mode, each packet is a message, but packets are received over
+    /// connections, in order.
+    #[cfg(unix)]
+    UnixSeqpacket(#[configurable(derived)] unix::UnixConfig),
 }
 
 impl SocketConfig {
@@ -55,14 +62,16 @@ impl SocketConfig {
         tcp::TcpConfig::from_address(addr.into()).into()
     }
 
-    fn output_type(&self) -> DataType {
+    const fn decoding(&self) -> &DeserializerConfig {
         match &self.mode {
-            Mode::Tcp(config) => config.decoding().output_type(),
-            Mode::Udp(config) => config.decoding().output_type(),
+            Mode::Tcp(config) => config.decoding(),
+            Mode::Udp(config) => config.decoding(),
+            #[cfg(unix)]
+            Mode::UnixDatagram(config) => &config.decoding,
             #[cfg(unix)]
-            Mode::UnixDatagram(config) => config.decoding.output_type(),
+            Mode::UnixStream(config) => &config.decoding,
             #[cfg(unix)]
-            Mode::UnixStream(config) => config.decoding.output_type(),
+            Mode::UnixSeqpacket(config) => &config.decoding,
         }
     }
 }
@@ -165,9 +174,10 @@ impl SourceConfig for SocketConfig {
                     LogNamespace::Legacy,
                 )
                 .build();
+                let permissions = config.permissions()?;
                 unix::unix_datagram(
                     config.path,
-                    config.socket_file_mode,
+                    permissions,
                     config
                         .max_length
                         .unwrap_or_else(crate::serde::default_max_length),
@@ -205,9 +215,34 @@ impl SourceConfig for SocketConfig {
                 let host_key = config
                     .host_key
                     .unwrap_or_else(|| log_schema().host_key().to_string());
+                let permissions = config.permissions()?;
                 unix::unix_stream(
                     config.path,
-                    config.socket_file_mode,
+                    permissions,
+                    host_key,
+                    decoder,
+                    cx.shutdown,
+                    cx.out,
+                )
+            }
+            #[cfg(unix)]
+            Mode::UnixSeqpacket(config) => {
+                let host_key = config
+                    .host_key
+                    .unwrap_or_else(|| log_schema().host_key().to_string());
+                let decoder = DecodingConfig::new(
+                    config.framing.unwrap_or_else(default_framing_message_based),
+                    config.decoding.clone(),
+                    LogNamespace::Legacy,
+                )
+                .build();
+                let permissions = config.permissions()?;
+                unix::unix_seqpacket(
+                    config.path,
+                    permissions,
+                    config
+                        .max_length
+                        .unwrap_or_else(crate::serde::default_max_length),
                     host_key,
                     decoder,
                     cx.shutdown,
@@ -218,7 +253,8 @@ impl SourceConfig for SocketConfig {
     }
 
     fn outputs(&self, _global_log_namespace: LogNamespace) -> Vec<Output> {
-        vec![Output::default(self.output_type())]
+        let decoding = self.decoding();
+        decoding.with_errors_output(vec![Output::default(decoding.output_type())])
     }
 
     fn resources(&self) -> Vec<Resource> {
@@ -229,6 +265,8 @@ impl SourceConfig for SocketConfig {
             Mode::UnixDatagram(_) => vec![],
             #[cfg(unix)]
             Mode::UnixStream(_) => vec![],
+            #[cfg(unix)]
+            Mode::UnixSeqpacket(_) => vec![],
         }
     }
 
@@ -744,6 +782,31 @@ mod test {
         .await;
     }
 
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn udp_multiple_sockets() {
+        assert_source_compliance(&SOCKET_HIGH_CARDINALITY_PUSH_SOURCE_TAGS, async {
+            let (tx, rx) = SourceSender::new_test();
+            let address = next_addr();
+            let mut config = UdpConfig::from_address(address);
+            config.sockets = std::num::NonZeroUsize::new(4);
+            let address = init_udp_with_config(tx, config).await;
+
+            send_lines_udp(address, vec!["test".to_string(), "test2".to_string()]);
+            let events = collect_n(rx, 2).await;
+
+            assert_eq!(
+                events[0].as_log()[log_schema().message_key()],
+                "test".into()
+            );
+            assert_eq!(
+                events[1].as_log()[log_schema().message_key()],
+                "test2".into()
+            );
+        })
+        .await;
+    }
+
     #[tokio::test]
     async fn udp_max_length() {
         assert_source_compliance(&SOCKET_HIGH_CARDINALITY_PUSH_SOURCE_TAGS, async {
@@ -1228,4 +1291,42 @@ mod test {
         })
         .await;
     }
+
+    ////////////// UNIX SEQPACKET TESTS //////////////
+    #[cfg(unix)]
+    #[test]
+    fn parses_unix_seqpacket_config() {
+        let config = parses_unix_config("unix_seqpacket");
+        assert!(matches!(config.mode, Mode::UnixSeqpacket { .. }));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn unix_seqpacket_message_per_packet() {
+        let in_path = tempfile::tempdir().unwrap().into_path().join("unix_test");
+        let (tx, rx) = SourceSender::new_test();
+
+        let mode = Mode::UnixSeqpacket(UnixConfig::new(in_path.clone()));
+        let server = SocketConfig { mode }
+            .build(SourceContext::new_test(tx, None))
+            .await
+            .unwrap();
+        tokio::spawn(server);
+
+        let socket = loop {
+            match crate::unix::UnixSeqpacket::connect(&in_path) {
+                Ok(socket) => break socket,
+                Err(_) => yield_now().await,
+            }
+        };
+        socket.send(b"foo\nbar").await.unwrap();
+        socket.send(b"baz").await.unwrap();
+
+        let events = collect_n(rx, 2).await;
+        assert_eq!(
+            events[0].as_log()[log_schema().message_key()],
+            "foo\nbar".into()
+        );
+        assert_eq!(events[1].as_log()[log_schema().message_key()], "baz".into());
+    }
 }
@@ -1,4 +1,4 @@
-use std::net::SocketAddr;
+use std::{net::SocketAddr, num::NonZeroUsize};
 
 use bytes::{Bytes, BytesMut};
 use chrono::Utc;
@@ -6,9 +6,8 @@ use codecs::{
     decoding::{DeserializerConfig, FramingConfig},
     StreamDecodingError,
 };
-use futures::StreamExt;
 use tokio::net::UdpSocket;
-use tokio_util::codec::FramedRead;
+use tokio_util::codec::Decoder as _;
 use vector_common::internal_event::{ByteSize, BytesReceived, InternalEventHandle as _, Protocol};
 use vector_config::configurable_component;
 use vector_core::ByteSizeOf;
@@ -59,6 +58,15 @@ pub struct UdpConfig {
     /// This should not typically needed to be changed.
     receive_buffer_bytes: Option<usize>,
 
+    /// The number of sockets to listen on, each received on by its own task.
+    ///
+    /// When more than one, the sockets share the address with `SO_REUSEPORT`, and the operating
+    /// system spreads the incoming messages across them. On Linux, the messages of a given peer
+    /// are always received by the same socket. Only supported on Unix.
+    ///
+    /// Defaults to one socket.
+    pub(super) sockets: Option<NonZeroUsize>,
+
     #[configurable(derived)]
     #[serde(default = "default_framing_message_based")]
     pub(super) framing: FramingConfig,
@@ -92,6 +100,7 @@ impl UdpConfig {
             host_key: None,
             port_key: Some(String::from("port")),
             receive_buffer_bytes: None,
+            sockets: None,
             framing: default_framing_message_based(),
             decoding: default_decoding(),
         }
@@ -102,127 +111,150 @@ pub(super) fn udp(
     config: UdpConfig,
     host_key: String,
     decoder: Decoder,
-    mut shutdown: ShutdownSignal,
-    mut out: SourceSender,
+    shutdown: ShutdownSignal,
+    out: SourceSender,
 ) -> Source {
     Box::pin(async move {
-        let socket = UdpSocket::bind(&config.address)
-            .await
-            .expect("Failed to bind to udp listener socket");
-
-        if let Some(receive_buffer_bytes) = config.receive_buffer_bytes {
-            if let Err(error) = udp::set_receive_buffer_size(&socket, receive_buffer_bytes) {
-                warn!(message = "Failed configuring receive buffer size on UDP socket.", %error);
-            }
-        }
+        let sockets = udp::bind_sockets(
+            config.address,
+            config.sockets.map_or(1, NonZeroUsize::get),
+            config.receive_buffer_bytes,
+        )
+        .expect("Failed to bind to udp listener socket");
 
         let max_length = match config.receive_buffer_bytes {
             Some(receive_buffer_bytes) => std::cmp::min(config.max_length, receive_buffer_bytes),
             None => config.max_length,
         };
 
-        let bytes_received = register!(BytesReceived::from(Protocol::UDP));
+        info!(message = "Listening.", address = %config.address, sockets = sockets.len());
 
-        info!(message = "Listening.", address = %config.address);
+        udp::receive_on_sockets(sockets, |socket| {
+            receive(
+                socket,
+                max_length,
+                host_key.clone(),
+                config.port_key.clone(),
+                decoder.clone(),
+                shutdown.clone(),
+                out.clone(),
+            )
+        })
+        .await
+    })
+}
 
-        // We add 1 to the max_length in order to determine if the received data has been truncated.
-        let mut buf = BytesMut::with_capacity(max_length + 1);
-        loop {
-            buf.resize(max_length + 1, 0);
-            tokio::select! {
-                recv = socket.recv_from(&mut buf) => {
-                    let (byte_size, address) = match recv {
-                        Ok(res) => res,
-                        Err(error) => {
-                            #[cfg(windows)]
-                            if let Some(err) = error.raw_os_error() {
-                                if err == 10040 {
-                                    // 10040 is the Windows error that the Udp message has exceeded max_length
-                                    warn!(
-                                        message = "Discarding frame larger than max_length.",
-                                        max_length = max_length,
-                                        internal_log_rate_limit = true
-                                    );
-                                    continue;
-                                }
+async fn receive(
+    socket: UdpSocket,
+    max_length: usize,
+    host_key: String,
+    port_key: Option<String>,
+    decoder: Decoder,
+    mut shutdown: ShutdownSignal,
+    mut out: SourceSender,
+) -> Result<(), ()> {
+    let bytes_received = register!(BytesReceived::from(Protocol::UDP));
+
+    // We add 1 to the max_length in order to determine if the received data has been truncated.
+    let mut buf = BytesMut::with_capacity(max_length + 1);
+    loop {
+        buf.resize(max_length + 1, 0);
+        tokio::select! {
+            recv = socket.recv_from(&mut buf) => {
+                let (byte_size, address) = match recv {
+                    Ok(res) => res,
+                    Err(error) => {
+                        #[cfg(windows)]
+                        if let Some(err) = error.raw_os_error() {
+                            if err == 10040 {
+                                // 10040 is the Windows error that the Udp message has exceeded max_length
+                                warn!(
+                                    message = "Discarding frame larger than max_length.",
+                                    max_length = max_length,
+                                    internal_log_rate_limit = true
+                                );
+                                continue;
                             }
+                        }
 
-                            let error = codecs::decoding::Error::FramingError(error.into());
-                            return Err(emit!(SocketReceiveError {
-                                mode: SocketMode::Udp,
-                                error: &error
-                            }));
-                       }
-                    };
-
-                    bytes_received.emit(ByteSize(byte_size));
-
-                    let payload = buf.split_to(byte_size);
-                    let truncated = byte_size == max_length + 1;
-
-                    let mut stream = FramedRead::new(payload.as_ref(), decoder.clone()).peekable();
-
-                    while let Some(result) = stream.next().await {
-                        let last = Pin::new(&mut stream).peek().await.is_none();
-                        match result {
-                            Ok((mut events, _byte_size)) => {
-                                if last && truncated {
-                                    // The last event in this payload was truncated, so we want to drop it.
-                                    let _ = events.pop();
-                                    warn!(
-                                        message = "Discarding frame larger than max_length.",
-                                        max_length = max_length,
-                                        internal_log_rate_limit = true
-                                    );
-                                }
+                        let error = codecs::decoding::Error::FramingError(error.into());
+                        return Err(emit!(SocketReceiveError {
+                            mode: SocketMode::Udp,
+                            error: &error
+                        }));
+                   }
+                };
 
-                                if events.is_empty() {
-                                    continue;
-                                }
+                bytes_received.emit(ByteSize(byte_size));
 
-                                let count = events.len();
-                                emit!(SocketEventsReceived {
-                                    mode: SocketMode::Udp,
-                                    byte_size: events.size_of(),
-                                    count,
-                                });
+                // The datagram is copied out of the receive buffer into one of its own size, which
+                // the frames are sliced out of, so the events don't keep the whole receive buffer
+                // alive.
+                let mut payload = BytesMut::from(&buf[..byte_size]);
+                let truncated = byte_size == max_length + 1;
 
-                                let now = Utc::now();
+                let mut decoder = decoder.clone();
+                loop {
+                    match decoder.decode_eof(&mut payload) {
+                        Ok(Some((mut events, _byte_size))) => {
+                            let last = payload.is_empty();
+                            if last && truncated {
+                                // The last event in this payload was truncated, so we want to drop it.
+                                let _ = events.pop();
+                                warn!(
+                                    message = "Discarding frame larger than max_length.",
+                                    max_length = max_length,
+                                    internal_log_rate_limit = true
+                                );
+                            }
+
+                            if events.is_empty() {
+                                continue;
+                            }
 
-                                for event in &mut events {
-                                    if let Event::Log(ref mut log) = event {
-                                        log.try_insert(log_schema().source_type_key(), Bytes::from("socket"));
-                                        log.try_insert(log_schema().timestamp_key(), now);
-                                        log.try_insert(host_key.as_str(), address.ip().to_string());
+                            let count = events.len();
+                            emit!(SocketEventsReceived {
+                                mode: SocketMode::Udp,
+                                byte_size: events.size_of(),
+                                count,
+                            });
 
-                                        if let Some(port_key) = &config.port_key {
-                                            log.try_insert(port_key.as_str(), address.port());
-                                        }
+                            let now = Utc::now();
+
+                            for event in &mut events {
+                                if let Event::Log(ref mut log) = event {
+                                    log.try_insert(log_schema().source_type_key(), Bytes::from("socket"));
+                                    log.try_insert(log_schema().timestamp_key(), now);
+                                    log.try_insert(host_key.as_str(), address.ip().to_string());
+
+                                    if let Some(port_key) = &port_key {
+                                        log.try_insert(port_key.as_str(), address.port());
                                     }
                                 }
+                            }
 
-                                tokio::select!{
-                                    result = out.send_batch(events) => {
-                                        if let Err(error) = result {
-                                            emit!(StreamClosedError { error, count });
-                                            return Ok(())
-                                        }
+                            tokio::select!{
+                                result = out.send_batch(events) => {
+                                    if let Err(error) = result {
+                                        emit!(StreamClosedError { error, count });
+                                        return Ok(())
                                     }
-                                    _ = &mut shutdown => return Ok(()),
                                 }
+                                _ = &mut shutdown => return Ok(()),
                             }
-                            Err(error) => {
-                                // Error is logged by `crate::codecs::Decoder`, no
-                                // further handling is needed here.
-                                if !error.can_continue() {
-                                    break;
-                                }
+                        }
+                        Ok(None) => break,
+                        Err(error) => {
+                            // Error is logged by `crate::codecs::Decoder`, no
+                            // further handling is needed here.
+                            if !error.can_continue() {
+                                break;
                             }
                         }
                     }
                 }
-                _ = &mut shutdown => return Ok(()),
             }
+            _ = &mut shutdown => return Ok(()),
         }
-    })
+    }
 }
@@ -12,7 +12,10 @@ use crate::{
     serde::default_decoding,
     shutdown::ShutdownSignal,
     sources::{
-        util::{build_unix_datagram_source, build_unix_stream_source},
+        util::{
+            build_unix_datagram_source, build_unix_seqpacket_source, build_unix_stream_source,
+            SocketFilePermissions,
+        },
         Source,
     },
     SourceSender,
@@ -25,7 +28,8 @@ use crate::{
 pub struct UnixConfig {
     /// The Unix socket path.
     ///
-    /// This should be an absolute path.
+    /// This should be an absolute path. On Linux, a path starting with `@` designates an address
+    /// in the abstract namespace, such as `@vector`, for which no socket file is created.
     pub path: PathBuf,
 
     /// Unix file mode bits to be applied to the unix socket file as its designated file permissions.
@@ -34,6 +38,14 @@ pub struct UnixConfig {
     /// language, but it is most intuitive to use an octal number.
     pub socket_file_mode: Option<u32>,
 
+    /// The user owning the unix socket file, as a user name or a numeric user ID.
+    ///
+    /// Changing the owner of the file usually requires Vector to run as root.
+    pub socket_file_owner: Option<String>,
+
+    /// The group owning the unix socket file, as a group name or a numeric group ID.
+    pub socket_file_group: Option<String>,
+
     /// The maximum buffer size, in bytes, of incoming messages.
     ///
     /// Messages larger than this are truncated.
@@ -62,12 +74,22 @@ impl UnixConfig {
         Self {
             path,
             socket_file_mode: None,
+            socket_file_owner: None,
+            socket_file_group: None,
             max_length: Some(crate::serde::default_max_length()),
             host_key: None,
             framing: None,
             decoding: default_decoding(),
         }
     }
+
+    pub fn permissions(&self) -> crate::Result<SocketFilePermissions> {
+        SocketFilePermissions::new(
+            self.socket_file_mode,
+            self.socket_file_owner.as_deref(),
+            self.socket_file_group.as_deref(),
+        )
+    }
 }
 
 /// Function to pass to `build_unix_*_source`, specific to the basic unix source
@@ -89,7 +111,7 @@ fn handle_events(events: &mut [Event], host_key: &str, received_from: Option<Byt
 
 pub(super) fn unix_datagram(
     path: PathBuf,
-    socket_file_mode: Option<u32>,
+    permissions: SocketFilePermissions,
     max_length: usize,
     host_key: String,
     decoder: Decoder,
@@ -98,7 +120,7 @@ pub(super) fn unix_datagram(
 ) -> crate::Result<Source> {
     build_unix_datagram_source(
         path,
-        socket_file_mode,
+        permissions,
         max_length,
         decoder,
         move |events, received_from| handle_events(events, &host_key, received_from),
@@ -109,7 +131,7 @@ pub(super) fn unix_datagram(
 
 pub(super) fn unix_stream(
     path: PathBuf,
-    socket_file_mode: Option<u32>,
+    permissions: SocketFilePermissions,
     host_key: String,
     decoder: Decoder,
     shutdown: ShutdownSignal,
@@ -117,7 +139,27 @@ pub(super) fn unix_stream(
 ) -> crate::Result<Source> {
     build_unix_stream_source(
         path,
-        socket_file_mode,
+        permissions,
+        decoder,
+        move |events, received_from| handle_events(events, &host_key, received_from),
+        shutdown,
+        out,
+    )
+}
+
+pub(super) fn unix_seqpacket(
+    path: PathBuf,
+    permissions: SocketFilePermissions,
+    max_length: usize,
+    host_key: String,
+    decoder: Decoder,
+    shutdown: ShutdownSignal,
+    out: SourceSender,
+) -> crate::Result<Source> {
+    build_unix_seqpacket_source(
+        path,
+        permissions,
+        max_length,
         decoder,
         move |events, received_from| handle_events(events, &host_key, received_from),
         shutdown,
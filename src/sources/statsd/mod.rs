@@ -1,11 +1,14 @@
-use std::net::{Ipv4Addr, SocketAddr, SocketAddrV4};
+use std::{
+    net::{Ipv4Addr, SocketAddr, SocketAddrV4},
+    num::NonZeroUsize,
+};
 
 use bytes::Bytes;
 use codecs::{
     decoding::{self, Deserializer, Framer},
     NewlineDelimitedDecoder,
 };
-use futures::{StreamExt, TryFutureExt};
+use futures::StreamExt;
 use smallvec::{smallvec, SmallVec};
 use tokio::net::UdpSocket;
 use tokio_util::udp::UdpFramed;
@@ -64,6 +67,15 @@ pub struct UdpConfig {
     ///
     /// This should not typically needed to be changed.
     receive_buffer_bytes: Option<usize>,
+
+    /// The number of sockets to listen on, each received on by its own task.
+    ///
+    /// When more than one, the sockets share the address with `SO_REUSEPORT`, and the operating
+    /// system spreads the incoming messages across them. On Linux, the messages of a given peer
+    /// are always received by the same socket. Only supported on Unix.
+    ///
+    /// Defaults to one socket.
+    sockets: Option<NonZeroUsize>,
 }
 
 impl UdpConfig {
@@ -71,6 +83,7 @@ impl UdpConfig {
         Self {
             address,
             receive_buffer_bytes: None,
+            sockets: None,
         }
     }
 }
@@ -244,26 +257,35 @@ impl decoding::format::Deserializer for StatsdDeserializer {
 async fn statsd_udp(
     config: UdpConfig,
     shutdown: ShutdownSignal,
-    mut out: SourceSender,
+    out: SourceSender,
 ) -> Result<(), ()> {
     // TODO: This should probably be based off of the `socket` source in UDP mode. If it's missing features needed, we
     // should add them. Reduce, reuse, recycle.
-    let socket = UdpSocket::bind(&config.address)
-        .map_err(|error| emit!(StatsdSocketError::bind(error)))
-        .await?;
-
-    if let Some(receive_buffer_bytes) = config.receive_buffer_bytes {
-        if let Err(error) = udp::set_receive_buffer_size(&socket, receive_buffer_bytes) {
-            warn!(message = "Failed configuring receive buffer size on UDP socket.", %error);
-        }
-    }
+    let sockets = udp::bind_sockets(
+        config.address,
+        config.sockets.map_or(1, NonZeroUsize::get),
+        config.receive_buffer_bytes,
+    )
+    .map_err(|error| emit!(StatsdSocketError::bind(error)))?;
 
     info!(
         message = "Listening.",
         addr = %config.address,
-        r#type = "udp"
+        r#type = "udp",
+        sockets = sockets.len(),
     );
 
+    udp::receive_on_sockets(sockets, |socket| {
+        statsd_udp_receive(socket, shutdown.clone(), out.clone())
+    })
+    .await
+}
+
+async fn statsd_udp_receive(
+    socket: UdpSocket,
+    shutdown: ShutdownSignal,
+    mut out: SourceSender,
+) -> Result<(), ()> {
     let codec = Decoder::new(
         Framer::NewlineDelimited(NewlineDelimitedDecoder::new()),
         Deserializer::Boxed(Box::new(StatsdDeserializer::udp())),
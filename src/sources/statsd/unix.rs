@@ -36,7 +36,7 @@ pub fn statsd_unix(
 
     build_unix_stream_source(
         config.path,
-        None,
+        Default::default(),
         decoder,
         |_events, _host| {},
         shutdown,
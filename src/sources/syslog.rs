@@ -1,12 +1,12 @@
-use std::net::SocketAddr;
 #[cfg(unix)]
 use std::path::PathBuf;
+use std::{net::SocketAddr, num::NonZeroUsize};
 
 use bytes::Bytes;
 use chrono::Utc;
 use codecs::{
     decoding::{Deserializer, Framer},
-    BytesDecoder, OctetCountingDecoder, SyslogDeserializer,
+    BytesDecoder, OctetCountingDecoder, OctetCountingFraming, SyslogDeserializer,
 };
 use futures::StreamExt;
 use smallvec::SmallVec;
@@ -21,7 +21,7 @@ use crate::sources::util::build_unix_stream_source;
 use crate::{
     config::{log_schema, DataType, GenerateConfig, Output, Resource, SourceConfig, SourceContext},
     event::Event,
-    internal_events::SyslogUdpReadError,
+    internal_events::{SocketBytesReceived, SocketMode, SyslogUdpReadError},
     shutdown::ShutdownSignal,
     sources::util::{SocketListenAddr, TcpNullAcker, TcpSource},
     tcp::TcpKeepaliveConfig,
@@ -42,6 +42,15 @@ pub struct SyslogConfig {
     #[serde(default = "crate::serde::default_max_length")]
     max_length: usize,
 
+    /// The framing of the messages received over TCP or UDS connections.
+    ///
+    /// By default, the framing is detected from the first message of each connection, so that
+    /// senders using octet counting and senders delimiting messages with newlines can send to
+    /// the same source.
+    #[configurable(derived)]
+    #[serde(default)]
+    framing: OctetCountingFraming,
+
     /// Overrides the name of the log field used to add the peer host to each event.
     ///
     /// If using TCP or UDP, the value will be the peer host's address, including the port i.e. `1.2.3.4:9000`. If using
@@ -87,6 +96,15 @@ pub enum Mode {
         ///
         /// This should not typically needed to be changed.
         receive_buffer_bytes: Option<usize>,
+
+        /// The number of sockets to listen on, each received on by its own task.
+        ///
+        /// When more than one, the sockets share the address with `SO_REUSEPORT`, and the
+        /// operating system spreads the incoming messages across them. On Linux, the messages of a
+        /// given peer are always received by the same socket. Only supported on Unix.
+        ///
+        /// Defaults to one socket.
+        sockets: Option<NonZeroUsize>,
     },
 
     /// Listen on UDS. (Unix domain socket)
@@ -111,6 +129,7 @@ impl SyslogConfig {
             mode,
             host_key: None,
             max_length: crate::serde::default_max_length(),
+            framing: OctetCountingFraming::default(),
         }
     }
 }
@@ -127,6 +146,7 @@ impl GenerateConfig for SyslogConfig {
             },
             host_key: None,
             max_length: crate::serde::default_max_length(),
+            framing: OctetCountingFraming::default(),
         })
         .unwrap()
     }
@@ -150,6 +170,7 @@ impl SourceConfig for SyslogConfig {
             } => {
                 let source = SyslogTcpSource {
                     max_length: self.max_length,
+                    framing: self.framing,
                     host_key,
                 };
                 let shutdown_secs = 30;
@@ -172,11 +193,13 @@ impl SourceConfig for SyslogConfig {
             Mode::Udp {
                 address,
                 receive_buffer_bytes,
+                sockets,
             } => Ok(udp(
                 address,
                 self.max_length,
                 host_key,
                 receive_buffer_bytes,
+                sockets.map_or(1, NonZeroUsize::get),
                 cx.shutdown,
                 cx.out,
             )),
@@ -186,15 +209,16 @@ impl SourceConfig for SyslogConfig {
                 socket_file_mode,
             } => {
                 let decoder = Decoder::new(
-                    Framer::OctetCounting(OctetCountingDecoder::new_with_max_length(
-                        self.max_length,
-                    )),
+                    Framer::OctetCounting(
+                        OctetCountingDecoder::new_with_max_length(self.max_length)
+                            .with_framing(self.framing),
+                    ),
                     Deserializer::Syslog(SyslogDeserializer),
                 );
 
                 build_unix_stream_source(
                     path,
-                    socket_file_mode,
+                    socket_file_mode.into(),
                     decoder,
                     move |events, host| handle_events(events, &host_key, host),
                     cx.shutdown,
@@ -225,6 +249,7 @@ impl SourceConfig for SyslogConfig {
 #[derive(Debug, Clone)]
 struct SyslogTcpSource {
     max_length: usize,
+    framing: OctetCountingFraming,
     host_key: String,
 }
 
@@ -236,7 +261,10 @@ impl TcpSource for SyslogTcpSource {
 
     fn decoder(&self) -> Self::Decoder {
         Decoder::new(
-            Framer::OctetCounting(OctetCountingDecoder::new_with_max_length(self.max_length)),
+            Framer::OctetCounting(
+                OctetCountingDecoder::new_with_max_length(self.max_length)
+                    .with_framing(self.framing),
+            ),
             Deserializer::Syslog(SyslogDeserializer),
         )
     }
@@ -255,63 +283,75 @@ pub fn udp(
     _max_length: usize,
     host_key: String,
     receive_buffer_bytes: Option<usize>,
+    sockets: usize,
     shutdown: ShutdownSignal,
-    mut out: SourceSender,
+    out: SourceSender,
 ) -> super::Source {
     Box::pin(async move {
-        let socket = UdpSocket::bind(&addr)
-            .await
+        let sockets = udp::bind_sockets(addr, sockets, receive_buffer_bytes)
             .expect("Failed to bind to UDP listener socket");
 
-        if let Some(receive_buffer_bytes) = receive_buffer_bytes {
-            if let Err(error) = udp::set_receive_buffer_size(&socket, receive_buffer_bytes) {
-                warn!(message = "Failed configuring receive buffer size on UDP socket.", %error);
-            }
-        }
-
         info!(
             message = "Listening.",
             addr = %addr,
-            r#type = "udp"
+            r#type = "udp",
+            sockets = sockets.len(),
         );
 
-        let mut stream = UdpFramed::new(
-            socket,
-            Decoder::new(
-                Framer::Bytes(BytesDecoder::new()),
-                Deserializer::Syslog(SyslogDeserializer),
-            ),
-        )
-        .take_until(shutdown)
-        .filter_map(|frame| {
-            let host_key = host_key.clone();
-            async move {
-                match frame {
-                    Ok(((mut events, _byte_size), received_from)) => {
-                        let received_from = received_from.ip().to_string().into();
-                        handle_events(&mut events, &host_key, Some(received_from));
-                        Some(events.remove(0))
-                    }
-                    Err(error) => {
-                        emit!(SyslogUdpReadError { error });
-                        None
-                    }
-                }
-            }
+        udp::receive_on_sockets(sockets, |socket| {
+            receive_udp(socket, host_key.clone(), shutdown.clone(), out.clone())
         })
-        .boxed();
+        .await
+    })
+}
 
-        match out.send_event_stream(&mut stream).await {
-            Ok(()) => {
-                debug!("Finished sending.");
-                Ok(())
-            }
-            Err(error) => {
-                error!(message = "Error sending line.", %error);
-                Err(())
+async fn receive_udp(
+    socket: UdpSocket,
+    host_key: String,
+    shutdown: ShutdownSignal,
+    mut out: SourceSender,
+) -> Result<(), ()> {
+    let mut stream = UdpFramed::new(
+        socket,
+        Decoder::new(
+            Framer::Bytes(BytesDecoder::new()),
+            Deserializer::Syslog(SyslogDeserializer),
+        ),
+    )
+    .take_until(shutdown)
+    .filter_map(|frame| {
+        let host_key = host_key.clone();
+        async move {
+            match frame {
+                Ok(((mut events, byte_size), received_from)) => {
+                    // The whole datagram is a single frame.
+                    emit!(SocketBytesReceived {
+                        mode: SocketMode::Udp,
+                        byte_size,
+                    });
+                    let received_from = received_from.ip().to_string().into();
+                    handle_events(&mut events, &host_key, Some(received_from));
+                    Some(events.remove(0))
+                }
+                Err(error) => {
+                    emit!(SyslogUdpReadError { error });
+                    None
+                }
             }
         }
     })
+    .boxed();
+
+    match out.send_event_stream(&mut stream).await {
+        Ok(()) => {
+            debug!("Finished sending.");
+            Ok(())
+        }
+        Err(error) => {
+            error!(message = "Error sending line.", %error);
+            Err(())
+        }
+    }
 }
 
 fn handle_events(events: &mut [Event], host_key: &str, default_host: Option<Bytes>) {
@@ -404,6 +444,20 @@ mod test {
         )
         .unwrap();
         assert!(matches!(config.mode, Mode::Tcp { .. }));
+        assert_eq!(config.framing, OctetCountingFraming::Auto);
+    }
+
+    #[test]
+    fn config_tcp_with_framing() {
+        let config: SyslogConfig = toml::from_str(
+            r#"
+            mode = "tcp"
+            address = "127.0.0.1:1235"
+            framing = "newline_delimited"
+          "#,
+        )
+        .unwrap();
+        assert_eq!(config.framing, OctetCountingFraming::NewlineDelimited);
     }
 
     #[test]
@@ -68,8 +68,13 @@ impl CompressionScheme {
 
 enum State {
     WaitingForHeader,
-    Forward { overall_len: usize },
-    Decompress { remaining: usize },
+    Forward {
+        overall_len: usize,
+    },
+    Decompress {
+        remaining: usize,
+        overall_len: usize,
+    },
 }
 
 impl Default for State {
@@ -138,6 +143,7 @@ async fn drive_body_decompression(
 
                         state = State::Decompress {
                             remaining: message_len,
+                            overall_len: GRPC_MESSAGE_HEADER_LEN + message_len,
                         };
                     } else {
                         let overall_len = GRPC_MESSAGE_HEADER_LEN + message_len;
@@ -160,7 +166,10 @@ async fn drive_body_decompression(
 
                     break Some(message);
                 }
-                State::Decompress { ref mut remaining } => {
+                State::Decompress {
+                    ref mut remaining,
+                    overall_len,
+                } => {
                     if *remaining > 0 {
                         // We're waiting for `remaining` more bytes to feed to the decompressor before we finalize it and
                         // generate our new chunk of data. We might have data in our internal buffer, so try and drain that
@@ -197,7 +206,8 @@ async fn drive_body_decompression(
                                 "reached impossible error during decompressor finalization",
                             )
                         })?;
-                        bytes_received += buf.len();
+                        // The bytes received are the compressed bytes, as read from the wire.
+                        bytes_received += overall_len;
 
                         // Write the length of our decompressed message in the pre-allocated slot for the message's length prefix.
                         let message_len_actual = buf.len() - GRPC_MESSAGE_HEADER_LEN;
@@ -1,21 +1,29 @@
 use crate::{
+    internal_events::TcpSocketTlsConnectionError,
     shutdown::{ShutdownSignal, ShutdownSignalToken},
-    tls::MaybeTlsSettings,
+    tls::{self, MaybeTlsIncomingStream, MaybeTlsListener, MaybeTlsSettings},
 };
-use futures::FutureExt;
+use futures::{future, FutureExt, Stream, StreamExt};
 use http::{Request, Response};
-use hyper::Body;
-use std::{convert::Infallible, net::SocketAddr};
+use hyper::{service::make_service_fn, Body};
+use std::{convert::Infallible, net::SocketAddr, time::Duration};
+use tokio::net::TcpStream;
 use tonic::{
     body::BoxBody,
     transport::server::{NamedService, Server},
 };
-use tower::Service;
+use tower::{Layer, Service};
 use tracing::{Instrument, Span};
 
 mod decompression;
 pub use self::decompression::{DecompressionAndMetrics, DecompressionAndMetricsLayer};
 
+/// The time allowed to clients to complete their TLS handshake.
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The number of TLS handshakes done concurrently, before accepting more connections.
+const MAX_CONCURRENT_HANDSHAKES: usize = 128;
+
 pub async fn run_grpc_server<S>(
     address: SocketAddr,
     tls_settings: MaybeTlsSettings,
@@ -33,16 +41,17 @@ where
     let span = Span::current();
     let (tx, rx) = tokio::sync::oneshot::channel::<ShutdownSignalToken>();
     let listener = tls_settings.bind(&address).await?;
-    let stream = listener.accept_stream();
+    let stream = accept_handshaken(listener);
 
     info!(message = "Building gRPC server.", address = %address);
 
     Server::builder()
         .trace_fn(move |_| span.clone())
         // This layer explicitly decompresses payloads, if compressed, and reports the number of message bytes we've
-        // received if the message is processed successfully, aka `BytesReceived`. We do this because otherwise the only
-        // access we have is either the event-specific bytes (the in-memory representation) or the raw bytes over the
-        // wire prior to decompression... and if that case, any bytes at all, not just the ones we successfully process.
+        // received over the wire, prior to decompression, if the message is processed successfully, aka
+        // `BytesReceived`. We do this because otherwise the only access we have is either the event-specific bytes (the
+        // in-memory representation) or the raw bytes over the wire... and in that case, any bytes at all, not just the
+        // ones we successfully process.
         //
         // The weaving of `tonic`, `axum`, `tower`, and `hyper` is fairly complex and there currently exists no way to
         // use independent `tower` layers when the request body itself (the body type, not the actual bytes) must be
@@ -58,3 +67,73 @@ where
 
     Ok(())
 }
+
+/// Runs a gRPC server for a service which routes the requests of its methods itself, rather than
+/// being one of the services generated by `tonic`, whose names are known at compile time.
+pub async fn run_grpc_service<S>(
+    address: SocketAddr,
+    tls_settings: MaybeTlsSettings,
+    service: S,
+    shutdown: ShutdownSignal,
+) -> crate::Result<()>
+where
+    S: Service<Request<Body>, Response = Response<BoxBody>, Error = Infallible>
+        + Clone
+        + Send
+        + 'static,
+    S::Future: Send + 'static,
+{
+    let span = Span::current();
+    let (tx, rx) = tokio::sync::oneshot::channel::<ShutdownSignalToken>();
+    let listener = tls_settings.bind(&address).await?;
+    let stream = accept_handshaken(listener);
+
+    info!(message = "Building gRPC server.", address = %address);
+
+    // See `run_grpc_server` for why this layer is used.
+    let service = DecompressionAndMetricsLayer::default().layer(service);
+    hyper::Server::builder(hyper::server::accept::from_stream(stream))
+        .http2_only(true)
+        .serve(make_service_fn(move |_| {
+            let service = service.clone();
+            future::ok::<_, Infallible>(service)
+        }))
+        .with_graceful_shutdown(shutdown.map(|token| tx.send(token).unwrap()))
+        .instrument(span)
+        .await?;
+
+    drop(rx.await);
+
+    Ok(())
+}
+
+/// Accepts the connections of `listener`.
+///
+/// The TLS handshake is done before handing connections over to the server, so that the peer
+/// certificates are available in the connection info of the requests. Failed handshakes only drop
+/// their connection, rather than stopping the server.
+fn accept_handshaken(
+    listener: MaybeTlsListener,
+) -> impl Stream<Item = tls::Result<MaybeTlsIncomingStream<TcpStream>>> {
+    listener
+        .accept_stream()
+        .map(|result| async move {
+            let mut stream = match result {
+                Ok(stream) => stream,
+                Err(error) => return Some(Err(error)),
+            };
+            match tokio::time::timeout(HANDSHAKE_TIMEOUT, stream.handshake()).await {
+                Ok(Ok(())) => Some(Ok(stream)),
+                Ok(Err(error)) => {
+                    emit!(TcpSocketTlsConnectionError { error });
+                    None
+                }
+                Err(_) => {
+                    debug!(message = "TLS handshake timed out.", peer_addr = %stream.peer_addr());
+                    None
+                }
+            }
+        })
+        .buffer_unordered(MAX_CONCURRENT_HANDSHAKES)
+        .filter_map(future::ready)
+}
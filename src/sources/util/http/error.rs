@@ -6,6 +6,10 @@ use serde::Serialize;
 pub struct ErrorMessage {
     code: u16,
     message: String,
+    /// The number of seconds the client is told to wait before retrying, sent as `Retry-After`.
+    #[serde(skip)]
+    #[allow(unused)] // triggered by check-component-features
+    retry_after: Option<u64>,
 }
 
 #[cfg(any(
@@ -20,6 +24,7 @@ impl ErrorMessage {
         ErrorMessage {
             code: code.as_u16(),
             message,
+            retry_after: None,
         }
     }
 
@@ -38,6 +43,15 @@ impl ErrorMessage {
     pub fn message(&self) -> &str {
         self.message.as_str()
     }
+
+    pub const fn with_retry_after(mut self, secs: u64) -> Self {
+        self.retry_after = Some(secs);
+        self
+    }
+
+    pub const fn retry_after(&self) -> Option<u64> {
+        self.retry_after
+    }
 }
 
 impl Error for ErrorMessage {}
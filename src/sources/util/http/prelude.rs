@@ -1,11 +1,12 @@
-use std::{collections::HashMap, convert::TryFrom, fmt, net::SocketAddr};
+use std::{collections::HashMap, convert::TryFrom, fmt, net::SocketAddr, sync::Arc};
 
 use async_trait::async_trait;
 use bytes::Bytes;
-use futures::{FutureExt, TryFutureExt};
+use futures::{future::Either, FutureExt};
+use tokio::sync::Semaphore;
 use tracing::Span;
 use vector_core::{
-    event::{BatchNotifier, BatchStatus, BatchStatusReceiver, Event},
+    event::{BatchNotifier, BatchStatus, BatchStatusReceiver, Event, ResponseHandle},
     ByteSizeOf,
 };
 use warp::{
@@ -21,7 +22,8 @@ use warp::{
 use crate::{
     config::{AcknowledgementsConfig, SourceContext},
     internal_events::{
-        HttpBadRequest, HttpBytesReceived, HttpEventsReceived, HttpInternalError, StreamClosedError,
+        HttpBadRequest, HttpBytesReceived, HttpEventsReceived, HttpInternalError,
+        HttpRequestThrottled, StreamClosedError,
     },
     sources::http::HttpMethod,
     tls::{MaybeTlsSettings, TlsEnableableConfig},
@@ -34,6 +36,19 @@ use super::{
     error::ErrorMessage,
 };
 
+/// Limits the number of requests an HTTP source handles at the same time.
+///
+/// Requests arriving while the limit is reached are answered with `503 Service Unavailable`,
+/// instead of being held in memory until the pipeline catches up.
+#[derive(Clone, Copy, Debug)]
+pub struct RequestLimit {
+    /// The maximum number of requests handled at the same time.
+    pub max_concurrent_requests: usize,
+
+    /// The number of seconds clients are told to wait before retrying rejected requests.
+    pub retry_after_secs: u64,
+}
+
 #[async_trait]
 pub trait HttpSource: Clone + Send + Sync + 'static {
     fn build_events(
@@ -44,6 +59,23 @@ pub trait HttpSource: Clone + Send + Sync + 'static {
         path: &str,
     ) -> Result<Vec<Event>, ErrorMessage>;
 
+    /// The limit on the number of requests handled at the same time, if any.
+    fn request_limit(&self) -> Option<RequestLimit> {
+        None
+    }
+
+    /// The named output the events of requests to `path` are sent to, or `None` for the default
+    /// output.
+    fn output_name(&self, _path: &str) -> Option<String> {
+        None
+    }
+
+    /// Whether requests are answered with the response set by the components downstream of the
+    /// source, such as the `respond` transform, once all their events are processed.
+    fn pipeline_response(&self) -> bool {
+        false
+    }
+
     #[allow(clippy::too_many_arguments)]
     fn run(
         self,
@@ -61,6 +93,12 @@ pub trait HttpSource: Clone + Send + Sync + 'static {
         let auth = HttpSourceAuth::try_from(auth.as_ref())?;
         let path = path.to_owned();
         let acknowledgements = cx.do_acknowledgements(&acknowledgements);
+        let request_limit = self.request_limit().map(|limit| {
+            (
+                limit,
+                Arc::new(Semaphore::new(limit.max_concurrent_requests)),
+            )
+        });
         Ok(Box::pin(async move {
             let span = Span::current();
             let mut filter: BoxedFilter<()> = match method {
@@ -108,12 +146,40 @@ pub trait HttpSource: Clone + Send + Sync + 'static {
                           query_parameters: HashMap<String, String>| {
                         debug!(message = "Handling HTTP request.", headers = ?headers);
                         let http_path = path.as_str();
+
+                        // The permit is held until the request is answered.
+                        let permit = match &request_limit {
+                            Some((limit, semaphore)) => {
+                                match Arc::clone(semaphore).try_acquire_owned() {
+                                    Ok(permit) => Some(permit),
+                                    Err(_) => {
+                                        emit!(HttpRequestThrottled {
+                                            max_concurrent_requests: limit.max_concurrent_requests,
+                                            http_path,
+                                        });
+                                        return Either::Right(futures::future::err(
+                                            warp::reject::custom(
+                                                ErrorMessage::new(
+                                                    StatusCode::SERVICE_UNAVAILABLE,
+                                                    "Too many pending requests".to_string(),
+                                                )
+                                                .with_retry_after(limit.retry_after_secs),
+                                            ),
+                                        ));
+                                    }
+                                }
+                            }
+                            None => None,
+                        };
+
                         emit!(HttpBytesReceived {
                             byte_size: body.len(),
                             http_path,
                             protocol,
                         });
 
+                        let output = self.output_name(http_path);
+                        let pipeline_response = self.pipeline_response();
                         let events = auth
                             .is_valid(&auth_header)
                             .and_then(|()| decode(&encoding_header, body))
@@ -130,7 +196,19 @@ pub trait HttpSource: Clone + Send + Sync + 'static {
                                 events
                             });
 
-                        handle_request(events, acknowledgements, cx.out.clone())
+                        Either::Left(
+                            handle_request(
+                                events,
+                                acknowledgements,
+                                pipeline_response,
+                                output,
+                                cx.out.clone(),
+                            )
+                            .map(move |result| {
+                                drop(permit);
+                                result
+                            }),
+                        )
                     },
                 )
                 .with(warp::trace(move |_info| span.clone()));
@@ -139,7 +217,15 @@ pub trait HttpSource: Clone + Send + Sync + 'static {
             let routes = svc.or(ping).recover(|r: Rejection| async move {
                 if let Some(e_msg) = r.find::<ErrorMessage>() {
                     let json = warp::reply::json(e_msg);
-                    Ok(warp::reply::with_status(json, e_msg.status_code()))
+                    let reply = warp::reply::with_status(json, e_msg.status_code());
+                    Ok(match e_msg.retry_after() {
+                        Some(secs) => Box::new(warp::reply::with_header(
+                            reply,
+                            warp::http::header::RETRY_AFTER,
+                            secs.to_string(),
+                        )) as Box<dyn warp::Reply>,
+                        None => Box::new(reply),
+                    })
                 } else {
                     //other internal error - will return 500 internal server error
                     emit!(HttpInternalError {
@@ -176,22 +262,45 @@ impl warp::reject::Reject for RejectShuttingDown {}
 async fn handle_request(
     events: Result<Vec<Event>, ErrorMessage>,
     acknowledgements: bool,
+    pipeline_response: bool,
+    output: Option<String>,
     mut out: SourceSender,
-) -> Result<impl warp::Reply, Rejection> {
+) -> Result<Box<dyn warp::Reply>, Rejection> {
     match events {
         Ok(mut events) => {
             let receiver = BatchNotifier::maybe_apply_to(acknowledgements, &mut events);
+            let response_receiver = pipeline_response.then(|| {
+                let (handle, receiver) = ResponseHandle::new_with_receiver();
+                for event in &mut events {
+                    event.metadata_mut().set_response_handle(handle.clone());
+                }
+                receiver
+            });
 
             let count = events.len();
-            out.send_batch(events)
-                .map_err(move |error: crate::source_sender::ClosedError| {
-                    // can only fail if receiving end disconnected, so we are shutting down,
-                    // probably not gracefully.
-                    emit!(StreamClosedError { error, count });
-                    warp::reject::custom(RejectShuttingDown)
-                })
-                .and_then(|_| handle_batch_status(receiver))
-                .await
+            let sent = match &output {
+                Some(output) => out.send_batch_named(output, events).await,
+                None => out.send_batch(events).await,
+            };
+            if let Err(error) = sent {
+                // can only fail if receiving end disconnected, so we are shutting down,
+                // probably not gracefully.
+                emit!(StreamClosedError { error, count });
+                return Err(warp::reject::custom(RejectShuttingDown));
+            }
+            let reply = handle_batch_status(receiver).await?;
+            match response_receiver {
+                Some(response_receiver) => match response_receiver.await {
+                    Some(response) => {
+                        let mut reply = warp::http::Response::new(hyper::Body::from(response.body));
+                        *reply.status_mut() = StatusCode::from_u16(response.status)
+                            .unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+                        Ok(Box::new(reply))
+                    }
+                    None => Ok(Box::new(reply)),
+                },
+                None => Ok(Box::new(reply)),
+            }
         }
         Err(error) => {
             emit!(HttpBadRequest::new(error.code(), error.message()));
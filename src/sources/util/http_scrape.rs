@@ -17,6 +17,7 @@ use std::{collections::HashMap, future::ready};
 use tokio_stream::wrappers::IntervalStream;
 
 use crate::{
+    coordination::Coordinator,
     http::{Auth, HttpClient},
     internal_events::{
         EndpointBytesReceived, HttpScrapeEventsReceived, HttpScrapeHttpError,
@@ -42,6 +43,8 @@ pub(crate) struct GenericHttpScrapeInputs {
     pub tls: TlsSettings,
     pub proxy: ProxyConfig,
     pub shutdown: ShutdownSignal,
+    /// Coordinator splitting the URLs between the instances of the source, if any
+    pub coordinator: Option<Coordinator>,
 }
 
 /// The default interval to scrape the http endpoint if none is configured.
@@ -95,6 +98,7 @@ pub(crate) fn build_url(uri: &Uri, query: &HashMap<String, Vec<String>>) -> Uri
 }
 
 /// Scrapes one or more urls at an interval.
+///   - Only the urls owned by this instance are scraped, if coordinated with other instances.
 ///   - The HTTP request is built per the options in provided generic inputs.
 ///   - The HTTP response is decoded/parsed into events by the specific context.
 ///   - The events are then sent to the output stream.
@@ -106,11 +110,29 @@ pub(crate) async fn http_scrape<
     context_builder: B,
     mut out: SourceSender,
 ) -> Result<(), ()> {
+    let shards = inputs.coordinator.map(|coordinator| {
+        let shards = coordinator.shards();
+        tokio::spawn(coordinator.run(inputs.shutdown.clone()));
+        shards
+    });
+
     let mut stream = IntervalStream::new(tokio::time::interval(Duration::from_secs(
         inputs.interval_secs,
     )))
     .take_until(inputs.shutdown)
-    .map(move |_| stream::iter(inputs.urls.clone()))
+    .map(move |_| {
+        let urls = inputs
+            .urls
+            .iter()
+            .filter(|url| {
+                shards
+                    .as_ref()
+                    .map_or(true, |shards| shards.owns(&url.to_string()))
+            })
+            .cloned()
+            .collect::<Vec<_>>();
+        stream::iter(urls)
+    })
     .flatten()
     .map(move |url| {
         // Building the HttpClient should not fail as it is just setting up the client with the
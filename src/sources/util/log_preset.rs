@@ -0,0 +1,495 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs::File,
+    io::{BufRead, BufReader, Read},
+    sync::{Arc, Mutex},
+};
+
+use chrono::{DateTime, Local, NaiveDateTime, TimeZone, Utc};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use vector_config::configurable_component;
+
+use crate::{
+    config::log_schema,
+    event::{LogEvent, Value},
+};
+
+/// A well-known log format the lines of files are parsed from.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum LogPreset {
+    /// The [W3C extended log file format][w3c], as written by IIS and many web servers and
+    /// proxies.
+    ///
+    /// The fields of each line are named after the last `#Fields` directive of its file, in snake
+    /// case, such as `cs_uri_stem` for `cs-uri-stem`. The `date` and `time` fields are parsed into
+    /// the timestamp of the event, and the status, byte count, port and `time-taken` fields into
+    /// integers. Fields whose value is `-` are left out, and directives aren't emitted.
+    ///
+    /// [w3c]: https://www.w3.org/TR/WD-logfile.html
+    W3c,
+
+    /// The debug log of the Windows DNS server.
+    ///
+    /// Packet lines are parsed into fields, such as `remote_ip`, `question_type` and
+    /// `question_name`. The other lines with a timestamp are emitted as their `message`, and the
+    /// header of the file isn't emitted.
+    WindowsDns,
+}
+
+/// Parses the lines of files written in the format of a [`LogPreset`] into events.
+///
+/// Clones share what is known of the files, so that it can be released once they're not read
+/// anymore.
+#[derive(Clone)]
+pub struct PresetParser {
+    preset: LogPreset,
+    /// The fields of the lines of each W3C file, by path, as set by its last `#Fields` directive,
+    /// or `None` for the files found to have none.
+    w3c_fields: Arc<Mutex<HashMap<String, Option<Vec<String>>>>>,
+}
+
+impl PresetParser {
+    pub fn new(preset: LogPreset) -> Self {
+        Self {
+            preset,
+            w3c_fields: Arc::default(),
+        }
+    }
+
+    /// Reads what is needed to parse the line starting at `offset` in `file`, off the runtime.
+    ///
+    /// The directives of W3C files read from a checkpoint were read before Vector restarted, so
+    /// their last `#Fields` directive is read from the file, once.
+    pub async fn prepare(&self, file: &str, offset: u64, line: &[u8]) {
+        if self.preset != LogPreset::W3c
+            || line.starts_with(b"#")
+            || line.iter().all(u8::is_ascii_whitespace)
+            || self.fields().contains_key(file)
+        {
+            return;
+        }
+
+        let path = file.to_owned();
+        let fields = tokio::task::spawn_blocking(move || read_w3c_fields(&path, offset))
+            .await
+            .unwrap_or_default();
+        self.fields().entry(file.to_owned()).or_insert(fields);
+    }
+
+    /// Parses a line of `file`, returning `None` for the lines which aren't events, such as
+    /// directives and headers.
+    pub fn parse(&self, file: &str, line: &[u8]) -> Option<LogEvent> {
+        let line = String::from_utf8_lossy(line);
+        let line = line.trim_end_matches('\r');
+        match self.preset {
+            LogPreset::W3c => self.parse_w3c(file, line),
+            LogPreset::WindowsDns => parse_windows_dns(line),
+        }
+    }
+
+    /// Releases what is known of `file`, once it's not read anymore.
+    pub fn forget(&self, file: &str) {
+        self.fields().remove(file);
+    }
+
+    fn fields(&self) -> std::sync::MutexGuard<'_, HashMap<String, Option<Vec<String>>>> {
+        self.w3c_fields.lock().expect("W3C fields lock poisoned")
+    }
+
+    fn parse_w3c(&self, file: &str, line: &str) -> Option<LogEvent> {
+        if let Some(directive) = line.strip_prefix('#') {
+            if let Some(fields) = directive.strip_prefix("Fields:") {
+                self.fields()
+                    .insert(file.to_owned(), Some(parse_w3c_fields(fields)));
+            }
+            return None;
+        }
+        if line.trim().is_empty() {
+            return None;
+        }
+
+        Some(match self.fields().get(file) {
+            Some(Some(fields)) => w3c_event(fields, line),
+            _ => message_event(line, None),
+        })
+    }
+}
+
+fn parse_w3c_fields(fields: &str) -> Vec<String> {
+    fields.split_whitespace().map(snake_case).collect()
+}
+
+/// Reads the last `#Fields` directive of a file before `offset`.
+fn read_w3c_fields(file: &str, offset: u64) -> Option<Vec<String>> {
+    let reader = BufReader::new(File::open(file).ok()?.take(offset));
+    reader
+        .split(b'\n')
+        .filter_map(Result::ok)
+        .filter_map(|line| {
+            line.strip_prefix(b"#Fields:")
+                .map(|fields| parse_w3c_fields(&String::from_utf8_lossy(fields)))
+        })
+        .last()
+}
+
+/// Converts a W3C field name, such as `cs-uri-stem` or `cs(User-Agent)`, to snake case.
+fn snake_case(name: &str) -> String {
+    name.split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_ascii_lowercase)
+        .collect::<Vec<_>>()
+        .join("_")
+}
+
+fn w3c_event(fields: &[String], line: &str) -> LogEvent {
+    let mut map = BTreeMap::new();
+    for (field, value) in fields.iter().zip(split_w3c_values(line)) {
+        if value == "-" {
+            continue;
+        }
+        let value = if is_w3c_integer_field(field) {
+            value
+                .parse::<i64>()
+                .map(Value::from)
+                .unwrap_or_else(|_| Value::from(value))
+        } else {
+            Value::from(value)
+        };
+        map.insert(field.clone(), value);
+    }
+
+    let timestamp = match (map.get("date"), map.get("time")) {
+        (Some(Value::Bytes(date)), Some(Value::Bytes(time))) => NaiveDateTime::parse_from_str(
+            &format!(
+                "{} {}",
+                String::from_utf8_lossy(date),
+                String::from_utf8_lossy(time)
+            ),
+            "%Y-%m-%d %H:%M:%S%.f",
+        )
+        .ok()
+        // W3C times are in UTC.
+        .map(|timestamp| DateTime::<Utc>::from_utc(timestamp, Utc)),
+        _ => None,
+    };
+    if timestamp.is_some() {
+        map.remove("date");
+        map.remove("time");
+    }
+
+    let mut event = LogEvent::from(map);
+    event.insert(
+        log_schema().timestamp_key(),
+        timestamp.unwrap_or_else(Utc::now),
+    );
+    event
+}
+
+fn is_w3c_integer_field(field: &str) -> bool {
+    field.ends_with("status")
+        || field.ends_with("bytes")
+        || field.ends_with("port")
+        || field == "time_taken"
+}
+
+/// Splits the values of a W3C line, separated by spaces, some of them quoted with doubled quotes
+/// as escapes.
+fn split_w3c_values(line: &str) -> Vec<String> {
+    let mut values = Vec::new();
+    let mut chars = line.chars().peekable();
+    loop {
+        while chars.next_if(|c| *c == ' ' || *c == '\t').is_some() {}
+        let mut value = String::new();
+        match chars.peek() {
+            None => return values,
+            Some('"') => {
+                chars.next();
+                while let Some(c) = chars.next() {
+                    if c == '"' && chars.next_if_eq(&'"').is_none() {
+                        break;
+                    }
+                    value.push(c);
+                }
+            }
+            Some(_) => {
+                while let Some(c) = chars.next_if(|c| *c != ' ' && *c != '\t') {
+                    value.push(c);
+                }
+            }
+        }
+        values.push(value);
+    }
+}
+
+static DNS_LINE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"(?x)
+        ^(?P<date>\d{1,4}[/.-]\d{1,2}[/.-]\d{1,4})
+        \ (?P<time>\d{1,2}:\d{2}:\d{2}(?:\ [AP]M)?)
+        \ (?P<rest>.*)$",
+    )
+    .unwrap()
+});
+
+static DNS_PACKET: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"(?x)
+        ^(?P<thread_id>[0-9A-Fa-f]+)\ PACKET\s+(?P<packet_id>[0-9A-Fa-f]+)
+        \ (?P<protocol>UDP|TCP)\ (?P<direction>Snd|Rcv)\ (?P<remote_ip>\S+)\s+(?P<xid>[0-9A-Fa-f]+)
+        \ (?P<response>[\ R])\ (?P<opcode>\S)
+        \ \[(?P<flags_hex>[0-9A-Fa-f]+)\s+(?P<flags>[A-Z]*)\s+(?P<response_code>\w+)\]
+        \s+(?P<question_type>\S+)\s+(?P<question_name>\S+)",
+    )
+    .unwrap()
+});
+
+fn parse_windows_dns(line: &str) -> Option<LogEvent> {
+    // The header of the file, describing its fields, doesn't start with a timestamp.
+    let captures = DNS_LINE.captures(line)?;
+    let timestamp = parse_windows_dns_timestamp(&captures["date"], &captures["time"]);
+    let rest = &captures["rest"];
+
+    let packet = match DNS_PACKET.captures(rest) {
+        Some(packet) => packet,
+        None => {
+            let message = if timestamp.is_some() { rest } else { line };
+            return Some(message_event(message, timestamp));
+        }
+    };
+    let mut map = BTreeMap::new();
+    for field in [
+        "thread_id",
+        "packet_id",
+        "protocol",
+        "remote_ip",
+        "xid",
+        "opcode",
+        "flags",
+        "response_code",
+        "question_type",
+    ] {
+        map.insert(field.to_owned(), Value::from(&packet[field]));
+    }
+    let direction = match &packet["direction"] {
+        "Snd" => "send",
+        _ => "receive",
+    };
+    map.insert("direction".to_owned(), Value::from(direction));
+    map.insert(
+        "response".to_owned(),
+        Value::from(&packet["response"] == "R"),
+    );
+    map.insert(
+        "question_name".to_owned(),
+        Value::from(decode_dns_name(&packet["question_name"])),
+    );
+    if timestamp.is_none() {
+        map.insert("date".to_owned(), Value::from(&captures["date"]));
+        map.insert("time".to_owned(), Value::from(&captures["time"]));
+    }
+
+    let mut event = LogEvent::from(map);
+    event.insert(
+        log_schema().timestamp_key(),
+        timestamp.unwrap_or_else(Utc::now),
+    );
+    Some(event)
+}
+
+/// Parses the timestamp of a line of the debug log, written in the local time of the server.
+///
+/// Only the date format of the `en-US` locale is known. The date and time of the lines of servers
+/// with other locales are kept in the events instead.
+fn parse_windows_dns_timestamp(date: &str, time: &str) -> Option<DateTime<Utc>> {
+    let timestamp =
+        NaiveDateTime::parse_from_str(&format!("{} {}", date, time), "%m/%d/%Y %I:%M:%S %p")
+            .ok()?;
+    Local
+        .from_local_datetime(&timestamp)
+        .earliest()
+        .map(|timestamp| timestamp.with_timezone(&Utc))
+}
+
+/// Decodes a name of the debug log, written as its labels each preceded by their length, such as
+/// `(7)example(3)com(0)`.
+fn decode_dns_name(name: &str) -> String {
+    let mut labels = Vec::new();
+    let mut rest = name;
+    while let Some(start) = rest.find('(') {
+        if start > 0 {
+            labels.push(&rest[..start]);
+        }
+        rest = match rest[start..].find(')') {
+            Some(end) => &rest[start + end + 1..],
+            None => "",
+        };
+    }
+    if !rest.is_empty() {
+        labels.push(rest);
+    }
+    labels.join(".")
+}
+
+fn message_event(message: &str, timestamp: Option<DateTime<Utc>>) -> LogEvent {
+    let mut event = LogEvent::from_str_legacy(message);
+    if let Some(timestamp) = timestamp {
+        event.insert(log_schema().timestamp_key(), timestamp);
+    }
+    event
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Seek, Write};
+
+    use super::*;
+
+    const W3C_FIELDS: &str = "#Fields: date time s-ip cs-method cs-uri-stem cs-uri-query s-port \
+                              c-ip cs(User-Agent) sc-status sc-substatus sc-win32-status time-taken";
+
+    #[test]
+    fn parses_w3c_lines_with_their_fields() {
+        let parser = PresetParser::new(LogPreset::W3c);
+        assert!(parser
+            .parse(
+                "u_ex221016.log",
+                b"#Software: Microsoft Internet Information Services 10.0"
+            )
+            .is_none());
+        assert!(parser
+            .parse("u_ex221016.log", W3C_FIELDS.as_bytes())
+            .is_none());
+
+        let event = parser
+            .parse(
+                "u_ex221016.log",
+                b"2022-10-16 08:15:42 10.0.0.4 GET /index.html - 443 203.0.113.9 \
+                  Mozilla/5.0+(Windows+NT+10.0) 200 0 0 15\r",
+            )
+            .unwrap();
+        assert_eq!(
+            event[log_schema().timestamp_key()],
+            Value::from(Utc.ymd(2022, 10, 16).and_hms(8, 15, 42))
+        );
+        assert_eq!(event["cs_method"], Value::from("GET"));
+        assert_eq!(event["cs_uri_stem"], Value::from("/index.html"));
+        assert_eq!(event["s_port"], Value::from(443));
+        assert_eq!(
+            event["cs_user_agent"],
+            Value::from("Mozilla/5.0+(Windows+NT+10.0)")
+        );
+        assert_eq!(event["sc_status"], Value::from(200));
+        assert_eq!(event["time_taken"], Value::from(15));
+        assert!(!event.contains("cs_uri_query"));
+        assert!(!event.contains("date"));
+    }
+
+    #[test]
+    fn tracks_w3c_fields_changing_mid_file() {
+        let parser = PresetParser::new(LogPreset::W3c);
+        parser.parse("a.log", b"#Fields: date time cs-method");
+        parser.parse("b.log", b"#Fields: date time sc-status");
+        parser.parse("a.log", b"#Fields: date time cs-uri-stem");
+
+        let a = parser
+            .parse("a.log", b"2022-10-16 08:15:42 /index.html")
+            .unwrap();
+        assert_eq!(a["cs_uri_stem"], Value::from("/index.html"));
+        let b = parser.parse("b.log", b"2022-10-16 08:15:42 404").unwrap();
+        assert_eq!(b["sc_status"], Value::from(404));
+    }
+
+    #[tokio::test]
+    async fn reads_w3c_fields_of_files_read_from_a_checkpoint() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("u_ex221016.log");
+        let mut file = File::create(&path).unwrap();
+        writeln!(file, "#Fields: date time cs-method").unwrap();
+        writeln!(file, "2022-10-16 08:15:42 GET").unwrap();
+        writeln!(file, "#Fields: date time cs-method sc-status").unwrap();
+        let offset = file.stream_position().unwrap();
+        writeln!(file, "2022-10-16 08:15:43 POST 201").unwrap();
+        writeln!(file, "#Fields: date time cs-uri-stem").unwrap();
+
+        let parser = PresetParser::new(LogPreset::W3c);
+        let file = path.to_str().unwrap();
+        let line = b"2022-10-16 08:15:43 POST 201";
+        parser.prepare(file, offset, line).await;
+        let event = parser.parse(file, line).unwrap();
+        assert_eq!(event["cs_method"], Value::from("POST"));
+        assert_eq!(event["sc_status"], Value::from(201));
+
+        // Files are read once, whether they have a `#Fields` directive or not.
+        std::fs::remove_file(&path).unwrap();
+        parser.prepare(file, offset, line).await;
+        assert_eq!(
+            parser.parse(file, line).unwrap()["sc_status"],
+            Value::from(201)
+        );
+
+        parser.forget(file);
+        parser.prepare(file, offset, line).await;
+        assert_eq!(parser.fields().get(file), Some(&None));
+        assert!(!parser.parse(file, line).unwrap().contains("sc_status"));
+    }
+
+    #[test]
+    fn splits_quoted_w3c_values() {
+        assert_eq!(
+            split_w3c_values(r#"a "b c" - "say ""hi""" """#),
+            vec!["a", "b c", "-", r#"say "hi""#, ""]
+        );
+    }
+
+    #[test]
+    fn parses_windows_dns_packets() {
+        let event = parse_windows_dns(
+            "10/16/2022 10:44:12 AM 0A5C PACKET  000001D3F3A6B1A0 UDP Snd 192.168.1.20    \
+             6b2b R Q [8081   DR  NOERROR] A      (7)example(3)com(0)",
+        )
+        .unwrap();
+        assert_eq!(event["thread_id"], Value::from("0A5C"));
+        assert_eq!(event["protocol"], Value::from("UDP"));
+        assert_eq!(event["direction"], Value::from("send"));
+        assert_eq!(event["remote_ip"], Value::from("192.168.1.20"));
+        assert_eq!(event["xid"], Value::from("6b2b"));
+        assert_eq!(event["response"], Value::from(true));
+        assert_eq!(event["opcode"], Value::from("Q"));
+        assert_eq!(event["flags"], Value::from("DR"));
+        assert_eq!(event["response_code"], Value::from("NOERROR"));
+        assert_eq!(event["question_type"], Value::from("A"));
+        assert_eq!(event["question_name"], Value::from("example.com"));
+        assert!(!event.contains("date"));
+
+        let query = parse_windows_dns(
+            "10/16/2022 10:44:12 AM 0A5C PACKET  000001D3F3A6B1A0 UDP Rcv 192.168.1.20    \
+             6b2b   Q [0001   D   NOERROR] AAAA   (3)www(7)example(3)com(0)",
+        )
+        .unwrap();
+        assert_eq!(query["direction"], Value::from("receive"));
+        assert_eq!(query["response"], Value::from(false));
+        assert_eq!(query["question_name"], Value::from("www.example.com"));
+    }
+
+    #[test]
+    fn skips_the_windows_dns_header() {
+        for line in [
+            "DNS Server log file creation at 10/16/2022 10:44:04 AM",
+            "",
+            "\tField #  Information         Values",
+        ] {
+            assert!(parse_windows_dns(line).is_none(), "{}", line);
+        }
+
+        let event =
+            parse_windows_dns("10/16/2022 10:44:13 AM 0A5C EVENT   The DNS server has started.")
+                .unwrap();
+        assert_eq!(
+            event[log_schema().message_key()],
+            Value::from("0A5C EVENT   The DNS server has started.")
+        );
+    }
+}
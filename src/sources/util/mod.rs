@@ -3,7 +3,11 @@ mod body_decoding;
 mod encoding_config;
 #[cfg(all(unix, feature = "sources-dnstap"))]
 pub mod framestream;
-#[cfg(any(feature = "sources-vector", feature = "sources-opentelemetry"))]
+#[cfg(any(
+    feature = "sources-grpc_server",
+    feature = "sources-opentelemetry",
+    feature = "sources-vector"
+))]
 pub mod grpc;
 #[cfg(any(
     feature = "sources-utils-http-auth",
@@ -15,6 +19,8 @@ pub mod grpc;
 mod http;
 #[cfg(any(feature = "sources-http_scrape", feature = "sources-prometheus"))]
 pub mod http_scrape;
+#[cfg(feature = "sources-file")]
+mod log_preset;
 #[cfg(any(feature = "sources-aws_sqs", feature = "sources-gcp_pubsub"))]
 mod message_decoding;
 pub mod multiline_config;
@@ -24,19 +30,25 @@ mod tcp;
 mod unix;
 #[cfg(all(unix, feature = "sources-socket"))]
 mod unix_datagram;
+#[cfg(all(unix, feature = "sources-socket"))]
+mod unix_seqpacket;
 #[cfg(all(unix, feature = "sources-utils-unix"))]
 mod unix_stream;
 mod wrappers;
 
 #[cfg(feature = "sources-file")]
 pub use encoding_config::EncodingConfig;
+#[cfg(feature = "sources-file")]
+pub use log_preset::{LogPreset, PresetParser};
 pub use multiline_config::MultilineConfig;
 #[cfg(feature = "listenfd")]
 pub use tcp::{SocketListenAddr, TcpNullAcker, TcpSource, TcpSourceAck, TcpSourceAcker};
 #[cfg(all(unix, any(feature = "sources-socket", feature = "sources-utils-unix",)))]
-pub use unix::change_socket_permissions;
+pub use unix::{change_socket_permissions, delete_socket_file, SocketFilePermissions};
 #[cfg(all(unix, feature = "sources-socket",))]
 pub use unix_datagram::build_unix_datagram_source;
+#[cfg(all(unix, feature = "sources-socket",))]
+pub use unix_seqpacket::build_unix_seqpacket_source;
 #[cfg(all(unix, feature = "sources-utils-unix",))]
 pub use unix_stream::build_unix_stream_source;
 pub use wrappers::{AfterRead, AfterReadExt};
@@ -52,9 +64,9 @@ pub use self::http::add_query_parameters;
 pub use self::http::decode;
 #[cfg(feature = "sources-utils-http-error")]
 pub use self::http::ErrorMessage;
-#[cfg(feature = "sources-utils-http-prelude")]
-pub use self::http::HttpSource;
 #[cfg(feature = "sources-utils-http-auth")]
 pub use self::http::HttpSourceAuthConfig;
+#[cfg(feature = "sources-utils-http-prelude")]
+pub use self::http::{HttpSource, RequestLimit};
 #[cfg(any(feature = "sources-aws_sqs", feature = "sources-gcp_pubsub"))]
 pub use self::message_decoding::decode_message;
@@ -1,19 +1,100 @@
 use std::os::unix::fs::PermissionsExt;
 use std::{fs, fs::remove_file, path::Path};
 
-use crate::internal_events::UnixSocketFileDeleteError;
-
-pub fn change_socket_permissions(path: &Path, perms: Option<u32>) -> crate::Result<()> {
-    if let Some(mode) = perms {
-        match fs::set_permissions(path, fs::Permissions::from_mode(mode)) {
-            Ok(_) => debug!(message = "Socket permissions updated.", permission = mode),
-            Err(e) => {
-                if let Err(error) = remove_file(path) {
-                    emit!(UnixSocketFileDeleteError { path, error });
-                }
-                return Err(Box::new(e));
-            }
+use nix::unistd::{chown, Gid, Group, Uid, User};
+
+use crate::{internal_events::UnixSocketFileDeleteError, unix::is_abstract};
+
+/// The permissions applied to the file of a Unix socket once it's bound.
+#[derive(Clone, Debug, Default)]
+pub struct SocketFilePermissions {
+    /// The file mode bits of the file.
+    pub mode: Option<u32>,
+    /// The user owning the file.
+    pub owner: Option<Uid>,
+    /// The group owning the file.
+    pub group: Option<Gid>,
+}
+
+impl SocketFilePermissions {
+    /// Resolves the `owner` and `group` of the file, which are either names or numeric IDs.
+    pub fn new(mode: Option<u32>, owner: Option<&str>, group: Option<&str>) -> crate::Result<Self> {
+        Ok(Self {
+            mode,
+            owner: owner.map(resolve_owner).transpose()?,
+            group: group.map(resolve_group).transpose()?,
+        })
+    }
+}
+
+fn resolve_owner(owner: &str) -> crate::Result<Uid> {
+    match owner.parse() {
+        Ok(uid) => Ok(Uid::from_raw(uid)),
+        Err(_) => User::from_name(owner)?
+            .map(|user| user.uid)
+            .ok_or_else(|| format!("Unknown socket file owner {:?}.", owner).into()),
+    }
+}
+
+fn resolve_group(group: &str) -> crate::Result<Gid> {
+    match group.parse() {
+        Ok(gid) => Ok(Gid::from_raw(gid)),
+        Err(_) => Group::from_name(group)?
+            .map(|group| group.gid)
+            .ok_or_else(|| format!("Unknown socket file group {:?}.", group).into()),
+    }
+}
+
+impl From<Option<u32>> for SocketFilePermissions {
+    fn from(mode: Option<u32>) -> Self {
+        Self {
+            mode,
+            ..Default::default()
         }
     }
+}
+
+/// Applies `permissions` to the file of the socket bound to `path`, deleting the file if they
+/// can't be applied. Sockets in the abstract namespace have no file, and are left unchanged.
+pub fn change_socket_permissions(
+    path: &Path,
+    permissions: &SocketFilePermissions,
+) -> crate::Result<()> {
+    if is_abstract(path) {
+        return Ok(());
+    }
+
+    let result = set_socket_permissions(path, permissions);
+    if result.is_err() {
+        if let Err(error) = remove_file(path) {
+            emit!(UnixSocketFileDeleteError { path, error });
+        }
+    }
+    result
+}
+
+fn set_socket_permissions(path: &Path, permissions: &SocketFilePermissions) -> crate::Result<()> {
+    if let Some(mode) = permissions.mode {
+        fs::set_permissions(path, fs::Permissions::from_mode(mode))?;
+        debug!(message = "Socket permissions updated.", permission = mode);
+    }
+    if permissions.owner.is_some() || permissions.group.is_some() {
+        chown(path, permissions.owner, permissions.group)?;
+        debug!(
+            message = "Socket ownership updated.",
+            owner = ?permissions.owner,
+            group = ?permissions.group,
+        );
+    }
     Ok(())
 }
+
+/// Deletes the file of the socket bound to `path`, if it isn't in the abstract namespace.
+pub fn delete_socket_file(path: &Path) {
+    if is_abstract(path) {
+        return;
+    }
+    if let Err(error) = remove_file(path) {
+        emit!(UnixSocketFileDeleteError { path, error });
+    }
+}
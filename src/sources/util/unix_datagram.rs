@@ -1,10 +1,9 @@
-use std::{fs::remove_file, path::PathBuf};
+use std::path::PathBuf;
 
 use bytes::{Bytes, BytesMut};
 use codecs::StreamDecodingError;
-use futures::StreamExt;
 use tokio::net::UnixDatagram;
-use tokio_util::codec::FramedRead;
+use tokio_util::codec::Decoder as _;
 use tracing::field;
 use vector_common::internal_event::{ByteSize, BytesReceived, InternalEventHandle as _, Protocol};
 use vector_core::ByteSizeOf;
@@ -12,13 +11,11 @@ use vector_core::ByteSizeOf;
 use crate::{
     codecs::Decoder,
     event::Event,
-    internal_events::{
-        SocketEventsReceived, SocketMode, SocketReceiveError, StreamClosedError,
-        UnixSocketFileDeleteError,
-    },
+    internal_events::{SocketEventsReceived, SocketMode, SocketReceiveError, StreamClosedError},
     shutdown::ShutdownSignal,
-    sources::util::change_socket_permissions,
+    sources::util::{change_socket_permissions, delete_socket_file, SocketFilePermissions},
     sources::Source,
+    unix::socket_path,
     SourceSender,
 };
 
@@ -28,7 +25,7 @@ use crate::{
 /// syslog source).
 pub fn build_unix_datagram_source(
     listen_path: PathBuf,
-    socket_file_mode: Option<u32>,
+    permissions: SocketFilePermissions,
     max_length: usize,
     decoder: Decoder,
     handle_events: impl Fn(&mut [Event], Option<Bytes>) + Clone + Send + Sync + 'static,
@@ -36,21 +33,16 @@ pub fn build_unix_datagram_source(
     out: SourceSender,
 ) -> crate::Result<Source> {
     Ok(Box::pin(async move {
-        let socket = UnixDatagram::bind(&listen_path).expect("Failed to bind to datagram socket");
+        let bind_path = socket_path(&listen_path);
+        let socket = UnixDatagram::bind(&bind_path).expect("Failed to bind to datagram socket");
         info!(message = "Listening.", path = ?listen_path, r#type = "unix_datagram");
 
-        change_socket_permissions(&listen_path, socket_file_mode)
+        change_socket_permissions(&bind_path, &permissions)
             .expect("Failed to set socket permissions");
 
         let result = listen(socket, max_length, decoder, shutdown, handle_events, out).await;
 
-        // Delete socket file.
-        if let Err(error) = remove_file(&listen_path) {
-            emit!(UnixSocketFileDeleteError {
-                path: &listen_path,
-                error
-            });
-        }
+        delete_socket_file(&bind_path);
 
         result
     }))
@@ -80,7 +72,10 @@ async fn listen(
 
                 bytes_received.emit(ByteSize(byte_size));
 
-                let payload = buf.split_to(byte_size);
+                // The datagram is copied out of the receive buffer into one of its own size, which
+                // the frames are sliced out of, so the events don't keep the whole receive buffer
+                // alive.
+                let mut payload = BytesMut::from(&buf[..byte_size]);
 
                 let span = info_span!("datagram");
                 let path = address.as_pathname().map(|e| e.to_owned()).map(|path| {
@@ -91,11 +86,11 @@ async fn listen(
                 let received_from: Option<Bytes> =
                     path.map(|p| p.to_string_lossy().into_owned().into());
 
-                let mut stream = FramedRead::new(payload.as_ref(), decoder.clone());
+                let mut decoder = decoder.clone();
 
                 loop {
-                    match stream.next().await {
-                        Some(Ok((mut events, _byte_size))) => {
+                    match decoder.decode_eof(&mut payload) {
+                        Ok(Some((mut events, _byte_size))) => {
                             emit!(SocketEventsReceived {
                                 mode: SocketMode::Unix,
                                 byte_size: events.size_of(),
@@ -109,7 +104,7 @@ async fn listen(
                                 emit!(StreamClosedError { error, count });
                             }
                         },
-                        Some(Err(error)) => {
+                        Err(error) => {
                             emit!(SocketReceiveError {
                                 mode: SocketMode::Unix,
                                 error: &error
@@ -118,7 +113,7 @@ async fn listen(
                                 break;
                             }
                         },
-                        None => break,
+                        Ok(None) => break,
                     }
                 }
             }
@@ -0,0 +1,164 @@
+use std::{path::PathBuf, time::Duration};
+
+use bytes::{Bytes, BytesMut};
+use codecs::StreamDecodingError;
+use tokio::time::sleep;
+use tokio_util::codec::Decoder as _;
+use tracing::Instrument;
+use vector_common::internal_event::{
+    ByteSize, BytesReceived, InternalEventHandle as _, Protocol, Registered,
+};
+use vector_core::ByteSizeOf;
+
+use crate::{
+    codecs::Decoder,
+    event::Event,
+    internal_events::{
+        ConnectionOpen, OpenGauge, SocketEventsReceived, SocketMode, SocketReceiveError,
+        StreamClosedError,
+    },
+    shutdown::ShutdownSignal,
+    sources::util::{change_socket_permissions, delete_socket_file, SocketFilePermissions},
+    sources::Source,
+    unix::{socket_path, UnixSeqpacket, UnixSeqpacketListener},
+    SourceSender,
+};
+
+/// Returns a `Source` object corresponding to a Unix domain sequenced packet socket.
+/// Each packet received on a connection is decoded on its own, as with datagram sockets.
+pub fn build_unix_seqpacket_source(
+    listen_path: PathBuf,
+    permissions: SocketFilePermissions,
+    max_length: usize,
+    decoder: Decoder,
+    handle_events: impl Fn(&mut [Event], Option<Bytes>) + Clone + Send + Sync + 'static,
+    mut shutdown: ShutdownSignal,
+    out: SourceSender,
+) -> crate::Result<Source> {
+    Ok(Box::pin(async move {
+        let bind_path = socket_path(&listen_path);
+        let listener =
+            UnixSeqpacketListener::bind(&bind_path).expect("Failed to bind to seqpacket socket");
+        info!(message = "Listening.", path = ?listen_path, r#type = "unix_seqpacket");
+
+        change_socket_permissions(&bind_path, &permissions)
+            .expect("Failed to set socket permissions");
+
+        let bytes_received = register!(BytesReceived::from(Protocol::UNIX));
+
+        let connection_open = OpenGauge::new();
+        loop {
+            let socket = tokio::select! {
+                socket = listener.accept() => match socket {
+                    Ok(socket) => socket,
+                    Err(error) => {
+                        error!(message = "Failed to accept socket.", %error);
+                        continue;
+                    }
+                },
+                _ = &mut shutdown => break,
+            };
+
+            let connection = Connection {
+                socket,
+                max_length,
+                decoder: decoder.clone(),
+                handle_events: handle_events.clone(),
+                bytes_received: bytes_received.clone(),
+                shutdown: shutdown.clone(),
+                out: out.clone(),
+            };
+            let connection_open = connection_open.clone();
+            tokio::spawn(
+                async move {
+                    let _open_token = connection_open.open(|count| emit!(ConnectionOpen { count }));
+                    connection.run().await;
+                    info!("Finished sending.");
+                }
+                .instrument(info_span!("connection").or_current()),
+            );
+        }
+
+        // Wait for open connections to finish
+        while connection_open.any_open() {
+            sleep(Duration::from_millis(10)).await;
+        }
+
+        delete_socket_file(&bind_path);
+
+        Ok(())
+    }))
+}
+
+struct Connection<H> {
+    socket: UnixSeqpacket,
+    max_length: usize,
+    decoder: Decoder,
+    handle_events: H,
+    bytes_received: Registered<BytesReceived>,
+    shutdown: ShutdownSignal,
+    out: SourceSender,
+}
+
+impl<H> Connection<H>
+where
+    H: Fn(&mut [Event], Option<Bytes>),
+{
+    async fn run(mut self) {
+        let mut buf = BytesMut::with_capacity(self.max_length);
+        loop {
+            buf.resize(self.max_length, 0);
+            let byte_size = tokio::select! {
+                recv = self.socket.recv(&mut buf) => match recv {
+                    // The peer closed the connection.
+                    Ok(0) => return,
+                    Ok(byte_size) => byte_size,
+                    Err(error) => {
+                        let error = codecs::decoding::Error::FramingError(error.into());
+                        emit!(SocketReceiveError {
+                            mode: SocketMode::Unix,
+                            error: &error
+                        });
+                        return;
+                    }
+                },
+                _ = &mut self.shutdown => return,
+            };
+
+            self.bytes_received.emit(ByteSize(byte_size));
+
+            // The packet is copied out of the receive buffer into one of its own size, which the
+            // frames are sliced out of, so the events don't keep the whole receive buffer alive.
+            let mut payload = BytesMut::from(&buf[..byte_size]);
+            let mut decoder = self.decoder.clone();
+            loop {
+                match decoder.decode_eof(&mut payload) {
+                    Ok(Some((mut events, _byte_size))) => {
+                        emit!(SocketEventsReceived {
+                            mode: SocketMode::Unix,
+                            byte_size: events.size_of(),
+                            count: events.len()
+                        });
+
+                        (self.handle_events)(&mut events, None);
+
+                        let count = events.len();
+                        if let Err(error) = self.out.send_batch(events).await {
+                            emit!(StreamClosedError { error, count });
+                        }
+                    }
+                    Err(error) => {
+                        emit!(SocketReceiveError {
+                            mode: SocketMode::Unix,
+                            error: &error
+                        });
+                        if !error.can_continue() {
+                            break;
+                        }
+                    }
+                    Ok(None) => break,
+                }
+            }
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use std::{fs::remove_file, path::PathBuf, time::Duration};
+use std::{path::PathBuf, time::Duration};
 
 use bytes::Bytes;
 use codecs::StreamDecodingError;
@@ -21,11 +21,12 @@ use crate::{
     event::Event,
     internal_events::{
         ConnectionOpen, OpenGauge, SocketEventsReceived, SocketMode, StreamClosedError,
-        UnixSocketError, UnixSocketFileDeleteError,
+        UnixSocketError,
     },
     shutdown::ShutdownSignal,
-    sources::util::change_socket_permissions,
+    sources::util::{change_socket_permissions, delete_socket_file, SocketFilePermissions},
     sources::Source,
+    unix::socket_path,
     SourceSender,
 };
 
@@ -35,17 +36,18 @@ use crate::{
 /// syslog source).
 pub fn build_unix_stream_source(
     listen_path: PathBuf,
-    socket_file_mode: Option<u32>,
+    permissions: SocketFilePermissions,
     decoder: Decoder,
     handle_events: impl Fn(&mut [Event], Option<Bytes>) + Clone + Send + Sync + 'static,
     shutdown: ShutdownSignal,
     out: SourceSender,
 ) -> crate::Result<Source> {
     Ok(Box::pin(async move {
-        let listener = UnixListener::bind(&listen_path).expect("Failed to bind to listener socket");
+        let bind_path = socket_path(&listen_path);
+        let listener = UnixListener::bind(&bind_path).expect("Failed to bind to listener socket");
         info!(message = "Listening.", path = ?listen_path, r#type = "unix");
 
-        change_socket_permissions(&listen_path, socket_file_mode)
+        change_socket_permissions(&bind_path, &permissions)
             .expect("Failed to set socket permssions");
 
         let bytes_received = register!(BytesReceived::from(Protocol::UNIX));
@@ -139,13 +141,7 @@ pub fn build_unix_stream_source(
             sleep(Duration::from_millis(10)).await;
         }
 
-        // Delete socket file
-        if let Err(error) = remove_file(&listen_path) {
-            emit!(UnixSocketFileDeleteError {
-                path: &listen_path,
-                error
-            });
-        }
+        delete_socket_file(&bind_path);
 
         Ok(())
     }))
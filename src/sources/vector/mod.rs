@@ -1,7 +1,12 @@
-use std::net::SocketAddr;
+use std::{collections::HashMap, fmt, net::SocketAddr, num::NonZeroU32, sync::Arc, time::Duration};
 
 use futures::TryFutureExt;
+use governor::{DefaultKeyedRateLimiter, NegativeMultiDecision, Quota, RateLimiter};
+use openssl::x509::X509;
+use prost::Message;
+use snafu::Snafu;
 use tonic::{Request, Response, Status};
+use vector_common::sensitive_string::SensitiveString;
 use vector_config::configurable_component;
 use vector_core::{
     config::LogNamespace,
@@ -14,14 +19,20 @@ use crate::{
         AcknowledgementsConfig, DataType, GenerateConfig, Output, Resource, SourceConfig,
         SourceContext,
     },
-    internal_events::{EventsReceived, StreamClosedError},
+    internal_events::{
+        EventsReceived, StreamClosedError, VectorClientEventsReceived, VectorClientRateLimited,
+        VectorClientUnauthenticatedError,
+    },
     proto::vector as proto,
     serde::bool_or_struct,
     sources::{util::grpc::run_grpc_server, Source},
-    tls::{MaybeTlsSettings, TlsEnableableConfig},
+    tls::{CertificateMetadata, MaybeTlsConnectInfo, MaybeTlsSettings, TlsEnableableConfig},
     SourceSender,
 };
 
+/// The number of rate limited clients above which the idle ones are forgotten.
+const MAX_RATE_LIMITED_CLIENTS: usize = 10_000;
+
 /// Marker type for the version two of the configuration for the `vector` source.
 #[configurable_component]
 #[derive(Clone, Debug)]
@@ -31,10 +42,188 @@ enum VectorConfigVersion {
     V2,
 }
 
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display(
+        "`auth.tls_identity` requires `tls.enabled` and `tls.verify_certificate` to be `true`"
+    ))]
+    UnverifiedTlsIdentity,
+    #[snafu(display("`rate_limit.threshold` and `rate_limit.window_secs` must be non-zero"))]
+    NonZeroRateLimit,
+}
+
+/// Client authentication of the `vector` source.
+///
+/// Requests of clients that aren't authenticated by any of the configured methods are rejected
+/// with the `UNAUTHENTICATED` status.
+#[configurable_component]
+#[derive(Clone, Debug, Default)]
+#[serde(deny_unknown_fields)]
+pub struct ClientAuthConfig {
+    /// Tokens accepted from clients, keyed by the identity of the client they authenticate.
+    ///
+    /// Clients send their token in the `authorization` metadata of their requests, as
+    /// `Bearer <token>`. The `vector` sink does so when its `auth_token` option is set.
+    #[serde(default)]
+    tokens: HashMap<String, SensitiveString>,
+
+    /// Whether or not clients without a token are authenticated by their TLS client certificate.
+    ///
+    /// The identity of these clients is the common name (CN) of their certificate. This requires
+    /// `tls.enabled` and `tls.verify_certificate`, so that certificates are verified against the
+    /// configured CA.
+    #[serde(default)]
+    tls_identity: bool,
+}
+
+/// Per-client rate limit of the `vector` source.
+///
+/// Clients are identified by their authenticated identity, or by their IP address when `auth` isn't
+/// configured. Requests of clients that exceeded their rate limit are rejected with the
+/// `RESOURCE_EXHAUSTED` status, which the `vector` sink retries.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct ClientRateLimitConfig {
+    /// The number of events each client is allowed to send per configured `window_secs`.
+    ///
+    /// Requests of more events than this are rejected with the `INVALID_ARGUMENT` status, so this
+    /// must be at least the `batch.max_events` of the sinks sending to this source.
+    threshold: u32,
+
+    /// The time window in which the configured `threshold` is applied, in seconds.
+    #[serde(default = "default_window_secs")]
+    window_secs: f64,
+}
+
+const fn default_window_secs() -> f64 {
+    1.0
+}
+
+/// Clients accepted by the source, keyed by their token.
+#[derive(Debug)]
+struct ClientAuth {
+    identities: HashMap<String, String>,
+    tls_identity: bool,
+}
+
+impl ClientAuth {
+    fn new(config: &ClientAuthConfig) -> Self {
+        Self {
+            identities: config
+                .tokens
+                .iter()
+                .map(|(identity, token)| (token.inner().to_owned(), identity.clone()))
+                .collect(),
+            tls_identity: config.tls_identity,
+        }
+    }
+
+    /// Gets the identity of the client sending a request, if it's authenticated.
+    fn identify<T>(&self, request: &Request<T>) -> Option<String> {
+        let token = request
+            .metadata()
+            .get(http::header::AUTHORIZATION.as_str())
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "));
+
+        match token {
+            Some(token) => self.identities.get(token.trim()).cloned(),
+            None if self.tls_identity => peer_common_name(request),
+            None => None,
+        }
+    }
+}
+
+fn peer_common_name<T>(request: &Request<T>) -> Option<String> {
+    let certificate = request
+        .extensions()
+        .get::<MaybeTlsConnectInfo>()?
+        .peer_certs
+        .as_ref()?
+        .first()?;
+    let certificate = X509::from_pem(certificate.get_ref()).ok()?;
+    CertificateMetadata::from(certificate).common_name
+}
+
+fn peer_addr<T>(request: &Request<T>) -> Option<SocketAddr> {
+    request
+        .extensions()
+        .get::<MaybeTlsConnectInfo>()
+        .map(|info| info.remote_addr)
+}
+
+struct ClientRateLimiter {
+    limiter: DefaultKeyedRateLimiter<String>,
+    threshold: NonZeroU32,
+}
+
+impl ClientRateLimiter {
+    fn new(config: &ClientRateLimitConfig) -> crate::Result<Self> {
+        let threshold = NonZeroU32::new(config.threshold).ok_or(BuildError::NonZeroRateLimit)?;
+        if !(config.window_secs.is_finite() && config.window_secs > 0.0) {
+            return Err(BuildError::NonZeroRateLimit.into());
+        }
+        let quota = Quota::with_period(Duration::from_secs_f64(
+            config.window_secs / threshold.get() as f64,
+        ))
+        .ok_or(BuildError::NonZeroRateLimit)?
+        .allow_burst(threshold);
+
+        Ok(Self {
+            limiter: RateLimiter::keyed(quota),
+            threshold,
+        })
+    }
+
+    fn check(&self, client: &str, count: usize) -> Result<(), Status> {
+        let count = match u32::try_from(count).ok().and_then(NonZeroU32::new) {
+            Some(count) => count,
+            None if count == 0 => return Ok(()),
+            None => return Err(self.insufficient_capacity(count)),
+        };
+
+        if self.limiter.len() >= MAX_RATE_LIMITED_CLIENTS {
+            self.limiter.retain_recent();
+        }
+
+        match self.limiter.check_key_n(&client.to_owned(), count) {
+            Ok(()) => Ok(()),
+            Err(NegativeMultiDecision::BatchNonConforming(..)) => {
+                emit!(VectorClientRateLimited {
+                    client,
+                    count: count.get() as usize,
+                });
+                Err(Status::resource_exhausted("Rate limit exceeded."))
+            }
+            Err(NegativeMultiDecision::InsufficientCapacity(_)) => {
+                Err(self.insufficient_capacity(count.get() as usize))
+            }
+        }
+    }
+
+    fn insufficient_capacity(&self, count: usize) -> Status {
+        Status::invalid_argument(format!(
+            "Request of {} events exceeds the rate limit threshold of {}.",
+            count, self.threshold
+        ))
+    }
+}
+
+impl fmt::Debug for ClientRateLimiter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ClientRateLimiter")
+            .field("threshold", &self.threshold)
+            .finish_non_exhaustive()
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct Service {
     pipeline: SourceSender,
     acknowledgements: bool,
+    auth: Option<Arc<ClientAuth>>,
+    rate_limiter: Option<Arc<ClientRateLimiter>>,
 }
 
 #[tonic::async_trait]
@@ -43,6 +232,35 @@ impl proto::Service for Service {
         &self,
         request: Request<proto::PushEventsRequest>,
     ) -> Result<Response<proto::PushEventsResponse>, Status> {
+        let client = match &self.auth {
+            Some(auth) => match auth.identify(&request) {
+                Some(client) => Some(client),
+                None => {
+                    emit!(VectorClientUnauthenticatedError {
+                        peer_addr: peer_addr(&request),
+                    });
+                    return Err(Status::unauthenticated("Invalid or missing credentials."));
+                }
+            },
+            None => None,
+        };
+
+        if let Some(rate_limiter) = &self.rate_limiter {
+            let key = client
+                .clone()
+                .or_else(|| peer_addr(&request).map(|addr| addr.ip().to_string()))
+                .unwrap_or_default();
+            rate_limiter.check(&key, request.get_ref().events.len())?;
+        }
+
+        if let Some(client) = &client {
+            emit!(VectorClientEventsReceived {
+                client,
+                count: request.get_ref().events.len(),
+                byte_size: request.get_ref().encoded_len(),
+            });
+        }
+
         let mut events: Vec<Event> = request
             .into_inner()
             .events
@@ -117,6 +335,22 @@ pub struct VectorConfig {
     #[configurable(derived)]
     #[serde(default, deserialize_with = "bool_or_struct")]
     acknowledgements: AcknowledgementsConfig,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    auth: Option<ClientAuthConfig>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    rate_limit: Option<ClientRateLimitConfig>,
+}
+
+impl VectorConfig {
+    fn verifies_certificates(&self) -> bool {
+        self.tls.as_ref().map_or(false, |tls| {
+            tls.enabled == Some(true) && tls.options.verify_certificate == Some(true)
+        })
+    }
 }
 
 impl GenerateConfig for VectorConfig {
@@ -126,6 +360,8 @@ impl GenerateConfig for VectorConfig {
             address: "0.0.0.0:6000".parse().unwrap(),
             tls: None,
             acknowledgements: Default::default(),
+            auth: None,
+            rate_limit: None,
         })
         .unwrap()
     }
@@ -136,9 +372,25 @@ impl SourceConfig for VectorConfig {
     async fn build(&self, cx: SourceContext) -> crate::Result<Source> {
         let tls_settings = MaybeTlsSettings::from_config(&self.tls, true)?;
         let acknowledgements = cx.do_acknowledgements(&self.acknowledgements);
+
+        if let Some(auth) = &self.auth {
+            if auth.tls_identity && !self.verifies_certificates() {
+                return Err(BuildError::UnverifiedTlsIdentity.into());
+            }
+        }
+        let auth = self.auth.as_ref().map(ClientAuth::new).map(Arc::new);
+        let rate_limiter = self
+            .rate_limit
+            .as_ref()
+            .map(ClientRateLimiter::new)
+            .transpose()?
+            .map(Arc::new);
+
         let service = proto::Server::new(Service {
             pipeline: cx.out,
             acknowledgements,
+            auth,
+            rate_limiter,
         })
         .accept_compressed(tonic::codec::CompressionEncoding::Gzip);
 
@@ -165,10 +417,92 @@ impl SourceConfig for VectorConfig {
 
 #[cfg(test)]
 mod test {
+    use super::*;
+
     #[test]
     fn generate_config() {
         crate::test_util::test_generate_config::<super::VectorConfig>();
     }
+
+    #[test]
+    fn identifies_clients_by_token() {
+        let config: ClientAuthConfig = toml::from_str(
+            r#"
+            tokens.edge-1 = "secret-1"
+            tokens.edge-2 = "secret-2"
+            "#,
+        )
+        .unwrap();
+        let auth = ClientAuth::new(&config);
+
+        let request = |authorization: Option<&str>| {
+            let mut request = Request::new(());
+            if let Some(authorization) = authorization {
+                request
+                    .metadata_mut()
+                    .insert("authorization", authorization.parse().unwrap());
+            }
+            request
+        };
+
+        assert_eq!(
+            auth.identify(&request(Some("Bearer secret-2"))).as_deref(),
+            Some("edge-2")
+        );
+        assert_eq!(auth.identify(&request(Some("Bearer other"))), None);
+        assert_eq!(auth.identify(&request(Some("secret-1"))), None);
+        assert_eq!(auth.identify(&request(None)), None);
+    }
+
+    #[test]
+    fn rate_limits_clients() {
+        let config: ClientRateLimitConfig = toml::from_str(
+            r#"
+            threshold = 2
+            window_secs = 60
+            "#,
+        )
+        .unwrap();
+        let rate_limiter = ClientRateLimiter::new(&config).unwrap();
+
+        assert!(rate_limiter.check("edge-1", 2).is_ok());
+        assert_eq!(
+            rate_limiter.check("edge-1", 1).unwrap_err().code(),
+            tonic::Code::ResourceExhausted
+        );
+        assert!(rate_limiter.check("edge-2", 1).is_ok());
+        assert!(rate_limiter.check("edge-2", 0).is_ok());
+        assert_eq!(
+            rate_limiter.check("edge-2", 3).unwrap_err().code(),
+            tonic::Code::InvalidArgument
+        );
+    }
+
+    #[test]
+    fn rejects_zero_rate_limit() {
+        let config: ClientRateLimitConfig = toml::from_str("threshold = 0").unwrap();
+        assert!(ClientRateLimiter::new(&config).is_err());
+
+        let config: ClientRateLimitConfig =
+            toml::from_str("threshold = 1\nwindow_secs = 0").unwrap();
+        assert!(ClientRateLimiter::new(&config).is_err());
+    }
+
+    #[tokio::test]
+    async fn tls_identity_requires_certificate_verification() {
+        let config: VectorConfig = toml::from_str(
+            r#"
+            address = "127.0.0.1:6000"
+            auth.tls_identity = true
+            "#,
+        )
+        .unwrap();
+        let (tx, _rx) = SourceSender::new_test();
+        assert!(config
+            .build(SourceContext::new_test(tx, None))
+            .await
+            .is_err());
+    }
 }
 
 #[cfg(feature = "sinks-vector")]
@@ -219,6 +553,79 @@ mod tests {
         .await;
     }
 
+    #[tokio::test]
+    async fn receive_authenticated_message() {
+        let addr = test_util::next_addr();
+        let config = format!(
+            r#"
+            address = "{}"
+            auth.tokens.edge = "secret"
+            "#,
+            addr
+        );
+        let source: VectorConfig = toml::from_str(&config).unwrap();
+
+        let (tx, rx) = SourceSender::new_test();
+        let server = source
+            .build(SourceContext::new_test(tx, None))
+            .await
+            .unwrap();
+        tokio::spawn(server);
+        test_util::wait_for_tcp(addr).await;
+
+        let config = format!(
+            r#"
+            address = "{}"
+            auth_token = "secret"
+            "#,
+            addr
+        );
+        let sink: SinkConfig = toml::from_str(&config).unwrap();
+        let (sink, _) = sink.build(SinkContext::new_test()).await.unwrap();
+
+        let (events, stream) = test_util::random_events_with_stream(100, 100, None);
+        sink.run(stream).await.unwrap();
+
+        let output = test_util::collect_ready(rx).await;
+        assert_event_data_eq!(events, output);
+    }
+
+    #[tokio::test]
+    async fn reject_unauthenticated_message() {
+        let addr = test_util::next_addr();
+        let config = format!(
+            r#"
+            address = "{}"
+            auth.tokens.edge = "secret"
+            "#,
+            addr
+        );
+        let source: VectorConfig = toml::from_str(&config).unwrap();
+
+        let (tx, rx) = SourceSender::new_test();
+        let server = source
+            .build(SourceContext::new_test(tx, None))
+            .await
+            .unwrap();
+        tokio::spawn(server);
+        test_util::wait_for_tcp(addr).await;
+
+        let config = format!(
+            r#"
+            address = "{}"
+            auth_token = "other"
+            "#,
+            addr
+        );
+        let sink: SinkConfig = toml::from_str(&config).unwrap();
+        let (sink, _) = sink.build(SinkContext::new_test()).await.unwrap();
+
+        let (_, stream) = test_util::random_events_with_stream(100, 10, None);
+        sink.run(stream).await.unwrap();
+
+        assert!(test_util::collect_ready(rx).await.is_empty());
+    }
+
     #[tokio::test]
     async fn receive_compressed_message() {
         assert_source_compliance(&SOURCE_TAGS, async {
@@ -0,0 +1,450 @@
+use chrono::{DateTime, Utc};
+use futures::StreamExt;
+use snafu::Snafu;
+use tokio::time;
+use tokio_stream::wrappers::IntervalStream;
+use vector_common::internal_event::{ByteSize, BytesReceived, InternalEventHandle as _, Protocol};
+use vector_config::configurable_component;
+use vector_core::{config::LogNamespace, ByteSizeOf};
+
+use crate::{
+    config::{log_schema, DataType, GenerateConfig, Output, SourceConfig, SourceContext},
+    event::metric::{Metric, MetricKind, MetricTags, MetricValue},
+    internal_events::{EventsReceived, StreamClosedError, WindowsPerfcountersCollectError},
+    shutdown::ShutdownSignal,
+    SourceSender,
+};
+
+mod pdh;
+
+use self::pdh::Query;
+
+/// Configuration for the `windows_perfcounters` source.
+#[configurable_component(source("windows_perfcounters"))]
+#[configurable(metadata(status = "alpha"))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct WindowsPerfcountersConfig {
+    /// The interval between collections of the counters, in seconds.
+    #[serde(default = "default_scrape_interval_secs")]
+    pub scrape_interval_secs: f64,
+
+    /// The performance counters to collect.
+    pub counters: Vec<CounterConfig>,
+
+    /// Overrides the default namespace for the metrics emitted by the source.
+    #[serde(default = "default_namespace")]
+    pub namespace: String,
+}
+
+/// A performance counter to collect.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct CounterConfig {
+    /// The path of the counter, such as `\Processor(*)\% Processor Time`.
+    ///
+    /// Counters are looked up by their English names, whatever the language of the system. An
+    /// instance of `*` collects all the instances of the counter, each tagged with its name. The
+    /// path can start with `\\<machine>` to collect the counter of another machine.
+    pub path: String,
+
+    /// The name of the metric.
+    ///
+    /// By default, the names of the object and the counter, such as
+    /// `processor_percent_processor_time`.
+    pub name: Option<String>,
+}
+
+const fn default_scrape_interval_secs() -> f64 {
+    15.0
+}
+
+fn default_namespace() -> String {
+    "windows".to_owned()
+}
+
+impl GenerateConfig for WindowsPerfcountersConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"
+            [[counters]]
+            path = '\Processor(*)\% Processor Time'
+
+            [[counters]]
+            path = '\Memory\Available Bytes'
+            "#,
+        )
+        .unwrap()
+    }
+}
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("At least one counter must be configured"))]
+    NoCounters,
+    #[snafu(display(
+        "Invalid counter path {:?}, expected `\\<object>(<instance>)\\<counter>`",
+        path
+    ))]
+    InvalidPath { path: String },
+    #[snafu(display("`scrape_interval_secs` must be a positive number of seconds"))]
+    InvalidScrapeInterval,
+}
+
+#[async_trait::async_trait]
+impl SourceConfig for WindowsPerfcountersConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
+        if self.counters.is_empty() {
+            return Err(BuildError::NoCounters.into());
+        }
+        if !(self.scrape_interval_secs > 0.0 && self.scrape_interval_secs < u64::MAX as f64) {
+            return Err(BuildError::InvalidScrapeInterval.into());
+        }
+        let counters = self
+            .counters
+            .iter()
+            .map(Counter::new)
+            .collect::<Result<Vec<_>, _>>()?;
+        let query = Query::open(counters.iter().map(|counter| counter.path.as_str()))?;
+
+        Ok(Box::pin(
+            WindowsPerfcounters {
+                counters,
+                query,
+                namespace: self.namespace.clone(),
+                interval: time::Duration::from_secs_f64(self.scrape_interval_secs),
+                out: cx.out,
+                shutdown: cx.shutdown,
+            }
+            .run(),
+        ))
+    }
+
+    fn outputs(&self, _global_log_namespace: LogNamespace) -> Vec<Output> {
+        vec![Output::default(DataType::Metric)]
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        false
+    }
+}
+
+/// The value of an instance of a counter.
+#[derive(Debug)]
+struct Sample {
+    /// The name of the instance, empty for the counters of objects without instances.
+    instance: String,
+    value: f64,
+}
+
+/// A counter to collect, and the metrics its samples are emitted as.
+#[derive(Debug)]
+struct Counter {
+    path: String,
+    name: String,
+    object: String,
+    host: Option<String>,
+}
+
+impl Counter {
+    fn new(config: &CounterConfig) -> Result<Self, BuildError> {
+        let path = CounterPath::parse(&config.path).ok_or_else(|| BuildError::InvalidPath {
+            path: config.path.clone(),
+        })?;
+        let name = config
+            .name
+            .clone()
+            .unwrap_or_else(|| metric_name(path.object, path.counter));
+        let host = match path.machine {
+            Some(machine) => Some(machine.to_owned()),
+            None => crate::get_hostname().ok(),
+        };
+
+        Ok(Self {
+            path: config.path.clone(),
+            name,
+            object: path.object.to_owned(),
+            host,
+        })
+    }
+
+    fn metrics(
+        &self,
+        namespace: &str,
+        samples: Vec<Sample>,
+        timestamp: DateTime<Utc>,
+    ) -> impl Iterator<Item = Metric> + '_ {
+        let namespace = namespace.to_owned();
+        samples.into_iter().map(move |sample| {
+            let mut tags = MetricTags::new();
+            tags.insert("object".into(), self.object.clone());
+            if !sample.instance.is_empty() {
+                tags.insert("instance".into(), sample.instance);
+            }
+            if let Some(host) = &self.host {
+                tags.insert(log_schema().host_key().into(), host.clone());
+            }
+            Metric::new(
+                self.name.clone(),
+                MetricKind::Absolute,
+                MetricValue::Gauge {
+                    value: sample.value,
+                },
+            )
+            .with_namespace(Some(namespace.clone()))
+            .with_tags(Some(tags))
+            .with_timestamp(Some(timestamp))
+        })
+    }
+}
+
+/// The parts of a counter path, `\\<machine>\<object>(<instance>)\<counter>`.
+#[derive(Debug, PartialEq)]
+struct CounterPath<'a> {
+    machine: Option<&'a str>,
+    object: &'a str,
+    instance: Option<&'a str>,
+    counter: &'a str,
+}
+
+impl<'a> CounterPath<'a> {
+    fn parse(path: &'a str) -> Option<Self> {
+        let (machine, path) = match path.strip_prefix("\\\\") {
+            Some(path) => {
+                let (machine, path) = path.split_at(path.find('\\')?);
+                if machine.is_empty() {
+                    return None;
+                }
+                (Some(machine), path)
+            }
+            None => (None, path),
+        };
+        let path = path.strip_prefix('\\')?;
+
+        // Instance names can contain backslashes, but not counter names.
+        let (object, counter) = path.rsplit_once('\\')?;
+        let (object, instance) = match object.split_once('(') {
+            Some((object, instance)) => (object, Some(instance.strip_suffix(')')?)),
+            None => (object, None),
+        };
+
+        (!object.is_empty() && !counter.is_empty() && instance != Some("")).then_some(Self {
+            machine,
+            object,
+            instance,
+            counter,
+        })
+    }
+}
+
+/// Derives the name of a metric from the names of the object and the counter, such as
+/// `processor_percent_processor_time` for the `% Processor Time` counter of the `Processor`
+/// object.
+fn metric_name(object: &str, counter: &str) -> String {
+    let mut name = String::new();
+    for word in format!("{} {}", object, counter)
+        .replace('%', " percent ")
+        .replace('#', " number ")
+        .replace('/', " per ")
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|word| !word.is_empty())
+    {
+        if !name.is_empty() {
+            name.push('_');
+        }
+        name.push_str(&word.to_ascii_lowercase());
+    }
+    name
+}
+
+struct WindowsPerfcounters {
+    counters: Vec<Counter>,
+    query: Query,
+    namespace: String,
+    interval: time::Duration,
+    out: SourceSender,
+    shutdown: ShutdownSignal,
+}
+
+impl WindowsPerfcounters {
+    async fn run(mut self) -> Result<(), ()> {
+        // The query is collected once when opened, so that rate counters have a previous value to
+        // be computed from, and so the first interval is skipped.
+        let mut interval = IntervalStream::new(time::interval_at(
+            time::Instant::now() + self.interval,
+            self.interval,
+        ))
+        .take_until(self.shutdown.clone());
+        let bytes_received = register!(BytesReceived::from(Protocol::NONE));
+        while interval.next().await.is_some() {
+            bytes_received.emit(ByteSize(0));
+            let samples = match self.query.collect() {
+                Ok(samples) => samples,
+                Err(error) => {
+                    emit!(WindowsPerfcountersCollectError {
+                        counter: None,
+                        error,
+                    });
+                    continue;
+                }
+            };
+
+            let timestamp = Utc::now();
+            let mut metrics = Vec::new();
+            for (counter, samples) in self.counters.iter().zip(samples) {
+                match samples {
+                    Ok(samples) => {
+                        metrics.extend(counter.metrics(&self.namespace, samples, timestamp))
+                    }
+                    Err(error) => emit!(WindowsPerfcountersCollectError {
+                        counter: Some(&counter.path),
+                        error,
+                    }),
+                }
+            }
+
+            let count = metrics.len();
+            emit!(EventsReceived {
+                count,
+                byte_size: metrics.size_of(),
+            });
+
+            if let Err(error) = self.out.send_batch(metrics).await {
+                emit!(StreamClosedError { error, count });
+                return Err(());
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<WindowsPerfcountersConfig>();
+    }
+
+    #[test]
+    fn parses_counter_paths() {
+        assert_eq!(
+            CounterPath::parse(r"\Processor(*)\% Processor Time"),
+            Some(CounterPath {
+                machine: None,
+                object: "Processor",
+                instance: Some("*"),
+                counter: "% Processor Time",
+            })
+        );
+        assert_eq!(
+            CounterPath::parse(r"\\web01\Memory\Available Bytes"),
+            Some(CounterPath {
+                machine: Some("web01"),
+                object: "Memory",
+                instance: None,
+                counter: "Available Bytes",
+            })
+        );
+        assert_eq!(
+            CounterPath::parse(r"\LogicalDisk(C:)\Avg. Disk sec/Read"),
+            Some(CounterPath {
+                machine: None,
+                object: "LogicalDisk",
+                instance: Some("C:"),
+                counter: "Avg. Disk sec/Read",
+            })
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_counter_paths() {
+        for path in [
+            "",
+            r"Memory\Available Bytes",
+            r"\Memory",
+            r"\Memory\",
+            r"\Processor()\% Processor Time",
+            r"\Processor(*\% Processor Time",
+            r"\\\Memory\Available Bytes",
+        ] {
+            assert_eq!(CounterPath::parse(path), None, "{}", path);
+        }
+    }
+
+    #[test]
+    fn derives_metric_names() {
+        assert_eq!(
+            metric_name("Processor", "% Processor Time"),
+            "processor_percent_processor_time"
+        );
+        assert_eq!(
+            metric_name("LogicalDisk", "Avg. Disk sec/Read"),
+            "logicaldisk_avg_disk_sec_per_read"
+        );
+        assert_eq!(
+            metric_name(".NET CLR Exceptions", "# of Exceps Thrown"),
+            "net_clr_exceptions_number_of_exceps_thrown"
+        );
+    }
+
+    #[test]
+    fn emits_a_metric_per_instance() {
+        let counter = Counter::new(&CounterConfig {
+            path: r"\\web01\Processor(*)\% Processor Time".to_owned(),
+            name: None,
+        })
+        .unwrap();
+        let timestamp = Utc::now();
+        let metrics = counter
+            .metrics(
+                "windows",
+                vec![
+                    Sample {
+                        instance: "0".to_owned(),
+                        value: 12.5,
+                    },
+                    Sample {
+                        instance: "_Total".to_owned(),
+                        value: 10.0,
+                    },
+                ],
+                timestamp,
+            )
+            .collect::<Vec<_>>();
+
+        assert_eq!(metrics.len(), 2);
+        assert_eq!(metrics[0].name(), "processor_percent_processor_time");
+        assert_eq!(metrics[0].namespace(), Some("windows"));
+        assert_eq!(metrics[0].value(), &MetricValue::Gauge { value: 12.5 });
+        assert_eq!(metrics[0].tag_value("instance"), Some("0".to_owned()));
+        assert_eq!(metrics[0].tag_value("object"), Some("Processor".to_owned()));
+        assert_eq!(metrics[0].tag_value("host"), Some("web01".to_owned()));
+        assert_eq!(metrics[1].tag_value("instance"), Some("_Total".to_owned()));
+    }
+
+    #[test]
+    fn uses_the_configured_metric_name() {
+        let counter = Counter::new(&CounterConfig {
+            path: r"\Memory\Available Bytes".to_owned(),
+            name: Some("memory_free_bytes".to_owned()),
+        })
+        .unwrap();
+        let metrics = counter
+            .metrics(
+                "windows",
+                vec![Sample {
+                    instance: String::new(),
+                    value: 1024.0,
+                }],
+                Utc::now(),
+            )
+            .collect::<Vec<_>>();
+
+        assert_eq!(metrics[0].name(), "memory_free_bytes");
+        assert_eq!(metrics[0].tag_value("instance"), None);
+    }
+}
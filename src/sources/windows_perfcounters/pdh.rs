@@ -0,0 +1,188 @@
+//! Collection of performance counters through the Performance Data Helper (PDH) library.
+
+use snafu::Snafu;
+
+use super::Sample;
+
+#[derive(Debug, Snafu)]
+pub(super) enum PdhError {
+    #[cfg(windows)]
+    #[snafu(display("Failed to add counter {:?}: PDH status 0x{:08X}", path, status))]
+    AddCounter { path: String, status: u32 },
+    #[cfg(windows)]
+    #[snafu(display("{} failed: PDH status 0x{:08X}", function, status))]
+    Call { function: &'static str, status: u32 },
+    #[cfg(not(windows))]
+    #[snafu(display("The `windows_perfcounters` source is only supported on Windows."))]
+    Unsupported,
+}
+
+/// The samples of each counter of a query, in the order the counters were added.
+type Samples = Vec<Result<Vec<Sample>, PdhError>>;
+
+#[cfg(windows)]
+pub(super) use self::windows::Query;
+
+#[cfg(windows)]
+mod windows {
+    use std::{ffi::OsStr, mem, os::windows::ffi::OsStrExt, ptr, slice};
+
+    use windows_sys::Win32::System::Performance::{
+        PdhAddEnglishCounterW, PdhCloseQuery, PdhCollectQueryData, PdhGetFormattedCounterArrayW,
+        PdhOpenQueryW, PDH_CSTATUS_NEW_DATA, PDH_CSTATUS_VALID_DATA, PDH_FMT_COUNTERVALUE_ITEM_W,
+        PDH_FMT_DOUBLE, PDH_FMT_NOCAP100, PDH_MORE_DATA,
+    };
+
+    use super::{PdhError, Sample, Samples};
+
+    const ERROR_SUCCESS: i32 = 0;
+
+    /// A PDH query, and the handles of its counters.
+    pub(in super::super) struct Query {
+        handle: isize,
+        counters: Vec<isize>,
+    }
+
+    impl Query {
+        /// Opens a query of the counters with the given English paths, and collects it once so
+        /// that rate counters have a previous value to be computed from.
+        pub(in super::super) fn open<'a>(
+            paths: impl IntoIterator<Item = &'a str>,
+        ) -> Result<Self, PdhError> {
+            let mut handle = 0;
+            check("PdhOpenQueryW", unsafe {
+                PdhOpenQueryW(ptr::null(), 0, &mut handle)
+            })?;
+            // Closes the query if adding a counter fails.
+            let mut query = Self {
+                handle,
+                counters: Vec::new(),
+            };
+
+            for path in paths {
+                let wide_path = to_wide(path);
+                let mut counter = 0;
+                let status = unsafe {
+                    PdhAddEnglishCounterW(query.handle, wide_path.as_ptr(), 0, &mut counter)
+                };
+                if status != ERROR_SUCCESS {
+                    return Err(PdhError::AddCounter {
+                        path: path.to_owned(),
+                        status: status as u32,
+                    });
+                }
+                query.counters.push(counter);
+            }
+
+            check("PdhCollectQueryData", unsafe {
+                PdhCollectQueryData(query.handle)
+            })?;
+            Ok(query)
+        }
+
+        /// Collects the query, returning the samples of each of its counters.
+        pub(in super::super) fn collect(&self) -> Result<Samples, PdhError> {
+            check("PdhCollectQueryData", unsafe {
+                PdhCollectQueryData(self.handle)
+            })?;
+            Ok(self
+                .counters
+                .iter()
+                .map(|counter| formatted_samples(*counter))
+                .collect())
+        }
+    }
+
+    impl Drop for Query {
+        fn drop(&mut self) {
+            unsafe { PdhCloseQuery(self.handle) };
+        }
+    }
+
+    /// Reads the values of all the instances of a counter.
+    fn formatted_samples(counter: isize) -> Result<Vec<Sample>, PdhError> {
+        // Keep the percentages of counters such as `% Processor Time` of processes running on
+        // several processors above 100.
+        let format = PDH_FMT_DOUBLE | PDH_FMT_NOCAP100;
+        let item_size = mem::size_of::<PDH_FMT_COUNTERVALUE_ITEM_W>();
+        let mut items = Vec::<PDH_FMT_COUNTERVALUE_ITEM_W>::new();
+        loop {
+            let mut size = (items.capacity() * item_size) as u32;
+            let mut count = 0;
+            let buffer = if items.capacity() == 0 {
+                ptr::null_mut()
+            } else {
+                items.as_mut_ptr()
+            };
+            let status = unsafe {
+                PdhGetFormattedCounterArrayW(counter, format, &mut size, &mut count, buffer)
+            };
+            if status == PDH_MORE_DATA {
+                // The items are followed by their names in the buffer.
+                items.reserve((size as usize + item_size - 1) / item_size);
+                continue;
+            }
+            check("PdhGetFormattedCounterArrayW", status)?;
+            if buffer.is_null() {
+                return Ok(Vec::new());
+            }
+
+            let values = unsafe { slice::from_raw_parts(buffer, count as usize) };
+            return Ok(values
+                .iter()
+                .filter(|item| {
+                    matches!(
+                        item.FmtValue.CStatus,
+                        PDH_CSTATUS_VALID_DATA | PDH_CSTATUS_NEW_DATA
+                    )
+                })
+                .map(|item| Sample {
+                    instance: unsafe { from_wide(item.szName) },
+                    value: unsafe { item.FmtValue.Anonymous.doubleValue },
+                })
+                .collect());
+        }
+    }
+
+    fn check(function: &'static str, status: i32) -> Result<(), PdhError> {
+        if status == ERROR_SUCCESS {
+            Ok(())
+        } else {
+            Err(PdhError::Call {
+                function,
+                status: status as u32,
+            })
+        }
+    }
+
+    fn to_wide(string: &str) -> Vec<u16> {
+        OsStr::new(string).encode_wide().chain(Some(0)).collect()
+    }
+
+    /// Reads a null-terminated wide string, which may be null.
+    unsafe fn from_wide(string: *const u16) -> String {
+        if string.is_null() {
+            return String::new();
+        }
+        let mut len = 0;
+        while *string.add(len) != 0 {
+            len += 1;
+        }
+        String::from_utf16_lossy(slice::from_raw_parts(string, len))
+    }
+}
+
+/// The query of the source on platforms without performance counters, which can't be opened.
+#[cfg(not(windows))]
+pub(super) struct Query;
+
+#[cfg(not(windows))]
+impl Query {
+    pub(super) fn open<'a>(_paths: impl IntoIterator<Item = &'a str>) -> Result<Self, PdhError> {
+        Err(PdhError::Unsupported)
+    }
+
+    pub(super) fn collect(&self) -> Result<Samples, PdhError> {
+        unreachable!("the query can't be opened")
+    }
+}
@@ -4,7 +4,7 @@ use colored::{ColoredString, Colorize};
 use tokio_stream::StreamExt;
 use url::Url;
 use vector_api_client::{
-    connect_subscription_client,
+    connect_subscription_client_with_token,
     gql::{
         output_events_by_component_id_patterns_subscription::OutputEventsByComponentIdPatternsSubscriptionOutputEventsByComponentIdPatterns,
         TapEncodingFormat, TapSubscriptionExt,
@@ -34,7 +34,12 @@ pub(crate) async fn cmd(opts: &super::Opts, mut signal_rx: SignalRx) -> exitcode
 
     // Return early with instructions for enabling the API if the endpoint isn't reachable
     // via a healthcheck.
-    if Client::new_with_healthcheck(url.clone()).await.is_none() {
+    if Client::new(url.clone())
+        .with_token(opts.token.clone())
+        .healthcheck()
+        .await
+        .is_none()
+    {
         return exitcode::UNAVAILABLE;
     }
 
@@ -85,16 +90,17 @@ async fn run(
     outputs_patterns: Vec<String>,
     formatter: EventFormatter,
 ) -> exitcode::ExitCode {
-    let subscription_client = match connect_subscription_client(url).await {
-        Ok(c) => c,
-        Err(e) => {
-            #[allow(clippy::print_stderr)]
-            {
-                eprintln!("[tap] Couldn't connect to Vector API via WebSockets: {}", e);
+    let subscription_client =
+        match connect_subscription_client_with_token(url, opts.token.as_deref()).await {
+            Ok(c) => c,
+            Err(e) => {
+                #[allow(clippy::print_stderr)]
+                {
+                    eprintln!("[tap] Couldn't connect to Vector API via WebSockets: {}", e);
+                }
+                return exitcode::UNAVAILABLE;
             }
-            return exitcode::UNAVAILABLE;
-        }
-    };
+        };
 
     tokio::pin! {
         let stream = subscription_client.output_events_by_component_id_patterns_subscription(
@@ -103,6 +109,7 @@ async fn run(
             opts.format,
             opts.limit as i64,
             opts.interval as i64,
+            opts.sample_rate as i64,
         );
     };
 
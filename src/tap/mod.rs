@@ -16,10 +16,19 @@ pub struct Opts {
     #[arg(short, long)]
     url: Option<Url>,
 
+    /// Vector GraphQL API token, if the API requires one
+    #[arg(long, env = "VECTOR_API_TOKEN")]
+    token: Option<String>,
+
     /// Maximum number of events to sample each interval
     #[arg(default_value = "100", short = 'l', long)]
     limit: u32,
 
+    /// Only tap one of every N events, before sampling each interval. Applied by the Vector
+    /// instance, to keep tapping busy components cheap
+    #[arg(default_value = "1", value_parser = clap::value_parser!(u32).range(1..), short = 's', long)]
+    sample_rate: u32,
+
     /// Encoding format for events printed to screen
     #[arg(default_value = "json", value_parser(["json", "yaml", "logfmt"]), short = 'f', long)]
     format: TapEncodingFormat,
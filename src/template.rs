@@ -1,4 +1,13 @@
-use std::{borrow::Cow, convert::TryFrom, fmt, hash::Hash, path::PathBuf};
+use std::{
+    borrow::Cow,
+    cell::RefCell,
+    collections::HashMap,
+    convert::TryFrom,
+    fmt,
+    hash::{Hash, Hasher},
+    path::PathBuf,
+    sync::Arc,
+};
 
 use bytes::Bytes;
 use chrono::{
@@ -8,25 +17,46 @@ use chrono::{
 use once_cell::sync::Lazy;
 use regex::{Captures, Regex};
 use snafu::Snafu;
+use vector_common::TimeZone;
 use vector_config::{configurable_component, ConfigurableString};
+use vector_core::compile_vrl;
+use vrl::{diagnostic::Formatter, CompilationResult, CompileConfig, Program, Runtime};
 
 use crate::{
     config::log_schema,
-    event::{EventRef, Metric, Value},
+    event::{EventRef, Metric, Value, VrlTargetRef},
+    host_metadata,
+    internal_events::TemplateRenderingFallback,
+    protobuf_descriptors,
 };
 
+/// The output events are sent to by components when a template with the `error_output` policy fails
+/// to render for them.
+pub const RENDER_ERRORS_OUTPUT: &str = "render_errors";
+
 static RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"\{\{(?P<key>[^\}]+)\}\}").unwrap());
 
-#[derive(Clone, Copy, Debug, Eq, PartialEq, Snafu)]
+/// Matches the keys of templates which are field paths, as opposed to VRL expressions.
+static FIELD_PATH_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"^(?:[\w.@%\[\]-]|"[^"]*")+$"#).unwrap());
+
+#[derive(Clone, Debug, Eq, PartialEq, Snafu)]
 pub enum TemplateParseError {
     #[snafu(display("Invalid strftime item"))]
     StrftimeError,
+    #[snafu(display("Invalid rendering error policy {:?}: {}", policy, reason))]
+    InvalidRenderErrorPolicy {
+        policy: String,
+        reason: &'static str,
+    },
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, Snafu)]
 pub enum TemplateRenderingError {
     #[snafu(display("Missing fields on event: {:?}", missing_keys))]
     MissingKeys { missing_keys: Vec<String> },
+    #[snafu(display("Failed to evaluate VRL expression {:?}: {}", expression, error))]
+    ExpressionFailed { expression: String, error: String },
 }
 
 /// A templated field.
@@ -40,9 +70,18 @@ pub enum TemplateRenderingError {
 /// refer to fields in an event that will serve as the input data when rendering the template.  While a fixed string may
 /// look something like `my-file.log`, a template string could look something like `my-file-{{key}}.log`, and the `key`
 /// field of the event being processed would serve as the value when rendering the template into a string.
+///
+/// Besides field paths, the `{{ ... }}` sections of a template can hold [VRL][vrl] expressions, such as
+/// `{{ downcase!(.service) }}` or `{{ .team || "unknown" }}`, which are evaluated against the event when rendering
+/// the template.
+///
+/// Templates can also be given as a table holding the template string under `template`, along with an
+/// `on_render_error` policy applied when the template fails to render for an event.
+///
+/// [vrl]: https://vector.dev/docs/reference/vrl
 #[configurable_component]
 #[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
-#[serde(try_from = "String", into = "String")]
+#[serde(try_from = "TemplateConfig", into = "TemplateConfig")]
 pub struct Template {
     src: String,
 
@@ -51,6 +90,192 @@ pub struct Template {
 
     #[serde(skip)]
     has_fields: bool,
+
+    #[serde(skip)]
+    expressions: Expressions,
+
+    #[serde(skip)]
+    on_render_error: Option<RenderErrorPolicy>,
+}
+
+/// A template, either as a plain template string, or along with the policy applied when it fails to
+/// render.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(untagged)]
+pub enum TemplateConfig {
+    /// A template string.
+    Plain(String),
+
+    /// A template string, along with the policy applied when it fails to render.
+    WithPolicy {
+        /// The template string.
+        template: String,
+
+        #[configurable(derived)]
+        on_render_error: RenderErrorPolicy,
+    },
+}
+
+/// What happens to an event for which a template fails to render, such as when a field it refers to
+/// is missing.
+///
+/// This is one of `drop`, `fallback:<value>` to render the template as `<value>` instead, or
+/// `error_output` to send the event to the `render_errors` output of the component.
+#[configurable_component]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[serde(try_from = "String", into = "String")]
+pub enum RenderErrorPolicy {
+    /// The event is dropped, and the error is reported.
+    ///
+    /// This applies even to components which otherwise handle the error on their own, such as by
+    /// leaving out the value of the option.
+    Drop,
+
+    /// The template renders as the given value, and the error is reported as a warning.
+    Fallback(String),
+
+    /// The event is sent to the `render_errors` output of the component, and the error is reported.
+    ///
+    /// Only components with such an output, such as the `respond` transform, accept this policy.
+    ErrorOutput,
+}
+
+impl TryFrom<String> for RenderErrorPolicy {
+    type Error = TemplateParseError;
+
+    fn try_from(policy: String) -> Result<Self, Self::Error> {
+        if policy == "drop" {
+            Ok(Self::Drop)
+        } else if policy == "error_output" {
+            Ok(Self::ErrorOutput)
+        } else if let Some(fallback) = policy.strip_prefix("fallback:") {
+            Ok(Self::Fallback(fallback.to_owned()))
+        } else {
+            Err(TemplateParseError::InvalidRenderErrorPolicy {
+                policy,
+                reason: "expected `drop`, `fallback:<value>` or `error_output`",
+            })
+        }
+    }
+}
+
+impl From<RenderErrorPolicy> for String {
+    fn from(policy: RenderErrorPolicy) -> String {
+        match policy {
+            RenderErrorPolicy::Drop => "drop".to_owned(),
+            RenderErrorPolicy::Fallback(fallback) => format!("fallback:{}", fallback),
+            RenderErrorPolicy::ErrorOutput => "error_output".to_owned(),
+        }
+    }
+}
+
+impl TryFrom<TemplateConfig> for Template {
+    type Error = TemplateParseError;
+
+    fn try_from(config: TemplateConfig) -> Result<Self, Self::Error> {
+        match config {
+            TemplateConfig::Plain(src) => Template::try_from(src),
+            TemplateConfig::WithPolicy {
+                template,
+                on_render_error,
+            } => Template::try_from(template).map(|template| Template {
+                on_render_error: Some(on_render_error),
+                ..template
+            }),
+        }
+    }
+}
+
+impl From<Template> for TemplateConfig {
+    fn from(template: Template) -> TemplateConfig {
+        match template.on_render_error {
+            None => TemplateConfig::Plain(template.src),
+            Some(on_render_error) => TemplateConfig::WithPolicy {
+                template: template.src,
+                on_render_error,
+            },
+        }
+    }
+}
+
+/// The VRL expressions of a template, compiled when the template is parsed.
+///
+/// Keys which don't compile, such as field names with spaces, are left out to be looked up as
+/// fields, as they were before templates could hold expressions.
+///
+/// The expressions are entirely determined by the source of the template, so they are ignored when
+/// comparing or hashing templates.
+#[derive(Clone, Debug, Default)]
+struct Expressions(Arc<HashMap<String, Program>>);
+
+impl Expressions {
+    fn compile(src: &str) -> Self {
+        let mut expressions = HashMap::new();
+        for key in keys(src).filter(|key| !is_field_path(key)) {
+            if !expressions.contains_key(key) {
+                if let Some(program) = compile_expression(key) {
+                    expressions.insert(key.to_owned(), program);
+                }
+            }
+        }
+        Self(Arc::new(expressions))
+    }
+
+    fn get(&self, key: &str) -> Option<&Program> {
+        self.0.get(key)
+    }
+}
+
+impl PartialEq for Expressions {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+impl Eq for Expressions {}
+
+impl Hash for Expressions {
+    fn hash<H: Hasher>(&self, _state: &mut H) {}
+}
+
+/// The trimmed keys of the `{{ ... }}` sections of a template.
+fn keys(src: &str) -> impl Iterator<Item = &str> {
+    RE.captures_iter(src).map(|caps| {
+        caps.get(1)
+            .map(|s| s.as_str().trim())
+            .expect("src should match regex")
+    })
+}
+
+fn is_field_path(key: &str) -> bool {
+    FIELD_PATH_RE.is_match(key)
+}
+
+fn compile_expression(expression: &str) -> Option<Program> {
+    let functions = vrl_stdlib::all()
+        .into_iter()
+        .chain(vector_vrl_functions::vrl_functions())
+        .collect::<Vec<_>>();
+
+    let state = vrl::state::TypeState::default();
+
+    let mut config = CompileConfig::default();
+    config.set_custom(host_metadata::store());
+    protobuf_descriptors::set_custom(&mut config);
+    config.set_read_only();
+
+    match compile_vrl(expression, &functions, &state, config) {
+        Ok(CompilationResult { program, .. }) => Some(program),
+        Err(diagnostics) => {
+            debug!(
+                message = "Template key is not a valid VRL expression, looking it up as a field.",
+                key = %expression,
+                error = %Formatter::new(expression, diagnostics),
+            );
+            None
+        }
+    }
 }
 
 impl Template {
@@ -88,7 +313,16 @@ impl TryFrom<Cow<'_, str>> for Template {
     type Error = TemplateParseError;
 
     fn try_from(src: Cow<'_, str>) -> Result<Self, Self::Error> {
-        let (has_error, is_dynamic) = StrftimeItems::new(&src)
+        // Expressions are free to use `%`, so they're left out when looking for strftime items.
+        let expressions = Expressions::compile(&src);
+        let strftime_src = RE.replace_all(&src, |caps: &Captures<'_>| {
+            if expressions.get(caps[1].trim()).is_some() {
+                String::new()
+            } else {
+                caps[0].to_owned()
+            }
+        });
+        let (has_error, is_dynamic) = StrftimeItems::new(&strftime_src)
             .fold((false, false), |(error, dynamic), item| {
                 (error || is_error(&item), dynamic || is_dynamic(&item))
             });
@@ -99,6 +333,8 @@ impl TryFrom<Cow<'_, str>> for Template {
                 has_fields: RE.is_match(&src),
                 src: src.into_owned(),
                 has_ts: is_dynamic,
+                expressions,
+                on_render_error: None,
             })
         }
     }
@@ -116,7 +352,8 @@ impl fmt::Display for Template {
     }
 }
 
-// This is safe because we literally defer to `String` for the schema of `Template`.
+// This is safe because templates used where strings are expected, such as in map keys, can only be
+// given as plain template strings.
 impl ConfigurableString for Template {}
 
 const fn is_error(item: &Item) -> bool {
@@ -146,25 +383,49 @@ impl Template {
         event: impl Into<EventRef<'a>>,
     ) -> Result<String, TemplateRenderingError> {
         let event = event.into();
-        match (self.has_fields, self.has_ts) {
+        let result = match (self.has_fields, self.has_ts) {
             (false, false) => Ok(self.src.clone()),
-            (true, false) => render_fields(&self.src, event),
+            (true, false) => render_fields(&self.src, event, &self.expressions),
             (false, true) => Ok(render_timestamp(&self.src, event)),
-            (true, true) => {
-                let tmp = render_fields(&self.src, event)?;
-                Ok(render_timestamp(&tmp, event))
+            (true, true) => render_fields(&self.src, event, &self.expressions)
+                .map(|tmp| render_timestamp(&tmp, event)),
+        };
+        match (result, &self.on_render_error) {
+            (Err(error), Some(RenderErrorPolicy::Fallback(fallback))) => {
+                emit!(TemplateRenderingFallback {
+                    template: &self.src,
+                    error: &error,
+                });
+                Ok(fallback.clone())
             }
+            (result, _) => result,
         }
     }
 
+    /// Returns the policy applied when this template fails to render, if one is set.
+    ///
+    /// Without a policy, the component handles the error as it does by default.
+    pub const fn on_render_error(&self) -> Option<&RenderErrorPolicy> {
+        self.on_render_error.as_ref()
+    }
+
+    /// Returns `true` if events for which this template fails to render must be dropped.
+    pub const fn drops_on_render_error(&self) -> bool {
+        matches!(self.on_render_error, Some(RenderErrorPolicy::Drop))
+    }
+
+    /// Returns `true` if events for which this template fails to render must be sent to the
+    /// [`RENDER_ERRORS_OUTPUT`] output.
+    pub const fn routes_render_errors(&self) -> bool {
+        matches!(self.on_render_error, Some(RenderErrorPolicy::ErrorOutput))
+    }
+
+    /// Returns the fields used by this template, leaving out those only read by VRL expressions.
     pub fn get_fields(&self) -> Option<Vec<String>> {
         if self.has_fields {
-            RE.captures_iter(&self.src)
-                .map(|c| {
-                    c.get(1)
-                        .map(|s| s.as_str().trim().to_string())
-                        .expect("src should match regex")
-                })
+            keys(&self.src)
+                .filter(|key| self.expressions.get(key).is_none())
+                .map(str::to_string)
                 .collect::<Vec<_>>()
                 .into()
         } else {
@@ -181,32 +442,87 @@ impl Template {
     }
 }
 
-fn render_fields(src: &str, event: EventRef<'_>) -> Result<String, TemplateRenderingError> {
+fn render_fields(
+    src: &str,
+    event: EventRef<'_>,
+    expressions: &Expressions,
+) -> Result<String, TemplateRenderingError> {
     let mut missing_keys = Vec::new();
+    let mut failure = None;
     let out = RE
         .replace_all(src, |caps: &Captures<'_>| {
             let key = caps
                 .get(1)
                 .map(|s| s.as_str().trim())
                 .expect("src should match regex");
+            if let Some(program) = expressions.get(key) {
+                return match render_expression(program, event) {
+                    Ok(Some(value)) => value,
+                    Ok(None) => {
+                        missing_keys.push(key.to_owned());
+                        String::new()
+                    }
+                    Err(error) => {
+                        failure.get_or_insert(TemplateRenderingError::ExpressionFailed {
+                            expression: key.to_owned(),
+                            error,
+                        });
+                        String::new()
+                    }
+                };
+            }
             match event {
                 EventRef::Log(log) => log.get(key).map(|val| val.to_string_lossy()),
                 EventRef::Metric(metric) => render_metric_field(key, metric),
                 EventRef::Trace(trace) => trace.get(&key).map(|val| val.to_string_lossy()),
             }
+            .or_else(|| render_host_metadata_field(key))
             .unwrap_or_else(|| {
                 missing_keys.push(key.to_owned());
                 String::new()
             })
         })
         .into_owned();
-    if missing_keys.is_empty() {
+    if let Some(failure) = failure {
+        Err(failure)
+    } else if missing_keys.is_empty() {
         Ok(out)
     } else {
         Err(TemplateRenderingError::MissingKeys { missing_keys })
     }
 }
 
+thread_local! {
+    // Each thread reuses a runtime for the expressions it renders, cleared after each of them.
+    static RUNTIME: RefCell<Runtime> = RefCell::new(Runtime::default());
+}
+
+/// Evaluates a VRL expression against the event, returning `None` if it results in `null`.
+///
+/// The expressions are compiled read-only, so they're evaluated against the event itself rather
+/// than a copy of it.
+fn render_expression(program: &Program, event: EventRef<'_>) -> Result<Option<String>, String> {
+    let mut target = VrlTargetRef::new(event, program.info());
+    let result = RUNTIME.with(|runtime| {
+        let mut runtime = runtime.borrow_mut();
+        let result = runtime.resolve(&mut target, program, &TimeZone::default());
+        runtime.clear();
+        result
+    });
+    match result {
+        Ok(Value::Null) => Ok(None),
+        Ok(value) => Ok(Some(value.to_string_lossy())),
+        Err(error) => Err(error.to_string()),
+    }
+}
+
+/// Renders fields like `host_metadata.cloud.region` from the host metadata, unless the event has a
+/// field with that name.
+fn render_host_metadata_field(key: &str) -> Option<String> {
+    key.strip_prefix(host_metadata::TEMPLATE_PREFIX)
+        .and_then(|key| host_metadata::store().get(key))
+}
+
 fn render_metric_field(key: &str, metric: &Metric) -> Option<String> {
     match key {
         "name" => Some(metric.name().into()),
@@ -306,6 +622,25 @@ mod tests {
         assert_eq!(Ok(Bytes::from("metadata_value")), template.render(&event))
     }
 
+    #[test]
+    fn render_host_metadata() {
+        host_metadata::store().replace(BTreeMap::from([(
+            "cloud.region".to_owned(),
+            "eu-west-1".to_owned(),
+        )]));
+        let template = Template::try_from("logs-{{ host_metadata.cloud.region }}").unwrap();
+
+        let event = Event::Log(LogEvent::from("hello world"));
+        assert_eq!(Ok(Bytes::from("logs-eu-west-1")), template.render(&event));
+
+        // Fields of the event take precedence.
+        let mut event = Event::Log(LogEvent::from("hello world"));
+        event
+            .as_mut_log()
+            .insert("host_metadata.cloud.region", "us-east-1");
+        assert_eq!(Ok(Bytes::from("logs-us-east-1")), template.render(&event));
+    }
+
     #[test]
     fn render_log_dynamic_with_prefix() {
         let mut event = Event::Log(LogEvent::from("hello world"));
@@ -510,4 +845,133 @@ mod tests {
             TemplateParseError::StrftimeError
         );
     }
+
+    #[test]
+    fn render_log_expression() {
+        let mut event = Event::Log(LogEvent::from("hello world"));
+        event.as_mut_log().insert("service", "Checkout");
+
+        let template =
+            Template::try_from("logs-{{ downcase!(.service) }}-{{ .team || \"unknown\" }}")
+                .unwrap();
+
+        assert_eq!(
+            Ok(Bytes::from("logs-checkout-unknown")),
+            template.render(&event)
+        );
+        assert_eq!(template.get_fields(), Some(vec![]));
+    }
+
+    #[test]
+    fn render_log_expression_with_strftime() {
+        let ts = Utc.ymd(2001, 2, 3).and_hms(4, 5, 6);
+
+        let mut event = Event::Log(LogEvent::from("hello world"));
+        event.as_mut_log().insert("service", "checkout");
+        event.as_mut_log().insert(log_schema().timestamp_key(), ts);
+
+        let template =
+            Template::try_from("{{ service }}-{{ format_timestamp!(now(), \"%Y\") }}-%F").unwrap();
+        let rendered = template.render_string(&event).unwrap();
+
+        assert!(rendered.starts_with("checkout-"));
+        assert!(rendered.ends_with("-2001-02-03"));
+    }
+
+    #[test]
+    fn render_log_expression_null() {
+        let event = Event::Log(LogEvent::from("hello world"));
+        let template = Template::try_from("{{ .team || null }}").unwrap();
+
+        assert_eq!(
+            Err(TemplateRenderingError::MissingKeys {
+                missing_keys: vec![".team || null".to_owned()]
+            }),
+            template.render(&event)
+        );
+    }
+
+    #[test]
+    fn render_metric_expression() {
+        let template = Template::try_from("{{ upcase!(.name) }}").unwrap();
+
+        assert_eq!(
+            Ok(Bytes::from("A-COUNTER")),
+            template.render(&sample_metric())
+        );
+    }
+
+    #[test]
+    fn render_fallback() {
+        let event = Event::Log(LogEvent::from("hello world"));
+        let template: Template = serde_json::from_value(serde_json::json!({
+            "template": "logs-{{ service }}",
+            "on_render_error": "fallback:logs-unknown",
+        }))
+        .unwrap();
+
+        assert_eq!(Ok(Bytes::from("logs-unknown")), template.render(&event));
+        assert_eq!(
+            serde_json::to_value(&template).unwrap(),
+            serde_json::json!({
+                "template": "logs-{{ service }}",
+                "on_render_error": "fallback:logs-unknown",
+            })
+        );
+    }
+
+    #[test]
+    fn render_error_policies() {
+        let event = Event::Log(LogEvent::from("hello world"));
+        for (policy, drops, routes) in [("drop", true, false), ("error_output", false, true)] {
+            let template: Template = serde_json::from_value(serde_json::json!({
+                "template": "logs-{{ service }}",
+                "on_render_error": policy,
+            }))
+            .unwrap();
+
+            assert!(template.render(&event).is_err());
+            assert_eq!(template.drops_on_render_error(), drops);
+            assert_eq!(template.routes_render_errors(), routes);
+            assert_eq!(
+                serde_json::to_value(&template).unwrap()["on_render_error"],
+                serde_json::json!(policy)
+            );
+        }
+
+        let template = Template::try_from("logs-{{ service }}").unwrap();
+        assert_eq!(template.on_render_error(), None);
+        assert!(!template.drops_on_render_error());
+        assert!(!template.routes_render_errors());
+    }
+
+    #[test]
+    fn render_error_policy_error() {
+        assert!(serde_json::from_value::<Template>(serde_json::json!({
+            "template": "logs-{{ service }}",
+            "on_render_error": "skip",
+        }))
+        .is_err());
+        assert_eq!(
+            serde_json::from_value::<Template>(serde_json::json!("logs-{{ service }}")).unwrap(),
+            Template::try_from("logs-{{ service }}").unwrap()
+        );
+    }
+
+    #[test]
+    fn invalid_expression_is_field() {
+        let mut event = Event::Log(LogEvent::from("hello world"));
+        event.as_mut_log().insert("app", "checkout");
+
+        let template = Template::try_from("{{ (service | app) }}").unwrap();
+        assert_eq!(Ok(Bytes::from("checkout")), template.render(&event));
+
+        let template = Template::try_from("{{ (service | app) }}-{{ downcase( }}").unwrap();
+        assert_eq!(
+            Err(TemplateRenderingError::MissingKeys {
+                missing_keys: vec!["downcase(".to_owned()]
+            }),
+            template.render(&event)
+        );
+    }
 }
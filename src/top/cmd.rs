@@ -3,7 +3,7 @@ use std::time::Duration;
 use futures_util::future::join_all;
 use tokio::sync::oneshot;
 use url::Url;
-use vector_api_client::{connect_subscription_client, Client};
+use vector_api_client::{connect_subscription_client_with_token, Client};
 
 use super::{
     dashboard::{init_dashboard, is_tty},
@@ -37,7 +37,11 @@ pub async fn cmd(opts: &super::Opts) -> exitcode::ExitCode {
     });
 
     // Create a new API client for connecting to the local/remote Vector instance.
-    let client = match Client::new_with_healthcheck(url.clone()).await {
+    let client = match Client::new(url.clone())
+        .with_token(opts.token.clone())
+        .healthcheck()
+        .await
+    {
         Some(client) => client,
         None => return exitcode::UNAVAILABLE,
     };
@@ -73,7 +77,12 @@ pub async fn cmd(opts: &super::Opts) -> exitcode::ExitCode {
             };
             let _ = tx.send(EventType::InitializeState(state)).await;
 
-            let subscription_client = match connect_subscription_client(ws_url.clone()).await {
+            let subscription_client = match connect_subscription_client_with_token(
+                ws_url.clone(),
+                opts_clone.token.as_deref(),
+            )
+            .await
+            {
                 Ok(c) => c,
                 Err(_) => {
                     tokio::time::sleep(Duration::from_millis(RECONNECT_DELAY)).await;
@@ -128,6 +128,7 @@ impl<'a> Widgets<'a> {
         let constraints = vec![
             Constraint::Length(3),
             Constraint::Max(90),
+            Constraint::Length(state::RECENT_INTERNAL_EVENTS as u16 + 2),
             Constraint::Length(3),
         ];
 
@@ -251,6 +252,30 @@ impl<'a> Widgets<'a> {
         f.render_widget(w, area);
     }
 
+    /// Renders a box showing the most recent errors and discarded events of components.
+    fn internal_events_box<B: Backend>(&self, f: &mut Frame<B>, state: &state::State, area: Rect) {
+        let text = state
+            .internal_events
+            .iter()
+            .map(|event| {
+                Spans::from(vec![
+                    Span::styled(
+                        format!("{}: ", event.key.id()),
+                        Style::default().add_modifier(Modifier::BOLD),
+                    ),
+                    Span::from(event.message.as_str()),
+                ])
+            })
+            .collect::<Vec<_>>();
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title("Recent errors");
+        let w = Paragraph::new(text).block(block);
+
+        f.render_widget(w, area);
+    }
+
     /// Alerts the user to resize the window to view columns
     fn components_resize_window<B: Backend>(&self, f: &mut Frame<B>, area: Rect) {
         let block = Block::default().borders(Borders::ALL).title("Components");
@@ -292,7 +317,8 @@ impl<'a> Widgets<'a> {
             self.components_resize_window(f, rects[1]);
         }
 
-        self.quit_box(f, rects[2]);
+        self.internal_events_box(f, &state, rects[2]);
+        self.quit_box(f, rects[3]);
     }
 }
 
@@ -6,7 +6,10 @@ use std::{
 use tokio::task::JoinHandle;
 use tokio_stream::StreamExt;
 use vector_api_client::{
-    gql::{ComponentsQueryExt, ComponentsSubscriptionExt, MetricsSubscriptionExt},
+    gql::{
+        ComponentsQueryExt, ComponentsSubscriptionExt, InternalEventsSubscriptionExt,
+        MetricsSubscriptionExt,
+    },
     Client, SubscriptionClient,
 };
 
@@ -236,6 +239,37 @@ async fn errors_totals(client: Arc<SubscriptionClient>, tx: state::EventTx, inte
     }
 }
 
+/// Errors and discarded events of components, as they are logged
+async fn internal_events(client: Arc<SubscriptionClient>, tx: state::EventTx) {
+    tokio::pin! {
+        let stream = client.internal_events_subscription(Vec::new());
+    };
+
+    while let Some(Some(res)) = stream.next().await {
+        if let Some(d) = res.data {
+            let e = d.internal_events;
+            let message = match (e.error, e.reason) {
+                (Some(error), _) => format!("{} {}", e.message, error),
+                (None, Some(reason)) => {
+                    format!(
+                        "{} ({}): {}",
+                        e.message,
+                        e.count.unwrap_or_default(),
+                        reason
+                    )
+                }
+                (None, None) => e.message,
+            };
+            let _ = tx
+                .send(state::EventType::InternalEvent(state::InternalEventRow {
+                    key: ComponentKey::from(e.component_id),
+                    message,
+                }))
+                .await;
+        }
+    }
+}
+
 /// Subscribe to each metrics channel through a separate client. This is a temporary workaround
 /// until client multiplexing is fixed. In future, we should be able to use a single client
 pub fn subscribe(
@@ -278,7 +312,8 @@ pub fn subscribe(
             tx.clone(),
             interval,
         )),
-        tokio::spawn(errors_totals(Arc::clone(&client), tx, interval)),
+        tokio::spawn(errors_totals(Arc::clone(&client), tx.clone(), interval)),
+        tokio::spawn(internal_events(Arc::clone(&client), tx)),
     ]
 }
 
@@ -19,6 +19,10 @@ pub struct Opts {
     #[arg(short, long)]
     url: Option<Url>,
 
+    /// Vector GraphQL API token, if the API requires one
+    #[arg(long, env = "VECTOR_API_TOKEN")]
+    token: Option<String>,
+
     /// Humanize metrics, using numeric suffixes - e.g. 1,100 = 1.10 k, 1,000,000 = 1.00 M
     #[arg(short = 'H', long)]
     human_metrics: bool,
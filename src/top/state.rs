@@ -1,5 +1,5 @@
 use std::{
-    collections::{BTreeMap, HashMap},
+    collections::{BTreeMap, HashMap, VecDeque},
     fmt::Display,
 };
 
@@ -11,6 +11,9 @@ use crate::config::ComponentKey;
 
 type IdentifiedMetric = (ComponentKey, i64);
 
+/// Number of recent errors and discarded events of components kept for display
+pub const RECENT_INTERNAL_EVENTS: usize = 5;
+
 #[derive(Debug)]
 pub struct SentEventsMetric {
     pub key: ComponentKey,
@@ -18,6 +21,13 @@ pub struct SentEventsMetric {
     pub outputs: HashMap<String, i64>,
 }
 
+/// An error or discarded events of a component
+#[derive(Debug, Clone)]
+pub struct InternalEventRow {
+    pub key: ComponentKey,
+    pub message: String,
+}
+
 #[derive(Debug)]
 pub enum EventType {
     InitializeState(State),
@@ -32,6 +42,7 @@ pub enum EventType {
     /// Interval + identified metric
     ProcessedBytesThroughputs(i64, Vec<IdentifiedMetric>),
     ErrorsTotals(Vec<IdentifiedMetric>),
+    InternalEvent(InternalEventRow),
     ComponentAdded(ComponentRow),
     ComponentRemoved(ComponentKey),
     ConnectionUpdated(ConnectionStatus),
@@ -77,6 +88,8 @@ impl Display for ConnectionStatus {
 pub struct State {
     pub connection_status: ConnectionStatus,
     pub components: BTreeMap<ComponentKey, ComponentRow>,
+    /// Most recent errors and discarded events of components, newest first
+    pub internal_events: VecDeque<InternalEventRow>,
 }
 
 impl State {
@@ -84,6 +97,7 @@ impl State {
         Self {
             connection_status: ConnectionStatus::Pending,
             components,
+            internal_events: VecDeque::with_capacity(RECENT_INTERNAL_EVENTS),
         }
     }
 }
@@ -208,6 +222,10 @@ pub async fn updater(mut event_rx: EventRx) -> StateRx {
                         }
                     }
                 }
+                EventType::InternalEvent(event) => {
+                    state.internal_events.truncate(RECENT_INTERNAL_EVENTS - 1);
+                    state.internal_events.push_front(event);
+                }
                 EventType::ComponentAdded(c) => {
                     let _ = state.components.insert(c.key.clone(), c);
                 }
@@ -25,32 +25,38 @@ use vector_core::{
         },
         BufferType, WhenFull,
     },
-    internal_event::EventsSent,
+    internal_event::{Count, EventsSent, InternalEventHandle as _},
     schema::Definition,
     ByteSizeOf,
 };
 
 use super::{
+    event_tracing,
     fanout::{self, Fanout},
     schema,
     task::{Task, TaskOutput, TaskResult},
     BuiltBuffer, ConfigDiff,
 };
 use crate::{
+    conditions::Condition,
     config::{
-        ComponentKey, DataType, EnrichmentTableConfig, Input, Output, OutputId, ProxyConfig,
-        SinkConfig, SinkContext, SourceConfig, SourceContext, TransformConfig, TransformContext,
-        TransformOuter,
+        feature_flags, ComponentKey, DataType, EnrichmentTableConfig, EnrichmentTableOuter, Input,
+        Output, OutputId, ProxyConfig, SinkConfig, SinkContext, SourceConfig, SourceContext,
+        TransformConfig, TransformContext, TransformOuter,
     },
-    event::{EventArray, EventContainer},
-    internal_events::EventsReceived,
+    enrichment_tables::status as table_status,
+    event::{Event, EventArray, EventContainer},
+    host_metadata,
+    internal_events::{EventsReceived, FilterEventsDropped},
+    protobuf_descriptors,
     shutdown::SourceShutdownCoordinator,
+    sinks::util::service::rate_limit_pool,
     source_sender::CHUNK_SIZE,
     spawn_named,
     topology::task::TaskError,
     transforms::{SyncTransform, TaskTransform, Transform, TransformOutputs, TransformOutputsBuf},
     utilization::wrap,
-    SourceSender,
+    vrl_functions, SourceSender,
 };
 
 static ENRICHMENT_TABLES: Lazy<enrichment::TableRegistry> =
@@ -78,53 +84,121 @@ pub(self) async fn load_enrichment_tables<'a>(
     let mut errors = vec![];
 
     // Build enrichment tables
-    'tables: for (name, table) in config.enrichment_tables.iter() {
+    for (name, table) in config.enrichment_tables.iter() {
         let table_name = name.to_string();
         if ENRICHMENT_TABLES.needs_reload(&table_name) {
-            let indexes = if !diff.enrichment_tables.is_added(name) {
-                // If this is an existing enrichment table, we need to store the indexes to reapply
-                // them again post load.
-                Some(ENRICHMENT_TABLES.index_fields(&table_name))
-            } else {
-                None
-            };
-
-            let mut table = match table.inner.build(&config.global).await {
-                Ok(table) => table,
-                Err(error) => {
-                    errors.push(format!("Enrichment Table \"{}\": {}", name, error));
-                    continue;
-                }
-            };
-
-            if let Some(indexes) = indexes {
-                for (case, index) in indexes {
-                    match table
-                        .add_index(case, &index.iter().map(|s| s.as_ref()).collect::<Vec<_>>())
-                    {
-                        Ok(_) => (),
-                        Err(error) => {
-                            // If there is an error adding an index we do not want to use the reloaded
-                            // data, the previously loaded data will still need to be used.
-                            // Just report the error and continue.
-                            error!(message = "Unable to add index to reloaded enrichment table.",
-                                    table = ?name.to_string(),
-                                    %error);
-                            continue 'tables;
-                        }
-                    }
-                }
+            // If this is an existing enrichment table, we need to store the indexes to reapply
+            // them again post load.
+            let reload = !diff.enrichment_tables.is_added(name);
+            if let Some(table) =
+                build_enrichment_table(&table_name, table, config, reload, &mut errors).await
+            {
+                enrichment_tables.insert(table_name, table);
             }
-
-            enrichment_tables.insert(table_name, table);
         }
     }
 
+    table_status::retain(
+        &config
+            .enrichment_tables
+            .keys()
+            .map(ToString::to_string)
+            .collect(),
+    );
     ENRICHMENT_TABLES.load(enrichment_tables);
 
     (&ENRICHMENT_TABLES, errors)
 }
 
+/// Reloads the enrichment table `name` of the running topology, even if its data hasn't changed,
+/// leaving the other tables and components as they are.
+///
+/// Returns `false` if there's no such table. If the table fails to load, the previously loaded
+/// data is still used, and the error is reported in the status of the table.
+pub(super) async fn reload_enrichment_table(config: &super::Config, name: &str) -> bool {
+    let table = match config
+        .enrichment_tables
+        .iter()
+        .find(|(key, _)| key.id() == name)
+    {
+        Some((_, table)) => table,
+        None => return false,
+    };
+
+    let mut errors = vec![];
+    if let Some(table) = build_enrichment_table(name, table, config, true, &mut errors).await {
+        ENRICHMENT_TABLES.load(HashMap::from([(name.to_owned(), table)]));
+        ENRICHMENT_TABLES.finish_load();
+    }
+    true
+}
+
+/// Builds an enrichment table, reapplying the indexes of the loaded table it replaces if
+/// `reload` is set, and records the outcome in the status of the table.
+///
+/// Build errors are pushed to `errors`. Returns `None` if the table failed to load.
+async fn build_enrichment_table(
+    name: &str,
+    table: &EnrichmentTableOuter,
+    config: &super::Config,
+    reload: bool,
+    errors: &mut Vec<String>,
+) -> Option<Box<dyn enrichment::Table + Send + Sync>> {
+    let indexes = reload.then(|| ENRICHMENT_TABLES.index_fields(name));
+
+    let mut table = match table.inner.build(&config.global).await {
+        Ok(table) => table,
+        Err(error) => {
+            table_status::failed(name, error.to_string());
+            errors.push(format!("Enrichment Table \"{}\": {}", name, error));
+            return None;
+        }
+    };
+
+    for (case, index) in indexes.unwrap_or_default() {
+        if let Err(error) =
+            table.add_index(case, &index.iter().map(|s| s.as_ref()).collect::<Vec<_>>())
+        {
+            // If there is an error adding an index we do not want to use the reloaded
+            // data, the previously loaded data will still need to be used.
+            // Just report the error and continue.
+            table_status::failed(
+                name,
+                format!("Unable to add index to reloaded table: {}", error),
+            );
+            return None;
+        }
+    }
+
+    table_status::loaded(name, table.row_count());
+    Some(table)
+}
+
+/// Drops the events of a source that don't match its `filter` condition.
+fn filter_source_events(array: EventArray, filter: &Condition) -> EventArray {
+    fn retain<T: Into<Event>>(
+        events: Vec<T>,
+        filter: &Condition,
+        unwrap: fn(Event) -> T,
+    ) -> Vec<T> {
+        events
+            .into_iter()
+            .filter_map(|event| {
+                let (pass, event) = filter.check(event.into());
+                pass.then(|| unwrap(event))
+            })
+            .collect()
+    }
+
+    match array {
+        EventArray::Logs(logs) => EventArray::Logs(retain(logs, filter, Event::into_log)),
+        EventArray::Metrics(metrics) => {
+            EventArray::Metrics(retain(metrics, filter, Event::into_metric))
+        }
+        EventArray::Traces(traces) => EventArray::Traces(retain(traces, filter, Event::into_trace)),
+    }
+}
+
 pub struct Pieces {
     pub(super) inputs: HashMap<ComponentKey, (BufferSender<EventArray>, Vec<OutputId>)>,
     pub(crate) outputs: HashMap<ComponentKey, HashMap<Option<String>, fanout::ControlChannel>>,
@@ -154,6 +228,21 @@ pub async fn build_pieces(
     let (enrichment_tables, enrichment_errors) = load_enrichment_tables(config, diff).await;
     errors.extend(enrichment_errors);
 
+    // Loaded first, as the functions defined in VRL can parse and encode protobuf messages too.
+    if let Err(error) =
+        protobuf_descriptors::configure(config.global.protobuf_descriptor_set.as_deref())
+    {
+        errors.push(error);
+    }
+
+    if let Err(function_errors) =
+        vrl_functions::configure(&config.global.vrl_functions, enrichment_tables)
+    {
+        errors.extend(function_errors);
+    }
+
+    event_tracing::set_enabled(config.sources().any(|(_, source)| source.trace.is_some()));
+
     // Build sources
     for (key, source) in config
         .sources()
@@ -161,14 +250,41 @@ pub async fn build_pieces(
     {
         debug!(component = %key, "Building new source.");
 
+        let filter = match source
+            .filter
+            .as_ref()
+            .map(|filter| filter.build(enrichment_tables))
+            .transpose()
+        {
+            Ok(filter) => filter,
+            Err(error) => {
+                errors.push(format!("Source \"{}\": invalid filter: {}", key, error));
+                continue;
+            }
+        };
+
+        let trace = match source
+            .trace
+            .as_ref()
+            .map(|trace| trace.build(enrichment_tables))
+            .transpose()
+        {
+            Ok(trace) => trace,
+            Err(error) => {
+                errors.push(format!("Source \"{}\": invalid trace: {}", key, error));
+                continue;
+            }
+        };
+
         let typetag = source.inner.get_component_name();
-        let source_outputs = source.inner.outputs(config.schema.log_namespace());
+        let source_outputs = source.outputs(config.schema.log_namespace());
 
         let span = error_span!(
             "source",
             component_kind = "source",
             component_id = %key.id(),
             component_type = %source.inner.get_component_name(),
+            component_status = feature_flags::source_status(typetag).as_deref(),
             // maintained for compatibility
             component_name = %key.id(),
         );
@@ -180,7 +296,16 @@ pub async fn build_pieces(
 
         let mut builder = {
             let _span = span.enter();
-            SourceSender::builder().with_buffer(*SOURCE_SENDER_BUFFER_SIZE)
+            SourceSender::builder()
+                .with_buffer(*SOURCE_SENDER_BUFFER_SIZE)
+                .with_decoding_errors_routed(
+                    source.routes_decoding_errors(config.schema.log_namespace()),
+                )
+                .with_event_size_limit(
+                    source.max_event_bytes.or(config.global.max_event_bytes),
+                    source.oversized_events,
+                )
+                .with_ingestion_limits(source.limits)
         };
         let mut pumps = Vec::new();
         let mut controls = HashMap::new();
@@ -188,12 +313,32 @@ pub async fn build_pieces(
 
         for output in source_outputs {
             let mut rx = builder.add_output(output.clone());
+            let host_metadata_key = source.host_metadata_key.clone();
+            let filter = filter.clone();
+            let trace = trace.clone();
 
             let (mut fanout, control) = Fanout::new();
             let pump = async move {
                 debug!("Source pump starting.");
 
-                while let Some(array) = rx.next().await {
+                let filter = filter.map(|filter| (filter, register!(FilterEventsDropped)));
+                while let Some(mut array) = rx.next().await {
+                    if let Some((filter, events_dropped)) = &filter {
+                        let count = array.len();
+                        array = filter_source_events(array, filter);
+                        if array.len() < count {
+                            events_dropped.emit(Count(count - array.len()));
+                        }
+                        if array.is_empty() {
+                            continue;
+                        }
+                    }
+                    if let Some(trace) = &trace {
+                        array = event_tracing::start(array, trace);
+                    }
+                    if let Some(key) = &host_metadata_key {
+                        host_metadata::tag_events(&mut array, key);
+                    }
                     fanout.send(array).await.map_err(|e| {
                         debug!("Source pump finished with an error.");
                         TaskError::wrapped(e)
@@ -384,6 +529,7 @@ pub async fn build_pieces(
     }
 
     // Build sinks
+    rate_limit_pool::configure(&config.global.rate_limit_pools);
     for (key, sink) in config
         .sinks()
         .filter(|(key, _)| diff.sinks.contains_new(key))
@@ -396,6 +542,15 @@ pub async fn build_pieces(
 
         let typetag = sink.inner.get_component_name();
         let input_type = sink.inner.input().data_type();
+        let meaning_targets = if config.schema.semantic_mapping {
+            sink.inner
+                .input()
+                .schema_requirement()
+                .targets()
+                .collect::<Vec<_>>()
+        } else {
+            Vec::new()
+        };
 
         if config.schema.validation {
             // At this point, we've validated that all transforms are valid, including any
@@ -418,6 +573,7 @@ pub async fn build_pieces(
                 component_kind = "sink",
                 component_id = %key.id(),
                 component_type = typetag,
+                component_status = feature_flags::sink_status(typetag).as_deref(),
                 component_name = %key.id(),
                 buffer_type,
             );
@@ -441,12 +597,39 @@ pub async fn build_pieces(
             schema: config.schema,
         };
 
-        let (sink, healthcheck) = match sink.inner.build(cx).await {
+        let schedule = match sink
+            .schedule
+            .as_ref()
+            .map(|schedule| schedule.build(config.global.timezone))
+            .transpose()
+        {
             Err(error) => {
                 errors.push(format!("Sink \"{}\": {}", key, error));
                 continue;
             }
-            Ok(built) => built,
+            Ok(schedule) => schedule,
+        };
+
+        let metric_rewrite = sink.metrics.clone();
+
+        let pool = sink
+            .rate_limit_pool
+            .as_deref()
+            .and_then(rate_limit_pool::get);
+        let has_pool = pool.is_some();
+        let (sink, healthcheck) = match rate_limit_pool::scope(pool, sink.inner.build(cx)).await {
+            (Err(error), _) => {
+                errors.push(format!("Sink \"{}\": {}", key, error));
+                continue;
+            }
+            (Ok(_), false) if has_pool => {
+                errors.push(format!(
+                    "Sink \"{}\": The `rate_limit_pool` option is not supported by sinks of type \"{}\", as they have no request settings.",
+                    key, typetag
+                ));
+                continue;
+            }
+            (Ok(built), _) => built,
         };
 
         let (trigger, tripwire) = Tripwire::new();
@@ -467,15 +650,25 @@ pub async fn build_pieces(
                 .expect("Task started but input has been taken.");
 
             let mut rx = wrap(rx);
+            let input = match schedule {
+                Some(schedule) => schedule.gate(rx.by_ref()),
+                None => rx.by_ref().boxed(),
+            };
 
             sink.run(
-                rx.by_ref()
+                input
                     .filter(|events: &EventArray| ready(filter_events_type(events, input_type)))
+                    .map(|events| map_semantic_meanings(events, &meaning_targets))
+                    .map(|events| match &metric_rewrite {
+                        Some(rewrite) => rewrite.rewrite(events),
+                        None => events,
+                    })
                     .inspect(|events| {
                         emit!(EventsReceived {
                             count: events.len(),
                             byte_size: events.size_of(),
-                        })
+                        });
+                        event_tracing::received(events);
                     })
                     .take_until_if(tripwire),
             )
@@ -578,6 +771,21 @@ const fn filter_events_type(events: &EventArray, data_type: DataType) -> bool {
     }
 }
 
+/// Move fields carrying a semantic meaning to the location the sink expects them in.
+fn map_semantic_meanings(
+    mut events: EventArray,
+    targets: &[(&'static str, &'static str)],
+) -> EventArray {
+    if !targets.is_empty() {
+        if let EventArray::Logs(logs) = &mut events {
+            for log in logs {
+                log.map_meanings(targets.iter().copied());
+            }
+        }
+    }
+    events
+}
+
 #[derive(Debug, Clone)]
 struct TransformNode {
     key: ComponentKey,
@@ -703,9 +911,11 @@ impl Runner {
             count: events.len(),
             byte_size: events.size_of(),
         });
+        event_tracing::received(events);
     }
 
     async fn send_outputs(&mut self, outputs_buf: &mut TransformOutputsBuf) -> crate::Result<()> {
+        event_tracing::sent_outputs(outputs_buf);
         self.timer.start_wait();
         self.outputs.send(outputs_buf).await
     }
@@ -820,7 +1030,8 @@ fn build_task_transform(
             emit!(EventsReceived {
                 count: events.len(),
                 byte_size: events.size_of(),
-            })
+            });
+            event_tracing::received(events);
         });
     let stream = t
         .transform(Box::pin(filtered))
@@ -830,6 +1041,7 @@ fn build_task_transform(
                 byte_size: events.size_of(),
                 output: None,
             });
+            event_tracing::sent(events);
         });
     let transform = async move {
         debug!("Task transform starting.");
@@ -0,0 +1,102 @@
+//! Following events through the topology.
+//!
+//! Sources mark the events matching their `trace` condition with an [`EventTrace`], which is kept
+//! by the events derived from them. Every component these events pass through then logs them, along
+//! with the time elapsed since they were marked, so that a single event can be followed without
+//! tapping every component.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use vector_core::internal_event::DEFAULT_OUTPUT;
+
+use crate::{
+    conditions::Condition,
+    event::{Event, EventArray, EventContainer, EventTrace},
+    internal_events::{EventTraceStarted, TracedEventReceived, TracedEventSent},
+    transforms::TransformOutputsBuf,
+};
+
+/// Whether any source of the running topology traces events, so that components only look for
+/// traced events when there may be some.
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+pub(super) fn set_enabled(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+fn enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Starts tracing the events matching the `trace` condition of a source.
+pub(super) fn start(array: EventArray, condition: &Condition) -> EventArray {
+    fn mark<T: Into<Event>>(
+        events: Vec<T>,
+        condition: &Condition,
+        unwrap: fn(Event) -> T,
+    ) -> Vec<T> {
+        events
+            .into_iter()
+            .map(|event| {
+                let (matched, mut event) = condition.check(event.into());
+                if matched && event.metadata().trace().is_none() {
+                    let trace = EventTrace::start();
+                    emit!(EventTraceStarted { trace: &trace });
+                    event.metadata_mut().set_trace(trace);
+                }
+                unwrap(event)
+            })
+            .collect()
+    }
+
+    match array {
+        EventArray::Logs(logs) => EventArray::Logs(mark(logs, condition, Event::into_log)),
+        EventArray::Metrics(metrics) => {
+            EventArray::Metrics(mark(metrics, condition, Event::into_metric))
+        }
+        EventArray::Traces(traces) => {
+            EventArray::Traces(mark(traces, condition, Event::into_trace))
+        }
+    }
+}
+
+/// Logs the traced events received by a transform or a sink.
+pub(super) fn received(events: &EventArray) {
+    if enabled() {
+        for trace in events
+            .iter_events()
+            .filter_map(|event| event.metadata().trace())
+        {
+            emit!(TracedEventReceived { trace });
+        }
+    }
+}
+
+/// Logs the traced events sent by a transform to its default output.
+pub(super) fn sent(events: &EventArray) {
+    if enabled() {
+        for trace in events
+            .iter_events()
+            .filter_map(|event| event.metadata().trace())
+        {
+            emit!(TracedEventSent {
+                trace,
+                output: DEFAULT_OUTPUT,
+            });
+        }
+    }
+}
+
+/// Logs the traced events sent by a transform to any of its outputs.
+pub(super) fn sent_outputs(buf: &TransformOutputsBuf) {
+    if enabled() {
+        for (output, event) in buf.iter_events() {
+            if let Some(trace) = event.metadata().trace() {
+                emit!(TracedEventSent {
+                    trace,
+                    output: output.unwrap_or(DEFAULT_OUTPUT),
+                });
+            }
+        }
+    }
+}
@@ -18,7 +18,8 @@ use vector_common::trigger::DisabledTrigger;
 use super::{TapOutput, TapResource};
 use crate::{
     config::{
-        ComponentKey, Config, ConfigDiff, HealthcheckOptions, OutputId, Resource, SourceConfig,
+        feature_flags, ComponentKey, Config, ConfigDiff, HealthcheckOptions, OutputId, Resource,
+        SourceConfig,
     },
     event::EventArray,
     shutdown::SourceShutdownCoordinator,
@@ -190,6 +191,12 @@ impl RunningTopology {
         futures::future::join(source_shutdown_complete, shutdown_complete_future).map(|_| ())
     }
 
+    /// Reloads the enrichment table `name`, even if its data hasn't changed, without reloading
+    /// the configuration or the other components. Returns `false` if there's no such table.
+    pub async fn reload_enrichment_table(&self, name: &str) -> bool {
+        builder::reload_enrichment_table(&self.config, name).await
+    }
+
     /// Attempts to load a new configuration and update this running topology.
     ///
     /// If the new configuration was valid, and all changes were able to be made -- removing of
@@ -799,6 +806,7 @@ impl RunningTopology {
             component_kind = "sink",
             component_id = %task.id(),
             component_type = %task.typetag(),
+            component_status = feature_flags::sink_status(task.typetag()).as_deref(),
             // maintained for compatibility
             component_name = %task.id(),
         );
@@ -817,6 +825,7 @@ impl RunningTopology {
             component_kind = "transform",
             component_id = %task.id(),
             component_type = %task.typetag(),
+            component_status = feature_flags::transform_status(task.typetag()).as_deref(),
             // maintained for compatibility
             component_name = %task.id(),
         );
@@ -835,6 +844,7 @@ impl RunningTopology {
             component_kind = "source",
             component_id = %task.id(),
             component_type = %task.typetag(),
+            component_status = feature_flags::source_status(task.typetag()).as_deref(),
             // maintained for compatibility
             component_name = %task.id(),
         );
@@ -313,7 +313,7 @@ impl ComponentContainer for Config {
 
     fn source_outputs(&self, key: &ComponentKey) -> Option<Vec<Output>> {
         self.source(key)
-            .map(|source| source.inner.outputs(self.schema.log_namespace()))
+            .map(|source| source.outputs(self.schema.log_namespace()))
     }
 
     fn transform_inputs(&self, key: &ComponentKey) -> Option<&[OutputId]> {
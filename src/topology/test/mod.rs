@@ -8,7 +8,8 @@ use std::{
 };
 
 use crate::{
-    config::{Config, ConfigDiff, SinkOuter},
+    conditions::AnyCondition,
+    config::{ComponentKey, Config, ConfigDiff, SinkOuter},
     event::{into_event_stream, Event, EventArray, EventContainer, LogEvent},
     test_util::{
         mock::{
@@ -148,6 +149,69 @@ async fn topology_source_and_sink() {
     assert_eq!(vec![event], res);
 }
 
+#[tokio::test]
+async fn topology_source_filter() {
+    trace_init();
+
+    let (mut in1, source1) = basic_source();
+    let (out1, sink1) = basic_sink(10);
+
+    let mut config = Config::builder();
+    config.add_source("in1", source1);
+    config.sources[&ComponentKey::from("in1")].filter = Some(AnyCondition::String(
+        r#".message != "healthcheck""#.to_owned(),
+    ));
+    config.add_sink("out1", &["in1"], sink1);
+
+    let (topology, _crash) = start_topology(config.build().unwrap(), false).await;
+
+    let event = Event::Log(LogEvent::from("this"));
+    in1.send_batch(vec![
+        Event::Log(LogEvent::from("healthcheck")),
+        event.clone(),
+    ])
+    .await
+    .unwrap();
+    in1.send_event(LogEvent::from("healthcheck")).await.unwrap();
+
+    topology.stop().await;
+
+    let res = out1.flat_map(into_event_stream).collect::<Vec<_>>().await;
+
+    assert_eq!(vec![event], res);
+}
+
+#[tokio::test]
+async fn topology_source_trace() {
+    trace_init();
+
+    let (mut in1, source1) = basic_source();
+    let (out1, sink1) = basic_sink(10);
+
+    let mut config = Config::builder();
+    config.add_source("in1", source1);
+    config.sources[&ComponentKey::from("in1")].trace =
+        Some(AnyCondition::String(r#".message == "traced""#.to_owned()));
+    config.add_sink("out1", &["in1"], sink1);
+
+    let (topology, _crash) = start_topology(config.build().unwrap(), false).await;
+
+    in1.send_batch(vec![
+        Event::Log(LogEvent::from("traced")),
+        Event::Log(LogEvent::from("untraced")),
+    ])
+    .await
+    .unwrap();
+
+    topology.stop().await;
+
+    let res = out1.flat_map(into_event_stream).collect::<Vec<_>>().await;
+
+    assert_eq!(res.len(), 2);
+    assert!(res[0].metadata().trace().is_some());
+    assert!(res[1].metadata().trace().is_none());
+}
+
 #[tokio::test]
 async fn topology_multiple_sources() {
     trace_init();
@@ -6,6 +6,7 @@ use std::{
         atomic::{AtomicBool, Ordering},
         Mutex, MutexGuard,
     },
+    time::Duration,
 };
 
 use futures_util::{future::ready, Stream, StreamExt};
@@ -20,8 +21,10 @@ use tokio_stream::wrappers::BroadcastStream;
 use tracing::{Event, Subscriber};
 use tracing_limit::RateLimitedLayer;
 use tracing_subscriber::{
+    filter::Targets,
     layer::{Context, SubscriberExt},
     registry::LookupSpan,
+    reload,
     util::SubscriberInitExt,
     Layer,
 };
@@ -51,6 +54,17 @@ static SUBSCRIBERS: Mutex<Option<Vec<oneshot::Sender<Vec<LogEvent>>>>> =
 /// has been initialized.
 static SENDER: OnceCell<Sender<LogEvent>> = OnceCell::new();
 
+/// LOG_FILTER holds the handle changing the filter of internal logs at runtime, along with the filter set at startup,
+/// which temporary filters revert to.
+static LOG_FILTER: Mutex<Option<LogFilter>> = Mutex::new(None);
+
+struct LogFilter {
+    reload: Box<dyn Fn(Targets) -> Result<(), reload::Error> + Send>,
+    initial: String,
+    /// Incremented by every change, so that a temporary filter only reverts if it wasn't replaced since.
+    generation: u64,
+}
+
 fn metrics_layer_enabled() -> bool {
     !matches!(std::env::var("DISABLE_INTERNAL_METRICS_TRACING_INTEGRATION"), Ok(x) if x == "true")
 }
@@ -63,9 +77,21 @@ pub fn init(color: bool, json: bool, levels: &str, internal_log_rate_limit: u64)
     let metrics_layer = metrics_layer_enabled()
         .then(|| MetricsLayer::new().with_filter(tracing_subscriber::filter::LevelFilter::INFO));
 
+    // Both filters are reloadable, so that the verbosity can be raised at runtime through the API.
+    let (broadcast_filter, broadcast_handle) = reload::Layer::new(fmt_filter.clone());
+    let (fmt_filter, fmt_handle) = reload::Layer::new(fmt_filter);
+    let log_filter = LogFilter {
+        reload: Box::new(move |filter: Targets| {
+            broadcast_handle.reload(filter.clone())?;
+            fmt_handle.reload(filter)
+        }),
+        initial: levels.to_owned(),
+        generation: 0,
+    };
+
     let broadcast_layer = RateLimitedLayer::new(BroadcastLayer::new())
         .with_default_limit(internal_log_rate_limit)
-        .with_filter(fmt_filter.clone());
+        .with_filter(broadcast_filter);
 
     let subscriber = tracing_subscriber::registry()
         .with(metrics_layer)
@@ -90,7 +116,9 @@ pub fn init(color: bool, json: bool, levels: &str, internal_log_rate_limit: u64)
             RateLimitedLayer::new(formatter).with_default_limit(internal_log_rate_limit);
         let subscriber = subscriber.with(rate_limited.with_filter(fmt_filter));
 
-        let _ = subscriber.try_init();
+        if subscriber.try_init().is_ok() {
+            *get_log_filter() = Some(log_filter);
+        }
     } else {
         let formatter = tracing_subscriber::fmt::layer()
             .with_ansi(color)
@@ -103,10 +131,65 @@ pub fn init(color: bool, json: bool, levels: &str, internal_log_rate_limit: u64)
             RateLimitedLayer::new(formatter).with_default_limit(internal_log_rate_limit);
         let subscriber = subscriber.with(rate_limited.with_filter(fmt_filter));
 
-        let _ = subscriber.try_init();
+        if subscriber.try_init().is_ok() {
+            *get_log_filter() = Some(log_filter);
+        }
     }
 }
 
+fn get_log_filter() -> MutexGuard<'static, Option<LogFilter>> {
+    LOG_FILTER
+        .lock()
+        .expect("Couldn't acquire lock on the logging filter")
+}
+
+/// Replaces the filter of internal logs with `levels`, in the same format as `VECTOR_LOG`, until `ttl` elapses and the
+/// filter set at startup is restored.
+///
+/// Must be called within a Tokio runtime.
+pub fn set_log_filter(levels: &str, ttl: Duration) -> Result<(), String> {
+    let filter = Targets::from_str(levels).map_err(|error| error.to_string())?;
+    let generation = {
+        let mut log_filter = get_log_filter();
+        let log_filter = log_filter.as_mut().ok_or("Logging isn't initialized.")?;
+        (log_filter.reload)(filter).map_err(|error| error.to_string())?;
+        log_filter.generation += 1;
+        log_filter.generation
+    };
+    info!(message = "Logging filter changed.", filter = %levels, ttl_secs = ttl.as_secs());
+
+    tokio::spawn(async move {
+        tokio::time::sleep(ttl).await;
+        let reverted = {
+            let mut log_filter = get_log_filter();
+            match log_filter.as_mut() {
+                Some(log_filter) if log_filter.generation == generation => {
+                    log_filter.generation += 1;
+                    let initial = Targets::from_str(&log_filter.initial)
+                        .expect("initial logging filter was already parsed");
+                    (log_filter.reload)(initial).is_ok()
+                }
+                _ => false,
+            }
+        };
+        if reverted {
+            info!(message = "Logging filter reverted.");
+        }
+    });
+    Ok(())
+}
+
+/// Restores the filter of internal logs set at startup, returning it.
+pub fn reset_log_filter() -> Result<String, String> {
+    let mut log_filter = get_log_filter();
+    let log_filter = log_filter.as_mut().ok_or("Logging isn't initialized.")?;
+    let initial =
+        Targets::from_str(&log_filter.initial).expect("initial logging filter was already parsed");
+    (log_filter.reload)(initial).map_err(|error| error.to_string())?;
+    log_filter.generation += 1;
+    Ok(log_filter.initial.clone())
+}
+
 #[cfg(test)]
 pub fn reset_early_buffer() -> Option<Vec<LogEvent>> {
     get_early_buffer().replace(Vec::new())
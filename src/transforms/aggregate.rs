@@ -1,11 +1,8 @@
-use std::{
-    collections::{btree_map::Entry, BTreeMap},
-    pin::Pin,
-    time::Duration,
-};
+use std::{pin::Pin, time::Duration};
 
 use async_stream::stream;
 use futures::{Stream, StreamExt};
+use indexmap::{map::Entry, IndexMap};
 use vector_config::configurable_component;
 
 use crate::{
@@ -54,19 +51,20 @@ type MetricEntry = (metric::MetricData, EventMetadata);
 #[derive(Debug)]
 pub struct Aggregate {
     interval: Duration,
-    map: BTreeMap<metric::MetricSeries, MetricEntry>,
+    map: IndexMap<metric::MetricSeriesKey, MetricEntry>,
 }
 
 impl Aggregate {
     pub fn new(config: &AggregateConfig) -> crate::Result<Self> {
         Ok(Self {
             interval: Duration::from_millis(config.interval_ms),
-            map: BTreeMap::new(),
+            map: IndexMap::new(),
         })
     }
 
     fn record(&mut self, event: Event) {
         let (series, data, metadata) = event.into_metric().into_parts();
+        let series = metric::MetricSeriesKey::new(series);
 
         match data.kind {
             metric::MetricKind::Incremental => match self.map.entry(series) {
@@ -96,7 +94,7 @@ impl Aggregate {
     fn flush_into(&mut self, output: &mut Vec<Event>) {
         let map = std::mem::take(&mut self.map);
         for (series, entry) in map.into_iter() {
-            let metric = metric::Metric::from_parts(series, entry.0, entry.1);
+            let metric = metric::Metric::from_parts(series.into_series(), entry.0, entry.1);
             output.push(Event::Metric(metric));
         }
 
@@ -0,0 +1,475 @@
+use std::io::{Read, Write};
+
+use bytes::{Buf, Bytes};
+use flate2::{
+    read::{MultiGzDecoder, ZlibDecoder},
+    write::{GzEncoder, ZlibEncoder},
+};
+use snafu::{ResultExt, Snafu};
+use vector_config::configurable_component;
+
+use crate::{
+    config::{
+        log_schema, DataType, GenerateConfig, Input, Output, TransformConfig, TransformContext,
+    },
+    event::{Event, Value},
+    internal_events::{CompressionFieldError, ParserMissingFieldError, DROP_EVENT, RETAIN_EVENT},
+    schema,
+    transforms::{FunctionTransform, OutputBuffer, Transform},
+};
+
+/// Configuration for the `compression` transform.
+#[configurable_component(transform("compression"))]
+#[configurable(metadata(status = "alpha"))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct CompressionConfig {
+    /// The log field holding the payload.
+    #[serde(default = "default_field")]
+    pub field: String,
+
+    /// The log field to write the result to.
+    ///
+    /// If not set, the payload in `field` is replaced.
+    pub target_field: Option<String>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub mode: CompressionMode,
+
+    #[configurable(derived)]
+    pub algorithm: CompressionAlgorithm,
+
+    /// Whether the compressed payload is wrapped in base64.
+    ///
+    /// When decompressing, the payload is base64-decoded first. When compressing, the result is
+    /// base64-encoded, so that it can be stored as a string.
+    #[serde(default)]
+    pub base64: bool,
+
+    /// Whether to drop events whose payload can't be processed.
+    ///
+    /// By default, such events are passed on unchanged.
+    #[serde(default)]
+    pub drop_on_error: bool,
+
+    /// The maximum size of a decompressed payload, in bytes.
+    ///
+    /// Payloads decompressing to more than this can't be processed, which guards against payloads
+    /// crafted to decompress to huge sizes.
+    #[serde(default = "default_max_decompressed_bytes")]
+    pub max_decompressed_bytes: usize,
+}
+
+/// Whether to decompress or compress the payload.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Derivative, Eq, PartialEq)]
+#[derivative(Default)]
+#[serde(rename_all = "snake_case")]
+pub enum CompressionMode {
+    /// Decompress the payload.
+    #[derivative(Default)]
+    Decompress,
+
+    /// Compress the payload.
+    Compress,
+}
+
+/// The compression algorithm of the payload.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum CompressionAlgorithm {
+    /// [Gzip][gzip] compression.
+    ///
+    /// When decompressing, concatenated gzip members are decompressed as one payload.
+    ///
+    /// [gzip]: https://www.gzip.org/
+    Gzip,
+
+    /// [Zlib][zlib] compression.
+    ///
+    /// [zlib]: https://zlib.net/
+    Zlib,
+
+    /// [Zstandard][zstd] compression.
+    ///
+    /// [zstd]: https://facebook.github.io/zstd/
+    Zstd,
+
+    /// [Snappy][snappy] compression, in the raw format without framing.
+    ///
+    /// [snappy]: https://github.com/google/snappy
+    Snappy,
+}
+
+impl CompressionAlgorithm {
+    const fn as_str(self) -> &'static str {
+        match self {
+            Self::Gzip => "gzip",
+            Self::Zlib => "zlib",
+            Self::Zstd => "zstd",
+            Self::Snappy => "snappy",
+        }
+    }
+}
+
+fn default_field() -> String {
+    log_schema().message_key().to_owned()
+}
+
+const fn default_max_decompressed_bytes() -> usize {
+    10 * 1024 * 1024
+}
+
+impl GenerateConfig for CompressionConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self {
+            field: default_field(),
+            target_field: None,
+            mode: CompressionMode::Decompress,
+            algorithm: CompressionAlgorithm::Gzip,
+            base64: false,
+            drop_on_error: false,
+            max_decompressed_bytes: default_max_decompressed_bytes(),
+        })
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+impl TransformConfig for CompressionConfig {
+    async fn build(&self, _context: &TransformContext) -> crate::Result<Transform> {
+        Ok(Transform::function(Compression::new(self)))
+    }
+
+    fn input(&self) -> Input {
+        Input::log()
+    }
+
+    fn outputs(&self, _: &schema::Definition) -> Vec<Output> {
+        vec![Output::default(DataType::Log)]
+    }
+
+    fn enable_concurrency(&self) -> bool {
+        true
+    }
+}
+
+#[derive(Debug, Snafu)]
+enum CompressionError {
+    #[snafu(display("Field is not a string."))]
+    NotBytes,
+    #[snafu(display("Invalid base64: {}", source))]
+    Base64 { source: base64::DecodeError },
+    #[snafu(display("Failed to decompress {} payload: {}", algorithm, source))]
+    Decompress {
+        algorithm: &'static str,
+        source: std::io::Error,
+    },
+    #[snafu(display("Failed to compress {} payload: {}", algorithm, source))]
+    Compress {
+        algorithm: &'static str,
+        source: std::io::Error,
+    },
+}
+
+#[derive(Clone, Debug)]
+pub struct Compression {
+    field: String,
+    target_field: Option<String>,
+    mode: CompressionMode,
+    algorithm: CompressionAlgorithm,
+    base64: bool,
+    drop_on_error: bool,
+    max_decompressed_bytes: usize,
+}
+
+impl Compression {
+    pub fn new(config: &CompressionConfig) -> Self {
+        Self {
+            field: config.field.clone(),
+            target_field: config.target_field.clone(),
+            mode: config.mode,
+            algorithm: config.algorithm,
+            base64: config.base64,
+            drop_on_error: config.drop_on_error,
+            max_decompressed_bytes: config.max_decompressed_bytes,
+        }
+    }
+
+    fn process(&self, value: &Value) -> Result<Value, CompressionError> {
+        let payload = value.as_bytes().ok_or(CompressionError::NotBytes)?;
+        Ok(match self.mode {
+            CompressionMode::Decompress => {
+                let payload = if self.base64 {
+                    base64::decode(payload).context(Base64Snafu)?.into()
+                } else {
+                    payload.clone()
+                };
+                Value::Bytes(
+                    decompress(self.algorithm, payload, self.max_decompressed_bytes).context(
+                        DecompressSnafu {
+                            algorithm: self.algorithm.as_str(),
+                        },
+                    )?,
+                )
+            }
+            CompressionMode::Compress => {
+                let compressed = compress(self.algorithm, payload).context(CompressSnafu {
+                    algorithm: self.algorithm.as_str(),
+                })?;
+                if self.base64 {
+                    Value::from(base64::encode(compressed))
+                } else {
+                    Value::Bytes(compressed)
+                }
+            }
+        })
+    }
+}
+
+fn snappy_error(error: snap::Error) -> std::io::Error {
+    std::io::Error::new(std::io::ErrorKind::InvalidData, error)
+}
+
+fn too_large(max_bytes: usize) -> std::io::Error {
+    std::io::Error::new(
+        std::io::ErrorKind::InvalidData,
+        format!("Decompressed payload exceeds {} bytes.", max_bytes),
+    )
+}
+
+/// Decompresses a payload, failing once more than `max_bytes` are decompressed.
+fn decompress(
+    algorithm: CompressionAlgorithm,
+    payload: Bytes,
+    max_bytes: usize,
+) -> std::io::Result<Bytes> {
+    // One more byte than allowed is read, to tell payloads of exactly the maximum size from larger
+    // ones.
+    let limit = max_bytes as u64 + 1;
+    let mut decompressed = Vec::new();
+    match algorithm {
+        CompressionAlgorithm::Gzip => {
+            MultiGzDecoder::new(payload.reader())
+                .take(limit)
+                .read_to_end(&mut decompressed)?;
+        }
+        CompressionAlgorithm::Zlib => {
+            ZlibDecoder::new(payload.reader())
+                .take(limit)
+                .read_to_end(&mut decompressed)?;
+        }
+        CompressionAlgorithm::Zstd => {
+            zstd::stream::Decoder::new(payload.reader())?
+                .take(limit)
+                .read_to_end(&mut decompressed)?;
+        }
+        CompressionAlgorithm::Snappy => {
+            // The decompressed length is stored ahead of the payload.
+            if snap::raw::decompress_len(&payload).map_err(snappy_error)? > max_bytes {
+                return Err(too_large(max_bytes));
+            }
+            decompressed = snap::raw::Decoder::new()
+                .decompress_vec(&payload)
+                .map_err(snappy_error)?;
+        }
+    }
+    if decompressed.len() > max_bytes {
+        return Err(too_large(max_bytes));
+    }
+    Ok(decompressed.into())
+}
+
+fn compress(algorithm: CompressionAlgorithm, payload: &[u8]) -> std::io::Result<Bytes> {
+    let compressed = match algorithm {
+        CompressionAlgorithm::Gzip => {
+            let mut encoder = GzEncoder::new(Vec::new(), flate2::Compression::default());
+            encoder.write_all(payload)?;
+            encoder.finish()?
+        }
+        CompressionAlgorithm::Zlib => {
+            let mut encoder = ZlibEncoder::new(Vec::new(), flate2::Compression::default());
+            encoder.write_all(payload)?;
+            encoder.finish()?
+        }
+        CompressionAlgorithm::Zstd => zstd::stream::encode_all(payload, 0)?,
+        CompressionAlgorithm::Snappy => snap::raw::Encoder::new()
+            .compress_vec(payload)
+            .map_err(snappy_error)?,
+    };
+    Ok(compressed.into())
+}
+
+impl FunctionTransform for Compression {
+    fn transform(&mut self, output: &mut OutputBuffer, mut event: Event) {
+        let log = event.as_mut_log();
+        let value = match log.get(self.field.as_str()) {
+            Some(value) => value,
+            None => {
+                if self.drop_on_error {
+                    emit!(ParserMissingFieldError::<DROP_EVENT> { field: &self.field });
+                    return;
+                }
+                emit!(ParserMissingFieldError::<RETAIN_EVENT> { field: &self.field });
+                output.push(event);
+                return;
+            }
+        };
+
+        match self.process(value) {
+            Ok(value) => {
+                let target_field = self.target_field.as_ref().unwrap_or(&self.field);
+                log.insert(target_field.as_str(), value);
+            }
+            Err(error) => {
+                emit!(CompressionFieldError {
+                    field: &self.field,
+                    error,
+                    drop_event: self.drop_on_error,
+                });
+                if self.drop_on_error {
+                    return;
+                }
+            }
+        }
+        output.push(event);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{event::LogEvent, transforms::test::transform_one};
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<CompressionConfig>();
+    }
+
+    fn compression(
+        mode: CompressionMode,
+        algorithm: CompressionAlgorithm,
+        base64: bool,
+    ) -> Compression {
+        Compression::new(&CompressionConfig {
+            field: default_field(),
+            target_field: None,
+            mode,
+            algorithm,
+            base64,
+            drop_on_error: false,
+            max_decompressed_bytes: default_max_decompressed_bytes(),
+        })
+    }
+
+    fn roundtrip(algorithm: CompressionAlgorithm, base64: bool) {
+        let message = "The quick brown fox jumps over the lazy dog.";
+        let event = Event::from(LogEvent::from(message));
+
+        let compressed = transform_one(
+            &mut compression(CompressionMode::Compress, algorithm, base64),
+            event,
+        )
+        .unwrap();
+        assert_ne!(compressed.as_log()["message"], message.into());
+
+        let decompressed = transform_one(
+            &mut compression(CompressionMode::Decompress, algorithm, base64),
+            compressed,
+        )
+        .unwrap();
+        assert_eq!(decompressed.as_log()["message"], message.into());
+    }
+
+    #[test]
+    fn roundtrips_all_algorithms() {
+        for algorithm in [
+            CompressionAlgorithm::Gzip,
+            CompressionAlgorithm::Zlib,
+            CompressionAlgorithm::Zstd,
+            CompressionAlgorithm::Snappy,
+        ] {
+            roundtrip(algorithm, false);
+            roundtrip(algorithm, true);
+        }
+    }
+
+    #[test]
+    fn decompresses_base64_gzip_into_target_field() {
+        let mut encoder = GzEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder.write_all(br#"{"logEvents":[]}"#).unwrap();
+        let payload = base64::encode(encoder.finish().unwrap());
+
+        let mut log = LogEvent::default();
+        log.insert("data", payload.clone());
+        let mut transform = Compression::new(&CompressionConfig {
+            field: "data".to_owned(),
+            target_field: Some("decoded".to_owned()),
+            mode: CompressionMode::Decompress,
+            algorithm: CompressionAlgorithm::Gzip,
+            base64: true,
+            drop_on_error: false,
+            max_decompressed_bytes: default_max_decompressed_bytes(),
+        });
+
+        let event = transform_one(&mut transform, log.into()).unwrap();
+        assert_eq!(event.as_log()["data"], payload.into());
+        assert_eq!(event.as_log()["decoded"], r#"{"logEvents":[]}"#.into());
+    }
+
+    #[test]
+    fn passes_on_invalid_payloads() {
+        let event = Event::from(LogEvent::from("not compressed"));
+
+        let output = transform_one(
+            &mut compression(
+                CompressionMode::Decompress,
+                CompressionAlgorithm::Zstd,
+                false,
+            ),
+            event.clone(),
+        );
+        assert_eq!(output, Some(event));
+    }
+
+    #[test]
+    fn limits_decompressed_size() {
+        let payload = vec![b'a'; 1024];
+        for algorithm in [
+            CompressionAlgorithm::Gzip,
+            CompressionAlgorithm::Zlib,
+            CompressionAlgorithm::Zstd,
+            CompressionAlgorithm::Snappy,
+        ] {
+            let compressed = compress(algorithm, &payload).unwrap();
+            assert_eq!(
+                decompress(algorithm, compressed.clone(), 1024).unwrap(),
+                payload
+            );
+            let error = decompress(algorithm, compressed, 1023).unwrap_err();
+            assert_eq!(
+                error.to_string(),
+                "Decompressed payload exceeds 1023 bytes."
+            );
+        }
+    }
+
+    #[test]
+    fn drops_invalid_payloads() {
+        let mut transform = Compression::new(&CompressionConfig {
+            drop_on_error: true,
+            ..toml::from_str("algorithm = \"gzip\"").unwrap()
+        });
+
+        assert_eq!(
+            transform_one(&mut transform, LogEvent::from("not compressed").into()),
+            None
+        );
+        assert_eq!(
+            transform_one(&mut transform, LogEvent::default().into()),
+            None
+        );
+    }
+}
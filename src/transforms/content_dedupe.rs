@@ -0,0 +1,325 @@
+use std::{
+    collections::{HashSet, VecDeque},
+    hash::Hasher,
+    time::{Duration, Instant},
+};
+
+use seahash::SeaHasher;
+use snafu::Snafu;
+use vector_config::configurable_component;
+
+use crate::{
+    config::{
+        log_schema, DataType, GenerateConfig, Input, Output, TransformConfig, TransformContext,
+    },
+    event::{Event, LogEvent, Value},
+    internal_events::ContentDedupeEventsDropped,
+    schema,
+    transforms::{FunctionTransform, OutputBuffer, Transform},
+};
+
+/// The fields hashed to identify duplicate events.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub enum HashFieldsConfig {
+    /// Hashes only the specified fields.
+    #[serde(rename = "match")]
+    MatchFields(#[configurable(transparent)] Vec<String>),
+
+    /// Hashes all the fields except for the ignored ones.
+    #[serde(rename = "ignore")]
+    IgnoreFields(#[configurable(transparent)] Vec<String>),
+}
+
+/// Configuration for the `content_dedupe` transform.
+#[configurable_component(transform("content_dedupe"))]
+#[configurable(metadata(status = "alpha"))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct ContentDedupeConfig {
+    /// The fields hashed to identify duplicate events.
+    ///
+    /// By default, all the fields except for the `timestamp` field are hashed, as sources usually
+    /// set it to the time the event was read, which differs between replays of the same payload.
+    /// The specific field name used will be the one set in the global
+    /// [`log schema`][global_log_schema] configuration.
+    ///
+    /// [global_log_schema]: https://vector.dev/docs/reference/configuration/global-options/#log_schema
+    #[configurable(derived)]
+    #[serde(default)]
+    pub fields: Option<HashFieldsConfig>,
+
+    /// The length of the window in which duplicate events are dropped, in seconds.
+    ///
+    /// The window of an event starts when it's first seen, and isn't extended by its duplicates.
+    #[serde(default = "default_window_secs")]
+    pub window_secs: f64,
+
+    /// The number of event hashes remembered at most.
+    ///
+    /// Once this many hashes are remembered, the oldest ones are forgotten before their window
+    /// ends. Each hash takes about 40 bytes of memory.
+    #[serde(default = "default_capacity")]
+    pub capacity: usize,
+}
+
+const fn default_window_secs() -> f64 {
+    300.0
+}
+
+const fn default_capacity() -> usize {
+    100_000
+}
+
+impl ContentDedupeConfig {
+    /// The default fields can't be populated by Serde, as they depend on the configured log
+    /// schema, which is only known once the config is parsed.
+    fn fill_default_fields(&self) -> HashFieldsConfig {
+        self.fields.clone().unwrap_or_else(|| {
+            HashFieldsConfig::IgnoreFields(vec![log_schema().timestamp_key().into()])
+        })
+    }
+}
+
+impl GenerateConfig for ContentDedupeConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self {
+            fields: None,
+            window_secs: default_window_secs(),
+            capacity: default_capacity(),
+        })
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+impl TransformConfig for ContentDedupeConfig {
+    async fn build(&self, _context: &TransformContext) -> crate::Result<Transform> {
+        ContentDedupe::new(self).map(Transform::function)
+    }
+
+    fn input(&self) -> Input {
+        Input::log()
+    }
+
+    fn outputs(&self, _: &schema::Definition) -> Vec<Output> {
+        vec![Output::default(DataType::Log)]
+    }
+}
+
+#[derive(Debug, Snafu)]
+pub enum ConfigError {
+    #[snafu(display("`window_secs` must be positive"))]
+    InvalidWindow,
+
+    #[snafu(display("`capacity` must be non-zero"))]
+    ZeroCapacity,
+}
+
+pub struct ContentDedupe {
+    fields: HashFieldsConfig,
+    window: Duration,
+    capacity: usize,
+    /// The hashes of the events seen within the window.
+    hashes: HashSet<u64>,
+    /// The same hashes, oldest first, with the time they were first seen.
+    seen: VecDeque<(Instant, u64)>,
+}
+
+impl ContentDedupe {
+    pub fn new(config: &ContentDedupeConfig) -> crate::Result<Self> {
+        if !config.window_secs.is_finite() || config.window_secs <= 0.0 {
+            return Err(Box::new(ConfigError::InvalidWindow));
+        }
+        if config.capacity == 0 {
+            return Err(Box::new(ConfigError::ZeroCapacity));
+        }
+        Ok(Self {
+            fields: config.fill_default_fields(),
+            window: Duration::from_secs_f64(config.window_secs),
+            capacity: config.capacity,
+            hashes: HashSet::new(),
+            seen: VecDeque::new(),
+        })
+    }
+
+    /// Whether the event with the given hash was already seen within the window, remembering it if
+    /// it wasn't.
+    fn is_duplicate(&mut self, hash: u64, now: Instant) -> bool {
+        while let Some((seen_at, seen_hash)) = self.seen.front() {
+            if now.saturating_duration_since(*seen_at) < self.window {
+                break;
+            }
+            self.hashes.remove(seen_hash);
+            self.seen.pop_front();
+        }
+
+        if !self.hashes.insert(hash) {
+            return true;
+        }
+
+        if self.seen.len() >= self.capacity {
+            if let Some((_, oldest)) = self.seen.pop_front() {
+                self.hashes.remove(&oldest);
+            }
+        }
+        self.seen.push_back((now, hash));
+        false
+    }
+
+    fn transform_one(&mut self, event: Event, now: Instant) -> Option<Event> {
+        let hash = hash_fields(event.as_log(), &self.fields);
+        if self.is_duplicate(hash, now) {
+            emit!(ContentDedupeEventsDropped { count: 1 });
+            None
+        } else {
+            Some(event)
+        }
+    }
+}
+
+/// Hashes the JSON encoding of the fields of `log` selected by `fields`.
+///
+/// The fields of an event are iterated in a stable order, as they're held in sorted maps, so that
+/// equal events have equal hashes.
+fn hash_fields(log: &LogEvent, fields: &HashFieldsConfig) -> u64 {
+    let mut hasher = SeaHasher::default();
+    let mut hash_value = |name: &str, value: &Value| {
+        hasher.write(name.as_bytes());
+        hasher.write_u8(0);
+        serde_json::to_writer(HasherWriter(&mut hasher), value).expect("values are serializable");
+        hasher.write_u8(0);
+    };
+
+    match fields {
+        HashFieldsConfig::MatchFields(fields) => {
+            for name in fields {
+                if let Some(value) = log.get(name.as_str()) {
+                    hash_value(name, value);
+                }
+            }
+        }
+        HashFieldsConfig::IgnoreFields(fields) => {
+            if let Some(all_fields) = log.all_fields() {
+                for (name, value) in all_fields {
+                    if !fields.contains(&name) {
+                        hash_value(&name, value);
+                    }
+                }
+            }
+        }
+    }
+    hasher.finish()
+}
+
+/// Feeds the bytes written to it to a hasher, so that values can be hashed without being encoded
+/// into a buffer first.
+struct HasherWriter<'a>(&'a mut SeaHasher);
+
+impl<'a> std::io::Write for HasherWriter<'a> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.write(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+impl FunctionTransform for ContentDedupe {
+    fn transform(&mut self, output: &mut OutputBuffer, event: Event) {
+        if let Some(event) = self.transform_one(event, Instant::now()) {
+            output.push(event);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<ContentDedupeConfig>();
+    }
+
+    fn dedupe(config: &str) -> ContentDedupe {
+        ContentDedupe::new(&toml::from_str::<ContentDedupeConfig>(config).unwrap()).unwrap()
+    }
+
+    fn event(message: &str, timestamp: i64) -> Event {
+        let mut log = LogEvent::from(message);
+        log.insert("timestamp", Utc.timestamp(timestamp, 0));
+        log.insert("host", "localhost");
+        Event::Log(log)
+    }
+
+    #[test]
+    fn drops_duplicates_ignoring_timestamps() {
+        let mut dedupe = dedupe("");
+        let now = Instant::now();
+
+        assert!(dedupe.transform_one(event("first", 1), now).is_some());
+        assert!(dedupe.transform_one(event("second", 1), now).is_some());
+        assert!(dedupe.transform_one(event("first", 2), now).is_none());
+
+        let mut different = event("first", 3);
+        different.as_mut_log().insert("host", "remote");
+        assert!(dedupe.transform_one(different, now).is_some());
+    }
+
+    #[test]
+    fn hashes_matched_fields() {
+        let mut dedupe = dedupe(r#"fields.match = ["message"]"#);
+        let now = Instant::now();
+
+        assert!(dedupe.transform_one(event("first", 1), now).is_some());
+        let mut different = event("first", 2);
+        different.as_mut_log().insert("host", "remote");
+        assert!(dedupe.transform_one(different, now).is_none());
+    }
+
+    #[test]
+    fn forgets_events_after_window() {
+        let mut dedupe = dedupe("window_secs = 10");
+        let now = Instant::now();
+
+        assert!(dedupe.transform_one(event("first", 1), now).is_some());
+        assert!(dedupe
+            .transform_one(event("first", 1), now + Duration::from_secs(5))
+            .is_none());
+        assert!(dedupe
+            .transform_one(event("first", 1), now + Duration::from_secs(10))
+            .is_some());
+    }
+
+    #[test]
+    fn forgets_oldest_events_at_capacity() {
+        let mut dedupe = dedupe("capacity = 2");
+        let now = Instant::now();
+
+        for message in ["first", "second", "third"] {
+            assert!(dedupe.transform_one(event(message, 1), now).is_some());
+        }
+        assert_eq!(dedupe.seen.len(), 2);
+        assert_eq!(dedupe.hashes.len(), 2);
+        assert!(dedupe.transform_one(event("third", 1), now).is_none());
+        assert!(dedupe.transform_one(event("first", 1), now).is_some());
+    }
+
+    #[test]
+    fn rejects_invalid_config() {
+        assert!(ContentDedupe::new(
+            &toml::from_str::<ContentDedupeConfig>("window_secs = 0").unwrap()
+        )
+        .is_err());
+        assert!(ContentDedupe::new(
+            &toml::from_str::<ContentDedupeConfig>("capacity = 0").unwrap()
+        )
+        .is_err());
+    }
+}
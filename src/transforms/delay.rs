@@ -0,0 +1,752 @@
+use std::{
+    collections::BTreeMap,
+    fs,
+    num::{NonZeroU64, NonZeroUsize},
+    path::{Path, PathBuf},
+    pin::Pin,
+    time::Duration,
+};
+
+use async_stream::stream;
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+use chrono::{DateTime, TimeZone, Utc};
+use futures::{Stream, StreamExt};
+use prost::Message;
+use snafu::{OptionExt, ResultExt, Snafu};
+use vector_common::TimeZone as VrlTimeZone;
+use vector_config::configurable_component;
+use vector_core::compile_vrl;
+use vrl::{diagnostic::Formatter, CompilationResult, CompileConfig, Program, Runtime};
+
+use crate::{
+    config::{DataType, GenerateConfig, Input, Output, TransformConfig, TransformContext},
+    event::{
+        proto::EventWrapper, Event, EventFinalizers, EventStatus, Finalizable, TargetEvents, Value,
+        VrlTarget,
+    },
+    internal_events::{
+        DelayQueueReadError, DelayQueueWriteError, DelayReleaseTimeError, DelayedEvents,
+    },
+    schema,
+    transforms::{TaskTransform, Transform},
+};
+
+const SEGMENT_EXTENSION: &str = "events";
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("At least one of `delay_secs` or `release_at` must be set."))]
+    NoDelay,
+    #[snafu(display("`delay_secs` must be a positive number of seconds."))]
+    InvalidDelay,
+    #[snafu(display(
+        "The `delay` transform requires a component key to name its data directory."
+    ))]
+    WithoutKey,
+    #[snafu(display("Invalid `release_at` expression:\n{}", diagnostics))]
+    InvalidReleaseAt { diagnostics: String },
+    #[snafu(display("Could not read the spilled events in {:?}: {}", path, source))]
+    ReadDataDir {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+}
+
+#[derive(Debug, Snafu)]
+enum SegmentError {
+    #[snafu(display("Failed to read segment: {}", source))]
+    Read { source: std::io::Error },
+    #[snafu(display("Failed to decode segment: {}", source))]
+    Decode { source: prost::DecodeError },
+    #[snafu(display("Segment is truncated."))]
+    Truncated,
+}
+
+/// Configuration for the `delay` transform.
+#[configurable_component(transform("delay"))]
+#[configurable(metadata(status = "alpha"))]
+#[derive(Clone, Debug, Derivative)]
+#[derivative(Default)]
+#[serde(deny_unknown_fields, default)]
+pub struct DelayConfig {
+    /// How long each event is held, in seconds.
+    ///
+    /// When `release_at` is also set, this is only used for the events whose release time can't be
+    /// evaluated.
+    pub delay_secs: Option<f64>,
+
+    /// A [Vector Remap Language][vrl] (VRL) expression evaluating to the timestamp each event is
+    /// held until.
+    ///
+    /// The expression can't modify the event. Events for which it fails, or evaluates to anything
+    /// other than a timestamp, are held for `delay_secs` instead, or released immediately if
+    /// `delay_secs` isn't set. Events whose release time is in the past are released immediately.
+    ///
+    /// [vrl]: https://vector.dev/docs/reference/vrl
+    pub release_at: Option<String>,
+
+    /// The longest time an event is held, in seconds.
+    ///
+    /// Release times further in the future are brought back to this. By default, events are held
+    /// for as long as their release time says.
+    pub max_delay_secs: Option<NonZeroU64>,
+
+    /// The maximum number of events held in memory.
+    ///
+    /// Once more events are held, those released last are written to the data directory, and read
+    /// back when they're about to be released.
+    #[derivative(Default(value = "default_max_memory_events()"))]
+    pub max_memory_events: NonZeroUsize,
+
+    /// The directory used to write the events held on disk.
+    ///
+    /// By default, the global `data_dir` option is used. Please make sure the user Vector is
+    /// running as has write permissions to this directory.
+    pub data_dir: Option<PathBuf>,
+}
+
+fn default_max_memory_events() -> NonZeroUsize {
+    NonZeroUsize::new(10_000).expect("static non-zero number")
+}
+
+impl GenerateConfig for DelayConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self {
+            delay_secs: Some(60.0),
+            ..Default::default()
+        })
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+impl TransformConfig for DelayConfig {
+    async fn build(&self, context: &TransformContext) -> crate::Result<Transform> {
+        Delay::new(self, context).map(Transform::event_task)
+    }
+
+    fn input(&self) -> Input {
+        Input::all()
+    }
+
+    fn outputs(&self, _: &schema::Definition) -> Vec<Output> {
+        vec![Output::default(DataType::all())]
+    }
+}
+
+pub struct Delay {
+    delay: chrono::Duration,
+    release_at: Option<Program>,
+    max_delay: Option<chrono::Duration>,
+    timezone: VrlTimeZone,
+    queue: DelayQueue,
+}
+
+impl Delay {
+    pub fn new(config: &DelayConfig, context: &TransformContext) -> crate::Result<Self> {
+        if config.delay_secs.is_none() && config.release_at.is_none() {
+            return Err(Box::new(BuildError::NoDelay));
+        }
+        let delay = match config.delay_secs {
+            Some(secs) if !secs.is_finite() || secs < 0.0 => {
+                return Err(Box::new(BuildError::InvalidDelay))
+            }
+            Some(secs) => to_chrono(Duration::from_secs_f64(secs)),
+            None => chrono::Duration::zero(),
+        };
+
+        let release_at = config
+            .release_at
+            .as_ref()
+            .map(|source| compile_release_at(source, context))
+            .transpose()?;
+
+        let key = context.key.as_ref().context(WithoutKeySnafu)?;
+        let dir = context
+            .globals
+            .resolve_and_make_data_subdir(config.data_dir.as_ref(), key.id())?;
+        let queue = DelayQueue::open(dir, config.max_memory_events.get())?;
+
+        Ok(Self {
+            delay,
+            release_at,
+            max_delay: config
+                .max_delay_secs
+                .map(|secs| to_chrono(Duration::from_secs(secs.get()))),
+            timezone: context.globals.timezone,
+            queue,
+        })
+    }
+
+    /// Evaluates the time at which an event is released.
+    fn release_time(&self, event: Event, now: DateTime<Utc>) -> (DateTime<Utc>, Event) {
+        let (release_at, event) = match &self.release_at {
+            Some(program) => {
+                let mut target = VrlTarget::new(event, program.info());
+                let result = Runtime::default().resolve(&mut target, program, &self.timezone);
+                let event = match target.into_events() {
+                    TargetEvents::One(event) => event,
+                    _ => panic!(
+                        "Event was modified in `release_at`. This is an internal compiler error."
+                    ),
+                };
+                let release_at = match result {
+                    Ok(Value::Timestamp(timestamp)) => Some(timestamp),
+                    Ok(value) => {
+                        emit!(DelayReleaseTimeError {
+                            error: format!("Expected a timestamp, got {}.", value.kind_str()),
+                        });
+                        None
+                    }
+                    Err(error) => {
+                        emit!(DelayReleaseTimeError {
+                            error: error.to_string(),
+                        });
+                        None
+                    }
+                };
+                (release_at, event)
+            }
+            None => (None, event),
+        };
+
+        let release_at = release_at.unwrap_or_else(|| after(now, self.delay));
+        let release_at = match self.max_delay {
+            Some(max_delay) => release_at.min(after(now, max_delay)),
+            None => release_at,
+        };
+        (release_at, event)
+    }
+}
+
+fn compile_release_at(source: &str, context: &TransformContext) -> crate::Result<Program> {
+    let functions = vrl_stdlib::all()
+        .into_iter()
+        .chain(enrichment::vrl_functions().into_iter())
+        .chain(vector_vrl_functions::vrl_functions())
+        .collect::<Vec<_>>();
+
+    let state = vrl::state::TypeState::default();
+
+    let mut config = CompileConfig::default();
+    config.set_custom(context.enrichment_tables.clone());
+    config.set_custom(crate::host_metadata::store());
+    crate::protobuf_descriptors::set_custom(&mut config);
+    config.set_read_only();
+
+    let CompilationResult {
+        program, warnings, ..
+    } = compile_vrl(source, &functions, &state, config).map_err(|diagnostics| {
+        BuildError::InvalidReleaseAt {
+            diagnostics: Formatter::new(source, diagnostics).colored().to_string(),
+        }
+    })?;
+
+    if !warnings.is_empty() {
+        let warnings = Formatter::new(source, warnings).colored().to_string();
+        warn!(message = "VRL compilation warning.", %warnings);
+    }
+
+    Ok(program)
+}
+
+fn to_chrono(duration: Duration) -> chrono::Duration {
+    chrono::Duration::from_std(duration).unwrap_or_else(|_| chrono::Duration::max_value())
+}
+
+fn after(now: DateTime<Utc>, duration: chrono::Duration) -> DateTime<Utc> {
+    now.checked_add_signed(duration)
+        .unwrap_or(DateTime::<Utc>::MAX_UTC)
+}
+
+impl TaskTransform<Event> for Delay {
+    fn transform(
+        self: Box<Self>,
+        mut input_rx: Pin<Box<dyn Stream<Item = Event> + Send>>,
+    ) -> Pin<Box<dyn Stream<Item = Event> + Send>>
+    where
+        Self: 'static,
+    {
+        let mut delay = self;
+
+        Box::pin(stream! {
+            loop {
+                let now = Utc::now();
+                for event in delay.queue.release(now).await {
+                    yield event;
+                }
+
+                let next_release = delay
+                    .queue
+                    .next_release()
+                    .map(|release_at| (release_at - now).to_std().unwrap_or_default());
+
+                tokio::select! {
+                    maybe_event = input_rx.next() => match maybe_event {
+                        Some(event) => {
+                            let (release_at, event) = delay.release_time(event, Utc::now());
+                            delay.queue.push(release_at, event).await;
+                        }
+                        None => break,
+                    },
+                    _ = tokio::time::sleep(next_release.unwrap_or_default()), if next_release.is_some() => {}
+                }
+            }
+
+            // Events still held when the input ends are written to disk, to be restored when the
+            // transform starts again. Only those that can't be written are released right away.
+            for event in delay.queue.persist().await {
+                yield event;
+            }
+        })
+    }
+}
+
+/// A file holding events that didn't fit in memory.
+///
+/// The finalizers of its events are kept in memory, in the order of the events in the file, and
+/// attached back to the events when they're read.
+struct Segment {
+    id: u64,
+    path: PathBuf,
+    /// The earliest release time of its events.
+    release_at: DateTime<Utc>,
+    count: usize,
+    finalizers: Vec<EventFinalizers>,
+}
+
+/// Events ordered by release time, kept in memory up to a limit and in segment files beyond it.
+struct DelayQueue {
+    dir: PathBuf,
+    max_memory_events: usize,
+    /// Events keyed by release time, and by the order in which they were pushed to keep that order
+    /// among events released at the same time.
+    memory: BTreeMap<(DateTime<Utc>, u64), Event>,
+    segments: Vec<Segment>,
+    next_sequence: u64,
+    next_segment_id: u64,
+}
+
+impl DelayQueue {
+    /// Opens the queue in a directory, picking up the segments left by a previous run.
+    ///
+    /// The finalizers of the events of these segments are lost, so they can't be acknowledged.
+    fn open(dir: PathBuf, max_memory_events: usize) -> crate::Result<Self> {
+        let mut segments = Vec::new();
+        for entry in fs::read_dir(&dir).with_context(|_| ReadDataDirSnafu { path: dir.clone() })? {
+            let path = entry
+                .with_context(|_| ReadDataDirSnafu { path: dir.clone() })?
+                .path();
+            if let Some((id, release_at)) = parse_segment_name(&path) {
+                segments.push(Segment {
+                    id,
+                    path,
+                    release_at,
+                    count: 0,
+                    finalizers: Vec::new(),
+                });
+            }
+        }
+        if !segments.is_empty() {
+            info!(
+                message = "Restoring events spilled to disk by a previous run.",
+                segments = segments.len(),
+                dir = ?dir,
+            );
+        }
+
+        Ok(Self {
+            dir,
+            max_memory_events,
+            memory: BTreeMap::new(),
+            next_segment_id: segments
+                .iter()
+                .map(|segment| segment.id + 1)
+                .max()
+                .unwrap_or(0),
+            segments,
+            next_sequence: 0,
+        })
+    }
+
+    async fn push(&mut self, release_at: DateTime<Utc>, event: Event) {
+        self.insert(release_at, event);
+        if self.memory.len() > self.max_memory_events {
+            self.spill().await;
+        }
+        self.emit_held();
+    }
+
+    fn insert(&mut self, release_at: DateTime<Utc>, event: Event) {
+        self.memory.insert((release_at, self.next_sequence), event);
+        self.next_sequence += 1;
+    }
+
+    /// The earliest time at which events are due.
+    fn next_release(&self) -> Option<DateTime<Utc>> {
+        let memory = self.memory.keys().next().map(|(release_at, _)| *release_at);
+        let disk = self.segments.iter().map(|segment| segment.release_at).min();
+        memory.into_iter().chain(disk).min()
+    }
+
+    /// Takes the events due by `now`, in the order of their release times.
+    async fn release(&mut self, now: DateTime<Utc>) -> Vec<Event> {
+        let mut index = 0;
+        while index < self.segments.len() {
+            if self.segments[index].release_at <= now {
+                let segment = self.segments.swap_remove(index);
+                for (release_at, event) in self.read_segment(segment).await {
+                    self.insert(release_at, event);
+                }
+            } else {
+                index += 1;
+            }
+        }
+
+        let later = self.memory.split_off(&(now, u64::MAX));
+        let released = std::mem::replace(&mut self.memory, later);
+        if self.memory.len() > self.max_memory_events {
+            self.spill().await;
+        }
+        if !released.is_empty() {
+            self.emit_held();
+        }
+        released.into_values().collect()
+    }
+
+    /// Writes all the events held in memory to a segment, to be restored by the next run.
+    ///
+    /// The events of this segment and of the ones already written are acknowledged once they're
+    /// dropped along with the queue, as they're kept on disk. The events are returned if the
+    /// segment can't be written.
+    async fn persist(&mut self) -> Vec<Event> {
+        let held = std::mem::take(&mut self.memory);
+        let unwritten = match self.write_segment(held).await {
+            Ok(()) => Vec::new(),
+            Err(held) => held.into_values().collect(),
+        };
+        self.emit_held();
+        unwritten
+    }
+
+    /// Writes the events released last to a segment, to bring the events held in memory back to
+    /// half of the limit.
+    ///
+    /// The events are kept in memory if the segment can't be written.
+    async fn spill(&mut self) {
+        let split_key = match self.memory.keys().nth(self.max_memory_events / 2) {
+            Some(key) => *key,
+            None => return,
+        };
+        let spilled = self.memory.split_off(&split_key);
+        if let Err(mut spilled) = self.write_segment(spilled).await {
+            self.memory.append(&mut spilled);
+        }
+    }
+
+    /// Writes events to a new segment, handing them back if it can't be written.
+    async fn write_segment(
+        &mut self,
+        mut events: BTreeMap<(DateTime<Utc>, u64), Event>,
+    ) -> Result<(), BTreeMap<(DateTime<Utc>, u64), Event>> {
+        let release_at = match events.keys().next() {
+            Some((release_at, _)) => *release_at,
+            None => return Ok(()),
+        };
+
+        let id = self.next_segment_id;
+        let path = self.dir.join(format!(
+            "{:020}-{}.{}",
+            id,
+            release_at.timestamp_millis(),
+            SEGMENT_EXTENSION
+        ));
+
+        let mut finalizers = Vec::with_capacity(events.len());
+        let mut records = BytesMut::new();
+        for ((release_at, _), event) in events.iter_mut() {
+            finalizers.push(event.take_finalizers());
+            records.put_i64(release_at.timestamp_millis());
+            EventWrapper::from(event.clone())
+                .encode_length_delimited(&mut records)
+                .expect("the buffer grows as needed");
+        }
+
+        match tokio::fs::write(&path, records).await {
+            Ok(()) => {
+                self.next_segment_id += 1;
+                self.segments.push(Segment {
+                    id,
+                    path,
+                    release_at,
+                    count: events.len(),
+                    finalizers,
+                });
+                Ok(())
+            }
+            Err(error) => {
+                emit!(DelayQueueWriteError { error, path });
+                for (event, finalizers) in events.values_mut().zip(finalizers) {
+                    event.metadata_mut().merge_finalizers(finalizers);
+                }
+                Err(events)
+            }
+        }
+    }
+
+    /// Reads the events of a segment back, and removes its file.
+    ///
+    /// The events are errored if the segment can't be read.
+    async fn read_segment(&mut self, segment: Segment) -> Vec<(DateTime<Utc>, Event)> {
+        let events = match tokio::fs::read(&segment.path)
+            .await
+            .context(ReadSnafu)
+            .and_then(|records| decode_segment(Bytes::from(records)))
+        {
+            Ok(events) => events,
+            Err(error) => {
+                emit!(DelayQueueReadError {
+                    error: error.to_string(),
+                    path: segment.path.clone(),
+                    count: segment.count.max(segment.finalizers.len()) as u64,
+                });
+                for finalizers in segment.finalizers {
+                    finalizers.update_status(EventStatus::Errored);
+                }
+                Vec::new()
+            }
+        };
+
+        if let Err(error) = tokio::fs::remove_file(&segment.path).await {
+            emit!(DelayQueueWriteError {
+                error,
+                path: segment.path.clone(),
+            });
+        }
+
+        let mut finalizers = segment.finalizers.into_iter();
+        events
+            .into_iter()
+            .map(|(release_at, mut event)| {
+                if let Some(finalizers) = finalizers.next() {
+                    event.metadata_mut().merge_finalizers(finalizers);
+                }
+                (release_at, event)
+            })
+            .collect()
+    }
+
+    fn emit_held(&self) {
+        emit!(DelayedEvents {
+            in_memory: self.memory.len(),
+            on_disk: self.segments.iter().map(|segment| segment.count).sum(),
+        });
+    }
+}
+
+fn decode_segment(mut records: Bytes) -> Result<Vec<(DateTime<Utc>, Event)>, SegmentError> {
+    let mut events = Vec::new();
+    while records.has_remaining() {
+        if records.remaining() < 8 {
+            return Err(SegmentError::Truncated);
+        }
+        let release_at = Utc.timestamp_millis(records.get_i64());
+        let event = EventWrapper::decode_length_delimited(&mut records).context(DecodeSnafu)?;
+        events.push((release_at, Event::from(event)));
+    }
+    Ok(events)
+}
+
+/// Parses the ID and the earliest release time of a segment from the name of its file.
+fn parse_segment_name(path: &Path) -> Option<(u64, DateTime<Utc>)> {
+    if path.extension()? != SEGMENT_EXTENSION {
+        return None;
+    }
+    let (id, release_at) = path.file_stem()?.to_str()?.split_once('-')?;
+    Some((
+        id.parse().ok()?,
+        Utc.timestamp_millis(release_at.parse().ok()?),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Instant;
+
+    use tokio::sync::mpsc;
+    use tokio_stream::wrappers::ReceiverStream;
+    use vector_core::event::{BatchNotifier, BatchStatus};
+
+    use super::*;
+    use crate::{
+        config::ComponentKey,
+        event::LogEvent,
+        test_util::{components::assert_transform_compliance, temp_dir},
+        transforms::test::create_topology,
+    };
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<DelayConfig>();
+    }
+
+    fn event(message: &str) -> Event {
+        Event::from(LogEvent::from(message))
+    }
+
+    #[tokio::test]
+    async fn holds_events_for_delay() {
+        assert_transform_compliance(async {
+            let config = DelayConfig {
+                delay_secs: Some(0.5),
+                data_dir: Some(temp_dir()),
+                ..Default::default()
+            };
+            let (tx, rx) = mpsc::channel(1);
+            let (topology, mut out) = create_topology(ReceiverStream::new(rx), config).await;
+
+            let start = Instant::now();
+            tx.send(event("delayed")).await.unwrap();
+            let output = out.recv().await.unwrap();
+            assert!(start.elapsed() >= Duration::from_millis(500));
+            assert_eq!(output.as_log()["message"], "delayed".into());
+
+            drop(tx);
+            topology.stop().await;
+            assert_eq!(out.recv().await, None);
+        })
+        .await;
+    }
+
+    #[tokio::test]
+    async fn releases_events_at_release_at() {
+        let config = DelayConfig {
+            release_at: Some("timestamp!(.release_at)".to_owned()),
+            data_dir: Some(temp_dir()),
+            ..Default::default()
+        };
+        let (tx, rx) = mpsc::channel(2);
+        let (topology, mut out) = create_topology(ReceiverStream::new(rx), config).await;
+
+        let now = Utc::now();
+        for (message, delay_ms) in [("later", 600), ("sooner", 200)] {
+            let mut log = LogEvent::from(message);
+            log.insert("release_at", now + chrono::Duration::milliseconds(delay_ms));
+            tx.send(log.into()).await.unwrap();
+        }
+
+        assert_eq!(
+            out.recv().await.unwrap().as_log()["message"],
+            "sooner".into()
+        );
+        assert_eq!(
+            out.recv().await.unwrap().as_log()["message"],
+            "later".into()
+        );
+        assert!(Utc::now() >= now + chrono::Duration::milliseconds(600));
+
+        drop(tx);
+        topology.stop().await;
+    }
+
+    #[tokio::test]
+    async fn spills_events_to_disk() {
+        let dir = temp_dir();
+        let mut queue = DelayQueue::open(dir.clone(), 4).unwrap();
+
+        let (batch, mut receiver) = BatchNotifier::new_with_receiver();
+        let now = Utc::now();
+        for i in 0..10 {
+            let log = LogEvent::from(format!("event {}", i)).with_batch_notifier(&batch);
+            queue
+                .push(now + chrono::Duration::seconds(10 - i), log.into())
+                .await;
+        }
+        drop(batch);
+
+        assert!(queue.memory.len() <= 4);
+        assert!(!queue.segments.is_empty());
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), queue.segments.len());
+
+        let released = queue.release(now + chrono::Duration::seconds(20)).await;
+        let messages = released
+            .iter()
+            .map(|event| event.as_log()["message"].to_string_lossy())
+            .collect::<Vec<_>>();
+        let expected = (0..10)
+            .rev()
+            .map(|i| format!("event {}", i))
+            .collect::<Vec<_>>();
+        assert_eq!(messages, expected);
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 0);
+
+        drop(released);
+        assert_eq!(receiver.try_recv(), Ok(BatchStatus::Delivered));
+    }
+
+    #[tokio::test]
+    async fn restores_events_spilled_by_previous_run() {
+        let dir = temp_dir();
+        let now = Utc::now();
+
+        let mut queue = DelayQueue::open(dir.clone(), 2).unwrap();
+        for i in 0..5 {
+            queue
+                .push(now + chrono::Duration::seconds(i), event("held"))
+                .await;
+        }
+        let spilled = queue
+            .segments
+            .iter()
+            .map(|segment| segment.count)
+            .sum::<usize>();
+        assert!(spilled > 0);
+        drop(queue);
+
+        let mut queue = DelayQueue::open(dir, 2).unwrap();
+        assert!(queue.next_release().is_some());
+        let released = queue.release(now + chrono::Duration::seconds(10)).await;
+        assert_eq!(released.len(), spilled);
+    }
+
+    #[tokio::test]
+    async fn persists_held_events_when_input_ends() {
+        let dir = temp_dir();
+        let config = DelayConfig {
+            delay_secs: Some(3600.0),
+            data_dir: Some(dir.clone()),
+            ..Default::default()
+        };
+        let context = TransformContext {
+            key: Some(ComponentKey::from("delay")),
+            ..Default::default()
+        };
+        let delay = Box::new(Delay::new(&config, &context).unwrap());
+
+        let (batch, mut receiver) = BatchNotifier::new_with_receiver();
+        let input = futures::stream::iter((0..3).map(|i| {
+            LogEvent::from(format!("event {}", i))
+                .with_batch_notifier(&batch)
+                .into()
+        }));
+        drop(batch);
+        let output = delay.transform(Box::pin(input)).collect::<Vec<_>>().await;
+        assert!(output.is_empty());
+        assert_eq!(receiver.try_recv(), Ok(BatchStatus::Delivered));
+
+        let mut queue = DelayQueue::open(dir.join("delay"), 10).unwrap();
+        let released = queue.release(Utc::now() + chrono::Duration::hours(2)).await;
+        assert_eq!(released.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn rejects_missing_delay() {
+        let error = Delay::new(&DelayConfig::default(), &TransformContext::default())
+            .err()
+            .unwrap();
+        assert_eq!(
+            error.to_string(),
+            "At least one of `delay_secs` or `release_at` must be set."
+        );
+    }
+}
@@ -0,0 +1,246 @@
+use vector_common::conversion::Conversion;
+use vector_config::configurable_component;
+
+use crate::{
+    config::{DataType, GenerateConfig, Input, Output, TransformConfig, TransformContext},
+    event::{Event, Value},
+    schema,
+    transforms::{schema_map::Rule, FunctionTransform, OutputBuffer, Transform},
+};
+
+/// The version of the Elastic Common Schema the presets map to.
+pub const ECS_VERSION: &str = "8.4.0";
+
+/// Configuration for the `ecs_normalize` transform.
+#[configurable_component(transform("ecs_normalize"))]
+#[configurable(metadata(status = "alpha"))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct EcsNormalizeConfig {
+    /// The shapes of events to map to ECS fields.
+    ///
+    /// The presets are applied in order. Fields not produced by any of them are left as they are.
+    pub presets: Vec<EcsPreset>,
+
+    /// Whether to set the `ecs.version` field to the version of ECS the presets map to.
+    #[serde(default = "crate::serde::default_true")]
+    pub tag_version: bool,
+}
+
+/// A shape of events, along with how it maps to ECS fields.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum EcsPreset {
+    /// Events of the `syslog` source, or parsed with the `parse_syslog` VRL function.
+    Syslog,
+
+    /// Access logs parsed with the `parse_nginx_log` VRL function.
+    Nginx,
+
+    /// Events of the `kubernetes_logs` source.
+    Kubernetes,
+}
+
+impl EcsPreset {
+    fn rules(self) -> Vec<Rule> {
+        match self {
+            Self::Syslog => vec![
+                rename("appname", "process.name"),
+                coerce("procid", "process.pid", Conversion::Integer),
+                rename("hostname", "host.hostname"),
+                rename("severity", "log.syslog.severity.name"),
+                rename("facility", "log.syslog.facility.name"),
+                rename("msgid", "event.code"),
+                rename("version", "log.syslog.version"),
+                default("event.module", "syslog"),
+            ],
+            Self::Nginx => vec![
+                rename("client", "source.ip"),
+                rename("user", "user.name"),
+                rename("method", "http.request.method"),
+                rename("path", "url.original"),
+                rename("referer", "http.request.referrer"),
+                rename("agent", "user_agent.original"),
+                coerce("status", "http.response.status_code", Conversion::Integer),
+                coerce("size", "http.response.body.bytes", Conversion::Integer),
+                default("event.module", "nginx"),
+                default("event.dataset", "nginx.access"),
+                default("event.category", "web"),
+            ],
+            Self::Kubernetes => vec![
+                rename("kubernetes.pod_name", "kubernetes.pod.name"),
+                rename("kubernetes.pod_uid", "kubernetes.pod.uid"),
+                rename("kubernetes.pod_ip", "kubernetes.pod.ip"),
+                rename("kubernetes.pod_namespace", "kubernetes.namespace"),
+                rename("kubernetes.pod_node_name", "kubernetes.node.name"),
+                rename("kubernetes.pod_labels", "kubernetes.labels"),
+                rename("kubernetes.pod_annotations", "kubernetes.annotations"),
+                rename("kubernetes.container_name", "container.name"),
+                rename("kubernetes.container_id", "container.id"),
+                rename("kubernetes.container_image", "container.image.name"),
+                rename("file", "log.file.path"),
+            ],
+        }
+    }
+}
+
+fn rename(field: &str, target: &str) -> Rule {
+    Rule {
+        field: field.to_owned(),
+        rename: Some(target.to_owned()),
+        coerce: None,
+        default: None,
+    }
+}
+
+fn coerce(field: &str, target: &str, conversion: Conversion) -> Rule {
+    Rule {
+        coerce: Some(conversion),
+        ..rename(field, target)
+    }
+}
+
+fn default(field: &str, value: &str) -> Rule {
+    Rule {
+        field: field.to_owned(),
+        rename: None,
+        coerce: None,
+        default: Some(Value::from(value)),
+    }
+}
+
+impl GenerateConfig for EcsNormalizeConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self {
+            presets: vec![EcsPreset::Syslog],
+            tag_version: true,
+        })
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+impl TransformConfig for EcsNormalizeConfig {
+    async fn build(&self, _context: &TransformContext) -> crate::Result<Transform> {
+        Ok(Transform::function(EcsNormalize::new(self)))
+    }
+
+    fn input(&self) -> Input {
+        Input::log()
+    }
+
+    fn outputs(&self, _: &schema::Definition) -> Vec<Output> {
+        vec![Output::default(DataType::Log)]
+    }
+
+    fn enable_concurrency(&self) -> bool {
+        true
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct EcsNormalize {
+    rules: Vec<Rule>,
+    tag_version: bool,
+}
+
+impl EcsNormalize {
+    pub fn new(config: &EcsNormalizeConfig) -> Self {
+        Self {
+            rules: config
+                .presets
+                .iter()
+                .flat_map(|preset| preset.rules())
+                .collect(),
+            tag_version: config.tag_version,
+        }
+    }
+}
+
+impl FunctionTransform for EcsNormalize {
+    fn transform(&mut self, output: &mut OutputBuffer, mut event: Event) {
+        let log = event.as_mut_log();
+        for rule in &self.rules {
+            rule.apply(log);
+        }
+        if self.tag_version {
+            log.insert("ecs.version", ECS_VERSION);
+        }
+        output.push(event);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{event::LogEvent, transforms::test::transform_one};
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<EcsNormalizeConfig>();
+    }
+
+    fn normalize(presets: Vec<EcsPreset>, log: LogEvent) -> LogEvent {
+        let mut transform = EcsNormalize::new(&EcsNormalizeConfig {
+            presets,
+            tag_version: true,
+        });
+        transform_one(&mut transform, log.into())
+            .unwrap()
+            .into_log()
+    }
+
+    #[test]
+    fn maps_syslog_events() {
+        let mut log = LogEvent::from("connection accepted");
+        log.insert("appname", "sshd");
+        log.insert("procid", "8421");
+        log.insert("hostname", "bastion");
+        log.insert("severity", "info");
+        let log = normalize(vec![EcsPreset::Syslog], log);
+
+        assert_eq!(log["message"], "connection accepted".into());
+        assert_eq!(log["process.name"], "sshd".into());
+        assert_eq!(log["process.pid"], 8421.into());
+        assert_eq!(log["host.hostname"], "bastion".into());
+        assert_eq!(log["log.syslog.severity.name"], "info".into());
+        assert_eq!(log["event.module"], "syslog".into());
+        assert_eq!(log["ecs.version"], ECS_VERSION.into());
+        assert!(log.get("appname").is_none());
+    }
+
+    #[test]
+    fn maps_nginx_events() {
+        let mut log = LogEvent::default();
+        log.insert("client", "192.0.2.1");
+        log.insert("method", "GET");
+        log.insert("path", "/index.html");
+        log.insert("status", 200);
+        log.insert("size", 612);
+        let log = normalize(vec![EcsPreset::Nginx], log);
+
+        assert_eq!(log["source.ip"], "192.0.2.1".into());
+        assert_eq!(log["http.request.method"], "GET".into());
+        assert_eq!(log["url.original"], "/index.html".into());
+        assert_eq!(log["http.response.status_code"], 200.into());
+        assert_eq!(log["http.response.body.bytes"], 612.into());
+        assert_eq!(log["event.dataset"], "nginx.access".into());
+    }
+
+    #[test]
+    fn maps_kubernetes_events() {
+        let mut log = LogEvent::default();
+        log.insert("kubernetes.pod_name", "web-1");
+        log.insert("kubernetes.pod_namespace", "default");
+        log.insert("kubernetes.container_image", "nginx:1.23");
+        log.insert("kubernetes.pod_labels.app", "web");
+        let log = normalize(vec![EcsPreset::Kubernetes, EcsPreset::Nginx], log);
+
+        assert_eq!(log["kubernetes.pod.name"], "web-1".into());
+        assert_eq!(log["kubernetes.namespace"], "default".into());
+        assert_eq!(log["kubernetes.labels.app"], "web".into());
+        assert_eq!(log["container.image.name"], "nginx:1.23".into());
+        assert!(log.get("kubernetes.pod_name").is_none());
+    }
+}
@@ -1,13 +1,22 @@
+use std::{collections::HashMap, pin::Pin, time::Duration};
+
+use async_stream::stream;
+use chrono::Utc;
+use futures::{Stream, StreamExt};
+use snafu::Snafu;
 use vector_common::internal_event::{Count, InternalEventHandle as _, Registered};
 use vector_config::configurable_component;
 
 use crate::{
     conditions::{AnyCondition, Condition},
-    config::{DataType, GenerateConfig, Input, Output, TransformConfig, TransformContext},
-    event::Event,
-    internal_events::FilterEventsDropped,
+    config::{
+        log_schema, DataType, GenerateConfig, Input, Output, TransformConfig, TransformContext,
+    },
+    event::{Event, LogEvent},
+    internal_events::{FilterEventsDropped, FilterQuotaExceeded, TemplateRenderingError},
     schema,
-    transforms::{FunctionTransform, OutputBuffer, Transform},
+    template::Template,
+    transforms::{FunctionTransform, OutputBuffer, TaskTransform, Transform},
 };
 
 /// Configuration for the `filter` transform.
@@ -17,11 +26,49 @@ use crate::{
 pub struct FilterConfig {
     #[configurable(derived)]
     condition: AnyCondition,
+
+    #[configurable(derived)]
+    quota: Option<QuotaConfig>,
+}
+
+/// Keeping at most a number of the events matching the condition per key and window.
+///
+/// The events matching the condition beyond the quota of their key are dropped, and a summary event
+/// counting them is emitted at the end of the window, so storms of similar events are reduced to a
+/// sample of them.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct QuotaConfig {
+    /// The maximum number of events kept per key and window.
+    max_events: u32,
+
+    /// The duration of the windows, in seconds.
+    window_secs: f64,
+
+    /// The key the events are counted by, such as `{{ service }}`.
+    ///
+    /// All the events share a single quota when unset, or if the key can't be rendered and has no
+    /// `on_render_error` policy.
+    #[configurable(metadata(templatable))]
+    key_field: Option<Template>,
+
+    /// Whether a summary event is emitted at the end of the windows in which events of a key were
+    /// dropped.
+    ///
+    /// The summary event is a log event with the number of events dropped in the
+    /// `suppressed_count` field, and their key in the `key` field. Only the dropped log events are
+    /// summarized, so summaries only go where log events already do.
+    #[serde(default = "crate::serde::default_true")]
+    summary: bool,
 }
 
 impl From<AnyCondition> for FilterConfig {
     fn from(condition: AnyCondition) -> Self {
-        Self { condition }
+        Self {
+            condition,
+            quota: None,
+        }
     }
 }
 
@@ -38,9 +85,11 @@ impl GenerateConfig for FilterConfig {
 #[async_trait::async_trait]
 impl TransformConfig for FilterConfig {
     async fn build(&self, context: &TransformContext) -> crate::Result<Transform> {
-        Ok(Transform::function(Filter::new(
-            self.condition.build(&context.enrichment_tables)?,
-        )))
+        let condition = self.condition.build(&context.enrichment_tables)?;
+        match &self.quota {
+            None => Ok(Transform::function(Filter::new(condition))),
+            Some(quota) => QuotaFilter::new(condition, quota).map(Transform::event_task),
+        }
     }
 
     fn input(&self) -> Input {
@@ -52,7 +101,8 @@ impl TransformConfig for FilterConfig {
     }
 
     fn enable_concurrency(&self) -> bool {
-        true
+        // The quotas are counted by a single task.
+        self.quota.is_none()
     }
 }
 
@@ -82,6 +132,176 @@ impl FunctionTransform for Filter {
     }
 }
 
+/// The number of events kept and dropped for a key in the current window.
+#[derive(Default)]
+struct KeyCount {
+    kept: u32,
+    dropped_logs: u64,
+}
+
+/// The counts of the events of each key in the current window.
+struct Quotas {
+    max_events: u32,
+    counts: HashMap<Option<String>, KeyCount>,
+}
+
+impl Quotas {
+    /// Counts an event with `key`, returning whether it's kept.
+    fn check(&mut self, key: Option<String>, is_log: bool) -> bool {
+        let count = self.counts.entry(key).or_default();
+        if count.kept < self.max_events {
+            count.kept += 1;
+            true
+        } else {
+            if is_log {
+                count.dropped_logs += 1;
+            }
+            false
+        }
+    }
+
+    /// Ends the current window, returning the keys of which log events were dropped in it along
+    /// with their count.
+    fn end_window(&mut self) -> Vec<(Option<String>, u64)> {
+        self.counts
+            .drain()
+            .filter(|(_, count)| count.dropped_logs > 0)
+            .map(|(key, count)| (key, count.dropped_logs))
+            .collect()
+    }
+}
+
+fn summary_event(key: Option<String>, dropped: u64, window: Duration) -> Event {
+    let mut log = LogEvent::from(format!(
+        "Suppressed {} events exceeding the quota over the last {} seconds.",
+        dropped,
+        window.as_secs_f64()
+    ));
+    log.insert(log_schema().timestamp_key(), Utc::now());
+    log.insert("suppressed_count", dropped as i64);
+    if let Some(key) = key {
+        log.insert("key", key);
+    }
+    log.into()
+}
+
+pub struct QuotaFilter {
+    condition: Condition,
+    quotas: Quotas,
+    window: Duration,
+    key_field: Option<Template>,
+    summary: bool,
+    events_dropped: Registered<FilterEventsDropped>,
+    quota_exceeded: Registered<FilterQuotaExceeded>,
+}
+
+impl QuotaFilter {
+    pub fn new(condition: Condition, config: &QuotaConfig) -> crate::Result<Self> {
+        if config.max_events == 0 || !config.window_secs.is_finite() || config.window_secs <= 0.0 {
+            return Err(Box::new(ConfigError::NonZeroQuota));
+        }
+
+        Ok(Self {
+            condition,
+            quotas: Quotas {
+                max_events: config.max_events,
+                counts: HashMap::new(),
+            },
+            window: Duration::from_secs_f64(config.window_secs),
+            key_field: config.key_field.clone(),
+            summary: config.summary,
+            events_dropped: register!(FilterEventsDropped),
+            quota_exceeded: register!(FilterQuotaExceeded),
+        })
+    }
+
+    /// Returns the key of the event, or `Err` if it must be dropped as its key can't be rendered.
+    fn key(&self, event: &Event) -> Result<Option<String>, ()> {
+        match &self.key_field {
+            None => Ok(None),
+            Some(template) => match template.render_string(event) {
+                Ok(key) => Ok(Some(key)),
+                Err(error) => {
+                    let drop_event = template.drops_on_render_error();
+                    emit!(TemplateRenderingError {
+                        error,
+                        field: Some("key_field"),
+                        drop_event,
+                    });
+                    if drop_event {
+                        Err(())
+                    } else {
+                        Ok(None)
+                    }
+                }
+            },
+        }
+    }
+}
+
+impl TaskTransform<Event> for QuotaFilter {
+    fn transform(
+        mut self: Box<Self>,
+        mut input_rx: Pin<Box<dyn Stream<Item = Event> + Send>>,
+    ) -> Pin<Box<dyn Stream<Item = Event> + Send>>
+    where
+        Self: 'static,
+    {
+        let mut windows =
+            tokio::time::interval_at(tokio::time::Instant::now() + self.window, self.window);
+
+        Box::pin(stream! {
+            loop {
+                tokio::select! {
+                    // The end of the window goes first, or a busy input would keep it from ending.
+                    biased;
+
+                    _ = windows.tick() => {
+                        for (key, dropped) in self.quotas.end_window() {
+                            if self.summary {
+                                yield summary_event(key, dropped, self.window);
+                            }
+                        }
+                    }
+                    maybe_event = input_rx.next() => {
+                        let event = match maybe_event {
+                            Some(event) => event,
+                            None => break,
+                        };
+                        let (result, event) = self.condition.check(event);
+                        if !result {
+                            self.events_dropped.emit(Count(1));
+                            continue;
+                        }
+                        let key = match self.key(&event) {
+                            Ok(key) => key,
+                            Err(()) => continue,
+                        };
+                        if self.quotas.check(key, matches!(event, Event::Log(_))) {
+                            yield event;
+                        } else {
+                            self.quota_exceeded.emit(Count(1));
+                        }
+                    }
+                }
+            }
+
+            // Summarize the events dropped in the last, partial, window.
+            if self.summary {
+                for (key, dropped) in self.quotas.end_window() {
+                    yield summary_event(key, dropped, self.window);
+                }
+            }
+        })
+    }
+}
+
+#[derive(Debug, Snafu)]
+pub enum ConfigError {
+    #[snafu(display("`quota.max_events` and `quota.window_secs` must be non-zero"))]
+    NonZeroQuota,
+}
+
 #[cfg(test)]
 mod test {
     use tokio::sync::mpsc;
@@ -128,4 +348,109 @@ mod test {
         })
         .await;
     }
+
+    #[test]
+    fn quotas_keep_first_events_per_key() {
+        let mut quotas = Quotas {
+            max_events: 2,
+            counts: HashMap::new(),
+        };
+        let a = || Some("a".to_owned());
+
+        assert!(quotas.check(a(), true));
+        assert!(quotas.check(a(), true));
+        assert!(!quotas.check(a(), true));
+        assert!(!quotas.check(a(), true));
+        assert!(quotas.check(None, true));
+        assert_eq!(quotas.end_window(), vec![(a(), 2)]);
+
+        assert!(quotas.check(a(), true));
+        assert_eq!(quotas.end_window(), vec![]);
+
+        // Dropped metrics aren't summarized, as summaries are log events.
+        assert!(quotas.check(a(), false));
+        assert!(!quotas.check(a(), false));
+        assert!(quotas.check(None, false));
+        assert_eq!(quotas.end_window(), vec![]);
+    }
+
+    #[tokio::test]
+    async fn quota_summarizes_dropped_events() {
+        let config = toml::from_str::<FilterConfig>(
+            r#"
+            condition = ".level == \"error\""
+            quota.max_events = 1
+            quota.window_secs = 60
+            quota.key_field = "{{ service }}"
+            "#,
+        )
+        .unwrap();
+        let filter = config.build(&TransformContext::default()).await.unwrap();
+
+        let events = [
+            ("api", "error"),
+            ("api", "error"),
+            ("api", "info"),
+            ("db", "error"),
+            ("api", "error"),
+        ]
+        .into_iter()
+        .map(|(service, level)| {
+            let mut log = LogEvent::from("message");
+            log.insert("service", service);
+            log.insert("level", level);
+            Event::from(log)
+        })
+        .collect::<Vec<_>>();
+        let output = filter
+            .into_task()
+            .transform_events(Box::pin(futures::stream::iter(events.clone())))
+            .collect::<Vec<_>>()
+            .await;
+
+        assert_eq!(output.len(), 3);
+        assert_eq!(output[0], events[0]);
+        assert_eq!(output[1], events[3]);
+        let summary = output[2].as_log();
+        assert_eq!(summary["suppressed_count"], 2.into());
+        assert_eq!(summary["key"], "api".into());
+    }
+
+    #[tokio::test]
+    async fn quota_drops_events_without_key() {
+        for (policy, kept) in [(None, 2), (Some("drop"), 1)] {
+            let mut config = toml::from_str::<FilterConfig>(
+                r#"
+                condition = "true"
+                quota.max_events = 10
+                quota.window_secs = 60
+                quota.key_field = "{{ service }}"
+                quota.summary = false
+                "#,
+            )
+            .unwrap();
+            if let Some(policy) = policy {
+                let quota = config.quota.as_mut().unwrap();
+                quota.key_field = Some(
+                    serde_json::from_value(serde_json::json!({
+                        "template": "{{ service }}",
+                        "on_render_error": policy,
+                    }))
+                    .unwrap(),
+                );
+            }
+            let filter = config.build(&TransformContext::default()).await.unwrap();
+
+            let mut log = LogEvent::from("message");
+            log.insert("service", "api");
+            let events = vec![Event::from(log), Event::from(LogEvent::from("message"))];
+            let output = filter
+                .into_task()
+                .transform_events(Box::pin(futures::stream::iter(events.clone())))
+                .collect::<Vec<_>>()
+                .await;
+
+            assert_eq!(output, events[..kept]);
+        }
+    }
 }
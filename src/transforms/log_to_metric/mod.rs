@@ -1,7 +1,16 @@
-use std::{collections::BTreeMap, convert::TryFrom, num::ParseFloatError};
+use std::{
+    collections::{BTreeMap, HashMap},
+    convert::TryFrom,
+    num::ParseFloatError,
+    pin::Pin,
+    time::Duration,
+};
 
+use async_stream::stream;
 use chrono::Utc;
+use futures::{Stream, StreamExt};
 use indexmap::IndexMap;
+use snafu::Snafu;
 use vector_config::configurable_component;
 
 use crate::{
@@ -9,7 +18,7 @@ use crate::{
         log_schema, DataType, GenerateConfig, Input, Output, TransformConfig, TransformContext,
     },
     event::{
-        metric::{Metric, MetricKind, MetricValue, StatisticKind},
+        metric::{Metric, MetricKind, MetricSeries, MetricValue, StatisticKind},
         Event, Value,
     },
     internal_events::{
@@ -18,9 +27,13 @@ use crate::{
     },
     schema,
     template::{Template, TemplateParseError, TemplateRenderingError},
-    transforms::{FunctionTransform, OutputBuffer, Transform},
+    transforms::{FunctionTransform, OutputBuffer, TaskTransform, Transform},
 };
 
+mod hyperloglog;
+
+use hyperloglog::HyperLogLog;
+
 /// Configuration for the `log_to_metric` transform.
 #[configurable_component(transform("log_to_metric"))]
 #[derive(Clone, Debug)]
@@ -28,6 +41,12 @@ use crate::{
 pub struct LogToMetricConfig {
     /// A list of metrics to generate.
     pub metrics: Vec<MetricConfig>,
+
+    /// The interval, in seconds, at which the estimates of `distinct` metrics are emitted.
+    ///
+    /// Distinct values are counted separately for each interval.
+    #[serde(default = "default_flush_interval_secs")]
+    pub flush_interval_secs: u64,
 }
 
 /// Specification of a counter derived from a log event.
@@ -133,6 +152,35 @@ pub struct SummaryConfig {
     tags: Option<IndexMap<String, String>>,
 }
 
+/// Specification of a distinct count derived from a log event.
+#[configurable_component]
+#[derive(Clone, Debug)]
+pub struct DistinctConfig {
+    /// Name of the field in the event to count the distinct values of.
+    field: String,
+
+    /// Overrides the name of the gauge.
+    ///
+    /// If not specified, `field` is used as the name of the gauge.
+    name: Option<String>,
+
+    /// Sets the namespace for the gauge.
+    namespace: Option<String>,
+
+    /// Tags to apply to the gauge.
+    ///
+    /// Distinct values are counted separately for each set of rendered tags.
+    tags: Option<IndexMap<String, String>>,
+
+    /// The precision of the HyperLogLog sketch the distinct values are counted with, between `4`
+    /// and `16`.
+    ///
+    /// Each sketch uses `2^precision` bytes of memory, and its estimates have a relative standard
+    /// error of about `1.04 / sqrt(2^precision)`, or 1.6% with the default precision.
+    #[serde(default = "default_precision")]
+    precision: u8,
+}
+
 /// Specification of a metric derived from a log event.
 #[configurable_component]
 #[derive(Clone, Debug)]
@@ -152,6 +200,9 @@ pub enum MetricConfig {
 
     /// A summary.
     Summary(#[configurable(derived)] SummaryConfig),
+
+    /// A gauge estimating the number of distinct values of a field.
+    Distinct(#[configurable(derived)] DistinctConfig),
 }
 
 impl MetricConfig {
@@ -162,6 +213,7 @@ impl MetricConfig {
             MetricConfig::Gauge(GaugeConfig { field, .. }) => field,
             MetricConfig::Set(SetConfig { field, .. }) => field,
             MetricConfig::Summary(SummaryConfig { field, .. }) => field,
+            MetricConfig::Distinct(DistinctConfig { field, .. }) => field,
         }
     }
 }
@@ -174,9 +226,32 @@ const fn default_kind() -> MetricKind {
     MetricKind::Incremental
 }
 
+const fn default_flush_interval_secs() -> u64 {
+    60
+}
+
+const fn default_precision() -> u8 {
+    12
+}
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display(
+        "Precision of distinct metric {:?} must be between {} and {}",
+        field,
+        hyperloglog::MIN_PRECISION,
+        hyperloglog::MAX_PRECISION
+    ))]
+    InvalidPrecision { field: String },
+
+    #[snafu(display("`flush_interval_secs` must be non-zero"))]
+    ZeroFlushInterval,
+}
+
 #[derive(Debug, Clone)]
 pub struct LogToMetric {
     config: LogToMetricConfig,
+    distinct: HashMap<MetricSeries, HyperLogLog>,
 }
 
 impl GenerateConfig for LogToMetricConfig {
@@ -190,6 +265,7 @@ impl GenerateConfig for LogToMetricConfig {
                 kind: MetricKind::Incremental,
                 tags: None,
             })],
+            flush_interval_secs: default_flush_interval_secs(),
         })
         .unwrap()
     }
@@ -198,7 +274,31 @@ impl GenerateConfig for LogToMetricConfig {
 #[async_trait::async_trait]
 impl TransformConfig for LogToMetricConfig {
     async fn build(&self, _context: &TransformContext) -> crate::Result<Transform> {
-        Ok(Transform::function(LogToMetric::new(self.clone())))
+        let mut has_distinct = false;
+        for metric in &self.metrics {
+            if let MetricConfig::Distinct(distinct) = metric {
+                if !(hyperloglog::MIN_PRECISION..=hyperloglog::MAX_PRECISION)
+                    .contains(&distinct.precision)
+                {
+                    return Err(Box::new(BuildError::InvalidPrecision {
+                        field: distinct.field.clone(),
+                    }));
+                }
+                has_distinct = true;
+            }
+        }
+
+        if has_distinct && self.flush_interval_secs == 0 {
+            return Err(Box::new(BuildError::ZeroFlushInterval));
+        }
+
+        let transform = LogToMetric::new(self.clone());
+        // Distinct counts are emitted periodically, rather than for each event.
+        Ok(if has_distinct {
+            Transform::event_task(transform)
+        } else {
+            Transform::function(transform)
+        })
     }
 
     fn input(&self) -> Input {
@@ -215,8 +315,31 @@ impl TransformConfig for LogToMetricConfig {
 }
 
 impl LogToMetric {
-    pub const fn new(config: LogToMetricConfig) -> Self {
-        LogToMetric { config }
+    pub fn new(config: LogToMetricConfig) -> Self {
+        LogToMetric {
+            config,
+            distinct: HashMap::new(),
+        }
+    }
+
+    /// Emits the estimates of the distinct counts, and resets them.
+    fn flush_distinct_into(&mut self, output: &mut Vec<Event>) {
+        let timestamp = Some(Utc::now());
+        output.extend(self.distinct.drain().map(|(series, sketch)| {
+            let (name, tags) = (series.name, series.tags);
+            Event::Metric(
+                Metric::new(
+                    name.name,
+                    MetricKind::Absolute,
+                    MetricValue::Gauge {
+                        value: sketch.estimate().round(),
+                    },
+                )
+                .with_namespace(name.namespace)
+                .with_tags(tags)
+                .with_timestamp(timestamp),
+            )
+        }));
     }
 }
 
@@ -274,7 +397,12 @@ fn render_tags(
     })
 }
 
-fn to_metric(config: &MetricConfig, event: &Event) -> Result<Metric, TransformError> {
+/// Derives a metric from the event, or records the value of a distinct count in `distinct`.
+fn to_metric(
+    config: &MetricConfig,
+    event: &Event,
+    distinct: &mut HashMap<MetricSeries, HyperLogLog>,
+) -> Result<Option<Metric>, TransformError> {
     let log = event.as_log();
 
     let timestamp = log
@@ -319,15 +447,17 @@ fn to_metric(config: &MetricConfig, event: &Event) -> Result<Metric, TransformEr
 
             let tags = render_tags(&counter.tags, event)?;
 
-            Ok(Metric::new_with_metadata(
-                name,
-                counter.kind,
-                MetricValue::Counter { value },
-                metadata,
-            )
-            .with_namespace(namespace)
-            .with_tags(tags)
-            .with_timestamp(timestamp))
+            Ok(Some(
+                Metric::new_with_metadata(
+                    name,
+                    counter.kind,
+                    MetricValue::Counter { value },
+                    metadata,
+                )
+                .with_namespace(namespace)
+                .with_tags(tags)
+                .with_timestamp(timestamp),
+            ))
         }
         MetricConfig::Histogram(hist) => {
             let value = value.to_string_lossy().parse().map_err(|error| {
@@ -347,18 +477,20 @@ fn to_metric(config: &MetricConfig, event: &Event) -> Result<Metric, TransformEr
 
             let tags = render_tags(&hist.tags, event)?;
 
-            Ok(Metric::new_with_metadata(
-                name,
-                MetricKind::Incremental,
-                MetricValue::Distribution {
-                    samples: vector_core::samples![value => 1],
-                    statistic: StatisticKind::Histogram,
-                },
-                metadata,
-            )
-            .with_namespace(namespace)
-            .with_tags(tags)
-            .with_timestamp(timestamp))
+            Ok(Some(
+                Metric::new_with_metadata(
+                    name,
+                    MetricKind::Incremental,
+                    MetricValue::Distribution {
+                        samples: vector_core::samples![value => 1],
+                        statistic: StatisticKind::Histogram,
+                    },
+                    metadata,
+                )
+                .with_namespace(namespace)
+                .with_tags(tags)
+                .with_timestamp(timestamp),
+            ))
         }
         MetricConfig::Summary(summary) => {
             let value = value.to_string_lossy().parse().map_err(|error| {
@@ -378,18 +510,20 @@ fn to_metric(config: &MetricConfig, event: &Event) -> Result<Metric, TransformEr
 
             let tags = render_tags(&summary.tags, event)?;
 
-            Ok(Metric::new_with_metadata(
-                name,
-                MetricKind::Incremental,
-                MetricValue::Distribution {
-                    samples: vector_core::samples![value => 1],
-                    statistic: StatisticKind::Summary,
-                },
-                metadata,
-            )
-            .with_namespace(namespace)
-            .with_tags(tags)
-            .with_timestamp(timestamp))
+            Ok(Some(
+                Metric::new_with_metadata(
+                    name,
+                    MetricKind::Incremental,
+                    MetricValue::Distribution {
+                        samples: vector_core::samples![value => 1],
+                        statistic: StatisticKind::Summary,
+                    },
+                    metadata,
+                )
+                .with_namespace(namespace)
+                .with_tags(tags)
+                .with_timestamp(timestamp),
+            ))
         }
         MetricConfig::Gauge(gauge) => {
             let value = value.to_string_lossy().parse().map_err(|error| {
@@ -409,15 +543,17 @@ fn to_metric(config: &MetricConfig, event: &Event) -> Result<Metric, TransformEr
 
             let tags = render_tags(&gauge.tags, event)?;
 
-            Ok(Metric::new_with_metadata(
-                name,
-                MetricKind::Absolute,
-                MetricValue::Gauge { value },
-                metadata,
-            )
-            .with_namespace(namespace)
-            .with_tags(tags)
-            .with_timestamp(timestamp))
+            Ok(Some(
+                Metric::new_with_metadata(
+                    name,
+                    MetricKind::Absolute,
+                    MetricValue::Gauge { value },
+                    metadata,
+                )
+                .with_namespace(namespace)
+                .with_tags(tags)
+                .with_timestamp(timestamp),
+            ))
         }
         MetricConfig::Set(set) => {
             let value = value.to_string_lossy();
@@ -432,17 +568,46 @@ fn to_metric(config: &MetricConfig, event: &Event) -> Result<Metric, TransformEr
 
             let tags = render_tags(&set.tags, event)?;
 
-            Ok(Metric::new_with_metadata(
+            Ok(Some(
+                Metric::new_with_metadata(
+                    name,
+                    MetricKind::Incremental,
+                    MetricValue::Set {
+                        values: std::iter::once(value).collect(),
+                    },
+                    metadata,
+                )
+                .with_namespace(namespace)
+                .with_tags(tags)
+                .with_timestamp(timestamp),
+            ))
+        }
+        MetricConfig::Distinct(config) => {
+            let name = config.name.as_ref().unwrap_or(&config.field);
+            let name = render_template(name, event)?;
+
+            let namespace = config.namespace.as_ref();
+            let namespace = namespace
+                .map(|namespace| render_template(namespace, event))
+                .transpose()?;
+
+            let tags = render_tags(&config.tags, event)?;
+
+            let series = Metric::new(
                 name,
-                MetricKind::Incremental,
-                MetricValue::Set {
-                    values: std::iter::once(value).collect(),
-                },
-                metadata,
+                MetricKind::Absolute,
+                MetricValue::Gauge { value: 0.0 },
             )
             .with_namespace(namespace)
             .with_tags(tags)
-            .with_timestamp(timestamp))
+            .series()
+            .clone();
+            distinct
+                .entry(series)
+                .or_insert_with(|| HyperLogLog::new(config.precision))
+                .insert(&value.to_string_lossy());
+
+            Ok(None)
         }
     }
 }
@@ -450,10 +615,11 @@ fn to_metric(config: &MetricConfig, event: &Event) -> Result<Metric, TransformEr
 impl FunctionTransform for LogToMetric {
     fn transform(&mut self, output: &mut OutputBuffer, event: Event) {
         for config in self.config.metrics.iter() {
-            match to_metric(config, &event) {
-                Ok(metric) => {
+            match to_metric(config, &event, &mut self.distinct) {
+                Ok(Some(metric)) => {
                     output.push(Event::Metric(metric));
                 }
+                Ok(None) => {}
                 Err(TransformError::FieldNull { field }) => emit!(LogToMetricFieldNullError {
                     field: field.as_ref()
                 }),
@@ -483,6 +649,47 @@ impl FunctionTransform for LogToMetric {
     }
 }
 
+impl TaskTransform<Event> for LogToMetric {
+    fn transform(
+        mut self: Box<Self>,
+        mut input_rx: Pin<Box<dyn Stream<Item = Event> + Send>>,
+    ) -> Pin<Box<dyn Stream<Item = Event> + Send>>
+    where
+        Self: 'static,
+    {
+        let mut flush_stream =
+            tokio::time::interval(Duration::from_secs(self.config.flush_interval_secs));
+
+        Box::pin(stream! {
+            let mut output = Vec::new();
+            let mut done = false;
+            while !done {
+                tokio::select! {
+                    _ = flush_stream.tick() => {
+                        self.flush_distinct_into(&mut output);
+                    },
+                    maybe_event = input_rx.next() => {
+                        match maybe_event {
+                            None => {
+                                self.flush_distinct_into(&mut output);
+                                done = true;
+                            }
+                            Some(event) => {
+                                let mut buffer = OutputBuffer::with_capacity(self.config.metrics.len());
+                                FunctionTransform::transform(self.as_mut(), &mut buffer, event);
+                                output.extend(buffer.into_events());
+                            }
+                        }
+                    }
+                };
+                for event in output.drain(..) {
+                    yield event;
+                }
+            }
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use chrono::{offset::TimeZone, DateTime, Utc};
@@ -970,4 +1177,58 @@ mod tests {
             .with_timestamp(Some(ts()))
         );
     }
+
+    #[test]
+    fn distinct_users() {
+        let config = parse_config(
+            r#"
+            [[metrics]]
+            type = "distinct"
+            field = "user"
+            name = "unique_users"
+            tags = {region = "{{region}}"}
+            "#,
+        );
+
+        let mut transform = LogToMetric::new(config);
+        for (user, region) in [("a", "eu"), ("b", "eu"), ("a", "eu"), ("a", "us")] {
+            let mut event = create_event("user", user);
+            event.as_mut_log().insert("region", region);
+            assert!(transform_one(&mut transform, event).is_none());
+        }
+
+        let mut output = Vec::new();
+        transform.flush_distinct_into(&mut output);
+        let mut metrics = output
+            .into_iter()
+            .map(|event| event.into_metric())
+            .collect::<Vec<_>>();
+        metrics.sort_by(|a, b| a.series().cmp(b.series()));
+
+        assert_eq!(metrics.len(), 2);
+        assert_eq!(metrics[0].name(), "unique_users");
+        assert_eq!(metrics[0].tags().unwrap()["region"], "eu");
+        assert_eq!(metrics[0].value(), &MetricValue::Gauge { value: 2.0 });
+        assert_eq!(metrics[0].kind(), MetricKind::Absolute);
+        assert_eq!(metrics[1].tags().unwrap()["region"], "us");
+        assert_eq!(metrics[1].value(), &MetricValue::Gauge { value: 1.0 });
+
+        let mut output = Vec::new();
+        transform.flush_distinct_into(&mut output);
+        assert!(output.is_empty());
+    }
+
+    #[tokio::test]
+    async fn distinct_rejects_invalid_precision() {
+        let config = parse_config(
+            r#"
+            [[metrics]]
+            type = "distinct"
+            field = "user"
+            precision = 20
+            "#,
+        );
+
+        assert!(config.build(&TransformContext::default()).await.is_err());
+    }
 }
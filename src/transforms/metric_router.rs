@@ -0,0 +1,292 @@
+use std::collections::BTreeMap;
+
+use indexmap::IndexMap;
+use snafu::{ResultExt, Snafu};
+use vector_config::configurable_component;
+use vector_core::transform::SyncTransform;
+
+use crate::{
+    config::{DataType, GenerateConfig, Input, Output, TransformConfig, TransformContext},
+    event::{metric::Metric, Event},
+    schema,
+    transforms::Transform,
+};
+
+const UNMATCHED_ROUTE: &str = "_unmatched";
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("Route {:?} has an invalid pattern {:?}: {}", route, pattern, source))]
+    InvalidPattern {
+        route: String,
+        pattern: String,
+        source: glob::PatternError,
+    },
+}
+
+/// Configuration for the `metric_router` transform.
+#[configurable_component(transform("metric_router"))]
+#[configurable(metadata(status = "alpha"))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct MetricRouterConfig {
+    /// A table of route identifiers to the rules matching the metrics of the route.
+    ///
+    /// Each route can then be referenced as an input by other components with the name
+    /// `<transform_name>.<route_id>`. Unlike with the `route` transform, each metric is sent to a
+    /// single output: the first route, in order, whose rules it matches, or the
+    /// `<transform_name>._unmatched` output if it doesn't match any route.
+    ///
+    /// `_unmatched` is a reserved output name and cannot be used as a route name.
+    route: IndexMap<String, MetricRouteConfig>,
+}
+
+/// The rules matching the metrics of a route.
+///
+/// A metric matches a route if it matches each of the rules that are set.
+#[configurable_component]
+#[derive(Clone, Debug, Default)]
+#[serde(deny_unknown_fields)]
+pub struct MetricRouteConfig {
+    /// Glob patterns matched against the name of metrics, such as `http_*`.
+    ///
+    /// A metric matches if its name matches any of the patterns.
+    #[serde(default)]
+    names: Vec<String>,
+
+    /// Glob patterns matched against the namespace of metrics.
+    ///
+    /// A metric matches if its namespace matches any of the patterns. Metrics without a namespace
+    /// don't match.
+    #[serde(default)]
+    namespaces: Vec<String>,
+
+    /// A table of tag names to glob patterns matched against their value.
+    ///
+    /// A metric matches if it has each of the tags, with a value matching its pattern.
+    #[serde(default)]
+    tags: BTreeMap<String, String>,
+}
+
+impl GenerateConfig for MetricRouterConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self {
+            route: IndexMap::new(),
+        })
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+impl TransformConfig for MetricRouterConfig {
+    async fn build(&self, _context: &TransformContext) -> crate::Result<Transform> {
+        let router = MetricRouter::new(self)?;
+        Ok(Transform::synchronous(router))
+    }
+
+    fn input(&self) -> Input {
+        Input::metric()
+    }
+
+    fn validate(&self, _: &schema::Definition) -> Result<(), Vec<String>> {
+        if self.route.contains_key(UNMATCHED_ROUTE) {
+            Err(vec![format!(
+                "cannot have a named output with reserved name: `{UNMATCHED_ROUTE}`"
+            )])
+        } else {
+            Ok(())
+        }
+    }
+
+    fn outputs(&self, _: &schema::Definition) -> Vec<Output> {
+        let mut result: Vec<Output> = self
+            .route
+            .keys()
+            .map(|output_name| Output::default(DataType::Metric).with_port(output_name))
+            .collect();
+        result.push(Output::default(DataType::Metric).with_port(UNMATCHED_ROUTE));
+        result
+    }
+
+    fn enable_concurrency(&self) -> bool {
+        true
+    }
+}
+
+#[derive(Clone)]
+struct Rules {
+    names: Vec<glob::Pattern>,
+    namespaces: Vec<glob::Pattern>,
+    tags: Vec<(String, glob::Pattern)>,
+}
+
+impl Rules {
+    fn new(route: &str, config: &MetricRouteConfig) -> Result<Self, BuildError> {
+        let pattern = |pattern: &String| {
+            glob::Pattern::new(pattern).context(InvalidPatternSnafu { route, pattern })
+        };
+        Ok(Self {
+            names: config.names.iter().map(pattern).collect::<Result<_, _>>()?,
+            namespaces: config
+                .namespaces
+                .iter()
+                .map(pattern)
+                .collect::<Result<_, _>>()?,
+            tags: config
+                .tags
+                .iter()
+                .map(|(tag, value)| Ok((tag.clone(), pattern(value)?)))
+                .collect::<Result<_, BuildError>>()?,
+        })
+    }
+
+    fn matches(&self, metric: &Metric) -> bool {
+        let any = |patterns: &[glob::Pattern], value: &str| {
+            patterns.iter().any(|pattern| pattern.matches(value))
+        };
+        (self.names.is_empty() || any(&self.names, metric.name()))
+            && (self.namespaces.is_empty()
+                || metric
+                    .namespace()
+                    .map_or(false, |namespace| any(&self.namespaces, namespace)))
+            && self.tags.iter().all(|(tag, pattern)| {
+                metric
+                    .tags()
+                    .and_then(|tags| tags.get(tag))
+                    .map_or(false, |value| pattern.matches(value))
+            })
+    }
+}
+
+#[derive(Clone)]
+pub struct MetricRouter {
+    routes: Vec<(String, Rules)>,
+}
+
+impl MetricRouter {
+    pub fn new(config: &MetricRouterConfig) -> crate::Result<Self> {
+        let routes = config
+            .route
+            .iter()
+            .map(|(name, rules)| Ok((name.clone(), Rules::new(name, rules)?)))
+            .collect::<Result<Vec<_>, BuildError>>()?;
+        Ok(Self { routes })
+    }
+}
+
+impl SyncTransform for MetricRouter {
+    fn transform(
+        &mut self,
+        event: Event,
+        output: &mut vector_core::transform::TransformOutputsBuf,
+    ) {
+        let route = self
+            .routes
+            .iter()
+            .find(|(_, rules)| rules.matches(event.as_metric()))
+            .map_or(UNMATCHED_ROUTE, |(name, _)| name.as_str());
+        output.push_named(route, event);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use vector_core::transform::TransformOutputsBuf;
+
+    use super::*;
+    use crate::event::metric::{MetricKind, MetricValue};
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<MetricRouterConfig>();
+    }
+
+    fn metric(name: &str, namespace: Option<&str>, tags: &[(&str, &str)]) -> Event {
+        Event::Metric(
+            Metric::new(
+                name,
+                MetricKind::Incremental,
+                MetricValue::Counter { value: 1.0 },
+            )
+            .with_namespace(namespace)
+            .with_tags((!tags.is_empty()).then(|| {
+                tags.iter()
+                    .map(|(key, value)| ((*key).to_owned(), (*value).to_owned()))
+                    .collect()
+            })),
+        )
+    }
+
+    fn route(config: &str, event: Event) -> &'static str {
+        let config = toml::from_str::<MetricRouterConfig>(config).unwrap();
+        let mut transform = MetricRouter::new(&config).unwrap();
+        let outputs = config.outputs(&schema::Definition::any());
+        let mut buf = TransformOutputsBuf::new_with_capacity(outputs, 1);
+
+        transform.transform(event.clone(), &mut buf);
+        let mut routed = None;
+        for output in ["infra", "business", UNMATCHED_ROUTE] {
+            let events = buf.drain_named(output).collect::<Vec<_>>();
+            if !events.is_empty() {
+                assert_eq!(events, vec![event.clone()]);
+                assert!(routed.is_none(), "routed to several outputs");
+                routed = Some(output);
+            }
+        }
+        routed.expect("routed to no output")
+    }
+
+    const CONFIG: &str = r#"
+        route.infra.namespaces = ["host", "kubernetes_*"]
+        route.business.names = ["orders_*", "checkout_*"]
+        route.business.tags.team = "commerce-*"
+    "#;
+
+    #[test]
+    fn routes_to_the_first_matching_route() {
+        assert_eq!(
+            route(CONFIG, metric("cpu_seconds_total", Some("host"), &[])),
+            "infra"
+        );
+        assert_eq!(
+            route(
+                CONFIG,
+                metric("orders_total", Some("host"), &[("team", "commerce-eu")])
+            ),
+            "infra"
+        );
+        assert_eq!(
+            route(
+                CONFIG,
+                metric("orders_total", Some("app"), &[("team", "commerce-eu")])
+            ),
+            "business"
+        );
+    }
+
+    #[test]
+    fn requires_every_rule_to_match() {
+        assert_eq!(
+            route(CONFIG, metric("orders_total", None, &[("team", "search")])),
+            UNMATCHED_ROUTE
+        );
+        assert_eq!(
+            route(CONFIG, metric("orders_total", None, &[])),
+            UNMATCHED_ROUTE
+        );
+        assert_eq!(
+            route(
+                CONFIG,
+                metric("latency_seconds", None, &[("team", "commerce-us")])
+            ),
+            UNMATCHED_ROUTE
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_patterns() {
+        let config =
+            toml::from_str::<MetricRouterConfig>(r#"route.infra.names = ["[host"]"#).unwrap();
+        assert!(MetricRouter::new(&config).is_err());
+    }
+}
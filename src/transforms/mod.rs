@@ -8,8 +8,16 @@ use snafu::Snafu;
 pub mod aggregate;
 #[cfg(feature = "transforms-aws_ec2_metadata")]
 pub mod aws_ec2_metadata;
+#[cfg(feature = "transforms-compression")]
+pub mod compression;
+#[cfg(feature = "transforms-content_dedupe")]
+pub mod content_dedupe;
 #[cfg(feature = "transforms-dedupe")]
 pub mod dedupe;
+#[cfg(feature = "transforms-delay")]
+pub mod delay;
+#[cfg(feature = "transforms-ecs_normalize")]
+pub mod ecs_normalize;
 #[cfg(feature = "transforms-filter")]
 pub mod filter;
 #[cfg(feature = "transforms-geoip")]
@@ -17,22 +25,36 @@ pub mod geoip;
 pub mod log_to_metric;
 #[cfg(feature = "transforms-lua")]
 pub mod lua;
+#[cfg(feature = "transforms-metric_router")]
+pub mod metric_router;
 #[cfg(feature = "transforms-metric_to_log")]
 pub mod metric_to_log;
+#[cfg(feature = "transforms-otel_normalize")]
+pub mod otel_normalize;
 #[cfg(feature = "transforms-pipelines")]
 pub mod pipelines;
 #[cfg(feature = "transforms-reduce")]
 pub mod reduce;
 #[cfg(feature = "transforms-remap")]
 pub mod remap;
+#[cfg(feature = "transforms-respond")]
+pub mod respond;
 #[cfg(feature = "transforms-route")]
 pub mod route;
 #[cfg(feature = "transforms-sample")]
 pub mod sample;
+#[cfg(feature = "transforms-schema_map")]
+pub mod schema_map;
+#[cfg(feature = "transforms-sigma")]
+pub mod sigma;
 #[cfg(feature = "transforms-tag_cardinality_limit")]
 pub mod tag_cardinality_limit;
 #[cfg(feature = "transforms-throttle")]
 pub mod throttle;
+#[cfg(feature = "transforms-time_skew")]
+pub mod time_skew;
+#[cfg(feature = "transforms-top_k")]
+pub mod top_k;
 
 use vector_common::config::ComponentKey;
 use vector_config::{configurable_component, NamedComponent};
@@ -70,10 +92,26 @@ pub enum Transforms {
     #[cfg(feature = "transforms-aws_ec2_metadata")]
     AwsEc2Metadata(#[configurable(derived)] aws_ec2_metadata::Ec2Metadata),
 
+    /// Compression.
+    #[cfg(feature = "transforms-compression")]
+    Compression(#[configurable(derived)] compression::CompressionConfig),
+
+    /// Content dedupe.
+    #[cfg(feature = "transforms-content_dedupe")]
+    ContentDedupe(#[configurable(derived)] content_dedupe::ContentDedupeConfig),
+
     /// Dedupe.
     #[cfg(feature = "transforms-dedupe")]
     Dedupe(#[configurable(derived)] dedupe::DedupeConfig),
 
+    /// Delay.
+    #[cfg(feature = "transforms-delay")]
+    Delay(#[configurable(derived)] delay::DelayConfig),
+
+    /// ECS normalize.
+    #[cfg(feature = "transforms-ecs_normalize")]
+    EcsNormalize(#[configurable(derived)] ecs_normalize::EcsNormalizeConfig),
+
     /// Filter.
     #[cfg(feature = "transforms-filter")]
     Filter(#[configurable(derived)] filter::FilterConfig),
@@ -89,10 +127,18 @@ pub enum Transforms {
     #[cfg(feature = "transforms-lua")]
     Lua(#[configurable(derived)] lua::LuaConfig),
 
+    /// Metric router.
+    #[cfg(feature = "transforms-metric_router")]
+    MetricRouter(#[configurable(derived)] metric_router::MetricRouterConfig),
+
     /// Metric to log.
     #[cfg(feature = "transforms-metric_to_log")]
     MetricToLog(#[configurable(derived)] metric_to_log::MetricToLogConfig),
 
+    /// OpenTelemetry normalize.
+    #[cfg(feature = "transforms-otel_normalize")]
+    OtelNormalize(#[configurable(derived)] otel_normalize::OtelNormalizeConfig),
+
     /// Pipelines. (inner)
     #[cfg(feature = "transforms-pipelines")]
     #[configurable(metadata(skip_docs))]
@@ -110,6 +156,10 @@ pub enum Transforms {
     #[cfg(feature = "transforms-remap")]
     Remap(#[configurable(derived)] remap::RemapConfig),
 
+    /// Respond.
+    #[cfg(feature = "transforms-respond")]
+    Respond(#[configurable(derived)] respond::RespondConfig),
+
     /// Route.
     #[cfg(feature = "transforms-route")]
     Route(#[configurable(derived)] route::RouteConfig),
@@ -118,6 +168,14 @@ pub enum Transforms {
     #[cfg(feature = "transforms-sample")]
     Sample(#[configurable(derived)] sample::SampleConfig),
 
+    /// Schema map.
+    #[cfg(feature = "transforms-schema_map")]
+    SchemaMap(#[configurable(derived)] schema_map::SchemaMapConfig),
+
+    /// Sigma.
+    #[cfg(feature = "transforms-sigma")]
+    Sigma(#[configurable(derived)] sigma::SigmaConfig),
+
     /// Tag cardinality limit.
     #[cfg(feature = "transforms-tag_cardinality_limit")]
     TagCardinalityLimit(#[configurable(derived)] tag_cardinality_limit::TagCardinalityLimitConfig),
@@ -133,6 +191,14 @@ pub enum Transforms {
     /// Throttle.
     #[cfg(feature = "transforms-throttle")]
     Throttle(#[configurable(derived)] throttle::ThrottleConfig),
+
+    /// Time Skew.
+    #[cfg(feature = "transforms-time_skew")]
+    TimeSkew(#[configurable(derived)] time_skew::TimeSkewConfig),
+
+    /// Top K.
+    #[cfg(feature = "transforms-top_k")]
+    TopK(#[configurable(derived)] top_k::TopKConfig),
 }
 
 // We can't use `enum_dispatch` here because it doesn't support associated constants.
@@ -145,8 +211,16 @@ impl NamedComponent for Transforms {
             Transforms::Aggregate(config) => config.get_component_name(),
             #[cfg(feature = "transforms-aws_ec2_metadata")]
             Transforms::AwsEc2Metadata(config) => config.get_component_name(),
+            #[cfg(feature = "transforms-compression")]
+            Transforms::Compression(config) => config.get_component_name(),
+            #[cfg(feature = "transforms-content_dedupe")]
+            Transforms::ContentDedupe(config) => config.get_component_name(),
             #[cfg(feature = "transforms-dedupe")]
             Transforms::Dedupe(config) => config.get_component_name(),
+            #[cfg(feature = "transforms-delay")]
+            Transforms::Delay(config) => config.get_component_name(),
+            #[cfg(feature = "transforms-ecs_normalize")]
+            Self::EcsNormalize(config) => config.get_component_name(),
             #[cfg(feature = "transforms-filter")]
             Transforms::Filter(config) => config.get_component_name(),
             #[cfg(feature = "transforms-geoip")]
@@ -154,8 +228,12 @@ impl NamedComponent for Transforms {
             Transforms::LogToMetric(config) => config.get_component_name(),
             #[cfg(feature = "transforms-lua")]
             Transforms::Lua(config) => config.get_component_name(),
+            #[cfg(feature = "transforms-metric_router")]
+            Transforms::MetricRouter(config) => config.get_component_name(),
             #[cfg(feature = "transforms-metric_to_log")]
             Transforms::MetricToLog(config) => config.get_component_name(),
+            #[cfg(feature = "transforms-otel_normalize")]
+            Self::OtelNormalize(config) => config.get_component_name(),
             #[cfg(feature = "transforms-pipelines")]
             Transforms::Pipeline(config) => config.get_component_name(),
             #[cfg(feature = "transforms-pipelines")]
@@ -164,10 +242,16 @@ impl NamedComponent for Transforms {
             Transforms::Reduce(config) => config.get_component_name(),
             #[cfg(feature = "transforms-remap")]
             Transforms::Remap(config) => config.get_component_name(),
+            #[cfg(feature = "transforms-respond")]
+            Transforms::Respond(config) => config.get_component_name(),
             #[cfg(feature = "transforms-route")]
             Transforms::Route(config) => config.get_component_name(),
             #[cfg(feature = "transforms-sample")]
             Transforms::Sample(config) => config.get_component_name(),
+            #[cfg(feature = "transforms-schema_map")]
+            Self::SchemaMap(config) => config.get_component_name(),
+            #[cfg(feature = "transforms-sigma")]
+            Self::Sigma(config) => config.get_component_name(),
             #[cfg(feature = "transforms-tag_cardinality_limit")]
             Transforms::TagCardinalityLimit(config) => config.get_component_name(),
             #[cfg(test)]
@@ -176,6 +260,10 @@ impl NamedComponent for Transforms {
             Transforms::TestNoop(config) => config.get_component_name(),
             #[cfg(feature = "transforms-throttle")]
             Transforms::Throttle(config) => config.get_component_name(),
+            #[cfg(feature = "transforms-time_skew")]
+            Transforms::TimeSkew(config) => config.get_component_name(),
+            #[cfg(feature = "transforms-top_k")]
+            Transforms::TopK(config) => config.get_component_name(),
             #[allow(unreachable_patterns)]
             _ => unimplemented!(),
         }
@@ -193,6 +281,7 @@ mod test {
     use super::Transforms;
     use crate::{
         config::{
+            feature_flags::FeatureFlag,
             unit_test::{UnitTestStreamSinkConfig, UnitTestStreamSourceConfig},
             ConfigBuilder,
         },
@@ -226,6 +315,11 @@ mod test {
         transform_config: T,
     ) -> (RunningTopology, mpsc::Receiver<Event>) {
         let mut builder = ConfigBuilder::default();
+        // Alpha transforms are tested too.
+        builder
+            .global
+            .feature_flags
+            .insert(FeatureFlag::AlphaComponents);
 
         let (tx, rx) = mpsc::channel(1);
 
@@ -0,0 +1,317 @@
+use std::collections::BTreeMap;
+
+use chrono::Utc;
+use lookup::event_path;
+use vector_config::configurable_component;
+
+use crate::{
+    config::{
+        log_schema, DataType, GenerateConfig, Input, Output, TransformConfig, TransformContext,
+    },
+    event::{Event, LogEvent, Value},
+    schema,
+    transforms::{FunctionTransform, OutputBuffer, Transform},
+};
+
+const RESOURCES_KEY: &str = "resources";
+const ATTRIBUTES_KEY: &str = "attributes";
+const SEVERITY_TEXT_KEY: &str = "severity_text";
+const SEVERITY_NUMBER_KEY: &str = "severity_number";
+const OBSERVED_TIMESTAMP_KEY: &str = "observed_timestamp";
+
+/// The fields of the OpenTelemetry log data model, besides the body and the timestamp, as named by
+/// the `opentelemetry` source.
+const MODEL_FIELDS: &[&str] = &[
+    RESOURCES_KEY,
+    ATTRIBUTES_KEY,
+    SEVERITY_TEXT_KEY,
+    SEVERITY_NUMBER_KEY,
+    OBSERVED_TIMESTAMP_KEY,
+    "trace_id",
+    "span_id",
+    "flags",
+    "dropped_attributes_count",
+];
+
+/// The fields set by Vector sources that describe the entity producing the events, along with
+/// the resource attributes of the OpenTelemetry semantic conventions they map to.
+///
+/// The host field of the log schema is mapped to `host.name` separately.
+const RESOURCE_FIELDS: &[(&str, &str)] = &[
+    ("hostname", "host.name"),
+    ("appname", "service.name"),
+    ("procid", "process.pid"),
+    ("kubernetes.pod_name", "k8s.pod.name"),
+    ("kubernetes.pod_uid", "k8s.pod.uid"),
+    ("kubernetes.pod_namespace", "k8s.namespace.name"),
+    ("kubernetes.pod_node_name", "k8s.node.name"),
+    ("kubernetes.container_name", "k8s.container.name"),
+    ("kubernetes.container_id", "container.id"),
+    ("kubernetes.container_image", "container.image.name"),
+];
+
+/// Configuration for the `otel_normalize` transform.
+#[configurable_component(transform("otel_normalize"))]
+#[configurable(metadata(status = "alpha"))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct OtelNormalizeConfig {
+    /// The fields holding the severity of events, in order of precedence.
+    ///
+    /// The first of them found in an event is moved to `severity_text`, and mapped to the matching
+    /// `severity_number`.
+    #[serde(default = "default_severity_fields")]
+    pub severity_fields: Vec<String>,
+
+    /// Additional fields to move into the resource attributes, keyed by the name of the attribute
+    /// they become.
+    #[serde(default)]
+    pub resource_attributes: BTreeMap<String, String>,
+
+    /// Whether to move all the fields that aren't part of the OpenTelemetry log data model into
+    /// `attributes`.
+    #[serde(default = "crate::serde::default_true")]
+    pub collect_attributes: bool,
+}
+
+fn default_severity_fields() -> Vec<String> {
+    vec!["severity".to_owned(), "level".to_owned()]
+}
+
+impl GenerateConfig for OtelNormalizeConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self {
+            severity_fields: default_severity_fields(),
+            resource_attributes: BTreeMap::new(),
+            collect_attributes: true,
+        })
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+impl TransformConfig for OtelNormalizeConfig {
+    async fn build(&self, _context: &TransformContext) -> crate::Result<Transform> {
+        Ok(Transform::function(OtelNormalize::new(self)))
+    }
+
+    fn input(&self) -> Input {
+        Input::log()
+    }
+
+    fn outputs(&self, _: &schema::Definition) -> Vec<Output> {
+        vec![Output::default(DataType::Log)]
+    }
+
+    fn enable_concurrency(&self) -> bool {
+        true
+    }
+}
+
+/// Maps a severity name to the `SeverityNumber` of the OpenTelemetry log data model, following
+/// the example mappings of the specification, including those of syslog severities.
+fn severity_number(severity: &str) -> Option<i64> {
+    Some(match severity.to_ascii_lowercase().as_str() {
+        "trace" => 1,
+        "debug" => 5,
+        "info" | "informational" => 9,
+        "notice" => 10,
+        "warn" | "warning" => 13,
+        "err" | "error" => 17,
+        "crit" | "critical" => 18,
+        "alert" => 19,
+        "emerg" | "emergency" | "fatal" | "panic" => 21,
+        _ => return None,
+    })
+}
+
+#[derive(Clone, Debug)]
+pub struct OtelNormalize {
+    severity_fields: Vec<String>,
+    /// Pairs of field and resource attribute.
+    resource_fields: Vec<(String, String)>,
+    collect_attributes: bool,
+}
+
+impl OtelNormalize {
+    pub fn new(config: &OtelNormalizeConfig) -> Self {
+        let resource_fields = std::iter::once((log_schema().host_key(), "host.name"))
+            .chain(RESOURCE_FIELDS.iter().copied())
+            .map(|(field, attribute)| (field.to_owned(), attribute.to_owned()))
+            .chain(
+                config
+                    .resource_attributes
+                    .iter()
+                    .map(|(attribute, field)| (field.clone(), attribute.clone())),
+            )
+            .collect();
+
+        Self {
+            severity_fields: config.severity_fields.clone(),
+            resource_fields,
+            collect_attributes: config.collect_attributes,
+        }
+    }
+
+    fn normalize_severity(&self, log: &mut LogEvent) {
+        if log.contains(SEVERITY_NUMBER_KEY) {
+            return;
+        }
+        let severity = self
+            .severity_fields
+            .iter()
+            .find_map(|field| log.remove(field.as_str()));
+        if let Some(severity) = severity {
+            let text = String::from_utf8_lossy(&severity.coerce_to_bytes()).into_owned();
+            if let Some(number) = severity_number(&text) {
+                log.insert(SEVERITY_NUMBER_KEY, number);
+            }
+            log.try_insert(SEVERITY_TEXT_KEY, text);
+        }
+    }
+
+    fn normalize_resources(&self, log: &mut LogEvent) {
+        // Later mappings of the same attribute take precedence, like the names found in the
+        // message over the host the source received it from.
+        for (field, attribute) in &self.resource_fields {
+            if let Some(value) = log.remove(field.as_str()) {
+                log.insert(event_path!(RESOURCES_KEY, attribute.as_str()), value);
+            }
+        }
+        // Drop what's left of the objects the resource fields were taken out of.
+        if matches!(log.get("kubernetes"), Some(Value::Object(fields)) if fields.is_empty()) {
+            log.remove("kubernetes");
+        }
+    }
+
+    fn collect_attributes(log: &mut LogEvent) {
+        let schema = log_schema();
+        let keep = |key: &str| {
+            key == schema.message_key()
+                || key == schema.timestamp_key()
+                || MODEL_FIELDS.contains(&key)
+        };
+
+        let other_keys = match log.as_map() {
+            Some(fields) => fields
+                .keys()
+                .filter(|key| !keep(key))
+                .cloned()
+                .collect::<Vec<_>>(),
+            None => return,
+        };
+        for key in other_keys {
+            if let Some(value) = log.remove(event_path!(key.as_str())) {
+                log.insert(event_path!(ATTRIBUTES_KEY, key.as_str()), value);
+            }
+        }
+    }
+}
+
+impl FunctionTransform for OtelNormalize {
+    fn transform(&mut self, output: &mut OutputBuffer, mut event: Event) {
+        let log = event.as_mut_log();
+        self.normalize_severity(log);
+        self.normalize_resources(log);
+        if self.collect_attributes {
+            Self::collect_attributes(log);
+        }
+        log.try_insert(OBSERVED_TIMESTAMP_KEY, Utc::now());
+        output.push(event);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transforms::test::transform_one;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<OtelNormalizeConfig>();
+    }
+
+    fn normalize(config: &OtelNormalizeConfig, log: LogEvent) -> LogEvent {
+        let mut transform = OtelNormalize::new(config);
+        transform_one(&mut transform, log.into())
+            .unwrap()
+            .into_log()
+    }
+
+    fn config() -> OtelNormalizeConfig {
+        toml::from_str("").unwrap()
+    }
+
+    #[test]
+    fn maps_syslog_events() {
+        let mut log = LogEvent::from("disk almost full");
+        log.insert("host", "192.0.2.1");
+        log.insert("hostname", "storage-1");
+        log.insert("appname", "smartd");
+        log.insert("severity", "warning");
+        log.insert("facility", "daemon");
+        let log = normalize(&config(), log);
+
+        assert_eq!(log["message"], "disk almost full".into());
+        assert_eq!(log["severity_text"], "warning".into());
+        assert_eq!(log["severity_number"], 13.into());
+        assert_eq!(
+            log.get(event_path!("resources", "host.name")),
+            Some(&"storage-1".into())
+        );
+        assert_eq!(
+            log.get(event_path!("resources", "service.name")),
+            Some(&"smartd".into())
+        );
+        assert_eq!(log["attributes.facility"], "daemon".into());
+        assert!(log.get("severity").is_none());
+        assert!(log.get("observed_timestamp").is_some());
+    }
+
+    #[test]
+    fn maps_kubernetes_events() {
+        let mut log = LogEvent::from("GET / 200");
+        log.insert("kubernetes.pod_name", "web-1");
+        log.insert("kubernetes.pod_namespace", "default");
+        log.insert("stream", "stdout");
+        let log = normalize(&config(), log);
+
+        assert_eq!(
+            log.get(event_path!("resources", "k8s.pod.name")),
+            Some(&"web-1".into())
+        );
+        assert_eq!(
+            log.get(event_path!("resources", "k8s.namespace.name")),
+            Some(&"default".into())
+        );
+        assert_eq!(log["attributes.stream"], "stdout".into());
+        assert!(log.get("kubernetes").is_none());
+        assert!(log.get("attributes.kubernetes").is_none());
+    }
+
+    #[test]
+    fn keeps_model_fields() {
+        let mut log = LogEvent::from("already normalized");
+        log.insert("severity_number", 9);
+        log.insert("level", "debug");
+        log.insert("trace_id", "4bf92f3577b34da6a3ce929d0e0e4736");
+        let config = OtelNormalizeConfig {
+            resource_attributes: BTreeMap::from([(
+                "deployment.environment".to_owned(),
+                "env".to_owned(),
+            )]),
+            collect_attributes: false,
+            ..config()
+        };
+        log.insert("env", "production");
+        let log = normalize(&config, log);
+
+        assert_eq!(log["severity_number"], 9.into());
+        assert_eq!(log["level"], "debug".into());
+        assert_eq!(log["trace_id"], "4bf92f3577b34da6a3ce929d0e0e4736".into());
+        assert_eq!(
+            log.get(event_path!("resources", "deployment.environment")),
+            Some(&"production".into())
+        );
+    }
+}
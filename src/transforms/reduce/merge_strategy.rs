@@ -1,7 +1,11 @@
-use std::collections::HashSet;
+use std::{
+    collections::{btree_map, BTreeMap, HashSet},
+    sync::Arc,
+};
 
 use bytes::{Bytes, BytesMut};
 use chrono::{DateTime, Utc};
+use indexmap::IndexMap;
 use ordered_float::NotNan;
 use vector_config::configurable_component;
 
@@ -49,8 +53,23 @@ pub enum MergeStrategy {
 
     /// Create a flattened array of all unique values.
     FlatUnique,
+
+    /// Concatenate arrays, keeping only the first occurrence of each value, including objects.
+    ///
+    /// Values other than arrays and `null` are appended as elements.
+    ArrayConcatUnique,
+
+    /// Merge objects recursively.
+    ///
+    /// Nested fields are merged with the strategy configured for their path, such as
+    /// `payload.items`, if any. Otherwise, nested objects are merged recursively, and other values
+    /// are merged as with the `retain` strategy.
+    DeepMerge,
 }
 
+/// The merge strategies of fields, keyed by their path, shared by the mergers of nested fields.
+pub(crate) type MergeStrategies = Arc<IndexMap<String, MergeStrategy>>;
+
 #[derive(Debug, Clone)]
 struct DiscardMerger {
     v: Value,
@@ -298,6 +317,147 @@ impl ReduceValueMerger for FlatUniqueMerger {
     }
 }
 
+#[derive(Debug, Clone)]
+struct ArrayConcatUniqueMerger {
+    v: Vec<Value>,
+    seen: HashSet<Value>,
+}
+
+impl ArrayConcatUniqueMerger {
+    #[allow(clippy::mutable_key_type)] // false positive due to bytes::Bytes
+    fn new(v: Value) -> Self {
+        let mut merger = Self {
+            v: Vec::new(),
+            seen: HashSet::new(),
+        };
+        merger.extend(v);
+        merger
+    }
+
+    #[allow(clippy::mutable_key_type)] // false positive due to bytes::Bytes
+    fn extend(&mut self, v: Value) {
+        let values = match v {
+            Value::Array(values) => values,
+            Value::Null => Vec::new(),
+            v => vec![v],
+        };
+        for v in values {
+            if self.seen.insert(v.clone()) {
+                self.v.push(v);
+            }
+        }
+    }
+}
+
+impl ReduceValueMerger for ArrayConcatUniqueMerger {
+    fn add(&mut self, v: Value) -> Result<(), String> {
+        self.extend(v);
+        Ok(())
+    }
+
+    fn insert_into(self: Box<Self>, k: String, v: &mut LogEvent) -> Result<(), String> {
+        v.insert(k.as_str(), Value::Array(self.v));
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+struct DeepMergeMerger {
+    /// The path of the merged field, which the paths of its nested fields start with.
+    path: String,
+    strategies: MergeStrategies,
+    fields: BTreeMap<String, Box<dyn ReduceValueMerger>>,
+    /// The last value which isn't an object, replacing the merged fields. Null values don't
+    /// replace merged values, so this is only null if all of the values were null.
+    other: Option<Value>,
+}
+
+impl DeepMergeMerger {
+    fn new(v: Value, path: String, strategies: MergeStrategies) -> Result<Self, String> {
+        let mut merger = Self {
+            path,
+            strategies,
+            fields: BTreeMap::new(),
+            other: None,
+        };
+        if v.is_null() {
+            merger.other = Some(v);
+        } else {
+            merger.add(v)?;
+        }
+        Ok(merger)
+    }
+
+    fn add_field(&mut self, key: String, v: Value) -> Result<(), String> {
+        match self.fields.entry(key) {
+            btree_map::Entry::Occupied(mut entry) => entry.get_mut().add(v),
+            btree_map::Entry::Vacant(entry) => {
+                // Looked up by the same path the field is inserted at, so fields which need to be
+                // quoted are configured quoted.
+                let path = field_path(&self.path, entry.key());
+                let merger = match self.strategies.get(&path) {
+                    Some(strategy) => get_field_merger(v, strategy, path, &self.strategies)?,
+                    // Merging the other values deeply too, so a field first seen as a value which
+                    // isn't an object, such as null, is still merged deeply once it's an object.
+                    None => Box::new(DeepMergeMerger::new(v, path, Arc::clone(&self.strategies))?),
+                };
+                entry.insert(merger);
+                Ok(())
+            }
+        }
+    }
+}
+
+impl ReduceValueMerger for DeepMergeMerger {
+    fn add(&mut self, v: Value) -> Result<(), String> {
+        match v {
+            Value::Object(fields) => {
+                if self.other.take().is_some() {
+                    self.fields.clear();
+                }
+                // Merges all of the fields, even if some of them fail to.
+                fields
+                    .into_iter()
+                    .map(|(key, v)| self.add_field(key, v))
+                    .fold(Ok(()), Result::and)
+            }
+            Value::Null => Ok(()),
+            v => {
+                self.fields.clear();
+                self.other = Some(v);
+                Ok(())
+            }
+        }
+    }
+
+    fn insert_into(self: Box<Self>, k: String, v: &mut LogEvent) -> Result<(), String> {
+        if let Some(other) = self.other {
+            v.insert(k.as_str(), other);
+            return Ok(());
+        }
+
+        v.insert(k.as_str(), Value::Object(BTreeMap::new()));
+        self.fields
+            .into_iter()
+            .map(|(key, merger)| merger.insert_into(field_path(&k, &key), v))
+            .fold(Ok(()), Result::and)
+    }
+}
+
+/// Appends a field to a path, quoting it if it isn't made of only alphanumeric characters and
+/// underscores.
+fn field_path(path: &str, key: &str) -> String {
+    if !key.is_empty() && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+        format!("{}.{}", path, key)
+    } else {
+        format!(
+            "{}.\"{}\"",
+            path,
+            key.replace('\\', "\\\\").replace('"', "\\\"")
+        )
+    }
+}
+
 #[derive(Debug, Clone)]
 struct TimestampWindowMerger {
     started: DateTime<Utc>,
@@ -604,6 +764,30 @@ pub(crate) fn get_value_merger(
         MergeStrategy::Discard => Ok(Box::new(DiscardMerger::new(v))),
         MergeStrategy::Retain => Ok(Box::new(RetainMerger::new(v))),
         MergeStrategy::FlatUnique => Ok(Box::new(FlatUniqueMerger::new(v))),
+        MergeStrategy::ArrayConcatUnique => Ok(Box::new(ArrayConcatUniqueMerger::new(v))),
+        MergeStrategy::DeepMerge => Ok(Box::new(DeepMergeMerger::new(
+            v,
+            String::new(),
+            MergeStrategies::default(),
+        )?)),
+    }
+}
+
+/// Gets the merger of the field at `path`, whose nested fields are merged with the strategies
+/// configured for their own path when the field is merged with the `deep_merge` strategy.
+pub(crate) fn get_field_merger(
+    v: Value,
+    m: &MergeStrategy,
+    path: String,
+    strategies: &MergeStrategies,
+) -> Result<Box<dyn ReduceValueMerger>, String> {
+    match m {
+        MergeStrategy::DeepMerge => Ok(Box::new(DeepMergeMerger::new(
+            v,
+            path,
+            Arc::clone(strategies),
+        )?)),
+        m => get_value_merger(v, m),
     }
 }
 
@@ -869,6 +1053,86 @@ mod test {
         }
     }
 
+    #[test]
+    fn merging_nested_values() {
+        assert_eq!(
+            merge(
+                json!([{"id": 1}, 2]).into(),
+                json!([2, {"id": 1}, {"id": 3}]).into(),
+                &MergeStrategy::ArrayConcatUnique
+            ),
+            Ok(json!([{"id": 1}, 2, {"id": 3}]).into())
+        );
+        assert_eq!(
+            merge(
+                json!([1]).into(),
+                2.into(),
+                &MergeStrategy::ArrayConcatUnique
+            ),
+            Ok(json!([1, 2]).into())
+        );
+
+        assert_eq!(
+            merge(
+                json!({"a": {"b": 1, "c": [1]}, "d": "x"}).into(),
+                json!({"a": {"c": [2], "e": null}, "d": null, "f": true}).into(),
+                &MergeStrategy::DeepMerge
+            ),
+            Ok(json!({"a": {"b": 1, "c": [2], "e": null}, "d": "x", "f": true}).into())
+        );
+        assert_eq!(
+            merge(
+                json!({"a": 1}).into(),
+                "x".into(),
+                &MergeStrategy::DeepMerge
+            ),
+            Ok("x".into())
+        );
+        assert_eq!(
+            merge(
+                json!({"a": null, "b": 1}).into(),
+                json!({"a": {"c": 1}, "b": null}).into(),
+                &MergeStrategy::DeepMerge
+            ),
+            Ok(json!({"a": {"c": 1}, "b": 1}).into())
+        );
+        assert_eq!(
+            merge(Value::Null, Value::Null, &MergeStrategy::DeepMerge),
+            Ok(Value::Null)
+        );
+    }
+
+    #[test]
+    fn merging_nested_values_with_path_strategies() {
+        let strategies = MergeStrategies::new(
+            [
+                ("out.a.c".to_owned(), MergeStrategy::ArrayConcatUnique),
+                ("out.a.n".to_owned(), MergeStrategy::Sum),
+                ("out.\"a b\"".to_owned(), MergeStrategy::Sum),
+            ]
+            .into_iter()
+            .collect(),
+        );
+        let mut merger = get_field_merger(
+            json!({"a": {"c": [1], "n": 1}, "a b": 1}).into(),
+            &MergeStrategy::DeepMerge,
+            "out".to_owned(),
+            &strategies,
+        )
+        .unwrap();
+        merger
+            .add(json!({"a": {"c": [1, 2], "n": 2}, "a b": 2}).into())
+            .unwrap();
+        assert!(merger.add(json!({"a": {"n": "x"}}).into()).is_err());
+
+        let mut output = LogEvent::default();
+        merger.insert_into("out".into(), &mut output).unwrap();
+        assert_eq!(
+            output["out"],
+            json!({"a": {"c": [1, 2], "n": 3}, "a b": 3}).into()
+        );
+    }
+
     fn merge(initial: Value, additional: Value, strategy: &MergeStrategy) -> Result<Value, String> {
         let mut merger = get_value_merger(initial, strategy)?;
         merger.add(additional)?;
@@ -2,6 +2,7 @@ use std::collections::BTreeMap;
 use std::{
     collections::{hash_map, HashMap},
     pin::Pin,
+    sync::Arc,
     time::{Duration, Instant},
 };
 
@@ -58,6 +59,10 @@ pub struct ReduceConfig {
     /// - For timestamp fields the first is kept and a new field `[field-name]_end` is added with
     ///   the last received timestamp value.
     /// - Numeric values are summed.
+    ///
+    /// The fields of objects merged with the `deep_merge` strategy can be given their own strategy
+    /// by their path, such as `payload.items`. Fields which aren't made of only alphanumeric
+    /// characters and underscores are quoted, such as `payload."user agent"`.
     #[serde(default)]
     pub merge_strategies: IndexMap<String, MergeStrategy>,
 
@@ -99,7 +104,7 @@ struct ReduceState {
 }
 
 impl ReduceState {
-    fn new(e: LogEvent, strategies: &IndexMap<String, MergeStrategy>) -> Self {
+    fn new(e: LogEvent, strategies: &MergeStrategies) -> Self {
         let (value, metadata) = e.into_parts();
 
         let fields = if let Value::Object(fields) = value {
@@ -107,7 +112,7 @@ impl ReduceState {
                 .into_iter()
                 .filter_map(|(k, v)| {
                     if let Some(strat) = strategies.get(&k) {
-                        match get_value_merger(v, strat) {
+                        match get_field_merger(v, strat, k.clone(), strategies) {
                             Ok(m) => Some((k, m)),
                             Err(error) => {
                                 warn!(message = "Failed to create merger.", field = ?k, %error);
@@ -130,7 +135,7 @@ impl ReduceState {
         }
     }
 
-    fn add_event(&mut self, e: LogEvent, strategies: &IndexMap<String, MergeStrategy>) {
+    fn add_event(&mut self, e: LogEvent, strategies: &MergeStrategies) {
         let (value, metadata) = e.into_parts();
         self.metadata.merge(metadata);
 
@@ -145,7 +150,7 @@ impl ReduceState {
             match self.fields.entry(k) {
                 hash_map::Entry::Vacant(entry) => {
                     if let Some(strat) = strategy {
-                        match get_value_merger(v, strat) {
+                        match get_field_merger(v, strat, entry.key().clone(), strategies) {
                             Ok(m) => {
                                 entry.insert(m);
                             }
@@ -182,7 +187,7 @@ pub struct Reduce {
     expire_after: Duration,
     flush_period: Duration,
     group_by: Vec<String>,
-    merge_strategies: IndexMap<String, MergeStrategy>,
+    merge_strategies: MergeStrategies,
     reduce_merge_states: HashMap<Discriminant, ReduceState>,
     ends_when: Option<Condition>,
     starts_when: Option<Condition>,
@@ -213,7 +218,7 @@ impl Reduce {
             expire_after: Duration::from_millis(config.expire_after_ms.unwrap_or(30000)),
             flush_period: Duration::from_millis(config.flush_period_ms.unwrap_or(1000)),
             group_by,
-            merge_strategies: config.merge_strategies.clone(),
+            merge_strategies: Arc::new(config.merge_strategies.clone()),
             reduce_merge_states: HashMap::new(),
             ends_when,
             starts_when,
@@ -615,4 +620,69 @@ merge_strategies.bar = "concat"
         })
         .await;
     }
+
+    #[tokio::test]
+    async fn nested_objects() {
+        let reduce_config = toml::from_str::<ReduceConfig>(
+            r#"
+group_by = [ "request_id" ]
+
+merge_strategies.payload = "deep_merge"
+merge_strategies."payload.items" = "array_concat_unique"
+merge_strategies."payload.stats.count" = "sum"
+
+[ends_when]
+  type = "check_fields"
+  "test_end.exists" = true
+"#,
+        )
+        .unwrap();
+
+        assert_transform_compliance(async move {
+            let (tx, rx) = mpsc::channel(1);
+            let (topology, mut out) = create_topology(ReceiverStream::new(rx), reduce_config).await;
+
+            let mut e_1 = LogEvent::from("part 1");
+            e_1.insert(
+                "payload",
+                json!({
+                    "id": "a",
+                    "items": [{"name": "x"}, {"name": "y"}],
+                    "stats": {"count": 2, "status": "partial"},
+                }),
+            );
+            e_1.insert("request_id", "1");
+            tx.send(e_1.into()).await.unwrap();
+
+            let mut e_2 = LogEvent::from("part 2");
+            e_2.insert(
+                "payload",
+                json!({
+                    "items": [{"name": "y"}, {"name": "z"}],
+                    "stats": {"count": 1, "status": "complete"},
+                    "user agent": "curl",
+                }),
+            );
+            e_2.insert("request_id", "1");
+            e_2.insert("test_end", "yep");
+            tx.send(e_2.into()).await.unwrap();
+
+            let output = out.recv().await.unwrap().into_log();
+            assert_eq!(
+                output["payload"],
+                json!({
+                    "id": "a",
+                    "items": [{"name": "x"}, {"name": "y"}, {"name": "z"}],
+                    "stats": {"count": 3, "status": "complete"},
+                    "user agent": "curl",
+                })
+                .into()
+            );
+
+            drop(tx);
+            topology.stop().await;
+            assert_eq!(out.recv().await, None);
+        })
+        .await;
+    }
 }
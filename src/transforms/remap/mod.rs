@@ -1,14 +1,16 @@
 use std::sync::Arc;
 use std::{
-    collections::BTreeMap,
+    collections::{BTreeMap, BTreeSet},
     fs::File,
     io::{self, Read},
+    num::NonZeroUsize,
     path::PathBuf,
 };
 
 use lookup::lookup_v2::ValuePath;
-use lookup::{metadata_path, path, PathPrefix};
+use lookup::{metadata_path, path, OwnedTargetPath, OwnedValuePath, PathPrefix};
 use snafu::{ResultExt, Snafu};
+use tracing::Instrument;
 use value::Kind;
 use vector_common::TimeZone;
 use vector_config::configurable_component;
@@ -16,7 +18,7 @@ use vector_core::compile_vrl;
 use vector_core::config::LogNamespace;
 use vector_core::schema::Definition;
 
-use vector_vrl_functions::set_semantic_meaning::MeaningList;
+use vector_vrl_functions::{set_semantic_meaning::MeaningList, state::StateStore};
 use vrl::prelude::state::TypeState;
 use vrl::{
     diagnostic::{Formatter, Note},
@@ -28,15 +30,23 @@ use crate::{
     config::{
         log_schema, ComponentKey, DataType, Input, Output, TransformConfig, TransformContext,
     },
-    event::{Event, TargetEvents, VrlTarget},
+    event::{Event, EventArray, TargetEvents, VrlTarget, PRIORITY_FIELD},
     internal_events::{RemapMappingAbort, RemapMappingError},
     schema,
     transforms::{SyncTransform, Transform, TransformOutputsBuf},
     Result,
 };
 
+mod state;
+
+pub use self::state::RemapStateConfig;
+
 const DROPPED: &str = "dropped";
 
+/// The number of errors of a batch, one per failed event, reported along with the number of
+/// failed events.
+const MAX_ERROR_SAMPLES: usize = 5;
+
 /// Configuration for the `remap` transform.
 #[configurable_component(transform("remap"))]
 #[derive(Clone, Debug, Derivative)]
@@ -111,6 +121,27 @@ pub struct RemapConfig {
     #[configurable(derived)]
     #[serde(default)]
     pub runtime: VrlRuntime,
+
+    /// The maximum number of events to run the program on as a single batch.
+    ///
+    /// The events of a batch are run one after the other against the same compiled program,
+    /// reusing the runtime state and the working buffers of the transform, and mapping errors and
+    /// aborts are reported once per batch, along with the errors of the first failed events.
+    /// Larger batches lower the per-event overhead of simple programs, at the cost of holding more
+    /// events in memory at once.
+    #[serde(default = "default_batch_size")]
+    #[derivative(Default(value = "default_batch_size()"))]
+    pub batch_size: NonZeroUsize,
+
+    /// Persists the state set by the `set_state` function, and expires its keys.
+    ///
+    /// By default, the state is only kept in memory, and is lost when Vector restarts.
+    #[configurable(derived)]
+    pub state: Option<RemapStateConfig>,
+}
+
+fn default_batch_size() -> NonZeroUsize {
+    NonZeroUsize::new(128).expect("static non-zero number")
 }
 
 impl RemapConfig {
@@ -118,6 +149,7 @@ impl RemapConfig {
         &self,
         enrichment_tables: enrichment::TableRegistry,
         merged_schema_definition: schema::Definition,
+        state_store: StateStore,
     ) -> Result<(
         vrl::Program,
         String,
@@ -154,6 +186,9 @@ impl RemapConfig {
 
         config.set_custom(enrichment_tables);
         config.set_custom(MeaningList::default());
+        config.set_custom(crate::host_metadata::store());
+        config.set_custom(state_store);
+        crate::protobuf_descriptors::set_custom(&mut config);
 
         compile_vrl(&source, &functions, &state, config)
             .map_err(|diagnostics| {
@@ -178,13 +213,25 @@ impl_generate_config_from_default!(RemapConfig);
 #[async_trait::async_trait]
 impl TransformConfig for RemapConfig {
     async fn build(&self, context: &TransformContext) -> Result<Transform> {
+        let (state, checkpointer) = match &self.state {
+            Some(config) => {
+                let (state, checkpointer) = config.build(context).await?;
+                (state, Some(checkpointer))
+            }
+            None => (StateStore::default(), None),
+        };
+
         let (transform, warnings) = match self.runtime {
             VrlRuntime::Ast => {
-                let (remap, warnings) = Remap::new_ast(self.clone(), context)?;
+                let (remap, warnings) = Remap::new_ast_with_state(self.clone(), context, state)?;
                 (Transform::synchronous(remap), warnings)
             }
         };
 
+        if let Some(checkpointer) = checkpointer {
+            tokio::spawn(checkpointer.run().in_current_span());
+        }
+
         // TODO: We could improve on this by adding support for non-fatal error
         // messages in the topology. This would make the topology responsible
         // for printing warnings (including potentially emiting metrics),
@@ -208,6 +255,7 @@ impl TransformConfig for RemapConfig {
             .compile_vrl_program(
                 enrichment::TableRegistry::default(),
                 input_definition.clone(),
+                StateStore::default(),
             )
             .map(|(program, _, _, external_context)| {
                 let meaning = external_context
@@ -289,6 +337,86 @@ impl TransformConfig for RemapConfig {
         }
     }
 
+    fn check_schema(
+        &self,
+        merged_definition: &schema::Definition,
+    ) -> std::result::Result<Vec<String>, Vec<String>> {
+        // Programs that don't compile regardless of the schema of their inputs, for example because
+        // they use enrichment tables that aren't loaded yet, are reported when the transform is
+        // built. Only what is caused by the schema is reported here, before any event is processed.
+        let program = match self.compile_vrl_program(
+            enrichment::TableRegistry::default(),
+            Definition::any(),
+            StateStore::default(),
+        ) {
+            Ok((program, _, _, _)) => program,
+            Err(_) => return Ok(Vec::new()),
+        };
+
+        let location = self
+            .file
+            .as_ref()
+            .map(|path| format!(" in {:?}", path))
+            .unwrap_or_default();
+
+        if let Err(error) = self.compile_vrl_program(
+            enrichment::TableRegistry::default(),
+            merged_definition.clone(),
+            StateStore::default(),
+        ) {
+            return Err(vec![format!(
+                "program{} does not match the schema of its inputs:\n{}",
+                location, error
+            )]);
+        }
+
+        // Fields the program reads before setting them, but that none of its inputs ever set,
+        // are most likely typos or leftovers from a changed source.
+        let event_kind = merged_definition.event_kind();
+        if event_kind.is_never() {
+            return Ok(Vec::new());
+        }
+        let info = program.info();
+        let unknown_fields = info
+            .target_queries
+            .iter()
+            .filter(|query| query.prefix == PathPrefix::Event)
+            .filter(|query| {
+                !info
+                    .target_assignments
+                    .iter()
+                    .any(|assignment| query.can_start_with(assignment))
+            })
+            .filter(|query| event_kind.at_path(&query.path).is_undefined())
+            .collect::<BTreeSet<_>>();
+
+        Ok(unknown_fields
+            .into_iter()
+            .map(|query| {
+                format!(
+                    "program{} reads field `{}`, which the schema of its inputs doesn't contain",
+                    location, query
+                )
+            })
+            .collect())
+    }
+
+    fn sets_priority(&self) -> bool {
+        let priority = OwnedTargetPath::metadata(OwnedValuePath::single_field(PRIORITY_FIELD));
+        self.compile_vrl_program(
+            enrichment::TableRegistry::default(),
+            Definition::any(),
+            StateStore::default(),
+        )
+        .map_or(false, |(program, _, _, _)| {
+            program
+                .info()
+                .target_assignments
+                .iter()
+                .any(|assignment| priority.can_start_with(assignment))
+        })
+    }
+
     fn enable_concurrency(&self) -> bool {
         true
     }
@@ -308,6 +436,25 @@ where
     default_schema_definition: Arc<schema::Definition>,
     dropped_schema_definition: Arc<schema::Definition>,
     runner: Runner,
+    batch_size: usize,
+    buffers: BatchBuffers,
+}
+
+/// The buffers a [`Remap`] transform processes a batch of events in, kept between batches so
+/// their allocations are reused.
+#[derive(Debug, Default)]
+struct BatchBuffers {
+    targets: Vec<VrlTarget>,
+    /// The events of the batch as they were before running the program, if they need to be
+    /// forwarded when it fails or aborts.
+    originals: Vec<Option<Event>>,
+    results: Vec<std::result::Result<(), Terminate>>,
+}
+
+impl Clone for BatchBuffers {
+    fn clone(&self) -> Self {
+        Self::default()
+    }
 }
 
 pub trait VrlRunner {
@@ -349,10 +496,22 @@ impl Remap<AstRunner> {
     pub fn new_ast(
         config: RemapConfig,
         context: &TransformContext,
+    ) -> crate::Result<(Self, String)> {
+        let limits = RemapStateConfig::limits(config.state.as_ref());
+        Self::new_ast_with_state(config, context, StateStore::new(limits, []))
+    }
+
+    /// Builds the transform with the given state, which is otherwise empty and only kept in
+    /// memory.
+    fn new_ast_with_state(
+        config: RemapConfig,
+        context: &TransformContext,
+        state: StateStore,
     ) -> crate::Result<(Self, String)> {
         let (program, warnings, _, _) = config.compile_vrl_program(
             context.enrichment_tables.clone(),
             context.merged_schema_definition.clone(),
+            state,
         )?;
 
         let runtime = Runtime::default();
@@ -395,6 +554,8 @@ where
             default_schema_definition: Arc::new(default_schema_definition),
             dropped_schema_definition: Arc::new(dropped_schema_definition),
             runner,
+            batch_size: config.batch_size.get(),
+            buffers: BatchBuffers::default(),
         })
     }
 
@@ -466,11 +627,16 @@ where
     }
 }
 
-impl<Runner> SyncTransform for Remap<Runner>
+impl<Runner> Remap<Runner>
 where
-    Runner: VrlRunner + Clone + Send + Sync,
+    Runner: VrlRunner,
 {
-    fn transform(&mut self, event: Event, output: &mut TransformOutputsBuf) {
+    /// Runs the program on a batch of events, pushing the resulting events to `output`.
+    fn transform_batch(
+        &mut self,
+        events: impl Iterator<Item = Event>,
+        output: &mut TransformOutputsBuf,
+    ) {
         // If a program can fail or abort at runtime and we know that we will still need to forward
         // the event in that case (either to the main output or `dropped`, depending on the
         // config), we need to clone the original event and keep it around, to allow us to discard
@@ -483,58 +649,104 @@ where
         // the event to the `dropped` output.
         let forward_on_error = !self.drop_on_error || self.reroute_dropped;
         let forward_on_abort = !self.drop_on_abort || self.reroute_dropped;
-        let original_event = if (self.program.info().fallible && forward_on_error)
-            || (self.program.info().abortable && forward_on_abort)
-        {
-            Some(event.clone())
-        } else {
-            None
-        };
-
-        let mut target = VrlTarget::new(event, self.program.info());
-        let result = self.run_vrl(&mut target);
+        let keep_originals = (self.program.info().fallible && forward_on_error)
+            || (self.program.info().abortable && forward_on_abort);
+
+        let mut buffers = std::mem::take(&mut self.buffers);
+        for event in events {
+            buffers
+                .originals
+                .push(keep_originals.then(|| event.clone()));
+            buffers
+                .targets
+                .push(VrlTarget::new(event, self.program.info()));
+        }
+        for target in &mut buffers.targets {
+            let result = self.run_vrl(target).map(|_| ());
+            buffers.results.push(result);
+        }
 
-        match result {
-            Ok(_) => match target.into_events() {
-                TargetEvents::One(event) => {
-                    push_default(event, output, &self.default_schema_definition)
-                }
-                TargetEvents::Logs(events) => events
-                    .for_each(|event| push_default(event, output, &self.default_schema_definition)),
-                TargetEvents::Traces(events) => events
-                    .for_each(|event| push_default(event, output, &self.default_schema_definition)),
-            },
-            Err(reason) => {
-                let (reason, error, drop) = match reason {
-                    Terminate::Abort(error) => {
-                        emit!(RemapMappingAbort {
-                            event_dropped: self.drop_on_abort,
-                        });
-
-                        ("abort", error, self.drop_on_abort)
+        let mut aborted = 0u64;
+        let mut failed = 0u64;
+        let mut error_samples = Vec::new();
+        let outcomes = buffers
+            .targets
+            .drain(..)
+            .zip(buffers.originals.drain(..))
+            .zip(buffers.results.drain(..));
+        for ((target, original_event), result) in outcomes {
+            let (reason, error, drop) = match result {
+                Ok(()) => {
+                    match target.into_events() {
+                        TargetEvents::One(event) => {
+                            push_default(event, output, &self.default_schema_definition)
+                        }
+                        TargetEvents::Logs(events) => events.for_each(|event| {
+                            push_default(event, output, &self.default_schema_definition)
+                        }),
+                        TargetEvents::Traces(events) => events.for_each(|event| {
+                            push_default(event, output, &self.default_schema_definition)
+                        }),
                     }
-                    Terminate::Error(error) => {
-                        emit!(RemapMappingError {
-                            error: error.to_string(),
-                            event_dropped: self.drop_on_error,
-                        });
-
-                        ("error", error, self.drop_on_error)
+                    continue;
+                }
+                Err(Terminate::Abort(error)) => {
+                    aborted += 1;
+                    ("abort", error, self.drop_on_abort)
+                }
+                Err(Terminate::Error(error)) => {
+                    failed += 1;
+                    if error_samples.len() < MAX_ERROR_SAMPLES {
+                        error_samples.push(error.to_string());
                     }
-                };
+                    ("error", error, self.drop_on_error)
+                }
+            };
 
-                if !drop {
-                    let event = original_event.expect("event will be set");
+            if !drop {
+                let event = original_event.expect("event will be set");
 
-                    push_default(event, output, &self.default_schema_definition);
-                } else if self.reroute_dropped {
-                    let mut event = original_event.expect("event will be set");
+                push_default(event, output, &self.default_schema_definition);
+            } else if self.reroute_dropped {
+                let mut event = original_event.expect("event will be set");
 
-                    self.annotate_dropped(&mut event, reason, error);
-                    push_dropped(event, output, &self.dropped_schema_definition);
-                }
+                self.annotate_dropped(&mut event, reason, error);
+                push_dropped(event, output, &self.dropped_schema_definition);
             }
         }
+        self.buffers = buffers;
+
+        if aborted > 0 {
+            emit!(RemapMappingAbort {
+                count: aborted,
+                event_dropped: self.drop_on_abort,
+            });
+        }
+        if let Some(error) = error_samples.first().cloned() {
+            emit!(RemapMappingError {
+                count: failed,
+                error,
+                error_samples,
+                event_dropped: self.drop_on_error,
+            });
+        }
+    }
+}
+
+impl<Runner> SyncTransform for Remap<Runner>
+where
+    Runner: VrlRunner + Clone + Send + Sync,
+{
+    fn transform(&mut self, event: Event, output: &mut TransformOutputsBuf) {
+        self.transform_batch(std::iter::once(event), output);
+    }
+
+    fn transform_all(&mut self, events: EventArray, output: &mut TransformOutputsBuf) {
+        let batch_size = self.batch_size;
+        let mut events = events.into_events().peekable();
+        while events.peek().is_some() {
+            self.transform_batch(events.by_ref().take(batch_size), output);
+        }
     }
 }
 
@@ -573,11 +785,19 @@ pub enum BuildError {
     FileOpenFailed { path: PathBuf, source: io::Error },
     #[snafu(display("Could not read vrl program {:?}: {}", path, source))]
     FileReadFailed { path: PathBuf, source: io::Error },
+
+    #[snafu(display("`state` requires the key of the transform to name its checkpoint"))]
+    StateWithoutKey,
+    #[snafu(display("Invalid state checkpoint: {}", source))]
+    StateCheckpointInvalid { source: serde_json::Error },
 }
 
 #[cfg(test)]
 mod tests {
-    use std::collections::{BTreeMap, HashMap};
+    use std::{
+        collections::{BTreeMap, HashMap},
+        num::NonZeroU64,
+    };
 
     use indoc::{formatdoc, indoc};
     use vector_common::btreemap;
@@ -626,6 +846,64 @@ mod tests {
         crate::test_util::test_generate_config::<RemapConfig>();
     }
 
+    #[test]
+    fn check_schema_reports_mismatches() {
+        let config = RemapConfig {
+            source: Some(".bar = upcase!(.foo)".to_owned()),
+            ..Default::default()
+        };
+
+        let string_definition =
+            schema::Definition::empty_legacy_namespace().with_field("foo", Kind::bytes(), None);
+        assert_eq!(config.check_schema(&string_definition), Ok(vec![]));
+        assert_eq!(config.check_schema(&schema::Definition::any()), Ok(vec![]));
+
+        let integer_definition =
+            schema::Definition::empty_legacy_namespace().with_field("foo", Kind::integer(), None);
+        let errors = config.check_schema(&integer_definition).unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].starts_with("program does not match the schema of its inputs"));
+    }
+
+    #[test]
+    fn check_schema_ignores_errors_unrelated_to_schema() {
+        let config = RemapConfig {
+            source: Some(".bar = upcase(.foo)".to_owned()),
+            ..Default::default()
+        };
+
+        let definition =
+            schema::Definition::empty_legacy_namespace().with_field("foo", Kind::integer(), None);
+        assert_eq!(config.check_schema(&definition), Ok(vec![]));
+    }
+
+    #[test]
+    fn check_schema_warns_about_unknown_fields() {
+        let config = RemapConfig {
+            source: Some(
+                indoc! {r#"
+                    .qux = 1
+                    .bar = .foo
+                    .baz = .fooo
+                    .quux = .qux
+                "#}
+                .to_owned(),
+            ),
+            ..Default::default()
+        };
+
+        let definition =
+            schema::Definition::empty_legacy_namespace().with_field("foo", Kind::bytes(), None);
+        assert_eq!(
+            config.check_schema(&definition),
+            Ok(vec![
+                "program reads field `.fooo`, which the schema of its inputs doesn't contain"
+                    .to_owned()
+            ])
+        );
+        assert_eq!(config.check_schema(&schema::Definition::any()), Ok(vec![]));
+    }
+
     #[test]
     fn config_missing_source_and_file() {
         let config = RemapConfig {
@@ -701,6 +979,171 @@ mod tests {
         assert!(tform.runner().runtime.is_empty());
     }
 
+    #[test]
+    fn check_remap_processes_events_in_batches() {
+        let conf = RemapConfig {
+            source: Some(".double = int!(.number) * 2".to_string()),
+            drop_on_error: false,
+            batch_size: NonZeroUsize::new(2).unwrap(),
+            ..Default::default()
+        };
+        let mut tform = remap(conf).unwrap();
+
+        let events = [
+            Value::from(1),
+            Value::from("not a number"),
+            Value::from(3),
+            Value::from(4),
+            Value::from(5),
+        ]
+        .into_iter()
+        .map(|number| {
+            let mut log = LogEvent::default();
+            log.insert("number", number);
+            log
+        })
+        .collect::<Vec<_>>();
+        let mut outputs = TransformOutputsBuf::new_with_capacity(
+            vec![Output::default(DataType::all())],
+            events.len(),
+        );
+        tform.transform_all(EventArray::Logs(events), &mut outputs);
+
+        let doubled = outputs
+            .drain()
+            .map(|event| event.as_log().get("double").cloned())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            doubled,
+            vec![
+                Some(Value::from(2)),
+                None,
+                Some(Value::from(6)),
+                Some(Value::from(8)),
+                Some(Value::from(10)),
+            ]
+        );
+        assert!(tform.runner().runtime.is_empty());
+    }
+
+    #[test]
+    fn check_remap_shares_state() {
+        let conf = RemapConfig {
+            source: Some(".first_seen = set_state(string!(.host), true) == null".to_string()),
+            drop_on_error: true,
+            ..Default::default()
+        };
+        let mut tform = remap(conf).unwrap();
+        let mut clone = tform.clone();
+
+        let first_seen = |tform: &mut Remap<AstRunner>, host: &str| {
+            let mut event = LogEvent::default();
+            event.insert("host", host);
+            let output = transform_one(tform, event.into()).unwrap();
+            output.as_log()["first_seen"].clone()
+        };
+        assert_eq!(first_seen(&mut tform, "a"), true.into());
+        assert_eq!(first_seen(&mut tform, "b"), true.into());
+        assert_eq!(first_seen(&mut tform, "a"), false.into());
+        // Clones of the transform, running concurrently, share its state.
+        assert_eq!(first_seen(&mut clone, "b"), false.into());
+    }
+
+    #[tokio::test]
+    async fn check_remap_persists_state() {
+        let data_dir = tempfile::tempdir().unwrap();
+        let conf = RemapConfig {
+            source: Some(".previous = set_state(\"last\", .message)".to_string()),
+            state: Some(RemapStateConfig {
+                ttl_secs: NonZeroU64::new(3600).unwrap(),
+                max_entries: NonZeroUsize::new(10).unwrap(),
+                checkpoint_interval_secs: NonZeroU64::new(1).unwrap(),
+                data_dir: Some(data_dir.path().to_owned()),
+                checkpoint_store: Default::default(),
+            }),
+            ..Default::default()
+        };
+        let context = TransformContext {
+            key: Some(ComponentKey::from("stateful")),
+            ..Default::default()
+        };
+
+        for (message, previous) in [("one", Value::Null), ("two", Value::from("one"))] {
+            let (state, checkpointer) = conf.state.as_ref().unwrap().build(&context).await.unwrap();
+            let mut tform = Remap::new_ast_with_state(conf.clone(), &context, state)
+                .unwrap()
+                .0;
+            let output = transform_one(&mut tform, LogEvent::from(message).into()).unwrap();
+            assert_eq!(output.as_log()["previous"], previous);
+
+            // The state is checkpointed once more after the transform is dropped.
+            drop(tform);
+            checkpointer.run().await;
+        }
+        assert!(data_dir.path().join("stateful/state.json").exists());
+    }
+
+    #[tokio::test]
+    async fn check_remap_journals_state() {
+        let data_dir = tempfile::tempdir().unwrap();
+        let conf = RemapConfig {
+            source: Some(".previous = set_state(string!(.key), .message)".to_string()),
+            state: Some(RemapStateConfig {
+                ttl_secs: NonZeroU64::new(3600).unwrap(),
+                max_entries: NonZeroUsize::new(4).unwrap(),
+                checkpoint_interval_secs: NonZeroU64::new(1).unwrap(),
+                data_dir: Some(data_dir.path().to_owned()),
+                checkpoint_store: Default::default(),
+            }),
+            ..Default::default()
+        };
+        // Runs the transform on events setting keys to messages, until Vector restarts.
+        let run = |events: &'static [(&'static str, &'static str)]| {
+            let conf = conf.clone();
+            async move {
+                let context = TransformContext {
+                    key: Some(ComponentKey::from("stateful")),
+                    ..Default::default()
+                };
+                let (state, checkpointer) =
+                    conf.state.as_ref().unwrap().build(&context).await.unwrap();
+                let mut tform = Remap::new_ast_with_state(conf.clone(), &context, state)
+                    .unwrap()
+                    .0;
+                let previous = events
+                    .iter()
+                    .map(|(key, message)| {
+                        let mut event = LogEvent::from(*message);
+                        event.insert("key", *key);
+                        let output = transform_one(&mut tform, event.into()).unwrap();
+                        output.as_log()["previous"].clone()
+                    })
+                    .collect::<Vec<_>>();
+                drop(tform);
+                checkpointer.run().await;
+                previous
+            }
+        };
+
+        let null = Value::Null;
+        assert_eq!(
+            run(&[("a", "1"), ("b", "1"), ("c", "1"), ("d", "1")]).await,
+            vec![null.clone(), null.clone(), null.clone(), null.clone()]
+        );
+        // A single key changed, so only the journal is written.
+        assert_eq!(run(&[("a", "2")]).await, vec![Value::from("1")]);
+        assert!(data_dir.path().join("stateful/state.journal.json").exists());
+        // Setting a new key evicts the key set the longest ago.
+        assert_eq!(
+            run(&[("a", "3"), ("e", "1")]).await,
+            vec![Value::from("2"), null.clone()]
+        );
+        assert_eq!(
+            run(&[("b", "2"), ("a", "4")]).await,
+            vec![null, Value::from("3")]
+        );
+    }
+
     #[test]
     fn check_remap_adds() {
         let event = {
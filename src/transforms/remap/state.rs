@@ -0,0 +1,308 @@
+//! Persistence of the state set and read by the `set_state` and `get_state` functions.
+//!
+//! The state lives in memory while the transform runs, and is checkpointed periodically to a
+//! [checkpoint store][crate::checkpoint], from which it's restored when the transform is built
+//! again, such as when Vector restarts.
+//!
+//! Checkpoints are incremental: the keys changed since the last snapshot of the whole state are
+//! written to a journal, and the state is only written again once the journal grows to half its
+//! size.
+//!
+//! The state isn't kept in an embedded key-value store such as sled or LMDB, which would write
+//! each key as it's set, for two reasons. `get_state` and `set_state` run for every event, within
+//! the synchronous VRL runtime, where blocking on disk writes would stall the transform. And the
+//! state may be checkpointed to a remote store, to outlive ephemeral nodes, which an embedded store
+//! can't write to. The checkpoint stores are the ones the `file` source uses, so local and remote
+//! checkpoints behave the same.
+//!
+//! The cost is a durability window: the keys set since the last checkpoint, at most
+//! `checkpoint_interval_secs` ago, are lost if Vector stops before the next one. This includes
+//! shutdowns, as the last checkpoint is only taken on the first tick after the transform is
+//! dropped. A checkpoint that fails is retried on the next tick, so the window grows for as long
+//! as the store can't be written to.
+
+use std::{
+    collections::BTreeMap,
+    num::{NonZeroU64, NonZeroUsize},
+    path::PathBuf,
+    time::Duration,
+};
+
+use bytes::Bytes;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use snafu::{OptionExt, ResultExt};
+use vector_config::configurable_component;
+use vector_vrl_functions::state::{StateEntry, StateLimits, StateStore};
+
+use super::{StateCheckpointInvalidSnafu, StateWithoutKeySnafu};
+use crate::{
+    checkpoint::{CheckpointStore, CheckpointStoreConfig},
+    config::TransformContext,
+    event::Value,
+    internal_events::RemapStateCheckpointError,
+};
+
+const SNAPSHOT_FILENAME: &str = "state.json";
+const JOURNAL_FILENAME: &str = "state.journal.json";
+
+/// Persistence of the state set by the `set_state` function.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct RemapStateConfig {
+    /// The time-to-live of the keys set without a `ttl_secs` argument, in seconds.
+    #[serde(default = "default_ttl_secs")]
+    pub ttl_secs: NonZeroU64,
+
+    /// The maximum number of keys of the state.
+    ///
+    /// Setting a new key beyond it evicts the keys set the longest ago.
+    #[serde(default = "default_max_entries")]
+    pub max_entries: NonZeroUsize,
+
+    /// How often the state is checkpointed, in seconds.
+    ///
+    /// This is how much of the state may be lost: the keys set since the last checkpoint are lost
+    /// if Vector stops before the next one, even when shutting down gracefully.
+    #[serde(default = "default_checkpoint_interval_secs")]
+    pub checkpoint_interval_secs: NonZeroU64,
+
+    /// The directory used to persist the state.
+    ///
+    /// By default, the global `data_dir` option is used. Please make sure the user Vector is
+    /// running as has write permissions to this directory.
+    #[serde(default)]
+    pub data_dir: Option<PathBuf>,
+
+    /// Where the state is checkpointed.
+    ///
+    /// By default, it's stored in the data directory of the transform. Remote stores allow the
+    /// state to outlive ephemeral nodes.
+    #[configurable(derived)]
+    #[serde(default)]
+    pub checkpoint_store: CheckpointStoreConfig,
+}
+
+fn default_ttl_secs() -> NonZeroU64 {
+    NonZeroU64::new(86400).expect("static non-zero number")
+}
+
+fn default_max_entries() -> NonZeroUsize {
+    NonZeroUsize::new(100_000).expect("static non-zero number")
+}
+
+fn default_checkpoint_interval_secs() -> NonZeroU64 {
+    NonZeroU64::new(10).expect("static non-zero number")
+}
+
+impl RemapStateConfig {
+    /// The limits of the state, which also apply to the state only kept in memory when `config`
+    /// is missing.
+    pub(super) fn limits(config: Option<&Self>) -> StateLimits {
+        let (ttl_secs, max_entries) = config.map_or_else(
+            || (default_ttl_secs(), default_max_entries()),
+            |config| (config.ttl_secs, config.max_entries),
+        );
+        StateLimits {
+            default_ttl: Some(Duration::from_secs(ttl_secs.get())),
+            max_entries: Some(max_entries.get()),
+        }
+    }
+
+    /// Restores the state of the transform from its last checkpoint, returning it along with the
+    /// checkpointer to run while the transform uses it.
+    pub(super) async fn build(
+        &self,
+        context: &TransformContext,
+    ) -> crate::Result<(StateStore, StateCheckpointer)> {
+        let key = context.key.as_ref().context(StateWithoutKeySnafu)?;
+        let build_store = |file_name| {
+            self.checkpoint_store.build(
+                key,
+                &context.globals,
+                self.data_dir.as_ref(),
+                file_name,
+                &context.globals.proxy,
+            )
+        };
+        let snapshots = build_store(SNAPSHOT_FILENAME).await?;
+        let journal = build_store(JOURNAL_FILENAME).await?;
+
+        let snapshot = match snapshots.load().await? {
+            Some(snapshot) => serde_json::from_slice::<Snapshot>(&snapshot)
+                .context(StateCheckpointInvalidSnafu)?,
+            None => Snapshot::default(),
+        };
+        let mut entries = snapshot.entries;
+        // A journal of an earlier snapshot is part of the snapshot already.
+        if let Some(journal) = journal.load().await? {
+            let journal =
+                serde_json::from_slice::<Journal>(&journal).context(StateCheckpointInvalidSnafu)?;
+            if journal.snapshot == snapshot.sequence {
+                for (key, entry) in journal.changes {
+                    match entry {
+                        Some(entry) => entries.insert(key, entry),
+                        None => entries.remove(&key),
+                    };
+                }
+            }
+        }
+
+        let progress = Progress {
+            snapshot: snapshot.sequence,
+            snapshot_len: entries.len(),
+            journal: BTreeMap::new(),
+            pending: false,
+        };
+        let state = StateStore::new(
+            Self::limits(Some(self)),
+            entries.into_iter().map(|(key, entry)| (key, entry.into())),
+        );
+
+        let checkpointer = StateCheckpointer {
+            snapshots,
+            journal,
+            state: state.clone(),
+            interval: Duration::from_secs(self.checkpoint_interval_secs.get()),
+            progress,
+        };
+        Ok((state, checkpointer))
+    }
+}
+
+/// Checkpoints the state of a transform periodically, until the transform is dropped.
+pub(super) struct StateCheckpointer {
+    snapshots: Box<dyn CheckpointStore>,
+    journal: Box<dyn CheckpointStore>,
+    state: StateStore,
+    interval: Duration,
+    progress: Progress,
+}
+
+/// What was checkpointed so far.
+struct Progress {
+    /// The sequence number of the last snapshot.
+    snapshot: u64,
+    /// The number of keys of the last snapshot.
+    snapshot_len: usize,
+    /// The keys changed since the last snapshot.
+    journal: BTreeMap<String, Option<CheckpointEntry>>,
+    /// Whether the last checkpoint failed, and has to be written again.
+    pending: bool,
+}
+
+impl StateCheckpointer {
+    /// Checkpoints the state whenever it changed since the last checkpoint, and once more after
+    /// the programs using it are dropped.
+    pub(super) async fn run(mut self) {
+        let mut interval = tokio::time::interval(self.interval);
+        loop {
+            interval.tick().await;
+            let done = self.state.is_unique();
+            self.checkpoint().await;
+            if done {
+                break;
+            }
+        }
+    }
+
+    async fn checkpoint(&mut self) {
+        let changes = self.state.take_changes();
+        if changes.is_empty() && !self.progress.pending {
+            return;
+        }
+        let progress = &mut self.progress;
+        progress.journal.extend(
+            changes
+                .into_iter()
+                .map(|(key, entry)| (key, entry.map(Into::into))),
+        );
+
+        let result = if progress.journal.len() * 2 >= progress.snapshot_len {
+            // The state changed enough for a snapshot to be cheaper to load than the journal.
+            let snapshot = Snapshot {
+                sequence: progress.snapshot + 1,
+                entries: self
+                    .state
+                    .entries()
+                    .into_iter()
+                    .map(|(key, entry)| (key, entry.into()))
+                    .collect(),
+            };
+            let len = snapshot.entries.len();
+            let result = store(self.snapshots.as_ref(), &snapshot).await;
+            if result.is_ok() {
+                progress.snapshot = snapshot.sequence;
+                progress.snapshot_len = len;
+                progress.journal.clear();
+            }
+            result
+        } else {
+            let journal = JournalRef {
+                snapshot: progress.snapshot,
+                changes: &progress.journal,
+            };
+            store(self.journal.as_ref(), &journal).await
+        };
+
+        progress.pending = result.is_err();
+        if let Err(error) = result {
+            emit!(RemapStateCheckpointError { error });
+        }
+    }
+}
+
+async fn store(store: &dyn CheckpointStore, checkpoint: &impl Serialize) -> Result<(), String> {
+    let checkpoint = serde_json::to_vec(checkpoint).map_err(|error| error.to_string())?;
+    store
+        .store(Bytes::from(checkpoint))
+        .await
+        .map_err(|error| error.to_string())
+}
+
+/// The whole state, as of the checkpoint numbered `sequence`.
+#[derive(Default, Deserialize, Serialize)]
+struct Snapshot {
+    #[serde(default)]
+    sequence: u64,
+    entries: BTreeMap<String, CheckpointEntry>,
+}
+
+/// The keys changed since the snapshot numbered `snapshot`, with `null` for the keys removed.
+#[derive(Deserialize)]
+struct Journal {
+    snapshot: u64,
+    changes: BTreeMap<String, Option<CheckpointEntry>>,
+}
+
+#[derive(Serialize)]
+struct JournalRef<'a> {
+    snapshot: u64,
+    changes: &'a BTreeMap<String, Option<CheckpointEntry>>,
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+struct CheckpointEntry {
+    value: Value,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    expires_at: Option<DateTime<Utc>>,
+}
+
+impl From<StateEntry> for CheckpointEntry {
+    fn from(entry: StateEntry) -> Self {
+        Self {
+            value: entry.value,
+            expires_at: entry.expires_at.map(Into::into),
+        }
+    }
+}
+
+impl From<CheckpointEntry> for StateEntry {
+    fn from(entry: CheckpointEntry) -> Self {
+        Self {
+            value: entry.value,
+            expires_at: entry.expires_at.map(Into::into),
+        }
+    }
+}
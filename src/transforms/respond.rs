@@ -0,0 +1,309 @@
+use bytes::Bytes;
+use snafu::Snafu;
+use vector_config::configurable_component;
+
+use crate::{
+    config::{DataType, GenerateConfig, Input, Output, TransformConfig, TransformContext},
+    event::{Event, Response, Value},
+    internal_events::TemplateRenderingError,
+    schema,
+    template::{Template, RENDER_ERRORS_OUTPUT},
+    transforms::{SyncTransform, Transform, TransformOutputsBuf},
+};
+
+const STATUS_FIELD: &str = "response.status";
+const BODY_FIELD: &str = "response.body";
+const DEFAULT_STATUS: u16 = 200;
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("Invalid response status {}", status))]
+    InvalidStatus { status: u16 },
+}
+
+/// Configuration for the `respond` transform.
+#[configurable_component(transform("respond"))]
+#[configurable(metadata(status = "alpha"))]
+#[derive(Clone, Debug, Default)]
+#[serde(deny_unknown_fields)]
+pub struct RespondConfig {
+    /// The status of the response, as an HTTP status code.
+    ///
+    /// If not set, the `response.status` metadata field of the event is used, such as set with
+    /// `%response.status = 422` in a `remap` transform, or `200` if it isn't set either.
+    status: Option<u16>,
+
+    /// The body of the response.
+    ///
+    /// If not set, the `response.body` metadata field of the event is used, or an empty body if
+    /// it isn't set either.
+    ///
+    /// An empty body is sent if the template fails to render, unless it has an `on_render_error`
+    /// policy. With the `error_output` policy, the event is sent to the `render_errors` output
+    /// without a response, so that a `respond` transform downstream of that output can set one.
+    #[configurable(metadata(templatable))]
+    body: Option<Template>,
+}
+
+impl GenerateConfig for RespondConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(r#"status = 200"#).unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+impl TransformConfig for RespondConfig {
+    async fn build(&self, _context: &TransformContext) -> crate::Result<Transform> {
+        if let Some(status) = self.status {
+            if !is_valid_status(status) {
+                return Err(BuildError::InvalidStatus { status }.into());
+            }
+        }
+        Ok(Transform::synchronous(Respond {
+            status: self.status,
+            body: self.body.clone(),
+        }))
+    }
+
+    fn input(&self) -> Input {
+        Input::all()
+    }
+
+    fn outputs(&self, _: &schema::Definition) -> Vec<Output> {
+        let mut outputs = vec![Output::default(DataType::all())];
+        if self
+            .body
+            .as_ref()
+            .map_or(false, Template::routes_render_errors)
+        {
+            outputs.push(Output::default(DataType::all()).with_port(RENDER_ERRORS_OUTPUT));
+        }
+        outputs
+    }
+
+    fn enable_concurrency(&self) -> bool {
+        true
+    }
+}
+
+const fn is_valid_status(status: u16) -> bool {
+    matches!(status, 100..=599)
+}
+
+/// Sets the response of the sources replying to requests with the response set downstream of
+/// them, passing the events through unchanged.
+#[derive(Clone)]
+pub struct Respond {
+    status: Option<u16>,
+    body: Option<Template>,
+}
+
+impl Respond {
+    fn status(&self, event: &Event) -> u16 {
+        self.status
+            .or_else(|| match event.metadata().value().get(STATUS_FIELD) {
+                Some(Value::Integer(status)) => u16::try_from(*status)
+                    .ok()
+                    .filter(|status| is_valid_status(*status)),
+                _ => None,
+            })
+            .unwrap_or(DEFAULT_STATUS)
+    }
+
+    /// Returns the body of the response to the event, or `None` if the body template failed to
+    /// render and its policy doesn't fall back to an empty body.
+    fn body(&self, event: &Event) -> Option<Bytes> {
+        match &self.body {
+            Some(template) => match template.render(event) {
+                Ok(body) => Some(body),
+                Err(error) => {
+                    let fallback = template.on_render_error().is_none();
+                    emit!(TemplateRenderingError {
+                        error,
+                        field: Some("body"),
+                        drop_event: template.drops_on_render_error(),
+                    });
+                    fallback.then(Bytes::new)
+                }
+            },
+            None => Some(match event.metadata().value().get(BODY_FIELD) {
+                Some(Value::Bytes(body)) => body.clone(),
+                _ => Bytes::new(),
+            }),
+        }
+    }
+}
+
+impl SyncTransform for Respond {
+    fn transform(&mut self, mut event: Event, output: &mut TransformOutputsBuf) {
+        if event.metadata().response_handle().is_none() {
+            output.push(event);
+            return;
+        }
+
+        match self.body(&event) {
+            Some(body) => {
+                // The source replies once every event of the request dropped the handle, so it's
+                // detached here rather than after the event is delivered by the sinks.
+                if let Some(handle) = event.metadata_mut().take_response_handle() {
+                    handle.set(Response {
+                        status: self.status(&event),
+                        body,
+                    });
+                }
+                output.push(event);
+            }
+            None if self
+                .body
+                .as_ref()
+                .map_or(false, Template::routes_render_errors) =>
+            {
+                output.push_named(RENDER_ERRORS_OUTPUT, event)
+            }
+            // Dropping the event along with its handle leaves the source to reply with its
+            // default response.
+            None => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::FutureExt;
+
+    use super::*;
+    use crate::event::{LogEvent, ResponseHandle};
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<RespondConfig>();
+    }
+
+    /// Runs `event` through a `respond` transform with `config`, returning its outputs.
+    fn transform(config: &str, event: Event) -> TransformOutputsBuf {
+        let config = toml::from_str::<RespondConfig>(config).unwrap();
+        let mut output =
+            TransformOutputsBuf::new_with_capacity(config.outputs(&schema::Definition::any()), 1);
+        let mut transform = Respond {
+            status: config.status,
+            body: config.body,
+        };
+        transform.transform(event, &mut output);
+        output
+    }
+
+    fn respond(config: &str, event: LogEvent) -> Option<Response> {
+        let (handle, receiver) = ResponseHandle::new_with_receiver();
+        let mut event = Event::from(event);
+        event.metadata_mut().set_response_handle(handle);
+
+        let mut output = transform(config, event);
+        let mut events = output.take_primary();
+        assert_eq!(events.len(), 1);
+        // The response is sent while the event is still in the pipeline.
+        let response = receiver.now_or_never().unwrap();
+        let event = events.drain().next().unwrap();
+        assert!(event.metadata().response_handle().is_none());
+        response
+    }
+
+    #[test]
+    fn responds_with_the_configured_response() {
+        let response = respond(
+            r#"
+                status = 422
+                body = "invalid {{ id }}"
+            "#,
+            LogEvent::from_iter([("id".to_owned(), Value::from("42"))]),
+        );
+        assert_eq!(
+            response,
+            Some(Response {
+                status: 422,
+                body: Bytes::from("invalid 42"),
+            })
+        );
+    }
+
+    #[test]
+    fn responds_with_the_metadata_response() {
+        let mut event = LogEvent::default();
+        event.metadata_mut().value_mut().insert(STATUS_FIELD, 400);
+        event
+            .metadata_mut()
+            .value_mut()
+            .insert(BODY_FIELD, "missing id");
+        assert_eq!(
+            respond("", event),
+            Some(Response {
+                status: 400,
+                body: Bytes::from("missing id"),
+            })
+        );
+        assert_eq!(
+            respond("", LogEvent::default()),
+            Some(Response {
+                status: DEFAULT_STATUS,
+                body: Bytes::new(),
+            })
+        );
+    }
+
+    #[test]
+    fn passes_through_events_without_a_response_handle() {
+        let mut output = transform("", Event::from(LogEvent::from("message")));
+        assert_eq!(output.take_primary().len(), 1);
+    }
+
+    #[test]
+    fn responds_with_an_empty_body_when_the_body_fails_to_render() {
+        assert_eq!(
+            respond(r#"body = "invalid {{ id }}""#, LogEvent::default()),
+            Some(Response {
+                status: DEFAULT_STATUS,
+                body: Bytes::new(),
+            })
+        );
+    }
+
+    #[test]
+    fn drops_events_when_the_body_fails_to_render() {
+        let (handle, receiver) = ResponseHandle::new_with_receiver();
+        let mut event = Event::from(LogEvent::default());
+        event.metadata_mut().set_response_handle(handle);
+
+        let mut output = transform(
+            r#"body = { template = "invalid {{ id }}", on_render_error = "drop" }"#,
+            event,
+        );
+        assert!(output.take_primary().is_empty());
+        // The source replies with its default response.
+        assert_eq!(receiver.now_or_never().unwrap(), None);
+    }
+
+    #[test]
+    fn routes_events_when_the_body_fails_to_render() {
+        let config =
+            r#"body = { template = "invalid {{ id }}", on_render_error = "error_output" }"#;
+        let outputs = toml::from_str::<RespondConfig>(config)
+            .unwrap()
+            .outputs(&schema::Definition::any());
+        assert_eq!(outputs[1].port.as_deref(), Some(RENDER_ERRORS_OUTPUT));
+
+        let (handle, mut receiver) = ResponseHandle::new_with_receiver();
+        let mut event = Event::from(LogEvent::default());
+        event.metadata_mut().set_response_handle(handle);
+
+        let mut output = transform(config, event);
+        assert!(output.take_primary().is_empty());
+        let mut routed = output
+            .take_all_named()
+            .remove(RENDER_ERRORS_OUTPUT)
+            .unwrap();
+        assert_eq!(routed.len(), 1);
+        // The event keeps its handle, so a `respond` transform downstream can still respond.
+        let event = routed.drain().next().unwrap();
+        assert!(event.metadata().response_handle().is_some());
+        assert!((&mut receiver).now_or_never().is_none());
+    }
+}
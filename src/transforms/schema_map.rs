@@ -0,0 +1,405 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    pin::Pin,
+    time::{Duration, SystemTime},
+};
+
+use async_stream::stream;
+use futures::{Stream, StreamExt};
+use serde::Deserialize;
+use vector_common::{conversion::Conversion, datetime::TimeZone};
+use vector_config::configurable_component;
+
+use crate::{
+    config::{DataType, GenerateConfig, Input, Output, TransformConfig, TransformContext},
+    event::{Event, LogEvent, Value},
+    internal_events::{SchemaMapConversionError, SchemaMapReloadError, SchemaMapReloaded},
+    schema,
+    transforms::{TaskTransform, Transform},
+};
+
+/// Configuration for the `schema_map` transform.
+#[configurable_component(transform("schema_map"))]
+#[configurable(metadata(status = "alpha"))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct SchemaMapConfig {
+    /// The path of the mapping file.
+    ///
+    /// Files with a `.csv` extension are read as CSV, with a header row naming the `field`,
+    /// `rename`, `coerce`, and `default` columns. All other files are read as YAML, as a list of
+    /// rules with the same keys.
+    pub path: PathBuf,
+
+    /// How often, in seconds, to check the mapping file for changes.
+    ///
+    /// The rules are reloaded whenever the file is modified. If the modified file can't be loaded,
+    /// the previous rules are kept.
+    #[serde(default = "default_reload_interval_secs")]
+    pub reload_interval_secs: u64,
+}
+
+const fn default_reload_interval_secs() -> u64 {
+    30
+}
+
+impl GenerateConfig for SchemaMapConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self {
+            path: PathBuf::from("/etc/vector/ecs_mapping.yaml"),
+            reload_interval_secs: default_reload_interval_secs(),
+        })
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+impl TransformConfig for SchemaMapConfig {
+    async fn build(&self, context: &TransformContext) -> crate::Result<Transform> {
+        if self.reload_interval_secs == 0 {
+            return Err("`reload_interval_secs` must be greater than zero.".into());
+        }
+
+        SchemaMap::new(self, context.globals.timezone).map(Transform::event_task)
+    }
+
+    fn input(&self) -> Input {
+        Input::log()
+    }
+
+    fn outputs(&self, _: &schema::Definition) -> Vec<Output> {
+        vec![Output::default(DataType::Log)]
+    }
+}
+
+/// A rule of the mapping file, as written in it.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RuleDefinition<D> {
+    /// The field the rule applies to.
+    field: String,
+    /// The field to move the value to.
+    rename: Option<String>,
+    /// The conversion to apply to the value, such as `integer` or `timestamp|%d/%m/%Y`.
+    coerce: Option<String>,
+    /// The value to set if the field is missing.
+    default: Option<D>,
+}
+
+/// A rule, ready to be applied to events.
+#[derive(Clone, Debug)]
+pub(crate) struct Rule {
+    pub(crate) field: String,
+    pub(crate) rename: Option<String>,
+    pub(crate) coerce: Option<Conversion>,
+    pub(crate) default: Option<Value>,
+}
+
+impl Rule {
+    pub(crate) fn apply(&self, log: &mut LogEvent) {
+        let value = match log.remove(self.field.as_str()) {
+            Some(value) => match &self.coerce {
+                Some(conversion) => match conversion.convert::<Value>(value.coerce_to_bytes()) {
+                    Ok(converted) => Some(converted),
+                    Err(error) => {
+                        emit!(SchemaMapConversionError {
+                            field: &self.field,
+                            error,
+                        });
+                        Some(value)
+                    }
+                },
+                None => Some(value),
+            },
+            None => self.default.clone(),
+        };
+
+        if let Some(value) = value {
+            let target = self.rename.as_deref().unwrap_or(&self.field);
+            log.insert(target, value);
+        }
+    }
+}
+
+/// Reads the rules of the mapping file at `path`.
+fn load_rules(path: &Path, timezone: TimeZone) -> crate::Result<Vec<Rule>> {
+    let is_csv = path
+        .extension()
+        .map_or(false, |extension| extension.eq_ignore_ascii_case("csv"));
+
+    if is_csv {
+        let mut reader = csv::Reader::from_path(path)?;
+        reader
+            .deserialize::<RuleDefinition<String>>()
+            .map(|definition| -> crate::Result<Rule> {
+                let definition = definition?;
+                let coerce = parse_conversion(definition.coerce.as_deref(), timezone)?;
+                // Defaults are strings in CSV files, so they are converted like field values.
+                let default = match (definition.default, &coerce) {
+                    (Some(default), Some(conversion)) => Some(
+                        conversion
+                            .convert::<Value>(default.into())
+                            .map_err(|error| {
+                                format!(
+                                    "Invalid default of field {:?}: {}",
+                                    definition.field, error
+                                )
+                            })?,
+                    ),
+                    (default, _) => default.map(Value::from),
+                };
+                Ok(Rule {
+                    field: definition.field,
+                    rename: definition.rename,
+                    coerce,
+                    default,
+                })
+            })
+            .collect()
+    } else {
+        let definitions: Vec<RuleDefinition<Value>> =
+            serde_yaml::from_str(&fs::read_to_string(path)?)?;
+        definitions
+            .into_iter()
+            .map(|definition| -> crate::Result<Rule> {
+                Ok(Rule {
+                    coerce: parse_conversion(definition.coerce.as_deref(), timezone)?,
+                    field: definition.field,
+                    rename: definition.rename,
+                    default: definition.default,
+                })
+            })
+            .collect()
+    }
+}
+
+fn parse_conversion(coerce: Option<&str>, timezone: TimeZone) -> crate::Result<Option<Conversion>> {
+    Ok(coerce
+        .map(|name| Conversion::parse(name, timezone))
+        .transpose()?)
+}
+
+/// The modification time of the file at `path`, if it can be read.
+fn modified(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
+
+#[derive(Debug)]
+pub struct SchemaMap {
+    path: PathBuf,
+    timezone: TimeZone,
+    reload_interval: Duration,
+    rules: Vec<Rule>,
+    last_modified: Option<SystemTime>,
+}
+
+impl SchemaMap {
+    pub fn new(config: &SchemaMapConfig, timezone: TimeZone) -> crate::Result<Self> {
+        let last_modified = modified(&config.path);
+        let rules = load_rules(&config.path, timezone).map_err(|error| {
+            format!(
+                "Failed to load mapping file {:?}: {}",
+                config.path.display(),
+                error
+            )
+        })?;
+
+        Ok(Self {
+            path: config.path.clone(),
+            timezone,
+            reload_interval: Duration::from_secs(config.reload_interval_secs),
+            rules,
+            last_modified,
+        })
+    }
+
+    /// Reloads the rules if the mapping file was modified since they were last loaded.
+    fn reload_if_modified(&mut self) {
+        let modified = modified(&self.path);
+        if modified.is_none() || modified == self.last_modified {
+            return;
+        }
+        self.last_modified = modified;
+
+        match load_rules(&self.path, self.timezone) {
+            Ok(rules) => {
+                emit!(SchemaMapReloaded {
+                    path: &self.path,
+                    rules: rules.len(),
+                });
+                self.rules = rules;
+            }
+            Err(error) => emit!(SchemaMapReloadError {
+                path: &self.path,
+                error,
+            }),
+        }
+    }
+
+    fn transform_one(&self, mut event: Event) -> Event {
+        if let Event::Log(log) = &mut event {
+            for rule in self.rules.iter() {
+                rule.apply(log);
+            }
+        }
+        event
+    }
+}
+
+impl TaskTransform<Event> for SchemaMap {
+    fn transform(
+        mut self: Box<Self>,
+        mut input_rx: Pin<Box<dyn Stream<Item = Event> + Send>>,
+    ) -> Pin<Box<dyn Stream<Item = Event> + Send>>
+    where
+        Self: 'static,
+    {
+        let mut reload_interval = tokio::time::interval(self.reload_interval);
+
+        Box::pin(stream! {
+            loop {
+                let event = tokio::select! {
+                    _ = reload_interval.tick() => {
+                        self.reload_if_modified();
+                        continue;
+                    }
+                    maybe_event = input_rx.next() => match maybe_event {
+                        Some(event) => event,
+                        None => break,
+                    },
+                };
+                yield self.transform_one(event);
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use chrono::{TimeZone as _, Utc};
+    use tempfile::NamedTempFile;
+
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<SchemaMapConfig>();
+    }
+
+    fn mapping_file(suffix: &str, contents: &str) -> NamedTempFile {
+        let mut file = tempfile::Builder::new().suffix(suffix).tempfile().unwrap();
+        file.write_all(contents.as_bytes()).unwrap();
+        file
+    }
+
+    fn schema_map(file: &NamedTempFile) -> SchemaMap {
+        let config = SchemaMapConfig {
+            path: file.path().to_owned(),
+            reload_interval_secs: 1,
+        };
+        SchemaMap::new(&config, TimeZone::parse("UTC").unwrap()).unwrap()
+    }
+
+    fn transform(schema_map: &SchemaMap, log: LogEvent) -> LogEvent {
+        schema_map.transform_one(log.into()).into_log()
+    }
+
+    #[test]
+    fn applies_yaml_rules() {
+        let file = mapping_file(
+            ".yaml",
+            r#"
+            - field: src_ip
+              rename: source.ip
+            - field: status
+              rename: http.response.status_code
+              coerce: integer
+            - field: time
+              coerce: "timestamp|%d/%m/%Y %H:%M:%S"
+            - field: event.dataset
+              default: nginx.access
+            "#,
+        );
+        let schema_map = schema_map(&file);
+
+        let mut log = LogEvent::default();
+        log.insert("src_ip", "192.0.2.1");
+        log.insert("status", "404");
+        log.insert("time", "01/10/2022 12:00:00");
+        let log = transform(&schema_map, log);
+
+        assert_eq!(log["source.ip"], "192.0.2.1".into());
+        assert!(log.get("src_ip").is_none());
+        assert_eq!(log["http.response.status_code"], 404.into());
+        assert_eq!(log["time"], Utc.ymd(2022, 10, 1).and_hms(12, 0, 0).into());
+        assert_eq!(log["event.dataset"], "nginx.access".into());
+    }
+
+    #[test]
+    fn applies_csv_rules() {
+        let file = mapping_file(
+            ".csv",
+            "field,rename,coerce,default\n\
+             src_port,source.port,integer,0\n\
+             user,user.name,,\n",
+        );
+        let schema_map = schema_map(&file);
+
+        let mut log = LogEvent::default();
+        log.insert("user", "alice");
+        let log = transform(&schema_map, log);
+
+        assert_eq!(log["source.port"], 0.into());
+        assert_eq!(log["user.name"], "alice".into());
+    }
+
+    #[test]
+    fn keeps_values_failing_coercion() {
+        let file = mapping_file(".yaml", "- field: status\n  coerce: integer\n");
+        let schema_map = schema_map(&file);
+
+        let mut log = LogEvent::default();
+        log.insert("status", "unknown");
+        let log = transform(&schema_map, log);
+
+        assert_eq!(log["status"], "unknown".into());
+    }
+
+    #[test]
+    fn rejects_invalid_mapping_files() {
+        let file = mapping_file(".yaml", "- field: status\n  coerce: decimal\n");
+        let config = SchemaMapConfig {
+            path: file.path().to_owned(),
+            reload_interval_secs: 1,
+        };
+
+        assert!(SchemaMap::new(&config, TimeZone::Local).is_err());
+    }
+
+    #[test]
+    fn reloads_modified_mapping_file() {
+        let file = mapping_file(".yaml", "- field: a\n  rename: b\n");
+        let mut schema_map = schema_map(&file);
+
+        fs::write(file.path(), "- field: a\n  rename: c\n").unwrap();
+        // Make sure the modification is seen, whatever the resolution of the file system.
+        schema_map.last_modified = Some(SystemTime::UNIX_EPOCH);
+        schema_map.reload_if_modified();
+
+        let mut log = LogEvent::default();
+        log.insert("a", 1);
+        let log = transform(&schema_map, log);
+        assert_eq!(log["c"], 1.into());
+
+        // Invalid changes keep the previous rules.
+        fs::write(file.path(), "not: [a list").unwrap();
+        schema_map.last_modified = Some(SystemTime::UNIX_EPOCH);
+        schema_map.reload_if_modified();
+        assert_eq!(schema_map.rules.len(), 1);
+    }
+}
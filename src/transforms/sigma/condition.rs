@@ -0,0 +1,283 @@
+use super::rule::Search;
+use crate::event::LogEvent;
+
+/// The condition of a rule, combining the results of its searches.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Condition {
+    /// Whether the search at this index of the rule matches.
+    Search(usize),
+    Not(Box<Condition>),
+    All(Vec<Condition>),
+    Any(Vec<Condition>),
+}
+
+impl Condition {
+    pub fn evaluate(&self, searches: &[Search], log: &LogEvent) -> bool {
+        match self {
+            Self::Search(index) => searches[*index].matches(log),
+            Self::Not(condition) => !condition.evaluate(searches, log),
+            Self::All(conditions) => conditions
+                .iter()
+                .all(|condition| condition.evaluate(searches, log)),
+            Self::Any(conditions) => conditions
+                .iter()
+                .any(|condition| condition.evaluate(searches, log)),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    OpenParen,
+    CloseParen,
+    And,
+    Or,
+    Not,
+    OneOf,
+    AllOf,
+    Identifier(String),
+}
+
+fn tokenize(condition: &str) -> Result<Vec<Token>, String> {
+    if condition.contains('|') {
+        return Err("aggregations in conditions are not supported".to_owned());
+    }
+
+    let mut words = Vec::new();
+    let mut word = String::new();
+    for c in condition.chars() {
+        match c {
+            '(' | ')' => {
+                if !word.is_empty() {
+                    words.push(std::mem::take(&mut word));
+                }
+                words.push(c.to_string());
+            }
+            c if c.is_whitespace() => {
+                if !word.is_empty() {
+                    words.push(std::mem::take(&mut word));
+                }
+            }
+            c => word.push(c),
+        }
+    }
+    if !word.is_empty() {
+        words.push(word);
+    }
+
+    let mut tokens = Vec::with_capacity(words.len());
+    let mut words = words.into_iter().peekable();
+    while let Some(word) = words.next() {
+        let token = match word.to_ascii_lowercase().as_str() {
+            "(" => Token::OpenParen,
+            ")" => Token::CloseParen,
+            "and" => Token::And,
+            "or" => Token::Or,
+            "not" => Token::Not,
+            quantifier @ ("1" | "all")
+                if words
+                    .peek()
+                    .map_or(false, |next| next.eq_ignore_ascii_case("of")) =>
+            {
+                words.next();
+                if quantifier == "1" {
+                    Token::OneOf
+                } else {
+                    Token::AllOf
+                }
+            }
+            _ => Token::Identifier(word),
+        };
+        tokens.push(token);
+    }
+    Ok(tokens)
+}
+
+/// Parses the condition of a rule, referring to its searches by their index in `names`.
+///
+/// Supports `and`, `or`, `not`, parentheses, and the `1 of` and `all of` quantifiers over search
+/// names with `*` wildcards or `them`.
+pub fn parse(condition: &str, names: &[String]) -> Result<Condition, String> {
+    let tokens = tokenize(condition)?;
+    let mut parser = Parser {
+        tokens: &tokens,
+        position: 0,
+        names,
+    };
+    let parsed = parser.parse_or()?;
+    match parser.tokens.get(parser.position) {
+        None => Ok(parsed),
+        Some(token) => Err(format!("unexpected {:?} in condition", token)),
+    }
+}
+
+struct Parser<'a> {
+    tokens: &'a [Token],
+    position: usize,
+    names: &'a [String],
+}
+
+impl<'a> Parser<'a> {
+    fn next(&mut self) -> Option<&'a Token> {
+        let token = self.tokens.get(self.position);
+        self.position += 1;
+        token
+    }
+
+    fn next_if(&mut self, expected: &Token) -> bool {
+        let matches = self.tokens.get(self.position) == Some(expected);
+        if matches {
+            self.position += 1;
+        }
+        matches
+    }
+
+    fn parse_or(&mut self) -> Result<Condition, String> {
+        let mut conditions = vec![self.parse_and()?];
+        while self.next_if(&Token::Or) {
+            conditions.push(self.parse_and()?);
+        }
+        Ok(flatten(conditions, Condition::Any))
+    }
+
+    fn parse_and(&mut self) -> Result<Condition, String> {
+        let mut conditions = vec![self.parse_not()?];
+        while self.next_if(&Token::And) {
+            conditions.push(self.parse_not()?);
+        }
+        Ok(flatten(conditions, Condition::All))
+    }
+
+    fn parse_not(&mut self) -> Result<Condition, String> {
+        if self.next_if(&Token::Not) {
+            Ok(Condition::Not(Box::new(self.parse_not()?)))
+        } else {
+            self.parse_primary()
+        }
+    }
+
+    fn parse_primary(&mut self) -> Result<Condition, String> {
+        match self.next() {
+            Some(Token::OpenParen) => {
+                let condition = self.parse_or()?;
+                if self.next_if(&Token::CloseParen) {
+                    Ok(condition)
+                } else {
+                    Err("unbalanced parentheses in condition".to_owned())
+                }
+            }
+            Some(Token::OneOf) => Ok(flatten(self.parse_quantified()?, Condition::Any)),
+            Some(Token::AllOf) => Ok(flatten(self.parse_quantified()?, Condition::All)),
+            Some(Token::Identifier(name)) => self
+                .names
+                .iter()
+                .position(|candidate| candidate == name)
+                .map(Condition::Search)
+                .ok_or_else(|| format!("unknown search {:?} in condition", name)),
+            Some(token) => Err(format!("unexpected {:?} in condition", token)),
+            None => Err("unexpected end of condition".to_owned()),
+        }
+    }
+
+    /// The searches a quantifier applies to.
+    fn parse_quantified(&mut self) -> Result<Vec<Condition>, String> {
+        let pattern = match self.next() {
+            Some(Token::Identifier(pattern)) => pattern,
+            _ => return Err("expected search names after quantifier".to_owned()),
+        };
+        let searches = self
+            .names
+            .iter()
+            .enumerate()
+            .filter(|(_, name)| {
+                if pattern == "them" {
+                    // Searches starting with an underscore are excluded from `them` by convention.
+                    !name.starts_with('_')
+                } else {
+                    matches_wildcard(pattern, name)
+                }
+            })
+            .map(|(index, _)| Condition::Search(index))
+            .collect::<Vec<_>>();
+        if searches.is_empty() {
+            Err(format!("no search matches {:?} in condition", pattern))
+        } else {
+            Ok(searches)
+        }
+    }
+}
+
+fn flatten(mut conditions: Vec<Condition>, combine: fn(Vec<Condition>) -> Condition) -> Condition {
+    if conditions.len() == 1 {
+        conditions.remove(0)
+    } else {
+        combine(conditions)
+    }
+}
+
+/// Whether `name` matches `pattern`, in which `*` matches any number of characters.
+fn matches_wildcard(pattern: &str, name: &str) -> bool {
+    match pattern.split_once('*') {
+        None => pattern == name,
+        Some((prefix, rest)) => {
+            let name = match name.strip_prefix(prefix) {
+                Some(name) => name,
+                None => return false,
+            };
+            rest.is_empty()
+                || (0..=name.len())
+                    .filter(|index| name.is_char_boundary(*index))
+                    .any(|index| matches_wildcard(rest, &name[index..]))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names() -> Vec<String> {
+        ["selection", "selection_cmd", "filter", "_helper"]
+            .into_iter()
+            .map(String::from)
+            .collect()
+    }
+
+    #[test]
+    fn parses_boolean_operators() {
+        assert_eq!(
+            parse("selection and not filter or (selection_cmd)", &names()).unwrap(),
+            Condition::Any(vec![
+                Condition::All(vec![
+                    Condition::Search(0),
+                    Condition::Not(Box::new(Condition::Search(2))),
+                ]),
+                Condition::Search(1),
+            ])
+        );
+    }
+
+    #[test]
+    fn parses_quantifiers() {
+        assert_eq!(
+            parse("1 of selection* and not all of them", &names()).unwrap(),
+            Condition::All(vec![
+                Condition::Any(vec![Condition::Search(0), Condition::Search(1)]),
+                Condition::Not(Box::new(Condition::All(vec![
+                    Condition::Search(0),
+                    Condition::Search(1),
+                    Condition::Search(2),
+                ]))),
+            ])
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_conditions() {
+        assert!(parse("selection and", &names()).is_err());
+        assert!(parse("(selection", &names()).is_err());
+        assert!(parse("unknown", &names()).is_err());
+        assert!(parse("1 of nothing*", &names()).is_err());
+        assert!(parse("selection | count() > 5", &names()).is_err());
+    }
+}
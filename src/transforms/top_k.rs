@@ -0,0 +1,363 @@
+use std::{
+    collections::{BTreeSet, HashMap},
+    pin::Pin,
+    time::Duration,
+};
+
+use async_stream::stream;
+use chrono::Utc;
+use futures::{Stream, StreamExt};
+use snafu::Snafu;
+use vector_config::configurable_component;
+
+use crate::{
+    config::{
+        log_schema, DataType, GenerateConfig, Input, Output, TransformConfig, TransformContext,
+    },
+    event::{Event, LogEvent},
+    internal_events::TemplateRenderingError,
+    schema,
+    template::Template,
+    transforms::{TaskTransform, Transform},
+};
+
+/// Configuration for the `top_k` transform.
+#[configurable_component(transform("top_k"))]
+#[configurable(metadata(status = "alpha"))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct TopKConfig {
+    /// The key that events are counted by.
+    ///
+    /// Events for which the key can't be rendered are not counted.
+    #[configurable(metadata(templatable))]
+    key: Template,
+
+    /// The number of keys with the highest counts to emit at the end of each window.
+    #[serde(default = "default_k")]
+    k: usize,
+
+    /// The number of keys tracked at once.
+    ///
+    /// Once this many keys are tracked, a new key replaces the one with the lowest count. Counts are
+    /// overestimated by at most the number of events in the window divided by this number, so
+    /// larger values trade memory for accuracy. Defaults to ten times `k`.
+    sketch_size: Option<usize>,
+
+    /// The length of the window over which keys are counted, in seconds.
+    ///
+    /// Must be positive.
+    ///
+    /// Counts are reset after the top keys of a window are emitted.
+    #[serde(default = "default_window_secs")]
+    window_secs: f64,
+}
+
+const fn default_k() -> usize {
+    10
+}
+
+const fn default_window_secs() -> f64 {
+    60.0
+}
+
+impl GenerateConfig for TopKConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(r#"key = "{{ client_ip }}""#).unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+impl TransformConfig for TopKConfig {
+    async fn build(&self, _context: &TransformContext) -> crate::Result<Transform> {
+        TopK::new(self).map(Transform::event_task)
+    }
+
+    fn input(&self) -> Input {
+        Input::new(DataType::Log | DataType::Metric)
+    }
+
+    fn outputs(&self, _: &schema::Definition) -> Vec<Output> {
+        vec![Output::default(DataType::Log)]
+    }
+}
+
+#[derive(Debug, Snafu)]
+pub enum ConfigError {
+    #[snafu(display("`k` must be non-zero"))]
+    ZeroK,
+
+    #[snafu(display("`window_secs` must be positive"))]
+    InvalidWindow,
+
+    #[snafu(display("`sketch_size` must not be smaller than `k`"))]
+    SketchTooSmall,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+struct Counter {
+    count: u64,
+    error: u64,
+}
+
+/// A space-saving sketch, approximating the most frequent keys of a stream in bounded memory.
+///
+/// Once the sketch is full, a new key replaces the key with the lowest count and inherits that
+/// count, which is recorded as the error of the new counter. Counts are never underestimated, and
+/// overestimated by at most the total count divided by the capacity of the sketch.
+#[derive(Debug)]
+struct SpaceSaving {
+    capacity: usize,
+    counters: HashMap<String, Counter>,
+    by_count: BTreeSet<(u64, String)>,
+}
+
+impl SpaceSaving {
+    fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            counters: HashMap::with_capacity(capacity),
+            by_count: BTreeSet::new(),
+        }
+    }
+
+    fn insert(&mut self, key: String) {
+        if let Some(counter) = self.counters.get_mut(&key) {
+            self.by_count.remove(&(counter.count, key.clone()));
+            counter.count += 1;
+            self.by_count.insert((counter.count, key));
+            return;
+        }
+
+        let counter = if self.counters.len() < self.capacity {
+            Counter { count: 1, error: 0 }
+        } else {
+            let (min, evicted) = self
+                .by_count
+                .iter()
+                .next()
+                .cloned()
+                .expect("sketch is full");
+            self.by_count.remove(&(min, evicted.clone()));
+            self.counters.remove(&evicted);
+            Counter {
+                count: min + 1,
+                error: min,
+            }
+        };
+        self.by_count.insert((counter.count, key.clone()));
+        self.counters.insert(key, counter);
+    }
+
+    /// Returns the `k` keys with the highest counts, highest first.
+    fn top(&self, k: usize) -> impl Iterator<Item = (&str, Counter)> + '_ {
+        self.by_count
+            .iter()
+            .rev()
+            .take(k)
+            .map(|(_, key)| (key.as_str(), self.counters[key]))
+    }
+
+    fn clear(&mut self) {
+        self.counters.clear();
+        self.by_count.clear();
+    }
+}
+
+#[derive(Debug)]
+pub struct TopK {
+    key: Template,
+    k: usize,
+    window: Duration,
+    sketch: SpaceSaving,
+}
+
+impl TopK {
+    pub fn new(config: &TopKConfig) -> crate::Result<Self> {
+        if config.k == 0 {
+            return Err(Box::new(ConfigError::ZeroK));
+        }
+        if !config.window_secs.is_finite() || config.window_secs <= 0.0 {
+            return Err(Box::new(ConfigError::InvalidWindow));
+        }
+
+        let sketch_size = config.sketch_size.unwrap_or(config.k * 10);
+        if sketch_size < config.k {
+            return Err(Box::new(ConfigError::SketchTooSmall));
+        }
+
+        Ok(Self {
+            key: config.key.clone(),
+            k: config.k,
+            window: Duration::from_secs_f64(config.window_secs),
+            sketch: SpaceSaving::new(sketch_size),
+        })
+    }
+
+    fn record(&mut self, event: &Event) {
+        match self.key.render_string(event) {
+            Ok(key) => self.sketch.insert(key),
+            Err(error) => emit!(TemplateRenderingError {
+                error,
+                field: Some("key"),
+                drop_event: false,
+            }),
+        }
+    }
+
+    fn flush_into(&mut self, output: &mut Vec<Event>) {
+        let timestamp = Utc::now();
+        output.extend(
+            self.sketch
+                .top(self.k)
+                .zip(1..)
+                .map(|((key, counter), rank)| {
+                    let mut log = LogEvent::default();
+                    log.insert("key", key);
+                    log.insert("count", counter.count);
+                    log.insert("error", counter.error);
+                    log.insert("rank", rank);
+                    log.insert(log_schema().timestamp_key(), timestamp);
+                    Event::from(log)
+                }),
+        );
+
+        self.sketch.clear();
+    }
+}
+
+impl TaskTransform<Event> for TopK {
+    fn transform(
+        mut self: Box<Self>,
+        mut input_rx: Pin<Box<dyn Stream<Item = Event> + Send>>,
+    ) -> Pin<Box<dyn Stream<Item = Event> + Send>>
+    where
+        Self: 'static,
+    {
+        let mut flush_stream = tokio::time::interval(self.window);
+
+        Box::pin(stream! {
+            let mut output = Vec::new();
+            let mut done = false;
+            while !done {
+                tokio::select! {
+                    _ = flush_stream.tick() => {
+                        self.flush_into(&mut output);
+                    },
+                    maybe_event = input_rx.next() => {
+                        match maybe_event {
+                            None => {
+                                self.flush_into(&mut output);
+                                done = true;
+                            }
+                            Some(event) => self.record(&event),
+                        }
+                    }
+                };
+                for event in output.drain(..) {
+                    yield event;
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<TopKConfig>();
+    }
+
+    fn config(k: usize, sketch_size: Option<usize>) -> TopKConfig {
+        TopKConfig {
+            key: Template::try_from("{{ client }}").unwrap(),
+            k,
+            sketch_size,
+            window_secs: 60.0,
+        }
+    }
+
+    fn event(client: &str) -> Event {
+        let mut log = LogEvent::default();
+        log.insert("client", client);
+        Event::from(log)
+    }
+
+    #[test]
+    fn sketch_counts_exactly_below_capacity() {
+        let mut sketch = SpaceSaving::new(3);
+        for key in ["a", "b", "a", "c", "a", "b"] {
+            sketch.insert(key.to_string());
+        }
+
+        let top = sketch.top(2).collect::<Vec<_>>();
+        assert_eq!(
+            top,
+            vec![
+                ("a", Counter { count: 3, error: 0 }),
+                ("b", Counter { count: 2, error: 0 }),
+            ]
+        );
+    }
+
+    #[test]
+    fn sketch_replaces_lowest_count() {
+        let mut sketch = SpaceSaving::new(2);
+        for key in ["a", "a", "a", "b", "c"] {
+            sketch.insert(key.to_string());
+        }
+
+        let top = sketch.top(2).collect::<Vec<_>>();
+        assert_eq!(
+            top,
+            vec![
+                ("a", Counter { count: 3, error: 0 }),
+                ("c", Counter { count: 2, error: 1 }),
+            ]
+        );
+    }
+
+    #[test]
+    fn emits_top_keys_and_resets() {
+        let mut top_k = TopK::new(&config(2, None)).unwrap();
+        for client in ["x", "y", "z", "x", "y", "x"] {
+            top_k.record(&event(client));
+        }
+        // Events without a key are not counted.
+        top_k.record(&Event::from(LogEvent::default()));
+
+        let mut output = Vec::new();
+        top_k.flush_into(&mut output);
+
+        assert_eq!(output.len(), 2);
+        let first = output[0].as_log();
+        assert_eq!(first["key"], "x".into());
+        assert_eq!(first["count"], 3.into());
+        assert_eq!(first["error"], 0.into());
+        assert_eq!(first["rank"], 1.into());
+        let second = output[1].as_log();
+        assert_eq!(second["key"], "y".into());
+        assert_eq!(second["rank"], 2.into());
+
+        output.clear();
+        top_k.flush_into(&mut output);
+        assert!(output.is_empty());
+    }
+
+    #[test]
+    fn rejects_invalid_config() {
+        assert!(TopK::new(&config(0, None)).is_err());
+        assert!(TopK::new(&config(5, Some(4))).is_err());
+
+        for window_secs in [0.0, -1.0, f64::NAN, f64::INFINITY] {
+            let config = TopKConfig {
+                window_secs,
+                ..config(5, None)
+            };
+            assert!(TopK::new(&config).is_err());
+        }
+    }
+}
@@ -1,6 +1,12 @@
+#[cfg(feature = "sources-utils-udp")]
+use std::{future::Future, io, net::SocketAddr};
+
 use socket2::SockRef;
 use tokio::net::UdpSocket;
 
+#[cfg(feature = "sources-utils-udp")]
+use crate::internal_events::UdpReceiveBufferSize;
+
 // This function will be obsolete after tokio/mio internally use `socket2` and expose the methods to
 // apply options to a socket.
 pub fn set_receive_buffer_size(socket: &UdpSocket, size: usize) -> std::io::Result<()> {
@@ -12,3 +18,206 @@ pub fn set_receive_buffer_size(socket: &UdpSocket, size: usize) -> std::io::Resu
 pub fn set_send_buffer_size(socket: &UdpSocket, size: usize) -> std::io::Result<()> {
     SockRef::from(socket).set_send_buffer_size(size)
 }
+
+/// Binds `count` sockets to `address`, sharing it with `SO_REUSEPORT` when there's more than one,
+/// so the operating system spreads the incoming datagrams across them.
+///
+/// The receive buffer of the sockets is raised to `receive_buffer_bytes`, if given, and its
+/// resulting size is reported.
+#[cfg(feature = "sources-utils-udp")]
+pub fn bind_sockets(
+    mut address: SocketAddr,
+    count: usize,
+    receive_buffer_bytes: Option<usize>,
+) -> io::Result<Vec<UdpSocket>> {
+    #[cfg(not(unix))]
+    if count > 1 {
+        return Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "Listening on multiple UDP sockets is only supported on Unix.",
+        ));
+    }
+
+    let mut sockets = Vec::with_capacity(count);
+    for _ in 0..count {
+        let socket = socket2::Socket::new(
+            socket2::Domain::for_address(address),
+            socket2::Type::DGRAM,
+            Some(socket2::Protocol::UDP),
+        )?;
+        #[cfg(unix)]
+        if count > 1 {
+            socket.set_reuse_port(true)?;
+        }
+        socket.set_nonblocking(true)?;
+        socket.bind(&address.into())?;
+        // Binding to port 0 picks a port, which the other sockets have to share.
+        address = socket.local_addr()?.as_socket().unwrap_or(address);
+
+        let socket = UdpSocket::from_std(socket.into())?;
+        if let Some(receive_buffer_bytes) = receive_buffer_bytes {
+            raise_receive_buffer_size(&socket, receive_buffer_bytes);
+        }
+        sockets.push(socket);
+    }
+
+    if let Some(socket) = sockets.first() {
+        match SockRef::from(socket).recv_buffer_size() {
+            Ok(size) => emit!(UdpReceiveBufferSize {
+                requested: receive_buffer_bytes,
+                size,
+            }),
+            Err(error) => {
+                warn!(message = "Failed reading receive buffer size of UDP socket.", %error)
+            }
+        }
+    }
+
+    Ok(sockets)
+}
+
+/// Raises the receive buffer of `socket` to `size`. On Linux, `SO_RCVBUFFORCE` is tried when
+/// `net.core.rmem_max` caps it, which needs the `CAP_NET_ADMIN` capability.
+#[cfg(feature = "sources-utils-udp")]
+fn raise_receive_buffer_size(socket: &UdpSocket, size: usize) {
+    if let Err(error) = set_receive_buffer_size(socket, size) {
+        warn!(message = "Failed configuring receive buffer size on UDP socket.", %error);
+        return;
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        use std::os::unix::io::AsRawFd;
+
+        use nix::sys::socket::{setsockopt, sockopt::RcvBufForce};
+
+        let capped = SockRef::from(socket)
+            .recv_buffer_size()
+            .map_or(false, |actual| actual < size);
+        if capped {
+            if let Err(error) = setsockopt(socket.as_raw_fd(), RcvBufForce, &size) {
+                debug!(message = "Failed forcing receive buffer size on UDP socket.", %error);
+            }
+        }
+    }
+}
+
+/// Runs `receive` on each of the sockets, on their own task so they're spread across the worker
+/// threads, until they all return. Fails if any of them fails.
+///
+/// The datagrams the operating system drops because the receive buffer of the sockets is full are
+/// reported meanwhile.
+#[cfg(feature = "sources-utils-udp")]
+pub async fn receive_on_sockets<F, Fut>(sockets: Vec<UdpSocket>, receive: F) -> Result<(), ()>
+where
+    F: Fn(UdpSocket) -> Fut,
+    Fut: Future<Output = Result<(), ()>> + Send + 'static,
+{
+    use tracing::Instrument;
+
+    let drops = drops::monitor(&sockets);
+    let tasks = sockets
+        .into_iter()
+        .map(|socket| tokio::spawn(receive(socket).in_current_span()))
+        .collect::<Vec<_>>();
+
+    tokio::pin!(drops);
+    let results = tokio::select! {
+        results = futures::future::join_all(tasks) => results,
+        _ = &mut drops => unreachable!("monitoring drops never ends"),
+    };
+
+    let mut outcome = Ok(());
+    for result in results {
+        match result {
+            Ok(result) => outcome = outcome.and(result),
+            Err(error) if error.is_panic() => std::panic::resume_unwind(error.into_panic()),
+            Err(_) => outcome = Err(()),
+        }
+    }
+    outcome
+}
+
+#[cfg(all(feature = "sources-utils-udp", target_os = "linux"))]
+mod drops {
+    use std::{collections::HashSet, future::Future, os::unix::io::AsRawFd, time::Duration};
+
+    use nix::libc::ino_t;
+    use tokio::net::UdpSocket;
+
+    use crate::internal_events::UdpReceiveDrops;
+
+    const INTERVAL: Duration = Duration::from_secs(10);
+
+    /// Reports the datagrams dropped by the sockets, as counted in `/proc/net/udp{,6}`.
+    pub(super) fn monitor(sockets: &[UdpSocket]) -> impl Future<Output = ()> {
+        let inodes = sockets
+            .iter()
+            .filter_map(|socket| nix::sys::stat::fstat(socket.as_raw_fd()).ok())
+            .map(|stat| stat.st_ino)
+            .collect::<HashSet<_>>();
+
+        async move {
+            let mut reported = 0;
+            let mut interval = tokio::time::interval(INTERVAL);
+            loop {
+                interval.tick().await;
+
+                let mut drops = 0;
+                for path in ["/proc/net/udp", "/proc/net/udp6"] {
+                    if let Ok(table) = tokio::fs::read_to_string(path).await {
+                        drops += parse_drops(&table, &inodes);
+                    }
+                }
+                if drops > reported {
+                    emit!(UdpReceiveDrops {
+                        count: drops - reported,
+                    });
+                    reported = drops;
+                }
+            }
+        }
+    }
+
+    /// Sums the drops of the sockets with the given inodes in a `/proc/net/udp` table.
+    fn parse_drops(table: &str, inodes: &HashSet<ino_t>) -> u64 {
+        table
+            .lines()
+            .skip(1)
+            .filter_map(|line| {
+                let fields = line.split_whitespace().collect::<Vec<_>>();
+                let inode = fields.get(9)?.parse::<ino_t>().ok()?;
+                let drops = fields.get(12)?.parse::<u64>().ok()?;
+                inodes.contains(&inode).then_some(drops)
+            })
+            .sum()
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn parses_drops() {
+            let table = "   sl  local_address rem_address   st tx_queue rx_queue tr tm->when retrnsmt   uid  timeout inode ref pointer drops
+  123: 00000000:2FBD 00000000:0000 07 00000000:00000000 00:00000000 00000000  1000        0 41870 2 0000000000000000 17
+  124: 00000000:2FBD 00000000:0000 07 00000000:00000000 00:00000000 00000000  1000        0 41871 2 0000000000000000 5
+  530: 0100007F:0035 00000000:0000 07 00000000:00000000 00:00000000 00000000   101        0 20334 2 0000000000000000 9
+";
+            let inodes = [41870, 41871].into_iter().collect();
+            assert_eq!(parse_drops(table, &inodes), 22);
+        }
+    }
+}
+
+#[cfg(all(feature = "sources-utils-udp", not(target_os = "linux")))]
+mod drops {
+    use std::future::Future;
+
+    use tokio::net::UdpSocket;
+
+    /// The drops of the sockets are only known on Linux.
+    pub(super) fn monitor(_sockets: &[UdpSocket]) -> impl Future<Output = ()> {
+        futures::future::pending()
+    }
+}
@@ -0,0 +1,142 @@
+//! Unix domain socket helpers shared by the sources and sinks using them.
+
+use std::{
+    io::{self, Read, Write},
+    path::{Path, PathBuf},
+};
+
+use socket2::{Domain, SockAddr, Socket, Type};
+use tokio::io::unix::AsyncFd;
+
+/// Resolves the configured path of a Unix socket to the address to bind or connect to.
+///
+/// On Linux, a path starting with `@` designates the rest of the path as an address in the
+/// abstract namespace, which isn't bound to a file.
+pub fn socket_path(path: &Path) -> PathBuf {
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    {
+        use std::{
+            ffi::OsString,
+            os::unix::ffi::{OsStrExt, OsStringExt},
+        };
+
+        if let Some(name) = path.as_os_str().as_bytes().strip_prefix(b"@") {
+            let mut address = vec![0];
+            address.extend_from_slice(name);
+            return PathBuf::from(OsString::from_vec(address));
+        }
+    }
+    path.to_owned()
+}
+
+/// Whether the resolved `path` of a socket is an address in the abstract namespace, for which no
+/// file is created.
+pub fn is_abstract(path: &Path) -> bool {
+    use std::os::unix::ffi::OsStrExt;
+
+    path.as_os_str().as_bytes().first() == Some(&0)
+}
+
+/// A Unix socket of type `SOCK_SEQPACKET`, listening for connections.
+pub struct UnixSeqpacketListener(AsyncFd<Socket>);
+
+impl UnixSeqpacketListener {
+    /// Binds a listener to the resolved `path`.
+    pub fn bind(path: &Path) -> io::Result<Self> {
+        let socket = Socket::new(Domain::UNIX, Type::SEQPACKET, None)?;
+        socket.set_nonblocking(true)?;
+        socket.bind(&SockAddr::unix(path)?)?;
+        socket.listen(1024)?;
+        AsyncFd::new(socket).map(Self)
+    }
+
+    /// Accepts a new connection.
+    pub async fn accept(&self) -> io::Result<UnixSeqpacket> {
+        loop {
+            let mut guard = self.0.readable().await?;
+            if let Ok(result) = guard.try_io(|inner| inner.get_ref().accept()) {
+                let (socket, _address) = result?;
+                return UnixSeqpacket::new(socket);
+            }
+        }
+    }
+}
+
+/// A connected Unix socket of type `SOCK_SEQPACKET`, which preserves the boundaries of the
+/// messages sent through it.
+pub struct UnixSeqpacket(AsyncFd<Socket>);
+
+impl UnixSeqpacket {
+    fn new(socket: Socket) -> io::Result<Self> {
+        socket.set_nonblocking(true)?;
+        AsyncFd::new(socket).map(Self)
+    }
+
+    /// Connects to the listener bound to the resolved `path`.
+    ///
+    /// Connecting to a Unix socket completes immediately, without waiting for the listener to
+    /// accept the connection.
+    pub fn connect(path: &Path) -> io::Result<Self> {
+        let socket = Socket::new(Domain::UNIX, Type::SEQPACKET, None)?;
+        socket.connect(&SockAddr::unix(path)?)?;
+        Self::new(socket)
+    }
+
+    /// Receives a message into `buf`, truncating it to the length of `buf`.
+    ///
+    /// Returns 0 once the peer closed the connection.
+    pub async fn recv(&self, buf: &mut [u8]) -> io::Result<usize> {
+        loop {
+            let mut guard = self.0.readable().await?;
+            if let Ok(result) = guard.try_io(|inner| (&mut inner.get_ref()).read(buf)) {
+                return result;
+            }
+        }
+    }
+
+    /// Sends `buf` as a single message.
+    pub async fn send(&self, buf: &[u8]) -> io::Result<usize> {
+        loop {
+            let mut guard = self.0.writable().await?;
+            if let Ok(result) = guard.try_io(|inner| (&mut inner.get_ref()).write(buf)) {
+                return result;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    #[test]
+    fn resolves_abstract_paths() {
+        let path = socket_path(Path::new("@vector"));
+        assert_eq!(path, PathBuf::from("\0vector"));
+        assert!(is_abstract(&path));
+
+        let path = socket_path(Path::new("/run/vector.sock"));
+        assert_eq!(path, PathBuf::from("/run/vector.sock"));
+        assert!(!is_abstract(&path));
+    }
+
+    #[tokio::test]
+    async fn seqpacket_preserves_message_boundaries() {
+        let path = tempfile::tempdir().unwrap().into_path().join("seqpacket");
+        let listener = UnixSeqpacketListener::bind(&path).unwrap();
+        let client = UnixSeqpacket::connect(&path).unwrap();
+        let server = listener.accept().await.unwrap();
+
+        client.send(b"first").await.unwrap();
+        client.send(b"second").await.unwrap();
+        drop(client);
+
+        let mut buf = [0; 16];
+        let len = server.recv(&mut buf).await.unwrap();
+        assert_eq!(&buf[..len], b"first");
+        let len = server.recv(&mut buf).await.unwrap();
+        assert_eq!(&buf[..len], b"second");
+        assert_eq!(server.recv(&mut buf).await.unwrap(), 0);
+    }
+}
@@ -69,6 +69,11 @@ pub struct Opts {
         value_delimiter(',')
     )]
     pub config_dirs: Vec<PathBuf>,
+
+    /// A file holding the policy the configuration must comply with, in addition to the `policy`
+    /// section of the configuration. The format is detected from the file name.
+    #[arg(long, env = "VECTOR_POLICY")]
+    pub policy: Option<PathBuf>,
 }
 
 impl Opts {
@@ -137,6 +142,11 @@ pub fn validate_config(opts: &Opts, fmt: &mut Formatter) -> Option<Config> {
     config::init_log_schema(&paths, true)
         .map_err(&mut report_error)
         .ok()?;
+    if let Some(policy) = &opts.policy {
+        config::init_policy(policy)
+            .map_err(&mut report_error)
+            .ok()?;
+    }
     let (builder, load_warnings) = config::load_builder_from_paths(&paths)
         .map_err(&mut report_error)
         .ok()?;
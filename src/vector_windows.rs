@@ -23,8 +23,9 @@ pub mod service_control {
     use snafu::ResultExt;
     use windows_service::{
         service::{
-            ServiceAccess, ServiceErrorControl, ServiceExitCode, ServiceInfo, ServiceStartType,
-            ServiceState, ServiceStatus,
+            Service, ServiceAccess, ServiceAction, ServiceActionType, ServiceErrorControl,
+            ServiceExitCode, ServiceFailureActions, ServiceFailureResetPeriod, ServiceInfo,
+            ServiceStartType, ServiceState, ServiceStatus,
         },
         service_manager::{ServiceManager, ServiceManagerAccess},
         Result,
@@ -104,6 +105,12 @@ pub mod service_control {
 
         pub executable_path: std::path::PathBuf,
         pub launch_arguments: Vec<OsString>,
+
+        /// The delays before restarting the service after consecutive failures. The service isn't
+        /// restarted if there are none.
+        pub restart_delays: Vec<Duration>,
+        /// How long the service must run without failing for the failure count to be reset.
+        pub restart_reset_period: Duration,
     }
 
     impl std::str::FromStr for ControlAction {
@@ -224,9 +231,16 @@ pub mod service_control {
             account_password: None,
         };
 
-        service_manager
-            .create_service(&service_info, ServiceAccess::empty())
+        // Restart actions require the service to be startable by the service control manager.
+        let service = service_manager
+            .create_service(
+                &service_info,
+                ServiceAccess::CHANGE_CONFIG | ServiceAccess::START,
+            )
             .context(ServiceSnafu)?;
+        if !service_def.restart_delays.is_empty() {
+            configure_recovery(&service, service_def)?;
+        }
 
         emit!(WindowsServiceInstall {
             name: &*service_def.name.to_string_lossy(),
@@ -240,6 +254,31 @@ pub mod service_control {
         Ok(())
     }
 
+    /// Makes the service control manager restart the service when it fails, including when it
+    /// stops with an error exit code rather than crashing.
+    fn configure_recovery(service: &Service, service_def: &ServiceDefinition) -> crate::Result<()> {
+        let actions = service_def
+            .restart_delays
+            .iter()
+            .map(|delay| ServiceAction {
+                action_type: ServiceActionType::Restart,
+                delay: *delay,
+            })
+            .collect();
+        service
+            .update_failure_actions(ServiceFailureActions {
+                reset_period: ServiceFailureResetPeriod::After(service_def.restart_reset_period),
+                reboot_msg: None,
+                command: None,
+                actions: Some(actions),
+            })
+            .context(ServiceSnafu)?;
+        service
+            .set_failure_actions_on_non_crash_failures(true)
+            .context(ServiceSnafu)?;
+        Ok(())
+    }
+
     fn uninstall_service(service_def: &ServiceDefinition) -> crate::Result<()> {
         let service_access =
             ServiceAccess::QUERY_STATUS | ServiceAccess::STOP | ServiceAccess::DELETE;
@@ -354,10 +393,59 @@ pub mod service_control {
     }
 }
 
+/// Reports errors to the Windows Event Log.
+///
+/// Before Vector is fully started as a service, its own logs aren't visible anywhere, so the Event
+/// Log is the only place where the reason of a failed start can be looked up.
+mod event_log {
+    use std::{ffi::OsStr, iter, os::windows::ffi::OsStrExt, ptr};
+
+    use windows_sys::Win32::System::EventLog::{
+        DeregisterEventSource, RegisterEventSourceW, ReportEventW, EVENTLOG_ERROR_TYPE,
+    };
+
+    fn to_wide(value: &str) -> Vec<u16> {
+        OsStr::new(value)
+            .encode_wide()
+            .chain(iter::once(0))
+            .collect()
+    }
+
+    /// Writes `message` as an error of the `vector` source to the Application log.
+    pub(super) fn report_error(message: &str) {
+        let source = to_wide(super::SERVICE_NAME);
+        let mut message = to_wide(message);
+        let strings = [message.as_mut_ptr()];
+
+        // SAFETY: The strings are NUL terminated and outlive the calls, and the handle is only
+        // used while it's registered.
+        unsafe {
+            let handle = RegisterEventSourceW(ptr::null(), source.as_ptr());
+            if handle == 0 {
+                return;
+            }
+            ReportEventW(
+                handle,
+                EVENTLOG_ERROR_TYPE,
+                0,
+                0,
+                ptr::null_mut(),
+                strings.len() as u16,
+                0,
+                strings.as_ptr(),
+                ptr::null(),
+            );
+            DeregisterEventSource(handle);
+        }
+    }
+}
+
 define_windows_service!(ffi_service_main, win_main);
 
 fn win_main(arguments: Vec<OsString>) {
-    if let Err(_e) = run_service(arguments) {}
+    if let Err(error) = run_service(arguments) {
+        event_log::report_error(&format!("Vector service failed: {}.", error));
+    }
 }
 
 pub fn run() -> Result<()> {
@@ -412,6 +500,30 @@ fn run_service(_arguments: Vec<OsString>) -> Result<()> {
 
             Ok(())
         }
-        _ => Ok(()),
+        Err(code) => {
+            event_log::report_error(&format!(
+                "Vector failed to start as a service, exiting with code {}. Run `vector validate` \
+                 with the configuration of the service for details.",
+                code
+            ));
+
+            // Report the failure to the service control manager, so that the recovery actions of
+            // the service apply.
+            let status_handle =
+                windows_service::service_control_handler::register(SERVICE_NAME, |_| {
+                    ServiceControlHandlerResult::NotImplemented
+                })?;
+            status_handle.set_service_status(ServiceStatus {
+                service_type: SERVICE_TYPE,
+                current_state: ServiceState::Stopped,
+                controls_accepted: ServiceControlAccept::empty(),
+                exit_code: ServiceExitCode::ServiceSpecific(code as u32),
+                checkpoint: 0,
+                wait_hint: Duration::default(),
+                process_id: None,
+            })?;
+
+            Ok(())
+        }
     }
 }
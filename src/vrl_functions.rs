@@ -0,0 +1,187 @@
+//! Functions defined in VRL by the `vrl_functions` global option.
+
+use std::collections::{BTreeMap, HashSet};
+
+use vector_core::config::VrlFunctionConfig;
+use vector_vrl_functions::user_function::{self, UserFunction};
+use vrl::{diagnostic::Formatter, CompileConfig, Function};
+
+/// Builds the functions of the `vrl_functions` global option, and makes them available to the
+/// VRL programs compiled afterwards.
+pub fn configure(
+    functions: &BTreeMap<String, VrlFunctionConfig>,
+    enrichment_tables: &enrichment::TableRegistry,
+) -> Result<(), Vec<String>> {
+    user_function::register(build(functions, enrichment_tables)?);
+    Ok(())
+}
+
+/// The functions that functions defined in VRL can use.
+fn builtin_functions() -> Vec<Box<dyn Function>> {
+    vrl_stdlib::all()
+        .into_iter()
+        .chain(enrichment::vrl_functions().into_iter())
+        .chain(vector_vrl_functions::builtin_vrl_functions())
+        .collect()
+}
+
+fn build(
+    functions: &BTreeMap<String, VrlFunctionConfig>,
+    enrichment_tables: &enrichment::TableRegistry,
+) -> Result<Vec<UserFunction>, Vec<String>> {
+    let builtins = builtin_functions();
+    let builtin_names = builtins
+        .iter()
+        .map(|function| function.identifier())
+        .collect::<HashSet<_>>();
+
+    let mut built = Vec::new();
+    let mut errors = Vec::new();
+    for (name, config) in functions {
+        match build_function(name, config, &builtins, &builtin_names, enrichment_tables) {
+            Ok(function) => built.push(function),
+            Err(error) => errors.push(format!("VRL function \"{}\": {}", name, error)),
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(built)
+    } else {
+        Err(errors)
+    }
+}
+
+fn build_function(
+    name: &str,
+    config: &VrlFunctionConfig,
+    builtins: &[Box<dyn Function>],
+    builtin_names: &HashSet<&str>,
+    enrichment_tables: &enrichment::TableRegistry,
+) -> Result<UserFunction, String> {
+    if !is_identifier(name) {
+        return Err("names can only contain letters, digits and underscores.".to_owned());
+    }
+    if builtin_names.contains(name) {
+        return Err("a VRL function already has this name.".to_owned());
+    }
+    let mut parameters = HashSet::new();
+    for parameter in &config.parameters {
+        if !is_identifier(parameter) {
+            return Err(format!(
+                "parameter \"{}\": names can only contain letters, digits and underscores.",
+                parameter
+            ));
+        }
+        if !parameters.insert(parameter) {
+            return Err(format!("parameter \"{}\" is defined twice.", parameter));
+        }
+    }
+
+    let source = match (&config.source, &config.file) {
+        (Some(source), None) => source.clone(),
+        (None, Some(path)) => std::fs::read_to_string(path)
+            .map_err(|error| format!("couldn't read {:?}: {}", path, error))?,
+        _ => return Err("exactly one of `source` and `file` must be set.".to_owned()),
+    };
+
+    let mut compile_config = CompileConfig::default();
+    compile_config.set_custom(enrichment_tables.clone());
+    compile_config.set_custom(crate::host_metadata::store());
+    crate::protobuf_descriptors::set_custom(&mut compile_config);
+
+    let (function, warnings) =
+        UserFunction::compile(name, &config.parameters, &source, builtins, compile_config)
+            .map_err(|diagnostics| Formatter::new(&source, diagnostics).colored().to_string())?;
+
+    if function.queries_event() {
+        return Err(
+            "functions can only use their parameters, not the event they're called on.".to_owned(),
+        );
+    }
+
+    if !warnings.is_empty() {
+        let warnings = Formatter::new(&source, warnings).colored().to_string();
+        warn!(message = "VRL compilation warning.", function = %name, %warnings);
+    }
+
+    Ok(function)
+}
+
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    matches!(chars.next(), Some(c) if c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+#[cfg(test)]
+mod tests {
+    use value::Value;
+    use vrl::{state::TypeState, Runtime, TargetValue};
+
+    use super::*;
+
+    fn config(parameters: &[&str], source: &str) -> VrlFunctionConfig {
+        VrlFunctionConfig {
+            parameters: parameters.iter().map(|&p| p.to_owned()).collect(),
+            source: Some(source.to_owned()),
+            file: None,
+        }
+    }
+
+    fn build_one(name: &str, config: VrlFunctionConfig) -> Result<Vec<UserFunction>, Vec<String>> {
+        build(
+            &BTreeMap::from([(name.to_owned(), config)]),
+            &enrichment::TableRegistry::default(),
+        )
+    }
+
+    #[test]
+    fn calls_functions() {
+        let functions = build_one(
+            "parse_app_log",
+            config(
+                &["message", "prefix"],
+                r#"string!(parse_json!(message).level) + prefix"#,
+            ),
+        )
+        .unwrap();
+
+        let mut all = builtin_functions();
+        all.extend(
+            functions
+                .into_iter()
+                .map(|f| Box::new(f) as Box<dyn Function>),
+        );
+        let program = vrl::compile_with_state(
+            r#"parse_app_log!(.message, prefix: "-")"#,
+            &all,
+            &TypeState::default(),
+            CompileConfig::default(),
+        )
+        .unwrap()
+        .program;
+
+        let mut target = TargetValue {
+            value: Value::Object(BTreeMap::from([(
+                "message".to_owned(),
+                Value::from(r#"{"level": "info"}"#),
+            )])),
+            metadata: Value::Object(BTreeMap::new()),
+            secrets: Default::default(),
+        };
+        let result = Runtime::default().resolve(&mut target, &program, &Default::default());
+        assert_eq!(result.unwrap(), Value::from("info-"));
+    }
+
+    #[test]
+    fn rejects_functions_using_the_event() {
+        let errors = build_one("get_message", config(&[], ".message")).unwrap_err();
+        assert!(errors[0].contains("not the event"), "{}", errors[0]);
+    }
+
+    #[test]
+    fn rejects_builtin_names() {
+        let errors = build_one("parse_json", config(&["value"], "value")).unwrap_err();
+        assert!(errors[0].contains("already has this name"), "{}", errors[0]);
+    }
+}
@@ -49,6 +49,7 @@ fn get_root_opts(config_path: PathBuf) -> RootOpts {
         log_format: LogFormat::Text,
         color: Color::Auto,
         watch_config: false,
+        feature_flags: vec![],
     }
 }
 
@@ -53,7 +53,7 @@ _values: {
 // * `stable` - The component is production ready.
 // * `deprecated` - The component will be removed in a future version.
 // * `removed` - The component has been removed.
-#DevelopmentStatus: "beta" | "stable" | "deprecated" | "removed"
+#DevelopmentStatus: "alpha" | "beta" | "stable" | "deprecated" | "removed"
 
 #EncodingCodec: "json" | "logfmt" | "text" | "native" | "native_json" | "avro" | "gelf"
 
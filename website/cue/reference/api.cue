@@ -47,6 +47,59 @@ api: {
 				of the address set using the `bind` parameter.
 				"""
 		}
+		tokens: {
+			common:   false
+			required: false
+			description: """
+				The tokens accepted by the API. If any are set, requests to the
+				`/graphql` endpoint must send one of them as a bearer token in the
+				`Authorization` header, and are rejected with a `401` otherwise. The
+				`/health` and `/playground` endpoints stay accessible without a token.
+				The `/reload` and `/quit` endpoints always require a token with the
+				`control` scope.
+				"""
+			type: array: {
+				default: []
+				items: type: object: {
+					examples: []
+					options: {
+						token: {
+							description: "The secret sent by clients to authenticate."
+							required:    true
+							type: string: {
+								examples: ["${VECTOR_API_TOKEN}"]
+							}
+						}
+						scopes: {
+							description: "The operations the token grants access to."
+							required:    true
+							type: array: items: type: string: {
+								enum: {
+									"read-metrics": "Query and subscribe to components, their metrics, and their errors and discarded events, as used by `vector top`."
+									tap:            "Tap the events flowing through components, as used by `vector tap`."
+									control:        "Control the running Vector instance, with the `/reload` and `/quit` endpoints and the `setLogFilter`, `resetLogFilter` and `reloadEnrichmentTable` mutations, and query its resolved configuration, with secrets masked."
+								}
+							}
+						}
+						components: {
+							description: """
+								Glob patterns of the component IDs the token grants access to.
+								Components not matching any of them are left out of query,
+								subscription, and tap results. If not set, all components are
+								accessible.
+								"""
+							required: false
+							type: array: {
+								default: null
+								items: type: string: {
+									examples: ["team_a_*"]
+								}
+							}
+						}
+					}
+				}
+			}
+		}
 	}
 
 	endpoints: {
@@ -66,6 +119,12 @@ api: {
 							there were any errors in your query.
 							"""
 					}
+					"401": {
+						description: """
+							API tokens are configured, and the request doesn't
+							carry a valid one.
+							"""
+					}
 				}
 			}
 		}
@@ -82,6 +141,50 @@ api: {
 				}
 			}
 		}
+		"/quit": {
+			POST: {
+				description: """
+					Gracefully shuts Vector down, the same way as
+					sending it a `SIGTERM` signal. Useful where signals
+					can't easily be sent to the process, such as in
+					Windows containers. Requires an API token with the
+					`control` scope.
+					"""
+				responses: {
+					"202": {
+						description: "The shutdown has been triggered."
+					}
+					"401": {
+						description: "The request doesn't carry a valid API token."
+					}
+					"403": {
+						description: "The API token doesn't grant the `control` scope."
+					}
+				}
+			}
+		}
+		"/reload": {
+			POST: {
+				description: """
+					Reloads the configuration from disk, the same way as
+					sending Vector a `SIGHUP` signal. Useful where signals
+					can't easily be sent to the process, such as in
+					Windows containers. Requires an API token with the
+					`control` scope.
+					"""
+				responses: {
+					"202": {
+						description: "The reload has been triggered."
+					}
+					"401": {
+						description: "The request doesn't carry a valid API token."
+					}
+					"403": {
+						description: "The API token doesn't grant the `control` scope."
+					}
+				}
+			}
+		}
 		"/playground": {
 			GET: {
 				description: """
@@ -162,6 +162,16 @@ cli: {
 			type:        "integer"
 			env_var:     "VECTOR_THREADS"
 		}
+		"feature-flags": {
+			description: env_vars.VECTOR_FEATURE_FLAGS.description
+			type:        "string"
+			env_var:     "VECTOR_FEATURE_FLAGS"
+		}
+		"policy": {
+			description: env_vars.VECTOR_POLICY.description
+			type:        "string"
+			env_var:     "VECTOR_POLICY"
+		}
 		"internal-log-rate-limit": {
 			_short:      "i"
 			description: env_vars.VECTOR_INTERNAL_LOG_RATE_LIMIT.description
@@ -303,12 +313,27 @@ cli: {
 					description: "Vector GraphQL API server endpoint"
 					type:        "string"
 				}
+				"token": {
+					description: env_vars.VECTOR_API_TOKEN.description
+					type:        "string"
+					env_var:     "VECTOR_API_TOKEN"
+				}
 				"limit": {
 					_short:      "l"
 					description: "Maximum number of events to sample each interval"
 					type:        "integer"
 					default:     100
 				}
+				"sample-rate": {
+					_short: "s"
+					description: """
+						Only tap one of every N events, before sampling each interval. The
+						rate is applied by the Vector instance, which keeps tapping busy
+						components cheap.
+						"""
+					type:    "integer"
+					default: 1
+				}
 				"format": {
 					_short:      "f"
 					description: "Encoding format for events printed to screen"
@@ -348,7 +373,8 @@ cli: {
 		"top": {
 			description: """
 				Display topology and metrics in the console, for a local or remote Vector
-				instance
+				instance, along with the most recent errors and discarded events of its
+				components
 				"""
 
 			flags: _default_flags & {
@@ -377,6 +403,11 @@ cli: {
 					description: "The URL for the GraphQL endpoint of the running Vector instance"
 					type:        "string"
 				}
+				"token": {
+					description: env_vars.VECTOR_API_TOKEN.description
+					type:        "string"
+					env_var:     "VECTOR_API_TOKEN"
+				}
 			}
 		}
 
@@ -419,6 +450,11 @@ cli: {
 						"""
 					type: "string"
 				}
+				"policy": {
+					description: env_vars.VECTOR_POLICY.description
+					type:        "string"
+					env_var:     "VECTOR_POLICY"
+				}
 			}
 
 			args: {
@@ -503,6 +539,13 @@ cli: {
 				examples: ["/mnt/host/sys"]
 			}
 		}
+		VECTOR_API_TOKEN: {
+			description: "The token used by `vector tap` and `vector top` to authenticate to the Vector API."
+			type: string: {
+				default: null
+				examples: ["s3cr3t"]
+			}
+		}
 		VECTOR_COLOR: {
 			description: "Control when ANSI terminal formatting is used."
 			type: string: {
@@ -555,6 +598,29 @@ cli: {
 				"""
 			type: string: default: null
 		}
+		VECTOR_FEATURE_FLAGS: {
+			description: """
+				A comma-separated list of feature flags to enable, in addition to the ones of the
+				`feature_flags` global option. The `alpha_components` flag allows the use of
+				components whose status is `alpha`, and the `internal_events_audit` flag checks
+				that components emit the internal events they are required to.
+				"""
+			type: string: {
+				default: null
+				examples: ["alpha_components", "internal_events_audit"]
+			}
+		}
+		VECTOR_POLICY: {
+			description: """
+				A file holding the policy the configuration must comply with, in addition to the
+				`policy` section of the configuration. It holds the same options as that section,
+				and its format is detected from its name. It's read when Vector starts.
+				"""
+			type: string: {
+				default: null
+				examples: ["/etc/vector/policy.toml"]
+			}
+		}
 		VECTOR_LOG: {
 			description: "Vector's log level. Each log level includes messages from higher priority levels."
 			type: string: {
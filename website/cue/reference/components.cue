@@ -609,6 +609,197 @@ components: {
 				}
 			}
 
+			_sink_adaptive_batching: {
+				common:      false
+				description: "Adjusts the size of the batches of each partition of this sink, such as a log stream or a key prefix, to the latency of its requests. When set, the batch limits are the size of the largest batches sent to each partition, and the batches of partitions whose requests are slow or fail are made smaller."
+				required:    false
+				type: object: options: {
+					target_latency_secs: {
+						common:      false
+						description: "The latency that requests should stay under. Must be a finite number greater than `0`. The batches of a partition are made smaller while the average latency of its requests is above this."
+						required:    false
+						type: float: {
+							default: 1.0
+							unit:    "seconds"
+						}
+					}
+					min_ratio: {
+						common:      false
+						description: "The smallest fraction of the batch limits that batches can be made. Valid values are greater than `0` and less than or equal to `1`."
+						required:    false
+						type: float: default: 0.1
+					}
+					decrease_ratio: {
+						common:      false
+						description: "The fraction of their current size to make batches when decreasing their size. Valid values are greater than `0` and less than `1`."
+						required:    false
+						type: float: default: 0.9
+					}
+					ewma_alpha: {
+						common:      false
+						description: "The weighting of new latency measurements compared to older measurements, in the exponentially weighted moving average of the latency of requests. Valid values are greater than `0` and less than or equal to `1`."
+						required:    false
+						type: float: default: 0.4
+					}
+				}
+			}
+
+			_source_checkpoint_store: {
+				common:      false
+				description: "Where the checkpoints of this source are stored. By default, they are stored in the data directory of the source. Remote stores allow Vector to resume from the last checkpoint when it runs on ephemeral nodes, whose data directory doesn't outlive them."
+				required:    false
+				type: object: options: {
+					type: {
+						common:      false
+						description: "The type of checkpoint store."
+						required:    false
+						type: string: {
+							default: "file"
+							enum: {
+								file:         "Stores checkpoints in the data directory of the source."
+								redis:        "Stores checkpoints in Redis."
+								aws_s3:       "Stores checkpoints in an AWS S3 bucket."
+								aws_dynamodb: "Stores checkpoints in an AWS DynamoDB table."
+							}
+						}
+					}
+					url: {
+						description:   "The URL of the Redis server."
+						relevant_when: "type = \"redis\""
+						required:      true
+						type: string: {
+							examples: ["redis://127.0.0.1:6379/0"]
+						}
+					}
+					bucket: {
+						description:   "The S3 bucket the checkpoints are stored in."
+						relevant_when: "type = \"aws_s3\""
+						required:      true
+						type: string: {
+							examples: ["my-vector-checkpoints"]
+						}
+					}
+					table: {
+						description:   "The DynamoDB table the checkpoints are stored in. Its partition key must be a string attribute named `key`."
+						relevant_when: "type = \"aws_dynamodb\""
+						required:      true
+						type: string: {
+							examples: ["vector-checkpoints"]
+						}
+					}
+					region: {
+						common:        false
+						description:   "The [AWS region](\(urls.aws_regions)) of the S3 bucket or DynamoDB table."
+						relevant_when: "type = \"aws_s3\" or type = \"aws_dynamodb\""
+						required:      false
+						type: string: {
+							default: null
+							examples: ["us-east-1"]
+						}
+					}
+					endpoint: {
+						common:        false
+						description:   "Custom endpoint for use with AWS-compatible services."
+						relevant_when: "type = \"aws_s3\" or type = \"aws_dynamodb\""
+						required:      false
+						type: string: {
+							default: null
+							examples: ["http://127.0.0.0:5000/path/to/service"]
+						}
+					}
+					auth: {
+						common:        false
+						description:   "Options for the authentication strategy, as for the [`aws_s3` sink](\(urls.vector_sinks)/aws_s3/#auth)."
+						relevant_when: "type = \"aws_s3\" or type = \"aws_dynamodb\""
+						required:      false
+						type: object: options: {}
+					}
+					key_prefix: {
+						common:        false
+						description:   "The prefix of the keys the checkpoints are stored under. The ID of the node, the ID of the source, and the name of the checkpoint are appended to it, such as `vector/checkpoints/node-1/my_source/checkpoints.json`."
+						relevant_when: "type = \"redis\" or type = \"aws_s3\" or type = \"aws_dynamodb\""
+						required:      false
+						type: string: {
+							default: "vector/checkpoints/"
+						}
+					}
+					node_id: {
+						common:        false
+						description:   "The identity of the node the checkpoints are stored for. A node resumes from the checkpoints stored under its identity, which defaults to the hostname. Set it to an identity that outlives the node, such as the name of the Kubernetes node with `${VECTOR_SELF_NODE_NAME}`, to resume on a node replacing it."
+						relevant_when: "type = \"redis\" or type = \"aws_s3\" or type = \"aws_dynamodb\""
+						required:      false
+						type: string: {
+							default: null
+							examples: ["${VECTOR_SELF_NODE_NAME}"]
+						}
+					}
+				}
+			}
+
+			_source_coordination: {
+				common:      false
+				description: "Coordination between the instances of Vector running this source. The instances running a source with the same ID, and the same coordination backend, split the endpoints to scrape between them, instead of all scraping every endpoint. The endpoints of an instance which stops, or can no longer reach the backend, move to the other instances once its lease expires."
+				required:    false
+				type: object: options: {
+					type: {
+						description: "The backend holding the members of the coordination group."
+						required:    true
+						type: string: {
+							enum: {
+								kubernetes: "Holds each member of the group as a `Lease` in Kubernetes."
+								redis:      "Holds the members of the group in a sorted set in Redis."
+							}
+						}
+					}
+					lease_secs: {
+						common:      false
+						description: "How long, in seconds, an instance stays a member of the group without renewing its lease. Leases are renewed three times per period."
+						required:    false
+						type: uint: {
+							default: 15
+							unit:    "seconds"
+						}
+					}
+					namespace: {
+						common:        false
+						description:   "The namespace the leases are created in. By default, the namespace of the Kubernetes client configuration is used."
+						relevant_when: "type = \"kubernetes\""
+						required:      false
+						type: string: {
+							default: null
+							examples: ["vector"]
+						}
+					}
+					kube_config_file: {
+						common:        false
+						description:   "Optional path to a readable kubeconfig file. If not set, Vector will try to connect to Kubernetes using in-cluster configuration."
+						relevant_when: "type = \"kubernetes\""
+						required:      false
+						type: string: {
+							default: null
+							examples: ["/path/to/.kube/config"]
+						}
+					}
+					url: {
+						description:   "The URL of the Redis server."
+						relevant_when: "type = \"redis\""
+						required:      true
+						type: string: {
+							examples: ["redis://127.0.0.1:6379/0"]
+						}
+					}
+					key_prefix: {
+						common:        false
+						description:   "The prefix of the keys the groups are held under. The ID of the source is appended to it."
+						relevant_when: "type = \"redis\""
+						required:      false
+						type: string: {
+							default: "vector/coordination/"
+						}
+					}
+				}
+			}
+
 			_tls_accept: {
 				_args: {
 					can_verify_certificate:  bool | *true
@@ -937,6 +1128,20 @@ components: {
 				}
 			}
 
+			annotations: {
+				common: false
+				description: """
+					Free-form annotations of the component, such as the team or tenant owning it. Annotations
+					are only used to check the component against the rules of the global
+					[`policy`](\(urls.vector_configuration)/#policy).
+					"""
+				required: false
+				type: object: {
+					examples: [{"tenant": "team-a"}]
+					options: {}
+				}
+			}
+
 			if Kind != "source" {
 				inputs: {
 					description: """
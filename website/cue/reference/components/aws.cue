@@ -39,6 +39,90 @@ components: _aws: {
 							examples: ["arn:aws:iam::123456789098:role/my_role"]
 						}
 					}
+					external_id: {
+						category:    "Auth"
+						common:      false
+						description: "The external ID to pass when assuming `assume_role`. Required by roles whose trust policy grants access to a third party."
+						required:    false
+						type: string: {
+							default: null
+							examples: ["my-external-id"]
+						}
+					}
+					session_name: {
+						category:    "Auth"
+						common:      false
+						description: "The name of the role session when assuming `assume_role`. If not set, a name is generated."
+						required:    false
+						type: string: {
+							default: null
+							examples: ["vector-session"]
+						}
+					}
+					session_tags: {
+						category:    "Auth"
+						common:      false
+						description: "[Session tags](\(urls.aws_session_tags)) to pass when assuming `assume_role`."
+						required:    false
+						type: object: {
+							examples: [{"team": "observability"}]
+							options: {
+								"*": {
+									description: "A session tag."
+									required:    true
+									type: string: {}
+								}
+							}
+						}
+					}
+					role_chain: {
+						category:    "Auth"
+						common:      false
+						description: """
+							Roles to assume, in order, before assuming `assume_role`. The first role is assumed with
+							the credentials found by the default credentials chain, and each following role with the
+							credentials of the one before it.
+							"""
+						required:    false
+						type: array: {
+							default: []
+							items: type: object: options: {
+								assume_role: {
+									description: "The ARN of the role to assume."
+									required:    true
+									type: string: examples: ["arn:aws:iam::123456789098:role/hop"]
+								}
+								external_id: {
+									description: "The external ID to pass when assuming the role."
+									required:    false
+									type: string: default: null
+								}
+								session_name: {
+									description: "The name of the role session. If not set, a name is generated."
+									required:    false
+									type: string: default: null
+								}
+								session_tags: {
+									description: "Session tags to pass when assuming the role."
+									required:    false
+									type: object: {
+										examples: [{"team": "observability"}]
+										options: {}
+									}
+								}
+							}
+						}
+					}
+					web_identity_token_file: {
+						category:    "Auth"
+						common:      false
+						description: "Path to a file holding a web identity token to assume `assume_role` with, as done for IAM roles for service accounts on EKS."
+						required:    false
+						type: string: {
+							default: null
+							examples: ["/var/run/secrets/eks.amazonaws.com/serviceaccount/token"]
+						}
+					}
 					region: {
 						category:    "Auth"
 						common:      false
@@ -83,6 +167,18 @@ components: _aws: {
 				examples: ["http://127.0.0.0:5000/path/to/service"]
 			}
 		}
+		endpoint_variant: {
+			common:      false
+			description: "The variant of the regional AWS endpoint to use. Can't be combined with a custom `endpoint`."
+			required:    false
+			type: string: {
+				default: null
+				enum: {
+					fips:      "Use the [FIPS 140-2](\(urls.aws_fips)) validated endpoint of the service. Not available in the China regions."
+					dualstack: "Use the [dual-stack](\(urls.aws_dualstack)) endpoint of the service, reachable over both IPv4 and IPv6."
+				}
+			}
+		}
 		region: {
 			description: "The [AWS region](\(urls.aws_regions)) of the target service."
 			required:    true
@@ -193,7 +289,74 @@ components: _aws: {
 						account access.
 						"""
 				},
+				{
+					title: "Role chaining"
+					body: """
+						Roles that can only be assumed from another account are reached by listing the roles in
+						between in [`role_chain`](#auth.role_chain). Each role is assumed with the credentials of
+						the one before it, and [`assume_role`](#auth.assume_role) is assumed last. Every role can
+						set its own external ID and session tags.
+						"""
+				},
 			]
 		}
 	}
 }
+
+components: _aws_failover: {
+	configuration: failover: {
+		common:      false
+		description: "The regions, or endpoints, to fail over to when requests to the primary one keep failing with timeouts, throttling, or server errors. After `failback_secs`, the primary region is tried again."
+		required:    false
+		type: object: options: {
+			regions: {
+				description: "The regions, or endpoints, to fail over to, in order of preference. Each of them takes the `region`, `endpoint`, and `endpoint_variant` options."
+				required:    true
+				type: array: items: type: object: {
+					examples: [{region: "us-west-2"}]
+					options: {
+						region: {
+							description: "The [AWS region](\(urls.aws_regions)) to fail over to."
+							required:    false
+							type: string: {
+								default: null
+								examples: ["us-west-2"]
+							}
+						}
+						endpoint: {
+							description: "The custom endpoint to fail over to."
+							required:    false
+							type: string: {
+								default: null
+								examples: ["http://127.0.0.0:5000/path/to/service"]
+							}
+						}
+					}
+				}
+			}
+			threshold: {
+				common:      false
+				description: "The number of consecutive requests failing with a retriable error after which the next region is used."
+				required:    false
+				type: uint: {
+					default: 5
+					unit:    null
+				}
+			}
+			failback_secs: {
+				common:      false
+				description: "How long to wait after failing over before trying the primary region again."
+				required:    false
+				type: uint: {
+					default: 300
+					unit:    "seconds"
+				}
+			}
+		}
+	}
+
+	telemetry: metrics: {
+		aws_endpoint_requests_served_total: components.sources.internal_metrics.output.metrics.aws_endpoint_requests_served_total
+		aws_endpoint_switches_total:        components.sources.internal_metrics.output.metrics.aws_endpoint_switches_total
+	}
+}
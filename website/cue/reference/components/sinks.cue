@@ -4,6 +4,7 @@ components: sinks: [Name=string]: {
 	kind: "sink"
 
 	features: _
+	input:    _
 
 	configuration: {
 		if features.acknowledgements {
@@ -73,11 +74,28 @@ components: sinks: [Name=string]: {
 				Configures the sink specific buffer behavior.
 
 				More information about the individual buffer types, and buffer behavior, can be found in the [Buffering Model](\(urls.vector_buffering_model)) section.
+
+				Events whose `priority` metadata field is set to `"high"`, for example with `%priority = "high"` in a `remap` transform, are read out of memory buffers ahead of all other events. This keeps important events from waiting behind bulk data when a sink recovers from an outage. Disk buffers read out events in the order they were written, within each of their shards, so a sink with a disk buffer, including one that a memory buffer overflows to, is rejected when loading the configuration if a `remap` transform upstream of it sets `%priority`. Events that already carry a priority when they're received, for example from the `vector` source, are read out of disk buffers in order.
 				"""
 			required:    false
 			type: object: {
 				examples: []
 				options: {
+					max_age_secs: {
+						common:      false
+						description: """
+							The maximum age of buffered events. Events older than this are discarded instead of being sent.
+
+							Events are expired in batches, as they were written to disk, once the newest event of their batch is older than this, which is checked every second. Events may therefore be sent even though they are older than this.
+							"""
+						required:      false
+						relevant_when: "type = \"disk\""
+						type: uint: {
+							default: null
+							examples: [3600]
+							unit: "seconds"
+						}
+					}
 					max_events: {
 						common:        true
 						description:   "The maximum number of [events](\(urls.vector_data_model)) allowed in the buffer."
@@ -99,6 +117,20 @@ components: sinks: [Name=string]: {
 							unit: "bytes"
 						}
 					}
+					shards: {
+						common:      false
+						description: """
+							The number of shards to spread the buffer over. Each shard is an independent disk buffer with its own writer, which avoids contention on a single writer at very high event rates.
+
+							Events keep their order within a shard, but not across shards. The maximum size of the buffer is split evenly between the shards, each of which must be at least ~256 megabytes. The first shard holds the events buffered before sharding was enabled, and shards left over from a configuration with more shards, even a single one, are still read from until they're empty. The events left in them count towards the maximum size of the buffer, and the rest of it is split between the configured shards.
+							"""
+						required:      false
+						relevant_when: "type = \"disk\""
+						type: uint: {
+							default: 1
+							unit:    null
+						}
+					}
 					type: {
 						common:      true
 						description: "The type of buffer to use."
@@ -216,6 +248,9 @@ components: sinks: [Name=string]: {
 											if codec == "avro" {
 												avro: "Avro encoded event with a given schema."
 											}
+											if codec == "pretty" {
+												pretty: "Human-readable rendering of the event, with its timestamp, level, and message followed by its remaining fields. Not meant to be parsed."
+											}
 										}
 									}
 								}
@@ -244,6 +279,55 @@ components: sinks: [Name=string]: {
 											}
 										}
 									}
+									if codec == "logfmt" {
+										logfmt: {
+											description:   "Options for the `logfmt` codec."
+											required:      false
+											relevant_when: "codec = `logfmt`"
+											type: object: options: {
+												fields_order: {
+													description: "The keys that are encoded first, in the given order. All other keys follow in lexicographic order."
+													required:    false
+													type: array: {
+														default: []
+														items: type: string: examples: ["timestamp", "level", "message"]
+													}
+												}
+												flatten: {
+													description: "How nested fields are encoded."
+													required:    false
+													type: string: {
+														default: "dotted"
+														enum: {
+															dotted: "Every nested value is encoded as its own key, named by its path, such as `parent.child=value`."
+															json:   "Objects and arrays are encoded as the JSON value of their top-level key."
+														}
+													}
+												}
+											}
+										}
+									}
+									if codec == "pretty" {
+										pretty: {
+											description:   "Options for the `pretty` codec."
+											required:      false
+											relevant_when: "codec = `pretty`"
+											type: object: options: {
+												colors: {
+													description: "Whether to color the level and field names of log events with ANSI escape sequences."
+													required:    false
+													type: string: {
+														default: "auto"
+														enum: {
+															auto:   "Color the output if it is written to a terminal."
+															always: "Always color the output."
+															never:  "Never color the output."
+														}
+													}
+												}
+											}
+										}
+									}
 								}
 							}
 
@@ -352,6 +436,110 @@ components: sinks: [Name=string]: {
 			}
 		}
 
+		schedule: {
+			common: false
+			description: """
+				Restricts the delivery of events to windows of time. Outside of its
+				windows, the sink stops taking events from its buffer, so the events
+				wait in the buffer until the next window opens.
+				"""
+			required: false
+			type: object: {
+				examples: []
+				options: {
+					timezone: {
+						common:      false
+						description: "The name of the time zone the windows are in. By default, the [global `timezone` option](\(urls.vector_configuration)/global-options#timezone) is used. The time zone name may be any name in the [TZ database](\(urls.tz_time_zones)), or `local` to indicate system local time."
+						required:    false
+						type: string: {
+							default: null
+							examples: ["local", "America/New_York", "EST5EDT"]
+						}
+					}
+					windows: {
+						common:      true
+						description: "The windows during which events are delivered, as cron expressions with the five fields of a crontab entry: minute, hour, day of month, month, and day of week. Events are delivered during every minute matched by any of the expressions."
+						required:    true
+						type: array: items: type: string: {
+							examples: ["* 0-5 * * *", "* * * * sat,sun"]
+						}
+					}
+				}
+			}
+		}
+
+		if input.metrics != null {
+			metrics: {
+				common: false
+				description: """
+					Rewrites the names and tags of the metrics received by the sink, to follow
+					the conventions of the backend without a `remap` transform. Metrics are
+					renamed, then prefixed, and their tags are renamed, removed, and added, in
+					this order.
+					"""
+				required: false
+				type: object: {
+					examples: []
+					options: {
+						add_tags: {
+							common:      false
+							description: "The tags added to every metric, replacing any tag of the same name."
+							required:    false
+							type: object: {
+								examples: [{"env": "production"}]
+								options: {}
+							}
+						}
+						namespace: {
+							common:      false
+							description: "The namespace set on every metric, replacing their own."
+							required:    false
+							type: string: {
+								default: null
+								examples: ["app"]
+							}
+						}
+						prefix: {
+							common:      false
+							description: "A prefix added to the name of every metric, once renamed."
+							required:    false
+							type: string: {
+								default: null
+								examples: ["vector_"]
+							}
+						}
+						remove_tags: {
+							common:      false
+							description: "The names of the tags removed from every metric, once renamed."
+							required:    false
+							type: array: {
+								default: []
+								items: type: string: examples: ["pid"]
+							}
+						}
+						rename: {
+							common:      false
+							description: "The new names of metrics, by their current name."
+							required:    false
+							type: object: {
+								examples: [{"requests": "http_requests"}]
+								options: {}
+							}
+						}
+						tag_map: {
+							common:      false
+							description: "The new names of tags, by their current name. A renamed tag replaces any tag that already has its new name. A tag can't be both renamed and removed."
+							required:    false
+							type: object: {
+								examples: [{"host": "hostname"}]
+								options: {}
+							}
+						}
+					}
+				}
+			}
+		}
+
 		if features.send != _|_ {
 			if features.send.proxy != _|_ {
 				if features.send.proxy.enabled {
@@ -360,6 +548,21 @@ components: sinks: [Name=string]: {
 			}
 
 			if features.send.request.enabled {
+				rate_limit_pool: {
+					common: false
+					description: """
+						The name of a pool defined in the global
+						[`rate_limit_pools`](\(urls.vector_configuration_global)/#rate_limit_pools)
+						option. The sink shares the rate limit of the pool with the other sinks
+						referencing it, on top of its own rate limit.
+						"""
+					required: false
+					type: string: {
+						default: null
+						examples: ["datadog"]
+					}
+				}
+
 				request: {
 					common:      false
 					description: "Configures the sink request behavior."
@@ -622,6 +825,28 @@ components: sinks: [Name=string]: {
 			}
 		}
 
+		delivery_schedule: {
+			title: "Delivery schedule"
+			body: """
+				The `schedule` option restricts the delivery of events to windows of
+				time, such as restricting bulk archival to off-peak hours while
+				realtime sinks receive events continuously. Each window is a cron
+				expression, and events are delivered during every minute matched by
+				any of them:
+
+				```toml title="vector.toml"
+				[sinks.my-sink.schedule]
+				windows = ["* 0-5 * * *", "* * * * sat,sun"]
+				timezone = "America/New_York"
+				```
+
+				Outside of the windows, events wait in the buffer of the sink, so the
+				buffer must be large enough to hold the events received between
+				windows. Use a [disk buffer](#buffer) to keep them across restarts, as
+				stopping Vector outside of a window doesn't deliver them.
+				"""
+		}
+
 		if features.send != _|_ {
 			if features.send.request.enabled {
 				partitioning: _ | *{
@@ -734,5 +959,7 @@ components: sinks: [Name=string]: {
 		buffer_sent_events_total:             components.sources.internal_metrics.output.metrics.buffer_sent_events_total
 		buffer_sent_event_bytes_total:        components.sources.internal_metrics.output.metrics.buffer_sent_event_bytes_total
 		buffer_discarded_events_total:        components.sources.internal_metrics.output.metrics.buffer_discarded_events_total
+		buffer_expired_events_total:          components.sources.internal_metrics.output.metrics.buffer_expired_events_total
+		buffer_expired_bytes_total:           components.sources.internal_metrics.output.metrics.buffer_expired_bytes_total
 	}
 }
@@ -1,6 +1,6 @@
 package metadata
 
-components: sinks: aws_cloudwatch_logs: components._aws & {
+components: sinks: aws_cloudwatch_logs: components._aws & components._aws_failover & {
 	title: "AWS Cloudwatch Logs"
 
 	classes: {
@@ -73,6 +73,7 @@ components: sinks: aws_cloudwatch_logs: components._aws & {
 	}
 
 	configuration: {
+		adaptive_batching: configuration._sink_adaptive_batching
 		create_missing_group: {
 			common:      true
 			description: "Dynamically create a [log group](\(urls.aws_cloudwatch_logs_group_name)) if it does not already exist. This will ignore `create_missing_stream` directly after creating the group and will create the first stream."
@@ -137,7 +138,21 @@ components: sinks: aws_cloudwatch_logs: components._aws & {
 		},
 	]
 
+	how_it_works: adaptive_batching: {
+		title: "Adaptive batching"
+		body: """
+			CloudWatch Logs throttles the requests to each log stream, so that the same batch limits
+			rarely suit every log stream. With `adaptive_batching` set, Vector keeps an average of the
+			latency of the requests to each log stream. While it's above `target_latency_secs`, or
+			requests fail, the batches of the log stream are made smaller by `decrease_ratio`, down to
+			`min_ratio` of the `batch` limits. Once requests are fast again, batches grow back to the
+			`batch` limits, by 5% of them after each request.
+			"""
+	}
+
 	telemetry: metrics: {
+		adaptive_batch_averaged_latency:  components.sources.internal_metrics.output.metrics.adaptive_batch_averaged_latency
+		adaptive_batch_ratio:             components.sources.internal_metrics.output.metrics.adaptive_batch_ratio
 		component_sent_events_total:      components.sources.internal_metrics.output.metrics.component_sent_events_total
 		component_sent_event_bytes_total: components.sources.internal_metrics.output.metrics.component_sent_event_bytes_total
 		events_discarded_total:           components.sources.internal_metrics.output.metrics.events_discarded_total
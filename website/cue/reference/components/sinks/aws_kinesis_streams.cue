@@ -1,6 +1,6 @@
 package metadata
 
-components: sinks: aws_kinesis_streams: components._aws & {
+components: sinks: aws_kinesis_streams: components._aws & components._aws_failover & {
 	title: "AWS Kinesis Data Streams"
 
 	classes: {
@@ -1,6 +1,6 @@
 package metadata
 
-components: sinks: aws_s3: components._aws & {
+components: sinks: aws_s3: components._aws & components._aws_failover & {
 	title: "AWS S3"
 
 	classes: {
@@ -33,7 +33,7 @@ components: sinks: aws_s3: components._aws & {
 				codec: {
 					enabled: true
 					framing: true
-					enum: ["json", "text"]
+					enum: ["json", "logfmt", "text"]
 				}
 			}
 			proxy: enabled: true
@@ -73,6 +73,13 @@ components: sinks: aws_s3: components._aws & {
 	}
 
 	configuration: {
+		accelerate: {
+			common:      false
+			description: "Whether or not to upload objects through [S3 Transfer Acceleration](\(urls.aws_s3_transfer_acceleration)). Transfer acceleration must be enabled on the bucket, and the bucket name must not contain dots. Can't be combined with a custom `endpoint` or the `fips` endpoint variant."
+			required:    false
+			type: bool: default: false
+		}
+		adaptive_batching: configuration._sink_adaptive_batching
 		acl: {
 			category:    "ACL"
 			common:      false
@@ -379,6 +386,8 @@ components: sinks: aws_s3: components._aws & {
 	]
 
 	telemetry: metrics: {
+		adaptive_batch_averaged_latency:  components.sources.internal_metrics.output.metrics.adaptive_batch_averaged_latency
+		adaptive_batch_ratio:             components.sources.internal_metrics.output.metrics.adaptive_batch_ratio
 		component_sent_bytes_total:       components.sources.internal_metrics.output.metrics.component_sent_bytes_total
 		component_sent_events_total:      components.sources.internal_metrics.output.metrics.component_sent_events_total
 		component_sent_event_bytes_total: components.sources.internal_metrics.output.metrics.component_sent_event_bytes_total
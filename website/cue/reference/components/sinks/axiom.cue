@@ -23,7 +23,12 @@ components: sinks: axiom: {
 				max_bytes:    1_048_576
 				timeout_secs: 1.0
 			}
-			compression: enabled: false
+			compression: {
+				enabled: true
+				default: "gzip"
+				algorithms: ["none", "gzip"]
+				levels: ["none", "fast", "default", "best", 0, 1, 2, 3, 4, 5, 6, 7, 8, 9]
+			}
 			encoding: {
 				enabled: true
 				codec: enabled: false
@@ -129,6 +134,16 @@ components: sinks: axiom: {
 				2. Once registered, create a new dataset and create an API token for it
 				"""
 		}
+
+		ingest_api: {
+			title: "Ingest API"
+			body:  """
+				Events are sent as newline delimited JSON to the [ingest API](\(urls.axiom_ingest_api)) of
+				the dataset, authenticated with the API token. The timestamp field of the
+				[global log schema](\(urls.vector_log_schema)) is passed as the timestamp field of the
+				dataset, so that Axiom uses the timestamps of the events as their time.
+				"""
+		}
 	}
 
 	telemetry: metrics: {
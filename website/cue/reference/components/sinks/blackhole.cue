@@ -30,6 +30,100 @@ components: sinks: blackhole: {
 	}
 
 	configuration: {
+		backpressure: {
+			common:      false
+			description: "Periods during which the sink stops consuming events altogether, to test how the topology buffers events when a sink stalls."
+			required:    false
+			type: object: options: {
+				interval_secs: {
+					description: "The number of seconds between the start of two periods of backpressure."
+					required:    true
+					type: uint: {
+						examples: [60]
+						unit: "seconds"
+					}
+				}
+				duration_secs: {
+					description: "The number of seconds each period of backpressure lasts. Must be shorter than `interval_secs`."
+					required:    true
+					type: uint: {
+						examples: [10]
+						unit: "seconds"
+					}
+				}
+			}
+		}
+		error_rate: {
+			common:      false
+			description: "The fraction of batches of events, between `0.0` and `1.0`, rejected instead of being acknowledged. Sources waiting on the acknowledgement of rejected events are told that their delivery failed."
+			required:    false
+			type: float: {
+				default: 0.0
+				examples: [0.01]
+			}
+		}
+		latency: {
+			common:      false
+			description: "Artificial latency applied to each batch of events before it is acknowledged. Batches are handled one at a time, so the latency also limits the rate at which events are consumed."
+			required:    false
+			type: object: options: {
+				distribution: {
+					description: "The distribution latencies are sampled from."
+					required:    true
+					type: string: {
+						enum: {
+							constant:    "The same latency for every batch."
+							uniform:     "A latency picked uniformly between `min_ms` and `max_ms`."
+							normal:      "A latency following a normal distribution, with negative latencies rounded up to zero."
+							exponential: "A latency following an exponential distribution, where most batches are handled quickly but a few take much longer."
+						}
+					}
+				}
+				ms: {
+					description:   "The latency, in milliseconds."
+					relevant_when: "distribution = \"constant\""
+					required:      true
+					type: uint: {
+						examples: [100]
+						unit: "milliseconds"
+					}
+				}
+				min_ms: {
+					description:   "The lowest latency, in milliseconds."
+					relevant_when: "distribution = \"uniform\""
+					required:      true
+					type: uint: {
+						examples: [50]
+						unit: "milliseconds"
+					}
+				}
+				max_ms: {
+					description:   "The highest latency, in milliseconds."
+					relevant_when: "distribution = \"uniform\""
+					required:      true
+					type: uint: {
+						examples: [500]
+						unit: "milliseconds"
+					}
+				}
+				mean_ms: {
+					description:   "The mean latency, in milliseconds."
+					relevant_when: "distribution = \"normal\" or distribution = \"exponential\""
+					required:      true
+					type: float: {
+						examples: [200.0]
+					}
+				}
+				std_dev_ms: {
+					description:   "The standard deviation of the latency, in milliseconds."
+					relevant_when: "distribution = \"normal\""
+					required:      true
+					type: float: {
+						examples: [50.0]
+					}
+				}
+			}
+		}
 		print_interval_secs: {
 			common:      false
 			description: "The number of seconds between reporting a summary of activity. Set to `0` to disable reporting."
@@ -66,6 +160,7 @@ components: sinks: blackhole: {
 	}
 
 	telemetry: metrics: {
+		component_errors_total: components.sources.internal_metrics.output.metrics.component_errors_total
 		processed_bytes_total:  components.sources.internal_metrics.output.metrics.processed_bytes_total
 		processed_events_total: components.sources.internal_metrics.output.metrics.processed_events_total
 	}
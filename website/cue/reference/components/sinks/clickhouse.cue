@@ -97,7 +97,45 @@ components: sinks: clickhouse: {
 			description: "The table that data will be inserted into."
 			required:    true
 			type: string: {
-				examples: ["mytable"]
+				examples: ["mytable", "logs_{{ application_id }}"]
+				syntax: "template"
+			}
+		}
+		max_tables: {
+			common:      false
+			description: """
+				The maximum number of distinct tables data is inserted into. Each table is batched separately, so
+				this bounds the number of batches a `table` template can open. Tables without events for longer than
+				the batch timeout, whose batches are flushed, make room for new tables. Events rendering a table name
+				beyond the limit are dropped.
+				"""
+			required:    false
+			type: uint: {
+				default: 100
+				unit:    null
+			}
+		}
+		async_insert: {
+			common:      false
+			description: "Settings for the [asynchronous inserts](\(urls.clickhouse_async_inserts)) of Clickhouse."
+			required:    false
+			type: object: options: {
+				enabled: {
+					common:      true
+					description: "Sets `async_insert`, letting Clickhouse buffer the inserted data and write it to the table in larger batches."
+					required:    false
+					type: bool: default: false
+				}
+				wait: {
+					common:      false
+					description: """
+						Sets `wait_for_async_insert`, making Clickhouse respond once the buffered data is written to the
+						table. If disabled, events are acknowledged as soon as Clickhouse buffered them, and errors writing
+						them to the table are not reported back.
+						"""
+					required:    false
+					type: bool: default: true
+				}
 			}
 		}
 		skip_unknown_fields: {
@@ -108,6 +146,18 @@ components: sinks: clickhouse: {
 		}
 	}
 
+	how_it_works: {
+		schema_mismatches: {
+			title: "Schema mismatches"
+			body: """
+				Requests that Clickhouse rejects because the data doesn't match the schema of the table, such as
+				fields it can't parse or a table that doesn't exist, are not retried. The events of the request
+				are dropped, and the error returned by Clickhouse is logged, naming the offending field.
+				Enable `skip_unknown_fields` to have Clickhouse discard fields that are not present in the table.
+				"""
+		}
+	}
+
 	input: {
 		logs:    true
 		metrics: null
@@ -22,7 +22,7 @@ components: sinks: console: {
 				codec: {
 					enabled: true
 					framing: true
-					enum: ["json", "text"]
+					enum: ["json", "logfmt", "pretty", "text"]
 				}
 			}
 			request: enabled: false
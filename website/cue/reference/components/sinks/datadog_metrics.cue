@@ -80,13 +80,27 @@ components: sinks: datadog_metrics: {
 			counter:      true
 			distribution: true
 			gauge:        true
-			histogram:    false
+			histogram:    true
 			set:          false
 			summary:      false
 		}
 		traces: false
 	}
 
+	how_it_works: {
+		sketches: {
+			title: "Distributions and histograms"
+			body: """
+				Distributions and aggregated histograms are sent to Datadog as
+				[DDSketches](https://www.datadoghq.com/blog/engineering/computing-accurate-percentiles-with-ddsketch/)
+				rather than being broken down into gauges, so percentiles can be computed accurately in Datadog.
+
+				Distributions keep every sample, while the observations of a histogram are spread
+				across each of its buckets. The count and sum of a histogram are kept exactly.
+				"""
+		}
+	}
+
 	telemetry: metrics: {
 		component_sent_events_total:      components.sources.internal_metrics.output.metrics.component_sent_events_total
 		component_sent_event_bytes_total: components.sources.internal_metrics.output.metrics.component_sent_event_bytes_total
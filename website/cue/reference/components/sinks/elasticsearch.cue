@@ -216,6 +216,20 @@ components: sinks: elasticsearch: {
 				}
 			}
 		}
+		dead_letter_index: {
+			common: false
+			description: """
+				The name of the index to send documents rejected by Elasticsearch to. Documents that
+				Elasticsearch rejects for reasons that retrying won't fix, such as mapping conflicts, are
+				sent to this index along with the reason they were rejected, instead of being dropped.
+				See [Partial failures](#partial-failures) for more info.
+				"""
+			required: false
+			type: string: {
+				default: null
+				examples: ["vector-dead-letters"]
+			}
+		}
 		distribution: {
 			common:      false
 			description: "Options for distributing events to multiple endpoints."
@@ -400,6 +414,13 @@ components: sinks: elasticsearch: {
 				due to Elasticsearch index mapping errors, where data keys aren't consistently
 				typed. To change this behavior, refer to the Elasticsearch [`ignore_malformed`
 				setting](\(urls.elasticsearch_ignore_malformed)).
+
+				If `dead_letter_index` is set, the documents Elasticsearch rejects with a client
+				error other than `429 Too Many Requests` are sent to that index with the `create`
+				action. Each of them is stored as a string in the `document` field, next to the
+				`index` it was meant for and the `status`, `error.type`, and `error.reason` returned
+				by Elasticsearch. If all rejected documents could be sent to the dead letter index,
+				the events are considered delivered.
 				"""
 		}
 
@@ -410,6 +431,8 @@ components: sinks: elasticsearch: {
 		component_sent_bytes_total:       components.sources.internal_metrics.output.metrics.component_sent_bytes_total
 		component_sent_events_total:      components.sources.internal_metrics.output.metrics.component_sent_events_total
 		component_sent_event_bytes_total: components.sources.internal_metrics.output.metrics.component_sent_event_bytes_total
+		component_errors_total:           components.sources.internal_metrics.output.metrics.component_errors_total
+		dead_letter_events_total:         components.sources.internal_metrics.output.metrics.dead_letter_events_total
 		events_discarded_total:           components.sources.internal_metrics.output.metrics.events_discarded_total
 		events_out_total:                 components.sources.internal_metrics.output.metrics.events_out_total
 		processing_errors_total:          components.sources.internal_metrics.output.metrics.processing_errors_total
@@ -0,0 +1,224 @@
+package metadata
+
+components: sinks: gcp_chronicle: {
+	title: "GCP Chronicle"
+
+	classes: {
+		commonly_used: true
+		delivery:      "at_least_once"
+		development:   "alpha"
+		egress_method: "batch"
+		service_providers: ["GCP"]
+		stateful: false
+	}
+
+	features: {
+		acknowledgements: true
+		healthcheck: enabled: true
+		send: {
+			batch: {
+				enabled:      true
+				common:       false
+				max_bytes:    10_000_000
+				timeout_secs: 300.0
+			}
+			compression: enabled: false
+			encoding: {
+				enabled: true
+				codec: {
+					enabled: true
+					framing: true
+					enum: ["json", "text"]
+				}
+			}
+			proxy: enabled: true
+			request: {
+				enabled:        true
+				rate_limit_num: 1000
+				headers:        false
+			}
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        true
+				enabled_by_scheme:      true
+			}
+			to: {
+				service: services.gcp_chronicle
+
+				interface: {
+					socket: {
+						api: {
+							title: "GCP XML Interface"
+							url:   urls.gcp_xml_interface
+						}
+						direction: "outgoing"
+						protocols: ["http"]
+						ssl: "required"
+					}
+				}
+			}
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: [
+			"""
+				This component is in alpha, and can only be used with the `alpha_components` feature flag
+				enabled.
+				""",
+		]
+	}
+
+	configuration: {
+		api_key: configuration._gcp_api_key
+		credentials_path: {
+			category:    "Auth"
+			common:      true
+			description: "The filename for a Google Cloud service account credentials JSON file used to authenticate access to the Cloud Storage API. If this is unset, Vector checks the `GOOGLE_APPLICATION_CREDENTIALS` environment variable for a filename.\n\nIf no filename is named, Vector will attempt to fetch an instance service account for the compute instance the program is running on. If Vector is not running on a GCE instance, you must define a credentials file as above."
+			required:    false
+			type: string: {
+				default: null
+				examples: ["/path/to/credentials.json"]
+			}
+		}
+		endpoint: {
+			common:        false
+			description:   "The endpoint to send data to."
+			relevant_when: "region is not set"
+			required:      false
+			type: string: {
+				default: null
+				examples: ["127.0.0.1:8080", "example.com:12345"]
+			}
+		}
+		region: {
+			common:        false
+			description:   "The region to send data to."
+			required:      false
+			relevant_when: "endpoint is not set"
+			type: string: {
+				default: null
+				enum: {
+					us:                        "United States"
+					eu:                        "Europe"
+					asia:                      "Asia"
+					"europe-west2":            "London"
+					"europe-west3":            "Frankfurt"
+					"europe-west6":            "Zurich"
+					"europe-west9":            "Paris"
+					"australia-southeast1":    "Sydney"
+					"asia-northeast1":         "Tokyo"
+					"asia-south1":             "Mumbai"
+					"me-west1":                "Tel Aviv"
+					"me-central2":             "Dammam"
+					"northamerica-northeast2": "Toronto"
+				}
+			}
+		}
+		api: {
+			common:      true
+			description: "The Chronicle ingestion API to send events to."
+			required:    false
+			type: string: {
+				default: "unstructured"
+				enum: {
+					unstructured: "Unstructured log entries, parsed by Chronicle according to their log type."
+					udm:          "Events already in the [Unified Data Model (UDM)](\(urls.gcp_chronicle_udm)), sent as is."
+				}
+			}
+		}
+		customer_id: {
+			description: """
+				The Unique identifier (UUID) corresponding to the Chronicle instance. Events are routed to
+				the Chronicle instance of the rendered identifier, so that a single sink can send events
+				to several instances.
+				"""
+			required:    true
+			type: string: {
+				examples: ["c8c65bfa-5f2c-42d4-9189-64bb7b939f2c", "{{ customer_id }}"]
+				syntax: "template"
+			}
+		}
+		labels: {
+			common:      false
+			description: """
+				Ingestion labels attached to the events, as label keys mapped to their values.
+				Unstructured log entries are labelled per request, while UDM events get the labels
+				appended to their `metadata.ingestion_labels`.
+				"""
+			required:    false
+			type: object: {
+				examples: [{"environment": "production", "team": "{{ team }}"}]
+				options: {
+					"*": {
+						description: "The value of the label."
+						required:    true
+						type: string: {
+							examples: ["production", "{{ team }}"]
+							syntax: "template"
+						}
+					}
+				}
+			}
+		}
+		log_type: {
+			description:   "Identifies the log entry. This must be one of the supported log types, otherwise Chronicle will reject the entry with an error."
+			relevant_when: "api = \"unstructured\""
+			required:      true
+			type: string: {
+				examples: ["WINDOWS_DNS", "{{ log_type }}"]
+				syntax: "template"
+			}
+		}
+		namespace: {
+			common:        false
+			description:   "The namespace of the log entries."
+			relevant_when: "api = \"unstructured\""
+			required:      false
+			type: string: {
+				default: null
+				examples: ["production", "{{ namespace }}"]
+				syntax: "template"
+			}
+		}
+	}
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	how_it_works: {
+		routing: {
+			title: "Routing"
+			body: """
+				Events are batched by their rendered `customer_id`, `log_type`, `namespace`, and `labels`,
+				and each batch is sent in a single request, so that events can be routed to several
+				Chronicle instances, with different log types and labels. Events for which any of these
+				templates fail to render are dropped.
+				"""
+		}
+
+		udm: {
+			title: "UDM events"
+			body: """
+				With the `udm` API, each log event is sent as a [UDM event](\(urls.gcp_chronicle_udm)), so it
+				must already have the UDM fields, such as `metadata.event_type`, for example by mapping
+				them with a `remap` transform. The `encoding` option is then only used to select the
+				fields of the events, and its codec is ignored.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_sent_events_total:      components.sources.internal_metrics.output.metrics.component_sent_events_total
+		component_sent_event_bytes_total: components.sources.internal_metrics.output.metrics.component_sent_event_bytes_total
+		events_discarded_total:           components.sources.internal_metrics.output.metrics.events_discarded_total
+		processing_errors_total:          components.sources.internal_metrics.output.metrics.processing_errors_total
+	}
+}
@@ -98,9 +98,19 @@ components: sinks: gcp_chronicle_unstructured: {
 			type: string: {
 				default: null
 				enum: {
-					us:   "United States"
-					eu:   "Europe"
-					asia: "Asia"
+					us:                        "United States"
+					eu:                        "Europe"
+					asia:                      "Asia"
+					"europe-west2":            "London"
+					"europe-west3":            "Frankfurt"
+					"europe-west6":            "Zurich"
+					"europe-west9":            "Paris"
+					"australia-southeast1":    "Sydney"
+					"asia-northeast1":         "Tokyo"
+					"asia-south1":             "Mumbai"
+					"me-west1":                "Tel Aviv"
+					"me-central2":             "Dammam"
+					"northamerica-northeast2": "Toronto"
 				}
 			}
 		}
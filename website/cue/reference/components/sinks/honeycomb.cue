@@ -69,7 +69,39 @@ components: sinks: honeycomb: {
 			description: "The dataset that Vector will send logs to."
 			required:    true
 			type: string: {
-				examples: ["my-honeycomb-dataset"]
+				examples: ["my-honeycomb-dataset", "{{ service }}"]
+				syntax: "template"
+			}
+		}
+		markers: {
+			common:      false
+			description: "Creates [markers](\(urls.honeycomb_markers)) from events, such as deploy markers. Events with the `type_field` are sent to the Markers API of their dataset instead of being sent as events."
+			required:    false
+			type: object: options: {
+				type_field: {
+					common:      true
+					description: "The log field identifying marker events, holding the type of the marker, such as `deploy`."
+					required:    false
+					type: string: {
+						default: "marker_type"
+					}
+				}
+				message_field: {
+					common:      false
+					description: "The log field holding the message of the marker."
+					required:    false
+					type: string: {
+						default: "message"
+					}
+				}
+				url_field: {
+					common:      false
+					description: "The log field holding the URL of the marker."
+					required:    false
+					type: string: {
+						default: "marker_url"
+					}
+				}
 			}
 		}
 	}
@@ -81,6 +113,14 @@ components: sinks: honeycomb: {
 	}
 
 	how_it_works: {
+		sample_rate: {
+			title: "Sample Rate"
+			body:  """
+				Events sampled before reaching Vector can carry their sample rate in the `samplerate` metadata
+				field, for example by setting `%samplerate = 10` in a `remap` transform. The sample rate is sent
+				along with the event, so that Honeycomb weighs it accordingly.
+				"""
+		}
 		setup: {
 			title: "Setup"
 			body:  """
@@ -33,7 +33,7 @@ components: sinks: http: {
 				codec: {
 					enabled: true
 					framing: true
-					enum: ["json", "text"]
+					enum: ["json", "logfmt", "text"]
 				}
 			}
 			proxy: enabled: true
@@ -122,6 +122,8 @@ components: sinks: http: {
 		events_out_total:                 components.sources.internal_metrics.output.metrics.events_out_total
 		events_discarded_total:           components.sources.internal_metrics.output.metrics.events_discarded_total
 		http_bad_requests_total:          components.sources.internal_metrics.output.metrics.http_bad_requests_total
+		http_client_responses_total:      components.sources.internal_metrics.output.metrics.http_client_responses_total
+		http_client_rtt_seconds:          components.sources.internal_metrics.output.metrics.http_client_rtt_seconds
 		processed_bytes_total:            components.sources.internal_metrics.output.metrics.processed_bytes_total
 		processed_events_total:           components.sources.internal_metrics.output.metrics.processed_events_total
 	}
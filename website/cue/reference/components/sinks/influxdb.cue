@@ -58,14 +58,75 @@ components: sinks: _influxdb: {
 				examples: ["vector-database", "iot-store"]
 			}
 		}
+		field_type_conflicts: {
+			common:      false
+			description: "Configures the handling of fields whose type conflicts with the type of the field in the existing series, which InfluxDB rejects."
+			groups: ["v1", "v2", "v3"]
+			required: false
+			type: object: options: {
+				policy: {
+					common:      true
+					description: "What to do with a field whose type conflicts with the type of the field in the existing series."
+					required:    false
+					type: string: {
+						default: "drop_point"
+						enum: {
+							cast:       "Cast the value to the type of the existing field. Values that can't be cast are dropped from the point."
+							drop_field: "Drop the field from the point."
+							drop_point: "Drop the point, as InfluxDB would when rejecting it."
+							reroute:    "Write the point to the conflict `measurement` instead, with the conflicting fields as strings."
+						}
+					}
+				}
+				max_fields: {
+					common:      false
+					description: "The maximum number of fields whose type is tracked, across all measurements. Once reached, the fields tracked the longest are forgotten first, and their type is tracked again from the next point written with them."
+					required:    false
+					type: uint: {
+						default: 10000
+						unit:    null
+					}
+				}
+				measurement: {
+					common:      false
+					description: "The measurement to write points with conflicting fields to, when using the `reroute` policy. Defaults to the measurement of the sink, suffixed with `_conflicts`. For metrics, it defaults to the `default_namespace`, or `vector`, suffixed with `_conflicts`, and the name of the metric is kept in the `measurement` tag."
+					required:    false
+					type: string: {
+						default: null
+						examples: ["vector-logs-conflicts"]
+					}
+				}
+			}
+		}
 		endpoint: {
 			description: "The endpoint to send data to."
-			groups: ["v1", "v2"]
+			groups: ["v1", "v2", "v3"]
 			required: true
 			type: string: {
 				examples: ["http://localhost:8086/", "https://us-west-2-1.aws.cloud1.influxdata.com", "https://us-west-2-1.aws.cloud2.influxdata.com"]
 			}
 		}
+		influxdb3: {
+			description: "Settings for writes into InfluxDB 3, through its [write API](\(urls.influxdb_http_api_v3)). Set instead of the settings for InfluxDB 1 or 2."
+			groups: ["v3"]
+			required: true
+			type: object: options: {
+				database: {
+					description: "The destination database for writes into InfluxDB 3."
+					required:    true
+					type: string: {
+						examples: ["vector-database"]
+					}
+				}
+				token: {
+					description: "[Authentication token](\(urls.influxdb3_authentication_token)) for InfluxDB 3."
+					required:    true
+					type: string: {
+						examples: ["${INFLUXDB_TOKEN}"]
+					}
+				}
+			}
+		}
 		org: {
 			category:    "Auth"
 			description: "Specifies the destination organization for writes into InfluxDB 2."
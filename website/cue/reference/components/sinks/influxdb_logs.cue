@@ -45,7 +45,7 @@ components: sinks: influxdb_logs: {
 	configuration: sinks._influxdb.configuration & {
 		measurement: {
 			description: "The influxdb measurement name that will be written to."
-			groups: ["v1", "v2"]
+			groups: ["v1", "v2", "v3"]
 			required: true
 			type: string: {
 				examples: ["vector-logs"]
@@ -55,7 +55,7 @@ components: sinks: influxdb_logs: {
 			description: """
 				`{namespace}.vector` will be encoded as the destination infuxdb measurement.
 				"""
-			groups: ["v1", "v2"]
+			groups: ["v1", "v2", "v3"]
 			required: false
 			common:   true
 			warnings: ["Deprecated, please use `measurement` instead."]
@@ -68,7 +68,7 @@ components: sinks: influxdb_logs: {
 			required:    false
 			common:      false
 			description: "The set of fields that will be attached to each LineProtocol as tags. Note: If the set of tag values has high cardinality this also increase cardinality in InfluxDB."
-			groups: ["v1", "v2"]
+			groups: ["v1", "v2", "v3"]
 			type: array: {
 				default: null
 				items: type: string: {
@@ -86,6 +86,16 @@ components: sinks: influxdb_logs: {
 	}
 
 	how_it_works: {
+		field_type_conflicts: {
+			title: "Field Type Conflicts"
+			body:  """
+				InfluxDB rejects points with a field whose type differs from the type of the field in the existing
+				series. The sink records the type each field was first written with, as well as the types InfluxDB
+				reports when rejecting a point, and applies the `field_type_conflicts.policy` to the fields of later
+				points conflicting with them. Conflicts are logged and counted in the `field_type_conflicts_total`
+				metric, tagged with the policy applied.
+				"""
+		}
 		mapping: {
 			title: "Mapping Log Fields"
 			body:  """
@@ -137,5 +147,6 @@ components: sinks: influxdb_logs: {
 		component_sent_events_total:      components.sources.internal_metrics.output.metrics.component_sent_events_total
 		component_sent_event_bytes_total: components.sources.internal_metrics.output.metrics.component_sent_event_bytes_total
 		events_out_total:                 components.sources.internal_metrics.output.metrics.events_out_total
+		field_type_conflicts_total:       components.sources.internal_metrics.output.metrics.field_type_conflicts_total
 	}
 }
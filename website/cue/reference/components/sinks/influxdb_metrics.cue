@@ -59,7 +59,7 @@ components: sinks: influxdb_metrics: {
 			required:    false
 			common:      false
 			description: "A map of additional key-value pairs that will be attached to each LineProtocol as tags."
-			groups: ["v1", "v2"]
+			groups: ["v1", "v2", "v3"]
 			type: object: {
 				examples: [{region: "us-west-1"}]
 			}
@@ -211,8 +211,19 @@ components: sinks: influxdb_metrics: {
 		},
 	]
 
+	how_it_works: field_type_conflicts: {
+		title: "Field Type Conflicts"
+		body:  """
+			InfluxDB rejects points with a field whose type differs from the type of the field in the existing
+			series, such as a histogram and a distribution sharing a name. The sink records the type each field
+			was first written with, as well as the types InfluxDB reports when rejecting a point, and applies
+			the `field_type_conflicts.policy` to the fields of later points conflicting with them.
+			"""
+	}
+
 	telemetry: metrics: {
 		component_sent_events_total:      components.sources.internal_metrics.output.metrics.component_sent_events_total
 		component_sent_event_bytes_total: components.sources.internal_metrics.output.metrics.component_sent_event_bytes_total
+		field_type_conflicts_total:       components.sources.internal_metrics.output.metrics.field_type_conflicts_total
 	}
 }
@@ -0,0 +1,140 @@
+package metadata
+
+components: sinks: journald: {
+	title: "Journald"
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		development:   "alpha"
+		egress_method: "stream"
+		service_providers: []
+		stateful: false
+	}
+
+	features: {
+		acknowledgements: true
+		healthcheck: enabled: true
+		send: {
+			compression: enabled: false
+			encoding: {
+				enabled: true
+				codec: enabled: false
+			}
+			request: enabled: false
+			tls: enabled:     false
+			to: {
+				service: services.journald
+
+				interface: {
+					socket: {
+						direction: "outgoing"
+						protocols: ["unix"]
+						ssl: "disabled"
+					}
+				}
+			}
+		}
+	}
+
+	support: {
+		targets: {
+			"x86_64-apple-darwin":   false
+			"x86_64-pc-windows-msv": false
+		}
+
+		requirements: []
+		warnings: [
+			"""
+				Each event is sent to the journal as a single datagram. Events larger than the maximum
+				datagram size of the journal socket are rejected and dropped.
+				""",
+		]
+		notices: [
+			"""
+				This component is in alpha, and can only be used with the `alpha_components` feature flag
+				enabled.
+				""",
+		]
+	}
+
+	configuration: {
+		default_priority: {
+			common:      false
+			description: "The priority of the events whose priority is missing or unknown, from `0` (`emerg`) to `7` (`debug`)."
+			required:    false
+			type: uint: {
+				default: 6
+				unit:    null
+			}
+		}
+		priority_key: {
+			common:      true
+			description: """
+				The field holding the priority of events. Priorities are either syslog severity numbers, from
+				`0` (`emerg`) to `7` (`debug`), or severity names, such as `err`, `warning`, or `info`.
+				"""
+			required:    false
+			type: string: {
+				default: "severity"
+			}
+		}
+		socket_path: {
+			common:      false
+			description: "The path of the socket the journal receives entries on."
+			required:    false
+			type: string: {
+				default: "/run/systemd/journal/socket"
+			}
+		}
+		syslog_identifier: {
+			common:      false
+			description: "The syslog identifier of the events without a `SYSLOG_IDENTIFIER` field."
+			required:    false
+			type: string: {
+				default: "vector"
+			}
+		}
+	}
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	how_it_works: {
+		field_names: {
+			title: "Field names"
+			body: """
+				Events are written to the journal using its [native protocol](\(urls.journald_native_protocol)),
+				with one journal field for each field of the event. Journal field names may only contain
+				uppercase letters, digits, and underscores, and must start with a letter, so field names are
+				uppercased, their other characters are replaced with underscores, and their leading characters
+				which aren't letters are removed. For example, the `user.name` field is written as
+				`USER_NAME`, and the `_pid` field as `PID`, so that events can't forge the trusted fields the
+				journal adds itself.
+
+				The message of events is written as the `MESSAGE` field, regardless of the
+				[global `message_key` option](\(urls.vector_configuration)/global-options#log_schema.message_key).
+				"""
+		}
+
+		priority: {
+			title: "Priority"
+			body: """
+				The `PRIORITY` field of entries is mapped from the `priority_key` field of events, which holds
+				either a syslog severity number or a severity name: `emerg`, `alert`, `crit`, `err`,
+				`warning`, `notice`, `info`, or `debug`. Common variations, such as `error`, `warn`, or
+				`critical`, are also recognized. Events whose priority is missing or unknown are written
+				with the `default_priority`.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_sent_bytes_total:  components.sources.internal_metrics.output.metrics.component_sent_bytes_total
+		component_sent_events_total: components.sources.internal_metrics.output.metrics.component_sent_events_total
+		component_errors_total:      components.sources.internal_metrics.output.metrics.component_errors_total
+	}
+}
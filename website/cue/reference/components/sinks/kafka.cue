@@ -72,6 +72,32 @@ components: sinks: kafka: {
 				unit: null
 			}
 		}
+		partition_field: {
+			common:      false
+			description: "The log field name or tags key to use for the partition of each message. The field must hold the number of the partition, as an integer or a string. The partition of messages whose event doesn't have the field, or whose field isn't a partition number, is selected by the `partitioner`."
+			required:    false
+			type: string: {
+				default: null
+				examples: ["partition"]
+			}
+		}
+		partitioner: {
+			common:      false
+			description: "The partitioner selecting the partition of each message from its key. Sets the `partitioner` option of `librdkafka`."
+			required:    false
+			type: string: {
+				default: "consistent_random"
+				enum: {
+					consistent:        "A CRC32 hash of the key, with the messages with an empty or no key all sent to the same partition."
+					consistent_random: "A CRC32 hash of the key, with the messages with an empty or no key sent to random partitions."
+					random:            "A random partition for each message."
+					murmur2:           "A murmur2 hash of the key, consistent with Java clients, with the messages without a key all sent to the same partition."
+					murmur2_random:    "A murmur2 hash of the key, consistent with Java clients, with the messages without a key sent to random partitions, as the default partitioner of Java clients does."
+					fnv1a:             "A FNV-1a hash of the key, with the messages without a key all sent to the same partition."
+					fnv1a_random:      "A FNV-1a hash of the key, with the messages without a key sent to random partitions."
+				}
+			}
+		}
 		sasl: {
 			common:      false
 			description: "Options for SASL/SCRAM authentication support."
@@ -116,6 +142,15 @@ components: sinks: kafka: {
 			}
 		}
 		socket_timeout_ms: components._kafka.configuration.socket_timeout_ms
+		sticky_partitioning_linger_ms: {
+			common:      false
+			description: "How long the messages without a key are sent to the same partition, in milliseconds, before another partition is selected for them. Sending consecutive messages without a key to the same partition batches them together, rather than scattering them across all partitions. `0` disables sticky partitioning. By default, twice `batch.timeout_secs`. Sets the `sticky.partitioning.linger.ms` option of `librdkafka`."
+			required:    false
+			type: uint: {
+				default: null
+				unit:    "milliseconds"
+			}
+		}
 		topic: {
 			description: "The Kafka topic name to write events to."
 			required:    true
@@ -0,0 +1,149 @@
+package metadata
+
+components: sinks: logstash: {
+	title: "Logstash"
+
+	description: """
+		Sends log events to Logstash over the Lumberjack v2 protocol, as Elastic Agent and
+		Beats do, so that they can be received by the Logstash `beats` input.
+		"""
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		development:   "alpha"
+		egress_method: "batch"
+		service_providers: []
+		stateful: false
+	}
+
+	features: {
+		acknowledgements: true
+		healthcheck: enabled: true
+		send: {
+			batch: {
+				enabled:      true
+				common:       false
+				max_events:   2048
+				timeout_secs: 1.0
+			}
+			compression: enabled: false
+			encoding: {
+				enabled: true
+				codec: enabled: false
+			}
+			send_buffer_bytes: enabled: true
+			keepalive: enabled:         true
+			request: enabled:           false
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        false
+				enabled_by_scheme:      false
+			}
+			to: {
+				service: services.logstash
+
+				interface: {
+					socket: {
+						api: {
+							title: "Lumberjack v2"
+							url:   urls.logstash_beats_protocol
+						}
+						direction: "outgoing"
+						protocols: ["tcp"]
+						ssl: "optional"
+					}
+				}
+			}
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: [
+			"""
+				This component is in alpha, and can only be used with the `alpha_components` feature flag
+				enabled.
+				""",
+		]
+	}
+
+	configuration: {
+		address: {
+			description: "The address of the Logstash `beats` input to connect to. The address _must_ include a port."
+			required:    true
+			type: string: {
+				examples: ["127.0.0.1:5044", "logstash.example.com:5044"]
+			}
+		}
+		compression_level: {
+			description: "The zlib compression level of the windows of events, from `0` (no compression) to `9`."
+			common:      true
+			required:    false
+			type: uint: {
+				default: 3
+				unit:    null
+			}
+		}
+		timeout_secs: {
+			description: """
+				The number of seconds to wait for Logstash to acknowledge a window of events. If
+				Logstash doesn't acknowledge the window in time, the connection is closed and the
+				window is sent again.
+				"""
+			common:      false
+			required:    false
+			type: uint: {
+				default: 30
+				unit:    "seconds"
+			}
+		}
+	}
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	how_it_works: {
+		windows: {
+			title: "Windows and acknowledgements"
+			body: """
+				Events are sent in windows of up to `batch.max_events` events. Logstash acknowledges
+				each window, possibly in several parts, and the next window is only sent once the
+				current one is fully acknowledged. While it's busy processing a window, Logstash keeps
+				acknowledging the events it has already processed, which restarts the `timeout_secs`
+				wait.
+
+				If the window isn't acknowledged in time, or the connection fails, Vector reconnects and
+				sends the whole window again, so events are delivered at least once.
+				"""
+		}
+		compression: {
+			title: "Compression"
+			body: """
+				Unless `compression_level` is `0`, the events of each window are compressed with zlib
+				into a single compressed frame, as Beats do.
+				"""
+		}
+		timestamp: {
+			title: "Timestamps"
+			body: """
+				Logstash expects the timestamp of Beats events in their `@timestamp` field. The
+				timestamp of events, at the
+				[global `timestamp_key` option](\(urls.vector_configuration)/global-options#log_schema.timestamp_key),
+				is moved to `@timestamp` unless the events already have such a field.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_sent_bytes_total:  components.sources.internal_metrics.output.metrics.component_sent_bytes_total
+		component_sent_events_total: components.sources.internal_metrics.output.metrics.component_sent_events_total
+		open_connections:            components.sources.internal_metrics.output.metrics.open_connections
+	}
+}
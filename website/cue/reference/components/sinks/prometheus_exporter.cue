@@ -134,6 +134,12 @@ components: sinks: prometheus_exporter: {
 			required:    false
 			type: bool: default: false
 		}
+		openmetrics: {
+			common:      false
+			description: "Serves the metrics in the [OpenMetrics](\(urls.openmetrics)) format to the scrapers requesting it, along with the time their series were created and their exemplars. See [OpenMetrics](#openmetrics) for more info."
+			required:    false
+			type: bool: default: false
+		}
 	}
 
 	input: {
@@ -346,6 +352,29 @@ components: sinks: prometheus_exporter: {
 	]
 
 	how_it_works: {
+		openmetrics: {
+			title: "OpenMetrics"
+			body: """
+				When the `openmetrics` option is enabled, scrapers accepting the
+				`application/openmetrics-text` content type in the `Accept` header of their
+				requests, such as Prometheus, are served the metrics in the OpenMetrics 1.0
+				format. Other scrapers are still served the Prometheus text format.
+
+				In this format, counters, histograms, and summaries are followed by a `_created`
+				series, holding the time their series was first seen by the sink. Counters and
+				histogram buckets also carry an exemplar linking them to the trace they were
+				last observed in, made of the `trace_id` and `span_id` fields of the metadata of
+				the metrics, which can be set with the `remap` transform:
+
+				```coffee
+				%trace_id = "4bf92f3577b34da6a3ce929d0e0e4736"
+				```
+
+				The value of an exemplar is the increment of a counter, or the last sample of a
+				distribution. The statistics of distributions rendered as summaries that aren't
+				part of OpenMetrics summaries, such as `_min`, are left out.
+				"""
+		}
 		histogram_buckets: {
 			title: "Histogram Buckets"
 			body:  """
@@ -22,7 +22,7 @@ components: sinks: socket: {
 				codec: {
 					enabled: true
 					framing: true
-					enum: ["json", "text", "gelf"]
+					enum: ["json", "logfmt", "text", "gelf"]
 				}
 			}
 			send_buffer_bytes: {
@@ -44,7 +44,7 @@ components: sinks: socket: {
 				interface: {
 					socket: {
 						direction: "outgoing"
-						protocols: ["tcp", "udp", "unix"]
+						protocols: ["tcp", "udp", "unix", "unix_seqpacket"]
 						ssl: "required"
 					}
 				}
@@ -72,18 +72,22 @@ components: sinks: socket: {
 			required:    true
 			type: string: {
 				enum: {
-					tcp:  "TCP socket"
-					udp:  "UDP socket"
-					unix: "Unix domain socket"
+					tcp:            "TCP socket"
+					udp:            "UDP socket"
+					unix:           "Unix domain socket"
+					unix_seqpacket: "Unix domain sequenced packet socket, sending each event as a single packet without framing"
 				}
 			}
 		}
 		path: {
-			description:   "The unix socket path. This should be the absolute path."
-			relevant_when: "mode = `unix`"
+			description: """
+				The unix socket path. This should be the absolute path.
+				On Linux, a path starting with `@` designates an address in the abstract namespace.
+				"""
+			relevant_when: "mode = `unix` or `unix_seqpacket`"
 			required:      true
 			type: string: {
-				examples: ["/path/to/socket"]
+				examples: ["/path/to/socket", "@vector"]
 			}
 		}
 	}
@@ -86,6 +86,18 @@ components: sinks: statsd: {
 				examples: ["service"]
 			}
 		}
+		flavor: {
+			common:      false
+			description: "The flavor of the StatsD protocol spoken by the receiver."
+			required:    false
+			type: string: {
+				default: "dogstatsd"
+				enum: {
+					dogstatsd: "The DogStatsD protocol. Tags are sent, and distributions are sent as histograms (`h`) or distributions (`d`), depending on their statistic."
+					statsd:    "The original StatsD protocol. Tags aren't sent, as the protocol doesn't support them, and distributions are sent as timings (`ms`)."
+				}
+			}
+		}
 	}
 
 	telemetry: metrics: {
@@ -88,6 +88,19 @@ components: sinks: vector: {
 			required:    false
 			type: bool: default: false
 		}
+		auth_token: {
+			description: """
+				The token to authenticate with to the downstream Vector instance. It's sent as
+				`Bearer <token>` in the `authorization` metadata of requests, and must be one of the
+				`auth.tokens` of the downstream `vector` source.
+				"""
+			common:      false
+			required:    false
+			type: string: {
+				default:  null
+				examples: ["${VECTOR_AUTH_TOKEN}"]
+			}
+		}
 	}
 
 	how_it_works: components.sources.vector.how_it_works
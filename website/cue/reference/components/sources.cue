@@ -5,6 +5,105 @@ components: sources: [Name=string]: {
 	features: _
 
 	configuration: {
+		host_metadata_key: {
+			common: false
+			description: """
+				The field to add the host metadata to the events of this source under, as fetched
+				with the global `host_metadata` option.
+				"""
+			required: false
+			type: string: {
+				default: null
+				examples: ["host_metadata"]
+			}
+		}
+
+		filter: {
+			common: false
+			description: """
+				A condition the events of this source must match to be sent on. It's evaluated as soon
+				as events are decoded, so that unwanted events, such as health checks, are dropped
+				before being sent to the components consuming the source.
+				"""
+			required: false
+			type: condition: {}
+		}
+
+		trace: {
+			common: false
+			description: """
+				A condition matching the events of this source to follow through the topology. Each
+				component a matching event, or an event derived from it, passes through logs it with
+				its trace ID and the time elapsed since it was sent by this source, which shows where
+				the event was dropped or delayed. Traces are kept in memory only, so they're lost
+				when events are written to disk buffers.
+				"""
+			required: false
+			type: condition: {}
+		}
+
+		max_event_bytes: {
+			common: false
+			description: """
+				The maximum size of the events of this source, estimated as the size of their JSON
+				encoding. Overrides the global `max_event_bytes` option.
+				"""
+			required: false
+			type: uint: {
+				default: null
+				examples: [1048576]
+				unit: "bytes"
+			}
+		}
+
+		oversized_events: {
+			common:      false
+			description: "What to do with the events exceeding the maximum event size."
+			required:    false
+			type: string: {
+				default: "drop"
+				enum: {
+					drop:     "Drop the events."
+					truncate: "Truncate the message of log events to fit. Events which still don't fit are dropped."
+					route:    "Send the events to the `errors` output of the source."
+				}
+			}
+		}
+
+		limits: {
+			common: false
+			description: """
+				Limits the rate at which this source ingests events, as a safety valve protecting
+				downstream systems during upstream log storms. Once a limit is reached, the source is
+				held back until its events fit in the limit, which slows down reading from the upstream
+				system where the source supports it, such as for files and TCP connections, rather than
+				dropping events.
+				"""
+			required: false
+			type: object: options: {
+				events_per_second: {
+					common:      true
+					description: "The maximum number of events ingested per second. Bursts of up to this number of events aren't delayed."
+					required:    false
+					type: uint: {
+						default: null
+						examples: [10000]
+						unit: "events"
+					}
+				}
+				bytes_per_second: {
+					common:      true
+					description: "The maximum number of bytes of events ingested per second, estimated as the size of their JSON encoding. Bursts of up to this number of bytes aren't delayed."
+					required:    false
+					type: uint: {
+						default: null
+						examples: [10485760]
+						unit: "bytes"
+					}
+				}
+			}
+		}
+
 		if features.collect != _|_ {
 			if features.collect.checkpoint.enabled {
 				data_dir: {
@@ -86,6 +185,7 @@ components: sources: [Name=string]: {
 								enum: {
 									bytes:               "Byte frames are passed through as-is according to the underlying I/O boundaries (e.g. split between messages or stream segments)."
 									character_delimited: "Byte frames which are delimited by a chosen character."
+									json_stream:         "Byte frames which each hold one JSON value of a stream of concatenated values, such as pretty-printed JSON documents spanning multiple lines."
 									length_delimited:    "Byte frames which are prefixed by an unsigned big-endian 32-bit integer indicating the length."
 									newline_delimited:   "Byte frames which are delimited by a newline character."
 									octet_counting:      "Byte frames according to the [octet counting](\(urls.rfc_6587_3_4_1)) format."
@@ -116,6 +216,24 @@ components: sources: [Name=string]: {
 								}
 							}
 						}
+						json_stream: {
+							description:   "Options for `json_stream` framing."
+							required:      false
+							common:        false
+							relevant_when: "method = `json_stream`"
+							type: object: options: {
+								max_length: {
+									description: "The maximum length of a single JSON value. Any values longer than `max_length` bytes will be discarded entirely."
+									required:    false
+									common:      false
+									type: uint: {
+										default: null
+										examples: [65535, 102400]
+										unit: "bytes"
+									}
+								}
+							}
+						}
 						newline_delimited: {
 							description:   "Options for `newline_delimited` framing."
 							required:      false
@@ -175,6 +293,23 @@ components: sources: [Name=string]: {
 								}
 							}
 						}
+						on_error: {
+							description: """
+								How payloads that can't be decoded are handled. Passed through and routed payloads are
+								kept as-is in the message of the event, as with the `bytes` codec, and the decoding
+								error is set to the `decoding_error` metadata field.
+								"""
+							required: false
+							common:   false
+							type: string: {
+								default: "drop"
+								enum: {
+									drop:        "Drop the payloads, logging and counting the errors."
+									passthrough: "Pass the payloads on along with the decoded events."
+									route:       "Send the payloads to the `errors` output of the source."
+								}
+							}
+						}
 					}
 				}
 			}
@@ -366,9 +501,10 @@ components: sources: [Name=string]: {
 	}
 
 	telemetry: metrics: {
-		events_out_total:                 components.sources.internal_metrics.output.metrics.events_out_total
-		component_sent_events_total:      components.sources.internal_metrics.output.metrics.component_sent_events_total
-		component_sent_event_bytes_total: components.sources.internal_metrics.output.metrics.component_sent_event_bytes_total
-		lag_time_seconds:                 components.sources.internal_metrics.output.metrics.lag_time_seconds
+		events_out_total:                                    components.sources.internal_metrics.output.metrics.events_out_total
+		component_received_event_estimated_json_bytes_total: components.sources.internal_metrics.output.metrics.component_received_event_estimated_json_bytes_total
+		component_sent_events_total:                         components.sources.internal_metrics.output.metrics.component_sent_events_total
+		component_sent_event_bytes_total:                    components.sources.internal_metrics.output.metrics.component_sent_event_bytes_total
+		lag_time_seconds:                                    components.sources.internal_metrics.output.metrics.lag_time_seconds
 	}
 }
@@ -0,0 +1,178 @@
+package metadata
+
+components: sources: auditd: {
+	title: "Linux Audit"
+
+	description: """
+		Collects the records of the Linux audit subsystem, either from the audit netlink socket of
+		the kernel or from the `af_unix` plugin of the audit dispatcher, and emits an event for the
+		records of each audit event.
+		"""
+
+	classes: {
+		commonly_used: false
+		delivery:      "best_effort"
+		deployment_roles: ["daemon"]
+		development:   "alpha"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	features: {
+		acknowledgements: false
+		collect: {
+			checkpoint: enabled: false
+			from: {
+				service: services.linux_audit
+
+				interface: socket: {
+					direction: "outgoing"
+					protocols: ["unix"]
+					socket:    "/var/run/audispd_events"
+					ssl:       "disabled"
+				}
+			}
+		}
+		multiline: enabled: false
+	}
+
+	support: {
+		targets: {
+			"x86_64-pc-windows-msv": false
+		}
+
+		requirements: [
+			"""
+				The `netlink` mode requires the `CAP_AUDIT_READ` capability, and is only supported on
+				Linux.
+				""",
+			"""
+				The `audisp` mode requires the [`af_unix` plugin](\(urls.audisp_af_unix)) of the audit
+				dispatcher to be enabled, with the `string` format.
+				""",
+		]
+		warnings: []
+		notices: [
+			"""
+				This component is in alpha, and can only be used with the `alpha_components` feature flag
+				enabled.
+				""",
+		]
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: {
+		event_timeout_ms: {
+			common:      false
+			description: "How long, in milliseconds, to wait for further records of an event whose end isn't marked by an `EOE` record, before emitting it with the records received so far."
+			required:    false
+			type: uint: {
+				default: 2000
+				unit:    "milliseconds"
+			}
+		}
+		interpret: {
+			common:      true
+			description: "Whether to resolve the architecture, system call, user, and group fields of records into readable values. The resolved values are added next to the raw ones, under the upper case name of the field, as `auditd` does when writing enriched logs."
+			required:    false
+			type: bool: default: true
+		}
+		mode: {
+			common:      true
+			description: "Where to read audit records from."
+			required:    false
+			type: string: {
+				default: "netlink"
+				enum: {
+					netlink: "Subscribe to the audit netlink socket of the kernel. The records are received whether or not `auditd` is running."
+					audisp:  "Read the records forwarded by the `af_unix` plugin of the audit dispatcher."
+				}
+			}
+		}
+		socket_path: {
+			common:      false
+			description: "The path of the socket the `af_unix` plugin of the audit dispatcher writes records to, in `audisp` mode."
+			required:    false
+			type: string: {
+				default: "/var/run/audispd_events"
+			}
+		}
+	}
+
+	output: logs: event: {
+		description: "The records of a single audit event."
+		fields: {
+			message: {
+				description: "The records of the event, one per line, as `auditd` writes them."
+				required:    true
+				type: string: {
+					examples: [
+						"""
+							type=SYSCALL msg=audit(1364481363.243:24287): arch=c000003e syscall=2 success=no exit=-13 pid=3538 auid=1000 uid=1000 comm="cat" exe="/usr/bin/cat"
+							type=CWD msg=audit(1364481363.243:24287): cwd="/home/alice"
+							""",
+					]
+				}
+			}
+			records: {
+				description: "The records of the event, with their fields keyed by name, and their type under `type`. Quoted and hex encoded values are decoded, and the fields of the `msg` of user space records are added along the others."
+				required:    true
+				type: array: items: type: object: {
+					examples: [{"type": "SYSCALL", "arch": "c000003e", "ARCH": "x86_64", "syscall": "2", "SYSCALL": "open", "uid": "1000", "UID": "alice"}]
+				}
+			}
+			sequence: {
+				description: "The serial number of the event."
+				required:    true
+				type: uint: {
+					examples: [24287]
+					unit: null
+				}
+			}
+			source_type: {
+				description: "The name of the source type."
+				required:    true
+				type: string: {
+					examples: ["auditd"]
+				}
+			}
+			timestamp: fields._current_timestamp & {
+				description: "The time at which the event occurred."
+			}
+		}
+	}
+
+	how_it_works: {
+		reassembly: {
+			title: "Event reassembly"
+			body: """
+				The audit subsystem writes an event as several records sharing the same serial number,
+				such as the `SYSCALL`, `CWD`, and `PATH` records of a system call, which can be
+				interleaved with the records of other events. The records are grouped by serial
+				number until the `EOE` record ending the event is received. Events whose end isn't
+				marked, such as `AVC` denials, are emitted once no further record was received for
+				`event_timeout_ms`. Records sent by user space programs, such as PAM, are events of
+				their own.
+				"""
+		}
+		interpretation: {
+			title: "Interpreted fields"
+			body: """
+				The architecture and system call numbers are resolved into names for the `x86_64`
+				and `aarch64` architectures. User and group IDs are resolved with the user and group
+				databases of the host Vector runs on, and cached for the lifetime of the source.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		auditd_receive_buffer_overruns_total: components.sources.internal_metrics.output.metrics.auditd_receive_buffer_overruns_total
+		component_errors_total:               components.sources.internal_metrics.output.metrics.component_errors_total
+		component_received_bytes_total:       components.sources.internal_metrics.output.metrics.component_received_bytes_total
+		component_received_events_total:      components.sources.internal_metrics.output.metrics.component_received_events_total
+		component_received_event_bytes_total: components.sources.internal_metrics.output.metrics.component_received_event_bytes_total
+	}
+}
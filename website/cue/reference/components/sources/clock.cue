@@ -0,0 +1,200 @@
+package metadata
+
+components: sources: clock: {
+	title: "Clock"
+
+	description: """
+		Reads the status of the system clock and generates metrics describing
+		how far it is from the correct time and how well it is being disciplined
+		by NTP.
+		"""
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		deployment_roles: ["daemon"]
+		development:   "alpha"
+		egress_method: "batch"
+		stateful:      false
+	}
+
+	features: {
+		acknowledgements: false
+		collect: {
+			checkpoint: enabled: false
+			from: service:       services.host
+		}
+		multiline: enabled: false
+	}
+
+	support: {
+		notices: [
+			"""
+				This component is in alpha, and can only be used with the `alpha_components` feature flag
+				enabled.
+				""",
+		]
+		requirements: [
+			"""
+				The `chronyc` method requires [chrony](\(urls.chrony)) to be installed and `chronyd`
+				to be running.
+				""",
+		]
+		warnings: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: {
+		chronyc_path: {
+			description:   "The path of the `chronyc` binary. By default, `chronyc` is looked up in the `PATH`."
+			common:        false
+			required:      false
+			relevant_when: "method = \"chronyc\""
+			type: string: {
+				default: "chronyc"
+				examples: ["/usr/bin/chronyc"]
+			}
+		}
+		method: {
+			description: "The method used to read the status of the system clock."
+			common:      true
+			required:    false
+			type: string: {
+				default: "adjtimex"
+				enum: {
+					adjtimex: "Read the kernel clock discipline through the [`adjtimex`](\(urls.adjtimex)) system call. Only supported on Linux."
+					chronyc:  "Read the tracking status of `chronyd` through `chronyc`."
+				}
+			}
+		}
+		namespace: {
+			description: "The namespace of the metric."
+			common:      false
+			required:    false
+			type: string: {
+				default: "clock"
+			}
+		}
+		scrape_interval_secs: {
+			description: "The interval between reads of the clock status, in seconds."
+			common:      true
+			required:    false
+			type: float: {
+				default: 15.0
+				unit:    "seconds"
+			}
+		}
+	}
+
+	output: metrics: {
+		_clock_tags: {
+			host: {
+				description: "The hostname of the system Vector is running on."
+				required:    true
+				examples: [_values.local_host]
+			}
+			method: {
+				description: "The method used to read the clock status."
+				required:    true
+				examples: ["adjtimex", "chronyc"]
+			}
+		}
+
+		estimated_error_seconds: {
+			description:       "The estimated error of the system clock."
+			relevant_when:     "method = \"adjtimex\""
+			type:              "gauge"
+			default_namespace: "clock"
+			tags:              _clock_tags
+		}
+		frequency_ppm: {
+			description:       "The rate at which the system clock is being corrected, in parts per million."
+			type:              "gauge"
+			default_namespace: "clock"
+			tags:              _clock_tags
+		}
+		last_offset_seconds: {
+			description:       "The offset of the system clock measured at the last clock update."
+			relevant_when:     "method = \"chronyc\""
+			type:              "gauge"
+			default_namespace: "clock"
+			tags:              _clock_tags
+		}
+		max_error_seconds: {
+			description:       "The maximum error of the system clock."
+			relevant_when:     "method = \"adjtimex\""
+			type:              "gauge"
+			default_namespace: "clock"
+			tags:              _clock_tags
+		}
+		offset_seconds: {
+			description:       "The offset of the system clock from the correct time."
+			type:              "gauge"
+			default_namespace: "clock"
+			tags:              _clock_tags
+		}
+		rms_offset_seconds: {
+			description:       "The long-term average of the offset of the system clock."
+			relevant_when:     "method = \"chronyc\""
+			type:              "gauge"
+			default_namespace: "clock"
+			tags:              _clock_tags
+		}
+		root_delay_seconds: {
+			description:       "The total network path delay to the stratum-1 time source."
+			relevant_when:     "method = \"chronyc\""
+			type:              "gauge"
+			default_namespace: "clock"
+			tags:              _clock_tags
+		}
+		root_dispersion_seconds: {
+			description:       "The total dispersion accumulated through all computers back to the stratum-1 time source."
+			relevant_when:     "method = \"chronyc\""
+			type:              "gauge"
+			default_namespace: "clock"
+			tags:              _clock_tags
+		}
+		skew_ppm: {
+			description:       "The estimated error bound on the frequency, in parts per million."
+			relevant_when:     "method = \"chronyc\""
+			type:              "gauge"
+			default_namespace: "clock"
+			tags:              _clock_tags
+		}
+		stratum: {
+			description:       "The number of hops away from a reference clock the system clock is."
+			relevant_when:     "method = \"chronyc\""
+			type:              "gauge"
+			default_namespace: "clock"
+			tags:              _clock_tags
+		}
+		synchronized: {
+			description:       "Whether the system clock is synchronized, `1` if it is and `0` if not."
+			type:              "gauge"
+			default_namespace: "clock"
+			tags:              _clock_tags
+		}
+	}
+
+	how_it_works: {
+		time_skew: {
+			title: "Time skew"
+			body: """
+				Timestamps of events are taken from the clock of the host they are
+				produced on. A clock that drifts away from the correct time skews these
+				timestamps without any error being reported, which silently breaks
+				anything relying on them, such as windowed aggregations or retention.
+				Alerting on `offset_seconds` and `synchronized` catches such drift early.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_errors_total:               components.sources.internal_metrics.output.metrics.component_errors_total
+		component_received_events_total:      components.sources.internal_metrics.output.metrics.component_received_events_total
+		component_received_event_bytes_total: components.sources.internal_metrics.output.metrics.component_received_event_bytes_total
+	}
+}
@@ -0,0 +1,128 @@
+package metadata
+
+components: sources: cloudflare_logpush: {
+	_port: 80
+
+	title: "Cloudflare Logpush"
+
+	description: """
+		Receives the logs delivered by Cloudflare Logpush jobs to an HTTP destination.
+		"""
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		deployment_roles: ["aggregator"]
+		development:   "alpha"
+		egress_method: "batch"
+		stateful:      false
+	}
+
+	features: {
+		acknowledgements: true
+		multiline: enabled: false
+		receive: {
+			from: {
+				service: services.cloudflare
+
+				interface: socket: {
+					api: {
+						title: "Logpush HTTP destination"
+						url:   urls.cloudflare_logpush_http
+					}
+					direction: "incoming"
+					port:      _port
+					protocols: ["http"]
+					ssl: "optional"
+				}
+			}
+
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				enabled_default:        false
+			}
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: [
+			"""
+				This component is in alpha, and can only be used with the `alpha_components` feature flag
+				enabled.
+				""",
+		]
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: {
+		acknowledgements: configuration._source_acknowledgements
+		address:          sources.http.configuration.address
+		auth:             sources.http.configuration.auth
+		path: {
+			common:      false
+			description: "The URL path on which Logpush jobs deliver logs."
+			required:    false
+			type: string: {
+				default: "/"
+				examples: ["/logpush"]
+			}
+		}
+	}
+
+	output: logs: line: {
+		description: "An individual record of a file delivered by a Logpush job. The fields of the record are placed at the root of the event."
+		fields: {
+			logpush: {
+				description: "The `X-Logpush-*` headers of the request, keyed by the snake case rest of their names."
+				required:    false
+				type: object: {
+					examples: [{"dataset": "http_requests"}]
+				}
+			}
+			source_type: {
+				description: "The name of the source type."
+				required:    true
+				type: string: {
+					examples: ["cloudflare_logpush"]
+				}
+			}
+			timestamp: fields._current_timestamp
+		}
+	}
+
+	how_it_works: {
+		delivery: {
+			title: "Logpush deliveries"
+			body: """
+				Logpush delivers gzip compressed files of newline delimited JSON records. The files
+				are decompressed whether or not they are announced with a `Content-Encoding` header,
+				and each record becomes an event.
+
+				When a job is created, Logpush validates the destination by delivering a file with a
+				single `{"content":"tests"}` record. It is acknowledged without producing an event.
+				"""
+		}
+		metadata: {
+			title: "Job metadata"
+			body: """
+				Logpush jobs can send custom headers with the `header_<name>` parameters of their
+				destination. Headers whose name starts with `X-Logpush-`, such as
+				`X-Logpush-Dataset`, are added to the `logpush` field of the events, so that the
+				records of several jobs delivered to the same source can be told apart.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_errors_total:               components.sources.internal_metrics.output.metrics.component_errors_total
+		component_received_bytes_total:       components.sources.internal_metrics.output.metrics.component_received_bytes_total
+		component_received_events_total:      components.sources.internal_metrics.output.metrics.component_received_events_total
+		component_received_event_bytes_total: components.sources.internal_metrics.output.metrics.component_received_event_bytes_total
+	}
+}
@@ -0,0 +1,145 @@
+package metadata
+
+components: sources: envoy_als: {
+	_port: 9001
+
+	title: "Envoy Access Log Service"
+
+	description: """
+		Receives access logs streamed by Envoy proxies over the gRPC
+		[Access Log Service](\(urls.envoy_als)) (ALS) protocol.
+		"""
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		deployment_roles: ["aggregator"]
+		development:   "alpha"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	features: {
+		acknowledgements: true
+		multiline: enabled: false
+		receive: {
+			from: {
+				service: services.envoy
+
+				interface: socket: {
+					direction: "incoming"
+					port:      _port
+					protocols: ["http"]
+					ssl: "optional"
+				}
+			}
+			receive_buffer_bytes: enabled: false
+			keepalive: enabled:            true
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				enabled_default:        false
+			}
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: [
+			"""
+				This component is in alpha, and can only be used with the `alpha_components` feature flag
+				enabled.
+				""",
+		]
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: {
+		acknowledgements: configuration._source_acknowledgements
+		address: {
+			description: """
+				The gRPC address to listen for connections on. It _must_ include a port.
+				"""
+			required: true
+			type: string: {
+				examples: ["0.0.0.0:\(_port)"]
+			}
+		}
+	}
+
+	output: logs: access_log: {
+		description: "A single HTTP or TCP access log entry."
+		fields: {
+			access_log_type: {
+				description: "The kind of access log entry."
+				required:    true
+				type: string: {
+					enum: {
+						http: "An HTTP access log entry."
+						tcp:  "A TCP access log entry."
+					}
+				}
+			}
+			common: {
+				description: "Properties common to HTTP and TCP entries, such as peer addresses, the upstream cluster, and the duration."
+				required:    false
+				type: object: {}
+			}
+			connection: {
+				description: "Bytes sent and received on the connection, for TCP entries."
+				required:    false
+				type: object: {}
+			}
+			log_name: {
+				description: "The name of the access log configured in Envoy."
+				required:    false
+				type: string: {
+					examples: ["als"]
+				}
+			}
+			node: {
+				description: "The ID and cluster of the Envoy node that sent the entry."
+				required:    false
+				type: object: {}
+			}
+			protocol_version: {
+				description: "The HTTP version of the request, for HTTP entries."
+				required:    false
+				type: string: {
+					examples: ["HTTP/1.1", "HTTP/2"]
+				}
+			}
+			request: {
+				description: "The request properties, for HTTP entries."
+				required:    false
+				type: object: {}
+			}
+			response: {
+				description: "The response properties, for HTTP entries."
+				required:    false
+				type: object: {}
+			}
+			source_type: {
+				description: "The name of the source type."
+				required:    true
+				type: string: {
+					examples: ["envoy_als"]
+				}
+			}
+			timestamp: fields._current_timestamp & {
+				description: "The time at which the downstream request started, falling back to the time the entry was received."
+			}
+		}
+	}
+
+	telemetry: metrics: {
+		component_errors_total:               components.sources.internal_metrics.output.metrics.component_errors_total
+		component_received_bytes_total:       components.sources.internal_metrics.output.metrics.component_received_bytes_total
+		component_received_events_total:      components.sources.internal_metrics.output.metrics.component_received_events_total
+		component_received_event_bytes_total: components.sources.internal_metrics.output.metrics.component_received_event_bytes_total
+	}
+}
@@ -49,6 +49,19 @@ components: sources: file: {
 
 	configuration: {
 		acknowledgements: configuration._source_acknowledgements
+		checkpoint_store: configuration._source_checkpoint_store
+		copytruncate: {
+			common: false
+			description: """
+				Detect files rotated by copying them under another name and truncating them, as done by the
+				`copytruncate` option of `logrotate`. Truncated files are read from their beginning again, rather
+				than from where they were read up to. With the `checksum` and `inode_checksum` fingerprinting
+				strategies, the copies of truncated files are also read from where the files were read up to
+				before their truncation, rather than from their beginning.
+				"""
+			required: false
+			type: bool: default: false
+		}
 		exclude: {
 			common:      false
 			description: "Array of file patterns to exclude. [Globbing](#globbing) is supported.*Takes precedence over the [`include` option](#include).*"
@@ -84,13 +97,24 @@ components: sources: file: {
 						enum: {
 							checksum:         "Read first N lines of the file, skipping the first `ignored_header_bytes` bytes, to uniquely identify files via a checksum."
 							device_and_inode: "Uses the [device and inode](\(urls.inode)) to unique identify files."
+							inode_checksum:   "Uses the [inode](\(urls.inode)) and a checksum of the first `checksum_bytes` bytes of the file, skipping the first `ignored_header_bytes` bytes, to uniquely identify files."
 						}
 					}
 				}
+				checksum_bytes: {
+					common:        false
+					description:   "The number of bytes to compute the checksum over. Files smaller than this, after the ignored header bytes, aren't read until they grow."
+					relevant_when: "strategy = \"inode_checksum\""
+					required:      false
+					type: uint: {
+						default: 256
+						unit:    "bytes"
+					}
+				}
 				ignored_header_bytes: {
 					common:        false
 					description:   "The number of bytes to skip ahead (or ignore) when generating a unique fingerprint. This is helpful if all files share a common header."
-					relevant_when: "strategy = \"checksum\""
+					relevant_when: "strategy = \"checksum\" or strategy = \"inode_checksum\""
 					required:      false
 					type: uint: {
 						default: 0
@@ -113,6 +137,59 @@ components: sources: file: {
 				}
 			}
 		}
+		framing: {
+			category: "Parsing"
+			common:   false
+			description: """
+				Framing of the lines of the files into messages, such as `json_stream` to read pretty-printed
+				JSON documents spanning multiple lines. The lines of each file are passed to the framing
+				followed by a newline. If not specified, each line is a message. Can't be used along with
+				`multiline`.
+				"""
+			required: false
+			type: object: options: {
+				method: {
+					description: "The framing method."
+					required:    true
+					type: string: enum: {
+						bytes:               "Each line is a message."
+						character_delimited: "Messages which are delimited by a chosen character."
+						json_stream:         "Messages which each hold one JSON value of a stream of concatenated values, such as pretty-printed JSON documents spanning multiple lines."
+						length_delimited:    "Messages which are prefixed by an unsigned big-endian 32-bit integer indicating the length."
+						newline_delimited:   "Messages which are delimited by a newline character."
+						octet_counting:      "Messages according to the [octet counting](\(urls.rfc_6587_3_4_1)) format."
+					}
+				}
+				character_delimited: {
+					description:   "Options for `character_delimited` framing."
+					required:      true
+					relevant_when: "method = `character_delimited`"
+					type: object: options: delimiter: {
+						description: "The character used to separate messages."
+						required:    true
+						type: ascii_char: examples: [","]
+					}
+				}
+				json_stream: {
+					description:   "Options for `json_stream` framing."
+					required:      false
+					common:        false
+					relevant_when: "method = `json_stream`"
+					type: object: options: {
+						max_length: {
+							description: "The maximum length of a single JSON value. Any values longer than `max_length` bytes will be discarded entirely."
+							required:    false
+							common:      false
+							type: uint: {
+								default: null
+								examples: [65535, 102400]
+								unit: "bytes"
+							}
+						}
+					}
+				}
+			}
+		}
 		glob_minimum_cooldown_ms: {
 			common: false
 			description: """
@@ -161,6 +238,17 @@ components: sources: file: {
 				examples: ["\(_directory)/**/*.log"]
 			}
 		}
+		io_uring: {
+			common: false
+			description: """
+				Read the files in batches through `io_uring`, rather than one at a time. This reduces the number of
+				system calls made when tailing many files, most of which have no new data most of the time. Only
+				supported on Linux 5.1 and later, the files are read one at a time when `io_uring` isn't available,
+				such as when it's disabled by the seccomp profile of a container runtime.
+				"""
+			required: false
+			type: bool: default: false
+		}
 		line_delimiter: {
 			common:      false
 			description: "String sequence used to separate one file line from another"
@@ -207,6 +295,19 @@ components: sources: file: {
 			required:    false
 			type: bool: default: false
 		}
+		preset: {
+			category:    "Parsing"
+			common:      false
+			description: "The format the lines of the files are parsed from into fields. If not specified, each line is the `message` of its event."
+			required:    false
+			type: string: {
+				default: null
+				enum: {
+					w3c:         "The [W3C extended log file format](\(urls.w3c_extended_log_format)), as written by IIS and many web servers and proxies."
+					windows_dns: "The debug log of the Windows DNS server."
+				}
+			}
+		}
 		remove_after_secs: {
 			common:      false
 			description: "Timeout from reaching `eof` after which file will be removed from filesystem, unless new data is written in the meantime. If not specified, files will not be removed."
@@ -382,15 +483,17 @@ components: sources: file: {
 
 				A popular alternative strategy is `copytruncate`, in which
 				`logrotate` will copy the old log file to a new location before
-				truncating the original. Vector will also handle this well out of
-				the box, but there are a couple configuration options that will help
-				reduce the very small chance of missed data in some edge cases. We
-				recommend a combination of `delaycompress` (if applicable) on the
-				`logrotate` side and including the first rotated file in Vector's
-				`include` option. This allows Vector to find the file after rotation,
-				read it uncompressed to identify it, and then ensure it has all of
-				the data, including any written in a gap between Vector's last read
-				and the actual rotation event.
+				truncating the original. Setting the [`copytruncate`](#copytruncate)
+				option makes Vector detect the truncation, and read the truncated
+				file from its beginning again. There are a couple configuration
+				options that will help reduce the very small chance of missed data in
+				some edge cases. We recommend a combination of `delaycompress` (if
+				applicable) on the `logrotate` side and including the first rotated
+				file in Vector's `include` option. This allows Vector to find the file
+				after rotation, read it uncompressed to identify it as the copy of the
+				truncated file, and then read the data written in a gap between
+				Vector's last read and the actual rotation event, without reading the
+				rest of the copy again.
 				"""
 		}
 
@@ -406,6 +509,12 @@ components: sources: file: {
 				This strategy avoids the common pitfalls associated with using device and inode
 				names since inode names can be reused across files. This enables Vector to properly
 				tail files across various rotation strategies.
+
+				The `inode_checksum` strategy combines both: it identifies files by their inode and
+				a checksum of their first [`fingerprint.checksum_bytes`](#fingerprint.checksum_bytes)
+				bytes, but not their device number, which can change when NFS shares are mounted
+				again. This keeps files on NFS mounts from being read again, while telling apart
+				files reusing the inode of deleted files.
 				"""
 		}
 
@@ -550,6 +659,36 @@ components: sources: file: {
 			]
 		}
 
+		presets: {
+			title: "Presets"
+			body: """
+				The `preset` option parses the lines of files written in well-known
+				formats into fields, instead of emitting each line as the `message` of
+				its event.
+
+				With the `w3c` preset, the fields of each line are named after the last
+				`#Fields` directive of its file, in snake case, such as `cs_uri_stem` for
+				`cs-uri-stem` and `cs_user_agent` for `cs(User-Agent)`. Servers such as IIS
+				write a new `#Fields` directive when their logged fields change, which the
+				following lines of the file are then parsed with. The `date` and `time`
+				fields are parsed into the `timestamp` of the event, and the status, byte
+				count, port, and `time-taken` fields into integers. Fields whose value is
+				`-` are left out, and directives aren't emitted. Files read from a
+				checkpoint have their directives read again when Vector starts.
+
+				With the `windows_dns` preset, the packets of the debug log of the Windows
+				DNS server are parsed into the `thread_id`, `packet_id`, `protocol`,
+				`direction`, `remote_ip`, `xid`, `response`, `opcode`, `flags`,
+				`response_code`, `question_type`, and `question_name` fields, with the
+				name decoded from its labels, such as `example.com` for
+				`(7)example(3)com(0)`. The other lines with a timestamp, such as events,
+				are emitted as their `message`, and the header of the file isn't emitted.
+				The timestamps of servers with the `en-US` locale are parsed into the
+				`timestamp` of the event, while the others are kept in the `date` and
+				`time` fields.
+				"""
+		}
+
 		permissions: {
 			title: "File permissions"
 			body:  """
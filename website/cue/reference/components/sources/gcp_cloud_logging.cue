@@ -0,0 +1,255 @@
+package metadata
+
+components: sources: gcp_cloud_logging: {
+	title: "GCP Cloud Logging"
+
+	features: {
+		acknowledgements: true
+		collect: {
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        true
+				enabled_by_scheme:      true
+			}
+			checkpoint: enabled: false
+			proxy: enabled:      true
+			from: service:       services.gcp_pubsub
+		}
+		multiline: enabled: false
+	}
+
+	classes: {
+		commonly_used: false
+		deployment_roles: ["aggregator"]
+		delivery:      "at_least_once"
+		development:   "alpha"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	support: {
+		targets: {
+			"aarch64-unknown-linux-gnu":      true
+			"aarch64-unknown-linux-musl":     true
+			"armv7-unknown-linux-gnueabihf":  true
+			"armv7-unknown-linux-musleabihf": true
+			"x86_64-apple-darwin":            true
+			"x86_64-pc-windows-msv":          true
+			"x86_64-unknown-linux-gnu":       true
+			"x86_64-unknown-linux-musl":      true
+		}
+		requirements: [
+			"""
+					The GCP Cloud Logging source requires a Pub/Sub subscription to the topic of a
+					[Cloud Logging sink](\(urls.gcp_logging_routing)).
+				""",
+		]
+		warnings: []
+		notices: [
+			"""
+				This component is in alpha, and can only be used with the `alpha_components` feature flag
+				enabled.
+				""",
+		]
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: {
+		acknowledgements: configuration._source_acknowledgements
+		ack_deadline_secs: {
+			common:      false
+			description: "The acknowledgement deadline to use for this stream. Messages that are not acknowledged when this deadline expires may be retransmitted."
+			required:    false
+			type: uint: {
+				default: 600
+				examples: [10, 600]
+				unit: "seconds"
+			}
+		}
+		api_key:          configuration._gcp_api_key
+		credentials_path: configuration._gcp_credentials_path
+		endpoint: {
+			common:      false
+			description: "The endpoint from which to pull data."
+			required:    false
+			type: string: {
+				default: "https://pubsub.googleapis.com"
+				examples: ["https://us-central1-pubsub.googleapis.com"]
+			}
+		}
+		full_response_size: {
+			common: false
+			description: """
+					The number of messages in a response to mark a stream as "busy".
+					This is used to determine if more streams should be started.
+					The GCP Pub/Sub servers send responses with 100 or more messages when
+					the subscription is busy.
+				"""
+			required: false
+			type: uint: {
+				default: 100
+				examples: [100, 128]
+				unit: null
+			}
+		}
+		keepalive_secs: {
+			common:      false
+			description: "The amount of time, in seconds, with no received activity before sending a keepalive request. If this is set larger than `60`, you may see periodic errors sent from the server."
+			required:    false
+			type: float: {
+				default: 60.0
+				examples: [10.0]
+			}
+		}
+		max_concurrency: {
+			common:      false
+			description: "The maximum number of concurrent stream connections to open at once."
+			required:    false
+			type: uint: {
+				default: 5
+				examples: [1, 9]
+				unit: "concurrency"
+			}
+		}
+		poll_time_seconds: {
+			common:      false
+			description: "How often to poll the currently active streams to see if they are all busy and so open a new stream."
+			required:    false
+			type: float: {
+				default: 2.0
+				examples: [1.0, 5.0]
+				unit: "seconds"
+			}
+		}
+		project: {
+			description: "The project name from which to pull logs."
+			required:    true
+			type: string: {
+				examples: ["vector-123456"]
+			}
+		}
+		retry_delay_secs: {
+			common:      false
+			description: "The amount of time to wait between retry attempts after an error."
+			required:    false
+			type: float: {
+				default: 1.0
+				examples: [0.5]
+				unit: "seconds"
+			}
+		}
+		subscription: {
+			description: "The subscription within the project which receives the log entries of a Cloud Logging sink."
+			required:    true
+			type: string: {
+				examples: ["vector-123456"]
+			}
+		}
+	}
+
+	output: logs: record: {
+		description: "An individual Cloud Logging log entry, with its payload unwrapped."
+		fields: {
+			attributes: {
+				description: "Attributes that were published with the Pub/Sub record."
+				required:    true
+				type: object: {
+					examples: [{"logging.googleapis.com/timestamp": "2022-10-01T12:00:00.123Z"}]
+				}
+			}
+			labels: {
+				description: "The user-defined labels of the log entry."
+				required:    false
+				type: object: {
+					examples: [{"env": "production"}]
+				}
+			}
+			log_name: {
+				description: "The resource name of the log the entry belongs to."
+				required:    true
+				type: string: {
+					examples: ["projects/vector-123456/logs/stdout"]
+				}
+			}
+			message: {
+				description: "The `textPayload` of the log entry. Entries with a `jsonPayload` have the fields of their payload at the root of the event instead."
+				required:    false
+				type: string: {
+					examples: ["Connection refused."]
+					syntax: "literal"
+				}
+			}
+			message_id: {
+				description: "The ID of this message, assigned by the server when the message is published. Guaranteed to be unique within the topic."
+				required:    true
+				type: string: {
+					examples: ["2345"]
+					syntax: "literal"
+				}
+			}
+			proto_payload: {
+				description: "The `protoPayload` of the log entry, such as a Cloud Audit Logs record."
+				required:    false
+				type: object: {
+					examples: [{"@type": "type.googleapis.com/google.cloud.audit.AuditLog", "methodName": "storage.buckets.create"}]
+				}
+			}
+			resource: {
+				description: "The monitored resource that produced the log entry, with its `type` and `labels`."
+				required:    false
+				type: object: {
+					examples: [{"type": "k8s_container", "labels": {"namespace_name": "default"}}]
+				}
+			}
+			severity: {
+				description: "The [severity](\(urls.gcp_stackdriver_severity)) of the log entry, `DEFAULT` if it has none."
+				required:    true
+				type: string: {
+					examples: ["ERROR"]
+				}
+			}
+			source_type: {
+				description: "The name of the source type."
+				required:    true
+				type: string: {
+					examples: ["gcp_cloud_logging"]
+				}
+			}
+			timestamp: fields._current_timestamp & {
+				description: "The time the log entry was emitted, falling back to the time it was received by Cloud Logging, and then to the time the message was published in the topic."
+			}
+		}
+	}
+
+	telemetry: metrics: {
+		component_errors_total:               components.sources.internal_metrics.output.metrics.component_errors_total
+		component_received_event_bytes_total: components.sources.internal_metrics.output.metrics.component_received_event_bytes_total
+		component_received_events_total:      components.sources.internal_metrics.output.metrics.component_received_events_total
+		component_received_bytes_total:       components.sources.internal_metrics.output.metrics.component_received_bytes_total
+	}
+
+	how_it_works: {
+		log_entries: {
+			title: "Log entry unwrapping"
+			body: """
+				Cloud Logging sinks publish every [log entry](\(urls.gcp_logging_log_entry)) as a
+				JSON document to their Pub/Sub topic. The `gcp_cloud_logging` source pulls them from
+				a subscription of the topic, like the `gcp_pubsub` source does, and unwraps them:
+
+				* The `textPayload` becomes the `message` of the event.
+				* The fields of the `jsonPayload` are placed at the root of the event.
+				* The `protoPayload`, such as a Cloud Audit Logs record, is kept as `proto_payload`.
+				* The metadata of the entry is added as snake case fields, such as `log_name`,
+				  `severity`, `resource`, `labels`, `insert_id`, `trace` and `http_request`. They take
+				  precedence over payload fields of the same name.
+
+				Messages that aren't log entries are dropped, and reported as errors.
+				"""
+		}
+	}
+}
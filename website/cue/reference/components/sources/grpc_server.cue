@@ -0,0 +1,210 @@
+package metadata
+
+components: sources: grpc_server: {
+	_port: 50051
+
+	title: "gRPC Server"
+
+	description: """
+		Receives the requests of arbitrary [gRPC](\(urls.grpc)) services, described by a
+		[protobuf descriptor set](\(urls.protobuf_descriptor_set)), decoding each request message
+		into an event.
+		"""
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		deployment_roles: ["aggregator"]
+		development:   "alpha"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	features: {
+		acknowledgements: true
+		multiline: enabled: false
+		receive: {
+			from: {
+				service: services.grpc
+
+				interface: socket: {
+					direction: "incoming"
+					port:      _port
+					protocols: ["http"]
+					ssl: "optional"
+				}
+			}
+			receive_buffer_bytes: enabled: false
+			keepalive: enabled:            true
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				enabled_default:        false
+			}
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: [
+			"""
+				This component is in alpha, and can only be used with the `alpha_components` feature flag
+				enabled.
+				""",
+		]
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: {
+		acknowledgements: configuration._source_acknowledgements
+		address: {
+			description: """
+				The gRPC address to listen for connections on. It _must_ include a port.
+				"""
+			required: true
+			type: string: {
+				examples: ["0.0.0.0:\(_port)"]
+			}
+		}
+		descriptor_set_file: {
+			description: """
+				The path of a protobuf descriptor set describing the services of the methods served.
+				Such a file is generated by `protoc` with the `--descriptor_set_out` and
+				`--include_imports` options.
+				"""
+			required: true
+			type: string: {
+				examples: ["/etc/vector/agent.desc"]
+			}
+		}
+		method_key: {
+			common:      false
+			description: "The event key in which the name of the method called is stored."
+			required:    false
+			type: string: {
+				default: "grpc_method"
+			}
+		}
+		methods: {
+			description: """
+				A table of the methods served, by their fully qualified name such as
+				`acme.agent.v1.AgentService/PushEvents`. Each request message is decoded into an event.
+				Requests to other methods are rejected with the `UNIMPLEMENTED` status.
+				"""
+			required: true
+			type: object: {
+				examples: [{"acme.agent.v1.AgentService/PushEvents": {"output": "agent_events"}}]
+				options: {
+					"*": {
+						description: "A method served."
+						required:    true
+						type: object: options: {
+							output: {
+								description: """
+									The output the events of the method are sent to, as `<component_id>.<output>`.
+									The events are sent to the default output if not set. Several methods can
+									share an output.
+									"""
+								required: false
+								type: string: examples: ["agent_events"]
+							}
+							response: {
+								description: """
+									The response message returned to each request, as a JSON object of its
+									fields. Enum values are given by name, and `bytes` fields in base64. The
+									response is an empty message, whose fields all have their default value, if
+									not set.
+									"""
+								required: false
+								type: string: examples: ["{\"accepted\": true}"]
+							}
+						}
+					}
+				}
+			}
+		}
+		pipeline_response: {
+			common: false
+			description: """
+				Whether to answer requests with the response set by a downstream
+				[`respond`](\(urls.vector_transforms)/respond) transform. The source then waits for all the
+				events of a request to be processed by the pipeline, rather than only sent into it, before
+				answering. A response with an HTTP status of `400` or more fails the request
+				with the matching gRPC status, such as `INVALID_ARGUMENT` for `400` and `422`, and the body as
+				its message. Otherwise, a non-empty body is the response message, as a JSON object of its
+				fields, replacing the `response` of the method.
+				"""
+			required: false
+			type: bool: default: false
+		}
+	}
+
+	outputs: [
+		{
+			name:        components._default_output.name
+			description: "Default output stream of the component, receiving the events of methods without an `output`."
+		},
+		{
+			name:        "<output>"
+			description: "The events of the methods with this `output`. Use `<component_id>.<output>` as an input to downstream transforms and sinks."
+		},
+	]
+
+	output: logs: message: {
+		description: "A request message."
+		fields: {
+			"*": {
+				description: """
+					The fields of the request message, named as in its `.proto` file. Fields with their
+					default value are left out, enum values are their name, and maps are objects.
+					"""
+				required: false
+				type: "*": {}
+			}
+			grpc_method: {
+				description: "The name of the method called. The key can be changed using the `method_key` configuration setting."
+				required:    true
+				type: string: {
+					examples: ["acme.agent.v1.AgentService/PushEvents"]
+				}
+			}
+			source_type: {
+				description: "The name of the source type."
+				required:    true
+				type: string: {
+					examples: ["grpc_server"]
+				}
+			}
+			timestamp: fields._current_timestamp
+		}
+	}
+
+	how_it_works: {
+		descriptor_sets: {
+			title: "Descriptor sets"
+			body: """
+				The services served are described by a descriptor set rather than compiled in, so that
+				the requests of bespoke agents can be received without changes to Vector. The descriptor
+				set must include the imports of the `.proto` files, for instance:
+
+				```shell
+				protoc --include_imports --descriptor_set_out=agent.desc acme/agent/v1/agent.proto
+				```
+
+				All the request messages of client streaming methods are decoded, and the response is
+				returned once the stream ends. Server streaming methods return their response once.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_errors_total:               components.sources.internal_metrics.output.metrics.component_errors_total
+		component_received_bytes_total:       components.sources.internal_metrics.output.metrics.component_received_bytes_total
+		component_received_events_total:      components.sources.internal_metrics.output.metrics.component_received_events_total
+		component_received_event_bytes_total: components.sources.internal_metrics.output.metrics.component_received_event_bytes_total
+	}
+}
@@ -65,7 +65,26 @@ components: sources: heroku_logs: {
 		acknowledgements: configuration._source_acknowledgements
 		address:          sources.http.configuration.address
 		auth:             sources.http.configuration.auth
+		max_concurrent_requests: {
+			common:      false
+			description: "The maximum number of requests handled at the same time. Further requests are answered with `503 Service Unavailable` and a `Retry-After` header, which makes Logplex retry them later, instead of being held in memory until the pipeline catches up. With acknowledgements enabled, requests are pending until their events are delivered. By default, the number of requests is not limited."
+			required:    false
+			type: uint: {
+				default: null
+				examples: [100]
+				unit: null
+			}
+		}
 		query_parameters: sources.http.configuration.query_parameters
+		retry_after_secs: {
+			common:      false
+			description: "The number of seconds Logplex is told to wait before retrying a request rejected because of `max_concurrent_requests`."
+			required:    false
+			type: uint: {
+				default: 5
+				unit:    "seconds"
+			}
+		}
 	}
 
 	output: logs: line: {
@@ -78,6 +97,20 @@ components: sources: heroku_logs: {
 					examples: ["erlang"]
 				}
 			}
+			drain_token: {
+				description: "The token of the Logplex drain the message was sent through, from the `Logplex-Drain-Token` header."
+				required:    true
+				type: string: {
+					examples: ["d.d6f1c3a9-ba5b-4e7e-9b9d-5a9a7d1f4d3e"]
+				}
+			}
+			frame_id: {
+				description: "The ID of the Logplex frame the message was part of, from the `Logplex-Frame-Id` header."
+				required:    true
+				type: string: {
+					examples: ["09C557EAFCFB6CF2740EE62F62971098"]
+				}
+			}
 			host: fields._local_host
 			message: {
 				description: "The message field, containing the plain text message."
@@ -104,12 +137,25 @@ components: sources: heroku_logs: {
 		}
 	}
 
+	how_it_works: {
+		message_count: {
+			title: "Message count validation"
+			body: """
+				Logplex sends the number of messages in each request in the `Logplex-Msg-Count` header.
+				Requests whose body holds a different number of messages are rejected with
+				`400 Bad Request`. Messages are counted before decoding, so decoders producing any
+				number of events per message don't affect the check.
+				"""
+		}
+	}
+
 	telemetry: metrics: {
 		component_errors_total:               components.sources.internal_metrics.output.metrics.component_errors_total
 		component_received_bytes_total:       components.sources.internal_metrics.output.metrics.component_received_bytes_total
 		component_received_events_total:      components.sources.internal_metrics.output.metrics.component_received_events_total
 		component_received_event_bytes_total: components.sources.internal_metrics.output.metrics.component_received_event_bytes_total
 		events_in_total:                      components.sources.internal_metrics.output.metrics.events_in_total
+		http_requests_throttled_total:        components.sources.internal_metrics.output.metrics.http_requests_throttled_total
 		processed_bytes_total:                components.sources.internal_metrics.output.metrics.processed_bytes_total
 		request_read_errors_total:            components.sources.internal_metrics.output.metrics.request_read_errors_total
 		requests_received_total:              components.sources.internal_metrics.output.metrics.requests_received_total
@@ -128,6 +128,18 @@ components: sources: http: {
 				examples: ["vector_http_path"]
 			}
 		}
+		pipeline_response: {
+			common: false
+			description: """
+				Whether to answer requests with the response set by a downstream
+				[`respond`](\(urls.vector_transforms)/respond) transform. The source then waits for all the
+				events of a request to be processed by the pipeline, rather than only sent into it, before
+				answering. Requests whose events are not given a response are answered with
+				`200 OK`.
+				"""
+			required: false
+			type: bool: default: false
+		}
 		method: {
 			common:      false
 			description: "Specifies the action of the HTTP request."
@@ -144,7 +156,56 @@ components: sources: http: {
 				}
 			}
 		}
+		routes: {
+			common: false
+			description: """
+				A table of route identifiers to the URL paths served by the route, each with its own decoding.
+				The events of requests to a route are sent to the `<component_id>.<route_id>` output. Routes
+				are matched in order, and `strict_path` applies to their paths as it does to `path`. Requests
+				matching no route are handled by `path`, or rejected with a `404 Not Found` response if they
+				don't match `path` either.
+				"""
+			required: false
+			type: object: {
+				examples: [{"github": {"path": "/webhooks/github", "decoding": {"codec": "json"}}}]
+				options: {
+					"*": {
+						description: "A route."
+						required:    true
+						type: object: options: {
+							path: {
+								description: "The URL path served by the route."
+								required:    true
+								type: string: examples: ["/webhooks/github"]
+							}
+							framing: {
+								description: "The framing of the requests to the route, with the same options as `framing`."
+								required:    false
+								type: object: options: {}
+							}
+							decoding: {
+								description: "The decoding of the requests to the route, with the same options as `decoding`."
+								required:    false
+								type: object: options: {}
+							}
+						}
+					}
+				}
+			}
+		}
 	}
+
+	outputs: [
+		{
+			name:        components._default_output.name
+			description: "Default output stream of the component, receiving the events of requests to `path`."
+		},
+		{
+			name:        "<route_id>"
+			description: "The events of requests to a route of `routes`. Use `<component_id>.<route_id>` as an input to downstream transforms and sinks."
+		},
+	]
+
 	output: logs: {
 		text: {
 			description: "An individual line from a `text/plain` request"
@@ -284,5 +345,14 @@ components: sources: http: {
 				Supported algorithms are `gzip`, `deflate`, and `snappy`.
 				"""
 		}
+		routes: {
+			title: "Routes"
+			body: """
+				A single source can receive several kinds of payloads on the same address, such as the
+				webhooks of different services, by setting `routes`. Each route serves a URL path with its
+				own `framing` and `decoding`, and its events are sent to a named output, so that each kind
+				can be processed separately downstream.
+				"""
+		}
 	}
 }
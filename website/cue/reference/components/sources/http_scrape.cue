@@ -56,6 +56,7 @@ components: sources: http_scrape: {
 			password_example: "${PASSWORD}"
 			username_example: "${USERNAME}"
 		}}
+		coordination: configuration._source_coordination
 		endpoint: {
 			description: "Endpoint to scrape observability data from."
 			required:    true
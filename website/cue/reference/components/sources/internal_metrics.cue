@@ -120,6 +120,46 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _component_tags
 		}
+		auditd_receive_buffer_overruns_total: {
+			description:       "The number of times the kernel dropped audit records because the `auditd` source didn't read them fast enough."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
+		aws_endpoint_requests_served_total: {
+			description:       "The number of requests served by each of the regions, or endpoints, of an AWS sink with failover regions."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags & {
+				endpoint: {
+					description: "The region, or custom endpoint, that served the request."
+					required:    true
+				}
+			}
+		}
+		aws_endpoint_switches_total: {
+			description:       "The number of times an AWS sink with failover regions switched to another region, or endpoint."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags & {
+				from: {
+					description: "The region, or custom endpoint, switched from."
+					required:    true
+				}
+				to: {
+					description: "The region, or custom endpoint, switched to."
+					required:    true
+				}
+				reason: {
+					description: "Why the sink switched."
+					required:    true
+					enum: {
+						failover: "The previous region kept failing."
+						failback: "The primary region is tried again."
+					}
+				}
+			}
+		}
 		aggregate_flushes_total: {
 			description:       "The number of flushes done by the aggregate transform."
 			type:              "counter"
@@ -138,6 +178,30 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _internal_metrics_tags
 		}
+		config_provider_errors_total: {
+			description:       "The total number of errors fetching, verifying, or loading the configuration bundle of a remote provider."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _internal_metrics_tags & {
+				provider: {
+					description: "The type of the configuration provider."
+					required:    true
+					examples: ["http", "aws_s3"]
+				}
+			}
+		}
+		config_provider_version: {
+			description:       "The version of the configuration bundle of a remote provider Vector was last loaded or reloaded with, as set by the top-level `version` of the bundle."
+			type:              "gauge"
+			default_namespace: "vector"
+			tags:              _internal_metrics_tags & {
+				provider: {
+					description: "The type of the configuration provider."
+					required:    true
+					examples: ["http", "aws_s3"]
+				}
+			}
+		}
 		connection_errors_total: {
 			description:       "The total number of connection errors for this Vector instance."
 			type:              "counter"
@@ -174,6 +238,50 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _internal_metrics_tags
 		}
+		enrichment_table_last_reload_timestamp_seconds: {
+			description:       "The time the enrichment table was last loaded, in seconds since the Unix epoch."
+			type:              "gauge"
+			default_namespace: "vector"
+			tags:              _internal_metrics_tags & {
+				table: {
+					description: "The name of the enrichment table."
+					required:    true
+				}
+			}
+		}
+		enrichment_table_reload_errors_total: {
+			description:       "The total number of errors loading the enrichment table. The previously loaded data, if any, is still used."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _internal_metrics_tags & {
+				table: {
+					description: "The name of the enrichment table."
+					required:    true
+				}
+			}
+		}
+		enrichment_table_reloads_total: {
+			description:       "The total number of times the enrichment table has been loaded."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _internal_metrics_tags & {
+				table: {
+					description: "The name of the enrichment table."
+					required:    true
+				}
+			}
+		}
+		enrichment_table_rows: {
+			description:       "The number of rows loaded into the enrichment table, for tables made of rows."
+			type:              "gauge"
+			default_namespace: "vector"
+			tags:              _internal_metrics_tags & {
+				table: {
+					description: "The name of the enrichment table."
+					required:    true
+				}
+			}
+		}
 		quit_total: {
 			description:       "The total number of times the Vector instance has quit."
 			type:              "counter"
@@ -213,6 +321,18 @@ components: sources: internal_metrics: {
 
 		// Metrics emitted by one or more components
 		// Reusable metric definitions
+		adaptive_batch_averaged_latency: {
+			description:       "The average latency of the requests of a partition, used to adjust the size of its batches."
+			type:              "histogram"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
+		adaptive_batch_ratio: {
+			description:       "The fraction of the batch limits that the batches of a partition are limited to."
+			type:              "histogram"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
 		adaptive_concurrency_averaged_rtt: {
 			description:       "The average round-trip time (RTT) for the current window."
 			type:              "histogram"
@@ -257,6 +377,30 @@ components: sources: internal_metrics: {
 				file: _file
 			}
 		}
+		client_rate_limited_requests_total: {
+			description:       "The total number of requests rejected because their client exceeded its rate limit."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags & {
+				client: _client
+			}
+		}
+		client_received_bytes_total: {
+			description:       "The total number of bytes received from an authenticated client, as encoded in its requests before compression."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags & {
+				client: _client
+			}
+		}
+		client_received_events_total: {
+			description:       "The total number of events received from an authenticated client."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags & {
+				client: _client
+			}
+		}
 		collect_completed_total: {
 			description:       "The total number of metrics collections completed for this component."
 			type:              "counter"
@@ -287,6 +431,12 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _component_tags
 		}
+		coordination_group_members: {
+			description:       "The number of instances of Vector in the coordination group of this component."
+			type:              "gauge"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
 		connection_read_errors_total: {
 			description:       "The total number of errors reading datagram."
 			type:              "counter"
@@ -331,6 +481,33 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _component_tags
 		}
+		dead_letter_events_total: {
+			description:       "The total number of events rejected by the downstream service and sent to a dead letter destination instead."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
+		delayed_events: {
+			description:       "The number of events currently held by the `delay` transform."
+			type:              "gauge"
+			default_namespace: "vector"
+			tags: _component_tags & {
+				location: {
+					description: "Where the events are held."
+					required:    true
+					enum: {
+						memory: "The events are held in memory."
+						disk:   "The events are held in the data directory."
+					}
+				}
+			}
+		}
+		delivery_window_open: {
+			description:       "Whether a sink with a `schedule` is in one of its delivery windows, as `1`, or holding events in its buffer, as `0`."
+			type:              "gauge"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
 		decode_errors_total: {
 			description:       "The total number of decode errors seen when decoding data in a source component."
 			type:              "counter"
@@ -484,6 +661,18 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _component_tags
 		}
+		buffer_expired_bytes_total: {
+			description:       "The number of bytes expired by this disk buffer for exceeding its maximum age."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
+		buffer_expired_events_total: {
+			description:       "The number of events expired by this disk buffer for exceeding its maximum age."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
 		buffer_received_event_bytes_total: {
 			description:       "The number of bytes received by this buffer."
 			type:              "counter"
@@ -524,7 +713,7 @@ components: sources: internal_metrics: {
 			}
 		}
 		component_received_bytes_total: {
-			description:       string | *"The number of raw bytes accepted by this component from source origins."
+			description:       string | *"The number of raw bytes accepted by this component from source origins, before they are decompressed or decoded."
 			type:              "counter"
 			default_namespace: "vector"
 			tags:              component_received_events_total.tags
@@ -610,6 +799,17 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              component_received_events_total.tags
 		}
+		component_received_event_estimated_json_bytes_total: {
+			description: """
+				The estimated number of bytes of the events accepted by this component once encoded as
+				JSON, either from tagged origins like file and uri, or cumulatively from other origins.
+				Only reported by sources limited with `limits.bytes_per_second`, which is measured against
+				this estimate.
+				"""
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              component_received_events_total.tags
+		}
 		component_sent_bytes_total: {
 			description:       "The number of raw bytes sent by this component to destination sinks."
 			type:              "counter"
@@ -657,6 +857,33 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _component_tags
 		}
+		ingestion_throttle_delay_seconds: {
+			description:       "The time sources were held back for, each time they reached their `limits`."
+			type:              "histogram"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
+		ingestion_throttled_total: {
+			description:       "The total number of times sources were held back because they reached their `limits`."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
+		internal_events_audit_violations_total: {
+			description:       "The total number of internal events found missing from components by the `internal_events_audit` feature flag. Each missing event of a component is only counted once."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _internal_metrics_tags & {
+				component_kind: _component_kind
+				component_id:   _component_id
+				component_type: _component_type
+				missing_event: {
+					description: "The internal event missing from the component."
+					required:    true
+					examples: ["ComponentEventsSent", "ComponentBytesReceived"]
+				}
+			}
+		}
 		internal_metrics_cardinality: {
 			description:       "The total number of metrics emitted from the internal metrics registry."
 			type:              "gauge"
@@ -717,6 +944,12 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _component_tags
 		}
+		kafka_consumer_pauses_total: {
+			description:       "Total number of times the consumer paused fetching from its assigned partitions due to downstream backpressure."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
 		kafka_consumed_messages_total: {
 			description:       "Total number of messages consumed, not including ignored messages (due to offset, etc), from Kafka brokers."
 			type:              "counter"
@@ -729,6 +962,17 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _component_tags
 		}
+		field_type_conflicts_total: {
+			description:       "The total number of fields whose type conflicted with the type of the field in the existing series."
+			type:              "counter"
+			default_namespace: "vector"
+			tags: _component_tags & {
+				policy: {
+					description: "The policy applied to the conflicting field."
+					required:    true
+				}
+			}
+		}
 		file_delete_errors_total: {
 			description:       "The total number of failures to delete a file. This metric is deprecated in favor of `component_errors_total`."
 			type:              "counter"
@@ -799,12 +1043,43 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _internal_metrics_tags
 		}
+		http_client_error_rtt_seconds: {
+			description:       "The round-trip time (RTT) of HTTP requests that failed without a response, tagged with the error."
+			type:              "histogram"
+			default_namespace: "vector"
+			tags:              _component_tags & {
+				endpoint:   _http_client_endpoint
+				error_kind: _http_client_error_kind
+			}
+		}
+		http_client_errors_total: {
+			description:       "The total number of HTTP requests that failed without a response, tagged with the error."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags & {
+				endpoint:   _http_client_endpoint
+				error_kind: _http_client_error_kind
+			}
+		}
+		http_client_requests_sent_total: {
+			description:       "The total number of HTTP requests sent, tagged with the request method."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags & {
+				endpoint: _http_client_endpoint
+				method: {
+					description: "The HTTP method of the request."
+					required:    true
+				}
+			}
+		}
 		http_client_response_rtt_seconds: {
 			description:       "The round-trip time (RTT) of HTTP requests, tagged with the response code."
 			type:              "histogram"
 			default_namespace: "vector"
 			tags:              _component_tags & {
-				status: _status
+				endpoint: _http_client_endpoint
+				status:   _status
 			}
 		}
 		http_client_responses_total: {
@@ -812,14 +1087,17 @@ components: sources: internal_metrics: {
 			type:              "counter"
 			default_namespace: "vector"
 			tags:              _component_tags & {
-				status: _status
+				endpoint: _http_client_endpoint
+				status:   _status
 			}
 		}
 		http_client_rtt_seconds: {
 			description:       "The round-trip time (RTT) of HTTP requests."
 			type:              "histogram"
 			default_namespace: "vector"
-			tags:              _component_tags
+			tags:              _component_tags & {
+				endpoint: _http_client_endpoint
+			}
 		}
 		http_error_response_total: {
 			description:       "The total number of HTTP error responses for this component."
@@ -839,6 +1117,12 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _component_tags
 		}
+		http_requests_throttled_total: {
+			description:       "The total number of HTTP requests rejected with `503 Service Unavailable` because too many requests were pending."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
 		invalid_record_total: {
 			description:       "The total number of invalid records that have been discarded."
 			type:              "counter"
@@ -884,18 +1168,36 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _component_tags
 		}
+		netflow_missing_template_sets_total: {
+			description:       "The total number of NetFlow v9 or IPFIX data sets skipped because their template had not been received yet."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
 		open_connections: {
 			description:       "The number of current open connections to Vector."
 			type:              "gauge"
 			default_namespace: "vector"
 			tags:              _internal_metrics_tags
 		}
+		oversized_events_truncated_total: {
+			description:       "The total number of events truncated to fit the maximum event size."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
 		parse_errors_total: {
 			description:       "The total number of errors parsing metrics for this component."
 			type:              "counter"
 			default_namespace: "vector"
 			tags:              _internal_metrics_tags
 		}
+		pcap_dropped_packets_total: {
+			description:       "The total number of packets dropped by the kernel or the network interface before the `pcap` source could read them."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
 		processed_bytes_total: {
 			description:       "The number of bytes processed by the component."
 			type:              "counter"
@@ -1074,12 +1376,30 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _component_tags
 		}
+		template_render_fallbacks_total: {
+			description:       "The total number of templates rendered as the fallback value of their `on_render_error` policy, because they failed to render."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
 		timestamp_parse_errors_total: {
 			description:       "The total number of errors encountered parsing [RFC 3339](\(urls.rfc_3339)) timestamps."
 			type:              "counter"
 			default_namespace: "vector"
 			tags:              _component_tags
 		}
+		udp_receive_buffer_bytes: {
+			description:       "The size of the receive buffer of the UDP sockets, as reported by the operating system."
+			type:              "gauge"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
+		udp_receive_buffer_drops_total: {
+			description:       "The total number of datagrams dropped by the operating system because the receive buffer of the UDP sockets was full. Only reported on Linux."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
 		uptime_seconds: {
 			description:       "The total number of seconds the Vector instance has been up."
 			type:              "gauge"
@@ -1296,10 +1616,23 @@ components: sources: internal_metrics: {
 				sending:    "While sending data."
 			}
 		}
+		_client: {
+			description: "The identity of the client, or its IP address for rate limits without authentication."
+			required:    true
+		}
 		_status: {
 			description: "The HTTP status code of the request."
 			required:    false
 		}
+		_http_client_endpoint: {
+			description: "The host, and the port if any, of the endpoint the HTTP request was sent to."
+			required:    true
+			examples: ["logs.example.com", "localhost:8080"]
+		}
+		_http_client_error_kind: {
+			description: "The error that made the HTTP request fail."
+			required:    true
+		}
 		_path: {
 			description: "The path that produced the error."
 			required:    true
@@ -54,6 +54,7 @@ components: sources: journald: {
 				unit:    null
 			}
 		}
+		checkpoint_store: configuration._source_checkpoint_store
 		current_boot_only: {
 			common:      true
 			description: "Include only entries from the current boot."
@@ -64,6 +64,20 @@ components: sources: kafka: {
 				unit: "milliseconds"
 			}
 		}
+		backpressure_pause_ms: {
+			common:      false
+			description: """
+				The amount of time that sending events downstream may be blocked before the consumer pauses fetching
+				from its assigned partitions. Fetching is resumed once the blocked send completes, keeping `librdkafka`'s
+				local queues bounded while the pipeline applies backpressure.
+				"""
+			required: false
+			type: uint: {
+				default: 1000
+				examples: [500, 1000]
+				unit: "milliseconds"
+			}
+		}
 		fetch_wait_max_ms: {
 			common:      false
 			description: "Maximum time the broker may wait to fill the response."
@@ -247,6 +261,7 @@ components: sources: kafka: {
 		kafka_responses_bytes_total:          components.sources.internal_metrics.output.metrics.kafka_responses_bytes_total
 		kafka_produced_messages_total:        components.sources.internal_metrics.output.metrics.kafka_produced_messages_total
 		kafka_produced_messages_bytes_total:  components.sources.internal_metrics.output.metrics.kafka_produced_messages_bytes_total
+		kafka_consumer_pauses_total:          components.sources.internal_metrics.output.metrics.kafka_consumer_pauses_total
 		kafka_consumed_messages_total:        components.sources.internal_metrics.output.metrics.kafka_consumed_messages_total
 		kafka_consumed_messages_bytes_total:  components.sources.internal_metrics.output.metrics.kafka_consumed_messages_bytes_total
 		processed_bytes_total:                components.sources.internal_metrics.output.metrics.processed_bytes_total
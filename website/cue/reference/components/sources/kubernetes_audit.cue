@@ -0,0 +1,127 @@
+package metadata
+
+components: sources: kubernetes_audit: {
+	_port: 8443
+
+	title: "Kubernetes Audit"
+
+	description: """
+		Receives the audit events sent by the webhook backend of the Kubernetes API server.
+		"""
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		deployment_roles: ["aggregator"]
+		development:   "alpha"
+		egress_method: "batch"
+		stateful:      false
+	}
+
+	features: {
+		acknowledgements: true
+		multiline: enabled: false
+		receive: {
+			from: {
+				service: services.kubernetes
+
+				interface: socket: {
+					api: {
+						title: "Audit webhook backend"
+						url:   urls.kubernetes_audit_webhook
+					}
+					direction: "incoming"
+					port:      _port
+					protocols: ["http"]
+					ssl: "optional"
+				}
+			}
+
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				enabled_default:        false
+			}
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: [
+			"""
+				This component is in alpha, and can only be used with the `alpha_components` feature flag
+				enabled.
+				""",
+		]
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: {
+		acknowledgements: configuration._source_acknowledgements
+		address:          sources.http.configuration.address
+		auth:             sources.http.configuration.auth
+		path: {
+			common:      false
+			description: "The URL path on which the API server sends audit events."
+			required:    false
+			type: string: {
+				default: "/"
+				examples: ["/audit"]
+			}
+		}
+	}
+
+	output: logs: event: {
+		description: "An audit event of a batch sent by the API server. The fields of the audit event, such as `verb`, `user`, and `objectRef`, are placed at the root of the event."
+		fields: {
+			source_type: {
+				description: "The name of the source type."
+				required:    true
+				type: string: {
+					examples: ["kubernetes_audit"]
+				}
+			}
+			timestamp: {
+				description: "The `stageTimestamp` of the audit event, or its `requestReceivedTimestamp` if it has none, or the time the batch was received at if it has neither."
+				required:    true
+				type: timestamp: {}
+			}
+		}
+	}
+
+	how_it_works: {
+		batches: {
+			title: "Audit event batches"
+			body: """
+				The API server sends the audit events in batches, as `EventList` objects of the
+				`audit.k8s.io/v1` or `audit.k8s.io/v1beta1` API versions. Each item of a batch
+				becomes an event. Requests that aren't such batches are answered with a
+				`400 Bad Request` status, as sending them again wouldn't succeed.
+
+				The API server retries batches answered with any other error. With
+				acknowledgements enabled, a batch is only answered once its events are delivered,
+				so that the API server sends it again if they can't be.
+				"""
+		}
+		webhook_configuration: {
+			title: "Webhook configuration"
+			body: """
+				The API server reads the address of the source from the kubeconfig file passed to
+				its `--audit-webhook-config-file` flag, whose `server` is the address and `path` the
+				source listens on. The source doesn't need to write the audit log to disk, so the
+				`--audit-log-path` flag can be left unset.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_errors_total:               components.sources.internal_metrics.output.metrics.component_errors_total
+		component_received_bytes_total:       components.sources.internal_metrics.output.metrics.component_received_bytes_total
+		component_received_events_total:      components.sources.internal_metrics.output.metrics.component_received_events_total
+		component_received_event_bytes_total: components.sources.internal_metrics.output.metrics.component_received_event_bytes_total
+	}
+}
@@ -326,7 +326,8 @@ components: sources: kubernetes_logs: {
 				unit:    "milliseconds"
 			}
 		}
-		timezone: configuration._timezone
+		checkpoint_store: configuration._source_checkpoint_store
+		timezone:         configuration._timezone
 	}
 
 	output: logs: line: {
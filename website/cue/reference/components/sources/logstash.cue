@@ -69,6 +69,17 @@ components: sources: logstash: {
 				unit:    "concurrency"
 			}
 		}
+		move_timestamp: {
+			common: false
+			description: """
+				Whether to move the `@timestamp` field of events to their timestamp. By default,
+				`@timestamp` is kept, and only parsed for events without a timestamp. Elastic Beats,
+				such as Filebeat or Winlogbeat, always set it, so enabling this avoids keeping the
+				time of their events twice.
+				"""
+			required: false
+			type: bool: default: false
+		}
 	}
 
 	output: logs: line: {
@@ -89,6 +100,9 @@ components: sources: logstash: {
 					2. The `@timestamp` field on the event if it can be parsed as a timestamp
 					3. The current timestamp
 
+					With `move_timestamp` enabled, the `@timestamp` field is removed from the event
+					in any case.
+
 					The assigned field, `timestamp`, could be different depending if you have configured
 					`log_schema.timestamp_key`.
 					"""
@@ -301,6 +315,20 @@ components: sources: logstash: {
 		beats_configuration: {
 			title: "Elastic Beats configuration"
 			body: """
+				There's no separate source for [Elastic Beats](\(urls.elastic_beats)), such as Filebeat or
+				Winlogbeat: they speak the same [Lumberjack v2 protocol](\(urls.logstash_beats_protocol)) as
+				Logstash, so this source receives their events, and acknowledges them once delivered when
+				`acknowledgements` are enabled. Enable `move_timestamp` so their `@timestamp` becomes the
+				timestamp of their events rather than being kept twice:
+
+				```toml
+				[sources.beats]
+				type = "logstash"
+				address = "0.0.0.0:5044"
+				move_timestamp = true
+				acknowledgements.enabled = true
+				```
+
 				To configure one of the Elastic Beats agents to forward to a Vector instance, you can use the following
 				output configuration:
 
@@ -0,0 +1,190 @@
+package metadata
+
+components: sources: netflow: {
+	_port: 2055
+
+	title: "NetFlow"
+
+	description: """
+		Collects flow records exported by routers, switches, and probes using
+		[NetFlow](\(urls.netflow)) v5, NetFlow v9, or [IPFIX](\(urls.ipfix)).
+		"""
+
+	classes: {
+		commonly_used: false
+		delivery:      "best_effort"
+		deployment_roles: ["aggregator"]
+		development:   "alpha"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	features: {
+		acknowledgements: false
+		multiline: enabled: false
+		receive: {
+			from: {
+				service: services.netflow
+
+				interface: socket: {
+					direction: "incoming"
+					port:      _port
+					protocols: ["udp"]
+					ssl: "disabled"
+				}
+			}
+			receive_buffer_bytes: enabled: true
+			keepalive: enabled:            false
+			tls: enabled:                  false
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: [
+			"""
+				This component is in alpha, and can only be used with the `alpha_components` feature flag
+				enabled.
+				""",
+		]
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: {
+		address: {
+			description: """
+				The UDP address to listen for flow datagrams on. It _must_ include a port.
+				"""
+			required: true
+			type: string: {
+				examples: ["0.0.0.0:\(_port)"]
+			}
+		}
+		max_length: {
+			common:      false
+			description: "The maximum size, in bytes, of incoming datagrams. Larger datagrams are discarded."
+			required:    false
+			type: uint: {
+				default: 102400
+				unit:    "bytes"
+			}
+		}
+		max_templates: {
+			common:      false
+			description: "The maximum number of NetFlow v9 and IPFIX templates to keep, across all exporters. Once reached, the templates received the longest ago are forgotten first."
+			required:    false
+			type: uint: {
+				default: 10000
+				unit:    null
+			}
+		}
+	}
+
+	output: logs: flow: {
+		description: """
+			A single flow or options record. Fields of NetFlow v9 and IPFIX records are named after
+			their IANA information element, converted to snake case, such as `octet_delta_count` or
+			`source_ipv4_address`. Unknown fields are named `field_<id>`, and enterprise-specific
+			fields are named `enterprise_<enterprise number>_<id>`.
+			"""
+		fields: {
+			exporter: {
+				description: "The IP address of the exporter that sent the record."
+				required:    true
+				type: string: {
+					examples: ["192.0.2.1"]
+				}
+			}
+			export_time: {
+				description: "The time at which the exporter sent the datagram containing the record."
+				required:    true
+				type: timestamp: {}
+			}
+			flow_type: {
+				description: "The protocol the record was exported with."
+				required:    true
+				type: string: {
+					enum: {
+						netflow_v5: "NetFlow version 5."
+						netflow_v9: "NetFlow version 9."
+						ipfix:      "IPFIX, also known as NetFlow version 10."
+					}
+				}
+			}
+			observation_domain_id: {
+				description: "The observation domain of the exporter, for IPFIX records."
+				required:    false
+				type: uint: {
+					examples: [1]
+					unit: null
+				}
+			}
+			record_type: {
+				description: "Whether the record describes a flow or carries exporter options."
+				required:    true
+				type: string: {
+					enum: {
+						flow:    "A flow record."
+						options: "An options record, describing the exporter itself."
+					}
+				}
+			}
+			sequence: {
+				description: "The sequence number of the datagram containing the record."
+				required:    true
+				type: uint: {
+					examples: [42]
+					unit: null
+				}
+			}
+			source_id: {
+				description: "The source ID of the exporter, for NetFlow v9 records."
+				required:    false
+				type: uint: {
+					examples: [1]
+					unit: null
+				}
+			}
+			source_type: {
+				description: "The name of the source type."
+				required:    true
+				type: string: {
+					examples: ["netflow"]
+				}
+			}
+			template_id: {
+				description: "The ID of the template the record was decoded with, for NetFlow v9 and IPFIX records."
+				required:    false
+				type: uint: {
+					examples: [256]
+					unit: null
+				}
+			}
+			timestamp: fields._current_timestamp
+		}
+	}
+
+	how_it_works: {
+		templates: {
+			title: "Templates"
+			body: """
+				NetFlow v9 and IPFIX exporters periodically send templates describing the layout of
+				their data records. Templates are cached per exporter address and source ID or
+				observation domain. Data records received before their template are skipped and
+				counted in the `netflow_missing_template_sets_total` metric.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_errors_total:               components.sources.internal_metrics.output.metrics.component_errors_total
+		component_received_bytes_total:       components.sources.internal_metrics.output.metrics.component_received_bytes_total
+		component_received_events_total:      components.sources.internal_metrics.output.metrics.component_received_events_total
+		component_received_event_bytes_total: components.sources.internal_metrics.output.metrics.component_received_event_bytes_total
+		netflow_missing_template_sets_total:  components.sources.internal_metrics.output.metrics.netflow_missing_template_sets_total
+	}
+}
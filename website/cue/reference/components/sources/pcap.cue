@@ -0,0 +1,216 @@
+package metadata
+
+components: sources: pcap: {
+	title: "Packet Capture"
+
+	description: """
+		Captures packets on a network interface using [libpcap](\(urls.libpcap)), optionally
+		selected with a [BPF filter](\(urls.bpf_filter)), and emits the metadata of each packet,
+		optionally followed by the start of its payload.
+		"""
+
+	classes: {
+		commonly_used: false
+		delivery:      "best_effort"
+		deployment_roles: ["daemon"]
+		development:   "alpha"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	features: {
+		acknowledgements: false
+		collect: {
+			checkpoint: enabled: false
+			from: {
+				service: services.libpcap
+
+				interface: ffi: {}
+			}
+		}
+		multiline: enabled: false
+	}
+
+	support: {
+		targets: {
+			"x86_64-pc-windows-msv": false
+		}
+
+		requirements: [
+			"""
+				Vector must be built with the `sources-pcap` feature and linked against libpcap, which
+				is not part of the default feature set.
+				""",
+			"""
+				Capturing packets requires the `CAP_NET_RAW` capability on Linux, or running Vector
+				as root. Promiscuous mode additionally requires `CAP_NET_ADMIN`.
+				""",
+		]
+		warnings: [
+			"""
+				Capturing all traffic on a busy interface can be expensive. Use a `filter` to narrow
+				the captured packets down to the ones of interest.
+				""",
+		]
+		notices: [
+			"""
+				This component is in alpha, and can only be used with the `alpha_components` feature flag
+				enabled.
+				""",
+		]
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: {
+		buffer_size_bytes: {
+			common:      false
+			description: "The size, in bytes, of the kernel buffer holding captured packets until they are read."
+			required:    false
+			type: uint: {
+				default: null
+				unit:    "bytes"
+			}
+		}
+		filter: {
+			common:      true
+			description: "A [BPF filter](\(urls.bpf_filter)) expression, in `tcpdump` syntax, selecting the packets to capture. All packets are captured if not set."
+			required:    false
+			type: string: {
+				default: null
+				examples: ["tcp port 22", "udp and not port 53"]
+			}
+		}
+		interface: {
+			description: "The name of the network interface to capture packets on. On Linux, `any` captures packets on all interfaces."
+			required:    true
+			type: string: {
+				examples: ["eth0", "any"]
+			}
+		}
+		max_payload_bytes: {
+			common:      false
+			description: "The maximum number of bytes of the packet payload, following the transport header, to include in each event. Only the packet metadata is included if not set."
+			required:    false
+			type: uint: {
+				default: null
+				unit:    "bytes"
+			}
+		}
+		promiscuous: {
+			common:      false
+			description: "Whether or not to put the interface into promiscuous mode."
+			required:    false
+			type: bool: default: false
+		}
+	}
+
+	output: logs: packet: {
+		description: "The metadata of a single captured packet. Header fields are only present if the headers were captured and could be decoded."
+		fields: {
+			captured_length: {
+				description: "The number of bytes of the packet that were captured."
+				required:    true
+				type: uint: {
+					examples: [128]
+					unit: "bytes"
+				}
+			}
+			destination_address: {
+				description: "The destination IP address."
+				required:    false
+				type: string: {
+					examples: ["192.0.2.2"]
+				}
+			}
+			destination_port: {
+				description: "The destination port, for TCP and UDP packets."
+				required:    false
+				type: uint: {
+					examples: [443]
+					unit: null
+				}
+			}
+			interface: {
+				description: "The interface the packet was captured on, as configured."
+				required:    true
+				type: string: {
+					examples: ["eth0"]
+				}
+			}
+			length: {
+				description: "The length of the packet on the wire."
+				required:    true
+				type: uint: {
+					examples: [1500]
+					unit: "bytes"
+				}
+			}
+			payload: {
+				description: "The start of the transport payload, when `max_payload_bytes` is set."
+				required:    false
+				type: string: {
+					examples: ["GET / HTTP/1.1"]
+				}
+			}
+			protocol: {
+				description: "The IP protocol number of the transport layer, such as `6` for TCP or `17` for UDP."
+				required:    false
+				type: uint: {
+					examples: [6, 17]
+					unit: null
+				}
+			}
+			source_address: {
+				description: "The source IP address."
+				required:    false
+				type: string: {
+					examples: ["192.0.2.1"]
+				}
+			}
+			source_mac: {
+				description: "The source MAC address, for Ethernet captures."
+				required:    false
+				type: string: {
+					examples: ["00:66:77:88:99:aa"]
+				}
+			}
+			source_port: {
+				description: "The source port, for TCP and UDP packets."
+				required:    false
+				type: uint: {
+					examples: [54321]
+					unit: null
+				}
+			}
+			source_type: {
+				description: "The name of the source type."
+				required:    true
+				type: string: {
+					examples: ["pcap"]
+				}
+			}
+			tcp_flags: {
+				description: "The TCP flags, for TCP packets."
+				required:    false
+				type: uint: {
+					examples: [18]
+					unit: null
+				}
+			}
+			timestamp: fields._current_timestamp & {
+				description: "The time at which the packet was captured."
+			}
+		}
+	}
+
+	telemetry: metrics: {
+		component_errors_total:               components.sources.internal_metrics.output.metrics.component_errors_total
+		component_received_bytes_total:       components.sources.internal_metrics.output.metrics.component_received_bytes_total
+		component_received_events_total:      components.sources.internal_metrics.output.metrics.component_received_events_total
+		component_received_event_bytes_total: components.sources.internal_metrics.output.metrics.component_received_event_bytes_total
+		pcap_dropped_packets_total:           components.sources.internal_metrics.output.metrics.pcap_dropped_packets_total
+	}
+}
@@ -148,6 +148,7 @@ components: sources: prometheus_scrape: {
 			password_example: "${PROMETHEUS_PASSWORD}"
 			username_example: "${PROMETHEUS_USERNAME}"
 		}}
+		coordination: configuration._source_coordination
 	}
 
 	output: metrics: {
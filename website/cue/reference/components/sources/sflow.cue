@@ -0,0 +1,156 @@
+package metadata
+
+components: sources: sflow: {
+	_port: 6343
+
+	title: "sFlow"
+
+	description: """
+		Collects flow and counter samples exported by switches and routers using
+		[sFlow](\(urls.sflow)) version 5.
+		"""
+
+	classes: {
+		commonly_used: false
+		delivery:      "best_effort"
+		deployment_roles: ["aggregator"]
+		development:   "alpha"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	features: {
+		acknowledgements: false
+		multiline: enabled: false
+		receive: {
+			from: {
+				service: services.sflow
+
+				interface: socket: {
+					direction: "incoming"
+					port:      _port
+					protocols: ["udp"]
+					ssl: "disabled"
+				}
+			}
+			receive_buffer_bytes: enabled: true
+			keepalive: enabled:            false
+			tls: enabled:                  false
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: [
+			"""
+				This component is in alpha, and can only be used with the `alpha_components` feature flag
+				enabled.
+				""",
+		]
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: {
+		address: {
+			description: """
+				The UDP address to listen for sFlow datagrams on. It _must_ include a port.
+				"""
+			required: true
+			type: string: {
+				examples: ["0.0.0.0:\(_port)"]
+			}
+		}
+		max_length: {
+			common:      false
+			description: "The maximum size, in bytes, of incoming datagrams. Larger datagrams are discarded."
+			required:    false
+			type: uint: {
+				default: 102400
+				unit:    "bytes"
+			}
+		}
+	}
+
+	output: logs: sample: {
+		description: "A single flow or counter sample."
+		fields: {
+			agent_address: {
+				description: "The address of the agent that took the sample, as reported in the datagram."
+				required:    true
+				type: string: {
+					examples: ["192.0.2.10"]
+				}
+			}
+			datagram_sequence: {
+				description: "The sequence number of the datagram containing the sample."
+				required:    true
+				type: uint: {
+					examples: [17]
+					unit: null
+				}
+			}
+			ethernet: {
+				description: "Ethernet interface counters, for counter samples."
+				required:    false
+				type: object: {}
+			}
+			interface: {
+				description: "Generic interface counters, such as `in_octets` and `out_errors`, for counter samples."
+				required:    false
+				type: object: {}
+			}
+			packet: {
+				description: "The decoded headers of the sampled packet, such as addresses, ports, and protocol, for flow samples."
+				required:    false
+				type: object: {}
+			}
+			router: {
+				description: "Extended router data, such as the next hop, for flow samples."
+				required:    false
+				type: object: {}
+			}
+			sample_type: {
+				description: "The kind of sample."
+				required:    true
+				type: string: {
+					enum: {
+						counter: "A counter sample."
+						flow:    "A flow sample, describing a single sampled packet."
+					}
+				}
+			}
+			sampling_rate: {
+				description: "The rate at which packets were sampled, for flow samples."
+				required:    false
+				type: uint: {
+					examples: [1024]
+					unit: null
+				}
+			}
+			source_type: {
+				description: "The name of the source type."
+				required:    true
+				type: string: {
+					examples: ["sflow"]
+				}
+			}
+			switch: {
+				description: "Extended switch data, such as source and destination VLANs, for flow samples."
+				required:    false
+				type: object: {}
+			}
+			timestamp: fields._current_timestamp
+		}
+	}
+
+	telemetry: metrics: {
+		component_errors_total:               components.sources.internal_metrics.output.metrics.component_errors_total
+		component_received_bytes_total:       components.sources.internal_metrics.output.metrics.component_received_bytes_total
+		component_received_events_total:      components.sources.internal_metrics.output.metrics.component_received_events_total
+		component_received_event_bytes_total: components.sources.internal_metrics.output.metrics.component_received_event_bytes_total
+	}
+}
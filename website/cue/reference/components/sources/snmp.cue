@@ -0,0 +1,320 @@
+package metadata
+
+components: sources: snmp: {
+	title: "SNMP"
+
+	description: """
+		Polls the objects of [SNMP](\(urls.snmp)) agents, such as the counters of
+		the interfaces of switches and routers, and converts them into metrics.
+		"""
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		deployment_roles: ["daemon", "aggregator"]
+		development:   "alpha"
+		egress_method: "batch"
+		stateful:      false
+	}
+
+	features: {
+		acknowledgements: false
+		collect: {
+			checkpoint: enabled: false
+			from: {
+				service: services.snmp
+
+				interface: socket: {
+					direction: "outgoing"
+					port:      161
+					protocols: ["udp"]
+					ssl: "disabled"
+				}
+			}
+		}
+		multiline: enabled: false
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: [
+			"""
+				This component is in alpha, and can only be used with the `alpha_components` feature flag
+				enabled.
+				""",
+		]
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: {
+		endpoints: {
+			description: "The agents to poll, as host names or IP addresses optionally followed by a port, which defaults to `161`."
+			required:    true
+			type: array: {
+				items: type: string: {
+					examples: ["192.0.2.1", "switch.example.com:1161"]
+				}
+			}
+		}
+		scrape_interval_secs: {
+			description: "The interval between polls."
+			common:      true
+			required:    false
+			type: uint: {
+				default: 15
+				unit:    "seconds"
+			}
+		}
+		timeout_secs: {
+			description: "The time to wait for the response to a request before sending it again."
+			common:      false
+			required:    false
+			type: float: {
+				default: 5.0
+			}
+		}
+		retries: {
+			description: "The number of times a request is sent again when no response is received."
+			common:      false
+			required:    false
+			type: uint: {
+				default: 1
+				unit:    null
+			}
+		}
+		max_repetitions: {
+			description: "The most objects returned by each `GetBulk` request of walks."
+			common:      false
+			required:    false
+			type: uint: {
+				default: 25
+				unit:    null
+			}
+		}
+		namespace: {
+			description: "The namespace of metrics. Disabled if empty."
+			common:      false
+			required:    false
+			type: string: {
+				default: "snmp"
+			}
+		}
+		auth: {
+			description: "The version of SNMP, and how requests are authenticated."
+			common:      true
+			required:    false
+			type: object: options: {
+				version: {
+					description: "The version of SNMP."
+					required:    false
+					type: string: {
+						default: "v2c"
+						enum: {
+							v2c: "SNMPv2c, whose requests carry a community."
+							v3:  "SNMPv3, whose requests are authenticated, and optionally encrypted, by the [user-based security model](\(urls.snmp_usm))."
+						}
+					}
+				}
+				community: {
+					description:   "The community of the requests."
+					relevant_when: "version = \"v2c\""
+					required:      false
+					type: string: {
+						default: "public"
+					}
+				}
+				username: {
+					description:   "The name of the user."
+					relevant_when: "version = \"v3\""
+					required:      true
+					type: string: {
+						examples: ["vector"]
+					}
+				}
+				auth_protocol: {
+					description:   "The protocol authenticating the requests."
+					relevant_when: "version = \"v3\""
+					required:      false
+					type: string: {
+						default: "sha"
+						enum: {
+							md5: "HMAC-MD5-96."
+							sha: "HMAC-SHA-96."
+						}
+					}
+				}
+				auth_password: {
+					description:   "The password authenticating the requests, of at least 8 characters. Requests are neither authenticated nor encrypted if unset."
+					relevant_when: "version = \"v3\""
+					required:      false
+					type: string: {
+						examples: ["${SNMP_AUTH_PASSWORD}"]
+					}
+				}
+				privacy_protocol: {
+					description:   "The protocol encrypting the requests."
+					relevant_when: "version = \"v3\""
+					required:      false
+					type: string: {
+						default: "aes"
+						enum: {
+							des: "DES in CBC mode."
+							aes: "AES-128 in CFB mode."
+						}
+					}
+				}
+				privacy_password: {
+					description:   "The password encrypting the requests, of at least 8 characters, which requires `auth_password`. Requests are not encrypted if unset."
+					relevant_when: "version = \"v3\""
+					required:      false
+					type: string: {
+						examples: ["${SNMP_PRIVACY_PASSWORD}"]
+					}
+				}
+				context_name: {
+					description:   "The context of the requests, for agents exposing several contexts."
+					relevant_when: "version = \"v3\""
+					required:      false
+					type: string: {
+						default: ""
+					}
+				}
+			}
+		}
+		metrics: {
+			description: "The metrics polled from each agent."
+			required:    true
+			type: array: items: type: object: options: {
+				oid: {
+					description: """
+						The object polled, either as a numeric OID or as the name of an object of a
+						common MIB, optionally qualified by its module. Named scalars are polled with a
+						`Get` of their instance, and named columns of tables are walked. The objects of
+						`SNMPv2-MIB::system`, the interface tables of `IF-MIB`, and the storage and
+						processor tables of `HOST-RESOURCES-MIB` are known by name.
+						"""
+					required: true
+					type: string: {
+						examples: ["1.3.6.1.2.1.1.3.0", "sysUpTime", "IF-MIB::ifHCInOctets"]
+					}
+				}
+				name: {
+					description: "The name of the metric. Defaults to the name of the object, for named objects."
+					required:    false
+					type: string: {
+						examples: ["cisco_cpu_load_5min"]
+					}
+				}
+				walk: {
+					description: "Whether the descendants of a numeric OID, such as the entries of a column of a table, are walked rather than the OID polled with a `Get`."
+					required:    false
+					type: bool: default: false
+				}
+				kind: {
+					description: "The type of the metric. By default, `Counter32` and `Counter64` objects are counters, and other objects are gauges."
+					required:    false
+					type: string: {
+						enum: {
+							counter: "A counter."
+							gauge:   "A gauge."
+						}
+					}
+				}
+				indexes: {
+					description: """
+						The labels decoded from the index of each walked object, in order. Defaults to
+						the indexes of the table of named columns, and otherwise to an `index` label
+						with the whole index.
+						"""
+					required: false
+					type: array: items: type: object: options: {
+						label: {
+							description: "The name of the label."
+							required:    true
+							type: string: examples: ["ifIndex"]
+						}
+						type: {
+							description: "How the index is encoded in the OID."
+							required:    false
+							type: string: {
+								default: "integer"
+								enum: {
+									integer:    "A single integer."
+									ip_address: "An IPv4 address, as four sub-identifiers."
+									string:     "A string, as its length followed by each of its bytes."
+								}
+							}
+						}
+					}
+				}
+				lookups: {
+					description: "Labels set to the value of other columns of the table at the index of each walked object."
+					required:    false
+					type: array: items: type: object: options: {
+						label: {
+							description: "The name of the label."
+							required:    true
+							type: string: examples: ["ifName"]
+						}
+						oid: {
+							description: "The column whose value the label is set to, as a numeric OID or the name of an object."
+							required:    true
+							type: string: examples: ["IF-MIB::ifName"]
+						}
+					}
+				}
+			}
+		}
+	}
+
+	how_it_works: {
+		metric_values: {
+			title: "Metric values"
+			body: """
+				Numeric objects become metrics of the same value, and objects that aren't
+				numbers, such as `sysDescr`, become a gauge of `1` with a `value` tag set to
+				the object. Strings that aren't printable, such as MAC addresses, are written
+				in hexadecimal.
+				"""
+		}
+		walks: {
+			title: "Walks"
+			body: """
+				Columns of tables are walked with `GetBulk` requests, and produce a metric for
+				each entry, tagged with the labels decoded from its index and with the values of
+				the `lookups` at the same index. Polls of agents which fail produce no metrics
+				other than `up`, whose value is then `0`.
+				"""
+		}
+	}
+
+	output: metrics: {
+		_snmp_metrics_tags: {
+			host: {
+				description: "The endpoint of the agent."
+				required:    true
+				examples: ["192.0.2.1"]
+			}
+		}
+
+		up: {
+			description:       "If the agent responded to the last poll or not."
+			type:              "gauge"
+			default_namespace: "snmp"
+			tags:              _snmp_metrics_tags
+		}
+	}
+
+	telemetry: metrics: {
+		collect_completed_total:              components.sources.internal_metrics.output.metrics.collect_completed_total
+		collect_duration_seconds:             components.sources.internal_metrics.output.metrics.collect_duration_seconds
+		component_errors_total:               components.sources.internal_metrics.output.metrics.component_errors_total
+		component_received_bytes_total:       components.sources.internal_metrics.output.metrics.component_received_bytes_total
+		component_received_events_total:      components.sources.internal_metrics.output.metrics.component_received_events_total
+		component_received_event_bytes_total: components.sources.internal_metrics.output.metrics.component_received_event_bytes_total
+	}
+}
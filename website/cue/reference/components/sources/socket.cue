@@ -27,7 +27,7 @@ components: sources: socket: {
 				interface: socket: {
 					direction: "incoming"
 					port:      _port
-					protocols: ["tcp", "unix_datagram", "unix_stream", "udp"]
+					protocols: ["tcp", "unix_datagram", "unix_seqpacket", "unix_stream", "udp"]
 					ssl: "optional"
 				}
 			}
@@ -103,17 +103,22 @@ components: sources: socket: {
 				enum: {
 					tcp:           "TCP socket."
 					udp:           "UDP socket."
-					unix_datagram: "Unix domain datagram socket."
-					unix_stream:   "Unix domain stream socket."
+					unix_datagram:  "Unix domain datagram socket."
+					unix_seqpacket: "Unix domain sequenced packet socket. Each packet is a message, received over a connection."
+					unix_stream:    "Unix domain stream socket."
 				}
 			}
 		}
 		path: {
-			description:   "The unix socket path. *This should be an absolute path*."
-			relevant_when: "mode = `unix_datagram` or `unix_stream`"
+			description: """
+				The unix socket path. *This should be an absolute path*.
+				On Linux, a path starting with `@` designates an address in the abstract namespace,
+				for which no socket file is created.
+				"""
+			relevant_when: "mode = `unix_datagram`, `unix_seqpacket` or `unix_stream`"
 			required:      true
 			type: string: {
-				examples: ["/path/to/socket"]
+				examples: ["/path/to/socket", "@vector"]
 			}
 		}
 		socket_file_mode: {
@@ -124,7 +129,7 @@ components: sources: socket: {
 				Note that the file mode value can be specified in any numeric format
 				supported by your configuration language, but it is most intuitive to use an octal number.
 				"""
-			relevant_when: "mode = `unix_datagram` or `unix_stream`"
+			relevant_when: "mode = `unix_datagram`, `unix_seqpacket` or `unix_stream`"
 			required:      false
 			type: uint: {
 				default: null
@@ -132,6 +137,29 @@ components: sources: socket: {
 				examples: [0o777, 0o600, 508]
 			}
 		}
+		socket_file_owner: {
+			common: false
+			description: """
+				The user owning the unix socket file, as a user name or a numeric user ID.
+				Changing the owner of the file usually requires Vector to run as root.
+				"""
+			relevant_when: "mode = `unix_datagram`, `unix_seqpacket` or `unix_stream`"
+			required:      false
+			type: string: {
+				default: null
+				examples: ["vector", "1000"]
+			}
+		}
+		socket_file_group: {
+			common:        false
+			description:   "The group owning the unix socket file, as a group name or a numeric group ID."
+			relevant_when: "mode = `unix_datagram`, `unix_seqpacket` or `unix_stream`"
+			required:      false
+			type: string: {
+				default: null
+				examples: ["vector", "1000"]
+			}
+		}
 		shutdown_timeout_secs: {
 			common:        false
 			description:   "The timeout before a connection is forcefully closed during shutdown."
@@ -152,6 +180,21 @@ components: sources: socket: {
 				unit:    "concurrency"
 			}
 		}
+		sockets: {
+			common:      false
+			description: """
+				The number of sockets to listen on, each received on by its own task. When more than one,
+				the sockets share the address with `SO_REUSEPORT`, and the operating system spreads the
+				incoming messages across them. On Linux, the messages of a given peer are always received
+				by the same socket. Only supported on Unix.
+				"""
+			relevant_when: "mode = `udp`"
+			required:      false
+			type: uint: {
+				default: 1
+				unit:    null
+			}
+		}
 	}
 
 	output: logs: line: {
@@ -220,5 +263,7 @@ components: sources: socket: {
 		component_received_bytes_total:       components.sources.internal_metrics.output.metrics.component_received_bytes_total
 		component_received_event_bytes_total: components.sources.internal_metrics.output.metrics.component_received_event_bytes_total
 		component_received_events_total:      components.sources.internal_metrics.output.metrics.component_received_events_total
+		udp_receive_buffer_bytes:             components.sources.internal_metrics.output.metrics.udp_receive_buffer_bytes
+		udp_receive_buffer_drops_total:       components.sources.internal_metrics.output.metrics.udp_receive_buffer_drops_total
 	}
 }
@@ -113,6 +113,21 @@ components: sources: statsd: {
 	}
 
 	how_it_works: {
+		sockets: {
+			common:      false
+			description: """
+				The number of sockets to listen on, each received on by its own task. When more than one,
+				the sockets share the address with `SO_REUSEPORT`, and the operating system spreads the
+				incoming messages across them. On Linux, the messages of a given peer are always received
+				by the same socket. Only supported on Unix.
+				"""
+			relevant_when: "mode = `udp`"
+			required:      false
+			type: uint: {
+				default: 1
+				unit:    null
+			}
+		}
 		timestamps: {
 			title: "Timestamps"
 			body:  """
@@ -138,5 +153,7 @@ components: sources: statsd: {
 		component_received_bytes_total:       components.sources.internal_metrics.output.metrics.component_received_bytes_total
 		component_received_events_total:      components.sources.internal_metrics.output.metrics.component_received_events_total
 		component_received_event_bytes_total: components.sources.internal_metrics.output.metrics.component_received_event_bytes_total
+		udp_receive_buffer_bytes:             components.sources.internal_metrics.output.metrics.udp_receive_buffer_bytes
+		udp_receive_buffer_drops_total:       components.sources.internal_metrics.output.metrics.udp_receive_buffer_drops_total
 	}
 }
@@ -57,6 +57,20 @@ components: sources: syslog: {
 				examples: ["0.0.0.0:\(_port)", "systemd", "systemd#3"]
 			}
 		}
+		framing: {
+			common:        false
+			description:   "The framing of the messages received over TCP or Unix domain socket connections."
+			relevant_when: "mode = `tcp` or mode = `unix`"
+			required:      false
+			type: string: {
+				default: "auto"
+				enum: {
+					auto:              "The framing is detected from the first message of each connection. See [Framing](#framing)."
+					octet_counting:    "Messages are prefixed with their length, as specified in [RFC 6587](\(urls.syslog_6587))."
+					newline_delimited: "Messages are delimited by newlines."
+				}
+			}
+		}
 		host_key: {
 			category:    "Context"
 			common:      false
@@ -108,6 +122,21 @@ components: sources: syslog: {
 				unit:    "concurrency"
 			}
 		}
+		sockets: {
+			common:      false
+			description: """
+				The number of sockets to listen on, each received on by its own task. When more than one,
+				the sockets share the address with `SO_REUSEPORT`, and the operating system spreads the
+				incoming messages across them. On Linux, the messages of a given peer are always received
+				by the same socket. Only supported on Unix.
+				"""
+			relevant_when: "mode = `udp`"
+			required:      false
+			type: uint: {
+				default: 1
+				unit:    null
+			}
+		}
 	}
 
 	output: logs: line: {
@@ -246,6 +275,22 @@ components: sources: syslog: {
 				"""
 		}
 
+		framing: {
+			title: "Framing"
+			body:  """
+				Messages received over TCP or Unix domain socket connections are either octet-counted,
+				that is prefixed with their length as specified in [RFC 6587](\(urls.syslog_6587)), or
+				delimited by newlines. By default, the framing is detected independently for each
+				connection: connections whose first message starts with a non-zero digit are
+				octet-counted, and other connections are newline-delimited. This allows senders using
+				either framing to send to the same source.
+
+				The detected framing is kept for the whole connection, so that newline-delimited messages
+				starting with a digit aren't mistaken for octet counts. Set the `framing` option to
+				`octet_counting` or `newline_delimited` to force either framing.
+				"""
+		}
+
 		parsing: {
 			title: "Parsing"
 			body:  """
@@ -273,5 +318,7 @@ components: sources: syslog: {
 		component_received_bytes_total:  components.sources.internal_metrics.output.metrics.component_received_bytes_total
 		component_received_events_total: components.sources.internal_metrics.output.metrics.component_received_events_total
 		utf8_convert_errors_total:       components.sources.internal_metrics.output.metrics.utf8_convert_errors_total
+		udp_receive_buffer_bytes:        components.sources.internal_metrics.output.metrics.udp_receive_buffer_bytes
+		udp_receive_buffer_drops_total:  components.sources.internal_metrics.output.metrics.udp_receive_buffer_drops_total
 	}
 }
@@ -63,6 +63,72 @@ components: sources: vector: {
 				examples: ["0.0.0.0:\(_port)"]
 			}
 		}
+		auth: {
+			common:      false
+			description: """
+				Authenticates the clients sending events to this source. Requests of clients that aren't
+				authenticated by any of the configured methods are rejected with the `UNAUTHENTICATED` status.
+				"""
+			required:    false
+			type: object: options: {
+				tokens: {
+					common:      true
+					description: """
+						The tokens accepted from clients, keyed by the identity of the client they authenticate.
+						Clients send their token as `Bearer <token>` in the `authorization` metadata of their
+						requests, which the `vector` sink does when its `auth_token` option is set.
+						"""
+					required:    false
+					type: object: {
+						examples: [{"edge-eu-1": "${EDGE_EU_1_TOKEN}"}]
+						options: {}
+					}
+				}
+				tls_identity: {
+					common:      false
+					description: """
+						Whether or not clients without a token are authenticated by their TLS client certificate,
+						with the common name (CN) of the certificate as their identity. Requires `tls.enabled` and
+						`tls.verify_certificate` to be `true`.
+						"""
+					required:    false
+					type: bool: default: false
+				}
+			}
+		}
+		rate_limit: {
+			common:      false
+			description: """
+				Limits the rate of events each client can send. Clients are identified by their
+				authenticated identity, or by their IP address when `auth` isn't configured. Requests of
+				clients that exceeded their rate limit are rejected with the `RESOURCE_EXHAUSTED` status,
+				which the `vector` sink retries.
+				"""
+			required:    false
+			type: object: options: {
+				threshold: {
+					description: """
+						The number of events each client is allowed to send per `window_secs`. Requests of
+						more events than this are rejected with the `INVALID_ARGUMENT` status, so it must be at
+						least the `batch.max_events` of the sinks sending to this source.
+						"""
+					required:    true
+					type: uint: {
+						examples: [1000]
+						unit: "events"
+					}
+				}
+				window_secs: {
+					common:      true
+					description: "The time window in which the `threshold` applies, in seconds."
+					required:    false
+					type: float: {
+						default: 1.0
+						unit:    "seconds"
+					}
+				}
+			}
+		}
 		version: {
 			description: "Source API version. Specifying this version ensures that Vector does not silently break backward compatibility."
 			common:      true
@@ -122,7 +188,27 @@ components: sources: vector: {
 		}
 	}
 
+	how_it_works: {
+		client_authentication: {
+			title: "Client authentication and quotas"
+			body: """
+				When exposed to many upstream Vector instances, the `auth` option lets this source attribute
+				their traffic, either with a token per client or with the common name of their TLS client
+				certificate. The events and bytes received from each authenticated client are reported in
+				the `client_received_events_total` and `client_received_bytes_total` metrics, tagged with
+				the `client` identity.
+
+				The `rate_limit` option then polices the traffic of each client, by rejecting its requests
+				once it sent more than `threshold` events per `window_secs`. Upstream `vector` sinks retry
+				rejected requests, so that their events are delayed rather than lost.
+				"""
+		}
+	}
+
 	telemetry: metrics: {
+		client_rate_limited_requests_total:   components.sources.internal_metrics.output.metrics.client_rate_limited_requests_total
+		client_received_bytes_total:          components.sources.internal_metrics.output.metrics.client_received_bytes_total
+		client_received_events_total:         components.sources.internal_metrics.output.metrics.client_received_events_total
 		component_discarded_events_total:     components.sources.internal_metrics.output.metrics.component_discarded_events_total
 		component_errors_total:               components.sources.internal_metrics.output.metrics.component_errors_total
 		component_received_bytes_total:       components.sources.internal_metrics.output.metrics.component_received_bytes_total
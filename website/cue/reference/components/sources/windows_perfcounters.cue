@@ -0,0 +1,148 @@
+package metadata
+
+components: sources: windows_perfcounters: {
+	title: "Windows Performance Counters"
+
+	description: """
+		Collects Windows performance counters, such as the counters of processors, disks,
+		network interfaces, or of services like IIS and SQL Server, into metrics.
+		"""
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		deployment_roles: ["daemon"]
+		development:   "alpha"
+		egress_method: "batch"
+		stateful:      false
+	}
+
+	features: {
+		acknowledgements: false
+		collect: {
+			checkpoint: enabled: false
+			from: service:       services.host
+		}
+		multiline: enabled: false
+	}
+
+	support: {
+		targets: {
+			"aarch64-unknown-linux-gnu":      false
+			"aarch64-unknown-linux-musl":     false
+			"armv7-unknown-linux-gnueabihf":  false
+			"armv7-unknown-linux-musleabihf": false
+			"x86_64-apple-darwin":            false
+			"x86_64-unknown-linux-gnu":       false
+			"x86_64-unknown-linux-musl":      false
+		}
+
+		notices: [
+			"""
+				This component is in alpha, and can only be used with the `alpha_components` feature flag
+				enabled.
+				""",
+		]
+		requirements: []
+		warnings: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: {
+		counters: {
+			description: "The performance counters to collect."
+			required:    true
+			type: array: items: type: object: options: {
+				name: {
+					description: """
+						The name of the metric. Defaults to the names of the object and the counter, in
+						snake case, such as `processor_percent_processor_time`.
+						"""
+					required: false
+					type: string: {
+						examples: ["cpu_usage_percent"]
+					}
+				}
+				path: {
+					description: """
+						The path of the counter, as `\\<object>(<instance>)\\<counter>`, or
+						`\\<object>\\<counter>` for the counters of objects without instances. Counters are
+						looked up by their English names, whatever the language of the system. An instance
+						of `*` collects all the instances of the counter. The path can start with
+						`\\\\<machine>` to collect the counter of another machine.
+						"""
+					required: true
+					type: string: {
+						examples: ["\\Processor(*)\\% Processor Time", "\\Memory\\Available Bytes", "\\\\web01\\LogicalDisk(C:)\\% Free Space"]
+					}
+				}
+			}
+		}
+		namespace: {
+			description: "The namespace of the metrics."
+			common:      false
+			required:    false
+			type: string: {
+				default: "windows"
+			}
+		}
+		scrape_interval_secs: {
+			description: "The interval between collections of the counters, in seconds."
+			common:      true
+			required:    false
+			type: float: {
+				default: 15.0
+				unit:    "seconds"
+			}
+		}
+	}
+
+	output: metrics: {
+		"*": {
+			description:       "The value of an instance of a counter, named as its `name`."
+			type:              "gauge"
+			default_namespace: "windows"
+			tags: {
+				host: {
+					description: "The machine of the counter, or the hostname of the system Vector is running on."
+					required:    true
+					examples: [_values.local_host]
+				}
+				instance: {
+					description: "The name of the instance of the counter, for the counters of objects with instances."
+					required:    false
+					examples: ["_Total", "0", "C:"]
+				}
+				object: {
+					description: "The object of the counter."
+					required:    true
+					examples: ["Processor", "Memory"]
+				}
+			}
+		}
+	}
+
+	how_it_works: {
+		counters: {
+			title: "Counters"
+			body: """
+				The counters are collected through the [Performance Data Helper](\(urls.pdh)) library,
+				the same as `perfmon` and `typeperf`, which can be used to list the counters available
+				on a system with `typeperf -q`. Rate counters such as `% Processor Time` are computed
+				between two collections, and so the counters are first emitted after one
+				`scrape_interval_secs` rather than on start-up.
+				Counters that fail to be collected, for instance when a specific instance has stopped,
+				are reported as errors, while the other counters are still emitted.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_errors_total:               components.sources.internal_metrics.output.metrics.component_errors_total
+		component_received_events_total:      components.sources.internal_metrics.output.metrics.component_received_events_total
+		component_received_event_bytes_total: components.sources.internal_metrics.output.metrics.component_received_event_bytes_total
+	}
+}
@@ -34,7 +34,13 @@ components: transforms: aws_ec2_metadata: {
 				```
 				""",
 		]
-		notices: []
+		notices: [
+			"""
+				The global [`host_metadata`](\(urls.vector_configuration)/global-options#host_metadata) option
+				supersedes this transform: it fetches the metadata of EC2, GCE, and Azure instances once for
+				all components, and exposes it to templates, VRL, and the events of sources.
+				""",
+		]
 		warnings: [
 			"""
 				Do not enable this transform if you are running Vector as an Aggregator, tags will be sourced from the Aggregator node's metadata server and not the client's.
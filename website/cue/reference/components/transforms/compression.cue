@@ -0,0 +1,131 @@
+package metadata
+
+components: transforms: compression: {
+	title: "Compression"
+
+	description: """
+		Decompresses or compresses the payload held in a log field, optionally wrapped
+		in base64.
+		"""
+
+	classes: {
+		commonly_used: false
+		development:   "alpha"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	features: {
+		convert: {}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: [
+			"""
+				This component is in alpha, and can only be used with the `alpha_components` feature flag
+				enabled.
+				""",
+		]
+	}
+
+	configuration: {
+		algorithm: {
+			common:      true
+			description: "The compression algorithm of the payload."
+			required:    true
+			type: string: {
+				enum: {
+					gzip:   "[Gzip](\(urls.gzip)) compression. When decompressing, concatenated gzip members are decompressed as one payload."
+					zlib:   "[Zlib](\(urls.zlib)) compression."
+					zstd:   "[Zstandard](\(urls.zstd)) compression."
+					snappy: "[Snappy](\(urls.snappy)) compression, in the raw format without framing."
+				}
+			}
+		}
+		base64: {
+			common: true
+			description: """
+				Whether the compressed payload is wrapped in base64. When decompressing, the payload is
+				base64-decoded first. When compressing, the result is base64-encoded, so that it can be stored
+				as a string.
+				"""
+			required: false
+			type: bool: default: false
+		}
+		drop_on_error: {
+			common:      false
+			description: "Whether to drop events whose payload can't be processed. By default, such events are passed on unchanged."
+			required:    false
+			type: bool: default: false
+		}
+		field: {
+			common:      true
+			description: "The log field holding the payload."
+			required:    false
+			type: string: {
+				default: "message"
+				examples: ["data", "payload.body"]
+			}
+		}
+		max_decompressed_bytes: {
+			common: false
+			description: """
+				The maximum size of a decompressed payload, in bytes. Payloads decompressing to more than this
+				can't be processed, which guards against payloads crafted to decompress to huge sizes.
+				"""
+			required: false
+			type: uint: {
+				default: 10485760
+				unit:    "bytes"
+			}
+		}
+		mode: {
+			common:      true
+			description: "Whether to decompress or compress the payload."
+			required:    false
+			type: string: {
+				default: "decompress"
+				enum: {
+					decompress: "Decompress the payload."
+					compress:   "Compress the payload."
+				}
+			}
+		}
+		target_field: {
+			common:      false
+			description: "The log field to write the result to. If not set, the payload in `field` is replaced."
+			required:    false
+			type: string: {
+				default: null
+				examples: ["decompressed"]
+			}
+		}
+	}
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	how_it_works: {
+		nested_payloads: {
+			title: "Nested payloads"
+			body: """
+				Many envelopes carry compressed inner payloads, such as CloudWatch Logs
+				subscriptions delivered through Kinesis Firehose, or syslog relays
+				forwarding compressed batches. Decompressing them in place lets later
+				transforms, such as `remap`, parse the inner payload.
+
+				Field values that are not strings, or that can't be decoded, are reported
+				as errors.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_errors_total: components.sources.internal_metrics.output.metrics.component_errors_total
+	}
+}
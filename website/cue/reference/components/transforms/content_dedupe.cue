@@ -0,0 +1,108 @@
+package metadata
+
+components: transforms: content_dedupe: {
+	title: "Content Dedupe"
+
+	description: """
+		Drops events whose content exactly matches an event seen within a sliding time
+		window, such as the overlapping chunks replayed by sources after a restart.
+		"""
+
+	classes: {
+		commonly_used: false
+		development:   "alpha"
+		egress_method: "stream"
+		stateful:      true
+	}
+
+	features: {
+		filter: {}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: [
+			"""
+				This component is in alpha, and can only be used with the `alpha_components` feature flag
+				enabled.
+				""",
+		]
+	}
+
+	configuration: {
+		capacity: {
+			common: false
+			description: """
+				The number of event hashes remembered at most. Once this many hashes are
+				remembered, the oldest ones are forgotten before their window ends.
+				"""
+			required: false
+			type: uint: {
+				default: 100000
+				unit:    null
+			}
+		}
+		fields: {
+			common:      false
+			description: "Options controlling which fields are hashed to identify duplicate events."
+			required:    false
+			type: object: {
+				options: {
+					ignore: {
+						common:      false
+						description: "The field names to leave out of the hash. Defaults to the `timestamp` field, as set by the [global `log_schema` options](\(urls.vector_configuration_global)/#log_schema). Incompatible with the `fields.match` option."
+						required:    false
+						type: array: {
+							default: ["timestamp"]
+							items: type: string: {
+								examples: ["timestamp", "parent.child_field"]
+							}
+						}
+					}
+					match: {
+						common:      false
+						description: "The field names to hash. Incompatible with the `fields.ignore` option."
+						required:    false
+						type: array: {
+							default: null
+							items: type: string: {
+								examples: ["message", "parent.child_field"]
+							}
+						}
+					}
+				}
+			}
+		}
+		window_secs: {
+			common:      true
+			description: "The length of the window in which duplicate events are dropped, starting when an event is first seen."
+			required:    false
+			type: float: {
+				default: 300.0
+				unit:    "seconds"
+			}
+		}
+	}
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	how_it_works: {
+		hashing: {
+			title: "Hashing"
+			body: """
+				Each event is identified by a 64-bit hash of the names and JSON encoded values
+				of its hashed fields. Only the hashes are kept in memory, which takes about 40
+				bytes per remembered event, regardless of the size of the events.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_discarded_events_total: components.sources.internal_metrics.output.metrics.component_discarded_events_total
+	}
+}
@@ -0,0 +1,152 @@
+package metadata
+
+components: transforms: delay: {
+	title: "Delay"
+
+	description: """
+		Holds events for a fixed duration, or until a release time evaluated from
+		each event, before passing them on.
+		"""
+
+	classes: {
+		commonly_used: false
+		development:   "alpha"
+		egress_method: "stream"
+		stateful:      true
+	}
+
+	features: {}
+
+	support: {
+		requirements: []
+		warnings: [
+			"""
+				Events are acknowledged once they're written to disk when Vector stops,
+				and events restored after a restart can't be acknowledged again.
+				""",
+		]
+		notices: [
+			"""
+				This component is in alpha, and can only be used with the `alpha_components` feature flag
+				enabled.
+				""",
+		]
+	}
+
+	configuration: {
+		data_dir: {
+			common:      false
+			description: "The directory used to write the events held on disk. By default, the [global `data_dir` option](\(urls.vector_configuration)/global-options#data_dir) is used. Please make sure the user Vector is running as has write permissions to this directory."
+			required:    false
+			type: string: {
+				default: null
+				examples: ["/var/lib/vector"]
+			}
+		}
+		delay_secs: {
+			common:      true
+			description: "How long each event is held. When `release_at` is also set, this is only used for the events whose release time can't be evaluated. At least one of `delay_secs` and `release_at` must be set."
+			required:    false
+			type: float: {
+				default: null
+				examples: [60.0, 0.5]
+				unit: "seconds"
+			}
+		}
+		max_delay_secs: {
+			common:      false
+			description: "The longest time an event is held. Release times further in the future are brought back to this."
+			required:    false
+			type: uint: {
+				default: null
+				examples: [86400]
+				unit: "seconds"
+			}
+		}
+		max_memory_events: {
+			common:      false
+			description: "The maximum number of events held in memory. Once more events are held, those released last are written to the data directory, and read back when they're about to be released."
+			required:    false
+			type: uint: {
+				default: 10000
+				unit:    "events"
+			}
+		}
+		release_at: {
+			common:      true
+			description: "A [Vector Remap Language](\(urls.vrl_reference)) (VRL) expression evaluating to the timestamp each event is held until. The expression can't modify the event. Events for which it fails, or evaluates to anything other than a timestamp, are held for `delay_secs` instead, or released immediately if `delay_secs` isn't set."
+			required:    false
+			type: string: {
+				default: null
+				examples: [
+					"timestamp!(.deliver_at)",
+					#"parse_timestamp!(.deliver_at, "%+")"#,
+				]
+				syntax: "remap_program"
+			}
+		}
+	}
+
+	input: {
+		logs: true
+		metrics: {
+			counter:      true
+			distribution: true
+			gauge:        true
+			histogram:    true
+			set:          true
+			summary:      true
+		}
+		traces: true
+	}
+
+	examples: [
+		{
+			title: "Hold events for a minute"
+			configuration: {
+				delay_secs: 60.0
+			}
+			input: log: {
+				message: "Sent a minute after it was received"
+			}
+			output: log: {
+				message: "Sent a minute after it was received"
+			}
+		},
+	]
+
+	how_it_works: {
+		release_order: {
+			title: "Release order"
+			body: """
+				Events are released in the order of their release times, and events
+				with the same release time in the order they were received. Events
+				whose release time is in the past are released immediately.
+
+				This allows deferring the delivery of events, such as holding them
+				back from a rate-limited backend during business hours by evaluating
+				a release time outside of those hours with `release_at`.
+				"""
+		}
+		disk_queue: {
+			title: "Holding events on disk"
+			body: """
+				Once more than `max_memory_events` events are held, the half of them
+				released last is written to a file in the data directory of the
+				transform, and read back when the earliest of them is due. This keeps
+				the memory used by long delays bounded.
+
+				Files left by a previous run, such as after a crash, are picked up when
+				the transform starts. When Vector stops, the events still held in memory
+				are written to disk too, to be restored when it starts again. Only the
+				events that can't be written are released right away.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_errors_total:           components.sources.internal_metrics.output.metrics.component_errors_total
+		component_discarded_events_total: components.sources.internal_metrics.output.metrics.component_discarded_events_total
+		delayed_events:                   components.sources.internal_metrics.output.metrics.delayed_events
+	}
+}
@@ -0,0 +1,82 @@
+package metadata
+
+components: transforms: ecs_normalize: {
+	title: "ECS Normalize"
+
+	description: """
+		Maps the fields of common shapes of log events, such as syslog messages, NGINX
+		access logs, and Kubernetes logs, to the field names of the
+		[Elastic Common Schema](\(urls.elastic_common_schema)).
+		"""
+
+	classes: {
+		commonly_used: false
+		development:   "alpha"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	features: {
+		shape: {}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: [
+			"""
+				This component is in alpha, and can only be used with the `alpha_components` feature flag
+				enabled.
+				""",
+		]
+	}
+
+	configuration: {
+		presets: {
+			description: """
+				The shapes of events to map to ECS fields. The presets are applied in order.
+				Fields not produced by any of them are left as they are.
+				"""
+			required: true
+			type: array: items: type: string: {
+				enum: {
+					syslog:     "Events of the `syslog` source, or parsed with the `parse_syslog` VRL function."
+					nginx:      "Access logs parsed with the `parse_nginx_log` VRL function."
+					kubernetes: "Events of the `kubernetes_logs` source."
+				}
+			}
+		}
+		tag_version: {
+			common:      false
+			description: "Whether to set the `ecs.version` field to the version of ECS the presets map to, currently `8.4.0`."
+			required:    false
+			type: bool: default: true
+		}
+	}
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	how_it_works: {
+		mappings: {
+			title: "Mappings"
+			body: """
+				Each preset renames the fields it knows about to their ECS equivalents, such
+				as `appname` to `process.name` for syslog messages, `status` to
+				`http.response.status_code` for NGINX access logs, or
+				`kubernetes.pod_name` to `kubernetes.pod.name` for Kubernetes logs.
+				Numeric fields are converted to integers, and `event.module` and
+				`event.dataset` are set where they can be inferred from the preset.
+
+				For mappings beyond the presets, use the `schema_map` transform.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_errors_total: components.sources.internal_metrics.output.metrics.component_errors_total
+	}
+}
@@ -33,6 +33,60 @@ components: transforms: filter: {
 			required: true
 			type: condition: {}
 		}
+		quota: {
+			common: false
+			description: """
+				Keeps at most `max_events` of the events matching the condition per key and window. The events beyond
+				the quota of their key are dropped, and a summary event counting them is emitted at the end of the
+				window, so storms of similar events, such as errors, are reduced to a sample of them.
+				"""
+			required: false
+			type: object: {
+				options: {
+					max_events: {
+						description: "The maximum number of events kept per key and window."
+						required:    true
+						type: uint: {
+							examples: [10, 100]
+							unit: null
+						}
+					}
+					window_secs: {
+						description: "The duration of the windows."
+						required:    true
+						type: float: {
+							examples: [60.0]
+							unit: "seconds"
+						}
+					}
+					key_field: {
+						common: false
+						description: """
+							A [template string](/docs/reference/configuration/template-syntax/) rendered to determine the
+							key the events are counted by. All the events share a single quota when unset, or if the
+							template can't be rendered.
+							"""
+						required: false
+						type: string: {
+							default: null
+							examples: ["{{ service }}", "{{ message }}"]
+							syntax: "template"
+						}
+					}
+					summary: {
+						common: false
+						description: """
+							Whether a summary log event is emitted at the end of the windows in which events of a key
+							were dropped, with their number in the `suppressed_count` field and their key in the `key`
+							field. Only the dropped log events are summarized, so summaries only go where log events
+							already do.
+							"""
+						required: false
+						type: bool: default: true
+					}
+				}
+			}
+		}
 	}
 
 	input: {
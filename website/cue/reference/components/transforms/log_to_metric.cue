@@ -25,6 +25,15 @@ components: transforms: log_to_metric: {
 	}
 
 	configuration: {
+		flush_interval_secs: {
+			common:      false
+			description: "The interval at which the estimates of `distinct` metrics are emitted. Distinct values are counted separately for each interval."
+			required:    false
+			type: uint: {
+				default: 60
+				unit:    "seconds"
+			}
+		}
 		metrics: {
 			description: "A table of key/value pairs representing the keys to be added to the event."
 			required:    true
@@ -75,6 +84,20 @@ components: transforms: log_to_metric: {
 							syntax: "template"
 						}
 					}
+					precision: {
+						description: """
+							The precision of the [HyperLogLog](\(urls.hyperloglog)) sketch the distinct values are counted
+							with, between `4` and `16`. Each sketch uses `2^precision` bytes of memory, and its estimates
+							have a relative standard error of about `1.04 / sqrt(2^precision)`.
+							"""
+						required:      false
+						common:        false
+						relevant_when: #"type = "distinct""#
+						type: uint: {
+							default: 12
+							unit:    null
+						}
+					}
 					namespace: {
 						description: "The namespace of the metric."
 						required:    false
@@ -115,6 +138,7 @@ components: transforms: log_to_metric: {
 						type: string: {
 							enum: {
 								counter:   "A [counter metric type](\(urls.vector_metric)#counter)."
+								distinct:  "A [gauge metric type](\(urls.vector_metric)#gauge) estimating the number of distinct values of `field`, emitted every `flush_interval_secs`."
 								gauge:     "A [gauge metric type](\(urls.vector_metric)#gauge)."
 								histogram: "A [distribution metric type](\(urls.vector_metric)#histogram) with histogram statistic."
 								set:       "A [set metric type](\(urls.vector_metric)#set)."
@@ -419,6 +443,16 @@ components: transforms: log_to_metric: {
 				individual metrics for reduction in the metrics storage itself.
 				"""
 		}
+		distinct_counts: {
+			title: "Distinct Counts"
+			body: """
+				Unlike the other metric types, `distinct` metrics are not emitted for each log event.
+				The values of `field` are counted in a [HyperLogLog](\(urls.hyperloglog)) sketch per
+				metric name, namespace, and set of rendered tags, and the estimated number of distinct
+				values is emitted as an absolute gauge every `flush_interval_secs`. The sketches are reset
+				after each interval, so the gauges count the distinct values seen during that interval.
+				"""
+		}
 		null_fields: {
 			title: "Null Fields"
 			body: """
@@ -0,0 +1,134 @@
+package metadata
+
+components: transforms: metric_router: {
+	title: "Metric Router"
+
+	description: """
+		Splits a stream of metrics into multiple sub-streams based on rules matching
+		their name, namespace, and tags. Unlike the `route` transform, each metric is
+		sent to a single sub-stream, the first whose rules it matches.
+		"""
+
+	classes: {
+		commonly_used: false
+		development:   "alpha"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	features: {
+		route: {}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: [
+			"""
+				This component is in alpha, and can only be used with the `alpha_components` feature flag
+				enabled.
+				""",
+		]
+	}
+
+	configuration: {
+		route: {
+			description: """
+				A table of route identifiers to the rules matching the metrics of the route. Each route can
+				then be referenced as an input by other components with the name `<transform_name>.<route_id>`.
+				Each metric is sent to the first route, in order, whose rules it all matches, or to the
+				`<transform_name>._unmatched` output if it doesn't match any route. Note, `_unmatched` is a
+				reserved output name and cannot be used as a route name.
+				"""
+			required: true
+			type: object: {
+				options: {
+					"*": {
+						description: "The rules matching the metrics of the route."
+						required:    true
+						type: object: options: {
+							names: {
+								description: "Glob patterns matched against the name of metrics. A metric matches if its name matches any of the patterns."
+								required:    false
+								type: array: {
+									default: []
+									items: type: string: examples: ["http_*", "orders_total"]
+								}
+							}
+							namespaces: {
+								description: "Glob patterns matched against the namespace of metrics. A metric matches if its namespace matches any of the patterns. Metrics without a namespace don't match."
+								required:    false
+								type: array: {
+									default: []
+									items: type: string: examples: ["host", "kubernetes_*"]
+								}
+							}
+							tags: {
+								description: "A table of tag names to glob patterns matched against their value. A metric matches if it has each of the tags, with a value matching its pattern."
+								required:    false
+								type: object: {
+									examples: [{"team": "commerce-*"}]
+									options: {}
+								}
+							}
+						}
+					}
+				}
+			}
+		}
+	}
+
+	input: {
+		logs: false
+		metrics: {
+			counter:      true
+			distribution: true
+			gauge:        true
+			histogram:    true
+			set:          true
+			summary:      true
+		}
+		traces: false
+	}
+
+	examples: [
+		{
+			title: "Split infrastructure and business metrics"
+
+			configuration: {
+				route: {
+					infra: namespaces: ["host", "kubernetes_*"]
+					business: names: ["orders_*", "checkout_*"]
+				}
+			}
+
+			input: metric: {
+				counter: {
+					value: 42.0
+				}
+				kind:      "incremental"
+				name:      "orders_total"
+				namespace: "shop"
+			}
+			output: metric: {
+				counter: {
+					value: 42.0
+				}
+				kind:      "incremental"
+				name:      "orders_total"
+				namespace: "shop"
+			}
+		},
+	]
+
+	outputs: [
+		{
+			name:        "<route_id>"
+			description: "Each route can be referenced as an input by other components with the name `<transform_name>.<route_id>`."
+		},
+		{
+			name:        "_unmatched"
+			description: "The metrics which don't match any route."
+		},
+	]
+}
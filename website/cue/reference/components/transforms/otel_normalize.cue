@@ -0,0 +1,98 @@
+package metadata
+
+components: transforms: otel_normalize: {
+	title: "OpenTelemetry Normalize"
+
+	description: """
+		Maps log events to the fields of the
+		[OpenTelemetry log data model](\(urls.opentelemetry_log_data_model)), as produced
+		by the `opentelemetry` source, moving the metadata set by sources into resource
+		attributes named after the OpenTelemetry semantic conventions.
+		"""
+
+	classes: {
+		commonly_used: false
+		development:   "alpha"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	features: {
+		shape: {}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: [
+			"""
+				This component is in alpha, and can only be used with the `alpha_components` feature flag
+				enabled.
+				""",
+		]
+	}
+
+	configuration: {
+		collect_attributes: {
+			common:      false
+			description: "Whether to move all the fields that aren't part of the OpenTelemetry log data model into `attributes`."
+			required:    false
+			type: bool: default: true
+		}
+		resource_attributes: {
+			common: false
+			description: """
+				Additional fields to move into the resource attributes, keyed by the name of
+				the attribute they become.
+				"""
+			required: false
+			type: object: {
+				examples: [{"deployment.environment": "env"}]
+				options: {}
+			}
+		}
+		severity_fields: {
+			common: false
+			description: """
+				The fields holding the severity of events, in order of precedence. The first
+				of them found in an event is moved to `severity_text`, and mapped to the
+				matching `severity_number`.
+				"""
+			required: false
+			type: array: {
+				default: ["severity", "level"]
+				items: type: string: {}
+			}
+		}
+	}
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	how_it_works: {
+		data_model: {
+			title: "Data model"
+			body: """
+				The message of events is their body, and their timestamp is kept as is. The
+				other fields of the data model are `severity_text`, `severity_number`,
+				`trace_id`, `span_id`, `flags`, `observed_timestamp`, `resources`, and
+				`attributes`. Events that already have a `severity_number` keep it, and
+				`observed_timestamp` is set to the current time if it's missing.
+				"""
+		}
+		resources: {
+			title: "Resource attributes"
+			body: """
+				The fields describing where events come from are moved into `resources`:
+
+				* The host of the event, and the `hostname` of syslog messages, to `host.name`.
+				* The `appname` and `procid` of syslog messages to `service.name` and `process.pid`.
+				* The pod and container metadata of the `kubernetes_logs` source to the
+				  `k8s.*`, `container.id`, and `container.image.name` attributes.
+				"""
+		}
+	}
+}
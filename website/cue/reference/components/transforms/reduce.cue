@@ -92,6 +92,12 @@ components: transforms: reduce: {
 				   `[field-name]_end` is added with the last received
 				   timestamp value.
 				3. Numeric values are summed.
+
+				The fields of objects merged with the `deep_merge` strategy
+				can be given their own strategy by their path, such as
+				`payload.items`. Fields which aren't made of only
+				alphanumeric characters and underscores are quoted, such as
+				`payload."user agent"`.
 				"""
 			required: false
 			type: object: {
@@ -102,6 +108,11 @@ components: transforms: reduce: {
 						duration_ms: "sum"
 						query:       "array"
 					},
+					{
+						payload:                 "deep_merge"
+						"payload.items":         "array_concat_unique"
+						"payload.stats.retries": "max"
+					},
 				]
 				options: {
 					"*": {
@@ -109,18 +120,20 @@ components: transforms: reduce: {
 						required:    true
 						type: string: {
 							enum: {
-								array:          "Each value is appended to an array."
-								longest_array:  "Retains the longest array seen"
-								shortest_array: "Retains the shortest array seen"
-								concat:         "Concatenate each string value (delimited with a space)."
-								concat_newline: "Concatenate each string value (delimited with a newline)."
-								concat_raw:     "Concatenate each string value (without any delimiter)."
-								discard:        "Discard all but the first value found."
-								retain:         "Discard all but the last value found. Works as a coalesce by not retaining null."
-								sum:            "Sum all numeric values."
-								max:            "The maximum of all numeric values."
-								min:            "The minimum of all numeric values."
-								flat_unique:    "Create a flattened array of all the unique values."
+								array:               "Each value is appended to an array."
+								array_concat_unique: "Concatenate arrays, keeping only the first occurrence of each value, including objects. Other values than arrays and null are appended as elements."
+								longest_array:       "Retains the longest array seen"
+								shortest_array:      "Retains the shortest array seen"
+								concat:              "Concatenate each string value (delimited with a space)."
+								concat_newline:      "Concatenate each string value (delimited with a newline)."
+								concat_raw:          "Concatenate each string value (without any delimiter)."
+								deep_merge:          "Merge objects recursively. Nested fields are merged with the strategy configured for their path, such as `payload.items`, if any. Otherwise nested objects are merged recursively, and other values as with `retain`, null values not replacing merged values."
+								discard:             "Discard all but the first value found."
+								retain:              "Discard all but the last value found. Works as a coalesce by not retaining null."
+								sum:                 "Sum all numeric values."
+								max:                 "The maximum of all numeric values."
+								min:                 "The minimum of all numeric values."
+								flat_unique:         "Create a flattened array of all the unique values."
 							}
 						}
 					}
@@ -104,6 +104,75 @@ components: transforms: "remap": {
 				"""
 			type: bool: default: false
 		}
+		batch_size: {
+			common:   false
+			required: false
+			description: """
+				The maximum number of events to run the program on as a single batch. The
+				events of a batch are run one after the other against the same compiled
+				program, reusing the runtime state and the working buffers of the transform,
+				and mapping errors and aborts are reported once per batch, along with the
+				errors of the first failed events. Larger batches lower the per-event
+				overhead of simple programs, at the cost of holding more events in memory at
+				once.
+				"""
+			type: uint: {
+				default: 128
+				unit:    "events"
+			}
+		}
+		state: {
+			common:   false
+			required: false
+			description: """
+				Persists the state set by the `set_state` function, and expires its keys. By
+				default, the state is only kept in memory, and is lost when Vector restarts.
+				"""
+			type: object: options: {
+				ttl_secs: {
+					common:      false
+					required:    false
+					description: "The time-to-live of the keys set without a `ttl_secs` argument."
+					type: uint: {
+						default: 86400
+						unit:    "seconds"
+					}
+				}
+				max_entries: {
+					common:      false
+					required:    false
+					description: "The maximum number of keys of the state. Setting a new key beyond it evicts the keys set the longest ago."
+					type: uint: {
+						default: 100000
+						unit:    null
+					}
+				}
+				checkpoint_interval_secs: {
+					common:      false
+					required:    false
+					description: "How often the state is checkpointed. This is how much of the state may be lost: the keys set since the last checkpoint are lost if Vector stops before the next one, even when shutting down gracefully."
+					type: uint: {
+						default: 10
+						unit:    "seconds"
+					}
+				}
+				data_dir: {
+					common:      false
+					required:    false
+					description: "The directory used to persist the state. By default, the [global `data_dir` option](\(urls.vector_configuration)/global-options#data_dir) is used. Please make sure the user Vector is running as has write permissions to this directory."
+					type: string: {
+						default: null
+						examples: ["/var/lib/vector"]
+					}
+				}
+				checkpoint_store: {
+					common:      false
+					required:    false
+					description: "Where the state is checkpointed, with the same options as the `checkpoint_store` option of the [`file` source](\(urls.vector_sources)/file/#checkpoint_store). By default, it's stored in the data directory of the transform. Remote stores allow the state to outlive ephemeral nodes."
+					type: object: options: {}
+				}
+			}
+		}
 	}
 
 	input: {
@@ -172,6 +241,39 @@ components: transforms: "remap": {
 				the disallowed action.
 				"""
 		}
+		state: {
+			title: "State"
+			body: """
+				The `set_state` and `get_state` functions set and read keys of a state kept by
+				each `remap` transform, shared by all the events it processes. This allows
+				programs to remember what they saw in previous events, such as whether a host
+				was already seen:
+
+				```coffee
+				.first_seen = set_state!(string!(.host), now(), ttl_secs: 86400) == null
+				```
+
+				Keys expire after their `ttl_secs`, or the `state.ttl_secs` option of the
+				transform, which defaults to a day. The state holds at most `state.max_entries`
+				keys, and setting a new key beyond it evicts the keys set the longest ago.
+
+				When the `state` option is set, the state is checkpointed to the data directory
+				of the transform, or to another checkpoint store, and restored when Vector
+				restarts. A checkpoint only writes the keys changed since the previous one to a
+				journal, and the whole state is written again once the journal grows to half its
+				size. Values are checkpointed as JSON, so timestamps and regular expressions are
+				restored as strings.
+
+				The state isn't written to disk as each key is set, as an embedded key-value store
+				would, so that setting keys never blocks the processing of events on disk writes,
+				and so that the state can be checkpointed to remote stores as well. As a result,
+				the keys set within the last `state.checkpoint_interval_secs` seconds, 10 by
+				default, are lost when Vector stops, whether it's killed or shut down gracefully.
+				Lower the interval to narrow this window, at the cost of more frequent writes. A
+				checkpoint that fails is retried at the next interval, so the window grows for as
+				long as the store can't be written to.
+				"""
+		}
 		lazy_event_mutation: {
 			title: "Lazy Event Mutation"
 			body:  #"""
@@ -190,6 +292,21 @@ components: transforms: "remap": {
 				reference](\#(urls.vrl_runtime_errors)).
 				"""#
 		}
+		schema_checks: {
+			title: "Checking programs against input schemas"
+			body:  """
+				When `schema.enabled` is set in the global configuration, Vector knows the shape of the
+				events each source emits and each transform produces. The `remap` transform then
+				checks its program against the schema of its inputs when the configuration is loaded,
+				before any event is processed:
+
+				* A program that can never succeed on those events, such as passing an integer field to
+				  a function that only accepts strings, is rejected with the position of the offending
+				  expression instead of failing on every event at runtime.
+				* A field the program reads without setting it first, but that none of its inputs
+				  contain, is reported as a warning, since it's most likely a typo.
+				"""
+		}
 		emitting_multiple_events: {
 			title: "Emitting multiple log events"
 			body: #"""
@@ -0,0 +1,121 @@
+package metadata
+
+components: transforms: respond: {
+	title: "Respond"
+
+	description: """
+		Sets the response the source of each event answers its request with, for the sources
+		with `pipeline_response` enabled, passing the events through unchanged.
+		"""
+
+	classes: {
+		commonly_used: false
+		development:   "alpha"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	features: {}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: [
+			"""
+				This component is in alpha, and can only be used with the `alpha_components` feature flag
+				enabled.
+				""",
+		]
+	}
+
+	configuration: {
+		body: {
+			common: false
+			description: """
+				A [template string](/docs/reference/configuration/template-syntax/) rendered into the
+				body of the response. If not set, the `response.body` metadata field of the event is
+				used, or an empty body if it isn't set either.
+
+				An empty body is also used if the template fails to render, unless the template has an
+				[`on_render_error` policy](/docs/reference/configuration/template-syntax/#rendering-errors).
+				"""
+			required: false
+			type: string: {
+				default: null
+				examples: ["invalid event {{ id }}"]
+				syntax: "template"
+			}
+		}
+		status: {
+			common: true
+			description: """
+				The status of the response, as an HTTP status code. If not set, the `response.status`
+				metadata field of the event is used, such as set with `%response.status = 422` in a
+				`remap` transform, or `200` if it isn't set either.
+				"""
+			required: false
+			type: uint: {
+				default: null
+				examples: [200, 422]
+				unit: null
+			}
+		}
+	}
+
+	input: {
+		logs: true
+		metrics: {
+			counter:      true
+			distribution: true
+			gauge:        true
+			histogram:    true
+			set:          true
+			summary:      true
+		}
+		traces: true
+	}
+
+	outputs: [
+		components._default_output,
+		{
+			name: "render_errors"
+			description: """
+				When the `body` template has the `error_output` rendering error policy, the events
+				for which it fails to render are sent to the `render_errors` output instead, without
+				a response. For a transform component named `foo`, this output can be accessed by
+				specifying `foo.render_errors` as the input to another component, such as a
+				`respond` transform rejecting the request.
+				"""
+		},
+	]
+
+	how_it_works: {
+		pipeline_responses: {
+			title: "Pipeline responses"
+			body: """
+				Sources with `pipeline_response` enabled, such as the `http` and `grpc_server` sources,
+				wait for all the events of a request to be processed by the pipeline before answering it,
+				with the response set by this transform. This allows the pipeline to validate requests,
+				for instance with a `remap` transform setting the response metadata fields of invalid
+				events:
+
+				```coffee
+				if !exists(.id) {
+				  %response.status = 422
+				  %response.body = "missing id"
+				}
+				```
+
+				A request is answered as soon as all of its events went through this transform, without
+				waiting for them to be delivered by the sinks. When the events of a request are given
+				several responses, the one with the highest status is kept, so that a single invalid
+				event rejects the whole request. Requests whose events are not given a response, or
+				whose events are written to a disk buffer, are answered as usual.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_errors_total: components.sources.internal_metrics.output.metrics.component_errors_total
+	}
+}
@@ -0,0 +1,98 @@
+package metadata
+
+components: transforms: schema_map: {
+	title: "Schema Map"
+
+	description: """
+		Renames, coerces, and sets defaults for fields of log events, following the
+		rules of a YAML or CSV mapping file that is reloaded when it changes.
+		"""
+
+	classes: {
+		commonly_used: false
+		development:   "alpha"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	features: {
+		shape: {}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: [
+			"""
+				This component is in alpha, and can only be used with the `alpha_components` feature flag
+				enabled.
+				""",
+		]
+	}
+
+	configuration: {
+		path: {
+			description: """
+				The path of the mapping file. Files with a `.csv` extension are read as CSV,
+				with a header row naming the `field`, `rename`, `coerce`, and `default`
+				columns. All other files are read as YAML, as a list of rules with the same
+				keys.
+				"""
+			required: true
+			type: string: {
+				examples: ["/etc/vector/ecs_mapping.yaml", "/etc/vector/ecs_mapping.csv"]
+			}
+		}
+		reload_interval_secs: {
+			common: false
+			description: """
+				How often to check the mapping file for changes. The rules are reloaded
+				whenever the file is modified. If the modified file can't be loaded, the
+				previous rules are kept.
+				"""
+			required: false
+			type: uint: {
+				default: 30
+				unit:    "seconds"
+			}
+		}
+	}
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	how_it_works: {
+		rules: {
+			title: "Rules"
+			body: """
+				Every rule of the mapping file applies to the `field` it names, in the order
+				of the file:
+
+				* `rename` moves the value of the field to another field.
+				* `coerce` converts the value with one of the `string`, `integer`, `float`,
+				  `boolean`, `timestamp`, or `timestamp|<format>` conversions, such as
+				  `timestamp|%d/%m/%Y %H:%M:%S`. Values that can't be converted are kept as
+				  they are.
+				* `default` sets the field if it's missing from the event. In CSV files,
+				  defaults are converted with the `coerce` conversion of the rule.
+
+				```yaml
+				- field: src_ip
+				  rename: source.ip
+				- field: status
+				  rename: http.response.status_code
+				  coerce: integer
+				- field: event.dataset
+				  default: nginx.access
+				```
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_errors_total: components.sources.internal_metrics.output.metrics.component_errors_total
+	}
+}
@@ -0,0 +1,106 @@
+package metadata
+
+components: transforms: sigma: {
+	title: "Sigma"
+
+	description: """
+		Evaluates [Sigma](\(urls.sigma)) detection rules against log events, tagging the
+		events matching any rule with the metadata of the rules and routing the others
+		to a separate output.
+		"""
+
+	classes: {
+		commonly_used: false
+		development:   "alpha"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	features: {
+		filter: {}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: [
+			"""
+				This component is in alpha, and can only be used with the `alpha_components` feature flag
+				enabled.
+				""",
+		]
+	}
+
+	configuration: {
+		rules_path: {
+			description: """
+				The path of the directory holding the Sigma rules. All the files with a `.yml`
+				or `.yaml` extension in the directory and its subdirectories are loaded. Rules
+				that can't be loaded, such as rules using aggregations or unsupported
+				modifiers, are skipped with a warning.
+				"""
+			required: true
+			type: string: {
+				examples: ["/etc/vector/sigma"]
+			}
+		}
+		field_mappings: {
+			common: false
+			description: """
+				A table of Sigma field names to the paths of the event fields they refer to.
+				Fields that aren't mapped are read from the path named like them.
+				"""
+			required: false
+			type: object: {
+				examples: [{"CommandLine": "process.command_line", "Image": "process.executable"}]
+				options: {}
+			}
+		}
+		match_field: {
+			common: false
+			description: """
+				The field to store the metadata of the rules matching an event in. The field
+				is set to an array holding the `id`, `title`, `level`, and `tags` of each
+				matching rule.
+				"""
+			required: false
+			type: string: {
+				default: "sigma"
+			}
+		}
+	}
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	outputs: [
+		{
+			name:        "_unmatched"
+			description: "The events that don't match any rule, which can be referenced as an input by other components with the name `<transform_name>._unmatched`."
+		},
+	]
+
+	how_it_works: {
+		detection: {
+			title: "Detection"
+			body: """
+				Each search of the `detection` of a rule is either a map of fields, which
+				matches if all the fields match, a list of such maps, which matches if any of
+				them does, or a list of keywords, which matches if the message of the event
+				contains any of them. A field matches if its value matches any of the listed
+				values, or all of them with the `all` modifier. Values are compared without
+				regard to case, and `*` and `?` are wildcards.
+
+				The `contains`, `startswith`, `endswith`, `all`, `re`, `cidr`, `gt`, `gte`,
+				`lt`, and `lte` modifiers are supported.
+
+				The `condition` of a rule combines its searches with `and`, `or`, `not`,
+				parentheses, and the `1 of` and `all of` quantifiers. Aggregations, such as
+				`selection | count() > 5`, aren't supported.
+				"""
+		}
+	}
+}
@@ -0,0 +1,120 @@
+package metadata
+
+components: transforms: time_skew: {
+	title: "Time Skew"
+
+	description: """
+		Drops, tags, or corrects events whose timestamps are too far in the past or
+		the future relative to the time they are processed.
+		"""
+
+	classes: {
+		commonly_used: false
+		development:   "alpha"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	features: {
+		filter: {}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: [
+			"""
+				This component is in alpha, and can only be used with the `alpha_components` feature flag
+				enabled.
+				""",
+		]
+	}
+
+	configuration: {
+		action: {
+			common:      true
+			description: "What to do with events whose timestamp is outside of the tolerances."
+			required:    false
+			type: string: {
+				default: "drop"
+				enum: {
+					drop:    "Drop the event."
+					tag:     "Pass the event on unchanged, apart from marking it with `tag_field`."
+					clamp:   "Move the timestamp to the nearest time within the tolerances."
+					rewrite: "Replace the timestamp with the time the event passes through the transform."
+				}
+			}
+		}
+		max_age_secs: {
+			common: true
+			description: """
+				The maximum age of an event. Events with a timestamp further in the past than this, relative to
+				the time they pass through the transform, are considered skewed. If not set, events are never
+				considered too old.
+				"""
+			required: false
+			type: uint: {
+				default: null
+				examples: [3600]
+				unit: "seconds"
+			}
+		}
+		max_future_secs: {
+			common: true
+			description: """
+				The maximum distance of an event's timestamp into the future. Events with a timestamp further in
+				the future than this, relative to the time they pass through the transform, are considered
+				skewed. If not set, events are never considered too new.
+				"""
+			required: false
+			type: uint: {
+				default: null
+				examples: [300]
+				unit: "seconds"
+			}
+		}
+		tag_field: {
+			common: false
+			description: """
+				The name of the field, or of the tag for metrics, marking skewed events that are not dropped. The
+				value is `past` for events that are too old, and `future` for events that are too new.
+				"""
+			required: false
+			type: string: {
+				default: "timestamp_skew"
+			}
+		}
+	}
+
+	input: {
+		logs: true
+		metrics: {
+			counter:      true
+			distribution: true
+			gauge:        true
+			histogram:    true
+			set:          true
+			summary:      true
+		}
+		traces: true
+	}
+
+	how_it_works: {
+		tolerances: {
+			title: "Tolerances"
+			body: """
+				Each event's timestamp is compared to the time it passes through the
+				transform. At least one of `max_age_secs` and `max_future_secs` must be
+				set. Events without a timestamp are passed on unchanged.
+
+				Many time series backends reject a whole request when a single point in
+				it is too old or too new, so dropping or correcting such events early
+				keeps the rest of a batch from being lost.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_discarded_events_total: components.sources.internal_metrics.output.metrics.component_discarded_events_total
+	}
+}
@@ -0,0 +1,152 @@
+package metadata
+
+components: transforms: top_k: {
+	title: "Top K"
+
+	description: """
+		Counts events by a key over a window and emits the keys with the highest counts at the end
+		of each window. This is useful for spotting heavy hitters, such as noisy tenants or client IPs,
+		directly in the pipeline.
+		"""
+
+	classes: {
+		commonly_used: false
+		development:   "alpha"
+		egress_method: "stream"
+		stateful:      true
+	}
+
+	features: {
+		aggregate: {}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: [
+			"""
+				This component is in alpha, and can only be used with the `alpha_components` feature flag
+				enabled.
+				""",
+		]
+	}
+
+	configuration: {
+		key: {
+			description: """
+				A [template string](/docs/reference/configuration/template-syntax/) rendering the key that events
+				are counted by. Events for which the key can't be rendered are not counted.
+				"""
+			required: true
+			type: string: {
+				examples: ["{{ client_ip }}", "{{ tenant }}/{{ service }}"]
+				syntax: "template"
+			}
+		}
+		k: {
+			common:      true
+			description: "The number of keys with the highest counts to emit at the end of each window."
+			required:    false
+			type: uint: {
+				default: 10
+				unit:    null
+			}
+		}
+		sketch_size: {
+			common: false
+			description: """
+				The number of keys tracked at once. Defaults to ten times `k`. See
+				[Accuracy](#accuracy) for how this affects the emitted counts.
+				"""
+			required: false
+			type: uint: {
+				default: null
+				examples: [1000]
+				unit: null
+			}
+		}
+		window_secs: {
+			common:      true
+			description: "The length of the window over which keys are counted. Counts are reset after each window. Must be positive."
+			required:    false
+			type: float: {
+				default: 60.0
+				unit:    "seconds"
+			}
+		}
+	}
+
+	input: {
+		logs: true
+		metrics: {
+			counter:      true
+			distribution: true
+			gauge:        true
+			histogram:    true
+			set:          true
+			summary:      true
+		}
+		traces: false
+	}
+
+	output: logs: top_key: {
+		description: "One event per top key, emitted at the end of each window."
+		fields: {
+			key: {
+				description: "The rendered key."
+				required:    true
+				type: string: {
+					examples: ["10.0.0.12"]
+				}
+			}
+			count: {
+				description: "The estimated number of events with this key in the window."
+				required:    true
+				type: uint: {
+					examples: [1024]
+					unit: null
+				}
+			}
+			error: {
+				description: "The upper bound of the overestimation of `count`. The exact count is at least `count - error`."
+				required:    true
+				type: uint: {
+					examples: [0, 12]
+					unit: null
+				}
+			}
+			rank: {
+				description: "The position of the key in the window, starting at 1 for the key with the highest count."
+				required:    true
+				type: uint: {
+					examples: [1]
+					unit: null
+				}
+			}
+			timestamp: {
+				description: "The time the window ended."
+				required:    true
+				type: timestamp: {}
+			}
+		}
+	}
+
+	how_it_works: {
+		accuracy: {
+			title: "Accuracy"
+			body: """
+				Keys are counted with a [space-saving sketch](\(urls.space_saving)), which tracks at most
+				`sketch_size` keys at once. When a new key arrives while the sketch is full, it replaces the key
+				with the lowest count and takes over its count, which is reported as the `error` of the new key.
+
+				Counts are never underestimated, and are overestimated by at most the number of events in the
+				window divided by `sketch_size`. Keys that account for more than that share of the events are
+				guaranteed to be tracked.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_errors_total: components.sources.internal_metrics.output.metrics.component_errors_total
+	}
+}
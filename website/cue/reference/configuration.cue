@@ -90,6 +90,211 @@ configuration: {
 			}
 		}
 
+		protobuf_descriptor_set: {
+			common: false
+			description: """
+				The path to a compiled [protobuf](\(urls.protobuf)) descriptor set, as produced by
+				`protoc --include_imports --descriptor_set_out`. The message types it defines can be
+				used by the [`parse_proto`](\(urls.vrl_functions)/#parse_proto) and
+				[`encode_proto`](\(urls.vrl_functions)/#encode_proto) VRL functions, in remap
+				programs as well as in conditions and templates.
+				"""
+			required: false
+			type: string: {
+				default: null
+				examples: ["/etc/vector/events.desc"]
+			}
+		}
+
+		max_event_bytes: {
+			common: false
+			description: """
+				The maximum size of an event, estimated as the size of its JSON encoding. Sources handle
+				the events exceeding it as soon as they're decoded, as set by their `oversized_events`
+				option. Sources can override this limit with their own `max_event_bytes` option.
+				"""
+			required: false
+			type: uint: {
+				default: null
+				examples: [1048576]
+				unit: "bytes"
+			}
+		}
+
+		rate_limit_pools: {
+			common: false
+			description: """
+				Named rate limits shared by several sinks, such as the quota of an API key used by
+				both a logs and a metrics sink. Sinks join a pool with their `rate_limit_pool`
+				option, and collectively respect its limit on top of their own. Once the limit is
+				reached, the requests of the sinks are queued and sent in turns, so that every sink
+				gets a fair share of the limit. Only sinks with `request` options support pools, and
+				referencing a pool from other sinks is an error.
+				"""
+			required: false
+			type: object: {
+				examples: [{datadog: {rate_limit_num: 100, rate_limit_duration_secs: 1}}]
+				options: {
+					"*": {
+						description: "The rate limit of the pool."
+						required:    true
+						type: object: options: {
+							rate_limit_num: {
+								description: "The maximum number of requests the sinks of the pool are allowed to make within the `rate_limit_duration_secs` time window."
+								required:    true
+								type: uint: {
+									examples: [100]
+									unit: "requests"
+								}
+							}
+							rate_limit_duration_secs: {
+								common:      false
+								description: "The time window, in seconds, used for the `rate_limit_num` option."
+								required:    false
+								type: uint: {
+									default: 1
+									unit:    "seconds"
+								}
+							}
+						}
+					}
+				}
+			}
+		}
+
+		vrl_functions: {
+			common: false
+			description: """
+				Functions defined in VRL, which every `remap` transform and VRL condition can call
+				by their name, so that parsing logic shared by several components is written once.
+				The parameters of a function are variables of its source, and are required. Its
+				result is the result of its source, and it's fallible if its source is, such as
+				when it calls `parse_json!`. Functions can use the functions built into VRL, but
+				not other functions defined here, nor the event they're called on.
+				"""
+			required: false
+			type: object: {
+				examples: [{parse_app_log: {parameters: ["message"], source: "parse_key_value!(string!(parse_json!(message).payload))"}}]
+				options: {
+					"*": {
+						description: "The definition of the function."
+						required:    true
+						type: object: options: {
+							parameters: {
+								common:      true
+								description: "The names of the parameters of the function, which can be passed by position or by name."
+								required:    false
+								type: array: {
+									default: []
+									items: type: string: examples: ["message"]
+								}
+							}
+							source: {
+								common:      true
+								description: "The VRL source of the function. Either this or `file` must be set."
+								required:    false
+								type: string: {
+									default: null
+									examples: ["parse_key_value!(string!(parse_json!(message).payload))"]
+									syntax: "remap_program"
+								}
+							}
+							file: {
+								common:      false
+								description: "The path to a file containing the VRL source of the function. Either this or `source` must be set."
+								required:    false
+								type: string: {
+									default: null
+									examples: ["./parse_app_log.vrl"]
+								}
+							}
+						}
+					}
+				}
+			}
+		}
+
+		host_metadata: {
+			common: false
+			description: """
+				Fetches the metadata of the host Vector runs on from the metadata services of cloud
+				providers, once at startup and then periodically. The metadata is keyed with the
+				names of the OpenTelemetry resource conventions, such as `cloud.region` or
+				`host.id`. It can be used in templates as `{{ host_metadata.<key> }}`, with the
+				`get_host_metadata` VRL function, and added to the events of a source with its
+				`host_metadata_key` option. This supersedes the `aws_ec2_metadata` transform.
+				"""
+			required: false
+			type: object: options: {
+				providers: {
+					common: false
+					description: """
+						The cloud providers whose metadata services are queried. The metadata of
+						every provider answering is collected.
+						"""
+					required: false
+					type: array: {
+						default: ["ec2", "gce", "azure"]
+						items: type: string: {
+							enum: {
+								ec2:   "Amazon EC2, through the instance metadata service."
+								gce:   "Google Compute Engine, through the metadata server."
+								azure: "Azure virtual machines, through the instance metadata service."
+							}
+						}
+					}
+				}
+				tags: {
+					common: false
+					description: """
+						Whether to fetch the tags of the instance, as `tags.<name>` keys. On EC2,
+						access to tags in the instance metadata must be allowed on the instance,
+						otherwise only the other metadata is kept.
+						"""
+					required: false
+					type: bool: default: false
+				}
+				refresh_interval_secs: {
+					common:      false
+					description: "How often to refresh the metadata. Set to `0` to only fetch it at startup and when the configuration is reloaded."
+					required:    false
+					type: uint: {
+						default: 600
+						unit:    "seconds"
+					}
+				}
+				request_timeout_secs: {
+					common:      false
+					description: "The timeout of the requests to the metadata services."
+					required:    false
+					type: uint: {
+						default: 1
+						unit:    "seconds"
+					}
+				}
+			}
+		}
+
+		feature_flags: {
+			common: false
+			description: """
+				Feature flags enabling experimental behaviors of Vector. Vector refuses to start with
+				components whose status is `alpha`, unless the `alpha_components` flag is enabled,
+				and labels the internal metrics of these components with a `component_status` tag.
+				Flags can also be enabled with the `--feature-flags` command line option.
+				"""
+			required: false
+			type: array: {
+				default: []
+				items: type: string: {
+					enum: {
+						alpha_components:      "Allow the use of components whose status is `alpha`."
+						internal_events_audit: "Periodically check that each component emits the internal events required for the events flowing through it, logging violations and counting them with the `internal_events_audit_violations_total` metric. Meant for debugging the instrumentation of components."
+					}
+				}
+			}
+		}
+
 		enrichment_tables: {
 			common:      false
 			description: """
@@ -355,6 +560,110 @@ configuration: {
 			}
 		}
 
+		policy: {
+			common: false
+			description: """
+				Policies the configuration must comply with. Vector refuses to load a configuration with
+				components violating any of the rules, reporting the ID of the rules in the errors. The
+				rules of all the configuration files are checked, along with the rules of the file given
+				with the `--policy` flag.
+				"""
+			required: false
+			type: object: options: {
+				rules: {
+					common:      true
+					description: "The rules the components must comply with."
+					required:    false
+					type: array: {
+						default: []
+						items: type: object: options: {
+							id: {
+								description: "The ID of the rule, reported along with the components violating it."
+								required:    true
+								type: string: examples: ["sinks-require-tls"]
+							}
+							description: {
+								description: "A description of the policy enforced by the rule, reported along with its ID."
+								required:    false
+								type: string: {
+									default: null
+									examples: ["All sinks must use TLS."]
+								}
+							}
+							kind: {
+								description: "The kind of components the rule applies to. The rule applies to all kinds of components when unset."
+								required:    false
+								type: string: {
+									default: null
+									enum: {
+										source:    "Sources."
+										transform: "Transforms."
+										sink:      "Sinks."
+									}
+								}
+							}
+							types: {
+								description: "The types of components the rule applies to. The rule applies to all types of components when empty."
+								required:    false
+								type: array: {
+									default: []
+									items: type: string: examples: ["http", "socket"]
+								}
+							}
+							deny: {
+								description: "Whether the components the rule applies to are denied altogether."
+								required:    false
+								type: bool: default: false
+							}
+							require_options: {
+								description: """
+									The options the components must set. Nested options are separated by a dot. An option
+									is set when it's present and neither null nor `false`.
+									"""
+								required: false
+								type: array: {
+									default: []
+									items: type: string: examples: ["tls.enabled"]
+								}
+							}
+							forbid_options: {
+								description: """
+									The options the components must not set. An option is set as for `require_options`,
+									so this can't forbid options explicitly set to `false`. Use `require_values` to
+									require an option to have a given value instead.
+									"""
+								required: false
+								type: array: {
+									default: []
+									items: type: string: examples: ["tls.key_pass"]
+								}
+							}
+							require_values: {
+								description: """
+									The values the options of the components must have. Nested options are separated by
+									a dot. Options not set fail the rule, unless their value is required to be `false`,
+									which is what unset boolean options mean.
+									"""
+								required: false
+								type: object: {
+									examples: [{"tls.verify_certificate": true}]
+									options: {}
+								}
+							}
+							require_annotations: {
+								description: "The `annotations` the components must set."
+								required:    false
+								type: array: {
+									default: []
+									items: type: string: examples: ["tenant"]
+								}
+							}
+						}
+					}
+				}
+			}
+		}
+
 		secret: {
 			common: false
 			description: """
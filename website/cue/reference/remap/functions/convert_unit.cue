@@ -0,0 +1,74 @@
+package metadata
+
+remap: functions: convert_unit: {
+	category: "Number"
+	description: """
+		Converts the `value` from the duration or data size unit `from` to the unit `to`.
+
+		Duration units are the units supported by `parse_duration_any`, such as `ms`, `min` or
+		`hours`. Data size units are the units supported by `parse_bytes`, such as `kB`, `MiB` or
+		`Gbit`. `m` is a minute when converted to or from another duration unit, and a megabyte
+		otherwise.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The number to convert."
+			required:    true
+			type: ["integer", "float"]
+		},
+		{
+			name:        "from"
+			description: "The unit of `value`."
+			required:    true
+			type: ["string"]
+		},
+		{
+			name:        "to"
+			description: "The unit to convert `value` to."
+			required:    true
+			type: ["string"]
+		},
+		{
+			name: "base"
+			description: """
+				The base of the SI prefixes of data size units, either `10` so that `1kB` is 1000 bytes,
+				or `2` so that `1kB` is 1024 bytes, like `1KiB`.
+				"""
+			required: false
+			type: ["integer"]
+			default: 10
+		},
+	]
+	internal_failure_reasons: [
+		"`from` or `to` is an unknown unit",
+		"`from` and `to` aren't both duration units or both data size units",
+		"`base` isn't `2` or `10`",
+	]
+	return: types: ["float"]
+
+	examples: [
+		{
+			title: "Convert duration"
+			source: #"""
+				convert_unit!(1500, from: "ms", to: "s")
+				"""#
+			return: 1.5
+		},
+		{
+			title: "Convert data size"
+			source: #"""
+				convert_unit!(2.5, from: "GiB", to: "MiB")
+				"""#
+			return: 2560.0
+		},
+		{
+			title: "Convert data size (SI prefixes in base 2)"
+			source: #"""
+				convert_unit!(1, from: "MB", to: "kB", base: 2)
+				"""#
+			return: 1024.0
+		},
+	]
+}
@@ -0,0 +1,43 @@
+package metadata
+
+remap: functions: encode_proto: {
+	category:    "Codec"
+	description: """
+		Encodes the `value` as a [protobuf](\(urls.protobuf)) message of the given `message_type`.
+		The message type must be defined in the descriptor set loaded with the global
+		`protobuf_descriptor_set` option.
+
+		Enum fields accept either the name or the number of the value. Fields set to `null` are
+		left out of the message.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The object to encode, keyed by field name."
+			required:    true
+			type: ["object"]
+		},
+		{
+			name:        "message_type"
+			description: "The fully qualified name of the message type, such as `example.Event`."
+			required:    true
+			type: ["string"]
+		},
+	]
+	internal_failure_reasons: [
+		"`value` contains a field that is not part of `message_type`",
+		"`value` contains a field whose type does not match its definition in `message_type`",
+	]
+	return: types: ["string"]
+
+	examples: [
+		{
+			title: "Encode a protobuf message"
+			source: #"""
+				encode_base64(encode_proto!({"message": "hello"}, "example.Event"))
+				"""#
+			return: "CgVoZWxsbw=="
+		},
+	]
+}
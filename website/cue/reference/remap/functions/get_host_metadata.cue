@@ -0,0 +1,33 @@
+package metadata
+
+remap: functions: get_host_metadata: {
+	category: "System"
+	description: """
+		Returns the value of the given key of the host metadata, fetched with the global
+		`host_metadata` option, or `null` if the key is missing.
+		"""
+
+	arguments: [
+		{
+			name: "key"
+			description: """
+				The key of the host metadata, such as `cloud.region` or `tags.<name>`.
+				"""
+			required: true
+			type: ["string"]
+		},
+	]
+	internal_failure_reasons: [
+	]
+	return: types: ["string", "null"]
+
+	examples: [
+		{
+			title: "Get the region of the cloud instance."
+			source: #"""
+				get_host_metadata("cloud.region")
+				"""#
+			return: "eu-west-1"
+		},
+	]
+}
@@ -0,0 +1,31 @@
+package metadata
+
+remap: functions: get_state: {
+	category: "System"
+	description: """
+		Returns the value of the given key of the state of the `remap` transform, set by the
+		`set_state` function, or `null` if the key is missing or expired.
+		"""
+
+	arguments: [
+		{
+			name:        "key"
+			description: "The key of the state."
+			required:    true
+			type: ["string"]
+		},
+	]
+	internal_failure_reasons: [
+	]
+	return: types: ["any"]
+
+	examples: [
+		{
+			title: "Get a key of the state."
+			source: #"""
+				get_state("host-1")
+				"""#
+			return: "2021-03-04T05:06:07Z"
+		},
+	]
+}
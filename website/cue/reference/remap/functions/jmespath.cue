@@ -0,0 +1,55 @@
+package metadata
+
+remap: functions: jmespath: {
+	category: "Path"
+	description: """
+		Evaluates the [JMESPath](\(urls.jmespath)) `expression` against the `value`.
+
+		Unlike static paths such as `.foo.bar[1]`, the expression is evaluated at
+		runtime, so it can come from a field of the event or from enrichment data.
+		JMESPath also supports projections, filters, multi-selects, and functions that
+		static paths can't express.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The object or array to query."
+			required:    true
+			type: ["object", "array"]
+		},
+		{
+			name:        "expression"
+			description: "The JMESPath expression to evaluate."
+			required:    true
+			type: ["string"]
+		},
+	]
+	internal_failure_reasons: [
+		"`expression` is not a valid JMESPath expression.",
+		"`expression` fails to evaluate against `value`, such as when a function receives an argument of the wrong type.",
+	]
+	return: {
+		types: ["any"]
+		rules: [
+			"Returns `null` if `expression` doesn't match anything in `value`.",
+		]
+	}
+
+	examples: [
+		{
+			title: "Get a nested field"
+			source: #"""
+				jmespath!({"foo": {"bar": "baz"}}, "foo.bar")
+				"""#
+			return: "baz"
+		},
+		{
+			title: "Filter an array"
+			source: #"""
+				jmespath!({"users": [{"name": "a", "age": 20}, {"name": "b", "age": 40}]}, "users[?age > `30`].name")
+				"""#
+			return: ["b"]
+		},
+	]
+}
@@ -0,0 +1,55 @@
+package metadata
+
+remap: functions: json_path: {
+	category: "Path"
+	description: """
+		Evaluates the [JSONPath](\(urls.json_path)) `path` against the `value`, returning
+		all the values it matches.
+
+		Unlike static paths such as `.foo.bar[1]`, the path is evaluated at runtime, so
+		it can come from a field of the event or from enrichment data. JSONPath also
+		supports wildcards, recursive descent, and filters that static paths can't
+		express.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The object or array to query."
+			required:    true
+			type: ["object", "array"]
+		},
+		{
+			name:        "path"
+			description: "The JSONPath expression to evaluate, such as `$.foo[*].bar`."
+			required:    true
+			type: ["string"]
+		},
+	]
+	internal_failure_reasons: [
+		"`path` is not a valid JSONPath expression.",
+	]
+	return: {
+		types: ["array"]
+		rules: [
+			"Returns an empty array if `path` doesn't match anything in `value`.",
+		]
+	}
+
+	examples: [
+		{
+			title: "Get a nested field"
+			source: #"""
+				json_path!({"foo": {"bar": "baz"}}, "$.foo.bar")
+				"""#
+			return: ["baz"]
+		},
+		{
+			title: "Filter an array"
+			source: #"""
+				json_path!({"users": [{"name": "a", "age": 20}, {"name": "b", "age": 40}]}, "$.users[?(@.age > 30)].name")
+				"""#
+			return: ["b"]
+		},
+	]
+}
@@ -0,0 +1,69 @@
+package metadata
+
+remap: functions: parse_bytes: {
+	category: "Parse"
+	description: """
+		Parses the `value` into a number of bytes, or of the data size specified by `unit`.
+
+		Data sizes are made of a number, optionally followed by a unit. Units are made of an optional
+		SI prefix, such as `k` or `kilo`, or IEC prefix, such as `Ki` or `kibi`, and of a `B` or `byte`
+		suffix for bytes, or of a `b` or `bit` suffix for bits. Units with only a prefix, such as `M`,
+		are bytes, and data sizes without a unit are a number of bytes. Prefixes are case-insensitive.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The string of the data size."
+			required:    true
+			type: ["string"]
+		},
+		{
+			name:        "unit"
+			description: "The output unit for the data size, such as `MiB` or `kbit`."
+			required:    false
+			type: ["string"]
+			default: "B"
+		},
+		{
+			name: "base"
+			description: """
+				The base of SI prefixes, either `10` so that `1kB` is 1000 bytes, or `2` so that `1kB` is
+				1024 bytes, like `1KiB`. IEC prefixes are always in base 2.
+				"""
+			required: false
+			type: ["integer"]
+			default: 10
+		},
+	]
+	internal_failure_reasons: [
+		"`value` isn't a properly formatted data size",
+		"`value` or `unit` has an unknown unit",
+		"`base` isn't `2` or `10`",
+	]
+	return: types: ["float"]
+
+	examples: [
+		{
+			title: "Parse data size (IEC prefix)"
+			source: #"""
+				parse_bytes!("1.5GiB", unit: "MiB")
+				"""#
+			return: 1536.0
+		},
+		{
+			title: "Parse data size (SI prefix)"
+			source: #"""
+				parse_bytes!("2 kB")
+				"""#
+			return: 2000.0
+		},
+		{
+			title: "Parse data size (SI prefix in base 2)"
+			source: #"""
+				parse_bytes!("2 kB", base: 2)
+				"""#
+			return: 2048.0
+		},
+	]
+}
@@ -0,0 +1,82 @@
+package metadata
+
+remap: functions: parse_duration_any: {
+	category: "Parse"
+	description: """
+		Parses the `value` into a number of seconds, or of the duration unit specified by `unit`.
+
+		Unlike `parse_duration`, durations may be in any of these formats, optionally preceded by a `-`:
+
+		* Compound durations, such as `1h30m` or `2 hours, 30 minutes and 5s`, whose parts may only be
+		  separated by whitespace, commas, or `and`.
+		* ISO 8601 durations, such as `P1DT2H30M`, made of weeks, days, hours, minutes and seconds.
+		  Years and months aren't supported, as their length varies.
+		* Clock durations, such as `02:30` (minutes and seconds), `01:02:30` or `1-01:02:30`
+		  (days, hours, minutes and seconds).
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The string of the duration."
+			required:    true
+			type: ["string"]
+		},
+		{
+			name:        "unit"
+			description: "The output unit for the duration."
+			required:    false
+			type: ["string"]
+			default: "s"
+			enum: {
+				ns: "Nanoseconds (1 billion nanoseconds in a second)"
+				us: "Microseconds (1 million microseconds in a second)"
+				µs: "Microseconds (1 million microseconds in a second)"
+				ms: "Milliseconds (1 thousand milliseconds in a second)"
+				cs: "Centiseconds (100 centiseconds in a second)"
+				ds: "Deciseconds (10 deciseconds in a second)"
+				s:  "Seconds"
+				m:  "Minutes (60 seconds in a minute)"
+				h:  "Hours (60 minutes in an hour)"
+				d:  "Days (24 hours in a day)"
+				w:  "Weeks (7 days in a week)"
+			}
+		},
+	]
+	internal_failure_reasons: [
+		"`value` isn't a properly formatted duration",
+		"`value` or `unit` has an unknown unit",
+	]
+	return: types: ["float"]
+
+	examples: [
+		{
+			title: "Parse compound duration"
+			source: #"""
+				parse_duration_any!("1h30m", unit: "m")
+				"""#
+			return: 90.0
+		},
+		{
+			title: "Parse duration with long unit names"
+			source: #"""
+				parse_duration_any!("2 minutes and 30 seconds")
+				"""#
+			return: 150.0
+		},
+		{
+			title: "Parse ISO 8601 duration"
+			source: #"""
+				parse_duration_any!("PT1M30.5S", unit: "ms")
+				"""#
+			return: 90500.0
+		},
+		{
+			title: "Parse clock duration"
+			source: #"""
+				parse_duration_any!("01:02:03")
+				"""#
+			return: 3723.0
+		},
+	]
+}
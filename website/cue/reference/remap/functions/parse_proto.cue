@@ -0,0 +1,44 @@
+package metadata
+
+remap: functions: parse_proto: {
+	category:    "Parse"
+	description: """
+		Parses the `value` as a [protobuf](\(urls.protobuf)) message of the given `message_type`.
+		The message type must be defined in the descriptor set loaded with the global
+		`protobuf_descriptor_set` option.
+
+		Enum values are returned as their names, and fields that are not present in the message
+		are left out of the returned object.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The encoded protobuf message."
+			required:    true
+			type: ["string"]
+		},
+		{
+			name:        "message_type"
+			description: "The fully qualified name of the message type, such as `example.Event`."
+			required:    true
+			type: ["string"]
+		},
+	]
+	internal_failure_reasons: [
+		"`value` is not a valid encoding of `message_type`",
+	]
+	return: types: ["object"]
+
+	examples: [
+		{
+			title: "Parse a protobuf message"
+			source: #"""
+				parse_proto!(decode_base64!("CgVoZWxsbw=="), "example.Event")
+				"""#
+			return: {
+				message: "hello"
+			}
+		},
+	]
+}
@@ -0,0 +1,56 @@
+package metadata
+
+remap: functions: set_state: {
+	category: "System"
+	description: """
+		Sets the given key of the state of the `remap` transform, shared by all the events it
+		processes, and returns the previous value of the key, or `null` if it was missing or
+		expired. Setting a key to `null` removes it.
+
+		The state is only kept in memory, unless the `state` option of the transform is set to
+		checkpoint it.
+		"""
+
+	arguments: [
+		{
+			name:        "key"
+			description: "The key of the state."
+			required:    true
+			type: ["string"]
+		},
+		{
+			name:        "value"
+			description: "The value to set the key to."
+			required:    true
+			type: ["any"]
+		},
+		{
+			name:        "ttl_secs"
+			description: "The number of seconds after which the key expires. Defaults to the `state.ttl_secs` option of the transform."
+			required:    false
+			type: ["integer"]
+		},
+	]
+	internal_failure_reasons: [
+		"`ttl_secs` isn't a positive number.",
+	]
+	return: types: ["any"]
+
+	examples: [
+		{
+			title: "Detect the first event of each host."
+			source: #"""
+				set_state(string!(.host), true) == null
+				"""#
+			input: log: host: "host-1"
+			return: true
+		},
+		{
+			title: "Set a key expiring after an hour."
+			source: #"""
+				set_state!("host-1", true, ttl_secs: 3600)
+				"""#
+			return: null
+		},
+	]
+}
@@ -0,0 +1,47 @@
+package metadata
+
+services: cloudflare: {
+	name:     "Cloudflare"
+	thing:    "a \(name) zone or account"
+	url:      urls.cloudflare
+	versions: null
+
+	description: """
+		[Cloudflare](\(urls.cloudflare)) is a content delivery network and DDoS mitigation
+		service. Its [Logpush](\(urls.cloudflare_logpush)) service delivers the logs of a zone
+		or an account, such as HTTP requests or firewall events, to a destination in batches.
+		"""
+
+	connect_to: {
+		vector: logs: setup: [
+			{
+				title: "Configure Vector to accept Logpush deliveries"
+				vector: configure: sources: cloudflare_logpush: {
+					type:    "cloudflare_logpush"
+					address: "0.0.0.0:80"
+				}
+			},
+			{
+				title: "Configure TLS termination"
+				description: """
+					Logpush only delivers to HTTPS destinations. Either put a load balancer in front
+					of the Vector instance to handle TLS termination or configure the `tls` options
+					of the `cloudflare_logpush` source to serve a valid certificate.
+					"""
+				detour: url: urls.aws_elb_https
+			},
+			{
+				title: "Create a Logpush job"
+				description: """
+					Create a Logpush job with an [HTTP destination](\(urls.cloudflare_logpush_http))
+					pointing to your Vector instance's address. Headers set with `header_X-Logpush-*`
+					parameters are added to the events:
+
+					```text
+					https://<address>/?header_Authorization=Basic%20<credentials>&header_X-Logpush-Dataset=http_requests
+					```
+					"""
+			},
+		]
+	}
+}
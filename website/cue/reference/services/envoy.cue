@@ -0,0 +1,8 @@
+package metadata
+
+services: envoy: {
+	name:     "Envoy"
+	thing:    "an \(name) proxy"
+	url:      urls.envoy
+	versions: null
+}
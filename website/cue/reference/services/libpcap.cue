@@ -0,0 +1,10 @@
+package metadata
+
+services: libpcap: {
+	name:     "libpcap"
+	thing:    "a network interface, through \(name)"
+	url:      urls.libpcap
+	versions: null
+
+	description: "[libpcap](\(urls.libpcap)) is a portable library for capturing network traffic, used by tools such as `tcpdump`."
+}
@@ -0,0 +1,10 @@
+package metadata
+
+services: linux_audit: {
+	name:     "Linux audit subsystem"
+	thing:    "the \(name)"
+	url:      urls.auditd
+	versions: null
+
+	description: "The [Linux audit subsystem](\(urls.auditd)) records security relevant events, such as system calls matching audit rules, logins, and changes of its own configuration."
+}
@@ -0,0 +1,8 @@
+package metadata
+
+services: netflow: {
+	name:     "NetFlow"
+	thing:    "a \(name) or IPFIX exporter"
+	url:      urls.netflow
+	versions: null
+}
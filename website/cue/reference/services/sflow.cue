@@ -0,0 +1,8 @@
+package metadata
+
+services: sflow: {
+	name:     "sFlow"
+	thing:    "an \(name) agent"
+	url:      urls.sflow
+	versions: ">= 5"
+}
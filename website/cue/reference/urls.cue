@@ -5,6 +5,7 @@ urls: {
 	azure_blob_storage:                         "https://azure.microsoft.com/en-us/services/storage/blobs/"
 	affine_type_system:                         "\(wikipedia)/wiki/Substructural_type_system#Affine_type_systems"
 	adaptive_request_concurrency_post:          "/blog/adaptive-request-concurrency/"
+	adjtimex:                                   "https://man7.org/linux/man-pages/man2/adjtimex.2.html"
 	amazon_linux:                               "https://aws.amazon.com/amazon-linux-ami/"
 	amqp_protocol:                              "https://www.amqp.org/about/what"
 	ansi_escape_codes:                          "\(wikipedia)/wiki/ANSI_escape_code"
@@ -17,6 +18,8 @@ urls: {
 	apache_mod_status:                          "http://httpd.apache.org/docs/current/mod/mod_status.html"
 	apt:                                        "\(wikipedia)/wiki/APT_(software)"
 	arm:                                        "\(wikipedia)/wiki/ARM_architecture"
+	audisp_af_unix:                             "https://man7.org/linux/man-pages/man8/audispd-af_unix.8.html"
+	auditd:                                     "https://man7.org/linux/man-pages/man8/auditd.8.html"
 	aws_access_keys:                            "\(aws_docs)/IAM/latest/UserGuide/id_credentials_access-keys.html"
 	aws_arm_g2_announcement:                    "https://aws.amazon.com/about-aws/whats-new/2019/12/announcing-new-amazon-ec2-m6g-c6g-and-r6g-instances-powered-by-next-generation-arm-based-aws-graviton2-processors/"
 	aws_athena:                                 "https://aws.amazon.com/athena/"
@@ -41,6 +44,7 @@ urls: {
 	aws_credential_process:                     "\(aws_docs)/cli/latest/userguide/cli-configure-sourcing-external.html"
 	aws_credentials_file:                       "\(aws_docs)/cli/latest/userguide/cli-configure-files.html"
 	aws_docs:                                   "https://docs.aws.amazon.com"
+	aws_dualstack:                              "\(aws_docs)/general/latest/gr/rande.html#dual-stack-endpoints"
 	aws_elasticsearch:                          "https://aws.amazon.com/elasticsearch-service/"
 	aws_elasticsearch_regions:                  "\(aws_docs)/general/latest/gr/rande.html#elasticsearch-service-regions"
 	aws_ec2_instance_metadata:                  "\(aws_docs)/AWSEC2/latest/UserGuide/ec2-instance-metadata.html"
@@ -49,6 +53,7 @@ urls: {
 	aws_elb:                                    "https://aws.amazon.com/elasticloadbalancing/"
 	aws_elb_access_format:                      "\(aws_docs)/elasticloadbalancing/latest/application/load-balancer-access-logs.html#access-log-entry-examples"
 	aws_elb_https:                              "\(aws_docs)/elasticloadbalancing/latest/classic/elb-create-https-ssl-load-balancer.html"
+	aws_fips:                                   "https://aws.amazon.com/compliance/fips/"
 	aws_iam:                                    "\(aws_docs)/IAM/latest/UserGuide/introduction.html"
 	aws_iam_role:                               "\(aws_docs)/IAM/latest/UserGuide/id_roles.html"
 	aws_imds_v1_security_problems:              "https://aws.amazon.com/blogs/security/defense-in-depth-open-firewalls-reverse-proxies-ssrf-vulnerabilities-ec2-instance-metadata-service/"
@@ -79,6 +84,8 @@ urls: {
 	aws_s3_sse:                                 "\(aws_docs)/AmazonS3/latest/dev/UsingServerSideEncryption.html"
 	aws_s3_storage_classes:                     "https://aws.amazon.com/s3/storage-classes/"
 	aws_s3_tags:                                "\(aws_docs)/AmazonS3/latest/user-guide/add-object-tags.html"
+	aws_s3_transfer_acceleration:               "\(aws_docs)/AmazonS3/latest/userguide/transfer-acceleration.html"
+	aws_session_tags:                           "\(aws_docs)/IAM/latest/UserGuide/id_session-tags.html"
 	aws_sqs:                                    "https://aws.amazon.com/sqs/"
 	aws_sqs_api:                                "\(aws_docs)/AWSSimpleQueueService/latest/APIReference/Welcome.html"
 	aws_sqs_create:                             "\(aws_docs)/AWSSimpleQueueService/latest/SQSDeveloperGuide/sqs-configure-create-queue.html"
@@ -86,6 +93,7 @@ urls: {
 	aws_vpc_flow_logs:                          "\(aws_docs)/vpc/latest/userguide/flow-logs.html"
 	axiom:                                      "https://axiom.co"
 	axiom_cloud:                                "https://cloud.axiom.co"
+	axiom_ingest_api:                           "https://axiom.co/docs/restapi/ingest"
 	azure_blob:                                 "https://azure.microsoft.com/en-us/services/storage/blobs/"
 	azure_blob_endpoints:                       "https://docs.microsoft.com/en-us/rest/api/storageservices/blob-service-rest-api"
 	azure_monitor:                              "https://azure.microsoft.com/en-us/services/monitor/"
@@ -98,12 +106,18 @@ urls: {
 	big_query_streaming:                        "https://cloud.google.com/bigquery/streaming-data-into-bigquery"
 	bind_dnstap:                                "https://kb.isc.org/docs/aa-01342"
 	b_tree_map:                                 "https://doc.rust-lang.org/std/collections/struct.BTreeMap.html"
+	bpf_filter:                                 "https://www.tcpdump.org/manpages/pcap-filter.7.html"
 	cargo_audit:                                "\(github)/RustSec/cargo-audit"
 	centos:                                     "https://www.centos.org/"
+	chrony:                                     "https://chrony.tuxfamily.org/"
 	chrono_time_formats:                        "https://docs.rs/chrono/latest/chrono/format/strftime/index.html#specifiers"
 	cgroups_limit_resources:                    "https://the.binbashtheory.com/control-resources-cgroups/"
 	clickhouse:                                 "https://clickhouse.yandex/"
+	clickhouse_async_inserts:                   "https://clickhouse.com/docs/en/optimize/asynchronous-inserts"
 	clickhouse_http:                            "https://clickhouse.yandex/docs/en/interfaces/http/"
+	cloudflare:                                 "https://www.cloudflare.com"
+	cloudflare_logpush:                         "https://developers.cloudflare.com/logs/about/"
+	cloudflare_logpush_http:                    "https://developers.cloudflare.com/logs/get-started/enable-destinations/http/"
 	cloudsmith:                                 "https://cloudsmith.io/~timber/repos/vector/packages/"
 	cloudsmith_apt:                             "https://cloudsmith.io/~timber/repos/vector/setup/#formats-deb"
 	cloudsmith_yum:                             "https://cloudsmith.io/~timber/repos/vector/setup/#formats-rpm"
@@ -167,6 +181,7 @@ urls: {
 	dry_code:                                   "\(wikipedia)/wiki/Don%27t_repeat_yourself"
 	cidr:                                       "\(wikipedia)/wiki/Classless_Inter-Domain_Routing"
 	elastic_beats:                              "https://www.elastic.co/beats/"
+	elastic_common_schema:                      "https://www.elastic.co/guide/en/ecs/current/index.html"
 	elasticsearch:                              "https://www.elastic.co/products/elasticsearch"
 	elasticsearch_bulk:                         "https://www.elastic.co/guide/en/elasticsearch/reference/current/docs-bulk.html"
 	elasticsearch_data_streams:                 "https://www.elastic.co/guide/en/elasticsearch/reference/current/data-streams.html"
@@ -177,6 +192,8 @@ urls: {
 	encoding_standard:                          "https://encoding.spec.whatwg.org/"
 	endler_dev:                                 "https://endler.dev/"
 	enrichment_tables_concept:                  "/docs/reference/glossary/#enrichment-tables"
+	envoy:                                      "https://www.envoyproxy.io"
+	envoy_als:                                  "\(envoy)/docs/envoy/latest/api-v3/service/accesslog/v3/als.proto"
 	eof:                                        "\(wikipedia)/wiki/End-of-file#EOF_character"
 	etsy:                                       "https://www.etsy.com"
 	event_proto:                                "\(vector_repo)/blob/master/lib/vector-core/proto/event.proto"
@@ -200,7 +217,11 @@ urls: {
 	gcp_authentication_service_account:         "\(gcp)/docs/authentication/production#obtaining_and_providing_service_account_credentials_manually"
 	gcp_cloud_storage:                          "\(gcp)/storage"
 	gcp_chronicle:                              "https://chronicle.security"
+	gcp_chronicle_ingestion_api:                "\(gcp)/chronicle/docs/reference/ingestion-api"
+	gcp_chronicle_udm:                          "\(gcp)/chronicle/docs/reference/udm-field-list"
 	gcp_folders:                                "\(gcp)/resource-manager/docs/creating-managing-folders"
+	gcp_logging_log_entry:                      "\(gcp)/logging/docs/reference/v2/rest/v2/LogEntry"
+	gcp_logging_routing:                        "\(gcp)/logging/docs/export/configure_export_v2"
 	gcp_pubsub:                                 "\(gcp)/pubsub/"
 	gcp_pubsub_rest:                            "\(gcp)/pubsub/docs/reference/rest/"
 	gcp_projects:                               "\(gcp)/resource-manager/docs/creating-managing-projects"
@@ -223,6 +244,7 @@ urls: {
 	github_sign_commits:                        "https://help.github.com/en/github/authenticating-to-github/signing-commits"
 	globbing:                                   "\(wikipedia)/wiki/Glob_(programming)"
 	glog:                                       "\(github)/google/glog"
+	grpc:                                       "https://grpc.io/"
 	graphql:                                    "https://graphql.org"
 	graphql_playground:                         "\(github)/graphql/graphql-playground"
 	graphviz:                                   "https://graphviz.org/"
@@ -239,6 +261,7 @@ urls: {
 	homebrew_services:                          "\(github)/Homebrew/homebrew-services"
 	honeycomb:                                  "https://honeycomb.io"
 	honeycomb_batch:                            "https://docs.honeycomb.io/api/events/#batched-events"
+	honeycomb_markers:                          "https://docs.honeycomb.io/api/markers/"
 	honeycomb_signup:                           "https://ui.honeycomb.io/signup"
 	host:                                       "\(wikipedia)/wiki/Host_(network)"
 	http:                                       "https://www.w3.org/Protocols/"
@@ -248,6 +271,7 @@ urls: {
 	humio:                                      "https://humio.com"
 	humio_hec:                                  "https://docs.humio.com/integrations/data-shippers/hec/"
 	humio_hec_format_of_data:                   "https://docs.humio.com/integrations/data-shippers/hec/#format-of-data"
+	hyperloglog:                                "\(wikipedia)/wiki/HyperLogLog"
 	iam_instance_profile:                       "\(aws_docs)/IAM/latest/UserGuide/id_roles_use_switch-role-ec2_instance-profiles.html"
 	iana_time_zone_format:                      "\(wikipedia)/wiki/Tz_database#Names_of_time_zones"
 	iana_time_zones:                            "\(wikipedia)/wiki/List_of_tz_database_time_zones"
@@ -258,18 +282,24 @@ urls: {
 	influxdb_http_api_v1:                       "https://docs.influxdata.com/influxdb/latest/tools/api/#write-http-endpoint"
 	influxdb_http_api_v2:                       "https://v2.docs.influxdata.com/v2.0/api/#tag/Write"
 	influxdb_authentication_token:              "https://v2.docs.influxdata.com/v2.0/security/tokens/"
+	influxdb_http_api_v3:                       "https://docs.influxdata.com/influxdb3/core/write-data/http-api/v3-write-lp/"
+	influxdb3_authentication_token:             "https://docs.influxdata.com/influxdb3/core/admin/tokens/"
 	influxdb_line_protocol:                     "https://v2.docs.influxdata.com/v2.0/reference/syntax/line-protocol/"
 	inode:                                      "\(wikipedia)/wiki/Inode"
 	ip_aton:                                    "https://linux.die.net/man/3/inet_aton"
 	ip_ntoa:                                    "https://linux.die.net/man/3/inet_ntoa"
 	ip_ntop:                                    "https://linux.die.net/man/3/inet_ntop"
 	ip_pton:                                    "https://linux.die.net/man/3/inet_pton"
+	ipfix:                                      "https://www.rfc-editor.org/rfc/rfc7011"
 	iso_8601:                                   "\(wikipedia)/wiki/ISO_8601"
 	iso3166_2:                                  "\(wikipedia)/wiki/ISO_3166-2"
 	issue_1694:                                 "\(vector_repo)/issues/1694"
+	jmespath:                                   "https://jmespath.org/"
 	journalctl:                                 "https://www.freedesktop.org/software/systemd/man/journalctl.html"
 	journald:                                   "https://www.freedesktop.org/software/systemd/man/systemd-journald.service.html"
+	journald_native_protocol:                   "https://systemd.io/JOURNAL_NATIVE_PROTOCOL/"
 	json:                                       "\(wikipedia)/wiki/JSON"
+	json_path:                                  "https://goessner.net/articles/JsonPath/"
 	json_types:                                 "\(wikipedia)/wiki/JSON#Data_types_and_syntax"
 	jsonnet:                                    "https://jsonnet.org/"
 	kafka:                                      "https://kafka.apache.org/"
@@ -282,6 +312,7 @@ urls: {
 	kubernetes_accessing_api_from_pod:          "\(kubernetes)/docs/tasks/access-application-cluster/access-cluster/#accessing-the-api-from-a-pod"
 	kubernetes_api:                             "\(kubernetes)/docs/concepts/overview/kubernetes-api/"
 	kubernetes_api_server:                      "\(kubernetes)/docs/reference/command-line-tools-reference/kube-apiserver/"
+	kubernetes_audit_webhook:                   "\(kubernetes)/docs/tasks/debug/debug-cluster/audit/#webhook-backend"
 	kubernetes_authorization:                   "\(kubernetes)/docs/reference/access-authn-authz/authorization/"
 	kubernetes_daemonset:                       "\(kubernetes)/docs/concepts/workloads/controllers/daemonset/"
 	kubernetes_example_daemonset:               "\(vector_repo)/blob/master/config/kubernetes/vector-daemonset.yaml"
@@ -294,6 +325,7 @@ urls: {
 	leveldb:                                    "\(github)/google/leveldb"
 	leveldb_sys_2:                              "https://crates.io/crates/leveldb-sys"
 	leveldb_sys_3:                              "\(github)/vectordotdev/leveldb-sys/tree/v3.0.0"
+	libpcap:                                    "https://www.tcpdump.org/"
 	librdkafka:                                 "\(github)/edenhill/librdkafka"
 	librdkafka_config:                          "\(github)/edenhill/librdkafka/blob/master/CONFIGURATION.md"
 	linux_capability:                           "https://man7.org/linux/man-pages/man7/capabilities.7.html"
@@ -301,6 +333,7 @@ urls: {
 	logfmt:                                     "https://brandur.org/logfmt"
 	logfmt_specs:                               "https://pkg.go.dev/github.com/kr/logfmt#section-documentation"
 	logstash:                                   "https://www.elastic.co/logstash"
+	logstash_beats_protocol:                    "\(github)/logstash-plugins/logstash-input-beats/blob/main/PROTOCOL.md"
 	logstash_protocol:                          "https://github.com/elastic/logstash-forwarder/blob/master/PROTOCOL.md"
 	loki:                                       "https://grafana.com/oss/loki/"
 	loki_multi_tenancy:                         "\(github)/grafana/loki/blob/master/docs/operations/multi-tenancy.md"
@@ -351,6 +384,7 @@ urls: {
 	nats_rs:                                    "\(github)/nats-io/nats.rs"
 	new_bug_report:                             "\(vector_repo)/issues/new?labels=type%3A+bug"
 	new_feature_request:                        "\(vector_repo)/issues/new?labels=type%3A+new+feature"
+	netflow:                                    "https://www.cisco.com/c/en/us/td/docs/ios/solutions_docs/netflow/nfwhite.html"
 	new_relic:                                  "https://newrelic.com/"
 	new_relic_log_api:                          "https://docs.newrelic.com/docs/logs/new-relic-logs/log-api/introduction-log-api"
 	new_relic_apis:                             "https://docs.newrelic.com/docs/apis/intro-apis/introduction-new-relic-apis/"
@@ -366,12 +400,15 @@ urls: {
 	nix:                                        "https://nixos.org/nix/"
 	nixos:                                      "https://nixos.org/"
 	nixpkgs_9682:                               "\(github)/NixOS/nixpkgs/issues/9682"
+	openmetrics:                                "https://openmetrics.io/"
 	openssl:                                    "https://www.openssl.org/"
 	opentelemetry:                              "https://opentelemetry.io"
+	opentelemetry_log_data_model:               "\(opentelemetry)/docs/reference/specification/logs/data-model/"
 	opentelemetry_protocol:                     "\(opentelemetry)/docs/reference/specification/protocol/otlp/"
 	order_of_ops:                               "\(wikipedia)/wiki/Order_of_operations"
 	papertrail:                                 "https://www.papertrail.com/"
 	papertrail_syslog:                          "https://help.papertrailapp.com/kb/how-it-works/http-api/#submitting-log-messages"
+	pdh:                                        "https://learn.microsoft.com/en-us/windows/win32/perfctrs/using-the-pdh-functions-to-consume-counter-data"
 	perl_windows:                               "https://www.perl.org/get.html#win32"
 	percent_encoded_bytes:                      "https://url.spec.whatwg.org/#percent-encoded-bytes"
 	percent_encoding_controls:                  "https://infra.spec.whatwg.org/#c0-control"
@@ -402,6 +439,7 @@ urls: {
 	prometheus_remote_write:                    "https://prometheus.io/docs/prometheus/latest/configuration/configuration/#remote_write"
 	prometheus_remote_write_protocol:           "https://docs.google.com/document/d/1LPhVRSFkGNSuU1fBd81ulhsCPR4hkSZyyBj1SZ8fWOM/edit#heading=h.n0d0vphea3fe"
 	protobuf:                                   "https://developers.google.com/protocol-buffers"
+	protobuf_descriptor_set:                    "https://developers.google.com/protocol-buffers/docs/techniques#self-description"
 	pulsar:                                     "https://pulsar.apache.org/"
 	pulsar_protocol:                            "https://pulsar.apache.org/docs/en/develop-binary-protocol/"
 	raspbian:                                   "https://www.raspbian.org/"
@@ -445,13 +483,18 @@ urls: {
 	sematext_monitoring:                        "https://sematext.com/docs/monitoring/"
 	sematext_registration:                      "https://apps.sematext.com/ui/registration"
 	semver:                                     "https://semver.org/"
+	sflow:                                      "https://sflow.org/sflow_version_5.txt"
 	sha1:                                       "\(wikipedia)/wiki/SHA-1"
 	sha2:                                       "\(wikipedia)/wiki/SHA-2"
 	sha3:                                       "\(wikipedia)/wiki/SHA-3"
+	sigma:                                      "https://github.com/SigmaHQ/sigma-specification"
 	signal:                                     "\(wikipedia)/wiki/Signal_(IPC)"
 	snake_case:                                 "\(wikipedia)/wiki/Snake_case"
+	snmp:                                       "https://datatracker.ietf.org/doc/html/rfc3416"
+	snmp_usm:                                   "https://datatracker.ietf.org/doc/html/rfc3414"
 	snappy:                                     "https://google.github.io/snappy/"
 	socket:                                     "\(wikipedia)/wiki/Network_socket"
+	space_saving:                               "https://www.cs.ucsb.edu/sites/default/files/documents/2005-23.pdf"
 	splunk:                                     "https://www.splunk.com"
 	splunk_hec:                                 "https://dev.splunk.com/enterprise/docs/dataapps/httpeventcollector/"
 	splunk_hec_channel_header:                  "https://docs.splunk.com/Documentation/Splunk/8.2.4/Data/FormateventsforHTTPEventCollector#Channel_identifier_header"
@@ -627,6 +670,7 @@ urls: {
 	vrl_safety:                                 "\(vrl_reference)#safety"
 	vrl_type_safety:                            "\(vrl_reference)#type-safety"
 	vote_feature:                               "\(vector_repo)/issues?q=is%3Aissue+is%3Aopen+sort%3Areactions-%2B1-desc+label%3A%22Type%3A+New+Feature%22"
+	w3c_extended_log_format:                    "https://www.w3.org/TR/WD-logfile.html"
 	wasm:                                       "https://webassembly.org/"
 	wasm_languages:                             "\(github)/appcypher/awesome-wasm-langs"
 	websocket:                                  "\(wikipedia)/wiki/WebSocket"